@@ -36,7 +36,16 @@ const EXCLUDED_IDS = new Set([
   "cleanup-images",
   "export-html",
   "export-pdf",
+  "export-odt",
+  "export-rst",
+  "export-latex",
+  "export-mediawiki",
+  "import-file",
   "copy-html",
+  "copy-rich-text",
+  "live-preview",
+  "live-preview-lan",
+  "stop-live-preview",
   // Find operations (handled by find bar)
   "find-replace",
   "find-next",
@@ -55,6 +64,12 @@ const EXCLUDED_IDS = new Set([
   "sidebar",
   "outline",
   "terminal",
+  // Window pinning (handled in Rust)
+  "keep-on-top",
+  "opacity-100",
+  "opacity-80",
+  "opacity-60",
+  "opacity-40",
 ]);
 
 function extractMenuIds(): string[] {
@@ -0,0 +1,387 @@
+//! Notes Context for AI Prompts
+//!
+//! `build_context` picks the note excerpts most relevant to a question and
+//! assembles them into one block, numbered and attributed to their source
+//! note, that a genie can paste into its prompt to answer from the user's
+//! notes.
+//!
+//! Excerpts are the same heading/paragraph chunks the embedding index uses.
+//! They're ranked by the semantic index when the embedding model is
+//! reachable, and by keyword overlap otherwise, then added best first until
+//! the token budget is spent.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::ai_usage::estimate_tokens;
+use crate::embeddings::{self, Passage};
+use crate::link_index::note_title;
+use crate::workspace_stats::is_cjk;
+use crate::file_finder::{collect_notes, relative_path};
+
+const DEFAULT_BUDGET_TOKENS: usize = 2000;
+const MIN_BUDGET_TOKENS: usize = 100;
+const MAX_BUDGET_TOKENS: usize = 32_000;
+
+/// Passages considered before the budget cuts the list short
+const CANDIDATES: usize = 50;
+
+/// Characters per token when truncating an excerpt to fit
+const CHARS_PER_TOKEN: usize = 4;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "do", "does", "for", "from", "how", "i", "in",
+    "is", "it", "me", "my", "of", "on", "or", "that", "the", "this", "to", "was", "what", "when",
+    "where", "which", "who", "why", "with",
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextSource {
+    /// Number of the excerpt in the context block (`[1]`, `[2]`, ...)
+    pub index: usize,
+    pub path: String,
+    pub title: String,
+    pub heading: Option<String>,
+    pub line: usize,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextBlock {
+    /// Excerpts ready to paste into a prompt; empty when nothing matched
+    pub context: String,
+    pub sources: Vec<ContextSource>,
+    /// Estimated size of `context`
+    pub tokens: usize,
+    /// "semantic" or "keyword"
+    pub method: String,
+}
+
+fn clamp_budget(budget: Option<usize>) -> usize {
+    budget
+        .unwrap_or(DEFAULT_BUDGET_TOKENS)
+        .clamp(MIN_BUDGET_TOKENS, MAX_BUDGET_TOKENS)
+}
+
+/// Lowercased words of `text`. CJK text has no spaces, so each CJK
+/// character counts as a term of its own.
+fn terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let mut current = String::new();
+        for c in word.chars() {
+            if is_cjk(c) {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+                terms.push(c.to_string());
+            } else {
+                current.extend(c.to_lowercase());
+            }
+        }
+        if !current.is_empty() {
+            terms.push(current);
+        }
+    }
+    terms
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    terms(query)
+        .into_iter()
+        .filter(|t| !STOPWORDS.contains(&t.as_str()))
+        .filter(|t| seen.insert(t.clone()))
+        .collect()
+}
+
+/// Rank `passages` (with scores unset) by tf-idf overlap with `query`,
+/// dropping those that share no terms with it.
+fn keyword_rank(query: &str, passages: Vec<Passage>, limit: usize) -> Vec<Passage> {
+    let query = query_terms(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let counts: Vec<HashMap<String, usize>> = passages
+        .iter()
+        .map(|p| {
+            let mut counts = HashMap::new();
+            for term in terms(&format!(
+                "{} {}",
+                p.heading.as_deref().unwrap_or(""),
+                p.text
+            )) {
+                *counts.entry(term).or_insert(0) += 1;
+            }
+            counts
+        })
+        .collect();
+    let total = passages.len() as f32;
+    let idf: Vec<f32> = query
+        .iter()
+        .map(|term| {
+            let df = counts.iter().filter(|c| c.contains_key(term)).count() as f32;
+            (1.0 + total / (1.0 + df)).ln()
+        })
+        .collect();
+
+    let mut ranked: Vec<Passage> = passages
+        .into_iter()
+        .zip(&counts)
+        .filter_map(|(mut passage, counts)| {
+            let score: f32 = query
+                .iter()
+                .zip(&idf)
+                .filter_map(|(term, idf)| {
+                    counts.get(term).map(|&tf| (1.0 + (tf as f32).ln()) * idf)
+                })
+                .sum();
+            (score > 0.0).then(|| {
+                passage.score = score;
+                passage
+            })
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Every chunk of every note in the workspace, unscored.
+fn workspace_passages(root: &Path) -> Vec<Passage> {
+    collect_notes(root, &crate::workspace::workspace_excludes(root))
+        .into_iter()
+        .flat_map(|path| {
+            let rel = relative_path(root, &path);
+            let content = embeddings::read_note_text(&path);
+            let title = note_title(&rel, &content);
+            embeddings::chunk_note(&content)
+                .into_iter()
+                .map(move |chunk| Passage {
+                    path: rel.clone(),
+                    title: title.clone(),
+                    heading: chunk.heading,
+                    line: chunk.line,
+                    text: chunk.text,
+                    score: 0.0,
+                })
+        })
+        .collect()
+}
+
+fn excerpt_header(index: usize, passage: &Passage) -> String {
+    let heading = passage
+        .heading
+        .as_deref()
+        .filter(|h| *h != passage.title)
+        .map(|h| format!(" — {}", h))
+        .unwrap_or_default();
+    format!(
+        "[{}] {}{} ({}:{})",
+        index, passage.title, heading, passage.path, passage.line
+    )
+}
+
+/// Number and attribute `passages` (best first) until `budget` tokens are
+/// used. Passages that don't fit are skipped in favour of shorter ones
+/// further down; if not even the best one fits, it's cut short.
+fn assemble(passages: &[Passage], budget: usize, method: &str) -> ContextBlock {
+    let mut seen = HashSet::new();
+    let mut context = String::new();
+    let mut sources = Vec::new();
+    let mut used = 0;
+    for passage in passages {
+        if !seen.insert((passage.path.as_str(), passage.line)) {
+            continue;
+        }
+        let index = sources.len() + 1;
+        let header = excerpt_header(index, passage);
+        let mut excerpt = format!("{}\n{}", header, passage.text.trim());
+        // The blank line between excerpts counts as a token
+        let separator = usize::from(!sources.is_empty());
+        let mut tokens = estimate_tokens(&excerpt) as usize + separator;
+        if used + tokens > budget {
+            if !sources.is_empty() {
+                continue;
+            }
+            let room = budget.saturating_sub(estimate_tokens(&header) as usize + 1);
+            let cut: String = passage
+                .text
+                .trim()
+                .chars()
+                .take(room * CHARS_PER_TOKEN)
+                .collect();
+            excerpt = format!("{}\n{}…", header, cut.trim_end());
+            tokens = estimate_tokens(&excerpt) as usize;
+        }
+        if separator > 0 {
+            context.push_str("\n\n");
+        }
+        context.push_str(&excerpt);
+        used += tokens;
+        sources.push(ContextSource {
+            index,
+            path: passage.path.clone(),
+            title: passage.title.clone(),
+            heading: passage.heading.clone(),
+            line: passage.line,
+            score: passage.score,
+        });
+    }
+    ContextBlock {
+        tokens: estimate_tokens(&context) as usize,
+        context,
+        sources,
+        method: method.to_string(),
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Excerpts from the workspace's notes relevant to `query`, within
+/// `budget_tokens` (2000 by default). `model` selects the embedding model
+/// as for `semantic_search`.
+#[tauri::command]
+pub async fn build_context(
+    query: String,
+    workspace_root: String,
+    budget_tokens: Option<usize>,
+    model: Option<String>,
+) -> Result<ContextBlock, String> {
+    let budget = clamp_budget(budget_tokens);
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(assemble(&[], budget, "keyword"));
+    }
+    if !Path::new(&workspace_root).is_dir() {
+        return Err(format!("Not a folder: {}", workspace_root));
+    }
+
+    match embeddings::search_passages(&workspace_root, &query, CANDIDATES, model).await {
+        Ok(passages) if !passages.is_empty() => return Ok(assemble(&passages, budget, "semantic")),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("[AI] Semantic context unavailable, using keywords: {}", e),
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let passages = workspace_passages(Path::new(&workspace_root));
+        assemble(
+            &keyword_rank(&query, passages, CANDIDATES),
+            budget,
+            "keyword",
+        )
+    })
+    .await
+    .map_err(|e| format!("Context task failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passage(path: &str, heading: Option<&str>, line: usize, text: &str) -> Passage {
+        Passage {
+            path: path.to_string(),
+            title: path.trim_end_matches(".md").to_string(),
+            heading: heading.map(str::to_string),
+            line,
+            text: text.to_string(),
+            score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_terms_split_words_and_cjk() {
+        assert_eq!(
+            terms("Rust's borrow-checker"),
+            vec!["rust", "s", "borrow", "checker"]
+        );
+        assert_eq!(terms("学习Rust笔记"), vec!["学", "习", "rust", "笔", "记"]);
+        assert_eq!(
+            query_terms("What is the borrow checker? Borrow!"),
+            vec!["borrow", "checker"]
+        );
+    }
+
+    #[test]
+    fn test_keyword_rank_prefers_rare_terms() {
+        let passages = vec![
+            passage("a.md", None, 1, "The garden needs water every day."),
+            passage(
+                "b.md",
+                Some("Tomatoes"),
+                1,
+                "Tomato plants need water and sun.",
+            ),
+            passage("c.md", None, 1, "Meeting notes about the budget."),
+        ];
+        let ranked = keyword_rank("how much water do tomatoes need", passages, 10);
+        let paths: Vec<&str> = ranked.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, vec!["b.md", "a.md"]);
+        assert!(keyword_rank("the of", ranked, 10).is_empty());
+    }
+
+    #[test]
+    fn test_assemble_numbers_and_attributes() {
+        let passages = vec![
+            passage("garden.md", Some("Watering"), 4, "Water in the morning."),
+            passage("garden.md", Some("Watering"), 4, "Water in the morning."),
+            passage("notes/soil.md", None, 1, "Loamy soil drains well."),
+        ];
+        let block = assemble(&passages, 500, "keyword");
+        assert_eq!(
+            block.context,
+            "[1] garden — Watering (garden.md:4)\nWater in the morning.\n\n[2] notes/soil (notes/soil.md:1)\nLoamy soil drains well."
+        );
+        assert_eq!(block.sources.len(), 2);
+        assert_eq!(block.sources[1].index, 2);
+        assert_eq!(block.method, "keyword");
+        assert_eq!(block.tokens, estimate_tokens(&block.context) as usize);
+    }
+
+    #[test]
+    fn test_assemble_respects_budget() {
+        let long = "word ".repeat(400);
+        let passages = vec![
+            passage("long.md", None, 1, &long),
+            passage("short.md", None, 1, "Short and relevant."),
+        ];
+        // The long passage doesn't fit, the short one after it does
+        let block = assemble(&passages[1..], 100, "semantic");
+        assert_eq!(block.sources.len(), 1);
+        let block = assemble(&passages, 100, "semantic");
+        // Nothing fits whole, so the best passage is truncated
+        assert_eq!(block.sources[0].path, "long.md");
+        assert!(block.context.ends_with('…'));
+        assert!(block.tokens <= 100);
+        assert_eq!(block.sources.len(), 1);
+        assert!(assemble(&[], 100, "keyword").context.is_empty());
+    }
+
+    #[test]
+    fn test_workspace_passages_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("plants.md"),
+            "# Plants\n\nTomatoes want full sun.\n\n## Soil\n\nKeep the soil moist.\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("todo.md"), "Buy milk.\n").unwrap();
+        let passages = workspace_passages(dir.path());
+        let ranked = keyword_rank("soil moisture for tomatoes", passages, 10);
+        assert!(!ranked.is_empty());
+        assert!(ranked.iter().all(|p| p.path == "plants.md"));
+    }
+
+    #[test]
+    fn test_clamp_budget() {
+        assert_eq!(clamp_budget(None), DEFAULT_BUDGET_TOKENS);
+        assert_eq!(clamp_budget(Some(1)), MIN_BUDGET_TOKENS);
+        assert_eq!(clamp_budget(Some(1_000_000)), MAX_BUDGET_TOKENS);
+    }
+}
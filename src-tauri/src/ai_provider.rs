@@ -3,10 +3,14 @@
 //! Detects available CLI AI providers and executes prompts via shell commands
 //! or REST APIs. Streams results back to the frontend via Tauri events.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::io::{BufRead, BufReader, Write as IoWrite};
 use std::process::{Command, Stdio};
-use tauri::{command, Emitter, WebviewWindow};
+use tauri::{command, Emitter, Manager, WebviewWindow};
+
+use crate::ai_redaction::{self, Redactor, RestoreGuard};
+use crate::ai_usage::{self, BudgetExceeded, TokenUsage};
+use crate::{http_client, settings};
 
 // ============================================================================
 // Types
@@ -22,6 +26,39 @@ pub struct CliProviderEntry {
     pub path: Option<String>,
 }
 
+/// Error returned by `run_ai_prompt`
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AiPromptError {
+    /// A rate limit or monthly budget was reached; nothing was sent
+    Budget(BudgetExceeded),
+    Failed { message: String },
+}
+
+impl From<String> for AiPromptError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+/// Which provider to send a prompt to, as configured in the frontend
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConfig {
+    pub provider: String,
+    pub model: Option<String>,
+    pub api_key: Option<String>,
+    pub endpoint: Option<String>,
+    /// Resolved CLI path from detection
+    pub cli_path: Option<String>,
+}
+
+/// A completed response
+struct Reply {
+    usage: TokenUsage,
+    text: String,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct AiResponseChunk {
     #[serde(rename = "requestId")]
@@ -114,7 +151,7 @@ pub(crate) fn login_shell_path() -> String {
         .clone()
 }
 
-fn check_command(cmd: &str) -> (bool, Option<String>) {
+pub(crate) fn check_command(cmd: &str) -> (bool, Option<String>) {
     let which_cmd = if cfg!(target_os = "windows") {
         "where"
     } else {
@@ -174,8 +211,8 @@ pub fn read_env_api_keys() -> std::collections::HashMap<String, String> {
 // Shared Helpers (test / list / validate)
 // ============================================================================
 
-fn make_client(timeout_secs: u64) -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
+fn make_client(timeout_secs: u64, endpoint: Option<&str>) -> Result<reqwest::Client, String> {
+    http_client::builder_for(endpoint)?
         .timeout(std::time::Duration::from_secs(timeout_secs))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))
@@ -215,7 +252,7 @@ pub async fn test_api_key(
     api_key: Option<String>,
     endpoint: Option<String>,
 ) -> Result<String, String> {
-    let client = make_client(10)?;
+    let client = make_client(10, endpoint.as_deref())?;
 
     match provider.as_str() {
         "openai" => {
@@ -295,7 +332,7 @@ pub async fn list_models(
     api_key: Option<String>,
     endpoint: Option<String>,
 ) -> Result<Vec<String>, String> {
-    let client = make_client(10)?;
+    let client = make_client(10, endpoint.as_deref())?;
 
     match provider.as_str() {
         "ollama-api" => {
@@ -412,7 +449,7 @@ pub async fn validate_model(
     api_key: Option<String>,
     endpoint: Option<String>,
 ) -> Result<String, String> {
-    let client = make_client(15)?;
+    let client = make_client(15, endpoint.as_deref())?;
 
     match provider.as_str() {
         "openai" => {
@@ -505,6 +542,10 @@ pub async fn validate_model(
 /// For REST providers: sends HTTP request via reqwest.
 /// `cli_path` is the resolved absolute path from detection (used on
 /// Windows where bare command names may not find `.cmd`/`.bat` shims).
+///
+/// Refused with [`AiPromptError::Budget`] when the provider's rate limit or
+/// monthly budget is reached (see `ai_usage`). Prompts to remote providers
+/// go through the redaction pass when it's enabled (see `ai_redaction`).
 #[command]
 pub async fn run_ai_prompt(
     window: WebviewWindow,
@@ -515,22 +556,70 @@ pub async fn run_ai_prompt(
     api_key: Option<String>,
     endpoint: Option<String>,
     cli_path: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AiPromptError> {
+    run_prompt(
+        &window,
+        &request_id,
+        ProviderConfig {
+            provider,
+            model,
+            api_key,
+            endpoint,
+            cli_path,
+        },
+        prompt,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Send `prompt` to a provider, streaming the response as `ai:response`
+/// events for `request_id`, and return the full response. `None` means the
+/// provider failed and the error was sent as an event.
+pub(crate) async fn run_prompt(
+    window: &WebviewWindow,
+    request_id: &str,
+    config: ProviderConfig,
+    prompt: String,
+) -> Result<Option<String>, AiPromptError> {
+    let ProviderConfig {
+        provider,
+        model,
+        api_key,
+        endpoint,
+        cli_path,
+    } = config;
+    let app = window.app_handle().clone();
+    ai_usage::begin(&app, &provider).map_err(AiPromptError::Budget)?;
+
+    let redactor = if ai_redaction::is_remote(&provider, endpoint.as_deref()) {
+        Redactor::from_settings(&settings::current().advanced.prompt_redaction)?
+    } else {
+        None
+    };
+    let (prompt, _restore) = match redactor {
+        Some(redactor) => {
+            let redacted = redactor.redact(&prompt);
+            (redacted.text, Some(RestoreGuard::new(request_id, redacted.mapping)))
+        }
+        None => (prompt, None),
+    };
+
     let path_ref = cli_path.as_deref();
-    match provider.as_str() {
+    let reply = match provider.as_str() {
         // CLI providers
-        "claude" => run_cli_provider(&window, &request_id, "claude", &["--print", "--output-format", "text"], Some(&prompt), path_ref),
-        "codex" => run_cli_provider(&window, &request_id, "codex", &["exec", &prompt], None, path_ref),
-        "gemini" => run_cli_provider(&window, &request_id, "gemini", &["-p", &prompt], None, path_ref),
+        "claude" => run_cli_provider(window, request_id, "claude", &["--print", "--output-format", "text"], Some(&prompt), path_ref),
+        "codex" => run_cli_provider(window, request_id, "codex", &["exec", &prompt], None, path_ref),
+        "gemini" => run_cli_provider(window, request_id, "gemini", &["-p", &prompt], None, path_ref),
 
         // REST providers
         "anthropic" => {
-            let Some(key) = require_api_key(&window, &request_id, &api_key, "Anthropic") else {
-                return Ok(());
+            let Some(key) = require_api_key(window, request_id, &api_key, "Anthropic") else {
+                return Ok(None);
             };
             run_rest_anthropic(
-                &window,
-                &request_id,
+                window,
+                request_id,
                 &endpoint.unwrap_or_else(|| "https://api.anthropic.com".to_string()),
                 key,
                 &model.unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string()),
@@ -539,12 +628,12 @@ pub async fn run_ai_prompt(
             .await
         }
         "openai" => {
-            let Some(key) = require_api_key(&window, &request_id, &api_key, "OpenAI") else {
-                return Ok(());
+            let Some(key) = require_api_key(window, request_id, &api_key, "OpenAI") else {
+                return Ok(None);
             };
             run_rest_openai(
-                &window,
-                &request_id,
+                window,
+                request_id,
                 &endpoint.unwrap_or_else(|| "https://api.openai.com".to_string()),
                 key,
                 &model.unwrap_or_else(|| "gpt-4o".to_string()),
@@ -553,12 +642,12 @@ pub async fn run_ai_prompt(
             .await
         }
         "google-ai" => {
-            let Some(key) = require_api_key(&window, &request_id, &api_key, "Google AI") else {
-                return Ok(());
+            let Some(key) = require_api_key(window, request_id, &api_key, "Google AI") else {
+                return Ok(None);
             };
             run_rest_google(
-                &window,
-                &request_id,
+                window,
+                request_id,
                 key,
                 &model.unwrap_or_else(|| "gemini-2.0-flash".to_string()),
                 &prompt,
@@ -567,8 +656,8 @@ pub async fn run_ai_prompt(
         }
         "ollama-api" => {
             run_rest_ollama(
-                &window,
-                &request_id,
+                window,
+                request_id,
                 &endpoint.unwrap_or_else(|| "http://localhost:11434".to_string()),
                 &model.unwrap_or_else(|| "llama3.2".to_string()),
                 &prompt,
@@ -577,7 +666,13 @@ pub async fn run_ai_prompt(
         }
 
         _ => Err(format!("Unknown provider: {}", provider)),
-    }
+    }?;
+
+    let Some(reply) = reply else {
+        return Ok(None);
+    };
+    ai_usage::record(&app, &provider, reply.usage);
+    Ok(Some(ai_redaction::restore(request_id, &reply.text)))
 }
 
 // ============================================================================
@@ -620,7 +715,7 @@ fn run_cli_provider(
     args: &[&str],
     stdin_prompt: Option<&str>,
     cli_path: Option<&str>,
-) -> Result<(), String> {
+) -> Result<Option<Reply>, String> {
     let stdin_cfg = if stdin_prompt.is_some() { Stdio::piped() } else { Stdio::null() };
     let effective_cmd = cli_path.unwrap_or(cmd);
 
@@ -643,17 +738,20 @@ fn run_cli_provider(
     }
 
     // Stream stdout line by line
+    let mut response = String::new();
     if let Some(stdout) = child.stdout.take() {
         let reader = BufReader::new(stdout);
         for line in reader.lines() {
             match line {
                 Ok(text) => {
-                    emit_chunk(window, request_id, &(text + "\n"));
+                    let text = text + "\n";
+                    emit_chunk(window, request_id, &text);
+                    response.push_str(&text);
                 }
                 Err(e) => {
                     emit_error(window, request_id, &format!("Read error: {}", e));
                     let _ = child.kill();
-                    return Ok(());
+                    return Ok(None);
                 }
             }
         }
@@ -670,17 +768,32 @@ fn run_cli_provider(
             format!("{} exited with status {}: {}", cmd, output.status, stderr_msg)
         };
         emit_error(window, request_id, &msg);
-    } else {
-        emit_done(window, request_id);
+        return Ok(None);
     }
 
-    Ok(())
+    emit_done(window, request_id);
+    // CLIs don't report token counts
+    Ok(Some(Reply {
+        usage: TokenUsage::estimate(&prompt_text(args, stdin_prompt), &response),
+        text: response,
+    }))
+}
+
+/// The prompt of a CLI invocation, whether piped or passed as an argument.
+fn prompt_text(args: &[&str], stdin_prompt: Option<&str>) -> String {
+    stdin_prompt.map_or_else(|| args.join(" "), str::to_string)
 }
 
 // ============================================================================
 // REST Execution (reqwest)
 // ============================================================================
 
+fn rest_client(endpoint: &str) -> Result<reqwest::Client, String> {
+    http_client::builder_for(Some(endpoint))?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
 async fn run_rest_anthropic(
     window: &WebviewWindow,
     request_id: &str,
@@ -688,8 +801,8 @@ async fn run_rest_anthropic(
     api_key: &str,
     model: &str,
     prompt: &str,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<Option<Reply>, String> {
+    let client = rest_client(endpoint)?;
     let body = serde_json::json!({
         "model": model,
         "max_tokens": 4096,
@@ -710,7 +823,7 @@ async fn run_rest_anthropic(
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
         emit_error(window, request_id, &format!("Anthropic API error {}: {}", status, text));
-        return Ok(());
+        return Ok(None);
     }
 
     let json: serde_json::Value = resp
@@ -719,19 +832,24 @@ async fn run_rest_anthropic(
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
     // Extract text from content blocks
+    let mut response = String::new();
     if let Some(content) = json.get("content").and_then(|c| c.as_array()) {
         for block in content {
             if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
                 emit_chunk(window, request_id, text);
+                response.push_str(text);
             }
         }
     } else {
         emit_error(window, request_id, "No content blocks in Anthropic response");
-        return Ok(());
+        return Ok(None);
     }
 
     emit_done(window, request_id);
-    Ok(())
+    Ok(Some(Reply {
+        usage: TokenUsage::from_response(&json, prompt, &response),
+        text: response,
+    }))
 }
 
 async fn run_rest_openai(
@@ -741,8 +859,8 @@ async fn run_rest_openai(
     api_key: &str,
     model: &str,
     prompt: &str,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<Option<Reply>, String> {
+    let client = rest_client(endpoint)?;
     let body = serde_json::json!({
         "model": model,
         "messages": [{"role": "user", "content": prompt}]
@@ -761,7 +879,7 @@ async fn run_rest_openai(
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
         emit_error(window, request_id, &format!("OpenAI API error {}: {}", status, text));
-        return Ok(());
+        return Ok(None);
     }
 
     let json: serde_json::Value = resp
@@ -769,22 +887,23 @@ async fn run_rest_openai(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    if let Some(text) = json
+    let Some(text) = json
         .get("choices")
         .and_then(|c| c.as_array())
         .and_then(|choices| choices.first())
         .and_then(|c| c.get("message"))
         .and_then(|m| m.get("content"))
         .and_then(|t| t.as_str())
-    {
-        emit_chunk(window, request_id, text);
-    } else {
+    else {
         emit_error(window, request_id, "No choices in OpenAI response");
-        return Ok(());
-    }
-
+        return Ok(None);
+    };
+    emit_chunk(window, request_id, text);
     emit_done(window, request_id);
-    Ok(())
+    Ok(Some(Reply {
+        usage: TokenUsage::from_response(&json, prompt, text),
+        text: text.to_string(),
+    }))
 }
 
 async fn run_rest_google(
@@ -793,8 +912,8 @@ async fn run_rest_google(
     api_key: &str,
     model: &str,
     prompt: &str,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<Option<Reply>, String> {
+    let client = rest_client("https://generativelanguage.googleapis.com")?;
     let body = serde_json::json!({
         "contents": [{"parts": [{"text": prompt}]}]
     });
@@ -817,7 +936,7 @@ async fn run_rest_google(
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
         emit_error(window, request_id, &format!("Google AI error {}: {}", status, text));
-        return Ok(());
+        return Ok(None);
     }
 
     let json: serde_json::Value = resp
@@ -825,7 +944,7 @@ async fn run_rest_google(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    if let Some(text) = json
+    let Some(text) = json
         .get("candidates")
         .and_then(|c| c.as_array())
         .and_then(|candidates| candidates.first())
@@ -835,15 +954,16 @@ async fn run_rest_google(
         .and_then(|parts| parts.first())
         .and_then(|p| p.get("text"))
         .and_then(|t| t.as_str())
-    {
-        emit_chunk(window, request_id, text);
-    } else {
+    else {
         emit_error(window, request_id, "No candidates in Google AI response");
-        return Ok(());
-    }
-
+        return Ok(None);
+    };
+    emit_chunk(window, request_id, text);
     emit_done(window, request_id);
-    Ok(())
+    Ok(Some(Reply {
+        usage: TokenUsage::from_response(&json, prompt, text),
+        text: text.to_string(),
+    }))
 }
 
 async fn run_rest_ollama(
@@ -852,8 +972,8 @@ async fn run_rest_ollama(
     endpoint: &str,
     model: &str,
     prompt: &str,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<Option<Reply>, String> {
+    let client = rest_client(endpoint)?;
     let body = serde_json::json!({
         "model": model,
         "prompt": prompt,
@@ -872,7 +992,7 @@ async fn run_rest_ollama(
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
         emit_error(window, request_id, &format!("Ollama API error {}: {}", status, text));
-        return Ok(());
+        return Ok(None);
     }
 
     let json: serde_json::Value = resp
@@ -880,15 +1000,16 @@ async fn run_rest_ollama(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    if let Some(text) = json.get("response").and_then(|r| r.as_str()) {
-        emit_chunk(window, request_id, text);
-    } else {
+    let Some(text) = json.get("response").and_then(|r| r.as_str()) else {
         emit_error(window, request_id, "No response field in Ollama response");
-        return Ok(());
-    }
-
+        return Ok(None);
+    };
+    emit_chunk(window, request_id, text);
     emit_done(window, request_id);
-    Ok(())
+    Ok(Some(Reply {
+        usage: TokenUsage::from_response(&json, prompt, text),
+        text: text.to_string(),
+    }))
 }
 
 // ============================================================================
@@ -918,7 +1039,7 @@ fn emit_chunk(window: &WebviewWindow, request_id: &str, text: &str) {
         "ai:response",
         AiResponseChunk {
             request_id: request_id.to_string(),
-            chunk: text.to_string(),
+            chunk: ai_redaction::restore(request_id, text),
             done: false,
             error: None,
         },
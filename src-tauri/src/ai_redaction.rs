@@ -0,0 +1,303 @@
+//! Prompt Redaction
+//!
+//! Optional privacy pass for prompts sent to remote AI providers. Sensitive
+//! spans (emails, API keys, listed names, custom patterns) are replaced with
+//! placeholders such as `[EMAIL_1]` before the prompt leaves the machine,
+//! and the mapping kept here puts the originals back into the response as
+//! it streams in. The same value always gets the same placeholder, so the
+//! model can still tell that two mentions refer to one thing.
+//!
+//! Configured in Settings → Advanced (`promptRedaction`). Prompts to a local
+//! Ollama server are sent as-is.
+
+use regex::{Regex, RegexBuilder};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::settings::PromptRedactionSettings;
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}";
+const API_KEY_PATTERN: &str = r"\b(?:sk-(?:ant-|proj-)?[A-Za-z0-9_-]{20,}|AKIA[0-9A-Z]{16}|gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,}|AIza[0-9A-Za-z_-]{35}|xox[abprs]-[A-Za-z0-9-]{10,})";
+const PHONE_PATTERN: &str = r"(?:\+\d{1,3}[ .-]?)?\(?\b\d{3}\)?[ .-]?\d{3}[ .-]?\d{4}\b";
+const IP_ADDRESS_PATTERN: &str =
+    r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b";
+
+/// Placeholder → original, for requests whose prompt was redacted
+static ACTIVE: Mutex<BTreeMap<String, Vec<(String, String)>>> = Mutex::new(BTreeMap::new());
+
+struct Rule {
+    label: &'static str,
+    regex: Regex,
+}
+
+pub(crate) struct Redactor {
+    rules: Vec<Rule>,
+}
+
+/// A redacted prompt and the placeholders it contains
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Redacted {
+    pub text: String,
+    /// (placeholder, original)
+    pub mapping: Vec<(String, String)>,
+}
+
+fn entity_rule(entity: &str) -> Option<(&'static str, &'static str)> {
+    match entity {
+        "email" => Some(("EMAIL", EMAIL_PATTERN)),
+        "apiKey" => Some(("API_KEY", API_KEY_PATTERN)),
+        "phone" => Some(("PHONE", PHONE_PATTERN)),
+        "ipAddress" => Some(("IP", IP_ADDRESS_PATTERN)),
+        _ => None,
+    }
+}
+
+impl Redactor {
+    /// Rules from settings; `None` when redaction is off or has no rules.
+    pub(crate) fn from_settings(
+        settings: &PromptRedactionSettings,
+    ) -> Result<Option<Self>, String> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let mut rules = Vec::new();
+        for entity in &settings.entities {
+            match entity_rule(entity) {
+                Some((label, pattern)) => rules.push(Rule {
+                    label,
+                    regex: Regex::new(pattern).map_err(|e| e.to_string())?,
+                }),
+                None => tracing::warn!("[AI] Unknown redaction entity: {}", entity),
+            }
+        }
+
+        let names: Vec<String> = settings
+            .names
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(regex::escape)
+            .collect();
+        if !names.is_empty() {
+            let regex = RegexBuilder::new(&format!(r"\b(?:{})\b", names.join("|")))
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Invalid redaction name list: {}", e))?;
+            rules.push(Rule {
+                label: "NAME",
+                regex,
+            });
+        }
+
+        for pattern in settings.patterns.iter().filter(|p| !p.trim().is_empty()) {
+            let regex = Regex::new(pattern)
+                .map_err(|e| format!("Invalid redaction pattern {:?}: {}", pattern, e))?;
+            rules.push(Rule {
+                label: "REDACTED",
+                regex,
+            });
+        }
+
+        Ok((!rules.is_empty()).then_some(Self { rules }))
+    }
+
+    /// Replace every match with a placeholder. Where rules overlap, the
+    /// earliest and then longest match wins.
+    pub(crate) fn redact(&self, text: &str) -> Redacted {
+        let mut spans: Vec<(usize, usize, &'static str)> = self
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                rule.regex
+                    .find_iter(text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| (m.start(), m.end(), rule.label))
+            })
+            .collect();
+        spans.sort_by_key(|&(start, end, _)| (start, std::cmp::Reverse(end)));
+
+        let mut redacted = Redacted::default();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut pos = 0;
+        for (start, end, label) in spans {
+            if start < pos {
+                continue;
+            }
+            let original = &text[start..end];
+            let placeholder = match redacted.mapping.iter().find(|(_, o)| o == original) {
+                Some((placeholder, _)) => placeholder.clone(),
+                None => {
+                    let n = counts.entry(label).or_default();
+                    *n += 1;
+                    let placeholder = format!("[{}_{}]", label, n);
+                    redacted
+                        .mapping
+                        .push((placeholder.clone(), original.to_string()));
+                    placeholder
+                }
+            };
+            redacted.text.push_str(&text[pos..start]);
+            redacted.text.push_str(&placeholder);
+            pos = end;
+        }
+        redacted.text.push_str(&text[pos..]);
+        redacted
+    }
+}
+
+/// Put the originals back in place of their placeholders.
+fn restore_text(text: &str, mapping: &[(String, String)]) -> String {
+    mapping
+        .iter()
+        .fold(text.to_string(), |acc, (placeholder, original)| {
+            acc.replace(placeholder, original)
+        })
+}
+
+/// Whether prompts to this provider leave the machine. Ollama is local
+/// unless its endpoint points elsewhere.
+pub(crate) fn is_remote(provider: &str, endpoint: Option<&str>) -> bool {
+    if provider != "ollama-api" {
+        return true;
+    }
+    let Some(endpoint) = endpoint.filter(|e| !e.trim().is_empty()) else {
+        return false;
+    };
+    match reqwest::Url::parse(endpoint.trim()) {
+        Ok(url) => !matches!(
+            url.host_str(),
+            Some("localhost" | "127.0.0.1" | "[::1]" | "::1")
+        ),
+        Err(_) => true,
+    }
+}
+
+/// Keeps a request's mapping available to [`restore`] until dropped.
+pub(crate) struct RestoreGuard {
+    request_id: String,
+}
+
+impl RestoreGuard {
+    pub(crate) fn new(request_id: &str, mapping: Vec<(String, String)>) -> Self {
+        ACTIVE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request_id.to_string(), mapping);
+        Self {
+            request_id: request_id.to_string(),
+        }
+    }
+}
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        ACTIVE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.request_id);
+    }
+}
+
+/// A response chunk for `request_id` with redacted spans restored.
+pub(crate) fn restore(request_id: &str, text: &str) -> String {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    match active.get(request_id) {
+        Some(mapping) => restore_text(text, mapping),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor(entities: &[&str], names: &[&str], patterns: &[&str]) -> Redactor {
+        let settings = PromptRedactionSettings {
+            enabled: true,
+            entities: entities.iter().map(|s| s.to_string()).collect(),
+            names: names.iter().map(|s| s.to_string()).collect(),
+            patterns: patterns.iter().map(|s| s.to_string()).collect(),
+        };
+        Redactor::from_settings(&settings).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_redacts_entities_with_stable_placeholders() {
+        let r = redactor(&["email", "apiKey"], &[], &[]);
+        let out = r.redact("Mail ada@example.com, key sk-abcdefghijklmnopqrstuvwx, cc ada@example.com and bob@corp.io");
+        assert_eq!(
+            out.text,
+            "Mail [EMAIL_1], key [API_KEY_1], cc [EMAIL_1] and [EMAIL_2]"
+        );
+        assert_eq!(out.mapping.len(), 3);
+        assert_eq!(restore_text(&out.text, &out.mapping), "Mail ada@example.com, key sk-abcdefghijklmnopqrstuvwx, cc ada@example.com and bob@corp.io");
+    }
+
+    #[test]
+    fn test_names_and_custom_patterns() {
+        let r = redactor(&[], &["Ada Lovelace", "Acme"], &[r"PRJ-\d+"]);
+        let out = r.redact("ada lovelace at Acme works on PRJ-42, not Acmeville.");
+        assert_eq!(
+            out.text,
+            "[NAME_1] at [NAME_2] works on [REDACTED_1], not Acmeville."
+        );
+        assert_eq!(
+            out.mapping[0],
+            ("[NAME_1]".to_string(), "ada lovelace".to_string())
+        );
+    }
+
+    #[test]
+    fn test_overlapping_matches_take_the_longest() {
+        let r = redactor(&["email"], &["example"], &[]);
+        let out = r.redact("ada@example.com");
+        assert_eq!(out.text, "[EMAIL_1]");
+    }
+
+    #[test]
+    fn test_phone_and_ip() {
+        let r = redactor(&["phone", "ipAddress"], &[], &[]);
+        let out = r.redact("Call +1 415-555-0100 from 10.0.0.12 (build 1.2.3)");
+        assert_eq!(out.text, "Call [PHONE_1] from [IP_1] (build 1.2.3)");
+    }
+
+    #[test]
+    fn test_disabled_or_empty_settings() {
+        assert!(Redactor::from_settings(&PromptRedactionSettings::default())
+            .unwrap()
+            .is_none());
+        let empty = PromptRedactionSettings {
+            enabled: true,
+            entities: Vec::new(),
+            names: vec!["  ".into()],
+            patterns: Vec::new(),
+        };
+        assert!(Redactor::from_settings(&empty).unwrap().is_none());
+        let bad = PromptRedactionSettings {
+            enabled: true,
+            patterns: vec!["(".into()],
+            ..PromptRedactionSettings::default()
+        };
+        assert!(Redactor::from_settings(&bad).is_err());
+    }
+
+    #[test]
+    fn test_restore_by_request() {
+        {
+            let _guard = RestoreGuard::new("req-1", vec![("[NAME_1]".into(), "Ada".into())]);
+            assert_eq!(restore("req-1", "Dear [NAME_1],"), "Dear Ada,");
+            assert_eq!(restore("req-2", "Dear [NAME_1],"), "Dear [NAME_1],");
+        }
+        assert_eq!(restore("req-1", "Dear [NAME_1],"), "Dear [NAME_1],");
+    }
+
+    #[test]
+    fn test_remote_providers() {
+        assert!(is_remote("anthropic", None));
+        assert!(is_remote("claude", None));
+        assert!(!is_remote("ollama-api", None));
+        assert!(!is_remote("ollama-api", Some("http://localhost:11434")));
+        assert!(!is_remote("ollama-api", Some("http://127.0.0.1:11434")));
+        assert!(is_remote("ollama-api", Some("https://llm.corp.example")));
+    }
+}
@@ -0,0 +1,606 @@
+//! AI Usage Limits
+//!
+//! Per-provider request-rate limits and monthly token/cost budgets for the
+//! AI router. `run_ai_prompt` calls [`begin`] before sending a prompt and
+//! [`record`] after a reply; when a cap is hit the prompt is refused with a
+//! [`BudgetExceeded`] error rather than spending money.
+//!
+//! Token counts come from the provider's response when it reports them and
+//! are estimated (about four characters per token) otherwise, e.g. for CLI
+//! providers. Cost is computed from the per-million-token prices the user
+//! enters with the limits. Limits and monthly counters are stored in
+//! `<app_data>/ai-usage.json`; the per-minute request window is in memory.
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+const DATA_FILE: &str = "ai-usage.json";
+
+/// Months of counters kept in the data file
+const MAX_HISTORY_MONTHS: usize = 12;
+
+const RATE_WINDOW_MS: i64 = 60_000;
+
+/// Rough characters per token, for providers that don't report usage
+const CHARS_PER_TOKEN: u64 = 4;
+
+/// Serializes reads and writes of the data file
+static DATA_LOCK: Mutex<()> = Mutex::new(());
+
+/// Start times (ms) of requests in the last minute, by provider
+static RECENT_REQUESTS: Mutex<BTreeMap<String, VecDeque<i64>>> = Mutex::new(BTreeMap::new());
+
+/// Caps for one provider. Unset fields don't limit anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProviderLimits {
+    pub requests_per_minute: Option<u32>,
+    /// Input plus output tokens per calendar month
+    pub monthly_tokens: Option<u64>,
+    /// Estimated spend per calendar month, in USD
+    pub monthly_cost: Option<f64>,
+    /// USD per million input tokens
+    pub input_price: Option<f64>,
+    /// USD per million output tokens
+    pub output_price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+struct UsageCounters {
+    requests: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cost: f64,
+}
+
+/// Contents of the data file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct UsageData {
+    limits: BTreeMap<String, ProviderLimits>,
+    /// Keyed by `YYYY-MM`, then provider
+    months: BTreeMap<String, BTreeMap<String, UsageCounters>>,
+}
+
+/// Tokens used by one prompt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+}
+
+impl TokenUsage {
+    /// Estimate from text length.
+    pub(crate) fn estimate(prompt: &str, output: &str) -> Self {
+        Self {
+            input: estimate_tokens(prompt),
+            output: estimate_tokens(output),
+        }
+    }
+
+    /// Usage reported in a REST response, or an estimate when it's missing.
+    pub(crate) fn from_response(json: &Value, prompt: &str, output: &str) -> Self {
+        reported_usage(json).unwrap_or_else(|| Self::estimate(prompt, output))
+    }
+}
+
+/// Rough token count of `text`.
+pub(crate) fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Usage fields of Anthropic, OpenAI, Google AI and Ollama responses.
+fn reported_usage(json: &Value) -> Option<TokenUsage> {
+    let field = |value: &Value, key: &str| value.get(key).and_then(Value::as_u64);
+    let pair = |value: &Value, input: &str, output: &str| {
+        Some(TokenUsage {
+            input: field(value, input)?,
+            output: field(value, output).unwrap_or(0),
+        })
+    };
+    if let Some(usage) = json.get("usage") {
+        pair(usage, "input_tokens", "output_tokens")
+            .or_else(|| pair(usage, "prompt_tokens", "completion_tokens"))
+    } else if let Some(usage) = json.get("usageMetadata") {
+        pair(usage, "promptTokenCount", "candidatesTokenCount")
+    } else {
+        pair(json, "prompt_eval_count", "eval_count")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BudgetLimit {
+    Rate,
+    Tokens,
+    Cost,
+}
+
+/// A prompt refused because a cap was reached.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetExceeded {
+    pub provider: String,
+    pub limit: BudgetLimit,
+    /// Requests in the last minute, tokens or USD this month
+    pub used: f64,
+    pub cap: f64,
+    /// When the limit frees up (ms since epoch)
+    pub retry_at: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUsage {
+    pub provider: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated USD spent this month
+    pub cost: f64,
+    pub requests_last_minute: u32,
+    pub limits: ProviderLimits,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    /// `YYYY-MM`
+    pub month: String,
+    /// Start of next month (ms since epoch), when monthly counters reset
+    pub resets_at: i64,
+    pub providers: Vec<ProviderUsage>,
+}
+
+fn month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+/// Local midnight on the first of the month after `date`, in ms.
+fn next_month_start(date: NaiveDate) -> i64 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|d| d.and_local_timezone(Local).earliest())
+        .map_or(i64::MAX, |d| d.timestamp_millis())
+}
+
+fn cost_of(limits: &ProviderLimits, usage: TokenUsage) -> f64 {
+    let per_million =
+        |price: Option<f64>, tokens: u64| price.unwrap_or(0.0) * tokens as f64 / 1_000_000.0;
+    per_million(limits.input_price, usage.input) + per_million(limits.output_price, usage.output)
+}
+
+/// Drop requests older than the rate window.
+fn prune_window(recent: &mut VecDeque<i64>, now_ms: i64) {
+    while recent
+        .front()
+        .is_some_and(|&start| now_ms - start >= RATE_WINDOW_MS)
+    {
+        recent.pop_front();
+    }
+}
+
+fn check_limits(
+    provider: &str,
+    limits: &ProviderLimits,
+    counters: &UsageCounters,
+    recent: &VecDeque<i64>,
+    now_ms: i64,
+    resets_at: i64,
+) -> Result<(), BudgetExceeded> {
+    let exceeded = |limit, used: f64, cap: f64, retry_at, message: String| BudgetExceeded {
+        provider: provider.to_string(),
+        limit,
+        used,
+        cap,
+        retry_at,
+        message,
+    };
+    if let Some(cap) = limits.requests_per_minute {
+        if recent.len() >= cap as usize {
+            let retry_at = recent
+                .front()
+                .map_or(now_ms, |&start| start + RATE_WINDOW_MS);
+            let wait = ((retry_at - now_ms) as f64 / 1000.0).ceil().max(1.0);
+            return Err(exceeded(
+                BudgetLimit::Rate,
+                recent.len() as f64,
+                cap as f64,
+                retry_at,
+                format!(
+                    "{} is limited to {} requests per minute. Try again in {}s.",
+                    provider, cap, wait
+                ),
+            ));
+        }
+    }
+    let tokens = counters.input_tokens + counters.output_tokens;
+    if let Some(cap) = limits.monthly_tokens.filter(|&cap| tokens >= cap) {
+        return Err(exceeded(
+            BudgetLimit::Tokens,
+            tokens as f64,
+            cap as f64,
+            resets_at,
+            format!(
+                "{} has used its monthly budget of {} tokens.",
+                provider, cap
+            ),
+        ));
+    }
+    if let Some(cap) = limits.monthly_cost.filter(|&cap| counters.cost >= cap) {
+        return Err(exceeded(
+            BudgetLimit::Cost,
+            counters.cost,
+            cap,
+            resets_at,
+            format!("{} has used its monthly budget of ${:.2}.", provider, cap),
+        ));
+    }
+    Ok(())
+}
+
+fn apply_usage(data: &mut UsageData, month: &str, provider: &str, usage: TokenUsage) {
+    let cost = cost_of(
+        &data.limits.get(provider).cloned().unwrap_or_default(),
+        usage,
+    );
+    let counters = data
+        .months
+        .entry(month.to_string())
+        .or_default()
+        .entry(provider.to_string())
+        .or_default();
+    counters.requests += 1;
+    counters.input_tokens += usage.input;
+    counters.output_tokens += usage.output;
+    counters.cost += cost;
+    while data.months.len() > MAX_HISTORY_MONTHS {
+        data.months.pop_first();
+    }
+}
+
+fn summary(
+    data: &UsageData,
+    recent: &BTreeMap<String, VecDeque<i64>>,
+    today: NaiveDate,
+    now_ms: i64,
+) -> UsageSummary {
+    let month = month_key(today);
+    let counters = data.months.get(&month);
+    let mut providers: Vec<&String> = data
+        .limits
+        .keys()
+        .chain(counters.into_iter().flat_map(|c| c.keys()))
+        .collect();
+    providers.sort();
+    providers.dedup();
+    UsageSummary {
+        resets_at: next_month_start(today),
+        providers: providers
+            .into_iter()
+            .map(|provider| {
+                let used = counters
+                    .and_then(|c| c.get(provider))
+                    .cloned()
+                    .unwrap_or_default();
+                let in_window = recent.get(provider).map_or(0, |r| {
+                    r.iter()
+                        .filter(|&&start| now_ms - start < RATE_WINDOW_MS)
+                        .count()
+                });
+                ProviderUsage {
+                    provider: provider.clone(),
+                    requests: used.requests,
+                    input_tokens: used.input_tokens,
+                    output_tokens: used.output_tokens,
+                    cost: used.cost,
+                    requests_last_minute: in_window as u32,
+                    limits: data.limits.get(provider).cloned().unwrap_or_default(),
+                }
+            })
+            .collect(),
+        month,
+    }
+}
+
+fn data_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join(DATA_FILE))
+}
+
+fn load_data(path: &Path) -> UsageData {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_data(path: &Path, data: &UsageData) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data folder: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(path, json.as_bytes())
+}
+
+fn update_data<T>(app: &AppHandle, f: impl FnOnce(&mut UsageData) -> T) -> Result<T, String> {
+    let path = data_path(app)?;
+    let _guard = DATA_LOCK.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut data = load_data(&path);
+    let result = f(&mut data);
+    save_data(&path, &data)?;
+    Ok(result)
+}
+
+/// Check `provider`'s limits before a prompt and count it toward the rate
+/// window.
+pub(crate) fn begin(app: &AppHandle, provider: &str) -> Result<(), BudgetExceeded> {
+    let data = data_path(app)
+        .map(|path| load_data(&path))
+        .unwrap_or_default();
+    let Some(limits) = data.limits.get(provider) else {
+        return Ok(());
+    };
+    let today = Local::now().date_naive();
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let counters = data
+        .months
+        .get(&month_key(today))
+        .and_then(|m| m.get(provider))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut recent_requests = RECENT_REQUESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let recent = recent_requests.entry(provider.to_string()).or_default();
+    prune_window(recent, now_ms);
+    check_limits(
+        provider,
+        limits,
+        &counters,
+        recent,
+        now_ms,
+        next_month_start(today),
+    )?;
+    recent.push_back(now_ms);
+    Ok(())
+}
+
+/// Add a finished prompt to this month's counters.
+pub(crate) fn record(app: &AppHandle, provider: &str, usage: TokenUsage) {
+    let month = month_key(Local::now().date_naive());
+    if let Err(e) = update_data(app, |data| apply_usage(data, &month, provider, usage)) {
+        tracing::warn!("[AI] Failed to record usage: {}", e);
+    }
+}
+
+/// This month's usage and limits for every provider that has either.
+#[tauri::command]
+pub fn get_usage_summary(app: AppHandle) -> Result<UsageSummary, String> {
+    let data = load_data(&data_path(&app)?);
+    let recent = RECENT_REQUESTS.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(summary(
+        &data,
+        &recent,
+        Local::now().date_naive(),
+        chrono::Utc::now().timestamp_millis(),
+    ))
+}
+
+/// Set `provider`'s limits and prices. All-empty limits remove them.
+#[tauri::command]
+pub fn set_usage_limits(
+    app: AppHandle,
+    provider: String,
+    limits: ProviderLimits,
+) -> Result<UsageSummary, String> {
+    if provider.trim().is_empty() {
+        return Err("Provider is required".to_string());
+    }
+    let data = update_data(&app, |data| {
+        if limits == ProviderLimits::default() {
+            data.limits.remove(&provider);
+        } else {
+            data.limits.insert(provider, limits);
+        }
+        data.clone()
+    })?;
+    let recent = RECENT_REQUESTS.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(summary(
+        &data,
+        &recent,
+        Local::now().date_naive(),
+        chrono::Utc::now().timestamp_millis(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn limits() -> ProviderLimits {
+        ProviderLimits {
+            requests_per_minute: Some(2),
+            monthly_tokens: Some(1_000),
+            monthly_cost: Some(1.0),
+            input_price: Some(3.0),
+            output_price: Some(15.0),
+        }
+    }
+
+    #[test]
+    fn test_reported_usage_shapes() {
+        let anthropic = json!({"usage": {"input_tokens": 12, "output_tokens": 30}});
+        let openai = json!({"usage": {"prompt_tokens": 7, "completion_tokens": 9}});
+        let google = json!({"usageMetadata": {"promptTokenCount": 5, "candidatesTokenCount": 8}});
+        let ollama = json!({"response": "hi", "prompt_eval_count": 4, "eval_count": 2});
+        assert_eq!(
+            reported_usage(&anthropic),
+            Some(TokenUsage {
+                input: 12,
+                output: 30
+            })
+        );
+        assert_eq!(
+            reported_usage(&openai),
+            Some(TokenUsage {
+                input: 7,
+                output: 9
+            })
+        );
+        assert_eq!(
+            reported_usage(&google),
+            Some(TokenUsage {
+                input: 5,
+                output: 8
+            })
+        );
+        assert_eq!(
+            reported_usage(&ollama),
+            Some(TokenUsage {
+                input: 4,
+                output: 2
+            })
+        );
+
+        // Missing usage falls back to an estimate
+        let usage = TokenUsage::from_response(&json!({}), "abcdefgh", "abcde");
+        assert_eq!(
+            usage,
+            TokenUsage {
+                input: 2,
+                output: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let counters = UsageCounters::default();
+        let mut recent = VecDeque::from([1_000, 30_000]);
+        let err = check_limits("openai", &limits(), &counters, &recent, 40_000, 0).unwrap_err();
+        assert_eq!(err.limit, BudgetLimit::Rate);
+        assert_eq!(err.retry_at, 61_000);
+
+        // The first request leaves the window after a minute
+        prune_window(&mut recent, 61_000);
+        assert_eq!(recent.len(), 1);
+        assert!(check_limits("openai", &limits(), &counters, &recent, 61_000, 0).is_ok());
+    }
+
+    #[test]
+    fn test_monthly_budgets() {
+        let recent = VecDeque::new();
+        let tokens = UsageCounters {
+            requests: 3,
+            input_tokens: 600,
+            output_tokens: 400,
+            cost: 0.0,
+        };
+        let err = check_limits("anthropic", &limits(), &tokens, &recent, 0, 99).unwrap_err();
+        assert_eq!(
+            (err.limit, err.used, err.cap, err.retry_at),
+            (BudgetLimit::Tokens, 1_000.0, 1_000.0, 99)
+        );
+
+        let cost = UsageCounters {
+            cost: 1.25,
+            ..UsageCounters::default()
+        };
+        let err = check_limits("anthropic", &limits(), &cost, &recent, 0, 99).unwrap_err();
+        assert_eq!(err.limit, BudgetLimit::Cost);
+        assert!(check_limits(
+            "anthropic",
+            &ProviderLimits::default(),
+            &cost,
+            &recent,
+            0,
+            99
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_apply_usage_counts_cost_and_prunes_months() {
+        let mut data = UsageData::default();
+        data.limits.insert("anthropic".into(), limits());
+        apply_usage(
+            &mut data,
+            "2026-03",
+            "anthropic",
+            TokenUsage {
+                input: 1_000_000,
+                output: 100_000,
+            },
+        );
+        apply_usage(
+            &mut data,
+            "2026-03",
+            "ollama-api",
+            TokenUsage {
+                input: 10,
+                output: 20,
+            },
+        );
+        let month = &data.months["2026-03"];
+        assert_eq!(month["anthropic"].requests, 1);
+        assert!((month["anthropic"].cost - 4.5).abs() < 1e-9);
+        assert_eq!(month["ollama-api"].cost, 0.0);
+
+        for m in 1..=13 {
+            apply_usage(
+                &mut data,
+                &format!("2027-{:02}", m),
+                "openai",
+                TokenUsage::default(),
+            );
+        }
+        assert_eq!(data.months.len(), MAX_HISTORY_MONTHS);
+        assert!(!data.months.contains_key("2026-03"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut data = UsageData::default();
+        data.limits.insert("openai".into(), limits());
+        apply_usage(
+            &mut data,
+            "2026-03",
+            "claude",
+            TokenUsage {
+                input: 5,
+                output: 6,
+            },
+        );
+        apply_usage(&mut data, "2026-02", "gemini", TokenUsage::default());
+        let recent = BTreeMap::from([("openai".to_string(), VecDeque::from([0, 50_000]))]);
+        let today = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+
+        let result = summary(&data, &recent, today, 70_000);
+        assert_eq!(result.month, "2026-03");
+        let names: Vec<&str> = result
+            .providers
+            .iter()
+            .map(|p| p.provider.as_str())
+            .collect();
+        assert_eq!(names, ["claude", "openai"]);
+        assert_eq!(result.providers[0].input_tokens, 5);
+        assert_eq!(result.providers[1].requests_last_minute, 1);
+        assert_eq!(result.providers[1].limits, limits());
+        assert!(result.resets_at > 0);
+    }
+}
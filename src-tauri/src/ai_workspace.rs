@@ -0,0 +1,339 @@
+//! Workspace Q&A
+//!
+//! `ask_workspace` answers questions from the user's notes: it picks
+//! relevant excerpts with `ai_context`, sends them with the question and
+//! the conversation so far to the active AI provider, and streams the
+//! answer back as `ai:response` events like any other prompt.
+//!
+//! Sources are announced on `ai:citations` twice per question: once before
+//! the provider is called (all excerpts, `done: false`) so the frontend can
+//! list them while the answer streams, and once afterwards with the excerpt
+//! numbers the answer actually cites.
+//!
+//! Conversations are kept in memory per session id, so follow-up questions
+//! can refer to earlier ones. They're not persisted.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{Emitter, WebviewWindow};
+
+use crate::ai_context::{self, ContextSource};
+use crate::ai_provider::{self, AiPromptError, ProviderConfig};
+
+/// Earlier turns included in the prompt
+const HISTORY_TURNS: usize = 6;
+
+/// Earlier answers are cut to this length in the prompt
+const HISTORY_ANSWER_CHARS: usize = 1500;
+
+/// Sessions kept before the least recently used is dropped
+const MAX_SESSIONS: usize = 32;
+
+static SESSIONS: Mutex<BTreeMap<String, Session>> = Mutex::new(BTreeMap::new());
+
+struct Session {
+    workspace_root: String,
+    turns: Vec<WorkspaceTurn>,
+    last_used: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskWorkspaceRequest {
+    pub session_id: String,
+    /// Id of the `ai:response` / `ai:citations` events for this answer
+    pub request_id: String,
+    pub question: String,
+    pub workspace_root: String,
+    /// Size of the notes excerpts (see `build_context`)
+    pub budget_tokens: Option<usize>,
+    pub embedding_model: Option<String>,
+    #[serde(flatten)]
+    pub provider: ProviderConfig,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceTurn {
+    pub question: String,
+    pub answer: String,
+    pub sources: Vec<ContextSource>,
+    /// Numbers of the sources the answer cites (`[1]` → 1)
+    pub cited: Vec<usize>,
+}
+
+/// Payload of `ai:citations`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceCitations {
+    pub request_id: String,
+    pub session_id: String,
+    pub sources: Vec<ContextSource>,
+    pub cited: Vec<usize>,
+    pub done: bool,
+}
+
+fn lock_sessions() -> std::sync::MutexGuard<'static, BTreeMap<String, Session>> {
+    SESSIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Earlier turns of `session_id`, if it belongs to `workspace_root`.
+fn history(session_id: &str, workspace_root: &str) -> Vec<WorkspaceTurn> {
+    lock_sessions()
+        .get(session_id)
+        .filter(|s| s.workspace_root == workspace_root)
+        .map(|s| s.turns.clone())
+        .unwrap_or_default()
+}
+
+fn record_turn(session_id: &str, workspace_root: &str, turn: WorkspaceTurn) {
+    let mut sessions = lock_sessions();
+    let session = sessions
+        .entry(session_id.to_string())
+        .or_insert_with(|| Session {
+            workspace_root: workspace_root.to_string(),
+            turns: Vec::new(),
+            last_used: Instant::now(),
+        });
+    if session.workspace_root != workspace_root {
+        session.workspace_root = workspace_root.to_string();
+        session.turns.clear();
+    }
+    session.turns.push(turn);
+    session.last_used = Instant::now();
+
+    while sessions.len() > MAX_SESSIONS {
+        let Some(oldest) = sessions
+            .iter()
+            .min_by_key(|(_, s)| s.last_used)
+            .map(|(id, _)| id.clone())
+        else {
+            break;
+        };
+        sessions.remove(&oldest);
+    }
+}
+
+/// What to search the notes for. Follow-ups like "and in March?" say
+/// little on their own, so the previous question is searched along with it.
+fn retrieval_query(question: &str, history: &[WorkspaceTurn]) -> String {
+    match history.last() {
+        Some(previous) => format!("{}\n{}", previous.question, question),
+        None => question.to_string(),
+    }
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn build_prompt(question: &str, notes: &str, history: &[WorkspaceTurn]) -> String {
+    let mut prompt = String::from(
+        "Answer the question using only the numbered excerpts from my notes below.\n\
+         Cite the excerpts you rely on by their number, like [1] or [2].\n\
+         If the notes don't contain the answer, say so instead of guessing.\n",
+    );
+    let recent = &history[history.len().saturating_sub(HISTORY_TURNS)..];
+    if !recent.is_empty() {
+        prompt.push_str("\nConversation so far:\n");
+        for turn in recent {
+            prompt.push_str(&format!(
+                "\nQuestion: {}\nAnswer: {}\n",
+                turn.question.trim(),
+                truncate_chars(turn.answer.trim(), HISTORY_ANSWER_CHARS)
+            ));
+        }
+    }
+    prompt.push_str("\nNotes:\n\n");
+    if notes.trim().is_empty() {
+        prompt.push_str("(No matching notes were found.)\n");
+    } else {
+        prompt.push_str(notes.trim());
+        prompt.push('\n');
+    }
+    prompt.push_str(&format!("\nQuestion: {}\n", question.trim()));
+    prompt
+}
+
+/// Source numbers cited in `answer` as `[2]` or `[1, 3]`, ascending, limited
+/// to `1..=count`.
+fn cited_sources(answer: &str, count: usize) -> Vec<usize> {
+    let mut cited = BTreeSet::new();
+    for part in answer.split('[').skip(1) {
+        let Some((inside, _)) = part.split_once(']') else {
+            continue;
+        };
+        let numbers: Option<Vec<usize>> = inside
+            .split(',')
+            .map(|n| n.trim().parse::<usize>().ok())
+            .collect();
+        for n in numbers.unwrap_or_default() {
+            if (1..=count).contains(&n) {
+                cited.insert(n);
+            }
+        }
+    }
+    cited.into_iter().collect()
+}
+
+fn emit_citations(window: &WebviewWindow, citations: WorkspaceCitations) {
+    let _ = window.emit("ai:citations", citations);
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Answer `question` from the notes in `workspace_root`, continuing the
+/// conversation in `session_id`. The answer streams as `ai:response`
+/// events for `request_id`; the resolved value is `None` when the provider
+/// failed (the error is on the event stream, as for `run_ai_prompt`).
+#[tauri::command]
+pub async fn ask_workspace(
+    window: WebviewWindow,
+    request: AskWorkspaceRequest,
+) -> Result<Option<WorkspaceTurn>, AiPromptError> {
+    let question = request.question.trim().to_string();
+    if question.is_empty() {
+        return Err("Question is empty".to_string().into());
+    }
+    let history = history(&request.session_id, &request.workspace_root);
+
+    let context = ai_context::build_context(
+        retrieval_query(&question, &history),
+        request.workspace_root.clone(),
+        request.budget_tokens,
+        request.embedding_model,
+    )
+    .await?;
+    let citations = |cited: Vec<usize>, done: bool| WorkspaceCitations {
+        request_id: request.request_id.clone(),
+        session_id: request.session_id.clone(),
+        sources: context.sources.clone(),
+        cited,
+        done,
+    };
+    emit_citations(&window, citations(Vec::new(), false));
+
+    let prompt = build_prompt(&question, &context.context, &history);
+    let Some(answer) =
+        ai_provider::run_prompt(&window, &request.request_id, request.provider, prompt).await?
+    else {
+        return Ok(None);
+    };
+
+    let cited = cited_sources(&answer, context.sources.len());
+    emit_citations(&window, citations(cited.clone(), true));
+
+    let turn = WorkspaceTurn {
+        question,
+        answer,
+        sources: context.sources.clone(),
+        cited,
+    };
+    record_turn(&request.session_id, &request.workspace_root, turn.clone());
+    Ok(Some(turn))
+}
+
+/// Questions and answers so far in `session_id`.
+#[tauri::command]
+pub fn get_workspace_session(session_id: String) -> Vec<WorkspaceTurn> {
+    lock_sessions()
+        .get(&session_id)
+        .map(|s| s.turns.clone())
+        .unwrap_or_default()
+}
+
+/// Forget a conversation.
+#[tauri::command]
+pub fn clear_workspace_session(session_id: String) {
+    lock_sessions().remove(&session_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(question: &str, answer: &str) -> WorkspaceTurn {
+        WorkspaceTurn {
+            question: question.to_string(),
+            answer: answer.to_string(),
+            sources: Vec::new(),
+            cited: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cited_sources() {
+        assert_eq!(
+            cited_sources("Water daily [2]. Tomatoes like sun [1, 3][2].", 3),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            cited_sources("See [4] and [0] and [a] and [1", 3),
+            Vec::<usize>::new()
+        );
+        assert_eq!(cited_sources("No citations.", 3), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_build_prompt_includes_history_and_notes() {
+        let history = vec![turn("When do I water?", "In the morning [1].")];
+        let prompt = build_prompt(
+            " And how often? ",
+            "[1] Garden (garden.md:3)\nDaily.",
+            &history,
+        );
+        assert!(prompt.contains("Conversation so far:"));
+        assert!(prompt.contains("Question: When do I water?\nAnswer: In the morning [1]."));
+        assert!(prompt.contains("Notes:\n\n[1] Garden (garden.md:3)\nDaily.\n"));
+        assert!(prompt.ends_with("Question: And how often?\n"));
+
+        let prompt = build_prompt("Anything?", "  ", &[]);
+        assert!(!prompt.contains("Conversation so far:"));
+        assert!(prompt.contains("(No matching notes were found.)"));
+    }
+
+    #[test]
+    fn test_build_prompt_keeps_recent_history() {
+        let history: Vec<WorkspaceTurn> = (0..10)
+            .map(|i| turn(&format!("q{}", i), &"a".repeat(HISTORY_ANSWER_CHARS + 10)))
+            .collect();
+        let prompt = build_prompt("next", "", &history);
+        assert!(!prompt.contains("Question: q3\n"));
+        assert!(prompt.contains("Question: q4\n"));
+        assert!(prompt.contains(&format!("{}…", "a".repeat(HISTORY_ANSWER_CHARS))));
+    }
+
+    #[test]
+    fn test_retrieval_query_adds_previous_question() {
+        assert_eq!(retrieval_query("Budget?", &[]), "Budget?");
+        assert_eq!(
+            retrieval_query("And in March?", &[turn("Rainfall in February?", "")]),
+            "Rainfall in February?\nAnd in March?"
+        );
+    }
+
+    #[test]
+    fn test_sessions_are_per_workspace() {
+        record_turn("test-session", "/ws-a", turn("q1", "a1"));
+        record_turn("test-session", "/ws-a", turn("q2", "a2"));
+        assert_eq!(history("test-session", "/ws-a").len(), 2);
+        assert!(history("test-session", "/ws-b").is_empty());
+
+        // Asking in another workspace starts the session over
+        record_turn("test-session", "/ws-b", turn("q3", "a3"));
+        assert_eq!(
+            get_workspace_session("test-session".into()),
+            vec![turn("q3", "a3")]
+        );
+
+        clear_workspace_session("test-session".into());
+        assert!(get_workspace_session("test-session".into()).is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 //! - Bootstrap file writing for MCP sidecar discovery
 //! - Migration from legacy ~/.vmark/ to standard app data directory
 //! - Atomic file operations to prevent race conditions
+//! - Safe replacement of user documents
 
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
@@ -109,9 +110,8 @@ fn write_bootstrap_file_impl(legacy_dir: &Path, app_data: &Path) -> Result<(), S
     // Atomic write: temp file -> sync -> rename
     atomic_write_file(&bootstrap_path, app_data_str.as_bytes())?;
 
-    #[cfg(debug_assertions)]
-    eprintln!(
-        "[App Paths] Bootstrap file written: {:?} -> {:?}",
+    tracing::debug!(
+        "Bootstrap file written: {:?} -> {:?}",
         bootstrap_path, app_data
     );
 
@@ -158,18 +158,17 @@ fn migrate_legacy_files_impl(legacy_dir: &Path, app_data: &Path) -> Result<(), S
             // Write marker to indicate successful migration
             if let Err(e) = atomic_write_file(&marker_path, b"") {
                 // Log but don't fail - migration itself succeeded
-                eprintln!(
-                    "[App Paths] Warning: Failed to write migration marker {:?}: {}",
+                tracing::warn!(
+                    "Failed to write migration marker {:?}: {}",
                     marker_path, e
                 );
             }
 
-            #[cfg(debug_assertions)]
-            eprintln!("[App Paths] Migration completed successfully");
+            tracing::debug!("Migration completed successfully");
         }
         Err(e) => {
             // Migration failed - don't write marker so we can retry
-            eprintln!("[App Paths] Migration failed, will retry on next launch: {}", e);
+            tracing::warn!("Migration failed, will retry on next launch: {}", e);
         }
     }
 
@@ -202,8 +201,7 @@ fn perform_migration(legacy_dir: &Path, app_data: &Path) -> Result<(), String> {
 
     // Only migrate if source exists
     if !legacy_settings.exists() {
-        #[cfg(debug_assertions)]
-        eprintln!("[App Paths] No legacy settings to migrate");
+        tracing::debug!("No legacy settings to migrate");
         return Ok(());
     }
 
@@ -233,9 +231,8 @@ fn perform_migration(legacy_dir: &Path, app_data: &Path) -> Result<(), String> {
                 format!("Failed to sync settings file {:?}: {}", new_settings, e)
             })?;
 
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "[App Paths] Migrated {} from {:?} to {:?}",
+            tracing::debug!(
+                "Migrated {} from {:?} to {:?}",
                 MCP_SETTINGS_FILE, legacy_settings, new_settings
             );
 
@@ -243,9 +240,8 @@ fn perform_migration(legacy_dir: &Path, app_data: &Path) -> Result<(), String> {
         }
         Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
             // Destination already exists - another instance migrated or user has settings
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "[App Paths] Settings already exist at {:?}, skipping migration",
+            tracing::debug!(
+                "Settings already exist at {:?}, skipping migration",
                 new_settings
             );
             Ok(())
@@ -322,6 +318,53 @@ pub fn atomic_write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
     Ok(())
 }
 
+/// Replace a user document atomically without disturbing the file around it.
+///
+/// Unlike `atomic_write_file` (for app-owned files), this writes through a
+/// symlink to its target instead of replacing the link, keeps the existing
+/// file's permissions, uses a uniquely named temp file so concurrent saves
+/// can't share one, and replaces the target in a single step on every
+/// platform (no remove-then-rename window on Windows).
+pub fn write_document_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let target = match fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => fs::canonicalize(path)
+            .map_err(|e| format!("Failed to resolve link {:?}: {}", path, e))?,
+        _ => path.to_path_buf(),
+    };
+    let parent = target
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .ok_or_else(|| format!("Cannot determine parent directory of {:?}", target))?;
+    let name = target.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+
+    let mut temp = tempfile::Builder::new()
+        .prefix(&format!(".{name}."))
+        .suffix(".tmp")
+        .tempfile_in(parent)
+        .map_err(|e| format!("Failed to create temp file in {:?}: {}", parent, e))?;
+    temp.write_all(contents)
+        .map_err(|e| format!("Failed to write temp file {:?}: {}", temp.path(), e))?;
+    if let Ok(meta) = fs::metadata(&target) {
+        fs::set_permissions(temp.path(), meta.permissions())
+            .map_err(|e| format!("Failed to copy permissions of {:?}: {}", target, e))?;
+    }
+    temp.as_file()
+        .sync_all()
+        .map_err(|e| format!("Failed to sync temp file {:?}: {}", temp.path(), e))?;
+
+    // Rename over the target (MoveFileEx with replace on Windows); the temp
+    // file is removed if this fails
+    temp.persist(&target)
+        .map_err(|e| format!("Failed to replace {:?}: {}", target, e.error))?;
+
+    // Flush the directory entry so the new file survives a crash
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
 /// Remove a file, returning Ok for NotFound (idempotent delete).
 /// Returns error for other failures (permission denied, etc.)
 #[allow(dead_code)]
@@ -670,4 +713,34 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn test_write_document_file_replaces_content_and_leaves_no_temp() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        write_document_file(&path, b"one").unwrap();
+        write_document_file(&path, b"two").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_document_file_keeps_symlink_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("real.md");
+        fs::write(&target, "old").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600)).unwrap();
+        let link = dir.path().join("link.md");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_document_file(&link, b"new").unwrap();
+
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o777, 0o600);
+    }
 }
@@ -0,0 +1,414 @@
+//! Automation
+//!
+//! `vmark://` actions beyond opening a file (see `deep_link`), so
+//! Shortcuts, the share sheet and AppleScript
+//! (`open location "vmark://append?text=..."`) can drive VMark:
+//!
+//! - `vmark://new?text=...&title=...&folder=...` creates a note and opens it
+//! - `vmark://append?text=...&path=...&open=true` appends to a note (the
+//!   quick capture inbox when `path` is omitted)
+//!
+//! Paths must be absolute and name markdown files. Encrypted notes are never
+//! written. A link that would write outside the inbox's folder and the open
+//! workspaces asks first. The same actions are available to the frontend as
+//! commands, which refuse such paths.
+
+use chrono::{DateTime, Local};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Url};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::deep_link::{link_action, link_param, note_path};
+use crate::{quick_capture, window_manager, window_registry};
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    New {
+        text: String,
+        title: Option<String>,
+        folder: Option<PathBuf>,
+    },
+    Append {
+        text: String,
+        path: Option<PathBuf>,
+        open: bool,
+    },
+}
+
+fn parse_url(url: &Url) -> Result<Action, String> {
+    let param = |key: &str| link_param(url, key);
+    match link_action(url).as_str() {
+        "new" => Ok(Action::New {
+            text: param("text").unwrap_or_default(),
+            title: param("title"),
+            folder: param("folder").map(PathBuf::from),
+        }),
+        "append" => Ok(Action::Append {
+            text: param("text").ok_or("Missing text")?,
+            path: param("path").as_deref().map(note_path).transpose()?,
+            open: param("open").is_some_and(|v| v == "true" || v == "1"),
+        }),
+        other => Err(format!("Unknown action: {other}")),
+    }
+}
+
+/// Carry out an action. Returns the file to open, if any.
+fn run(action: Action) -> Result<Option<PathBuf>, String> {
+    match action {
+        Action::New {
+            text,
+            title,
+            folder,
+        } => create_note(&text, title.as_deref(), folder.as_deref(), Local::now()).map(Some),
+        Action::Append { text, path, open } => {
+            let path = append_to_note(&text, path.as_deref(), Local::now())?;
+            Ok(open.then_some(path))
+        }
+    }
+}
+
+/// What the action would write to, described for the confirmation prompt,
+/// when that is outside the inbox's folder and the open workspaces.
+fn needs_confirmation(action: &Action) -> Option<String> {
+    let roots = allowed_roots();
+    match action {
+        Action::New {
+            folder: Some(folder),
+            ..
+        } if !is_within(folder, &roots) => {
+            Some(format!("create a note in {}", folder.display()))
+        }
+        Action::Append {
+            path: Some(path), ..
+        } if !is_within(path, &roots) => Some(format!("add text to {}", path.display())),
+        _ => None,
+    }
+}
+
+/// Carry out a `vmark://new` or `vmark://append` URL. Returns the file to
+/// open, if any. A link writing outside the inbox's folder and the open
+/// workspaces is carried out (and its file opened) only once the user
+/// allows it.
+pub fn handle_url(app: &AppHandle, url: &Url) -> Option<PathBuf> {
+    let action = match parse_url(url) {
+        Ok(action) => action,
+        Err(e) => {
+            tracing::warn!("[Automation] {}: {}", url.as_str(), e);
+            return None;
+        }
+    };
+    let Some(description) = needs_confirmation(&action) else {
+        return run(action).unwrap_or_else(|e| {
+            tracing::warn!("[Automation] {}: {}", url.as_str(), e);
+            None
+        });
+    };
+
+    let app_handle = app.clone();
+    let link = url.as_str().to_string();
+    app.dialog()
+        .message(format!(
+            "Another app wants to {description}, which is outside your open workspaces."
+        ))
+        .title("Allow Automation?")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Allow".to_string(),
+            "Don't Allow".to_string(),
+        ))
+        .show(move |allowed| {
+            if !allowed {
+                tracing::info!("[Automation] Not allowed: {}", link);
+                return;
+            }
+            match run(action) {
+                Ok(Some(path)) => open_file(&app_handle, &path),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("[Automation] {}: {}", link, e),
+            }
+        });
+    None
+}
+
+/// Open a file written after the user allowed it, focusing the window that
+/// already has it.
+fn open_file(app: &AppHandle, path: &Path) {
+    let path = path.to_string_lossy();
+    if window_registry::focus_existing_window_for_file(app, &path).is_some() {
+        return;
+    }
+    if let Err(e) = window_manager::create_document_window(app, Some(&path), None) {
+        tracing::warn!("[Automation] Failed to open {}: {}", path, e);
+    }
+}
+
+// ============================================================================
+// Allowed folders
+// ============================================================================
+
+/// Folders automation may write to without asking: the inbox's folder and
+/// the workspace of every open window.
+fn allowed_roots() -> Vec<PathBuf> {
+    quick_capture::inbox_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .into_iter()
+        .chain(window_registry::workspace_roots().into_iter().map(PathBuf::from))
+        .filter_map(|root| root.canonicalize().ok())
+        .collect()
+}
+
+/// `path` with its nearest existing ancestor resolved, so symlinks and `..`
+/// can't lead out of the folder it appears to be in.
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return Some(rest.iter().rev().fold(resolved, |acc, part| acc.join(part)));
+        }
+        rest.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+}
+
+fn is_within(path: &Path, roots: &[PathBuf]) -> bool {
+    resolve(path).is_some_and(|path| roots.iter().any(|root| path.starts_with(root)))
+}
+
+/// Refuse a path outside the allowed folders (for the commands, which can't
+/// ask).
+fn check_allowed(path: &Path) -> Result<(), String> {
+    if is_within(path, &allowed_roots()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Outside the inbox's folder and the open workspaces: {}",
+            path.display()
+        ))
+    }
+}
+
+// ============================================================================
+// Actions
+// ============================================================================
+
+fn first_heading(text: &str) -> Option<&str> {
+    text.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(str::trim)
+        .filter(|heading| !heading.is_empty())
+}
+
+/// A title with the characters that are invalid in file names removed.
+fn file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .filter(|c| {
+            !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') && !c.is_control()
+        })
+        .collect();
+    stem.trim().trim_matches('.').to_string()
+}
+
+/// Write a new note into `folder` (default: the inbox's folder). The file is
+/// named after `title`, else the first heading, else the time; a `title`
+/// also becomes the heading when the text has none.
+pub(crate) fn create_note(
+    text: &str,
+    title: Option<&str>,
+    folder: Option<&Path>,
+    now: DateTime<Local>,
+) -> Result<PathBuf, String> {
+    let folder = match folder {
+        Some(folder) if folder.is_absolute() => folder.to_path_buf(),
+        Some(folder) => return Err(format!("Not an absolute path: {}", folder.display())),
+        None => quick_capture::inbox_path()
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or("No folder for new notes")?,
+    };
+    let title = title.map(str::trim).filter(|t| !t.is_empty());
+    let name = title
+        .or_else(|| first_heading(text))
+        .map(file_stem)
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| format!("Note {}", now.format("%Y-%m-%d %H%M")));
+
+    let mut content = String::new();
+    if let Some(title) = title.filter(|_| first_heading(text).is_none()) {
+        content.push_str(&format!("# {title}\n\n"));
+    }
+    content.push_str(text.trim());
+    content.push('\n');
+
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+    let path = crate::file_ops::unique_path(&folder.join(format!("{name}.md")));
+    crate::app_paths::atomic_write_file(&path, content.as_bytes())?;
+    tracing::info!("[Automation] Created {}", path.display());
+    Ok(path)
+}
+
+/// Append `text` to `path` as a new paragraph, creating the file if needed.
+/// Without a path the text goes to the quick capture inbox, under a
+/// timestamped heading.
+fn append_to_note(
+    text: &str,
+    path: Option<&Path>,
+    now: DateTime<Local>,
+) -> Result<PathBuf, String> {
+    if text.trim().is_empty() {
+        return Err("Nothing to append".to_string());
+    }
+    let Some(path) = path else {
+        let inbox = quick_capture::inbox_path();
+        quick_capture::append_entry(&inbox, text, now)?;
+        return Ok(inbox);
+    };
+
+    if crate::encryption::is_encrypted_path(path) {
+        return Err("Encrypted notes can't be changed by automation".to_string());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let existing = fs::read(path).unwrap_or_default();
+    let separator = match existing.as_slice() {
+        [] => "",
+        [.., b'\n', b'\n'] => "",
+        [.., b'\n'] => "\n",
+        _ => "\n\n",
+    };
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    file.write_all(format!("{separator}{}\n", text.trim_end()).as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path.to_path_buf())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Create a note (see `vmark://new`). Returns its path.
+#[tauri::command]
+pub fn automation_create_note(
+    text: String,
+    title: Option<String>,
+    folder: Option<String>,
+) -> Result<String, String> {
+    let folder = folder.as_deref().map(Path::new);
+    if let Some(folder) = folder {
+        check_allowed(folder)?;
+    }
+    create_note(&text, title.as_deref(), folder, Local::now())
+    .map(|path| path.to_string_lossy().into_owned())
+}
+
+/// Append to a note, or the inbox when `path` is None (see
+/// `vmark://append`). Returns the note's path.
+#[tauri::command]
+pub fn automation_append_to_note(text: String, path: Option<String>) -> Result<String, String> {
+    let path = path.as_deref().map(note_path).transpose()?;
+    if let Some(path) = &path {
+        check_allowed(path)?;
+    }
+    append_to_note(&text, path.as_deref(), Local::now())
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap()
+    }
+
+    fn parse(url: &str) -> Result<Action, String> {
+        parse_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            parse("vmark:append?text=Buy%20milk&open=1"),
+            Ok(Action::Append {
+                text: "Buy milk".into(),
+                path: None,
+                open: true
+            })
+        );
+        assert_eq!(
+            parse("vmark://new?text=hi&title=Plan"),
+            Ok(Action::New {
+                text: "hi".into(),
+                title: Some("Plan".into()),
+                folder: None
+            })
+        );
+        assert!(parse("vmark://append?text=x&path=relative.md").is_err());
+        assert!(parse("vmark://append?text=x&path=/etc/passwd").is_err());
+        assert!(parse("vmark://append").is_err());
+        assert!(parse("vmark://delete?path=/a.md").is_err());
+    }
+
+    #[test]
+    fn test_create_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = create_note("Body", Some("Plan: Q2"), Some(dir.path()), at()).unwrap();
+        assert_eq!(path, dir.path().join("Plan Q2.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Plan: Q2\n\nBody\n");
+
+        let path = create_note("# Plan Q2\n\nMore", None, Some(dir.path()), at()).unwrap();
+        assert_eq!(path, dir.path().join("Plan Q2 (2).md"));
+
+        let path = create_note("", None, Some(dir.path()), at()).unwrap();
+        assert_eq!(path, dir.path().join("Note 2026-03-14 0905.md"));
+
+        assert!(create_note("x", None, Some(Path::new("relative")), at()).is_err());
+    }
+
+    #[test]
+    fn test_append_to_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("log.md");
+        append_to_note("first", Some(&note), at()).unwrap();
+        fs::write(&note, "first\nno newline").unwrap();
+        append_to_note("second\n\n", Some(&note), at()).unwrap();
+        append_to_note("third", Some(&note), at()).unwrap();
+        assert_eq!(
+            fs::read_to_string(&note).unwrap(),
+            "first\nno newline\n\nsecond\n\nthird\n"
+        );
+        assert!(append_to_note("  ", Some(&note), at()).is_err());
+        assert!(append_to_note("x", Some(&dir.path().join("a.md.enc")), at()).is_err());
+    }
+
+    #[test]
+    fn test_is_within() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        fs::create_dir(&workspace).unwrap();
+        let roots = vec![workspace.canonicalize().unwrap()];
+
+        assert!(is_within(&workspace, &roots));
+        assert!(is_within(&workspace.join("new/dir/note.md"), &roots));
+        assert!(!is_within(&dir.path().join("other.md"), &roots));
+        assert!(!is_within(&workspace.join("../other.md"), &roots));
+        assert!(!is_within(&workspace.join("new/../../other.md"), &roots));
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.path(), workspace.join("out")).unwrap();
+            assert!(!is_within(&workspace.join("out/note.md"), &roots));
+        }
+    }
+}
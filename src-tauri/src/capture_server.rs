@@ -0,0 +1,724 @@
+//! Capture Server
+//!
+//! An optional HTTP endpoint on 127.0.0.1 that browser extensions and
+//! scripts can POST clippings to, the way a share extension would:
+//!
+//! ```text
+//! POST /capture
+//! Authorization: Bearer <token>
+//! Content-Type: application/json
+//!
+//! {"text": "...", "url": "https://...", "title": "...", "target": "inbox" | "new"}
+//! ```
+//!
+//! A `text/plain` or `text/markdown` body is captured to the inbox as-is.
+//! Captures go to the quick capture inbox by default, or into a new note
+//! (see `automation`) with `"target": "new"`.
+//!
+//! The server is off by default. When enabled it binds a random port that is
+//! remembered, so extensions keep working across restarts, and requires the
+//! token shown in Settings. Requests are rate limited per minute.
+//!
+//! Settings are in `<app_data>/capture-server.json`.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::quick_capture;
+
+const CONFIG_FILE: &str = "capture-server.json";
+
+/// Emitted after every capture with a [`Captured`] payload
+const CAPTURED_EVENT: &str = "capture-server:captured";
+
+/// Largest accepted request head and body
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// A connection must deliver its whole request within this time
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The running accept loop
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+struct RunningServer {
+    port: u16,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaptureServerConfig {
+    pub enabled: bool,
+    /// Requests accepted per minute
+    pub max_per_minute: u32,
+    /// Port to bind, chosen at random the first time
+    pub port: Option<u16>,
+    /// Bearer token, generated the first time
+    pub token: Option<String>,
+}
+
+impl Default for CaptureServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_per_minute: 30,
+            port: None,
+            token: None,
+        }
+    }
+}
+
+/// What Settings shows: whether the server runs and how to reach it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureServerStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: Option<u16>,
+    pub token: Option<String>,
+    pub max_per_minute: u32,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+struct CaptureRequest {
+    text: Option<String>,
+    url: Option<String>,
+    title: Option<String>,
+    target: CaptureTarget,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+enum CaptureTarget {
+    #[default]
+    Inbox,
+    New,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Captured {
+    path: String,
+    target: CaptureTarget,
+}
+
+// ============================================================================
+// Config
+// ============================================================================
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))
+}
+
+fn load_config(path: &Path) -> CaptureServerConfig {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_config(path: &Path, config: &CaptureServerConfig) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(path, json.as_bytes())
+}
+
+fn new_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+fn running_port() -> Option<u16> {
+    SERVER.lock().ok()?.as_ref().map(|server| server.port)
+}
+
+fn status(config: &CaptureServerConfig) -> CaptureServerStatus {
+    let running = running_port();
+    CaptureServerStatus {
+        enabled: config.enabled,
+        running: running.is_some(),
+        port: running.or(config.port),
+        token: config.token.clone(),
+        max_per_minute: config.max_per_minute,
+    }
+}
+
+// ============================================================================
+// HTTP
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RequestHead {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            429 => "Too Many Requests",
+            _ => "Internal Server Error",
+        };
+        let body = if self.status == 204 {
+            String::new()
+        } else {
+            self.body.to_string()
+        };
+        format!(
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Authorization, Content-Type\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            body.len(),
+            body
+        )
+        .into_bytes()
+    }
+}
+
+pub(crate) fn parse_head(head: &str) -> Option<RequestHead> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some(RequestHead {
+        method,
+        path,
+        headers,
+    })
+}
+
+/// Compare without exiting early, so response timing doesn't leak how much
+/// of a guessed token was right.
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Sliding one-minute window of accepted requests
+#[derive(Debug, Default)]
+struct RateLimiter {
+    accepted: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    fn allow(&mut self, limit: u32, now: Instant) -> bool {
+        while self
+            .accepted
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            self.accepted.pop_front();
+        }
+        if self.accepted.len() >= limit as usize {
+            return false;
+        }
+        self.accepted.push_back(now);
+        true
+    }
+}
+
+/// Markdown for a capture: a link for `url` (titled when given) followed by
+/// the text.
+fn capture_markdown(request: &CaptureRequest) -> Option<String> {
+    let title = request
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    let link = request
+        .url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .map(|url| match title {
+            Some(title) => format!("[{}]({})", title.replace(['[', ']'], ""), url),
+            None => format!("<{url}>"),
+        });
+    let text = request
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    match (link, text) {
+        (Some(link), Some(text)) => Some(format!("{link}\n\n{text}")),
+        (Some(link), None) => Some(link),
+        (None, Some(text)) => Some(text.to_string()),
+        (None, None) => None,
+    }
+}
+
+fn parse_capture(head: &RequestHead, body: &[u8]) -> Result<CaptureRequest, String> {
+    let body = std::str::from_utf8(body).map_err(|_| "Body is not UTF-8".to_string())?;
+    let content_type = head.header("content-type").unwrap_or("text/plain");
+    if content_type.starts_with("application/json") {
+        serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {e}"))
+    } else {
+        Ok(CaptureRequest {
+            text: Some(body.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+fn save_capture(request: &CaptureRequest) -> Result<PathBuf, String> {
+    let markdown = capture_markdown(request).ok_or("Nothing to capture")?;
+    match request.target {
+        CaptureTarget::Inbox => {
+            let inbox = quick_capture::inbox_path();
+            quick_capture::append_entry(&inbox, &markdown, Local::now())?;
+            Ok(inbox)
+        }
+        CaptureTarget::New => {
+            crate::automation::create_note(&markdown, request.title.as_deref(), None, Local::now())
+        }
+    }
+}
+
+/// Check and answer one request. `save` stores the capture.
+fn respond(
+    head: &RequestHead,
+    body: &[u8],
+    token: &str,
+    allowed: impl FnOnce() -> bool,
+    save: impl FnOnce(&CaptureRequest) -> Result<PathBuf, String>,
+) -> Response {
+    if head.method == "OPTIONS" {
+        return Response {
+            status: 204,
+            body: serde_json::Value::Null,
+        };
+    }
+    if head.path.split('?').next() != Some("/capture") {
+        return Response::error(404, "Not found");
+    }
+    if head.method != "POST" {
+        return Response::error(405, "Use POST");
+    }
+    let given = head
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !token_matches(token, given.trim()) {
+        return Response::error(401, "Invalid token");
+    }
+    if !allowed() {
+        return Response::error(429, "Too many captures, try again in a minute");
+    }
+    let request = match parse_capture(head, body) {
+        Ok(request) if capture_markdown(&request).is_none() => {
+            return Response::error(400, "Nothing to capture")
+        }
+        Ok(request) => request,
+        Err(e) => return Response::error(400, &e),
+    };
+    match save(&request) {
+        Ok(path) => Response {
+            status: 200,
+            body: serde_json::json!({ "path": path.to_string_lossy() }),
+        },
+        Err(e) => Response::error(500, &e),
+    }
+}
+
+/// Read a request head and its body. Errors are the response to send.
+async fn read_request(stream: &mut TcpStream) -> Result<(RequestHead, Vec<u8>), Response> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(Response::error(413, "Request head too large"));
+        }
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| Response::error(400, "Read failed"))?;
+        if n == 0 {
+            return Err(Response::error(400, "Incomplete request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buf[..head_end])
+        .ok()
+        .and_then(parse_head)
+        .ok_or_else(|| Response::error(400, "Malformed request"))?;
+    let length: usize = head
+        .header("content-length")
+        .map(|v| {
+            v.parse()
+                .map_err(|_| Response::error(400, "Bad Content-Length"))
+        })
+        .transpose()?
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Err(Response::error(413, "Capture too large"));
+    }
+
+    let mut body = buf.split_off(head_end + 4);
+    while body.len() < length {
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| Response::error(400, "Read failed"))?;
+        if n == 0 {
+            return Err(Response::error(400, "Incomplete body"));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(length);
+    Ok((head, body))
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    app: AppHandle,
+    token: Arc<String>,
+    limiter: Arc<Mutex<RateLimiter>>,
+    max_per_minute: u32,
+) {
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Err(_) => Response::error(400, "Request timed out"),
+        Ok(Err(response)) => response,
+        Ok(Ok((head, body))) => {
+            let allowed = || {
+                limiter
+                    .lock()
+                    .map(|mut l| l.allow(max_per_minute, Instant::now()))
+                    .unwrap_or(false)
+            };
+            let save = |request: &CaptureRequest| {
+                let path = save_capture(request)?;
+                let _ = app.emit(
+                    CAPTURED_EVENT,
+                    Captured {
+                        path: path.to_string_lossy().into_owned(),
+                        target: request.target,
+                    },
+                );
+                Ok(path)
+            };
+            respond(&head, &body, &token, allowed, save)
+        }
+    };
+    let _ = stream.write_all(&response.to_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+fn stop() {
+    if let Some(server) = SERVER.lock().ok().and_then(|mut guard| guard.take()) {
+        server.handle.abort();
+        tracing::info!("[CaptureServer] Stopped");
+    }
+}
+
+/// Bind the remembered port (or a random one) and start accepting
+/// captures. Returns the config with the chosen port and token filled in.
+async fn start(
+    app: &AppHandle,
+    mut config: CaptureServerConfig,
+) -> Result<CaptureServerConfig, String> {
+    stop();
+    let listener = match config.port {
+        Some(port) => match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => listener,
+            Err(_) => TcpListener::bind(("127.0.0.1", 0))
+                .await
+                .map_err(|e| format!("Failed to start capture server: {e}"))?,
+        },
+        None => TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|e| format!("Failed to start capture server: {e}"))?,
+    };
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    config.port = Some(port);
+    let token = Arc::new(config.token.get_or_insert_with(new_token).clone());
+    let limiter = Arc::new(Mutex::new(RateLimiter::default()));
+    let max_per_minute = config.max_per_minute;
+
+    let app_handle = app.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(handle_connection(
+                        stream,
+                        app_handle.clone(),
+                        token.clone(),
+                        limiter.clone(),
+                        max_per_minute,
+                    ));
+                }
+                Err(e) => tracing::debug!("[CaptureServer] Accept error: {}", e),
+            }
+        }
+    });
+    if let Ok(mut guard) = SERVER.lock() {
+        *guard = Some(RunningServer { port, handle });
+    }
+    tracing::info!("[CaptureServer] Listening on 127.0.0.1:{}", port);
+    Ok(config)
+}
+
+/// Apply `config`: start or stop the server and save the result.
+async fn apply(
+    app: &AppHandle,
+    config: CaptureServerConfig,
+) -> Result<CaptureServerStatus, String> {
+    let path = config_path(app)?;
+    let config = if config.enabled {
+        start(app, config).await?
+    } else {
+        stop();
+        config
+    };
+    save_config(&path, &config)?;
+    Ok(status(&config))
+}
+
+/// Start the server if it was left enabled.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let config = load_config(&config_path(app)?);
+    if config.enabled {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = apply(&app, config).await {
+                tracing::warn!("[CaptureServer] {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_capture_server_status(app: AppHandle) -> Result<CaptureServerStatus, String> {
+    Ok(status(&load_config(&config_path(&app)?)))
+}
+
+/// Turn the server on or off and change its rate limit.
+#[tauri::command]
+pub async fn set_capture_server_enabled(
+    app: AppHandle,
+    enabled: bool,
+    max_per_minute: Option<u32>,
+) -> Result<CaptureServerStatus, String> {
+    let mut config = load_config(&config_path(&app)?);
+    config.enabled = enabled;
+    if let Some(limit) = max_per_minute {
+        config.max_per_minute = limit.max(1);
+    }
+    apply(&app, config).await
+}
+
+/// Replace the token, invalidating every extension set up with the old one.
+#[tauri::command]
+pub async fn regenerate_capture_server_token(
+    app: AppHandle,
+) -> Result<CaptureServerStatus, String> {
+    let mut config = load_config(&config_path(&app)?);
+    config.token = Some(new_token());
+    apply(&app, config).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "secret-token";
+
+    fn head(method: &str, path: &str, headers: &[(&str, &str)]) -> RequestHead {
+        RequestHead {
+            method: method.into(),
+            path: path.into(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn post(body: &str, content_type: &str) -> Response {
+        let head = head(
+            "POST",
+            "/capture",
+            &[
+                ("Authorization", "Bearer secret-token"),
+                ("Content-Type", content_type),
+            ],
+        );
+        respond(
+            &head,
+            body.as_bytes(),
+            TOKEN,
+            || true,
+            |_| Ok(PathBuf::from("/inbox.md")),
+        )
+    }
+
+    #[test]
+    fn test_parse_head() {
+        let parsed =
+            parse_head("POST /capture HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5").unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.path, "/capture");
+        assert_eq!(parsed.header("content-length"), Some("5"));
+    }
+
+    #[test]
+    fn test_authentication() {
+        assert_eq!(post("hello", "text/plain").status, 200);
+
+        let wrong = head(
+            "POST",
+            "/capture",
+            &[("Authorization", "Bearer secret-tokeN")],
+        );
+        let response = respond(&wrong, b"hi", TOKEN, || true, |_| unreachable!());
+        assert_eq!(response.status, 401);
+
+        let missing = head("POST", "/capture", &[]);
+        assert_eq!(
+            respond(&missing, b"hi", TOKEN, || true, |_| unreachable!()).status,
+            401
+        );
+        assert!(!token_matches(TOKEN, ""));
+    }
+
+    #[test]
+    fn test_routing_and_limits() {
+        let get = head("GET", "/capture", &[]);
+        assert_eq!(
+            respond(&get, b"", TOKEN, || true, |_| unreachable!()).status,
+            405
+        );
+        let other = head("POST", "/other", &[]);
+        assert_eq!(
+            respond(&other, b"", TOKEN, || true, |_| unreachable!()).status,
+            404
+        );
+        let preflight = head("OPTIONS", "/capture", &[]);
+        assert_eq!(
+            respond(&preflight, b"", TOKEN, || true, |_| unreachable!()).status,
+            204
+        );
+
+        let authed = head(
+            "POST",
+            "/capture",
+            &[("Authorization", "Bearer secret-token")],
+        );
+        let response = respond(&authed, b"hi", TOKEN, || false, |_| unreachable!());
+        assert_eq!(response.status, 429);
+
+        assert_eq!(post("{not json", "application/json").status, 400);
+        assert_eq!(post("{}", "application/json").status, 400);
+        assert_eq!(post(r#"{"text": "hi"}"#, "application/json").status, 200);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+        assert!(limiter.allow(2, start));
+        assert!(limiter.allow(2, start + Duration::from_secs(1)));
+        assert!(!limiter.allow(2, start + Duration::from_secs(2)));
+        assert!(limiter.allow(2, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_capture_markdown() {
+        let request: CaptureRequest = serde_json::from_str(
+            r#"{"url": "https://example.com", "title": "Example [site]", "text": " Quote ", "target": "new"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.target, CaptureTarget::New);
+        assert_eq!(
+            capture_markdown(&request).as_deref(),
+            Some("[Example site](https://example.com)\n\nQuote")
+        );
+        let bare = CaptureRequest {
+            url: Some("https://example.com".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            capture_markdown(&bare).as_deref(),
+            Some("<https://example.com>")
+        );
+        assert_eq!(capture_markdown(&CaptureRequest::default()), None);
+    }
+}
@@ -0,0 +1,1303 @@
+//! Citations
+//!
+//! Parses the workspace BibTeX file and formats pandoc-style citations
+//! (`[@key]`, `[see @a, p. 3; @b]`) and bibliographies for export.
+//!
+//! The bibliography is the first of `references.bib`, `bibliography.bib`,
+//! `library.bib` in the workspace root, or else any `*.bib` file there.
+//! Styles are built in (APA, Chicago author-date, IEEE, MLA) and selected by
+//! their CSL style name.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Preferred bibliography file names, checked in order
+const BIB_FILE_NAMES: &[&str] = &["references.bib", "bibliography.bib", "library.bib"];
+
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct Name {
+    family: String,
+    given: String,
+}
+
+#[derive(Debug, Clone)]
+struct BibEntry {
+    key: String,
+    entry_type: String,
+    authors: Vec<Name>,
+    /// Cleaned (LaTeX-free) field values, lowercase field names
+    fields: BTreeMap<String, String>,
+}
+
+/// Entry summary returned to the editor (citation picker)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationEntry {
+    pub key: String,
+    pub entry_type: String,
+    pub title: String,
+    pub authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CitationResult {
+    /// Markdown with citations replaced and a bibliography appended
+    pub content: String,
+    /// Cited keys that are not in the bibliography (left untouched)
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Apa,
+    Chicago,
+    Ieee,
+    Mla,
+}
+
+impl Style {
+    fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim().to_ascii_lowercase();
+        match name.trim_end_matches(".csl") {
+            "apa" => Ok(Style::Apa),
+            "chicago" | "chicago-author-date" => Ok(Style::Chicago),
+            "ieee" => Ok(Style::Ieee),
+            "mla" | "modern-language-association" => Ok(Style::Mla),
+            _ => Err(format!(
+                "Unsupported citation style: {} (supported: apa, chicago-author-date, ieee, mla)",
+                name
+            )),
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Style::Mla => "Works Cited",
+            _ => "References",
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Search the workspace bibliography by key, title, author, year or container.
+#[command]
+pub fn search_citations(
+    workspace_root: String,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<CitationEntry>, String> {
+    let entries = load_bibliography(Path::new(&workspace_root))?;
+    Ok(search(&entries, &query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)))
+}
+
+/// Format bibliography entries (markdown) for the given keys. Unknown keys are skipped.
+#[command]
+pub fn format_bibliography(
+    workspace_root: String,
+    keys: Vec<String>,
+    csl_style: String,
+) -> Result<Vec<String>, String> {
+    let style = Style::parse(&csl_style)?;
+    let entries = load_bibliography(Path::new(&workspace_root))?;
+    let by_key = index_by_key(&entries);
+    let cited: Vec<&BibEntry> = keys.iter().filter_map(|key| by_key.get(key.as_str()).copied()).collect();
+    Ok(bibliography(&cited, style))
+}
+
+/// Rewrite `[@key]` citations in markdown and append the bibliography (used by export).
+#[command]
+pub fn apply_citations(
+    workspace_root: String,
+    content: String,
+    csl_style: String,
+) -> Result<CitationResult, String> {
+    let style = Style::parse(&csl_style)?;
+    let entries = load_bibliography(Path::new(&workspace_root))?;
+    Ok(render_citations(&content, &entries, style))
+}
+
+// ============================================================================
+// Bibliography file
+// ============================================================================
+
+fn find_bib_file(root: &Path) -> Option<PathBuf> {
+    if let Some(path) = BIB_FILE_NAMES.iter().map(|name| root.join(name)).find(|p| p.is_file()) {
+        return Some(path);
+    }
+    let mut bibs: Vec<PathBuf> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("bib")))
+        .collect();
+    bibs.sort();
+    bibs.into_iter().next()
+}
+
+fn load_bibliography(root: &Path) -> Result<Vec<BibEntry>, String> {
+    let path = find_bib_file(root).ok_or("No .bib file found in workspace")?;
+    let source = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(parse_bibtex(&source))
+}
+
+fn index_by_key(entries: &[BibEntry]) -> HashMap<&str, &BibEntry> {
+    entries.iter().map(|entry| (entry.key.as_str(), entry)).collect()
+}
+
+// ============================================================================
+// BibTeX parsing
+// ============================================================================
+
+struct BibParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    macros: &'a mut HashMap<String, String>,
+}
+
+impl BibParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !"{}(),=#\"".contains(c))
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Content of a `{...}` group (the opening brace at pos), braces inside kept.
+    fn braced(&mut self) -> String {
+        let start = self.pos + 1;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return self.chars[start..self.pos - 1].iter().collect();
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        self.chars[start.min(self.chars.len())..].iter().collect()
+    }
+
+    fn quoted(&mut self) -> String {
+        self.pos += 1;
+        let start = self.pos;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' if depth == 0 => {
+                    let value = self.chars[start..self.pos].iter().collect();
+                    self.pos += 1;
+                    return value;
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        self.chars[start..].iter().collect()
+    }
+
+    /// A field value: parts joined with `#`.
+    fn value(&mut self) -> String {
+        let mut value = String::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some('{') => value.push_str(&self.braced()),
+                Some('"') => value.push_str(&self.quoted()),
+                Some(_) => {
+                    let word = self.ident();
+                    if word.is_empty() {
+                        break;
+                    }
+                    let lower = word.to_ascii_lowercase();
+                    match self.macros.get(&lower) {
+                        Some(expanded) => value.push_str(expanded),
+                        None => value.push_str(month_name(&lower).unwrap_or(&word)),
+                    }
+                }
+                None => break,
+            }
+            self.skip_ws();
+            if self.peek() == Some('#') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        value
+    }
+
+    /// Parse one entry after `@`. Returns None for @comment/@preamble/@string.
+    fn entry(&mut self) -> Option<BibEntry> {
+        let entry_type = self.ident().to_ascii_lowercase();
+        self.skip_ws();
+        let close = match self.peek() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => return None,
+        };
+
+        if entry_type == "comment" || entry_type == "preamble" {
+            if close == '}' {
+                self.braced();
+            }
+            return None;
+        }
+        self.pos += 1;
+
+        if entry_type == "string" {
+            self.skip_ws();
+            let name = self.ident().to_ascii_lowercase();
+            self.skip_ws();
+            if self.peek() == Some('=') {
+                self.pos += 1;
+                let value = self.value();
+                self.macros.insert(name, value);
+            }
+            self.skip_ws();
+            if self.peek() == Some(close) {
+                self.pos += 1;
+            }
+            return None;
+        }
+
+        self.skip_ws();
+        let key = self.ident();
+        let mut raw_fields: BTreeMap<String, String> = BTreeMap::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                None | Some('@') => break,
+                _ => {}
+            }
+            let name = self.ident().to_ascii_lowercase();
+            self.skip_ws();
+            if name.is_empty() || self.peek() != Some('=') {
+                // Malformed field: skip to the next comma
+                while self.peek().is_some_and(|c| c != ',' && c != close) {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            self.pos += 1;
+            let value = self.value();
+            raw_fields.insert(name, value);
+        }
+
+        if key.is_empty() {
+            return None;
+        }
+        let authors = raw_fields
+            .get("author")
+            .or_else(|| raw_fields.get("editor"))
+            .map(|raw| parse_names(raw))
+            .unwrap_or_default();
+        let mut fields: BTreeMap<String, String> = raw_fields
+            .into_iter()
+            .map(|(name, value)| (name, clean_latex(&value)))
+            .collect();
+        if !fields.contains_key("year") {
+            if let Some(year) = fields.get("date").and_then(|date| date.get(..4)).map(str::to_string) {
+                fields.insert("year".to_string(), year);
+            }
+        }
+
+        Some(BibEntry {
+            key,
+            entry_type,
+            authors,
+            fields,
+        })
+    }
+}
+
+fn month_name(abbrev: &str) -> Option<&'static str> {
+    Some(match abbrev {
+        "jan" => "January",
+        "feb" => "February",
+        "mar" => "March",
+        "apr" => "April",
+        "may" => "May",
+        "jun" => "June",
+        "jul" => "July",
+        "aug" => "August",
+        "sep" => "September",
+        "oct" => "October",
+        "nov" => "November",
+        "dec" => "December",
+        _ => return None,
+    })
+}
+
+fn parse_bibtex(source: &str) -> Vec<BibEntry> {
+    let mut macros = HashMap::new();
+    let mut parser = BibParser {
+        chars: source.chars().collect(),
+        pos: 0,
+        macros: &mut macros,
+    };
+    let mut entries = Vec::new();
+    while parser.pos < parser.chars.len() {
+        if parser.chars[parser.pos] == '@' {
+            parser.pos += 1;
+            if let Some(entry) = parser.entry() {
+                entries.push(entry);
+            }
+        } else {
+            parser.pos += 1;
+        }
+    }
+    entries
+}
+
+/// Split on `and` outside braces.
+fn split_names(raw: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for word in raw.split_whitespace() {
+        if depth == 0 && word.eq_ignore_ascii_case("and") {
+            names.push(std::mem::take(&mut current));
+            continue;
+        }
+        for c in word.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    names.push(current);
+    names.into_iter().filter(|n| !n.trim().is_empty()).collect()
+}
+
+fn parse_names(raw: &str) -> Vec<Name> {
+    split_names(raw)
+        .into_iter()
+        .map(|name| {
+            let name = name.trim();
+            // {World Health Organization}: a corporate name, kept whole
+            if name.starts_with('{') && name.ends_with('}') && !name[1..].contains('{') {
+                return Name {
+                    family: clean_latex(name),
+                    given: String::new(),
+                };
+            }
+            if let Some((family, given)) = name.split_once(',') {
+                return Name {
+                    family: clean_latex(family),
+                    given: clean_latex(given),
+                };
+            }
+            // "First von Last": lowercase particles belong to the family name
+            let words: Vec<&str> = name.split_whitespace().collect();
+            let mut split = words.len().saturating_sub(1);
+            while split > 0 && words[split - 1].chars().next().is_some_and(char::is_lowercase) {
+                split -= 1;
+            }
+            Name {
+                family: clean_latex(&words[split..].join(" ")),
+                given: clean_latex(&words[..split].join(" ")),
+            }
+        })
+        .collect()
+}
+
+fn accented(accent: char, letter: char) -> Option<char> {
+    let (plain, composed) = match accent {
+        '\'' => ("aeiouyAEIOUYcnszCNSZ", "áéíóúýÁÉÍÓÚÝćńśźĆŃŚŹ"),
+        '`' => ("aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+        '^' => ("aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+        '"' => ("aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+        '~' => ("anoANO", "ãñõÃÑÕ"),
+        'c' => ("csCS", "çşÇŞ"),
+        'v' => ("cszrneCSZRNE", "čšžřňěČŠŽŘŇĚ"),
+        _ => return None,
+    };
+    let index = plain.chars().position(|c| c == letter)?;
+    composed.chars().nth(index)
+}
+
+fn special_letter(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "ss" => "ß",
+        "o" => "ø",
+        "O" => "Ø",
+        "aa" => "å",
+        "AA" => "Å",
+        "ae" => "æ",
+        "AE" => "Æ",
+        "oe" => "œ",
+        "OE" => "Œ",
+        "l" => "ł",
+        "L" => "Ł",
+        "i" => "i",
+        "j" => "j",
+        _ => return None,
+    })
+}
+
+/// Turn a BibTeX value into plain text: accents, escapes, dashes, braces.
+fn clean_latex(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut out = String::with_capacity(value.len());
+    let mut i = 0;
+
+    // Letter an accent applies to: `o`, `{o}`, `\i` or `{\i}`
+    let accent_target = |i: &mut usize| -> Option<char> {
+        let braced = chars.get(*i) == Some(&'{');
+        if braced {
+            *i += 1;
+        }
+        let letter = if chars.get(*i) == Some(&'\\') {
+            *i += 2;
+            chars.get(*i - 1).copied()
+        } else {
+            *i += 1;
+            chars.get(*i - 1).copied()
+        };
+        if braced && chars.get(*i) == Some(&'}') {
+            *i += 1;
+        }
+        letter
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' => {
+                let Some(&next) = chars.get(i + 1) else {
+                    break;
+                };
+                if "'`^\"~".contains(next) {
+                    i += 2;
+                    if let Some(letter) = accent_target(&mut i) {
+                        out.push(accented(next, letter).unwrap_or(letter));
+                    }
+                } else if next.is_ascii_alphabetic() {
+                    let start = i + 1;
+                    i = start;
+                    while chars.get(i).is_some_and(char::is_ascii_alphabetic) {
+                        i += 1;
+                    }
+                    let command: String = chars[start..i].iter().collect();
+                    // TeX swallows one space after a control word
+                    if chars.get(i) == Some(&' ') {
+                        i += 1;
+                    }
+                    if command == "c" || command == "v" {
+                        let accent = command.chars().next().unwrap_or('c');
+                        if let Some(letter) = accent_target(&mut i) {
+                            out.push(accented(accent, letter).unwrap_or(letter));
+                        }
+                    } else if let Some(letter) = special_letter(&command) {
+                        out.push_str(letter);
+                    }
+                    // Formatting commands (\emph, \textbf, ...) keep their argument
+                } else {
+                    out.push(if next == '\\' { ' ' } else { next });
+                    i += 2;
+                }
+            }
+            '{' | '}' => i += 1,
+            '~' => {
+                out.push('\u{a0}');
+                i += 1;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                if chars.get(i + 2) == Some(&'-') {
+                    out.push('\u{2014}');
+                    i += 3;
+                } else {
+                    out.push('\u{2013}');
+                    i += 2;
+                }
+            }
+            c if c.is_whitespace() => {
+                if !out.ends_with(' ') && !out.is_empty() {
+                    out.push(' ');
+                }
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out.trim_end().to_string()
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+fn display_name(name: &Name) -> String {
+    if name.given.is_empty() {
+        name.family.clone()
+    } else {
+        format!("{} {}", name.given, name.family)
+    }
+}
+
+fn container(entry: &BibEntry) -> Option<&str> {
+    ["journal", "journaltitle", "booktitle"]
+        .iter()
+        .find_map(|field| entry.fields.get(*field))
+        .map(String::as_str)
+}
+
+fn to_citation_entry(entry: &BibEntry) -> CitationEntry {
+    CitationEntry {
+        key: entry.key.clone(),
+        entry_type: entry.entry_type.clone(),
+        title: entry.fields.get("title").cloned().unwrap_or_default(),
+        authors: entry.authors.iter().map(display_name).collect(),
+        year: entry.fields.get("year").cloned(),
+        container: container(entry).map(str::to_string),
+    }
+}
+
+fn search(entries: &[BibEntry], query: &str, limit: usize) -> Vec<CitationEntry> {
+    let query = query.trim().trim_start_matches('@').to_lowercase();
+    let terms: Vec<&str> = query.split_whitespace().collect();
+
+    let mut matches: Vec<CitationEntry> = entries
+        .iter()
+        .map(to_citation_entry)
+        .filter(|entry| {
+            let haystack = format!(
+                "{} {} {} {} {}",
+                entry.key,
+                entry.title,
+                entry.authors.join(" "),
+                entry.year.as_deref().unwrap_or(""),
+                entry.container.as_deref().unwrap_or("")
+            )
+            .to_lowercase();
+            terms.iter().all(|term| haystack.contains(term))
+        })
+        .collect();
+
+    // Keys starting with the query first, then alphabetical
+    matches.sort_by(|a, b| {
+        let a_prefix = !a.key.to_lowercase().starts_with(&query);
+        let b_prefix = !b.key.to_lowercase().starts_with(&query);
+        (a_prefix, &a.key).cmp(&(b_prefix, &b.key))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+fn initials(given: &str) -> String {
+    given
+        .split_whitespace()
+        .filter_map(|part| part.chars().next())
+        .map(|c| format!("{}.", c))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Join with commas and a final conjunction ("A, B, and C"; "A and B").
+fn join_list(items: &[String], conjunction: &str) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [a, b] => format!("{} {} {}", a, conjunction, b),
+        [rest @ .., last] => format!("{}, {} {}", rest.join(", "), conjunction, last),
+    }
+}
+
+/// Add a period unless the text already ends with sentence punctuation.
+fn sentence(text: &str) -> String {
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{}.", text)
+    }
+}
+
+fn year_or_nd(entry: &BibEntry) -> &str {
+    entry.fields.get("year").map_or("n.d.", String::as_str)
+}
+
+/// Author part of an in-text citation.
+fn short_authors(entry: &BibEntry, style: Style) -> String {
+    let families: Vec<&str> = entry.authors.iter().map(|n| n.family.as_str()).collect();
+    let et_al_from = if style == Style::Chicago { 4 } else { 3 };
+    match families.as_slice() {
+        [] => entry
+            .fields
+            .get("title")
+            .map_or_else(|| entry.key.clone(), |title| format!("“{}”", title)),
+        [one] => one.to_string(),
+        [a, b] => {
+            let and = if style == Style::Apa { "&" } else { "and" };
+            format!("{} {} {}", a, and, b)
+        }
+        list if list.len() >= et_al_from => format!("{} et al.", list[0]),
+        list => join_list(&list.iter().map(|s| s.to_string()).collect::<Vec<_>>(), "and"),
+    }
+}
+
+/// Author part of a bibliography entry.
+fn full_authors(entry: &BibEntry, style: Style) -> String {
+    let names = &entry.authors;
+    match style {
+        Style::Apa => {
+            let formatted: Vec<String> = names
+                .iter()
+                .map(|n| {
+                    if n.given.is_empty() {
+                        n.family.clone()
+                    } else {
+                        format!("{}, {}", n.family, initials(&n.given))
+                    }
+                })
+                .collect();
+            match formatted.as_slice() {
+                [] => String::new(),
+                [one] => one.clone(),
+                [rest @ .., last] => format!("{}, & {}", rest.join(", "), last),
+            }
+        }
+        Style::Chicago | Style::Mla => {
+            let mut formatted: Vec<String> = Vec::new();
+            for (i, n) in names.iter().enumerate() {
+                formatted.push(if i == 0 && !n.given.is_empty() {
+                    format!("{}, {}", n.family, n.given)
+                } else {
+                    display_name(n)
+                });
+            }
+            if style == Style::Mla && formatted.len() >= 3 {
+                return format!("{}, et al", formatted[0]);
+            }
+            match formatted.as_slice() {
+                [a, b] => format!("{}, and {}", a, b),
+                list => join_list(list, "and"),
+            }
+        }
+        Style::Ieee => {
+            let formatted: Vec<String> = names
+                .iter()
+                .map(|n| {
+                    if n.given.is_empty() {
+                        n.family.clone()
+                    } else {
+                        format!("{} {}", initials(&n.given), n.family)
+                    }
+                })
+                .collect();
+            join_list(&formatted, "and")
+        }
+    }
+}
+
+/// One bibliography entry as markdown. `number` is used by numeric styles.
+fn format_entry(entry: &BibEntry, style: Style, number: usize) -> String {
+    let field = |name: &str| entry.fields.get(name).map(String::as_str).filter(|v| !v.is_empty());
+    let title = field("title").unwrap_or(&entry.key);
+    let authors = full_authors(entry, style);
+    let year = year_or_nd(entry);
+    let journal = container(entry);
+    let pages = field("pages");
+    let volume = field("volume");
+    let issue = field("number").or_else(|| field("issue"));
+    let publisher = field("publisher").or_else(|| field("institution")).or_else(|| field("school"));
+    let link = field("doi")
+        .map(|doi| format!("https://doi.org/{}", doi.trim_start_matches("https://doi.org/")))
+        .or_else(|| field("url").map(str::to_string));
+
+    let mut out = String::new();
+    match style {
+        Style::Apa => {
+            if authors.is_empty() {
+                out.push_str(&sentence(title));
+                out.push_str(&format!(" ({}).", year));
+            } else {
+                out.push_str(&format!("{} ({}). ", authors, year));
+                if journal.is_some() {
+                    out.push_str(&sentence(title));
+                } else {
+                    out.push_str(&format!("*{}*", title));
+                    if !title.ends_with(['.', '?', '!']) {
+                        out.push('.');
+                    }
+                }
+            }
+            if let Some(journal) = journal {
+                out.push_str(&format!(" *{}*", journal));
+                if let Some(volume) = volume {
+                    out.push_str(&format!(", *{}*", volume));
+                    if let Some(issue) = issue {
+                        out.push_str(&format!("({})", issue));
+                    }
+                }
+                if let Some(pages) = pages {
+                    out.push_str(&format!(", {}", pages));
+                }
+                out.push('.');
+            } else if let Some(publisher) = publisher {
+                out.push_str(&format!(" {}.", publisher));
+            }
+        }
+        Style::Chicago => {
+            if !authors.is_empty() {
+                out.push_str(&sentence(&authors));
+                out.push(' ');
+            }
+            out.push_str(&format!("{}. ", year));
+            if let Some(journal) = journal {
+                out.push_str(&format!("“{}” *{}*", sentence(title), journal));
+                if let Some(volume) = volume {
+                    out.push_str(&format!(" {}", volume));
+                }
+                if let Some(issue) = issue {
+                    out.push_str(&format!(" ({})", issue));
+                }
+                if let Some(pages) = pages {
+                    out.push_str(&format!(": {}", pages));
+                }
+                out.push('.');
+            } else {
+                out.push_str(&format!("*{}*.", title.trim_end_matches('.')));
+                if let Some(publisher) = publisher {
+                    out.push_str(&format!(" {}.", publisher));
+                }
+            }
+        }
+        Style::Mla => {
+            if !authors.is_empty() {
+                out.push_str(&sentence(&authors));
+                out.push(' ');
+            }
+            if let Some(journal) = journal {
+                out.push_str(&format!("“{}” *{}*", sentence(title), journal));
+                if let Some(volume) = volume {
+                    out.push_str(&format!(", vol. {}", volume));
+                }
+                if let Some(issue) = issue {
+                    out.push_str(&format!(", no. {}", issue));
+                }
+                out.push_str(&format!(", {}", year));
+                if let Some(pages) = pages {
+                    out.push_str(&format!(", pp. {}", pages));
+                }
+                out.push('.');
+            } else {
+                out.push_str(&format!("*{}*.", title.trim_end_matches('.')));
+                match publisher {
+                    Some(publisher) => out.push_str(&format!(" {}, {}.", publisher, year)),
+                    None => out.push_str(&format!(" {}.", year)),
+                }
+            }
+        }
+        Style::Ieee => {
+            out.push_str(&format!("[{}] ", number));
+            if !authors.is_empty() {
+                out.push_str(&format!("{}, ", authors));
+            }
+            if let Some(journal) = journal {
+                out.push_str(&format!("“{},” *{}*", title, journal));
+                if let Some(volume) = volume {
+                    out.push_str(&format!(", vol. {}", volume));
+                }
+                if let Some(issue) = issue {
+                    out.push_str(&format!(", no. {}", issue));
+                }
+                if let Some(pages) = pages {
+                    out.push_str(&format!(", pp. {}", pages));
+                }
+                out.push_str(&format!(", {}.", year));
+            } else {
+                out.push_str(&format!("*{}*.", title.trim_end_matches('.')));
+                match publisher {
+                    Some(publisher) => out.push_str(&format!(" {}, {}.", publisher, year)),
+                    None => out.push_str(&format!(" {}.", year)),
+                }
+            }
+        }
+    }
+
+    if let Some(link) = link {
+        out.push_str(&format!(" <{}>", link));
+    }
+    out
+}
+
+/// Bibliography entries in style order: citation order for numeric styles,
+/// otherwise by first author (or title) and year.
+fn bibliography(cited: &[&BibEntry], style: Style) -> Vec<String> {
+    let mut ordered: Vec<&BibEntry> = cited.to_vec();
+    if style != Style::Ieee {
+        ordered.sort_by_cached_key(|entry| {
+            let first = entry
+                .authors
+                .first()
+                .map(|n| n.family.clone())
+                .or_else(|| entry.fields.get("title").cloned())
+                .unwrap_or_default()
+                .to_lowercase();
+            (first, year_or_nd(entry).to_string())
+        });
+    }
+    ordered
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| format_entry(entry, style, i + 1))
+        .collect()
+}
+
+// ============================================================================
+// Citation rewriting
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+struct CiteItem {
+    prefix: String,
+    key: String,
+    locator: String,
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || "_:.#$%&-+?<>~/".contains(c)
+}
+
+/// Parse the inside of `[...]` as a citation group; None if it is not one.
+fn parse_group(inner: &str) -> Option<Vec<CiteItem>> {
+    let mut items = Vec::new();
+    for part in inner.split(';') {
+        let at = part.char_indices().find(|&(i, c)| {
+            c == '@' && part[..i].chars().last().is_none_or(|p| p.is_whitespace() || p == '-')
+        })?;
+        let prefix = part[..at.0].trim().trim_end_matches('-').trim().to_string();
+        let rest = &part[at.0 + 1..];
+        let key_len = rest.find(|c: char| !is_key_char(c)).unwrap_or(rest.len());
+        // Trailing punctuation is not part of the key
+        let key = rest[..key_len].trim_end_matches(['.', ':', '?']);
+        if key.is_empty() {
+            return None;
+        }
+        let locator = rest[key.len()..].trim().trim_start_matches(',').trim().to_string();
+        items.push(CiteItem {
+            prefix,
+            key: key.to_string(),
+            locator,
+        });
+    }
+    (!items.is_empty()).then_some(items)
+}
+
+/// Drop "p."/"pp." from a locator (styles that print bare page numbers).
+fn bare_locator(locator: &str) -> &str {
+    locator
+        .strip_prefix("pp.")
+        .or_else(|| locator.strip_prefix("p."))
+        .map_or(locator, str::trim_start)
+}
+
+struct Citer<'a> {
+    by_key: HashMap<&'a str, &'a BibEntry>,
+    style: Style,
+    /// Cited keys in order of first citation
+    cited: Vec<&'a str>,
+    missing: Vec<String>,
+}
+
+impl<'a> Citer<'a> {
+    fn number(&mut self, key: &'a str) -> usize {
+        match self.cited.iter().position(|k| *k == key) {
+            Some(i) => i + 1,
+            None => {
+                self.cited.push(key);
+                self.cited.len()
+            }
+        }
+    }
+
+    fn cite(&mut self, items: &[CiteItem]) -> Option<String> {
+        let missing: Vec<&CiteItem> = items.iter().filter(|item| !self.by_key.contains_key(item.key.as_str())).collect();
+        if !missing.is_empty() {
+            for item in missing {
+                if !self.missing.contains(&item.key) {
+                    self.missing.push(item.key.clone());
+                }
+            }
+            return None;
+        }
+
+        let style = self.style;
+        let mut parts = Vec::new();
+        for item in items {
+            let (key, entry) = self.by_key.get_key_value(item.key.as_str()).map(|(k, e)| (*k, *e))?;
+            let number = self.number(key);
+            let mut part = String::new();
+            if !item.prefix.is_empty() {
+                part.push_str(&item.prefix);
+                part.push(' ');
+            }
+            match style {
+                Style::Ieee => {
+                    part.push_str(&format!("[{}", number));
+                    if !item.locator.is_empty() {
+                        part.push_str(&format!(", {}", item.locator));
+                    }
+                    part.push(']');
+                }
+                Style::Apa => {
+                    part.push_str(&format!("{}, {}", short_authors(entry, style), year_or_nd(entry)));
+                    if !item.locator.is_empty() {
+                        part.push_str(&format!(", {}", item.locator));
+                    }
+                }
+                Style::Chicago => {
+                    part.push_str(&format!("{} {}", short_authors(entry, style), year_or_nd(entry)));
+                    if !item.locator.is_empty() {
+                        part.push_str(&format!(", {}", bare_locator(&item.locator)));
+                    }
+                }
+                Style::Mla => {
+                    part.push_str(&short_authors(entry, style));
+                    if !item.locator.is_empty() {
+                        part.push_str(&format!(" {}", bare_locator(&item.locator)));
+                    }
+                }
+            }
+            parts.push(part);
+        }
+
+        Some(match style {
+            Style::Ieee => parts.join(", "),
+            _ => format!("({})", parts.join("; ")),
+        })
+    }
+
+    /// Rewrite citation groups in a run of text outside code.
+    fn rewrite_text(&mut self, text: &str, out: &mut String) {
+        let mut rest = text;
+        while let Some(open) = rest.find('[') {
+            let after = &rest[open + 1..];
+            let Some(close) = after.find([']', '[']).filter(|&i| after.as_bytes()[i] == b']') else {
+                out.push_str(&rest[..=open]);
+                rest = after;
+                continue;
+            };
+            let inner = &after[..close];
+            let following = after[close + 1..].chars().next();
+            let is_image = rest[..open].ends_with('!');
+            let is_link = matches!(following, Some('(' | '['));
+
+            match (is_image || is_link, parse_group(inner)) {
+                (false, Some(items)) => match self.cite(&items) {
+                    Some(citation) => {
+                        out.push_str(&rest[..open]);
+                        out.push_str(&citation);
+                    }
+                    None => out.push_str(&rest[..open + close + 2]),
+                },
+                _ => out.push_str(&rest[..open + close + 2]),
+            }
+            rest = &after[close + 1..];
+        }
+        out.push_str(rest);
+    }
+
+    /// Rewrite one line, leaving inline code spans alone.
+    fn rewrite_line(&mut self, line: &str, out: &mut String) {
+        let mut rest = line;
+        while let Some(tick) = rest.find('`') {
+            self.rewrite_text(&rest[..tick], out);
+            let run = rest[tick..].len() - rest[tick..].trim_start_matches('`').len();
+            let fence = &rest[tick..tick + run];
+            let code = &rest[tick + run..];
+            match code.find(fence) {
+                Some(end) => {
+                    out.push_str(&rest[tick..tick + run + end + run]);
+                    rest = &code[end + run..];
+                }
+                None => {
+                    out.push_str(&rest[tick..]);
+                    rest = "";
+                }
+            }
+        }
+        self.rewrite_text(rest, out);
+    }
+}
+
+fn render_citations(content: &str, entries: &[BibEntry], style: Style) -> CitationResult {
+    let mut citer = Citer {
+        by_key: index_by_key(entries),
+        style,
+        cited: Vec::new(),
+        missing: Vec::new(),
+    };
+
+    let mut out = String::with_capacity(content.len());
+    let mut fence: Option<String> = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker: String = trimmed.chars().take_while(|&c| c == '`' || c == '~').collect();
+        if let Some(open) = &fence {
+            if trimmed.starts_with(open.as_str()) && trimmed.trim().chars().all(|c| c == '`' || c == '~') {
+                fence = None;
+            }
+            out.push_str(line);
+            continue;
+        }
+        if marker.len() >= 3 && marker.chars().all(|c| c == marker.chars().next().unwrap_or('`')) {
+            fence = Some(marker);
+            out.push_str(line);
+            continue;
+        }
+        citer.rewrite_line(line, &mut out);
+    }
+
+    if !citer.cited.is_empty() {
+        let cited: Vec<&BibEntry> = citer.cited.iter().filter_map(|key| citer.by_key.get(key).copied()).collect();
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("\n## {}\n\n", style.heading()));
+        out.push_str(&bibliography(&cited, style).join("\n\n"));
+        out.push('\n');
+    }
+
+    CitationResult {
+        content: out,
+        missing: citer.missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BIB: &str = r#"
+@string{nat = "Nature"}
+@comment{ignored {nested} }
+@article{smith2020,
+  author  = {Smith, John and Alice Jones},
+  title   = {{Deep} Learning for {M}arkdown},
+  journal = nat,
+  year    = 2020,
+  volume  = {12},
+  number  = {3},
+  pages   = {45--67},
+  doi     = {10.1000/xyz}
+}
+@book{muller2019,
+  author    = {M{\"u}ller, Hans and Jos\'{e} Garc\'ia and van der Berg, Piet},
+  title     = {Writing Well},
+  publisher = {Acme Press},
+  date      = {2019-04-01},
+}
+@misc{who2021,
+  author = {{World Health Organization}},
+  title  = "Report " # "on " # {Health},
+  year   = {2021},
+  month  = jan,
+}
+"#;
+
+    fn entries() -> Vec<BibEntry> {
+        parse_bibtex(BIB)
+    }
+
+    #[test]
+    fn test_parse_bibtex_entries_and_fields() {
+        let entries = entries();
+        assert_eq!(entries.len(), 3);
+
+        let smith = &entries[0];
+        assert_eq!(smith.key, "smith2020");
+        assert_eq!(smith.entry_type, "article");
+        assert_eq!(smith.fields["title"], "Deep Learning for Markdown");
+        assert_eq!(smith.fields["journal"], "Nature");
+        assert_eq!(smith.fields["pages"], "45–67");
+        assert_eq!(
+            smith.authors,
+            vec![
+                Name { family: "Smith".into(), given: "John".into() },
+                Name { family: "Jones".into(), given: "Alice".into() },
+            ]
+        );
+
+        let muller = &entries[1];
+        assert_eq!(muller.authors[0].family, "Müller");
+        assert_eq!(muller.authors[1].given, "José");
+        assert_eq!(muller.authors[1].family, "García");
+        assert_eq!(muller.authors[2].family, "van der Berg");
+        assert_eq!(muller.fields["year"], "2019");
+
+        let who = &entries[2];
+        assert_eq!(who.authors[0].family, "World Health Organization");
+        assert_eq!(who.fields["title"], "Report on Health");
+        assert_eq!(who.fields["month"], "January");
+    }
+
+    #[test]
+    fn test_clean_latex() {
+        assert_eq!(clean_latex(r"\emph{Stra\ss e} --- {\c c}a \& b~c"), "Straße — ça & b\u{a0}c");
+        assert_eq!(clean_latex(r"\v{S}koda {\'\i}"), "Škoda í");
+    }
+
+    #[test]
+    fn test_search_matches_all_terms() {
+        let entries = entries();
+        let results = search(&entries, "smith markdown", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].authors, vec!["John Smith", "Alice Jones"]);
+        assert_eq!(results[0].container.as_deref(), Some("Nature"));
+
+        assert_eq!(search(&entries, "@mu", 10)[0].key, "muller2019");
+        assert_eq!(search(&entries, "", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_group() {
+        assert_eq!(
+            parse_group("see @smith2020, p. 3; -@muller2019").unwrap(),
+            vec![
+                CiteItem { prefix: "see".into(), key: "smith2020".into(), locator: "p. 3".into() },
+                CiteItem { prefix: String::new(), key: "muller2019".into(), locator: String::new() },
+            ]
+        );
+        assert!(parse_group("mail me at a@b.com").is_none());
+        assert!(parse_group("plain text").is_none());
+    }
+
+    #[test]
+    fn test_render_apa() {
+        let entries = entries();
+        let result = render_citations(
+            "As shown [@smith2020, p. 4], and [see @muller2019; @who2021].\n",
+            &entries,
+            Style::Apa,
+        );
+        assert!(result.missing.is_empty());
+        assert!(result.content.starts_with(
+            "As shown (Smith & Jones, 2020, p. 4), and (see Müller et al., 2019; World Health Organization, 2021).\n"
+        ));
+        assert!(result.content.contains("\n## References\n\n"));
+        // Alphabetical by first author
+        let refs: Vec<&str> = result.content.split("## References\n\n").nth(1).unwrap().split("\n\n").collect();
+        assert_eq!(
+            refs[0],
+            "Müller, H., García, J., & van der Berg, P. (2019). *Writing Well*. Acme Press."
+        );
+        assert_eq!(
+            refs[1],
+            "Smith, J., & Jones, A. (2020). Deep Learning for Markdown. *Nature*, *12*(3), 45–67. <https://doi.org/10.1000/xyz>"
+        );
+    }
+
+    #[test]
+    fn test_render_ieee_numbers_by_first_citation() {
+        let entries = entries();
+        let result = render_citations("A [@who2021]. B [@smith2020, p. 2]. C [@who2021].", &entries, Style::Ieee);
+        assert!(result.content.starts_with("A [1]. B [2, p. 2]. C [1].\n"));
+        assert!(result.content.contains("[1] World Health Organization, *Report on Health*. 2021."));
+        assert!(result.content.contains(
+            "[2] J. Smith and A. Jones, “Deep Learning for Markdown,” *Nature*, vol. 12, no. 3, pp. 45–67, 2020."
+        ));
+    }
+
+    #[test]
+    fn test_render_chicago_and_mla_in_text() {
+        let entries = entries();
+        let chicago = render_citations("[@smith2020, pp. 4-5]", &entries, Style::Chicago);
+        assert!(chicago.content.starts_with("(Smith and Jones 2020, 4-5)"));
+        assert!(chicago.content.contains(
+            "Smith, John, and Alice Jones. 2020. “Deep Learning for Markdown.” *Nature* 12 (3): 45–67."
+        ));
+
+        let mla = render_citations("[@muller2019, p. 7]", &entries, Style::Mla);
+        assert!(mla.content.starts_with("(Müller et al. 7)"));
+        assert!(mla.content.contains("## Works Cited"));
+        assert!(mla.content.contains("Müller, Hans, et al. *Writing Well*. Acme Press, 2019."));
+    }
+
+    #[test]
+    fn test_render_skips_code_links_and_missing_keys() {
+        let entries = entries();
+        let content = "```\n[@smith2020]\n```\n`[@smith2020]` [@nope] [@smith2020](url) ![@smith2020](img.png)\n";
+        let result = render_citations(content, &entries, Style::Apa);
+        assert_eq!(result.content, content);
+        assert_eq!(result.missing, vec!["nope"]);
+    }
+
+    #[test]
+    fn test_style_names() {
+        assert_eq!(Style::parse("APA").unwrap(), Style::Apa);
+        assert_eq!(Style::parse("chicago-author-date.csl").unwrap(), Style::Chicago);
+        assert!(Style::parse("vancouver").is_err());
+    }
+
+    #[test]
+    fn test_find_bib_file_prefers_known_names() {
+        let temp = TempDir::new().unwrap();
+        assert!(find_bib_file(temp.path()).is_none());
+        fs::write(temp.path().join("a.bib"), "").unwrap();
+        assert_eq!(find_bib_file(temp.path()).unwrap(), temp.path().join("a.bib"));
+        fs::write(temp.path().join("references.bib"), "").unwrap();
+        assert_eq!(find_bib_file(temp.path()).unwrap(), temp.path().join("references.bib"));
+    }
+}
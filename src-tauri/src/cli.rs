@@ -0,0 +1,459 @@
+//! Command-line entry point: `vmark [options] [file | folder ...]`
+//!
+//! Options:
+//! - `-n`, `--new-window` — open files in a new window instead of the main one
+//! - `-w`, `--wait` — return once the opened files are closed (e.g. `git config core.editor "vmark --wait"`)
+//! - `--diff <a> <b>` — open two files side by side for comparison
+//! - `--` — treat every following argument as a path
+//!
+//! `vmark://` links are handed over as an argument on Windows and Linux and
+//! are carried out like macOS URL events (see `deep_link`).
+//!
+//! `--wait` runs the app as a child process and waits on a marker file the
+//! app deletes when the files are closed, so the caller can carry on while
+//! the app and its other windows stay open.
+//!
+//! Unknown options are ignored, since platforms inject their own
+//! (e.g. macOS `-psn_*` process serial numbers).
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Url};
+
+use crate::{deep_link, quit, window_manager, window_registry, PENDING_FILE_OPENS};
+
+/// Parsed command line
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    pub files: Vec<String>,
+    pub folders: Vec<String>,
+    pub new_window: bool,
+    pub wait: bool,
+    pub diff: Option<(String, String)>,
+    /// `vmark://` links
+    pub urls: Vec<String>,
+    /// Paths that don't exist (reported, then skipped)
+    pub missing: Vec<String>,
+    /// Marker file of the `--wait` launcher that started this process
+    pub wait_marker: Option<PathBuf>,
+}
+
+/// Two files opened with `--diff`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiffRequest {
+    pub left: String,
+    pub right: String,
+}
+
+/// Files a `--wait` invocation is waiting on
+#[derive(Debug, Default)]
+struct WaitState {
+    files: Vec<PathBuf>,
+    /// Set once any of the files has been seen open in a window
+    seen_open: bool,
+    /// Deleted to release the launcher; without one, the app quits instead
+    marker: Option<PathBuf>,
+}
+
+static WAIT_STATE: Mutex<Option<WaitState>> = Mutex::new(None);
+
+/// `--diff` pairs waiting for their window's compare view, keyed by window label
+static PENDING_DIFFS: Mutex<Option<HashMap<String, DiffRequest>>> = Mutex::new(None);
+
+/// Resolve a path argument against the working directory.
+fn resolve(arg: &str, cwd: &Path) -> String {
+    let path = Path::new(arg);
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    absolute.to_string_lossy().into_owned()
+}
+
+/// Parse arguments (without the program name).
+pub fn parse_args<I>(args: I, cwd: &Path) -> Result<CliArgs, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut parsed = CliArgs::default();
+    let mut args = args.into_iter();
+    let mut only_paths = false;
+
+    while let Some(arg) = args.next() {
+        if !only_paths && arg.starts_with('-') && arg.len() > 1 {
+            match arg.as_str() {
+                "--" => only_paths = true,
+                "-n" | "--new-window" => parsed.new_window = true,
+                "-w" | "--wait" => parsed.wait = true,
+                WAIT_MARKER_ARG => {
+                    let marker = args.next().ok_or("--wait-marker requires a path")?;
+                    parsed.wait_marker = Some(PathBuf::from(marker));
+                }
+                "--diff" => {
+                    let (Some(left), Some(right)) = (args.next(), args.next()) else {
+                        return Err("--diff requires two files".to_string());
+                    };
+                    parsed.diff = Some((resolve(&left, cwd), resolve(&right, cwd)));
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if !only_paths && deep_link::is_link(&arg) {
+            parsed.urls.push(arg);
+            continue;
+        }
+
+        let path = resolve(&arg, cwd);
+        let fs_path = Path::new(&path);
+        if fs_path.is_dir() {
+            parsed.folders.push(path);
+        } else if fs_path.is_file() {
+            parsed.files.push(path);
+        } else {
+            parsed.missing.push(path);
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Passes the launcher's marker file to the app it starts
+const WAIT_MARKER_ARG: &str = "--wait-marker";
+
+/// How often the launcher checks on the app
+const WAIT_POLL: Duration = Duration::from_millis(200);
+
+/// Whether the arguments ask for a `--wait` launcher: `-w`/`--wait` before
+/// any `--`, and not already started by one.
+fn wants_launcher(args: &[String]) -> bool {
+    let options: Vec<&String> = args.iter().take_while(|a| a.as_str() != "--").collect();
+    options.iter().any(|a| *a == "-w" || *a == "--wait")
+        && !options.iter().any(|a| *a == WAIT_MARKER_ARG)
+}
+
+/// `vmark --wait`: start the app as a child process and return once the
+/// waited-on files are closed (or the app exits), leaving the app running.
+/// Returns this process's exit code, or None when it should run the app.
+pub fn run_wait_launcher() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !wants_launcher(&args) {
+        return None;
+    }
+    let exe = std::env::current_exe().ok()?;
+    let marker = std::env::temp_dir().join(format!("vmark-wait-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&marker, b"").ok()?;
+
+    let mut child = match std::process::Command::new(exe)
+        .arg(WAIT_MARKER_ARG)
+        .arg(&marker)
+        .args(&args)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("[vmark] Failed to start: {}", e);
+            let _ = std::fs::remove_file(&marker);
+            return None;
+        }
+    };
+    loop {
+        if !marker.exists() {
+            return Some(0);
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            let _ = std::fs::remove_file(&marker);
+            return Some(status.code().unwrap_or(1));
+        }
+        std::thread::sleep(WAIT_POLL);
+    }
+}
+
+/// Files whose closing ends a `--wait` invocation.
+fn wait_targets(args: &CliArgs) -> Vec<String> {
+    let mut files = args.files.clone();
+    if let Some((left, right)) = &args.diff {
+        files.push(left.clone());
+        files.push(right.clone());
+    }
+    files
+}
+
+fn normalize(path: &str) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path))
+}
+
+/// Whether any waited-on file is among the open files.
+fn any_open(wait_files: &[PathBuf], open_files: &[String]) -> bool {
+    let open: HashSet<PathBuf> = open_files.iter().map(|f| normalize(f)).collect();
+    wait_files.iter().any(|f| open.contains(f))
+}
+
+/// Parse the process arguments and act on them. Called once during setup.
+pub fn handle_startup_args(app: &AppHandle) {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let args = match parse_args(std::env::args().skip(1), &cwd) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("[vmark] {}", e);
+            return;
+        }
+    };
+    for path in &args.missing {
+        eprintln!("[vmark] No such file or directory: {}", path);
+    }
+    apply(app, args);
+}
+
+fn apply(app: &AppHandle, mut args: CliArgs) {
+    if args.wait {
+        let files: Vec<PathBuf> = wait_targets(&args).iter().map(|f| normalize(f)).collect();
+        if !files.is_empty() {
+            if let Ok(mut guard) = WAIT_STATE.lock() {
+                *guard = Some(WaitState {
+                    files,
+                    seen_open: false,
+                    marker: args.wait_marker.clone(),
+                });
+            }
+        }
+    }
+
+    if !args.urls.is_empty() {
+        for url in &args.urls {
+            let Ok(url) = Url::parse(url) else {
+                tracing::warn!("[DeepLink] Invalid link: {}", url);
+                continue;
+            };
+            let Some(path) = deep_link::handle_url(app, &url) else {
+                continue;
+            };
+            let path = path.to_string_lossy().into_owned();
+            // Already open somewhere: focus that window instead
+            if window_registry::focus_existing_window_for_file(app, &path).is_none() {
+                args.files.push(path);
+            }
+        }
+        let _ = app.emit(deep_link::REVEAL_PENDING_EVENT, ());
+    }
+
+    for folder in &args.folders {
+        let _ = window_manager::create_document_window(app, None, Some(folder));
+    }
+
+    if let Some((left, right)) = &args.diff {
+        let files = vec![left.clone(), right.clone()];
+        if let Ok(label) = window_manager::create_document_window_with_files(app, &files, None) {
+            if let Ok(mut guard) = PENDING_DIFFS.lock() {
+                guard.get_or_insert_with(HashMap::new).insert(
+                    label,
+                    DiffRequest {
+                        left: left.clone(),
+                        right: right.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    if args.files.is_empty() {
+        return;
+    }
+
+    let groups = window_manager::group_paths_by_workspace(&args.files);
+    if args.new_window {
+        for (workspace_key, paths) in groups {
+            let ws = (!workspace_key.is_empty()).then_some(workspace_key.as_str());
+            let _ = window_manager::create_document_window_with_files(app, &paths, ws);
+        }
+    } else if let Ok(mut pending) = PENDING_FILE_OPENS.lock() {
+        // Main window drains the queue once its frontend is ready
+        for (workspace_key, paths) in groups {
+            let ws = (!workspace_key.is_empty()).then_some(workspace_key.as_str());
+            window_manager::queue_pending_file_opens(&mut pending, paths, ws);
+        }
+    }
+}
+
+/// Re-check `--wait` after windows changed which files they show.
+/// Releases the launcher once every waited-on file has been closed.
+pub fn check_wait(app: &AppHandle) {
+    let marker = {
+        let Ok(mut guard) = WAIT_STATE.lock() else {
+            return;
+        };
+        let Some(state) = guard.as_mut() else {
+            return;
+        };
+        if any_open(&state.files, &window_registry::all_open_files()) {
+            state.seen_open = true;
+            return;
+        }
+        if !state.seen_open {
+            return;
+        }
+        guard.take().and_then(|state| state.marker)
+    };
+
+    match marker {
+        Some(marker) => {
+            let _ = std::fs::remove_file(marker);
+        }
+        // No launcher (it failed to start us): the caller is waiting on this process
+        None => quit::start_quit(app),
+    }
+}
+
+/// Take the `--diff` pair for a window, if it was opened for one.
+#[tauri::command]
+pub fn take_pending_diff(window_label: String) -> Option<DiffRequest> {
+    let mut guard = PENDING_DIFFS.lock().ok()?;
+    guard.as_mut()?.remove(&window_label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "# A").unwrap();
+        fs::write(dir.path().join("b.md"), "# B").unwrap();
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn classifies_files_folders_and_missing() {
+        let dir = workspace();
+        let cwd = dir.path();
+        let parsed = parse_args(args(&["a.md", "notes", "nope.md"]), cwd).unwrap();
+
+        assert_eq!(parsed.files, vec![cwd.join("a.md").to_string_lossy()]);
+        assert_eq!(parsed.folders, vec![cwd.join("notes").to_string_lossy()]);
+        assert_eq!(parsed.missing, vec![cwd.join("nope.md").to_string_lossy()]);
+        assert!(!parsed.new_window && !parsed.wait);
+    }
+
+    #[test]
+    fn parses_flags_and_ignores_unknown_options() {
+        let dir = workspace();
+        let parsed = parse_args(
+            args(&["-psn_0_12345", "--new-window", "-w", "a.md"]),
+            dir.path(),
+        )
+        .unwrap();
+
+        assert!(parsed.new_window);
+        assert!(parsed.wait);
+        assert_eq!(parsed.files.len(), 1);
+        assert!(parsed.missing.is_empty());
+    }
+
+    #[test]
+    fn parses_diff_pair() {
+        let dir = workspace();
+        let cwd = dir.path();
+        let parsed = parse_args(args(&["--diff", "a.md", "b.md"]), cwd).unwrap();
+
+        assert_eq!(
+            parsed.diff,
+            Some((
+                cwd.join("a.md").to_string_lossy().into_owned(),
+                cwd.join("b.md").to_string_lossy().into_owned(),
+            ))
+        );
+        assert!(parsed.files.is_empty());
+        assert_eq!(wait_targets(&parsed).len(), 2);
+    }
+
+    #[test]
+    fn diff_requires_two_files() {
+        let dir = workspace();
+        assert!(parse_args(args(&["--diff", "a.md"]), dir.path()).is_err());
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let dir = workspace();
+        fs::write(dir.path().join("-odd.md"), "").unwrap();
+        let parsed = parse_args(args(&["--", "-odd.md"]), dir.path()).unwrap();
+        assert_eq!(parsed.files.len(), 1);
+    }
+
+    #[test]
+    fn absolute_paths_are_kept() {
+        let dir = workspace();
+        let abs = dir.path().join("b.md");
+        let parsed = parse_args(args(&[abs.to_str().unwrap()]), Path::new("/")).unwrap();
+        assert_eq!(parsed.files, vec![abs.to_string_lossy()]);
+    }
+
+    #[test]
+    fn collects_vmark_links() {
+        let dir = workspace();
+        let parsed = parse_args(
+            args(&["vmark://open?file=%2Fa.md&line=3", "a.md", "--", "vmark:x"]),
+            dir.path(),
+        )
+        .unwrap();
+        assert_eq!(parsed.urls, vec!["vmark://open?file=%2Fa.md&line=3"]);
+        assert_eq!(parsed.files.len(), 1);
+        assert_eq!(parsed.missing.len(), 1);
+    }
+
+    #[test]
+    fn launcher_only_for_wait_without_marker() {
+        assert!(wants_launcher(&args(&["--wait", "a.md"])));
+        assert!(wants_launcher(&args(&["-n", "-w", "a.md"])));
+        assert!(!wants_launcher(&args(&["a.md"])));
+        assert!(!wants_launcher(&args(&["--", "--wait"])));
+        assert!(!wants_launcher(&args(&["--wait-marker", "/tmp/m", "--wait", "a.md"])));
+
+        let dir = workspace();
+        let parsed = parse_args(args(&["--wait-marker", "/tmp/m", "--wait", "a.md"]), dir.path())
+            .unwrap();
+        assert_eq!(parsed.wait_marker, Some(PathBuf::from("/tmp/m")));
+        assert!(parsed.wait);
+        assert_eq!(parsed.files.len(), 1);
+    }
+
+    #[test]
+    fn wait_detects_open_files() {
+        let dir = workspace();
+        let a = dir.path().join("a.md").to_string_lossy().into_owned();
+        let b = dir.path().join("b.md").to_string_lossy().into_owned();
+        let wait_files = vec![normalize(&a)];
+
+        assert!(any_open(&wait_files, &[b.clone(), a]));
+        assert!(!any_open(&wait_files, &[b]));
+        assert!(!any_open(&wait_files, &[]));
+    }
+
+    #[test]
+    fn pending_diff_is_taken_once() {
+        PENDING_DIFFS
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(
+                "doc-diff".into(),
+                DiffRequest {
+                    left: "/a.md".into(),
+                    right: "/b.md".into(),
+                },
+            );
+        assert!(take_pending_diff("doc-diff".into()).is_some());
+        assert!(take_pending_diff("doc-diff".into()).is_none());
+    }
+}
@@ -0,0 +1,329 @@
+//! Clipboard History
+//!
+//! A ring of the last copies made inside VMark (newest first), so the editor
+//! can offer "Paste from History". Copies are recorded by the frontend; the
+//! ring lives in memory and is only written to the app data directory
+//! (`clipboard-history.json`) when persistence is turned on.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+
+use crate::app_paths;
+
+/// History file name in the app data directory
+pub const CLIPBOARD_HISTORY_FILE: &str = "clipboard-history.json";
+
+const DEFAULT_CAPACITY: usize = 20;
+const MAX_CAPACITY: usize = 100;
+
+/// Copies larger than this are not recorded (1 MB)
+const MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+/// One recorded copy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardEntry {
+    pub text: String,
+    /// Milliseconds since the Unix epoch
+    pub copied_at: i64,
+}
+
+/// User-facing options
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardHistoryOptions {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub persist: bool,
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_capacity() -> usize {
+    DEFAULT_CAPACITY
+}
+
+impl Default for ClipboardHistoryOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            persist: false,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
+
+/// On-disk format: options, plus entries when persistence is on
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct HistoryFile {
+    #[serde(default)]
+    options: ClipboardHistoryOptions,
+    #[serde(default)]
+    entries: Vec<ClipboardEntry>,
+}
+
+/// The ring itself, newest entry first
+#[derive(Debug, Default)]
+struct ClipboardRing {
+    options: ClipboardHistoryOptions,
+    entries: VecDeque<ClipboardEntry>,
+}
+
+impl ClipboardRing {
+    /// Record a copy. Returns false when nothing changed.
+    fn record(&mut self, text: &str, copied_at: i64) -> bool {
+        if !self.options.enabled || text.trim().is_empty() || text.len() > MAX_ENTRY_BYTES {
+            return false;
+        }
+        // Copying the same text again moves it to the front instead of duplicating it
+        self.entries.retain(|entry| entry.text != text);
+        self.entries.push_front(ClipboardEntry {
+            text: text.to_string(),
+            copied_at,
+        });
+        self.entries.truncate(self.options.capacity);
+        true
+    }
+
+    /// Text of the nth entry (0 = most recent), moved to the front.
+    fn take_nth(&mut self, index: usize) -> Option<String> {
+        let entry = self.entries.remove(index)?;
+        let text = entry.text.clone();
+        self.entries.push_front(entry);
+        Some(text)
+    }
+
+    fn set_options(&mut self, options: ClipboardHistoryOptions) {
+        if !options.enabled {
+            self.entries.clear();
+        }
+        self.entries.truncate(options.capacity);
+        self.options = options;
+    }
+
+    fn to_file(&self) -> HistoryFile {
+        HistoryFile {
+            options: self.options.clone(),
+            entries: if self.options.persist {
+                self.entries.iter().cloned().collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+}
+
+/// Loaded ring plus the file it persists to
+struct HistoryState {
+    file: PathBuf,
+    ring: ClipboardRing,
+}
+
+static HISTORY_STATE: OnceLock<Mutex<HistoryState>> = OnceLock::new();
+
+/// Load clipboard history options (and persisted entries) from app data.
+/// Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(CLIPBOARD_HISTORY_FILE);
+    let ring = load_ring(&file);
+    let _ = HISTORY_STATE.set(Mutex::new(HistoryState { file, ring }));
+    Ok(())
+}
+
+fn load_ring(file: &Path) -> ClipboardRing {
+    let saved: HistoryFile = fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let mut ring = ClipboardRing::default();
+    ring.set_options(normalize_options(saved.options));
+    if ring.options.persist {
+        ring.entries = saved.entries.into_iter().collect();
+        ring.entries.truncate(ring.options.capacity);
+    }
+    ring
+}
+
+fn save_ring(file: &Path, ring: &ClipboardRing) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(&ring.to_file())
+        .map_err(|e| format!("Failed to serialize clipboard history: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+fn normalize_options(mut options: ClipboardHistoryOptions) -> ClipboardHistoryOptions {
+    options.capacity = options.capacity.clamp(1, MAX_CAPACITY);
+    options
+}
+
+fn with_state<T>(f: impl FnOnce(&mut HistoryState) -> Result<T, String>) -> Result<T, String> {
+    let state = HISTORY_STATE
+        .get()
+        .ok_or("Clipboard history not initialized")?;
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    f(&mut guard)
+}
+
+/// Record a copy or cut made in the editor.
+#[tauri::command]
+pub fn record_clipboard_copy(text: String) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    with_state(|state| {
+        if state.ring.record(&text, now) && state.ring.options.persist {
+            save_ring(&state.file, &state.ring)?;
+        }
+        Ok(())
+    })
+}
+
+/// Recorded copies, newest first.
+#[tauri::command]
+pub fn list_clipboard_history() -> Result<Vec<ClipboardEntry>, String> {
+    with_state(|state| Ok(state.ring.entries.iter().cloned().collect()))
+}
+
+/// Text of the nth copy (0 = most recent) for pasting; it becomes the most recent entry.
+#[tauri::command]
+pub fn paste_clipboard_history(index: usize) -> Result<String, String> {
+    with_state(|state| {
+        let text = state
+            .ring
+            .take_nth(index)
+            .ok_or_else(|| format!("No clipboard history entry at {}", index))?;
+        if state.ring.options.persist {
+            save_ring(&state.file, &state.ring)?;
+        }
+        Ok(text)
+    })
+}
+
+#[tauri::command]
+pub fn clear_clipboard_history() -> Result<(), String> {
+    with_state(|state| {
+        state.ring.entries.clear();
+        save_ring(&state.file, &state.ring)
+    })
+}
+
+#[tauri::command]
+pub fn get_clipboard_history_options() -> Result<ClipboardHistoryOptions, String> {
+    with_state(|state| Ok(state.ring.options.clone()))
+}
+
+/// Update options. Turning persistence off removes saved entries from disk;
+/// disabling the history clears it.
+#[tauri::command]
+pub fn set_clipboard_history_options(
+    options: ClipboardHistoryOptions,
+) -> Result<ClipboardHistoryOptions, String> {
+    with_state(|state| {
+        state.ring.set_options(normalize_options(options));
+        save_ring(&state.file, &state.ring)?;
+        Ok(state.ring.options.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn texts(ring: &ClipboardRing) -> Vec<&str> {
+        ring.entries.iter().map(|e| e.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_record_keeps_newest_first_and_dedups() {
+        let mut ring = ClipboardRing::default();
+        assert!(ring.record("a", 1));
+        assert!(ring.record("b", 2));
+        assert!(ring.record("a", 3));
+        assert_eq!(texts(&ring), vec!["a", "b"]);
+        assert_eq!(ring.entries[0].copied_at, 3);
+    }
+
+    #[test]
+    fn test_record_ignores_blank_and_disabled() {
+        let mut ring = ClipboardRing::default();
+        assert!(!ring.record("  \n", 1));
+        ring.set_options(ClipboardHistoryOptions {
+            enabled: false,
+            ..Default::default()
+        });
+        assert!(!ring.record("a", 2));
+        assert!(ring.entries.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut ring = ClipboardRing::default();
+        ring.set_options(ClipboardHistoryOptions {
+            capacity: 2,
+            ..Default::default()
+        });
+        ring.record("a", 1);
+        ring.record("b", 2);
+        ring.record("c", 3);
+        assert_eq!(texts(&ring), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn test_take_nth_moves_entry_to_front() {
+        let mut ring = ClipboardRing::default();
+        ring.record("a", 1);
+        ring.record("b", 2);
+        ring.record("c", 3);
+        assert_eq!(ring.take_nth(2).as_deref(), Some("a"));
+        assert_eq!(texts(&ring), vec!["a", "c", "b"]);
+        assert_eq!(ring.take_nth(5), None);
+    }
+
+    #[test]
+    fn test_entries_only_persist_when_enabled() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join(CLIPBOARD_HISTORY_FILE);
+
+        let mut ring = ClipboardRing::default();
+        ring.record("secret", 1);
+        save_ring(&file, &ring).unwrap();
+        assert!(load_ring(&file).entries.is_empty());
+
+        ring.set_options(ClipboardHistoryOptions {
+            persist: true,
+            ..Default::default()
+        });
+        save_ring(&file, &ring).unwrap();
+        let loaded = load_ring(&file);
+        assert!(loaded.options.persist);
+        assert_eq!(texts(&loaded), vec!["secret"]);
+    }
+
+    #[test]
+    fn test_capacity_is_clamped() {
+        let options = normalize_options(ClipboardHistoryOptions {
+            capacity: 0,
+            ..Default::default()
+        });
+        assert_eq!(options.capacity, 1);
+        let options = normalize_options(ClipboardHistoryOptions {
+            capacity: 10_000,
+            ..Default::default()
+        });
+        assert_eq!(options.capacity, MAX_CAPACITY);
+    }
+}
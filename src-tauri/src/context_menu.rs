@@ -0,0 +1,304 @@
+//! Native context menus built from a JSON description.
+//!
+//! The frontend describes a right-click menu (labels, ids, checkmarks,
+//! submenus, separators) and `show_context_menu` pops it up as a native menu.
+//! Item ids are namespaced per request (`ctx:<request>:<id>`). The command
+//! resolves with the picked item id, or None once the menu is known to be
+//! gone: the frontend reports dismissal with `dismiss_context_menu`, a newer
+//! menu from the same window replaces it, or the window closes.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, LogicalPosition, Wry};
+use tokio::sync::oneshot;
+
+/// Prefix of context menu item ids
+const CONTEXT_ID_PREFIX: &str = "ctx:";
+
+/// Deepest submenu nesting accepted
+const MAX_DEPTH: usize = 4;
+
+/// One entry of a context menu description.
+///
+/// `{ "separator": true }` is a separator, an entry with `items` is a
+/// submenu, an entry with `checked` is a check item, anything else a plain item.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuItem {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub checked: Option<bool>,
+    pub accelerator: Option<String>,
+    #[serde(default)]
+    pub separator: bool,
+    pub items: Option<Vec<ContextMenuItem>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A context menu waiting for its answer
+struct OpenRequest {
+    label: String,
+    reply: oneshot::Sender<Option<String>>,
+}
+
+/// Open context menu requests, keyed by the frontend's request id.
+static OPEN_REQUESTS: Mutex<Option<HashMap<String, OpenRequest>>> = Mutex::new(None);
+
+fn open_requests() -> std::sync::MutexGuard<'static, Option<HashMap<String, OpenRequest>>> {
+    OPEN_REQUESTS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Answer an open request; does nothing if it was already answered.
+fn resolve_request(request_id: &str, choice: Option<String>) {
+    let request = open_requests().as_mut().and_then(|map| map.remove(request_id));
+    if let Some(request) = request {
+        let _ = request.reply.send(choice);
+    }
+}
+
+/// Dismiss every open menu of a window. Called when another menu replaces
+/// them and from the `WindowEvent::Destroyed` handler.
+pub fn clear_window_requests(label: &str) {
+    let dropped: Vec<OpenRequest> = match open_requests().as_mut() {
+        Some(map) => {
+            let ids: Vec<String> = map
+                .iter()
+                .filter(|(_, r)| r.label == label)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| map.remove(id)).collect()
+        }
+        None => Vec::new(),
+    };
+    for request in dropped {
+        let _ = request.reply.send(None);
+    }
+}
+
+fn context_item_id(request_id: &str, id: &str) -> String {
+    format!("{}{}:{}", CONTEXT_ID_PREFIX, request_id, id)
+}
+
+/// Split a menu event id into (request id, item id) if it came from a context menu.
+fn parse_context_item_id(menu_id: &str) -> Option<(&str, &str)> {
+    let rest = menu_id.strip_prefix(CONTEXT_ID_PREFIX)?;
+    let (request, id) = rest.split_once(':')?;
+    if request.is_empty() {
+        return None;
+    }
+    Some((request, id))
+}
+
+/// Check a description before building it: items need ids and labels,
+/// submenus need labels, and nesting is bounded.
+fn validate_items(items: &[ContextMenuItem], depth: usize) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("Context menu is nested too deeply".to_string());
+    }
+    for item in items {
+        if item.separator {
+            continue;
+        }
+        if item.label.is_empty() {
+            return Err("Context menu entry is missing a label".to_string());
+        }
+        match &item.items {
+            Some(children) => validate_items(children, depth + 1)?,
+            None if item.id.is_empty() => {
+                return Err(format!("Context menu item \"{}\" is missing an id", item.label));
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+fn build_item(
+    app: &AppHandle,
+    request_id: &str,
+    item: &ContextMenuItem,
+) -> tauri::Result<MenuItemKind<Wry>> {
+    if item.separator {
+        return Ok(MenuItemKind::Predefined(PredefinedMenuItem::separator(app)?));
+    }
+    if let Some(children) = &item.items {
+        let submenu = Submenu::new(app, &item.label, item.enabled)?;
+        for child in children {
+            submenu.append(&build_item(app, request_id, child)?)?;
+        }
+        return Ok(MenuItemKind::Submenu(submenu));
+    }
+
+    let id = context_item_id(request_id, &item.id);
+    let accelerator = item.accelerator.as_deref();
+    Ok(match item.checked {
+        Some(checked) => MenuItemKind::Check(CheckMenuItem::with_id(
+            app,
+            id,
+            &item.label,
+            item.enabled,
+            checked,
+            accelerator,
+        )?),
+        None => MenuItemKind::MenuItem(MenuItem::with_id(
+            app,
+            id,
+            &item.label,
+            item.enabled,
+            accelerator,
+        )?),
+    })
+}
+
+/// Answer the context menu that owns `menu_id` with the picked item.
+/// Returns false if `menu_id` isn't a context menu item.
+pub fn handle_context_menu_event(menu_id: &str) -> bool {
+    let Some((request_id, id)) = parse_context_item_id(menu_id) else {
+        return false;
+    };
+    resolve_request(request_id, Some(id.to_string()));
+    true
+}
+
+/// Show a native context menu at (`x`, `y`) in logical window coordinates,
+/// or at the cursor when no position is given. `request_id` is chosen by the
+/// caller so it can dismiss the menu while this command is pending.
+/// Resolves to the picked item id, or None if the menu was dismissed.
+#[tauri::command]
+pub async fn show_context_menu(
+    app: AppHandle,
+    window: tauri::Window,
+    request_id: String,
+    items: Vec<ContextMenuItem>,
+    x: Option<f64>,
+    y: Option<f64>,
+) -> Result<Option<String>, String> {
+    if request_id.is_empty() || request_id.contains(':') {
+        return Err(format!("Invalid context menu request id: {}", request_id));
+    }
+    validate_items(&items, 0)?;
+
+    let menu = Menu::new(&app).map_err(|e| e.to_string())?;
+    for item in &items {
+        let built = build_item(&app, &request_id, item).map_err(|e| e.to_string())?;
+        menu.append(&built).map_err(|e| e.to_string())?;
+    }
+
+    // Only the newest menu per window can still be answered
+    let label = window.label().to_string();
+    clear_window_requests(&label);
+    let (reply, answer) = oneshot::channel();
+    open_requests()
+        .get_or_insert_with(HashMap::new)
+        .insert(request_id.clone(), OpenRequest { label, reply });
+
+    let shown = match (x, y) {
+        (Some(x), Some(y)) => window.popup_menu_at(&menu, LogicalPosition::new(x, y)),
+        _ => window.popup_menu(&menu),
+    };
+    if let Err(e) = shown {
+        resolve_request(&request_id, None);
+        return Err(e.to_string());
+    }
+
+    // A dropped sender (window gone) counts as dismissed
+    Ok(answer.await.unwrap_or(None))
+}
+
+/// The frontend saw the menu close without a pick (the user went back to
+/// the page). Resolves the pending `show_context_menu` with None.
+#[tauri::command]
+pub fn dismiss_context_menu(request_id: String) {
+    resolve_request(&request_id, None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, label: &str) -> ContextMenuItem {
+        ContextMenuItem {
+            id: id.to_string(),
+            label: label.to_string(),
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_description_json() {
+        let items: Vec<ContextMenuItem> = serde_json::from_str(
+            r#"[
+                { "id": "copy", "label": "Copy", "accelerator": "CmdOrCtrl+C" },
+                { "separator": true },
+                { "id": "wrap", "label": "Word Wrap", "checked": true },
+                { "label": "Insert", "items": [{ "id": "table", "label": "Table", "enabled": false }] }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(items.len(), 4);
+        assert!(items[0].enabled);
+        assert!(items[1].separator);
+        assert_eq!(items[2].checked, Some(true));
+        assert!(!items[3].items.as_ref().unwrap()[0].enabled);
+        assert!(validate_items(&items, 0).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_descriptions() {
+        assert!(validate_items(&[item("", "Copy")], 0).is_err());
+        assert!(validate_items(&[item("copy", "")], 0).is_err());
+
+        let mut nested = item("leaf", "Leaf");
+        for _ in 0..=MAX_DEPTH {
+            nested = ContextMenuItem {
+                items: Some(vec![nested]),
+                ..item("", "Sub")
+            };
+        }
+        assert!(validate_items(&[nested], 0).is_err());
+    }
+
+    #[test]
+    fn item_ids_round_trip() {
+        let menu_id = context_item_id("req-42", "insert:table");
+        assert_eq!(parse_context_item_id(&menu_id), Some(("req-42", "insert:table")));
+        assert_eq!(parse_context_item_id("bold"), None);
+        assert_eq!(parse_context_item_id("ctx::copy"), None);
+    }
+
+    #[test]
+    fn requests_resolve_once_and_clear_with_their_window() {
+        let (reply, mut picked) = oneshot::channel();
+        let (other_reply, mut dismissed) = oneshot::channel();
+        {
+            let mut guard = open_requests();
+            let map = guard.get_or_insert_with(HashMap::new);
+            map.insert("ctx-test-a".into(), OpenRequest { label: "doc-ctx".into(), reply });
+            map.insert(
+                "ctx-test-b".into(),
+                OpenRequest { label: "doc-ctx-2".into(), reply: other_reply },
+            );
+        }
+
+        assert!(handle_context_menu_event(&context_item_id("ctx-test-a", "copy")));
+        assert_eq!(picked.try_recv().unwrap(), Some("copy".to_string()));
+        // Already answered: a late dismissal is a no-op
+        dismiss_context_menu("ctx-test-a".into());
+
+        clear_window_requests("doc-ctx-2");
+        assert_eq!(dismissed.try_recv().unwrap(), None);
+        assert!(!open_requests().as_ref().unwrap().contains_key("ctx-test-b"));
+    }
+}
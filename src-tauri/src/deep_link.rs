@@ -0,0 +1,291 @@
+//! Deep Links
+//!
+//! The `vmark://` URL scheme, for linking into notes from other apps:
+//!
+//! - `vmark://open?file=/notes/a.md&line=42` opens a file (`path=` works
+//!   too), or focuses the window that already has it open, and jumps to the
+//!   line
+//! - `vmark://new` and `vmark://append` create and extend notes (see
+//!   `automation`)
+//!
+//! macOS delivers the URLs as open events (the scheme is declared in
+//! Info.plist). Windows and Linux start the app with the URL as an argument
+//! (see `cli`); the scheme is registered for the current user at startup.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Url};
+
+use crate::file_tree::is_markdown;
+
+/// Scheme declared in Info.plist and registered on Windows and Linux
+pub const URL_SCHEME: &str = "vmark";
+
+/// Emitted when a `line=` is waiting, so a window already showing the file
+/// can take it without switching tabs
+pub const REVEAL_PENDING_EVENT: &str = "deep-link:reveal-pending";
+
+/// How long a `line=` waits for its file to become the active tab
+const REVEAL_TTL: Duration = Duration::from_secs(30);
+
+/// Lines to jump to once their file is shown, keyed by path
+static PENDING_REVEALS: Mutex<Option<HashMap<PathBuf, (u32, Instant)>>> = Mutex::new(None);
+
+#[derive(Debug, PartialEq)]
+struct OpenLink {
+    path: PathBuf,
+    /// 1-based line to jump to
+    line: Option<u32>,
+}
+
+/// Whether a command-line argument is a `vmark:` link.
+pub fn is_link(arg: &str) -> bool {
+    arg.strip_prefix(URL_SCHEME)
+        .is_some_and(|rest| rest.starts_with(':'))
+}
+
+/// An absolute markdown file path from a link.
+pub(crate) fn note_path(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path.trim());
+    if !path.is_absolute() {
+        return Err(format!("Not an absolute path: {}", path.display()));
+    }
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if !is_markdown(&name) {
+        return Err(format!("Not a markdown file: {}", path.display()));
+    }
+    Ok(path)
+}
+
+/// A non-blank query parameter.
+pub(crate) fn link_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// The link's action, lowercased.
+pub(crate) fn link_action(url: &Url) -> String {
+    // `vmark://open?...` puts the action in the host, `vmark:open?...` in the path
+    url.host_str()
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/')
+        .to_ascii_lowercase()
+}
+
+fn parse_url(url: &Url) -> Result<OpenLink, String> {
+    let param = |key: &str| link_param(url, key);
+    let action = link_action(url);
+    if action != "open" {
+        return Err(format!("Unknown action: {action}"));
+    }
+    Ok(OpenLink {
+        path: note_path(
+            &param("file")
+                .or_else(|| param("path"))
+                .ok_or("Missing file")?,
+        )?,
+        line: param("line")
+            .map(|line| {
+                line.trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("Invalid line: {line}"))
+            })
+            .transpose()?,
+    })
+}
+
+/// Carry out a `vmark://` URL. Returns the file to open, if any; callers
+/// should then emit `REVEAL_PENDING_EVENT`.
+pub fn handle_url(app: &AppHandle, url: &Url) -> Option<PathBuf> {
+    if link_action(url) != "open" {
+        return crate::automation::handle_url(app, url);
+    }
+    match parse_url(url) {
+        Ok(OpenLink { path, line }) => {
+            if let Some(line) = line {
+                set_pending_reveal(&path, line, Instant::now());
+            }
+            Some(path)
+        }
+        Err(e) => {
+            tracing::warn!("[DeepLink] {}: {}", url.as_str(), e);
+            None
+        }
+    }
+}
+
+fn set_pending_reveal(path: &Path, line: u32, now: Instant) {
+    if let Ok(mut guard) = PENDING_REVEALS.lock() {
+        let reveals = guard.get_or_insert_with(HashMap::new);
+        reveals.retain(|_, (_, at)| now.duration_since(*at) < REVEAL_TTL);
+        reveals.insert(path.to_path_buf(), (line, now));
+    }
+}
+
+fn take_reveal(path: &Path, now: Instant) -> Option<u32> {
+    let mut guard = PENDING_REVEALS.lock().ok()?;
+    let (line, at) = guard.as_mut()?.remove(path)?;
+    (now.duration_since(at) < REVEAL_TTL).then_some(line)
+}
+
+/// Take the line a `vmark://open` link asked for, once `path` is the
+/// window's active tab.
+#[tauri::command]
+pub fn take_pending_reveal(path: String) -> Option<u32> {
+    take_reveal(Path::new(&path), Instant::now())
+}
+
+// ============================================================================
+// Registration
+// ============================================================================
+
+/// Point the scheme at this executable for the current user. macOS reads
+/// it from Info.plist, so there's nothing to do there.
+pub fn register_url_scheme() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Cannot get executable path: {}", e))?;
+    register(&exe)
+}
+
+#[cfg(target_os = "windows")]
+fn register(exe: &Path) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let key = format!("HKCU\\Software\\Classes\\{URL_SCHEME}");
+    let command_key = format!("{key}\\shell\\open\\command");
+    let label = format!("URL:{URL_SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let runs: [Vec<&str>; 3] = [
+        vec!["add", &key, "/ve", "/d", &label, "/f"],
+        vec!["add", &key, "/v", "URL Protocol", "/d", "", "/f"],
+        vec!["add", &command_key, "/ve", "/d", &command, "/f"],
+    ];
+    for args in &runs {
+        let status = std::process::Command::new("reg")
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .status()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !status.success() {
+            return Err(format!("reg {} failed", args.join(" ")));
+        }
+    }
+    Ok(())
+}
+
+/// Desktop entry that hands `vmark:` links to this executable
+#[cfg(any(target_os = "linux", test))]
+fn desktop_entry(exe: &Path) -> String {
+    // Exec arguments are quoted per the Desktop Entry spec
+    let exec = exe
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('`', "\\`")
+        .replace('$', "\\$");
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=VMark\n\
+         Exec=\"{exec}\" %u\n\
+         Terminal=false\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/{URL_SCHEME};\n"
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn register(exe: &Path) -> Result<(), String> {
+    let name = format!("{URL_SCHEME}-url-handler.desktop");
+    let dir = dirs::data_dir()
+        .ok_or("No data directory")?
+        .join("applications");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(&name);
+    let entry = desktop_entry(exe);
+    if std::fs::read_to_string(&path).ok().as_deref() == Some(entry.as_str()) {
+        return Ok(());
+    }
+    std::fs::write(&path, entry)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", &name, &format!("x-scheme-handler/{URL_SCHEME}")])
+        .status()
+        .map_err(|e| format!("Failed to run xdg-mime: {}", e))?;
+    if !status.success() {
+        return Err("xdg-mime default failed".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn register(_exe: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(url: &str) -> Result<OpenLink, String> {
+        parse_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(
+            parse("vmark://open?path=%2Fnotes%2Fa.md"),
+            Ok(OpenLink {
+                path: PathBuf::from("/notes/a.md"),
+                line: None
+            })
+        );
+        assert_eq!(
+            parse("vmark:open?file=%2Fnotes%2Fa.md&line=42"),
+            Ok(OpenLink {
+                path: PathBuf::from("/notes/a.md"),
+                line: Some(42)
+            })
+        );
+        assert!(parse("vmark://open?file=%2Fnotes%2Fa.md&line=0").is_err());
+        assert!(parse("vmark://open?file=%2Fnotes%2Fa.md&line=top").is_err());
+        assert!(parse("vmark://open?file=relative.md").is_err());
+        assert!(parse("vmark://open?file=/etc/passwd").is_err());
+        assert!(parse("vmark://open").is_err());
+        assert!(parse("vmark://delete?file=/a.md").is_err());
+    }
+
+    #[test]
+    fn test_is_link() {
+        assert!(is_link("vmark://open?file=/a.md"));
+        assert!(is_link("vmark:open"));
+        assert!(!is_link("vmark.md"));
+        assert!(!is_link("/notes/vmark:a.md"));
+    }
+
+    #[test]
+    fn test_pending_reveal() {
+        let path = Path::new("/notes/reveal-test.md");
+        let now = Instant::now();
+        set_pending_reveal(path, 12, now);
+        assert_eq!(take_reveal(path, now), Some(12));
+        assert_eq!(take_reveal(path, now), None);
+
+        set_pending_reveal(path, 3, now);
+        assert_eq!(take_reveal(path, now + REVEAL_TTL), None);
+    }
+
+    #[test]
+    fn test_desktop_entry() {
+        let entry = desktop_entry(Path::new("/opt/My Apps/vmark"));
+        assert!(entry.contains("Exec=\"/opt/My Apps/vmark\" %u\n"));
+        assert!(entry.contains("MimeType=x-scheme-handler/vmark;\n"));
+    }
+}
@@ -0,0 +1,340 @@
+//! Crash reports and diagnostics bundles.
+//!
+//! A panic hook writes a crash report (message, location, backtrace,
+//! versions) to `crash-reports/` in the app data directory. The
+//! `generate_diagnostics_bundle` command zips what a bug report needs:
+//! system info, AI provider availability, a summary of the hot exit session
+//! (counts only - never document content or paths), recent crash reports
+//! and recent log files.
+
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::hot_exit::session::SessionData;
+
+/// Crash report folder in the app data directory
+pub const CRASH_REPORTS_DIR: &str = "crash-reports";
+
+/// Crash reports kept on disk (oldest are removed first)
+const MAX_CRASH_REPORTS: usize = 20;
+/// Crash reports and log files included in a bundle
+const MAX_BUNDLE_FILES: usize = 5;
+/// Only the tail of each log file goes into a bundle
+const MAX_LOG_BYTES: usize = 1024 * 1024;
+
+/// Versions and platform, written to `system.json`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemInfo {
+    app_version: String,
+    tauri_version: String,
+    os: String,
+    os_version: Option<String>,
+    arch: String,
+}
+
+fn system_info(app_version: &str) -> SystemInfo {
+    SystemInfo {
+        app_version: app_version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_version: os_version(),
+        arch: std::env::consts::ARCH.to_string(),
+    }
+}
+
+fn os_version() -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        std::process::Command::new("sw_vers").arg("-productVersion").output()
+    } else if cfg!(windows) {
+        std::process::Command::new("cmd").args(["/C", "ver"]).output()
+    } else {
+        std::process::Command::new("uname").arg("-r").output()
+    };
+    let output = output.ok().filter(|o| o.status.success())?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+fn format_crash_report(message: &str, location: Option<String>, backtrace: &str, app_version: &str) -> String {
+    format!(
+        "VMark {} crashed at {}\nPlatform: {} {}\nThread: {}\nLocation: {}\n\n{}\n\nBacktrace:\n{}\n",
+        app_version,
+        Local::now().format("%Y-%m-%d %H:%M:%S %z"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::thread::current().name().unwrap_or("<unnamed>"),
+        location.unwrap_or_else(|| "unknown".to_string()),
+        message,
+        backtrace
+    )
+}
+
+/// Files in `dir` whose name ends with `extension`, newest first.
+fn newest_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(extension))
+        .filter_map(|e| {
+            let modified = e.metadata().ok()?.modified().ok()?;
+            Some((modified, e.path()))
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Keep only the newest `keep` crash reports.
+fn prune_crash_reports(dir: &Path, keep: usize) {
+    for old in newest_files(dir, ".txt").into_iter().skip(keep) {
+        let _ = fs::remove_file(old);
+    }
+}
+
+/// Install a panic hook that writes a crash report before the default
+/// hook runs. Called once during app setup.
+pub fn install_panic_hook(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(CRASH_REPORTS_DIR);
+    let app_version = app.package_info().version.to_string();
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let report = format_crash_report(&message, location, &backtrace, &app_version);
+
+        if fs::create_dir_all(&dir).is_ok() {
+            let name = format!("crash-{}.txt", Local::now().format("%Y%m%d-%H%M%S%.3f"));
+            let _ = fs::write(dir.join(name), report);
+            prune_crash_reports(&dir, MAX_CRASH_REPORTS);
+        }
+        previous(info);
+    }));
+    Ok(())
+}
+
+/// Shape of the hot exit session without any document content or paths.
+fn session_summary(session: &SessionData) -> serde_json::Value {
+    let tabs = session.windows.iter().flat_map(|w| &w.tabs);
+    serde_json::json!({
+        "version": session.version,
+        "timestamp": session.timestamp,
+        "vmarkVersion": session.vmark_version,
+        "windows": session.windows.len(),
+        "tabs": tabs.clone().count(),
+        "dirtyTabs": tabs.clone().filter(|t| t.document.is_dirty).count(),
+        "untitledTabs": tabs.clone().filter(|t| t.document.is_untitled).count(),
+        "missingTabs": tabs.filter(|t| t.document.is_missing).count(),
+        "terminalSessions": session.windows.iter().map(|w| w.terminal_sessions.len()).sum::<usize>(),
+        "workspaceMode": session.workspace.as_ref().is_some_and(|w| w.is_workspace_mode),
+    })
+}
+
+/// Last `max` bytes of a file, starting at a line boundary when truncated.
+fn read_tail(path: &Path, max: usize) -> Option<Vec<u8>> {
+    let bytes = fs::read(path).ok()?;
+    if bytes.len() <= max {
+        return Some(bytes);
+    }
+    let tail = &bytes[bytes.len() - max..];
+    let start = tail.iter().position(|b| *b == b'\n').map_or(0, |i| i + 1);
+    Some(tail[start..].to_vec())
+}
+
+/// Write `entries` (name, bytes) into a new zip archive at `dest`.
+fn write_bundle(dest: &Path, entries: &[(String, Vec<u8>)]) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let file = fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {}: {}", name, e))?;
+        zip.write_all(bytes)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics bundle: {}", e))?;
+    Ok(())
+}
+
+fn json_entry(name: &str, value: &impl Serialize) -> Result<(String, Vec<u8>), String> {
+    let bytes = serde_json::to_vec_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    Ok((name.to_string(), bytes))
+}
+
+/// Zip diagnostics for a bug report. Writes to `destination`, or to
+/// `diagnostics/` in the app data directory. Returns the bundle path.
+#[tauri::command]
+pub async fn generate_diagnostics_bundle(app: AppHandle, destination: Option<String>) -> Result<String, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let app_version = app.package_info().version.to_string();
+    let mut entries = vec![json_entry("system.json", &system_info(&app_version))?];
+
+    let providers: Vec<serde_json::Value> = crate::ai_provider::detect_ai_providers()
+        .into_iter()
+        .map(|p| serde_json::json!({ "type": p.provider_type, "name": p.name, "available": p.available }))
+        .collect();
+    entries.push(json_entry("providers.json", &providers)?);
+
+    // An unreadable session is still worth reporting
+    let session = match crate::hot_exit::storage::read_session(&app).await {
+        Ok(Some(session)) => session_summary(&session),
+        Ok(None) => serde_json::Value::Null,
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+    entries.push(json_entry("session.json", &session)?);
+
+    for path in newest_files(&app_data.join(CRASH_REPORTS_DIR), ".txt").into_iter().take(MAX_BUNDLE_FILES) {
+        if let (Some(name), Ok(bytes)) = (path.file_name(), fs::read(&path)) {
+            entries.push((format!("crash-reports/{}", name.to_string_lossy()), bytes));
+        }
+    }
+    if let Ok(log_dir) = app.path().app_log_dir() {
+        for path in newest_files(&log_dir, ".log").into_iter().take(MAX_BUNDLE_FILES) {
+            if let (Some(name), Some(bytes)) = (path.file_name(), read_tail(&path, MAX_LOG_BYTES)) {
+                entries.push((format!("logs/{}", name.to_string_lossy()), bytes));
+            }
+        }
+    }
+
+    let dest = match destination {
+        Some(path) => PathBuf::from(path),
+        None => app_data
+            .join("diagnostics")
+            .join(format!("vmark-diagnostics-{}.zip", Local::now().format("%Y%m%d-%H%M%S"))),
+    };
+    write_bundle(&dest, &entries)?;
+    Ok(dest.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn crash_report_includes_message_and_location() {
+        let report = format_crash_report("boom", Some("src/lib.rs:1:2".into()), "frame 0", "1.2.3");
+        assert!(report.starts_with("VMark 1.2.3 crashed at "));
+        assert!(report.contains("Location: src/lib.rs:1:2\n"));
+        assert!(report.contains("\n\nboom\n\nBacktrace:\nframe 0\n"));
+    }
+
+    #[test]
+    fn session_summary_has_no_content_or_paths() {
+        let session: SessionData = serde_json::from_value(serde_json::json!({
+            "version": 2,
+            "timestamp": 1700000000,
+            "vmark_version": "1.2.3",
+            "windows": [{
+                "window_label": "main",
+                "is_main_window": true,
+                "active_tab_id": "t1",
+                "tabs": [{
+                    "id": "t1",
+                    "file_path": "/secret/notes.md",
+                    "title": "notes.md",
+                    "is_pinned": false,
+                    "document": {
+                        "content": "private text",
+                        "saved_content": "private",
+                        "is_dirty": true,
+                        "is_missing": false,
+                        "is_divergent": false,
+                        "line_ending": "\n",
+                        "cursor_info": null,
+                        "last_modified_timestamp": null,
+                        "is_untitled": false,
+                        "untitled_number": null
+                    }
+                }],
+                "ui_state": {
+                    "sidebar_visible": true,
+                    "sidebar_width": 260,
+                    "outline_visible": false,
+                    "sidebar_view_mode": "files",
+                    "status_bar_visible": true,
+                    "source_mode_enabled": false,
+                    "focus_mode_enabled": false,
+                    "typewriter_mode_enabled": false
+                },
+                "geometry": null
+            }],
+            "workspace": null
+        }))
+        .unwrap();
+
+        let summary = session_summary(&session);
+        assert_eq!(summary["windows"], 1);
+        assert_eq!(summary["tabs"], 1);
+        assert_eq!(summary["dirtyTabs"], 1);
+        let text = summary.to_string();
+        assert!(!text.contains("private"));
+        assert!(!text.contains("/secret"));
+    }
+
+    #[test]
+    fn bundle_contains_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("out").join("bundle.zip");
+        let entries = vec![
+            ("system.json".to_string(), b"{}".to_vec()),
+            ("logs/vmark.log".to_string(), b"line\n".to_vec()),
+        ];
+        write_bundle(&dest, &entries).unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut log = String::new();
+        archive.by_name("logs/vmark.log").unwrap().read_to_string(&mut log).unwrap();
+        assert_eq!(log, "line\n");
+    }
+
+    #[test]
+    fn log_tail_starts_at_line_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vmark.log");
+        fs::write(&path, "first line\nsecond\nthird\n").unwrap();
+        assert_eq!(read_tail(&path, 1024).unwrap(), b"first line\nsecond\nthird\n");
+        assert_eq!(read_tail(&path, 10).unwrap(), b"third\n");
+    }
+
+    #[test]
+    fn prunes_old_crash_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..4 {
+            fs::write(dir.path().join(format!("crash-{}.txt", i)), "x").unwrap();
+        }
+        fs::write(dir.path().join("notes.md"), "keep").unwrap();
+        prune_crash_reports(dir.path(), 2);
+        assert_eq!(newest_files(dir.path(), ".txt").len(), 2);
+        assert!(dir.path().join("notes.md").exists());
+    }
+}
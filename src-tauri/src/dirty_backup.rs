@@ -0,0 +1,522 @@
+//! Dirty Document Backups
+//!
+//! A guard against losing unsaved work to crashes or power loss,
+//! independent of hot exit. Every few minutes the backend emits
+//! `dirty-backup:request`; each window answers with `report_dirty_digests`
+//! (tab, path, title and SHA-256 of every unsaved document), and the backend
+//! replies with the tabs it has no copy of yet, which the window then sends
+//! with `write_dirty_backup`. Unchanged documents are never re-sent.
+//!
+//! Copies live in `<app_data>/dirty-backups/<key>/<YYYYmmdd-HHMMSS>-<digest>.md`,
+//! one folder per document (keyed by path, or tab id for untitled ones), with
+//! an `entry.json` naming the document. Old copies are pruned by count and
+//! age after every write. Encrypted notes are never copied, since the copy
+//! would be plaintext.
+//!
+//! Settings are in `<app_data>/dirty-backup.json`.
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::watcher::content_hash;
+
+const CONFIG_FILE: &str = "dirty-backup.json";
+const BACKUP_DIR: &str = "dirty-backups";
+const ENTRY_FILE: &str = "entry.json";
+const REQUEST_EVENT: &str = "dirty-backup:request";
+
+/// Digest characters kept in backup file names
+const DIGEST_LEN: usize = 16;
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Loop emitting backup requests
+static TIMER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+/// Serializes writes and pruning
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DirtyBackupConfig {
+    pub enabled: bool,
+    pub interval_minutes: u32,
+    /// Copies kept per document
+    pub keep_per_document: usize,
+    /// Copies older than this are deleted
+    pub max_age_days: u32,
+}
+
+impl Default for DirtyBackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 5,
+            keep_per_document: 10,
+            max_age_days: 14,
+        }
+    }
+}
+
+/// An unsaved document as reported by a window
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirtyDocument {
+    pub tab_id: String,
+    /// None for untitled documents
+    pub path: Option<String>,
+    pub title: String,
+    /// SHA-256 (hex) of the current content
+    pub digest: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct BackupEntry {
+    path: Option<String>,
+    title: String,
+    tab_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DirtyBackup {
+    /// `<key>/<file>`, for `restore_dirty_backup` and `delete_dirty_backup`
+    pub id: String,
+    pub path: Option<String>,
+    pub title: String,
+    /// Unix milliseconds
+    pub created_at: i64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupRequest {
+    request_id: String,
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn app_data(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+fn load_config(app_data: &Path) -> DirtyBackupConfig {
+    fs::read_to_string(app_data.join(CONFIG_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Folder name for a document: its path, or tab id when untitled.
+fn document_key(path: Option<&str>, tab_id: &str) -> String {
+    let source = match path {
+        Some(path) => format!("path:{path}"),
+        None => format!("untitled:{tab_id}"),
+    };
+    content_hash(source.as_bytes())[..DIGEST_LEN].to_string()
+}
+
+fn short_digest(digest: &str) -> &str {
+    digest.get(..DIGEST_LEN).unwrap_or(digest)
+}
+
+/// Backup files in a document folder, oldest first.
+fn versions(dir: &Path) -> Vec<(NaiveDateTime, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<_> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stem = name.strip_suffix(".md")?;
+            let stamp = NaiveDateTime::parse_from_str(stem.get(..15)?, TIMESTAMP_FORMAT).ok()?;
+            Some((stamp, entry.path()))
+        })
+        .collect();
+    versions.sort();
+    versions
+}
+
+fn has_copy(dir: &Path, digest: &str) -> bool {
+    let suffix = format!("-{}.md", short_digest(digest));
+    versions(dir)
+        .iter()
+        .any(|(_, path)| path.to_string_lossy().ends_with(&suffix))
+}
+
+/// Drop copies beyond `keep_per_document` and older than `max_age_days`,
+/// and document folders left empty.
+fn prune(root: &Path, config: &DirtyBackupConfig, now: NaiveDateTime) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let cutoff = now - chrono::Duration::days(i64::from(config.max_age_days));
+    for dir in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let existing = versions(&dir);
+        let excess = existing
+            .len()
+            .saturating_sub(config.keep_per_document.max(1));
+        for (index, (stamp, path)) in existing.iter().enumerate() {
+            if index < excess || *stamp < cutoff {
+                let _ = fs::remove_file(path);
+            }
+        }
+        if versions(&dir).is_empty() {
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+}
+
+fn write_copy(
+    root: &Path,
+    document: &DirtyDocument,
+    content: &str,
+    config: &DirtyBackupConfig,
+    now: NaiveDateTime,
+) -> Result<(), String> {
+    let _guard = WRITE_LOCK.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let dir = root.join(document_key(document.path.as_deref(), &document.tab_id));
+    if has_copy(&dir, &document.digest) {
+        return Ok(());
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup folder: {e}"))?;
+
+    let entry = BackupEntry {
+        path: document.path.clone(),
+        title: document.title.clone(),
+        tab_id: document.tab_id.clone(),
+    };
+    let json = serde_json::to_string_pretty(&entry).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(&dir.join(ENTRY_FILE), json.as_bytes())?;
+
+    let name = format!(
+        "{}-{}.md",
+        now.format(TIMESTAMP_FORMAT),
+        short_digest(&document.digest)
+    );
+    crate::app_paths::atomic_write_file(&dir.join(name), content.as_bytes())?;
+    prune(root, config, now);
+    Ok(())
+}
+
+fn list(root: &Path) -> Vec<DirtyBackup> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut backups = Vec::new();
+    for dir in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let Some(entry) = fs::read_to_string(dir.join(ENTRY_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<BackupEntry>(&json).ok())
+        else {
+            continue;
+        };
+        let key = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        for (stamp, path) in versions(&dir) {
+            let file = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            backups.push(DirtyBackup {
+                id: format!("{key}/{file}"),
+                path: entry.path.clone(),
+                title: entry.title.clone(),
+                created_at: Local
+                    .from_local_datetime(&stamp)
+                    .earliest()
+                    .map_or(0, |dt| dt.timestamp_millis()),
+                size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            });
+        }
+    }
+    backups.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    backups
+}
+
+/// Resolve a backup id, refusing anything that isn't `<key>/<file>.md`
+/// inside the backup folder.
+fn backup_file(root: &Path, id: &str) -> Result<PathBuf, String> {
+    let valid = id.split_once('/').is_some_and(|(key, file)| {
+        key.len() == DIGEST_LEN
+            && key.chars().all(|c| c.is_ascii_hexdigit())
+            && file.ends_with(".md")
+            && !file.contains(['/', '\\'])
+            && !file.starts_with('.')
+    });
+    if !valid {
+        return Err(format!("Invalid backup id: {id}"));
+    }
+    let path = root.join(id);
+    if !path.is_file() {
+        return Err(format!("Backup not found: {id}"));
+    }
+    Ok(path)
+}
+
+// ============================================================================
+// Timer
+// ============================================================================
+
+fn stop_timer() {
+    if let Ok(mut guard) = TIMER.lock() {
+        if let Some(handle) = guard.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// (Re)start the request loop for `config`, or stop it when disabled.
+fn start_timer(app: &AppHandle, config: &DirtyBackupConfig) {
+    stop_timer();
+    if !config.enabled {
+        return;
+    }
+    let app = app.clone();
+    let period = Duration::from_secs(u64::from(config.interval_minutes.max(1)) * 60);
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // The first tick fires immediately; nothing is dirty at startup
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let request = BackupRequest {
+                request_id: uuid::Uuid::new_v4().to_string(),
+            };
+            if let Err(e) = app.emit(REQUEST_EVENT, request) {
+                tracing::warn!("[DirtyBackup] Failed to request digests: {}", e);
+            }
+        }
+    });
+    if let Ok(mut guard) = TIMER.lock() {
+        *guard = Some(handle);
+    }
+}
+
+/// Start the backup timer with the saved settings.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let config = load_config(&app_data(app)?);
+    start_timer(app, &config);
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_dirty_backup_config(app: AppHandle) -> Result<DirtyBackupConfig, String> {
+    Ok(load_config(&app_data(&app)?))
+}
+
+#[tauri::command]
+pub fn set_dirty_backup_config(app: AppHandle, config: DirtyBackupConfig) -> Result<(), String> {
+    let dir = app_data(&app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data folder: {e}"))?;
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(&dir.join(CONFIG_FILE), json.as_bytes())?;
+    start_timer(&app, &config);
+    Ok(())
+}
+
+/// A window's answer to `dirty-backup:request`: returns the tab ids whose
+/// current content has no copy yet and should be sent with
+/// `write_dirty_backup`.
+#[tauri::command]
+pub fn report_dirty_digests(
+    app: AppHandle,
+    documents: Vec<DirtyDocument>,
+) -> Result<Vec<String>, String> {
+    let root = app_data(&app)?.join(BACKUP_DIR);
+    Ok(documents
+        .into_iter()
+        .filter(|doc| {
+            !doc.path
+                .as_deref()
+                .is_some_and(|p| crate::encryption::is_encrypted_path(Path::new(p)))
+        })
+        .filter(|doc| {
+            !has_copy(
+                &root.join(document_key(doc.path.as_deref(), &doc.tab_id)),
+                &doc.digest,
+            )
+        })
+        .map(|doc| doc.tab_id)
+        .collect())
+}
+
+/// Store a copy of an unsaved document.
+#[tauri::command]
+pub async fn write_dirty_backup(
+    app: AppHandle,
+    document: DirtyDocument,
+    content: String,
+) -> Result<(), String> {
+    if document
+        .path
+        .as_deref()
+        .is_some_and(|p| crate::encryption::is_encrypted_path(Path::new(p)))
+    {
+        return Err("Encrypted notes are not backed up".to_string());
+    }
+    if content_hash(content.as_bytes()) != document.digest {
+        return Err("Backup content does not match its digest".to_string());
+    }
+    let app_data = app_data(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = load_config(&app_data);
+        write_copy(
+            &app_data.join(BACKUP_DIR),
+            &document,
+            &content,
+            &config,
+            Local::now().naive_local(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to write backup: {e}"))?
+}
+
+/// All stored copies, newest first.
+#[tauri::command]
+pub fn list_dirty_backups(app: AppHandle) -> Result<Vec<DirtyBackup>, String> {
+    Ok(list(&app_data(&app)?.join(BACKUP_DIR)))
+}
+
+/// The content of a copy. With `target_path`, it is also written there
+/// (e.g. back over the original file).
+#[tauri::command]
+pub fn restore_dirty_backup(
+    app: AppHandle,
+    id: String,
+    target_path: Option<String>,
+) -> Result<String, String> {
+    let file = backup_file(&app_data(&app)?.join(BACKUP_DIR), &id)?;
+    let content = fs::read_to_string(&file).map_err(|e| format!("Failed to read backup: {e}"))?;
+    if let Some(target) = target_path {
+        crate::app_paths::atomic_write_file(Path::new(&target), content.as_bytes())?;
+        tracing::info!("[DirtyBackup] Restored {} to {}", id, target);
+    }
+    Ok(content)
+}
+
+#[tauri::command]
+pub fn delete_dirty_backup(app: AppHandle, id: String) -> Result<(), String> {
+    let root = app_data(&app)?.join(BACKUP_DIR);
+    let file = backup_file(&root, &id)?;
+    fs::remove_file(&file).map_err(|e| format!("Failed to delete backup: {e}"))?;
+    let config = load_config(&app_data(&app)?);
+    prune(&root, &config, Local::now().naive_local());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(path: Option<&str>, content: &str) -> DirtyDocument {
+        DirtyDocument {
+            tab_id: "tab-1".into(),
+            path: path.map(str::to_string),
+            title: "Notes".into(),
+            digest: content_hash(content.as_bytes()),
+        }
+    }
+
+    fn at(stamp: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_write_skips_unchanged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DirtyBackupConfig::default();
+        let document = doc(Some("/notes/a.md"), "draft");
+        write_copy(
+            dir.path(),
+            &document,
+            "draft",
+            &config,
+            at("2026-05-01 10:00:00"),
+        )
+        .unwrap();
+        write_copy(
+            dir.path(),
+            &document,
+            "draft",
+            &config,
+            at("2026-05-01 10:05:00"),
+        )
+        .unwrap();
+
+        let backups = list(dir.path());
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].path.as_deref(), Some("/notes/a.md"));
+        assert_eq!(backups[0].size, 5);
+        let file = backup_file(dir.path(), &backups[0].id).unwrap();
+        assert_eq!(fs::read_to_string(file).unwrap(), "draft");
+    }
+
+    #[test]
+    fn test_prune_by_count_and_age() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DirtyBackupConfig {
+            keep_per_document: 2,
+            max_age_days: 7,
+            ..Default::default()
+        };
+        for (i, content) in ["one", "two", "three"].iter().enumerate() {
+            let stamp = at(&format!("2026-05-0{} 10:00:00", i + 1));
+            write_copy(dir.path(), &doc(None, content), content, &config, stamp).unwrap();
+        }
+        let backups = list(dir.path());
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].id.contains("20260503-100000"));
+
+        // Everything is past the age limit two weeks later
+        prune(dir.path(), &config, at("2026-05-20 00:00:00"));
+        assert!(list(dir.path()).is_empty());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_documents_are_kept_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DirtyBackupConfig::default();
+        let now = at("2026-05-01 10:00:00");
+        write_copy(dir.path(), &doc(Some("/a.md"), "x"), "x", &config, now).unwrap();
+        write_copy(dir.path(), &doc(Some("/b.md"), "x"), "x", &config, now).unwrap();
+        write_copy(dir.path(), &doc(None, "x"), "x", &config, now).unwrap();
+        assert_eq!(list(dir.path()).len(), 3);
+        assert_ne!(
+            document_key(Some("tab-1"), "tab-1"),
+            document_key(None, "tab-1")
+        );
+    }
+
+    #[test]
+    fn test_backup_ids_are_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(backup_file(dir.path(), "../../etc/passwd").is_err());
+        assert!(backup_file(dir.path(), "0123456789abcdef/../x.md").is_err());
+        assert!(backup_file(dir.path(), "0123456789abcdef/missing.md")
+            .unwrap_err()
+            .contains("not found"));
+    }
+}
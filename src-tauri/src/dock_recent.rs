@@ -13,14 +13,12 @@ use std::path::Path;
 pub fn register_recent_document(path: &str) {
     // Validate path exists
     if !Path::new(path).exists() {
-        #[cfg(debug_assertions)]
-        eprintln!("[dock_recent] Path does not exist: {}", path);
+        tracing::debug!("Path does not exist: {}", path);
         return;
     }
 
     let Some(mtm) = MainThreadMarker::new() else {
-        #[cfg(debug_assertions)]
-        eprintln!("[dock_recent] Not on main thread, cannot register document");
+        tracing::debug!("Not on main thread, cannot register document");
         return;
     };
 
@@ -30,6 +28,17 @@ pub fn register_recent_document(path: &str) {
     let controller = NSDocumentController::sharedDocumentController(mtm);
     controller.noteNewRecentDocumentURL(&url);
 
-    #[cfg(debug_assertions)]
-    eprintln!("[dock_recent] Registered: {}", path);
+    tracing::debug!("Registered: {}", path);
+}
+
+/// Clear macOS Recent Documents (Dock menu), e.g. after "Clear Recent Files".
+pub fn clear_recent_documents() {
+    let Some(mtm) = MainThreadMarker::new() else {
+        tracing::debug!("Not on main thread, cannot clear documents");
+        return;
+    };
+
+    let controller = NSDocumentController::sharedDocumentController(mtm);
+    // SAFETY: clearRecentDocuments: accepts a nil sender
+    unsafe { controller.clearRecentDocuments(None) };
 }
@@ -0,0 +1,606 @@
+//! Document Diff
+//!
+//! Compares two versions of a document, line by line or word by word, for
+//! local history, external-change conflicts and "compare two files" (also
+//! `vmark --diff a.md b.md`). The result is a list of equal / inserted /
+//! deleted segments the frontend renders itself, plus an optional HTML
+//! rendering (`<ins>`/`<del>` runs, or a line table) for simple views.
+//!
+//! `merge_documents` does a line-based three-way merge for conflicted saves:
+//! when `save_file_safe` reports the file changed on disk while the editor
+//! was dirty, the last saved text (base), the editor's text and the disk text
+//! are merged, with git-style conflict markers where both sides changed the
+//! same lines.
+//!
+//! Diffing is bounded by a deadline so huge, very different documents come
+//! back with a coarser (still correct) diff instead of stalling.
+
+use serde::{Deserialize, Serialize};
+use similar::{Algorithm, ChangeTag, TextDiff};
+use std::time::Duration;
+
+/// Upper bound on diff computation before falling back to a coarser result
+const DIFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffMode {
+    #[default]
+    Line,
+    Word,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiffOptions {
+    /// Also render the diff as HTML
+    pub html: bool,
+    /// Treat lines that differ only in whitespace as equal (line mode)
+    pub ignore_whitespace: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A run of text with the same kind. In line mode each segment is one line
+/// (without its line break) with its 1-based line numbers.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffStats {
+    /// Lines (line mode) or words (word mode) added
+    pub insertions: usize,
+    pub deletions: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiff {
+    pub identical: bool,
+    pub segments: Vec<DiffSegment>,
+    pub stats: DiffStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+}
+
+fn kind_of(tag: ChangeTag) -> DiffKind {
+    match tag {
+        ChangeTag::Equal => DiffKind::Equal,
+        ChangeTag::Insert => DiffKind::Insert,
+        ChangeTag::Delete => DiffKind::Delete,
+    }
+}
+
+fn count(stats: &mut DiffStats, kind: DiffKind) {
+    match kind {
+        DiffKind::Equal => stats.unchanged += 1,
+        DiffKind::Insert => stats.insertions += 1,
+        DiffKind::Delete => stats.deletions += 1,
+    }
+}
+
+fn strip_line_break(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .unwrap_or(line)
+}
+
+/// Collapse runs of spaces and tabs and trim, for whitespace-insensitive
+/// comparison.
+fn normalize_whitespace(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn diff_lines(old: &str, new: &str, ignore_whitespace: bool) -> (Vec<DiffSegment>, DiffStats) {
+    let (old_cmp, new_cmp) = if ignore_whitespace {
+        (normalize_whitespace(old), normalize_whitespace(new))
+    } else {
+        (old.to_string(), new.to_string())
+    };
+    // Compare the (possibly normalized) text, but report the original lines
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Patience)
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(&old_cmp, &new_cmp);
+
+    let mut segments = Vec::new();
+    let mut stats = DiffStats::default();
+    for change in diff.iter_all_changes() {
+        let kind = kind_of(change.tag());
+        let old_line = change.old_index();
+        let new_line = change.new_index();
+        let text = match kind {
+            DiffKind::Delete => old_line.and_then(|i| old_lines.get(i)),
+            _ => new_line.and_then(|i| new_lines.get(i)),
+        }
+        .copied()
+        .unwrap_or_else(|| strip_line_break(change.value()));
+        count(&mut stats, kind);
+        segments.push(DiffSegment {
+            kind,
+            text: text.to_string(),
+            old_line: old_line.map(|i| i + 1),
+            new_line: new_line.map(|i| i + 1),
+        });
+    }
+    (segments, stats)
+}
+
+fn diff_words(old: &str, new: &str) -> (Vec<DiffSegment>, DiffStats) {
+    let diff = TextDiff::configure()
+        .timeout(DIFF_TIMEOUT)
+        .diff_words(old, new);
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut stats = DiffStats::default();
+    for change in diff.iter_all_changes() {
+        let kind = kind_of(change.tag());
+        let value = change.value();
+        if !value.trim().is_empty() {
+            count(&mut stats, kind);
+        }
+        match segments.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(value),
+            _ => segments.push(DiffSegment {
+                kind,
+                text: value.to_string(),
+                old_line: None,
+                new_line: None,
+            }),
+        }
+    }
+    (segments, stats)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn render_html(segments: &[DiffSegment], mode: DiffMode) -> String {
+    match mode {
+        DiffMode::Word => {
+            let mut html = String::from("<div class=\"vmark-diff vmark-diff-word\">");
+            for segment in segments {
+                let text = escape_html(&segment.text);
+                match segment.kind {
+                    DiffKind::Equal => html.push_str(&text),
+                    DiffKind::Insert => html.push_str(&format!("<ins>{text}</ins>")),
+                    DiffKind::Delete => html.push_str(&format!("<del>{text}</del>")),
+                }
+            }
+            html.push_str("</div>");
+            html
+        }
+        DiffMode::Line => {
+            let mut html = String::from("<table class=\"vmark-diff vmark-diff-line\">");
+            for segment in segments {
+                let (class, sign) = match segment.kind {
+                    DiffKind::Equal => ("equal", " "),
+                    DiffKind::Insert => ("insert", "+"),
+                    DiffKind::Delete => ("delete", "-"),
+                };
+                let number = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+                html.push_str(&format!(
+                    "<tr class=\"{class}\"><td class=\"old\">{}</td><td class=\"new\">{}</td>\
+                     <td class=\"sign\">{sign}</td><td class=\"text\">{}</td></tr>",
+                    number(segment.old_line),
+                    number(segment.new_line),
+                    escape_html(&segment.text),
+                ));
+            }
+            html.push_str("</table>");
+            html
+        }
+    }
+}
+
+fn compare(old: &str, new: &str, mode: DiffMode, options: &DiffOptions) -> DocumentDiff {
+    let (segments, stats) = match mode {
+        DiffMode::Line => diff_lines(old, new, options.ignore_whitespace),
+        DiffMode::Word => diff_words(old, new),
+    };
+    DocumentDiff {
+        identical: segments.iter().all(|s| s.kind == DiffKind::Equal),
+        html: options.html.then(|| render_html(&segments, mode)),
+        segments,
+        stats,
+    }
+}
+
+/// Diff two versions of a document (line mode by default).
+#[tauri::command]
+pub async fn diff_documents(
+    old: String,
+    new: String,
+    mode: Option<DiffMode>,
+    options: Option<DiffOptions>,
+) -> Result<DocumentDiff, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        compare(&old, &new, mode.unwrap_or_default(), &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Failed to diff documents: {e}"))
+}
+
+// ============================================================================
+// Three-way merge
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MergeOptions {
+    /// Label after `<<<<<<<`
+    pub mine_label: String,
+    /// Label after `>>>>>>>`
+    pub theirs_label: String,
+    /// Include the base text (`|||||||` section, diff3 style) in conflicts
+    pub show_base: bool,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            mine_label: "mine".to_string(),
+            theirs_label: "theirs".to_string(),
+            show_base: false,
+        }
+    }
+}
+
+/// One region both sides changed differently
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    /// 1-based line of the `<<<<<<<` marker in the merged content
+    pub start_line: usize,
+    /// 1-based line of the `>>>>>>>` marker
+    pub end_line: usize,
+    pub base: String,
+    pub mine: String,
+    pub theirs: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    /// Merged text, with conflict markers where both sides disagree
+    pub content: String,
+    /// True when there were no conflicts
+    pub clean: bool,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// For each base line, the index of the same line on the other side if the
+/// diff kept it.
+fn matched_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let deadline = Some(std::time::Instant::now() + DIFF_TIMEOUT);
+    let mut matched = vec![None; base.len()];
+    for op in similar::capture_diff_slices_deadline(Algorithm::Myers, base, other, deadline) {
+        if let similar::DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for k in 0..len {
+                matched[old_index + k] = Some(new_index + k);
+            }
+        }
+    }
+    matched
+}
+
+/// Lines with their line breaks, plus the same lines without them for
+/// comparison (so a missing final newline or CRLF vs LF isn't a change).
+fn split_lines(text: &str) -> (Vec<&str>, Vec<&str>) {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let keys = lines.iter().map(|line| strip_line_break(line)).collect();
+    (lines, keys)
+}
+
+struct MergeWriter<'a> {
+    content: String,
+    line_break: &'a str,
+    lines: usize,
+}
+
+impl MergeWriter<'_> {
+    fn push(&mut self, line: &str) {
+        if !self.content.is_empty() && !self.content.ends_with('\n') {
+            self.content.push_str(self.line_break);
+        }
+        self.content.push_str(line);
+        self.lines += 1;
+    }
+
+    fn push_all(&mut self, lines: &[&str]) {
+        for line in lines {
+            self.push(line);
+        }
+    }
+}
+
+fn merge(base: &str, mine: &str, theirs: &str, options: &MergeOptions) -> MergeResult {
+    let (base_lines, base_keys) = split_lines(base);
+    let (mine_lines, mine_keys) = split_lines(mine);
+    let (theirs_lines, theirs_keys) = split_lines(theirs);
+    let in_mine = matched_lines(&base_keys, &mine_keys);
+    let in_theirs = matched_lines(&base_keys, &theirs_keys);
+
+    let line_break = if mine.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut out = MergeWriter {
+        content: String::with_capacity(mine.len().max(theirs.len())),
+        line_break,
+        lines: 0,
+    };
+    let mut conflicts = Vec::new();
+    let (mut b, mut m, mut t) = (0, 0, 0);
+
+    loop {
+        // Next base line both sides kept: everything before it is a chunk
+        // one or both sides changed.
+        let stable = (b..base_lines.len()).find_map(|i| match (in_mine[i], in_theirs[i]) {
+            (Some(mi), Some(ti)) => Some((i, mi, ti)),
+            _ => None,
+        });
+        let (next_b, next_m, next_t) =
+            stable.unwrap_or((base_lines.len(), mine_lines.len(), theirs_lines.len()));
+
+        let base_chunk = &base_keys[b..next_b];
+        let mine_chunk = &mine_keys[m..next_m];
+        let theirs_chunk = &theirs_keys[t..next_t];
+        if mine_chunk == base_chunk {
+            out.push_all(&theirs_lines[t..next_t]);
+        } else if theirs_chunk == base_chunk || mine_chunk == theirs_chunk {
+            out.push_all(&mine_lines[m..next_m]);
+        } else {
+            let start_line = out.lines + 1;
+            let marker = |out: &mut MergeWriter, text: String| out.push(&format!("{text}{line_break}"));
+            marker(&mut out, format!("<<<<<<< {}", options.mine_label));
+            out.push_all(&mine_lines[m..next_m]);
+            if options.show_base {
+                marker(&mut out, "||||||| base".to_string());
+                out.push_all(&base_lines[b..next_b]);
+            }
+            marker(&mut out, "=======".to_string());
+            out.push_all(&theirs_lines[t..next_t]);
+            marker(&mut out, format!(">>>>>>> {}", options.theirs_label));
+            conflicts.push(MergeConflict {
+                start_line,
+                end_line: out.lines,
+                base: base_lines[b..next_b].concat(),
+                mine: mine_lines[m..next_m].concat(),
+                theirs: theirs_lines[t..next_t].concat(),
+            });
+        }
+
+        if stable.is_none() {
+            break;
+        }
+        out.push(mine_lines[next_m]);
+        (b, m, t) = (next_b + 1, next_m + 1, next_t + 1);
+    }
+
+    MergeResult {
+        content: out.content,
+        clean: conflicts.is_empty(),
+        conflicts,
+    }
+}
+
+/// Three-way merge of `mine` (the editor) and `theirs` (the file on disk)
+/// against their common `base` (last saved content). Non-overlapping changes
+/// are combined; overlapping ones get conflict markers.
+#[tauri::command]
+pub async fn merge_documents(
+    base: String,
+    mine: String,
+    theirs: String,
+    options: Option<MergeOptions>,
+) -> Result<MergeResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        merge(&base, &mine, &theirs, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Failed to merge documents: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(diff: &DocumentDiff) -> Vec<(DiffKind, &str)> {
+        diff.segments.iter().map(|s| (s.kind, s.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = compare(
+            "# Title\r\nold line\r\nsame\r\n",
+            "# Title\r\nnew line\r\nsame\r\nadded",
+            DiffMode::Line,
+            &DiffOptions::default(),
+        );
+        assert!(!diff.identical);
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (DiffKind::Equal, "# Title"),
+                (DiffKind::Delete, "old line"),
+                (DiffKind::Insert, "new line"),
+                (DiffKind::Equal, "same"),
+                (DiffKind::Insert, "added"),
+            ]
+        );
+        assert_eq!((diff.segments[1].old_line, diff.segments[1].new_line), (Some(2), None));
+        assert_eq!((diff.segments[4].old_line, diff.segments[4].new_line), (None, Some(4)));
+        assert_eq!(
+            diff.stats,
+            DiffStats {
+                insertions: 2,
+                deletions: 1,
+                unchanged: 2
+            }
+        );
+        assert!(diff.html.is_none());
+    }
+
+    #[test]
+    fn test_ignore_whitespace_keeps_original_text() {
+        let options = DiffOptions {
+            ignore_whitespace: true,
+            ..Default::default()
+        };
+        let diff = compare("a  b\nc\n", "a b\nc  \n", DiffMode::Line, &options);
+        assert!(diff.identical);
+        assert_eq!(kinds(&diff), vec![(DiffKind::Equal, "a b"), (DiffKind::Equal, "c  ")]);
+        assert!(!compare("a  b", "a b", DiffMode::Line, &DiffOptions::default()).identical);
+    }
+
+    #[test]
+    fn test_word_diff_merges_runs() {
+        let diff = compare(
+            "The quick brown fox",
+            "The slow brown dog jumps",
+            DiffMode::Word,
+            &DiffOptions::default(),
+        );
+        let joined_new: String = diff
+            .segments
+            .iter()
+            .filter(|s| s.kind != DiffKind::Delete)
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(joined_new, "The slow brown dog jumps");
+        assert!(diff
+            .segments
+            .windows(2)
+            .all(|pair| pair[0].kind != pair[1].kind));
+        assert_eq!(diff.stats.deletions, 2);
+        assert_eq!(diff.stats.insertions, 3);
+        assert_eq!(diff.stats.unchanged, 2);
+    }
+
+    #[test]
+    fn test_html_rendering() {
+        let options = DiffOptions {
+            html: true,
+            ..Default::default()
+        };
+        let html = compare("a <b>", "a <i>", DiffMode::Word, &options).html.unwrap();
+        assert_eq!(
+            html,
+            "<div class=\"vmark-diff vmark-diff-word\">a <del>&lt;b&gt;</del><ins>&lt;i&gt;</ins></div>"
+        );
+        let html = compare("x\n", "y\n", DiffMode::Line, &options).html.unwrap();
+        assert!(html.contains("<tr class=\"delete\"><td class=\"old\">1</td><td class=\"new\"></td>"));
+        assert!(html.contains("<td class=\"sign\">+</td><td class=\"text\">y</td>"));
+    }
+
+    #[test]
+    fn test_identical() {
+        let diff = compare("same\n", "same\n", DiffMode::Word, &DiffOptions::default());
+        assert!(diff.identical);
+        assert_eq!(diff.stats.insertions + diff.stats.deletions, 0);
+    }
+
+    fn merged(base: &str, mine: &str, theirs: &str) -> MergeResult {
+        merge(base, mine, theirs, &MergeOptions::default())
+    }
+
+    #[test]
+    fn test_merge_combines_separate_changes() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let result = merged(base, "ONE\ntwo\nthree\nfour\n", "one\ntwo\nthree\nFOUR\nfive\n");
+        assert!(result.clean);
+        assert_eq!(result.content, "ONE\ntwo\nthree\nFOUR\nfive\n");
+
+        // The same edit on both sides is not a conflict
+        let result = merged(base, "one\n2\nthree\nfour\n", "one\n2\nthree\nfour\n");
+        assert!(result.clean);
+        assert_eq!(result.content, "one\n2\nthree\nfour\n");
+
+        // One side deletes, the other leaves the line alone
+        let result = merged(base, "one\nthree\nfour\n", base);
+        assert_eq!(result.content, "one\nthree\nfour\n");
+    }
+
+    #[test]
+    fn test_merge_conflict_markers() {
+        let result = merged("a\nb\nc\n", "a\nmine\nc\n", "a\ntheirs\nc\n");
+        assert!(!result.clean);
+        assert_eq!(
+            result.content,
+            "a\n<<<<<<< mine\nmine\n=======\ntheirs\n>>>>>>> theirs\nc\n"
+        );
+        assert_eq!(
+            result.conflicts,
+            vec![MergeConflict {
+                start_line: 2,
+                end_line: 6,
+                base: "b\n".into(),
+                mine: "mine\n".into(),
+                theirs: "theirs\n".into(),
+            }]
+        );
+
+        let options = MergeOptions {
+            mine_label: "Editor".into(),
+            theirs_label: "Disk".into(),
+            show_base: true,
+        };
+        let result = merge("a\nb\n", "a\nx\n", "a\n", &options);
+        assert_eq!(
+            result.content,
+            "a\n<<<<<<< Editor\nx\n||||||| base\nb\n=======\n>>>>>>> Disk\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_line_breaks() {
+        // Appending after a last line without a newline isn't a conflict
+        let result = merged("a\r\nb", "a\r\nb\r\nmine", "A\r\nb");
+        assert!(result.clean);
+        assert_eq!(result.content, "A\r\nb\r\nmine");
+
+        // Conflict markers use the document's line breaks
+        let result = merged("x", "mine", "theirs");
+        assert_eq!(
+            result.content,
+            "<<<<<<< mine\nmine\n=======\ntheirs\n>>>>>>> theirs\n"
+        );
+        assert!(merged("", "", "").clean);
+    }
+}
@@ -0,0 +1,1074 @@
+//! Semantic Search
+//!
+//! Embedding vectors for a workspace's notes, behind `semantic_search` and
+//! `related_notes`. Notes are split into chunks at headings and paragraph
+//! breaks, and each chunk is embedded by an Ollama embedding model
+//! (`/api/embed`, `nomic-embed-text` by default).
+//!
+//! The index lives in `<root>/.vmark/embeddings/`: `index.json` holds the
+//! chunk metadata and `vectors.bin` the vectors (little-endian f32, one row
+//! per chunk in index order). It is loaded on the first query and refreshed
+//! before each one; the file watcher (`apply_fs_change`) also schedules a
+//! refresh when notes are saved. A refresh re-reads only notes whose mtime
+//! changed, and chunks whose text didn't change keep their vectors.
+//! Switching models re-embeds everything.
+//!
+//! Requests go to the endpoint in Settings → Advanced (`semanticSearch`),
+//! never to one named by the workspace. Text sent to a server off this
+//! machine goes through the prompt redaction pass first (see
+//! `ai_redaction`).
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::ai_redaction::{self, Redactor};
+use crate::link_index::{modified_ms, note_title};
+use crate::settings::PromptRedactionSettings;
+use crate::watcher::FsChangeEvent;
+use crate::file_finder::{collect_notes, relative_path};
+
+const INDEX_DIR: &str = "embeddings";
+const INDEX_FILE: &str = "index.json";
+const VECTORS_FILE: &str = "vectors.bin";
+const INDEX_VERSION: u32 = 1;
+
+const DEFAULT_MODEL: &str = "nomic-embed-text";
+const DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+/// A chunk is closed at the next paragraph break past this size
+const MAX_CHUNK_CHARS: usize = 1500;
+const SNIPPET_CHARS: usize = 200;
+
+/// Texts per embedding request
+const EMBED_BATCH: usize = 32;
+const REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Delay before a watcher-triggered refresh, so a burst of saves is one refresh
+const REFRESH_DELAY: Duration = Duration::from_secs(2);
+
+const DEFAULT_K: usize = 10;
+const MAX_K: usize = 100;
+
+/// Loaded indexes keyed by workspace root
+static INDEXES: Mutex<Option<HashMap<String, EmbeddingIndex>>> = Mutex::new(None);
+
+/// Workspaces with a watcher-triggered refresh waiting to run
+static PENDING_REFRESHES: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// One refresh at a time, so two don't embed the same notes
+static REFRESH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ChunkMeta {
+    heading: Option<String>,
+    /// 1-based line where the chunk starts
+    line: usize,
+    /// Hash of the embedded text
+    hash: String,
+    snippet: String,
+}
+
+#[derive(Debug, Clone)]
+struct Chunk {
+    meta: ChunkMeta,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedNote {
+    title: String,
+    modified: Option<i64>,
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Debug, Clone)]
+struct EmbeddingIndex {
+    model: String,
+    /// Keyed by workspace-relative path
+    notes: BTreeMap<String, IndexedNote>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NoteMeta {
+    title: String,
+    modified: Option<i64>,
+    chunks: Vec<ChunkMeta>,
+}
+
+/// Contents of `index.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct IndexFile {
+    version: u32,
+    model: String,
+    dimensions: usize,
+    notes: BTreeMap<String, NoteMeta>,
+}
+
+/// A chunk of a note, before embedding
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChunkText {
+    pub heading: Option<String>,
+    /// 1-based line where the chunk starts
+    pub line: usize,
+    pub text: String,
+}
+
+/// A chunk of a note that matched a query, with its full text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Passage {
+    /// Workspace-relative note path
+    pub path: String,
+    pub title: String,
+    pub heading: Option<String>,
+    pub line: usize,
+    pub text: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticMatch {
+    /// Workspace-relative note path
+    pub path: String,
+    pub title: String,
+    /// Heading above the best-matching chunk
+    pub heading: Option<String>,
+    /// 1-based line of the best-matching chunk
+    pub line: usize,
+    pub snippet: String,
+    /// Cosine similarity, higher is closer
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingIndexStatus {
+    pub model: String,
+    pub notes: usize,
+    pub chunks: usize,
+}
+
+// ============================================================================
+// Chunking
+// ============================================================================
+
+fn heading_text(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) || !trimmed[level..].starts_with(' ') {
+        return None;
+    }
+    let text = trimmed[level..].trim().trim_end_matches('#').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Split a note at headings and at paragraph breaks once a chunk is long
+/// enough. Front matter is skipped; fenced code stays in one piece.
+pub(crate) fn chunk_note(content: &str) -> Vec<ChunkText> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if lines.first().is_some_and(|l| l.trim_end() == "---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            start = end + 2;
+        }
+    }
+
+    let mut chunks = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut current = String::new();
+    let mut current_line = start + 1;
+    let mut in_fence = false;
+    let mut flush = |current: &mut String, heading: &Option<String>, line: usize| {
+        let text = current.trim();
+        if !text.is_empty() {
+            chunks.push(ChunkText {
+                heading: heading.clone(),
+                line,
+                text: text.to_string(),
+            });
+        }
+        current.clear();
+    };
+
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(text) = heading_text(line) {
+                flush(&mut current, &heading, current_line);
+                heading = Some(text);
+                current_line = i + 1;
+            } else if line.trim().is_empty() && current.len() >= MAX_CHUNK_CHARS {
+                flush(&mut current, &heading, current_line);
+                current_line = i + 2;
+                continue;
+            }
+        }
+        if current.trim().is_empty() && line.trim().is_empty() {
+            current_line = i + 2;
+            continue;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    flush(&mut current, &heading, current_line);
+    chunks
+}
+
+/// What gets embedded: the chunk with its note title and heading for context.
+fn embedding_text(title: &str, chunk: &ChunkText) -> String {
+    match &chunk.heading {
+        Some(heading) => format!("{}\n{}\n\n{}", title, heading, chunk.text),
+        None => format!("{}\n\n{}", title, chunk.text),
+    }
+}
+
+fn snippet(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}…", &collapsed[..end]),
+        None => collapsed,
+    }
+}
+
+fn text_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+// ============================================================================
+// Ranking
+// ============================================================================
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+/// Mean of a note's chunk vectors.
+fn centroid(note: &IndexedNote) -> Option<Vec<f32>> {
+    let first = note.chunks.first()?;
+    let mut sum = vec![0.0f32; first.vector.len()];
+    for chunk in &note.chunks {
+        for (s, v) in sum.iter_mut().zip(&chunk.vector) {
+            *s += v;
+        }
+    }
+    let count = note.chunks.len() as f32;
+    Some(sum.into_iter().map(|s| s / count).collect())
+}
+
+/// The `k` notes closest to `query`, each with its best chunk.
+fn rank(
+    index: &EmbeddingIndex,
+    query: &[f32],
+    k: usize,
+    exclude: Option<&str>,
+) -> Vec<SemanticMatch> {
+    let mut matches: Vec<SemanticMatch> = index
+        .notes
+        .iter()
+        .filter(|(path, _)| Some(path.as_str()) != exclude)
+        .filter_map(|(path, note)| {
+            let (score, chunk) = note
+                .chunks
+                .iter()
+                .map(|chunk| (cosine(query, &chunk.vector), chunk))
+                .max_by(|a, b| a.0.total_cmp(&b.0))?;
+            Some(SemanticMatch {
+                path: path.clone(),
+                title: note.title.clone(),
+                heading: chunk.meta.heading.clone(),
+                line: chunk.meta.line,
+                snippet: chunk.meta.snippet.clone(),
+                score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    matches.truncate(k);
+    matches
+}
+
+fn clamp_k(k: Option<usize>) -> usize {
+    k.unwrap_or(DEFAULT_K).clamp(1, MAX_K)
+}
+
+// ============================================================================
+// Storage
+// ============================================================================
+
+fn index_dir(root: &Path) -> PathBuf {
+    root.join(".vmark").join(INDEX_DIR)
+}
+
+fn load_index(root: &Path) -> Option<EmbeddingIndex> {
+    let dir = index_dir(root);
+    let file: IndexFile =
+        serde_json::from_str(&fs::read_to_string(dir.join(INDEX_FILE)).ok()?).ok()?;
+    if file.version != INDEX_VERSION {
+        return None;
+    }
+    let bytes = fs::read(dir.join(VECTORS_FILE)).ok()?;
+    let total: usize = file.notes.values().map(|n| n.chunks.len()).sum();
+    let expected = total
+        .checked_mul(file.dimensions)
+        .and_then(|n| n.checked_mul(4));
+    if expected != Some(bytes.len()) {
+        tracing::warn!("[Embeddings] Vector file doesn't match the index, rebuilding");
+        return None;
+    }
+    let mut rows = bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect::<Vec<_>>()
+        .into_iter();
+    let notes = file
+        .notes
+        .into_iter()
+        .map(|(path, note)| {
+            let chunks = note
+                .chunks
+                .into_iter()
+                .map(|meta| Chunk {
+                    meta,
+                    vector: rows.by_ref().take(file.dimensions).collect(),
+                })
+                .collect();
+            let indexed = IndexedNote {
+                title: note.title,
+                modified: note.modified,
+                chunks,
+            };
+            (path, indexed)
+        })
+        .collect();
+    Some(EmbeddingIndex {
+        model: file.model,
+        notes,
+    })
+}
+
+fn save_index(root: &Path, index: &EmbeddingIndex) -> Result<(), String> {
+    let dir = index_dir(root);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create index folder: {}", e))?;
+    let chunks = || index.notes.values().flat_map(|n| &n.chunks);
+    let dimensions = chunks().next().map_or(0, |c| c.vector.len());
+    if chunks().any(|c| c.vector.len() != dimensions) {
+        return Err("Embedding model returned vectors of different sizes".to_string());
+    }
+
+    let size = chunks()
+        .count()
+        .checked_mul(dimensions)
+        .and_then(|n| n.checked_mul(4))
+        .ok_or("Embedding index is too large")?;
+    let mut vectors = Vec::with_capacity(size);
+    for value in chunks().flat_map(|c| &c.vector) {
+        vectors.extend_from_slice(&value.to_le_bytes());
+    }
+    let file = IndexFile {
+        version: INDEX_VERSION,
+        model: index.model.clone(),
+        dimensions,
+        notes: index
+            .notes
+            .iter()
+            .map(|(path, note)| {
+                let meta = NoteMeta {
+                    title: note.title.clone(),
+                    modified: note.modified,
+                    chunks: note.chunks.iter().map(|c| c.meta.clone()).collect(),
+                };
+                (path.clone(), meta)
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string(&file).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(&dir.join(VECTORS_FILE), &vectors)?;
+    crate::app_paths::atomic_write_file(&dir.join(INDEX_FILE), json.as_bytes())
+}
+
+// ============================================================================
+// Embedding
+// ============================================================================
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// The embedding endpoint from settings.
+fn settings_endpoint(settings: &crate::settings::Settings) -> String {
+    let endpoint = settings.advanced.semantic_search.endpoint.trim();
+    if endpoint.is_empty() {
+        DEFAULT_ENDPOINT.to_string()
+    } else {
+        endpoint.trim_end_matches('/').to_string()
+    }
+}
+
+/// Texts as they'll be sent: redacted when the endpoint isn't local.
+fn outgoing_texts(
+    endpoint: &str,
+    redaction: &PromptRedactionSettings,
+    texts: &[String],
+) -> Result<Vec<String>, String> {
+    let redactor = if ai_redaction::is_remote("ollama-api", Some(endpoint)) {
+        Redactor::from_settings(redaction)?
+    } else {
+        None
+    };
+    Ok(match redactor {
+        Some(redactor) => texts.iter().map(|t| redactor.redact(t).text).collect(),
+        None => texts.to_vec(),
+    })
+}
+
+async fn embed(model: &str, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+    let settings = crate::settings::current();
+    let endpoint = settings_endpoint(&settings);
+    let texts = outgoing_texts(&endpoint, &settings.advanced.prompt_redaction, texts)?;
+    let client = crate::http_client::builder_for(Some(&endpoint))?
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut vectors = Vec::with_capacity(texts.len());
+    for batch in texts.chunks(EMBED_BATCH) {
+        let resp = client
+            .post(format!("{}/api/embed", endpoint))
+            .json(&serde_json::json!({ "model": model, "input": batch }))
+            .send()
+            .await
+            .map_err(|e| format!("Embedding request failed (is Ollama running?): {}", e))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(format!("Embedding model error {}: {}", status, text));
+        }
+        let body: EmbedResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse embedding response: {}", e))?;
+        if body.embeddings.len() != batch.len() {
+            return Err("Embedding model returned the wrong number of vectors".to_string());
+        }
+        vectors.extend(body.embeddings);
+    }
+    Ok(vectors)
+}
+
+// ============================================================================
+// Refresh
+// ============================================================================
+
+/// A changed note, chunked and waiting for vectors
+struct PendingNote {
+    path: String,
+    title: String,
+    modified: Option<i64>,
+    chunks: Vec<(ChunkMeta, String)>,
+}
+
+fn read_note(root: &Path, path: &Path, modified: Option<i64>) -> PendingNote {
+    let rel = relative_path(root, path);
+    let content = read_note_text(path);
+    let title = note_title(&rel, &content);
+    let chunks = chunk_note(&content)
+        .into_iter()
+        .map(|chunk| {
+            let text = embedding_text(&title, &chunk);
+            let meta = ChunkMeta {
+                heading: chunk.heading,
+                line: chunk.line,
+                hash: text_hash(&text),
+                snippet: snippet(&chunk.text),
+            };
+            (meta, text)
+        })
+        .collect();
+    PendingNote {
+        path: rel,
+        title,
+        modified,
+        chunks,
+    }
+}
+
+
+fn lock_indexes(
+) -> Result<std::sync::MutexGuard<'static, Option<HashMap<String, EmbeddingIndex>>>, String> {
+    INDEXES.lock().map_err(|e| format!("Lock error: {}", e))
+}
+
+/// Bring `root`'s index up to date, switching to `model` when given.
+async fn refresh(root_path: &str, model: Option<String>) -> Result<(), String> {
+    let root = Path::new(root_path);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", root_path));
+    }
+    let _refreshing = REFRESH_LOCK.lock().await;
+
+    // Snapshot what's indexed
+    let (model, known) = {
+        let mut guard = lock_indexes()?;
+        let index = guard
+            .get_or_insert_with(HashMap::new)
+            .entry(root_path.to_string())
+            .or_insert_with(|| {
+                load_index(root).unwrap_or_else(|| EmbeddingIndex {
+                    model: DEFAULT_MODEL.to_string(),
+                    notes: BTreeMap::new(),
+                })
+            });
+        let model = model.filter(|m| !m.trim().is_empty());
+        if model.as_ref().is_some_and(|m| *m != index.model) {
+            index.notes.clear();
+        }
+        if let Some(model) = model {
+            index.model = model;
+        }
+        let known: HashMap<String, Option<i64>> = index
+            .notes
+            .iter()
+            .map(|(path, note)| (path.clone(), note.modified))
+            .collect();
+        (index.model.clone(), known)
+    };
+
+    // Read changed notes
+    let (current, changed) = tauri::async_runtime::spawn_blocking({
+        let root = root.to_path_buf();
+        move || {
+            let mut current = HashSet::new();
+            let mut changed = Vec::new();
+            for path in collect_notes(&root, &crate::workspace::workspace_excludes(&root)) {
+                let rel = relative_path(&root, &path);
+                let modified = modified_ms(&path);
+                if known.get(&rel) != Some(&modified) {
+                    changed.push(read_note(&root, &path, modified));
+                }
+                current.insert(rel);
+            }
+            (current, changed)
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to read notes: {}", e))?;
+
+    let removed = {
+        let guard = lock_indexes()?;
+        guard
+            .as_ref()
+            .and_then(|indexes| indexes.get(root_path))
+            .is_some_and(|index| index.notes.keys().any(|path| !current.contains(path)))
+    };
+    if changed.is_empty() && !removed {
+        return Ok(());
+    }
+
+    // Embed chunks whose text isn't indexed yet
+    let reusable: HashMap<String, Vec<f32>> = {
+        let wanted: HashSet<&str> = changed
+            .iter()
+            .flat_map(|n| &n.chunks)
+            .map(|(meta, _)| meta.hash.as_str())
+            .collect();
+        let guard = lock_indexes()?;
+        guard
+            .as_ref()
+            .and_then(|indexes| indexes.get(root_path))
+            .map(|index| {
+                index
+                    .notes
+                    .values()
+                    .flat_map(|n| &n.chunks)
+                    .filter(|c| wanted.contains(c.meta.hash.as_str()))
+                    .map(|c| (c.meta.hash.clone(), c.vector.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut needed: Vec<(String, String)> = Vec::new();
+    let mut seen = HashSet::new();
+    for (meta, text) in changed.iter().flat_map(|n| &n.chunks) {
+        if !reusable.contains_key(&meta.hash) && seen.insert(meta.hash.clone()) {
+            needed.push((meta.hash.clone(), text.clone()));
+        }
+    }
+    let texts: Vec<String> = needed.iter().map(|(_, text)| text.clone()).collect();
+    let fresh = embed(&model, &texts).await?;
+    let mut vectors = reusable;
+    vectors.extend(needed.into_iter().map(|(hash, _)| hash).zip(fresh));
+
+    // Apply and save
+    let mut guard = lock_indexes()?;
+    let Some(index) = guard
+        .as_mut()
+        .and_then(|indexes| indexes.get_mut(root_path))
+    else {
+        return Ok(());
+    };
+    if index.model != model {
+        // Switched models while embedding; the next refresh starts over
+        return Ok(());
+    }
+    index.notes.retain(|path, _| current.contains(path));
+    for note in changed {
+        let chunks = note
+            .chunks
+            .into_iter()
+            .filter_map(|(meta, _)| {
+                let vector = vectors.get(&meta.hash)?.clone();
+                Some(Chunk { meta, vector })
+            })
+            .collect();
+        index.notes.insert(
+            note.path,
+            IndexedNote {
+                title: note.title,
+                modified: note.modified,
+                chunks,
+            },
+        );
+    }
+    save_index(root, index)
+}
+
+/// Schedule a refresh of loaded indexes when a note changes. Called by
+/// `watcher` for every change it reports; a no-op for workspaces nobody has
+/// searched.
+pub(crate) fn apply_fs_change(event: &FsChangeEvent) {
+    let roots: Vec<String> = {
+        let Ok(guard) = INDEXES.lock() else {
+            return;
+        };
+        let Some(indexes) = guard.as_ref() else {
+            return;
+        };
+        indexes
+            .keys()
+            .filter(|root| {
+                event
+                    .paths
+                    .iter()
+                    .chain(event.from.iter())
+                    .chain(event.to.iter())
+                    .any(|path| is_note_in(Path::new(root), Path::new(path)))
+            })
+            .cloned()
+            .collect()
+    };
+    for root in roots {
+        schedule_refresh(root);
+    }
+}
+
+/// A markdown file (or folder) under `root` outside hidden folders, so the
+/// index's own writes under `.vmark` don't trigger refreshes.
+fn is_note_in(root: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(root) else {
+        return false;
+    };
+    let names: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let Some(last) = names.last() else {
+        return false;
+    };
+    names.iter().all(|n| !n.starts_with('.'))
+        && (crate::file_tree::is_markdown(last) || !last.contains('.'))
+}
+
+fn schedule_refresh(root: String) {
+    {
+        let mut pending = PENDING_REFRESHES.lock().unwrap_or_else(|e| e.into_inner());
+        if !pending
+            .get_or_insert_with(HashSet::new)
+            .insert(root.clone())
+        {
+            return;
+        }
+    }
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(REFRESH_DELAY).await;
+        if let Some(pending) = PENDING_REFRESHES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+        {
+            pending.remove(&root);
+        }
+        if let Err(e) = refresh(&root, None).await {
+            tracing::warn!("[Embeddings] Refresh of {} failed: {}", root, e);
+        }
+    });
+}
+
+fn with_index<T>(root_path: &str, f: impl FnOnce(&EmbeddingIndex) -> T) -> Result<T, String> {
+    let guard = lock_indexes()?;
+    let index = guard
+        .as_ref()
+        .and_then(|indexes| indexes.get(root_path))
+        .ok_or("Embedding index not loaded")?;
+    Ok(f(index))
+}
+
+/// Note text, or nothing for encrypted notes.
+pub(crate) fn read_note_text(path: &Path) -> String {
+    if crate::encryption::is_encrypted_path(path) {
+        return String::new();
+    }
+    fs::read(path)
+        .map(|b| String::from_utf8_lossy(&b).into_owned())
+        .unwrap_or_default()
+}
+
+/// The `limit` chunks closest to `query` across the workspace, best first,
+/// with their text read back from the notes.
+pub(crate) async fn search_passages(
+    workspace_root: &str,
+    query: &str,
+    limit: usize,
+    model: Option<String>,
+) -> Result<Vec<Passage>, String> {
+    refresh(workspace_root, model).await?;
+    let model = with_index(workspace_root, |index| index.model.clone())?;
+    let vector = embed(&model, &[query.to_string()])
+        .await?
+        .pop()
+        .ok_or("Embedding model returned no vector")?;
+    let vector = &vector;
+    let hits = with_index(workspace_root, |index| {
+        let mut hits: Vec<(f32, String, String, usize)> = index
+            .notes
+            .iter()
+            .flat_map(|(path, note)| {
+                note.chunks.iter().map(move |chunk| {
+                    let score = cosine(vector, &chunk.vector);
+                    (score, path.clone(), note.title.clone(), chunk.meta.line)
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.0.total_cmp(&a.0));
+        hits.truncate(limit);
+        hits
+    })?;
+
+    let root = Path::new(workspace_root);
+    let mut contents: HashMap<String, Vec<ChunkText>> = HashMap::new();
+    Ok(hits
+        .into_iter()
+        .filter_map(|(score, path, title, line)| {
+            let chunks = contents
+                .entry(path.clone())
+                .or_insert_with(|| chunk_note(&read_note_text(&root.join(&path))));
+            let chunk = chunks.iter().find(|c| c.line == line)?;
+            Some(Passage {
+                heading: chunk.heading.clone(),
+                text: chunk.text.clone(),
+                path,
+                title,
+                line,
+                score,
+            })
+        })
+        .collect())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Notes closest in meaning to `query`, best first. `model` selects the
+/// Ollama embedding model (switching models re-embeds the workspace).
+#[tauri::command]
+pub async fn semantic_search(
+    workspace_root: String,
+    query: String,
+    k: Option<usize>,
+    model: Option<String>,
+) -> Result<Vec<SemanticMatch>, String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    refresh(&workspace_root, model).await?;
+    let model = with_index(&workspace_root, |index| index.model.clone())?;
+    let vector = embed(&model, &[query])
+        .await?
+        .pop()
+        .ok_or("Embedding model returned no vector")?;
+    with_index(&workspace_root, |index| {
+        rank(index, &vector, clamp_k(k), None)
+    })
+}
+
+/// Notes most similar to the note at `path` (absolute or workspace-relative).
+#[tauri::command]
+pub async fn related_notes(
+    workspace_root: String,
+    path: String,
+    k: Option<usize>,
+    model: Option<String>,
+) -> Result<Vec<SemanticMatch>, String> {
+    refresh(&workspace_root, model).await?;
+    let rel = relative_path(Path::new(&workspace_root), Path::new(&path));
+    with_index(&workspace_root, |index| {
+        let note = index
+            .notes
+            .get(&rel)
+            .ok_or_else(|| format!("Note is not indexed: {}", rel))?;
+        let Some(vector) = centroid(note) else {
+            return Ok(Vec::new());
+        };
+        Ok(rank(index, &vector, clamp_k(k), Some(&rel)))
+    })?
+}
+
+/// Drop a workspace's index and embed every note again.
+#[tauri::command]
+pub async fn rebuild_embedding_index(
+    workspace_root: String,
+    model: Option<String>,
+) -> Result<EmbeddingIndexStatus, String> {
+    if let Some(index) = lock_indexes()?
+        .as_mut()
+        .and_then(|indexes| indexes.get_mut(&workspace_root))
+    {
+        index.notes.clear();
+    } else {
+        let _ = fs::remove_dir_all(index_dir(Path::new(&workspace_root)));
+    }
+    refresh(&workspace_root, model).await?;
+    with_index(&workspace_root, |index| EmbeddingIndexStatus {
+        model: index.model.clone(),
+        notes: index.notes.len(),
+        chunks: index.notes.values().map(|n| n.chunks.len()).sum(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(heading: Option<&str>, line: usize, vector: Vec<f32>) -> Chunk {
+        Chunk {
+            meta: ChunkMeta {
+                heading: heading.map(str::to_string),
+                line,
+                hash: String::new(),
+                snippet: format!("line {}", line),
+            },
+            vector,
+        }
+    }
+
+    fn index(notes: &[(&str, Vec<Chunk>)]) -> EmbeddingIndex {
+        EmbeddingIndex {
+            model: DEFAULT_MODEL.into(),
+            notes: notes
+                .iter()
+                .map(|(path, chunks)| {
+                    let note = IndexedNote {
+                        title: path.to_string(),
+                        modified: Some(1),
+                        chunks: chunks.clone(),
+                    };
+                    (path.to_string(), note)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_chunk_note_splits_at_headings() {
+        let content = "---\ntitle: X\n---\nIntro line\n\n# First\nBody one\n\n```\n# not a heading\n```\n## Second\n\nBody two\n";
+        let chunks = chunk_note(content);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[0],
+            ChunkText {
+                heading: None,
+                line: 4,
+                text: "Intro line".into()
+            }
+        );
+        assert_eq!(chunks[1].heading.as_deref(), Some("First"));
+        assert_eq!(chunks[1].line, 6);
+        assert!(chunks[1].text.contains("# not a heading"));
+        assert_eq!(chunks[2].heading.as_deref(), Some("Second"));
+        assert_eq!(chunks[2].line, 12);
+    }
+
+    #[test]
+    fn test_chunk_note_splits_long_sections_at_paragraphs() {
+        let paragraph = "word ".repeat(200);
+        let content = format!("{p}\n\n{p}\n\n{p}\n", p = paragraph.trim());
+        let chunks = chunk_note(&content);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].line, 1);
+        assert_eq!(chunks[1].line, 5);
+        assert!(chunk_note("").is_empty());
+    }
+
+    #[test]
+    fn test_snippet_and_embedding_text() {
+        assert_eq!(snippet("  a\n\n b  c "), "a b c");
+        assert!(snippet(&"x".repeat(500)).ends_with('…'));
+        let text = ChunkText {
+            heading: Some("Setup".into()),
+            line: 1,
+            text: "Install it".into(),
+        };
+        assert_eq!(embedding_text("Guide", &text), "Guide\nSetup\n\nInstall it");
+    }
+
+    #[test]
+    fn test_rank_picks_best_chunk_per_note() {
+        let idx = index(&[
+            (
+                "a.md",
+                vec![
+                    chunk(None, 1, vec![1.0, 0.0]),
+                    chunk(Some("Deep"), 9, vec![0.0, 1.0]),
+                ],
+            ),
+            ("b.md", vec![chunk(None, 1, vec![0.7, 0.7])]),
+            ("c.md", vec![chunk(None, 1, vec![-1.0, 0.0])]),
+        ]);
+        let results = rank(&idx, &[0.0, 1.0], 2, None);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, "a.md");
+        assert_eq!(results[0].heading.as_deref(), Some("Deep"));
+        assert_eq!(results[0].line, 9);
+        assert_eq!(results[1].path, "b.md");
+
+        let related = rank(
+            &idx,
+            &centroid(&idx.notes["b.md"]).unwrap(),
+            5,
+            Some("b.md"),
+        );
+        assert!(related.iter().all(|m| m.path != "b.md"));
+        assert_eq!(related.last().unwrap().path, "c.md");
+    }
+
+    #[test]
+    fn test_cosine_edge_cases() {
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+        assert!((cosine(&[2.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let idx = index(&[
+            ("a.md", vec![chunk(None, 1, vec![0.5, -1.5, 2.0])]),
+            (
+                "notes/b.md",
+                vec![
+                    chunk(Some("H"), 3, vec![1.0, 2.0, 3.0]),
+                    chunk(None, 8, vec![4.0, 5.0, 6.0]),
+                ],
+            ),
+        ]);
+        save_index(dir.path(), &idx).unwrap();
+        let loaded = load_index(dir.path()).unwrap();
+        assert_eq!(loaded.model, DEFAULT_MODEL);
+        assert_eq!(loaded.notes.len(), 2);
+        assert_eq!(
+            loaded.notes["notes/b.md"].chunks[1].vector,
+            vec![4.0, 5.0, 6.0]
+        );
+        assert_eq!(
+            loaded.notes["notes/b.md"].chunks[0].meta.heading.as_deref(),
+            Some("H")
+        );
+
+        // A truncated vector file is ignored rather than misread
+        let vectors = index_dir(dir.path()).join(VECTORS_FILE);
+        let bytes = fs::read(&vectors).unwrap();
+        fs::write(&vectors, &bytes[..bytes.len() - 4]).unwrap();
+        assert!(load_index(dir.path()).is_none());
+
+        // So is one whose dimensions overflow the size check
+        fs::write(&vectors, &bytes).unwrap();
+        let index_file = index_dir(dir.path()).join(INDEX_FILE);
+        let mut json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&index_file).unwrap()).unwrap();
+        json["dimensions"] = serde_json::json!(usize::MAX);
+        fs::write(&index_file, json.to_string()).unwrap();
+        assert!(load_index(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_outgoing_texts_redacted_for_remote_endpoints() {
+        let redaction = PromptRedactionSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let texts = vec!["Mail ada@example.com".to_string()];
+        assert_eq!(
+            outgoing_texts("http://localhost:11434", &redaction, &texts).unwrap(),
+            texts
+        );
+        assert_eq!(
+            outgoing_texts("https://embed.example.com", &redaction, &texts).unwrap(),
+            vec!["Mail [EMAIL_1]".to_string()]
+        );
+        let off = PromptRedactionSettings::default();
+        assert_eq!(
+            outgoing_texts("https://embed.example.com", &off, &texts).unwrap(),
+            texts
+        );
+    }
+
+    #[test]
+    fn test_settings_endpoint_defaults_when_blank() {
+        let mut settings = crate::settings::Settings::default();
+        settings.advanced.semantic_search.endpoint = " ".into();
+        assert_eq!(settings_endpoint(&settings), DEFAULT_ENDPOINT);
+        settings.advanced.semantic_search.endpoint = "http://gpu-box:11434/".into();
+        assert_eq!(settings_endpoint(&settings), "http://gpu-box:11434");
+    }
+
+    #[test]
+    fn test_is_note_in_skips_hidden_and_other_files() {
+        let root = Path::new("/ws");
+        assert!(is_note_in(root, Path::new("/ws/notes/a.md")));
+        assert!(is_note_in(root, Path::new("/ws/notes")));
+        assert!(!is_note_in(
+            root,
+            Path::new("/ws/.vmark/embeddings/index.json")
+        ));
+        assert!(!is_note_in(root, Path::new("/ws/image.png")));
+        assert!(!is_note_in(root, Path::new("/elsewhere/a.md")));
+    }
+}
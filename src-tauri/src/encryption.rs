@@ -0,0 +1,466 @@
+//! Encrypted documents (`.md.enc`).
+//!
+//! An encrypted document is a small header followed by the AES-256-GCM
+//! ciphertext of the markdown:
+//!
+//! ```text
+//! "VMARKENC" | version (1 byte) | argon2 m_cost, t_cost, p_cost (u32 LE each)
+//!            | salt (16 bytes) | nonce (12 bytes) | ciphertext + tag
+//! ```
+//!
+//! The key is derived from the passphrase with Argon2id using the salt and
+//! parameters in the header, and the header is authenticated as associated
+//! data. Once a document is opened its key stays in memory (never the
+//! passphrase) so saves don't ask again, until `lock_encrypted_document`.
+//! Keys and passphrases are zeroed when dropped, and key derivation runs on
+//! a blocking thread.
+//!
+//! Workspaces choose a policy in `vmark.editor.encryption`: "off", "ask"
+//! (offer to encrypt new documents) or "always".
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+use crate::app_paths;
+
+/// Extension appended to encrypted documents (`notes.md` -> `notes.md.enc`)
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Accepted values of the workspace `encryption` policy
+pub const ENCRYPTION_POLICIES: &[&str] = &["off", "ask", "always"];
+
+const MAGIC: &[u8; 8] = b"VMARKENC";
+const FORMAT_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 12 + SALT_LEN + NONCE_LEN;
+
+/// Argon2id cost for new documents (19 MiB, 2 passes)
+const DEFAULT_KDF: KdfParams = KdfParams {
+    m_cost: 19 * 1024,
+    t_cost: 2,
+    p_cost: 1,
+};
+/// Refuse headers asking for more memory than this (KiB)
+const MAX_M_COST: u32 = 1024 * 1024;
+/// Refuse headers asking for more passes than this
+const MAX_T_COST: u32 = 16;
+/// Refuse headers asking for more lanes than this
+const MAX_P_COST: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct KdfParams {
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+/// Parsed header of an encrypted document
+#[derive(Debug, Clone, PartialEq)]
+struct Header {
+    kdf: KdfParams,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&self.kdf.m_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.kdf.t_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.kdf.p_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Header, String> {
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err("Not an encrypted VMark document".to_string());
+        }
+        if bytes[MAGIC.len()] != FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported encrypted document version: {}",
+                bytes[MAGIC.len()]
+            ));
+        }
+        let u32_at = |offset: usize| {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_le_bytes(word)
+        };
+        let base = MAGIC.len() + 1;
+        let kdf = KdfParams {
+            m_cost: u32_at(base),
+            t_cost: u32_at(base + 4),
+            p_cost: u32_at(base + 8),
+        };
+        if kdf.m_cost > MAX_M_COST {
+            return Err("Encrypted document asks for too much memory".to_string());
+        }
+        if kdf.t_cost > MAX_T_COST || kdf.p_cost > MAX_P_COST {
+            return Err("Encrypted document asks for too much work".to_string());
+        }
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(&bytes[base + 12..base + 12 + SALT_LEN]);
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&bytes[base + 12 + SALT_LEN..HEADER_LEN]);
+        Ok(Header { kdf, salt, nonce })
+    }
+}
+
+/// Key for an opened document, with the salt/params it was derived from
+#[derive(Clone)]
+struct UnlockedKey {
+    key: Zeroizing<[u8; 32]>,
+    kdf: KdfParams,
+    salt: [u8; SALT_LEN],
+}
+
+/// Keys of opened documents, by path
+static UNLOCKED: Mutex<Option<HashMap<String, UnlockedKey>>> = Mutex::new(None);
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Zeroizing<[u8; 32]>, String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".to_string());
+    }
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(32))
+        .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn new_key(passphrase: &str, kdf: KdfParams) -> Result<UnlockedKey, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    Ok(UnlockedKey {
+        key: derive_key(passphrase, &salt, kdf)?,
+        kdf,
+        salt,
+    })
+}
+
+/// Encrypt `plaintext` with a fresh nonce.
+fn seal(key: &UnlockedKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let header = Header {
+        kdf: key.kdf,
+        salt: key.salt,
+        nonce,
+    }
+    .to_bytes();
+
+    let cipher = Aes256Gcm::new_from_slice(key.key.as_ref()).map_err(|e| e.to_string())?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut out = header;
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a document with a known key.
+fn open_with_key(key: &UnlockedKey, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let header = Header::parse(bytes)?;
+    let cipher = Aes256Gcm::new_from_slice(key.key.as_ref()).map_err(|e| e.to_string())?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(&header.nonce),
+            Payload {
+                msg: &bytes[HEADER_LEN..],
+                aad: &bytes[..HEADER_LEN],
+            },
+        )
+        .map_err(|_| "Wrong passphrase or damaged document".to_string())
+}
+
+/// Decrypt a document with a passphrase, returning the plaintext and its key.
+fn open_with_passphrase(passphrase: &str, bytes: &[u8]) -> Result<(Vec<u8>, UnlockedKey), String> {
+    let header = Header::parse(bytes)?;
+    let key = UnlockedKey {
+        key: derive_key(passphrase, &header.salt, header.kdf)?,
+        kdf: header.kdf,
+        salt: header.salt,
+    };
+    let plaintext = open_with_key(&key, bytes)?;
+    Ok((plaintext, key))
+}
+
+/// Whether `path` names an encrypted document (`*.enc`).
+pub fn is_encrypted_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(ENCRYPTED_EXTENSION))
+}
+
+fn encrypted_path_for(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(name)
+}
+
+fn remember_key(path: &str, key: UnlockedKey) {
+    if let Ok(mut guard) = UNLOCKED.lock() {
+        guard.get_or_insert_with(HashMap::new).insert(path.to_string(), key);
+    }
+}
+
+fn remembered_key(path: &str) -> Option<UnlockedKey> {
+    UNLOCKED
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref()?.get(path).cloned())
+}
+
+fn to_utf8(bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|_| "Decrypted document is not valid UTF-8".to_string())
+}
+
+/// Run blocking key derivation and file IO off the async runtime.
+async fn run_blocking<T: Send + 'static>(
+    what: &str,
+    job: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(job)
+        .await
+        .map_err(|e| format!("Failed to {what}: {e}"))?
+}
+
+fn encrypt_path(path: &str, passphrase: &str, delete_original: bool) -> Result<String, String> {
+    let source = Path::new(path);
+    if is_encrypted_path(source) {
+        return Err(format!("{} is already encrypted", path));
+    }
+    let target = encrypted_path_for(source);
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let plaintext = Zeroizing::new(fs::read(source).map_err(|e| format!("Failed to read {}: {}", path, e))?);
+    let key = new_key(passphrase, DEFAULT_KDF)?;
+    app_paths::atomic_write_file(&target, &seal(&key, &plaintext)?)?;
+
+    if delete_original {
+        fs::remove_file(source).map_err(|e| format!("Encrypted, but failed to remove {}: {}", path, e))?;
+    }
+    let target = target.to_string_lossy().into_owned();
+    remember_key(&target, key);
+    Ok(target)
+}
+
+fn decrypt_path(path: &str, passphrase: &str, delete_original: bool) -> Result<String, String> {
+    let source = Path::new(path);
+    if !is_encrypted_path(source) {
+        return Err(format!("{} is not an encrypted document", path));
+    }
+    let target = source.with_extension("");
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let bytes = fs::read(source).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (plaintext, _) = open_with_passphrase(passphrase, &bytes)?;
+    app_paths::atomic_write_file(&target, &Zeroizing::new(plaintext))?;
+
+    if delete_original {
+        fs::remove_file(source).map_err(|e| format!("Decrypted, but failed to remove {}: {}", path, e))?;
+    }
+    forget_key(path);
+    Ok(target.to_string_lossy().into_owned())
+}
+
+fn open_document(path: &str, passphrase: Option<&str>) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    match passphrase {
+        Some(passphrase) => {
+            let (plaintext, key) = open_with_passphrase(passphrase, &bytes)?;
+            remember_key(path, key);
+            to_utf8(plaintext)
+        }
+        None => {
+            let key = remembered_key(path).ok_or("locked")?;
+            to_utf8(open_with_key(&key, &bytes)?)
+        }
+    }
+}
+
+fn save_document(path: &str, content: &str, passphrase: Option<&str>) -> Result<(), String> {
+    let key = match passphrase {
+        Some(passphrase) => new_key(passphrase, DEFAULT_KDF)?,
+        None => remembered_key(path).ok_or("locked")?,
+    };
+    app_paths::atomic_write_file(Path::new(path), &seal(&key, content.as_bytes())?)?;
+    remember_key(path, key);
+    Ok(())
+}
+
+fn forget_key(path: &str) {
+    if let Ok(mut guard) = UNLOCKED.lock() {
+        if let Some(keys) = guard.as_mut() {
+            keys.remove(path);
+        }
+    }
+}
+
+/// Encrypt a markdown file to `<path>.enc`. With `delete_original`, the
+/// plaintext file is removed afterwards. Returns the encrypted path.
+#[tauri::command]
+pub async fn encrypt_file(path: String, passphrase: String, delete_original: bool) -> Result<String, String> {
+    let passphrase = Zeroizing::new(passphrase);
+    run_blocking("encrypt document", move || encrypt_path(&path, &passphrase, delete_original)).await
+}
+
+/// Decrypt `<name>.enc` back to `<name>`. With `delete_original`, the
+/// encrypted file is removed afterwards. Returns the plaintext path.
+#[tauri::command]
+pub async fn decrypt_file(path: String, passphrase: String, delete_original: bool) -> Result<String, String> {
+    let passphrase = Zeroizing::new(passphrase);
+    run_blocking("decrypt document", move || decrypt_path(&path, &passphrase, delete_original)).await
+}
+
+/// Read an encrypted document. Without a passphrase the key remembered from
+/// an earlier open is used (fails with "locked" if there is none).
+#[tauri::command]
+pub async fn open_encrypted_document(path: String, passphrase: Option<String>) -> Result<String, String> {
+    let passphrase = passphrase.map(Zeroizing::new);
+    run_blocking("open encrypted document", move || {
+        open_document(&path, passphrase.as_deref().map(String::as_str))
+    })
+    .await
+}
+
+/// Save an encrypted document. A passphrase is needed the first time (new
+/// document); afterwards the remembered key is reused with a fresh nonce.
+#[tauri::command]
+pub async fn save_encrypted_document(path: String, content: String, passphrase: Option<String>) -> Result<(), String> {
+    let content = Zeroizing::new(content);
+    let passphrase = passphrase.map(Zeroizing::new);
+    run_blocking("save encrypted document", move || {
+        save_document(&path, &content, passphrase.as_deref().map(String::as_str))
+    })
+    .await
+}
+
+/// Forget the key of an opened document (on tab close).
+#[tauri::command]
+pub fn lock_encrypted_document(path: String) {
+    forget_key(&path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests stay fast
+    const TEST_KDF: KdfParams = KdfParams {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    #[test]
+    fn round_trips_with_passphrase() {
+        let key = new_key("correct horse", TEST_KDF).unwrap();
+        let sealed = seal(&key, "# Journal\n\nsecret".as_bytes()).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+
+        let (plaintext, reopened) = open_with_passphrase("correct horse", &sealed).unwrap();
+        assert_eq!(plaintext, b"# Journal\n\nsecret");
+        assert_eq!(*reopened.key, *key.key);
+        assert_eq!(open_with_key(&reopened, &sealed).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let key = new_key("right", TEST_KDF).unwrap();
+        let sealed = seal(&key, b"text").unwrap();
+        assert!(open_with_passphrase("wrong", &sealed).is_err());
+        assert!(new_key("", TEST_KDF).is_err());
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let key = new_key("pass", TEST_KDF).unwrap();
+        let sealed = seal(&key, b"text").unwrap();
+
+        let mut body = sealed.clone();
+        *body.last_mut().unwrap() ^= 1;
+        assert!(open_with_key(&key, &body).is_err());
+
+        // The header is authenticated too
+        let mut header = sealed.clone();
+        header[HEADER_LEN - 1] ^= 1;
+        assert!(open_with_key(&key, &header).is_err());
+    }
+
+    #[test]
+    fn each_save_uses_a_fresh_nonce() {
+        let key = new_key("pass", TEST_KDF).unwrap();
+        let a = seal(&key, b"same").unwrap();
+        let b = seal(&key, b"same").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(Header::parse(&a).unwrap().salt, Header::parse(&b).unwrap().salt);
+    }
+
+    #[test]
+    fn rejects_foreign_and_hostile_headers() {
+        assert!(Header::parse(b"# just markdown").is_err());
+
+        let key = new_key("pass", TEST_KDF).unwrap();
+        let mut sealed = seal(&key, b"text").unwrap();
+        let costs = MAGIC.len() + 1;
+        for offset in [costs, costs + 4, costs + 8] {
+            let mut hostile = sealed.clone();
+            hostile[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            assert!(Header::parse(&hostile).is_err());
+        }
+        sealed[costs + 4..costs + 8].copy_from_slice(&MAX_T_COST.to_le_bytes());
+        assert!(Header::parse(&sealed).is_ok());
+    }
+
+    #[test]
+    fn encrypted_paths() {
+        assert!(is_encrypted_path(Path::new("/notes/journal.md.enc")));
+        assert!(!is_encrypted_path(Path::new("/notes/journal.md")));
+        assert_eq!(
+            encrypted_path_for(Path::new("/notes/journal.md")),
+            PathBuf::from("/notes/journal.md.enc")
+        );
+    }
+
+    #[test]
+    fn remembered_key_reopens_and_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("diary.md.enc").to_string_lossy().into_owned();
+        let key = new_key("pass", TEST_KDF).unwrap();
+        remember_key(&path, key);
+
+        save_document(&path, "Dear diary", None).unwrap();
+        assert!(fs::read(&path).unwrap().starts_with(MAGIC));
+        assert_eq!(open_document(&path, None).unwrap(), "Dear diary");
+
+        lock_encrypted_document(path.clone());
+        assert_eq!(open_document(&path, None), Err("locked".to_string()));
+    }
+}
@@ -0,0 +1,312 @@
+//! Export Metadata
+//!
+//! Collects what HTML/PDF/pandoc exports stamp into their headers, footers
+//! and document properties: title, author, keywords and date from the
+//! frontmatter, plus the workspace's git commit and the VMark version.
+//!
+//! The date is chosen so re-exporting an unchanged document gives the same
+//! output: the frontmatter `date`, else `SOURCE_DATE_EPOCH` (the
+//! reproducible-builds convention), else the date of the last commit that
+//! touched the file (when it has no uncommitted changes), else the file's
+//! modification date. Only unsaved documents fall back to today.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::ai_provider::{build_command, login_shell_path};
+use crate::publish::{frontmatter_field, split_frontmatter, top_level_key, unquote};
+
+/// Where the export date came from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DateSource {
+    Frontmatter,
+    SourceDateEpoch,
+    GitCommit,
+    Modified,
+    Today,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportMetadata {
+    /// Frontmatter `title`, else the first `# heading`, else the file name
+    pub title: Option<String>,
+    /// Frontmatter `author` (lists are joined with ", ")
+    pub author: Option<String>,
+    /// Frontmatter `keywords`, else `tags`
+    pub keywords: Vec<String>,
+    /// Frontmatter `description`
+    pub description: Option<String>,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub date_source: DateSource,
+    /// Short hash of the workspace's HEAD commit
+    pub git_commit: Option<String>,
+    /// The document has uncommitted changes
+    pub git_dirty: bool,
+    pub app_version: String,
+}
+
+/// Information from git about the file being exported
+#[derive(Debug, Default, PartialEq)]
+struct GitInfo {
+    head: Option<String>,
+    /// Committer date of the last commit touching the file
+    file_date: Option<NaiveDate>,
+    dirty: bool,
+}
+
+// ============================================================================
+// Frontmatter
+// ============================================================================
+
+/// A frontmatter list in either flow (`[a, b]`, `a, b`) or block
+/// (`key:` followed by `- a` lines) style.
+fn frontmatter_list(content: &str, key: &str) -> Vec<String> {
+    let (Some(lines), _) = split_frontmatter(content) else {
+        return Vec::new();
+    };
+    let Some(start) = lines.iter().position(|l| top_level_key(l) == Some(key)) else {
+        return Vec::new();
+    };
+    let (_, value) = lines[start].split_once(':').unwrap_or_default();
+    let value = value.trim();
+    let items: Vec<String> = if value.is_empty() {
+        lines[start + 1..]
+            .iter()
+            .map_while(|line| line.trim_start().strip_prefix("- "))
+            .map(|item| unquote(item.trim()))
+            .collect()
+    } else {
+        let value = value
+            .strip_prefix('[')
+            .and_then(|v| v.strip_suffix(']'))
+            .unwrap_or(value);
+        value.split(',').map(|item| unquote(item.trim())).collect()
+    };
+    items.into_iter().filter(|item| !item.is_empty()).collect()
+}
+
+fn first_heading(content: &str) -> Option<String> {
+    let (_, body) = split_frontmatter(content);
+    body.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|heading| heading.trim().trim_end_matches('#').trim().to_string())
+        .filter(|heading| !heading.is_empty())
+}
+
+/// `2026-05-01`, `2026-05-01T10:00:00Z`, `2026-05-01 10:00` → the date.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim().get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn parse_epoch(value: &str) -> Option<NaiveDate> {
+    let secs = value.trim().parse::<i64>().ok()?;
+    DateTime::<Utc>::from_timestamp(secs, 0).map(|dt| dt.date_naive())
+}
+
+// ============================================================================
+// Git
+// ============================================================================
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let mut full = vec!["-C", dir.to_str()?];
+    full.extend_from_slice(args);
+    let output = build_command("git", &full)
+        .env("PATH", login_shell_path())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Git details for `file` in the repository containing `dir`. Empty when
+/// git isn't installed or `dir` isn't in a repository.
+fn git_info(dir: &Path, file: Option<&Path>) -> GitInfo {
+    let Some(head) = git(dir, &["rev-parse", "--short", "HEAD"]).filter(|h| !h.is_empty()) else {
+        return GitInfo::default();
+    };
+    let Some(file) = file.and_then(Path::to_str) else {
+        return GitInfo {
+            head: Some(head),
+            ..Default::default()
+        };
+    };
+    GitInfo {
+        head: Some(head),
+        file_date: git(dir, &["log", "-1", "--format=%cI", "--", file])
+            .and_then(|d| parse_date(&d)),
+        dirty: git(dir, &["status", "--porcelain", "--", file]).is_some_and(|s| !s.is_empty()),
+    }
+}
+
+// ============================================================================
+// Collection
+// ============================================================================
+
+fn modified_date(path: &Path) -> Option<NaiveDate> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(DateTime::<Local>::from(modified).date_naive())
+}
+
+fn collect(
+    content: &str,
+    path: Option<&Path>,
+    git: &GitInfo,
+    source_date_epoch: Option<&str>,
+    app_version: &str,
+) -> ExportMetadata {
+    let title = frontmatter_field(content, "title")
+        .or_else(|| first_heading(content))
+        .or_else(|| {
+            path.and_then(Path::file_stem)
+                .map(|stem| stem.to_string_lossy().into_owned())
+        });
+    let authors = frontmatter_list(content, "author");
+    let keywords = match frontmatter_list(content, "keywords") {
+        keywords if keywords.is_empty() => frontmatter_list(content, "tags"),
+        keywords => keywords,
+    };
+
+    let (date, date_source) =
+        if let Some(date) = frontmatter_field(content, "date").and_then(|d| parse_date(&d)) {
+            (date, DateSource::Frontmatter)
+        } else if let Some(date) = source_date_epoch.and_then(parse_epoch) {
+            (date, DateSource::SourceDateEpoch)
+        } else if let Some(date) = git.file_date.filter(|_| !git.dirty) {
+            (date, DateSource::GitCommit)
+        } else if let Some(date) = path.and_then(modified_date) {
+            (date, DateSource::Modified)
+        } else {
+            (Local::now().date_naive(), DateSource::Today)
+        };
+
+    ExportMetadata {
+        title,
+        author: (!authors.is_empty()).then(|| authors.join(", ")),
+        keywords,
+        description: frontmatter_field(content, "description"),
+        date: date.format("%Y-%m-%d").to_string(),
+        date_source,
+        git_commit: git.head.clone(),
+        git_dirty: git.dirty,
+        app_version: app_version.to_string(),
+    }
+}
+
+/// Metadata to embed in an export of `content` (saved at `path`, if any).
+/// Git details come from the repository containing `workspace_root`, or the
+/// document's folder.
+#[tauri::command]
+pub async fn get_export_metadata(
+    app: AppHandle,
+    content: String,
+    path: Option<String>,
+    workspace_root: Option<String>,
+) -> Result<ExportMetadata, String> {
+    let app_version = app.package_info().version.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = path.as_deref().map(Path::new);
+        let dir = workspace_root
+            .as_deref()
+            .map(Path::new)
+            .or_else(|| path.and_then(Path::parent));
+        let git = dir.map(|dir| git_info(dir, path)).unwrap_or_default();
+        let epoch = std::env::var("SOURCE_DATE_EPOCH").ok();
+        collect(&content, path, &git, epoch.as_deref(), &app_version)
+    })
+    .await
+    .map_err(|e| format!("Failed to collect export metadata: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "---\n\
+        title: \"Field Notes\"\n\
+        author:\n  - Ada Lovelace\n  - 'Charles Babbage'\n\
+        tags: [engines, \"notes\"]\n\
+        date: 2026-03-14T09:00:00Z\n\
+        ---\n\n# Heading\n";
+
+    #[test]
+    fn test_frontmatter_fields() {
+        let meta = collect(DOC, None, &GitInfo::default(), None, "1.2.3");
+        assert_eq!(meta.title.as_deref(), Some("Field Notes"));
+        assert_eq!(
+            meta.author.as_deref(),
+            Some("Ada Lovelace, Charles Babbage")
+        );
+        assert_eq!(meta.keywords, vec!["engines", "notes"]);
+        assert_eq!(meta.date, "2026-03-14");
+        assert_eq!(meta.date_source, DateSource::Frontmatter);
+        assert_eq!(meta.app_version, "1.2.3");
+    }
+
+    #[test]
+    fn test_title_fallbacks() {
+        let git = GitInfo::default();
+        let meta = collect("Intro\n\n# Chapter One #\n", None, &git, None, "1");
+        assert_eq!(meta.title.as_deref(), Some("Chapter One"));
+        let meta = collect(
+            "no heading",
+            Some(Path::new("/notes/draft.md")),
+            &git,
+            None,
+            "1",
+        );
+        assert_eq!(meta.title.as_deref(), Some("draft"));
+        assert_eq!(
+            frontmatter_list("---\nkeywords: a, b\n---\n", "keywords"),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_date_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.md");
+        std::fs::write(&file, "x").unwrap();
+        let committed = GitInfo {
+            head: Some("abc1234".into()),
+            file_date: NaiveDate::from_ymd_opt(2026, 1, 2),
+            dirty: false,
+        };
+
+        let meta = collect("x", Some(&file), &committed, Some("1767225600"), "1");
+        assert_eq!(
+            (meta.date.as_str(), meta.date_source),
+            ("2026-01-01", DateSource::SourceDateEpoch)
+        );
+
+        let meta = collect("x", Some(&file), &committed, None, "1");
+        assert_eq!(
+            (meta.date.as_str(), meta.date_source),
+            ("2026-01-02", DateSource::GitCommit)
+        );
+        assert_eq!(meta.git_commit.as_deref(), Some("abc1234"));
+
+        let dirty = GitInfo {
+            dirty: true,
+            ..committed
+        };
+        let meta = collect("x", Some(&file), &dirty, Some("not a number"), "1");
+        assert_eq!(meta.date_source, DateSource::Modified);
+        assert!(meta.git_dirty);
+
+        let meta = collect("x", None, &GitInfo::default(), None, "1");
+        assert_eq!(meta.date_source, DateSource::Today);
+    }
+
+    #[test]
+    fn test_git_info_outside_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(git_info(dir.path(), None), GitInfo::default());
+    }
+}
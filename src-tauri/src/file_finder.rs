@@ -0,0 +1,635 @@
+//! Fuzzy File Finder
+//!
+//! Backend of the quick switcher: `fuzzy_find_files` ranks a workspace's
+//! notes against what the user typed, fzf-style. The typed characters must
+//! appear in order; matches at the start of a path segment, word or
+//! camelCase hump score higher, as do consecutive runs and matches in the
+//! file name, while gaps cost a little. Space-separated terms must all
+//! match, and a term with an uppercase letter matches case-sensitively.
+//!
+//! The file list is built on the first query and kept in memory: `watcher`
+//! hands each change to `apply_fs_change`, so queries never walk the disk
+//! and stay instant on workspaces with tens of thousands of notes.
+
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::watcher::FsChangeEvent;
+
+/// File lists keyed by workspace root
+static INDEXES: Mutex<Option<HashMap<String, FileIndex>>> = Mutex::new(None);
+
+const SCORE_MATCH: i32 = 16;
+const GAP_START: i32 = -3;
+const GAP_EXTENSION: i32 = -1;
+/// Match right after `/`
+const BONUS_SEGMENT: i32 = 10;
+/// Match at the start, or after a space, `-`, `_` or `.`
+const BONUS_BOUNDARY: i32 = 8;
+/// Uppercase letter after a lowercase one, or a digit after a letter
+const BONUS_CAMEL: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 4;
+/// Matches in the file name count for more than matches in its folders
+const BONUS_FILE_NAME: i32 = 2;
+/// A term's first character weighs its position bonus this much more
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// Unreachable DP cell; far enough from `i32::MIN` that adding to it can't
+/// overflow
+const NONE: i32 = i32::MIN / 2;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMatch {
+    /// Workspace-relative path
+    pub path: String,
+    pub score: i32,
+    /// UTF-16 offsets of the matched characters in `path`, for highlighting
+    pub positions: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuzzyFindResult {
+    pub matches: Vec<FileMatch>,
+    /// Files that matched, before `limit`
+    pub total: usize,
+}
+
+struct FileIndex {
+    root: PathBuf,
+    excludes: Vec<String>,
+    /// Workspace-relative note paths
+    files: BTreeSet<String>,
+}
+
+// ============================================================================
+// Scoring
+// ============================================================================
+
+/// One space-separated part of the query
+struct Term {
+    chars: Vec<char>,
+    case_sensitive: bool,
+    /// All ASCII, so paths can be checked byte by byte
+    ascii: bool,
+}
+
+fn parse_terms(query: &str) -> Vec<Term> {
+    query
+        .split_whitespace()
+        .map(|term| {
+            let case_sensitive = term.chars().any(char::is_uppercase);
+            Term {
+                chars: if case_sensitive {
+                    term.chars().collect()
+                } else {
+                    term.chars().map(fold).collect()
+                },
+                case_sensitive,
+                ascii: term.is_ascii(),
+            }
+        })
+        .collect()
+}
+
+fn fold(c: char) -> char {
+    if c.is_ascii() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+/// Cheap check before scoring: do the term's characters appear in order?
+/// Most files fail it, so it's the hot loop of a query.
+fn is_subsequence(term: &Term, path: &str) -> bool {
+    let mut wanted = term.chars.iter().copied().peekable();
+    if term.ascii {
+        // Bytes of multi-byte characters never equal an ASCII character
+        for &b in path.as_bytes() {
+            let b = if term.case_sensitive {
+                b
+            } else {
+                b.to_ascii_lowercase()
+            };
+            if wanted.next_if(|&c| c as u32 == b as u32).is_some() && wanted.peek().is_none() {
+                return true;
+            }
+        }
+    } else {
+        for c in path.chars() {
+            let c = if term.case_sensitive { c } else { fold(c) };
+            if wanted.next_if_eq(&c).is_some() && wanted.peek().is_none() {
+                return true;
+            }
+        }
+    }
+    wanted.peek().is_none()
+}
+
+/// Buffers reused from one path to the next, so ranking a large workspace
+/// doesn't allocate per file
+#[derive(Default)]
+struct Scratch {
+    chars: Vec<char>,
+    folded: Vec<char>,
+    bonus: Vec<i32>,
+    prev: Vec<i32>,
+    row: Vec<i32>,
+}
+
+/// Position bonus of every character of `chars`.
+fn fill_bonuses(chars: &[char], bonus: &mut Vec<i32>) {
+    let name_start = chars
+        .iter()
+        .rposition(|&c| c == '/')
+        .map_or(0, |slash| slash + 1);
+    bonus.clear();
+    bonus.extend(chars.iter().enumerate().map(|(j, &c)| {
+        let position = match j.checked_sub(1).map(|p| chars[p]) {
+            None => BONUS_BOUNDARY,
+            Some('/') => BONUS_SEGMENT,
+            Some(' ' | '-' | '_' | '.') => BONUS_BOUNDARY,
+            Some(p) if p.is_lowercase() && c.is_uppercase() => BONUS_CAMEL,
+            Some(p) if !p.is_numeric() && c.is_numeric() => BONUS_CAMEL,
+            _ => 0,
+        };
+        position + if j >= name_start { BONUS_FILE_NAME } else { 0 }
+    }));
+}
+
+/// Best alignment of `term` in `hay` (the path, case-folded unless the term
+/// is case-sensitive): its score and, when `track` is set, the matched
+/// character indices.
+///
+/// `row[j]` is the best score with the term's current character matched
+/// at `j`. A match extends either the previous character's match at
+/// `j - 1` (a consecutive run) or the best earlier one, less the gap
+/// penalty; `gap` carries that best earlier score along the row. Only the
+/// stretch between the first possible start and the last possible end is
+/// scored.
+fn align(
+    term: &[char],
+    hay: &[char],
+    bonus: &[i32],
+    track: bool,
+    prev: &mut Vec<i32>,
+    row: &mut Vec<i32>,
+) -> Option<(i32, Vec<usize>)> {
+    let m = term.len();
+    let lo = hay.iter().position(|&c| c == term[0])?;
+    let hi = hay.iter().rposition(|&c| c == term[m - 1])?;
+    if hi < lo || hi - lo + 1 < m {
+        return None;
+    }
+    let (hay, bonus) = (&hay[lo..=hi], &bonus[lo..=hi]);
+    let n = hay.len();
+
+    prev.clear();
+    prev.extend((0..n).map(|j| {
+        if hay[j] == term[0] {
+            SCORE_MATCH + bonus[j] * FIRST_CHAR_MULTIPLIER
+        } else {
+            NONE
+        }
+    }));
+    row.clear();
+    row.resize(n, NONE);
+    // back[i][j]: where term[i - 1] matched when term[i] matched at j
+    let mut back = if track {
+        vec![vec![usize::MAX; n]; m]
+    } else {
+        Vec::new()
+    };
+
+    for i in 1..m {
+        row.fill(NONE);
+        let (mut gap, mut gap_from) = (NONE, usize::MAX);
+        for j in i..n {
+            if j >= 2 {
+                gap += GAP_EXTENSION;
+                let start = prev[j - 2] + GAP_START;
+                if start >= gap {
+                    gap = start;
+                    gap_from = j - 2;
+                }
+            }
+            if hay[j] != term[i] {
+                continue;
+            }
+            let consecutive = prev[j - 1] + BONUS_CONSECUTIVE;
+            let (best, from) = if consecutive >= gap {
+                (consecutive, j - 1)
+            } else {
+                (gap, gap_from)
+            };
+            if best <= NONE / 2 {
+                continue;
+            }
+            row[j] = best + SCORE_MATCH + bonus[j];
+            if track {
+                back[i][j] = from;
+            }
+        }
+        std::mem::swap(prev, row);
+    }
+
+    // Highest score; the earliest end on ties
+    let (end, score) = prev
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by_key(|&(j, score)| (score, Reverse(j)))?;
+    if score <= NONE / 2 {
+        return None;
+    }
+    let mut positions = Vec::new();
+    if track {
+        let mut j = end;
+        for i in (0..m).rev() {
+            positions.push(lo + j);
+            if i > 0 {
+                j = back[i][j];
+            }
+        }
+        positions.reverse();
+    }
+    Some((score, positions))
+}
+
+/// Score of `path` against every term, or None if any term doesn't match.
+/// Positions are character indices.
+fn score_path(
+    terms: &[Term],
+    path: &str,
+    track: bool,
+    scratch: &mut Scratch,
+) -> Option<(i32, Vec<usize>)> {
+    if !terms.iter().all(|term| is_subsequence(term, path)) {
+        return None;
+    }
+    let Scratch {
+        chars,
+        folded,
+        bonus,
+        prev,
+        row,
+    } = scratch;
+    chars.clear();
+    chars.extend(path.chars());
+    folded.clear();
+    folded.extend(chars.iter().map(|&c| fold(c)));
+    fill_bonuses(chars, bonus);
+
+    let mut total = 0;
+    let mut positions = Vec::new();
+    for term in terms {
+        let hay = if term.case_sensitive {
+            &*chars
+        } else {
+            &*folded
+        };
+        let (score, matched) = align(&term.chars, hay, bonus, track, prev, row)?;
+        total += score;
+        positions.extend(matched);
+    }
+    positions.sort_unstable();
+    positions.dedup();
+    Some((total, positions))
+}
+
+/// Character indices of `path` as UTF-16 offsets.
+fn utf16_positions(path: &str, positions: &[usize]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(positions.len());
+    let mut wanted = positions.iter().peekable();
+    let mut offset = 0;
+    for (index, c) in path.chars().enumerate() {
+        if wanted.peek() == Some(&&index) {
+            offsets.push(offset);
+            wanted.next();
+        }
+        offset += c.len_utf16();
+    }
+    offsets
+}
+
+/// Best matches for `query` among `files`: highest score first, then
+/// shorter paths, then alphabetical. An empty query lists the files.
+fn rank<'a>(files: impl Iterator<Item = &'a String>, query: &str, limit: usize) -> FuzzyFindResult {
+    let terms = parse_terms(query);
+    let mut scratch = Scratch::default();
+    let mut scored: Vec<(i32, &String)> = files
+        .filter_map(|path| {
+            score_path(&terms, path, false, &mut scratch).map(|(score, _)| (score, path))
+        })
+        .collect();
+    let total = scored.len();
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then(a.len().cmp(&b.len()))
+            .then(a.cmp(b))
+    });
+
+    // Positions only for what's returned
+    let matches = scored
+        .into_iter()
+        .take(limit)
+        .map(|(score, path)| {
+            let positions = score_path(&terms, path, true, &mut scratch)
+                .map(|(_, positions)| utf16_positions(path, &positions))
+                .unwrap_or_default();
+            FileMatch {
+                path: path.clone(),
+                score,
+                positions,
+            }
+        })
+        .collect();
+    FuzzyFindResult { matches, total }
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// Markdown files under `root`, sorted, skipping hidden entries and excluded
+/// folders.
+pub(crate) fn collect_notes(root: &Path, excludes: &[String]) -> Vec<PathBuf> {
+    let mut notes = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || excludes.iter().any(|e| e == &name) {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() && crate::file_tree::is_markdown(&name) {
+                notes.push(entry.path());
+            }
+        }
+    }
+    notes.sort();
+    notes
+}
+
+/// `path` relative to `root`, `/`-separated on every platform.
+pub(crate) fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl FileIndex {
+    fn build(root: &Path) -> Self {
+        let excludes = crate::workspace::workspace_excludes(root);
+        let files = collect_notes(root, &excludes)
+            .iter()
+            .map(|path| relative_path(root, path))
+            .collect();
+        Self {
+            root: root.to_path_buf(),
+            excludes,
+            files,
+        }
+    }
+
+    /// Whether `path` belongs in the list (the same rules as
+    /// `collect_notes`: markdown, not hidden, not in an excluded folder).
+    fn covers(&self, path: &Path) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let names: Vec<String> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let Some(file_name) = names.last() else {
+            return false;
+        };
+        crate::file_tree::is_markdown(file_name)
+            && names
+                .iter()
+                .all(|n| !n.starts_with('.') && !self.excludes.contains(n))
+    }
+
+    fn update(&mut self, path: &Path) {
+        if path.is_dir() {
+            // A folder appeared (moved in): add what's inside
+            for note in collect_notes(path, &self.excludes) {
+                if self.covers(&note) {
+                    self.files.insert(relative_path(&self.root, &note));
+                }
+            }
+            return;
+        }
+        let rel = relative_path(&self.root, path);
+        if path.is_file() {
+            if self.covers(path) {
+                self.files.insert(rel);
+            }
+        } else {
+            // Gone: the file itself, or every note under a removed folder
+            let prefix = format!("{rel}/");
+            self.files.retain(|f| *f != rel && !f.starts_with(&prefix));
+        }
+    }
+}
+
+fn with_index<T>(root_path: &str, f: impl FnOnce(&FileIndex) -> T) -> Result<T, String> {
+    let root = Path::new(root_path);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {root_path}"));
+    }
+    let mut guard = INDEXES.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let index = guard
+        .get_or_insert_with(HashMap::new)
+        .entry(root_path.to_string())
+        .or_insert_with(|| FileIndex::build(root));
+    Ok(f(index))
+}
+
+/// Keep built file lists current with a watcher event. Called by `watcher`
+/// for every change it reports; a no-op for workspaces nobody has searched.
+pub(crate) fn apply_fs_change(event: &FsChangeEvent) {
+    let Ok(mut guard) = INDEXES.lock() else {
+        return;
+    };
+    let Some(indexes) = guard.as_mut() else {
+        return;
+    };
+    let paths = event
+        .paths
+        .iter()
+        .chain(event.from.iter())
+        .chain(event.to.iter());
+    for path in paths {
+        let path = Path::new(path);
+        for index in indexes.values_mut().filter(|i| path.starts_with(&i.root)) {
+            index.update(path);
+        }
+    }
+}
+
+/// Notes in `root_path` matching `query`, best first. `limit` defaults to
+/// 50.
+#[tauri::command]
+pub async fn fuzzy_find_files(
+    root_path: String,
+    query: String,
+    limit: Option<usize>,
+) -> Result<FuzzyFindResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+        with_index(&root_path, |index| rank(index.files.iter(), &query, limit))
+    })
+    .await
+    .map_err(|e| format!("Failed to find files: {e}"))?
+}
+
+/// Drop a workspace's file list so the next query rebuilds it (e.g. after
+/// excluded folders change).
+#[tauri::command]
+pub fn rebuild_file_finder_index(root_path: String) -> Result<(), String> {
+    if let Some(indexes) = INDEXES
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .as_mut()
+    {
+        indexes.remove(&root_path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn ranked(files: &[&str], query: &str) -> Vec<String> {
+        let files: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+        rank(files.iter(), query, 10)
+            .matches
+            .into_iter()
+            .map(|m| m.path)
+            .collect()
+    }
+
+    #[test]
+    fn test_requires_characters_in_order() {
+        let files = ["notes/plan.md", "journal/2026-03-14.md", "ideas.md"];
+        assert_eq!(ranked(&files, "npl"), vec!["notes/plan.md"]);
+        assert!(ranked(&files, "lpn").is_empty());
+        assert_eq!(ranked(&files, "").len(), 3);
+    }
+
+    #[test]
+    fn test_prefers_boundaries_runs_and_file_names() {
+        // Segment starts beat scattered letters
+        assert_eq!(
+            ranked(&["a/xpxlxaxn.md", "projects/launch-plan.md"], "plan")[0],
+            "projects/launch-plan.md"
+        );
+        // A match in the file name beats the same match in a folder
+        assert_eq!(
+            ranked(&["plan/notes.md", "notes/plan.md"], "plan")[0],
+            "notes/plan.md"
+        );
+        // camelCase humps
+        assert_eq!(
+            ranked(
+                &["research/statistic.md", "research/SomeTopicCard.md"],
+                "stc"
+            )[0],
+            "research/SomeTopicCard.md"
+        );
+        // Equal scores: the shorter path
+        assert_eq!(
+            ranked(&["b/readme.md", "a/b/readme.md"], "readme"),
+            vec!["b/readme.md", "a/b/readme.md"]
+        );
+    }
+
+    #[test]
+    fn test_terms_and_smart_case() {
+        let files = ["work/meeting notes.md", "home/meeting.md", "work/Todo.md"];
+        assert_eq!(ranked(&files, "work meet"), vec!["work/meeting notes.md"]);
+        assert_eq!(ranked(&files, "todo"), vec!["work/Todo.md"]);
+        assert_eq!(ranked(&files, "To"), vec!["work/Todo.md"]);
+        assert!(ranked(&files, "TODO").is_empty());
+    }
+
+    #[test]
+    fn test_positions_are_utf16_offsets() {
+        let files = ["notes/plan.md".to_string(), "🗂️/café.md".to_string()];
+        let result = rank(files.iter(), "plan", 10);
+        assert_eq!(result.total, 1);
+        assert_eq!(result.matches[0].positions, vec![6, 7, 8, 9]);
+
+        let result = rank(files.iter(), "cafe", 10);
+        assert!(result.matches.is_empty());
+        let result = rank(files.iter(), "café", 10);
+        // 🗂️ is two UTF-16 units plus a variation selector
+        assert_eq!(result.matches[0].positions, vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_limit_and_total() {
+        let files: Vec<String> = (0..20).map(|i| format!("note-{i:02}.md")).collect();
+        let result = rank(files.iter(), "note", 5);
+        assert_eq!(result.total, 20);
+        assert_eq!(result.matches.len(), 5);
+        assert_eq!(result.matches[0].path, "note-00.md");
+    }
+
+    #[test]
+    fn test_index_follows_fs_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().into_owned();
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        fs::write(dir.path().join("notes/a.md"), "A").unwrap();
+        fs::write(dir.path().join("notes/image.png"), "").unwrap();
+
+        let find =
+            |query: &str| with_index(&root, |i| rank(i.files.iter(), query, 10).total).unwrap();
+        assert_eq!(find(""), 1);
+
+        let event = |path: PathBuf| FsChangeEvent {
+            watch_id: "main".into(),
+            root_path: root.clone(),
+            paths: vec![path.to_string_lossy().into_owned()],
+            kind: "create".into(),
+            from: None,
+            to: None,
+            digests: HashMap::new(),
+        };
+        let b = dir.path().join("notes/budget.md");
+        fs::write(&b, "B").unwrap();
+        apply_fs_change(&event(b));
+        assert_eq!(find("budget"), 1);
+
+        fs::remove_dir_all(dir.path().join("notes")).unwrap();
+        apply_fs_change(&event(dir.path().join("notes")));
+        assert_eq!(find(""), 0);
+        rebuild_file_finder_index(root.clone()).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+//! Advisory File Locks
+//!
+//! For notes on shared drives (SMB, NFS, Dropbox folders) where two people
+//! may open the same file. Opening a document with locking enabled writes a
+//! `.~lock.<name>#` sidecar next to it — the same naming LibreOffice uses —
+//! recording who holds it. Others see the lock on open and can open read-only
+//! or force-unlock it.
+//!
+//! Locks are advisory: nothing stops a write, and OS-level locks are not used
+//! because most network filesystems don't propagate them reliably.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Documents this process holds locks for
+static HELD_LOCKS: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Contents of a lock sidecar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockInfo {
+    pub user: String,
+    pub host: String,
+    pub pid: u32,
+    /// Unix timestamp ms
+    pub acquired_at: i64,
+}
+
+/// Outcome of `acquire_file_lock` / `check_file_lock`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "status", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum LockStatus {
+    /// No lock, or it's ours
+    Unlocked,
+    Acquired,
+    /// Someone else holds the lock. `stale` means it was left by a process on
+    /// this machine that is no longer running, so it is safe to take over.
+    LockedByOther { info: LockInfo, stale: bool },
+}
+
+fn lock_path(path: &Path) -> Result<PathBuf, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Not a file path: {}", path.display()))?
+        .to_string_lossy();
+    Ok(path.with_file_name(format!(".~lock.{name}#")))
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(unix)]
+fn current_host() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: buf is valid for buf.len() bytes; gethostname NUL-terminates on success
+    let ok = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) } == 0;
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    if ok && len > 0 {
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+#[cfg(not(unix))]
+fn current_host() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn our_info() -> LockInfo {
+    LockInfo {
+        user: current_user(),
+        host: current_host(),
+        pid: std::process::id(),
+        acquired_at: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+fn is_ours(info: &LockInfo) -> bool {
+    info.host == current_host() && info.pid == std::process::id()
+}
+
+/// A lock left behind by a crashed process on this machine.
+fn is_stale(info: &LockInfo) -> bool {
+    if info.host != current_host() {
+        return false; // can't tell for other machines
+    }
+    #[cfg(unix)]
+    {
+        !crate::terminal::process_alive(info.pid)
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+fn parse_lock(content: &str) -> LockInfo {
+    serde_json::from_str(content).unwrap_or(LockInfo {
+        // Unreadable lock (e.g. LibreOffice's CSV format): still a lock
+        user: content.split(',').nth(1).unwrap_or("unknown").trim().to_string(),
+        host: String::new(),
+        pid: 0,
+        acquired_at: 0,
+    })
+}
+
+fn read_lock_content(lock: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(lock) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read lock file: {e}")),
+    }
+}
+
+fn read_lock(lock: &Path) -> Result<Option<LockInfo>, String> {
+    Ok(read_lock_content(lock)?.map(|content| parse_lock(&content)))
+}
+
+/// Remove a lock only if it still holds `expected`, so a lock someone took
+/// in the meantime isn't deleted. Returns false when it changed.
+fn remove_lock_if_unchanged(lock: &Path, expected: &str) -> Result<bool, String> {
+    match read_lock_content(lock)? {
+        Some(content) if content == expected => match fs::remove_file(lock) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(format!("Failed to remove lock file: {e}")),
+            _ => Ok(true),
+        },
+        Some(_) => Ok(false),
+        None => Ok(true),
+    }
+}
+
+fn status_of(info: Option<LockInfo>) -> LockStatus {
+    match info {
+        Some(info) if !is_ours(&info) => {
+            let stale = is_stale(&info);
+            LockStatus::LockedByOther { info, stale }
+        }
+        _ => LockStatus::Unlocked,
+    }
+}
+
+fn remember(path: &Path, held: bool) {
+    if let Ok(mut guard) = HELD_LOCKS.lock() {
+        let set = guard.get_or_insert_with(HashSet::new);
+        if held {
+            set.insert(path.to_path_buf());
+        } else {
+            set.remove(path);
+        }
+    }
+}
+
+fn acquire_impl(path: &Path, force: bool) -> Result<LockStatus, String> {
+    let lock = lock_path(path)?;
+    // An existing lock is only replaced when it's ours, stale or forced
+    if let Some(existing) = read_lock_content(&lock)? {
+        let status = status_of(Some(parse_lock(&existing)));
+        if matches!(status, LockStatus::LockedByOther { stale: false, .. }) && !force {
+            return Ok(status);
+        }
+        if !remove_lock_if_unchanged(&lock, &existing)? {
+            return Ok(status_of(read_lock(&lock)?));
+        }
+    }
+
+    let content = serde_json::to_string(&our_info()).map_err(|e| e.to_string())?;
+    // create_new so two editors racing for the same file can't both win
+    let mut file = match fs::OpenOptions::new().write(true).create_new(true).open(&lock) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => return Ok(status_of(read_lock(&lock)?)),
+        Err(e) => return Err(format!("Failed to create lock file: {e}")),
+    };
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write lock file: {e}"))?;
+    remember(path, true);
+    Ok(LockStatus::Acquired)
+}
+
+fn release_impl(path: &Path) -> Result<(), String> {
+    let lock = lock_path(path)?;
+    remember(path, false);
+    match read_lock(&lock)? {
+        Some(info) if is_ours(&info) => fs::remove_file(&lock).map_err(|e| format!("Failed to remove lock file: {e}")),
+        _ => Ok(()), // gone, or taken over by someone else
+    }
+}
+
+/// Lock a document for editing. Returns `lockedByOther` (without taking the
+/// lock) when someone else holds a live lock, unless `force` is set.
+#[tauri::command]
+pub fn acquire_file_lock(path: String, force: Option<bool>) -> Result<LockStatus, String> {
+    acquire_impl(Path::new(&path), force.unwrap_or(false))
+}
+
+/// Whether someone else holds a lock on a document (checked on open).
+#[tauri::command]
+pub fn check_file_lock(path: String) -> Result<LockStatus, String> {
+    Ok(status_of(read_lock(&lock_path(Path::new(&path))?)?))
+}
+
+/// Release our lock on a document (no-op if we don't hold it).
+#[tauri::command]
+pub fn release_file_lock(path: String) -> Result<(), String> {
+    release_impl(Path::new(&path))
+}
+
+/// Remove a document's lock, whoever holds it, provided it is still the lock
+/// the user was shown (`expected`). Fails if it has changed since.
+#[tauri::command]
+pub fn force_unlock_file(path: String, expected: LockInfo) -> Result<(), String> {
+    let path = Path::new(&path);
+    let lock = lock_path(path)?;
+    let Some(content) = read_lock_content(&lock)? else { return Ok(()) };
+    if parse_lock(&content) != expected || !remove_lock_if_unchanged(&lock, &content)? {
+        return Err("The lock has changed since it was checked".to_string());
+    }
+    remember(path, false);
+    Ok(())
+}
+
+/// Release every lock this process holds. Called on quit.
+pub fn release_all_locks() {
+    let held: Vec<PathBuf> = HELD_LOCKS
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.take())
+        .map(|set| set.into_iter().collect())
+        .unwrap_or_default();
+    for path in held {
+        if let Err(e) = release_impl(&path) {
+            tracing::warn!("[FileLock] Failed to release {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foreign_lock(path: &Path, host: &str) {
+        let info = LockInfo {
+            user: "someone".to_string(),
+            host: host.to_string(),
+            pid: 1,
+            acquired_at: 0,
+        };
+        fs::write(lock_path(path).unwrap(), serde_json::to_string(&info).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_lock_path() {
+        assert_eq!(
+            lock_path(Path::new("/share/notes/a.md")).unwrap(),
+            Path::new("/share/notes/.~lock.a.md#")
+        );
+    }
+
+    #[test]
+    fn test_acquire_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("a.md");
+        fs::write(&doc, "a").unwrap();
+
+        assert_eq!(acquire_impl(&doc, false).unwrap(), LockStatus::Acquired);
+        assert!(lock_path(&doc).unwrap().exists());
+        // Our own lock doesn't block us
+        assert_eq!(status_of(read_lock(&lock_path(&doc).unwrap()).unwrap()), LockStatus::Unlocked);
+        assert_eq!(acquire_impl(&doc, false).unwrap(), LockStatus::Acquired);
+
+        release_impl(&doc).unwrap();
+        assert!(!lock_path(&doc).unwrap().exists());
+    }
+
+    #[test]
+    fn test_foreign_lock_blocks_until_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("a.md");
+        foreign_lock(&doc, "other-machine");
+
+        let status = acquire_impl(&doc, false).unwrap();
+        assert!(matches!(status, LockStatus::LockedByOther { ref info, stale: false } if info.user == "someone"));
+
+        // Releasing someone else's lock leaves it alone
+        release_impl(&doc).unwrap();
+        assert!(lock_path(&doc).unwrap().exists());
+
+        assert_eq!(acquire_impl(&doc, true).unwrap(), LockStatus::Acquired);
+        release_impl(&doc).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stale_lock_on_this_host_is_taken_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("a.md");
+        let info = LockInfo {
+            user: "me".to_string(),
+            host: current_host(),
+            pid: u32::MAX / 2, // not a running process
+            acquired_at: 0,
+        };
+        fs::write(lock_path(&doc).unwrap(), serde_json::to_string(&info).unwrap()).unwrap();
+
+        assert!(matches!(
+            status_of(read_lock(&lock_path(&doc).unwrap()).unwrap()),
+            LockStatus::LockedByOther { stale: true, .. }
+        ));
+        assert_eq!(acquire_impl(&doc, false).unwrap(), LockStatus::Acquired);
+        release_impl(&doc).unwrap();
+    }
+
+    #[test]
+    fn test_foreign_lock_format_still_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("a.odt");
+        fs::write(lock_path(&doc).unwrap(), ",Jane Doe,host,01.01.2026 10:00,file:///x;").unwrap();
+        let status = status_of(read_lock(&lock_path(&doc).unwrap()).unwrap());
+        assert!(matches!(status, LockStatus::LockedByOther { ref info, .. } if info.user == "Jane Doe"));
+    }
+
+    #[test]
+    fn test_force_unlock_only_removes_the_lock_that_was_shown() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("a.md");
+        foreign_lock(&doc, "other-machine");
+        let shown = read_lock(&lock_path(&doc).unwrap()).unwrap().unwrap();
+
+        // Someone else took the lock after it was shown
+        foreign_lock(&doc, "third-machine");
+        let path = doc.to_string_lossy().to_string();
+        assert!(force_unlock_file(path.clone(), shown).is_err());
+        assert!(lock_path(&doc).unwrap().exists());
+
+        let current = read_lock(&lock_path(&doc).unwrap()).unwrap().unwrap();
+        force_unlock_file(path, current).unwrap();
+        assert!(!lock_path(&doc).unwrap().exists());
+    }
+
+    #[test]
+    fn test_remove_lock_if_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = dir.path().join(".~lock.a.md#");
+        fs::write(&lock, "theirs").unwrap();
+        assert!(!remove_lock_if_unchanged(&lock, "what we read").unwrap());
+        assert!(lock.exists());
+        assert!(remove_lock_if_unchanged(&lock, "theirs").unwrap());
+        assert!(!lock.exists());
+    }
+}
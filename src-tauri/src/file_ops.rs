@@ -0,0 +1,982 @@
+//! File Operations
+//!
+//! Backend file management for the sidebar:
+//! - Safe delete: move to the platform trash (recoverable) instead of permanent removal
+//! - Restore from trash where the platform exposes trash contents (Windows, Linux)
+//! - Rename / move / duplicate / create folder with collision auto-suffixing
+//!   ("name.md" -> "name (2).md") and cross-device move fallback
+//! - Optional link rewriting after rename/move, with the previous contents returned for undo
+//! - Safe save: compare-and-swap writes that refuse to clobber a file changed on disk
+//! - Document writes: line-ending/trailing-newline normalization plus a durable atomic write
+//!
+//! Each successful operation emits a targeted `fs:operation` event so the sidebar
+//! can update without waiting for (or re-listing after) watcher events.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::app_paths;
+use crate::watcher::content_hash;
+
+/// Serializes safe saves so the digest check and the write can't interleave
+/// with another safe save of the same file.
+static SAFE_SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Per-item result for batch operations
+#[derive(Debug, Clone, Serialize)]
+pub struct FileOpResult {
+    pub path: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl FileOpResult {
+    fn from_result(path: String, result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                path,
+                success: true,
+                error: None,
+            },
+            Err(e) => Self {
+                path,
+                success: false,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+/// Payload for `fs:operation` events
+#[derive(Debug, Clone, Serialize)]
+pub struct FsOperationEvent {
+    /// "rename" | "move" | "duplicate" | "createFolder"
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub to: String,
+}
+
+fn emit_operation(app: &AppHandle, kind: &str, from: Option<&Path>, to: &Path) {
+    let _ = app.emit(
+        "fs:operation",
+        FsOperationEvent {
+            kind: kind.to_string(),
+            from: from.map(|p| p.to_string_lossy().to_string()),
+            to: to.to_string_lossy().to_string(),
+        },
+    );
+}
+
+// ============================================================================
+// Trash
+// ============================================================================
+
+fn trash_path(path: &Path) -> Result<(), String> {
+    if !path.exists() && path.symlink_metadata().is_err() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+    trash::delete(path).map_err(|e| format!("Failed to move to trash: {e}"))
+}
+
+/// Move a file or folder to the platform trash.
+#[tauri::command]
+pub fn delete_to_trash(path: String) -> Result<(), String> {
+    trash_path(Path::new(&path))
+}
+
+/// Move several files or folders to the trash, reporting each item separately.
+/// A failure on one item does not stop the rest.
+#[tauri::command]
+pub fn delete_paths_to_trash(paths: Vec<String>) -> Vec<FileOpResult> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = trash_path(Path::new(&path));
+            FileOpResult::from_result(path, result)
+        })
+        .collect()
+}
+
+/// Restore the most recently trashed item that originally lived at `path`.
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+fn restore_path(path: &Path) -> Result<(), String> {
+    use trash::os_limited;
+
+    if path.exists() {
+        return Err(format!("A file already exists at {}", path.display()));
+    }
+
+    let items = os_limited::list().map_err(|e| format!("Failed to read trash: {e}"))?;
+    let item = items
+        .into_iter()
+        .filter(|item| item.original_parent.join(&item.name) == path)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| format!("Not found in trash: {}", path.display()))?;
+
+    os_limited::restore_all([item]).map_err(|e| format!("Failed to restore from trash: {e}"))
+}
+
+/// macOS does not expose trash contents to apps; use Finder's "Put Back" instead.
+#[cfg(not(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+fn restore_path(_path: &Path) -> Result<(), String> {
+    Err("Restoring from trash is not supported on this platform".to_string())
+}
+
+/// Restore a trashed file or folder to its original location.
+#[tauri::command]
+pub fn restore_from_trash(path: String) -> Result<(), String> {
+    restore_path(Path::new(&path))
+}
+
+// ============================================================================
+// Rename / Move / Duplicate / Create
+// ============================================================================
+
+/// Split "name (3)" into ("name", 3); names without a numeric suffix return 1.
+fn split_copy_suffix(stem: &str) -> (&str, u32) {
+    if let Some(open) = stem.rfind(" (") {
+        if let Some(inner) = stem[open + 2..].strip_suffix(')') {
+            if let Ok(n) = inner.parse::<u32>() {
+                if n >= 2 {
+                    return (&stem[..open], n);
+                }
+            }
+        }
+    }
+    (stem, 1)
+}
+
+/// Return `path` if free, otherwise the first free "stem (n).ext" sibling.
+pub(crate) fn unique_path(path: &Path) -> PathBuf {
+    if path.symlink_metadata().is_err() {
+        return path.to_path_buf();
+    }
+
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+    // Directories and dotfiles have no extension to preserve
+    let is_dir = path.is_dir();
+    let (stem, ext) = match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(ext)) if !is_dir => (
+            stem.to_string_lossy().to_string(),
+            Some(ext.to_string_lossy().to_string()),
+        ),
+        _ => (
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            None,
+        ),
+    };
+
+    let (base, start) = split_copy_suffix(&stem);
+    let mut n = start.max(1) + 1;
+    loop {
+        let name = match ext {
+            Some(ref ext) => format!("{base} ({n}).{ext}"),
+            None => format!("{base} ({n})"),
+        };
+        let candidate = parent.join(name);
+        if candidate.symlink_metadata().is_err() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Validate a user-supplied file or folder name (no separators or traversal).
+fn validate_name(name: &str) -> Result<(), String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        return Err(format!("Invalid name: {name:?}"));
+    }
+    if name.contains('/') || name.contains('\\') {
+        return Err(format!("Name cannot contain path separators: {name}"));
+    }
+    Ok(())
+}
+
+/// Whether two paths refer to the same file (e.g. case-only rename on macOS/Windows).
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Whether a rename failed because source and target are on different volumes.
+fn is_cross_device(err: &io::Error) -> bool {
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18; // EXDEV
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17; // ERROR_NOT_SAME_DEVICE
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE: i32 = -1;
+    err.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+/// Recursively copy a file or directory.
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    let meta = fs::symlink_metadata(from)?;
+    if meta.file_type().is_symlink() {
+        // Copy the link itself, not what it points at
+        copy_symlink(from, to)
+    } else if meta.is_dir() {
+        fs::create_dir(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+#[cfg(windows)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    let target = fs::read_link(from)?;
+    if fs::metadata(from).is_ok_and(|m| m.is_dir()) {
+        std::os::windows::fs::symlink_dir(target, to)
+    } else {
+        std::os::windows::fs::symlink_file(target, to)
+    }
+}
+
+/// Rename or move, falling back to copy + delete across volumes.
+fn move_or_copy(from: &Path, to: &Path) -> Result<(), String> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device(&e) => {
+            copy_recursive(from, to).map_err(|e| {
+                // Leave the source intact; clean up the partial copy
+                let _ = if to.is_dir() { fs::remove_dir_all(to) } else { fs::remove_file(to) };
+                format!("Failed to copy across volumes: {e}")
+            })?;
+            let removed = if from.is_dir() { fs::remove_dir_all(from) } else { fs::remove_file(from) };
+            removed.map_err(|e| format!("Copied, but failed to remove original: {e}"))
+        }
+        Err(e) => Err(format!("Failed to move {}: {e}", from.display())),
+    }
+}
+
+fn rename_path_impl(path: &Path, new_name: &str) -> Result<PathBuf, String> {
+    validate_name(new_name)?;
+    if path.symlink_metadata().is_err() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+    let parent = path.parent().ok_or("Cannot rename a root path")?;
+    let requested = parent.join(new_name);
+
+    let target = if is_same_file(path, &requested) {
+        requested // Case-only rename of the same file
+    } else {
+        unique_path(&requested)
+    };
+
+    fs::rename(path, &target).map_err(|e| format!("Failed to rename: {e}"))?;
+    Ok(target)
+}
+
+fn move_path_impl(path: &Path, dest_dir: &Path) -> Result<PathBuf, String> {
+    if path.symlink_metadata().is_err() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+    if !dest_dir.is_dir() {
+        return Err(format!("Destination is not a folder: {}", dest_dir.display()));
+    }
+    if let (Ok(src), Ok(dest)) = (fs::canonicalize(path), fs::canonicalize(dest_dir)) {
+        if dest.starts_with(&src) {
+            return Err("Cannot move a folder into itself".to_string());
+        }
+        if src.parent() == Some(dest.as_path()) {
+            return Ok(path.to_path_buf()); // Already there
+        }
+    }
+
+    let name = path.file_name().ok_or("Cannot move a root path")?;
+    let target = unique_path(&dest_dir.join(name));
+    move_or_copy(path, &target)?;
+    Ok(target)
+}
+
+fn duplicate_file_impl(path: &Path) -> Result<PathBuf, String> {
+    if !path.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    let target = unique_path(path);
+    fs::copy(path, &target).map_err(|e| format!("Failed to duplicate: {e}"))?;
+    Ok(target)
+}
+
+fn create_folder_impl(parent: &Path, name: &str) -> Result<PathBuf, String> {
+    validate_name(name)?;
+    if !parent.is_dir() {
+        return Err(format!("Not a folder: {}", parent.display()));
+    }
+    let target = unique_path(&parent.join(name));
+    fs::create_dir(&target).map_err(|e| format!("Failed to create folder: {e}"))?;
+    Ok(target)
+}
+
+/// A note whose links were rewritten after a rename or move
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkUpdate {
+    pub path: String,
+    pub replacements: usize,
+    /// Content before the rewrite, for undo (`restore_link_updates`)
+    pub previous_content: String,
+}
+
+/// Result of `rename_path` / `move_path`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveOutcome {
+    /// Final path (suffixed on collision)
+    pub path: String,
+    /// Notes whose links were rewritten (empty unless `update_links` was set)
+    pub updated_links: Vec<LinkUpdate>,
+    /// Why links couldn't be rewritten; the move itself still happened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_warning: Option<String>,
+}
+
+fn write_previous_contents(updates: &[LinkUpdate]) -> Result<(), String> {
+    for update in updates {
+        app_paths::write_document_file(Path::new(&update.path), update.previous_content.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Rewrite links in `root` after `from` moved to `to`. All or nothing: if a
+/// note can't be written, the notes already rewritten are restored.
+fn update_links_impl(root: &Path, from: &Path, to: &Path) -> Result<Vec<LinkUpdate>, String> {
+    let mut previous: Vec<(PathBuf, String)> = Vec::new();
+    let result = crate::link_rewrite::rewrite_links_after_move(root, from, to, |path, content| {
+        previous.push((path.to_path_buf(), content.to_string()));
+    });
+    match result {
+        Ok(updated) => Ok(updated
+            .into_iter()
+            .zip(previous)
+            .map(|(file, (_, previous_content))| LinkUpdate {
+                path: file.path,
+                replacements: file.replacements,
+                previous_content,
+            })
+            .collect()),
+        Err(e) => {
+            // The last entry is the note whose write failed; it was never changed
+            previous.pop();
+            for (path, content) in &previous {
+                if let Err(restore_err) = app_paths::write_document_file(path, content.as_bytes()) {
+                    tracing::warn!("[FileOps] Failed to roll back {}: {}", path.display(), restore_err);
+                }
+            }
+            Err(format!("Failed to update links: {e}"))
+        }
+    }
+}
+
+/// Rewrite links after a move that already happened. The move isn't undone
+/// if that fails (the rewrite is rolled back); the error becomes a warning.
+fn finish_move(
+    from: &Path,
+    target: PathBuf,
+    update_links: Option<bool>,
+    root_path: Option<String>,
+) -> MoveOutcome {
+    let (updated_links, link_warning) = match root_path {
+        Some(root) if update_links.unwrap_or(false) && target != from => {
+            match update_links_impl(Path::new(&root), from, &target) {
+                Ok(updated) => (updated, None),
+                Err(e) => {
+                    tracing::warn!("[FileOps] Moved {} but {}", from.display(), e);
+                    (Vec::new(), Some(e))
+                }
+            }
+        }
+        _ => (Vec::new(), None),
+    };
+    MoveOutcome {
+        path: target.to_string_lossy().to_string(),
+        updated_links,
+        link_warning,
+    }
+}
+
+/// Rename a file or folder in place. The final path gets a " (n)" suffix if
+/// `new_name` is already taken. With `update_links`, links to it across
+/// `root_path` are rewritten.
+#[tauri::command]
+pub async fn rename_path(
+    app: AppHandle,
+    path: String,
+    new_name: String,
+    update_links: Option<bool>,
+    root_path: Option<String>,
+) -> Result<MoveOutcome, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let from = Path::new(&path);
+        let target = rename_path_impl(from, &new_name)?;
+        emit_operation(&app, "rename", Some(from), &target);
+        Ok(finish_move(from, target, update_links, root_path))
+    })
+    .await
+    .map_err(|e| format!("Failed to rename: {e}"))?
+}
+
+/// Move a file or folder into `dest_dir`. Works across volumes. The final
+/// path is suffixed on collision. With `update_links`, links to it (and its
+/// own relative links) across `root_path` are rewritten.
+#[tauri::command]
+pub async fn move_path(
+    app: AppHandle,
+    path: String,
+    dest_dir: String,
+    update_links: Option<bool>,
+    root_path: Option<String>,
+) -> Result<MoveOutcome, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let from = Path::new(&path);
+        let target = move_path_impl(from, Path::new(&dest_dir))?;
+        if target != from {
+            emit_operation(&app, "move", Some(from), &target);
+        }
+        Ok(finish_move(from, target, update_links, root_path))
+    })
+    .await
+    .map_err(|e| format!("Failed to move: {e}"))?
+}
+
+/// Undo link rewrites by putting back each note's previous content.
+#[tauri::command]
+pub fn restore_link_updates(updates: Vec<LinkUpdate>) -> Result<(), String> {
+    write_previous_contents(&updates)
+}
+
+/// Duplicate a file next to itself ("name (2).md"). Returns the new path.
+#[tauri::command]
+pub fn duplicate_file(app: AppHandle, path: String) -> Result<String, String> {
+    let from = Path::new(&path);
+    let target = duplicate_file_impl(from)?;
+    emit_operation(&app, "duplicate", Some(from), &target);
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Create a folder under `parent`. Returns the final path (suffixed on collision).
+#[tauri::command]
+pub fn create_folder(app: AppHandle, parent: String, name: String) -> Result<String, String> {
+    let target = create_folder_impl(Path::new(&parent), &name)?;
+    emit_operation(&app, "createFolder", None, &target);
+    Ok(target.to_string_lossy().to_string())
+}
+
+// ============================================================================
+// Safe save
+// ============================================================================
+
+/// Outcome of `save_file_safe`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase", rename_all_fields = "camelCase")]
+pub enum SafeSaveResult {
+    /// Written; `digest` is the new on-disk digest to expect next time
+    Saved { digest: String },
+    /// Not written: the file changed since it was read. `disk_content` is what
+    /// is on disk now (None if the file was deleted), for a merge UI.
+    Conflict {
+        disk_content: Option<String>,
+        disk_digest: Option<String>,
+    },
+}
+
+fn save_file_safe_impl(
+    path: &Path,
+    content: &str,
+    expected_digest: Option<&str>,
+) -> Result<SafeSaveResult, String> {
+    let _guard = SAFE_SAVE_LOCK.lock().map_err(|e| format!("Lock error: {e}"))?;
+
+    let disk = match fs::read(path) {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+    };
+    let disk_digest = disk.as_deref().map(content_hash);
+
+    if let Some(expected) = expected_digest {
+        if disk_digest.as_deref() != Some(expected) {
+            return Ok(SafeSaveResult::Conflict {
+                disk_content: disk.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+                disk_digest,
+            });
+        }
+    }
+
+    app_paths::write_document_file(path, content.as_bytes())?;
+    Ok(SafeSaveResult::Saved {
+        digest: content_hash(content.as_bytes()),
+    })
+}
+
+/// Write `content` only if the file still has `expected_digest` (SHA-256 hex,
+/// as returned by `read_file_with_encoding`, `compute_file_digest` or a
+/// previous save). Pass no digest for new
+/// files or a confirmed overwrite. On mismatch nothing is written and the
+/// current disk content is returned instead.
+#[tauri::command]
+pub fn save_file_safe(
+    path: String,
+    content: String,
+    expected_digest: Option<String>,
+) -> Result<SafeSaveResult, String> {
+    save_file_safe_impl(Path::new(&path), &content, expected_digest.as_deref())
+}
+
+// ============================================================================
+// Document writes
+// ============================================================================
+
+/// Line endings to write a document with
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    /// Leave line endings exactly as given
+    Preserve,
+}
+
+/// Result of `write_document`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WrittenDocument {
+    /// The text as written, for the editor's "last saved" state
+    pub content: String,
+    /// SHA-256 (hex) of the written bytes, usable as `save_file_safe`'s expected digest
+    pub digest: String,
+}
+
+/// The fence marker of a code fence line (up to three spaces of indent, then
+/// three or more backticks or tildes): the character and run length.
+fn code_fence(line: &str) -> Option<(char, usize)> {
+    let stripped = line.trim_start_matches(' ');
+    if line.len() - stripped.len() > 3 {
+        return None;
+    }
+    let marker = stripped.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let run = stripped.chars().take_while(|c| *c == marker).count();
+    (run >= 3).then_some((marker, run))
+}
+
+/// Strip trailing spaces and tabs. A Markdown hard break (two or more
+/// trailing spaces before a non-blank line) is kept as exactly two spaces.
+/// Lines inside fenced code blocks are left alone.
+fn trim_trailing_whitespace(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut open_fence: Option<(char, usize)> = None;
+    for (i, line) in lines.iter().enumerate() {
+        let (body, cr) = match line.strip_suffix('\r') {
+            Some(body) => (body, "\r"),
+            None => (*line, ""),
+        };
+        let fence = code_fence(body);
+        match (open_fence, fence) {
+            (None, Some(fence)) => open_fence = Some(fence),
+            (Some((marker, len)), Some((m, run)))
+                if m == marker && run >= len && body.trim_start_matches([' ', m]).trim().is_empty() =>
+            {
+                open_fence = None;
+            }
+            (Some(_), _) => {
+                out.push(line.to_string());
+                continue;
+            }
+            _ => {}
+        }
+        let trimmed = body.trim_end_matches([' ', '\t']);
+        let next_has_text = lines
+            .get(i + 1)
+            .is_some_and(|next| !next.trim().is_empty());
+        let hard_break = fence.is_none()
+            && !trimmed.is_empty()
+            && next_has_text
+            && body[trimmed.len()..].starts_with("  ");
+        out.push(format!("{trimmed}{}{cr}", if hard_break { "  " } else { "" }));
+    }
+    out.join("\n")
+}
+
+fn normalize_document(
+    content: &str,
+    line_ending: LineEnding,
+    final_newline: bool,
+    trim_whitespace: bool,
+) -> String {
+    let mut text = if trim_whitespace {
+        trim_trailing_whitespace(content)
+    } else {
+        content.to_string()
+    };
+
+    let eol = match line_ending {
+        LineEnding::Lf => "\n",
+        LineEnding::Crlf => "\r\n",
+        LineEnding::Preserve if content.contains("\r\n") => "\r\n",
+        LineEnding::Preserve => "\n",
+    };
+    if line_ending != LineEnding::Preserve {
+        text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if eol == "\r\n" {
+            text = text.replace('\n', "\r\n");
+        }
+    }
+
+    if final_newline && !text.is_empty() {
+        let trimmed_len = text.trim_end_matches(['\r', '\n']).len();
+        text.truncate(trimmed_len);
+        text.push_str(eol);
+    }
+    text
+}
+
+/// Save a document with the editor's save semantics: normalize line endings,
+/// optionally trim trailing whitespace, end with exactly one newline when
+/// `final_newline` is set, then write atomically (see `write_document_file`).
+#[tauri::command]
+pub fn write_document(
+    path: String,
+    content: String,
+    line_ending: LineEnding,
+    final_newline: bool,
+    trim_trailing_whitespace: Option<bool>,
+) -> Result<WrittenDocument, String> {
+    let text = normalize_document(
+        &content,
+        line_ending,
+        final_newline,
+        trim_trailing_whitespace.unwrap_or(false),
+    );
+    let path = Path::new(&path);
+    app_paths::write_document_file(path, text.as_bytes())?;
+    Ok(WrittenDocument {
+        digest: content_hash(text.as_bytes()),
+        content: text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_missing_path_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.md");
+        assert!(delete_to_trash(missing.to_string_lossy().to_string()).is_err());
+    }
+
+    #[test]
+    fn test_batch_reports_each_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md").to_string_lossy().to_string();
+        let b = dir.path().join("b.md").to_string_lossy().to_string();
+
+        let results = delete_paths_to_trash(vec![a.clone(), b.clone()]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, a);
+        assert!(!results[0].success);
+        assert!(results[0].error.is_some());
+        assert_eq!(results[1].path, b);
+    }
+
+    #[test]
+    fn test_split_copy_suffix() {
+        assert_eq!(split_copy_suffix("notes"), ("notes", 1));
+        assert_eq!(split_copy_suffix("notes (2)"), ("notes", 2));
+        assert_eq!(split_copy_suffix("notes (12)"), ("notes", 12));
+        assert_eq!(split_copy_suffix("notes (draft)"), ("notes (draft)", 1));
+        assert_eq!(split_copy_suffix("notes (1)"), ("notes (1)", 1));
+    }
+
+    #[test]
+    fn test_unique_path_suffixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.md");
+        assert_eq!(unique_path(&file), file);
+
+        fs::write(&file, "").unwrap();
+        assert_eq!(unique_path(&file), dir.path().join("a (2).md"));
+
+        fs::write(dir.path().join("a (2).md"), "").unwrap();
+        assert_eq!(unique_path(&file), dir.path().join("a (3).md"));
+        assert_eq!(unique_path(&dir.path().join("a (2).md")), dir.path().join("a (3).md"));
+    }
+
+    #[test]
+    fn test_unique_path_folder_keeps_dots() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("v1.2");
+        fs::create_dir(&folder).unwrap();
+        assert_eq!(unique_path(&folder), dir.path().join("v1.2 (2)"));
+    }
+
+    #[test]
+    fn test_rename_with_collision() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        fs::write(&a, "a").unwrap();
+        fs::write(dir.path().join("b.md"), "b").unwrap();
+
+        let target = rename_path_impl(&a, "b.md").unwrap();
+        assert_eq!(target, dir.path().join("b (2).md"));
+        assert_eq!(fs::read_to_string(target).unwrap(), "a");
+        assert!(!a.exists());
+    }
+
+    #[test]
+    fn test_rename_rejects_separators() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        fs::write(&a, "").unwrap();
+        assert!(rename_path_impl(&a, "../x.md").is_err());
+        assert!(rename_path_impl(&a, "").is_err());
+    }
+
+    #[test]
+    fn test_move_into_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let sub = dir.path().join("sub");
+        fs::write(&a, "a").unwrap();
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("a.md"), "existing").unwrap();
+
+        let target = move_path_impl(&a, &sub).unwrap();
+        assert_eq!(target, sub.join("a (2).md"));
+        assert!(!a.exists());
+    }
+
+    #[test]
+    fn test_move_folder_into_itself_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("f");
+        let child = folder.join("child");
+        fs::create_dir_all(&child).unwrap();
+        assert!(move_path_impl(&folder, &child).is_err());
+        assert!(move_path_impl(&folder, &folder).is_err());
+    }
+
+    #[test]
+    fn test_copy_recursive() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("nested").join("x.md"), "x").unwrap();
+
+        let dst = dir.path().join("dst");
+        copy_recursive(&src, &dst).unwrap();
+        assert_eq!(fs::read_to_string(dst.join("nested").join("x.md")).unwrap(), "x");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_recursive_keeps_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = dir.path().join("outside");
+        fs::create_dir(&outside).unwrap();
+        fs::write(outside.join("big.bin"), "data").unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        std::os::unix::fs::symlink(&outside, src.join("link")).unwrap();
+
+        let dst = dir.path().join("dst");
+        copy_recursive(&src, &dst).unwrap();
+        let link = dst.join("link");
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), outside);
+    }
+
+    #[test]
+    fn test_duplicate_and_create_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        fs::write(&a, "a").unwrap();
+
+        let copy = duplicate_file_impl(&a).unwrap();
+        assert_eq!(copy, dir.path().join("a (2).md"));
+        assert_eq!(fs::read_to_string(copy).unwrap(), "a");
+
+        let first = create_folder_impl(dir.path(), "notes").unwrap();
+        let second = create_folder_impl(dir.path(), "notes").unwrap();
+        assert_eq!(first, dir.path().join("notes"));
+        assert_eq!(second, dir.path().join("notes (2)"));
+    }
+
+    #[test]
+    fn test_safe_save_writes_when_digest_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "one").unwrap();
+
+        let result = save_file_safe_impl(&path, "two", Some(&content_hash(b"one"))).unwrap();
+        assert_eq!(
+            result,
+            SafeSaveResult::Saved {
+                digest: content_hash(b"two")
+            }
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+    }
+
+    #[test]
+    fn test_safe_save_refuses_external_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "edited elsewhere").unwrap();
+
+        let result = save_file_safe_impl(&path, "mine", Some(&content_hash(b"original"))).unwrap();
+        assert_eq!(
+            result,
+            SafeSaveResult::Conflict {
+                disk_content: Some("edited elsewhere".to_string()),
+                disk_digest: Some(content_hash(b"edited elsewhere")),
+            }
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited elsewhere");
+    }
+
+    #[test]
+    fn test_safe_save_deleted_and_new_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gone.md");
+
+        // Expected to exist but deleted externally
+        let result = save_file_safe_impl(&path, "mine", Some(&content_hash(b"old"))).unwrap();
+        assert_eq!(
+            result,
+            SafeSaveResult::Conflict {
+                disk_content: None,
+                disk_digest: None
+            }
+        );
+        assert!(!path.exists());
+
+        // No expectation: plain write
+        let result = save_file_safe_impl(&path, "new", None).unwrap();
+        assert!(matches!(result, SafeSaveResult::Saved { .. }));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_safe_save_result_serialization() {
+        let json = serde_json::to_value(SafeSaveResult::Conflict {
+            disk_content: Some("x".to_string()),
+            disk_digest: None,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"status": "conflict", "diskContent": "x", "diskDigest": null})
+        );
+    }
+
+    #[test]
+    fn test_normalize_line_endings_and_final_newline() {
+        assert_eq!(normalize_document("a\r\nb\rc", LineEnding::Lf, true, false), "a\nb\nc\n");
+        assert_eq!(normalize_document("a\nb\n\n\n", LineEnding::Crlf, true, false), "a\r\nb\r\n");
+        assert_eq!(normalize_document("a\r\nb", LineEnding::Preserve, true, false), "a\r\nb\r\n");
+        assert_eq!(normalize_document("a\nb", LineEnding::Lf, false, false), "a\nb");
+        assert_eq!(normalize_document("", LineEnding::Lf, true, false), "");
+    }
+
+    #[test]
+    fn test_trim_keeps_hard_breaks() {
+        assert_eq!(
+            trim_trailing_whitespace("line one   \nline two\t\n  \nlast  "),
+            "line one  \nline two\n\nlast"
+        );
+        assert_eq!(trim_trailing_whitespace("a \r\nb  \r\nc"), "a\r\nb  \r\nc");
+    }
+
+    #[test]
+    fn test_trim_skips_fenced_code() {
+        let text = "intro \n```rust  \nlet a = 1;   \n~~~ \n```\nafter \n~~~~\ncode  \n~~~\n~~~~~ \nend ";
+        assert_eq!(
+            trim_trailing_whitespace(text),
+            "intro\n```rust\nlet a = 1;   \n~~~ \n```\nafter\n~~~~\ncode  \n~~~\n~~~~~\nend"
+        );
+    }
+
+    #[test]
+    fn test_write_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.md");
+        let written = write_document(
+            path.to_string_lossy().into_owned(),
+            "# Title  \nbody \n".to_string(),
+            LineEnding::Crlf,
+            true,
+            Some(true),
+        )
+        .unwrap();
+        assert_eq!(written.content, "# Title  \r\nbody\r\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), written.content);
+        assert_eq!(written.digest, content_hash(written.content.as_bytes()));
+    }
+
+    #[test]
+    fn test_move_updates_links_and_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("archive")).unwrap();
+        fs::write(root.join("index.md"), "[n](note.md) [[note]]\n").unwrap();
+        fs::write(root.join("note.md"), "[i](index.md)\n").unwrap();
+
+        let target = move_path_impl(&root.join("note.md"), &root.join("archive")).unwrap();
+        let outcome = finish_move(
+            &root.join("note.md"),
+            target,
+            Some(true),
+            Some(root.to_string_lossy().into_owned()),
+        );
+
+        assert_eq!(outcome.updated_links.len(), 2);
+        assert!(outcome.link_warning.is_none());
+        assert_eq!(fs::read_to_string(root.join("index.md")).unwrap(), "[n](archive/note.md) [[note]]\n");
+        assert_eq!(fs::read_to_string(root.join("archive/note.md")).unwrap(), "[i](../index.md)\n");
+
+        restore_link_updates(outcome.updated_links).unwrap();
+        assert_eq!(fs::read_to_string(root.join("index.md")).unwrap(), "[n](note.md) [[note]]\n");
+    }
+
+    #[test]
+    fn test_move_without_update_links_leaves_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("index.md"), "[n](a.md)").unwrap();
+        fs::write(root.join("a.md"), "a").unwrap();
+
+        let target = rename_path_impl(&root.join("a.md"), "b.md").unwrap();
+        let outcome = finish_move(&root.join("a.md"), target, None, Some(root.to_string_lossy().into_owned()));
+        assert!(outcome.updated_links.is_empty());
+        assert_eq!(fs::read_to_string(root.join("index.md")).unwrap(), "[n](a.md)");
+    }
+}
@@ -1,5 +1,8 @@
-use serde::Serialize;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
 
 #[derive(Debug, Serialize)]
 pub struct DirectoryEntry {
@@ -9,6 +12,85 @@ pub struct DirectoryEntry {
     pub is_directory: bool,
     #[serde(rename = "isHidden")]
     pub is_hidden: bool,
+    /// File size in bytes (None for directories or unreadable metadata)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Last modification time (unix timestamp ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<i64>,
+}
+
+/// Sort key for directory listings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Name,
+    Modified,
+    Size,
+}
+
+/// Options for `list_directory_entries`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListOptions {
+    #[serde(rename = "sortBy", default)]
+    pub sort_by: SortBy,
+    #[serde(default)]
+    pub descending: bool,
+    /// List directories before files (default true)
+    #[serde(rename = "dirsFirst", default = "default_true")]
+    pub dirs_first: bool,
+    /// Glob patterns a file name must match (directories are always kept)
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns that exclude files and directories by name
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(rename = "markdownOnly", default)]
+    pub markdown_only: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>, String> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| format!("Invalid glob {pattern:?}: {e}"))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid glob patterns: {e}"))
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> Option<i64> {
+    let modified = metadata.modified().ok()?;
+    let duration = modified.duration_since(UNIX_EPOCH).ok()?;
+    i64::try_from(duration.as_millis()).ok()
+}
+
+fn sort_entries(entries: &mut [DirectoryEntry], options: &ListOptions) {
+    entries.sort_by(|a, b| {
+        let by_key = match options.sort_by {
+            SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortBy::Modified => a.modified.cmp(&b.modified),
+            SortBy::Size => a.size.cmp(&b.size),
+        }
+        .then_with(|| a.name.cmp(&b.name));
+        let by_key = if options.descending { by_key.reverse() } else { by_key };
+
+        if options.dirs_first {
+            b.is_directory.cmp(&a.is_directory).then(by_key)
+        } else {
+            by_key
+        }
+    });
 }
 
 fn is_hidden_by_name(name: &str) -> bool {
@@ -29,11 +111,22 @@ fn is_hidden_by_metadata(_: &fs::Metadata) -> bool {
     false
 }
 
+/// List one directory level.
+///
+/// Without `options`, entries come back in filesystem order. With options the
+/// listing is filtered (globs, markdown-only) and sorted.
 #[tauri::command]
-pub fn list_directory_entries(path: &str) -> Result<Vec<DirectoryEntry>, String> {
+pub fn list_directory_entries(
+    path: &str,
+    options: Option<ListOptions>,
+) -> Result<Vec<DirectoryEntry>, String> {
     let entries = fs::read_dir(path).map_err(|e| format!("Failed to read dir: {e}"))?;
     let mut results = Vec::new();
 
+    let include = options.as_ref().map(|o| build_globset(&o.include)).transpose()?.flatten();
+    let exclude = options.as_ref().map(|o| build_globset(&o.exclude)).transpose()?.flatten();
+    let markdown_only = options.as_ref().is_some_and(|o| o.markdown_only);
+
     for entry in entries {
         let entry = match entry {
             Ok(entry) => entry,
@@ -48,20 +141,227 @@ pub fn list_directory_entries(path: &str) -> Result<Vec<DirectoryEntry>, String>
             .map(|file_type| file_type.is_dir())
             .unwrap_or(false);
 
+        if exclude.as_ref().is_some_and(|set| set.is_match(&name)) {
+            continue;
+        }
+        if !is_directory {
+            if markdown_only && !is_markdown(&name) {
+                continue;
+            }
+            if include.as_ref().is_some_and(|set| !set.is_match(&name)) {
+                continue;
+            }
+        }
+
+        let metadata = entry.metadata().ok();
+
+        let is_hidden = metadata
+            .as_ref()
+            .map(|metadata| is_hidden_by_metadata(metadata) || is_hidden_by_name(&name))
+            .unwrap_or_else(|| is_hidden_by_name(&name));
+
+        let size = metadata
+            .as_ref()
+            .filter(|_| !is_directory)
+            .map(|metadata| metadata.len());
+        let modified = metadata.as_ref().and_then(modified_ms);
+
+        results.push(DirectoryEntry {
+            name,
+            path,
+            is_directory,
+            is_hidden,
+            size,
+            modified,
+        });
+    }
+
+    if let Some(ref options) = options {
+        sort_entries(&mut results, options);
+    }
+
+    Ok(results)
+}
+
+/// Markdown extensions recognized by the markdown-only filter
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mdx"];
+
+/// Deepest tree a single call will load
+const MAX_TREE_DEPTH: u32 = 32;
+
+/// Node in a recursive directory listing
+#[derive(Debug, Serialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "isDirectory")]
+    pub is_directory: bool,
+    #[serde(rename = "isHidden")]
+    pub is_hidden: bool,
+    /// Children, or None for directories beyond the requested depth (load lazily)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<TreeNode>>,
+    /// Files in this subtree (after filtering); None if the directory wasn't loaded
+    #[serde(rename = "fileCount", skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<usize>,
+    /// Number of children before pagination
+    #[serde(rename = "totalChildren", skip_serializing_if = "Option::is_none")]
+    pub total_children: Option<usize>,
+    /// More children exist beyond the returned page
+    #[serde(rename = "hasMore", skip_serializing_if = "std::ops::Not::not")]
+    pub has_more: bool,
+}
+
+/// Options for `list_directory_tree`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TreeOptions {
+    /// Only include markdown files (directories without any are pruned once loaded)
+    #[serde(rename = "markdownOnly", default)]
+    pub markdown_only: bool,
+    /// Include dotfiles and OS-hidden entries
+    #[serde(rename = "includeHidden", default)]
+    pub include_hidden: bool,
+    /// Skip this many children of the root (pagination)
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum children returned per directory; the rest are reported via `hasMore`
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub(crate) fn is_markdown(name: &str) -> bool {
+    // Encrypted documents (`notes.md.enc`) count as markdown
+    let path = Path::new(name);
+    let path = if crate::encryption::is_encrypted_path(path) {
+        Path::new(path.file_stem().unwrap_or_default())
+    } else {
+        path
+    };
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MARKDOWN_EXTENSIONS.iter().any(|m| ext.eq_ignore_ascii_case(m)))
+}
+
+/// Build the tree for one directory. `depth` is the number of levels still to load.
+fn build_tree(
+    dir: &Path,
+    depth: u32,
+    exclude: &[String],
+    options: &TreeOptions,
+    offset: usize,
+) -> Result<(Vec<TreeNode>, usize, usize, bool), String> {
+    let read_dir = fs::read_dir(dir).map_err(|e| format!("Failed to read dir: {e}"))?;
+
+    let mut nodes = Vec::new();
+    for entry in read_dir.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        // Don't follow symlinked directories (avoids cycles)
+        let is_directory = file_type.is_dir();
+
         let is_hidden = entry
             .metadata()
             .map(|metadata| is_hidden_by_metadata(&metadata) || is_hidden_by_name(&name))
             .unwrap_or_else(|_| is_hidden_by_name(&name));
 
-        results.push(DirectoryEntry {
+        if is_hidden && !options.include_hidden {
+            continue;
+        }
+        if is_directory && exclude.iter().any(|ex| ex == &name) {
+            continue;
+        }
+        if !is_directory && options.markdown_only && !is_markdown(&name) {
+            continue;
+        }
+
+        let path = entry.path();
+        let (children, file_count, total_children, has_more) = if is_directory && depth > 1 {
+            match build_tree(&path, depth - 1, exclude, options, 0) {
+                Ok((children, count, total, more)) => (Some(children), Some(count), Some(total), more),
+                // Unreadable subdirectory: show it, but unloaded
+                Err(_) => (None, None, None, false),
+            }
+        } else {
+            (None, None, None, false)
+        };
+
+        // Prune loaded directories with no markdown in them
+        if is_directory && options.markdown_only && file_count == Some(0) {
+            continue;
+        }
+
+        nodes.push(TreeNode {
             name,
-            path,
+            path: path.to_string_lossy().to_string(),
             is_directory,
             is_hidden,
+            children,
+            file_count,
+            total_children,
+            has_more,
         });
     }
 
-    Ok(results)
+    // Directories first, then case-insensitive name (stable order for pagination)
+    nodes.sort_by(|a, b| {
+        b.is_directory
+            .cmp(&a.is_directory)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    let file_count = nodes
+        .iter()
+        .map(|n| if n.is_directory { n.file_count.unwrap_or(0) } else { 1 })
+        .sum();
+    let total = nodes.len();
+
+    let limit = options.limit.unwrap_or(usize::MAX);
+    let page: Vec<TreeNode> = nodes.into_iter().skip(offset).take(limit).collect();
+    let has_more = offset.saturating_add(page.len()) < total;
+
+    Ok((page, file_count, total, has_more))
+}
+
+/// List a directory tree in one call.
+///
+/// `depth` is how many levels to load (1 = immediate children, like
+/// `list_directory_entries`); deeper directories come back with `children: None`
+/// for lazy loading. `exclude` holds folder names to skip (e.g. excludeFolders).
+#[tauri::command]
+pub fn list_directory_tree(
+    root: &str,
+    depth: u32,
+    exclude: Vec<String>,
+    options: Option<TreeOptions>,
+) -> Result<TreeNode, String> {
+    let root_path = Path::new(root);
+    if !root_path.is_dir() {
+        return Err(format!("Not a directory: {root}"));
+    }
+    let options = options.unwrap_or_default();
+    let depth = depth.clamp(1, MAX_TREE_DEPTH);
+
+    let (children, file_count, total, has_more) =
+        build_tree(root_path, depth, &exclude, &options, options.offset)?;
+
+    let name = root_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.to_string());
+
+    Ok(TreeNode {
+        is_hidden: is_hidden_by_name(&name),
+        name,
+        path: root.to_string(),
+        is_directory: true,
+        children: Some(children),
+        file_count: Some(file_count),
+        total_children: Some(total),
+        has_more,
+    })
 }
 
 #[cfg(test)]
@@ -78,7 +378,7 @@ mod tests {
         fs::write(root.join(".hidden.md"), "secret").unwrap();
         fs::write(root.join("visible.md"), "hello").unwrap();
 
-        let entries = list_directory_entries(root.to_str().unwrap()).unwrap();
+        let entries = list_directory_entries(root.to_str().unwrap(), None).unwrap();
 
         let hidden = entries.iter().find(|entry| entry.name == ".hidden.md");
         let visible = entries.iter().find(|entry| entry.name == "visible.md");
@@ -88,4 +388,162 @@ mod tests {
         assert!(hidden.unwrap().is_hidden);
         assert!(!visible.unwrap().is_hidden);
     }
+
+    fn sample_tree() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("docs/deep/deeper")).unwrap();
+        fs::create_dir_all(root.join("images")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("readme.md"), "").unwrap();
+        fs::write(root.join("notes.txt"), "").unwrap();
+        fs::write(root.join("docs/a.md"), "").unwrap();
+        fs::write(root.join("docs/deep/b.md"), "").unwrap();
+        fs::write(root.join("docs/deep/deeper/c.md"), "").unwrap();
+        fs::write(root.join("images/logo.png"), "").unwrap();
+        fs::write(root.join(".hidden.md"), "").unwrap();
+        dir
+    }
+
+    fn child<'a>(node: &'a TreeNode, name: &str) -> &'a TreeNode {
+        node.children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn list_directory_tree_respects_depth() {
+        let dir = sample_tree();
+        let tree = list_directory_tree(dir.path().to_str().unwrap(), 2, vec![], None).unwrap();
+
+        let docs = child(&tree, "docs");
+        assert!(docs.children.is_some());
+        let deep = child(docs, "deep");
+        assert!(deep.children.is_none(), "depth 2 should not load docs/deep");
+        assert!(deep.file_count.is_none());
+    }
+
+    #[test]
+    fn list_directory_tree_excludes_and_hides() {
+        let dir = sample_tree();
+        let tree = list_directory_tree(
+            dir.path().to_str().unwrap(),
+            1,
+            vec!["node_modules".to_string()],
+            None,
+        )
+        .unwrap();
+        let names: Vec<_> = tree.children.as_ref().unwrap().iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "images", "notes.txt", "readme.md"]);
+    }
+
+    #[test]
+    fn list_directory_tree_markdown_only_counts_and_prunes() {
+        let dir = sample_tree();
+        let options = TreeOptions {
+            markdown_only: true,
+            ..Default::default()
+        };
+        let tree = list_directory_tree(
+            dir.path().to_str().unwrap(),
+            10,
+            vec!["node_modules".to_string()],
+            Some(options),
+        )
+        .unwrap();
+
+        assert_eq!(tree.file_count, Some(4));
+        let names: Vec<_> = tree.children.as_ref().unwrap().iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "readme.md"]);
+        assert_eq!(child(&tree, "docs").file_count, Some(3));
+    }
+
+    #[test]
+    fn list_directory_tree_paginates_root() {
+        let dir = tempdir().unwrap();
+        for i in 0..5 {
+            fs::write(dir.path().join(format!("f{i}.md")), "").unwrap();
+        }
+        let options = TreeOptions {
+            offset: 2,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let tree = list_directory_tree(dir.path().to_str().unwrap(), 1, vec![], Some(options)).unwrap();
+        let names: Vec<_> = tree.children.as_ref().unwrap().iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["f2.md", "f3.md"]);
+        assert_eq!(tree.total_children, Some(5));
+        assert!(tree.has_more);
+    }
+
+    fn list_options() -> ListOptions {
+        serde_json::from_str("{}").unwrap()
+    }
+
+    #[test]
+    fn list_directory_entries_sorts_dirs_first_and_reports_size() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("b.md"), "12345").unwrap();
+        fs::write(root.join("A.md"), "1").unwrap();
+        fs::create_dir(root.join("zeta")).unwrap();
+
+        let entries = list_directory_entries(root.to_str().unwrap(), Some(list_options())).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["zeta", "A.md", "b.md"]);
+        assert_eq!(entries[2].size, Some(5));
+        assert!(entries[0].size.is_none());
+        assert!(entries[1].modified.is_some());
+
+        let by_size = ListOptions {
+            sort_by: SortBy::Size,
+            descending: true,
+            dirs_first: false,
+            ..list_options()
+        };
+        let entries = list_directory_entries(root.to_str().unwrap(), Some(by_size)).unwrap();
+        assert_eq!(entries[0].name, "b.md");
+    }
+
+    #[test]
+    fn list_directory_entries_filters_by_glob_and_markdown() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("draft.md"), "").unwrap();
+        fs::write(root.join("final.md"), "").unwrap();
+        fs::write(root.join("journal.md.enc"), "").unwrap();
+        fs::write(root.join("image.png"), "").unwrap();
+        fs::create_dir(root.join("build")).unwrap();
+        fs::create_dir(root.join("docs")).unwrap();
+
+        let options = ListOptions {
+            exclude: vec!["draft*".to_string(), "build".to_string()],
+            markdown_only: true,
+            ..list_options()
+        };
+        let entries = list_directory_entries(root.to_str().unwrap(), Some(options)).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "final.md", "journal.md.enc"]);
+
+        let options = ListOptions {
+            include: vec!["*.png".to_string()],
+            ..list_options()
+        };
+        let entries = list_directory_entries(root.to_str().unwrap(), Some(options)).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "docs", "image.png"]);
+    }
+
+    #[test]
+    fn list_directory_entries_rejects_bad_glob() {
+        let dir = tempdir().unwrap();
+        let options = ListOptions {
+            include: vec!["[".to_string()],
+            ..list_options()
+        };
+        assert!(list_directory_entries(dir.path().to_str().unwrap(), Some(options)).is_err());
+    }
 }
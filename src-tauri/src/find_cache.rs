@@ -0,0 +1,452 @@
+//! Native Find Cache
+//!
+//! Running a regex over a multi-MB string in the webview blocks typing, so for
+//! very large documents the source editor searches here instead:
+//!
+//! 1. `load_find_document` hands over the text once, keyed by tab id.
+//! 2. `edit_find_document` keeps it current with CodeMirror's change sets
+//!    instead of resending the whole document.
+//! 3. `find_in_document` returns one page of matches plus the total.
+//! 4. `release_find_document` frees the text when the tab closes.
+//!
+//! The commands are async, so the frontend sends them one at a time per tab
+//! to keep edits in order.
+//!
+//! All offsets, in and out, are UTF-16 code units so they map straight onto
+//! editor positions. The matches of the last query are kept until the next
+//! edit, so paging through results doesn't re-run the search.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Stop collecting matches past this point; the page reports `truncated`.
+const MAX_MATCHES: usize = 100_000;
+
+/// Upper bound for one page, whatever the caller asks for.
+const MAX_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FindQuery {
+    pub pattern: String,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub regex: bool,
+}
+
+/// A single change, in offsets of the document before the batch is applied.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    pub from: usize,
+    pub to: usize,
+    pub insert: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FindMatch {
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindPage {
+    pub matches: Vec<FindMatch>,
+    pub total: usize,
+    /// True when the search stopped at the match limit
+    pub truncated: bool,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+struct SearchResult {
+    query: FindQuery,
+    version: u64,
+    matches: Arc<Vec<FindMatch>>,
+    truncated: bool,
+}
+
+struct CachedDocument {
+    text: Arc<String>,
+    /// Changes on every load and edit so stale results are never reused
+    version: u64,
+    last: Option<SearchResult>,
+}
+
+/// Versions are unique across documents, so a search that finishes after its
+/// tab was released and reloaded can't cache results for the new text.
+fn next_version() -> u64 {
+    static VERSION: AtomicU64 = AtomicU64::new(0);
+    VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+fn documents() -> &'static Mutex<HashMap<String, CachedDocument>> {
+    static DOCUMENTS: OnceLock<Mutex<HashMap<String, CachedDocument>>> = OnceLock::new();
+    DOCUMENTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn build_regex(query: &FindQuery) -> Result<Option<Regex>, String> {
+    if query.pattern.is_empty() {
+        return Ok(None);
+    }
+    let pattern = if query.regex {
+        query.pattern.clone()
+    } else if query.whole_word {
+        // Like the frontend search, whole word only applies to plain text
+        format!(r"\b{}\b", regex::escape(&query.pattern))
+    } else {
+        regex::escape(&query.pattern)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!query.case_sensitive)
+        .multi_line(true)
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid search pattern: {e}"))
+}
+
+fn utf16_len(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+/// All non-empty matches in UTF-16 offsets, and whether the limit was hit.
+fn find_matches(text: &str, re: &Regex) -> (Vec<FindMatch>, bool) {
+    let mut matches = Vec::new();
+    let mut byte_pos = 0;
+    let mut utf16_pos = 0;
+    for m in re.find_iter(text) {
+        // The editor never highlights empty matches (e.g. a bare `^`)
+        if m.is_empty() {
+            continue;
+        }
+        if matches.len() >= MAX_MATCHES {
+            return (matches, true);
+        }
+        let from = utf16_pos + utf16_len(&text[byte_pos..m.start()]);
+        let to = from + utf16_len(m.as_str());
+        matches.push(FindMatch { from, to });
+        byte_pos = m.end();
+        utf16_pos = to;
+    }
+    (matches, false)
+}
+
+/// Byte indexes of ascending UTF-16 offsets, in one pass over the text.
+fn byte_offsets(text: &str, offsets: &[usize]) -> Result<Vec<usize>, String> {
+    let mut chars = text.chars();
+    let mut units = 0;
+    let mut byte = 0;
+    let mut result = Vec::with_capacity(offsets.len());
+    for &offset in offsets {
+        while units < offset {
+            let c = chars
+                .next()
+                .ok_or_else(|| format!("Offset {offset} is past the end of the document"))?;
+            units += c.len_utf16();
+            byte += c.len_utf8();
+        }
+        if units > offset {
+            return Err(format!("Offset {offset} splits a character"));
+        }
+        result.push(byte);
+    }
+    Ok(result)
+}
+
+/// Apply a change set whose offsets all refer to the original text. Every
+/// offset is checked before anything changes, so a bad batch leaves the
+/// text as it was.
+pub(crate) fn apply_edits(text: &mut String, edits: &[TextEdit]) -> Result<(), String> {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.from);
+    let mut limit = 0;
+    for edit in &edits {
+        if edit.from > edit.to || edit.from < limit {
+            return Err("Edits overlap or are out of order".to_string());
+        }
+        limit = edit.to;
+    }
+    let offsets: Vec<usize> = edits.iter().flat_map(|edit| [edit.from, edit.to]).collect();
+    let bytes = byte_offsets(text, &offsets)?;
+    // Back to front, so earlier offsets stay valid
+    for (edit, range) in edits.iter().zip(bytes.chunks(2)).rev() {
+        text.replace_range(range[0]..range[1], &edit.insert);
+    }
+    Ok(())
+}
+
+fn page_of(matches: &[FindMatch], truncated: bool, page: usize, page_size: usize) -> FindPage {
+    let page_size = page_size.min(MAX_PAGE_SIZE);
+    let start = page.saturating_mul(page_size).min(matches.len());
+    let end = start.saturating_add(page_size).min(matches.len());
+    FindPage {
+        matches: matches[start..end].to_vec(),
+        total: matches.len(),
+        truncated,
+        page,
+        page_size,
+    }
+}
+
+/// Store a document's text for searching, replacing any earlier copy.
+#[tauri::command]
+pub async fn load_find_document(tab_id: String, content: String) {
+    let replaced = documents().lock().unwrap_or_else(|e| e.into_inner()).insert(
+        tab_id,
+        CachedDocument {
+            text: Arc::new(content),
+            version: next_version(),
+            last: None,
+        },
+    );
+    // Free the old copy outside the lock
+    drop(replaced);
+}
+
+/// Apply editor changes to a loaded document.
+/// On error the copy is dropped; the caller should load the document again.
+#[tauri::command]
+pub async fn edit_find_document(tab_id: String, edits: Vec<TextEdit>) -> Result<(), String> {
+    // Splicing a multi-MB string (or copying it while a search holds it) is
+    // too slow for the async runtime's threads
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut docs = documents().lock().unwrap_or_else(|e| e.into_inner());
+        let doc = docs
+            .get_mut(&tab_id)
+            .ok_or_else(|| format!("No search document for tab {tab_id}"))?;
+        if let Err(e) = apply_edits(Arc::make_mut(&mut doc.text), &edits) {
+            docs.remove(&tab_id);
+            return Err(e);
+        }
+        doc.version = next_version();
+        doc.last = None;
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Edit failed: {e}"))?
+}
+
+/// One page of matches for `query` in a loaded document.
+#[tauri::command]
+pub async fn find_in_document(
+    tab_id: String,
+    query: FindQuery,
+    page: usize,
+    page_size: usize,
+) -> Result<FindPage, String> {
+    let (text, version) = {
+        let docs = documents().lock().unwrap_or_else(|e| e.into_inner());
+        let doc = docs
+            .get(&tab_id)
+            .ok_or_else(|| format!("No search document for tab {tab_id}"))?;
+        if let Some(last) = doc
+            .last
+            .as_ref()
+            .filter(|last| last.query == query && last.version == doc.version)
+        {
+            return Ok(page_of(&last.matches, last.truncated, page, page_size));
+        }
+        (Arc::clone(&doc.text), doc.version)
+    };
+
+    let search_query = query.clone();
+    let (matches, truncated) = tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, String>(match build_regex(&search_query)? {
+            Some(re) => find_matches(&text, &re),
+            None => (Vec::new(), false),
+        })
+    })
+    .await
+    .map_err(|e| format!("Search failed: {e}"))??;
+
+    let result = page_of(&matches, truncated, page, page_size);
+    let mut docs = documents().lock().unwrap_or_else(|e| e.into_inner());
+    // Only cache if the document wasn't edited while searching
+    if let Some(doc) = docs.get_mut(&tab_id).filter(|doc| doc.version == version) {
+        doc.last = Some(SearchResult {
+            query,
+            version,
+            matches: Arc::new(matches),
+            truncated,
+        });
+    }
+    Ok(result)
+}
+
+/// Forget a document's text.
+#[tauri::command]
+pub async fn release_find_document(tab_id: String) {
+    let released = documents()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&tab_id);
+    drop(released);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(pattern: &str) -> FindQuery {
+        FindQuery {
+            pattern: pattern.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn search(text: &str, q: &FindQuery) -> Vec<(usize, usize)> {
+        let re = build_regex(q).unwrap().unwrap();
+        find_matches(text, &re)
+            .0
+            .iter()
+            .map(|m| (m.from, m.to))
+            .collect()
+    }
+
+    #[test]
+    fn plain_search_is_escaped_and_case_insensitive() {
+        assert_eq!(search("a.b A.B axb", &query("a.b")), vec![(0, 3), (4, 7)]);
+    }
+
+    #[test]
+    fn case_sensitive_and_whole_word() {
+        let q = FindQuery {
+            case_sensitive: true,
+            whole_word: true,
+            ..query("cat")
+        };
+        assert_eq!(search("cat Cat concat cat", &q), vec![(0, 3), (15, 18)]);
+    }
+
+    #[test]
+    fn regex_anchors_apply_per_line() {
+        let q = FindQuery {
+            regex: true,
+            ..query("^#+ ")
+        };
+        assert_eq!(search("# A\ntext\n## B", &q), vec![(0, 2), (9, 12)]);
+    }
+
+    #[test]
+    fn empty_matches_are_skipped() {
+        let q = FindQuery {
+            regex: true,
+            ..query("x*")
+        };
+        assert_eq!(search("axxb", &q), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn offsets_are_utf16() {
+        // "😀" is two UTF-16 units, "é" is one
+        assert_eq!(search("😀é cat", &query("cat")), vec![(4, 7)]);
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        let q = FindQuery {
+            regex: true,
+            ..query("(")
+        };
+        assert!(build_regex(&q).is_err());
+        assert!(build_regex(&query("")).unwrap().is_none());
+    }
+
+    #[test]
+    fn edits_use_original_offsets() {
+        let mut text = "😀 hello world".to_string();
+        let edits = vec![
+            TextEdit {
+                from: 3,
+                to: 8,
+                insert: "hi".into(),
+            },
+            TextEdit {
+                from: 14,
+                to: 14,
+                insert: "!".into(),
+            },
+        ];
+        apply_edits(&mut text, &edits).unwrap();
+        assert_eq!(text, "😀 hi world!");
+    }
+
+    #[test]
+    fn bad_edits_are_rejected() {
+        let mut text = "😀ab".to_string();
+        let split = vec![TextEdit {
+            from: 1,
+            to: 1,
+            insert: "x".into(),
+        }];
+        assert!(apply_edits(&mut text, &split).is_err());
+        let past_end = vec![TextEdit {
+            from: 9,
+            to: 9,
+            insert: "x".into(),
+        }];
+        assert!(apply_edits(&mut text, &past_end).is_err());
+        let overlapping = vec![
+            TextEdit {
+                from: 2,
+                to: 4,
+                insert: String::new(),
+            },
+            TextEdit {
+                from: 3,
+                to: 4,
+                insert: String::new(),
+            },
+        ];
+        assert!(apply_edits(&mut text, &overlapping).is_err());
+        let partly_valid = vec![
+            TextEdit {
+                from: 3,
+                to: 4,
+                insert: "x".into(),
+            },
+            TextEdit {
+                from: 1,
+                to: 2,
+                insert: String::new(),
+            },
+        ];
+        assert!(apply_edits(&mut text, &partly_valid).is_err());
+        assert_eq!(text, "😀ab");
+    }
+
+    #[test]
+    fn pages_are_clamped() {
+        let matches: Vec<FindMatch> = (0..5).map(|i| FindMatch { from: i, to: i + 1 }).collect();
+        let page = page_of(&matches, false, 1, 2);
+        assert_eq!(page.matches, &matches[2..4]);
+        assert_eq!(page.total, 5);
+        assert!(page_of(&matches, false, 9, 2).matches.is_empty());
+        assert!(page_of(&matches, false, 0, 0).matches.is_empty());
+    }
+
+    #[test]
+    fn loaded_documents_track_edits() {
+        use tauri::async_runtime::block_on;
+
+        let tab = "find-cache-test".to_string();
+        block_on(load_find_document(tab.clone(), "one two".into()));
+        block_on(edit_find_document(
+            tab.clone(),
+            vec![TextEdit {
+                from: 0,
+                to: 3,
+                insert: "two".into(),
+            }],
+        ))
+        .unwrap();
+        let page = block_on(find_in_document(tab.clone(), query("two"), 0, 10)).unwrap();
+        assert_eq!(page.total, 2);
+        block_on(release_find_document(tab.clone()));
+        assert!(block_on(edit_find_document(tab, Vec::new())).is_err());
+    }
+}
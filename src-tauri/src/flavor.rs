@@ -0,0 +1,771 @@
+//! Markdown Flavor Conversion
+//!
+//! `convert_flavor` rewrites a document between CommonMark, GitHub and
+//! Obsidian Markdown, for migrating vaults or publishing notes to GitHub:
+//!
+//! | Syntax | Obsidian | GitHub | CommonMark |
+//! |--------|----------|--------|------------|
+//! | Links | `[[Note\|Alias]]` | `[Alias](Note.md)` | `[Alias](Note.md)` |
+//! | Embeds | `![[pic.png]]` | `![pic.png](pic.png)` | `![pic.png](pic.png)` |
+//! | Highlights | `==text==` | `<mark>text</mark>` | `<mark>text</mark>` |
+//! | Callouts | `> [!tip] Title` | `> [!TIP]` | `> **Title**` |
+//! | Footnotes | `[^1]`, `^[inline]` | `[^1]` | `<sup>1</sup>` |
+//! | Strikethrough | `~~text~~` | `~~text~~` | `<del>text</del>` |
+//! | Comments | `%%text%%` | `<!-- text -->` | `<!-- text -->` |
+//!
+//! Obsidian block ids (`^abc123`) have no equivalent elsewhere and are
+//! dropped. Frontmatter and code are left alone. Lossy steps are reported
+//! as warnings.
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+/// `![[target#section|alias]]`
+static WIKI_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(!?)\[\[([^\]\|#]*)(#[^\]\|]*)?(?:\|([^\]]*))?\]\]").expect("valid wiki regex")
+});
+/// `[text](target)`, not images
+static MD_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|[^!\]])\[([^\]]*)\]\(([^)\s]+)\)").expect("valid link regex"));
+static HIGHLIGHT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"==(\S(?:[^=\n]*?\S)?)==").expect("valid highlight regex"));
+static MARK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<mark>(.*?)</mark>").expect("valid mark regex"));
+static STRIKE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"~~(\S(?:[^~\n]*?\S)?)~~").expect("valid strike regex"));
+static DEL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<del>(.*?)</del>").expect("valid del regex"));
+static COMMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"%%(.*?)%%").expect("valid comment regex"));
+static CALLOUT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*>\s*)\[!([A-Za-z-]+)\][+-]?[ \t]*(.*)$").expect("valid callout regex")
+});
+static FOOTNOTE_DEF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[\^([^\]\s]+)\]:\s?").expect("valid footnote regex"));
+static FOOTNOTE_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\^([^\]\s]+)\]").expect("valid footnote regex"));
+static INLINE_FOOTNOTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\^\[([^\]]+)\]").expect("valid inline footnote regex"));
+static BLOCK_ID_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s+\^[A-Za-z0-9-]+\s*$").expect("valid block id regex"));
+static INLINE_CODE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(`+)[^`]*?`+").expect("valid code regex"));
+static LIST_ITEM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}(?:[-*+]|\d+[.)])(?:\s|$)").expect("valid list regex"));
+
+/// File extensions embedded as images
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "avif"];
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Flavor {
+    CommonMark,
+    #[serde(alias = "gfm")]
+    GitHub,
+    Obsidian,
+}
+
+impl Flavor {
+    fn has_footnotes(self) -> bool {
+        self != Flavor::CommonMark
+    }
+
+    fn has_strikethrough(self) -> bool {
+        self != Flavor::CommonMark
+    }
+
+    fn has_callouts(self) -> bool {
+        self != Flavor::CommonMark
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlavorConversion {
+    pub content: String,
+    /// Constructs rewritten
+    pub changes: usize,
+    /// Lossy conversions, e.g. callout types GitHub doesn't have
+    pub warnings: Vec<String>,
+}
+
+/// Conversion in progress
+struct Converter {
+    from: Flavor,
+    to: Flavor,
+    changes: usize,
+    /// Footnote id → number, for CommonMark
+    footnote_numbers: HashMap<String, usize>,
+    unmapped_callouts: BTreeSet<String>,
+    callout_titles: bool,
+    block_ids: bool,
+}
+
+/// GitHub's anchor for a heading: lowercase, punctuation dropped, spaces
+/// as hyphens.
+fn heading_slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+fn encode_target(target: &str) -> String {
+    target.replace(' ', "%20")
+}
+
+fn is_image(target: &str) -> bool {
+    target
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// GitHub alert type for an Obsidian callout type, if there's a close one.
+fn github_alert(kind: &str) -> Option<&'static str> {
+    Some(match kind.to_lowercase().as_str() {
+        "note" | "info" | "todo" | "abstract" | "summary" | "tldr" => "NOTE",
+        "tip" | "hint" | "success" | "check" | "done" => "TIP",
+        "important" => "IMPORTANT",
+        "warning" | "attention" | "question" | "help" | "faq" => "WARNING",
+        "caution" | "danger" | "error" | "bug" | "failure" | "fail" | "missing" => "CAUTION",
+        _ => return None,
+    })
+}
+
+fn capitalize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Converter {
+    fn new(from: Flavor, to: Flavor) -> Self {
+        Self {
+            from,
+            to,
+            changes: 0,
+            footnote_numbers: HashMap::new(),
+            unmapped_callouts: BTreeSet::new(),
+            callout_titles: false,
+            block_ids: false,
+        }
+    }
+
+    fn replace_all(&mut self, re: &Regex, text: &str, f: impl Fn(&Captures) -> String) -> String {
+        let mut count = 0;
+        let out = re
+            .replace_all(text, |caps: &Captures| {
+                count += 1;
+                f(caps)
+            })
+            .into_owned();
+        self.changes += count;
+        out
+    }
+
+    /// Number footnotes in order of first reference (CommonMark has no
+    /// footnotes, so they become numbered superscripts).
+    fn scan_footnotes(&mut self, lines: &[&str], kinds: &[LineKind]) {
+        for (line, _) in lines.iter().zip(kinds).filter(|(_, k)| **k == LineKind::Prose) {
+            for caps in FOOTNOTE_REF_RE.captures_iter(line) {
+                self.footnote_number(&caps[1]);
+            }
+        }
+    }
+
+    /// Turn Obsidian inline footnotes (`^[text]`) into references to
+    /// definitions appended at the end, numbered after the document's own
+    /// numeric footnotes, so they are converted like any other footnote.
+    fn expand_inline_footnotes(&mut self, content: &str) -> String {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let kinds = line_kinds(&lines, Flavor::Obsidian);
+        let prose = || {
+            lines
+                .iter()
+                .zip(&kinds)
+                .filter(|(_, k)| **k == LineKind::Prose)
+                .map(|(line, _)| *line)
+        };
+        let mut next = prose()
+            .flat_map(|line| FOOTNOTE_REF_RE.captures_iter(line))
+            .filter_map(|caps| caps[1].parse::<usize>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let mut defs = Vec::new();
+        let mut out = String::with_capacity(content.len() + 64);
+        for (raw, kind) in lines.iter().zip(&kinds) {
+            if *kind != LineKind::Prose {
+                out.push_str(raw);
+                continue;
+            }
+            out.push_str(&outside_code(raw, |text| {
+                INLINE_FOOTNOTE_RE
+                    .replace_all(text, |caps: &Captures| {
+                        defs.push(format!("[^{}]: {}", next, &caps[1]));
+                        next += 1;
+                        format!("[^{}]", next - 1)
+                    })
+                    .into_owned()
+            }));
+        }
+
+        if !defs.is_empty() {
+            self.changes += defs.len();
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push('\n');
+            for def in defs {
+                out.push_str(&def);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn footnote_number(&mut self, id: &str) -> usize {
+        let next = self.footnote_numbers.len() + 1;
+        *self.footnote_numbers.entry(id.to_string()).or_insert(next)
+    }
+
+    fn wiki_link(&mut self, caps: &Captures) -> String {
+        let embed = !caps[1].is_empty();
+        // In table cells the alias pipe is escaped (`[[Note\|alias]]`)
+        let target = caps[2].trim().trim_end_matches('\\');
+        let section = caps
+            .get(3)
+            .map(|m| m.as_str().trim_start_matches('#').trim_end_matches('\\'));
+        let alias = caps.get(4).map(|m| m.as_str().trim());
+
+        if embed && is_image(target) {
+            // Obsidian's "|300" sets the width; Markdown images can't
+            let alt = alias
+                .filter(|a| a.parse::<u32>().is_err())
+                .unwrap_or(target);
+            return format!("![{}]({})", alt, encode_target(target));
+        }
+        let mut href = String::new();
+        if !target.is_empty() {
+            href.push_str(&encode_target(target));
+            if !target.contains('.') {
+                href.push_str(".md");
+            }
+        }
+        match section {
+            // Block references only exist in Obsidian
+            Some(s) if s.starts_with('^') => self.block_ids = true,
+            Some(s) => {
+                href.push('#');
+                href.push_str(&heading_slug(s));
+            }
+            None => {}
+        }
+        let text =
+            alias
+                .map(str::to_string)
+                .unwrap_or_else(|| match (target.is_empty(), section) {
+                    (true, Some(s)) => s.to_string(),
+                    (false, Some(s)) if !s.starts_with('^') => format!("{} > {}", target, s),
+                    _ => target.to_string(),
+                });
+        format!("[{}]({})", text, href)
+    }
+
+    /// `[text](Note.md)` → `[[Note|text]]` for relative Markdown links.
+    fn markdown_link(caps: &Captures) -> Option<String> {
+        let (prefix, text, href) = (&caps[1], &caps[2], &caps[3]);
+        if href.contains("://") || href.starts_with('/') || href.starts_with("mailto:") {
+            return None;
+        }
+        let (path, anchor) = match href.split_once('#') {
+            Some((path, anchor)) => (path, Some(anchor)),
+            None => (href, None),
+        };
+        let note = path.strip_suffix(".md")?.replace("%20", " ");
+        let name = note.rsplit('/').next().unwrap_or(&note).to_string();
+        let mut link = format!("[[{}", note);
+        if let Some(anchor) = anchor {
+            link.push('#');
+            link.push_str(&anchor.replace('-', " "));
+        }
+        if text != name && text != note {
+            link.push('|');
+            link.push_str(text);
+        }
+        link.push_str("]]");
+        Some(format!("{}{}", prefix, link))
+    }
+
+    /// Inline syntax of a prose segment (no code).
+    fn convert_inline(&mut self, text: &str) -> String {
+        let (from, to) = (self.from, self.to);
+        let mut text = text.to_string();
+
+        if from == Flavor::Obsidian {
+            // Wiki links first, so their text isn't seen as other syntax
+            let mut count = 0;
+            text = WIKI_RE
+                .replace_all(&text, |caps: &Captures| {
+                    count += 1;
+                    self.wiki_link(caps)
+                })
+                .into_owned();
+            self.changes += count;
+            text = self.replace_all(&HIGHLIGHT_RE, &text, |c| format!("<mark>{}</mark>", &c[1]));
+            text = self.replace_all(&COMMENT_RE, &text, |c| format!("<!-- {} -->", c[1].trim()));
+        }
+
+        if to == Flavor::Obsidian {
+            let mut count = 0;
+            text = MD_LINK_RE
+                .replace_all(&text, |caps: &Captures| match Self::markdown_link(caps) {
+                    Some(link) => {
+                        count += 1;
+                        link
+                    }
+                    None => caps[0].to_string(),
+                })
+                .into_owned();
+            self.changes += count;
+            text = self.replace_all(&MARK_RE, &text, |c| format!("=={}==", &c[1]));
+        }
+
+        if to.has_strikethrough() && !from.has_strikethrough() {
+            text = self.replace_all(&DEL_RE, &text, |c| format!("~~{}~~", &c[1]));
+        }
+        if from.has_strikethrough() && !to.has_strikethrough() {
+            text = self.replace_all(&STRIKE_RE, &text, |c| format!("<del>{}</del>", &c[1]));
+        }
+
+        if from.has_footnotes() && !to.has_footnotes() {
+            let mut count = 0;
+            text = FOOTNOTE_REF_RE
+                .replace_all(&text, |caps: &Captures| {
+                    count += 1;
+                    format!("<sup>{}</sup>", self.footnote_number(&caps[1]))
+                })
+                .into_owned();
+            self.changes += count;
+        }
+        text
+    }
+
+    /// Inline syntax of a line, leaving inline code alone.
+    fn convert_segments(&mut self, line: &str) -> String {
+        outside_code(line, |text| self.convert_inline(text))
+    }
+
+    fn convert_callout(&mut self, caps: &Captures) -> Option<String> {
+        let (prefix, kind, title) = (&caps[1], &caps[2], caps[3].trim());
+        let label = if title.is_empty() {
+            capitalize(kind)
+        } else {
+            title.to_string()
+        };
+        let line = match self.to {
+            Flavor::GitHub if self.from == Flavor::Obsidian => {
+                let alert = github_alert(kind).unwrap_or_else(|| {
+                    self.unmapped_callouts.insert(kind.to_lowercase());
+                    "NOTE"
+                });
+                if title.is_empty() {
+                    format!("{}[!{}]", prefix, alert)
+                } else {
+                    // GitHub alerts have no titles; keep it as the first line
+                    self.callout_titles = true;
+                    format!("{}[!{}]\n{}**{}**", prefix, alert, prefix, title)
+                }
+            }
+            Flavor::Obsidian if self.from == Flavor::GitHub => {
+                format!("{}[!{}]", prefix, kind.to_lowercase())
+            }
+            Flavor::CommonMark => format!("{}**{}**", prefix, label),
+            _ => return None,
+        };
+        self.changes += 1;
+        Some(line)
+    }
+
+    fn convert_line(&mut self, line: &str) -> String {
+        let mut line = line.to_string();
+
+        if self.from.has_callouts() {
+            if let Some(converted) = CALLOUT_RE
+                .captures(&line)
+                .and_then(|caps| self.convert_callout(&caps))
+            {
+                return converted;
+            }
+        }
+
+        if self.from == Flavor::Obsidian && BLOCK_ID_RE.is_match(&line) {
+            line = BLOCK_ID_RE.replace(&line, "").into_owned();
+            self.block_ids = true;
+            self.changes += 1;
+        }
+
+        // Footnote definitions: `[^1]: text` → `<sup>1</sup> text`
+        if self.from.has_footnotes() && !self.to.has_footnotes() {
+            if let Some(caps) = FOOTNOTE_DEF_RE.captures(&line) {
+                let number = self.footnote_number(&caps[1]);
+                let rest = line[caps[0].len()..].to_string();
+                self.changes += 1;
+                return format!("<sup>{}</sup> {}", number, self.convert_segments(&rest));
+            }
+        }
+        self.convert_segments(&line)
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.unmapped_callouts.is_empty() {
+            let kinds: Vec<&str> = self.unmapped_callouts.iter().map(String::as_str).collect();
+            warnings.push(format!(
+                "Callout types with no GitHub equivalent became NOTE: {}",
+                kinds.join(", ")
+            ));
+        }
+        if self.callout_titles {
+            warnings.push("Callout titles were moved into the first line of the callout".into());
+        }
+        if self.block_ids {
+            warnings.push("Block references (^id) were removed".into());
+        }
+        warnings
+    }
+}
+
+/// Apply `f` to the parts of a line outside inline code spans.
+fn outside_code(line: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut last = 0;
+    for m in INLINE_CODE_RE.find_iter(line) {
+        out.push_str(&f(&line[last..m.start()]));
+        out.push_str(m.as_str());
+        last = m.end();
+    }
+    out.push_str(&f(&line[last..]));
+    out
+}
+
+/// How `convert` treats a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Prose,
+    /// Frontmatter, code, or the inside of an Obsidian comment block
+    Verbatim,
+    /// A `%%` line opening or closing an Obsidian comment block
+    CommentFence,
+}
+
+fn line_kinds(lines: &[&str], from: Flavor) -> Vec<LineKind> {
+    let mut kinds = vec![LineKind::Prose; lines.len()];
+    let mut start = 0;
+    // Frontmatter
+    if lines.first().is_some_and(|l| l.trim_end() == "---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            kinds[..end + 2].fill(LineKind::Verbatim);
+            start = end + 2;
+        }
+    }
+
+    let mut fence: Option<String> = None;
+    let mut in_comment = false;
+    let mut in_indented = false;
+    let mut in_list = false;
+    let mut previous_blank = true;
+    for (i, raw) in lines.iter().enumerate().skip(start) {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = &fence {
+            if trimmed.starts_with(marker.as_str()) {
+                fence = None;
+            }
+            kinds[i] = LineKind::Verbatim;
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let first = trimmed.chars().next().unwrap_or('`');
+            fence = Some(trimmed.chars().take_while(|&c| c == first).collect());
+            kinds[i] = LineKind::Verbatim;
+            continue;
+        }
+        // Obsidian comment blocks: a `%%` line opens and closes them
+        if from == Flavor::Obsidian && trimmed.trim_end() == "%%" {
+            in_comment = !in_comment;
+            kinds[i] = LineKind::CommentFence;
+            continue;
+        }
+        if in_comment {
+            kinds[i] = LineKind::Verbatim;
+            continue;
+        }
+
+        // An indented code block starts after a blank line (outside lists,
+        // where indentation continues the item) and runs until a non-blank
+        // line that isn't indented
+        let blank = trimmed.is_empty();
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        in_indented =
+            (in_indented || (previous_blank && !in_list)) && (indented || (in_indented && blank));
+        if !blank && !indented {
+            in_list = LIST_ITEM_RE.is_match(line);
+        }
+        previous_blank = blank;
+        if in_indented {
+            kinds[i] = LineKind::Verbatim;
+        }
+    }
+    kinds
+}
+
+fn convert(content: &str, from: Flavor, to: Flavor) -> FlavorConversion {
+    if from == to {
+        return FlavorConversion {
+            content: content.to_string(),
+            changes: 0,
+            warnings: Vec::new(),
+        };
+    }
+    let mut converter = Converter::new(from, to);
+    let expanded;
+    let content = if from == Flavor::Obsidian {
+        expanded = converter.expand_inline_footnotes(content);
+        expanded.as_str()
+    } else {
+        content
+    };
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let kinds = line_kinds(&lines, from);
+    converter.scan_footnotes(&lines, &kinds);
+
+    let mut out = String::with_capacity(content.len() + 64);
+    let mut in_comment = false;
+    for (raw, kind) in lines.iter().zip(&kinds) {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let ending = &raw[line.len()..];
+        match kind {
+            LineKind::Verbatim => out.push_str(raw),
+            LineKind::CommentFence => {
+                out.push_str(if in_comment { "-->" } else { "<!--" });
+                out.push_str(ending);
+                in_comment = !in_comment;
+                converter.changes += 1;
+            }
+            LineKind::Prose => {
+                out.push_str(&converter.convert_line(line));
+                out.push_str(ending);
+            }
+        }
+    }
+
+    FlavorConversion {
+        content: out,
+        changes: converter.changes,
+        warnings: converter.warnings(),
+    }
+}
+
+/// Convert `content` from one Markdown flavor to another ("commonmark",
+/// "github" or "obsidian").
+#[tauri::command]
+pub async fn convert_flavor(
+    content: String,
+    from: Flavor,
+    to: Flavor,
+) -> Result<FlavorConversion, String> {
+    tauri::async_runtime::spawn_blocking(move || convert(&content, from, to))
+        .await
+        .map_err(|e| format!("Failed to convert Markdown: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obsidian_to(to: Flavor, content: &str) -> String {
+        convert(content, Flavor::Obsidian, to).content
+    }
+
+    #[test]
+    fn test_wiki_links_to_github() {
+        assert_eq!(
+            obsidian_to(
+                Flavor::GitHub,
+                "See [[Project Plan]], [[Project Plan|the plan]] and [[Notes/Ideas#Next Steps!]].\n"
+            ),
+            "See [Project Plan](Project%20Plan.md), [the plan](Project%20Plan.md) and \
+             [Notes/Ideas > Next Steps!](Notes/Ideas.md#next-steps).\n"
+        );
+        assert_eq!(
+            obsidian_to(
+                Flavor::GitHub,
+                "[[#Intro]] ![[diagram.png|300]] ![[Other note]]"
+            ),
+            "[Intro](#intro) ![diagram.png](diagram.png) [Other note](Other%20note.md)"
+        );
+    }
+
+    #[test]
+    fn test_markdown_links_to_obsidian() {
+        let result = convert(
+            "[Project Plan](Project%20Plan.md), [the plan](docs/plan.md#next-steps), \
+             [site](https://example.com/a.md) ![pic](pic.png)\n",
+            Flavor::GitHub,
+            Flavor::Obsidian,
+        );
+        assert_eq!(
+            result.content,
+            "[[Project Plan]], [[docs/plan#next steps|the plan]], \
+             [site](https://example.com/a.md) ![pic](pic.png)\n"
+        );
+        assert_eq!(result.changes, 2);
+    }
+
+    #[test]
+    fn test_highlights_strikethrough_and_comments() {
+        assert_eq!(
+            obsidian_to(
+                Flavor::GitHub,
+                "A ==key== point, a == b == c. %%todo%% ~~old~~\n"
+            ),
+            "A <mark>key</mark> point, a == b == c. <!-- todo --> ~~old~~\n"
+        );
+        assert_eq!(
+            obsidian_to(Flavor::CommonMark, "==key== ~~old~~"),
+            "<mark>key</mark> <del>old</del>"
+        );
+        assert_eq!(
+            convert(
+                "<mark>key</mark> <del>old</del>",
+                Flavor::CommonMark,
+                Flavor::Obsidian
+            )
+            .content,
+            "==key== ~~old~~"
+        );
+        assert_eq!(
+            obsidian_to(Flavor::GitHub, "Text\n%%\nhidden [[x]]\n%%\nMore"),
+            "Text\n<!--\nhidden [[x]]\n-->\nMore"
+        );
+    }
+
+    #[test]
+    fn test_callouts() {
+        let result = convert(
+            "> [!tip]- Pro tip\n> Body\n\n> [!example]\n> Ex\n",
+            Flavor::Obsidian,
+            Flavor::GitHub,
+        );
+        assert_eq!(
+            result.content,
+            "> [!TIP]\n> **Pro tip**\n> Body\n\n> [!NOTE]\n> Ex\n"
+        );
+        assert_eq!(result.warnings.len(), 2, "{:?}", result.warnings);
+        assert!(result.warnings[0].ends_with("example"));
+
+        assert_eq!(
+            convert("> [!WARNING]\n> Careful", Flavor::GitHub, Flavor::Obsidian).content,
+            "> [!warning]\n> Careful"
+        );
+        assert_eq!(
+            convert(
+                "> [!WARNING]\n> Careful",
+                Flavor::GitHub,
+                Flavor::CommonMark
+            )
+            .content,
+            "> **Warning**\n> Careful"
+        );
+    }
+
+    #[test]
+    fn test_footnotes() {
+        let result = convert(
+            "Claim[^src] and more.^[Inline note.] Again[^1].\n\n[^1]: One.\n[^src]: Source.\n",
+            Flavor::Obsidian,
+            Flavor::GitHub,
+        );
+        assert_eq!(
+            result.content,
+            "Claim[^src] and more.[^2] Again[^1].\n\n[^1]: One.\n[^src]: Source.\n\n[^2]: Inline note.\n"
+        );
+
+        assert_eq!(
+            convert(
+                "Claim[^src]. Again[^1].\n\n[^1]: One.\n[^src]: Source.\n",
+                Flavor::GitHub,
+                Flavor::CommonMark
+            )
+            .content,
+            "Claim<sup>1</sup>. Again<sup>2</sup>.\n\n<sup>2</sup> One.\n<sup>1</sup> Source.\n"
+        );
+    }
+
+    #[test]
+    fn test_code_frontmatter_and_block_ids() {
+        let doc = "---\nlink: \"[[Home]]\"\n---\nA paragraph. ^abc123\n`[[code]]` [[Real]]\n```\n[[fenced]] ==x==\n```\n";
+        let result = convert(doc, Flavor::Obsidian, Flavor::GitHub);
+        assert_eq!(
+            result.content,
+            "---\nlink: \"[[Home]]\"\n---\nA paragraph.\n`[[code]]` [Real](Real.md)\n```\n[[fenced]] ==x==\n```\n"
+        );
+        assert_eq!(result.warnings, vec!["Block references (^id) were removed"]);
+    }
+
+    #[test]
+    fn test_inline_footnotes_to_commonmark() {
+        assert_eq!(
+            obsidian_to(
+                Flavor::CommonMark,
+                "Text^[an aside] and ref[^a].\n\n[^a]: def\n"
+            ),
+            "Text<sup>1</sup> and ref<sup>2</sup>.\n\n<sup>2</sup> def\n\n<sup>1</sup> an aside\n"
+        );
+    }
+
+    #[test]
+    fn test_indented_code_untouched() {
+        assert_eq!(
+            obsidian_to(
+                Flavor::GitHub,
+                "Para\n\n    code [[x]]\n\n    more ==y==\nAfter [[x]]\n"
+            ),
+            "Para\n\n    code [[x]]\n\n    more ==y==\nAfter [x](x.md)\n"
+        );
+        // Indentation after a list item continues the item
+        assert_eq!(
+            obsidian_to(Flavor::GitHub, "- item\n\n    more [[x]]\n"),
+            "- item\n\n    more [x](x.md)\n"
+        );
+    }
+
+    #[test]
+    fn test_escaped_alias_pipe_in_tables() {
+        assert_eq!(
+            obsidian_to(
+                Flavor::GitHub,
+                "| Link | Note |\n|---|---|\n| [[N\\|b]] | [[Home#Intro\\|c]] |\n"
+            ),
+            "| Link | Note |\n|---|---|\n| [b](N.md) | [c](Home.md#intro) |\n"
+        );
+    }
+
+    #[test]
+    fn test_same_flavor_is_unchanged() {
+        let result = convert("[[x]] ==y==", Flavor::Obsidian, Flavor::Obsidian);
+        assert_eq!(result.content, "[[x]] ==y==");
+        assert_eq!(result.changes, 0);
+    }
+}
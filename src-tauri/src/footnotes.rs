@@ -0,0 +1,458 @@
+//! Footnote normalization
+//!
+//! Renumbers footnote labels sequentially in order of first reference,
+//! moves all definitions to the end of the document, and reports
+//! references without a definition, unreferenced definitions and labels
+//! defined more than once. Code blocks and inline code are left alone.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::command;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FootnoteReport {
+    pub content: String,
+    pub changed: bool,
+    /// Referenced labels with no definition (original labels)
+    pub missing_definitions: Vec<String>,
+    /// Defined labels that are never referenced (kept, numbered after the rest)
+    pub orphaned_definitions: Vec<String>,
+    /// Labels defined more than once (extra definitions kept as orphans)
+    pub duplicate_definitions: Vec<String>,
+}
+
+/// Normalize footnotes in a markdown document.
+#[command]
+pub async fn normalize_footnotes(content: String) -> Result<FootnoteReport, String> {
+    tauri::async_runtime::spawn_blocking(move || normalize(&content))
+        .await
+        .map_err(|e| format!("Failed to normalize footnotes: {e}"))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum LineKind {
+    Text,
+    Code,
+    /// A definition was taken out here
+    Removed,
+}
+
+struct Definition {
+    label: String,
+    /// First line content (after `[^label]:`), then continuation lines as written
+    lines: Vec<String>,
+}
+
+/// `[^label]: text` at the start of a line (up to 3 spaces of indent).
+fn parse_definition_start(line: &str) -> Option<(String, String)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = line[indent..].strip_prefix("[^")?;
+    let close = rest.find(']')?;
+    let label = &rest[..close];
+    if label.is_empty() || label.contains(char::is_whitespace) {
+        return None;
+    }
+    let text = rest[close + 1..].strip_prefix(':')?;
+    Some((label.to_string(), text.strip_prefix(' ').unwrap_or(text).to_string()))
+}
+
+fn is_fence(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let marker: String = trimmed.chars().take_while(|&c| c == '`' || c == '~').collect();
+    let first = marker.chars().next()?;
+    (marker.len() >= 3 && marker.chars().all(|c| c == first)).then_some(marker)
+}
+
+fn is_indented(line: &str) -> bool {
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+fn is_list_item(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let digits = trimmed.len() - trimmed.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let after_digits = &trimmed[digits..];
+    ["- ", "* ", "+ "].iter().any(|m| trimmed.starts_with(m))
+        || (digits > 0 && (after_digits.starts_with(". ") || after_digits.starts_with(") ")))
+}
+
+/// Lines that end a lazy continuation of a definition paragraph.
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with('#')
+        || trimmed.starts_with('>')
+        || trimmed.starts_with("- ")
+        || trimmed.starts_with("* ")
+        || trimmed.starts_with("+ ")
+        || is_fence(line).is_some()
+        || parse_definition_start(line).is_some()
+}
+
+/// Rewrite every `[^label]` reference outside inline code.
+fn rewrite_refs(text: &str, mut relabel: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rewrite_plain = |plain: &str, out: &mut String| {
+        let mut rest = plain;
+        while let Some(start) = rest.find("[^") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            match after.find(']') {
+                Some(close) if close > 0 && !after[..close].contains(char::is_whitespace) => {
+                    out.push_str("[^");
+                    out.push_str(&relabel(&after[..close]));
+                    out.push(']');
+                    rest = &after[close + 1..];
+                }
+                _ => {
+                    out.push_str("[^");
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+    };
+
+    let mut rest = text;
+    while let Some(tick) = rest.find('`') {
+        rewrite_plain(&rest[..tick], &mut out);
+        let run = rest[tick..].len() - rest[tick..].trim_start_matches('`').len();
+        let fence = &rest[tick..tick + run];
+        let code = &rest[tick + run..];
+        match code.find(fence) {
+            Some(end) => {
+                out.push_str(&rest[tick..tick + run + end + run]);
+                rest = &code[end + run..];
+            }
+            None => {
+                out.push_str(&rest[tick..]);
+                rest = "";
+            }
+        }
+    }
+    rewrite_plain(rest, &mut out);
+    out
+}
+
+/// Give a label the next number on its first reference.
+fn note(label: &str, numbers: &mut HashMap<String, usize>, order: &mut Vec<String>) {
+    if !numbers.contains_key(label) {
+        order.push(label.to_string());
+        numbers.insert(label.to_string(), order.len());
+    }
+}
+
+fn normalize(content: &str) -> FootnoteReport {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let lines: Vec<&str> = content.lines().collect();
+
+    // 1. Split the document into body lines and definitions
+    let mut body: Vec<(&str, LineKind)> = Vec::new();
+    let mut definitions: Vec<Definition> = Vec::new();
+    let mut fence: Option<String> = None;
+    let mut in_indented = false;
+    let mut in_list = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(open) = &fence {
+            if line.trim_start().starts_with(open.as_str()) && line.trim().chars().all(|c| c == '`' || c == '~') {
+                fence = None;
+            }
+            body.push((line, LineKind::Code));
+            i += 1;
+            continue;
+        }
+        if let Some(marker) = is_fence(line) {
+            fence = Some(marker);
+            body.push((line, LineKind::Code));
+            i += 1;
+            continue;
+        }
+        // An indented code block starts after a blank line and runs until a
+        // non-blank line that isn't indented. In a list, indented lines
+        // continue the item instead.
+        let blank = line.trim().is_empty();
+        let indented = is_indented(line);
+        let previous_blank = i == 0 || lines[i - 1].trim().is_empty();
+        in_indented = !in_list && (in_indented || previous_blank) && (indented || (in_indented && blank));
+        if !blank && !indented {
+            in_list = is_list_item(line);
+        }
+        if in_indented {
+            body.push((line, LineKind::Code));
+            i += 1;
+            continue;
+        }
+        let Some((label, first)) = parse_definition_start(line) else {
+            body.push((line, LineKind::Text));
+            i += 1;
+            continue;
+        };
+        body.push(("", LineKind::Removed));
+
+        let mut def_lines = vec![first];
+        i += 1;
+        while i < lines.len() {
+            let next = lines[i];
+            if is_indented(next) {
+                def_lines.push(next.to_string());
+                i += 1;
+            } else if next.trim().is_empty() {
+                // Blank lines belong to the definition only if indented content follows
+                match lines[i..].iter().position(|l| !l.trim().is_empty()) {
+                    Some(blanks) if is_indented(lines[i + blanks]) => {
+                        def_lines.extend(std::iter::repeat_n(String::new(), blanks));
+                        i += blanks;
+                    }
+                    _ => break,
+                }
+            } else if !starts_block(next) && !def_lines.last().is_some_and(|l| l.trim().is_empty()) {
+                // Lazy paragraph continuation
+                def_lines.push(next.to_string());
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        definitions.push(Definition { label, lines: def_lines });
+    }
+
+    if definitions.is_empty() && !body.iter().any(|(line, kind)| *kind == LineKind::Text && line.contains("[^")) {
+        return FootnoteReport {
+            content: content.to_string(),
+            changed: false,
+            missing_definitions: Vec::new(),
+            orphaned_definitions: Vec::new(),
+            duplicate_definitions: Vec::new(),
+        };
+    }
+
+    // 2. Number labels by first reference (body first, then definitions)
+    let mut numbers: HashMap<String, usize> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    for (line, kind) in &body {
+        if *kind == LineKind::Text {
+            rewrite_refs(line, |label| {
+                note(label, &mut numbers, &mut order);
+                String::new()
+            });
+        }
+    }
+
+    let mut first_definition: HashMap<&str, usize> = HashMap::new();
+    let mut duplicates: Vec<String> = Vec::new();
+    for (index, def) in definitions.iter().enumerate() {
+        if first_definition.contains_key(def.label.as_str()) {
+            if !duplicates.contains(&def.label) {
+                duplicates.push(def.label.clone());
+            }
+        } else {
+            first_definition.insert(&def.label, index);
+        }
+    }
+
+    // Footnotes referenced only from other (referenced) footnotes
+    let mut scanned = 0;
+    while scanned < order.len() {
+        if let Some(&index) = first_definition.get(order[scanned].as_str()) {
+            for line in &definitions[index].lines {
+                rewrite_refs(line, |label| {
+                    note(label, &mut numbers, &mut order);
+                    String::new()
+                });
+            }
+        }
+        scanned += 1;
+    }
+
+    let missing: Vec<String> = order
+        .iter()
+        .filter(|label| !first_definition.contains_key(label.as_str()))
+        .cloned()
+        .collect();
+
+    // Unreferenced and duplicate definitions keep their place after the rest
+    let mut emitted: Vec<(usize, &Definition)> = Vec::new();
+    let mut orphaned: Vec<String> = Vec::new();
+    let mut next_number = order.len();
+    let mut extra_numbers: HashMap<usize, usize> = HashMap::new();
+    for (index, def) in definitions.iter().enumerate() {
+        let is_first = first_definition.get(def.label.as_str()) == Some(&index);
+        match numbers.get(&def.label) {
+            Some(&number) if is_first => emitted.push((number, def)),
+            _ => {
+                if is_first && !orphaned.contains(&def.label) {
+                    orphaned.push(def.label.clone());
+                }
+                next_number += 1;
+                extra_numbers.insert(index, next_number);
+                emitted.push((next_number, def));
+            }
+        }
+    }
+    emitted.sort_by_key(|(number, _)| *number);
+
+    // Orphans referenced from inside other orphans resolve to their new number
+    let orphan_numbers: HashMap<&str, usize> = definitions
+        .iter()
+        .enumerate()
+        .filter(|(index, def)| first_definition.get(def.label.as_str()) == Some(index))
+        .filter_map(|(index, def)| extra_numbers.get(&index).map(|n| (def.label.as_str(), *n)))
+        .collect();
+    let relabel = |label: &str| -> String {
+        numbers
+            .get(label)
+            .or_else(|| orphan_numbers.get(label))
+            .map_or_else(|| label.to_string(), |n| n.to_string())
+    };
+
+    // 3. Rebuild: body with references relabeled, then definitions
+    let mut compact: Vec<String> = Vec::with_capacity(body.len());
+    let mut after_removal = false;
+    for (line, kind) in &body {
+        match kind {
+            LineKind::Removed => after_removal = true,
+            LineKind::Code => {
+                compact.push(line.to_string());
+                after_removal = false;
+            }
+            LineKind::Text if line.trim().is_empty() => {
+                // Don't leave the blank lines around a removed definition doubled up
+                if !(after_removal && compact.last().is_none_or(|l| l.trim().is_empty())) {
+                    compact.push(line.to_string());
+                }
+            }
+            LineKind::Text => {
+                compact.push(rewrite_refs(line, relabel));
+                after_removal = false;
+            }
+        }
+    }
+    while compact.last().is_some_and(|l| l.trim().is_empty()) {
+        compact.pop();
+    }
+
+    if !emitted.is_empty() {
+        if !compact.is_empty() {
+            compact.push(String::new());
+        }
+        let mut previous_multiline = false;
+        for (position, (number, def)) in emitted.iter().enumerate() {
+            let multiline = def.lines.len() > 1;
+            if position > 0 && (multiline || previous_multiline) {
+                compact.push(String::new());
+            }
+            compact.push(format!("[^{}]: {}", number, rewrite_refs(&def.lines[0], relabel)));
+            compact.extend(def.lines[1..].iter().map(|l| rewrite_refs(l, relabel)));
+            previous_multiline = multiline;
+        }
+    }
+
+    let mut result = compact.join(newline);
+    if !result.is_empty() {
+        result.push_str(newline);
+    }
+
+    FootnoteReport {
+        changed: result != content,
+        content: result,
+        missing_definitions: missing,
+        orphaned_definitions: orphaned,
+        duplicate_definitions: duplicates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renumbers_by_first_reference_and_moves_definitions() {
+        let input = "# Doc\n\nFirst[^b] and second[^a].\n\n[^a]: Alpha.\n\nMore text[^b].\n\n[^b]: Beta.\n";
+        let report = normalize(input);
+        assert_eq!(
+            report.content,
+            "# Doc\n\nFirst[^1] and second[^2].\n\nMore text[^1].\n\n[^1]: Beta.\n[^2]: Alpha.\n"
+        );
+        assert!(report.changed);
+        assert!(report.missing_definitions.is_empty());
+        assert!(report.orphaned_definitions.is_empty());
+    }
+
+    #[test]
+    fn test_already_normalized_is_unchanged() {
+        let input = "Text[^1].\n\n[^1]: Note.\n";
+        let report = normalize(input);
+        assert_eq!(report.content, input);
+        assert!(!report.changed);
+    }
+
+    #[test]
+    fn test_reports_missing_orphaned_and_duplicate() {
+        let input = "A[^x] B[^gone].\n\n[^x]: One.\n[^x]: Two.\n[^unused]: Never cited.\n";
+        let report = normalize(input);
+        assert_eq!(report.missing_definitions, vec!["gone"]);
+        assert_eq!(report.orphaned_definitions, vec!["unused"]);
+        assert_eq!(report.duplicate_definitions, vec!["x"]);
+        assert_eq!(
+            report.content,
+            "A[^1] B[^2].\n\n[^1]: One.\n[^3]: Two.\n[^4]: Never cited.\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_paragraph_definitions_move_intact() {
+        let input = "[^long]: First paragraph\ncontinued lazily.\n\n    Second paragraph.\n\nBody[^long].\n";
+        let report = normalize(input);
+        assert_eq!(
+            report.content,
+            "Body[^1].\n\n[^1]: First paragraph\ncontinued lazily.\n\n    Second paragraph.\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_references_inside_definitions() {
+        let input = "See[^a].\n\n[^a]: Also see[^b].\n[^b]: Deep.\n";
+        let report = normalize(input);
+        assert_eq!(report.content, "See[^1].\n\n[^1]: Also see[^2].\n[^2]: Deep.\n");
+        assert!(report.orphaned_definitions.is_empty());
+    }
+
+    #[test]
+    fn test_code_is_left_alone() {
+        let input = "Use `[^x]` syntax[^n].\n\n```\n[^n]: not a definition\n```\n\n[^n]: Real.\n";
+        let report = normalize(input);
+        assert_eq!(
+            report.content,
+            "Use `[^x]` syntax[^1].\n\n```\n[^n]: not a definition\n```\n\n[^1]: Real.\n"
+        );
+    }
+
+    #[test]
+    fn test_indented_code_is_left_alone() {
+        let input = "Text[^n].\n\n    [^n]: in code\n    more [^n]\n\n[^n]: Real.\n";
+        let report = normalize(input);
+        assert_eq!(report.content, "Text[^1].\n\n    [^n]: in code\n    more [^n]\n\n[^1]: Real.\n");
+
+        // Indented lines in a list item are the item's text
+        let report = normalize("- Item\n\n    more[^a]\n\n[^a]: Note.\n");
+        assert_eq!(report.content, "- Item\n\n    more[^1]\n\n[^1]: Note.\n");
+    }
+
+    #[test]
+    fn test_no_footnotes_returns_input() {
+        let input = "Plain [link](x) text\n\n\n";
+        let report = normalize(input);
+        assert_eq!(report.content, input);
+        assert!(!report.changed);
+    }
+
+    #[test]
+    fn test_crlf_is_preserved() {
+        let report = normalize("A[^z].\r\n\r\n[^z]: Zed.\r\n");
+        assert_eq!(report.content, "A[^1].\r\n\r\n[^1]: Zed.\r\n");
+    }
+}
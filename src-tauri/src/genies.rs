@@ -8,7 +8,12 @@ use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write as IoWrite;
 use std::path::{Path, PathBuf};
-use tauri::{command, AppHandle, Manager};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{command, AppHandle, Emitter, Manager};
 
 // ============================================================================
 // Types
@@ -43,6 +48,10 @@ pub struct GenieMetadata {
     /// Number of surrounding blocks to include as context (0–2).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<u8>,
+    /// Target language of a translation genie (e.g. "en"); its results are
+    /// kept in translation memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translate: Option<String>,
 }
 
 // ============================================================================
@@ -276,6 +285,7 @@ fn parse_genie(content: &str, path: &str) -> Result<GenieContent, String> {
                 model: None,
                 action: None,
                 context: None,
+                translate: None,
             },
             template: content.to_string(),
         });
@@ -330,6 +340,7 @@ fn parse_genie(content: &str, path: &str) -> Result<GenieContent, String> {
             context: fields.get("context")
                 .and_then(|v| v.parse::<u8>().ok())
                 .filter(|&v| v <= 2),
+            translate: fields.get("translate").filter(|v| !v.is_empty()).cloned(),
         },
         template,
     })
@@ -401,6 +412,19 @@ const DEFAULT_GENIES: &[DefaultGenie] = &[
         path: "tools/rewrite-in-english.md",
         content: include_str!("../resources/genies/tools/rewrite-in-english.md"),
     },
+    DefaultGenie {
+        path: "tools/ask-notes.md",
+        content: include_str!("../resources/genies/tools/ask-notes.md"),
+    },
+    // Metadata
+    DefaultGenie {
+        path: "metadata/summary.md",
+        content: include_str!("../resources/genies/metadata/summary.md"),
+    },
+    DefaultGenie {
+        path: "metadata/keywords.md",
+        content: include_str!("../resources/genies/metadata/keywords.md"),
+    },
 ];
 
 /// Install default genies into `<appDataDir>/genies/` if they don't already exist.
@@ -434,6 +458,79 @@ pub fn install_default_genies(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// ============================================================================
+// Watcher
+// ============================================================================
+
+/// Watches the global genies directory so the menu follows edits on disk.
+static GENIES_WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Set while a debounced rebuild is waiting to run.
+static REBUILD_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Delay before rebuilding, so one save's burst of events rebuilds once.
+const REBUILD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Whether a file system event can change the genie list: any genie file,
+/// or a directory (category) being added, removed or renamed. Hidden files
+/// such as `.DS_Store` are ignored.
+fn is_genie_change(event: &Event) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    event.paths.iter().any(|p| {
+        let hidden = p
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        match p.extension() {
+            _ if hidden => false,
+            Some(ext) => ext.eq_ignore_ascii_case("md"),
+            None => true,
+        }
+    })
+}
+
+/// Start watching `<appDataDir>/genies/`. On changes the native Genies menu is
+/// rebuilt (when shown) and windows get `genies:changed` to reload their list.
+pub fn start_genies_watcher(app: &AppHandle) -> Result<(), String> {
+    let dir = global_genies_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create dir {:?}: {}", dir, e))?;
+
+    let handle = app.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<Event, notify::Error>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if !is_genie_change(&event) || REBUILD_PENDING.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let app = handle.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(REBUILD_DEBOUNCE);
+                REBUILD_PENDING.store(false, Ordering::SeqCst);
+                if crate::menu::genies_menu_shown() {
+                    if let Err(e) = crate::menu::rebuild_genies_menu(&app) {
+                        tracing::warn!("Failed to rebuild menu: {}", e);
+                    }
+                }
+                let _ = app.emit("genies:changed", ());
+            });
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create watcher: {e}"))?;
+
+    watcher
+        .watch(&dir, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch genies: {e}"))?;
+
+    if let Ok(mut guard) = GENIES_WATCHER.lock() {
+        *guard = Some(watcher);
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -485,6 +582,16 @@ You are an expert editor. Improve the following text:
         assert_eq!(result.metadata.context, None);
     }
 
+    #[test]
+    fn test_parse_genie_with_translate() {
+        let content = "---\nname: to-french\nscope: selection\ntranslate: fr\n---\n\n{{content}}";
+        let result = parse_genie(content, "to-french.md").unwrap();
+        assert_eq!(result.metadata.translate.as_deref(), Some("fr"));
+
+        let content = "---\nname: plain\ntranslate:\n---\n\n{{content}}";
+        assert_eq!(parse_genie(content, "plain.md").unwrap().metadata.translate, None);
+    }
+
     #[test]
     fn test_parse_genie_with_action_insert() {
         let content = "---\nname: continue\nscope: block\naction: insert\n---\n\nContinue writing.\n\n{{content}}";
@@ -575,4 +682,18 @@ You are an expert editor. Improve the following text:
         let content = "---\nname: \"My Genie\"\nscope: selection\n---\n\nTemplate";
         assert_eq!(extract_frontmatter_name(content), Some("My Genie".to_string()));
     }
+
+    #[test]
+    fn test_is_genie_change() {
+        let event = |kind: EventKind, path: &str| Event::new(kind).add_path(PathBuf::from(path));
+        let modify = EventKind::Modify(notify::event::ModifyKind::Any);
+
+        assert!(is_genie_change(&event(modify, "/genies/tools/translate.md")));
+        assert!(is_genie_change(&event(modify, "/genies/writing")));
+        assert!(!is_genie_change(&event(modify, "/genies/.DS_Store")));
+        assert!(!is_genie_change(&event(
+            EventKind::Access(notify::event::AccessKind::Any),
+            "/genies/translate.md"
+        )));
+    }
 }
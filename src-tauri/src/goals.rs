@@ -0,0 +1,404 @@
+//! Writing Goals
+//!
+//! Tracks words written per day and per document from save deltas: each
+//! save reports the document's content, and the growth in word count since
+//! the previous save is added to today's total. Deleting text doesn't count
+//! against the day.
+//!
+//! Goals are a daily word target and per-project targets (total words in a
+//! document, or across the documents under a folder). Meeting a goal shows a
+//! system notification and emits `goals:met`. Everything is stored in
+//! `<app_data>/goals.json`.
+
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::workspace_stats::{count_words, prose};
+
+const DATA_FILE: &str = "goals.json";
+
+/// Days of history kept in the data file
+const MAX_HISTORY_DAYS: i64 = 366;
+
+/// Serializes reads and writes of the data file
+static DATA_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalKind {
+    Daily,
+    Project,
+}
+
+/// Words written on one day
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct DayRecord {
+    words: u64,
+    /// Words written per document path
+    documents: BTreeMap<String, u64>,
+}
+
+/// Contents of the data file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct GoalsData {
+    daily_goal: Option<u64>,
+    /// Target word count by document or folder path
+    project_goals: BTreeMap<String, u64>,
+    /// Word count at the last save, by document path
+    word_counts: BTreeMap<String, u64>,
+    /// Keyed by `YYYY-MM-DD`
+    days: BTreeMap<String, DayRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectProgress {
+    pub path: String,
+    pub target: u64,
+    pub current: u64,
+    pub met: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayWords {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub words: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub today_words: u64,
+    pub daily_goal: Option<u64>,
+    pub daily_met: bool,
+    /// Consecutive days the daily goal was met (any writing counts when no
+    /// goal is set). Today counts once it's met; an unmet today doesn't
+    /// break the streak yet.
+    pub streak: u32,
+    pub projects: Vec<ProjectProgress>,
+    /// The last 7 days, oldest first
+    pub recent_days: Vec<DayWords>,
+}
+
+/// Payload for `goals:met`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum GoalMet {
+    Daily { target: u64 },
+    Project { path: String, target: u64 },
+}
+
+fn date_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn words_on(data: &GoalsData, date: NaiveDate) -> u64 {
+    data.days.get(&date_key(date)).map_or(0, |d| d.words)
+}
+
+fn day_met(data: &GoalsData, date: NaiveDate) -> bool {
+    let words = words_on(data, date);
+    match data.daily_goal {
+        Some(goal) => words >= goal,
+        None => words > 0,
+    }
+}
+
+fn streak(data: &GoalsData, today: NaiveDate) -> u32 {
+    let mut date = if day_met(data, today) { today } else { today - Duration::days(1) };
+    let mut count = 0;
+    while day_met(data, date) {
+        count += 1;
+        date -= Duration::days(1);
+    }
+    count
+}
+
+/// Whether `doc` is the project path or a document inside the project folder.
+fn in_project(doc: &str, project: &str) -> bool {
+    Path::new(doc).starts_with(Path::new(project))
+}
+
+fn project_words(data: &GoalsData, project: &str) -> u64 {
+    data.word_counts
+        .iter()
+        .filter(|(doc, _)| in_project(doc, project))
+        .map(|(_, words)| words)
+        .sum()
+}
+
+fn progress(data: &GoalsData, today: NaiveDate) -> GoalProgress {
+    let today_words = words_on(data, today);
+    GoalProgress {
+        today_words,
+        daily_goal: data.daily_goal,
+        daily_met: data.daily_goal.is_some_and(|goal| today_words >= goal),
+        streak: streak(data, today),
+        projects: data
+            .project_goals
+            .iter()
+            .map(|(path, &target)| {
+                let current = project_words(data, path);
+                ProjectProgress {
+                    path: path.clone(),
+                    target,
+                    current,
+                    met: current >= target,
+                }
+            })
+            .collect(),
+        recent_days: (0..7)
+            .rev()
+            .map(|offset| {
+                let date = today - Duration::days(offset);
+                DayWords {
+                    date: date_key(date),
+                    words: words_on(data, date),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Record a save of `path` with `words` words. Returns the goals this save met.
+fn apply_save(data: &mut GoalsData, today: NaiveDate, path: &str, words: u64, is_new: bool) -> Vec<GoalMet> {
+    let before = progress(data, today);
+
+    // An unseen existing document is a baseline, not words written today
+    let previous = match data.word_counts.get(path) {
+        Some(&count) => count,
+        None if is_new => 0,
+        None => words,
+    };
+    data.word_counts.insert(path.to_string(), words);
+    let written = words.saturating_sub(previous);
+    if written > 0 {
+        let day = data.days.entry(date_key(today)).or_default();
+        day.words += written;
+        *day.documents.entry(path.to_string()).or_default() += written;
+    }
+    let oldest = date_key(today - Duration::days(MAX_HISTORY_DAYS));
+    data.days.retain(|date, _| *date >= oldest);
+
+    let after = progress(data, today);
+    let mut met = Vec::new();
+    if let (false, true, Some(target)) = (before.daily_met, after.daily_met, data.daily_goal) {
+        met.push(GoalMet::Daily { target });
+    }
+    for (old, new) in before.projects.iter().zip(&after.projects) {
+        if !old.met && new.met {
+            met.push(GoalMet::Project {
+                path: new.path.clone(),
+                target: new.target,
+            });
+        }
+    }
+    met
+}
+
+fn data_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join(DATA_FILE))
+}
+
+fn load_data(path: &Path) -> GoalsData {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_data(path: &Path, data: &GoalsData) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data folder: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(path, json.as_bytes())
+}
+
+fn update_data<T>(app: &AppHandle, f: impl FnOnce(&mut GoalsData) -> T) -> Result<T, String> {
+    let path = data_path(app)?;
+    let _guard = DATA_LOCK.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut data = load_data(&path);
+    let result = f(&mut data);
+    save_data(&path, &data)?;
+    Ok(result)
+}
+
+fn notify_met(app: &AppHandle, met: &GoalMet) {
+    let body = match met {
+        GoalMet::Daily { target } => format!("You wrote {} words today.", target),
+        GoalMet::Project { path, target } => {
+            let name = Path::new(path).file_name().map_or_else(|| path.clone(), |n| n.to_string_lossy().into_owned());
+            format!("{} reached {} words.", name, target)
+        }
+    };
+    if let Err(e) = app.notification().builder().title("Writing goal met").body(body).show() {
+        tracing::warn!("[Goals] Failed to show notification: {}", e);
+    }
+    let _ = app.emit("goals:met", met);
+}
+
+/// Record a document save. `is_new` marks a document created in this
+/// session, whose first save counts in full.
+#[tauri::command]
+pub fn record_document_save(
+    app: AppHandle,
+    path: String,
+    content: String,
+    is_new: Option<bool>,
+) -> Result<GoalProgress, String> {
+    let words = count_words(&prose(&content)) as u64;
+    let today = Local::now().date_naive();
+    let (met, progress) = update_data(&app, |data| {
+        let met = apply_save(data, today, &path, words, is_new.unwrap_or(false));
+        (met, progress(data, today))
+    })?;
+    for goal in &met {
+        notify_met(&app, goal);
+    }
+    Ok(progress)
+}
+
+/// Set (or with `target` 0 / absent, clear) a goal. Project goals need the
+/// document or folder `path`.
+#[tauri::command]
+pub fn set_goal(app: AppHandle, kind: GoalKind, target: Option<u64>, path: Option<String>) -> Result<GoalProgress, String> {
+    let target = target.filter(|t| *t > 0);
+    let project = match kind {
+        GoalKind::Project => Some(
+            path.filter(|p| !p.is_empty())
+                .ok_or("A project goal needs a document or folder path")?,
+        ),
+        GoalKind::Daily => None,
+    };
+    let today = Local::now().date_naive();
+    update_data(&app, |data| {
+        match (project, target) {
+            (None, target) => data.daily_goal = target,
+            (Some(path), Some(target)) => {
+                data.project_goals.insert(path, target);
+            }
+            (Some(path), None) => {
+                data.project_goals.remove(&path);
+            }
+        }
+        progress(data, today)
+    })
+}
+
+/// Today's words, goals and streak.
+#[tauri::command]
+pub fn get_progress(app: AppHandle) -> Result<GoalProgress, String> {
+    let data = load_data(&data_path(&app)?);
+    Ok(progress(&data, Local::now().date_naive()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_save_deltas() {
+        let mut data = GoalsData::default();
+        // Existing document: first save is only a baseline
+        apply_save(&mut data, day(10), "/notes/a.md", 500, false);
+        assert_eq!(words_on(&data, day(10)), 0);
+
+        apply_save(&mut data, day(10), "/notes/a.md", 650, false);
+        apply_save(&mut data, day(10), "/notes/a.md", 600, false);
+        apply_save(&mut data, day(10), "/notes/b.md", 40, true);
+        assert_eq!(words_on(&data, day(10)), 190);
+        assert_eq!(data.days["2026-03-10"].documents["/notes/a.md"], 150);
+
+        // Deleted words must be rewritten before they count again
+        apply_save(&mut data, day(10), "/notes/a.md", 620, false);
+        assert_eq!(words_on(&data, day(10)), 210);
+    }
+
+    #[test]
+    fn test_goals_met_once() {
+        let mut data = GoalsData {
+            daily_goal: Some(100),
+            ..Default::default()
+        };
+        data.project_goals.insert("/book".to_string(), 1000);
+
+        apply_save(&mut data, day(10), "/book/ch1.md", 900, false);
+        let met = apply_save(&mut data, day(10), "/book/ch1.md", 1010, false);
+        assert_eq!(
+            met,
+            vec![
+                GoalMet::Daily { target: 100 },
+                GoalMet::Project {
+                    path: "/book".to_string(),
+                    target: 1000
+                }
+            ]
+        );
+        assert!(apply_save(&mut data, day(10), "/book/ch1.md", 1100, false).is_empty());
+        assert_eq!(progress(&data, day(10)).projects[0].current, 1100);
+    }
+
+    #[test]
+    fn test_in_project_matches_whole_components() {
+        assert!(in_project("/book/ch1.md", "/book"));
+        assert!(in_project("/book/ch1.md", "/book/ch1.md"));
+        assert!(!in_project("/bookshelf/a.md", "/book"));
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut data = GoalsData {
+            daily_goal: Some(10),
+            ..Default::default()
+        };
+        for (d, words) in [(6, 20), (7, 5), (8, 10), (9, 30)] {
+            data.days.insert(date_key(day(d)), DayRecord { words, ..Default::default() });
+        }
+        // Today not met yet: the streak through yesterday still stands
+        assert_eq!(streak(&data, day(10)), 2);
+        data.days.insert(date_key(day(10)), DayRecord { words: 10, ..Default::default() });
+        assert_eq!(streak(&data, day(10)), 3);
+        assert_eq!(streak(&data, day(12)), 0);
+    }
+
+    #[test]
+    fn test_progress_recent_days() {
+        let mut data = GoalsData::default();
+        apply_save(&mut data, day(10), "/a.md", 12, true);
+        let progress = progress(&data, day(10));
+        assert_eq!(progress.recent_days.len(), 7);
+        assert_eq!(progress.recent_days[6], DayWords { date: "2026-03-10".to_string(), words: 12 });
+        assert!(!progress.daily_met);
+        assert_eq!(progress.streak, 1);
+    }
+
+    #[test]
+    fn test_data_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DATA_FILE);
+        let mut data = load_data(&path);
+        data.daily_goal = Some(500);
+        apply_save(&mut data, day(10), "/a.md", 12, true);
+        save_data(&path, &data).unwrap();
+
+        let loaded = load_data(&path);
+        assert_eq!(loaded.daily_goal, Some(500));
+        assert_eq!(loaded.word_counts["/a.md"], 12);
+    }
+}
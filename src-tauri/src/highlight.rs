@@ -0,0 +1,229 @@
+//! Code Block Highlighting for Exports
+//!
+//! HTML/PDF export and Copy as HTML take the editor's rendered HTML, whose
+//! code blocks are colored by CSS classes that only exist inside the app.
+//! `highlight_code_blocks` re-highlights each fenced block with syntect and
+//! inline styles, so the colors survive in a browser, a mail client or a
+//! printed PDF. The syntect theme follows the editor theme.
+//!
+//! Highlighted blocks are cached by (language, theme, content hash), so
+//! re-exporting a document, or a live preview refresh, only highlights the
+//! blocks that changed.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::rich_text::decode_entities;
+
+/// Drop the cache once it holds this many blocks.
+const MAX_CACHED_BLOCKS: usize = 1000;
+
+#[derive(Hash, PartialEq, Eq)]
+struct CacheKey {
+    lang: String,
+    theme: &'static str,
+    hash: String,
+}
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn themes() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+fn cache() -> &'static Mutex<HashMap<CacheKey, String>> {
+    static CACHE: OnceLock<Mutex<HashMap<CacheKey, String>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The bundled syntect theme closest to an editor theme.
+fn syntect_theme(editor_theme: &str) -> &'static str {
+    match editor_theme {
+        "night" => "base16-ocean.dark",
+        "sepia" => "Solarized (light)",
+        _ => "InspiredGitHub",
+    }
+}
+
+/// Highlighted spans for `code`, or None if the language is unknown.
+fn highlight_spans(code: &str, lang: &str, theme: &Theme) -> Option<String> {
+    let syntax = syntaxes().find_syntax_by_token(lang)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut html = String::with_capacity(code.len() * 2);
+    for line in LinesWithEndings::from(code) {
+        let regions = highlighter.highlight_line(line, syntaxes()).ok()?;
+        html.push_str(&styled_line_to_highlighted_html(&regions, IncludeBackground::No).ok()?);
+    }
+    Some(html)
+}
+
+/// `highlight_spans` through the cache.
+fn highlight_cached(code: &str, lang: &str, editor_theme: &str) -> Option<String> {
+    let theme_name = syntect_theme(editor_theme);
+    let key = CacheKey {
+        lang: lang.to_ascii_lowercase(),
+        theme: theme_name,
+        hash: crate::watcher::content_hash(code.as_bytes()),
+    };
+    if let Some(html) = cache().lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return Some(html.clone());
+    }
+    let html = highlight_spans(code, lang, themes().themes.get(theme_name)?)?;
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= MAX_CACHED_BLOCKS {
+        cache.clear();
+    }
+    cache.insert(key, html.clone());
+    Some(html)
+}
+
+/// `style` for a `<pre>` using the theme's colors.
+fn pre_style(editor_theme: &str) -> String {
+    let Some(theme) = themes().themes.get(syntect_theme(editor_theme)) else {
+        return String::new();
+    };
+    let hex = |c: syntect::highlighting::Color| format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b);
+    let mut style = String::new();
+    if let Some(bg) = theme.settings.background {
+        style.push_str(&format!("background-color:{};", hex(bg)));
+    }
+    if let Some(fg) = theme.settings.foreground {
+        style.push_str(&format!("color:{};", hex(fg)));
+    }
+    style
+}
+
+/// Text content of an HTML fragment.
+fn text_content(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(lt) = rest.find('<') {
+        text.push_str(&rest[..lt]);
+        rest = rest[lt..].find('>').map_or("", |gt| &rest[lt + gt + 1..]);
+    }
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+/// Re-highlight every `<pre><code class="language-…">` block in `html`.
+/// Blocks in unknown languages (or diagrams) are left as they are.
+fn highlight_html(html: &str, editor_theme: &str) -> String {
+    const CODE_OPEN: &str = "<code class=\"language-";
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(pre) = rest.find("<pre") {
+        out.push_str(&rest[..pre]);
+        rest = &rest[pre..];
+        let replaced = (|| {
+            let pre_end = rest.find('>')?;
+            let pre_attrs = &rest[4..pre_end];
+            let after_pre = &rest[pre_end + 1..];
+            let code_start = after_pre.strip_prefix(CODE_OPEN)?;
+            let lang_end = code_start.find(['"', ' '])?;
+            let lang = &code_start[..lang_end];
+            let open_end = code_start.find('>')?;
+            let body = &code_start[open_end + 1..];
+            let close = body.find("</code>")?;
+            let spans = highlight_cached(&text_content(&body[..close]), lang, editor_theme)?;
+            let style = match pre_style(editor_theme) {
+                style if style.is_empty() || pre_attrs.contains("style=") => String::new(),
+                style => format!(" style=\"{style}\""),
+            };
+            let block = format!(
+                "<pre{pre_attrs}{style}>{CODE_OPEN}{}>{spans}</code>",
+                &code_start[..open_end]
+            );
+            let consumed = pre_end + 1 + CODE_OPEN.len() + open_end + 1 + close + "</code>".len();
+            Some((block, consumed))
+        })();
+        match replaced {
+            Some((block, consumed)) => {
+                out.push_str(&block);
+                rest = &rest[consumed..];
+            }
+            None => {
+                out.push_str("<pre");
+                rest = &rest[4..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Highlight one snippet; None when the language isn't known.
+#[tauri::command]
+pub async fn highlight_code(
+    code: String,
+    lang: String,
+    theme: String,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || highlight_cached(&code, &lang, &theme))
+        .await
+        .map_err(|e| format!("Highlighting failed: {e}"))
+}
+
+/// Re-highlight the code blocks of rendered document HTML for export.
+/// `theme` is the editor theme id (e.g. "paper", "night").
+#[tauri::command]
+pub async fn highlight_code_blocks(html: String, theme: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || highlight_html(&html, &theme))
+        .await
+        .map_err(|e| format!("Highlighting failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editor_themes_map_to_bundled_themes() {
+        for editor in ["white", "paper", "mint", "sepia", "night", "unknown"] {
+            assert!(
+                themes().themes.contains_key(syntect_theme(editor)),
+                "{editor}"
+            );
+        }
+    }
+
+    #[test]
+    fn text_content_strips_tags_and_entities() {
+        let html = "<span class=\"hljs-keyword\">if</span> a &lt; b &amp;&amp; c";
+        assert_eq!(text_content(html), "if a < b && c");
+    }
+
+    #[test]
+    fn highlights_known_languages_with_inline_styles() {
+        let html = "<p>x</p><pre><code class=\"language-rust\"><span class=\"hljs-keyword\">fn</span> main() {}</code></pre>";
+        let out = highlight_html(html, "paper");
+        assert!(out.starts_with("<p>x</p><pre style=\"background-color:"));
+        assert!(out.contains("<code class=\"language-rust\"><span style="));
+        assert!(!out.contains("hljs-keyword"));
+        assert!(out.ends_with("</code></pre>"));
+        assert_eq!(text_content(&out), "xfn main() {}");
+    }
+
+    #[test]
+    fn leaves_other_blocks_alone() {
+        let html = "<pre><code class=\"language-mermaid\">graph TD</code></pre><pre><code>plain</code></pre><pre>";
+        assert_eq!(highlight_html(html, "paper"), html);
+    }
+
+    #[test]
+    fn cache_returns_the_same_html() {
+        let first = highlight_cached("let x = 1;\n", "rust", "night").unwrap();
+        assert_eq!(
+            highlight_cached("let x = 1;\n", "rust", "night").unwrap(),
+            first
+        );
+    }
+}
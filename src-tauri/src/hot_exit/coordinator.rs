@@ -58,7 +58,7 @@ pub(crate) fn get_pending_restore_state() -> Arc<Mutex<PendingRestoreState>> {
 /// Lock the pending restore state, recovering from poisoning
 fn lock_pending_restore(pending: &Arc<Mutex<PendingRestoreState>>) -> std::sync::MutexGuard<'_, PendingRestoreState> {
     pending.lock().unwrap_or_else(|poisoned| {
-        eprintln!("[HotExit] Recovering from poisoned mutex");
+        tracing::warn!("Recovering from poisoned mutex");
         poisoned.into_inner()
     })
 }
@@ -94,8 +94,8 @@ struct CaptureState {
 /// Normalize window state label to match expected label
 fn normalize_window_label(state: &mut WindowState, expected_label: &str) {
     if state.window_label != expected_label {
-        eprintln!(
-            "[HotExit] Normalizing mismatched window_label: {} -> {}",
+        tracing::warn!(
+            "Normalizing mismatched window_label: {} -> {}",
             state.window_label,
             expected_label
         );
@@ -139,14 +139,14 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
         match serde_json::from_str::<CaptureResponse>(event.payload()) {
             Ok(mut response) => {
                 let mut state = state_clone.lock().unwrap_or_else(|poisoned| {
-                    eprintln!("[HotExit] Recovering from poisoned capture state mutex");
+                    tracing::warn!("Recovering from poisoned capture state mutex");
                     poisoned.into_inner()
                 });
 
                 // Ignore responses from different capture requests (stale responses)
                 if response.capture_id != state.capture_id {
-                    eprintln!(
-                        "[HotExit] Ignoring stale response (capture_id mismatch: {} vs {})",
+                    tracing::warn!(
+                        "Ignoring stale response (capture_id mismatch: {} vs {})",
                         response.capture_id,
                         state.capture_id
                     );
@@ -155,8 +155,8 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
 
                 // Only accept responses from expected windows
                 if !state.expected_windows.contains(&response.window_label) {
-                    eprintln!(
-                        "[HotExit] Ignoring response from unexpected window: {}",
+                    tracing::warn!(
+                        "Ignoring response from unexpected window: {}",
                         response.window_label
                     );
                     return;
@@ -164,8 +164,8 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
 
                 // Ignore duplicate responses from the same window
                 if state.responses.contains_key(&response.window_label) {
-                    eprintln!(
-                        "[HotExit] Ignoring duplicate response from window: {}",
+                    tracing::warn!(
+                        "Ignoring duplicate response from window: {}",
                         response.window_label
                     );
                     return;
@@ -177,8 +177,8 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
                 state.responses.insert(response.window_label.clone(), response.state);
             }
             Err(e) => {
-                eprintln!(
-                    "[HotExit] Failed to parse capture response ({}): {}",
+                tracing::warn!(
+                    "Failed to parse capture response ({}): {}",
                     event.payload().len(),
                     e
                 );
@@ -211,13 +211,13 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
 
     if result.is_err() {
         // Timeout occurred
-        eprintln!(
-            "[HotExit] Timeout: Got {}/{} window responses",
+        tracing::warn!(
+            "Timeout: Got {}/{} window responses",
             got_responses,
             expected_responses
         );
         if let Err(e) = app.emit(EVENT_CAPTURE_TIMEOUT, ()) {
-            eprintln!("[HotExit] Failed to emit capture timeout event: {}", e);
+            tracing::warn!("Failed to emit capture timeout event: {}", e);
         }
 
         // If we got zero responses, this is a critical failure
@@ -237,6 +237,14 @@ pub async fn capture_session(app: &AppHandle) -> Result<SessionData, String> {
         }
     });
 
+    // Terminal cwd/shell/scrollback live in the backend, not in the webview
+    let keep_scrollback = crate::settings::current().terminal.restore_scrollback;
+    for window in &mut windows_vec {
+        for terminal in &mut window.terminal_sessions {
+            crate::terminal::fill_session_state(terminal, keep_scrollback);
+        }
+    }
+
     let session = SessionData {
         version: SCHEMA_VERSION,
         timestamp: chrono::Utc::now().timestamp(),
@@ -264,8 +272,8 @@ async fn wait_for_all_responses(state: Arc<Mutex<CaptureState>>, expected: usize
 fn prepare_session_for_restore(session: SessionData) -> Result<SessionData, String> {
     // Migrate session if needed
     let session = if needs_migration(&session) {
-        eprintln!(
-            "[HotExit] Migrating session from v{} to v{}",
+        tracing::info!(
+            "Migrating session from v{} to v{}",
             session.version, SCHEMA_VERSION
         );
         migrate_session(session)?
@@ -283,6 +291,13 @@ fn prepare_session_for_restore(session: SessionData) -> Result<SessionData, Stri
         return Err(format!("Session is too old (>{} days)", MAX_SESSION_AGE_DAYS));
     }
 
+    // Seed terminal scrollback so recreated sessions replay their output
+    for window in &session.windows {
+        for terminal in &window.terminal_sessions {
+            crate::terminal::seed_scrollback(&terminal.session_id, &terminal.scrollback);
+        }
+    }
+
     Ok(session)
 }
 
@@ -408,7 +423,7 @@ pub fn restore_session_multi_window(
         };
         window_states_to_store.push((MAIN_WINDOW_LABEL.to_string(), normalized));
     } else {
-        eprintln!("[HotExit] Warning: No main window state in session, main will restore empty");
+        tracing::warn!("No main window state in session, main will restore empty");
     }
 
     // Create secondary windows and collect their new labels
@@ -427,8 +442,8 @@ pub fn restore_session_multi_window(
                 windows_created.push(new_label);
             }
             Err(e) => {
-                eprintln!(
-                    "[HotExit] Failed to create window for {}: {}",
+                tracing::warn!(
+                    "Failed to create window for {}: {}",
                     window_state.window_label, e
                 );
                 // Don't add to expected_labels - window doesn't exist
@@ -469,8 +484,8 @@ pub fn mark_window_restore_complete(window_label: &str) -> bool {
     if state.expected_labels.contains(window_label) {
         state.completed_windows.insert(window_label.to_string());
     } else {
-        eprintln!(
-            "[HotExit] Ignoring completion from unexpected window: {}",
+        tracing::warn!(
+            "Ignoring completion from unexpected window: {}",
             window_label
         );
     }
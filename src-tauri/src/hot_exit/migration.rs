@@ -61,8 +61,9 @@ pub fn migrate_session(mut session: SessionData) -> Result<SessionData, String>
 fn migrate_to_next_version(session: SessionData) -> Result<SessionData, String> {
     match session.version {
         1 => migrate_v1_to_v2(session),
+        2 => migrate_v2_to_v3(session),
         // Add future migrations here:
-        // 2 => migrate_v2_to_v3(session),
+        // 3 => migrate_v3_to_v4(session),
 
         _ => Err(format!("No migration path from version {}", session.version)),
     }
@@ -83,6 +84,16 @@ fn migrate_v1_to_v2(mut session: SessionData) -> Result<SessionData, String> {
     Ok(session)
 }
 
+/// Migrate v2 -> v3: Add terminal sessions to windows
+///
+/// v3 adds terminal_sessions to WindowState so integrated terminals
+/// survive restarts. Like v2, serde's #[serde(default)] fills the empty
+/// list; only the version changes.
+fn migrate_v2_to_v3(mut session: SessionData) -> Result<SessionData, String> {
+    session.version = 3;
+    Ok(session)
+}
+
 /// Check if session needs migration.
 pub fn needs_migration(session: &SessionData) -> bool {
     session.version < SCHEMA_VERSION
@@ -171,4 +182,35 @@ mod tests {
             assert!(needs_migration(&session));
         }
     }
+
+    #[test]
+    fn test_migrate_v2_session_gets_empty_terminals() {
+        let json = r#"{
+            "version": 2,
+            "timestamp": 0,
+            "vmark_version": "0.3.24",
+            "windows": [{
+                "window_label": "main",
+                "is_main_window": true,
+                "active_tab_id": null,
+                "tabs": [],
+                "ui_state": {
+                    "sidebar_visible": true,
+                    "sidebar_width": 260,
+                    "outline_visible": false,
+                    "sidebar_view_mode": "files",
+                    "status_bar_visible": true,
+                    "source_mode_enabled": false,
+                    "focus_mode_enabled": false,
+                    "typewriter_mode_enabled": false
+                },
+                "geometry": null
+            }],
+            "workspace": null
+        }"#;
+        let session: SessionData = serde_json::from_str(json).unwrap();
+        let migrated = migrate_session(session).unwrap();
+        assert_eq!(migrated.version, SCHEMA_VERSION);
+        assert!(migrated.windows[0].terminal_sessions.is_empty());
+    }
 }
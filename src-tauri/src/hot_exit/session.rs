@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 /// Schema version for hot exit sessions
 /// v1: Initial schema
 /// v2: Added undo_history and redo_history to DocumentState
-pub const SCHEMA_VERSION: u32 = 2;
+/// v3: Added terminal_sessions to WindowState
+pub const SCHEMA_VERSION: u32 = 3;
 
 /// Maximum session age in days before considering it stale
 pub const MAX_SESSION_AGE_DAYS: i64 = 7;
@@ -33,6 +34,28 @@ pub struct WindowState {
     pub tabs: Vec<TabState>,
     pub ui_state: UiState,
     pub geometry: Option<WindowGeometry>,
+    /// Integrated terminal sessions - added in v3
+    #[serde(default)]
+    pub terminal_sessions: Vec<TerminalSessionState>,
+}
+
+/// Integrated terminal session.
+/// The frontend reports id/label/active; cwd, shell and scrollback are
+/// filled in by the backend at capture time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TerminalSessionState {
+    pub session_id: String,
+    pub label: String,
+    pub is_active: bool,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub shell_path: Option<String>,
+    #[serde(default)]
+    pub shell_kind: Option<String>,
+    /// Recent raw output (escape sequences included) replayed on restore
+    #[serde(default)]
+    pub scrollback: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -142,7 +165,7 @@ impl SessionData {
     pub fn is_stale(&self, max_age_days: i64) -> bool {
         // Guard against invalid input
         if max_age_days <= 0 {
-            eprintln!("[HotExit] Warning: max_age_days must be positive (got {})", max_age_days);
+            tracing::warn!("max_age_days must be positive (got {})", max_age_days);
             return true; // Treat as stale to be safe
         }
 
@@ -151,7 +174,7 @@ impl SessionData {
 
         // Treat future timestamps as stale (clock skew)
         if age_seconds < 0 {
-            eprintln!("[HotExit] Warning: Session timestamp is in the future (clock skew)");
+            tracing::warn!("Session timestamp is in the future (clock skew)");
             return true;
         }
 
@@ -159,7 +182,7 @@ impl SessionData {
         match max_age_days.checked_mul(SECONDS_PER_DAY) {
             Some(max_age_seconds) => age_seconds > max_age_seconds,
             None => {
-                eprintln!("[HotExit] Warning: max_age_days overflow ({})", max_age_days);
+                tracing::warn!("max_age_days overflow ({})", max_age_days);
                 true // Treat as stale on overflow
             }
         }
@@ -0,0 +1,264 @@
+//! Global hotkeys - system-wide shortcuts that work while VMark is in the
+//! background.
+//!
+//! Bindings (action id -> accelerator) are stored in the app data directory
+//! (`global-hotkeys.json`) and registered at startup. Built-in actions run
+//! natively (`quick-capture` opens the capture window, `focus-vmark` brings
+//! VMark to the front); every trigger is also broadcast to the frontend as
+//! `global-hotkey:triggered` with the action id.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::keymap::normalize_accelerator;
+use crate::{app_paths, menu, quick_capture, quit, window_manager};
+
+/// Bindings file name in the app data directory
+pub const GLOBAL_HOTKEYS_FILE: &str = "global-hotkeys.json";
+
+/// Open the quick capture window
+pub const ACTION_QUICK_CAPTURE: &str = "quick-capture";
+/// Bring VMark to the front
+pub const ACTION_FOCUS_VMARK: &str = "focus-vmark";
+
+/// Loaded bindings plus the file they persist to
+struct HotkeyState {
+    file: PathBuf,
+    bindings: HashMap<String, String>,
+}
+
+static HOTKEY_STATE: OnceLock<Mutex<HotkeyState>> = OnceLock::new();
+
+/// Payload of the "global-hotkey:triggered" event
+#[derive(Debug, Clone, Serialize)]
+struct HotkeyTriggered {
+    id: String,
+}
+
+fn load_bindings(file: &Path) -> HashMap<String, String> {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_bindings(file: &Path, bindings: &HashMap<String, String>) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let sorted: BTreeMap<_, _> = bindings.iter().collect();
+    let content = serde_json::to_string_pretty(&sorted)
+        .map_err(|e| format!("Failed to serialize global hotkeys: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+pub(crate) fn parse_shortcut(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut \"{}\": {}", accelerator, e))
+}
+
+/// Why `accelerator` can't be bound to `id`: another hotkey or a menu
+/// item already uses it.
+fn find_conflict(
+    bindings: &HashMap<String, String>,
+    menu_accelerators: &[menu::MenuAccelerator],
+    id: &str,
+    accelerator: &str,
+) -> Option<String> {
+    let target = normalize_accelerator(accelerator);
+    let mut others: Vec<(&String, &String)> = bindings.iter().filter(|(other, _)| *other != id).collect();
+    others.sort();
+    if let Some((other, _)) = others
+        .into_iter()
+        .find(|(_, accel)| normalize_accelerator(accel) == target)
+    {
+        return Some(format!("global hotkey \"{}\"", other));
+    }
+    menu_accelerators
+        .iter()
+        .find(|a| !a.accelerator.is_empty() && normalize_accelerator(&a.accelerator) == target)
+        .map(|a| format!("menu item \"{}\"", a.id))
+}
+
+/// Action bound to a pressed shortcut.
+fn action_for(bindings: &HashMap<String, String>, shortcut: &Shortcut) -> Option<String> {
+    bindings
+        .iter()
+        .find(|(_, accel)| accel.parse::<Shortcut>().is_ok_and(|s| s == *shortcut))
+        .map(|(id, _)| id.clone())
+}
+
+/// Show and focus a document window, creating the main window if none exist.
+fn focus_vmark(app: &AppHandle) {
+    let mut labels: Vec<String> = app
+        .webview_windows()
+        .into_keys()
+        .filter(|label| quit::is_document_window_label(label))
+        .collect();
+    labels.sort();
+    let window = app
+        .get_webview_window("main")
+        .or_else(|| labels.first().and_then(|label| app.get_webview_window(label)));
+    match window {
+        Some(window) => {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        None => {
+            let _ = window_manager::create_main_window(app);
+        }
+    }
+}
+
+/// Global shortcut handler, installed with the plugin.
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let id = HOTKEY_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .and_then(|guard| action_for(&guard.bindings, shortcut));
+    let Some(id) = id else {
+        return;
+    };
+
+    match id.as_str() {
+        ACTION_QUICK_CAPTURE => {
+            if let Err(e) = quick_capture::show_capture_window(app) {
+                tracing::warn!("Failed to open quick capture: {}", e);
+            }
+        }
+        ACTION_FOCUS_VMARK => focus_vmark(app),
+        _ => {}
+    }
+    let _ = app.emit("global-hotkey:triggered", HotkeyTriggered { id });
+}
+
+/// Load saved bindings and register them. Called once during app setup.
+/// Bindings the OS refuses (taken by another app) are kept but reported.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(GLOBAL_HOTKEYS_FILE);
+    let bindings = load_bindings(&file);
+
+    for (id, accelerator) in &bindings {
+        let result = parse_shortcut(accelerator)
+            .and_then(|shortcut| app.global_shortcut().register(shortcut).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::warn!("Failed to register {} ({}): {}", id, accelerator, e);
+        }
+    }
+
+    let _ = HOTKEY_STATE.set(Mutex::new(HotkeyState { file, bindings }));
+    Ok(())
+}
+
+/// Bind `accelerator` to action `id`, replacing the action's previous binding.
+/// Fails on an invalid accelerator, a conflict with another hotkey or menu
+/// shortcut, or when the OS refuses it (already taken by another app).
+#[tauri::command]
+pub fn register_global_hotkey(app: AppHandle, id: String, accelerator: String) -> Result<(), String> {
+    let accelerator = accelerator.trim().to_string();
+    let shortcut = parse_shortcut(&accelerator)?;
+    let state = HOTKEY_STATE.get().ok_or("Global hotkeys not initialized")?;
+    let mut guard = state.lock().map_err(|_| "Global hotkeys lock poisoned".to_string())?;
+
+    if let Some(conflict) = find_conflict(&guard.bindings, &menu::menu_accelerators(), &id, &accelerator) {
+        return Err(format!("{} is already used by {}", accelerator, conflict));
+    }
+
+    let previous = guard.bindings.get(&id).cloned();
+    if let Some(previous) = &previous {
+        let _ = app.global_shortcut().unregister(previous.as_str());
+    }
+    if let Err(e) = app.global_shortcut().register(shortcut) {
+        // Put the old binding back so the action keeps working
+        if let Some(previous) = &previous {
+            let _ = app.global_shortcut().register(previous.as_str());
+        }
+        return Err(format!("{} is not available (in use by another application?): {}", accelerator, e));
+    }
+
+    guard.bindings.insert(id, accelerator);
+    save_bindings(&guard.file, &guard.bindings)
+}
+
+/// Remove the binding for action `id`.
+#[tauri::command]
+pub fn unregister_global_hotkey(app: AppHandle, id: String) -> Result<(), String> {
+    let state = HOTKEY_STATE.get().ok_or("Global hotkeys not initialized")?;
+    let mut guard = state.lock().map_err(|_| "Global hotkeys lock poisoned".to_string())?;
+    if let Some(accelerator) = guard.bindings.remove(&id) {
+        let _ = app.global_shortcut().unregister(accelerator.as_str());
+        save_bindings(&guard.file, &guard.bindings)?;
+    }
+    Ok(())
+}
+
+/// Current bindings (action id -> accelerator).
+#[tauri::command]
+pub fn get_global_hotkeys() -> HashMap<String, String> {
+    HOTKEY_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .map(|guard| guard.bindings.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(list: &[(&str, &str)]) -> HashMap<String, String> {
+        list.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn menu_accel(id: &str, accelerator: &str) -> menu::MenuAccelerator {
+        menu::MenuAccelerator {
+            id: id.to_string(),
+            default: accelerator.to_string(),
+            accelerator: accelerator.to_string(),
+        }
+    }
+
+    #[test]
+    fn detects_conflict_with_other_hotkey() {
+        let existing = bindings(&[(ACTION_QUICK_CAPTURE, "Alt+Shift+N")]);
+        let conflict = find_conflict(&existing, &[], ACTION_FOCUS_VMARK, "shift+alt+n");
+        assert_eq!(conflict, Some("global hotkey \"quick-capture\"".to_string()));
+    }
+
+    #[test]
+    fn rebinding_same_action_is_not_a_conflict() {
+        let existing = bindings(&[(ACTION_QUICK_CAPTURE, "Alt+Shift+N")]);
+        assert_eq!(find_conflict(&existing, &[], ACTION_QUICK_CAPTURE, "Alt+Shift+N"), None);
+    }
+
+    #[test]
+    fn detects_conflict_with_menu_shortcut() {
+        let menu = vec![menu_accel("bold", "CmdOrCtrl+B"), menu_accel("no-shortcut", "")];
+        let conflict = find_conflict(&HashMap::new(), &menu, ACTION_FOCUS_VMARK, "CmdOrCtrl+B");
+        assert_eq!(conflict, Some("menu item \"bold\"".to_string()));
+        assert_eq!(find_conflict(&HashMap::new(), &menu, ACTION_FOCUS_VMARK, "CmdOrCtrl+Alt+V"), None);
+    }
+
+    #[test]
+    fn bindings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(GLOBAL_HOTKEYS_FILE);
+        assert!(load_bindings(&file).is_empty());
+
+        let saved = bindings(&[(ACTION_QUICK_CAPTURE, "Alt+Shift+N"), (ACTION_FOCUS_VMARK, "Alt+Shift+V")]);
+        save_bindings(&file, &saved).unwrap();
+        assert_eq!(load_bindings(&file), saved);
+    }
+}
@@ -0,0 +1,138 @@
+//! Shared HTTP Client Setup
+//!
+//! Applies the TLS options from Settings → Advanced to clients that talk to
+//! user-configured endpoints (AI providers, WebDAV, S3), so self-hosted
+//! servers behind a private CA or corporate TLS interception still connect:
+//!
+//! - `caBundlePath`: extra root certificates (PEM, may hold several)
+//! - `insecureHosts`: hosts whose certificates are accepted unverified
+
+use crate::settings::{self, TlsSettings};
+
+/// A client builder for requests to `endpoint`, with the user's TLS options.
+pub(crate) fn builder_for(endpoint: Option<&str>) -> Result<reqwest::ClientBuilder, String> {
+    apply_tls(
+        reqwest::Client::builder(),
+        &settings::current().advanced.tls,
+        endpoint,
+    )
+}
+
+fn apply_tls(
+    mut builder: reqwest::ClientBuilder,
+    tls: &TlsSettings,
+    endpoint: Option<&str>,
+) -> Result<reqwest::ClientBuilder, String> {
+    if let Some(path) = tls
+        .ca_bundle_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        for cert in load_ca_bundle(path.trim())? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if endpoint.is_some_and(|url| is_insecure_host(&tls.insecure_hosts, url)) {
+        tracing::warn!(
+            "[TLS] Skipping certificate verification for {}",
+            endpoint.unwrap_or_default()
+        );
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+fn load_ca_bundle(path: &str) -> Result<Vec<reqwest::Certificate>, String> {
+    let pem =
+        std::fs::read(path).map_err(|e| format!("Failed to read CA bundle {}: {}", path, e))?;
+    let certs = reqwest::Certificate::from_pem_bundle(&pem)
+        .map_err(|e| format!("Invalid CA bundle {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in CA bundle {}", path));
+    }
+    Ok(certs)
+}
+
+/// `host` or `host:port` from a settings entry, which may be pasted as a full URL.
+fn normalize_host(entry: &str) -> String {
+    let entry = entry.trim();
+    let entry = entry.split_once("://").map_or(entry, |(_, rest)| rest);
+    let entry = entry.split(['/', '?', '#']).next().unwrap_or_default();
+    entry.to_ascii_lowercase()
+}
+
+/// Whether `url`'s host (or `host:port`) is listed in `hosts`.
+fn is_insecure_host(hosts: &[String], url: &str) -> bool {
+    let Ok(parsed) = reqwest::Url::parse(url.trim()) else {
+        return false;
+    };
+    let Some(host) = parsed.host_str().map(str::to_ascii_lowercase) else {
+        return false;
+    };
+    let with_port = parsed
+        .port_or_known_default()
+        .map(|port| format!("{}:{}", host, port));
+    hosts
+        .iter()
+        .map(|entry| normalize_host(entry))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| entry == host || Some(&entry) == with_port.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hosts(list: &[&str]) -> Vec<String> {
+        list.iter().map(|h| h.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_host_and_port() {
+        let list = hosts(&["ollama.internal", "10.0.0.5:11434"]);
+        assert!(is_insecure_host(&list, "https://ollama.internal/api"));
+        assert!(is_insecure_host(&list, "https://OLLAMA.internal:8443"));
+        assert!(is_insecure_host(&list, "https://10.0.0.5:11434"));
+        assert!(!is_insecure_host(&list, "https://10.0.0.5:443"));
+        assert!(!is_insecure_host(&list, "https://api.openai.com"));
+    }
+
+    #[test]
+    fn default_ports_and_pasted_urls() {
+        let list = hosts(&["https://llm.corp.example/v1", "gw.corp.example:443"]);
+        assert!(is_insecure_host(&list, "https://llm.corp.example"));
+        assert!(is_insecure_host(&list, "https://gw.corp.example/v1"));
+        assert!(!is_insecure_host(&list, "http://gw.corp.example"));
+    }
+
+    #[test]
+    fn ignores_blank_entries_and_bad_urls() {
+        let list = hosts(&["", "  "]);
+        assert!(!is_insecure_host(&list, "https://example.com"));
+        assert!(!is_insecure_host(&hosts(&["example.com"]), "not a url"));
+    }
+
+    #[test]
+    fn bad_ca_bundle_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ca.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        let tls = TlsSettings {
+            ca_bundle_path: Some(path.to_string_lossy().into_owned()),
+            insecure_hosts: Vec::new(),
+        };
+        assert!(apply_tls(reqwest::Client::builder(), &tls, None).is_err());
+
+        let missing = TlsSettings {
+            ca_bundle_path: Some(
+                dir.path()
+                    .join("missing.pem")
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            insecure_hosts: Vec::new(),
+        };
+        assert!(apply_tls(reqwest::Client::builder(), &missing, None).is_err());
+        assert!(apply_tls(reqwest::Client::builder(), &TlsSettings::default(), None).is_ok());
+    }
+}
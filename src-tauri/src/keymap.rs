@@ -0,0 +1,367 @@
+//! Keymap - user overrides for menu shortcuts
+//!
+//! Overrides are stored in the app data directory (`keymap.json`) as a map of
+//! menu item id to accelerator, e.g. `{ "bold": "CmdOrCtrl+Shift+B" }`. An
+//! empty accelerator removes the item's shortcut. Overrides are applied when
+//! the menu is built.
+//!
+//! This is the one store of menu shortcuts: the Settings shortcut editor
+//! saves its menu bindings here (via `rebuild_menu`), and reads them back on
+//! start, so edits made in Settings, through `set_shortcut` or by hand all
+//! end up in the same file.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{app_paths, hotkeys, menu};
+
+/// Keymap file name in the app data directory
+pub const KEYMAP_FILE: &str = "keymap.json";
+
+/// Loaded overrides plus the file they persist to
+struct KeymapState {
+    file: PathBuf,
+    overrides: HashMap<String, String>,
+}
+
+static KEYMAP_STATE: OnceLock<Mutex<KeymapState>> = OnceLock::new();
+
+/// A menu shortcut as reported to the frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeymapEntry {
+    pub id: String,
+    pub default_accelerator: String,
+    /// Accelerator in effect ("" = none)
+    pub accelerator: String,
+    /// Whether keymap.json overrides this item
+    pub overridden: bool,
+}
+
+/// Load keymap overrides from app data. Called once during app setup,
+/// before the menu is created.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(KEYMAP_FILE);
+    let overrides = load_keymap(&file);
+    let _ = KEYMAP_STATE.set(Mutex::new(KeymapState { file, overrides }));
+    Ok(())
+}
+
+fn load_keymap(file: &Path) -> HashMap<String, String> {
+    let overrides: HashMap<String, String> = fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    // Hand edits may contain accelerators the menu can't parse
+    overrides
+        .into_iter()
+        .filter(|(id, accelerator)| match validate_accelerator(accelerator) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("[Keymap] Ignoring shortcut for {}: {}", id, e);
+                false
+            }
+        })
+        .collect()
+}
+
+fn save_keymap(file: &Path, overrides: &HashMap<String, String>) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    // Sorted for stable, hand-editable output
+    let sorted: std::collections::BTreeMap<_, _> = overrides.iter().collect();
+    let content = serde_json::to_string_pretty(&sorted)
+        .map_err(|e| format!("Failed to serialize keymap: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Current overrides (empty if the keymap was never initialized).
+pub fn overrides() -> HashMap<String, String> {
+    KEYMAP_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .map(|guard| guard.overrides.clone())
+        .unwrap_or_default()
+}
+
+/// Check that `accelerator` parses ("" = no shortcut is always valid).
+pub fn validate_accelerator(accelerator: &str) -> Result<(), String> {
+    let accelerator = accelerator.trim();
+    if accelerator.is_empty() {
+        return Ok(());
+    }
+    hotkeys::parse_shortcut(accelerator).map(|_| ())
+}
+
+/// Canonical form of an accelerator for comparison: lowercase, modifier
+/// aliases unified and sorted, so "Shift+CmdOrCtrl+b" equals "CmdOrCtrl+Shift+B".
+pub fn normalize_accelerator(accel: &str) -> String {
+    let mut modifiers: Vec<&str> = Vec::new();
+    let mut key = String::new();
+    for part in accel.split('+').map(str::trim).filter(|p| !p.is_empty()) {
+        let modifier = match part.to_ascii_lowercase().as_str() {
+            "cmdorctrl" | "commandorcontrol" | "cmdorcontrol" | "commandorctrl" => {
+                if cfg!(target_os = "macos") {
+                    "super"
+                } else {
+                    "ctrl"
+                }
+            }
+            "cmd" | "command" | "super" | "meta" => "super",
+            "ctrl" | "control" => "ctrl",
+            "alt" | "option" => "alt",
+            "shift" => "shift",
+            other => {
+                key = other.to_string();
+                continue;
+            }
+        };
+        if !modifiers.contains(&modifier) {
+            modifiers.push(modifier);
+        }
+    }
+    modifiers.sort_unstable();
+    modifiers.push(&key);
+    modifiers.join("+")
+}
+
+/// The menu item (other than `id`) that already uses `accelerator`, if any.
+fn find_conflict(
+    accelerators: &[menu::MenuAccelerator],
+    id: &str,
+    accelerator: &str,
+) -> Option<String> {
+    if accelerator.is_empty() {
+        return None;
+    }
+    let target = normalize_accelerator(accelerator);
+    accelerators
+        .iter()
+        .filter(|a| a.id != id && !a.accelerator.is_empty())
+        .find(|a| normalize_accelerator(&a.accelerator) == target)
+        .map(|a| a.id.clone())
+}
+
+/// Merge the built menu's accelerators with the keymap overrides.
+fn keymap_entries(
+    accelerators: &[menu::MenuAccelerator],
+    overrides: &HashMap<String, String>,
+) -> Vec<KeymapEntry> {
+    accelerators
+        .iter()
+        .map(|a| KeymapEntry {
+            id: a.id.clone(),
+            default_accelerator: a.default.clone(),
+            accelerator: a.accelerator.clone(),
+            overridden: overrides.contains_key(&a.id),
+        })
+        .collect()
+}
+
+/// Fold the menu shortcuts sent by Settings into the overrides: a binding
+/// equal to the menu default clears the item's override, anything else sets
+/// it. Items Settings doesn't know about are left alone. Returns whether
+/// the overrides changed.
+fn merge_settings_shortcuts(
+    overrides: &mut HashMap<String, String>,
+    accelerators: &[menu::MenuAccelerator],
+    shortcuts: &HashMap<String, String>,
+) -> bool {
+    let mut changed = false;
+    for (id, accelerator) in shortcuts {
+        let Some(item) = accelerators.iter().find(|a| &a.id == id) else {
+            continue;
+        };
+        let accelerator = accelerator.trim();
+        if let Err(e) = validate_accelerator(accelerator) {
+            tracing::warn!("[Keymap] Ignoring shortcut for {}: {}", id, e);
+            continue;
+        }
+        if normalize_accelerator(accelerator) == normalize_accelerator(&item.default) {
+            changed |= overrides.remove(id).is_some();
+        } else if overrides.get(id).map(String::as_str) != Some(accelerator) {
+            overrides.insert(id.clone(), accelerator.to_string());
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Save the menu shortcuts from Settings as keymap overrides.
+pub fn apply_settings_shortcuts(shortcuts: &HashMap<String, String>) -> Result<(), String> {
+    let accelerators = menu::menu_accelerators();
+    with_state(|state| {
+        if merge_settings_shortcuts(&mut state.overrides, &accelerators, shortcuts) {
+            save_keymap(&state.file, &state.overrides)?;
+        }
+        Ok(())
+    })
+}
+
+fn with_state<T>(f: impl FnOnce(&mut KeymapState) -> Result<T, String>) -> Result<T, String> {
+    let state = KEYMAP_STATE.get().ok_or("Keymap not initialized")?;
+    let mut guard = state.lock().map_err(|_| "Keymap lock poisoned".to_string())?;
+    f(&mut guard)
+}
+
+/// All customizable menu shortcuts, in menu order.
+#[tauri::command]
+pub fn get_keymap() -> Vec<KeymapEntry> {
+    keymap_entries(&menu::menu_accelerators(), &overrides())
+}
+
+/// Override one menu item's shortcut ("" removes it) and rebuild the menu.
+/// Fails if the id is unknown, the accelerator doesn't parse, or another
+/// item already uses it.
+#[tauri::command]
+pub fn set_shortcut(app: AppHandle, id: String, accelerator: String) -> Result<(), String> {
+    let accelerators = menu::menu_accelerators();
+    if !accelerators.iter().any(|a| a.id == id) {
+        return Err(format!("Unknown menu item: {}", id));
+    }
+    validate_accelerator(&accelerator)?;
+    if let Some(other) = find_conflict(&accelerators, &id, &accelerator) {
+        return Err(format!("{} is already used by {}", accelerator, other));
+    }
+
+    with_state(|state| {
+        state.overrides.insert(id, accelerator.trim().to_string());
+        save_keymap(&state.file, &state.overrides)
+    })?;
+    menu::rebuild_menu_with_keymap(&app)?;
+    // Settings windows reload their shortcut list
+    let _ = app.emit("keymap:changed", ());
+    Ok(())
+}
+
+/// Remove keymap overrides - one item's, or all when `id` is None -
+/// and rebuild the menu.
+#[tauri::command]
+pub fn reset_keymap(app: AppHandle, id: Option<String>) -> Result<(), String> {
+    with_state(|state| {
+        match id {
+            Some(id) => {
+                state.overrides.remove(&id);
+            }
+            None => state.overrides.clear(),
+        }
+        save_keymap(&state.file, &state.overrides)
+    })?;
+    menu::rebuild_menu_with_keymap(&app)?;
+    let _ = app.emit("keymap:changed", ());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accel(id: &str, default: &str, accelerator: &str) -> menu::MenuAccelerator {
+        menu::MenuAccelerator {
+            id: id.to_string(),
+            default: default.to_string(),
+            accelerator: accelerator.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalizes_modifier_order_and_aliases() {
+        assert_eq!(
+            normalize_accelerator("Shift+CmdOrCtrl+b"),
+            normalize_accelerator("CmdOrCtrl+Shift+B")
+        );
+        assert_eq!(normalize_accelerator("Option+Z"), normalize_accelerator("Alt+z"));
+        assert_eq!(normalize_accelerator("Control+`"), "ctrl+`");
+        assert_ne!(normalize_accelerator("Alt+Z"), normalize_accelerator("Z"));
+    }
+
+    #[test]
+    fn detects_conflicts_with_other_items() {
+        let accelerators = vec![
+            accel("bold", "CmdOrCtrl+B", "CmdOrCtrl+B"),
+            accel("italic", "CmdOrCtrl+I", "CmdOrCtrl+I"),
+            accel("no-shortcut", "", ""),
+        ];
+
+        assert_eq!(
+            find_conflict(&accelerators, "italic", "cmdorctrl+b"),
+            Some("bold".to_string())
+        );
+        // Re-assigning an item its own shortcut is fine
+        assert_eq!(find_conflict(&accelerators, "bold", "CmdOrCtrl+B"), None);
+        // Removing a shortcut never conflicts
+        assert_eq!(find_conflict(&accelerators, "italic", ""), None);
+        assert_eq!(find_conflict(&accelerators, "italic", "CmdOrCtrl+Shift+I"), None);
+    }
+
+    #[test]
+    fn entries_mark_overrides() {
+        let accelerators = vec![
+            accel("bold", "CmdOrCtrl+B", "CmdOrCtrl+Shift+B"),
+            accel("italic", "CmdOrCtrl+I", "CmdOrCtrl+I"),
+        ];
+        let overrides = HashMap::from([("bold".to_string(), "CmdOrCtrl+Shift+B".to_string())]);
+
+        let entries = keymap_entries(&accelerators, &overrides);
+        assert!(entries[0].overridden);
+        assert_eq!(entries[0].default_accelerator, "CmdOrCtrl+B");
+        assert!(!entries[1].overridden);
+    }
+
+    #[test]
+    fn settings_shortcuts_set_and_clear_overrides() {
+        let accelerators = vec![
+            accel("bold", "CmdOrCtrl+B", "CmdOrCtrl+B"),
+            accel("italic", "CmdOrCtrl+I", "CmdOrCtrl+I"),
+            accel("close-workspace", "", "Alt+W"),
+        ];
+        let mut overrides = HashMap::from([
+            ("italic".to_string(), "Alt+I".to_string()),
+            ("close-workspace".to_string(), "Alt+W".to_string()),
+        ]);
+        let shortcuts = HashMap::from([
+            ("bold".to_string(), "CmdOrCtrl+Shift+B".to_string()),
+            // Back to the default: the override goes
+            ("italic".to_string(), "cmdorctrl+i".to_string()),
+            ("not-a-menu-item".to_string(), "Alt+X".to_string()),
+        ]);
+
+        assert!(merge_settings_shortcuts(&mut overrides, &accelerators, &shortcuts));
+        assert_eq!(
+            overrides,
+            HashMap::from([
+                ("bold".to_string(), "CmdOrCtrl+Shift+B".to_string()),
+                ("close-workspace".to_string(), "Alt+W".to_string()),
+            ])
+        );
+        // Same shortcuts again: nothing to save
+        assert!(!merge_settings_shortcuts(&mut overrides, &accelerators, &shortcuts));
+    }
+
+    #[test]
+    fn empty_accelerator_is_valid() {
+        assert!(validate_accelerator("").is_ok());
+        assert!(validate_accelerator("  ").is_ok());
+    }
+
+    #[test]
+    fn keymap_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(KEYMAP_FILE);
+        assert!(load_keymap(&file).is_empty());
+
+        let overrides = HashMap::from([
+            ("bold".to_string(), "Alt+B".to_string()),
+            ("italic".to_string(), String::new()),
+        ]);
+        save_keymap(&file, &overrides).unwrap();
+        assert_eq!(load_keymap(&file), overrides);
+    }
+}
@@ -0,0 +1,494 @@
+//! Language Detection
+//!
+//! `detect_language` guesses the language of a piece of text locally, so
+//! translation genies can name the source language and spellcheck can pick
+//! a dictionary without a network call.
+//!
+//! Most scripts are used by one language here (Hangul → Korean, Greek →
+//! Greek, …), so counting letters per script settles those; Japanese is told
+//! apart from Chinese by its kana. Languages sharing the Latin or Cyrillic
+//! script are scored by their most common short words ("the", "und", "les",
+//! …) plus a few letters only they use ("ß", "ñ", "ł", …).
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Only the start of long documents is looked at
+const MAX_CHARS: usize = 10_000;
+
+/// Confidence at which a guess is worth acting on
+const RELIABLE_CONFIDENCE: f64 = 0.5;
+
+/// Word matches needed before a Latin/Cyrillic guess gets full confidence
+const FULL_CONFIDENCE_HITS: f64 = 4.0;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code, e.g. "en", "zh"
+    pub code: String,
+    /// English name, e.g. "English"
+    pub name: String,
+    pub script: String,
+    /// 0.0–1.0
+    pub confidence: f64,
+    pub reliable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Han,
+    Kana,
+    Hangul,
+}
+
+impl Script {
+    fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Cyrillic => "Cyrillic",
+            Script::Greek => "Greek",
+            Script::Arabic => "Arabic",
+            Script::Hebrew => "Hebrew",
+            Script::Devanagari => "Devanagari",
+            Script::Thai => "Thai",
+            Script::Han => "Han",
+            Script::Kana => "Kana",
+            Script::Hangul => "Hangul",
+        }
+    }
+}
+
+fn script_of(c: char) -> Option<Script> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+    Some(match c {
+        'a'..='z' | 'A'..='Z' | '\u{00c0}'..='\u{024f}' | '\u{1e00}'..='\u{1eff}' => Script::Latin,
+        '\u{0400}'..='\u{052f}' => Script::Cyrillic,
+        '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => Script::Greek,
+        '\u{0600}'..='\u{06ff}' | '\u{0750}'..='\u{077f}' => Script::Arabic,
+        '\u{0590}'..='\u{05ff}' => Script::Hebrew,
+        '\u{0900}'..='\u{097f}' => Script::Devanagari,
+        '\u{0e00}'..='\u{0e7f}' => Script::Thai,
+        '\u{3040}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' => Script::Kana,
+        '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}' => Script::Han,
+        '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}' | '\u{ac00}'..='\u{d7af}' => {
+            Script::Hangul
+        }
+        _ => return None,
+    })
+}
+
+/// A language sharing its script with others
+struct Profile {
+    code: &'static str,
+    name: &'static str,
+    script: Script,
+    /// Very common words, lowercase
+    words: &'static [&'static str],
+    /// Letters (almost) only this language uses
+    letters: &'static str,
+}
+
+const PROFILES: &[Profile] = &[
+    Profile {
+        code: "en",
+        name: "English",
+        script: Script::Latin,
+        words: &[
+            "the", "and", "of", "to", "is", "in", "that", "it", "was", "for", "with", "you",
+            "this", "are", "have", "be", "not", "on", "they", "what", "which", "would", "there",
+            "from",
+        ],
+        letters: "",
+    },
+    Profile {
+        code: "de",
+        name: "German",
+        script: Script::Latin,
+        words: &[
+            "der", "die", "und", "das", "ist", "nicht", "ich", "mit", "sich", "auf", "ein", "eine",
+            "dem", "den", "zu", "auch", "es", "von", "wir", "sie", "wird", "oder", "aber",
+            "werden",
+        ],
+        letters: "ßäöü",
+    },
+    Profile {
+        code: "fr",
+        name: "French",
+        script: Script::Latin,
+        words: &[
+            "le", "la", "les", "et", "des", "est", "une", "un", "du", "que", "pas", "pour", "dans",
+            "qui", "sur", "au", "avec", "sont", "ce", "il", "nous", "vous", "mais", "aux",
+        ],
+        letters: "çèêëœ",
+    },
+    Profile {
+        code: "es",
+        name: "Spanish",
+        script: Script::Latin,
+        words: &[
+            "el", "la", "los", "las", "y", "que", "es", "en", "un", "una", "por", "con", "para",
+            "del", "se", "no", "lo", "su", "como", "pero", "está", "muy", "también", "al",
+        ],
+        letters: "ñ¿¡",
+    },
+    Profile {
+        code: "it",
+        name: "Italian",
+        script: Script::Latin,
+        words: &[
+            "il", "che", "di", "la", "è", "e", "non", "per", "una", "sono", "gli", "della", "con",
+            "del", "le", "anche", "come", "ma", "questo", "nel", "alla", "più", "io", "lo",
+        ],
+        letters: "ìò",
+    },
+    Profile {
+        code: "pt",
+        name: "Portuguese",
+        script: Script::Latin,
+        words: &[
+            "o", "a", "os", "as", "que", "de", "não", "um", "uma", "em", "para", "com", "do", "da",
+            "é", "se", "por", "mais", "mas", "como", "foi", "ao", "isso", "são",
+        ],
+        letters: "ãõ",
+    },
+    Profile {
+        code: "nl",
+        name: "Dutch",
+        script: Script::Latin,
+        words: &[
+            "de", "het", "een", "en", "van", "ik", "is", "dat", "niet", "zijn", "op", "te", "met",
+            "voor", "er", "maar", "ook", "als", "naar", "wordt", "bij", "dit", "wij", "hebben",
+        ],
+        letters: "ĳ",
+    },
+    Profile {
+        code: "sv",
+        name: "Swedish",
+        script: Script::Latin,
+        words: &[
+            "och", "att", "det", "som", "är", "en", "på", "för", "med", "inte", "jag", "har",
+            "till", "den", "av", "om", "ett", "var", "vi", "men", "kan", "sig", "eller", "också",
+        ],
+        letters: "å",
+    },
+    Profile {
+        code: "pl",
+        name: "Polish",
+        script: Script::Latin,
+        words: &[
+            "i", "w", "nie", "się", "na", "jest", "to", "że", "z", "do", "jak", "co", "ale", "tak",
+            "od", "po", "przez", "czy", "już", "jego", "może", "są", "dla", "tylko",
+        ],
+        letters: "ąęłńśźż",
+    },
+    Profile {
+        code: "tr",
+        name: "Turkish",
+        script: Script::Latin,
+        words: &[
+            "ve", "bir", "bu", "da", "de", "için", "ile", "ne", "çok", "olarak", "daha", "gibi",
+            "ama", "var", "ben", "sen", "o", "mı", "mi", "değil", "olan", "kadar", "her", "en",
+        ],
+        letters: "ğşı",
+    },
+    Profile {
+        code: "vi",
+        name: "Vietnamese",
+        script: Script::Latin,
+        words: &[
+            "và", "của", "là", "có", "không", "những", "được", "một", "cho", "này", "người",
+            "trong", "các", "với", "đã", "để", "khi", "tôi", "cũng", "thì",
+        ],
+        letters: "đơưạảấầẩẫậắằẳẵặẹẻẽếềểễệỉịọỏốồổỗộớờởỡợụủứừửữựỳỵỷỹ",
+    },
+    Profile {
+        code: "ru",
+        name: "Russian",
+        script: Script::Cyrillic,
+        words: &[
+            "и",
+            "в",
+            "не",
+            "на",
+            "что",
+            "я",
+            "с",
+            "он",
+            "как",
+            "это",
+            "по",
+            "но",
+            "из",
+            "к",
+            "у",
+            "за",
+            "от",
+            "так",
+            "же",
+            "все",
+            "было",
+            "его",
+            "только",
+            "или",
+        ],
+        letters: "ыэъё",
+    },
+    Profile {
+        code: "uk",
+        name: "Ukrainian",
+        script: Script::Cyrillic,
+        words: &[
+            "і",
+            "в",
+            "не",
+            "на",
+            "що",
+            "я",
+            "з",
+            "він",
+            "як",
+            "це",
+            "та",
+            "до",
+            "але",
+            "у",
+            "від",
+            "за",
+            "так",
+            "її",
+            "його",
+            "є",
+            "було",
+            "або",
+            "тільки",
+            "вже",
+        ],
+        letters: "іїєґ",
+    },
+];
+
+/// Scripts used by a single language here
+fn single_language(script: Script) -> Option<(&'static str, &'static str)> {
+    Some(match script {
+        Script::Greek => ("el", "Greek"),
+        Script::Arabic => ("ar", "Arabic"),
+        Script::Hebrew => ("he", "Hebrew"),
+        Script::Devanagari => ("hi", "Hindi"),
+        Script::Thai => ("th", "Thai"),
+        Script::Hangul => ("ko", "Korean"),
+        Script::Han => ("zh", "Chinese"),
+        Script::Kana => ("ja", "Japanese"),
+        Script::Latin | Script::Cyrillic => return None,
+    })
+}
+
+/// Best profile of `script` for `text`, with its margin over the runner-up
+/// (0.0–1.0) and the number of matches.
+fn score_profiles(text: &str, script: Script) -> Option<(&'static Profile, f64, f64)> {
+    let lower = text.to_lowercase();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in lower.split(|c: char| !c.is_alphabetic()) {
+        if !word.is_empty() {
+            *counts.entry(word).or_default() += 1;
+        }
+    }
+
+    let mut scores: Vec<(&Profile, f64)> = PROFILES
+        .iter()
+        .filter(|p| p.script == script)
+        .map(|p| {
+            let words: usize = p.words.iter().filter_map(|w| counts.get(w)).sum();
+            let letters = lower.chars().filter(|c| p.letters.contains(*c)).count();
+            (p, words as f64 + letters as f64 * 0.5)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let (best, best_score) = *scores.first()?;
+    if best_score <= 0.0 {
+        return None;
+    }
+    let second = scores.get(1).map_or(0.0, |s| s.1);
+    Some((best, (best_score - second) / best_score, best_score))
+}
+
+pub(crate) fn detect(text: &str) -> Option<DetectedLanguage> {
+    let text: String = text.chars().take(MAX_CHARS).collect();
+    let mut by_script: HashMap<Script, usize> = HashMap::new();
+    for script in text.chars().filter_map(script_of) {
+        *by_script.entry(script).or_default() += 1;
+    }
+    let letters: usize = by_script.values().sum();
+    if letters == 0 {
+        return None;
+    }
+
+    // Japanese mixes kanji with kana; any real share of kana means Japanese
+    let kana = by_script.remove(&Script::Kana).unwrap_or(0);
+    let han = by_script.get(&Script::Han).copied().unwrap_or(0);
+    if kana > 0 && kana * 10 >= kana + han {
+        by_script.remove(&Script::Han);
+        by_script.insert(Script::Kana, kana + han);
+    } else if kana > 0 {
+        *by_script.entry(Script::Han).or_default() += kana;
+    }
+
+    let (script, count) = by_script
+        .iter()
+        .max_by_key(|(s, n)| (**n, s.name()))
+        .map(|(s, n)| (*s, *n))?;
+    let share = count as f64 / letters as f64;
+
+    let (code, name, confidence) = match single_language(script) {
+        Some((code, name)) => (code, name, share),
+        None => {
+            let (profile, margin, hits) = score_profiles(&text, script)?;
+            let coverage = (hits / FULL_CONFIDENCE_HITS).min(1.0);
+            let confidence = share * coverage * (0.5 + 0.5 * margin);
+            (profile.code, profile.name, confidence)
+        }
+    };
+    let confidence = (confidence * 100.0).round() / 100.0;
+    Some(DetectedLanguage {
+        code: code.to_string(),
+        name: name.to_string(),
+        script: script.name().to_string(),
+        confidence,
+        reliable: confidence >= RELIABLE_CONFIDENCE,
+    })
+}
+
+/// Language of `text`, or `None` when it has no letters or no recognizable
+/// words.
+#[tauri::command]
+pub fn detect_language(text: String) -> Option<DetectedLanguage> {
+    detect(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(text: &str) -> Option<String> {
+        detect(text).map(|d| d.code)
+    }
+
+    #[test]
+    fn test_latin_languages() {
+        let samples = [
+            (
+                "en",
+                "The weather is nice today and we are going to the park with the kids.",
+            ),
+            (
+                "de",
+                "Das Wetter ist heute schön und wir gehen mit den Kindern in den Park.",
+            ),
+            (
+                "fr",
+                "Le temps est beau aujourd'hui et nous allons au parc avec les enfants.",
+            ),
+            (
+                "es",
+                "El tiempo está muy bueno hoy y vamos al parque con los niños.",
+            ),
+            (
+                "it",
+                "Il tempo è bello oggi e andiamo al parco con i bambini, come sempre.",
+            ),
+            (
+                "pt",
+                "O tempo está bom hoje e vamos ao parque com as crianças, não é?",
+            ),
+            (
+                "nl",
+                "Het weer is mooi vandaag en we gaan met de kinderen naar het park.",
+            ),
+            (
+                "sv",
+                "Vädret är fint i dag och vi går till parken med barnen, det är skönt.",
+            ),
+            (
+                "pl",
+                "Pogoda jest dziś ładna i idziemy do parku z dziećmi, to nie jest zły dzień.",
+            ),
+            (
+                "tr",
+                "Bugün hava çok güzel ve çocuklarla birlikte parka gidiyoruz, bu bir şans.",
+            ),
+            (
+                "vi",
+                "Hôm nay thời tiết đẹp và chúng tôi đi công viên với những đứa trẻ của mình.",
+            ),
+        ];
+        for (expected, text) in samples {
+            assert_eq!(code(text).as_deref(), Some(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_single_script_languages() {
+        assert_eq!(
+            code("今天天气很好，我们和孩子们去公园。").as_deref(),
+            Some("zh")
+        );
+        assert_eq!(
+            code("今日は天気がいいので、子供たちと公園に行きます。").as_deref(),
+            Some("ja")
+        );
+        assert_eq!(
+            code("오늘은 날씨가 좋아서 아이들과 공원에 갑니다.").as_deref(),
+            Some("ko")
+        );
+        assert_eq!(code("Σήμερα ο καιρός είναι ωραίος.").as_deref(), Some("el"));
+        assert_eq!(code("الطقس جميل اليوم").as_deref(), Some("ar"));
+    }
+
+    #[test]
+    fn test_cyrillic_languages() {
+        assert_eq!(
+            code("Сегодня хорошая погода, и мы идём в парк с детьми, это было так здорово.")
+                .as_deref(),
+            Some("ru")
+        );
+        assert_eq!(
+            code("Сьогодні гарна погода, і ми йдемо в парк з дітьми, це було так чудово.")
+                .as_deref(),
+            Some("uk")
+        );
+    }
+
+    #[test]
+    fn test_confidence() {
+        let detected = detect("The cat sat on the mat and it was happy with the sun.").unwrap();
+        assert_eq!(detected.script, "Latin");
+        assert!(detected.reliable, "{detected:?}");
+
+        // One weak hint: guessed, but not reliable
+        let detected = detect("Kubernetes the cluster").unwrap();
+        assert_eq!(detected.code, "en");
+        assert!(!detected.reliable, "{detected:?}");
+
+        // Mostly Chinese with a little English
+        let detected = detect("我们使用 React 和 TypeScript 开发这个项目的前端界面").unwrap();
+        assert_eq!(detected.code, "zh");
+        assert!(detected.confidence < 1.0);
+    }
+
+    #[test]
+    fn test_nothing_to_detect() {
+        assert_eq!(detect(""), None);
+        assert_eq!(detect("123 + 456 = 579 !!!"), None);
+        assert_eq!(detect("xyzzy plugh"), None);
+    }
+}
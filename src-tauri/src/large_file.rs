@@ -0,0 +1,232 @@
+//! Large File Reads
+//!
+//! Reading a multi-hundred-MB file into the webview as one string freezes the
+//! renderer. Instead the frontend checks `get_file_metadata`, then pulls the
+//! file with `read_file_chunked` one slice at a time:
+//!
+//! 1. Start at offset 0 with any chunk length.
+//! 2. Each chunk ends on a UTF-8 character boundary (and on a line break when
+//!    one is close), so `next_offset` may be a little short of `offset + len`.
+//! 3. Continue from `next_offset` until `eof`.
+//! 4. Every chunk carries the file's size and mtime; if either changes
+//!    mid-read the load should restart.
+
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Files above this size should be loaded incrementally.
+const LARGE_FILE_THRESHOLD: u64 = 10 * 1024 * 1024;
+
+/// Upper bound for a single chunk, whatever the caller asks for.
+const MAX_CHUNK_BYTES: u64 = 16 * 1024 * 1024;
+
+/// How far back from the end of a chunk to look for a line break.
+const LINE_BREAK_WINDOW: usize = 4096;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetadata {
+    pub size: u64,
+    /// Unix timestamp ms (None if the platform doesn't report it)
+    pub modified: Option<i64>,
+    pub is_large: bool,
+    pub is_readonly: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChunk {
+    pub content: String,
+    pub offset: u64,
+    pub next_offset: u64,
+    pub eof: bool,
+    /// File size and mtime when the chunk was read, to detect changes mid-load
+    pub total_size: u64,
+    pub modified: Option<i64>,
+}
+
+fn modified_ms(meta: &fs::Metadata) -> Option<i64> {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+}
+
+fn metadata_impl(path: &Path) -> Result<FileMetadata, String> {
+    let meta = fs::metadata(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    if !meta.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    Ok(FileMetadata {
+        size: meta.len(),
+        modified: modified_ms(&meta),
+        is_large: meta.len() > LARGE_FILE_THRESHOLD,
+        is_readonly: meta.permissions().readonly(),
+    })
+}
+
+/// Length of the longest prefix of `bytes` that ends on a UTF-8 boundary.
+/// Only trims an incomplete trailing sequence; invalid bytes elsewhere are kept.
+fn complete_utf8_len(bytes: &[u8]) -> usize {
+    // A sequence is at most 4 bytes, so only the last 3 can be incomplete
+    for back in 1..=bytes.len().min(3) {
+        let i = bytes.len() - back;
+        let b = bytes[i];
+        if b & 0b1100_0000 == 0b1000_0000 {
+            continue; // continuation byte, keep looking for the lead byte
+        }
+        let needed = match b {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            _ => 4,
+        };
+        return if back < needed { i } else { bytes.len() };
+    }
+    bytes.len()
+}
+
+/// Cut a chunk just after its last line break, if one is within the window.
+fn line_break_len(bytes: &[u8]) -> usize {
+    let start = bytes.len().saturating_sub(LINE_BREAK_WINDOW);
+    bytes[start..]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map(|i| start + i + 1)
+        .unwrap_or(bytes.len())
+}
+
+fn read_chunk_impl(path: &Path, offset: u64, len: u64) -> Result<FileChunk, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    let meta = file
+        .metadata()
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let total_size = meta.len();
+    if offset > total_size {
+        return Err(format!("Offset {offset} is past the end of the file ({total_size} bytes)"));
+    }
+
+    let len = len.clamp(1, MAX_CHUNK_BYTES).min(total_size - offset);
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek {}: {e}", path.display()))?;
+    let mut buf = vec![0u8; len as usize];
+    file.read_exact(&mut buf)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+    let at_end = offset + len >= total_size;
+    if !at_end {
+        buf.truncate(line_break_len(&buf));
+        buf.truncate(complete_utf8_len(&buf));
+        if buf.is_empty() {
+            return Err("Chunk length is too small to hold a character".to_string());
+        }
+    }
+
+    let next_offset = offset + buf.len() as u64;
+    Ok(FileChunk {
+        content: String::from_utf8_lossy(&buf).into_owned(),
+        offset,
+        next_offset,
+        eof: next_offset >= total_size,
+        total_size,
+        modified: modified_ms(&meta),
+    })
+}
+
+/// Size, mtime and whether the file should be loaded incrementally.
+#[tauri::command]
+pub fn get_file_metadata(path: String) -> Result<FileMetadata, String> {
+    metadata_impl(Path::new(&path))
+}
+
+/// Read up to `len` bytes from `offset` as text. See the module docs for the protocol.
+#[tauri::command]
+pub async fn read_file_chunked(path: String, offset: u64, len: u64) -> Result<FileChunk, String> {
+    tauri::async_runtime::spawn_blocking(move || read_chunk_impl(Path::new(&path), offset, len))
+        .await
+        .map_err(|e| format!("Read failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(path: &Path, len: u64) -> (String, usize) {
+        let (mut text, mut offset, mut chunks) = (String::new(), 0, 0);
+        loop {
+            let chunk = read_chunk_impl(path, offset, len).unwrap();
+            assert_eq!(chunk.offset, offset);
+            text.push_str(&chunk.content);
+            offset = chunk.next_offset;
+            chunks += 1;
+            if chunk.eof {
+                return (text, chunks);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complete_utf8_len() {
+        let s = "a中".as_bytes(); // 'a' + 3-byte char
+        assert_eq!(complete_utf8_len(s), 4);
+        assert_eq!(complete_utf8_len(&s[..3]), 1);
+        assert_eq!(complete_utf8_len(&s[..2]), 1);
+        assert_eq!(complete_utf8_len("é".as_bytes()), 2);
+        assert_eq!(complete_utf8_len(b""), 0);
+    }
+
+    #[test]
+    fn test_chunks_never_split_characters() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cjk.md");
+        let text = "中文内容😀".repeat(50);
+        fs::write(&path, &text).unwrap();
+
+        let (read, chunks) = read_all(&path, 7);
+        assert_eq!(read, text);
+        assert!(chunks > 1);
+    }
+
+    #[test]
+    fn test_chunks_end_on_line_breaks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.md");
+        let text = "line one\nline two\nline three\n";
+        fs::write(&path, text).unwrap();
+
+        let chunk = read_chunk_impl(&path, 0, 20).unwrap();
+        assert_eq!(chunk.content, "line one\nline two\n");
+        assert!(!chunk.eof);
+        assert_eq!(chunk.total_size, text.len() as u64);
+
+        let (read, _) = read_all(&path, 20);
+        assert_eq!(read, text);
+    }
+
+    #[test]
+    fn test_offset_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "abc").unwrap();
+
+        let end = read_chunk_impl(&path, 3, 10).unwrap();
+        assert!(end.eof);
+        assert_eq!(end.content, "");
+        assert!(read_chunk_impl(&path, 4, 10).is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "abc").unwrap();
+
+        let meta = metadata_impl(&path).unwrap();
+        assert_eq!(meta.size, 3);
+        assert!(!meta.is_large);
+        assert!(meta.modified.is_some());
+        assert!(metadata_impl(dir.path()).is_err());
+    }
+}
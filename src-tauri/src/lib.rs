@@ -1,23 +1,91 @@
+mod ai_context;
 mod ai_provider;
+mod ai_workspace;
+mod ai_redaction;
+mod ai_usage;
 mod app_paths;
+mod automation;
+mod capture_server;
+mod citations;
+mod cli;
+mod clipboard_history;
+mod context_menu;
+mod deep_link;
+mod diagnostics;
+mod dirty_backup;
+mod embeddings;
+mod encryption;
+mod export_metadata;
 mod mcp_bridge;
 mod mcp_config;
 mod mcp_server;
 mod menu;
 mod menu_events;
+mod menu_state;
+mod notifications;
+mod ocr;
+mod ollama;
+mod pandoc;
+mod pomodoro;
+mod preview_server;
+mod publish;
 mod genies;
+mod goals;
+mod quick_capture;
 mod quit;
+mod readability;
+mod rich_text;
+mod save_hooks;
+mod settings;
+mod transcription;
+mod snippets;
+mod symbols;
+mod task_calendar;
+mod task_index;
 mod watcher;
+mod backup;
+mod webdav_sync;
 mod window_manager;
+mod window_registry;
 mod workspace;
+mod workspace_stats;
+mod workspace_trust;
+mod zettel;
+mod file_finder;
 mod file_tree;
+mod file_lock;
+mod file_ops;
+mod find_cache;
+mod flavor;
+mod footnotes;
+mod highlight;
 mod hot_exit;
+mod hotkeys;
+mod http_client;
+mod keymap;
+mod language_detect;
+mod large_file;
+mod link_archive;
+mod link_index;
+mod link_rewrite;
+mod logging;
+mod markdown_ast;
 mod tab_transfer;
+mod table_data;
+mod tasks;
+mod terminal;
+mod text_encoding;
+mod tool_registry;
+mod translation_memory;
+mod tray;
+mod typography;
+mod url_metadata;
 
 #[cfg(target_os = "macos")]
 mod macos_menu;
 #[cfg(target_os = "macos")]
 mod dock_recent;
+mod document_diff;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
@@ -50,7 +118,7 @@ fn get_pending_file_opens() -> Vec<PendingFileOpen> {
 #[cfg(debug_assertions)]
 #[tauri::command]
 fn debug_log(message: String) {
-    eprintln!("[Frontend] {}", message);
+    tracing::info!(target: "frontend", "{}", message);
 }
 
 /// Write HTML content to a temp file for browser-based printing.
@@ -66,20 +134,6 @@ fn write_temp_html(html: String) -> Result<String, String> {
     Ok(path.to_string_lossy().into_owned())
 }
 
-/// Return the user's default shell.
-///
-/// - macOS/Linux: reads `$SHELL` (fallback: `/bin/sh`)
-/// - Windows: uses `powershell.exe` (fallback: `cmd.exe`)
-#[tauri::command]
-fn get_default_shell() -> String {
-    if cfg!(target_os = "windows") {
-        // Prefer PowerShell if available, fall back to cmd.exe
-        std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
-    } else {
-        std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string())
-    }
-}
-
 /// Register a file with macOS Dock recent documents
 #[cfg(target_os = "macos")]
 #[tauri::command]
@@ -89,6 +143,11 @@ fn register_dock_recent(path: String) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `vmark --wait` waits in this process while the app runs in a child
+    if let Some(code) = cli::run_wait_launcher() {
+        std::process::exit(code);
+    }
+
     #[allow(unused_mut)]
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -96,12 +155,24 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_pty::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(hotkeys::handle_shortcut)
+                .build(),
+        )
         .plugin(
             tauri_plugin_window_state::Builder::new()
                 .with_denylist(&["settings"])
+                // doc-* and viewer-* labels are reused across launches;
+                // window_manager places those windows from remembered
+                // per-type geometry
+                .with_filter(|label| {
+                    !label.starts_with("doc-")
+                        && !label.starts_with(window_manager::VIEWER_LABEL_PREFIX)
+                })
                 // Exclude VISIBLE from state restoration to prevent flash.
                 // Windows start hidden (visible: false) and are shown only
                 // after frontend emits "ready" event in mark_window_ready().
@@ -118,23 +189,232 @@ pub fn run() {
             menu::refresh_genies_menu,
             menu::hide_genies_menu,
             menu::rebuild_menu,
+            keymap::get_keymap,
+            keymap::set_shortcut,
+            keymap::reset_keymap,
+            menu_state::set_menu_item_enabled,
+            menu_state::set_menu_item_checked,
+            context_menu::show_context_menu,
+            context_menu::dismiss_context_menu,
+            quick_capture::append_quick_capture,
+            quick_capture::get_quick_capture_inbox,
+            quick_capture::set_quick_capture_inbox,
+            quick_capture::open_quick_capture,
+            automation::automation_create_note,
+            automation::automation_append_to_note,
+            capture_server::get_capture_server_status,
+            capture_server::set_capture_server_enabled,
+            capture_server::regenerate_capture_server_token,
+            preview_server::start_preview_server,
+            preview_server::update_preview,
+            preview_server::get_preview_status,
+            preview_server::stop_preview_server,
+            hotkeys::register_global_hotkey,
+            hotkeys::unregister_global_hotkey,
+            hotkeys::get_global_hotkeys,
+            diagnostics::generate_diagnostics_bundle,
+            logging::get_recent_logs,
+            logging::set_log_level,
+            logging::get_log_levels,
+            settings::get_settings,
+            settings::subscribe_settings,
+            settings::set_setting,
+            settings::import_settings,
+            settings::reset_settings,
+            encryption::encrypt_file,
+            encryption::decrypt_file,
+            encryption::open_encrypted_document,
+            encryption::save_encrypted_document,
+            encryption::lock_encrypted_document,
+            rich_text::copy_as_rich_text,
+            clipboard_history::record_clipboard_copy,
+            clipboard_history::list_clipboard_history,
+            clipboard_history::paste_clipboard_history,
+            clipboard_history::clear_clipboard_history,
+            clipboard_history::get_clipboard_history_options,
+            clipboard_history::set_clipboard_history_options,
+            snippets::get_snippets_dir,
+            snippets::list_snippets,
+            snippets::resolve_snippet,
+            citations::search_citations,
+            citations::format_bibliography,
+            citations::apply_citations,
+            footnotes::normalize_footnotes,
+            pandoc::detect_pandoc,
+            pandoc::list_pandoc_formats,
+            pandoc::convert_with_pandoc,
+            export_metadata::get_export_metadata,
+            dirty_backup::get_dirty_backup_config,
+            dirty_backup::set_dirty_backup_config,
+            dirty_backup::report_dirty_digests,
+            dirty_backup::write_dirty_backup,
+            dirty_backup::list_dirty_backups,
+            dirty_backup::restore_dirty_backup,
+            dirty_backup::delete_dirty_backup,
+            ocr::detect_tesseract,
+            ocr::extract_text_from_image,
+            ocr::list_ocr_languages,
+            ocr::download_ocr_language,
+            ocr::remove_ocr_language,
+            transcription::detect_whisper,
+            transcription::list_whisper_models,
+            transcription::transcribe_audio,
+            transcription::cancel_transcription,
+            transcription::download_whisper_model,
+            transcription::remove_whisper_model,
+            pomodoro::get_pomodoro_config,
+            pomodoro::set_pomodoro_config,
+            pomodoro::pomodoro_start,
+            pomodoro::pomodoro_pause,
+            pomodoro::pomodoro_stop,
+            pomodoro::get_pomodoro_state,
+            pomodoro::get_pomodoro_stats,
+            goals::record_document_save,
+            goals::set_goal,
+            goals::get_progress,
+            notifications::show_notification,
+            notifications::scan_note_reminders,
+            notifications::clear_note_reminders,
+            notifications::list_reminders,
+            notifications::snooze_reminder,
+            notifications::dismiss_reminder,
+            task_calendar::export_tasks_ics,
+            task_index::query_tasks,
+            task_index::list_task_tags,
+            task_index::rebuild_task_index,
+            task_index::toggle_task,
+            publish::set_publish_token,
+            publish::set_publish_webhook,
+            publish::delete_publish_token,
+            publish::has_publish_token,
+            publish::publish_document,
+            publish::update_published_document,
+            publish::unpublish_document,
+            webdav_sync::get_sync_config,
+            webdav_sync::set_sync_config,
+            webdav_sync::remove_sync_config,
+            webdav_sync::sync_clone,
+            webdav_sync::sync_now,
+            webdav_sync::start_scheduled_sync,
+            webdav_sync::stop_scheduled_sync,
+            backup::get_backup_config,
+            backup::set_backup_config,
+            backup::create_backup,
+            backup::list_backups,
+            backup::verify_backup,
+            backup::restore_backup,
+            backup::delete_backup,
+            backup::start_scheduled_backup,
+            backup::stop_scheduled_backup,
             window_manager::new_window,
             window_manager::open_file_in_new_window,
+            window_manager::create_preview_window,
+            window_manager::set_window_always_on_top,
+            window_manager::set_window_opacity,
             window_manager::open_workspace_in_new_window,
             window_manager::open_workspace_with_files_in_new_window,
+            window_manager::create_split_window,
             window_manager::close_window,
             window_manager::force_quit,
             window_manager::request_quit,
+            window_registry::update_window_state,
+            window_registry::get_window_for_file,
+            window_registry::list_windows_with_state,
+            window_registry::focus_window_for_file,
+            cli::take_pending_diff,
+            document_diff::diff_documents,
+            document_diff::merge_documents,
+            deep_link::take_pending_reveal,
+            file_finder::fuzzy_find_files,
+            file_finder::rebuild_file_finder_index,
             quit::cancel_quit,
             watcher::start_watching,
             watcher::stop_watching,
             watcher::stop_all_watchers,
             watcher::list_watchers,
+            watcher::watch_file,
+            watcher::unwatch_file,
+            watcher::compute_file_digest,
             file_tree::list_directory_entries,
+            file_tree::list_directory_tree,
+            file_ops::delete_to_trash,
+            file_ops::delete_paths_to_trash,
+            file_ops::restore_from_trash,
+            file_ops::rename_path,
+            file_ops::move_path,
+            file_ops::duplicate_file,
+            file_ops::create_folder,
+            file_ops::restore_link_updates,
+            file_ops::save_file_safe,
+            file_ops::write_document,
+            file_lock::acquire_file_lock,
+            file_lock::check_file_lock,
+            file_lock::release_file_lock,
+            file_lock::force_unlock_file,
+            find_cache::load_find_document,
+            find_cache::edit_find_document,
+            find_cache::find_in_document,
+            find_cache::release_find_document,
+            markdown_ast::parse_document,
+            markdown_ast::edit_parsed_document,
+            markdown_ast::get_document_blocks,
+            markdown_ast::get_document_outline,
+            markdown_ast::get_node_at_offset,
+            markdown_ast::get_fold_ranges,
+            markdown_ast::get_code_fence_languages,
+            markdown_ast::release_parsed_document,
+            highlight::highlight_code,
+            highlight::highlight_code_blocks,
+            symbols::search_emoji,
+            symbols::search_unicode,
+            url_metadata::fetch_url_title,
+            url_metadata::fetch_link_preview,
+            link_archive::archive_link,
+            tool_registry::list_tools,
+            tool_registry::detect_tool,
+            tool_registry::get_tool_install_hint,
+            ai_usage::get_usage_summary,
+            ai_usage::set_usage_limits,
+            embeddings::semantic_search,
+            embeddings::related_notes,
+            embeddings::rebuild_embedding_index,
+            ai_context::build_context,
+            ai_workspace::ask_workspace,
+            ai_workspace::get_workspace_session,
+            ai_workspace::clear_workspace_session,
+            save_hooks::run_save_hooks,
+            ollama::ollama_warmup,
+            ollama::ollama_keep_alive,
+            ollama::list_local_ollama_models,
+            ollama::pull_ollama_model,
+            ollama::delete_ollama_model,
+            translation_memory::lookup_translation,
+            translation_memory::store_translation,
+            translation_memory::translation_memory_stats,
+            translation_memory::clear_translation_memory,
+            language_detect::detect_language,
+            readability::analyze_readability,
+            typography::smarten_typography,
+            flavor::convert_flavor,
+            large_file::get_file_metadata,
+            large_file::read_file_chunked,
+            text_encoding::read_file_with_encoding,
+            text_encoding::save_file_with_encoding,
+            text_encoding::list_text_encodings,
+            table_data::csv_to_markdown_table,
+            table_data::markdown_table_to_csv,
             workspace::open_folder_dialog,
             workspace::read_workspace_config,
             workspace::write_workspace_config,
             workspace::has_workspace_config,
+            workspace_stats::get_workspace_stats,
+            link_index::suggest_link_targets,
+            link_rewrite::update_links_on_rename,
+            zettel::create_zettel,
+            workspace_trust::workspace_trust_grant,
+            workspace_trust::workspace_trust_revoke,
+            workspace_trust::workspace_trust_list,
+            workspace_trust::workspace_trust_check,
             mcp_server::mcp_bridge_start,
             mcp_server::mcp_bridge_stop,
             mcp_server::mcp_server_start,
@@ -144,11 +424,16 @@ pub fn run() {
             mcp_server::mcp_bridge_client_count,
             mcp_server::write_mcp_tool_mode,
             mcp_bridge::mcp_bridge_respond,
+            mcp_bridge::mcp_bridge_stats,
+            mcp_bridge::mcp_bridge_set_max_pending,
             mcp_config::mcp_config_get_status,
             mcp_config::mcp_config_diagnose,
             mcp_config::mcp_config_preview,
             mcp_config::mcp_config_install,
             mcp_config::mcp_config_uninstall,
+            mcp_config::list_config_backups,
+            mcp_config::restore_config_backup,
+            mcp_config::prune_config_backups,
             hot_exit::commands::hot_exit_capture,
             hot_exit::commands::hot_exit_restore,
             hot_exit::commands::hot_exit_inspect_session,
@@ -158,7 +443,28 @@ pub fn run() {
             hot_exit::commands::hot_exit_window_restore_complete,
             tab_transfer::detach_tab_to_new_window,
             tab_transfer::claim_tab_transfer,
-            get_default_shell,
+            tab_transfer::transfer_tab,
+            tab_transfer::claim_tab_moves,
+            tab_transfer::complete_tab_transfer,
+            tab_transfer::cancel_tab_transfer,
+            tab_transfer::find_window_at_position,
+            terminal::get_default_shell,
+            terminal::get_default_shell_info,
+            terminal::list_available_shells,
+            terminal::get_terminal_env,
+            terminal::pty_spawn,
+            terminal::pty_write,
+            terminal::pty_resize,
+            terminal::pty_kill,
+            terminal::pty_get_scrollback,
+            terminal::pty_clear_scrollback,
+            terminal::pty_wait,
+            terminal::pty_terminate,
+            tasks::start_task,
+            tasks::stop_task,
+            tasks::list_tasks,
+            tasks::clear_finished_tasks,
+            terminal::pty_release_session,
             genies::get_genies_dir,
             genies::list_genies,
             genies::read_genie,
@@ -175,8 +481,63 @@ pub fn run() {
             register_dock_recent,
         ])
         .setup(|app| {
+            // Logging first so the rest of setup is captured
+            if let Err(e) = logging::init(app.handle()) {
+                eprintln!("[Tauri] Warning: Failed to initialize logging: {}", e);
+            }
+
+            // Crash reports go to app data for diagnostics bundles
+            if let Err(e) = diagnostics::install_panic_hook(app.handle()) {
+                tracing::warn!("Failed to install crash reporter: {}", e);
+            }
+
+            // Settings store shared by all windows
+            if let Err(e) = settings::init(app.handle()) {
+                tracing::warn!("Failed to load settings: {}", e);
+            }
+
+            // Load keymap overrides before the menu is built
+            if let Err(e) = keymap::init(app.handle()) {
+                tracing::warn!("Failed to load keymap: {}", e);
+            }
+
             let menu = menu::create_menu(app.handle())?;
             app.set_menu(menu)?;
+            // Tray icon: Quick Note, recent files, show/hide
+            if let Err(e) = quick_capture::init(app.handle()) {
+                tracing::warn!("Failed to load quick capture settings: {}", e);
+            }
+            if let Err(e) = capture_server::init(app.handle()) {
+                tracing::warn!("Failed to start capture server: {}", e);
+            }
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::warn!("Failed to create tray icon: {}", e);
+            }
+            if let Err(e) = hotkeys::init(app.handle()) {
+                tracing::warn!("Failed to register global hotkeys: {}", e);
+            }
+
+            if let Err(e) = clipboard_history::init(app.handle()) {
+                tracing::warn!("Failed to load clipboard history: {}", e);
+            }
+
+            if let Err(e) = translation_memory::init(app.handle()) {
+                tracing::warn!("Failed to load translation memory: {}", e);
+            }
+
+            // Pending @remind reminders, including ones due while closed
+            if let Err(e) = notifications::init(app.handle()) {
+                tracing::warn!("Failed to load reminders: {}", e);
+            }
+
+            // Periodic copies of unsaved documents
+            if let Err(e) = dirty_backup::init(app.handle()) {
+                tracing::warn!("Failed to start dirty document backups: {}", e);
+            }
+
+            if let Err(e) = menu::restore_recent_files_menu(app.handle()) {
+                tracing::warn!("Failed to restore recent files: {}", e);
+            }
 
             // Fix macOS Help/Window menus (workaround for muda bug)
             #[cfg(target_os = "macos")]
@@ -185,53 +546,41 @@ pub fn run() {
             // Write bootstrap file for MCP sidecar discovery
             // This file contains the path to the app data directory
             if let Err(e) = app_paths::write_app_data_path_bootstrap(app.handle()) {
-                eprintln!("[Tauri] Warning: Failed to write app-data-path bootstrap: {}", e);
+                tracing::warn!("Failed to write app-data-path bootstrap: {}", e);
             }
 
             // Migrate legacy files from ~/.vmark/ to app data directory
             if let Err(e) = app_paths::migrate_legacy_files(app.handle()) {
-                eprintln!("[Tauri] Warning: Failed to migrate legacy files: {}", e);
+                tracing::warn!("Failed to migrate legacy files: {}", e);
+            }
+
+            // Load trusted workspace roots before any window asks for them
+            if let Err(e) = workspace_trust::init(app.handle()) {
+                tracing::warn!("Failed to load workspace trust: {}", e);
+            }
+
+            // Load remembered window geometry before any document window opens
+            if let Err(e) = window_manager::init_window_geometry(app.handle()) {
+                tracing::warn!("Failed to load window geometry: {}", e);
             }
 
             // Install default AI genies (no-op if already present)
             if let Err(e) = genies::install_default_genies(app.handle()) {
-                eprintln!("[Tauri] Warning: Failed to install default genies: {}", e);
+                tracing::warn!("Failed to install default genies: {}", e);
+            }
+            if let Err(e) = genies::start_genies_watcher(app.handle()) {
+                tracing::warn!("Failed to watch genies: {}", e);
             }
 
-            // Windows/Linux: handle files passed as CLI arguments
-            // (macOS uses RunEvent::Opened from Finder instead)
-            #[cfg(not(target_os = "macos"))]
-            {
-                let md_extensions = ["md", "markdown", "mdown", "mkd", "mdx"];
-                let args: Vec<String> = std::env::args().skip(1).collect();
-                let file_args: Vec<String> = args
-                    .into_iter()
-                    .filter(|arg| {
-                        let path = std::path::Path::new(arg);
-                        path.exists()
-                            && path.is_file()
-                            && path
-                                .extension()
-                                .and_then(|e| e.to_str())
-                                .map(|e| md_extensions.contains(&e.to_lowercase().as_str()))
-                                .unwrap_or(false)
-                    })
-                    .collect();
-
-                if !file_args.is_empty() {
-                    if let Ok(mut pending) = PENDING_FILE_OPENS.lock() {
-                        for path_str in file_args {
-                            let workspace_root =
-                                window_manager::get_workspace_root_for_file(&path_str);
-                            pending.push(PendingFileOpen {
-                                path: path_str,
-                                workspace_root,
-                            });
-                        }
-                    }
-                }
+            // Route vmark:// links to this app (Windows and Linux)
+            if let Err(e) = deep_link::register_url_scheme() {
+                tracing::warn!("Failed to register vmark:// links: {}", e);
             }
 
+            // Handle `vmark [options] [file | folder ...]` arguments
+            // (macOS Finder opens arrive separately via RunEvent::Opened)
+            cli::handle_startup_args(app.handle());
+
             // Listen for "ready" events from frontend windows
             // This is used by menu_events to know when it's safe to emit events
             // The payload contains the window label as a string
@@ -239,8 +588,7 @@ pub fn run() {
             app.listen("ready", move |event| {
                 // The payload is the window label
                 if let Ok(label) = serde_json::from_str::<String>(event.payload()) {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[Tauri] Window '{}' is ready", label);
+                    tracing::debug!("Window '{}' is ready", label);
                     menu_events::mark_window_ready(&app_handle, &label);
                 }
             });
@@ -252,17 +600,27 @@ pub fn run() {
         // Non-document windows (settings) should close normally
         .on_window_event(|window, event| {
             use tauri::Emitter;
+            if matches!(
+                event,
+                tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+            ) {
+                window_manager::record_window_geometry(window);
+            }
+            // The menu is shared; show the state of the focused document window
+            if let tauri::WindowEvent::Focused(true) = event {
+                if quit::is_document_window_label(window.label()) {
+                    menu_state::apply_window_state(window.app_handle(), window.label());
+                }
+            }
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let label = window.label();
-                #[cfg(debug_assertions)]
-                eprintln!("[Tauri] WindowEvent::CloseRequested for window '{}'", label);
+                tracing::debug!("WindowEvent::CloseRequested for window '{}'", label);
                 // Only intercept close for document windows
                 if label == "main" || label.starts_with("doc-") {
                     api.prevent_close();
                     // Include target label in payload so frontend can filter
                     let _ = window.emit("window:close-requested", label);
-                    #[cfg(debug_assertions)]
-                    eprintln!("[Tauri] Emitted window:close-requested to '{}'", label);
+                    tracing::debug!("Emitted window:close-requested to '{}'", label);
                 }
                 // Settings and other non-document windows close normally
             }
@@ -288,21 +646,20 @@ pub fn run() {
                 // CRITICAL: Prevent quit on last window close (macOS behavior)
                 // App should only quit via Cmd+Q or menu Quit
                 tauri::RunEvent::ExitRequested { api, code: _code, .. } => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[Tauri] ExitRequested received, code={:?}", _code);
+                    tracing::debug!("ExitRequested received, code={:?}", _code);
 
                     // If we explicitly allowed exit (we're done with coordinated quit), allow it through.
                     // IMPORTANT: Quit can be "in progress" while we still need to block OS quit requests.
                     if quit::is_exit_allowed() {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[Tauri] ExitRequested: exit allowed, allowing exit");
+                        tracing::debug!("ExitRequested: exit allowed, allowing exit");
+                        window_manager::persist_window_geometry();
+                        file_lock::release_all_locks();
                         return;
                     }
 
                     // Prevent exit for last-window-close scenario (macOS behavior)
                     api.prevent_exit();
-                    #[cfg(debug_assertions)]
-                    eprintln!("[Tauri] ExitRequested: prevent_exit() called");
+                    tracing::debug!("ExitRequested: prevent_exit() called");
 
                     // Only start coordinated quit if there are document windows
                     let has_doc_windows = app
@@ -311,8 +668,7 @@ pub fn run() {
                         .any(|label| quit::is_document_window_label(label));
 
                     if has_doc_windows {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[Tauri] ExitRequested: starting quit flow");
+                        tracing::debug!("ExitRequested: starting quit flow");
                         quit::start_quit(app);
                     }
                     // If no document windows, just stay alive (macOS dock behavior)
@@ -322,9 +678,17 @@ pub fn run() {
                     event: tauri::WindowEvent::Destroyed,
                     ..
                 } => {
+                    window_manager::persist_window_geometry();
                     quit::handle_window_destroyed(app, &label);
                     menu_events::clear_window_ready(&label);
                     tab_transfer::clear_unclaimed_transfer(&label);
+                    window_registry::unregister_window(&label);
+                    menu_state::clear_window_state(&label);
+                    context_menu::clear_window_requests(&label);
+                    webdav_sync::clear_window_schedules(&label);
+                    backup::clear_window_schedules(&label);
+                    settings::unsubscribe_window(&label);
+                    cli::check_wait(app);
                 }
                 // macOS: Clicking dock icon when no windows visible -> create main window
                 #[cfg(target_os = "macos")]
@@ -347,13 +711,22 @@ pub fn run() {
                     }
                 }
                 // Handle files opened from Finder (double-click, "Open With", etc.)
+                // and vmark:// links
                 // Groups files by workspace root to open them as tabs in a single window
                 #[cfg(target_os = "macos")]
                 tauri::RunEvent::Opened { urls } => {
                     // Convert URLs to file paths, handling directories immediately
                     let mut file_paths = Vec::new();
                     for url in urls {
-                        if let Ok(path) = url.to_file_path() {
+                        let path = if url.scheme() == deep_link::URL_SCHEME {
+                            use tauri::Emitter;
+                            let path = deep_link::handle_url(app, &url);
+                            let _ = app.emit(deep_link::REVEAL_PENDING_EVENT, ());
+                            path
+                        } else {
+                            url.to_file_path().ok()
+                        };
+                        if let Some(path) = path {
                             let Some(path_str) = path.to_str() else { continue };
                             if path.is_dir() {
                                 let _ = window_manager::create_document_window(
@@ -361,6 +734,12 @@ pub fn run() {
                                 );
                                 continue;
                             }
+                            // Already open somewhere: focus that window instead
+                            if window_registry::focus_existing_window_for_file(app, path_str)
+                                .is_some()
+                            {
+                                continue;
+                            }
                             file_paths.push(path_str.to_string());
                         }
                     }
@@ -0,0 +1,919 @@
+//! Link Archive
+//!
+//! Saves a readable copy of a linked page into `<workspace>/.vmark/archive/`
+//! so notes keep their sources when the web page changes or disappears.
+//!
+//! The copy is simplified, in the spirit of browser reader modes: the main
+//! content is taken from `<article>`, `<main>` or `role="main"` (the longest
+//! one when there are several, else `<body>`), and navigation, sidebars,
+//! comments, share widgets, scripts and forms are dropped. What's left is
+//! written as Markdown (default) or as minimal HTML, with the source URL and
+//! archive date at the top. Links and images point at absolute URLs.
+
+use chrono::Local;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::rich_text::{attr, decode_entities};
+use crate::url_metadata;
+
+const ARCHIVE_DIR: &str = "archive";
+
+/// Pages larger than this are cut off.
+const MAX_PAGE_BYTES: usize = 5 * 1024 * 1024;
+
+const MAX_SLUG_CHARS: usize = 60;
+
+/// Elements that never hold article content.
+const SKIP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "object", "nav",
+    "header", "footer", "aside", "form", "button", "select", "textarea", "input", "dialog", "head",
+];
+
+/// Landmark roles that aren't the main content.
+const SKIP_ROLES: &[&str] = &[
+    "navigation",
+    "banner",
+    "contentinfo",
+    "complementary",
+    "dialog",
+    "search",
+];
+
+/// Class/id prefixes of page furniture.
+const SKIP_CLASS_PREFIXES: &[&str] = &[
+    "comment",
+    "share",
+    "social",
+    "related",
+    "sidebar",
+    "advert",
+    "ad-",
+    "promo",
+    "newsletter",
+    "cookie",
+    "breadcrumb",
+    "subscribe",
+    "popup",
+    "modal",
+];
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Tags kept in the simplified HTML copy.
+const HTML_KEEP_TAGS: &[&str] = &[
+    "p",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "pre",
+    "code",
+    "a",
+    "img",
+    "strong",
+    "b",
+    "em",
+    "i",
+    "br",
+    "hr",
+    "table",
+    "thead",
+    "tbody",
+    "tr",
+    "th",
+    "td",
+    "figure",
+    "figcaption",
+    "del",
+    "s",
+    "dl",
+    "dt",
+    "dd",
+    "sup",
+    "sub",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedLink {
+    /// Absolute path of the archived copy
+    pub path: String,
+    /// Href for the copy, relative to the linking document (or to the
+    /// workspace root when no document is given)
+    pub href: String,
+    pub title: Option<String>,
+    /// RFC 3339
+    pub archived_at: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open { name: String, attrs: &'a str },
+    Close(String),
+    Text(&'a str),
+}
+
+fn is_void(name: &str) -> bool {
+    VOID_TAGS.contains(&name)
+}
+
+/// Split HTML into tags and text. Comments, doctypes and the bodies of
+/// `<script>`/`<style>` are dropped; a `<` that doesn't start a tag is text.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if lt > 0 {
+            tokens.push(Token::Text(&rest[..lt]));
+        }
+        rest = &rest[lt..];
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+        let starts_tag = rest[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        if !starts_tag {
+            tokens.push(Token::Text(&rest[..1]));
+            rest = &rest[1..];
+            continue;
+        }
+        let Some(gt) = rest.find('>') else {
+            break;
+        };
+        let inner = &rest[1..gt];
+        rest = &rest[gt + 1..];
+        if inner.starts_with(['!', '?']) {
+            continue;
+        }
+        if let Some(name) = inner.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+            continue;
+        }
+        let name_end = inner
+            .find(|c: char| c.is_ascii_whitespace() || c == '/')
+            .unwrap_or(inner.len());
+        let name = inner[..name_end].to_ascii_lowercase();
+        let attrs = inner[name_end..].trim_end_matches('/');
+        if name == "script" || name == "style" {
+            // Raw text: skip to the closing tag without tokenizing
+            let close = format!("</{name}");
+            let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+            rest = rest[end..].find('>').map_or("", |gt| &rest[end + gt + 1..]);
+            continue;
+        }
+        tokens.push(Token::Open { name, attrs });
+    }
+    tokens
+}
+
+/// Tokens inside the element opened at `start`, up to its matching close
+/// (or the end when it's never closed).
+fn element_range(tokens: &[Token], start: usize) -> Range<usize> {
+    let Token::Open { name, .. } = &tokens[start] else {
+        return start..start;
+    };
+    if is_void(name) {
+        return start + 1..start + 1;
+    }
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Open { name: n, .. } if n == name => depth += 1,
+            Token::Close(n) if n == name => {
+                depth -= 1;
+                if depth == 0 {
+                    return start + 1..i;
+                }
+            }
+            _ => {}
+        }
+    }
+    start + 1..tokens.len()
+}
+
+fn text_len(tokens: &[Token]) -> usize {
+    tokens
+        .iter()
+        .map(|t| match t {
+            Token::Text(text) => text.trim().len(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Whether an element is page furniture rather than content.
+fn is_boilerplate(name: &str, attrs: &str) -> bool {
+    if SKIP_TAGS.contains(&name) || attr(attrs, "hidden").is_some() {
+        return true;
+    }
+    if attr(attrs, "aria-hidden").is_some_and(|v| v == "true") {
+        return true;
+    }
+    if attr(attrs, "role")
+        .is_some_and(|role| SKIP_ROLES.contains(&role.to_ascii_lowercase().as_str()))
+    {
+        return true;
+    }
+    let names = [attr(attrs, "class"), attr(attrs, "id")];
+    names.iter().flatten().any(|value| {
+        value.split_ascii_whitespace().any(|class| {
+            let class = class.to_ascii_lowercase();
+            SKIP_CLASS_PREFIXES.iter().any(|p| class.starts_with(p))
+        })
+    })
+}
+
+/// The tokens of the page's main content.
+fn content_root<'t, 'a>(tokens: &'t [Token<'a>]) -> &'t [Token<'a>] {
+    let candidates = |pred: &dyn Fn(&str, &str) -> bool| {
+        tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches!(t, Token::Open { name, attrs } if pred(name, attrs)))
+            .map(|(i, _)| element_range(tokens, i))
+            .max_by_key(|range| text_len(&tokens[range.clone()]))
+            .filter(|range| text_len(&tokens[range.clone()]) > 0)
+    };
+    let found = candidates(&|name, _| name == "article")
+        .or_else(|| candidates(&|name, _| name == "main"))
+        .or_else(|| candidates(&|_, attrs| attr(attrs, "role").is_some_and(|r| r == "main")))
+        .or_else(|| candidates(&|name, _| name == "body"));
+    found.map_or(tokens, |range| &tokens[range])
+}
+
+/// `tokens` without boilerplate elements.
+fn strip_boilerplate<'t, 'a>(tokens: &'t [Token<'a>]) -> Vec<&'t Token<'a>> {
+    let mut kept = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Token::Open { name, attrs } = &tokens[i] {
+            if is_boilerplate(name, attrs) {
+                i = element_range(tokens, i).end + 1;
+                continue;
+            }
+        }
+        kept.push(&tokens[i]);
+        i += 1;
+    }
+    kept
+}
+
+/// Absolute http(s)/mailto URL for a link or image reference.
+fn absolute_url(base: &reqwest::Url, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    let url = base.join(href).ok()?;
+    matches!(url.scheme(), "http" | "https" | "mailto").then(|| url.to_string())
+}
+
+// --- Markdown ---
+
+#[derive(Default)]
+struct MarkdownWriter {
+    out: String,
+    /// Line breaks owed before the next content (2 = blank line)
+    breaks: usize,
+    /// Quote depth for blank lines in the pending break
+    break_quote: usize,
+    space: bool,
+    quote: usize,
+    /// None for `<ul>`, the next number for `<ol>`
+    lists: Vec<Option<usize>>,
+    /// Output position after `[`, or None for links that are dropped
+    links: Vec<Option<(usize, String)>>,
+    cell_depth: usize,
+    row_cells: usize,
+    row_has_header: bool,
+    table_header_done: bool,
+}
+
+impl MarkdownWriter {
+    fn block(&mut self, breaks: usize) {
+        if self.cell_depth > 0 {
+            self.space = true;
+            return;
+        }
+        if self.breaks == 0 {
+            self.break_quote = self.quote;
+        }
+        self.breaks = self.breaks.max(breaks);
+        self.break_quote = self.break_quote.min(self.quote);
+        self.space = false;
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.breaks > 0
+    }
+
+    /// Write content, honoring pending breaks and spaces.
+    fn emit(&mut self, s: &str) {
+        if self.at_line_start() {
+            if !self.out.is_empty() {
+                self.out.push('\n');
+                for _ in 1..self.breaks {
+                    self.out.push_str(&">".repeat(self.break_quote));
+                    self.out.push('\n');
+                }
+            }
+            self.out.push_str(&"> ".repeat(self.quote));
+            self.breaks = 0;
+        } else if self.space && !self.out.ends_with([' ', '[']) {
+            self.out.push(' ');
+        }
+        self.space = false;
+        self.out.push_str(s);
+    }
+
+    /// Closing markup goes right after the text it closes.
+    fn close(&mut self, s: &str) {
+        if !self.at_line_start() {
+            self.out.push_str(s);
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+        if text.starts_with(char::is_whitespace) {
+            self.space = true;
+        }
+        for word in text.split_whitespace() {
+            self.emit(word);
+            self.space = true;
+        }
+        if !text.ends_with(char::is_whitespace) {
+            self.space = false;
+        }
+    }
+
+    fn code_block(&mut self, code: &str, lang: Option<&str>) {
+        self.block(2);
+        self.emit(&format!("```{}", lang.unwrap_or("")));
+        let prefix = "> ".repeat(self.quote);
+        for line in code.trim_end_matches('\n').trim_start_matches('\n').lines() {
+            self.out.push('\n');
+            self.out.push_str(&prefix);
+            self.out.push_str(line);
+        }
+        self.out.push('\n');
+        self.out.push_str(&prefix);
+        self.out.push_str("```");
+        self.block(2);
+    }
+
+    fn finish(self) -> String {
+        let mut out = String::with_capacity(self.out.len());
+        let mut blank = 0;
+        for line in self.out.lines().map(str::trim_end) {
+            if line.is_empty() {
+                blank += 1;
+                if blank > 1 {
+                    continue;
+                }
+            } else {
+                blank = 0;
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.trim().to_string()
+    }
+}
+
+/// Language of a `<pre>`/`<code>` from its `language-…` or `lang-…` class.
+fn code_language(attrs: &str) -> Option<String> {
+    attr(attrs, "class")?
+        .split_ascii_whitespace()
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+                .map(str::to_string)
+        })
+}
+
+fn to_markdown(tokens: &[&Token], base: &reqwest::Url) -> String {
+    let mut w = MarkdownWriter::default();
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::Text(text) => w.text(text),
+            Token::Open { name, attrs } => match name.as_str() {
+                "pre" => {
+                    // Raw text up to </pre>, keeping whitespace
+                    let mut code = String::new();
+                    let mut lang = code_language(attrs);
+                    i += 1;
+                    while i < tokens.len() && !matches!(tokens[i], Token::Close(n) if n == "pre") {
+                        match tokens[i] {
+                            Token::Text(text) => code.push_str(&decode_entities(text)),
+                            Token::Open { name, .. } if name == "br" => code.push('\n'),
+                            Token::Open { attrs, .. } => {
+                                lang = lang.or_else(|| code_language(attrs));
+                            }
+                            Token::Close(_) => {}
+                        }
+                        i += 1;
+                    }
+                    w.code_block(&code, lang.as_deref());
+                }
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    w.block(2);
+                    let level = usize::from(name.as_bytes()[1] - b'0');
+                    w.emit(&format!("{} ", "#".repeat(level)));
+                }
+                "p" | "div" | "section" | "article" | "main" | "figure" | "figcaption" | "dl"
+                | "dt" | "dd" | "details" | "summary" | "address" => w.block(2),
+                "br" => w.block(1),
+                "hr" => {
+                    w.block(2);
+                    w.emit("---");
+                    w.block(2);
+                }
+                "blockquote" => {
+                    w.block(2);
+                    w.quote += 1;
+                }
+                "ul" | "ol" => {
+                    w.block(if w.lists.is_empty() { 2 } else { 1 });
+                    let start = attr(attrs, "start").and_then(|s| s.parse().ok());
+                    w.lists.push((name == "ol").then(|| start.unwrap_or(1)));
+                }
+                "li" => {
+                    w.block(1);
+                    let indent = "   ".repeat(w.lists.len().saturating_sub(1));
+                    let marker = match w.lists.last_mut() {
+                        Some(Some(n)) => {
+                            *n += 1;
+                            format!("{}. ", *n - 1)
+                        }
+                        _ => "- ".to_string(),
+                    };
+                    w.emit(&format!("{indent}{marker}"));
+                }
+                "table" => {
+                    w.block(2);
+                    w.table_header_done = false;
+                }
+                "tr" => {
+                    w.block(1);
+                    w.emit("|");
+                    w.row_cells = 0;
+                    w.row_has_header = false;
+                }
+                "td" | "th" => {
+                    w.cell_depth += 1;
+                    w.row_cells += 1;
+                    w.row_has_header |= name == "th";
+                    w.space = true;
+                }
+                "strong" | "b" => w.emit("**"),
+                "em" | "i" => w.emit("*"),
+                "del" | "s" => w.emit("~~"),
+                "code" => w.emit("`"),
+                "a" => {
+                    let href = attr(attrs, "href").and_then(|h| absolute_url(base, &h));
+                    match href {
+                        Some(href) => {
+                            w.emit("[");
+                            w.links.push(Some((w.out.len(), href)));
+                        }
+                        None => w.links.push(None),
+                    }
+                }
+                "img" => {
+                    if let Some(src) = attr(attrs, "src").and_then(|s| absolute_url(base, &s)) {
+                        let alt = attr(attrs, "alt").unwrap_or_default();
+                        w.emit(&format!("![{}]({src})", alt.trim()));
+                    }
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "div" | "section" | "article"
+                | "main" | "figure" | "figcaption" | "dl" | "dt" | "dd" | "details" | "summary"
+                | "address" => w.block(2),
+                "blockquote" => {
+                    w.block(2);
+                    w.quote = w.quote.saturating_sub(1);
+                }
+                "ul" | "ol" => {
+                    w.lists.pop();
+                    w.block(if w.lists.is_empty() { 2 } else { 1 });
+                }
+                "td" | "th" => {
+                    w.cell_depth = w.cell_depth.saturating_sub(1);
+                    w.space = false;
+                    w.out.push_str(" |");
+                }
+                "tr" => {
+                    if w.row_has_header && !w.table_header_done && w.row_cells > 0 {
+                        w.block(1);
+                        w.emit(&format!("|{}", " --- |".repeat(w.row_cells)));
+                        w.table_header_done = true;
+                    }
+                    w.block(1);
+                }
+                "table" => w.block(2),
+                "strong" | "b" => w.close("**"),
+                "em" | "i" => w.close("*"),
+                "del" | "s" => w.close("~~"),
+                "code" => w.close("`"),
+                "a" => {
+                    if let Some(Some((start, href))) = w.links.pop() {
+                        if w.out.len() == start {
+                            // No link text: drop the "["
+                            w.out.truncate(start - 1);
+                        } else {
+                            w.close(&format!("]({href})"));
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    w.finish()
+}
+
+// --- HTML ---
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Content as HTML with only structural tags and link/image attributes.
+fn to_simple_html(tokens: &[&Token], base: &reqwest::Url) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        match token {
+            Token::Text(text) => out.push_str(&escape_html(&decode_entities(text))),
+            Token::Open { name, attrs } if HTML_KEEP_TAGS.contains(&name.as_str()) => {
+                out.push('<');
+                out.push_str(name);
+                let kept: &[&str] = match name.as_str() {
+                    "a" => &["href"],
+                    "img" => &["src", "alt"],
+                    "ol" => &["start"],
+                    "pre" | "code" => &["class"],
+                    _ => &[],
+                };
+                for &key in kept {
+                    let Some(value) = attr(attrs, key) else {
+                        continue;
+                    };
+                    let value = match key {
+                        "href" | "src" => match absolute_url(base, &value) {
+                            Some(url) => url,
+                            None => continue,
+                        },
+                        _ => value,
+                    };
+                    out.push_str(&format!(" {key}=\"{}\"", escape_html(&value)));
+                }
+                out.push('>');
+            }
+            Token::Close(name) if HTML_KEEP_TAGS.contains(&name.as_str()) && !is_void(name) => {
+                out.push_str(&format!("</{name}>"));
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+// --- Files ---
+
+/// Create `<stem>.<ext>` in `folder`, adding "-n" if the name is taken.
+fn create_unique(folder: &Path, stem: &str, ext: &str, content: &str) -> Result<PathBuf, String> {
+    for n in 1..1000 {
+        let file_name = if n == 1 {
+            format!("{stem}.{ext}")
+        } else {
+            format!("{stem}-{n}.{ext}")
+        };
+        let path = folder.join(file_name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {e}", path.display())),
+        }
+    }
+    Err(format!("Too many archives named {stem}"))
+}
+
+/// File name stem: date plus a slug of the title (or the host).
+fn archive_stem(date: &str, title: Option<&str>, url: &reqwest::Url) -> String {
+    let slug = crate::zettel::slugify(title.or(url.host_str()).unwrap_or("page"));
+    let slug: String = slug.chars().take(MAX_SLUG_CHARS).collect();
+    match slug.trim_end_matches('-') {
+        "" => format!("{date}-page"),
+        slug => format!("{date}-{slug}"),
+    }
+}
+
+struct ArchiveDocument {
+    title: Option<String>,
+    content: String,
+}
+
+/// Build the archived copy of a page.
+fn render_archive(
+    html: &str,
+    url: &reqwest::Url,
+    format: ArchiveFormat,
+    archived_at: &str,
+) -> ArchiveDocument {
+    let title = url_metadata::extract_title(html);
+    let tokens = tokenize(html);
+    let content = strip_boilerplate(content_root(&tokens));
+    let content = match format {
+        ArchiveFormat::Markdown => {
+            let quoted = |s: &str| serde_json::to_string(s).unwrap_or_default();
+            let mut doc = String::from("---\n");
+            if let Some(title) = &title {
+                doc.push_str(&format!("title: {}\n", quoted(title)));
+            }
+            doc.push_str(&format!("source: {}\n", quoted(url.as_str())));
+            doc.push_str(&format!("archived: {archived_at}\n---\n\n"));
+            let body = to_markdown(&content, url);
+            // Most articles repeat the title as their first heading
+            if let Some(title) = title.as_ref().filter(|_| !body.starts_with("# ")) {
+                doc.push_str(&format!("# {title}\n\n"));
+            }
+            doc.push_str(&body);
+            doc.push('\n');
+            doc
+        }
+        ArchiveFormat::Html => {
+            let title_html = escape_html(title.as_deref().unwrap_or(url.as_str()));
+            let source = escape_html(url.as_str());
+            format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title_html}</title>\n\
+                 <meta name=\"source\" content=\"{source}\">\n</head>\n<body>\n\
+                 <p><small>Archived from <a href=\"{source}\">{source}</a> on {archived_at}</small></p>\n\
+                 <article>\n{}\n</article>\n</body>\n</html>\n",
+                to_simple_html(&content, url).trim()
+            )
+        }
+    };
+    ArchiveDocument { title, content }
+}
+
+/// Href from the document's folder (or the workspace root) to `path`.
+fn archive_href(root: &Path, path: &Path, document: Option<&Path>) -> String {
+    let target = crate::file_finder::relative_path(root, path);
+    let from_dir = document
+        .filter(|doc| doc.starts_with(root))
+        .and_then(Path::parent)
+        .map(|dir| crate::file_finder::relative_path(root, dir))
+        .unwrap_or_default();
+    crate::link_rewrite::encode_href(&crate::link_rewrite::relative_between(&from_dir, &target))
+}
+
+/// Save a simplified copy of the page at `url` under
+/// `<workspace_root>/.vmark/archive/`. The returned href is relative to
+/// `document_path` when given, ready to annotate the link with.
+#[tauri::command]
+pub async fn archive_link(
+    url: String,
+    workspace_root: String,
+    document_path: Option<String>,
+    format: Option<ArchiveFormat>,
+) -> Result<ArchivedLink, String> {
+    let url = url_metadata::parse_http_url(&url)?;
+    let root = PathBuf::from(&workspace_root);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {workspace_root}"));
+    }
+    let client = url_metadata::make_client()?;
+    let page = url_metadata::fetch_html(&client, &url, MAX_PAGE_BYTES, false)
+        .await?
+        .ok_or_else(|| format!("{url} is not a web page"))?;
+
+    let format = format.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let now = Local::now();
+        let archived_at = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+        let doc = render_archive(&page.html, &page.url, format, &archived_at);
+
+        let folder = root.join(".vmark").join(ARCHIVE_DIR);
+        fs::create_dir_all(&folder)
+            .map_err(|e| format!("Failed to create {}: {e}", folder.display()))?;
+        let stem = archive_stem(
+            &now.format("%Y-%m-%d").to_string(),
+            doc.title.as_deref(),
+            &page.url,
+        );
+        let ext = match format {
+            ArchiveFormat::Markdown => "md",
+            ArchiveFormat::Html => "html",
+        };
+        let path = create_unique(&folder, &stem, ext, &doc.content)?;
+        Ok(ArchivedLink {
+            href: archive_href(&root, &path, document_path.as_deref().map(Path::new)),
+            path: path.to_string_lossy().into_owned(),
+            title: doc.title,
+            archived_at,
+        })
+    })
+    .await
+    .map_err(|e| format!("Archiving failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> reqwest::Url {
+        reqwest::Url::parse("https://example.com/blog/post").unwrap()
+    }
+
+    fn markdown(html: &str) -> String {
+        let tokens = tokenize(html);
+        to_markdown(&strip_boilerplate(content_root(&tokens)), &base())
+    }
+
+    #[test]
+    fn tokenizer_skips_scripts_and_comments() {
+        let tokens = tokenize("<p>a < b<!-- x --></p><script>if (a<b) {}</script><br/>");
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Open {
+                    name: "p".into(),
+                    attrs: ""
+                },
+                Token::Text("a "),
+                Token::Text("<"),
+                Token::Text(" b"),
+                Token::Close("p".into()),
+                Token::Open {
+                    name: "br".into(),
+                    attrs: ""
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_the_longest_article() {
+        let html = "<body><nav><a href='/'>Home</a></nav>\
+            <article><p>Teaser</p></article>\
+            <article><h1>Real post</h1><p>Long body text here.</p></article>\
+            <footer>Copyright</footer></body>";
+        assert_eq!(markdown(html), "# Real post\n\nLong body text here.");
+    }
+
+    #[test]
+    fn drops_page_furniture() {
+        let html = "<main><p>Keep</p><div class=\"share-buttons\"><a href=\"x\">Tweet</a></div>\
+            <section id=\"comments\"><p>Spam</p></section><div hidden>Hidden</div>\
+            <div role=\"complementary\">Side</div><p>Also keep</p></main>";
+        assert_eq!(markdown(html), "Keep\n\nAlso keep");
+    }
+
+    #[test]
+    fn converts_inline_markup_and_links() {
+        let html = "<article><p>Some <strong>bold</strong>, <em>soft</em> and <code>x()</code> \
+            with a <a href=\"/docs/a b\">relative link</a>, <a href=\"#top\">anchor</a> \
+            and <img src=\"pic.png\" alt=\"A pic\"> image.</p></article>";
+        assert_eq!(
+            markdown(html),
+            "Some **bold**, *soft* and `x()` with a \
+             [relative link](https://example.com/docs/a%20b), anchor and \
+             ![A pic](https://example.com/blog/pic.png) image."
+        );
+    }
+
+    #[test]
+    fn converts_lists_quotes_and_code() {
+        let html = "<article><ul><li>One</li><li>Two<ol start=\"3\"><li>Three</li></ol></li></ul>\
+            <blockquote><p>Quoted</p><p>Twice</p></blockquote>\
+            <pre><code class=\"language-rust\">fn main() {\n    x &lt; y;\n}</code></pre></article>";
+        assert_eq!(
+            markdown(html),
+            "- One\n- Two\n   3. Three\n\n> Quoted\n>\n> Twice\n\n```rust\nfn main() {\n    x < y;\n}\n```"
+        );
+    }
+
+    #[test]
+    fn converts_tables() {
+        let html = "<article><table><tr><th>Name</th><th>Value</th></tr>\
+            <tr><td><p>a</p></td><td>1</td></tr></table></article>";
+        assert_eq!(markdown(html), "| Name | Value |\n| --- | --- |\n| a | 1 |");
+    }
+
+    #[test]
+    fn archive_has_front_matter_and_title() {
+        let html = "<html><head><title>A &amp; B</title></head><body><p>Text</p></body></html>";
+        let doc = render_archive(
+            html,
+            &base(),
+            ArchiveFormat::Markdown,
+            "2026-01-02T03:04:05+00:00",
+        );
+        assert_eq!(doc.title.as_deref(), Some("A & B"));
+        assert_eq!(
+            doc.content,
+            "---\ntitle: \"A & B\"\nsource: \"https://example.com/blog/post\"\n\
+             archived: 2026-01-02T03:04:05+00:00\n---\n\n# A & B\n\nText\n"
+        );
+    }
+
+    #[test]
+    fn html_archive_keeps_only_structure() {
+        let html = "<body><div class=\"wrap\"><p style=\"color:red\" onclick=\"x()\">Hi \
+            <a href=\"/x\" target=\"_blank\">there</a></p><img src=\"javascript:alert(1)\"></div></body>";
+        let doc = render_archive(html, &base(), ArchiveFormat::Html, "2026-01-02");
+        assert!(doc.content.contains(
+            "<article>\n<p>Hi <a href=\"https://example.com/x\">there</a></p><img>\n</article>"
+        ));
+        assert!(!doc.content.contains("onclick"));
+    }
+
+    #[test]
+    fn names_and_hrefs() {
+        let url = base();
+        assert_eq!(
+            archive_stem("2026-01-02", Some("Hello, World!"), &url),
+            "2026-01-02-hello-world"
+        );
+        assert_eq!(
+            archive_stem("2026-01-02", None, &url),
+            "2026-01-02-example-com"
+        );
+        assert_eq!(
+            archive_stem("2026-01-02", Some("!!!"), &url),
+            "2026-01-02-page"
+        );
+
+        let root = Path::new("/ws");
+        let path = Path::new("/ws/.vmark/archive/2026-01-02-x.md");
+        assert_eq!(
+            archive_href(root, path, Some(Path::new("/ws/notes/My Note.md"))),
+            "../.vmark/archive/2026-01-02-x.md"
+        );
+        assert_eq!(
+            archive_href(root, path, None),
+            ".vmark/archive/2026-01-02-x.md"
+        );
+    }
+
+    #[test]
+    fn create_unique_adds_a_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = create_unique(dir.path(), "a", "md", "1").unwrap();
+        let second = create_unique(dir.path(), "a", "md", "2").unwrap();
+        assert!(first.ends_with("a.md"));
+        assert!(second.ends_with("a-2.md"));
+    }
+}
@@ -0,0 +1,385 @@
+//! Link Target Index
+//!
+//! Powers `[[` autocomplete. The backend keeps, per workspace, each note's
+//! title and headings, and answers fuzzy queries so the webview never needs
+//! the whole file list.
+//!
+//! The index is built on first query and refreshed at most every few seconds
+//! (only notes whose mtime changed are re-read). The file watcher
+//! (`apply_fs_change`) marks an index stale so the next query refreshes it.
+//!
+//! Queries: `name` matches note titles and paths; `name#heading` matches
+//! headings inside the notes that best match `name`.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::watcher::FsChangeEvent;
+
+/// Rescan interval when nothing invalidated the index
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+const DEFAULT_LIMIT: usize = 20;
+
+/// Notes searched for headings in a `note#heading` query
+const HEADING_NOTE_LIMIT: usize = 5;
+
+static INDEXES: Mutex<Option<HashMap<String, LinkIndex>>> = Mutex::new(None);
+
+#[derive(Debug, Clone)]
+struct IndexedNote {
+    /// Relative to the workspace root, `/`-separated
+    rel_path: String,
+    title: String,
+    headings: Vec<String>,
+    modified: Option<i64>,
+}
+
+struct LinkIndex {
+    notes: Vec<IndexedNote>,
+    refreshed_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSuggestion {
+    pub path: String,
+    pub title: String,
+    /// Set when suggesting a heading inside the note
+    pub heading: Option<String>,
+    pub score: i64,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Frontmatter `title:`, else the first `# ` heading, else the file name.
+pub(crate) fn note_title(rel_path: &str, content: &str) -> String {
+    if let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) {
+        let frontmatter = rest.split("\n---").next().unwrap_or("");
+        for line in frontmatter.lines() {
+            if let Some(value) = line.strip_prefix("title:") {
+                let value = value.trim().trim_matches(['"', '\'']);
+                if !value.is_empty() {
+                    return value.to_string();
+                }
+            }
+        }
+    }
+    if let Some(h1) = content.lines().find_map(|l| l.strip_prefix("# ")) {
+        return h1.trim().to_string();
+    }
+    let name = rel_path.rsplit('/').next().unwrap_or(rel_path);
+    name.split('.').next().unwrap_or(name).to_string()
+}
+
+/// ATX headings outside fenced code blocks.
+fn note_headings(content: &str) -> Vec<String> {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim();
+            if !text.is_empty() {
+                headings.push(text.to_string());
+            }
+        }
+    }
+    headings
+}
+
+fn index_note(root: &Path, path: &Path, modified: Option<i64>) -> IndexedNote {
+    let rel_path = crate::file_finder::relative_path(root, path);
+    // Encrypted notes are indexed by name only
+    let content = if crate::encryption::is_encrypted_path(path) {
+        String::new()
+    } else {
+        fs::read(path)
+            .map(|b| String::from_utf8_lossy(&b).into_owned())
+            .unwrap_or_default()
+    };
+    IndexedNote {
+        title: note_title(&rel_path, &content),
+        headings: note_headings(&content),
+        rel_path,
+        modified,
+    }
+}
+
+pub(crate) fn modified_ms(path: &Path) -> Option<i64> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+}
+
+/// Rebuild `previous` for the current files, re-reading only changed notes.
+fn refresh(root: &Path, previous: Vec<IndexedNote>) -> Vec<IndexedNote> {
+    let excludes = crate::workspace::workspace_excludes(root);
+    let mut known: HashMap<PathBuf, IndexedNote> = previous
+        .into_iter()
+        .map(|n| (root.join(&n.rel_path), n))
+        .collect();
+    crate::file_finder::collect_notes(root, &excludes)
+        .into_iter()
+        .map(|path| {
+            let modified = modified_ms(&path);
+            match known.remove(&path) {
+                Some(note) if note.modified == modified => note,
+                _ => index_note(root, &path, modified),
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Ranking
+// ============================================================================
+
+/// Fuzzy subsequence score of `query` (lowercase) in `candidate`; None if
+/// not all query characters appear in order. Prefers prefix, word-start and
+/// consecutive matches, and shorter candidates.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut pos = 0usize;
+    let mut previous: Option<usize> = None;
+    for q in query.chars() {
+        let found = (pos..chars.len()).find(|&i| chars[i] == q)?;
+        score += 1;
+        if found == 0 {
+            score += 8;
+        } else if !chars[found - 1].is_alphanumeric() {
+            score += 5;
+        }
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 4;
+        }
+        previous = Some(found);
+        pos = found + 1;
+    }
+    if chars.iter().collect::<String>().contains(query) {
+        score += 10;
+    }
+    Some(score * 100 - chars.len() as i64)
+}
+
+fn note_score(query: &str, note: &IndexedNote) -> Option<i64> {
+    let by_title = fuzzy_score(query, &note.title);
+    // Path matches count a little less than title matches
+    let by_path = fuzzy_score(query, &note.rel_path).map(|s| s - 50);
+    by_title.max(by_path)
+}
+
+fn suggest(notes: &[IndexedNote], query: &str, limit: usize) -> Vec<LinkSuggestion> {
+    let query = query.trim().trim_start_matches("[[").to_lowercase();
+
+    let mut results: Vec<LinkSuggestion> = match query.split_once('#') {
+        Some((note_query, heading_query)) => {
+            let mut notes: Vec<(i64, &IndexedNote)> = notes
+                .iter()
+                .filter_map(|n| note_score(note_query.trim(), n).map(|s| (s, n)))
+                .collect();
+            notes.sort_by_key(|(s, _)| std::cmp::Reverse(*s));
+            notes
+                .into_iter()
+                .take(HEADING_NOTE_LIMIT)
+                .flat_map(|(note_score, note)| {
+                    let heading_query = heading_query.trim().to_string();
+                    note.headings.iter().filter_map(move |h| {
+                        fuzzy_score(&heading_query, h).map(|s| LinkSuggestion {
+                            path: note.rel_path.clone(),
+                            title: note.title.clone(),
+                            heading: Some(h.clone()),
+                            score: s + note_score / 10,
+                        })
+                    })
+                })
+                .collect()
+        }
+        None => notes
+            .iter()
+            .filter_map(|n| {
+                note_score(&query, n).map(|score| LinkSuggestion {
+                    path: n.rel_path.clone(),
+                    title: n.title.clone(),
+                    heading: None,
+                    score,
+                })
+            })
+            .collect(),
+    };
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    results.truncate(limit);
+    results
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Fuzzy-ranked notes (or headings, for `note#heading`) matching `query`.
+#[tauri::command]
+pub async fn suggest_link_targets(
+    workspace_root: String,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<LinkSuggestion>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = PathBuf::from(&workspace_root);
+        if !root.is_dir() {
+            return Err(format!("Not a folder: {workspace_root}"));
+        }
+
+        // Take the index out while refreshing so other workspaces aren't blocked
+        let stale = {
+            let mut guard = INDEXES.lock().map_err(|e| format!("Lock error: {e}"))?;
+            let indexes = guard.get_or_insert_with(HashMap::new);
+            match indexes.get(&workspace_root) {
+                Some(index) if index.refreshed_at.is_some_and(|t| t.elapsed() < REFRESH_INTERVAL) => None,
+                _ => Some(indexes.remove(&workspace_root).map(|i| i.notes).unwrap_or_default()),
+            }
+        };
+        let mut guard = match stale {
+            Some(previous) => {
+                let notes = refresh(&root, previous);
+                let mut guard = INDEXES.lock().map_err(|e| format!("Lock error: {e}"))?;
+                guard.get_or_insert_with(HashMap::new).insert(
+                    workspace_root.clone(),
+                    LinkIndex {
+                        notes,
+                        refreshed_at: Some(Instant::now()),
+                    },
+                );
+                guard
+            }
+            None => INDEXES.lock().map_err(|e| format!("Lock error: {e}"))?,
+        };
+
+        let notes = guard
+            .get_or_insert_with(HashMap::new)
+            .get(&workspace_root)
+            .map(|i| i.notes.as_slice())
+            .unwrap_or_default();
+        Ok(suggest(notes, &query, limit.unwrap_or(DEFAULT_LIMIT)))
+    })
+    .await
+    .map_err(|e| format!("Link suggestion failed: {e}"))?
+}
+
+/// Mark indexes containing a changed path as out of date. Called by
+/// `watcher` for every change it reports.
+pub(crate) fn apply_fs_change(event: &FsChangeEvent) {
+    let Ok(mut guard) = INDEXES.lock() else {
+        return;
+    };
+    let Some(indexes) = guard.as_mut() else {
+        return;
+    };
+    for (root, index) in indexes.iter_mut() {
+        let affected = event
+            .paths
+            .iter()
+            .chain(event.from.iter())
+            .chain(event.to.iter())
+            .any(|path| Path::new(path).starts_with(root));
+        if affected {
+            index.refreshed_at = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(path: &str, title: &str, headings: &[&str]) -> IndexedNote {
+        IndexedNote {
+            rel_path: path.to_string(),
+            title: title.to_string(),
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_note_title() {
+        assert_eq!(note_title("a/x.md", "---\ntitle: \"Front\"\n---\n# H1"), "Front");
+        assert_eq!(note_title("a/x.md", "intro\n# Heading One\n"), "Heading One");
+        assert_eq!(note_title("a/my-note.md", "no heading"), "my-note");
+    }
+
+    #[test]
+    fn test_note_headings_skip_code() {
+        let content = "# One\n```\n# not heading\n```\n## Two ##\n#hashtag\n";
+        assert_eq!(note_headings(content), vec!["One", "Two"]);
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_prefix_and_contiguous() {
+        assert!(fuzzy_score("xyz", "abc").is_none());
+        let prefix = fuzzy_score("proj", "Project plan").unwrap();
+        let scattered = fuzzy_score("proj", "public road of jazz").unwrap();
+        assert!(prefix > scattered);
+        let word_start = fuzzy_score("plan", "Project plan").unwrap();
+        let mid_word = fuzzy_score("plan", "explanation").unwrap();
+        assert!(word_start > mid_word);
+    }
+
+    #[test]
+    fn test_suggest_notes_and_headings() {
+        let notes = vec![
+            note("projects/roadmap.md", "Roadmap", &["Q1 Goals", "Q2 Goals"]),
+            note("daily/2026-01-01.md", "2026-01-01", &["Todo"]),
+            note("reading.md", "Reading List", &[]),
+        ];
+
+        let results = suggest(&notes, "[[road", 10);
+        assert_eq!(results[0].path, "projects/roadmap.md");
+        assert!(results.iter().all(|r| r.heading.is_none()));
+
+        let results = suggest(&notes, "roadmap#q2", 10);
+        assert_eq!(results[0].heading.as_deref(), Some("Q2 Goals"));
+
+        assert_eq!(suggest(&notes, "", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_refresh_reuses_unchanged_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.md"), "# Alpha\n## Section").unwrap();
+
+        let first = refresh(root, Vec::new());
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].title, "Alpha");
+
+        // Same mtime: the cached entry is kept as-is
+        let mut cached = first.clone();
+        cached[0].title = "Cached".to_string();
+        fs::write(root.join("b.md"), "plain").unwrap();
+        let second = refresh(root, cached);
+        assert_eq!(second.len(), 2);
+        assert_eq!(second[0].title, "Cached");
+        assert_eq!(second[1].title, "b");
+    }
+}
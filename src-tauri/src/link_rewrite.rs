@@ -0,0 +1,379 @@
+//! Link Rewriting
+//!
+//! Keeps the knowledge graph intact when a note or folder is renamed or
+//! moved. After the move has happened on disk, every note in the workspace
+//! is scanned and rewritten in place:
+//!
+//! - Relative Markdown links (`[text](../old.md#anchor)`, images too) that
+//!   pointed into the moved path now point at its new location.
+//! - The moved notes' own relative links are re-based on their new folder.
+//! - Wiki links (`[[old]]`, `[[folder/old#Heading|alias]]`) use the new name
+//!   or path. Bare-name links are left alone if another note still has the
+//!   old name, since they now resolve to that note.
+//!
+//! Links inside fenced code blocks are never touched.
+
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::app_paths;
+use crate::file_finder::{collect_notes, relative_path};
+use crate::workspace_stats::{resolve_relative, strip_markdown_ext};
+
+/// A note whose links were rewritten
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatedFile {
+    pub path: String,
+    pub replacements: usize,
+}
+
+/// Workspace-relative old/new locations of a moved file or folder
+struct Move<'a> {
+    old: &'a str,
+    new: &'a str,
+}
+
+impl Move<'_> {
+    /// Where `path` (as it was before the move) is now.
+    fn forward(&self, path: &str) -> Option<String> {
+        if path == self.old {
+            Some(self.new.to_string())
+        } else {
+            path.strip_prefix(self.old)
+                .filter(|rest| rest.starts_with('/'))
+                .map(|rest| format!("{}{rest}", self.new))
+        }
+    }
+
+    /// Where `path` (as it is now) was before the move.
+    fn backward(&self, path: &str) -> String {
+        Move {
+            old: self.new,
+            new: self.old,
+        }
+        .forward(path)
+        .unwrap_or_else(|| path.to_string())
+    }
+}
+
+fn parent_dir(rel: &str) -> &str {
+    rel.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Relative path from folder `from_dir` to `target` (both workspace-relative).
+pub(crate) fn relative_between(from_dir: &str, target: &str) -> String {
+    let from: Vec<&str> = from_dir.split('/').filter(|s| !s.is_empty()).collect();
+    let to: Vec<&str> = target.split('/').collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<&str> = vec![".."; from.len() - common];
+    parts.extend(&to[common..]);
+    parts.join("/")
+}
+
+pub(crate) fn encode_href(path: &str) -> String {
+    path.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+}
+
+/// New href for a Markdown link in `note_now` (previously at `note_before`),
+/// or None if the link doesn't need to change.
+fn rewrite_href(href: &str, note_before: &str, note_now: &str, mv: &Move, root: &Path) -> Option<String> {
+    let (inner, bracketed) = match href.strip_prefix('<').and_then(|h| h.strip_suffix('>')) {
+        Some(inner) => (inner, true),
+        None => (href, false),
+    };
+    // Split off an optional title: (path "title")
+    let (path_part, title) = match inner.find(char::is_whitespace) {
+        Some(i) if !bracketed => (&inner[..i], &inner[i..]),
+        _ => (inner, ""),
+    };
+    if path_part.is_empty() || path_part.starts_with('#') || path_part.contains("://") || path_part.starts_with("mailto:") {
+        return None;
+    }
+    let split_at = path_part.find(['#', '?']).unwrap_or(path_part.len());
+    let (raw_path, suffix) = path_part.split_at(split_at);
+    let decoded = urlencoding::decode(raw_path).ok()?.into_owned();
+
+    let target_before = resolve_relative(note_before, &decoded)?;
+    let target_now = mv.forward(&target_before).unwrap_or_else(|| target_before.clone());
+    if note_before == note_now && target_now == target_before {
+        return None;
+    }
+    // Only fix links that work after the move; broken links stay as they were
+    if !root.join(&target_now).exists() {
+        return None;
+    }
+
+    let mut new_path = relative_between(parent_dir(note_now), &target_now);
+    if raw_path.starts_with("./") && !new_path.starts_with("../") {
+        new_path = format!("./{new_path}");
+    }
+    let new_path = if bracketed { new_path } else { encode_href(&new_path) };
+    let rebuilt = format!("{new_path}{suffix}{title}");
+    let rebuilt = if bracketed { format!("<{rebuilt}>") } else { rebuilt };
+    (rebuilt != href).then_some(rebuilt)
+}
+
+/// New inner text for a wiki link (`target#heading|alias`), if it pointed at the moved path.
+fn rewrite_wiki(inner: &str, mv: &Move, old_name_taken: bool) -> Option<String> {
+    let split_at = inner.find(['#', '|']).unwrap_or(inner.len());
+    let (target, rest) = inner.split_at(split_at);
+    let target_trimmed = target.trim();
+    if target_trimmed.is_empty() {
+        return None;
+    }
+    let target_key = strip_markdown_ext(target_trimmed).to_lowercase();
+    let old_no_ext = strip_markdown_ext(mv.old);
+    let new_no_ext = strip_markdown_ext(mv.new);
+
+    let replacement = if target_trimmed.contains('/') {
+        // Path form, relative to the workspace root
+        let old_key = old_no_ext.to_lowercase();
+        if target_key == old_key {
+            new_no_ext.to_string()
+        } else if let Some(rest) = target_key.strip_prefix(&format!("{old_key}/")) {
+            // Folder move: keep the original casing of the part below the folder
+            let stripped = strip_markdown_ext(target_trimmed);
+            let tail = stripped.get(stripped.len().saturating_sub(rest.len())..).unwrap_or(rest);
+            format!("{new_no_ext}/{tail}")
+        } else {
+            return None;
+        }
+    } else {
+        let old_name = old_no_ext.rsplit('/').next().unwrap_or(old_no_ext);
+        let new_name = new_no_ext.rsplit('/').next().unwrap_or(new_no_ext);
+        if target_key != old_name.to_lowercase() || old_name == new_name || old_name_taken {
+            return None;
+        }
+        new_name.to_string()
+    };
+    Some(format!("{replacement}{rest}"))
+}
+
+/// Rewrite the links on one line outside code. Returns the number of changes.
+fn rewrite_line(line: &str, note_before: &str, note_now: &str, mv: &Move, root: &Path, old_name_taken: bool) -> (String, usize) {
+    let mut out = String::with_capacity(line.len());
+    let mut count = 0;
+    let mut rest = line;
+    loop {
+        let wiki = rest.find("[[");
+        let md = rest.find("](");
+        match (wiki, md) {
+            (Some(w), m) if m.is_none_or(|m| w < m) => {
+                let after = &rest[w + 2..];
+                let Some(end) = after.find("]]") else { break };
+                out.push_str(&rest[..w + 2]);
+                let inner = &after[..end];
+                match rewrite_wiki(inner, mv, old_name_taken) {
+                    Some(new_inner) => {
+                        out.push_str(&new_inner);
+                        count += 1;
+                    }
+                    None => out.push_str(inner),
+                }
+                out.push_str("]]");
+                rest = &after[end + 2..];
+            }
+            (_, Some(m)) => {
+                let after = &rest[m + 2..];
+                let Some(end) = after.find(')') else { break };
+                out.push_str(&rest[..m + 2]);
+                let href = &after[..end];
+                match rewrite_href(href, note_before, note_now, mv, root) {
+                    Some(new_href) => {
+                        out.push_str(&new_href);
+                        count += 1;
+                    }
+                    None => out.push_str(href),
+                }
+                out.push(')');
+                rest = &after[end + 1..];
+            }
+            _ => break,
+        }
+    }
+    out.push_str(rest);
+    (out, count)
+}
+
+fn rewrite_content(content: &str, note_before: &str, note_now: &str, mv: &Move, root: &Path, old_name_taken: bool) -> (String, usize) {
+    let mut out = String::with_capacity(content.len());
+    let mut total = 0;
+    let mut fence: Option<&str> = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None => {
+                let (rewritten, count) = rewrite_line(line, note_before, note_now, mv, root, old_name_taken);
+                if count > 0 {
+                    out.push_str(&rewritten);
+                    total += count;
+                    continue;
+                }
+            }
+        }
+        out.push_str(line);
+    }
+    (out, total)
+}
+
+/// Rewrite links across the workspace after `old_path` was moved to
+/// `new_path` (a note, any other file, or a folder). The move must already
+/// have happened. `before_write` sees each note's previous content just
+/// before it is overwritten.
+pub(crate) fn rewrite_links_after_move(
+    root: &Path,
+    old_path: &Path,
+    new_path: &Path,
+    mut before_write: impl FnMut(&Path, &str),
+) -> Result<Vec<UpdatedFile>, String> {
+    let old_rel = relative_path(root, old_path);
+    let new_rel = relative_path(root, new_path);
+    if old_rel == new_rel || old_path.strip_prefix(root).is_err() || new_path.strip_prefix(root).is_err() {
+        return Ok(Vec::new());
+    }
+    let mv = Move { old: &old_rel, new: &new_rel };
+
+    let notes = collect_notes(root, &crate::workspace::workspace_excludes(root));
+
+    // After a rename the moved note no longer has the old name, so any note
+    // that does is another one, and bare `[[old name]]` links now resolve to it
+    let name_of = |rel: &str| strip_markdown_ext(rel.rsplit('/').next().unwrap_or(rel)).to_lowercase();
+    let old_name = name_of(&old_rel);
+    let old_name_taken = notes.iter().any(|p| name_of(&relative_path(root, p)) == old_name);
+
+    let mut updated = Vec::new();
+    for path in notes {
+        if crate::encryption::is_encrypted_path(&path) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let note_now = relative_path(root, &path);
+        let note_before = mv.backward(&note_now);
+        let (rewritten, replacements) = rewrite_content(&content, &note_before, &note_now, &mv, root, old_name_taken);
+        if replacements == 0 {
+            continue;
+        }
+        before_write(&path, &content);
+        app_paths::write_document_file(&path, rewritten.as_bytes())?;
+        updated.push(UpdatedFile {
+            path: path.to_string_lossy().into_owned(),
+            replacements,
+        });
+    }
+    if !updated.is_empty() {
+        tracing::info!("[Links] Updated links in {} file(s) after moving {}", updated.len(), old_rel);
+    }
+    Ok(updated)
+}
+
+/// Update links across the workspace after a note or folder was renamed or
+/// moved from `old_path` to `new_path`.
+#[tauri::command]
+pub async fn update_links_on_rename(
+    root_path: String,
+    old_path: String,
+    new_path: String,
+) -> Result<Vec<UpdatedFile>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        rewrite_links_after_move(Path::new(&root_path), Path::new(&old_path), Path::new(&new_path), |_, _| {})
+    })
+    .await
+    .map_err(|e| format!("Failed to update links: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read(root: &Path, rel: &str) -> String {
+        fs::read_to_string(root.join(rel)).unwrap()
+    }
+
+    #[test]
+    fn test_relative_between() {
+        assert_eq!(relative_between("", "a/b.md"), "a/b.md");
+        assert_eq!(relative_between("a", "a/b.md"), "b.md");
+        assert_eq!(relative_between("a/x", "b/c.md"), "../../b/c.md");
+    }
+
+    #[test]
+    fn test_rename_updates_incoming_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "index.md", "[Old](notes/old.md#part) and [[old|Old note]] and [[notes/old#Intro]]\n");
+        write(root, "notes/other.md", "```\n[[old]]\n```\nsee [it](./old.md \"Title\")\n");
+        write(root, "notes/new name.md", "# moved\n");
+
+        let updated = rewrite_links_after_move(root, &root.join("notes/old.md"), &root.join("notes/new name.md"), |_, _| {}).unwrap();
+
+        assert_eq!(updated.len(), 2);
+        assert_eq!(
+            read(root, "index.md"),
+            "[Old](notes/new%20name.md#part) and [[new name|Old note]] and [[notes/new name#Intro]]\n"
+        );
+        assert_eq!(read(root, "notes/other.md"), "```\n[[old]]\n```\nsee [it](./new%20name.md \"Title\")\n");
+    }
+
+    #[test]
+    fn test_move_rebases_own_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "img/pic.png", "");
+        write(root, "target.md", "t");
+        // note.md moved from the root into archive/
+        write(root, "archive/note.md", "![p](img/pic.png) [t](target.md) [self](note.md) [[target]]\n");
+
+        let updated = rewrite_links_after_move(root, &root.join("note.md"), &root.join("archive/note.md"), |_, _| {}).unwrap();
+
+        assert_eq!(updated.len(), 1);
+        assert_eq!(
+            read(root, "archive/note.md"),
+            "![p](../img/pic.png) [t](../target.md) [self](note.md) [[target]]\n"
+        );
+    }
+
+    #[test]
+    fn test_folder_move() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "index.md", "[a](projects/a.md) [[projects/A]] [[a]]\n");
+        write(root, "archive/projects/a.md", "[b](b.md) [i](../index.md)\n");
+        write(root, "archive/projects/b.md", "b");
+
+        let mut previous = Vec::new();
+        let updated = rewrite_links_after_move(root, &root.join("projects"), &root.join("archive/projects"), |p, c| {
+            previous.push((p.to_path_buf(), c.to_string()))
+        })
+        .unwrap();
+
+        assert_eq!(updated.len(), 2);
+        assert_eq!(read(root, "index.md"), "[a](archive/projects/a.md) [[archive/projects/A]] [[a]]\n");
+        assert_eq!(read(root, "archive/projects/a.md"), "[b](b.md) [i](../../index.md)\n");
+        assert_eq!(previous.len(), 2);
+    }
+
+    #[test]
+    fn test_bare_wiki_link_kept_when_name_still_taken() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "a/todo.md", "other todo");
+        write(root, "b/tasks.md", "renamed");
+        write(root, "index.md", "[[todo]]\n");
+
+        let updated = rewrite_links_after_move(root, &root.join("b/todo.md"), &root.join("b/tasks.md"), |_, _| {}).unwrap();
+        assert!(updated.is_empty());
+        assert_eq!(read(root, "index.md"), "[[todo]]\n");
+    }
+}
@@ -0,0 +1,369 @@
+//! Logging - `tracing` output to a rotating file, with runtime levels.
+//!
+//! Log lines go to `vmark.log` in the app log directory (rotated to
+//! `vmark.1.log`, `vmark.2.log` when it grows too large), to an in-memory
+//! buffer served by `get_recent_logs`, and to stderr in debug builds.
+//!
+//! Levels are set per module (`set_log_level("hot_exit", "debug")`) or for
+//! everything at once, and persist in the app data directory (`logging.json`)
+//! so a release build can be switched to verbose logging to diagnose an issue.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+use crate::app_paths;
+
+/// Settings file name in the app data directory
+pub const LOGGING_FILE: &str = "logging.json";
+
+/// Current log file name in the app log directory
+pub const LOG_FILE_NAME: &str = "vmark.log";
+
+/// Size at which the log file is rotated
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the current one
+const MAX_ROTATED_FILES: usize = 2;
+/// Lines kept in memory for `get_recent_logs`
+const RECENT_LOG_LINES: usize = 1000;
+
+/// Crate name that module targets start with
+const CRATE_TARGET: &str = env!("CARGO_CRATE_NAME");
+
+/// On-disk log levels: a default and per-target overrides
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevels {
+    #[serde(default = "default_level")]
+    pub default: String,
+    #[serde(default)]
+    pub targets: BTreeMap<String, String>,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        Self {
+            default: default_level(),
+            targets: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_level() -> String {
+    if cfg!(debug_assertions) { "debug" } else { "info" }.to_string()
+}
+
+/// Log file with size-based rotation
+struct RotatingFile {
+    dir: PathBuf,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE_NAME);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file: Some(file),
+            size,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("vmark.{}.log", index))
+    }
+
+    /// Shift `vmark.log` -> `vmark.1.log` -> `vmark.2.log`, dropping the oldest.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        let _ = fs::remove_file(self.rotated_path(MAX_ROTATED_FILES));
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let _ = fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+        }
+        let current = self.dir.join(LOG_FILE_NAME);
+        fs::rename(&current, self.rotated_path(1))?;
+        self.file = Some(OpenOptions::new().create(true).append(true).open(&current)?);
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.size > 0 && self.size + bytes.len() as u64 > MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(bytes)?;
+            self.size += bytes.len() as u64;
+        }
+        Ok(())
+    }
+}
+
+/// Lines most recently logged, oldest first
+fn push_recent(recent: &mut VecDeque<String>, bytes: &[u8], capacity: usize) {
+    for line in String::from_utf8_lossy(bytes).lines() {
+        if recent.len() == capacity {
+            recent.pop_front();
+        }
+        recent.push_back(line.to_string());
+    }
+}
+
+/// Writer handed to the fmt layer: the log file plus the recent lines buffer.
+#[derive(Clone)]
+struct LogWriter {
+    file: Option<Arc<Mutex<RotatingFile>>>,
+}
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut recent) = RECENT_LOGS.lock() {
+            push_recent(&mut recent, buf, RECENT_LOG_LINES);
+        }
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                file.write_all(buf)?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Loaded levels, the file they persist to and the live filter handle
+struct LoggingState {
+    file: PathBuf,
+    levels: LogLevels,
+    filter: reload::Handle<Targets, Registry>,
+}
+
+static LOGGING_STATE: OnceLock<Mutex<LoggingState>> = OnceLock::new();
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .trim()
+        .parse::<LevelFilter>()
+        .map_err(|_| format!("Unknown log level: {}", level))
+}
+
+/// Full tracing target for a module name (`hot_exit` -> `vmark_lib::hot_exit`).
+/// `frontend` and targets that already name the crate are kept as-is.
+fn normalize_target(target: &str) -> String {
+    let target = target.trim();
+    if target == "frontend" || target == CRATE_TARGET || target.starts_with(&format!("{}::", CRATE_TARGET)) {
+        target.to_string()
+    } else {
+        format!("{}::{}", CRATE_TARGET, target)
+    }
+}
+
+fn build_filter(levels: &LogLevels) -> Targets {
+    let default = parse_level(&levels.default).unwrap_or(LevelFilter::INFO);
+    levels
+        .targets
+        .iter()
+        .filter_map(|(target, level)| Some((target.clone(), parse_level(level).ok()?)))
+        .fold(Targets::new().with_default(default), |filter, (target, level)| {
+            filter.with_target(target, level)
+        })
+}
+
+fn load_levels(file: &Path) -> LogLevels {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_levels(file: &Path, levels: &LogLevels) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(levels)
+        .map_err(|e| format!("Failed to serialize log levels: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Install the global subscriber. Called first thing during app setup.
+/// Without a writable log directory, logs still reach the in-memory buffer.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(LOGGING_FILE);
+    let levels = load_levels(&file);
+
+    let log_file = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| e.to_string())
+        .and_then(|dir| RotatingFile::open(&dir).map_err(|e| e.to_string()));
+    let file_error = log_file.as_ref().err().cloned();
+    let writer = LogWriter {
+        file: log_file.ok().map(|f| Arc::new(Mutex::new(f))),
+    };
+
+    let (filter, handle) = reload::Layer::new(build_filter(&levels));
+    let stderr = cfg!(debug_assertions).then(|| fmt::layer().with_writer(io::stderr));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_ansi(false).with_writer(move || writer.clone()))
+        .with(stderr)
+        .try_init()
+        .map_err(|e| e.to_string())?;
+
+    let _ = LOGGING_STATE.set(Mutex::new(LoggingState {
+        file,
+        levels,
+        filter: handle,
+    }));
+    if let Some(e) = file_error {
+        tracing::warn!("Logging to memory only, log file unavailable: {}", e);
+    }
+    Ok(())
+}
+
+/// Most recent log lines, oldest first (at most `limit`, default all buffered).
+#[tauri::command]
+pub fn get_recent_logs(limit: Option<usize>) -> Vec<String> {
+    let Ok(recent) = RECENT_LOGS.lock() else {
+        return Vec::new();
+    };
+    let skip = limit.map_or(0, |limit| recent.len().saturating_sub(limit));
+    recent.iter().skip(skip).cloned().collect()
+}
+
+/// Set the level for one module (`target`), or the default level when no
+/// target is given. `level` is one of off/error/warn/info/debug/trace, or
+/// "reset" to drop a module override.
+#[tauri::command]
+pub fn set_log_level(target: Option<String>, level: String) -> Result<(), String> {
+    let state = LOGGING_STATE.get().ok_or("Logging not initialized")?;
+    let mut guard = state.lock().map_err(|_| "Logging lock poisoned".to_string())?;
+
+    match target.filter(|t| !t.trim().is_empty()) {
+        Some(target) if level == "reset" => {
+            guard.levels.targets.remove(&normalize_target(&target));
+        }
+        Some(target) => {
+            parse_level(&level)?;
+            guard.levels.targets.insert(normalize_target(&target), level.trim().to_lowercase());
+        }
+        None => {
+            parse_level(&level)?;
+            guard.levels.default = level.trim().to_lowercase();
+        }
+    }
+
+    guard
+        .filter
+        .reload(build_filter(&guard.levels))
+        .map_err(|e| format!("Failed to apply log level: {}", e))?;
+    save_levels(&guard.file, &guard.levels)
+}
+
+/// Current default and per-module levels.
+#[tauri::command]
+pub fn get_log_levels() -> LogLevels {
+    LOGGING_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .map(|guard| guard.levels.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!(parse_level("debug"), Ok(LevelFilter::DEBUG));
+        assert_eq!(parse_level(" WARN "), Ok(LevelFilter::WARN));
+        assert_eq!(parse_level("off"), Ok(LevelFilter::OFF));
+        assert!(parse_level("loud").is_err());
+    }
+
+    #[test]
+    fn normalizes_module_targets() {
+        assert_eq!(normalize_target("hot_exit"), format!("{}::hot_exit", CRATE_TARGET));
+        assert_eq!(
+            normalize_target(&format!("{}::menu", CRATE_TARGET)),
+            format!("{}::menu", CRATE_TARGET)
+        );
+        assert_eq!(normalize_target("frontend"), "frontend");
+    }
+
+    #[test]
+    fn filter_applies_module_overrides() {
+        let mut levels = LogLevels {
+            default: "warn".into(),
+            targets: BTreeMap::new(),
+        };
+        levels.targets.insert(normalize_target("hot_exit"), "debug".into());
+        let filter = build_filter(&levels);
+
+        let hot_exit = format!("{}::hot_exit::coordinator", CRATE_TARGET);
+        let menu = format!("{}::menu", CRATE_TARGET);
+        assert!(filter.would_enable(&hot_exit, &Level::DEBUG));
+        assert!(!filter.would_enable(&menu, &Level::INFO));
+        assert!(filter.would_enable(&menu, &Level::WARN));
+    }
+
+    #[test]
+    fn recent_buffer_keeps_newest_lines() {
+        let mut recent = VecDeque::new();
+        push_recent(&mut recent, b"one\ntwo\n", 3);
+        push_recent(&mut recent, b"three\nfour\n", 3);
+        assert_eq!(recent, ["two", "three", "four"]);
+    }
+
+    #[test]
+    fn rotates_when_file_is_full() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = RotatingFile::open(dir.path()).unwrap();
+        file.write_all(b"first\n").unwrap();
+        file.size = MAX_LOG_SIZE;
+        file.write_all(b"second\n").unwrap();
+        file.size = MAX_LOG_SIZE;
+        file.write_all(b"third\n").unwrap();
+        file.size = MAX_LOG_SIZE;
+        file.write_all(b"fourth\n").unwrap();
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read(LOG_FILE_NAME), "fourth\n");
+        assert_eq!(read("vmark.1.log"), "third\n");
+        assert_eq!(read("vmark.2.log"), "second\n");
+        assert!(!dir.path().join("vmark.3.log").exists());
+    }
+
+    #[test]
+    fn levels_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(LOGGING_FILE);
+        assert_eq!(load_levels(&file), LogLevels::default());
+
+        let mut levels = LogLevels::default();
+        levels.targets.insert(normalize_target("menu"), "trace".into());
+        save_levels(&file, &levels).unwrap();
+        assert_eq!(load_levels(&file), levels);
+    }
+}
@@ -15,33 +15,32 @@ use objc2_foundation::NSString;
 /// Must be called after `app.set_menu()`.
 pub fn fix_help_menu() {
     let Some(mtm) = MainThreadMarker::new() else {
-        eprintln!("[macos_menu] Not on main thread, cannot fix Help menu");
+        tracing::warn!("Not on main thread, cannot fix Help menu");
         return;
     };
 
     let app = NSApplication::sharedApplication(mtm);
     let Some(main_menu) = app.mainMenu() else {
-        eprintln!("[macos_menu] No main menu found");
+        tracing::warn!("No main menu found");
         return;
     };
 
     // Find the Help menu by title
     let help_title = NSString::from_str("Help");
     let Some(help_item) = main_menu.itemWithTitle(&help_title) else {
-        eprintln!("[macos_menu] No 'Help' menu item found");
+        tracing::warn!("No 'Help' menu item found");
         return;
     };
 
     let Some(help_submenu) = help_item.submenu() else {
-        eprintln!("[macos_menu] Help item has no submenu");
+        tracing::warn!("Help item has no submenu");
         return;
     };
 
     // Register as the Help menu — this enables the native search field
     app.setHelpMenu(Some(&help_submenu));
 
-    #[cfg(debug_assertions)]
-    eprintln!("[macos_menu] Help menu registered with search field");
+    tracing::debug!("Help menu registered with search field");
 }
 
 /// Fix the Window menu on macOS.
@@ -70,8 +69,7 @@ pub fn fix_window_menu() {
 
     app.setWindowsMenu(Some(&window_submenu));
 
-    #[cfg(debug_assertions)]
-    eprintln!("[macos_menu] Window menu registered");
+    tracing::debug!("Window menu registered");
 }
 
 // ============================================================================
@@ -202,6 +200,7 @@ const MENU_ICONS: &[(&str, &str)] = &[
     ("Add Column After", "arrow.right.to.line"),
     ("Delete Row", "minus.rectangle"),
     ("Delete Column", "minus.rectangle.portrait"),
+    ("Delete Selected Cells", "minus.square"),
     ("Delete Table", "trash"),
     ("Align Left", "text.alignleft"),
     ("Align Center", "text.aligncenter"),
@@ -269,8 +268,7 @@ pub fn apply_menu_icons() {
 
     apply_icons_to_menu(&main_menu, None);
 
-    #[cfg(debug_assertions)]
-    eprintln!("[macos_menu] Menu icons applied");
+    tracing::debug!("Menu icons applied");
 }
 
 /// Fallback icon for dynamic menu items based on which submenu they're in.
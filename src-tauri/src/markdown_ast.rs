@@ -0,0 +1,1389 @@
+//! Markdown Block Structure
+//!
+//! Outline, folding and genie scope resolution all need the same thing: where
+//! each block of an open document starts and ends. Rather than re-parsing the
+//! whole document in the webview on every keystroke, the editor loads it here
+//! once with `parse_document` and forwards its change sets to
+//! `edit_parsed_document`.
+//!
+//! Edits are incremental: parsing restarts at the block before the first
+//! change and stops as soon as a new block starts where an old one did, past
+//! the changed region. From there on the text and parser state are the same,
+//! so the remaining old blocks are reused with shifted offsets.
+//!
+//! Blockquotes and list items are containers: their content, with the `>`
+//! markers and item indentation stripped, is parsed again into child blocks,
+//! so a fence in a list item or a heading in a quote is found like any other.
+//! Continuation follows CommonMark: item content must be indented to the
+//! item's content column, and a line without the markers only continues
+//! (lazily) when it extends a paragraph.
+//!
+//! This is a line-based block parser in the style of the other markdown
+//! scanners in this crate, not a full CommonMark implementation: inline
+//! content is not parsed, and link reference definitions are paragraphs.
+//! Offsets are UTF-16 code units, matching editor positions.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::find_cache::{apply_edits, TextEdit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockKind {
+    FrontMatter,
+    Heading,
+    Paragraph,
+    CodeBlock,
+    MathBlock,
+    Blockquote,
+    List,
+    ListItem,
+    Table,
+    ThematicBreak,
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    pub kind: BlockKind,
+    pub from: usize,
+    /// End of the last line, before its line break
+    pub to: usize,
+    /// 0-based
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Heading level (1-6)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+    /// Heading text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Code fence language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Content of a blockquote or list item; the items of a list
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Block>,
+    /// Byte offset of `from`, to restart parsing here after an edit
+    #[serde(skip)]
+    byte: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeAt {
+    /// Top-level block; None between blocks
+    pub block: Option<Block>,
+    /// Deepest block at the offset (e.g. a paragraph in a list item)
+    pub innermost: Option<Block>,
+    /// Headings whose sections contain the offset, outermost first
+    pub headings: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FoldRange {
+    pub kind: BlockKind,
+    pub from: usize,
+    pub to: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+struct ParsedDocument {
+    text: String,
+    blocks: Vec<Block>,
+}
+
+/// Containers nested deeper than this keep their content unparsed
+const MAX_DEPTH: usize = 32;
+
+fn documents() -> &'static Mutex<HashMap<String, ParsedDocument>> {
+    static DOCUMENTS: OnceLock<Mutex<HashMap<String, ParsedDocument>>> = OnceLock::new();
+    DOCUMENTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// ---------------------------------------------------------------------------
+// Lines
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct Line<'a> {
+    /// Without the line break (a trailing `\r` is trimmed)
+    content: &'a str,
+    byte: usize,
+    from: usize,
+    to: usize,
+    number: usize,
+}
+
+/// Walks a text line by line, tracking byte and UTF-16 positions.
+#[derive(Clone, Copy)]
+struct Cursor<'a> {
+    text: &'a str,
+    byte: usize,
+    utf16: usize,
+    line: usize,
+    done: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str, byte: usize, utf16: usize, line: usize) -> Self {
+        Self {
+            text,
+            byte,
+            utf16,
+            line,
+            done: byte > text.len(),
+        }
+    }
+}
+
+/// Lines for the block parser: the document itself, or a container's
+/// content with its markers stripped.
+trait LineSource<'a>: Copy {
+    fn current(&self) -> Option<Line<'a>>;
+    fn advance(&mut self);
+    /// Containers around the lines (0 for the document)
+    fn depth(&self) -> usize;
+
+    /// The line after the current one, without moving.
+    fn peek_next(&self) -> Option<Line<'a>> {
+        let mut next = *self;
+        next.advance();
+        next.current()
+    }
+
+    /// The next non-blank line after the current one, without moving.
+    fn peek_next_content(&self) -> Option<Line<'a>> {
+        let mut next = *self;
+        loop {
+            next.advance();
+            let line = next.current()?;
+            if !is_blank(line.content) {
+                return Some(line);
+            }
+        }
+    }
+}
+
+impl<'a> LineSource<'a> for Cursor<'a> {
+    fn current(&self) -> Option<Line<'a>> {
+        if self.done {
+            return None;
+        }
+        let rest = &self.text[self.byte..];
+        let raw = rest.find('\n').map_or(rest, |nl| &rest[..nl]);
+        Some(Line {
+            content: raw.strip_suffix('\r').unwrap_or(raw),
+            byte: self.byte,
+            from: self.utf16,
+            to: self.utf16 + raw.encode_utf16().count(),
+            number: self.line,
+        })
+    }
+
+    fn advance(&mut self) {
+        let rest = &self.text[self.byte..];
+        match rest.find('\n') {
+            Some(nl) => {
+                self.utf16 += rest[..nl].encode_utf16().count() + 1;
+                self.byte += nl + 1;
+                self.line += 1;
+            }
+            None => self.done = true,
+        }
+    }
+
+    fn depth(&self) -> usize {
+        0
+    }
+}
+
+/// The content lines of a container.
+#[derive(Clone, Copy)]
+struct ContainerLines<'s, 'a> {
+    lines: &'s [Line<'a>],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> LineSource<'a> for ContainerLines<'_, 'a> {
+    fn current(&self) -> Option<Line<'a>> {
+        self.lines.get(self.pos).copied()
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// `line` without its first `len` bytes (markers and indentation, which are
+/// ASCII, so bytes and UTF-16 units agree).
+fn skip(line: Line, len: usize) -> Line {
+    let len = len.min(line.content.len());
+    Line {
+        content: &line.content[len..],
+        byte: line.byte + len,
+        from: line.from + len,
+        ..line
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Line classification
+// ---------------------------------------------------------------------------
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Leading indentation in columns (tabs count as 4).
+fn indent(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Content after up to 3 spaces of indentation; None for indented code.
+fn block_content(line: &str) -> Option<&str> {
+    (indent(line) <= 3).then(|| line.trim_start())
+}
+
+/// Opening code fence: (fence char, length, info string).
+fn fence_open(line: &str) -> Option<(char, usize, &str)> {
+    let content = block_content(line)?;
+    let marker = content.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = content.chars().take_while(|c| *c == marker).count();
+    if len < 3 {
+        return None;
+    }
+    let info = content[len..].trim();
+    // A backtick fence's info string can't contain backticks
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some((marker, len, info))
+}
+
+fn closes_fence(line: &str, marker: char, len: usize) -> bool {
+    block_content(line).is_some_and(|content| {
+        let run = content.chars().take_while(|c| *c == marker).count();
+        run >= len && content[run..].trim().is_empty()
+    })
+}
+
+fn is_math_fence(line: &str) -> bool {
+    block_content(line).is_some_and(|content| content.trim_end() == "$$")
+}
+
+/// ATX heading: (level, text).
+fn atx_heading(line: &str) -> Option<(u8, String)> {
+    let content = block_content(line)?;
+    let level = content.chars().take_while(|c| *c == '#').count();
+    let rest = &content[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let text = rest.trim();
+    // Optional closing sequence, only when separated by a space
+    let text = match text.trim_end_matches('#') {
+        stripped if stripped.is_empty() || stripped.ends_with([' ', '\t']) => stripped.trim_end(),
+        _ => text,
+    };
+    Some((level as u8, text.to_string()))
+}
+
+fn is_thematic_break(line: &str) -> bool {
+    let Some(content) = block_content(line) else {
+        return false;
+    };
+    let Some(marker) = content
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '-' | '*' | '_'))
+    else {
+        return false;
+    };
+    content
+        .chars()
+        .all(|c| c == marker || c == ' ' || c == '\t')
+        && content.chars().filter(|c| *c == marker).count() >= 3
+}
+
+/// Setext underline: 1 for `===`, 2 for `---`.
+fn setext_level(line: &str) -> Option<u8> {
+    let content = block_content(line)?.trim_end();
+    let marker = content.chars().next()?;
+    if !content.chars().all(|c| c == marker) {
+        return None;
+    }
+    match marker {
+        '=' => Some(1),
+        '-' => Some(2),
+        _ => None,
+    }
+}
+
+fn is_blockquote(line: &str) -> bool {
+    block_content(line).is_some_and(|content| content.starts_with('>'))
+}
+
+fn is_bullet_item(content: &str) -> bool {
+    let mut chars = content.chars();
+    matches!(chars.next(), Some('-' | '*' | '+')) && matches!(chars.next(), None | Some(' ' | '\t'))
+}
+
+fn is_ordered_item(content: &str) -> bool {
+    let digits = content.chars().take_while(|c| c.is_ascii_digit()).count();
+    if !(1..=9).contains(&digits) {
+        return false;
+    }
+    let mut rest = content[digits..].chars();
+    matches!(rest.next(), Some('.' | ')')) && matches!(rest.next(), None | Some(' ' | '\t'))
+}
+
+fn is_list_item(line: &str) -> bool {
+    block_content(line).is_some_and(|content| is_bullet_item(content) || is_ordered_item(content))
+}
+
+/// Bytes of leading whitespace that make up `columns` columns.
+fn indent_bytes(line: &str, columns: usize) -> usize {
+    let mut width = 0;
+    line.chars()
+        .take_while(|c| {
+            let take = width < columns && (*c == ' ' || *c == '\t');
+            width += if *c == '\t' { 4 } else { 1 };
+            take
+        })
+        .count()
+}
+
+/// A blockquote line's content, after `>` and one optional space.
+fn quote_content(line: Line) -> Option<Line> {
+    let rest = block_content(line.content)?.strip_prefix('>')?;
+    let marker = line.content.len() - rest.len();
+    Some(skip(line, marker + usize::from(rest.starts_with([' ', '\t']))))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ListMarker {
+    /// `-`, `*` or `+`; the `.` or `)` after an ordered item's number
+    symbol: char,
+    ordered: bool,
+    /// Bytes before the item's content on its first line
+    content_bytes: usize,
+    /// Column the item's continuation lines are indented to
+    content_indent: usize,
+}
+
+impl ListMarker {
+    /// Whether `other` starts another item of the same list.
+    fn same_list(&self, other: &ListMarker) -> bool {
+        self.symbol == other.symbol && self.ordered == other.ordered
+    }
+}
+
+fn list_marker(line: &str) -> Option<ListMarker> {
+    let content = block_content(line)?;
+    let (marker_len, ordered) = if is_bullet_item(content) {
+        (1, false)
+    } else if is_ordered_item(content) {
+        (content.chars().take_while(char::is_ascii_digit).count() + 1, true)
+    } else {
+        return None;
+    };
+    let lead = line.len() - content.len();
+    let after = &content[marker_len..];
+    let spaces = after.len() - after.trim_start_matches([' ', '\t']).len();
+    let (pad_bytes, pad_columns) = if after.trim().is_empty() {
+        // The item starts with a blank line
+        (spaces, 1)
+    } else if indent(after) > 4 {
+        // Indented code inside the item: one space belongs to the marker
+        (1, 1)
+    } else {
+        (spaces, indent(after))
+    };
+    Some(ListMarker {
+        symbol: content[marker_len - 1..].chars().next()?,
+        ordered,
+        content_bytes: lead + marker_len + pad_bytes,
+        content_indent: indent(line) + marker_len + pad_columns,
+    })
+}
+
+/// A content line with any quote and list markers in front removed.
+fn innermost(mut content: &str) -> &str {
+    loop {
+        if let Some(rest) = block_content(content).and_then(|c| c.strip_prefix('>')) {
+            content = rest;
+        } else if let Some(marker) = list_marker(content) {
+            content = &content[marker.content_bytes..];
+        } else {
+            return content;
+        }
+    }
+}
+
+/// Whether a line is paragraph text, which a line without its container's
+/// markers may continue (a lazy continuation line).
+fn is_paragraph_text(content: &str) -> bool {
+    let content = innermost(content);
+    !is_blank(content)
+        && block_content(content).is_some()
+        && atx_heading(content).is_none()
+        && fence_open(content).is_none()
+        && !is_math_fence(content)
+        && !is_thematic_break(content)
+        && !is_html_start(content)
+        && setext_level(content).is_none()
+}
+
+/// Whether a container's lines are inside a fenced code or math block.
+#[derive(Default)]
+struct FenceState(Option<Option<(char, usize)>>);
+
+impl FenceState {
+    fn is_open(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn update(&mut self, content: &str) {
+        self.0 = match self.0 {
+            Some(Some((marker, len))) if closes_fence(content, marker, len) => None,
+            Some(None) if is_math_fence(content) => None,
+            Some(open) => Some(open),
+            None => fence_open(content)
+                .map(|(marker, len, _)| Some((marker, len)))
+                .or_else(|| is_math_fence(content).then_some(None)),
+        };
+    }
+}
+
+/// Lists only interrupt a paragraph when they can't be mistaken for text,
+/// e.g. "2019. was a year" stays a paragraph.
+fn list_interrupts_paragraph(line: &str) -> bool {
+    block_content(line).is_some_and(|content| {
+        let non_empty = !content[1..].trim().is_empty();
+        (is_bullet_item(content) && non_empty)
+            || (is_ordered_item(content)
+                && (content.starts_with("1.") || content.starts_with("1)")))
+    })
+}
+
+fn is_html_start(line: &str) -> bool {
+    block_content(line).is_some_and(|content| {
+        let rest = content.strip_prefix('<').unwrap_or("");
+        let rest = rest.strip_prefix('/').unwrap_or(rest);
+        rest.starts_with(|c: char| c.is_ascii_alphabetic()) || rest.starts_with("!--")
+    })
+}
+
+fn is_table_delimiter(line: &str) -> bool {
+    let content = line.trim();
+    content.contains('-')
+        && content.contains(['|', ':'])
+        && content
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// Lines that end a paragraph without a blank line in between.
+fn interrupts_paragraph(line: &str) -> bool {
+    atx_heading(line).is_some()
+        || fence_open(line).is_some()
+        || is_math_fence(line)
+        || is_thematic_break(line)
+        || is_blockquote(line)
+        || is_html_start(line)
+        || list_interrupts_paragraph(line)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+fn block(kind: BlockKind, first: &Line, last: &Line) -> Block {
+    Block {
+        kind,
+        from: first.from,
+        to: last.to,
+        start_line: first.number,
+        end_line: last.number,
+        level: None,
+        text: None,
+        lang: None,
+        children: Vec::new(),
+        byte: first.byte,
+    }
+}
+
+/// Blocks inside a container, from its content lines.
+fn parse_container(lines: &[Line], depth: usize) -> Vec<Block> {
+    if depth >= MAX_DEPTH {
+        return Vec::new();
+    }
+    let mut cursor = ContainerLines {
+        lines,
+        pos: 0,
+        depth: depth + 1,
+    };
+    std::iter::from_fn(|| next_block(&mut cursor)).collect()
+}
+
+/// A blockquote from its first line: `>` lines, and lazy paragraph
+/// continuation lines without the marker.
+fn blockquote<'a>(cursor: &mut impl LineSource<'a>, first: Line<'a>) -> Block {
+    let mut content = vec![quote_content(first).unwrap_or(first)];
+    let mut fence = FenceState::default();
+    fence.update(content[0].content);
+    let mut last = first;
+    cursor.advance();
+    while let Some(line) = cursor.current() {
+        let next = match quote_content(line) {
+            Some(inner) => inner,
+            None if !fence.is_open()
+                && !is_blank(line.content)
+                && !interrupts_paragraph(line.content)
+                && content.last().is_some_and(|l| is_paragraph_text(l.content)) =>
+            {
+                line
+            }
+            None => break,
+        };
+        fence.update(next.content);
+        content.push(next);
+        last = line;
+        cursor.advance();
+    }
+    Block {
+        children: parse_container(&content, cursor.depth()),
+        ..block(BlockKind::Blockquote, &first, &last)
+    }
+}
+
+struct OpenItem<'a> {
+    first: Line<'a>,
+    last: Line<'a>,
+    content: Vec<Line<'a>>,
+    content_indent: usize,
+    fence: FenceState,
+}
+
+impl<'a> OpenItem<'a> {
+    fn new(line: Line<'a>, marker: &ListMarker) -> Self {
+        let content = skip(line, marker.content_bytes);
+        let mut fence = FenceState::default();
+        fence.update(content.content);
+        Self {
+            first: line,
+            last: line,
+            content: vec![content],
+            content_indent: marker.content_indent,
+            fence,
+        }
+    }
+
+    fn push(&mut self, line: Line<'a>, content: Line<'a>) {
+        self.fence.update(content.content);
+        self.content.push(content);
+        if !is_blank(line.content) {
+            self.last = line;
+        }
+    }
+}
+
+/// A list from its first item: items with the same kind of marker, and the
+/// lines indented to their content (or lazily continuing a paragraph).
+fn list<'a>(cursor: &mut impl LineSource<'a>, first: Line<'a>, marker: ListMarker) -> Block {
+    let mut items = vec![OpenItem::new(first, &marker)];
+    cursor.advance();
+    while let Some(line) = cursor.current() {
+        let item = items.last_mut().expect("a list has an item");
+        if is_blank(line.content) {
+            // A blank line only continues the list if more of the item or
+            // another item follows
+            let continues = cursor.peek_next_content().is_some_and(|next| {
+                indent(next.content) >= item.content_indent
+                    || list_marker(next.content).is_some_and(|m| {
+                        marker.same_list(&m) && !is_thematic_break(next.content)
+                    })
+            });
+            if !continues {
+                break;
+            }
+            item.push(line, skip(line, line.content.len()));
+            cursor.advance();
+            continue;
+        }
+        if indent(line.content) >= item.content_indent {
+            let content = skip(line, indent_bytes(line.content, item.content_indent));
+            item.push(line, content);
+            cursor.advance();
+            continue;
+        }
+        if let Some(next) = list_marker(line.content)
+            .filter(|m| marker.same_list(m) && !is_thematic_break(line.content))
+        {
+            items.push(OpenItem::new(line, &next));
+            cursor.advance();
+            continue;
+        }
+        let lazy = !item.fence.is_open()
+            && !is_list_item(line.content)
+            && !interrupts_paragraph(line.content)
+            && item.content.last().is_some_and(|l| is_paragraph_text(l.content));
+        if !lazy {
+            break;
+        }
+        item.push(line, skip(line, indent_bytes(line.content, usize::MAX)));
+        cursor.advance();
+    }
+
+    let depth = cursor.depth();
+    let last = items.last().map_or(first, |item| item.last);
+    let children = items
+        .iter()
+        .map(|item| {
+            // Trailing blank lines belong to the list, not the item
+            let end = item
+                .content
+                .iter()
+                .rposition(|l| !is_blank(l.content))
+                .map_or(0, |i| i + 1);
+            Block {
+                children: parse_container(&item.content[..end], depth),
+                ..block(BlockKind::ListItem, &item.first, &item.last)
+            }
+        })
+        .collect();
+    Block {
+        children,
+        ..block(BlockKind::List, &first, &last)
+    }
+}
+
+/// Consume lines until `ends` matches one (inclusive) or the text runs out.
+fn consume_until<'a>(
+    cursor: &mut impl LineSource<'a>,
+    first: Line<'a>,
+    ends: impl Fn(&str) -> bool,
+) -> Line<'a> {
+    let mut last = first;
+    cursor.advance();
+    while let Some(line) = cursor.current() {
+        last = line;
+        cursor.advance();
+        if ends(line.content) {
+            break;
+        }
+    }
+    last
+}
+
+/// Consume following lines while `continues` accepts them.
+fn consume_while<'a>(
+    cursor: &mut impl LineSource<'a>,
+    first: Line<'a>,
+    continues: impl Fn(&str) -> bool,
+) -> Line<'a> {
+    let mut last = first;
+    cursor.advance();
+    while let Some(line) = cursor.current() {
+        if !continues(line.content) {
+            break;
+        }
+        last = line;
+        cursor.advance();
+    }
+    last
+}
+
+/// Parse the next block, skipping blank lines before it.
+fn next_block<'a>(cursor: &mut impl LineSource<'a>) -> Option<Block> {
+    let first = loop {
+        let line = cursor.current()?;
+        if !is_blank(line.content) {
+            break line;
+        }
+        cursor.advance();
+    };
+    let content = first.content;
+
+    if first.number == 0 && cursor.depth() == 0 && content.trim_end() == "---" {
+        let mut probe = *cursor;
+        probe.advance();
+        let closed = std::iter::from_fn(|| {
+            let line = probe.current()?;
+            probe.advance();
+            Some(line)
+        })
+        .any(|line| matches!(line.content.trim_end(), "---" | "..."));
+        if closed {
+            let last = consume_until(cursor, first, |l| matches!(l.trim_end(), "---" | "..."));
+            return Some(block(BlockKind::FrontMatter, &first, &last));
+        }
+    }
+
+    if let Some((marker, len, info)) = fence_open(content) {
+        let lang = info.split_whitespace().next().map(str::to_string);
+        let last = consume_until(cursor, first, |l| closes_fence(l, marker, len));
+        return Some(Block {
+            lang,
+            ..block(BlockKind::CodeBlock, &first, &last)
+        });
+    }
+
+    if is_math_fence(content) {
+        let last = consume_until(cursor, first, is_math_fence);
+        return Some(block(BlockKind::MathBlock, &first, &last));
+    }
+
+    if let Some((level, text)) = atx_heading(content) {
+        cursor.advance();
+        return Some(Block {
+            level: Some(level),
+            text: Some(text),
+            ..block(BlockKind::Heading, &first, &first)
+        });
+    }
+
+    if is_thematic_break(content) {
+        cursor.advance();
+        return Some(block(BlockKind::ThematicBreak, &first, &first));
+    }
+
+    if is_html_start(content) {
+        let last = consume_while(cursor, first, |l| !is_blank(l));
+        return Some(block(BlockKind::Html, &first, &last));
+    }
+
+    if is_blockquote(content) {
+        return Some(blockquote(cursor, first));
+    }
+
+    if let Some(marker) = list_marker(content) {
+        return Some(list(cursor, first, marker));
+    }
+
+    if indent(content) >= 4 {
+        let mut last = first;
+        cursor.advance();
+        while let Some(line) = cursor.current() {
+            if is_blank(line.content) {
+                match cursor.peek_next_content() {
+                    Some(next) if indent(next.content) >= 4 => {
+                        cursor.advance();
+                        continue;
+                    }
+                    _ => break,
+                }
+            }
+            if indent(line.content) < 4 {
+                break;
+            }
+            last = line;
+            cursor.advance();
+        }
+        return Some(block(BlockKind::CodeBlock, &first, &last));
+    }
+
+    if content.contains('|')
+        && cursor
+            .peek_next()
+            .is_some_and(|next| is_table_delimiter(next.content))
+    {
+        let last = consume_while(cursor, first, |l| !is_blank(l) && l.contains('|'));
+        return Some(block(BlockKind::Table, &first, &last));
+    }
+
+    // Paragraph, possibly turned into a heading by a setext underline
+    let mut last = first;
+    let mut text = content.trim().to_string();
+    cursor.advance();
+    while let Some(line) = cursor.current() {
+        if is_blank(line.content) {
+            break;
+        }
+        if let Some(level) = setext_level(line.content) {
+            cursor.advance();
+            return Some(Block {
+                level: Some(level),
+                text: Some(text),
+                ..block(BlockKind::Heading, &first, &line)
+            });
+        }
+        if interrupts_paragraph(line.content) {
+            break;
+        }
+        text.push(' ');
+        text.push_str(line.content.trim());
+        last = line;
+        cursor.advance();
+    }
+    Some(block(BlockKind::Paragraph, &first, &last))
+}
+
+fn parse(text: &str) -> Vec<Block> {
+    let mut cursor = Cursor::new(text, 0, 0, 0);
+    std::iter::from_fn(|| next_block(&mut cursor)).collect()
+}
+
+/// The changed region of a batch in old-document offsets, and the length change.
+fn edit_span(edits: &[TextEdit]) -> Option<(usize, usize, isize)> {
+    let from = edits.iter().map(|e| e.from).min()?;
+    let to = edits.iter().map(|e| e.to).max()?;
+    let delta = edits
+        .iter()
+        .map(|e| e.insert.encode_utf16().count() as isize - (e.to - e.from) as isize)
+        .sum();
+    Some((from, to, delta))
+}
+
+fn shift(value: usize, delta: isize) -> usize {
+    value.checked_add_signed(delta).unwrap_or(0)
+}
+
+/// A reused block (and its children) moved by the edit before it.
+fn shifted(b: &Block, delta: isize, line_delta: isize, byte_delta: isize) -> Block {
+    Block {
+        from: shift(b.from, delta),
+        to: shift(b.to, delta),
+        start_line: shift(b.start_line, line_delta),
+        end_line: shift(b.end_line, line_delta),
+        children: b
+            .children
+            .iter()
+            .map(|child| shifted(child, delta, line_delta, byte_delta))
+            .collect(),
+        byte: shift(b.byte, byte_delta),
+        ..b.clone()
+    }
+}
+
+/// Re-parse after `edits`, reusing blocks outside the changed region.
+fn reparse(doc: &mut ParsedDocument, edits: &[TextEdit]) -> Result<(), String> {
+    let Some((edit_from, edit_to, delta)) = edit_span(edits) else {
+        return Ok(());
+    };
+    let old_len = doc.text.len();
+    apply_edits(&mut doc.text, edits)?;
+    let byte_delta = doc.text.len() as isize - old_len as isize;
+
+    // Restart one block early: the change may merge it with the next one.
+    // An unclosed `---` on the first line becomes front matter as soon as a
+    // closing line is typed anywhere, so that case always starts over.
+    let first_touched = doc.blocks.partition_point(|b| b.to < edit_from);
+    let unclosed_front_matter = doc.text.starts_with("---")
+        && doc
+            .blocks
+            .first()
+            .is_some_and(|b| b.kind == BlockKind::ThematicBreak && b.start_line == 0);
+    let restart = first_touched
+        .checked_sub(1)
+        .filter(|_| !unclosed_front_matter);
+    let mut cursor = match restart {
+        Some(i) => {
+            let b = &doc.blocks[i];
+            Cursor::new(&doc.text, b.byte, b.from, b.start_line)
+        }
+        None => Cursor::new(&doc.text, 0, 0, 0),
+    };
+    let restart = restart.unwrap_or(0);
+
+    let changed_end = shift(edit_to, delta);
+    let mut reparsed = Vec::new();
+    let mut resume = None;
+    while let Some(block) = next_block(&mut cursor) {
+        if block.from >= changed_end {
+            let old_from = shift(block.from, -delta);
+            if let Ok(i) = doc.blocks[restart..].binary_search_by_key(&old_from, |b| b.from) {
+                let i = restart + i;
+                if doc.blocks[i].from >= edit_to {
+                    resume = Some((
+                        i,
+                        block.start_line as isize - doc.blocks[i].start_line as isize,
+                    ));
+                    break;
+                }
+            }
+        }
+        reparsed.push(block);
+    }
+
+    let tail: Vec<Block> = match resume {
+        Some((i, line_delta)) => doc.blocks[i..]
+            .iter()
+            .map(|b| shifted(b, delta, line_delta, byte_delta))
+            .collect(),
+        None => Vec::new(),
+    };
+    doc.blocks.truncate(restart);
+    doc.blocks.extend(reparsed);
+    doc.blocks.extend(tail);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Queries
+// ---------------------------------------------------------------------------
+
+/// Headings whose sections contain `offset`, outermost first.
+fn enclosing_headings(blocks: &[Block], offset: usize) -> Vec<Block> {
+    let mut stack: Vec<&Block> = Vec::new();
+    for b in blocks.iter().take_while(|b| b.from <= offset) {
+        if let Some(level) = b.level {
+            stack.retain(|h| h.level.is_some_and(|l| l < level));
+            stack.push(b);
+        }
+    }
+    stack.into_iter().cloned().collect()
+}
+
+/// The block among `blocks` that contains `offset`.
+fn block_at(blocks: &[Block], offset: usize) -> Option<&Block> {
+    let i = blocks.partition_point(|b| b.to < offset);
+    blocks.get(i).filter(|b| b.from <= offset)
+}
+
+fn node_at(blocks: &[Block], offset: usize) -> NodeAt {
+    let block = block_at(blocks, offset);
+    let mut innermost = block;
+    while let Some(child) = innermost.and_then(|b| block_at(&b.children, offset)) {
+        innermost = Some(child);
+    }
+    NodeAt {
+        block: block.cloned(),
+        innermost: innermost.cloned(),
+        headings: enclosing_headings(blocks, offset),
+    }
+}
+
+/// Heading sections and multi-line blocks, including those inside
+/// containers, in document order.
+fn fold_ranges(blocks: &[Block]) -> Vec<FoldRange> {
+    let mut ranges = Vec::new();
+    collect_fold_ranges(blocks, &mut ranges);
+    // Stable, so a container comes before its first child
+    ranges.sort_by_key(|r| r.from);
+    ranges
+}
+
+fn collect_fold_ranges(blocks: &[Block], ranges: &mut Vec<FoldRange>) {
+    for (i, b) in blocks.iter().enumerate() {
+        let last = match b.level {
+            // A section runs until the next heading of the same or higher rank
+            Some(level) => blocks[i + 1..]
+                .iter()
+                .take_while(|next| next.level.is_none_or(|l| l > level))
+                .last(),
+            None => Some(b).filter(|b| b.end_line > b.start_line),
+        };
+        if let Some(last) = last {
+            ranges.push(FoldRange {
+                kind: b.kind,
+                from: b.from,
+                to: last.to,
+                start_line: b.start_line,
+                end_line: last.end_line,
+            });
+        }
+        collect_fold_ranges(&b.children, ranges);
+    }
+}
+
+/// Distinct code fence languages in order of first use.
+fn fence_languages(blocks: &[Block]) -> Vec<String> {
+    fn collect(blocks: &[Block], langs: &mut Vec<String>) {
+        for b in blocks {
+            if let Some(lang) = b.lang.as_ref().filter(|lang| !langs.contains(lang)) {
+                langs.push(lang.clone());
+            }
+            collect(&b.children, langs);
+        }
+    }
+    let mut langs = Vec::new();
+    collect(blocks, &mut langs);
+    langs
+}
+
+fn with_document<T>(doc_id: &str, f: impl FnOnce(&ParsedDocument) -> T) -> Result<T, String> {
+    let docs = documents().lock().unwrap_or_else(|e| e.into_inner());
+    docs.get(doc_id)
+        .map(f)
+        .ok_or_else(|| format!("No parsed document for {doc_id}"))
+}
+
+// ---------------------------------------------------------------------------
+// Commands
+// ---------------------------------------------------------------------------
+
+/// Parse a document and keep it for queries, replacing any earlier copy.
+/// Returns the number of blocks.
+#[tauri::command]
+pub async fn parse_document(doc_id: String, content: String) -> Result<usize, String> {
+    let blocks = tauri::async_runtime::spawn_blocking(move || {
+        let blocks = parse(&content);
+        let count = blocks.len();
+        documents()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                doc_id,
+                ParsedDocument {
+                    text: content,
+                    blocks,
+                },
+            );
+        count
+    })
+    .await
+    .map_err(|e| format!("Parse failed: {e}"))?;
+    Ok(blocks)
+}
+
+/// Apply editor changes (offsets in the document before the batch).
+/// On error the copy is dropped; the caller should parse the document again.
+/// Batches must be sent one at a time, each after the last has finished.
+#[tauri::command]
+pub async fn edit_parsed_document(doc_id: String, edits: Vec<TextEdit>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut docs = documents().lock().unwrap_or_else(|e| e.into_inner());
+        let doc = docs
+            .get_mut(&doc_id)
+            .ok_or_else(|| format!("No parsed document for {doc_id}"))?;
+        if let Err(e) = reparse(doc, &edits) {
+            docs.remove(&doc_id);
+            return Err(e);
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Edit failed: {e}"))?
+}
+
+/// Blocks overlapping `from..=to` (all blocks when omitted).
+#[tauri::command]
+pub fn get_document_blocks(
+    doc_id: String,
+    from: Option<usize>,
+    to: Option<usize>,
+) -> Result<Vec<Block>, String> {
+    let from = from.unwrap_or(0);
+    let to = to.unwrap_or(usize::MAX);
+    with_document(&doc_id, |doc| {
+        doc.blocks
+            .iter()
+            .filter(|b| b.to >= from && b.from <= to)
+            .cloned()
+            .collect()
+    })
+}
+
+/// Headings in document order.
+#[tauri::command]
+pub fn get_document_outline(doc_id: String) -> Result<Vec<Block>, String> {
+    with_document(&doc_id, |doc| {
+        doc.blocks
+            .iter()
+            .filter(|b| b.kind == BlockKind::Heading)
+            .cloned()
+            .collect()
+    })
+}
+
+/// The blocks at `offset` and the headings whose sections contain it.
+#[tauri::command]
+pub fn get_node_at_offset(doc_id: String, offset: usize) -> Result<NodeAt, String> {
+    with_document(&doc_id, |doc| node_at(&doc.blocks, offset))
+}
+
+#[tauri::command]
+pub fn get_fold_ranges(doc_id: String) -> Result<Vec<FoldRange>, String> {
+    with_document(&doc_id, |doc| fold_ranges(&doc.blocks))
+}
+
+#[tauri::command]
+pub fn get_code_fence_languages(doc_id: String) -> Result<Vec<String>, String> {
+    with_document(&doc_id, |doc| fence_languages(&doc.blocks))
+}
+
+/// Forget a parsed document.
+#[tauri::command]
+pub fn release_parsed_document(doc_id: String) {
+    documents()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&doc_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<BlockKind> {
+        parse(text).iter().map(|b| b.kind).collect()
+    }
+
+    const SAMPLE: &str = "---\ntitle: Doc\n---\n\n# Intro\n\nSome text\nmore text\n\n## Code\n\n```rust\nfn main() {}\n\n```\n\n- one\n- two\n\n  nested\n\n> quote\nlazy\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n$$\nx^2\n$$\n\nSetext\n======\n\n***\n\n<div>\nhtml\n</div>\n\n    indented\n";
+
+    #[test]
+    fn parses_block_kinds() {
+        use BlockKind::*;
+        assert_eq!(
+            kinds(SAMPLE),
+            vec![
+                FrontMatter,
+                Heading,
+                Paragraph,
+                Heading,
+                CodeBlock,
+                List,
+                Blockquote,
+                Table,
+                MathBlock,
+                Heading,
+                ThematicBreak,
+                Html,
+                CodeBlock
+            ]
+        );
+    }
+
+    #[test]
+    fn records_positions_and_details() {
+        let blocks = parse(SAMPLE);
+        let intro = &blocks[1];
+        assert_eq!(
+            (intro.level, intro.text.as_deref()),
+            (Some(1), Some("Intro"))
+        );
+        assert_eq!((intro.start_line, intro.end_line), (4, 4));
+        assert_eq!(&SAMPLE[intro.byte..intro.byte + 7], "# Intro");
+
+        let para = &blocks[2];
+        assert_eq!((para.start_line, para.end_line), (6, 7));
+        assert_eq!(para.to - para.from, "Some text\nmore text".len());
+
+        assert_eq!(blocks[4].lang.as_deref(), Some("rust"));
+        assert_eq!(blocks[4].end_line, 14, "blank line inside the fence");
+        assert_eq!(
+            blocks[5].end_line, 19,
+            "indented content continues the list"
+        );
+        assert_eq!(
+            (blocks[9].level, blocks[9].text.as_deref()),
+            (Some(1), Some("Setext"))
+        );
+    }
+
+    #[test]
+    fn paragraph_interruptions() {
+        use BlockKind::*;
+        assert_eq!(kinds("text\n# h"), vec![Paragraph, Heading]);
+        assert_eq!(kinds("text\n- item"), vec![Paragraph, List]);
+        assert_eq!(kinds("text\n2019. was"), vec![Paragraph]);
+        assert_eq!(kinds("text\n---"), vec![Heading], "setext, not a break");
+        assert_eq!(kinds("#hashtag"), vec![Paragraph]);
+        assert_eq!(kinds("---\nno close"), vec![ThematicBreak, Paragraph]);
+    }
+
+    fn tree(blocks: &[Block]) -> String {
+        blocks
+            .iter()
+            .map(|b| {
+                let kind = format!("{:?}", b.kind);
+                if b.children.is_empty() {
+                    kind
+                } else {
+                    format!("{kind}[{}]", tree(&b.children))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn containers_have_children() {
+        assert_eq!(
+            tree(&parse("> # Title\n> - a\n>   ```\n>   code\n>   ```\n")),
+            "Blockquote[Heading List[ListItem[Paragraph CodeBlock]]]"
+        );
+        assert_eq!(
+            tree(&parse("1. one\n\n   > quote\n2. two\n")),
+            "List[ListItem[Paragraph Blockquote[Paragraph]] ListItem[Paragraph]]"
+        );
+        assert_eq!(
+            tree(&parse("- a\n  - b\n")),
+            "List[ListItem[Paragraph List[ListItem[Paragraph]]]]"
+        );
+
+        let blocks = parse("- item\n\n  ```js\n  x\n\n  y\n  ```\n");
+        let code = &blocks[0].children[0].children[1];
+        assert_eq!((code.kind, code.lang.as_deref()), (BlockKind::CodeBlock, Some("js")));
+        assert_eq!((code.start_line, code.end_line), (2, 6));
+        assert_eq!((code.from, code.to), (10, 30));
+    }
+
+    #[test]
+    fn container_continuation() {
+        use BlockKind::*;
+        // Lazy lines only continue a paragraph
+        assert_eq!(kinds("> text\nlazy"), vec![Blockquote]);
+        assert_eq!(kinds("> ```\n> code\nafter"), vec![Blockquote, Paragraph]);
+        assert_eq!(kinds("> # h\nafter"), vec![Blockquote, Paragraph]);
+        assert_eq!(kinds("- text\nlazy"), vec![List]);
+        // Content must reach the item's content column
+        assert_eq!(kinds("10. item\n\n  not in item"), vec![List, Paragraph]);
+        assert_eq!(kinds("10. item\n\n    in item"), vec![List]);
+        // Another marker starts another list
+        assert_eq!(kinds("- a\n+ b"), vec![List, List]);
+        assert_eq!(kinds("- a\n* * *"), vec![List, ThematicBreak]);
+        // No front matter inside a container
+        assert_eq!(tree(&parse("> ---\n> x\n> ---")), "Blockquote[ThematicBreak Heading]");
+    }
+
+    #[test]
+    fn deep_nesting_is_bounded() {
+        let text = ">".repeat(10_000) + " deep";
+        let blocks = parse(&text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(node_at(&blocks, 0).innermost.unwrap().kind, BlockKind::Blockquote);
+    }
+
+    #[test]
+    fn heading_text_drops_closing_hashes() {
+        assert_eq!(atx_heading("## Title ##"), Some((2, "Title".to_string())));
+        assert_eq!(atx_heading("# C#"), Some((1, "C#".to_string())));
+        assert_eq!(atx_heading("#"), Some((1, String::new())));
+    }
+
+    #[test]
+    fn utf16_offsets() {
+        let blocks = parse("😀\n\n# é");
+        assert_eq!((blocks[0].from, blocks[0].to), (0, 2));
+        assert_eq!((blocks[1].from, blocks[1].to), (4, 7));
+    }
+
+    fn edit(from: usize, to: usize, insert: &str) -> TextEdit {
+        TextEdit {
+            from,
+            to,
+            insert: insert.to_string(),
+        }
+    }
+
+    /// Apply edits incrementally and compare with a parse from scratch.
+    fn check_incremental(text: &str, edits: &[TextEdit]) {
+        let mut doc = ParsedDocument {
+            text: text.to_string(),
+            blocks: parse(text),
+        };
+        reparse(&mut doc, edits).unwrap();
+        assert_eq!(
+            doc.blocks,
+            parse(&doc.text),
+            "after editing {text:?} with {edits:?}"
+        );
+    }
+
+    #[test]
+    fn incremental_edits_match_full_parse() {
+        let offset = |needle: &str| {
+            SAMPLE.encode_utf16().count()
+                - SAMPLE[SAMPLE.find(needle).unwrap()..]
+                    .encode_utf16()
+                    .count()
+        };
+        let code = offset("```rust");
+        let quote = offset("> quote");
+        let intro = offset("# Intro");
+        let cases = vec![
+            vec![edit(intro, intro, "#")],
+            vec![edit(intro, intro + 2, "")],
+            vec![edit(code, code, "x")],
+            vec![edit(code + 3, code + 7, "python")],
+            vec![edit(quote - 1, quote, "")],
+            vec![edit(quote, quote + 7, "plain")],
+            vec![edit(0, 3, "")],
+            vec![edit(intro, intro, "text\n")],
+            vec![edit(intro, intro, "😀 "), edit(quote, quote, "\n\n")],
+        ];
+        let end = SAMPLE.encode_utf16().count();
+        let cases = cases
+            .into_iter()
+            .chain([vec![edit(end, end, "\n```\nopen fence")]]);
+        for edits in cases {
+            check_incremental(SAMPLE, &edits);
+        }
+
+        // Inside and around containers
+        let list = "- a\n- b\n\n  ```\n  code\n  ```\n\n> q\n> - x\n\nafter\n";
+        check_incremental(list, &[edit(13, 13, "x")]);
+        check_incremental(list, &[edit(9, 11, "")]);
+        check_incremental(list, &[edit(33, 35, "")]);
+        check_incremental(list, &[edit(42, 43, "")]);
+        check_incremental(list, &[edit(0, 2, "1. ")]);
+
+        // Leading blank lines, and front matter closed further down
+        check_incremental("\n\n# A\n", &[edit(0, 0, "text")]);
+        check_incremental("---\ntitle\n\n# A\n", &[edit(10, 10, "---\n")]);
+    }
+
+    #[test]
+    fn node_at_offset_and_sections() {
+        let text = "# A\n\n## B\n\ntext\n\n# C\n";
+        let blocks = parse(text);
+        let node = node_at(&blocks, 12);
+        assert_eq!(node.block.unwrap().kind, BlockKind::Paragraph);
+        let names: Vec<_> = node
+            .headings
+            .iter()
+            .filter_map(|h| h.text.as_deref())
+            .collect();
+        assert_eq!(names, vec!["A", "B"]);
+
+        let nested = parse("> - item\n");
+        let node = node_at(&nested, 5);
+        assert_eq!(node.block.unwrap().kind, BlockKind::Blockquote);
+        assert_eq!(node.innermost.unwrap().kind, BlockKind::Paragraph);
+
+        let between = node_at(&blocks, 4);
+        assert!(between.block.is_none());
+        assert_eq!(node_at(&blocks, 18).headings.len(), 1);
+    }
+
+    #[test]
+    fn fold_ranges_cover_sections() {
+        let text = "# A\n\n## B\n\ntext\n\n# C\n\n```\nx\n```";
+        let ranges = fold_ranges(&parse(text));
+        let spans: Vec<_> = ranges
+            .iter()
+            .map(|r| (r.kind, r.start_line, r.end_line))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (BlockKind::Heading, 0, 4),
+                (BlockKind::Heading, 2, 4),
+                (BlockKind::Heading, 6, 10),
+                (BlockKind::CodeBlock, 8, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn languages_are_distinct() {
+        let text = "```js\na\n```\n\n- ```py\n  b\n  ```\n\n```js\nc\n```";
+        assert_eq!(fence_languages(&parse(text)), vec!["js", "py"]);
+    }
+}
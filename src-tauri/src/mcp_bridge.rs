@@ -5,8 +5,14 @@
 //! - Read operations: All clients can execute simultaneously
 //! - Write operations: Serialized via write lock, released after each write
 //!
+//! Backpressure:
+//! - In-flight requests are capped (see `DEFAULT_MAX_PENDING_REQUESTS`)
+//! - Requests over the cap are rejected immediately with a "busy" error
+//! - Queue depth, latency and timeout counters are exposed via `mcp_bridge_stats`
+//!
 //! Port discovery:
-//! - Server binds to port 0 (OS assigns available port)
+//! - Server tries the preferred port, then the next `PORT_SEARCH_RANGE` ports in order
+//! - If all are taken (or no port is preferred), binds to port 0 (OS assigns one)
 //! - Actual port written to Tauri's app data directory (platform-specific)
 //! - MCP sidecar reads app data path from ~/.vmark/app-data-path bootstrap file
 
@@ -118,7 +124,6 @@ struct ClientIdentity {
 
 impl ClientIdentity {
     /// Get display name for logging (debug only).
-    #[cfg(debug_assertions)]
     fn display_name(&self) -> String {
         if let Some(ref version) = self.version {
             format!("{} v{}", self.name, version)
@@ -142,6 +147,12 @@ struct ClientConnection {
     identity: Option<ClientIdentity>,
 }
 
+/// Default cap on in-flight requests (waiting for the write lock or the frontend).
+const DEFAULT_MAX_PENDING_REQUESTS: usize = 64;
+
+/// How long to wait for the frontend to answer a request.
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// Bridge state shared across connections.
 struct BridgeState {
     /// All connected clients (equal access for reads).
@@ -150,6 +161,12 @@ struct BridgeState {
     pending: HashMap<String, PendingRequest>,
     /// Counter for generating unique client IDs.
     next_client_id: u64,
+    /// Requests admitted but not yet answered (includes those waiting on the write lock).
+    in_flight: usize,
+    /// Maximum number of in-flight requests before new ones are rejected.
+    max_pending: usize,
+    /// Cumulative request metrics since the bridge last started.
+    stats: BridgeStats,
 }
 
 /// Pending request with client ID for routing response.
@@ -159,6 +176,88 @@ struct PendingRequest {
     client_id: u64,
 }
 
+/// Cumulative request counters.
+#[derive(Clone, Debug, Default)]
+struct BridgeStats {
+    total_requests: u64,
+    completed: u64,
+    failed: u64,
+    timeouts: u64,
+    rejected: u64,
+    total_latency_ms: u64,
+    max_latency_ms: u64,
+    total_queue_depth: u64,
+    peak_queue_depth: usize,
+}
+
+impl BridgeStats {
+    /// Record a request being admitted at the given queue depth.
+    fn record_admitted(&mut self, queue_depth: usize) {
+        self.total_requests += 1;
+        self.total_queue_depth += queue_depth as u64;
+        self.peak_queue_depth = self.peak_queue_depth.max(queue_depth);
+    }
+
+    /// Record the outcome of an admitted request.
+    fn record_outcome(&mut self, outcome: RequestOutcome, latency_ms: u64) {
+        match outcome {
+            RequestOutcome::Succeeded => self.completed += 1,
+            RequestOutcome::Failed => self.failed += 1,
+            RequestOutcome::TimedOut => self.timeouts += 1,
+        }
+        self.total_latency_ms += latency_ms;
+        self.max_latency_ms = self.max_latency_ms.max(latency_ms);
+    }
+
+    /// Number of requests that have finished (any outcome).
+    fn finished(&self) -> u64 {
+        self.completed + self.failed + self.timeouts
+    }
+
+    fn average_latency_ms(&self) -> f64 {
+        match self.finished() {
+            0 => 0.0,
+            n => self.total_latency_ms as f64 / n as f64,
+        }
+    }
+
+    fn average_queue_depth(&self) -> f64 {
+        match self.total_requests {
+            0 => 0.0,
+            n => self.total_queue_depth as f64 / n as f64,
+        }
+    }
+}
+
+/// How an admitted request finished.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RequestOutcome {
+    /// Frontend answered with success.
+    Succeeded,
+    /// Frontend answered with an error, or the response channel closed.
+    Failed,
+    /// Frontend did not answer within `REQUEST_TIMEOUT`.
+    TimedOut,
+}
+
+/// Bridge statistics snapshot for the frontend.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpBridgeStats {
+    pub clients: usize,
+    pub pending: usize,
+    pub max_pending: usize,
+    pub peak_queue_depth: usize,
+    pub average_queue_depth: f64,
+    pub total_requests: u64,
+    pub completed: u64,
+    pub failed: u64,
+    pub timeouts: u64,
+    pub rejected: u64,
+    pub average_latency_ms: f64,
+    pub max_latency_ms: u64,
+}
+
 /// Global bridge state.
 static BRIDGE_STATE: std::sync::OnceLock<Arc<Mutex<BridgeState>>> = std::sync::OnceLock::new();
 
@@ -177,6 +276,9 @@ fn get_bridge_state() -> Arc<Mutex<BridgeState>> {
                 clients: HashMap::new(),
                 pending: HashMap::new(),
                 next_client_id: 1,
+                in_flight: 0,
+                max_pending: DEFAULT_MAX_PENDING_REQUESTS,
+                stats: BridgeStats::default(),
             }))
         })
         .clone()
@@ -212,8 +314,7 @@ fn write_port_file(app: &AppHandle, port: u16) -> Result<(), String> {
     // Write port atomically to prevent partial reads
     app_paths::atomic_write_file(&path, port.to_string().as_bytes())?;
 
-    #[cfg(debug_assertions)]
-    eprintln!("[MCP Bridge] Port {} written to {:?}", port, path);
+    tracing::debug!("Port {} written to {:?}", port, path);
 
     Ok(())
 }
@@ -225,23 +326,22 @@ fn remove_port_file(app: &AppHandle) {
         Ok(path) => {
             match fs::remove_file(&path) {
                 Ok(()) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[MCP Bridge] Port file removed: {:?}", path);
+                    tracing::debug!("Port file removed: {:?}", path);
                 }
                 Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                     // Already removed - not an error
                 }
                 Err(e) => {
                     // Real error - log it
-                    eprintln!(
-                        "[MCP Bridge] Warning: Failed to remove port file {:?}: {}",
+                    tracing::warn!(
+                        "Failed to remove port file {:?}: {}",
                         path, e
                     );
                 }
             }
         }
         Err(e) => {
-            eprintln!("[MCP Bridge] Warning: Cannot determine port file path: {}", e);
+            tracing::warn!("Cannot determine port file path: {}", e);
         }
     }
 }
@@ -286,15 +386,44 @@ fn is_read_only_operation(request_type: &str) -> bool {
     )
 }
 
+/// Number of ports after the preferred one to try before letting the OS pick.
+const PORT_SEARCH_RANGE: u16 = 20;
+
+/// Ports to try, in order, for a preferred port.
+///
+/// The search is deterministic (preferred, preferred+1, ...) so the bridge lands
+/// on the same port across restarts when possible. Port 0 is always the last resort.
+fn candidate_ports(preferred: u16) -> Vec<u16> {
+    let mut ports = Vec::new();
+    if preferred != 0 {
+        let last = preferred.saturating_add(PORT_SEARCH_RANGE);
+        ports.extend(preferred..=last);
+    }
+    ports.push(0);
+    ports
+}
+
+/// Bind the first available port from the candidate list.
+async fn bind_listener(preferred: u16) -> Result<TcpListener, String> {
+    let mut last_error = String::new();
+    for port in candidate_ports(preferred) {
+        let addr = format!("127.0.0.1:{}", port);
+        match TcpListener::bind(&addr).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) => {
+                tracing::debug!("Port {} unavailable: {}", port, e);
+                last_error = format!("Failed to bind to {}: {}", addr, e);
+            }
+        }
+    }
+    Err(last_error)
+}
+
 /// Start the MCP bridge WebSocket server.
-/// Returns the actual port the server is listening on.
-pub async fn start_bridge(app: AppHandle, _port: u16) -> Result<u16, String> {
-    // Always bind to port 0 to let OS assign an available port
-    // This eliminates port conflicts entirely
-    let addr = "127.0.0.1:0";
-    let listener = TcpListener::bind(addr)
-        .await
-        .map_err(|e| format!("Failed to bind to {}: {}", addr, e))?;
+/// Returns the actual port the server is listening on, which may differ from
+/// `preferred_port` if it was taken.
+pub async fn start_bridge(app: AppHandle, preferred_port: u16) -> Result<u16, String> {
+    let listener = bind_listener(preferred_port).await?;
 
     // Get the actual port assigned by the OS
     let actual_port = listener
@@ -305,12 +434,25 @@ pub async fn start_bridge(app: AppHandle, _port: u16) -> Result<u16, String> {
     // Write port to file for MCP sidecar discovery
     write_port_file(&app, actual_port)?;
 
-    #[cfg(debug_assertions)]
-    eprintln!(
-        "[MCP Bridge] WebSocket server listening on 127.0.0.1:{}",
+    if preferred_port != 0 && actual_port != preferred_port {
+        tracing::debug!(
+            "Preferred port {} in use, fell back to {}",
+            preferred_port, actual_port
+        );
+    }
+
+    tracing::debug!(
+        "WebSocket server listening on 127.0.0.1:{}",
         actual_port
     );
 
+    // Fresh counters for each bridge session
+    {
+        let state = get_bridge_state();
+        let mut guard = state.lock().await;
+        guard.stats = BridgeStats::default();
+    }
+
     let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
     {
         let holder = get_shutdown_holder();
@@ -324,8 +466,7 @@ pub async fn start_bridge(app: AppHandle, _port: u16) -> Result<u16, String> {
         loop {
             tokio::select! {
                 _ = &mut shutdown_rx => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[MCP Bridge] Shutdown signal received");
+                    tracing::debug!("Shutdown signal received");
                     break;
                 }
                 result = listener.accept() => {
@@ -334,9 +475,8 @@ pub async fn start_bridge(app: AppHandle, _port: u16) -> Result<u16, String> {
                             let app = app_handle.clone();
                             tauri::async_runtime::spawn(handle_connection(stream, addr, app));
                         }
-                        Err(_e) => {
-                            #[cfg(debug_assertions)]
-                            eprintln!("[MCP Bridge] Accept error: {}", _e);
+                        Err(e) => {
+                            tracing::debug!("Accept error: {}", e);
                         }
                     }
                 }
@@ -385,9 +525,8 @@ pub async fn stop_bridge(app: &AppHandle) {
 async fn handle_connection(stream: TcpStream, addr: SocketAddr, app: AppHandle) {
     let ws_stream = match accept_async(stream).await {
         Ok(ws) => ws,
-        Err(_e) => {
-            #[cfg(debug_assertions)]
-            eprintln!("[MCP Bridge] WebSocket handshake failed for {}: {}", addr, _e);
+        Err(e) => {
+            tracing::debug!("WebSocket handshake failed for {}: {}", addr, e);
             return;
         }
     };
@@ -421,8 +560,7 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, app: AppHandle)
         client_id
     };
 
-    #[cfg(debug_assertions)]
-    eprintln!("[MCP Bridge] Client {} connected from {}", client_id, addr);
+    tracing::debug!("Client {} connected from {}", client_id, addr);
 
     // Send welcome notification to client
     let welcome_msg = WsMessage {
@@ -450,31 +588,26 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, app: AppHandle)
     loop {
         tokio::select! {
             _ = &mut shutdown_rx => {
-                #[cfg(debug_assertions)]
-                eprintln!("[MCP Bridge] Client {} closing due to shutdown", client_id);
+                tracing::debug!("Client {} closing due to shutdown", client_id);
                 break;
             }
             result = ws_receiver.next() => {
                 match result {
                     Some(Ok(Message::Text(text))) => {
-                        if let Err(_e) = handle_message(&text, client_id, &app).await {
-                            #[cfg(debug_assertions)]
-                            eprintln!("[MCP Bridge] Error handling message from client {}: {}", client_id, _e);
+                        if let Err(e) = handle_message(&text, client_id, &app).await {
+                            tracing::debug!("Error handling message from client {}: {}", client_id, e);
                         }
                     }
                     Some(Ok(Message::Close(_))) => {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[MCP Bridge] Client {} disconnected", client_id);
+                        tracing::debug!("Client {} disconnected", client_id);
                         break;
                     }
-                    Some(Err(_e)) => {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[MCP Bridge] WebSocket error from client {}: {}", client_id, _e);
+                    Some(Err(e)) => {
+                        tracing::debug!("WebSocket error from client {}: {}", client_id, e);
                         break;
                     }
                     None => {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[MCP Bridge] Client {} stream ended", client_id);
+                        tracing::debug!("Client {} stream ended", client_id);
                         break;
                     }
                     _ => {}
@@ -488,16 +621,15 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, app: AppHandle)
         let state = get_bridge_state();
         let mut guard = state.lock().await;
 
-        if let Some(_client) = guard.clients.remove(&client_id) {
-            #[cfg(debug_assertions)]
+        if let Some(client) = guard.clients.remove(&client_id) {
             {
-                let name = _client
+                let name = client
                     .identity
                     .as_ref()
                     .map(|i| i.display_name())
                     .unwrap_or_else(|| format!("Client {}", client_id));
-                eprintln!(
-                    "[MCP Bridge] {} disconnected. Remaining clients: {}",
+                tracing::debug!(
+                    "{} disconnected. Remaining clients: {}",
                     name,
                     guard.clients.len()
                 );
@@ -510,12 +642,6 @@ async fn handle_connection(stream: TcpStream, addr: SocketAddr, app: AppHandle)
 
 /// Handle an incoming WebSocket message.
 async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(), String> {
-    // Debug: Log raw WebSocket message to trace markdown escaping
-    #[cfg(debug_assertions)]
-    if text.contains("insert") {
-        eprintln!("[MCP Bridge DEBUG] Raw WebSocket message: {}", text);
-    }
-
     let msg: WsMessage =
         serde_json::from_str(text).map_err(|e| format!("Invalid message format: {}", e))?;
 
@@ -526,9 +652,8 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
             let mut guard = state.lock().await;
 
             if let Some(client) = guard.clients.get_mut(&client_id) {
-                #[cfg(debug_assertions)]
-                eprintln!(
-                    "[MCP Bridge] Client {} identified as {}",
+                tracing::debug!(
+                    "Client {} identified as {}",
                     client_id,
                     identity.display_name()
                 );
@@ -544,11 +669,13 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
 
     let request = McpRequest::from_value(msg.payload.clone())?;
 
-    // Debug: Log request args to trace markdown escaping issues
-    #[cfg(debug_assertions)]
+    // Edit arguments carry document text, so only their size is logged
     if request.request_type.starts_with("document.insert") || request.request_type == "selection.replace" {
-        eprintln!("[MCP Bridge DEBUG] Request type: {}", request.request_type);
-        eprintln!("[MCP Bridge DEBUG] Args: {}", serde_json::to_string_pretty(&request.args).unwrap_or_default());
+        tracing::debug!(
+            "Request {} with {} bytes of args",
+            request.request_type,
+            request.args.to_string().len()
+        );
     }
 
     let is_read = is_read_only_operation(&request.request_type);
@@ -562,15 +689,82 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
 
     let client_tx = client_tx.ok_or("Client not found")?;
 
+    // Admission control: reject immediately when too many requests are in flight
+    let admitted = {
+        let state = get_bridge_state();
+        let mut guard = state.lock().await;
+        if guard.in_flight >= guard.max_pending {
+            guard.stats.rejected += 1;
+            None
+        } else {
+            guard.in_flight += 1;
+            let depth = guard.in_flight;
+            guard.stats.record_admitted(depth);
+            Some(depth)
+        }
+    };
+
+    let Some(_queue_depth) = admitted else {
+        tracing::debug!(
+            "Client {} request {} rejected - bridge busy",
+            client_id, request.request_type
+        );
+        send_error_response(&client_tx, &msg.id, "Bridge busy: too many pending requests");
+        return Ok(());
+    };
+
+    let queued_at = Instant::now();
+    let (outcome, result) =
+        dispatch_request(request, is_read, msg.id, client_id, &client_tx, app).await;
+
+    // Record metrics and release the in-flight slot
+    {
+        let latency_ms = queued_at.elapsed().as_millis() as u64;
+        let state = get_bridge_state();
+        let mut guard = state.lock().await;
+        guard.in_flight = guard.in_flight.saturating_sub(1);
+        guard.stats.record_outcome(outcome, latency_ms);
+    }
+
+    result
+}
+
+/// Send an error response for a request back to the sidecar.
+fn send_error_response(client_tx: &mpsc::UnboundedSender<String>, request_id: &str, error: &str) {
+    let error_response = McpResponse {
+        success: false,
+        data: None,
+        error: Some(error.to_string()),
+    };
+    let ws_response = WsMessage {
+        id: request_id.to_string(),
+        msg_type: "response".to_string(),
+        payload: serde_json::to_value(&error_response).unwrap_or_default(),
+    };
+    if let Ok(json) = serde_json::to_string(&ws_response) {
+        let _ = client_tx.send(json);
+    }
+}
+
+/// Forward an admitted request to the frontend and relay its response.
+///
+/// Returns the outcome for metrics alongside the result for the caller.
+async fn dispatch_request(
+    request: McpRequest,
+    is_read: bool,
+    request_id: String,
+    client_id: u64,
+    client_tx: &mpsc::UnboundedSender<String>,
+    app: &AppHandle,
+) -> (RequestOutcome, Result<(), String>) {
     // For write operations, acquire the write lock
     // This serializes writes while allowing concurrent reads
     let write_lock = get_write_lock();
     let _write_guard = if is_read {
         None
     } else {
-        #[cfg(debug_assertions)]
-        eprintln!(
-            "[MCP Bridge] Client {} acquiring write lock for {}",
+        tracing::debug!(
+            "Client {} acquiring write lock for {}",
             client_id, request.request_type
         );
         Some(write_lock.lock().await)
@@ -579,8 +773,6 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
     // Create a oneshot channel for the response
     let (response_tx, response_rx) = oneshot::channel();
 
-    let request_id = msg.id.clone();
-    #[cfg(debug_assertions)]
     let request_type_for_log = request.request_type.clone();
 
     // Store the pending request
@@ -611,18 +803,19 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
         let state = get_bridge_state();
         let mut guard = state.lock().await;
         guard.pending.remove(&request_id);
-        return Err(format!("Failed to emit event: {}", e));
+        return (
+            RequestOutcome::Failed,
+            Err(format!("Failed to emit event: {}", e)),
+        );
     }
 
-    #[cfg(debug_assertions)]
-    eprintln!(
-        "[MCP Bridge] Emitted mcp-bridge:request for {} (id: {})",
+    tracing::debug!(
+        "Emitted mcp-bridge:request for {} (id: {})",
         request.request_type, request_id
     );
 
-    // Wait for response with timeout (10 seconds - operations should be fast)
-    let response = match tokio::time::timeout(std::time::Duration::from_secs(10), response_rx).await
-    {
+    // Wait for response with timeout (operations should be fast)
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, response_rx).await {
         Ok(Ok(response)) => response,
         Ok(Err(_)) => {
             // Channel closed - clean up and send error to sidecar
@@ -631,20 +824,8 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
             guard.pending.remove(&request_id);
             drop(guard);
 
-            let error_response = McpResponse {
-                success: false,
-                data: None,
-                error: Some("Response channel closed".to_string()),
-            };
-            let ws_response = WsMessage {
-                id: msg.id.clone(),
-                msg_type: "response".to_string(),
-                payload: serde_json::to_value(&error_response).unwrap_or_default(),
-            };
-            if let Ok(json) = serde_json::to_string(&ws_response) {
-                let _ = client_tx.send(json);
-            }
-            return Ok(());
+            send_error_response(client_tx, &request_id, "Response channel closed");
+            return (RequestOutcome::Failed, Ok(()));
         }
         Err(_) => {
             // Timeout - clean up and send error to sidecar
@@ -653,54 +834,50 @@ async fn handle_message(text: &str, client_id: u64, app: &AppHandle) -> Result<(
             guard.pending.remove(&request_id);
             drop(guard);
 
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "[MCP Bridge] Client {} request {} timed out after 10s",
-                client_id, request_type_for_log
+            tracing::debug!(
+                "Client {} request {} timed out after {}s",
+                client_id,
+                request_type_for_log,
+                REQUEST_TIMEOUT.as_secs()
             );
 
-            let error_response = McpResponse {
-                success: false,
-                data: None,
-                error: Some("Request timeout after 10s".to_string()),
-            };
-            let ws_response = WsMessage {
-                id: msg.id.clone(),
-                msg_type: "response".to_string(),
-                payload: serde_json::to_value(&error_response).unwrap_or_default(),
-            };
-            if let Ok(json) = serde_json::to_string(&ws_response) {
-                let _ = client_tx.send(json);
-            }
-            return Ok(());
+            let error = format!("Request timeout after {}s", REQUEST_TIMEOUT.as_secs());
+            send_error_response(client_tx, &request_id, &error);
+            return (RequestOutcome::TimedOut, Ok(()));
         }
     };
 
-    #[cfg(debug_assertions)]
     if !is_read {
-        eprintln!(
-            "[MCP Bridge] Client {} completed {} - releasing write lock",
+        tracing::debug!(
+            "Client {} completed {} - releasing write lock",
             client_id, request_type_for_log
         );
     }
 
     // Write lock is automatically released here when _write_guard is dropped
 
+    let outcome = if response.success {
+        RequestOutcome::Succeeded
+    } else {
+        RequestOutcome::Failed
+    };
+
     // Send response back to client
     let ws_response = WsMessage {
-        id: msg.id,
+        id: request_id,
         msg_type: "response".to_string(),
         payload: serde_json::to_value(&response).unwrap_or_default(),
     };
 
-    let response_json =
-        serde_json::to_string(&ws_response).map_err(|e| format!("Failed to serialize: {}", e))?;
-
-    client_tx
-        .send(response_json)
-        .map_err(|e| format!("Failed to send response: {}", e))?;
+    let result = serde_json::to_string(&ws_response)
+        .map_err(|e| format!("Failed to serialize: {}", e))
+        .and_then(|json| {
+            client_tx
+                .send(json)
+                .map_err(|e| format!("Failed to send response: {}", e))
+        });
 
-    Ok(())
+    (outcome, result)
 }
 
 /// Tauri command to send a response from the frontend.
@@ -709,12 +886,11 @@ pub async fn mcp_bridge_respond(payload: McpResponsePayload) -> Result<(), Strin
     let state = get_bridge_state();
     let mut guard = state.lock().await;
 
-    #[cfg(debug_assertions)]
     if guard.pending.contains_key(&payload.id) {
-        eprintln!("[MCP Bridge] Response received for {}", payload.id);
+        tracing::debug!("Response received for {}", payload.id);
     } else {
-        eprintln!(
-            "[MCP Bridge] Response for unknown/expired request {}",
+        tracing::debug!(
+            "Response for unknown/expired request {}",
             payload.id
         );
     }
@@ -748,3 +924,71 @@ pub async fn client_count() -> usize {
     let guard = state.lock().await;
     guard.clients.len()
 }
+
+/// Get request queue and latency statistics for the bridge.
+#[tauri::command]
+pub async fn mcp_bridge_stats() -> McpBridgeStats {
+    let state = get_bridge_state();
+    let guard = state.lock().await;
+    McpBridgeStats {
+        clients: guard.clients.len(),
+        pending: guard.in_flight,
+        max_pending: guard.max_pending,
+        peak_queue_depth: guard.stats.peak_queue_depth,
+        average_queue_depth: guard.stats.average_queue_depth(),
+        total_requests: guard.stats.total_requests,
+        completed: guard.stats.completed,
+        failed: guard.stats.failed,
+        timeouts: guard.stats.timeouts,
+        rejected: guard.stats.rejected,
+        average_latency_ms: guard.stats.average_latency_ms(),
+        max_latency_ms: guard.stats.max_latency_ms,
+    }
+}
+
+/// Set the maximum number of in-flight requests before new ones are rejected.
+#[tauri::command]
+pub async fn mcp_bridge_set_max_pending(limit: usize) -> Result<(), String> {
+    if limit == 0 {
+        return Err("Pending request limit must be at least 1".to_string());
+    }
+    let state = get_bridge_state();
+    let mut guard = state.lock().await;
+    guard.max_pending = limit;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidate_ports_no_preference() {
+        assert_eq!(candidate_ports(0), vec![0]);
+    }
+
+    #[test]
+    fn test_candidate_ports_deterministic_range() {
+        let ports = candidate_ports(9223);
+        assert_eq!(ports.first(), Some(&9223));
+        assert_eq!(ports[1], 9224);
+        assert_eq!(ports.len(), PORT_SEARCH_RANGE as usize + 2);
+        assert_eq!(ports.last(), Some(&0));
+    }
+
+    #[test]
+    fn test_candidate_ports_near_max() {
+        let ports = candidate_ports(u16::MAX - 1);
+        assert_eq!(ports, vec![u16::MAX - 1, u16::MAX, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_bind_listener_falls_back_when_taken() {
+        let taken = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let listener = bind_listener(port).await.unwrap();
+        let actual = listener.local_addr().unwrap().port();
+        assert_ne!(actual, port);
+    }
+}
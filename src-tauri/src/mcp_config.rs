@@ -5,6 +5,10 @@
 //! - Claude Code: ~/.claude.json
 //! - Codex CLI: ~/.codex/config.toml
 //! - Gemini CLI: ~/.gemini/settings.json
+//! - Cursor: ~/.cursor/mcp.json
+//! - Windsurf: ~/.codeium/windsurf/mcp_config.json
+//! - VS Code: user settings.json (`mcp.servers`)
+//! - Zed: settings.json (`context_servers`)
 
 use chrono::Local;
 use serde::{Deserialize, Serialize};
@@ -55,6 +59,16 @@ pub struct UninstallResult {
     pub message: String,
 }
 
+/// A timestamped backup of a provider config file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigBackup {
+    pub path: String,
+    /// Timestamp from the file name (`%Y%m%d_%H%M%S`, plus `-N` for
+    /// further backups made in the same second)
+    pub timestamp: String,
+    pub size: u64,
+}
+
 /// Diagnostic status for MCP configuration
 #[derive(Clone, Serialize, Deserialize)]
 pub enum DiagnosticStatus {
@@ -66,6 +80,8 @@ pub enum DiagnosticStatus {
     BinaryMissing,
     /// No vmark entry in config
     NotConfigured,
+    /// Config pins a bridge port that differs from the running bridge
+    PortMismatch,
 }
 
 /// Detailed diagnostic information for a provider
@@ -85,6 +101,12 @@ pub struct ProviderDiagnostic {
     pub configured_binary_path: Option<String>,
     #[serde(rename = "binaryExists")]
     pub binary_exists: bool,
+    /// Port pinned in the config entry (legacy `--port` arg), if any
+    #[serde(rename = "configuredPort")]
+    pub configured_port: Option<u16>,
+    /// Port the bridge is currently listening on, if running
+    #[serde(rename = "bridgePort")]
+    pub bridge_port: Option<u16>,
     pub status: DiagnosticStatus,
     pub message: String,
 }
@@ -93,7 +115,7 @@ pub struct ProviderDiagnostic {
 struct ProviderConfig {
     name: &'static str,
     id: &'static str,
-    /// Path relative to `$HOME`. Claude Desktop differs per platform.
+    /// Path relative to `$HOME`. Claude Desktop, VS Code and Zed differ per platform.
     relative_path: &'static str,
 }
 
@@ -108,6 +130,20 @@ const CLAUDE_DESKTOP_PATH: &str = "AppData/Roaming/Claude/claude_desktop_config.
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
 const CLAUDE_DESKTOP_PATH: &str = ".config/Claude/claude_desktop_config.json";
 
+/// VS Code user settings path per platform.
+#[cfg(target_os = "macos")]
+const VSCODE_SETTINGS_PATH: &str = "Library/Application Support/Code/User/settings.json";
+#[cfg(target_os = "windows")]
+const VSCODE_SETTINGS_PATH: &str = "AppData/Roaming/Code/User/settings.json";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const VSCODE_SETTINGS_PATH: &str = ".config/Code/User/settings.json";
+
+/// Zed settings path per platform (macOS and Linux share ~/.config/zed).
+#[cfg(target_os = "windows")]
+const ZED_SETTINGS_PATH: &str = "AppData/Roaming/Zed/settings.json";
+#[cfg(not(target_os = "windows"))]
+const ZED_SETTINGS_PATH: &str = ".config/zed/settings.json";
+
 const PROVIDERS: &[ProviderConfig] = &[
     ProviderConfig {
         name: "Claude Desktop",
@@ -129,6 +165,26 @@ const PROVIDERS: &[ProviderConfig] = &[
         id: "gemini",
         relative_path: ".gemini/settings.json",
     },
+    ProviderConfig {
+        name: "Cursor",
+        id: "cursor",
+        relative_path: ".cursor/mcp.json",
+    },
+    ProviderConfig {
+        name: "Windsurf",
+        id: "windsurf",
+        relative_path: ".codeium/windsurf/mcp_config.json",
+    },
+    ProviderConfig {
+        name: "VS Code",
+        id: "vscode",
+        relative_path: VSCODE_SETTINGS_PATH,
+    },
+    ProviderConfig {
+        name: "Zed",
+        id: "zed",
+        relative_path: ZED_SETTINGS_PATH,
+    },
 ];
 
 fn get_provider_config(provider: &str) -> Result<&'static ProviderConfig, String> {
@@ -232,30 +288,75 @@ fn get_mcp_binary_path() -> Result<String, String> {
     ))
 }
 
+/// JSON object path to the map of MCP servers for JSON-based providers.
+/// - Most clients: `mcpServers`
+/// - VS Code: `mcp.servers` inside the user settings.json
+/// - Zed: `context_servers` inside settings.json
+fn json_servers_path(provider_id: &str) -> Option<&'static [&'static str]> {
+    match provider_id {
+        "claude-desktop" | "claude" | "gemini" | "cursor" | "windsurf" => Some(&["mcpServers"]),
+        "vscode" => Some(&["mcp", "servers"]),
+        "zed" => Some(&["context_servers"]),
+        _ => None,
+    }
+}
+
+/// Whether the provider's config is a general editor settings file.
+/// These are JSONC and often contain comments, so we never overwrite them
+/// when they fail to parse as plain JSON.
+fn is_shared_settings_file(provider_id: &str) -> bool {
+    matches!(provider_id, "vscode" | "zed")
+}
+
+/// Look up the vmark entry in a parsed JSON config.
+fn json_vmark_entry<'a>(
+    json: &'a serde_json::Value,
+    path: &[&str],
+) -> Option<&'a serde_json::Value> {
+    path.iter()
+        .try_fold(json, |node, key| node.get(*key))?
+        .get("vmark")
+}
+
+/// Build the vmark server entry in the provider's JSON shape.
+/// No args needed - sidecar auto-discovers port from ~/.vmark/mcp-port
+fn json_vmark_entry_for(provider_id: &str, binary_path: &str) -> serde_json::Value {
+    match provider_id {
+        "vscode" => serde_json::json!({
+            "type": "stdio",
+            "command": binary_path
+        }),
+        "zed" => serde_json::json!({
+            "source": "custom",
+            "command": binary_path,
+            "args": []
+        }),
+        _ => serde_json::json!({
+            "command": binary_path
+        }),
+    }
+}
+
 /// Read existing config and check if it has vmark entry
 fn read_existing_config(path: &PathBuf, provider_id: &str) -> (Option<String>, bool) {
     let content = fs::read_to_string(path).ok();
     let has_vmark = if let Some(ref c) = content {
-        match provider_id {
-            "claude-desktop" | "claude" | "gemini" => {
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(c) {
-                    json.get("mcpServers")
-                        .and_then(|s| s.get("vmark"))
-                        .is_some()
-                } else {
-                    false
-                }
+        if let Some(servers_path) = json_servers_path(provider_id) {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(c) {
+                json_vmark_entry(&json, servers_path).is_some()
+            } else {
+                false
             }
-            "codex" => {
-                if let Ok(toml) = c.parse::<toml::Table>() {
-                    toml.get("mcp_servers")
-                        .and_then(|s| s.get("vmark"))
-                        .is_some()
-                } else {
-                    false
-                }
+        } else if provider_id == "codex" {
+            if let Ok(toml) = c.parse::<toml::Table>() {
+                toml.get("mcp_servers")
+                    .and_then(|s| s.get("vmark"))
+                    .is_some()
+            } else {
+                false
             }
-            _ => false,
+        } else {
+            false
         }
     } else {
         false
@@ -265,19 +366,18 @@ fn read_existing_config(path: &PathBuf, provider_id: &str) -> (Option<String>, b
 
 /// Extract the vmark binary path from config content
 fn extract_vmark_binary_path(content: &str, provider_id: &str) -> Option<String> {
+    if let Some(servers_path) = json_servers_path(provider_id) {
+        // JSON format: <servers>.vmark.command
+        // Older Zed configs nest it as command.path
+        let json = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let command = json_vmark_entry(&json, servers_path)?.get("command")?;
+        return command
+            .as_str()
+            .or_else(|| command.get("path").and_then(|p| p.as_str()))
+            .map(|s| s.to_string());
+    }
+
     match provider_id {
-        "claude-desktop" | "claude" | "gemini" => {
-            // JSON format: mcpServers.vmark.command
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(content) {
-                json.get("mcpServers")
-                    .and_then(|s| s.get("vmark"))
-                    .and_then(|v| v.get("command"))
-                    .and_then(|c| c.as_str())
-                    .map(|s| s.to_string())
-            } else {
-                None
-            }
-        }
         "codex" => {
             // TOML format: mcp_servers.vmark.command
             if let Ok(toml) = content.parse::<toml::Table>() {
@@ -294,6 +394,50 @@ fn extract_vmark_binary_path(content: &str, provider_id: &str) -> Option<String>
     }
 }
 
+/// Extract a pinned bridge port from the vmark entry's args (`--port N` or `--port=N`).
+/// Current configs rely on port discovery, so this is only set by older installs
+/// or hand-edited configs.
+fn extract_vmark_pinned_port(content: &str, provider_id: &str) -> Option<u16> {
+    let args: Vec<String> = if let Some(servers_path) = json_servers_path(provider_id) {
+        let json = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let entry = json_vmark_entry(&json, servers_path)?;
+        // Older Zed configs nest args under command
+        entry
+            .get("args")
+            .or_else(|| entry.get("command").and_then(|c| c.get("args")))?
+            .as_array()?
+            .iter()
+            .filter_map(|a| a.as_str().map(|s| s.to_string()))
+            .collect()
+    } else if provider_id == "codex" {
+        let toml = content.parse::<toml::Table>().ok()?;
+        toml.get("mcp_servers")?
+            .get("vmark")?
+            .get("args")?
+            .as_array()?
+            .iter()
+            .filter_map(|a| a.as_str().map(|s| s.to_string()))
+            .collect()
+    } else {
+        return None;
+    };
+    parse_port_arg(&args)
+}
+
+/// Find a `--port` value in a list of command-line args.
+fn parse_port_arg(args: &[String]) -> Option<u16> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--port" {
+            return iter.next().and_then(|v| v.parse().ok());
+        }
+        if let Some(value) = arg.strip_prefix("--port=") {
+            return value.parse().ok();
+        }
+    }
+    None
+}
+
 /// Generate proposed config content for a provider.
 /// Note: No --port argument needed - sidecar auto-discovers port from ~/.vmark/mcp-port
 fn generate_config_content(
@@ -301,31 +445,43 @@ fn generate_config_content(
     binary_path: &str,
     existing_content: Option<&str>,
 ) -> Result<String, String> {
-    match provider_id {
-        "claude-desktop" | "claude" | "gemini" => {
-            let mut json: serde_json::Value = existing_content
-                .and_then(|c| serde_json::from_str(c).ok())
-                .unwrap_or_else(|| serde_json::json!({}));
+    if let Some(servers_path) = json_servers_path(provider_id) {
+        let existing = existing_content.filter(|c| !c.trim().is_empty());
+        let mut json: serde_json::Value = match existing {
+            Some(c) => match serde_json::from_str(c) {
+                Ok(json) => json,
+                Err(e) if is_shared_settings_file(provider_id) => {
+                    return Err(format!(
+                        "Cannot update settings file (comments or invalid JSON): {}. Add the vmark server manually.",
+                        e
+                    ));
+                }
+                Err(_) => serde_json::json!({}),
+            },
+            None => serde_json::json!({}),
+        };
 
-            let mcp_servers = json
+        let mut node = &mut json;
+        for key in servers_path {
+            node = node
                 .as_object_mut()
                 .ok_or("Invalid JSON structure")?
-                .entry("mcpServers")
+                .entry(key.to_string())
                 .or_insert_with(|| serde_json::json!({}));
-
-            // No args needed - sidecar auto-discovers port from ~/.vmark/mcp-port
-            mcp_servers
-                .as_object_mut()
-                .ok_or("mcpServers is not an object")?
-                .insert(
-                    "vmark".to_string(),
-                    serde_json::json!({
-                        "command": binary_path
-                    }),
-                );
-
-            serde_json::to_string_pretty(&json).map_err(|e| format!("JSON serialization error: {}", e))
         }
+
+        node.as_object_mut()
+            .ok_or_else(|| format!("{} is not an object", servers_path.join(".")))?
+            .insert(
+                "vmark".to_string(),
+                json_vmark_entry_for(provider_id, binary_path),
+            );
+
+        return serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("JSON serialization error: {}", e));
+    }
+
+    match provider_id {
         "codex" => {
             let mut toml_doc: toml::Table = existing_content
                 .and_then(|c| c.parse().ok())
@@ -350,17 +506,22 @@ fn generate_config_content(
 
 /// Remove vmark entry from config
 fn remove_vmark_from_config(provider_id: &str, content: &str) -> Result<String, String> {
-    match provider_id {
-        "claude-desktop" | "claude" | "gemini" => {
-            let mut json: serde_json::Value =
-                serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+    if let Some(servers_path) = json_servers_path(provider_id) {
+        let mut json: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+        let servers = servers_path
+            .iter()
+            .try_fold(&mut json, |node, key| node.get_mut(*key));
+        if let Some(servers) = servers.and_then(|s| s.as_object_mut()) {
+            servers.remove("vmark");
+        }
 
-            if let Some(servers) = json.get_mut("mcpServers").and_then(|s| s.as_object_mut()) {
-                servers.remove("vmark");
-            }
+        return serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("JSON serialization error: {}", e));
+    }
 
-            serde_json::to_string_pretty(&json).map_err(|e| format!("JSON serialization error: {}", e))
-        }
+    match provider_id {
         "codex" => {
             let mut toml_doc: toml::Table =
                 content.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
@@ -375,13 +536,109 @@ fn remove_vmark_from_config(provider_id: &str, content: &str) -> Result<String,
     }
 }
 
+/// A backup path that isn't taken yet: `{file}.backup.{timestamp}`, with a
+/// `-N` suffix when a backup was already made in the same second.
 fn generate_backup_path(config_path: &Path) -> PathBuf {
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    let base = format!(
+        "{}{}",
+        backup_prefix(config_path),
+        Local::now().format("%Y%m%d_%H%M%S")
+    );
+    let mut path = config_path.with_file_name(&base);
+    let mut n = 1;
+    while path.symlink_metadata().is_ok() {
+        path = config_path.with_file_name(format!("{}-{}", base, n));
+        n += 1;
+    }
+    path
+}
+
+/// Sort key for a backup timestamp: `20240101_120000-2` sorts after
+/// `20240101_120000-1` and `20240101_120000`.
+fn backup_order(timestamp: &str) -> (&str, u32) {
+    match timestamp.split_once('-') {
+        Some((stamp, n)) => (stamp, n.parse().unwrap_or(0)),
+        None => (timestamp, 0),
+    }
+}
+
+/// Backup file name prefix for a config file (`{file}.backup.`).
+fn backup_prefix(config_path: &Path) -> String {
     let file_name = config_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| "config".to_string());
-    config_path.with_file_name(format!("{}.backup.{}", file_name, timestamp))
+    format!("{}.backup.", file_name)
+}
+
+/// List backups of a config file, newest first.
+fn list_backups_for(config_path: &Path) -> Vec<ConfigBackup> {
+    let Some(dir) = config_path.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let prefix = backup_prefix(config_path);
+
+    let mut backups: Vec<ConfigBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let timestamp = name.strip_prefix(&prefix)?.to_string();
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(ConfigBackup {
+                path: entry.path().to_string_lossy().to_string(),
+                timestamp,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+
+    // Timestamps are zero-padded, so lexical order is chronological
+    backups.sort_by(|a, b| backup_order(&b.timestamp).cmp(&backup_order(&a.timestamp)));
+    backups
+}
+
+/// Restore a backup over the config file, backing up the current config first.
+/// Returns the path of the backup made of the current config, if one existed.
+fn restore_backup_for(config_path: &Path, backup_path: &Path) -> Result<Option<String>, String> {
+    // Only accept backups that belong to this config file
+    let is_known = list_backups_for(config_path)
+        .iter()
+        .any(|b| Path::new(&b.path) == backup_path);
+    if !is_known {
+        return Err(format!(
+            "Not a backup of {}: {}",
+            config_path.display(),
+            backup_path.display()
+        ));
+    }
+
+    let content =
+        fs::read(backup_path).map_err(|e| format!("Failed to read backup: {}", e))?;
+
+    let current_backup = if config_path.exists() {
+        let backup = generate_backup_path(config_path);
+        fs::copy(config_path, &backup).map_err(|e| format!("Failed to create backup: {}", e))?;
+        Some(backup.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    crate::app_paths::atomic_write_file(config_path, &content)?;
+    Ok(current_backup)
+}
+
+/// Delete all but the newest `keep` backups. Returns the removed paths.
+fn prune_backups_for(config_path: &Path, keep: usize) -> Result<Vec<String>, String> {
+    let mut removed = Vec::new();
+    for backup in list_backups_for(config_path).into_iter().skip(keep) {
+        fs::remove_file(&backup.path)
+            .map_err(|e| format!("Failed to remove {}: {}", backup.path, e))?;
+        removed.push(backup.path);
+    }
+    Ok(removed)
 }
 
 /// Get status of all AI providers
@@ -418,6 +675,7 @@ pub fn mcp_config_diagnose() -> Result<Vec<ProviderDiagnostic>, String> {
 
     // Get the expected binary path once (may fail if binary not found)
     let expected_binary_path = get_mcp_binary_path().ok();
+    let bridge_port = crate::mcp_server::current_bridge_port();
 
     for provider in PROVIDERS {
         let path = get_config_path(provider)?;
@@ -439,6 +697,15 @@ pub fn mcp_config_diagnose() -> Result<Vec<ProviderDiagnostic>, String> {
             .map(|p| std::path::Path::new(p).exists())
             .unwrap_or(false);
 
+        // A pinned port only matters if it disagrees with the running bridge
+        let configured_port = content
+            .as_ref()
+            .and_then(|c| extract_vmark_pinned_port(c, provider.id));
+        let port_mismatch = matches!(
+            (configured_port, bridge_port),
+            (Some(configured), Some(actual)) if configured != actual
+        );
+
         // Determine diagnostic status and message
         let (status, message) = if !has_vmark {
             (DiagnosticStatus::NotConfigured, String::new())
@@ -447,6 +714,11 @@ pub fn mcp_config_diagnose() -> Result<Vec<ProviderDiagnostic>, String> {
                 DiagnosticStatus::BinaryMissing,
                 "Binary not found - reinstall VMark".to_string(),
             )
+        } else if port_mismatch {
+            (
+                DiagnosticStatus::PortMismatch,
+                "Config pins an old bridge port - click Repair".to_string(),
+            )
         } else if let (Some(ref expected), Some(ref configured)) =
             (&expected_binary_path, &configured_binary_path)
         {
@@ -486,6 +758,8 @@ pub fn mcp_config_diagnose() -> Result<Vec<ProviderDiagnostic>, String> {
             expected_binary_path: expected_binary_path.clone(),
             configured_binary_path,
             binary_exists,
+            configured_port,
+            bridge_port,
             status,
             message,
         });
@@ -613,3 +887,206 @@ pub fn mcp_config_uninstall(provider: String) -> Result<UninstallResult, String>
         ),
     })
 }
+
+/// List config backups for a provider, newest first
+#[tauri::command]
+pub fn list_config_backups(provider: String) -> Result<Vec<ConfigBackup>, String> {
+    let config = get_provider_config(&provider)?;
+    let path = get_config_path(config)?;
+    Ok(list_backups_for(&path))
+}
+
+/// Restore a provider config from one of its backups
+#[tauri::command]
+pub fn restore_config_backup(
+    provider: String,
+    backup_path: String,
+) -> Result<InstallResult, String> {
+    let config = get_provider_config(&provider)?;
+    let path = get_config_path(config)?;
+
+    let current_backup = restore_backup_for(&path, Path::new(&backup_path))?;
+
+    Ok(InstallResult {
+        success: true,
+        message: format!("Restored {} configuration from backup", config.name),
+        backup_path: current_backup,
+    })
+}
+
+/// Delete all but the newest `keep` backups for a provider
+#[tauri::command]
+pub fn prune_config_backups(provider: String, keep_n: usize) -> Result<Vec<String>, String> {
+    let config = get_provider_config(&provider)?;
+    let path = get_config_path(config)?;
+    prune_backups_for(&path, keep_n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_port_arg() {
+        assert_eq!(parse_port_arg(&args(&["--port", "9223"])), Some(9223));
+        assert_eq!(parse_port_arg(&args(&["--port=9300"])), Some(9300));
+        assert_eq!(parse_port_arg(&args(&["--verbose"])), None);
+        assert_eq!(parse_port_arg(&args(&["--port"])), None);
+    }
+
+    #[test]
+    fn test_extract_pinned_port_json() {
+        let content = r#"{"mcpServers":{"vmark":{"command":"/bin/vmark","args":["--port","9223"]}}}"#;
+        assert_eq!(extract_vmark_pinned_port(content, "claude"), Some(9223));
+
+        let content = r#"{"mcpServers":{"vmark":{"command":"/bin/vmark"}}}"#;
+        assert_eq!(extract_vmark_pinned_port(content, "claude"), None);
+    }
+
+    #[test]
+    fn test_extract_pinned_port_toml() {
+        let content = "[mcp_servers.vmark]\ncommand = \"/bin/vmark\"\nargs = [\"--port=9224\"]\n";
+        assert_eq!(extract_vmark_pinned_port(content, "codex"), Some(9224));
+    }
+
+    #[test]
+    fn test_generated_config_drops_pinned_port() {
+        let existing = r#"{"mcpServers":{"vmark":{"command":"/old","args":["--port","9223"]}}}"#;
+        let content = generate_config_content("claude", "/new", Some(existing)).unwrap();
+        assert_eq!(extract_vmark_pinned_port(&content, "claude"), None);
+    }
+
+    #[test]
+    fn test_vscode_config_shape() {
+        let existing = r#"{"editor.fontSize": 14}"#;
+        let content = generate_config_content("vscode", "/bin/vmark", Some(existing)).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["editor.fontSize"], 14);
+        assert_eq!(json["mcp"]["servers"]["vmark"]["command"], "/bin/vmark");
+        assert_eq!(json["mcp"]["servers"]["vmark"]["type"], "stdio");
+        assert_eq!(
+            extract_vmark_binary_path(&content, "vscode").as_deref(),
+            Some("/bin/vmark")
+        );
+    }
+
+    #[test]
+    fn test_zed_config_shape() {
+        let content = generate_config_content("zed", "/bin/vmark", None).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["context_servers"]["vmark"]["command"], "/bin/vmark");
+
+        // Older nested command form is still recognized
+        let legacy = r#"{"context_servers":{"vmark":{"command":{"path":"/old","args":["--port","9223"]}}}}"#;
+        assert_eq!(extract_vmark_binary_path(legacy, "zed").as_deref(), Some("/old"));
+        assert_eq!(extract_vmark_pinned_port(legacy, "zed"), Some(9223));
+    }
+
+    #[test]
+    fn test_cursor_and_windsurf_use_mcp_servers() {
+        for provider in ["cursor", "windsurf"] {
+            let content = generate_config_content(provider, "/bin/vmark", None).unwrap();
+            let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+            assert_eq!(json["mcpServers"]["vmark"]["command"], "/bin/vmark");
+        }
+    }
+
+    #[test]
+    fn test_settings_file_with_comments_is_not_overwritten() {
+        let existing = "{\n  // font\n  \"editor.fontSize\": 14\n}";
+        assert!(generate_config_content("vscode", "/bin/vmark", Some(existing)).is_err());
+        assert!(generate_config_content("zed", "/bin/vmark", Some(existing)).is_err());
+    }
+
+    #[test]
+    fn test_remove_nested_vscode_entry() {
+        let content = generate_config_content("vscode", "/bin/vmark", None).unwrap();
+        let removed = remove_vmark_from_config("vscode", &content).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&removed).unwrap();
+        assert!(json["mcp"]["servers"].get("vmark").is_none());
+    }
+
+    #[test]
+    fn test_list_backups_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        fs::write(&config, "{}").unwrap();
+        fs::write(dir.path().join("mcp.json.backup.20240101_120000"), "a").unwrap();
+        fs::write(dir.path().join("mcp.json.backup.20240301_120000"), "b").unwrap();
+        fs::write(dir.path().join("other.json.backup.20240401_120000"), "c").unwrap();
+
+        let backups = list_backups_for(&config);
+        let stamps: Vec<_> = backups.iter().map(|b| b.timestamp.as_str()).collect();
+        assert_eq!(stamps, vec!["20240301_120000", "20240101_120000"]);
+    }
+
+    #[test]
+    fn test_backup_paths_are_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        fs::write(&config, "{}").unwrap();
+
+        let mut paths = Vec::new();
+        for _ in 0..12 {
+            let path = generate_backup_path(&config);
+            assert!(!paths.contains(&path));
+            fs::copy(&config, &path).unwrap();
+            paths.push(path);
+        }
+
+        // Newest first, even across same-second suffixes
+        let backups = list_backups_for(&config);
+        assert_eq!(backups.len(), 12);
+        assert_eq!(Path::new(&backups[0].path), paths.last().unwrap().as_path());
+    }
+
+    #[test]
+    fn test_restore_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        let backup = dir.path().join("mcp.json.backup.20240101_120000");
+        fs::write(&config, "current").unwrap();
+        fs::write(&backup, "old").unwrap();
+
+        let saved = restore_backup_for(&config, &backup).unwrap();
+        assert_eq!(fs::read_to_string(&config).unwrap(), "old");
+        let saved = saved.expect("current config should be backed up");
+        assert_eq!(fs::read_to_string(saved).unwrap(), "current");
+
+        // Restoring again right away keeps both earlier states
+        let saved = restore_backup_for(&config, &backup).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(saved).unwrap(), "old");
+        assert_eq!(list_backups_for(&config).len(), 3);
+    }
+
+    #[test]
+    fn test_restore_rejects_foreign_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        let other = dir.path().join("secrets.txt");
+        fs::write(&config, "current").unwrap();
+        fs::write(&other, "x").unwrap();
+
+        assert!(restore_backup_for(&config, &other).is_err());
+        assert_eq!(fs::read_to_string(&config).unwrap(), "current");
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        for stamp in ["20240101_000000", "20240102_000000", "20240103_000000"] {
+            fs::write(dir.path().join(format!("mcp.json.backup.{}", stamp)), "x").unwrap();
+        }
+
+        let removed = prune_backups_for(&config, 1).unwrap();
+        assert_eq!(removed.len(), 2);
+        let remaining = list_backups_for(&config);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].timestamp, "20240103_000000");
+    }
+}
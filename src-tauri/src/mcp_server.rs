@@ -52,9 +52,39 @@ pub struct McpServerStatus {
     pub local_sidecar: bool,
 }
 
+/// Payload for `mcp-server:port-changed` when the preferred port was unavailable.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPortChange {
+    pub requested_port: u16,
+    pub actual_port: u16,
+}
+
+/// Get the port the bridge is currently listening on, if running.
+pub fn current_bridge_port() -> Option<u16> {
+    if !BRIDGE_RUNNING.load(Ordering::SeqCst) {
+        return None;
+    }
+    BRIDGE_PORT.lock().ok().and_then(|guard| *guard)
+}
+
+/// Tell the frontend when the bridge fell back from the preferred port.
+fn emit_port_change(app: &AppHandle, requested_port: u16, actual_port: u16) {
+    if requested_port != 0 && requested_port != actual_port {
+        let _ = app.emit(
+            "mcp-server:port-changed",
+            McpPortChange {
+                requested_port,
+                actual_port,
+            },
+        );
+    }
+}
+
 /// Start only the MCP bridge WebSocket server (no sidecar).
 /// This is the recommended way to enable MCP - AI clients spawn their own sidecars.
-/// The port parameter is ignored - the OS assigns an available port automatically.
+/// The port parameter is the preferred port; if it is taken, the next free port in a
+/// small range is used, falling back to an OS-assigned port (pass 0 to skip the search).
 /// The actual port is written to ~/.vmark/mcp-port for sidecar discovery.
 #[command]
 pub async fn mcp_bridge_start(app: AppHandle, port: u16) -> Result<McpServerStatus, String> {
@@ -80,10 +110,10 @@ pub async fn mcp_bridge_start(app: AppHandle, port: u16) -> Result<McpServerStat
 
     // Emit started event with actual port
     let _ = app.emit("mcp-server:started", actual_port);
+    emit_port_change(&app, port, actual_port);
 
-    #[cfg(debug_assertions)]
-    eprintln!(
-        "[MCP] Bridge started on port {} (waiting for AI client sidecars)",
+    tracing::debug!(
+        "Bridge started on port {} (waiting for AI client sidecars)",
         actual_port
     );
 
@@ -151,6 +181,7 @@ pub async fn mcp_server_start(app: AppHandle, port: u16) -> Result<McpServerStat
             let mut port_guard = BRIDGE_PORT.lock().map_err(|e| e.to_string())?;
             *port_guard = Some(actual);
         }
+        emit_port_change(&app, port, actual);
         actual
     } else {
         current_port.unwrap_or(port)
@@ -182,18 +213,15 @@ pub async fn mcp_server_start(app: AppHandle, port: u16) -> Result<McpServerStat
 
         while let Some(event) = rx.recv().await {
             match event {
-                CommandEvent::Stdout(_line) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[MCP Server] {}", String::from_utf8_lossy(&_line));
+                CommandEvent::Stdout(line) => {
+                    tracing::debug!("{}", String::from_utf8_lossy(&line));
                 }
-                CommandEvent::Stderr(_line) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!("[MCP Server Error] {}", String::from_utf8_lossy(&_line));
+                CommandEvent::Stderr(line) => {
+                    tracing::warn!("{}", String::from_utf8_lossy(&line));
                 }
                 CommandEvent::Terminated(_payload) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!(
-                        "[MCP Server] Process terminated with code: {:?}",
+                    tracing::debug!(
+                        "Process terminated with code: {:?}",
                         _payload.code
                     );
 
@@ -320,9 +348,8 @@ pub fn write_mcp_tool_mode(app: AppHandle, mode: String) -> Result<(), String> {
 
     app_paths::atomic_write_file(&path, content.as_bytes())?;
 
-    #[cfg(debug_assertions)]
-    eprintln!(
-        "[MCP Settings] Tool mode '{}' written to {:?}",
+    tracing::debug!(
+        "Tool mode '{}' written to {:?}",
         mode, path
     );
 
@@ -1,12 +1,19 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
-use tauri::menu::{Menu, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu};
-use tauri::AppHandle;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager};
 
 pub const RECENT_FILES_SUBMENU_ID: &str = "recent-files-submenu";
 pub const RECENT_WORKSPACES_SUBMENU_ID: &str = "recent-workspaces-submenu";
 pub const GENIES_SUBMENU_ID: &str = "genies-submenu";
 
+/// Recent files list saved in the app data directory
+const RECENT_FILES_FILE: &str = "recent-files.json";
+
 /// Stores the recent files list snapshot at menu build time.
 /// This ensures that when a menu item is clicked, we can look up
 /// the correct path even if the store changed since menu creation.
@@ -19,6 +26,20 @@ static RECENT_WORKSPACES_SNAPSHOT: Mutex<Vec<String>> = Mutex::new(Vec::new());
 /// Index corresponds to `genie-item-{index}` menu item IDs.
 static GENIES_SNAPSHOT: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
+/// A customizable menu item's accelerator ("" = no shortcut)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuAccelerator {
+    pub id: String,
+    pub default: String,
+    pub accelerator: String,
+}
+
+/// Accelerators recorded when the menu was last built.
+static MENU_ACCELERATORS: Mutex<Vec<MenuAccelerator>> = Mutex::new(Vec::new());
+
+/// Set while the Genies submenu is present in the Edit menu.
+static GENIES_MENU_SHOWN: AtomicBool = AtomicBool::new(false);
+
 /// Get the path for a recent file by its menu index.
 /// Returns None if index is out of bounds.
 pub fn get_recent_file_path(index: usize) -> Option<String> {
@@ -59,519 +80,9 @@ pub fn get_genie_path(index: usize) -> Option<String> {
 // - About in App menu (macOS) or Help menu (others); updates are automatic
 // ============================================================================
 
+/// Create the application menu with the user's keymap overrides applied.
 pub fn create_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
-    // ========================================================================
-    // App menu (macOS only)
-    // ========================================================================
-    #[cfg(target_os = "macos")]
-    let app_menu = Submenu::with_items(
-        app,
-        "VMark",
-        true,
-        &[
-            &MenuItem::with_id(app, "about", "About VMark", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "preferences", "Settings...", true, Some("CmdOrCtrl+,"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &PredefinedMenuItem::services(app, Some("Services"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &PredefinedMenuItem::hide(app, Some("Hide VMark"))?,
-            &PredefinedMenuItem::hide_others(app, Some("Hide Others"))?,
-            &PredefinedMenuItem::show_all(app, Some("Show All"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "save-all-quit", "Save All and Quit", true, Some("Alt+Shift+CmdOrCtrl+Q"))?,
-            &MenuItem::with_id(app, "quit", "Quit VMark", true, Some("CmdOrCtrl+Q"))?,
-        ],
-    )?;
-
-    // ========================================================================
-    // File menu
-    // ========================================================================
-    let recent_submenu = Submenu::with_id_and_items(
-        app,
-        RECENT_FILES_SUBMENU_ID,
-        "Open Recent",
-        true,
-        &[
-            &MenuItem::with_id(app, "no-recent", "No Recent Files", false, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-recent", "Clear Recent Files", true, None::<&str>)?,
-        ],
-    )?;
-
-    let recent_workspaces_submenu = Submenu::with_id_and_items(
-        app,
-        RECENT_WORKSPACES_SUBMENU_ID,
-        "Open Recent Workspace",
-        true,
-        &[
-            &MenuItem::with_id(app, "no-recent-workspace", "No Recent Workspaces", false, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-recent-workspaces", "Clear Recent Workspaces", true, None::<&str>)?,
-        ],
-    )?;
-
-    let export_submenu = Submenu::with_items(
-        app,
-        "Export",
-        true,
-        &[
-            &MenuItem::with_id(app, "export-html", "HTML...", true, Some("Alt+CmdOrCtrl+E"))?,
-            &MenuItem::with_id(app, "export-pdf", "Print...", true, Some("CmdOrCtrl+P"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "copy-html", "Copy as HTML", true, Some("CmdOrCtrl+Shift+C"))?,
-        ],
-    )?;
-
-    let history_submenu = Submenu::with_items(
-        app,
-        "Document History",
-        true,
-        &[
-            &MenuItem::with_id(app, "view-history", "View History...", true, Some("CmdOrCtrl+Shift+H"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-history", "Clear History...", true, None::<&str>)?,
-        ],
-    )?;
-
-    #[cfg(target_os = "macos")]
-    let file_menu = Submenu::with_items(
-        app,
-        "File",
-        true,
-        &[
-            &MenuItem::with_id(app, "new", "New", true, Some("CmdOrCtrl+N"))?,
-            &MenuItem::with_id(app, "new-window", "New Window", true, Some("CmdOrCtrl+Shift+N"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "open", "Open...", true, Some("CmdOrCtrl+O"))?,
-            &MenuItem::with_id(app, "open-folder", "Open Folder...", true, Some("CmdOrCtrl+Shift+O"))?,
-            &recent_submenu,
-            &recent_workspaces_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "close", "Close", true, Some("CmdOrCtrl+W"))?,
-            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "save", "Save", true, Some("CmdOrCtrl+S"))?,
-            &MenuItem::with_id(app, "save-as", "Save As...", true, Some("CmdOrCtrl+Shift+S"))?,
-            &MenuItem::with_id(app, "move-to", "Move to...", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &export_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &history_submenu,
-        ],
-    )?;
-
-    #[cfg(not(target_os = "macos"))]
-    let file_menu = Submenu::with_items(
-        app,
-        "File",
-        true,
-        &[
-            &MenuItem::with_id(app, "new", "New", true, Some("CmdOrCtrl+N"))?,
-            &MenuItem::with_id(app, "new-window", "New Window", true, Some("CmdOrCtrl+Shift+N"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "open", "Open...", true, Some("CmdOrCtrl+O"))?,
-            &MenuItem::with_id(app, "open-folder", "Open Folder...", true, Some("CmdOrCtrl+Shift+O"))?,
-            &recent_submenu,
-            &recent_workspaces_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "close", "Close", true, Some("CmdOrCtrl+W"))?,
-            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "save", "Save", true, Some("CmdOrCtrl+S"))?,
-            &MenuItem::with_id(app, "save-as", "Save As...", true, Some("CmdOrCtrl+Shift+S"))?,
-            &MenuItem::with_id(app, "move-to", "Move to...", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &export_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &history_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "preferences", "Settings...", true, Some("CmdOrCtrl+,"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "save-all-quit", "Save All and Exit", true, Some("Alt+Shift+CmdOrCtrl+Q"))?,
-            &MenuItem::with_id(app, "quit", "Exit", true, Some("CmdOrCtrl+Q"))?,
-        ],
-    )?;
-
-    // ========================================================================
-    // Edit menu
-    // ========================================================================
-    let find_submenu = Submenu::with_items(
-        app,
-        "Find",
-        true,
-        &[
-            &MenuItem::with_id(app, "find-replace", "Find and Replace...", true, Some("CmdOrCtrl+F"))?,
-            &MenuItem::with_id(app, "find-next", "Find Next", true, Some("CmdOrCtrl+G"))?,
-            &MenuItem::with_id(app, "find-prev", "Find Previous", true, Some("CmdOrCtrl+Shift+G"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "use-selection-find", "Use Selection for Find", true, Some("CmdOrCtrl+E"))?,
-        ],
-    )?;
-
-    let selection_submenu = Submenu::with_items(
-        app,
-        "Selection",
-        true,
-        &[
-            &MenuItem::with_id(app, "select-word", "Select Word", true, None::<&str>)?,
-            &MenuItem::with_id(app, "select-line", "Select Line", true, Some("CmdOrCtrl+L"))?,
-            &MenuItem::with_id(app, "select-block", "Select Block", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "expand-selection", "Expand Selection", true, Some("Ctrl+Shift+Up"))?,
-        ],
-    )?;
-
-    let lines_submenu = Submenu::with_items(
-        app,
-        "Lines",
-        true,
-        &[
-            &MenuItem::with_id(app, "move-line-up", "Move Line Up", true, Some("Alt+Up"))?,
-            &MenuItem::with_id(app, "move-line-down", "Move Line Down", true, Some("Alt+Down"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "duplicate-line", "Duplicate Line", true, Some("Shift+Alt+Down"))?,
-            &MenuItem::with_id(app, "delete-line", "Delete Line", true, Some("CmdOrCtrl+Shift+K"))?,
-            &MenuItem::with_id(app, "join-lines", "Join Lines", true, Some("CmdOrCtrl+J"))?,
-            &MenuItem::with_id(app, "remove-blank-lines", "Remove Blank Lines", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "sort-lines-asc", "Sort Lines Ascending", true, Some("F4"))?,
-            &MenuItem::with_id(app, "sort-lines-desc", "Sort Lines Descending", true, Some("Shift+F4"))?,
-        ],
-    )?;
-
-    let line_endings_submenu = Submenu::with_items(
-        app,
-        "Line Endings",
-        true,
-        &[
-            &MenuItem::with_id(app, "line-endings-lf", "Convert to LF", true, None::<&str>)?,
-            &MenuItem::with_id(app, "line-endings-crlf", "Convert to CRLF", true, None::<&str>)?,
-        ],
-    )?;
-
-    let edit_menu = Submenu::with_items(
-        app,
-        "Edit",
-        true,
-        &[
-            &MenuItem::with_id(app, "undo", "Undo", true, Some("CmdOrCtrl+Z"))?,
-            &MenuItem::with_id(app, "redo", "Redo", true, Some("CmdOrCtrl+Shift+Z"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &PredefinedMenuItem::cut(app, Some("Cut"))?,
-            &PredefinedMenuItem::copy(app, Some("Copy"))?,
-            &PredefinedMenuItem::paste(app, Some("Paste"))?,
-            &PredefinedMenuItem::select_all(app, Some("Select All"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &find_submenu,
-            &selection_submenu,
-            &lines_submenu,
-            &line_endings_submenu,
-        ],
-    )?;
-
-    // ========================================================================
-    // Format menu (merged: Block + Format + Tools)
-    // ========================================================================
-    let headings_submenu = Submenu::with_items(
-        app,
-        "Headings",
-        true,
-        &[
-            &MenuItem::with_id(app, "heading-1", "Heading 1", true, Some("CmdOrCtrl+1"))?,
-            &MenuItem::with_id(app, "heading-2", "Heading 2", true, Some("CmdOrCtrl+2"))?,
-            &MenuItem::with_id(app, "heading-3", "Heading 3", true, Some("CmdOrCtrl+3"))?,
-            &MenuItem::with_id(app, "heading-4", "Heading 4", true, Some("CmdOrCtrl+4"))?,
-            &MenuItem::with_id(app, "heading-5", "Heading 5", true, Some("CmdOrCtrl+5"))?,
-            &MenuItem::with_id(app, "heading-6", "Heading 6", true, Some("CmdOrCtrl+6"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "paragraph", "Paragraph", true, Some("CmdOrCtrl+Shift+0"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "increase-heading", "Increase Heading Level", true, Some("Alt+CmdOrCtrl+]"))?,
-            &MenuItem::with_id(app, "decrease-heading", "Decrease Heading Level", true, Some("Alt+CmdOrCtrl+["))?,
-        ],
-    )?;
-
-    let lists_submenu = Submenu::with_items(
-        app,
-        "Lists",
-        true,
-        &[
-            &MenuItem::with_id(app, "ordered-list", "Ordered List", true, Some("Alt+CmdOrCtrl+O"))?,
-            &MenuItem::with_id(app, "unordered-list", "Unordered List", true, Some("Alt+CmdOrCtrl+U"))?,
-            &MenuItem::with_id(app, "task-list", "Task List", true, Some("Alt+CmdOrCtrl+X"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "indent", "Indent", true, Some("CmdOrCtrl+]"))?,
-            &MenuItem::with_id(app, "outdent", "Outdent", true, Some("CmdOrCtrl+["))?,
-            &MenuItem::with_id(app, "remove-list", "Remove List", true, None::<&str>)?,
-        ],
-    )?;
-
-    let quote_submenu = Submenu::with_items(
-        app,
-        "Quote",
-        true,
-        &[
-            &MenuItem::with_id(app, "quote", "Quote", true, Some("Alt+CmdOrCtrl+Q"))?,
-            &MenuItem::with_id(app, "nest-quote", "Nest Quote", true, None::<&str>)?,
-            &MenuItem::with_id(app, "unnest-quote", "Unnest Quote", true, None::<&str>)?,
-        ],
-    )?;
-
-    let transform_submenu = Submenu::with_items(
-        app,
-        "Transform",
-        true,
-        &[
-            &MenuItem::with_id(app, "transform-uppercase", "UPPERCASE", true, Some("Ctrl+Shift+U"))?,
-            &MenuItem::with_id(app, "transform-lowercase", "lowercase", true, Some("Ctrl+Shift+L"))?,
-            &MenuItem::with_id(app, "transform-title-case", "Title Case", true, Some("Ctrl+Shift+T"))?,
-            &MenuItem::with_id(app, "transform-toggle-case", "Toggle Case", true, None::<&str>)?,
-        ],
-    )?;
-
-    let cjk_submenu = Submenu::with_items(
-        app,
-        "CJK",
-        true,
-        &[
-            &MenuItem::with_id(app, "format-cjk", "Format Selection", true, Some("CmdOrCtrl+Shift+F"))?,
-            &MenuItem::with_id(app, "format-cjk-file", "Format Entire File", true, Some("Alt+CmdOrCtrl+Shift+F"))?,
-        ],
-    )?;
-
-    let cleanup_submenu = Submenu::with_items(
-        app,
-        "Text Cleanup",
-        true,
-        &[
-            &MenuItem::with_id(app, "remove-trailing-spaces", "Remove Trailing Spaces", true, None::<&str>)?,
-            &MenuItem::with_id(app, "collapse-blank-lines", "Collapse Blank Lines", true, None::<&str>)?,
-        ],
-    )?;
-
-    let format_menu = Submenu::with_items(
-        app,
-        "Format",
-        true,
-        &[
-            &headings_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "bold", "Bold", true, Some("CmdOrCtrl+B"))?,
-            &MenuItem::with_id(app, "italic", "Italic", true, Some("CmdOrCtrl+I"))?,
-            &MenuItem::with_id(app, "underline", "Underline", true, Some("CmdOrCtrl+U"))?,
-            &MenuItem::with_id(app, "strikethrough", "Strikethrough", true, Some("CmdOrCtrl+Shift+X"))?,
-            &MenuItem::with_id(app, "code", "Inline Code", true, Some("CmdOrCtrl+Shift+`"))?,
-            &MenuItem::with_id(app, "highlight", "Highlight", true, Some("CmdOrCtrl+Shift+M"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "subscript", "Subscript", true, Some("Alt+CmdOrCtrl+="))?,
-            &MenuItem::with_id(app, "superscript", "Superscript", true, Some("Alt+CmdOrCtrl+Shift+="))?,
-            &PredefinedMenuItem::separator(app)?,
-            &lists_submenu,
-            &quote_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &transform_submenu,
-            &cjk_submenu,
-            &cleanup_submenu,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-format", "Clear Format", true, Some("CmdOrCtrl+\\"))?,
-        ],
-    )?;
-
-    // ========================================================================
-    // Insert menu
-    // ========================================================================
-    let links_submenu = Submenu::with_items(
-        app,
-        "Links",
-        true,
-        &[
-            &MenuItem::with_id(app, "link", "Link", true, Some("CmdOrCtrl+K"))?,
-            &MenuItem::with_id(app, "wiki-link", "Wiki Link", true, None::<&str>)?,
-            &MenuItem::with_id(app, "bookmark", "Bookmark", true, None::<&str>)?,
-        ],
-    )?;
-
-    let table_submenu = Submenu::with_items(
-        app,
-        "Table",
-        true,
-        &[
-            &MenuItem::with_id(app, "insert-table", "Insert Table", true, Some("CmdOrCtrl+Shift+T"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "add-row-before", "Add Row Above", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-row-after", "Add Row Below", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-col-before", "Add Column Before", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-col-after", "Add Column After", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "delete-row", "Delete Row", true, None::<&str>)?,
-            &MenuItem::with_id(app, "delete-col", "Delete Column", true, None::<&str>)?,
-            &MenuItem::with_id(app, "delete-table", "Delete Table", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "align-left", "Align Left", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-center", "Align Center", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-right", "Align Right", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "align-all-left", "Align All Left", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-all-center", "Align All Center", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-all-right", "Align All Right", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "format-table", "Format Table", true, Some("Alt+CmdOrCtrl+T"))?,
-        ],
-    )?;
-
-    let info_boxes_submenu = Submenu::with_items(
-        app,
-        "Info Box",
-        true,
-        &[
-            &MenuItem::with_id(app, "info-note", "Note", true, None::<&str>)?,
-            &MenuItem::with_id(app, "info-tip", "Tip", true, None::<&str>)?,
-            &MenuItem::with_id(app, "info-important", "Important", true, None::<&str>)?,
-            &MenuItem::with_id(app, "info-warning", "Warning", true, None::<&str>)?,
-            &MenuItem::with_id(app, "info-caution", "Caution", true, None::<&str>)?,
-        ],
-    )?;
-
-    let insert_menu = Submenu::with_items(
-        app,
-        "Insert",
-        true,
-        &[
-            &links_submenu,
-            &MenuItem::with_id(app, "image", "Image...", true, Some("Shift+CmdOrCtrl+I"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &table_submenu,
-            &MenuItem::with_id(app, "code-fences", "Code Block", true, Some("Alt+CmdOrCtrl+C"))?,
-            &MenuItem::with_id(app, "math-block", "Math Block", true, Some("Alt+CmdOrCtrl+Shift+M"))?,
-            &MenuItem::with_id(app, "diagram", "Diagram", true, Some("Alt+Shift+CmdOrCtrl+D"))?,
-            &MenuItem::with_id(app, "mindmap", "Mindmap", true, Some("Alt+Shift+CmdOrCtrl+K"))?,
-            &MenuItem::with_id(app, "horizontal-line", "Horizontal Line", true, Some("Alt+CmdOrCtrl+-"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "footnote", "Footnote", true, None::<&str>)?,
-            &MenuItem::with_id(app, "collapsible-block", "Collapsible Block", true, None::<&str>)?,
-            &info_boxes_submenu,
-        ],
-    )?;
-
-    // ========================================================================
-    // View menu
-    // ========================================================================
-    let view_menu = Submenu::with_items(
-        app,
-        "View",
-        true,
-        &[
-            &MenuItem::with_id(app, "source-mode", "Source Code Mode", true, Some("F6"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "focus-mode", "Focus Mode", true, Some("F8"))?,
-            &MenuItem::with_id(app, "typewriter-mode", "Typewriter Mode", true, Some("F9"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "zoom-actual", "Actual Size", true, Some("CmdOrCtrl+0"))?,
-            &MenuItem::with_id(app, "zoom-in", "Zoom In", true, Some("CmdOrCtrl+="))?,
-            &MenuItem::with_id(app, "zoom-out", "Zoom Out", true, Some("CmdOrCtrl+-"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "word-wrap", "Toggle Word Wrap", true, Some("Alt+Z"))?,
-            &MenuItem::with_id(app, "line-numbers", "Toggle Line Numbers", true, Some("Alt+CmdOrCtrl+L"))?,
-            &MenuItem::with_id(app, "diagram-preview", "Toggle Diagram Preview", true, Some("Alt+CmdOrCtrl+P"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "sidebar", "Toggle Sidebar", true, Some("CmdOrCtrl+Shift+B"))?,
-            &MenuItem::with_id(app, "outline", "Toggle Outline", true, Some("Alt+CmdOrCtrl+1"))?,
-            &MenuItem::with_id(app, "toggle-terminal", "Toggle Terminal", true, Some("Ctrl+`"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &PredefinedMenuItem::fullscreen(app, Some("Enter Full Screen"))?,
-        ],
-    )?;
-
-    // ========================================================================
-    // Window menu
-    // ========================================================================
-    #[cfg(target_os = "macos")]
-    let window_menu = Submenu::with_items(
-        app,
-        "Window",
-        true,
-        &[
-            &PredefinedMenuItem::minimize(app, Some("Minimize"))?,
-            &PredefinedMenuItem::maximize(app, Some("Zoom"))?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "bring-all-to-front", "Bring All to Front", true, None::<&str>)?,
-        ],
-    )?;
-
-    #[cfg(not(target_os = "macos"))]
-    let window_menu = Submenu::with_items(
-        app,
-        "Window",
-        true,
-        &[
-            &PredefinedMenuItem::minimize(app, Some("Minimize"))?,
-            &PredefinedMenuItem::maximize(app, Some("Maximize"))?,
-        ],
-    )?;
-
-    // ========================================================================
-    // Help menu
-    // ========================================================================
-    #[cfg(target_os = "macos")]
-    let help_menu = Submenu::with_items(
-        app,
-        "Help",
-        true,
-        &[
-            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, None::<&str>)?,
-            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, None::<&str>)?,
-        ],
-    )?;
-
-    #[cfg(not(target_os = "macos"))]
-    let help_menu = Submenu::with_items(
-        app,
-        "Help",
-        true,
-        &[
-            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, None::<&str>)?,
-            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, None::<&str>)?,
-            &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "about", "About VMark", true, None::<&str>)?,
-        ],
-    )?;
-
-    // ========================================================================
-    // Assemble the menu bar
-    // ========================================================================
-    #[cfg(target_os = "macos")]
-    return Menu::with_items(
-        app,
-        &[
-            &app_menu,
-            &file_menu,
-            &edit_menu,
-            &format_menu,
-            &insert_menu,
-            &view_menu,
-            &window_menu,
-            &help_menu,
-        ],
-    );
-
-    #[cfg(not(target_os = "macos"))]
-    Menu::with_items(
-        app,
-        &[
-            &file_menu,
-            &edit_menu,
-            &format_menu,
-            &insert_menu,
-            &view_menu,
-            &window_menu,
-            &help_menu,
-        ],
-    )
+    create_menu_with_shortcuts(app, &crate::keymap::overrides())
 }
 
 /// Update the Open Recent submenu with the given list of file paths
@@ -580,6 +91,7 @@ pub fn update_recent_files_menu(app: &AppHandle, files: Vec<String>) -> tauri::R
     if let Ok(mut snapshot) = RECENT_FILES_SNAPSHOT.lock() {
         *snapshot = files.clone();
     }
+    crate::tray::refresh_recent_files(app, &files);
 
     let Some(menu) = app.menu() else {
         return Ok(());
@@ -634,9 +146,50 @@ pub fn update_recent_files_menu(app: &AppHandle, files: Vec<String>) -> tauri::R
 
 #[tauri::command]
 pub fn update_recent_files(app: AppHandle, files: Vec<String>) -> Result<(), String> {
+    // Persist so the next launch shows the list before any window loads
+    if let Ok(file) = recent_files_path(&app) {
+        if let Err(e) = save_recent_list(&file, &files) {
+            tracing::warn!("Failed to save recent files: {}", e);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    if files.is_empty() {
+        crate::dock_recent::clear_recent_documents();
+    }
+
     update_recent_files_menu(&app, files).map_err(|e| e.to_string())
 }
 
+fn recent_files_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(RECENT_FILES_FILE))
+}
+
+fn load_recent_list(file: &Path) -> Vec<String> {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_recent_list(file: &Path, files: &[String]) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(files)
+        .map_err(|e| format!("Failed to serialize recent files: {}", e))?;
+    crate::app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Populate Open Recent from the list saved by the last session.
+/// Called during setup, before the frontend syncs its recent files store.
+pub fn restore_recent_files_menu(app: &AppHandle) -> Result<(), String> {
+    let files = load_recent_list(&recent_files_path(app)?);
+    update_recent_files_menu(app, files).map_err(|e| e.to_string())
+}
+
 /// Update the Open Recent Workspace submenu with the given list of workspace paths
 pub fn update_recent_workspaces_menu(app: &AppHandle, workspaces: Vec<String>) -> tauri::Result<()> {
     if let Ok(mut snapshot) = RECENT_WORKSPACES_SNAPSHOT.lock() {
@@ -720,9 +273,22 @@ fn find_genies_submenu(parent: &Submenu<tauri::Wry>) -> Option<Submenu<tauri::Wr
 /// Creates the submenu dynamically inside Edit if it doesn't already exist.
 #[tauri::command]
 pub fn refresh_genies_menu(app: AppHandle) -> Result<(), String> {
+    rebuild_genies_menu(&app)
+}
+
+/// Whether the Genies submenu is currently part of the menu
+/// (the feature is enabled and a window has populated it).
+pub fn genies_menu_shown() -> bool {
+    GENIES_MENU_SHOWN.load(Ordering::SeqCst)
+}
+
+/// Build the Genies submenu from the genie files on disk: root-level genies
+/// flat, categorized genies grouped into submenus. Item ids map to genie
+/// paths through `GENIES_SNAPSHOT`.
+pub fn rebuild_genies_menu(app: &AppHandle) -> Result<(), String> {
     use crate::genies;
 
-    let global_dir = genies::global_genies_dir(&app)?;
+    let global_dir = genies::global_genies_dir(app)?;
     let global_entries = if global_dir.is_dir() {
         genies::scan_genies_with_titles(&global_dir)
     } else {
@@ -743,10 +309,10 @@ pub fn refresh_genies_menu(app: AppHandle) -> Result<(), String> {
         existing
     } else {
         // Create new submenu and append to Edit
-        let sep = PredefinedMenuItem::separator(&app).map_err(|e| e.to_string())?;
+        let sep = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
         edit_menu.append(&sep).map_err(|e| e.to_string())?;
         let new_sub = Submenu::with_id_and_items(
-            &app,
+            app,
             GENIES_SUBMENU_ID,
             "Genies",
             true,
@@ -757,31 +323,31 @@ pub fn refresh_genies_menu(app: AppHandle) -> Result<(), String> {
     };
 
     // "Search Genies…" at top — opens the picker (Cmd+Y)
-    let search_item = MenuItem::with_id(&app, "search-genies", "Search Genies…", true, Some("CmdOrCtrl+Y"))
+    let search_item = MenuItem::with_id(app, "search-genies", "Search Genies…", true, Some("CmdOrCtrl+Y"))
         .map_err(|e| e.to_string())?;
     submenu.append(&search_item).map_err(|e| e.to_string())?;
-    let sep = PredefinedMenuItem::separator(&app).map_err(|e| e.to_string())?;
+    let sep = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
     submenu.append(&sep).map_err(|e| e.to_string())?;
 
     if global_entries.is_empty() {
-        let no_genies = MenuItem::with_id(&app, "no-genies", "No Genies", false, None::<&str>)
+        let no_genies = MenuItem::with_id(app, "no-genies", "No Genies", false, None::<&str>)
             .map_err(|e| e.to_string())?;
         submenu.append(&no_genies).map_err(|e| e.to_string())?;
     } else {
-        append_genie_entries(&app, &submenu, &global_entries, &mut snapshot)?;
+        append_genie_entries(app, &submenu, &global_entries, &mut snapshot)?;
     }
 
     // Separator before folder action
-    let sep = PredefinedMenuItem::separator(&app).map_err(|e| e.to_string())?;
+    let sep = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
     submenu.append(&sep).map_err(|e| e.to_string())?;
 
     // Reload Genies
-    let reload = MenuItem::with_id(&app, "reload-genies", "Reload Genies", true, None::<&str>)
+    let reload = MenuItem::with_id(app, "reload-genies", "Reload Genies", true, None::<&str>)
         .map_err(|e| e.to_string())?;
     submenu.append(&reload).map_err(|e| e.to_string())?;
 
     // Open Genies Folder
-    let open_folder = MenuItem::with_id(&app, "open-genies-folder", "Open Genies Folder", true, None::<&str>)
+    let open_folder = MenuItem::with_id(app, "open-genies-folder", "Open Genies Folder", true, None::<&str>)
         .map_err(|e| e.to_string())?;
     submenu.append(&open_folder).map_err(|e| e.to_string())?;
 
@@ -789,6 +355,7 @@ pub fn refresh_genies_menu(app: AppHandle) -> Result<(), String> {
     if let Ok(mut s) = GENIES_SNAPSHOT.lock() {
         *s = snapshot;
     }
+    GENIES_MENU_SHOWN.store(true, Ordering::SeqCst);
 
     // Re-apply SF Symbol icons to cover newly added genie items
     #[cfg(target_os = "macos")]
@@ -829,6 +396,7 @@ pub fn hide_genies_menu(app: AppHandle) -> Result<(), String> {
     if let Ok(mut s) = GENIES_SNAPSHOT.lock() {
         s.clear();
     }
+    GENIES_MENU_SHOWN.store(false, Ordering::SeqCst);
 
     Ok(())
 }
@@ -886,27 +454,63 @@ fn append_genie_entries(
     Ok(())
 }
 
-/// Rebuild the application menu with custom keyboard shortcuts.
+/// Rebuild the application menu with custom keyboard shortcuts from settings.
 /// The shortcuts map is: menu_item_id -> accelerator_string (e.g., "bold" -> "CmdOrCtrl+B")
+///
+/// Settings edits are saved to keymap.json, so the menu has a single
+/// source of shortcuts that also applies on the next launch.
 #[tauri::command]
 pub fn rebuild_menu(app: AppHandle, shortcuts: HashMap<String, String>) -> Result<(), String> {
-    let menu = create_menu_with_shortcuts(&app, &shortcuts).map_err(|e| e.to_string())?;
+    crate::keymap::apply_settings_shortcuts(&shortcuts)?;
+    rebuild_menu_with_keymap(&app)
+}
+
+/// Rebuild the menu from the current keymap overrides.
+pub fn rebuild_menu_with_keymap(app: &AppHandle) -> Result<(), String> {
+    let menu = create_menu(app).map_err(|e| e.to_string())?;
     app.set_menu(menu).map_err(|e| e.to_string())?;
 
     #[cfg(target_os = "macos")]
     crate::macos_menu::apply_menu_fixes();
 
+    // The new menu has empty recent lists; re-populate them from the snapshots
+    let recent_files = RECENT_FILES_SNAPSHOT.lock().map(|s| s.clone()).unwrap_or_default();
+    update_recent_files_menu(app, recent_files).map_err(|e| e.to_string())?;
+    let recent_workspaces = RECENT_WORKSPACES_SNAPSHOT.lock().map(|s| s.clone()).unwrap_or_default();
+    update_recent_workspaces_menu(app, recent_workspaces).map_err(|e| e.to_string())?;
+
+    // The new menu has no Genies submenu; rebuild it if it was shown
+    if genies_menu_shown() {
+        rebuild_genies_menu(app)?;
+    }
+
+    // A new menu starts from defaults; restore the active window's state
+    crate::menu_state::reapply_window_state(app);
+
     Ok(())
 }
 
+/// Accelerators of the current menu, in menu order.
+pub fn menu_accelerators() -> Vec<MenuAccelerator> {
+    MENU_ACCELERATORS.lock().map(|a| a.clone()).unwrap_or_default()
+}
+
 /// Create menu with custom keyboard shortcuts
 fn create_menu_with_shortcuts(
     app: &AppHandle,
     shortcuts: &HashMap<String, String>,
 ) -> tauri::Result<Menu<tauri::Wry>> {
+    // Accelerators used by this menu, recorded for the keymap
+    let used: RefCell<Vec<MenuAccelerator>> = RefCell::new(Vec::new());
+
     // Helper to get shortcut for a menu item, falling back to default
     let get_accel = |id: &str, default: &str| -> Option<String> {
         let accel = shortcuts.get(id).map(|s| s.as_str()).unwrap_or(default);
+        used.borrow_mut().push(MenuAccelerator {
+            id: id.to_string(),
+            default: default.to_string(),
+            accelerator: accel.to_string(),
+        });
         if accel.is_empty() {
             None
         } else {
@@ -923,7 +527,7 @@ fn create_menu_with_shortcuts(
         "VMark",
         true,
         &[
-            &MenuItem::with_id(app, "about", "About VMark", true, None::<&str>)?,
+            &MenuItem::with_id(app, "about", "About VMark", true, get_accel("about", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "preferences", "Settings...", true, get_accel("preferences", "CmdOrCtrl+,"))?,
             &PredefinedMenuItem::separator(app)?,
@@ -949,7 +553,7 @@ fn create_menu_with_shortcuts(
         &[
             &MenuItem::with_id(app, "no-recent", "No Recent Files", false, None::<&str>)?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-recent", "Clear Recent Files", true, None::<&str>)?,
+            &MenuItem::with_id(app, "clear-recent", "Clear Recent Files", true, get_accel("clear-recent", ""))?,
         ],
     )?;
 
@@ -961,7 +565,7 @@ fn create_menu_with_shortcuts(
         &[
             &MenuItem::with_id(app, "no-recent-workspace", "No Recent Workspaces", false, None::<&str>)?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-recent-workspaces", "Clear Recent Workspaces", true, None::<&str>)?,
+            &MenuItem::with_id(app, "clear-recent-workspaces", "Clear Recent Workspaces", true, get_accel("clear-recent-workspaces", ""))?,
         ],
     )?;
 
@@ -971,9 +575,34 @@ fn create_menu_with_shortcuts(
         true,
         &[
             &MenuItem::with_id(app, "export-html", "HTML...", true, get_accel("export-html", "Alt+CmdOrCtrl+E"))?,
-            &MenuItem::with_id(app, "export-pdf", "Print...", true, get_accel("export-pdf", "CmdOrCtrl+P"))?,
+            &MenuItem::with_id(app, "export-pdf", "Print...", true, get_accel("export-pdf", ""))?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "live-preview", "Live Preview in Browser", true, get_accel("live-preview", ""))?,
+            &MenuItem::with_id(app, "live-preview-lan", "Share Live Preview on Network", true, get_accel("live-preview-lan", ""))?,
+            &MenuItem::with_id(app, "stop-live-preview", "Stop Live Preview", true, get_accel("stop-live-preview", ""))?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "export-odt", "OpenDocument (ODT)...", true, get_accel("export-odt", ""))?,
+            &MenuItem::with_id(app, "export-rst", "reStructuredText...", true, get_accel("export-rst", ""))?,
+            &MenuItem::with_id(app, "export-latex", "LaTeX...", true, get_accel("export-latex", ""))?,
+            &MenuItem::with_id(app, "export-mediawiki", "MediaWiki...", true, get_accel("export-mediawiki", ""))?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "export-tasks-ics", "Tasks to Calendar (ICS)", true, get_accel("export-tasks-ics", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "copy-html", "Copy as HTML", true, get_accel("copy-html", "CmdOrCtrl+Shift+C"))?,
+            &MenuItem::with_id(app, "copy-rich-text", "Copy as Rich Text", true, get_accel("copy-rich-text", ""))?,
+        ],
+    )?;
+
+    let publish_submenu = Submenu::with_items(
+        app,
+        "Publish",
+        true,
+        &[
+            &MenuItem::with_id(app, "publish-gist", "Publish to GitHub Gist", true, get_accel("publish-gist", ""))?,
+            &MenuItem::with_id(app, "publish-webhook", "Publish to Webhook", true, get_accel("publish-webhook", ""))?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "unpublish-gist", "Unpublish from GitHub Gist", true, get_accel("unpublish-gist", ""))?,
+            &MenuItem::with_id(app, "unpublish-webhook", "Unpublish from Webhook", true, get_accel("unpublish-webhook", ""))?,
         ],
     )?;
 
@@ -984,7 +613,7 @@ fn create_menu_with_shortcuts(
         &[
             &MenuItem::with_id(app, "view-history", "View History...", true, get_accel("view-history", "CmdOrCtrl+Shift+H"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "clear-history", "Clear History...", true, None::<&str>)?,
+            &MenuItem::with_id(app, "clear-history", "Clear History...", true, get_accel("clear-history", ""))?,
         ],
     )?;
 
@@ -999,19 +628,25 @@ fn create_menu_with_shortcuts(
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "open", "Open...", true, get_accel("open", "CmdOrCtrl+O"))?,
             &MenuItem::with_id(app, "open-folder", "Open Folder...", true, get_accel("open-folder", "CmdOrCtrl+Shift+O"))?,
+            &MenuItem::with_id(app, "quick-open", "Quick Open...", true, get_accel("quick-open", "CmdOrCtrl+P"))?,
+            &MenuItem::with_id(app, "import-file", "Import...", true, get_accel("import-file", ""))?,
             &recent_submenu,
             &recent_workspaces_submenu,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "close", "Close", true, get_accel("close", "CmdOrCtrl+W"))?,
-            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, None::<&str>)?,
+            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, get_accel("close-workspace", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "save", "Save", true, get_accel("save", "CmdOrCtrl+S"))?,
             &MenuItem::with_id(app, "save-as", "Save As...", true, get_accel("save-as", "CmdOrCtrl+Shift+S"))?,
             &MenuItem::with_id(app, "move-to", "Move to...", true, get_accel("move-to", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &export_submenu,
+            &publish_submenu,
             &PredefinedMenuItem::separator(app)?,
             &history_submenu,
+            &MenuItem::with_id(app, "workspace-backups", "Workspace Backups...", true, get_accel("workspace-backups", ""))?,
+            &MenuItem::with_id(app, "workspace-insights", "Workspace Insights...", true, get_accel("workspace-insights", ""))?,
+            &MenuItem::with_id(app, "ask-workspace", "Ask Workspace...", true, get_accel("ask-workspace", ""))?,
         ],
     )?;
 
@@ -1026,19 +661,25 @@ fn create_menu_with_shortcuts(
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "open", "Open...", true, get_accel("open", "CmdOrCtrl+O"))?,
             &MenuItem::with_id(app, "open-folder", "Open Folder...", true, get_accel("open-folder", "CmdOrCtrl+Shift+O"))?,
+            &MenuItem::with_id(app, "quick-open", "Quick Open...", true, get_accel("quick-open", "CmdOrCtrl+P"))?,
+            &MenuItem::with_id(app, "import-file", "Import...", true, get_accel("import-file", ""))?,
             &recent_submenu,
             &recent_workspaces_submenu,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "close", "Close", true, get_accel("close", "CmdOrCtrl+W"))?,
-            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, None::<&str>)?,
+            &MenuItem::with_id(app, "close-workspace", "Close Workspace", true, get_accel("close-workspace", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "save", "Save", true, get_accel("save", "CmdOrCtrl+S"))?,
             &MenuItem::with_id(app, "save-as", "Save As...", true, get_accel("save-as", "CmdOrCtrl+Shift+S"))?,
             &MenuItem::with_id(app, "move-to", "Move to...", true, get_accel("move-to", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &export_submenu,
+            &publish_submenu,
             &PredefinedMenuItem::separator(app)?,
             &history_submenu,
+            &MenuItem::with_id(app, "workspace-backups", "Workspace Backups...", true, get_accel("workspace-backups", ""))?,
+            &MenuItem::with_id(app, "workspace-insights", "Workspace Insights...", true, get_accel("workspace-insights", ""))?,
+            &MenuItem::with_id(app, "ask-workspace", "Ask Workspace...", true, get_accel("ask-workspace", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "preferences", "Settings...", true, get_accel("preferences", "CmdOrCtrl+,"))?,
             &PredefinedMenuItem::separator(app)?,
@@ -1068,9 +709,9 @@ fn create_menu_with_shortcuts(
         "Selection",
         true,
         &[
-            &MenuItem::with_id(app, "select-word", "Select Word", true, None::<&str>)?,
+            &MenuItem::with_id(app, "select-word", "Select Word", true, get_accel("select-word", ""))?,
             &MenuItem::with_id(app, "select-line", "Select Line", true, get_accel("select-line", "CmdOrCtrl+L"))?,
-            &MenuItem::with_id(app, "select-block", "Select Block", true, None::<&str>)?,
+            &MenuItem::with_id(app, "select-block", "Select Block", true, get_accel("select-block", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "expand-selection", "Expand Selection", true, get_accel("expand-selection", "Ctrl+Shift+Up"))?,
         ],
@@ -1099,8 +740,8 @@ fn create_menu_with_shortcuts(
         "Line Endings",
         true,
         &[
-            &MenuItem::with_id(app, "line-endings-lf", "Convert to LF", true, None::<&str>)?,
-            &MenuItem::with_id(app, "line-endings-crlf", "Convert to CRLF", true, None::<&str>)?,
+            &MenuItem::with_id(app, "line-endings-lf", "Convert to LF", true, get_accel("line-endings-lf", ""))?,
+            &MenuItem::with_id(app, "line-endings-crlf", "Convert to CRLF", true, get_accel("line-endings-crlf", ""))?,
         ],
     )?;
 
@@ -1157,7 +798,7 @@ fn create_menu_with_shortcuts(
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "indent", "Indent", true, get_accel("indent", "CmdOrCtrl+]"))?,
             &MenuItem::with_id(app, "outdent", "Outdent", true, get_accel("outdent", "CmdOrCtrl+["))?,
-            &MenuItem::with_id(app, "remove-list", "Remove List", true, None::<&str>)?,
+            &MenuItem::with_id(app, "remove-list", "Remove List", true, get_accel("remove-list", ""))?,
         ],
     )?;
 
@@ -1167,8 +808,8 @@ fn create_menu_with_shortcuts(
         true,
         &[
             &MenuItem::with_id(app, "quote", "Quote", true, get_accel("quote", "Alt+CmdOrCtrl+Q"))?,
-            &MenuItem::with_id(app, "nest-quote", "Nest Quote", true, None::<&str>)?,
-            &MenuItem::with_id(app, "unnest-quote", "Unnest Quote", true, None::<&str>)?,
+            &MenuItem::with_id(app, "nest-quote", "Nest Quote", true, get_accel("nest-quote", ""))?,
+            &MenuItem::with_id(app, "unnest-quote", "Unnest Quote", true, get_accel("unnest-quote", ""))?,
         ],
     )?;
 
@@ -1199,10 +840,12 @@ fn create_menu_with_shortcuts(
         "Text Cleanup",
         true,
         &[
-            &MenuItem::with_id(app, "remove-trailing-spaces", "Remove Trailing Spaces", true, None::<&str>)?,
-            &MenuItem::with_id(app, "collapse-blank-lines", "Collapse Blank Lines", true, None::<&str>)?,
+            &MenuItem::with_id(app, "remove-trailing-spaces", "Remove Trailing Spaces", true, get_accel("remove-trailing-spaces", ""))?,
+            &MenuItem::with_id(app, "collapse-blank-lines", "Collapse Blank Lines", true, get_accel("collapse-blank-lines", ""))?,
+            &MenuItem::with_id(app, "normalize-footnotes", "Normalize Footnotes", true, get_accel("normalize-footnotes", ""))?,
+            &MenuItem::with_id(app, "convert-flavor", "Convert Markdown Flavor...", true, get_accel("convert-flavor", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "cleanup-images", "Clean Up Unused Images...", true, None::<&str>)?,
+            &MenuItem::with_id(app, "cleanup-images", "Clean Up Unused Images...", true, get_accel("cleanup-images", ""))?,
         ],
     )?;
 
@@ -1243,8 +886,8 @@ fn create_menu_with_shortcuts(
         true,
         &[
             &MenuItem::with_id(app, "link", "Link", true, get_accel("link", "CmdOrCtrl+K"))?,
-            &MenuItem::with_id(app, "wiki-link", "Wiki Link", true, None::<&str>)?,
-            &MenuItem::with_id(app, "bookmark", "Bookmark", true, None::<&str>)?,
+            &MenuItem::with_id(app, "wiki-link", "Wiki Link", true, get_accel("wiki-link", ""))?,
+            &MenuItem::with_id(app, "bookmark", "Bookmark", true, get_accel("bookmark", ""))?,
         ],
     )?;
 
@@ -1254,23 +897,26 @@ fn create_menu_with_shortcuts(
         true,
         &[
             &MenuItem::with_id(app, "insert-table", "Insert Table", true, get_accel("insert-table", "CmdOrCtrl+Shift+T"))?,
+            &MenuItem::with_id(app, "import-csv-table", "Import CSV...", true, get_accel("import-csv-table", ""))?,
+            &MenuItem::with_id(app, "copy-table-csv", "Copy Table as CSV", true, get_accel("copy-table-csv", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "add-row-before", "Add Row Above", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-row-after", "Add Row Below", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-col-before", "Add Column Before", true, None::<&str>)?,
-            &MenuItem::with_id(app, "add-col-after", "Add Column After", true, None::<&str>)?,
+            &MenuItem::with_id(app, "add-row-before", "Add Row Above", true, get_accel("add-row-before", ""))?,
+            &MenuItem::with_id(app, "add-row-after", "Add Row Below", true, get_accel("add-row-after", ""))?,
+            &MenuItem::with_id(app, "add-col-before", "Add Column Before", true, get_accel("add-col-before", ""))?,
+            &MenuItem::with_id(app, "add-col-after", "Add Column After", true, get_accel("add-col-after", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "delete-row", "Delete Row", true, None::<&str>)?,
-            &MenuItem::with_id(app, "delete-col", "Delete Column", true, None::<&str>)?,
-            &MenuItem::with_id(app, "delete-table", "Delete Table", true, None::<&str>)?,
+            &MenuItem::with_id(app, "delete-row", "Delete Row", true, get_accel("delete-row", ""))?,
+            &MenuItem::with_id(app, "delete-col", "Delete Column", true, get_accel("delete-col", ""))?,
+            &MenuItem::with_id(app, "delete-selected-cells", "Delete Selected Cells", true, get_accel("delete-selected-cells", ""))?,
+            &MenuItem::with_id(app, "delete-table", "Delete Table", true, get_accel("delete-table", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "align-left", "Align Left", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-center", "Align Center", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-right", "Align Right", true, None::<&str>)?,
+            &MenuItem::with_id(app, "align-left", "Align Left", true, get_accel("align-left", ""))?,
+            &MenuItem::with_id(app, "align-center", "Align Center", true, get_accel("align-center", ""))?,
+            &MenuItem::with_id(app, "align-right", "Align Right", true, get_accel("align-right", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "align-all-left", "Align All Left", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-all-center", "Align All Center", true, None::<&str>)?,
-            &MenuItem::with_id(app, "align-all-right", "Align All Right", true, None::<&str>)?,
+            &MenuItem::with_id(app, "align-all-left", "Align All Left", true, get_accel("align-all-left", ""))?,
+            &MenuItem::with_id(app, "align-all-center", "Align All Center", true, get_accel("align-all-center", ""))?,
+            &MenuItem::with_id(app, "align-all-right", "Align All Right", true, get_accel("align-all-right", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "format-table", "Format Table", true, get_accel("format-table", "Alt+CmdOrCtrl+T"))?,
         ],
@@ -1296,6 +942,8 @@ fn create_menu_with_shortcuts(
         &[
             &links_submenu,
             &MenuItem::with_id(app, "image", "Image...", true, get_accel("image", "Shift+CmdOrCtrl+I"))?,
+            &MenuItem::with_id(app, "transcribe-audio", "Transcribe Audio...", true, get_accel("transcribe-audio", ""))?,
+            &MenuItem::with_id(app, "insert-symbol", "Emoji & Symbols...", true, get_accel("insert-symbol", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &table_submenu,
             &MenuItem::with_id(app, "code-fences", "Code Block", true, get_accel("code-fences", "Alt+CmdOrCtrl+C"))?,
@@ -1304,7 +952,7 @@ fn create_menu_with_shortcuts(
             &MenuItem::with_id(app, "mindmap", "Mindmap", true, get_accel("mindmap", "Alt+Shift+CmdOrCtrl+K"))?,
             &MenuItem::with_id(app, "horizontal-line", "Horizontal Line", true, get_accel("horizontal-line", "Alt+CmdOrCtrl+-"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "footnote", "Footnote", true, None::<&str>)?,
+            &MenuItem::with_id(app, "footnote", "Footnote", true, get_accel("footnote", ""))?,
             &MenuItem::with_id(app, "collapsible-block", "Collapsible Block", true, get_accel("collapsible-block", ""))?,
             &info_boxes_submenu,
         ],
@@ -1318,22 +966,23 @@ fn create_menu_with_shortcuts(
         "View",
         true,
         &[
-            &MenuItem::with_id(app, "source-mode", "Source Code Mode", true, get_accel("source-mode", "F6"))?,
+            &CheckMenuItem::with_id(app, "source-mode", "Source Code Mode", true, false, get_accel("source-mode", "F6"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "focus-mode", "Focus Mode", true, get_accel("focus-mode", "F8"))?,
-            &MenuItem::with_id(app, "typewriter-mode", "Typewriter Mode", true, get_accel("typewriter-mode", "F9"))?,
+            &CheckMenuItem::with_id(app, "focus-mode", "Focus Mode", true, false, get_accel("focus-mode", "F8"))?,
+            &CheckMenuItem::with_id(app, "typewriter-mode", "Typewriter Mode", true, false, get_accel("typewriter-mode", "F9"))?,
+            &MenuItem::with_id(app, "readability-report", "Readability Report...", true, get_accel("readability-report", ""))?,
             &PredefinedMenuItem::separator(app)?,
             &MenuItem::with_id(app, "zoom-actual", "Actual Size", true, get_accel("zoom-actual", "CmdOrCtrl+0"))?,
             &MenuItem::with_id(app, "zoom-in", "Zoom In", true, get_accel("zoom-in", "CmdOrCtrl+="))?,
             &MenuItem::with_id(app, "zoom-out", "Zoom Out", true, get_accel("zoom-out", "CmdOrCtrl+-"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "word-wrap", "Toggle Word Wrap", true, get_accel("word-wrap", "Alt+Z"))?,
-            &MenuItem::with_id(app, "line-numbers", "Toggle Line Numbers", true, get_accel("line-numbers", "Alt+CmdOrCtrl+L"))?,
-            &MenuItem::with_id(app, "diagram-preview", "Toggle Diagram Preview", true, get_accel("diagram-preview", "Alt+CmdOrCtrl+P"))?,
+            &CheckMenuItem::with_id(app, "word-wrap", "Toggle Word Wrap", true, false, get_accel("word-wrap", "Alt+Z"))?,
+            &CheckMenuItem::with_id(app, "line-numbers", "Toggle Line Numbers", true, false, get_accel("line-numbers", "Alt+CmdOrCtrl+L"))?,
+            &CheckMenuItem::with_id(app, "diagram-preview", "Toggle Diagram Preview", true, false, get_accel("diagram-preview", "Alt+CmdOrCtrl+P"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "sidebar", "Toggle Sidebar", true, get_accel("sidebar", "CmdOrCtrl+Shift+B"))?,
-            &MenuItem::with_id(app, "outline", "Toggle Outline", true, get_accel("outline", "Alt+CmdOrCtrl+1"))?,
-            &MenuItem::with_id(app, "toggle-terminal", "Toggle Terminal", true, get_accel("toggle-terminal", "Ctrl+`"))?,
+            &CheckMenuItem::with_id(app, "sidebar", "Toggle Sidebar", true, false, get_accel("sidebar", "CmdOrCtrl+Shift+B"))?,
+            &CheckMenuItem::with_id(app, "outline", "Toggle Outline", true, false, get_accel("outline", "Alt+CmdOrCtrl+1"))?,
+            &CheckMenuItem::with_id(app, "toggle-terminal", "Toggle Terminal", true, false, get_accel("toggle-terminal", "Ctrl+`"))?,
             &PredefinedMenuItem::separator(app)?,
             &PredefinedMenuItem::fullscreen(app, Some("Enter Full Screen"))?,
         ],
@@ -1351,7 +1000,20 @@ fn create_menu_with_shortcuts(
             &PredefinedMenuItem::minimize(app, Some("Minimize"))?,
             &PredefinedMenuItem::maximize(app, Some("Zoom"))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "bring-all-to-front", "Bring All to Front", true, None::<&str>)?,
+            &CheckMenuItem::with_id(app, "keep-on-top", "Keep on Top", true, false, get_accel("keep-on-top", ""))?,
+            &Submenu::with_items(
+                app,
+                "Opacity",
+                true,
+                &[
+                    &MenuItem::with_id(app, "opacity-100", "100%", true, get_accel("opacity-100", ""))?,
+                    &MenuItem::with_id(app, "opacity-80", "80%", true, get_accel("opacity-80", ""))?,
+                    &MenuItem::with_id(app, "opacity-60", "60%", true, get_accel("opacity-60", ""))?,
+                    &MenuItem::with_id(app, "opacity-40", "40%", true, get_accel("opacity-40", ""))?,
+                ],
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "bring-all-to-front", "Bring All to Front", true, get_accel("bring-all-to-front", ""))?,
         ],
     )?;
 
@@ -1363,6 +1025,8 @@ fn create_menu_with_shortcuts(
         &[
             &PredefinedMenuItem::minimize(app, Some("Minimize"))?,
             &PredefinedMenuItem::maximize(app, Some("Maximize"))?,
+            &PredefinedMenuItem::separator(app)?,
+            &CheckMenuItem::with_id(app, "keep-on-top", "Keep on Top", true, false, get_accel("keep-on-top", ""))?,
         ],
     )?;
 
@@ -1375,10 +1039,10 @@ fn create_menu_with_shortcuts(
         "Help",
         true,
         &[
-            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, None::<&str>)?,
-            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, None::<&str>)?,
+            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, get_accel("vmark-help", ""))?,
+            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, get_accel("keyboard-shortcuts", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, None::<&str>)?,
+            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, get_accel("report-issue", ""))?,
         ],
     )?;
 
@@ -1388,15 +1052,20 @@ fn create_menu_with_shortcuts(
         "Help",
         true,
         &[
-            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, None::<&str>)?,
-            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, None::<&str>)?,
+            &MenuItem::with_id(app, "vmark-help", "VMark Help", true, get_accel("vmark-help", ""))?,
+            &MenuItem::with_id(app, "keyboard-shortcuts", "Keyboard Shortcuts", true, get_accel("keyboard-shortcuts", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, None::<&str>)?,
+            &MenuItem::with_id(app, "report-issue", "Report an Issue...", true, get_accel("report-issue", ""))?,
             &PredefinedMenuItem::separator(app)?,
-            &MenuItem::with_id(app, "about", "About VMark", true, None::<&str>)?,
+            &MenuItem::with_id(app, "about", "About VMark", true, get_accel("about", ""))?,
         ],
     )?;
 
+    // Remember what was built so the keymap can report and validate shortcuts
+    if let Ok(mut accels) = MENU_ACCELERATORS.lock() {
+        *accels = used.into_inner();
+    }
+
     // ========================================================================
     // Assemble the menu bar
     // ========================================================================
@@ -1429,3 +1098,28 @@ fn create_menu_with_shortcuts(
         ],
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_list_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("nested").join(RECENT_FILES_FILE);
+        let files = vec!["/a/one.md".to_string(), "/b/two.md".to_string()];
+
+        save_recent_list(&file, &files).unwrap();
+        assert_eq!(load_recent_list(&file), files);
+    }
+
+    #[test]
+    fn missing_or_corrupt_recent_list_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(RECENT_FILES_FILE);
+        assert!(load_recent_list(&file).is_empty());
+
+        fs::write(&file, "not json").unwrap();
+        assert!(load_recent_list(&file).is_empty());
+    }
+}
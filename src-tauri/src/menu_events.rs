@@ -35,8 +35,7 @@ impl WindowReadyState {
 fn get_state() -> std::sync::MutexGuard<'static, Option<WindowReadyState>> {
     // Recover from poisoned mutex - state may be inconsistent but app won't crash
     WINDOW_READY_STATE.lock().unwrap_or_else(|poisoned| {
-        #[cfg(debug_assertions)]
-        eprintln!("[menu_events] WARNING: Mutex was poisoned, recovering");
+        tracing::debug!("Mutex was poisoned, recovering");
         poisoned.into_inner()
     })
 }
@@ -54,9 +53,8 @@ pub fn mark_window_ready(app: &AppHandle, label: &str) {
     // Emit pending events outside the lock
     if let Some(window) = app.get_webview_window(label) {
         for event in &pending {
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "[menu_events] Flushing pending event '{}' to window '{}'",
+            tracing::debug!(
+                "Flushing pending event '{}' to window '{}'",
                 event.event_name, label
             );
             emit_event(&window, event);
@@ -153,20 +151,17 @@ fn emit_event(window: &tauri::WebviewWindow, event: &PendingMenuEvent) {
 /// This is race-condition safe: check and queue happen in a single lock acquisition.
 fn emit_or_queue_atomic(window: &tauri::WebviewWindow, event: PendingMenuEvent) {
     let label = window.label();
-    #[cfg(debug_assertions)]
     let event_name = event.event_name.clone(); // For logging
 
     if check_ready_or_queue(label, event.clone()) {
-        #[cfg(debug_assertions)]
-        eprintln!(
-            "[menu_events] Window '{}' is ready, emitting '{}' directly",
+        tracing::debug!(
+            "Window '{}' is ready, emitting '{}' directly",
             label, event_name
         );
         emit_event(window, &event);
     } else {
-        #[cfg(debug_assertions)]
-        eprintln!(
-            "[menu_events] Window '{}' not ready, queued '{}'",
+        tracing::debug!(
+            "Window '{}' not ready, queued '{}'",
             label, event_name
         );
     }
@@ -200,9 +195,8 @@ fn make_recent_workspace_event(path: &str) -> PendingMenuEvent {
 /// The event will be emitted when the window becomes ready.
 fn create_window_and_queue(app: &AppHandle, event: PendingMenuEvent) {
     if let Ok(label) = crate::window_manager::create_document_window(app, None, None) {
-        #[cfg(debug_assertions)]
-        eprintln!(
-            "[menu_events] Created window '{}', queueing event '{}'",
+        tracing::debug!(
+            "Created window '{}', queueing event '{}'",
             label, event.event_name
         );
         queue_event(&label, event);
@@ -212,6 +206,16 @@ fn create_window_and_queue(app: &AppHandle, event: PendingMenuEvent) {
 pub fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     let id = event.id().as_ref();
 
+    // Native context menus answer the window that opened them
+    if crate::context_menu::handle_context_menu_event(id) {
+        return;
+    }
+
+    // Tray menu items
+    if crate::tray::handle_tray_menu_event(app, id) {
+        return;
+    }
+
     // Custom Quit (Cmd+Q) is handled in Rust so we can coordinate unsaved-changes prompts.
     if id == "quit" {
         quit::start_quit(app);
@@ -299,6 +303,26 @@ pub fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
         return;
     }
 
+    // Window pinning applies to whichever window is focused, viewers included
+    if id == "keep-on-top" {
+        if let Some(focused) = get_focused_window(app) {
+            let on_top = !focused.is_always_on_top().unwrap_or(false);
+            if let Err(e) = crate::window_manager::pin_window(app, focused.label(), on_top) {
+                tracing::warn!("Failed to pin window: {}", e);
+            }
+        }
+        return;
+    }
+    if let Some(percent) = id.strip_prefix("opacity-") {
+        if let (Ok(percent), Some(focused)) = (percent.parse::<u32>(), get_focused_window(app)) {
+            let opacity = f64::from(percent) / 100.0;
+            if let Err(e) = crate::window_manager::fade_window(app, focused.label(), opacity) {
+                tracing::warn!("Failed to set window opacity: {}", e);
+            }
+        }
+        return;
+    }
+
     // "new-window" creates a new window directly in Rust
     if id == "new-window" {
         let _ = crate::window_manager::create_document_window(app, None, None);
@@ -311,15 +335,13 @@ pub fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     // - No Settings window exists
     // - No document windows exist
     if id == "preferences" {
-        #[cfg(debug_assertions)]
-        eprintln!("[menu_events] Handling 'preferences' menu event");
+        tracing::debug!("Handling 'preferences' menu event");
         match crate::window_manager::show_settings_window(app) {
             Ok(_label) => {
-                #[cfg(debug_assertions)]
-                eprintln!("[menu_events] Settings window ready: {}", _label);
+                tracing::debug!("Settings window ready: {}", _label);
             }
             Err(e) => {
-                eprintln!("[menu_events] ERROR: Failed to show settings: {}", e);
+                tracing::error!("Failed to show settings: {}", e);
             }
         }
         return;
@@ -327,15 +349,13 @@ pub fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
 
     // "about" - open Settings window at About section
     if id == "about" {
-        #[cfg(debug_assertions)]
-        eprintln!("[menu_events] Handling 'about' menu event");
+        tracing::debug!("Handling 'about' menu event");
         match crate::window_manager::show_settings_window_section(app, Some("about")) {
             Ok(_label) => {
-                #[cfg(debug_assertions)]
-                eprintln!("[menu_events] Settings window (about) ready: {}", _label);
+                tracing::debug!("Settings window (about) ready: {}", _label);
             }
             Err(e) => {
-                eprintln!("[menu_events] ERROR: Failed to show about: {}", e);
+                tracing::error!("Failed to show about: {}", e);
             }
         }
         return;
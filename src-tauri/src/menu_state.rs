@@ -0,0 +1,274 @@
+//! Dynamic native menu state - enabled flags and checkmarks.
+//!
+//! The native menu is shared by every window, but items like Save or
+//! Focus Mode reflect the state of one document window. Each window reports
+//! its item state here; the state of the focused window is applied to the
+//! menu whenever focus changes or the menu is rebuilt.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::menu::{Menu, MenuItemKind};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Item state reported by one window
+#[derive(Debug, Clone, Default, PartialEq)]
+struct WindowMenuState {
+    enabled: HashMap<String, bool>,
+    checked: HashMap<String, bool>,
+}
+
+/// Menu item state, keyed by window label.
+static MENU_STATE: Mutex<Option<HashMap<String, WindowMenuState>>> = Mutex::new(None);
+
+/// Window whose state the menu currently shows.
+static ACTIVE_WINDOW: Mutex<Option<String>> = Mutex::new(None);
+
+fn menu_state() -> std::sync::MutexGuard<'static, Option<HashMap<String, WindowMenuState>>> {
+    MENU_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Item id -> value pairs, sorted by id
+type ItemValues = Vec<(String, bool)>;
+
+/// Values to apply to the menu for `label`.
+///
+/// Items another window has changed are included with their default
+/// (enabled, unchecked) so one window's state doesn't leak into the next.
+fn resolve_state(
+    states: &HashMap<String, WindowMenuState>,
+    label: &str,
+) -> (ItemValues, ItemValues) {
+    let own = states.get(label);
+    let mut enabled: HashMap<String, bool> = HashMap::new();
+    let mut checked: HashMap<String, bool> = HashMap::new();
+
+    for state in states.values() {
+        for id in state.enabled.keys() {
+            enabled.insert(id.clone(), true);
+        }
+        for id in state.checked.keys() {
+            checked.insert(id.clone(), false);
+        }
+    }
+    if let Some(own) = own {
+        enabled.extend(own.enabled.iter().map(|(k, v)| (k.clone(), *v)));
+        checked.extend(own.checked.iter().map(|(k, v)| (k.clone(), *v)));
+    }
+
+    let mut enabled: Vec<_> = enabled.into_iter().collect();
+    let mut checked: Vec<_> = checked.into_iter().collect();
+    enabled.sort();
+    checked.sort();
+    (enabled, checked)
+}
+
+/// Find a menu item by id, searching submenus recursively.
+fn find_item<R: Runtime>(items: Vec<MenuItemKind<R>>, id: &str) -> Option<MenuItemKind<R>> {
+    for item in items {
+        if *item.id() == id {
+            return Some(item);
+        }
+        if let MenuItemKind::Submenu(submenu) = &item {
+            if let Some(found) = submenu.items().ok().and_then(|items| find_item(items, id)) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+fn set_enabled<R: Runtime>(menu: &Menu<R>, id: &str, enabled: bool) {
+    let Some(item) = menu.items().ok().and_then(|items| find_item(items, id)) else {
+        return;
+    };
+    let _ = match item {
+        MenuItemKind::MenuItem(i) => i.set_enabled(enabled),
+        MenuItemKind::Check(i) => i.set_enabled(enabled),
+        MenuItemKind::Icon(i) => i.set_enabled(enabled),
+        MenuItemKind::Submenu(i) => i.set_enabled(enabled),
+        MenuItemKind::Predefined(_) => Ok(()),
+    };
+}
+
+fn set_checked<R: Runtime>(menu: &Menu<R>, id: &str, checked: bool) {
+    if let Some(MenuItemKind::Check(item)) = menu.items().ok().and_then(|items| find_item(items, id)) {
+        let _ = item.set_checked(checked);
+    }
+}
+
+/// Whether `label` is the window whose state the menu shows.
+fn is_active_window(app: &AppHandle, label: &str) -> bool {
+    let active = ACTIVE_WINDOW.lock().ok().and_then(|guard| guard.clone());
+    match active {
+        Some(active) => active == label,
+        None => app
+            .get_webview_window(label)
+            .and_then(|w| w.is_focused().ok())
+            .unwrap_or(false),
+    }
+}
+
+/// Apply the state reported by `label` to the application menu.
+/// Called when a document window gains focus.
+pub fn apply_window_state(app: &AppHandle, label: &str) {
+    if let Ok(mut active) = ACTIVE_WINDOW.lock() {
+        *active = Some(label.to_string());
+    }
+    let Some(menu) = app.menu() else {
+        return;
+    };
+    let (enabled, checked) = {
+        let guard = menu_state();
+        match guard.as_ref() {
+            Some(states) => resolve_state(states, label),
+            None => return,
+        }
+    };
+    for (id, value) in enabled {
+        set_enabled(&menu, &id, value);
+    }
+    for (id, value) in checked {
+        set_checked(&menu, &id, value);
+    }
+}
+
+/// Re-apply the state of the window last applied (e.g. after `rebuild_menu`).
+/// Settings may be focused at that point, so focus alone isn't enough.
+pub fn reapply_window_state(app: &AppHandle) {
+    let label = ACTIVE_WINDOW.lock().ok().and_then(|guard| guard.clone());
+    if let Some(label) = label {
+        apply_window_state(app, &label);
+    }
+}
+
+/// Forget a window's state. Called from the `WindowEvent::Destroyed` handler.
+pub fn clear_window_state(label: &str) {
+    if let Some(states) = menu_state().as_mut() {
+        states.remove(label);
+    }
+    if let Ok(mut active) = ACTIVE_WINDOW.lock() {
+        if active.as_deref() == Some(label) {
+            *active = None;
+        }
+    }
+}
+
+fn record_enabled(label: &str, id: &str, enabled: bool) {
+    menu_state()
+        .get_or_insert_with(HashMap::new)
+        .entry(label.to_string())
+        .or_default()
+        .enabled
+        .insert(id.to_string(), enabled);
+}
+
+fn record_checked(label: &str, id: &str, checked: bool) {
+    menu_state()
+        .get_or_insert_with(HashMap::new)
+        .entry(label.to_string())
+        .or_default()
+        .checked
+        .insert(id.to_string(), checked);
+}
+
+/// Enable or disable a menu item for the calling window.
+/// The menu updates immediately if it currently shows that window's state.
+#[tauri::command]
+pub fn set_menu_item_enabled(
+    app: AppHandle,
+    window: tauri::Window,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let label = window.label();
+    record_enabled(label, &id, enabled);
+    if is_active_window(&app, label) {
+        if let Some(menu) = app.menu() {
+            set_enabled(&menu, &id, enabled);
+        }
+    }
+    Ok(())
+}
+
+/// Check or uncheck a menu item for the calling window.
+/// The menu updates immediately if it currently shows that window's state.
+#[tauri::command]
+pub fn set_menu_item_checked(
+    app: AppHandle,
+    window: tauri::Window,
+    id: String,
+    checked: bool,
+) -> Result<(), String> {
+    set_window_item_checked(&app, window.label(), &id, checked);
+    Ok(())
+}
+
+/// Check or uncheck a menu item for `label` from Rust-handled menu actions.
+pub fn set_window_item_checked(app: &AppHandle, label: &str, id: &str, checked: bool) {
+    record_checked(label, id, checked);
+    if is_active_window(app, label) {
+        if let Some(menu) = app.menu() {
+            set_checked(&menu, id, checked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(enabled: &[(&str, bool)], checked: &[(&str, bool)]) -> WindowMenuState {
+        WindowMenuState {
+            enabled: enabled.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            checked: checked.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn resolves_own_state() {
+        let mut states = HashMap::new();
+        states.insert("main".to_string(), state(&[("save", false)], &[("focus-mode", true)]));
+
+        let (enabled, checked) = resolve_state(&states, "main");
+        assert_eq!(enabled, vec![("save".to_string(), false)]);
+        assert_eq!(checked, vec![("focus-mode".to_string(), true)]);
+    }
+
+    #[test]
+    fn other_windows_state_resets_to_defaults() {
+        let mut states = HashMap::new();
+        states.insert("main".to_string(), state(&[("save", false)], &[("focus-mode", true)]));
+        states.insert("doc-1".to_string(), state(&[], &[("sidebar", true)]));
+
+        let (enabled, checked) = resolve_state(&states, "doc-1");
+        assert_eq!(enabled, vec![("save".to_string(), true)]);
+        assert_eq!(
+            checked,
+            vec![("focus-mode".to_string(), false), ("sidebar".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn unknown_window_gets_defaults() {
+        let mut states = HashMap::new();
+        states.insert("main".to_string(), state(&[("save", false)], &[]));
+
+        let (enabled, checked) = resolve_state(&states, "settings");
+        assert_eq!(enabled, vec![("save".to_string(), true)]);
+        assert!(checked.is_empty());
+    }
+
+    #[test]
+    fn record_and_clear() {
+        record_enabled("doc-menu", "save", false);
+        record_checked("doc-menu", "outline", true);
+        {
+            let guard = menu_state();
+            let entry = &guard.as_ref().unwrap()["doc-menu"];
+            assert_eq!(entry, &state(&[("save", false)], &[("outline", true)]));
+        }
+        clear_window_state("doc-menu");
+        assert!(!menu_state().as_ref().unwrap().contains_key("doc-menu"));
+    }
+}
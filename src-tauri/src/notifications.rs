@@ -0,0 +1,543 @@
+//! Notifications and Reminders
+//!
+//! System notifications with action buttons ("Open note", "Snooze",
+//! "Dismiss"). On Linux the buttons are real (freedesktop notifications via
+//! notify-rust) and a press is reported as `notification:action`. macOS and
+//! Windows notifications shown through the notification plugin can't carry
+//! buttons, so there the notification is also emitted as
+//! `notification:shown` and the frontend offers the actions in-app.
+//!
+//! Reminders come from `@remind(2026-05-01 09:30)` markers in notes. The
+//! frontend rescans a note when it is saved; pending reminders are kept in
+//! `<app_data>/reminders.json`, so ones that came due while VMark was closed
+//! fire on the next launch.
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_paths;
+
+const REMINDERS_FILE: &str = "reminders.json";
+
+const REMIND_MARKER: &str = "@remind(";
+
+/// Time of day for reminders given only a date
+const DEFAULT_REMIND_TIME: (u32, u32) = (9, 0);
+
+const CHECK_INTERVAL_SECS: u64 = 30;
+const DEFAULT_SNOOZE_MINUTES: u32 = 10;
+
+/// Fired reminder ids are remembered this long, so a rescan of a note that
+/// still holds an old `@remind` doesn't fire it again
+const FIRED_RETENTION_MS: i64 = 90 * 24 * 60 * 60 * 1000;
+
+pub const ACTION_OPEN: &str = "open";
+pub const ACTION_SNOOZE: &str = "snooze";
+pub const ACTION_DISMISS: &str = "dismiss";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationAction {
+    pub id: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRequest {
+    /// Reported back with action presses; generated when absent
+    #[serde(default)]
+    pub id: Option<String>,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
+    /// Note the notification is about (for "Open note")
+    #[serde(default)]
+    pub note_path: Option<String>,
+}
+
+/// Payload for `notification:action`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationActionEvent {
+    pub notification_id: String,
+    /// Action id, or `default` when the notification itself was clicked
+    pub action_id: String,
+    pub note_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminder {
+    pub id: String,
+    pub note_path: String,
+    pub text: String,
+    /// Unix timestamp ms
+    pub due_at: i64,
+    /// 1-based line of the `@remind` marker
+    pub line: usize,
+    /// Rescheduled from a notification; kept when the note is rescanned
+    #[serde(default)]
+    pub snoozed: bool,
+}
+
+/// Contents of the reminders file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ReminderStore {
+    pending: Vec<Reminder>,
+    /// Fired reminders by id
+    fired: BTreeMap<String, Reminder>,
+}
+
+struct ReminderState {
+    file: PathBuf,
+    store: ReminderStore,
+}
+
+static REMINDER_STATE: OnceLock<Mutex<ReminderState>> = OnceLock::new();
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// `2026-05-01`, `2026-05-01 09:30` or `2026-05-01T09:30`, in local time.
+fn parse_remind_time(spec: &str) -> Option<i64> {
+    let spec = spec.trim();
+    let naive = NaiveDateTime::parse_from_str(spec, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(spec, "%Y-%m-%dT%H:%M"))
+        .ok()
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(spec, "%Y-%m-%d").ok()?;
+            let (h, m) = DEFAULT_REMIND_TIME;
+            Some(date.and_time(NaiveTime::from_hms_opt(h, m, 0)?))
+        })?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// `(start, end)` byte ranges of the `@remind(...)` markers in a line.
+fn find_markers(line: &str) -> Vec<(usize, usize)> {
+    let mut markers = Vec::new();
+    let mut search = 0;
+    while let Some(found) = line[search..].find(REMIND_MARKER) {
+        let start = search + found;
+        let Some(close) = line[start..].find(')') else {
+            break;
+        };
+        let end = start + close + 1;
+        markers.push((start, end));
+        search = end;
+    }
+    markers
+}
+
+/// The line without its markers and list/task/heading syntax.
+fn reminder_text(line: &str, markers: &[(usize, usize)]) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut last = 0;
+    for &(start, end) in markers {
+        text.push_str(&line[last..start]);
+        last = end;
+    }
+    text.push_str(&line[last..]);
+    let mut text = text.trim();
+    for prefix in ["- [ ] ", "* [ ] ", "- [x] ", "- ", "* ", "+ "] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = rest;
+            break;
+        }
+    }
+    let text = text.trim_start_matches('#').trim();
+    // Ordered list item: `1. text`
+    let text = match text.split_once(". ") {
+        Some((n, rest)) if !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => text,
+    };
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn reminder_id(note_path: &str, due_at: i64, text: &str) -> String {
+    crate::watcher::content_hash(format!("{note_path}\n{due_at}\n{text}").as_bytes())[..16].to_string()
+}
+
+/// All `@remind(...)` reminders in a note, skipping fenced code.
+fn parse_reminders(note_path: &str, content: &str) -> Vec<Reminder> {
+    let fallback = Path::new(note_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut reminders = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let markers = find_markers(line);
+        if markers.is_empty() {
+            continue;
+        }
+        let mut text = reminder_text(line, &markers);
+        if text.is_empty() {
+            // A marker alone on its line is about the note itself
+            text = fallback.clone();
+        }
+        for &(start, end) in &markers {
+            let Some(due_at) = parse_remind_time(&line[start + REMIND_MARKER.len()..end - 1]) else {
+                continue;
+            };
+            reminders.push(Reminder {
+                id: reminder_id(note_path, due_at, &text),
+                note_path: note_path.to_string(),
+                text: text.clone(),
+                due_at,
+                line: index + 1,
+                snoozed: false,
+            });
+        }
+    }
+    reminders
+}
+
+// ============================================================================
+// Store
+// ============================================================================
+
+/// Replace a note's pending reminders with the ones found in it now.
+fn rescan_note(store: &mut ReminderStore, note_path: &str, found: Vec<Reminder>) {
+    store.pending.retain(|r| r.note_path != note_path || r.snoozed);
+    for reminder in found {
+        let known = store.fired.contains_key(&reminder.id) || store.pending.iter().any(|r| r.id == reminder.id);
+        if !known {
+            store.pending.push(reminder);
+        }
+    }
+    store.pending.sort_by_key(|r| r.due_at);
+}
+
+/// Move reminders due at `now` to the fired list and return them.
+fn take_due(store: &mut ReminderStore, now: i64) -> Vec<Reminder> {
+    let (due, pending): (Vec<_>, Vec<_>) = store.pending.drain(..).partition(|r| r.due_at <= now);
+    store.pending = pending;
+    for reminder in &due {
+        store.fired.insert(reminder.id.clone(), reminder.clone());
+    }
+    store.fired.retain(|_, r| now - r.due_at < FIRED_RETENTION_MS);
+    due
+}
+
+fn snooze(store: &mut ReminderStore, reminder: Reminder, minutes: u32, now: i64) {
+    store.pending.retain(|r| r.id != reminder.id);
+    store.pending.push(Reminder {
+        due_at: now + i64::from(minutes.max(1)) * 60_000,
+        snoozed: true,
+        ..reminder
+    });
+    store.pending.sort_by_key(|r| r.due_at);
+}
+
+fn load_store(file: &Path) -> ReminderStore {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_store(file: &Path, store: &ReminderStore) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(store).map_err(|e| format!("Failed to serialize reminders: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+fn with_store<T>(f: impl FnOnce(&mut ReminderStore) -> T) -> Result<T, String> {
+    let state = REMINDER_STATE.get().ok_or("Reminders not initialized")?;
+    let mut state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let result = f(&mut state.store);
+    save_store(&state.file, &state.store)?;
+    Ok(result)
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+// ============================================================================
+// Showing notifications
+// ============================================================================
+
+fn on_action(app: &AppHandle, request: &NotificationRequest, action_id: &str) {
+    let notification_id = request.id.clone().unwrap_or_default();
+    match action_id {
+        "__closed" | ACTION_DISMISS => {}
+        ACTION_SNOOZE => {
+            let _ = snooze_reminder(notification_id, None);
+        }
+        _ => {
+            let _ = app.emit(
+                "notification:action",
+                NotificationActionEvent {
+                    notification_id,
+                    action_id: action_id.to_string(),
+                    note_path: request.note_path.clone(),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show_native(app: &AppHandle, request: &NotificationRequest) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.summary(&request.title).body(&request.body).auto_icon();
+    for action in &request.actions {
+        notification.action(&action.id, &action.label);
+    }
+    let handle = notification
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    let app = app.clone();
+    let request = request.clone();
+    // Blocks until the notification is acted on or closed
+    std::thread::spawn(move || handle.wait_for_action(|action| on_action(&app, &request, action)));
+    Ok(())
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn show_native(app: &AppHandle, request: &NotificationRequest) -> Result<(), String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    app.notification()
+        .builder()
+        .title(&request.title)
+        .body(&request.body)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+    // No buttons on these platforms; the frontend offers the actions in-app
+    let _ = app.emit("notification:shown", request);
+    Ok(())
+}
+
+fn reminder_notification(reminder: &Reminder) -> NotificationRequest {
+    let note = Path::new(&reminder.note_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let action = |id: &str, label: &str| NotificationAction {
+        id: id.to_string(),
+        label: label.to_string(),
+    };
+    NotificationRequest {
+        id: Some(reminder.id.clone()),
+        title: format!("Reminder · {}", note),
+        body: reminder.text.clone(),
+        actions: vec![
+            action(ACTION_OPEN, "Open note"),
+            action(ACTION_SNOOZE, "Snooze 10 min"),
+            action(ACTION_DISMISS, "Dismiss"),
+        ],
+        note_path: Some(reminder.note_path.clone()),
+    }
+}
+
+fn fire_due_reminders(app: &AppHandle) {
+    let due = match with_store(|store| take_due(store, now_ms())) {
+        Ok(due) => due,
+        Err(e) => {
+            tracing::warn!("[Reminders] {}", e);
+            return;
+        }
+    };
+    for reminder in due {
+        tracing::info!("[Reminders] Firing reminder in {}", reminder.note_path);
+        if let Err(e) = show_native(app, &reminder_notification(&reminder)) {
+            tracing::warn!("[Reminders] {}", e);
+        }
+        let _ = app.emit("reminders:fired", &reminder);
+    }
+}
+
+/// Load pending reminders and start checking for due ones. Called once
+/// during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(REMINDERS_FILE);
+    let store = load_store(&file);
+    let _ = REMINDER_STATE.set(Mutex::new(ReminderState { file, store }));
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            fire_due_reminders(&app);
+            tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Show a notification now. Action presses arrive as `notification:action`.
+#[tauri::command]
+pub fn show_notification(app: AppHandle, mut request: NotificationRequest) -> Result<String, String> {
+    let id = request
+        .id
+        .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+        .clone();
+    show_native(&app, &request)?;
+    Ok(id)
+}
+
+/// Update a note's reminders from its content (call after saving it).
+/// Returns the note's pending reminders.
+#[tauri::command]
+pub fn scan_note_reminders(path: String, content: String) -> Result<Vec<Reminder>, String> {
+    let found = parse_reminders(&path, &content);
+    with_store(|store| {
+        rescan_note(store, &path, found);
+        store.pending.iter().filter(|r| r.note_path == path).cloned().collect()
+    })
+}
+
+/// Forget the reminders of a deleted note.
+#[tauri::command]
+pub fn clear_note_reminders(path: String) -> Result<(), String> {
+    with_store(|store| store.pending.retain(|r| r.note_path != path))
+}
+
+/// All pending reminders, soonest first.
+#[tauri::command]
+pub fn list_reminders() -> Result<Vec<Reminder>, String> {
+    let state = REMINDER_STATE.get().ok_or("Reminders not initialized")?;
+    let state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(state.store.pending.clone())
+}
+
+/// Fire a reminder again in `minutes` (default 10). Works for pending and
+/// just-fired reminders.
+#[tauri::command]
+pub fn snooze_reminder(id: String, minutes: Option<u32>) -> Result<(), String> {
+    let state = REMINDER_STATE.get().ok_or("Reminders not initialized")?;
+    let mut state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let reminder = state
+        .store
+        .pending
+        .iter()
+        .find(|r| r.id == id)
+        .or_else(|| state.store.fired.get(&id))
+        .cloned()
+        .ok_or_else(|| format!("No reminder with id {}", id))?;
+    snooze(&mut state.store, reminder, minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES), now_ms());
+    save_store(&state.file, &state.store)
+}
+
+/// Drop a pending reminder without firing it.
+#[tauri::command]
+pub fn dismiss_reminder(id: String) -> Result<(), String> {
+    with_store(|store| {
+        if let Some(index) = store.pending.iter().position(|r| r.id == id) {
+            let reminder = store.pending.remove(index);
+            store.fired.insert(reminder.id.clone(), reminder);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(spec: &str) -> i64 {
+        parse_remind_time(spec).unwrap()
+    }
+
+    #[test]
+    fn test_parse_remind_time() {
+        assert_eq!(at("2026-05-01"), at("2026-05-01 09:00"));
+        assert_eq!(at("2026-05-01T14:30"), at("2026-05-01 14:30"));
+        assert_eq!(at("2026-05-01 14:30") - at("2026-05-01 14:00"), 30 * 60_000);
+        assert_eq!(parse_remind_time("tomorrow"), None);
+    }
+
+    #[test]
+    fn test_parse_reminders() {
+        let content = "# Plan\n\
+            - [ ] Call the printer @remind(2026-05-01 10:00)\n\
+            ```\n@remind(2026-05-02)\n```\n\
+            @remind(2026-05-03) @remind(not a date)\n";
+        let reminders = parse_reminders("/notes/Plan.md", content);
+        assert_eq!(reminders.len(), 2);
+        assert_eq!(reminders[0].text, "Call the printer");
+        assert_eq!(reminders[0].line, 2);
+        assert_eq!(reminders[0].due_at, at("2026-05-01 10:00"));
+        // Markers alone on their line are about the note itself
+        assert_eq!(reminders[1].text, "Plan");
+        assert_eq!(reminders[1].line, 6);
+    }
+
+    #[test]
+    fn test_reminder_text_strips_list_syntax() {
+        let line = "12. Send draft @remind(2026-05-01) to Sam";
+        assert_eq!(reminder_text(line, &find_markers(line)), "Send draft to Sam");
+    }
+
+    #[test]
+    fn test_rescan_does_not_refire() {
+        let mut store = ReminderStore::default();
+        let found = parse_reminders("/a.md", "x @remind(2026-05-01)\ny @remind(2026-06-01)");
+        rescan_note(&mut store, "/a.md", found.clone());
+        assert_eq!(store.pending.len(), 2);
+
+        let fired = take_due(&mut store, at("2026-05-02"));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].text, "x");
+
+        rescan_note(&mut store, "/a.md", found);
+        assert_eq!(store.pending.len(), 1);
+        assert_eq!(store.pending[0].text, "y");
+
+        // Removing the marker removes the reminder
+        rescan_note(&mut store, "/a.md", Vec::new());
+        assert!(store.pending.is_empty());
+    }
+
+    #[test]
+    fn test_snoozed_survives_rescan() {
+        let mut store = ReminderStore::default();
+        let found = parse_reminders("/a.md", "x @remind(2026-05-01)");
+        rescan_note(&mut store, "/a.md", found.clone());
+        let fired = take_due(&mut store, at("2026-05-02")).remove(0);
+
+        snooze(&mut store, fired, 10, at("2026-05-02"));
+        rescan_note(&mut store, "/a.md", found);
+        assert_eq!(store.pending.len(), 1);
+        assert_eq!(store.pending[0].due_at, at("2026-05-02") + 10 * 60_000);
+        assert_eq!(take_due(&mut store, at("2026-05-02 09:10")).len(), 1);
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(REMINDERS_FILE);
+        let mut store = load_store(&file);
+        rescan_note(&mut store, "/a.md", parse_reminders("/a.md", "x @remind(2026-05-01)"));
+        save_store(&file, &store).unwrap();
+        assert_eq!(load_store(&file).pending, store.pending);
+    }
+}
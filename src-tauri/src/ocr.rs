@@ -0,0 +1,413 @@
+//! OCR (Text Recognition)
+//!
+//! Extracts text from images dropped into a document, using a
+//! system-installed `tesseract`. Like pandoc, it is looked up on the user's
+//! login-shell PATH because GUI launches inherit a minimal one.
+//!
+//! Language packs (`<code>.traineddata`) come from two places: the ones
+//! tesseract ships or the system installed, and ones VMark downloads into
+//! `<app_data>/tessdata`. Downloads report `ocr:download-progress` events.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_provider::{build_command, login_shell_path};
+use crate::tool_registry;
+
+/// Shown when tesseract can't be found
+const INSTALL_HINT: &str = "Tesseract is not installed. Install it from \
+     https://tesseract-ocr.github.io/tessdoc/Installation.html and try again.";
+
+/// Fast (integer) models: a fraction of the size of the best models, and
+/// accurate enough for screenshots
+const TESSDATA_URL: &str = "https://github.com/tesseract-ocr/tessdata_fast/raw/main";
+
+const DEFAULT_LANG: &str = "eng";
+const DOWNLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// Detected tesseract installation
+#[derive(Debug, Clone, Serialize)]
+pub struct OcrInfo {
+    pub available: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// Language codes usable with `extract_text_from_image`
+    pub languages: Vec<String>,
+}
+
+/// Where an installed language pack lives
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OcrLanguageSource {
+    /// Installed alongside tesseract
+    System,
+    /// Downloaded by VMark (can be removed)
+    Managed,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OcrLanguage {
+    pub code: String,
+    pub source: OcrLanguageSource,
+}
+
+/// Payload for `ocr:download-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrDownloadProgress {
+    pub lang: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+/// `tesseract 5.3.4` → `5.3.4` (older versions print `tesseract v4.1.1`).
+pub(crate) fn parse_version(output: &str) -> Option<String> {
+    let first = output.lines().find(|l| !l.trim().is_empty())?;
+    let mut words = first.split_whitespace();
+    let name = words.next()?;
+    if !name.trim_end_matches(".exe").eq_ignore_ascii_case("tesseract") {
+        return None;
+    }
+    words.next().map(|v| v.trim_start_matches('v').to_string())
+}
+
+/// Language codes from `tesseract --list-langs`, skipping the header line
+/// and `osd` (orientation detection, not a language).
+fn parse_list_langs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with("List of") && *l != "osd")
+        .filter(|l| validate_lang_code(l).is_ok())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A single tesseract language code: `eng`, `chi_sim`, `deu_latf`.
+fn validate_lang_code(code: &str) -> Result<(), String> {
+    let valid = !code.is_empty()
+        && code.len() <= 32
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid OCR language: {:?}", code))
+    }
+}
+
+/// `eng+deu` → `["eng", "deu"]`.
+fn split_langs(lang: &str) -> Result<Vec<&str>, String> {
+    let codes: Vec<&str> = lang.split('+').collect();
+    for code in &codes {
+        validate_lang_code(code)?;
+    }
+    Ok(codes)
+}
+
+fn tessdata_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join("tessdata"))
+}
+
+fn managed_languages(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut codes: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(".traineddata").map(str::to_string)
+        })
+        .filter(|code| validate_lang_code(code).is_ok())
+        .collect();
+    codes.sort();
+    codes
+}
+
+fn system_languages(exe: &str) -> Vec<String> {
+    build_command(exe, &["--list-langs"])
+        .env("PATH", login_shell_path())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        // Older versions print the list to stderr
+        .map(|o| {
+            let mut text = String::from_utf8_lossy(&o.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&o.stderr));
+            parse_list_langs(&text)
+        })
+        .unwrap_or_default()
+}
+
+fn detect(app: &AppHandle, managed_dir: &Path) -> Result<OcrInfo, String> {
+    let status = tool_registry::refresh(app, &["tesseract"])?.remove(0);
+    let (available, path, version) = (status.available, status.path, status.version);
+    let exe = path.as_deref();
+    let mut languages = exe.map(system_languages).unwrap_or_default();
+    for code in managed_languages(managed_dir) {
+        if !languages.contains(&code) {
+            languages.push(code);
+        }
+    }
+    Ok(OcrInfo {
+        available,
+        path,
+        version,
+        languages,
+    })
+}
+
+/// Command-line arguments for recognizing `image`. The managed tessdata
+/// directory is used only when it holds every requested language, since
+/// `--tessdata-dir` hides the system packs.
+fn build_args(image: &str, lang: &str, managed_dir: &Path) -> Result<Vec<String>, String> {
+    let codes = split_langs(lang)?;
+    let mut args = vec![image.to_string(), "stdout".to_string(), "-l".to_string(), lang.to_string()];
+    let managed = managed_languages(managed_dir);
+    if codes.iter().all(|code| managed.iter().any(|m| m == code)) {
+        args.push("--tessdata-dir".to_string());
+        args.push(managed_dir.to_string_lossy().into_owned());
+    }
+    Ok(args)
+}
+
+/// Tidy recognized text for insertion: tesseract ends pages with a form
+/// feed and pads with blank lines.
+fn clean_output(text: &str) -> String {
+    let text = text.replace('\u{c}', "\n");
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push_str("\n\n");
+        } else if !out.is_empty() {
+            out.push('\n');
+        }
+        blank = false;
+        out.push_str(line);
+    }
+    out
+}
+
+fn run_tesseract(exe: &str, args: &[String]) -> Result<String, String> {
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = build_command(exe, &arg_refs)
+        .env("PATH", login_shell_path())
+        .output()
+        .map_err(|e| format!("Failed to start tesseract: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let details: Vec<&str> = stderr
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("Estimating resolution"))
+            .collect();
+        let summary = match output.status.code() {
+            Some(code) => format!("Tesseract exited with status {}", code),
+            None => "Tesseract was terminated".to_string(),
+        };
+        return Err(if details.is_empty() {
+            summary
+        } else {
+            format!("{}: {}", summary, details.join("\n"))
+        });
+    }
+    Ok(clean_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Detect a system tesseract and the languages it can recognize.
+#[tauri::command]
+pub async fn detect_tesseract(app: AppHandle) -> Result<OcrInfo, String> {
+    let dir = tessdata_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || detect(&app, &dir))
+        .await
+        .map_err(|e| format!("Tesseract detection failed: {}", e))?
+}
+
+/// Recognize the text in an image. `lang` is a tesseract language code or
+/// several joined with `+` (`eng+deu`); defaults to English.
+#[tauri::command]
+pub async fn extract_text_from_image(app: AppHandle, path: String, lang: Option<String>) -> Result<String, String> {
+    if !Path::new(&path).is_absolute() {
+        return Err(format!("Image path must be absolute: {}", path));
+    }
+    if !Path::new(&path).is_file() {
+        return Err(format!("Image not found: {}", path));
+    }
+    let lang = lang.filter(|l| !l.is_empty()).unwrap_or_else(|| DEFAULT_LANG.to_string());
+    let args = build_args(&path, &lang, &tessdata_dir(&app)?)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(exe) = tool_registry::find(&app, "tesseract")? else {
+            return Err(INSTALL_HINT.to_string());
+        };
+        tracing::info!("[OCR] Recognizing {} ({})", path, lang);
+        run_tesseract(&exe, &args).inspect_err(|e| tracing::warn!("[OCR] {}", e))
+    })
+    .await
+    .map_err(|e| format!("Text recognition failed: {}", e))?
+}
+
+/// Installed language packs, system and downloaded.
+#[tauri::command]
+pub async fn list_ocr_languages(app: AppHandle) -> Result<Vec<OcrLanguage>, String> {
+    let dir = tessdata_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let exe = tool_registry::find(&app, "tesseract").ok().flatten();
+        let system = exe.map(|e| system_languages(&e)).unwrap_or_default();
+        let managed = managed_languages(&dir);
+        let mut languages: Vec<OcrLanguage> = managed
+            .iter()
+            .map(|code| OcrLanguage {
+                code: code.clone(),
+                source: OcrLanguageSource::Managed,
+            })
+            .collect();
+        languages.extend(system.into_iter().filter(|c| !managed.contains(c)).map(|code| OcrLanguage {
+            code,
+            source: OcrLanguageSource::System,
+        }));
+        languages.sort_by(|a, b| a.code.cmp(&b.code));
+        languages
+    })
+    .await
+    .map_err(|e| format!("Listing OCR languages failed: {}", e))
+}
+
+/// Download a language pack into the app's tessdata directory.
+#[tauri::command]
+pub async fn download_ocr_language(app: AppHandle, lang: String) -> Result<(), String> {
+    validate_lang_code(&lang)?;
+    let dir = tessdata_dir(&app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create tessdata folder: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .user_agent(concat!("VMark/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let url = format!("{}/{}.traineddata", TESSDATA_URL, lang);
+    tracing::info!("[OCR] Downloading language pack {}", lang);
+    let mut resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", lang, e))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("No OCR language pack named {}", lang));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download {} (HTTP {})", lang, resp.status().as_u16()));
+    }
+
+    let total = resp.content_length();
+    let mut data = Vec::with_capacity(total.unwrap_or(0) as usize);
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", lang, e))?
+    {
+        data.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "ocr:download-progress",
+            OcrDownloadProgress {
+                lang: lang.clone(),
+                downloaded: data.len() as u64,
+                total,
+            },
+        );
+    }
+
+    // Written atomically so an interrupted download never leaves a pack
+    // tesseract would try (and fail) to load
+    crate::app_paths::atomic_write_file(&dir.join(format!("{}.traineddata", lang)), &data)?;
+    Ok(())
+}
+
+/// Remove a downloaded language pack. System packs can't be removed.
+#[tauri::command]
+pub fn remove_ocr_language(app: AppHandle, lang: String) -> Result<(), String> {
+    validate_lang_code(&lang)?;
+    let path = tessdata_dir(&app)?.join(format!("{}.traineddata", lang));
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(format!("{} was not downloaded by VMark and can't be removed", lang))
+        }
+        Err(e) => Err(format!("Failed to remove {}: {}", lang, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("tesseract 5.3.4\n leptonica-1.84.1\n").as_deref(), Some("5.3.4"));
+        assert_eq!(parse_version("tesseract v4.1.1\n").as_deref(), Some("4.1.1"));
+        assert_eq!(parse_version("something else 1.0"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn test_parse_list_langs() {
+        let output = "List of available languages in \"/usr/share/tessdata/\" (3):\neng\nosd\nchi_sim\n";
+        assert_eq!(parse_list_langs(output), vec!["eng", "chi_sim"]);
+    }
+
+    #[test]
+    fn test_split_langs_rejects_options() {
+        assert_eq!(split_langs("eng+deu").unwrap(), vec!["eng", "deu"]);
+        assert!(split_langs("--psm").is_err());
+        assert!(split_langs("eng+").is_err());
+        assert!(split_langs("../eng").is_err());
+    }
+
+    #[test]
+    fn test_build_args_uses_managed_dir_only_when_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("deu.traineddata"), b"x").unwrap();
+
+        let args = build_args("/img.png", "eng+deu", dir.path()).unwrap();
+        assert_eq!(args, ["/img.png", "stdout", "-l", "eng+deu"]);
+
+        let args = build_args("/img.png", "deu", dir.path()).unwrap();
+        assert_eq!(args[4], "--tessdata-dir");
+        assert_eq!(managed_languages(dir.path()), vec!["deu"]);
+    }
+
+    #[test]
+    fn test_clean_output() {
+        assert_eq!(clean_output("Hello  \nworld\n\n\n\nNext\n\u{c}"), "Hello\nworld\n\nNext");
+        assert_eq!(clean_output("\n\u{c}"), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_tesseract_reports_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("tesseract");
+        fs::write(
+            &exe,
+            "#!/bin/sh\n[ \"$4\" = eng ] && { printf 'Text %s\\n\\f' \"$1\"; exit 0; }\necho 'Failed loading language' >&2\nexit 1\n",
+        )
+        .unwrap();
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o755)).unwrap();
+        let exe = exe.to_str().unwrap();
+
+        let ok = run_tesseract(exe, &build_args("/a.png", "eng", temp.path()).unwrap()).unwrap();
+        assert_eq!(ok, "Text /a.png");
+        let err = run_tesseract(exe, &build_args("/a.png", "xyz", temp.path()).unwrap()).unwrap_err();
+        assert_eq!(err, "Tesseract exited with status 1: Failed loading language");
+    }
+}
@@ -0,0 +1,428 @@
+//! Ollama Model Loading
+//!
+//! Ollama loads a model into memory on its first request, which can take
+//! several seconds, and unloads it after five idle minutes. To keep the
+//! first genie run fast:
+//!
+//! - `ollama_warmup` loads a model ahead of time with an empty request.
+//! - `ollama_keep_alive` optionally keeps one model loaded by re-sending
+//!   that request in the background until it's turned off.
+//!
+//! Local models can also be managed here instead of the terminal:
+//! `list_local_ollama_models`, `pull_ollama_model` (reporting
+//! `ollama:pull-progress`) and `delete_ollama_model`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+/// How long Ollama keeps a model loaded after a warmup or ping
+const KEEP_ALIVE: &str = "10m";
+
+/// Ping interval, comfortably inside `KEEP_ALIVE`
+const PING_INTERVAL: Duration = Duration::from_secs(240);
+
+/// Loading a large model from disk can be slow
+const WARMUP_TIMEOUT_SECS: u64 = 300;
+
+/// Running keep-alive task: (model, endpoint, task)
+type KeepAliveTask = (String, String, tauri::async_runtime::JoinHandle<()>);
+
+static KEEP_ALIVE_TASK: Mutex<Option<KeepAliveTask>> = Mutex::new(None);
+
+/// Payload of `ollama:pull-progress`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PullProgress {
+    pub model: String,
+    /// Ollama's status line, e.g. "pulling manifest", "verifying sha256 digest"
+    pub status: String,
+    /// Bytes of the current layer downloaded so far
+    pub completed: Option<u64>,
+    /// Size of the current layer
+    pub total: Option<u64>,
+}
+
+/// A model installed in Ollama
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModel {
+    pub name: String,
+    /// Size on disk, in bytes
+    pub size: u64,
+    pub modified_at: Option<String>,
+    /// e.g. "3.2B"
+    pub parameter_size: Option<String>,
+    /// e.g. "Q4_K_M"
+    pub quantization_level: Option<String>,
+}
+
+/// `/api/tags` response
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagsModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsModel {
+    name: String,
+    #[serde(default)]
+    size: u64,
+    modified_at: Option<String>,
+    #[serde(default)]
+    details: TagsDetails,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TagsDetails {
+    parameter_size: Option<String>,
+    quantization_level: Option<String>,
+}
+
+/// One line of `/api/pull`'s streamed response
+#[derive(Debug, Deserialize)]
+struct PullLine {
+    #[serde(default)]
+    status: String,
+    completed: Option<u64>,
+    total: Option<u64>,
+    error: Option<String>,
+}
+
+fn resolve_endpoint(endpoint: Option<String>) -> String {
+    endpoint
+        .map(|e| e.trim().trim_end_matches('/').to_string())
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+}
+
+fn client(endpoint: &str, timeout: Option<Duration>) -> Result<reqwest::Client, String> {
+    let mut builder = crate::http_client::builder_for(Some(endpoint))?;
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+async fn check_response(resp: reqwest::Response) -> Result<reqwest::Response, String> {
+    if resp.status().is_success() {
+        return Ok(resp);
+    }
+    let status = resp.status();
+    let text = resp.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&text)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+        .unwrap_or(text);
+    Err(format!("Ollama error {}: {}", status.as_u16(), message))
+}
+
+/// Load `model` without generating anything.
+async fn load_model(endpoint: &str, model: &str) -> Result<(), String> {
+    let client = client(endpoint, Some(Duration::from_secs(WARMUP_TIMEOUT_SECS)))?;
+    let resp = client
+        .post(format!("{}/api/generate", endpoint))
+        .json(&serde_json::json!({ "model": model, "keep_alive": KEEP_ALIVE }))
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    check_response(resp).await.map(|_| ())
+}
+
+/// Installed models from an `/api/tags` response, sorted by name.
+fn parse_tags(body: &str) -> Result<Vec<LocalModel>, String> {
+    let tags: TagsResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Unexpected response from Ollama: {}", e))?;
+    let mut models: Vec<LocalModel> = tags
+        .models
+        .into_iter()
+        .map(|m| LocalModel {
+            name: m.name,
+            size: m.size,
+            modified_at: m.modified_at,
+            parameter_size: m.details.parameter_size.filter(|s| !s.is_empty()),
+            quantization_level: m.details.quantization_level.filter(|s| !s.is_empty()),
+        })
+        .collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+/// Parse a line of `/api/pull` output; `None` for blank lines.
+fn parse_pull_line(model: &str, line: &str) -> Result<Option<PullProgress>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let parsed: PullLine = serde_json::from_str(line)
+        .map_err(|e| format!("Unexpected response from Ollama: {}", e))?;
+    if let Some(error) = parsed.error {
+        return Err(format!("Failed to pull {}: {}", model, error));
+    }
+    Ok(Some(PullProgress {
+        model: model.to_string(),
+        status: parsed.status,
+        completed: parsed.completed,
+        total: parsed.total,
+    }))
+}
+
+/// Stop the keep-alive task, if any.
+fn stop_keep_alive() {
+    if let Some((model, _, task)) = KEEP_ALIVE_TASK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        tracing::debug!("[Ollama] Stopped keeping {} loaded", model);
+        task.abort();
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Load `model` into memory so the next prompt starts right away. Returns
+/// how long loading took, in milliseconds.
+#[tauri::command]
+pub async fn ollama_warmup(model: String, endpoint: Option<String>) -> Result<u64, String> {
+    let endpoint = resolve_endpoint(endpoint);
+    let started = Instant::now();
+    load_model(&endpoint, &model).await?;
+    let elapsed = started.elapsed().as_millis() as u64;
+    tracing::debug!("[Ollama] Warmed up {} in {} ms", model, elapsed);
+    Ok(elapsed)
+}
+
+/// Keep `model` loaded with periodic background pings, replacing any model
+/// kept before. `None` stops.
+#[tauri::command]
+pub fn ollama_keep_alive(model: Option<String>, endpoint: Option<String>) {
+    let Some(model) = model.filter(|m| !m.trim().is_empty()) else {
+        stop_keep_alive();
+        return;
+    };
+    let endpoint = resolve_endpoint(endpoint);
+    let mut current = KEEP_ALIVE_TASK.lock().unwrap_or_else(|e| e.into_inner());
+    if current
+        .as_ref()
+        .is_some_and(|(m, e, _)| *m == model && *e == endpoint)
+    {
+        return;
+    }
+    if let Some((_, _, task)) = current.take() {
+        task.abort();
+    }
+
+    let task = {
+        let (model, endpoint) = (model.clone(), endpoint.clone());
+        tauri::async_runtime::spawn(async move {
+            loop {
+                if let Err(e) = load_model(&endpoint, &model).await {
+                    tracing::debug!("[Ollama] Keep-alive ping for {} failed: {}", model, e);
+                }
+                tokio::time::sleep(PING_INTERVAL).await;
+            }
+        })
+    };
+    *current = Some((model, endpoint, task));
+}
+
+/// Download `model` (e.g. "llama3.2", "nomic-embed-text"), emitting
+/// `ollama:pull-progress` as it goes.
+#[tauri::command]
+pub async fn pull_ollama_model(
+    app: AppHandle,
+    model: String,
+    endpoint: Option<String>,
+) -> Result<(), String> {
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("Model name is empty".to_string());
+    }
+    let endpoint = resolve_endpoint(endpoint);
+    let resp = client(&endpoint, None)?
+        .post(format!("{}/api/pull", endpoint))
+        .json(&serde_json::json!({ "model": model, "stream": true }))
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    let mut resp = check_response(resp).await?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut last_status = String::new();
+    loop {
+        let chunk = resp
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to pull {}: {}", model, e))?;
+        let done = chunk.is_none();
+        if let Some(chunk) = chunk {
+            buffer.extend_from_slice(&chunk);
+        }
+        // Complete lines, plus whatever is left once the stream ends
+        let end = if done {
+            buffer.len()
+        } else {
+            buffer
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1)
+        };
+        let lines: Vec<u8> = buffer.drain(..end).collect();
+        for line in String::from_utf8_lossy(&lines).lines() {
+            if let Some(progress) = parse_pull_line(&model, line)? {
+                last_status = progress.status.clone();
+                let _ = app.emit("ollama:pull-progress", progress);
+            }
+        }
+        if done {
+            break;
+        }
+    }
+
+    if last_status != "success" {
+        return Err(format!("Pull of {} did not complete", model));
+    }
+    tracing::info!("[Ollama] Pulled {}", model);
+    Ok(())
+}
+
+/// Models installed in Ollama, with their sizes.
+#[tauri::command]
+pub async fn list_local_ollama_models(endpoint: Option<String>) -> Result<Vec<LocalModel>, String> {
+    let endpoint = resolve_endpoint(endpoint);
+    let resp = client(&endpoint, Some(Duration::from_secs(10)))?
+        .get(format!("{}/api/tags", endpoint))
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    let body = check_response(resp)
+        .await?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Ollama response: {}", e))?;
+    parse_tags(&body)
+}
+
+/// Remove `model` from disk.
+#[tauri::command]
+pub async fn delete_ollama_model(model: String, endpoint: Option<String>) -> Result<(), String> {
+    let model = model.trim().to_string();
+    if model.is_empty() {
+        return Err("Model name is empty".to_string());
+    }
+    let endpoint = resolve_endpoint(endpoint);
+
+    // Pinging a deleted model would only fail from now on
+    let kept = KEEP_ALIVE_TASK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|(m, e, _)| *m == model && *e == endpoint);
+    if kept {
+        stop_keep_alive();
+    }
+
+    let resp = client(&endpoint, Some(Duration::from_secs(30)))?
+        .delete(format!("{}/api/delete", endpoint))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed: {}", e))?;
+    check_response(resp).await?;
+    tracing::info!("[Ollama] Deleted {}", model);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pull_lines() {
+        assert_eq!(
+            parse_pull_line(
+                "llama3.2",
+                r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":2019377376,"completed":241970}"#
+            )
+            .unwrap(),
+            Some(PullProgress {
+                model: "llama3.2".into(),
+                status: "pulling 6a0746a1ec1a".into(),
+                completed: Some(241970),
+                total: Some(2019377376),
+            })
+        );
+        let success = parse_pull_line("llama3.2", r#"{"status":"success"}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(success.status, "success");
+        assert_eq!(success.total, None);
+        assert_eq!(parse_pull_line("llama3.2", "  ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_pull_errors() {
+        let err = parse_pull_line(
+            "nope",
+            r#"{"error":"pull model manifest: file does not exist"}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Failed to pull nope: pull model manifest: file does not exist"
+        );
+        assert!(parse_pull_line("x", "not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let models = parse_tags(
+            r#"{"models":[
+                {"name":"nomic-embed-text:latest","model":"nomic-embed-text:latest","size":274302450,
+                 "modified_at":"2026-09-01T10:00:00Z","details":{"parameter_size":"137M","quantization_level":"F16"}},
+                {"name":"llama3.2:latest","size":2019393189,"details":{"parameter_size":"","quantization_level":"Q4_K_M"}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            models,
+            vec![
+                LocalModel {
+                    name: "llama3.2:latest".into(),
+                    size: 2019393189,
+                    modified_at: None,
+                    parameter_size: None,
+                    quantization_level: Some("Q4_K_M".into()),
+                },
+                LocalModel {
+                    name: "nomic-embed-text:latest".into(),
+                    size: 274302450,
+                    modified_at: Some("2026-09-01T10:00:00Z".into()),
+                    parameter_size: Some("137M".into()),
+                    quantization_level: Some("F16".into()),
+                },
+            ]
+        );
+        assert!(parse_tags("{}").unwrap().is_empty());
+        assert!(parse_tags("nope").is_err());
+    }
+
+    #[test]
+    fn test_resolve_endpoint() {
+        assert_eq!(resolve_endpoint(None), DEFAULT_ENDPOINT);
+        assert_eq!(resolve_endpoint(Some("  ".into())), DEFAULT_ENDPOINT);
+        assert_eq!(
+            resolve_endpoint(Some("http://gpu-box:11434/".into())),
+            "http://gpu-box:11434"
+        );
+    }
+}
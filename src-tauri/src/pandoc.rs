@@ -0,0 +1,515 @@
+//! Pandoc Integration
+//!
+//! Converts documents with a system-installed `pandoc`, powering the
+//! import/export formats VMark doesn't render itself (ODT, reStructuredText,
+//! LaTeX, MediaWiki). Pandoc is looked up on the user's login-shell PATH
+//! because GUI launches inherit a minimal one.
+//!
+//! Conversions stream pandoc's log output as `pandoc:progress` events keyed
+//! by the caller's request id, so long exports can show what is happening.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter};
+
+use crate::ai_provider::{build_command, login_shell_path};
+use crate::tool_registry;
+
+/// Shown when pandoc can't be found
+const INSTALL_HINT: &str =
+    "Pandoc is not installed. Install it from https://pandoc.org/installing.html and try again.";
+
+/// Detected pandoc installation
+#[derive(Debug, Clone, Serialize)]
+pub struct PandocInfo {
+    pub available: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+}
+
+/// A format offered in the import/export menus
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PandocFormat {
+    /// Pandoc reader/writer name
+    pub id: &'static str,
+    pub name: &'static str,
+    /// File extension without the dot
+    pub extension: &'static str,
+    /// Binary formats must be read from / written to a file
+    pub binary: bool,
+}
+
+const FORMATS: &[PandocFormat] = &[
+    PandocFormat { id: "odt", name: "OpenDocument Text", extension: "odt", binary: true },
+    PandocFormat { id: "rst", name: "reStructuredText", extension: "rst", binary: false },
+    PandocFormat { id: "latex", name: "LaTeX", extension: "tex", binary: false },
+    PandocFormat { id: "mediawiki", name: "MediaWiki", extension: "wiki", binary: false },
+];
+
+/// Writers/readers that only work with files, not stdin/stdout
+const BINARY_FORMATS: &[&str] = &["docx", "epub", "epub2", "epub3", "odt", "pptx", "pdf"];
+
+/// Conversion options
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PandocOptions {
+    /// Read this file instead of the `input` text (required for binary formats)
+    #[serde(default)]
+    pub input_path: Option<String>,
+    /// Write the result to this file instead of returning it (required for binary formats)
+    #[serde(default)]
+    pub output_path: Option<String>,
+    /// Produce a complete document (with header/preamble) rather than a fragment
+    #[serde(default)]
+    pub standalone: bool,
+    /// Directory images and other resources are resolved against
+    #[serde(default)]
+    pub resource_path: Option<String>,
+    /// Document title metadata
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Further metadata fields (`author`, `date`, `keywords`, ...), written to
+    /// the output's document properties where the format has them
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Conversion result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PandocResult {
+    /// Converted text when no output path was given
+    pub content: Option<String>,
+    pub output_path: Option<String>,
+    /// `[WARNING]` lines pandoc reported
+    pub warnings: Vec<String>,
+}
+
+/// Conversion stage for `pandoc:progress`
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PandocStage {
+    Started,
+    Log,
+    Finished,
+    Failed,
+}
+
+/// Payload for `pandoc:progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PandocProgress {
+    pub request_id: String,
+    pub stage: PandocStage,
+    pub message: Option<String>,
+}
+
+/// `pandoc 3.1.9` → `3.1.9` (Windows prints `pandoc.exe 3.1.9`).
+pub(crate) fn parse_version(output: &str) -> Option<String> {
+    let first = output.lines().next()?;
+    let mut words = first.split_whitespace();
+    let name = words.next()?;
+    if !name.trim_end_matches(".exe").eq_ignore_ascii_case("pandoc") {
+        return None;
+    }
+    words.next().map(str::to_string)
+}
+
+fn detect(app: &AppHandle) -> Result<PandocInfo, String> {
+    let status = tool_registry::refresh(app, &["pandoc"])?.remove(0);
+    Ok(PandocInfo {
+        available: status.available,
+        path: status.path,
+        version: status.version,
+    })
+}
+
+/// Format name without extensions: `markdown+smart-raw_html` → `markdown`.
+fn base_format(format: &str) -> &str {
+    format
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '+' || *c == '-')
+        .map_or(format, |(i, _)| &format[..i])
+}
+
+fn validate_format(format: &str) -> Result<(), String> {
+    let valid = !format.is_empty()
+        && !format.starts_with(['-', '+'])
+        && format
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid pandoc format: {:?}", format))
+    }
+}
+
+fn require_absolute(path: &str, what: &str) -> Result<(), String> {
+    if Path::new(path).is_absolute() {
+        Ok(())
+    } else {
+        Err(format!("{} must be an absolute path: {}", what, path))
+    }
+}
+
+/// Command-line arguments for a conversion.
+fn build_args(from: &str, to: &str, options: &PandocOptions) -> Result<Vec<String>, String> {
+    validate_format(from)?;
+    validate_format(to)?;
+
+    let mut args = vec![
+        "--from".to_string(),
+        from.to_string(),
+        "--to".to_string(),
+        to.to_string(),
+        "--verbose".to_string(),
+    ];
+    if options.standalone {
+        args.push("--standalone".to_string());
+    }
+    if let Some(dir) = options.resource_path.as_deref().filter(|d| !d.is_empty()) {
+        args.push(format!("--resource-path={}", dir));
+    }
+    if let Some(title) = options.title.as_deref().filter(|t| !t.is_empty()) {
+        args.push("--metadata".to_string());
+        args.push(format!("title={}", title));
+    }
+    for (key, value) in options.metadata.iter().filter(|(k, v)| !k.is_empty() && !v.is_empty()) {
+        args.push("--metadata".to_string());
+        args.push(format!("{}={}", key, value));
+    }
+
+    match options.output_path.as_deref() {
+        Some(output) => {
+            require_absolute(output, "Output path")?;
+            args.push("--output".to_string());
+            args.push(output.to_string());
+        }
+        None if BINARY_FORMATS.contains(&base_format(to)) => {
+            return Err(format!("Converting to {} requires an output file", to));
+        }
+        None => {}
+    }
+
+    match options.input_path.as_deref() {
+        Some(input) => {
+            require_absolute(input, "Input path")?;
+            args.push(input.to_string());
+        }
+        None if BINARY_FORMATS.contains(&base_format(from)) => {
+            return Err(format!("Converting from {} requires an input file", from));
+        }
+        None => {}
+    }
+
+    Ok(args)
+}
+
+/// Readable error for a failed run, from pandoc's exit code and stderr.
+fn describe_failure(code: Option<i32>, from: &str, to: &str, stderr: &[String]) -> String {
+    let summary = match code {
+        Some(21) => format!("Pandoc can't read the {} format", from),
+        Some(22) => format!("Pandoc can't write the {} format", to),
+        Some(23) => "Pandoc doesn't support a requested format extension".to_string(),
+        Some(47) => "Pandoc couldn't find a PDF engine".to_string(),
+        Some(64) | Some(65) => format!("Pandoc couldn't parse the {} input", from),
+        Some(92) => "The input is not valid UTF-8".to_string(),
+        Some(99) => "Pandoc couldn't find a referenced resource".to_string(),
+        Some(code) => format!("Pandoc exited with status {}", code),
+        None => "Pandoc was terminated".to_string(),
+    };
+    // Log lines are noise in an error; keep what pandoc reported as the problem
+    let details: Vec<&str> = stderr
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("[INFO]"))
+        .collect();
+    if details.is_empty() {
+        summary
+    } else {
+        format!("{}: {}", summary, details.join("\n"))
+    }
+}
+
+/// Run pandoc, feeding `stdin` and reporting each stderr line to `on_log`.
+/// Returns stdout and the collected warnings.
+fn run_pandoc(
+    exe: &str,
+    args: &[String],
+    stdin: Option<String>,
+    from: &str,
+    to: &str,
+    mut on_log: impl FnMut(&str),
+) -> Result<(String, Vec<String>), String> {
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut child = build_command(exe, &arg_refs)
+        .env("PATH", login_shell_path())
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start pandoc: {}", e))?;
+
+    // Write and read on separate threads so a large document can't deadlock the pipes
+    let writer = match (stdin, child.stdin.take()) {
+        (Some(text), Some(mut pipe)) => Some(std::thread::spawn(move || {
+            // A write error means pandoc exited early; its status explains why
+            let _ = pipe.write_all(text.as_bytes());
+        })),
+        _ => None,
+    };
+    let reader = child.stdout.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut out = Vec::new();
+            let _ = pipe.read_to_end(&mut out);
+            out
+        })
+    });
+
+    let mut stderr_lines = Vec::new();
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            on_log(&line);
+            stderr_lines.push(line);
+        }
+    }
+
+    let status = child.wait().map_err(|e| format!("Failed to wait for pandoc: {}", e))?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    let stdout = reader
+        .and_then(|r| r.join().ok())
+        .map(|out| String::from_utf8_lossy(&out).into_owned())
+        .unwrap_or_default();
+
+    if !status.success() {
+        return Err(describe_failure(status.code(), from, to, &stderr_lines));
+    }
+
+    let warnings = stderr_lines
+        .iter()
+        .filter_map(|line| line.strip_prefix("[WARNING]"))
+        .map(|w| w.trim().to_string())
+        .collect();
+    Ok((stdout, warnings))
+}
+
+/// Detect a system pandoc.
+#[tauri::command]
+pub async fn detect_pandoc(app: AppHandle) -> Result<PandocInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || detect(&app))
+        .await
+        .map_err(|e| format!("Pandoc detection failed: {}", e))?
+}
+
+/// Formats offered for import and export through pandoc.
+#[tauri::command]
+pub fn list_pandoc_formats() -> Vec<PandocFormat> {
+    FORMATS.to_vec()
+}
+
+/// Convert `input` (or `options.inputPath`) from one pandoc format to another.
+/// Progress is emitted as `pandoc:progress` with `request_id`.
+#[tauri::command]
+pub async fn convert_with_pandoc(
+    app: AppHandle,
+    request_id: String,
+    input: String,
+    from: String,
+    to: String,
+    options: Option<PandocOptions>,
+) -> Result<PandocResult, String> {
+    let options = options.unwrap_or_default();
+    let args = build_args(&from, &to, &options)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let emit = |stage: PandocStage, message: Option<String>| {
+            let _ = app.emit(
+                "pandoc:progress",
+                PandocProgress {
+                    request_id: request_id.clone(),
+                    stage,
+                    message,
+                },
+            );
+        };
+
+        let Some(exe) = tool_registry::find(&app, "pandoc")? else {
+            emit(PandocStage::Failed, Some(INSTALL_HINT.to_string()));
+            return Err(INSTALL_HINT.to_string());
+        };
+
+        tracing::info!("[Pandoc] Converting {} -> {}", from, to);
+        emit(PandocStage::Started, None);
+
+        let stdin = options.input_path.is_none().then_some(input);
+        let result = run_pandoc(&exe, &args, stdin, &from, &to, |line| {
+            emit(PandocStage::Log, Some(line.to_string()));
+        });
+
+        match result {
+            Ok((stdout, warnings)) => {
+                emit(PandocStage::Finished, None);
+                Ok(PandocResult {
+                    content: options.output_path.is_none().then_some(stdout),
+                    output_path: options.output_path,
+                    warnings,
+                })
+            }
+            Err(e) => {
+                tracing::warn!("[Pandoc] {}", e);
+                emit(PandocStage::Failed, Some(e.clone()));
+                Err(e)
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Pandoc conversion failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abs(name: &str) -> String {
+        std::env::temp_dir().join(name).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("pandoc 3.1.9\nFeatures: +server\n").as_deref(), Some("3.1.9"));
+        assert_eq!(parse_version("pandoc.exe 2.19.2\n").as_deref(), Some("2.19.2"));
+        assert_eq!(parse_version("something else 1.0"), None);
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn test_base_format_strips_extensions() {
+        assert_eq!(base_format("markdown+smart-raw_html"), "markdown");
+        assert_eq!(base_format("odt"), "odt");
+        assert_eq!(base_format("gfm-yaml_metadata_block"), "gfm");
+    }
+
+    #[test]
+    fn test_validate_format_rejects_options() {
+        assert!(validate_format("markdown+smart").is_ok());
+        assert!(validate_format("--lua-filter=x.lua").is_err());
+        assert!(validate_format("").is_err());
+        assert!(validate_format("latex output.tex").is_err());
+    }
+
+    #[test]
+    fn test_build_args_text_to_text() {
+        let options = PandocOptions {
+            standalone: true,
+            title: Some("Notes".into()),
+            ..Default::default()
+        };
+        let args = build_args("markdown", "rst", &options).unwrap();
+        assert_eq!(
+            args,
+            ["--from", "markdown", "--to", "rst", "--verbose", "--standalone", "--metadata", "title=Notes"]
+        );
+    }
+
+    #[test]
+    fn test_build_args_metadata() {
+        let options = PandocOptions {
+            metadata: BTreeMap::from([
+                ("author".to_string(), "Ada".to_string()),
+                ("keywords".to_string(), String::new()),
+            ]),
+            ..Default::default()
+        };
+        let args = build_args("markdown", "rst", &options).unwrap();
+        assert_eq!(&args[5..], ["--metadata", "author=Ada"]);
+    }
+
+    #[test]
+    fn test_build_args_binary_formats_need_files() {
+        let err = build_args("markdown", "odt", &PandocOptions::default()).unwrap_err();
+        assert!(err.contains("requires an output file"));
+        let err = build_args("odt", "gfm", &PandocOptions::default()).unwrap_err();
+        assert!(err.contains("requires an input file"));
+
+        let output = abs("out.odt");
+        let options = PandocOptions {
+            output_path: Some(output.clone()),
+            ..Default::default()
+        };
+        let args = build_args("markdown", "odt", &options).unwrap();
+        assert_eq!(&args[args.len() - 2..], ["--output".to_string(), output]);
+    }
+
+    #[test]
+    fn test_build_args_rejects_relative_paths() {
+        let options = PandocOptions {
+            input_path: Some("-o.odt".into()),
+            ..Default::default()
+        };
+        assert!(build_args("odt", "gfm", &options).is_err());
+    }
+
+    #[test]
+    fn test_describe_failure_keeps_reported_problem() {
+        let stderr = vec![
+            "[INFO] Loaded image.png".to_string(),
+            "Unknown output format wiki".to_string(),
+        ];
+        assert_eq!(
+            describe_failure(Some(22), "markdown", "wiki", &stderr),
+            "Pandoc can't write the wiki format: Unknown output format wiki"
+        );
+        assert_eq!(describe_failure(Some(1), "a", "b", &[]), "Pandoc exited with status 1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_pandoc_streams_logs_and_collects_warnings() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("pandoc");
+        std::fs::write(
+            &exe,
+            "#!/bin/sh\necho '[INFO] Running' >&2\necho '[WARNING] Missing image' >&2\ntr a-z A-Z\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut logs = Vec::new();
+        let (stdout, warnings) = run_pandoc(
+            exe.to_str().unwrap(),
+            &[],
+            Some("hello".into()),
+            "markdown",
+            "rst",
+            |line| logs.push(line.to_string()),
+        )
+        .unwrap();
+        assert_eq!(stdout, "HELLO");
+        assert_eq!(warnings, vec!["Missing image"]);
+        assert_eq!(logs.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_pandoc_reports_exit_status() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("pandoc");
+        std::fs::write(&exe, "#!/bin/sh\necho 'Unknown input format foo' >&2\nexit 21\n").unwrap();
+        std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = run_pandoc(exe.to_str().unwrap(), &[], None, "foo", "gfm", |_| {}).unwrap_err();
+        assert_eq!(err, "Pandoc can't read the foo format: Unknown input format foo");
+    }
+}
@@ -0,0 +1,559 @@
+//! Focus Timer (Pomodoro)
+//!
+//! Work/break cycles run in the backend so the timer keeps going through
+//! webview reloads and window changes. While running it emits
+//! `pomodoro:tick` every second and `pomodoro:phase-changed` when a phase
+//! ends, and shows a system notification for the new phase.
+//!
+//! Completed focus time is recorded per local day in
+//! `<app_data>/pomodoro.json`, together with the timer settings.
+
+use chrono::{Duration as ChronoDuration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const DATA_FILE: &str = "pomodoro.json";
+
+/// Days of statistics kept in the data file
+const MAX_HISTORY_DAYS: i64 = 366;
+
+/// The active timer, if any
+static TIMER: Mutex<Option<Timer>> = Mutex::new(None);
+
+/// The once-a-second loop driving the active timer
+static TICKER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+/// Serializes reads and writes of the data file
+static DATA_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PomodoroConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Work sessions before a long break
+    pub sessions_before_long_break: u32,
+    /// Start the next phase automatically instead of waiting paused
+    pub auto_continue: bool,
+    pub notifications: bool,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            sessions_before_long_break: 4,
+            auto_continue: true,
+            notifications: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PomodoroStatus {
+    Idle,
+    Running,
+    Paused,
+}
+
+/// Timer snapshot, also the `pomodoro:tick` / `pomodoro:phase-changed` payload
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PomodoroState {
+    pub status: PomodoroStatus,
+    pub phase: PomodoroPhase,
+    pub remaining_ms: u64,
+    pub duration_ms: u64,
+    /// Work sessions completed since the timer was started
+    pub completed_sessions: u32,
+}
+
+/// Focus statistics for one day
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayStats {
+    pub focus_seconds: u64,
+    /// Work sessions completed in full
+    pub sessions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyStats {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub focus_seconds: u64,
+    pub sessions: u32,
+}
+
+/// Contents of the data file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PomodoroData {
+    config: PomodoroConfig,
+    /// Keyed by `YYYY-MM-DD`
+    days: BTreeMap<String, DayStats>,
+}
+
+/// Focus time to add to today's statistics
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FocusRecord {
+    seconds: u64,
+    completed: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Timer {
+    config: PomodoroConfig,
+    phase: PomodoroPhase,
+    /// Set while running
+    running_since: Option<Instant>,
+    /// Time spent in this phase before the last pause
+    elapsed: Duration,
+    completed_sessions: u32,
+}
+
+impl Timer {
+    fn new(config: PomodoroConfig, now: Instant) -> Self {
+        Self {
+            config,
+            phase: PomodoroPhase::Work,
+            running_since: Some(now),
+            elapsed: Duration::ZERO,
+            completed_sessions: 0,
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        let minutes = match self.phase {
+            PomodoroPhase::Work => self.config.work_minutes,
+            PomodoroPhase::ShortBreak => self.config.short_break_minutes,
+            PomodoroPhase::LongBreak => self.config.long_break_minutes,
+        };
+        Duration::from_secs(u64::from(minutes.max(1)) * 60)
+    }
+
+    fn elapsed(&self, now: Instant) -> Duration {
+        self.elapsed + self.running_since.map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+
+    fn state(&self, now: Instant) -> PomodoroState {
+        let duration = self.duration();
+        PomodoroState {
+            status: if self.running_since.is_some() {
+                PomodoroStatus::Running
+            } else {
+                PomodoroStatus::Paused
+            },
+            phase: self.phase,
+            remaining_ms: duration.saturating_sub(self.elapsed(now)).as_millis() as u64,
+            duration_ms: duration.as_millis() as u64,
+            completed_sessions: self.completed_sessions,
+        }
+    }
+
+    fn pause(&mut self, now: Instant) {
+        if self.running_since.is_some() {
+            self.elapsed = self.elapsed(now);
+            self.running_since = None;
+        }
+    }
+
+    fn resume(&mut self, now: Instant) {
+        if self.running_since.is_none() {
+            self.running_since = Some(now);
+        }
+    }
+
+    /// Focus time of an unfinished work phase, for when the timer is stopped.
+    fn partial_focus(&self, now: Instant) -> Option<FocusRecord> {
+        (self.phase == PomodoroPhase::Work).then(|| FocusRecord {
+            seconds: self.elapsed(now).min(self.duration()).as_secs(),
+            completed: false,
+        })
+    }
+
+    /// Move to the next phase if the current one is over. Returns the focus
+    /// time to record (for a finished work phase) when the phase changed.
+    fn tick(&mut self, now: Instant) -> Option<Option<FocusRecord>> {
+        let duration = self.duration();
+        if self.running_since.is_none() || self.elapsed(now) < duration {
+            return None;
+        }
+
+        let record = if self.phase == PomodoroPhase::Work {
+            self.completed_sessions += 1;
+            let every = self.config.sessions_before_long_break.max(1);
+            self.phase = if self.completed_sessions.is_multiple_of(every) {
+                PomodoroPhase::LongBreak
+            } else {
+                PomodoroPhase::ShortBreak
+            };
+            Some(FocusRecord {
+                seconds: duration.as_secs(),
+                completed: true,
+            })
+        } else {
+            self.phase = PomodoroPhase::Work;
+            None
+        };
+        self.elapsed = Duration::ZERO;
+        self.running_since = self.config.auto_continue.then_some(now);
+        Some(record)
+    }
+}
+
+fn idle_state(config: &PomodoroConfig) -> PomodoroState {
+    let duration = Timer::new(config.clone(), Instant::now()).duration().as_millis() as u64;
+    PomodoroState {
+        status: PomodoroStatus::Idle,
+        phase: PomodoroPhase::Work,
+        remaining_ms: duration,
+        duration_ms: duration,
+        completed_sessions: 0,
+    }
+}
+
+// ============================================================================
+// Persistence
+// ============================================================================
+
+fn data_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join(DATA_FILE))
+}
+
+fn load_data(path: &Path) -> PomodoroData {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_data(path: &Path, data: &PomodoroData) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app data folder: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    crate::app_paths::atomic_write_file(path, json.as_bytes())
+}
+
+/// Add focus time to `today`, dropping days older than the history limit.
+fn apply_record(data: &mut PomodoroData, today: NaiveDate, record: FocusRecord) {
+    let day = data.days.entry(today.format("%Y-%m-%d").to_string()).or_default();
+    day.focus_seconds += record.seconds;
+    if record.completed {
+        day.sessions += 1;
+    }
+    let oldest = (today - ChronoDuration::days(MAX_HISTORY_DAYS)).format("%Y-%m-%d").to_string();
+    data.days.retain(|date, _| *date >= oldest);
+}
+
+fn record_focus(app: &AppHandle, record: FocusRecord) {
+    if record.seconds == 0 {
+        return;
+    }
+    let result = data_path(app).and_then(|path| {
+        let _guard = DATA_LOCK.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut data = load_data(&path);
+        apply_record(&mut data, Local::now().date_naive(), record);
+        save_data(&path, &data)
+    });
+    if let Err(e) = result {
+        tracing::warn!("[Pomodoro] Failed to save statistics: {}", e);
+    }
+}
+
+/// The last `days` days (oldest first, today last), including days without focus time.
+fn daily_stats(data: &PomodoroData, today: NaiveDate, days: u32) -> Vec<DailyStats> {
+    (0..i64::from(days))
+        .rev()
+        .map(|offset| {
+            let date = (today - ChronoDuration::days(offset)).format("%Y-%m-%d").to_string();
+            let day = data.days.get(&date).cloned().unwrap_or_default();
+            DailyStats {
+                date,
+                focus_seconds: day.focus_seconds,
+                sessions: day.sessions,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Ticker
+// ============================================================================
+
+fn phase_message(phase: PomodoroPhase, config: &PomodoroConfig) -> (&'static str, String) {
+    match phase {
+        PomodoroPhase::Work => ("Break's over", format!("Time to write — {} minutes of focus.", config.work_minutes)),
+        PomodoroPhase::ShortBreak => ("Focus session done", format!("Take a {}-minute break.", config.short_break_minutes)),
+        PomodoroPhase::LongBreak => ("Focus session done", format!("Take a longer {}-minute break.", config.long_break_minutes)),
+    }
+}
+
+fn notify(app: &AppHandle, phase: PomodoroPhase, config: &PomodoroConfig) {
+    let (title, body) = phase_message(phase, config);
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("[Pomodoro] Failed to show notification: {}", e);
+    }
+}
+
+fn stop_ticker() {
+    if let Ok(mut guard) = TICKER.lock() {
+        if let Some(handle) = guard.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// (Re)start the loop driving the active timer; it ends with the timer.
+fn start_ticker(app: &AppHandle) {
+    stop_ticker();
+    let app = app.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let now = Instant::now();
+            let (state, change) = {
+                let Ok(mut timer) = TIMER.lock() else { break };
+                let Some(timer) = timer.as_mut() else { break };
+                let change = timer.tick(now).map(|record| (record, timer.config.clone()));
+                (timer.state(now), change)
+            };
+
+            if let Some((record, config)) = change {
+                if let Some(record) = record {
+                    record_focus(&app, record);
+                }
+                if config.notifications {
+                    notify(&app, state.phase, &config);
+                }
+                let _ = app.emit("pomodoro:phase-changed", &state);
+            }
+            if state.status == PomodoroStatus::Running {
+                let _ = app.emit("pomodoro:tick", &state);
+            }
+        }
+    });
+    if let Ok(mut guard) = TICKER.lock() {
+        *guard = Some(handle);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_pomodoro_config(app: AppHandle) -> Result<PomodoroConfig, String> {
+    Ok(load_data(&data_path(&app)?).config)
+}
+
+#[tauri::command]
+pub fn set_pomodoro_config(app: AppHandle, config: PomodoroConfig) -> Result<(), String> {
+    let path = data_path(&app)?;
+    let _guard = DATA_LOCK.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut data = load_data(&path);
+    data.config = config;
+    save_data(&path, &data)
+}
+
+/// Start a new focus cycle with the saved settings, or resume a paused one.
+#[tauri::command]
+pub fn pomodoro_start(app: AppHandle) -> Result<PomodoroState, String> {
+    let config = load_data(&data_path(&app)?).config;
+    let now = Instant::now();
+    let state = {
+        let mut guard = TIMER.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let timer = guard.get_or_insert_with(|| Timer::new(config, now));
+        timer.resume(now);
+        timer.state(now)
+    };
+    start_ticker(&app);
+    let _ = app.emit("pomodoro:tick", &state);
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn pomodoro_pause(app: AppHandle) -> Result<PomodoroState, String> {
+    let now = Instant::now();
+    let mut guard = TIMER.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let Some(timer) = guard.as_mut() else {
+        return Ok(idle_state(&load_data(&data_path(&app)?).config));
+    };
+    timer.pause(now);
+    let state = timer.state(now);
+    let _ = app.emit("pomodoro:tick", &state);
+    Ok(state)
+}
+
+/// Stop the timer, keeping the focus time of an unfinished work session.
+#[tauri::command]
+pub fn pomodoro_stop(app: AppHandle) -> Result<PomodoroState, String> {
+    let timer = TIMER.lock().map_err(|e| format!("Lock error: {}", e))?.take();
+    stop_ticker();
+    if let Some(record) = timer.and_then(|t| t.partial_focus(Instant::now())) {
+        record_focus(&app, record);
+    }
+    let state = idle_state(&load_data(&data_path(&app)?).config);
+    let _ = app.emit("pomodoro:tick", &state);
+    Ok(state)
+}
+
+/// Current timer state (after a webview reload, for example).
+#[tauri::command]
+pub fn get_pomodoro_state(app: AppHandle) -> Result<PomodoroState, String> {
+    let guard = TIMER.lock().map_err(|e| format!("Lock error: {}", e))?;
+    match guard.as_ref() {
+        Some(timer) => Ok(timer.state(Instant::now())),
+        None => Ok(idle_state(&load_data(&data_path(&app)?).config)),
+    }
+}
+
+/// Focus time for the last `days` days (default 7), oldest first.
+#[tauri::command]
+pub fn get_pomodoro_stats(app: AppHandle, days: Option<u32>) -> Result<Vec<DailyStats>, String> {
+    let data = load_data(&data_path(&app)?);
+    let days = days.unwrap_or(7).clamp(1, MAX_HISTORY_DAYS as u32);
+    Ok(daily_stats(&data, Local::now().date_naive(), days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PomodoroConfig {
+        PomodoroConfig {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            sessions_before_long_break: 2,
+            auto_continue: true,
+            notifications: false,
+        }
+    }
+
+    fn mins(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn test_cycle_with_long_break() {
+        let start = Instant::now();
+        let mut timer = Timer::new(config(), start);
+
+        assert_eq!(timer.tick(start + mins(10)), None);
+        assert_eq!(timer.state(start + mins(10)).remaining_ms, mins(15).as_millis() as u64);
+
+        let record = timer.tick(start + mins(25)).unwrap();
+        assert_eq!(record, Some(FocusRecord { seconds: 1500, completed: true }));
+        assert_eq!(timer.phase, PomodoroPhase::ShortBreak);
+
+        assert_eq!(timer.tick(start + mins(30)), Some(None));
+        assert_eq!(timer.phase, PomodoroPhase::Work);
+        timer.tick(start + mins(55)).unwrap();
+        assert_eq!(timer.phase, PomodoroPhase::LongBreak);
+        assert_eq!(timer.state(start + mins(55)).completed_sessions, 2);
+    }
+
+    #[test]
+    fn test_pause_stops_the_clock() {
+        let start = Instant::now();
+        let mut timer = Timer::new(config(), start);
+        timer.pause(start + mins(10));
+        assert_eq!(timer.state(start + mins(60)).status, PomodoroStatus::Paused);
+        assert_eq!(timer.tick(start + mins(60)), None);
+
+        timer.resume(start + mins(60));
+        assert_eq!(timer.tick(start + mins(74)), None);
+        assert!(timer.tick(start + mins(75)).is_some());
+    }
+
+    #[test]
+    fn test_without_auto_continue_next_phase_waits() {
+        let start = Instant::now();
+        let mut timer = Timer::new(
+            PomodoroConfig {
+                auto_continue: false,
+                ..config()
+            },
+            start,
+        );
+        timer.tick(start + mins(25)).unwrap();
+        let state = timer.state(start + mins(40));
+        assert_eq!(state.status, PomodoroStatus::Paused);
+        assert_eq!(state.remaining_ms, mins(5).as_millis() as u64);
+    }
+
+    #[test]
+    fn test_partial_focus_only_counts_work() {
+        let start = Instant::now();
+        let mut timer = Timer::new(config(), start);
+        assert_eq!(
+            timer.partial_focus(start + mins(3)),
+            Some(FocusRecord { seconds: 180, completed: false })
+        );
+        timer.tick(start + mins(25));
+        assert_eq!(timer.partial_focus(start + mins(26)), None);
+    }
+
+    #[test]
+    fn test_apply_record_and_daily_stats() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let mut data = PomodoroData::default();
+        data.days.insert("2024-01-01".to_string(), DayStats::default());
+        apply_record(&mut data, today, FocusRecord { seconds: 1500, completed: true });
+        apply_record(&mut data, today, FocusRecord { seconds: 60, completed: false });
+
+        assert!(!data.days.contains_key("2024-01-01"));
+        let stats = daily_stats(&data, today, 3);
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].date, "2026-03-08");
+        assert_eq!(stats[0].focus_seconds, 0);
+        assert_eq!(
+            stats[2],
+            DailyStats {
+                date: "2026-03-10".to_string(),
+                focus_seconds: 1560,
+                sessions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_data_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DATA_FILE);
+        assert_eq!(load_data(&path).config, PomodoroConfig::default());
+
+        let mut data = PomodoroData {
+            config: config(),
+            ..Default::default()
+        };
+        apply_record(&mut data, Local::now().date_naive(), FocusRecord { seconds: 60, completed: false });
+        save_data(&path, &data).unwrap();
+        let loaded = load_data(&path);
+        assert_eq!(loaded.config, config());
+        assert_eq!(loaded.days.len(), 1);
+    }
+}
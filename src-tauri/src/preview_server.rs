@@ -0,0 +1,416 @@
+//! Preview Server
+//!
+//! Serves the rendered HTML of one document over HTTP so it can be viewed in
+//! an external browser, or on another device when shared on the LAN. Pages
+//! get a small script that reloads them over a websocket (`/ws`) whenever
+//! the HTML changes.
+//!
+//! The frontend renders the document (the same way HTML export does) and
+//! hands the page over with `start_preview_server` / `update_preview`. When
+//! the watcher sees the previewed file change, the window that started the
+//! preview gets `preview:source-changed` and re-renders it, so saves and
+//! external edits show up in the browser.
+//!
+//! Every URL carries a random `key`; requests without it are refused, which
+//! keeps other devices on the network from reading the document.
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::net::{IpAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, WebviewWindow};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::capture_server::{parse_head, RequestHead};
+use crate::watcher::{content_hash, FsChangeEvent};
+
+/// Emitted to the previewing window when its file changed on disk
+const SOURCE_CHANGED_EVENT: &str = "preview:source-changed";
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reconnects after the app restarts the server or the device sleeps
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+  var key = new URLSearchParams(location.search).get("key") || "";
+  function connect() {
+    var ws = new WebSocket("ws://" + location.host + "/ws?key=" + encodeURIComponent(key));
+    ws.onmessage = function () { location.reload(); };
+    ws.onclose = function () { setTimeout(connect, 2000); };
+  }
+  connect();
+})();
+</script>"#;
+
+static PREVIEW: Mutex<Option<PreviewState>> = Mutex::new(None);
+
+struct PreviewState {
+    port: u16,
+    lan: bool,
+    key: String,
+    window_label: String,
+    path: Option<String>,
+    page: Arc<String>,
+    digest: String,
+    reload: broadcast::Sender<()>,
+    handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewStatus {
+    pub running: bool,
+    /// Previewed file (None for an untitled document)
+    pub path: Option<String>,
+    /// `http://127.0.0.1:<port>/?key=...`
+    pub url: Option<String>,
+    /// Address other devices can use, when shared on the LAN
+    pub lan_url: Option<String>,
+}
+
+/// The address this machine uses for outgoing traffic. Connecting a UDP
+/// socket sends nothing; it only picks the route.
+fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+fn page_url(host: &str, port: u16, key: &str) -> String {
+    format!("http://{host}:{port}/?key={key}")
+}
+
+fn status_of(state: Option<&PreviewState>) -> PreviewStatus {
+    let Some(state) = state else {
+        return PreviewStatus {
+            running: false,
+            path: None,
+            url: None,
+            lan_url: None,
+        };
+    };
+    PreviewStatus {
+        running: true,
+        path: state.path.clone(),
+        url: Some(page_url("127.0.0.1", state.port, &state.key)),
+        lan_url: state
+            .lan
+            .then(lan_address)
+            .flatten()
+            .map(|ip| page_url(&ip.to_string(), state.port, &state.key)),
+    }
+}
+
+/// Insert the live reload script before `</body>` (or at the end).
+fn with_reload_script(html: &str) -> String {
+    match html.rfind("</body>") {
+        Some(pos) => format!("{}{}\n{}", &html[..pos], RELOAD_SCRIPT, &html[pos..]),
+        None => format!("{html}\n{RELOAD_SCRIPT}"),
+    }
+}
+
+fn query_param<'a>(path: &'a str, name: &str) -> Option<&'a str> {
+    path.split_once('?')?
+        .1
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+// ============================================================================
+// HTTP
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Page,
+    Socket,
+    Forbidden,
+    NotFound,
+}
+
+fn route(head: &RequestHead, key: &str) -> Route {
+    let path = head.path.split('?').next().unwrap_or_default();
+    if head.method != "GET" || !matches!(path, "/" | "/ws") {
+        return Route::NotFound;
+    }
+    if query_param(&head.path, "key") != Some(key) {
+        return Route::Forbidden;
+    }
+    let upgrade = head
+        .header("upgrade")
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    match (path, upgrade) {
+        ("/ws", true) => Route::Socket,
+        ("/", _) => Route::Page,
+        _ => Route::NotFound,
+    }
+}
+
+async fn read_head(stream: &mut TcpStream) -> Option<RequestHead> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 2048];
+    loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            return std::str::from_utf8(&buf[..pos]).ok().and_then(parse_head);
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return None;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+async fn write_response(mut stream: TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         Cache-Control: no-store\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Complete the websocket handshake, then send "reload" on every change
+/// until either side goes away.
+async fn serve_socket(
+    mut stream: TcpStream,
+    head: &RequestHead,
+    mut reload: broadcast::Receiver<()>,
+) {
+    let Some(client_key) = head.header("sec-websocket-key") else {
+        return write_response(stream, "400 Bad Request", "text/plain", "Missing key").await;
+    };
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(client_key.as_bytes())
+    );
+    if stream.write_all(response.as_bytes()).await.is_err() {
+        return;
+    }
+    let mut socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    loop {
+        tokio::select! {
+            changed = reload.recv() => {
+                if matches!(changed, Err(broadcast::error::RecvError::Closed)) {
+                    break;
+                }
+                if socket.send(Message::Text("reload".into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.next() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream) {
+    let Ok(Some(head)) = tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream)).await else {
+        return;
+    };
+    let current = PREVIEW.lock().ok().and_then(|guard| {
+        guard.as_ref().map(|state| {
+            (
+                state.key.clone(),
+                state.page.clone(),
+                state.reload.subscribe(),
+            )
+        })
+    });
+    let Some((key, page, reload)) = current else {
+        return;
+    };
+    match route(&head, &key) {
+        Route::Page => {
+            write_response(stream, "200 OK", "text/html; charset=utf-8", &page).await;
+        }
+        Route::Socket => serve_socket(stream, &head, reload).await,
+        Route::Forbidden => {
+            write_response(stream, "403 Forbidden", "text/plain", "Forbidden").await
+        }
+        Route::NotFound => write_response(stream, "404 Not Found", "text/plain", "Not found").await,
+    }
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+fn stop() {
+    if let Some(state) = PREVIEW.lock().ok().and_then(|mut guard| guard.take()) {
+        state.handle.abort();
+        tracing::info!("[Preview] Stopped");
+    }
+}
+
+/// Called by the watcher for every change: asks the previewing window to
+/// re-render when the previewed file changed.
+pub fn apply_fs_change(app: &AppHandle, event: &FsChangeEvent) {
+    let Some((label, path)) = PREVIEW.lock().ok().and_then(|guard| {
+        guard
+            .as_ref()
+            .and_then(|state| Some((state.window_label.clone(), state.path.clone()?)))
+    }) else {
+        return;
+    };
+    let touched = event
+        .paths
+        .iter()
+        .chain(event.to.iter())
+        .any(|p| *p == path);
+    if touched {
+        let _ = app.emit_to(label.as_str(), SOURCE_CHANGED_EVENT, path);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Serve `html` for `path`, replacing any running preview. With `lan`, other
+/// devices on the network can connect too.
+#[tauri::command]
+pub async fn start_preview_server(
+    window: WebviewWindow,
+    path: Option<String>,
+    html: String,
+    lan: bool,
+) -> Result<PreviewStatus, String> {
+    stop();
+    let host = if lan { "0.0.0.0" } else { "127.0.0.1" };
+    let listener = TcpListener::bind((host, 0))
+        .await
+        .map_err(|e| format!("Failed to start preview server: {e}"))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(handle_connection(stream));
+                }
+                Err(e) => tracing::debug!("[Preview] Accept error: {}", e),
+            }
+        }
+    });
+    let (reload, _) = broadcast::channel(4);
+    let state = PreviewState {
+        port,
+        lan,
+        key: uuid::Uuid::new_v4().simple().to_string(),
+        window_label: window.label().to_string(),
+        path,
+        digest: content_hash(html.as_bytes()),
+        page: Arc::new(with_reload_script(&html)),
+        reload,
+        handle,
+    };
+    tracing::info!("[Preview] Serving on {}:{}", host, port);
+    let mut guard = PREVIEW.lock().map_err(|_| "Preview lock poisoned")?;
+    let status = status_of(Some(&state));
+    *guard = Some(state);
+    Ok(status)
+}
+
+/// Replace the served page and reload connected browsers. `path` retargets
+/// the preview (e.g. after Save As).
+#[tauri::command]
+pub fn update_preview(path: Option<String>, html: String) -> Result<PreviewStatus, String> {
+    let mut guard = PREVIEW.lock().map_err(|_| "Preview lock poisoned")?;
+    let state = guard.as_mut().ok_or("Preview server is not running")?;
+    if path.is_some() {
+        state.path = path;
+    }
+    let digest = content_hash(html.as_bytes());
+    if digest != state.digest {
+        state.digest = digest;
+        state.page = Arc::new(with_reload_script(&html));
+        // No receivers just means no browser is connected
+        let _ = state.reload.send(());
+    }
+    Ok(status_of(Some(state)))
+}
+
+#[tauri::command]
+pub fn get_preview_status() -> PreviewStatus {
+    status_of(PREVIEW.lock().ok().as_deref().and_then(Option::as_ref))
+}
+
+#[tauri::command]
+pub fn stop_preview_server() {
+    stop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(path: &str, upgrade: bool) -> RequestHead {
+        let mut headers = vec![("Host".to_string(), "localhost".to_string())];
+        if upgrade {
+            headers.push(("Upgrade".to_string(), "websocket".to_string()));
+        }
+        RequestHead {
+            method: "GET".into(),
+            path: path.into(),
+            headers,
+        }
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route(&get("/?key=abc", false), "abc"), Route::Page);
+        assert_eq!(route(&get("/ws?key=abc", true), "abc"), Route::Socket);
+        assert_eq!(route(&get("/ws?key=abc", false), "abc"), Route::NotFound);
+        assert_eq!(route(&get("/?key=abd", false), "abc"), Route::Forbidden);
+        assert_eq!(route(&get("/", false), "abc"), Route::Forbidden);
+        assert_eq!(route(&get("/other?key=abc", false), "abc"), Route::NotFound);
+    }
+
+    #[test]
+    fn test_reload_script_goes_before_body_end() {
+        let page = with_reload_script("<html><body><p>Hi</p></body></html>");
+        assert!(page.starts_with("<html><body><p>Hi</p><script>"));
+        assert!(page.ends_with("</script>\n</body></html>"));
+        assert!(with_reload_script("<p>bare</p>").starts_with("<p>bare</p>\n<script>"));
+    }
+
+    #[test]
+    fn test_query_param() {
+        assert_eq!(query_param("/ws?a=1&key=xyz", "key"), Some("xyz"));
+        assert_eq!(query_param("/ws", "key"), None);
+        assert_eq!(
+            page_url("127.0.0.1", 8080, "k"),
+            "http://127.0.0.1:8080/?key=k"
+        );
+    }
+
+    #[test]
+    fn test_status_when_stopped() {
+        let status = status_of(None);
+        assert!(!status.running);
+        assert_eq!(status.url, None);
+    }
+}
@@ -0,0 +1,670 @@
+//! Publishing
+//!
+//! Publishes a document to an external target and keeps track of where it
+//! went in the document's own frontmatter, so later publishes update the
+//! same post:
+//!
+//! ```yaml
+//! publish_gist_id: "8f1c…"
+//! publish_gist_url: "https://gist.github.com/…"
+//! publish_gist_updated: "2026-01-31T09:12:00Z"
+//! ```
+//!
+//! Targets:
+//! - `gist` — a GitHub Gist holding the markdown (needs a token with the
+//!   `gist` scope).
+//! - `webhook` — POSTs JSON `{action, id, title, fileName, markdown, html}` to
+//!   a user-supplied https URL, `action` being "publish", "update" or
+//!   "unpublish". A JSON reply with `id`/`url` is recorded; the token, if
+//!   any, is sent as a bearer token.
+//!
+//! Tokens live in the OS keychain and never travel back to the frontend. The
+//! webhook URL is stored with its token, so a request can't send the token
+//! anywhere else.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Keychain service under which target tokens are stored
+const KEYCHAIN_SERVICE: &str = "app.vmark.publish";
+
+const GITHUB_API: &str = "https://api.github.com";
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Where a document is published
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishTarget {
+    Gist,
+    Webhook,
+}
+
+impl PublishTarget {
+    fn key(self) -> &'static str {
+        match self {
+            PublishTarget::Gist => "gist",
+            PublishTarget::Webhook => "webhook",
+        }
+    }
+
+    fn id_field(self) -> String {
+        format!("publish_{}_id", self.key())
+    }
+
+    fn url_field(self) -> String {
+        format!("publish_{}_url", self.key())
+    }
+
+    fn updated_field(self) -> String {
+        format!("publish_{}_updated", self.key())
+    }
+}
+
+/// A publish/update/unpublish request from the frontend
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishRequest {
+    pub target: PublishTarget,
+    /// Full document markdown, frontmatter included
+    pub content: String,
+    /// Rendered HTML, passed through to webhooks
+    #[serde(default)]
+    pub html: Option<String>,
+    /// Document file name, used as the gist file name
+    #[serde(default)]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Create a public (rather than secret) gist
+    #[serde(default)]
+    pub public: bool,
+}
+
+/// Result of a publish operation
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishOutcome {
+    /// Document content with updated publish metadata
+    pub content: String,
+    pub id: Option<String>,
+    pub url: Option<String>,
+}
+
+// ============================================================================
+// Frontmatter
+// ============================================================================
+
+/// Split `content` into (frontmatter lines, body). Frontmatter is the block
+/// between a leading `---` line and the next `---` or `...` line.
+pub(crate) fn split_frontmatter(content: &str) -> (Option<Vec<&str>>, &str) {
+    let text = content.trim_start_matches('\u{FEFF}');
+    let Some(first_break) = text.find('\n') else {
+        return (None, content);
+    };
+    if text[..first_break].trim_end_matches('\r') != "---" {
+        return (None, content);
+    }
+
+    let mut lines = Vec::new();
+    let mut offset = first_break + 1;
+    while offset < text.len() {
+        let end = text[offset..].find('\n').map_or(text.len(), |i| offset + i + 1);
+        let line = text[offset..end].trim_end_matches(['\r', '\n']);
+        if line == "---" || line == "..." {
+            return (Some(lines), &text[end..]);
+        }
+        lines.push(line);
+        offset = end;
+    }
+    // Unclosed: not frontmatter
+    (None, content)
+}
+
+pub(crate) fn top_level_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    line.split_once(':').map(|(key, _)| key.trim())
+}
+
+/// Value of a top-level `key: value` frontmatter line.
+pub(crate) fn frontmatter_field(content: &str, key: &str) -> Option<String> {
+    let (lines, _) = split_frontmatter(content);
+    lines?.into_iter().find_map(|line| {
+        if top_level_key(line)? != key {
+            return None;
+        }
+        let (_, value) = line.split_once(':')?;
+        let value = unquote(value.trim());
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+pub(crate) fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        value[1..value.len() - 1].replace("\\\"", "\"").replace("\\\\", "\\")
+    } else {
+        value.trim_matches('\'').to_string()
+    }
+}
+
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Set (`Some`) or remove (`None`) top-level frontmatter fields, creating the
+/// frontmatter block when needed and dropping it when it ends up empty.
+fn set_frontmatter_fields(content: &str, fields: &[(String, Option<String>)]) -> String {
+    let quoted: Vec<(String, Option<String>)> = fields
+        .iter()
+        .map(|(key, value)| (key.clone(), value.as_deref().map(quote)))
+        .collect();
+    set_frontmatter_values(content, &quoted)
+}
+
+/// Like `set_frontmatter_fields`, with values written as given (YAML
+/// scalars, flow lists).
+pub(crate) fn set_frontmatter_values(
+    content: &str,
+    fields: &[(String, Option<String>)],
+) -> String {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let (existing, body) = split_frontmatter(content);
+    let had_frontmatter = existing.is_some();
+    let mut lines: Vec<String> = existing
+        .unwrap_or_default()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    for (key, value) in fields {
+        let position = lines.iter().position(|l| top_level_key(l) == Some(key.as_str()));
+        match (position, value) {
+            (Some(i), Some(v)) => lines[i] = format!("{}: {}", key, v),
+            (Some(i), None) => {
+                lines.remove(i);
+            }
+            (None, Some(v)) => lines.push(format!("{}: {}", key, v)),
+            (None, None) => {}
+        }
+    }
+
+    if lines.is_empty() {
+        return if had_frontmatter {
+            body.to_string()
+        } else {
+            content.to_string()
+        };
+    }
+    let mut out = format!("---{}", newline);
+    for line in &lines {
+        out.push_str(line);
+        out.push_str(newline);
+    }
+    out.push_str("---");
+    out.push_str(newline);
+    out.push_str(body);
+    out
+}
+
+/// Document body without frontmatter, as published.
+fn publish_body(content: &str) -> &str {
+    let (_, body) = split_frontmatter(content);
+    body.trim_start_matches(['\r', '\n'])
+}
+
+/// Title: explicit, frontmatter `title`, first H1, file stem, "Untitled".
+fn document_title(request: &PublishRequest) -> String {
+    request
+        .title
+        .clone()
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| frontmatter_field(&request.content, "title"))
+        .or_else(|| {
+            publish_body(&request.content).lines().find_map(|line| {
+                line.strip_prefix("# ")
+                    .map(|h| h.trim().to_string())
+                    .filter(|h| !h.is_empty())
+            })
+        })
+        .or_else(|| {
+            request.file_name.as_deref().and_then(|name| {
+                std::path::Path::new(name)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+            })
+        })
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+fn gist_file_name(request: &PublishRequest) -> String {
+    request
+        .file_name
+        .as_deref()
+        .and_then(|name| std::path::Path::new(name).file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "document.md".to_string())
+}
+
+/// Content with the target's publish metadata recorded, or cleared when
+/// `published` is false.
+fn record_publish(
+    content: &str,
+    target: PublishTarget,
+    published: bool,
+    id: Option<&str>,
+    url: Option<&str>,
+) -> String {
+    let updated =
+        published.then(|| chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    set_frontmatter_fields(
+        content,
+        &[
+            (target.id_field(), id.map(str::to_string)),
+            (target.url_field(), url.map(str::to_string)),
+            (target.updated_field(), updated),
+        ],
+    )
+}
+
+// ============================================================================
+// Token storage
+// ============================================================================
+
+fn keychain_entry(target: PublishTarget) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, target.key())
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+fn load_token(target: PublishTarget) -> Result<Option<String>, String> {
+    match keychain_entry(target)?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read {} token from keychain: {}", target.key(), e)),
+    }
+}
+
+/// Webhook endpoint and its optional bearer token, stored together
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct WebhookCredentials {
+    url: String,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+fn load_webhook() -> Result<Option<WebhookCredentials>, String> {
+    load_token(PublishTarget::Webhook)?
+        .map(|stored| {
+            serde_json::from_str(&stored)
+                .map_err(|_| "Stored webhook settings are damaged; set them again".to_string())
+        })
+        .transpose()
+}
+
+fn remove_token(target: PublishTarget) -> Result<(), String> {
+    match keychain_entry(target)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove {} token from keychain: {}", target.key(), e)),
+    }
+}
+
+// ============================================================================
+// Targets
+// ============================================================================
+
+fn make_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .user_agent(concat!("VMark/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+async fn error_for_status(resp: reqwest::Response, what: &str) -> Result<reqwest::Response, String> {
+    if resp.status().is_success() {
+        return Ok(resp);
+    }
+    let status = resp.status();
+    let text = resp.text().await.unwrap_or_default();
+    // GitHub errors are JSON with a `message`
+    let message = serde_json::from_str::<Value>(&text)
+        .ok()
+        .and_then(|v| v.get("message").and_then(Value::as_str).map(str::to_string))
+        .unwrap_or(text);
+    Err(format!("{} failed (HTTP {}): {}", what, status.as_u16(), message.trim()))
+}
+
+/// `(id, url)` from a GitHub gist response.
+fn parse_gist_response(value: &Value) -> Result<(String, String), String> {
+    let id = value.get("id").and_then(Value::as_str);
+    let url = value.get("html_url").and_then(Value::as_str);
+    match (id, url) {
+        (Some(id), Some(url)) => Ok((id.to_string(), url.to_string())),
+        _ => Err("Unexpected response from GitHub".to_string()),
+    }
+}
+
+fn gist_token() -> Result<String, String> {
+    load_token(PublishTarget::Gist)?
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "Add a GitHub token with the gist scope before publishing".to_string())
+}
+
+/// Create the gist, or update it when `existing_id` is set.
+async fn publish_gist(request: &PublishRequest, existing_id: Option<&str>) -> Result<(String, String), String> {
+    let token = gist_token()?;
+    let client = make_client()?;
+    let files = json!({ gist_file_name(request): { "content": publish_body(&request.content) } });
+    let description = document_title(request);
+
+    let builder = match existing_id {
+        Some(id) => client
+            .patch(format!("{}/gists/{}", GITHUB_API, id))
+            .json(&json!({ "description": description, "files": files })),
+        None => client
+            .post(format!("{}/gists", GITHUB_API))
+            .json(&json!({ "description": description, "public": request.public, "files": files })),
+    };
+    let resp = builder
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {}", e))?;
+    let resp = error_for_status(resp, "Publishing gist").await?;
+    let value: Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse GitHub response: {}", e))?;
+    parse_gist_response(&value)
+}
+
+async fn delete_gist(id: &str) -> Result<(), String> {
+    let token = gist_token()?;
+    let resp = make_client()?
+        .delete(format!("{}/gists/{}", GITHUB_API, id))
+        .bearer_auth(token)
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| format!("GitHub request failed: {}", e))?;
+    // Already deleted on GitHub: nothing left to unpublish
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    error_for_status(resp, "Deleting gist").await.map(|_| ())
+}
+
+/// Parse a webhook URL; only https is accepted, since the token goes along.
+fn validate_webhook_url(raw: &str) -> Result<reqwest::Url, String> {
+    let url = reqwest::Url::parse(raw.trim()).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if url.scheme() != "https" {
+        return Err("Webhook URL must use https".to_string());
+    }
+    Ok(url)
+}
+
+/// POST to the webhook. Returns `(id, url)` when the reply includes them.
+async fn send_webhook(
+    request: &PublishRequest,
+    action: &str,
+    existing_id: Option<&str>,
+) -> Result<(Option<String>, Option<String>), String> {
+    let webhook = load_webhook()?.ok_or("Set up the webhook in Settings before publishing")?;
+    let url = validate_webhook_url(&webhook.url)?;
+    let payload = json!({
+        "action": action,
+        "id": existing_id,
+        "title": document_title(request),
+        "fileName": request.file_name,
+        "markdown": publish_body(&request.content),
+        "html": request.html,
+    });
+
+    let mut builder = make_client()?.post(url).json(&payload);
+    if let Some(token) = webhook.token.filter(|t| !t.is_empty()) {
+        builder = builder.bearer_auth(token);
+    }
+    let resp = builder
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request failed: {}", e))?;
+    let resp = error_for_status(resp, "Webhook").await?;
+
+    // Replies are optional; anything that isn't JSON just means "no id/url"
+    let reply: Value = resp.json().await.unwrap_or(Value::Null);
+    let field = |name: &str| match reply.get(name) {
+        Some(Value::String(s)) if !s.is_empty() => Some(s.clone()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    };
+    Ok((field("id"), field("url")))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+fn store_secret(target: PublishTarget, secret: &str) -> Result<(), String> {
+    keychain_entry(target)?
+        .set_password(secret)
+        .map_err(|e| format!("Failed to save {} token to keychain: {}", target.key(), e))
+}
+
+/// Store the gist token in the keychain. An empty token removes it.
+/// The webhook is set up with `set_publish_webhook`.
+#[tauri::command]
+pub fn set_publish_token(target: PublishTarget, token: String) -> Result<(), String> {
+    if target == PublishTarget::Webhook {
+        return Err("Set the webhook URL and token together".to_string());
+    }
+    let token = token.trim();
+    if token.is_empty() {
+        return remove_token(target);
+    }
+    store_secret(target, token)
+}
+
+/// Store the webhook URL (https only) with its optional token.
+#[tauri::command]
+pub fn set_publish_webhook(url: String, token: Option<String>) -> Result<(), String> {
+    let url = validate_webhook_url(&url)?;
+    let credentials = WebhookCredentials {
+        url: url.to_string(),
+        token: token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()),
+    };
+    let stored = serde_json::to_string(&credentials).map_err(|e| e.to_string())?;
+    store_secret(PublishTarget::Webhook, &stored)
+}
+
+#[tauri::command]
+pub fn delete_publish_token(target: PublishTarget) -> Result<(), String> {
+    remove_token(target)
+}
+
+/// Whether a token (or the webhook) is stored for `target`; the secret
+/// itself stays in the keychain.
+#[tauri::command]
+pub fn has_publish_token(target: PublishTarget) -> Result<bool, String> {
+    Ok(load_token(target)?.is_some_and(|t| !t.is_empty()))
+}
+
+/// Publish the document, updating the existing post if it was published before.
+#[tauri::command]
+pub async fn publish_document(request: PublishRequest) -> Result<PublishOutcome, String> {
+    let existing = frontmatter_field(&request.content, &request.target.id_field());
+    publish_to_target(&request, existing.as_deref()).await
+}
+
+/// Update a previously published document.
+#[tauri::command]
+pub async fn update_published_document(request: PublishRequest) -> Result<PublishOutcome, String> {
+    let existing = frontmatter_field(&request.content, &request.target.id_field())
+        .ok_or_else(|| format!("Document is not published to {}", request.target.key()))?;
+    publish_to_target(&request, Some(&existing)).await
+}
+
+/// Remove the published post and the document's publish metadata.
+#[tauri::command]
+pub async fn unpublish_document(request: PublishRequest) -> Result<PublishOutcome, String> {
+    let target = request.target;
+    let existing = frontmatter_field(&request.content, &target.id_field());
+    match target {
+        PublishTarget::Gist => {
+            let id = existing.ok_or("Document is not published to gist")?;
+            delete_gist(&id).await?;
+        }
+        PublishTarget::Webhook => {
+            send_webhook(&request, "unpublish", existing.as_deref()).await?;
+        }
+    }
+    tracing::info!("[Publish] Unpublished from {}", target.key());
+    Ok(PublishOutcome {
+        content: record_publish(&request.content, target, false, None, None),
+        id: None,
+        url: None,
+    })
+}
+
+async fn publish_to_target(request: &PublishRequest, existing_id: Option<&str>) -> Result<PublishOutcome, String> {
+    let (id, url) = match request.target {
+        PublishTarget::Gist => {
+            let (id, url) = publish_gist(request, existing_id).await?;
+            (Some(id), Some(url))
+        }
+        PublishTarget::Webhook => {
+            let action = if existing_id.is_some() { "update" } else { "publish" };
+            let (id, url) = send_webhook(request, action, existing_id).await?;
+            // Keep what we knew if the endpoint doesn't echo it back
+            let url = url.or_else(|| frontmatter_field(&request.content, &request.target.url_field()));
+            (id.or(existing_id.map(str::to_string)), url)
+        }
+    };
+    tracing::info!("[Publish] Published to {}", request.target.key());
+
+    let content = record_publish(&request.content, request.target, true, id.as_deref(), url.as_deref());
+    Ok(PublishOutcome { content, id, url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(content: &str) -> PublishRequest {
+        PublishRequest {
+            target: PublishTarget::Gist,
+            content: content.to_string(),
+            html: None,
+            file_name: None,
+            title: None,
+            public: false,
+        }
+    }
+
+    #[test]
+    fn test_frontmatter_field_reads_top_level_keys() {
+        let doc = "---\ntitle: \"Notes: day 1\"\nnested:\n  title: inner\n---\n# Body\n";
+        assert_eq!(frontmatter_field(doc, "title").as_deref(), Some("Notes: day 1"));
+        assert_eq!(frontmatter_field(doc, "missing"), None);
+        assert_eq!(frontmatter_field("# No frontmatter\n", "title"), None);
+        assert_eq!(frontmatter_field("---\ntitle: x\nunclosed\n", "title"), None);
+    }
+
+    #[test]
+    fn test_set_fields_creates_and_updates_frontmatter() {
+        let fields = vec![("publish_gist_id".to_string(), Some("abc".to_string()))];
+        let created = set_frontmatter_fields("# Title\n", &fields);
+        assert_eq!(created, "---\npublish_gist_id: \"abc\"\n---\n# Title\n");
+
+        let fields = vec![("publish_gist_id".to_string(), Some("def".to_string()))];
+        let updated = set_frontmatter_fields("---\ntags: [a]\npublish_gist_id: abc\n---\nBody\n", &fields);
+        assert_eq!(updated, "---\ntags: [a]\npublish_gist_id: \"def\"\n---\nBody\n");
+    }
+
+    #[test]
+    fn test_set_fields_removes_and_drops_empty_block() {
+        let fields = vec![("publish_gist_id".to_string(), None)];
+        assert_eq!(set_frontmatter_fields("---\npublish_gist_id: abc\n---\nBody\n", &fields), "Body\n");
+        assert_eq!(set_frontmatter_fields("Body\n", &fields), "Body\n");
+    }
+
+    #[test]
+    fn test_set_fields_preserves_crlf() {
+        let fields = vec![("k".to_string(), Some("v".to_string()))];
+        let out = set_frontmatter_fields("---\r\na: 1\r\n---\r\nBody\r\n", &fields);
+        assert_eq!(out, "---\r\na: 1\r\nk: \"v\"\r\n---\r\nBody\r\n");
+    }
+
+    #[test]
+    fn test_record_publish_round_trips() {
+        let content = record_publish(
+            "# Post\n",
+            PublishTarget::Gist,
+            true,
+            Some("123"),
+            Some("https://gist.github.com/u/123"),
+        );
+        assert_eq!(frontmatter_field(&content, "publish_gist_id").as_deref(), Some("123"));
+        assert_eq!(
+            frontmatter_field(&content, "publish_gist_url").as_deref(),
+            Some("https://gist.github.com/u/123")
+        );
+        assert!(frontmatter_field(&content, "publish_gist_updated").is_some());
+        assert_eq!(publish_body(&content), "# Post\n");
+
+        let cleared = record_publish(&content, PublishTarget::Gist, false, None, None);
+        assert_eq!(cleared, "# Post\n");
+    }
+
+    #[test]
+    fn test_document_title_fallbacks() {
+        let mut req = request("---\ntitle: From Frontmatter\n---\n# Heading\n");
+        assert_eq!(document_title(&req), "From Frontmatter");
+        req.content = "Intro\n# Heading\n".to_string();
+        assert_eq!(document_title(&req), "Heading");
+        req.content = "No heading".to_string();
+        req.file_name = Some("/notes/idea.md".to_string());
+        assert_eq!(document_title(&req), "idea");
+        assert_eq!(gist_file_name(&req), "idea.md");
+        req.file_name = None;
+        assert_eq!(document_title(&req), "Untitled");
+        assert_eq!(gist_file_name(&req), "document.md");
+    }
+
+    #[test]
+    fn test_webhook_url_validation() {
+        assert!(validate_webhook_url("").is_err());
+        assert!(validate_webhook_url("ftp://example.com/hook").is_err());
+        assert!(validate_webhook_url("http://example.com/hook").unwrap_err().contains("https"));
+        assert_eq!(
+            validate_webhook_url(" https://example.com/hook ").unwrap().as_str(),
+            "https://example.com/hook"
+        );
+    }
+
+    #[test]
+    fn test_webhook_credentials_round_trip() {
+        let credentials = WebhookCredentials {
+            url: "https://example.com/hook".to_string(),
+            token: Some("t0k".to_string()),
+        };
+        let stored = serde_json::to_string(&credentials).unwrap();
+        assert_eq!(serde_json::from_str::<WebhookCredentials>(&stored).unwrap(), credentials);
+        let bare: WebhookCredentials = serde_json::from_str(r#"{"url":"https://example.com"}"#).unwrap();
+        assert_eq!(bare.token, None);
+    }
+
+    #[test]
+    fn test_parse_gist_response() {
+        let value = json!({ "id": "aa11", "html_url": "https://gist.github.com/aa11" });
+        assert_eq!(
+            parse_gist_response(&value).unwrap(),
+            ("aa11".to_string(), "https://gist.github.com/aa11".to_string())
+        );
+        assert!(parse_gist_response(&json!({ "message": "Bad credentials" })).is_err());
+    }
+}
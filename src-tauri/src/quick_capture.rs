@@ -0,0 +1,229 @@
+//! Quick Capture
+//!
+//! A small always-on-top window (opened from the tray or a global hotkey)
+//! whose text is appended to an inbox file under a timestamped heading.
+//!
+//! The inbox path is stored in the app data directory (`quick-capture.json`)
+//! and defaults to `~/Documents/VMark/inbox.md`.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::app_paths;
+
+/// Settings file name in the app data directory
+pub const QUICK_CAPTURE_FILE: &str = "quick-capture.json";
+
+/// Label of the capture window
+pub const CAPTURE_WINDOW_LABEL: &str = "capture";
+
+const CAPTURE_WIDTH: f64 = 440.0;
+const CAPTURE_HEIGHT: f64 = 240.0;
+
+/// On-disk quick capture settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CaptureSettings {
+    #[serde(default)]
+    inbox_path: Option<String>,
+}
+
+/// Loaded settings plus the file they persist to
+struct CaptureState {
+    file: PathBuf,
+    settings: CaptureSettings,
+}
+
+static CAPTURE_STATE: OnceLock<Mutex<CaptureState>> = OnceLock::new();
+
+/// Load quick capture settings from app data. Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(QUICK_CAPTURE_FILE);
+    let settings = load_settings(&file);
+    let _ = CAPTURE_STATE.set(Mutex::new(CaptureState { file, settings }));
+    Ok(())
+}
+
+fn load_settings(file: &Path) -> CaptureSettings {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(file: &Path, settings: &CaptureSettings) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize quick capture settings: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+fn default_inbox_path() -> PathBuf {
+    dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_default()
+        .join("VMark")
+        .join("inbox.md")
+}
+
+/// Inbox file captures are appended to.
+pub fn inbox_path() -> PathBuf {
+    CAPTURE_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .and_then(|guard| guard.settings.inbox_path.clone())
+        .map(PathBuf::from)
+        .unwrap_or_else(default_inbox_path)
+}
+
+/// Markdown appended for one capture.
+fn format_entry(text: &str, at: DateTime<Local>) -> String {
+    format!("\n## {}\n\n{}\n", at.format("%Y-%m-%d %H:%M"), text.trim())
+}
+
+/// Append a capture to the inbox, creating the file (with an `# Inbox`
+/// heading) and its folder if needed.
+pub(crate) fn append_entry(inbox: &Path, text: &str, at: DateTime<Local>) -> Result<(), String> {
+    if let Some(parent) = inbox.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let existing = fs::read(inbox).ok();
+    let mut content = String::new();
+    match &existing {
+        None => content.push_str("# Inbox\n"),
+        Some(bytes) if !bytes.is_empty() && !bytes.ends_with(b"\n") => content.push('\n'),
+        Some(_) => {}
+    }
+    content.push_str(&format_entry(text, at));
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(inbox)
+        .map_err(|e| format!("Failed to open {}: {}", inbox.display(), e))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", inbox.display(), e))
+}
+
+/// Show the capture window, creating it if needed.
+pub fn show_capture_window(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(CAPTURE_WINDOW_LABEL) {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    WebviewWindowBuilder::new(app, CAPTURE_WINDOW_LABEL, WebviewUrl::App("/capture".into()))
+        .title("Quick Note")
+        .inner_size(CAPTURE_WIDTH, CAPTURE_HEIGHT)
+        .resizable(false)
+        .minimizable(false)
+        .maximizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Append text to the inbox. Returns the inbox path.
+#[tauri::command]
+pub fn append_quick_capture(text: String) -> Result<String, String> {
+    if text.trim().is_empty() {
+        return Err("Nothing to capture".to_string());
+    }
+    let inbox = inbox_path();
+    append_entry(&inbox, &text, Local::now())?;
+    Ok(inbox.to_string_lossy().into_owned())
+}
+
+/// Current inbox path.
+#[tauri::command]
+pub fn get_quick_capture_inbox() -> String {
+    inbox_path().to_string_lossy().into_owned()
+}
+
+/// Change the inbox path (None restores the default).
+#[tauri::command]
+pub fn set_quick_capture_inbox(path: Option<String>) -> Result<(), String> {
+    let state = CAPTURE_STATE
+        .get()
+        .ok_or("Quick capture not initialized")?;
+    let mut guard = state.lock().map_err(|_| "Quick capture lock poisoned".to_string())?;
+    guard.settings.inbox_path = path.filter(|p| !p.trim().is_empty());
+    save_settings(&guard.file, &guard.settings)
+}
+
+/// Open the quick capture window.
+#[tauri::command]
+pub fn open_quick_capture(app: AppHandle) -> Result<(), String> {
+    show_capture_window(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn formats_entry_with_timestamp_heading() {
+        assert_eq!(format_entry("  Buy milk \n", at()), "\n## 2026-03-14 09:05\n\nBuy milk\n");
+    }
+
+    #[test]
+    fn creates_inbox_with_heading() {
+        let dir = tempfile::tempdir().unwrap();
+        let inbox = dir.path().join("notes").join("inbox.md");
+
+        append_entry(&inbox, "First", at()).unwrap();
+        append_entry(&inbox, "Second", at()).unwrap();
+
+        let content = fs::read_to_string(&inbox).unwrap();
+        assert!(content.starts_with("# Inbox\n\n## 2026-03-14 09:05\n\nFirst\n"));
+        assert!(content.ends_with("\n## 2026-03-14 09:05\n\nSecond\n"));
+    }
+
+    #[test]
+    fn appends_after_missing_trailing_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let inbox = dir.path().join("inbox.md");
+        fs::write(&inbox, "# My inbox").unwrap();
+
+        append_entry(&inbox, "Idea", at()).unwrap();
+        assert_eq!(
+            fs::read_to_string(&inbox).unwrap(),
+            "# My inbox\n\n## 2026-03-14 09:05\n\nIdea\n"
+        );
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(QUICK_CAPTURE_FILE);
+        assert_eq!(load_settings(&file), CaptureSettings::default());
+
+        let settings = CaptureSettings {
+            inbox_path: Some("/notes/inbox.md".into()),
+        };
+        save_settings(&file, &settings).unwrap();
+        assert_eq!(load_settings(&file), settings);
+    }
+}
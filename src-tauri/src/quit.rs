@@ -81,8 +81,7 @@ pub fn cancel_quit() {
 /// Handle a window being destroyed while quit is in progress.
 pub fn handle_window_destroyed(app: &AppHandle, label: &str) {
     let quit_in_progress = QUIT_IN_PROGRESS.load(Ordering::SeqCst);
-    #[cfg(debug_assertions)]
-    eprintln!("[Tauri] handle_window_destroyed: label={}, quit_in_progress={}", label, quit_in_progress);
+    tracing::debug!("handle_window_destroyed: label={}, quit_in_progress={}", label, quit_in_progress);
 
     if !quit_in_progress {
         return;
@@ -93,8 +92,7 @@ pub fn handle_window_destroyed(app: &AppHandle, label: &str) {
     }
 
     if remove_quit_target(label) {
-        #[cfg(debug_assertions)]
-        eprintln!("[Tauri] handle_window_destroyed: all targets done, calling app.exit(0)");
+        tracing::debug!("handle_window_destroyed: all targets done, calling app.exit(0)");
         // Allow the ExitRequested handler through (some platforms trigger it again during quit).
         set_exit_allowed(true);
         mcp_server::cleanup(app);
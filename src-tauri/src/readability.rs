@@ -0,0 +1,514 @@
+//! Readability
+//!
+//! `analyze_readability` scores a Markdown document paragraph by paragraph
+//! so the editor can shade hard-to-read passages:
+//!
+//! - Flesch reading ease and Flesch–Kincaid grade, from words per sentence
+//!   and syllables per word (syllables are estimated from vowel groups)
+//! - sentence lengths, bucketed for a distribution chart
+//! - sentences that look passive ("was written", "are being reviewed")
+//! - long words, i.e. three or more syllables
+//!
+//! The formulas are calibrated for English. Frontmatter, code blocks,
+//! headings and tables aren't prose and are skipped, as are paragraphs
+//! written mostly in CJK scripts, which have no spaces to count words by.
+
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+use crate::workspace_stats::is_cjk;
+
+/// Words with at least this many syllables count as long
+const LONG_WORD_SYLLABLES: usize = 3;
+
+/// Upper bounds (words, inclusive) and labels for the sentence length histogram
+const SENTENCE_BUCKETS: &[(usize, &str)] = &[
+    (10, "1–10"),
+    (20, "11–20"),
+    (30, "21–30"),
+    (40, "31–40"),
+    (usize::MAX, "> 40"),
+];
+
+/// Grade at which a paragraph is shaded as hardest
+const HARDEST_GRADE: f64 = 16.0;
+
+/// Common abbreviations whose period doesn't end a sentence
+const ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "st", "vs", "etc", "e.g", "i.e", "a.m", "p.m", "fig", "no",
+    "approx",
+];
+
+const BE_FORMS: &[&str] = &["am", "is", "are", "was", "were", "be", "been", "being"];
+
+/// Irregular past participles (regular ones end in "-ed")
+const IRREGULAR_PARTICIPLES: &[&str] = &[
+    "begun",
+    "born",
+    "bought",
+    "broken",
+    "brought",
+    "built",
+    "caught",
+    "chosen",
+    "done",
+    "drawn",
+    "driven",
+    "eaten",
+    "fallen",
+    "felt",
+    "found",
+    "forgotten",
+    "given",
+    "gotten",
+    "grown",
+    "heard",
+    "held",
+    "hidden",
+    "kept",
+    "known",
+    "left",
+    "lost",
+    "made",
+    "meant",
+    "paid",
+    "put",
+    "read",
+    "run",
+    "said",
+    "seen",
+    "sent",
+    "set",
+    "shown",
+    "sold",
+    "spoken",
+    "stolen",
+    "taken",
+    "taught",
+    "thought",
+    "thrown",
+    "told",
+    "torn",
+    "understood",
+    "won",
+    "worn",
+    "written",
+];
+
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").expect("valid link regex"));
+static INLINE_CODE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`[^`]*`").expect("valid code regex"));
+static LIST_MARKER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+(?:\[[ xX]\]\s+)?").expect("valid list regex")
+});
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParagraphReadability {
+    /// 1-based first and last line of the paragraph in the document
+    pub start_line: usize,
+    pub end_line: usize,
+    pub words: usize,
+    pub sentences: usize,
+    pub average_sentence_length: f64,
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+    pub passive_sentences: usize,
+    pub long_words: usize,
+    /// 0.0 (easy) – 1.0 (hard), for shading
+    pub difficulty: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SentenceBucket {
+    pub label: &'static str,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadabilityReport {
+    pub words: usize,
+    pub sentences: usize,
+    pub average_sentence_length: f64,
+    pub longest_sentence: usize,
+    pub flesch_reading_ease: f64,
+    pub flesch_kincaid_grade: f64,
+    pub passive_sentences: usize,
+    pub long_words: usize,
+    pub sentence_lengths: Vec<SentenceBucket>,
+    pub paragraphs: Vec<ParagraphReadability>,
+}
+
+/// Counts behind the scores, summed over sentences
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    words: usize,
+    sentences: usize,
+    syllables: usize,
+    passive: usize,
+    long_words: usize,
+}
+
+impl Tally {
+    fn add(&mut self, other: Tally) {
+        self.words += other.words;
+        self.sentences += other.sentences;
+        self.syllables += other.syllables;
+        self.passive += other.passive;
+        self.long_words += other.long_words;
+    }
+
+    fn words_per_sentence(&self) -> f64 {
+        self.words as f64 / self.sentences.max(1) as f64
+    }
+
+    fn syllables_per_word(&self) -> f64 {
+        self.syllables as f64 / self.words.max(1) as f64
+    }
+
+    fn reading_ease(&self) -> f64 {
+        round1(206.835 - 1.015 * self.words_per_sentence() - 84.6 * self.syllables_per_word())
+    }
+
+    fn grade(&self) -> f64 {
+        round1(0.39 * self.words_per_sentence() + 11.8 * self.syllables_per_word() - 15.59)
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Estimated syllables: vowel groups, less a silent final "e", at least one.
+fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    if word.ends_with('e') && !word.ends_with("le") && !word.ends_with("ee") && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+fn is_participle(word: &str) -> bool {
+    (word.len() > 4 && word.ends_with("ed")) || IRREGULAR_PARTICIPLES.contains(&word)
+}
+
+/// A form of "to be" followed by a past participle, optionally with an
+/// adverb ("is clearly stated") or "being" in between.
+fn is_passive(words: &[String]) -> bool {
+    words.iter().enumerate().any(|(i, word)| {
+        if !BE_FORMS.contains(&word.as_str()) {
+            return false;
+        }
+        let mut next = words.iter().skip(i + 1);
+        match next.next().map(String::as_str) {
+            Some(w) if w.ends_with("ly") || w == "being" || w == "not" => {
+                next.next().is_some_and(|w| is_participle(w))
+            }
+            Some(w) => is_participle(w),
+            None => false,
+        }
+    })
+}
+
+/// Sentences of `text`, split after ".", "!" or "?" unless the period
+/// belongs to an abbreviation or a number.
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        // Keep runs like "?!" or "..." together, and closing quotes
+        while let Some(&next) = chars.peek() {
+            if matches!(next, '.' | '!' | '?' | '"' | '\'' | ')' | '”' | '’') {
+                current.push(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        if chars.peek().is_some_and(|n| !n.is_whitespace()) {
+            continue;
+        }
+        let last_word = current
+            .trim_end_matches(['.', '!', '?', '"', '\'', ')', '”', '’'])
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        let abbreviation = ABBREVIATIONS.contains(&last_word.as_str()) || last_word.len() == 1;
+        // An ellipsis ends the sentence even after "No..."
+        if c == '.' && abbreviation && !current.ends_with("..") {
+            continue;
+        }
+        sentences.push(std::mem::take(&mut current));
+    }
+    sentences.push(current);
+    sentences.retain(|s| s.chars().any(char::is_alphabetic));
+    sentences
+}
+
+fn words(sentence: &str) -> Vec<String> {
+    sentence
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’' || c == '-'))
+        .map(|w| w.trim_matches(['\'', '’', '-']).to_lowercase())
+        .filter(|w| w.chars().any(char::is_alphabetic))
+        .collect()
+}
+
+/// Plain text of a Markdown paragraph.
+fn plain_text(lines: &[&str]) -> String {
+    let text = lines
+        .iter()
+        .map(|line| {
+            let line = line.trim_start().trim_start_matches('>').trim_start();
+            LIST_MARKER_RE.replace(line, "").into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let text = INLINE_CODE_RE.replace_all(&text, "");
+    let text = LINK_RE.replace_all(&text, "$1");
+    text.replace(['*', '_', '~', '='], "")
+}
+
+/// A paragraph: first and last line (1-based) and its lines
+type Paragraph<'a> = (usize, usize, Vec<&'a str>);
+
+fn flush<'a>(paragraphs: &mut Vec<Paragraph<'a>>, current: &mut Vec<&'a str>, first: usize) {
+    if !current.is_empty() {
+        paragraphs.push((first, first + current.len() - 1, std::mem::take(current)));
+    }
+}
+
+/// Prose paragraphs of `content`.
+fn paragraphs(content: &str) -> Vec<Paragraph<'_>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if lines.first().is_some_and(|l| l.trim_end() == "---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim_end() == "---") {
+            start = end + 2;
+        }
+    }
+
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut current_start = 0;
+    let mut fence: Option<&str> = None;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            flush(&mut paragraphs, &mut current, current_start);
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+        let not_prose = trimmed.starts_with('#')
+            || trimmed.starts_with('|')
+            || trimmed.starts_with('<')
+            || trimmed.starts_with("$$")
+            || line.starts_with("    ")
+            || line.starts_with('\t');
+        if trimmed.is_empty() || not_prose {
+            flush(&mut paragraphs, &mut current, current_start);
+            continue;
+        }
+        // Each list item is its own paragraph
+        if LIST_MARKER_RE.is_match(line) {
+            flush(&mut paragraphs, &mut current, current_start);
+        }
+        if current.is_empty() {
+            current_start = i + 1;
+        }
+        current.push(line);
+    }
+    flush(&mut paragraphs, &mut current, current_start);
+    paragraphs
+}
+
+fn analyze(content: &str) -> ReadabilityReport {
+    let mut total = Tally::default();
+    let mut longest_sentence = 0;
+    let mut buckets: Vec<SentenceBucket> = SENTENCE_BUCKETS
+        .iter()
+        .map(|(_, label)| SentenceBucket { label, count: 0 })
+        .collect();
+    let mut scored = Vec::new();
+
+    for (start_line, end_line, lines) in paragraphs(content) {
+        let text = plain_text(&lines);
+        let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+        let cjk = text.chars().filter(|c| is_cjk(*c)).count();
+        if letters == 0 || cjk * 2 > letters {
+            continue;
+        }
+
+        let mut tally = Tally::default();
+        for sentence in sentences(&text) {
+            let words = words(&sentence);
+            if words.is_empty() {
+                continue;
+            }
+            let counts: Vec<usize> = words.iter().map(|w| syllables(w)).collect();
+            tally.add(Tally {
+                words: words.len(),
+                sentences: 1,
+                syllables: counts.iter().sum(),
+                passive: usize::from(is_passive(&words)),
+                long_words: counts.iter().filter(|&&n| n >= LONG_WORD_SYLLABLES).count(),
+            });
+            longest_sentence = longest_sentence.max(words.len());
+            if let Some(i) = SENTENCE_BUCKETS
+                .iter()
+                .position(|(max, _)| words.len() <= *max)
+            {
+                buckets[i].count += 1;
+            }
+        }
+        if tally.sentences == 0 {
+            continue;
+        }
+        total.add(tally);
+        let grade = tally.grade();
+        scored.push(ParagraphReadability {
+            start_line,
+            end_line,
+            words: tally.words,
+            sentences: tally.sentences,
+            average_sentence_length: round1(tally.words_per_sentence()),
+            flesch_reading_ease: tally.reading_ease(),
+            flesch_kincaid_grade: grade,
+            passive_sentences: tally.passive,
+            long_words: tally.long_words,
+            difficulty: round1((grade / HARDEST_GRADE).clamp(0.0, 1.0) * 10.0) / 10.0,
+        });
+    }
+
+    let has_text = total.sentences > 0;
+    ReadabilityReport {
+        words: total.words,
+        sentences: total.sentences,
+        average_sentence_length: round1(total.words_per_sentence()),
+        longest_sentence,
+        flesch_reading_ease: if has_text { total.reading_ease() } else { 0.0 },
+        flesch_kincaid_grade: if has_text { total.grade() } else { 0.0 },
+        passive_sentences: total.passive,
+        long_words: total.long_words,
+        sentence_lengths: buckets,
+        paragraphs: scored,
+    }
+}
+
+/// Readability scores for a Markdown document, overall and per paragraph.
+#[tauri::command]
+pub async fn analyze_readability(text: String) -> Result<ReadabilityReport, String> {
+    tauri::async_runtime::spawn_blocking(move || analyze(&text))
+        .await
+        .map_err(|e| format!("Failed to analyze readability: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syllables() {
+        for (word, expected) in [
+            ("cat", 1),
+            ("make", 1),
+            ("table", 2),
+            ("readability", 5),
+            ("beautiful", 3),
+            ("the", 1),
+            ("rhythm", 1),
+        ] {
+            assert_eq!(syllables(word), expected, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_sentences() {
+        let split =
+            sentences("Dr. Smith arrived at 3.30 p.m. today. Was he late?! No... He was early.");
+        assert_eq!(
+            split.iter().map(|s| s.trim()).collect::<Vec<_>>(),
+            vec![
+                "Dr. Smith arrived at 3.30 p.m. today.",
+                "Was he late?!",
+                "No...",
+                "He was early."
+            ]
+        );
+        assert_eq!(sentences("No punctuation at the end").len(), 1);
+        assert!(sentences(" 42. ").is_empty());
+    }
+
+    #[test]
+    fn test_passive_voice() {
+        let passive = |s: &str| is_passive(&words(s));
+        assert!(passive("The report was written by the team."));
+        assert!(passive("Changes are being reviewed."));
+        assert!(passive("The bug was quickly fixed."));
+        assert!(!passive("The team wrote the report."));
+        assert!(!passive("She is happy."));
+    }
+
+    #[test]
+    fn test_paragraphs_skip_non_prose() {
+        let doc = "---\ntitle: x\n---\n# Title\n\nFirst line\nsecond line.\n\n```\ncode here.\n```\n| a | b |\n- Item one.\n- Item two.\n\n> Quoted text.\n";
+        let found: Vec<(usize, usize)> = paragraphs(doc).iter().map(|p| (p.0, p.1)).collect();
+        assert_eq!(found, vec![(6, 7), (13, 13), (14, 14), (16, 16)]);
+    }
+
+    #[test]
+    fn test_analyze_scores_paragraphs() {
+        let doc = "The cat sat. The dog ran. We had fun.\n\n\
+                   Notwithstanding considerable organizational complexity, the implementation \
+                   was comprehensively evaluated by independent international institutions \
+                   responsible for regulatory verification.\n";
+        let report = analyze(doc);
+        assert_eq!(report.paragraphs.len(), 2);
+        let (easy, hard) = (&report.paragraphs[0], &report.paragraphs[1]);
+        assert_eq!((easy.start_line, easy.sentences, easy.words), (1, 3, 9));
+        assert!(easy.flesch_reading_ease > 90.0, "{easy:?}");
+        assert_eq!(easy.difficulty, 0.0);
+        assert!(hard.flesch_kincaid_grade > 16.0, "{hard:?}");
+        assert_eq!(hard.difficulty, 1.0);
+        assert_eq!(hard.passive_sentences, 1);
+        assert!(hard.long_words >= 8, "{hard:?}");
+
+        assert_eq!(report.sentences, 4);
+        assert_eq!(report.longest_sentence, hard.words);
+        let counts: Vec<usize> = report.sentence_lengths.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![3, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_analyze_skips_cjk_and_empty() {
+        let report = analyze("今天天气很好，我们去公园散步。\n\n```\nlet x = 1;\n```\n");
+        assert!(report.paragraphs.is_empty());
+        assert_eq!(report.words, 0);
+        assert_eq!(report.flesch_reading_ease, 0.0);
+    }
+}
@@ -0,0 +1,661 @@
+//! Copy as rich text.
+//!
+//! Converts the rendered document HTML to RTF and puts RTF, HTML and plain
+//! text on the system clipboard in one go, so pasting into Word, Mail or
+//! Google Docs keeps headings, emphasis, links and lists.
+
+use tauri::AppHandle;
+
+const RTF_HEADER: &str = "{\\rtf1\\ansi\\ansicpg1252\\deff0\
+{\\fonttbl{\\f0\\fswiss Helvetica;}{\\f1\\fmodern Courier New;}}\
+{\\colortbl;\\red0\\green0\\blue238;}\\fs24\n";
+
+/// Indent step for lists and blockquotes, in twips.
+const INDENT_STEP: u32 = 720;
+
+/// Elements whose content is never rendered.
+const SKIPPED_TAGS: &[&str] = &["head", "style", "script", "template", "noscript", "math", "svg"];
+
+/// Elements without a closing tag.
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "input", "meta", "link", "wbr", "col", "source"];
+
+enum Token<'a> {
+    Text(&'a str),
+    Open { name: String, attrs: &'a str, self_closing: bool },
+    Close(String),
+}
+
+/// Split HTML into text runs and tags (comments and doctypes are dropped).
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let bytes = html.as_bytes();
+    let mut pos = 0;
+
+    while pos < html.len() {
+        let Some(rel) = html[pos..].find('<') else {
+            tokens.push(Token::Text(&html[pos..]));
+            break;
+        };
+        if rel > 0 {
+            tokens.push(Token::Text(&html[pos..pos + rel]));
+        }
+        let start = pos + rel;
+        let rest = &html[start..];
+
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(html.len(), |end| start + end + 3);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            pos = rest.find('>').map_or(html.len(), |end| start + end + 1);
+            continue;
+        }
+
+        // Find the end of the tag, ignoring '>' inside quoted attribute values
+        let mut end = None;
+        let mut quote: Option<u8> = None;
+        for (i, &b) in bytes[start + 1..].iter().enumerate() {
+            match quote {
+                Some(q) if b == q => quote = None,
+                Some(_) => {}
+                None if b == b'"' || b == b'\'' => quote = Some(b),
+                None if b == b'>' => {
+                    end = Some(start + 1 + i);
+                    break;
+                }
+                None => {}
+            }
+        }
+        let Some(end) = end else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+
+        let inner = &html[start + 1..end];
+        let (closing, inner) = match inner.strip_prefix('/') {
+            Some(stripped) => (true, stripped),
+            None => (false, inner),
+        };
+        let name_len = inner
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(inner.len());
+        if name_len == 0 {
+            // Not a tag ("a < b"), keep it as text
+            tokens.push(Token::Text(&html[start..start + 1]));
+            pos = start + 1;
+            continue;
+        }
+        let name = inner[..name_len].to_ascii_lowercase();
+        if closing {
+            tokens.push(Token::Close(name));
+        } else {
+            let attrs = inner[name_len..].trim_end();
+            let self_closing = attrs.ends_with('/');
+            tokens.push(Token::Open {
+                name,
+                attrs: attrs.trim_end_matches('/'),
+                self_closing,
+            });
+        }
+        pos = end + 1;
+    }
+
+    tokens
+}
+
+/// Read an attribute value from the raw attribute string of a tag.
+pub(crate) fn attr(attrs: &str, wanted: &str) -> Option<String> {
+    let mut rest = attrs.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let value = if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            match after_eq.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let body = &after_eq[1..];
+                    let close = body.find(q).unwrap_or(body.len());
+                    rest = body.get(close + 1..).unwrap_or("").trim_start();
+                    Some(&body[..close])
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    rest = after_eq[end..].trim_start();
+                    Some(&after_eq[..end])
+                }
+            }
+        } else {
+            None
+        };
+
+        if name.eq_ignore_ascii_case(wanted) {
+            return Some(decode_entities(value.unwrap_or("")));
+        }
+        if name.is_empty() {
+            break;
+        }
+    }
+    None
+}
+
+/// Decode the HTML character references that a renderer actually emits.
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest[1..].find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let entity = &rest[1..1 + semi];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "mdash" => Some('\u{2014}'),
+                "ndash" => Some('\u{2013}'),
+                "hellip" => Some('\u{2026}'),
+                "copy" => Some('\u{a9}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            ch.map(|c| (c, semi + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Append text to an RTF body, escaping control characters and encoding
+/// non-ASCII characters as `\uN?` (signed UTF-16 code units).
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '{' => out.push_str("\\{"),
+            '}' => out.push_str("\\}"),
+            '\t' => out.push_str("\\tab "),
+            '\n' => out.push_str("\\line "),
+            '\u{a0}' => out.push_str("\\~"),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            c if c.is_ascii() => {}
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{}?", *unit as i16));
+                }
+            }
+        }
+    }
+}
+
+struct RtfWriter {
+    out: String,
+    /// Open inline/heading groups: (tag name, closing RTF)
+    open: Vec<(String, &'static str)>,
+    /// List nesting: None for bullets, Some(next number) for ordered lists
+    lists: Vec<Option<u32>>,
+    quote_depth: u32,
+    pre_depth: u32,
+    /// Nothing has been written to the current paragraph yet
+    at_para_start: bool,
+    /// The last character written was whitespace (for collapsing)
+    after_space: bool,
+    /// The current paragraph holds only a list marker (loose list items
+    /// wrap their text in <p>, which must not start a new paragraph)
+    after_marker: bool,
+    /// A cell has been written in the current table row
+    row_has_cell: bool,
+}
+
+impl RtfWriter {
+    fn new() -> Self {
+        Self {
+            out: String::from(RTF_HEADER),
+            open: Vec::new(),
+            lists: Vec::new(),
+            quote_depth: 0,
+            pre_depth: 0,
+            at_para_start: true,
+            after_space: true,
+            after_marker: false,
+            row_has_cell: false,
+        }
+    }
+
+    fn indent(&self) -> u32 {
+        (self.lists.len() as u32 + self.quote_depth) * INDENT_STEP
+    }
+
+    /// Finish the current paragraph (if any) and start a fresh one.
+    fn start_block(&mut self) {
+        if self.after_marker {
+            return;
+        }
+        self.end_block();
+        let indent = self.indent();
+        self.out.push_str("\\pard");
+        if indent > 0 {
+            self.out.push_str(&format!("\\li{indent}"));
+        }
+        self.out.push_str("\\sa120 ");
+    }
+
+    fn end_block(&mut self) {
+        if !self.at_para_start {
+            self.out.push_str("\\par\n");
+            self.at_para_start = true;
+            self.after_space = true;
+            self.after_marker = false;
+        }
+    }
+
+    fn open_group(&mut self, name: &str, rtf_open: &str, rtf_close: &'static str) {
+        self.out.push_str(rtf_open);
+        self.open.push((name.to_string(), rtf_close));
+    }
+
+    fn close_group(&mut self, name: &str) {
+        // Close any unclosed children too, so groups always stay balanced
+        if let Some(idx) = self.open.iter().rposition(|(open, _)| open == name) {
+            for (_, rtf_close) in self.open.drain(idx..).rev() {
+                self.out.push_str(rtf_close);
+            }
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+        if self.pre_depth > 0 {
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            if !text.is_empty() {
+                push_escaped(&mut self.out, text);
+                self.at_para_start = false;
+                self.after_marker = false;
+            }
+            return;
+        }
+
+        let mut collapsed = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_whitespace() && c != '\u{a0}' {
+                if !self.after_space {
+                    collapsed.push(' ');
+                    self.after_space = true;
+                }
+            } else {
+                collapsed.push(c);
+                self.after_space = false;
+            }
+        }
+        if collapsed.is_empty() {
+            return;
+        }
+        push_escaped(&mut self.out, &collapsed);
+        self.at_para_start = false;
+        self.after_marker = false;
+    }
+
+    fn marker(&mut self, marker: &str) {
+        self.out.push_str(marker);
+        self.out.push_str("\\tab ");
+        self.at_para_start = false;
+        self.after_space = true;
+        self.after_marker = true;
+    }
+
+    fn open_tag(&mut self, name: &str, attrs: &str) {
+        match name {
+            "p" | "div" | "section" | "article" | "figure" | "figcaption" | "dd" | "dt" => {
+                self.start_block()
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let size = match name {
+                    "h1" => 48,
+                    "h2" => 40,
+                    "h3" => 32,
+                    "h4" => 28,
+                    _ => 24,
+                };
+                self.start_block();
+                self.open_group(name, &format!("{{\\b\\fs{size} "), "}");
+            }
+            "strong" | "b" => self.open_group(name, "{\\b ", "}"),
+            "em" | "i" => self.open_group(name, "{\\i ", "}"),
+            "u" | "ins" => self.open_group(name, "{\\ul ", "}"),
+            "s" | "del" | "strike" => self.open_group(name, "{\\strike ", "}"),
+            "sup" => self.open_group(name, "{\\super ", "}"),
+            "sub" => self.open_group(name, "{\\sub ", "}"),
+            "code" | "kbd" | "samp" => self.open_group(name, "{\\f1 ", "}"),
+            "mark" => self.open_group(name, "{\\highlight7 ", "}"),
+            "pre" => {
+                self.start_block();
+                self.pre_depth += 1;
+                self.open_group(name, "{\\f1\\fs20 ", "}");
+            }
+            "a" => match attr(attrs, "href") {
+                Some(href) if !href.is_empty() => {
+                    let mut open = String::from("{\\field{\\*\\fldinst{HYPERLINK \"");
+                    push_escaped(&mut open, &href.replace('"', "%22"));
+                    open.push_str("\"}}{\\fldrslt{\\ul\\cf1 ");
+                    self.open_group(name, &open, "}}}");
+                }
+                _ => self.open_group(name, "{", "}"),
+            },
+            "blockquote" => {
+                self.end_block();
+                self.quote_depth += 1;
+            }
+            "ul" => {
+                self.end_block();
+                self.lists.push(None);
+            }
+            "ol" => {
+                self.end_block();
+                let start = attr(attrs, "start").and_then(|s| s.parse().ok()).unwrap_or(1);
+                self.lists.push(Some(start));
+            }
+            "li" => {
+                self.start_block();
+                // Hanging indent so wrapped lines align with the text, not the marker
+                self.out.push_str(&format!("\\fi-360\\tx{} ", self.indent()));
+                match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        let marker = format!("{n}.");
+                        *n += 1;
+                        self.marker(&marker);
+                    }
+                    _ => self.marker("\\bullet"),
+                }
+            }
+            "input" if attr(attrs, "type").is_some_and(|t| t.eq_ignore_ascii_case("checkbox")) => {
+                let checked = attr(attrs, "checked").is_some();
+                self.text(if checked { "\u{2611} " } else { "\u{2610} " });
+            }
+            "br" => {
+                self.out.push_str("\\line ");
+                self.after_space = true;
+            }
+            "hr" => {
+                self.start_block();
+                self.out.push_str("\\brdrb\\brdrs\\brdrw10\\brsp20 ");
+                self.at_para_start = false;
+                self.end_block();
+            }
+            "img" => {
+                if let Some(alt) = attr(attrs, "alt").filter(|alt| !alt.is_empty()) {
+                    self.text(&format!("[{alt}]"));
+                }
+            }
+            "tr" => {
+                self.start_block();
+                self.row_has_cell = false;
+            }
+            "td" | "th" => {
+                if self.row_has_cell {
+                    self.out.push_str("\\tab ");
+                    self.after_space = true;
+                }
+                self.row_has_cell = true;
+                if name == "th" {
+                    self.open_group(name, "{\\b ", "}");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn close_tag(&mut self, name: &str) {
+        match name {
+            "p" | "div" | "section" | "article" | "figure" | "figcaption" | "dd" | "dt" | "li"
+            | "tr" => self.end_block(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.close_group(name);
+                self.end_block();
+            }
+            "pre" => {
+                self.close_group(name);
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                self.end_block();
+            }
+            "blockquote" => {
+                self.end_block();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            "ul" | "ol" => {
+                self.end_block();
+                self.lists.pop();
+            }
+            _ => self.close_group(name),
+        }
+    }
+
+    fn finish(mut self) -> String {
+        while let Some((_, rtf_close)) = self.open.pop() {
+            self.out.push_str(rtf_close);
+        }
+        self.end_block();
+        self.out.push('}');
+        self.out
+    }
+}
+
+/// Convert rendered HTML to an RTF document.
+pub fn html_to_rtf(html: &str) -> String {
+    let mut writer = RtfWriter::new();
+    // (tag name, nesting) of the element whose content is being skipped
+    let mut skipping: Option<(String, u32)> = None;
+
+    for token in tokenize(html) {
+        if let Some((skip_name, depth)) = skipping.as_mut() {
+            match &token {
+                Token::Open { name, self_closing: false, .. } if name == skip_name => *depth += 1,
+                Token::Close(name) if name == skip_name => {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        skipping = None;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match token {
+            Token::Text(text) => writer.text(text),
+            Token::Open { name, attrs, self_closing } => {
+                if SKIPPED_TAGS.contains(&name.as_str()) {
+                    if !self_closing {
+                        skipping = Some((name, 1));
+                    }
+                    continue;
+                }
+                writer.open_tag(&name, attrs);
+                if self_closing && !VOID_TAGS.contains(&name.as_str()) {
+                    writer.close_tag(&name);
+                }
+            }
+            Token::Close(name) => writer.close_tag(&name),
+        }
+    }
+
+    writer.finish()
+}
+
+/// Put RTF, HTML and plain text on the clipboard as a single item.
+#[tauri::command]
+pub fn copy_as_rich_text(app: AppHandle, html: String, plain_text: String) -> Result<(), String> {
+    let rtf = html_to_rtf(&html);
+    write_clipboard(&app, &rtf, &html, &plain_text)
+}
+
+#[cfg(target_os = "macos")]
+fn write_clipboard(_app: &AppHandle, rtf: &str, html: &str, plain_text: &str) -> Result<(), String> {
+    use objc2_app_kit::{
+        NSPasteboard, NSPasteboardTypeHTML, NSPasteboardTypeRTF, NSPasteboardTypeString,
+    };
+    use objc2_foundation::{NSData, NSString};
+
+    let pasteboard = NSPasteboard::generalPasteboard();
+    pasteboard.clearContents();
+
+    let rtf_data = NSData::with_bytes(rtf.as_bytes());
+    // SAFETY: the pasteboard type constants are immutable statics provided by AppKit
+    let written = unsafe {
+        pasteboard.setData_forType(Some(&rtf_data), NSPasteboardTypeRTF)
+            && pasteboard.setString_forType(&NSString::from_str(html), NSPasteboardTypeHTML)
+            && pasteboard.setString_forType(&NSString::from_str(plain_text), NSPasteboardTypeString)
+    };
+
+    if written {
+        Ok(())
+    } else {
+        Err("Failed to write to the pasteboard".to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn write_clipboard(_app: &AppHandle, rtf: &str, html: &str, plain_text: &str) -> Result<(), String> {
+    use clipboard_win::{formats, options, raw, Clipboard};
+
+    let _clipboard = Clipboard::new_attempts(10).map_err(|e| format!("Failed to open clipboard: {e}"))?;
+    raw::empty().map_err(|e| format!("Failed to clear clipboard: {e}"))?;
+
+    raw::set_string_with(plain_text, options::NoClear)
+        .map_err(|e| format!("Failed to write text: {e}"))?;
+    if let Some(html_format) = formats::Html::new() {
+        raw::set_html(html_format.code(), html).map_err(|e| format!("Failed to write HTML: {e}"))?;
+    }
+    let rtf_format = raw::register_format("Rich Text Format")
+        .ok_or_else(|| "Failed to register RTF clipboard format".to_string())?;
+    raw::set_without_clear(rtf_format.get(), rtf.as_bytes())
+        .map_err(|e| format!("Failed to write RTF: {e}"))?;
+
+    Ok(())
+}
+
+/// Linux desktops have no common RTF flavor; HTML is what rich editors read there.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn write_clipboard(app: &AppHandle, _rtf: &str, html: &str, plain_text: &str) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    app.clipboard()
+        .write_html(html, Some(plain_text))
+        .map_err(|e| format!("Failed to write to clipboard: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(rtf: &str) -> &str {
+        rtf.strip_prefix(RTF_HEADER).and_then(|b| b.strip_suffix('}')).unwrap()
+    }
+
+    #[test]
+    fn test_paragraphs_and_inline_formatting() {
+        let rtf = html_to_rtf("<p>Hello <strong>bold</strong> and <em>it</em></p><p>Next</p>");
+        assert_eq!(
+            body(&rtf),
+            "\\pard\\sa120 Hello {\\b bold} and {\\i it}\\par\n\\pard\\sa120 Next\\par\n"
+        );
+    }
+
+    #[test]
+    fn test_headings_are_bold_and_sized() {
+        let rtf = html_to_rtf("<h1 id=\"t\">Title</h1>");
+        assert!(body(&rtf).contains("{\\b\\fs48 Title}\\par"));
+    }
+
+    #[test]
+    fn test_escapes_and_unicode() {
+        let rtf = html_to_rtf("<p>{a} \\ &amp; &lt;b&gt; caf\u{e9} 中 \u{1f600}</p>");
+        let body = body(&rtf);
+        assert!(body.contains("\\{a\\} \\\\ & <b> caf\\u233? \\u20013?"));
+        // Characters outside the BMP become a surrogate pair
+        assert!(body.contains("\\u-10179?\\u-8704?"));
+    }
+
+    #[test]
+    fn test_links_become_hyperlink_fields() {
+        let rtf = html_to_rtf("<p><a href=\"https://example.com/?a=1&amp;b=2\">site</a></p>");
+        assert!(body(&rtf).contains(
+            "{\\field{\\*\\fldinst{HYPERLINK \"https://example.com/?a=1&b=2\"}}{\\fldrslt{\\ul\\cf1 site}}}"
+        ));
+    }
+
+    #[test]
+    fn test_lists_are_numbered_and_indented() {
+        let rtf = html_to_rtf("<ol start=\"3\"><li>a</li><li>b<ul><li>c</li></ul></li></ol>");
+        let body = body(&rtf);
+        assert!(body.contains("\\li720\\sa120 \\fi-360\\tx720 3.\\tab a\\par"));
+        assert!(body.contains("4.\\tab b\\par"));
+        assert!(body.contains("\\li1440\\sa120 \\fi-360\\tx1440 \\bullet\\tab c\\par"));
+    }
+
+    #[test]
+    fn test_loose_list_items_keep_marker_with_text() {
+        let rtf = html_to_rtf("<ul>\n<li>\n<p>first</p>\n</li>\n</ul>");
+        assert!(body(&rtf).contains("\\bullet\\tab first\\par"));
+    }
+
+    #[test]
+    fn test_code_blocks_keep_line_breaks() {
+        let rtf = html_to_rtf("<pre><code>fn main() {\n    x\n}\n</code></pre>");
+        assert!(body(&rtf).contains("{\\f1\\fs20 {\\f1 fn main() \\{\\line     x\\line \\}}}\\par"));
+    }
+
+    #[test]
+    fn test_skips_style_and_math_annotations() {
+        let rtf = html_to_rtf(
+            "<style>p { color: red }</style><p><span><math><mi>x</mi></math><span>x</span></span></p>",
+        );
+        assert_eq!(body(&rtf), "\\pard\\sa120 x\\par\n");
+    }
+
+    #[test]
+    fn test_unbalanced_tags_keep_groups_balanced() {
+        let rtf = html_to_rtf("<p><b>open <i>nested</p><p>after</b></p>");
+        let opens = rtf.matches('{').count() - rtf.matches("\\{").count();
+        let closes = rtf.matches('}').count() - rtf.matches("\\}").count();
+        assert_eq!(opens, closes);
+    }
+
+    #[test]
+    fn test_table_cells_are_tab_separated() {
+        let rtf = html_to_rtf("<table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>2</td></tr></table>");
+        let body = body(&rtf);
+        assert!(body.contains("{\\b A}\\tab {\\b B}\\par"));
+        assert!(body.contains("1\\tab 2\\par"));
+    }
+
+    #[test]
+    fn test_attr_parsing() {
+        assert_eq!(attr(" href='a b' class=x", "href").as_deref(), Some("a b"));
+        assert_eq!(attr(" class=x checked", "checked").as_deref(), Some(""));
+        assert_eq!(attr(" class=x", "href"), None);
+    }
+}
@@ -0,0 +1,390 @@
+//! Save Hooks
+//!
+//! Genies that run automatically after a note is saved and fill in
+//! frontmatter fields the note is missing — a one-line `summary`,
+//! `keywords`, and so on. Configured in Settings → Advanced (`saveHooks`);
+//! off by default.
+//!
+//! The frontend calls `run_save_hooks` after each save. Hooks whose field
+//! is already set are skipped, so a note is processed once. The genies run
+//! in the background, one at a time, through the active AI provider (with
+//! its budgets and redaction), and their output is written into the
+//! note's frontmatter. Two limits keep this from burning through requests:
+//! a per-note cooldown and a cap on runs per hour.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager, WebviewWindow};
+
+use crate::ai_provider::{self, AiPromptError, ProviderConfig};
+use crate::publish::{quote, set_frontmatter_values, split_frontmatter, top_level_key};
+use crate::settings::{self, SaveHook};
+
+/// Notes shorter than this aren't worth summarizing yet
+const MIN_BODY_CHARS: usize = 200;
+
+/// Longer notes are cut before being sent
+const MAX_BODY_CHARS: usize = 24_000;
+
+/// Items kept from a list result
+const MAX_LIST_ITEMS: usize = 10;
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// Last time hooks were queued, per note
+static LAST_RUN: Mutex<BTreeMap<String, Instant>> = Mutex::new(BTreeMap::new());
+
+/// Genie runs in the past hour
+static RECENT_RUNS: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+/// Hooks run one at a time
+static RUN_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Payload of `save-hooks:applied`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveHooksApplied {
+    pub path: String,
+    /// Frontmatter fields that were filled in
+    pub fields: Vec<String>,
+}
+
+/// Whether the note's frontmatter has `field`, whatever its value.
+fn has_field(content: &str, field: &str) -> bool {
+    let (lines, _) = split_frontmatter(content);
+    lines.is_some_and(|lines| lines.iter().any(|l| top_level_key(l) == Some(field)))
+}
+
+/// Hooks whose field `content` doesn't have yet.
+fn missing_hooks(hooks: &[SaveHook], content: &str) -> Vec<SaveHook> {
+    hooks
+        .iter()
+        .filter(|h| !h.genie.trim().is_empty() && !h.field.trim().is_empty())
+        .filter(|h| !has_field(content, h.field.trim()))
+        .cloned()
+        .collect()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "md" | "markdown"))
+}
+
+/// Whether `path` is out of its cooldown; if so, starts a new one.
+fn take_cooldown(path: &str, cooldown: Duration, now: Instant) -> bool {
+    let mut last = LAST_RUN.lock().unwrap_or_else(|e| e.into_inner());
+    last.retain(|_, at| now.duration_since(*at) < cooldown);
+    if last.contains_key(path) {
+        return false;
+    }
+    last.insert(path.to_string(), now);
+    true
+}
+
+/// Reserve up to `wanted` runs within the hourly cap; returns how many.
+fn reserve_runs(wanted: usize, max_per_hour: usize, now: Instant) -> usize {
+    let mut runs = RECENT_RUNS.lock().unwrap_or_else(|e| e.into_inner());
+    while runs
+        .front()
+        .is_some_and(|at| now.duration_since(*at) >= HOUR)
+    {
+        runs.pop_front();
+    }
+    let granted = wanted.min(max_per_hour.saturating_sub(runs.len()));
+    runs.extend(std::iter::repeat_n(now, granted));
+    granted
+}
+
+fn fill_template(template: &str, body: &str) -> String {
+    let body: String = body.chars().take(MAX_BODY_CHARS).collect();
+    let mut prompt = template.to_string();
+    for var in ["content", "context", "notes"] {
+        let value = if var == "content" { body.as_str() } else { "" };
+        for spelling in [format!("{{{{{}}}}}", var), format!("{{{{ {} }}}}", var)] {
+            prompt = prompt.replace(&spelling, value);
+        }
+    }
+    prompt
+}
+
+fn strip_quotes(text: &str) -> &str {
+    let text = text.trim();
+    for (open, close) in [('"', '"'), ('\'', '\''), ('“', '”'), ('`', '`')] {
+        if let Some(inner) = text
+            .strip_prefix(open)
+            .and_then(|rest| rest.strip_suffix(close))
+        {
+            return inner.trim();
+        }
+    }
+    text
+}
+
+/// A genie's output as a frontmatter value: a quoted one-line string, or a
+/// flow list. `None` when there's nothing usable.
+fn frontmatter_value(output: &str, list: bool) -> Option<String> {
+    let output = output.trim().trim_matches('`').trim();
+    if list {
+        let mut items: Vec<String> = Vec::new();
+        for item in output.split([',', '\n', '，', '、']) {
+            let item = item
+                .trim()
+                .trim_start_matches(|c: char| {
+                    c == '-' || c == '*' || c == '#' || c == '.' || c.is_ascii_digit()
+                })
+                .trim();
+            let item = strip_quotes(item).to_string();
+            if !item.is_empty() && !items.contains(&item) {
+                items.push(item);
+            }
+        }
+        items.truncate(MAX_LIST_ITEMS);
+        let quoted: Vec<String> = items.iter().map(|i| quote(i)).collect();
+        (!quoted.is_empty()).then(|| format!("[{}]", quoted.join(", ")))
+    } else {
+        let line = output.split_whitespace().collect::<Vec<_>>().join(" ");
+        let line = strip_quotes(&line);
+        (!line.is_empty()).then(|| quote(line))
+    }
+}
+
+/// Write `values` into the note's frontmatter, skipping fields that were
+/// set in the meantime. Returns the fields written.
+fn write_fields(path: &Path, values: Vec<(String, String)>) -> Result<Vec<String>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let fields: Vec<(String, Option<String>)> = values
+        .into_iter()
+        .filter(|(field, _)| !has_field(&content, field))
+        .map(|(field, value)| (field, Some(value)))
+        .collect();
+    if fields.is_empty() {
+        return Ok(Vec::new());
+    }
+    let updated = set_frontmatter_values(&content, &fields);
+    crate::app_paths::write_document_file(path, updated.as_bytes())?;
+    Ok(fields.into_iter().map(|(field, _)| field).collect())
+}
+
+async fn run_hooks(
+    window: WebviewWindow,
+    path: String,
+    hooks: Vec<SaveHook>,
+    provider: ProviderConfig,
+) {
+    let _running = RUN_LOCK.lock().await;
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let (_, body) = split_frontmatter(&content);
+    let genies_dir = crate::genies::global_genies_dir(window.app_handle());
+
+    let mut values = Vec::new();
+    for hook in hooks {
+        let genie_path = match &genies_dir {
+            Ok(dir) => dir.join(hook.genie.trim()),
+            Err(_) => break,
+        };
+        let genie = match crate::genies::read_genie(
+            window.app_handle().clone(),
+            genie_path.to_string_lossy().into_owned(),
+        ) {
+            Ok(genie) => genie,
+            Err(e) => {
+                tracing::warn!("[SaveHooks] Skipping {}: {}", hook.genie, e);
+                continue;
+            }
+        };
+        let mut config = provider.clone();
+        if genie.metadata.model.is_some() {
+            config.model = genie.metadata.model.clone();
+        }
+        let request_id = format!("save-hook-{}", uuid::Uuid::new_v4());
+        let prompt = fill_template(&genie.template, body);
+        match ai_provider::run_prompt(&window, &request_id, config, prompt).await {
+            Ok(Some(output)) => match frontmatter_value(&output, hook.list) {
+                Some(value) => values.push((hook.field.trim().to_string(), value)),
+                None => tracing::warn!("[SaveHooks] {} returned nothing", hook.genie),
+            },
+            Ok(None) => tracing::warn!("[SaveHooks] {} failed", hook.genie),
+            Err(AiPromptError::Budget(limit)) => {
+                tracing::info!("[SaveHooks] Stopped: {}", limit.message);
+                break;
+            }
+            Err(AiPromptError::Failed { message }) => {
+                tracing::warn!("[SaveHooks] {} failed: {}", hook.genie, message);
+            }
+        }
+    }
+    if values.is_empty() {
+        return;
+    }
+
+    match write_fields(Path::new(&path), values) {
+        Ok(fields) if !fields.is_empty() => {
+            tracing::info!("[SaveHooks] Filled {} in {}", fields.join(", "), path);
+            let _ = window.emit_to(
+                window.label(),
+                "save-hooks:applied",
+                SaveHooksApplied { path, fields },
+            );
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("[SaveHooks] Failed to update {}: {}", path, e),
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Queue the configured save hooks for the note at `path`, using
+/// `provider`. Returns how many genies were queued; they run in the
+/// background and `save-hooks:applied` reports the fields written.
+#[tauri::command]
+pub fn run_save_hooks(
+    window: WebviewWindow,
+    path: String,
+    provider: ProviderConfig,
+) -> Result<usize, String> {
+    let config = settings::current().advanced.save_hooks;
+    let file = Path::new(&path);
+    if !config.enabled
+        || config.hooks.is_empty()
+        || !is_markdown(file)
+        || crate::encryption::is_encrypted_path(file)
+    {
+        return Ok(0);
+    }
+
+    let content =
+        std::fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut hooks = missing_hooks(&config.hooks, &content);
+    let (_, body) = split_frontmatter(&content);
+    if hooks.is_empty() || body.trim().chars().count() < MIN_BODY_CHARS {
+        return Ok(0);
+    }
+
+    let now = Instant::now();
+    if !take_cooldown(&path, Duration::from_secs(config.cooldown_secs), now) {
+        return Ok(0);
+    }
+    let granted = reserve_runs(hooks.len(), config.max_runs_per_hour as usize, now);
+    if granted < hooks.len() {
+        tracing::info!(
+            "[SaveHooks] Hourly limit reached, running {} of {}",
+            granted,
+            hooks.len()
+        );
+    }
+    hooks.truncate(granted);
+    if hooks.is_empty() {
+        return Ok(0);
+    }
+
+    let queued = hooks.len();
+    tauri::async_runtime::spawn(run_hooks(window, path, hooks, provider));
+    Ok(queued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(genie: &str, field: &str, list: bool) -> SaveHook {
+        SaveHook {
+            genie: genie.into(),
+            field: field.into(),
+            list,
+        }
+    }
+
+    #[test]
+    fn test_missing_hooks() {
+        let hooks = vec![
+            hook("metadata/summary.md", "summary", false),
+            hook("metadata/keywords.md", "keywords", true),
+            hook("", "title", false),
+        ];
+        let content = "---\ntitle: Plants\nkeywords:\n  - garden\n---\n\nBody";
+        let missing = missing_hooks(&hooks, content);
+        assert_eq!(missing, vec![hooks[0].clone()]);
+        assert_eq!(missing_hooks(&hooks, "No frontmatter").len(), 2);
+    }
+
+    #[test]
+    fn test_frontmatter_value_text() {
+        assert_eq!(
+            frontmatter_value("  \"Notes on watering\n tomatoes.\"  ", false),
+            Some("\"Notes on watering tomatoes.\"".to_string())
+        );
+        assert_eq!(frontmatter_value(" \n ", false), None);
+    }
+
+    #[test]
+    fn test_frontmatter_value_list() {
+        assert_eq!(
+            frontmatter_value("gardening, \"tomatoes\", watering, gardening", true),
+            Some("[\"gardening\", \"tomatoes\", \"watering\"]".to_string())
+        );
+        assert_eq!(
+            frontmatter_value("1. soil\n2. compost\n- mulch", true),
+            Some("[\"soil\", \"compost\", \"mulch\"]".to_string())
+        );
+        assert_eq!(
+            frontmatter_value("园艺，番茄、浇水", true),
+            Some("[\"园艺\", \"番茄\", \"浇水\"]".to_string())
+        );
+        assert_eq!(frontmatter_value(",,", true), None);
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let prompt = fill_template("Summarize.\n\n{{ context }}{{content}}{{notes}}", "Body");
+        assert_eq!(prompt, "Summarize.\n\nBody");
+    }
+
+    #[test]
+    fn test_write_fields_keeps_existing_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.md");
+        std::fs::write(&path, "---\ntitle: Plants\nsummary: Mine\n---\n\nBody\n").unwrap();
+        let written = write_fields(
+            &path,
+            vec![
+                ("summary".into(), "\"Generated\"".into()),
+                ("keywords".into(), "[\"garden\"]".into()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(written, vec!["keywords".to_string()]);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\ntitle: Plants\nsummary: Mine\nkeywords: [\"garden\"]\n---\n\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_cooldown_and_hourly_cap() {
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(600);
+        assert!(take_cooldown("/tmp/cooldown-test.md", cooldown, now));
+        assert!(!take_cooldown("/tmp/cooldown-test.md", cooldown, now));
+        assert!(take_cooldown(
+            "/tmp/cooldown-test.md",
+            cooldown,
+            now + Duration::from_secs(601)
+        ));
+
+        // Far enough ahead that runs recorded by other tests have expired
+        let later = now + HOUR * 2;
+        assert_eq!(reserve_runs(3, 4, later), 3);
+        assert_eq!(reserve_runs(3, 4, later), 1);
+        assert_eq!(reserve_runs(1, 4, later), 0);
+        assert_eq!(reserve_runs(2, 4, later + HOUR), 2);
+    }
+}
@@ -0,0 +1,767 @@
+//! Settings store - versioned `settings.json` in the app data directory.
+//!
+//! The backend owns the settings so every window sees the same values.
+//! Windows read them with `subscribe_settings`, change them with
+//! `set_setting` (a section like `"appearance"` or a key path like
+//! `"appearance.theme"`), and receive `settings:changed` whenever any window
+//! changes something.
+//!
+//! Each section is typed with defaults, so a file from an older version
+//! loads with new settings filled in. Keys this build doesn't know yet are
+//! kept (`extra`), and `migrate` upgrades older file versions on load.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_paths;
+
+/// Settings file name in the app data directory
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Current settings file version
+pub const SETTINGS_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GeneralSettings {
+    pub auto_save_enabled: bool,
+    pub auto_save_interval: u32,
+    pub history_enabled: bool,
+    pub history_max_snapshots: u32,
+    pub history_max_age_days: u32,
+    pub tab_size: u32,
+    pub line_endings_on_save: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        Self {
+            auto_save_enabled: true,
+            auto_save_interval: 30,
+            history_enabled: true,
+            history_max_snapshots: 50,
+            history_max_age_days: 7,
+            tab_size: 2,
+            line_endings_on_save: "preserve".into(),
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppearanceSettings {
+    pub theme: String,
+    pub latin_font: String,
+    pub cjk_font: String,
+    pub mono_font: String,
+    pub font_size: f64,
+    pub line_height: f64,
+    pub block_spacing: f64,
+    pub cjk_letter_spacing: String,
+    pub editor_width: f64,
+    pub show_filename_in_titlebar: bool,
+    pub auto_hide_status_bar: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            theme: "paper".into(),
+            latin_font: "system".into(),
+            cjk_font: "system".into(),
+            mono_font: "system".into(),
+            font_size: 18.0,
+            line_height: 1.8,
+            block_spacing: 1.0,
+            cjk_letter_spacing: "0".into(),
+            editor_width: 50.0,
+            show_filename_in_titlebar: false,
+            auto_hide_status_bar: false,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CjkFormattingSettings {
+    pub ellipsis_normalization: bool,
+    pub newline_collapsing: bool,
+    pub fullwidth_alphanumeric: bool,
+    pub fullwidth_punctuation: bool,
+    pub fullwidth_parentheses: bool,
+    pub fullwidth_brackets: bool,
+    pub cjk_english_spacing: bool,
+    pub cjk_parenthesis_spacing: bool,
+    pub currency_spacing: bool,
+    pub slash_spacing: bool,
+    pub space_collapsing: bool,
+    pub dash_conversion: bool,
+    pub emdash_spacing: bool,
+    pub smart_quote_conversion: bool,
+    pub quote_style: String,
+    pub contextual_quotes: bool,
+    pub quote_spacing: bool,
+    pub single_quote_spacing: bool,
+    pub cjk_corner_quotes: bool,
+    pub cjk_nested_quotes: bool,
+    pub consecutive_punctuation_limit: u32,
+    pub trailing_space_removal: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for CjkFormattingSettings {
+    fn default() -> Self {
+        Self {
+            ellipsis_normalization: true,
+            newline_collapsing: true,
+            fullwidth_alphanumeric: true,
+            fullwidth_punctuation: true,
+            fullwidth_parentheses: true,
+            fullwidth_brackets: false,
+            cjk_english_spacing: true,
+            cjk_parenthesis_spacing: true,
+            currency_spacing: true,
+            slash_spacing: true,
+            space_collapsing: true,
+            dash_conversion: true,
+            emdash_spacing: true,
+            smart_quote_conversion: true,
+            quote_style: "curly".into(),
+            contextual_quotes: true,
+            quote_spacing: true,
+            single_quote_spacing: true,
+            cjk_corner_quotes: false,
+            cjk_nested_quotes: false,
+            consecutive_punctuation_limit: 0,
+            trailing_space_removal: true,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MarkdownSettings {
+    pub preserve_line_breaks: bool,
+    pub show_br_tags: bool,
+    pub enable_regex_search: bool,
+    pub paste_markdown_in_wysiwyg: String,
+    pub paste_mode: String,
+    pub media_border_style: String,
+    pub media_alignment: String,
+    pub heading_alignment: String,
+    pub block_font_size: String,
+    pub html_rendering_mode: String,
+    pub hard_break_style_on_save: String,
+    pub auto_pair_enabled: bool,
+    #[serde(rename = "autoPairCJKStyle")]
+    pub auto_pair_cjk_style: String,
+    pub auto_pair_curly_quotes: bool,
+    pub auto_pair_right_double_quote: bool,
+    pub copy_format: String,
+    pub copy_on_select: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for MarkdownSettings {
+    fn default() -> Self {
+        Self {
+            preserve_line_breaks: false,
+            show_br_tags: false,
+            enable_regex_search: true,
+            paste_markdown_in_wysiwyg: "auto".into(),
+            paste_mode: "smart".into(),
+            media_border_style: "none".into(),
+            media_alignment: "center".into(),
+            heading_alignment: "left".into(),
+            block_font_size: "1".into(),
+            html_rendering_mode: "hidden".into(),
+            hard_break_style_on_save: "preserve".into(),
+            auto_pair_enabled: true,
+            auto_pair_cjk_style: "auto".into(),
+            auto_pair_curly_quotes: true,
+            auto_pair_right_double_quote: false,
+            copy_format: "default".into(),
+            copy_on_select: false,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImageSettings {
+    pub auto_resize_max: u32,
+    pub auto_resize_custom: u32,
+    pub inline_threshold: f64,
+    pub copy_to_assets: bool,
+    pub cleanup_orphans_on_close: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for ImageSettings {
+    fn default() -> Self {
+        Self {
+            auto_resize_max: 0,
+            auto_resize_custom: 1600,
+            inline_threshold: 1.0,
+            copy_to_assets: true,
+            cleanup_orphans_on_close: false,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TerminalSettings {
+    pub font_size: f64,
+    pub line_height: f64,
+    pub copy_on_select: bool,
+    pub env: BTreeMap<String, String>,
+    pub startup_command: String,
+    /// Save recent output with hot exit so it is replayed after an update
+    /// restart. Off by default: output can contain secrets.
+    pub restore_scrollback: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for TerminalSettings {
+    fn default() -> Self {
+        Self {
+            font_size: 13.0,
+            line_height: 1.2,
+            copy_on_select: false,
+            env: BTreeMap::new(),
+            startup_command: String::new(),
+            restore_scrollback: false,
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct McpServerSettings {
+    pub port: u16,
+    pub auto_start: bool,
+    pub auto_approve_edits: bool,
+    pub tool_mode: String,
+}
+
+impl Default for McpServerSettings {
+    fn default() -> Self {
+        Self {
+            port: 9223,
+            auto_start: true,
+            auto_approve_edits: false,
+            tool_mode: "writer".into(),
+        }
+    }
+}
+
+/// TLS options for self-hosted endpoints (see `http_client`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TlsSettings {
+    /// PEM file with extra root certificates, trusted alongside the system ones
+    pub ca_bundle_path: Option<String>,
+    /// Hosts (`host` or `host:port`) whose certificates are not verified
+    pub insecure_hosts: Vec<String>,
+}
+
+/// Redaction of prompts sent to remote AI providers (see `ai_redaction`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromptRedactionSettings {
+    pub enabled: bool,
+    /// Built-in detectors: "email", "apiKey", "phone", "ipAddress"
+    pub entities: Vec<String>,
+    /// Names and other literal terms, matched as whole words
+    pub names: Vec<String>,
+    /// Extra regular expressions
+    pub patterns: Vec<String>,
+}
+
+impl Default for PromptRedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            entities: vec!["email".into(), "apiKey".into()],
+            names: Vec::new(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// A genie whose output fills a frontmatter field on save
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SaveHook {
+    /// Genie file, relative to the genies folder
+    pub genie: String,
+    /// Frontmatter field to fill when it's missing
+    pub field: String,
+    /// Write the output as a list (one item per line or comma)
+    pub list: bool,
+}
+
+/// Genies run in the background after a note is saved (see `save_hooks`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SaveHookSettings {
+    pub enabled: bool,
+    pub hooks: Vec<SaveHook>,
+    /// Minimum seconds between runs for the same note
+    pub cooldown_secs: u64,
+    /// Genie runs allowed per hour across all notes
+    pub max_runs_per_hour: u32,
+}
+
+impl Default for SaveHookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hooks: vec![
+                SaveHook {
+                    genie: "metadata/summary.md".into(),
+                    field: "summary".into(),
+                    list: false,
+                },
+                SaveHook {
+                    genie: "metadata/keywords.md".into(),
+                    field: "keywords".into(),
+                    list: true,
+                },
+            ],
+            cooldown_secs: 600,
+            max_runs_per_hour: 20,
+        }
+    }
+}
+
+/// Embedding server for semantic search (see `embeddings`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SemanticSearchSettings {
+    /// Ollama endpoint that embeds notes and queries
+    pub endpoint: String,
+}
+
+impl Default for SemanticSearchSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:11434".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AdvancedSettings {
+    pub mcp_server: McpServerSettings,
+    pub custom_link_protocols: Vec<String>,
+    pub keep_both_editors_alive: bool,
+    pub tls: TlsSettings,
+    pub prompt_redaction: PromptRedactionSettings,
+    pub save_hooks: SaveHookSettings,
+    pub semantic_search: SemanticSearchSettings,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            mcp_server: McpServerSettings::default(),
+            custom_link_protocols: ["obsidian", "vscode", "dict", "x-dictionary"]
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            keep_both_editors_alive: false,
+            tls: TlsSettings::default(),
+            prompt_redaction: PromptRedactionSettings::default(),
+            save_hooks: SaveHookSettings::default(),
+            semantic_search: SemanticSearchSettings::default(),
+            extra: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UpdateCheckSettings {
+    pub auto_check_enabled: bool,
+    pub check_frequency: String,
+    pub auto_download: bool,
+    pub last_check_timestamp: Option<i64>,
+    pub skip_version: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for UpdateCheckSettings {
+    fn default() -> Self {
+        Self {
+            auto_check_enabled: true,
+            check_frequency: "startup".into(),
+            auto_download: false,
+            last_check_timestamp: None,
+            skip_version: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+/// All settings, as stored in `settings.json`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub version: u32,
+    pub general: GeneralSettings,
+    pub appearance: AppearanceSettings,
+    pub cjk_formatting: CjkFormattingSettings,
+    pub markdown: MarkdownSettings,
+    pub image: ImageSettings,
+    pub terminal: TerminalSettings,
+    pub advanced: AdvancedSettings,
+    pub update: UpdateCheckSettings,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            general: GeneralSettings::default(),
+            appearance: AppearanceSettings::default(),
+            cjk_formatting: CjkFormattingSettings::default(),
+            markdown: MarkdownSettings::default(),
+            image: ImageSettings::default(),
+            terminal: TerminalSettings::default(),
+            advanced: AdvancedSettings::default(),
+            update: UpdateCheckSettings::default(),
+            extra: Map::new(),
+        }
+    }
+}
+
+/// Payload of the "settings:changed" event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsChanged {
+    /// Changed section or key path; None when everything changed (reset/import)
+    path: Option<String>,
+    settings: Settings,
+    /// Label of the window that made the change
+    source: Option<String>,
+}
+
+/// Returned to a window when it subscribes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSnapshot {
+    pub settings: Settings,
+    /// False until settings were first saved (the window may import its own)
+    pub persisted: bool,
+}
+
+/// Loaded settings, the file they persist to and subscribed windows
+struct SettingsState {
+    file: PathBuf,
+    settings: Settings,
+    persisted: bool,
+    subscribers: HashSet<String>,
+}
+
+static SETTINGS_STATE: OnceLock<Mutex<SettingsState>> = OnceLock::new();
+
+/// Upgrade raw settings JSON from an older file version.
+fn migrate(mut raw: Value) -> Value {
+    let version = raw.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version < 1 {
+        // v1: appearance.paragraphSpacing was renamed to blockSpacing
+        if let Some(appearance) = raw.get_mut("appearance").and_then(|a| a.as_object_mut()) {
+            if let Some(spacing) = appearance.remove("paragraphSpacing") {
+                appearance.entry("blockSpacing").or_insert(spacing);
+            }
+        }
+    }
+    if let Some(object) = raw.as_object_mut() {
+        object.insert("version".into(), Value::from(SETTINGS_VERSION));
+    }
+    raw
+}
+
+/// Parse settings JSON of any version, filling in defaults.
+fn parse_settings(raw: Value) -> Result<Settings, String> {
+    serde_json::from_value(migrate(raw)).map_err(|e| format!("Invalid settings: {}", e))
+}
+
+/// Load settings; a missing or unreadable file gives defaults.
+fn load_settings(file: &Path) -> (Settings, bool) {
+    let raw = fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+    match raw {
+        Some(raw) => match parse_settings(raw) {
+            Ok(settings) => (settings, true),
+            Err(e) => {
+                tracing::warn!("Ignoring settings file {}: {}", file.display(), e);
+                (Settings::default(), false)
+            }
+        },
+        None => (Settings::default(), false),
+    }
+}
+
+fn save_settings(file: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Settings with `value` stored at `path` ("section" or "section.key.sub").
+/// Fails if the path is unknown to the section shape or the value has the
+/// wrong type for a typed setting.
+fn apply_setting(settings: &Settings, path: &str, value: Value) -> Result<Settings, String> {
+    let keys: Vec<&str> = path.split('.').collect();
+    if path.is_empty() || keys.iter().any(|k| k.is_empty()) {
+        return Err(format!("Invalid setting path: {:?}", path));
+    }
+    if keys == ["version"] {
+        return Err("The settings version can't be set".to_string());
+    }
+
+    let mut raw = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    let (last, parents) = keys.split_last().expect("path has at least one key");
+    let mut target = &mut raw;
+    for key in parents {
+        target = target
+            .as_object_mut()
+            .and_then(|object| object.get_mut(*key))
+            .filter(|child| child.is_object())
+            .ok_or_else(|| format!("Unknown settings section: {}", path))?;
+    }
+    target
+        .as_object_mut()
+        .ok_or_else(|| format!("Unknown settings section: {}", path))?
+        .insert(last.to_string(), value);
+
+    serde_json::from_value(raw).map_err(|e| format!("Invalid value for {}: {}", path, e))
+}
+
+/// Load settings from app data. Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(SETTINGS_FILE);
+    let (settings, persisted) = load_settings(&file);
+    let _ = SETTINGS_STATE.set(Mutex::new(SettingsState {
+        file,
+        settings,
+        persisted,
+        subscribers: HashSet::new(),
+    }));
+    Ok(())
+}
+
+/// Current settings (defaults before `init`).
+pub fn current() -> Settings {
+    SETTINGS_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .map(|guard| guard.settings.clone())
+        .unwrap_or_default()
+}
+
+/// Forget a closed window.
+pub fn unsubscribe_window(label: &str) {
+    if let Some(mut guard) = SETTINGS_STATE.get().and_then(|state| state.lock().ok()) {
+        guard.subscribers.remove(label);
+    }
+}
+
+/// Save `settings` and tell every subscribed window.
+fn commit(
+    app: &AppHandle,
+    state: &mut SettingsState,
+    settings: Settings,
+    path: Option<String>,
+    source: Option<String>,
+) -> Result<Settings, String> {
+    save_settings(&state.file, &settings)?;
+    state.settings = settings.clone();
+    state.persisted = true;
+
+    let payload = SettingsChanged {
+        path,
+        settings: settings.clone(),
+        source,
+    };
+    for label in &state.subscribers {
+        let _ = app.emit_to(label.as_str(), "settings:changed", payload.clone());
+    }
+    Ok(settings)
+}
+
+fn lock_state() -> Result<std::sync::MutexGuard<'static, SettingsState>, String> {
+    SETTINGS_STATE
+        .get()
+        .ok_or("Settings not initialized")?
+        .lock()
+        .map_err(|_| "Settings lock poisoned".to_string())
+}
+
+/// All settings.
+#[tauri::command]
+pub fn get_settings() -> Settings {
+    current()
+}
+
+/// Receive "settings:changed" in this window. Returns the current settings.
+#[tauri::command]
+pub fn subscribe_settings(window: tauri::Window) -> Result<SettingsSnapshot, String> {
+    let mut state = lock_state()?;
+    state.subscribers.insert(window.label().to_string());
+    Ok(SettingsSnapshot {
+        settings: state.settings.clone(),
+        persisted: state.persisted,
+    })
+}
+
+/// Set a section (`"appearance"`) or a single key (`"appearance.theme"`).
+#[tauri::command]
+pub fn set_setting(
+    app: AppHandle,
+    window: tauri::Window,
+    path: String,
+    value: Value,
+) -> Result<Settings, String> {
+    let mut state = lock_state()?;
+    let settings = apply_setting(&state.settings, &path, value)?;
+    if settings == state.settings {
+        return Ok(settings);
+    }
+    commit(&app, &mut state, settings, Some(path), Some(window.label().to_string()))
+}
+
+/// Seed the store from settings the webview kept before the backend owned
+/// them. Ignored once settings have been saved.
+#[tauri::command]
+pub fn import_settings(app: AppHandle, window: tauri::Window, settings: Value) -> Result<Settings, String> {
+    let mut state = lock_state()?;
+    if state.persisted {
+        return Ok(state.settings.clone());
+    }
+    let settings = parse_settings(settings)?;
+    commit(&app, &mut state, settings, None, Some(window.label().to_string()))
+}
+
+/// Restore all defaults.
+#[tauri::command]
+pub fn reset_settings(app: AppHandle, window: tauri::Window) -> Result<Settings, String> {
+    let mut state = lock_state()?;
+    commit(&app, &mut state, Settings::default(), None, Some(window.label().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn defaults_serialize_with_frontend_keys() {
+        let raw = serde_json::to_value(Settings::default()).unwrap();
+        assert_eq!(raw["version"], SETTINGS_VERSION);
+        assert_eq!(raw["appearance"]["theme"], "paper");
+        assert_eq!(raw["cjkFormatting"]["quoteStyle"], "curly");
+        assert_eq!(raw["markdown"]["autoPairCJKStyle"], "auto");
+        assert_eq!(raw["advanced"]["mcpServer"]["port"], 9223);
+        assert_eq!(raw["update"]["skipVersion"], Value::Null);
+    }
+
+    #[test]
+    fn partial_file_gets_defaults_and_keeps_unknown_keys() {
+        let settings = parse_settings(json!({
+            "version": 1,
+            "appearance": { "theme": "night", "futureOption": 3 },
+            "newSection": { "enabled": true }
+        }))
+        .unwrap();
+
+        assert_eq!(settings.appearance.theme, "night");
+        assert_eq!(settings.appearance.font_size, 18.0);
+        assert_eq!(settings.general, GeneralSettings::default());
+
+        let raw = serde_json::to_value(&settings).unwrap();
+        assert_eq!(raw["appearance"]["futureOption"], 3);
+        assert_eq!(raw["newSection"]["enabled"], true);
+    }
+
+    #[test]
+    fn migrates_unversioned_paragraph_spacing() {
+        let settings = parse_settings(json!({ "appearance": { "paragraphSpacing": 1.5 } })).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.appearance.block_spacing, 1.5);
+        assert!(!settings.appearance.extra.contains_key("paragraphSpacing"));
+    }
+
+    #[test]
+    fn sets_keys_and_sections() {
+        let defaults = Settings::default();
+
+        let themed = apply_setting(&defaults, "appearance.theme", json!("mint")).unwrap();
+        assert_eq!(themed.appearance.theme, "mint");
+
+        let port = apply_setting(&defaults, "advanced.mcpServer.port", json!(9300)).unwrap();
+        assert_eq!(port.advanced.mcp_server.port, 9300);
+
+        let mut image = serde_json::to_value(&defaults.image).unwrap();
+        image["copyToAssets"] = json!(false);
+        let section = apply_setting(&defaults, "image", image).unwrap();
+        assert!(!section.image.copy_to_assets);
+    }
+
+    #[test]
+    fn rejects_bad_paths_and_types() {
+        let defaults = Settings::default();
+        assert!(apply_setting(&defaults, "appearance.fontSize", json!("big")).is_err());
+        assert!(apply_setting(&defaults, "nowhere.theme", json!("mint")).is_err());
+        assert!(apply_setting(&defaults, "appearance..theme", json!("mint")).is_err());
+        assert!(apply_setting(&defaults, "version", json!(7)).is_err());
+    }
+
+    #[test]
+    fn round_trip_and_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(SETTINGS_FILE);
+        assert_eq!(load_settings(&file), (Settings::default(), false));
+
+        let settings = apply_setting(&Settings::default(), "general.tabSize", json!(4)).unwrap();
+        save_settings(&file, &settings).unwrap();
+        assert_eq!(load_settings(&file), (settings, true));
+
+        fs::write(&file, r#"{ "general": { "tabSize": "wide" } }"#).unwrap();
+        assert_eq!(load_settings(&file), (Settings::default(), false));
+    }
+}
@@ -0,0 +1,555 @@
+//! Snippets
+//!
+//! Loads user snippet definitions from `<appDataDir>/snippets/*.json` and
+//! `<workspace>/.vmark/snippets/*.json` (workspace snippets win by name) and
+//! expands snippet bodies into plain text plus tab-stop ranges, so the editor
+//! can insert a snippet without parsing anything in JS.
+//!
+//! Files use the VS Code snippet format:
+//!
+//! ```json
+//! { "Callout": { "prefix": "note", "body": ["> [!NOTE]", "> ${1:text}$0"],
+//!   "description": "Note callout", "scope": "wysiwyg,source" } }
+//! ```
+//!
+//! Bodies support `$1`, `${1}`, `${1:placeholder}` (nestable), `$0` for the
+//! final cursor, `\$` escapes and a few variables (`$SELECTION`,
+//! `$CURRENT_DATE`, ...).
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+
+/// Snippets directory name (under app data, and under `.vmark` in a workspace)
+pub const SNIPPETS_DIR: &str = "snippets";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A prefix/body/scope field given either as one string or a list
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Snippet as written in a snippets file
+#[derive(Debug, Deserialize)]
+struct RawSnippet {
+    prefix: OneOrMany,
+    body: OneOrMany,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    scope: Option<OneOrMany>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub name: String,
+    pub prefix: Vec<String>,
+    pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Editor scopes the snippet applies to; empty means everywhere
+    pub scope: Vec<String>,
+    pub source: String, // "global" | "workspace"
+}
+
+/// Range in the expanded text, in UTF-16 code units (JS string offsets)
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct TextRange {
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TabStop {
+    pub index: u32,
+    /// Every occurrence of this tab stop (mirrors share the placeholder)
+    pub ranges: Vec<TextRange>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedSnippet {
+    pub name: String,
+    pub text: String,
+    /// Tab stops in visiting order (`$0` excluded)
+    pub tab_stops: Vec<TabStop>,
+    /// Cursor position after the last tab stop (`$0`, or the end of the text)
+    pub final_cursor: usize,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Return the global snippets directory path.
+#[command]
+pub fn get_snippets_dir(app: AppHandle) -> Result<String, String> {
+    Ok(global_snippets_dir(&app)?.to_string_lossy().to_string())
+}
+
+/// List snippets available in a scope (all scopes when `scope` is None).
+#[command]
+pub fn list_snippets(
+    app: AppHandle,
+    workspace_root: Option<String>,
+    scope: Option<String>,
+) -> Result<Vec<Snippet>, String> {
+    let snippets = load_all(&global_snippets_dir(&app)?, workspace_root.as_deref().map(Path::new));
+    Ok(snippets
+        .into_iter()
+        .filter(|snippet| scope.as_deref().is_none_or(|scope| in_scope(snippet, scope)))
+        .collect())
+}
+
+/// Expand the snippet whose prefix matches exactly. Returns None when no
+/// snippet matches in the given scope.
+#[command]
+pub fn resolve_snippet(
+    app: AppHandle,
+    prefix: String,
+    workspace_root: Option<String>,
+    scope: Option<String>,
+    selection: Option<String>,
+) -> Result<Option<ResolvedSnippet>, String> {
+    let snippets = load_all(&global_snippets_dir(&app)?, workspace_root.as_deref().map(Path::new));
+    let found = snippets.into_iter().find(|snippet| {
+        snippet.prefix.contains(&prefix)
+            && scope.as_deref().is_none_or(|scope| in_scope(snippet, scope))
+    });
+
+    Ok(found.map(|snippet| {
+        let vars = SnippetVars {
+            selection: selection.unwrap_or_default(),
+            now: Local::now(),
+        };
+        expand(&snippet.name, &snippet.body, &vars)
+    }))
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+pub fn global_snippets_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join(SNIPPETS_DIR))
+}
+
+fn workspace_snippets_dir(root: &Path) -> PathBuf {
+    root.join(".vmark").join(SNIPPETS_DIR)
+}
+
+fn in_scope(snippet: &Snippet, scope: &str) -> bool {
+    snippet.scope.is_empty() || snippet.scope.iter().any(|s| s.eq_ignore_ascii_case(scope))
+}
+
+fn into_list(value: OneOrMany) -> Vec<String> {
+    match value {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    }
+}
+
+/// Parse one snippets file. Malformed files are skipped (with a warning).
+fn parse_snippets_file(path: &Path, source: &str) -> Vec<Snippet> {
+    let parsed: Result<BTreeMap<String, RawSnippet>, String> = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+
+    let raw = match parsed {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::warn!("Skipping snippets file {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    raw.into_iter()
+        .filter_map(|(name, raw)| {
+            let prefix: Vec<String> = into_list(raw.prefix)
+                .into_iter()
+                .filter(|p| !p.trim().is_empty())
+                .collect();
+            if prefix.is_empty() {
+                return None;
+            }
+            let scope = match raw.scope {
+                Some(OneOrMany::One(s)) => s
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                Some(OneOrMany::Many(v)) => v,
+                None => Vec::new(),
+            };
+            Some(Snippet {
+                name,
+                prefix,
+                body: into_list(raw.body).join("\n"),
+                description: raw.description,
+                scope,
+                source: source.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Load every `*.json` file of a directory, in file name order.
+fn load_dir(dir: &Path, source: &str) -> Vec<Snippet> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .flat_map(|file| parse_snippets_file(file, source))
+        .collect()
+}
+
+/// Global snippets overlaid with workspace snippets (same name replaces),
+/// workspace snippets first, each group sorted by name.
+fn load_all(global_dir: &Path, workspace_root: Option<&Path>) -> Vec<Snippet> {
+    let mut by_name: HashMap<String, Snippet> = HashMap::new();
+    for snippet in load_dir(global_dir, "global") {
+        by_name.insert(snippet.name.clone(), snippet);
+    }
+    if let Some(root) = workspace_root {
+        for snippet in load_dir(&workspace_snippets_dir(root), "workspace") {
+            by_name.insert(snippet.name.clone(), snippet);
+        }
+    }
+
+    let mut snippets: Vec<Snippet> = by_name.into_values().collect();
+    // Workspace snippets come first so they win prefix lookups
+    snippets.sort_by(|a, b| (a.source != "workspace", &a.name).cmp(&(b.source != "workspace", &b.name)));
+    snippets
+}
+
+// ============================================================================
+// Expansion
+// ============================================================================
+
+struct SnippetVars {
+    selection: String,
+    now: DateTime<Local>,
+}
+
+impl SnippetVars {
+    fn value(&self, name: &str) -> Option<String> {
+        let format = match name {
+            "SELECTION" | "TM_SELECTED_TEXT" => return Some(self.selection.clone()),
+            "CURRENT_YEAR" => "%Y",
+            "CURRENT_MONTH" => "%m",
+            "CURRENT_DATE" => "%d",
+            "CURRENT_HOUR" => "%H",
+            "CURRENT_MINUTE" => "%M",
+            "CURRENT_SECOND" => "%S",
+            "CURRENT_DAY_NAME" => "%A",
+            "CURRENT_MONTH_NAME" => "%B",
+            _ => return None,
+        };
+        Some(self.now.format(format).to_string())
+    }
+}
+
+/// Expansion output being built
+struct Output<'a> {
+    text: String,
+    utf16_len: usize,
+    stops: BTreeMap<u32, Vec<TextRange>>,
+    /// Placeholder text per tab stop, used for bare mirrors (`$1` after `${1:x}`)
+    placeholders: &'a HashMap<u32, String>,
+    seen_placeholders: HashMap<u32, String>,
+}
+
+impl Output<'_> {
+    fn push(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.utf16_len += s.encode_utf16().count();
+    }
+}
+
+fn read_number(chars: &[char], pos: &mut usize) -> Option<u32> {
+    let start = *pos;
+    while *pos < chars.len() && chars[*pos].is_ascii_digit() {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect::<String>().parse().ok()
+}
+
+fn read_name(chars: &[char], pos: &mut usize) -> String {
+    let start = *pos;
+    while *pos < chars.len() && (chars[*pos].is_ascii_alphanumeric() || chars[*pos] == '_') {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect()
+}
+
+/// Expand a body up to the end, or up to the `}` closing a placeholder when `nested`.
+fn expand_into(chars: &[char], pos: &mut usize, nested: bool, vars: &SnippetVars, out: &mut Output) {
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        match c {
+            '\\' if matches!(chars.get(*pos + 1), Some('$' | '}' | '\\')) => {
+                out.push(&chars[*pos + 1].to_string());
+                *pos += 2;
+            }
+            '}' if nested => {
+                *pos += 1;
+                return;
+            }
+            '$' => {
+                *pos += 1;
+                match chars.get(*pos) {
+                    Some(d) if d.is_ascii_digit() => {
+                        let index = read_number(chars, pos).unwrap_or(0);
+                        tab_stop(index, false, chars, pos, vars, out);
+                    }
+                    Some('{') if chars.get(*pos + 1).is_some_and(|d| d.is_ascii_digit()) => {
+                        *pos += 1;
+                        let index = read_number(chars, pos).unwrap_or(0);
+                        match chars.get(*pos) {
+                            Some(':') => {
+                                *pos += 1;
+                                tab_stop(index, true, chars, pos, vars, out);
+                            }
+                            Some('}') => {
+                                *pos += 1;
+                                tab_stop(index, false, chars, pos, vars, out);
+                            }
+                            _ => out.push("${"),
+                        }
+                    }
+                    Some('{') if chars.get(*pos + 1).is_some_and(|c| c.is_ascii_alphabetic() || *c == '_') => {
+                        *pos += 1;
+                        let name = read_name(chars, pos);
+                        let value = vars.value(&name);
+                        match chars.get(*pos) {
+                            Some(':') => {
+                                // Default used when the variable is unknown or empty
+                                *pos += 1;
+                                let mut default = Output {
+                                    text: String::new(),
+                                    utf16_len: 0,
+                                    stops: BTreeMap::new(),
+                                    placeholders: out.placeholders,
+                                    seen_placeholders: HashMap::new(),
+                                };
+                                expand_into(chars, pos, true, vars, &mut default);
+                                match value.filter(|v| !v.is_empty()) {
+                                    Some(v) => out.push(&v),
+                                    None => out.push(&default.text),
+                                }
+                            }
+                            Some('}') => {
+                                *pos += 1;
+                                out.push(&value.unwrap_or(name));
+                            }
+                            _ => {
+                                out.push("${");
+                                out.push(&name);
+                            }
+                        }
+                    }
+                    Some(ch) if ch.is_ascii_alphabetic() || *ch == '_' => {
+                        let name = read_name(chars, pos);
+                        let value = vars.value(&name).unwrap_or(name);
+                        out.push(&value);
+                    }
+                    _ => out.push("$"),
+                }
+            }
+            _ => {
+                out.push(&c.to_string());
+                *pos += 1;
+            }
+        }
+    }
+}
+
+/// Emit one tab stop occurrence; `has_placeholder` when a `:placeholder}` follows.
+fn tab_stop(
+    index: u32,
+    has_placeholder: bool,
+    chars: &[char],
+    pos: &mut usize,
+    vars: &SnippetVars,
+    out: &mut Output,
+) {
+    let from = out.utf16_len;
+    if has_placeholder {
+        let start_text = out.text.len();
+        expand_into(chars, pos, true, vars, out);
+        let text = out.text[start_text..].to_string();
+        out.seen_placeholders.entry(index).or_insert(text);
+    } else if let Some(text) = out.placeholders.get(&index) {
+        let text = text.clone();
+        out.push(&text);
+    }
+    out.stops.entry(index).or_default().push(TextRange {
+        from,
+        to: out.utf16_len,
+    });
+}
+
+fn expand(name: &str, body: &str, vars: &SnippetVars) -> ResolvedSnippet {
+    let chars: Vec<char> = body.chars().collect();
+
+    // First pass collects placeholder text so mirrors before the defining
+    // occurrence get it too
+    let no_placeholders = HashMap::new();
+    let mut first = Output {
+        text: String::new(),
+        utf16_len: 0,
+        stops: BTreeMap::new(),
+        placeholders: &no_placeholders,
+        seen_placeholders: HashMap::new(),
+    };
+    expand_into(&chars, &mut 0, false, vars, &mut first);
+
+    let placeholders = first.seen_placeholders;
+    let mut out = Output {
+        text: String::new(),
+        utf16_len: 0,
+        stops: BTreeMap::new(),
+        placeholders: &placeholders,
+        seen_placeholders: HashMap::new(),
+    };
+    expand_into(&chars, &mut 0, false, vars, &mut out);
+
+    let final_cursor = out
+        .stops
+        .remove(&0)
+        .and_then(|ranges| ranges.first().map(|r| r.from))
+        .unwrap_or(out.utf16_len);
+
+    ResolvedSnippet {
+        name: name.to_string(),
+        text: out.text,
+        tab_stops: out
+            .stops
+            .into_iter()
+            .map(|(index, ranges)| TabStop { index, ranges })
+            .collect(),
+        final_cursor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn vars() -> SnippetVars {
+        SnippetVars {
+            selection: "picked".to_string(),
+            now: Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap(),
+        }
+    }
+
+    fn range(from: usize, to: usize) -> TextRange {
+        TextRange { from, to }
+    }
+
+    #[test]
+    fn test_expand_placeholders_and_final_cursor() {
+        let resolved = expand("link", "[${1:text}](${2:url})$0", &vars());
+        assert_eq!(resolved.text, "[text](url)");
+        assert_eq!(
+            resolved.tab_stops,
+            vec![
+                TabStop { index: 1, ranges: vec![range(1, 5)] },
+                TabStop { index: 2, ranges: vec![range(7, 10)] },
+            ]
+        );
+        assert_eq!(resolved.final_cursor, 11);
+    }
+
+    #[test]
+    fn test_expand_mirrors_and_nesting() {
+        let resolved = expand("tag", "<$1>${1:div ${2:id}}</$1>", &vars());
+        assert_eq!(resolved.text, "<div id>div id</div id>");
+        assert_eq!(resolved.tab_stops[0].ranges, vec![range(1, 7), range(8, 14), range(16, 22)]);
+        assert_eq!(resolved.tab_stops[1].ranges, vec![range(12, 14)]);
+        // No $0: cursor ends at the end of the text
+        assert_eq!(resolved.final_cursor, 23);
+    }
+
+    #[test]
+    fn test_expand_variables_and_escapes() {
+        let resolved = expand(
+            "v",
+            "**$SELECTION** ${CURRENT_YEAR}-${CURRENT_MONTH} ${UNKNOWN:fallback} \\$1 $$",
+            &vars(),
+        );
+        assert_eq!(resolved.text, "**picked** 2024-03 fallback $1 $$");
+        assert!(resolved.tab_stops.is_empty());
+    }
+
+    #[test]
+    fn test_offsets_are_utf16() {
+        let resolved = expand("e", "😀 ${1:中}", &vars());
+        assert_eq!(resolved.tab_stops[0].ranges, vec![range(3, 4)]);
+    }
+
+    #[test]
+    fn test_parse_file_formats() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("md.json");
+        fs::write(
+            &file,
+            r#"{
+                "Note": { "prefix": ["note", "nb"], "body": ["> [!NOTE]", "> $1"], "scope": "wysiwyg, source" },
+                "Bad": { "prefix": "", "body": "x" },
+                "Plain": { "prefix": "hr", "body": "---", "description": "Rule" }
+            }"#,
+        )
+        .unwrap();
+
+        let snippets = parse_snippets_file(&file, "global");
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].name, "Note");
+        assert_eq!(snippets[0].prefix, vec!["note", "nb"]);
+        assert_eq!(snippets[0].body, "> [!NOTE]\n> $1");
+        assert_eq!(snippets[0].scope, vec!["wysiwyg", "source"]);
+        assert!(in_scope(&snippets[1], "anything"));
+        assert!(!in_scope(&snippets[0], "codeblock"));
+    }
+
+    #[test]
+    fn test_workspace_snippets_override_global() {
+        let temp = TempDir::new().unwrap();
+        let global = temp.path().join("global");
+        let root = temp.path().join("project");
+        fs::create_dir_all(&global).unwrap();
+        fs::create_dir_all(workspace_snippets_dir(&root)).unwrap();
+        fs::write(global.join("a.json"), r#"{ "Sig": { "prefix": "sig", "body": "global" }, "Hr": { "prefix": "hr", "body": "---" } }"#).unwrap();
+        fs::write(global.join("broken.json"), "{ not json").unwrap();
+        fs::write(workspace_snippets_dir(&root).join("b.json"), r#"{ "Sig": { "prefix": "sig", "body": "workspace" } }"#).unwrap();
+
+        let snippets = load_all(&global, Some(&root));
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].body, "workspace");
+        assert_eq!(snippets[0].source, "workspace");
+        assert_eq!(snippets[1].name, "Hr");
+
+        let without_workspace = load_all(&global, None);
+        assert_eq!(without_workspace.iter().find(|s| s.name == "Sig").unwrap().body, "global");
+    }
+}
@@ -0,0 +1,298 @@
+//! Emoji and Symbol Lookup
+//!
+//! Backs the `:emoji:` autocomplete and the Insert Symbol dialog, so the
+//! frontend doesn't need to bundle large tables. Both are embedded here:
+//!
+//! - `emoji.tsv`: emoji, GitHub-style shortcodes (preferred first), Unicode name
+//! - `unicode.tsv`: code point and Unicode name for punctuation, arrows, math,
+//!   currency, box drawing and other symbol blocks
+//!
+//! Results are ranked: exact shortcode, then shortcode prefix, then names
+//! whose words start with every query word, then plain substring matches.
+
+use serde::Serialize;
+use std::sync::OnceLock;
+
+const EMOJI_TSV: &str = include_str!("emoji.tsv");
+const UNICODE_TSV: &str = include_str!("unicode.tsv");
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiMatch {
+    pub emoji: String,
+    /// Preferred shortcode, without colons
+    pub shortcode: String,
+    pub aliases: Vec<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolMatch {
+    pub symbol: String,
+    /// e.g. "U+2192"
+    pub codepoint: String,
+    pub name: String,
+}
+
+struct EmojiEntry {
+    emoji: &'static str,
+    shortcodes: Vec<&'static str>,
+    name: &'static str,
+}
+
+struct SymbolEntry {
+    symbol: char,
+    name: &'static str,
+}
+
+fn data_rows(tsv: &'static str) -> impl Iterator<Item = Vec<&'static str>> {
+    tsv.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split('\t').collect())
+}
+
+fn emoji_table() -> &'static [EmojiEntry] {
+    static TABLE: OnceLock<Vec<EmojiEntry>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        data_rows(EMOJI_TSV)
+            .filter_map(|cols| match cols[..] {
+                [emoji, shortcodes, name] => Some(EmojiEntry {
+                    emoji,
+                    shortcodes: shortcodes.split(',').collect(),
+                    name,
+                }),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+fn symbol_table() -> &'static [SymbolEntry] {
+    static TABLE: OnceLock<Vec<SymbolEntry>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        data_rows(UNICODE_TSV)
+            .filter_map(|cols| match cols[..] {
+                [hex, name] => Some(SymbolEntry {
+                    symbol: u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)?,
+                    name,
+                }),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+/// Lowercase, without surrounding colons or whitespace.
+fn normalize(query: &str) -> String {
+    query.trim().trim_matches(':').trim().to_lowercase()
+}
+
+/// Every query word starts some word of `name`.
+fn words_match(name: &str, query: &str) -> bool {
+    query
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|w| !w.is_empty())
+        .all(|q| name.split([' ', '-']).any(|w| w.starts_with(q)))
+}
+
+/// Rank of `name` for `query`, lower is better.
+fn name_rank(name: &str, query: &str) -> Option<u8> {
+    if name == query {
+        Some(2)
+    } else if words_match(name, query) {
+        Some(3)
+    } else if name.contains(query) {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+fn emoji_rank(entry: &EmojiEntry, query: &str) -> Option<u8> {
+    let snake = query.replace(' ', "_");
+    if entry.shortcodes[0] == snake {
+        return Some(0);
+    }
+    if entry.shortcodes.contains(&snake.as_str()) {
+        return Some(1);
+    }
+    if entry.shortcodes.iter().any(|s| s.starts_with(&snake)) {
+        return Some(2);
+    }
+    name_rank(entry.name, query).or_else(|| {
+        entry
+            .shortcodes
+            .iter()
+            .any(|s| s.contains(&snake))
+            .then_some(4)
+    })
+}
+
+fn clamp_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+}
+
+fn search_emoji_impl(query: &str, limit: usize) -> Vec<EmojiMatch> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut ranked: Vec<(u8, usize, &EmojiEntry)> = emoji_table()
+        .iter()
+        .filter_map(|entry| {
+            let rank = if entry.emoji == query {
+                0
+            } else {
+                emoji_rank(entry, &query)?
+            };
+            Some((rank, entry.shortcodes[0].len(), entry))
+        })
+        .collect();
+    // Stable: equal ranks keep table order, which lists common emoji first
+    ranked.sort_by_key(|&(rank, len, _)| (rank, len));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, _, entry)| EmojiMatch {
+            emoji: entry.emoji.to_string(),
+            shortcode: entry.shortcodes[0].to_string(),
+            aliases: entry.shortcodes[1..]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            name: entry.name.to_string(),
+        })
+        .collect()
+}
+
+/// A code point query such as "U+2192", "u+2192" or "0x2192".
+fn parse_codepoint(query: &str) -> Option<char> {
+    let hex = query
+        .strip_prefix("u+")
+        .or_else(|| query.strip_prefix("0x"))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+fn symbol_match(symbol: char, name: &str) -> SymbolMatch {
+    SymbolMatch {
+        symbol: symbol.to_string(),
+        codepoint: format!("U+{:04X}", symbol as u32),
+        name: name.to_string(),
+    }
+}
+
+fn search_unicode_impl(query: &str, limit: usize) -> Vec<SymbolMatch> {
+    let trimmed = query.trim();
+    let mut chars = trimmed.chars();
+    // A pasted character: look up its name
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if !c.is_ascii_alphanumeric() {
+            let name = symbol_table()
+                .iter()
+                .find(|e| e.symbol == c)
+                .map_or("", |e| e.name);
+            return vec![symbol_match(c, name)];
+        }
+    }
+    let query = trimmed.to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    if let Some(c) = parse_codepoint(&query) {
+        let name = symbol_table()
+            .iter()
+            .find(|e| e.symbol == c)
+            .map_or("", |e| e.name);
+        return vec![symbol_match(c, name)];
+    }
+    let mut ranked: Vec<(u8, &SymbolEntry)> = symbol_table()
+        .iter()
+        .filter_map(|entry| Some((name_rank(entry.name, &query)?, entry)))
+        .collect();
+    ranked.sort_by_key(|&(rank, entry)| (rank, entry.name.len()));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, entry)| symbol_match(entry.symbol, entry.name))
+        .collect()
+}
+
+/// Emoji matching a shortcode or name, best first.
+#[tauri::command]
+pub fn search_emoji(query: String, limit: Option<usize>) -> Vec<EmojiMatch> {
+    search_emoji_impl(&query, clamp_limit(limit))
+}
+
+/// Symbols matching a name, a code point ("U+2192") or a pasted character.
+#[tauri::command]
+pub fn search_unicode(query: String, limit: Option<usize>) -> Vec<SymbolMatch> {
+    search_unicode_impl(&query, clamp_limit(limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emoji(query: &str) -> Vec<String> {
+        search_emoji_impl(query, 5)
+            .into_iter()
+            .map(|m| m.emoji)
+            .collect()
+    }
+
+    #[test]
+    fn tables_parse() {
+        assert!(emoji_table().len() > 1000);
+        assert!(symbol_table().len() > 2000);
+        assert!(emoji_table().iter().all(|e| !e.shortcodes[0].is_empty()));
+    }
+
+    #[test]
+    fn exact_shortcodes_come_first() {
+        assert_eq!(emoji(":+1:")[0], "👍");
+        assert_eq!(emoji("thumbsup")[0], "👍");
+        assert_eq!(emoji("tada")[0], "🎉");
+        assert_eq!(emoji("dog")[0], "🐶");
+    }
+
+    #[test]
+    fn prefix_and_name_matches() {
+        let results = search_emoji_impl("heart_ey", 10);
+        assert_eq!(results[0].shortcode, "heart_eyes");
+        assert!(emoji("rocket").contains(&"🚀".to_string()));
+        // Name words, in any order
+        assert!(search_emoji_impl("face grinning", 50)
+            .iter()
+            .any(|m| m.emoji == "😀"));
+    }
+
+    #[test]
+    fn empty_query_finds_nothing() {
+        assert!(search_emoji_impl("  ", 10).is_empty());
+        assert!(search_unicode_impl("", 10).is_empty());
+    }
+
+    #[test]
+    fn unicode_by_name_codepoint_and_character() {
+        let arrows = search_unicode_impl("rightwards arrow", 5);
+        assert_eq!(arrows[0].symbol, "→");
+        assert_eq!(arrows[0].codepoint, "U+2192");
+
+        assert_eq!(search_unicode_impl("U+00B0", 5)[0].name, "degree sign");
+        assert_eq!(search_unicode_impl("∞", 5)[0].name, "infinity");
+        assert_eq!(
+            search_unicode_impl("greek small letter alpha", 1)[0].symbol,
+            "α"
+        );
+    }
+
+    #[test]
+    fn limit_is_respected() {
+        assert_eq!(search_unicode_impl("arrow", 3).len(), 3);
+        assert_eq!(clamp_limit(Some(10_000)), MAX_LIMIT);
+    }
+}
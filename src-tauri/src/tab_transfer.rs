@@ -1,9 +1,12 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager};
 
+use crate::hot_exit::session::TabState;
+use crate::quit;
 use crate::window_manager;
 
 /// Data transferred when a tab is dragged out to a new window.
@@ -51,9 +54,298 @@ pub fn claim_tab_transfer(window_label: String) -> Option<TabTransferData> {
 
 /// Remove any unclaimed transfer data for a window that was destroyed.
 /// Called from the `WindowEvent::Destroyed` handler to prevent leaks.
+/// Moves into or out of the window are dropped too; the source keeps its tab
+/// because it only removes it once the target confirms.
 pub fn clear_unclaimed_transfer(window_label: &str) {
     let mut guard = registry();
     if let Some(map) = guard.as_mut() {
         map.remove(window_label);
     }
+    drop(guard);
+
+    let mut moves = moves();
+    if let Some(map) = moves.as_mut() {
+        map.retain(|_, m| m.source_label != window_label && m.target_label != window_label);
+    }
+}
+
+// ============================================================================
+// Moving tabs between existing windows
+// ============================================================================
+//
+// Handoff: the source window serializes the tab (hot-exit TabState, so undo
+// history and cursor travel with it) and calls `transfer_tab`. The target is
+// notified with "tab:transfer-available" and pulls pending moves with
+// `claim_tab_moves` (also on mount, so a missed event is harmless). After
+// restoring the tab it calls `complete_tab_transfer`, which emits
+// "tab:transfer-complete" to the source — only then does the source drop
+// its copy. A target that refuses the tab calls `cancel_tab_transfer`.
+
+/// A tab on its way from one window to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabMove {
+    pub transfer_id: String,
+    pub source_label: String,
+    pub target_label: String,
+    pub tab: TabState,
+    /// Set once the target has pulled the move
+    #[serde(default)]
+    pub claimed: bool,
+}
+
+/// Payload of "tab:transfer-complete"
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferComplete {
+    transfer_id: String,
+    tab_id: String,
+    target_label: String,
+}
+
+/// Moves keyed by transfer id.
+static TAB_MOVES: Mutex<Option<HashMap<String, TabMove>>> = Mutex::new(None);
+static MOVE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn moves() -> std::sync::MutexGuard<'static, Option<HashMap<String, TabMove>>> {
+    TAB_MOVES.lock().unwrap()
+}
+
+/// Unclaimed moves for a window, marking them claimed.
+fn claim_moves_for(map: &mut HashMap<String, TabMove>, window_label: &str) -> Vec<TabMove> {
+    let mut claimed: Vec<TabMove> = map
+        .values_mut()
+        .filter(|m| m.target_label == window_label && !m.claimed)
+        .map(|m| {
+            m.claimed = true;
+            m.clone()
+        })
+        .collect();
+    claimed.sort_by(|a, b| a.transfer_id.cmp(&b.transfer_id));
+    claimed
+}
+
+/// Screen rectangle of a window in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WindowRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// First window (in the given order) whose rectangle contains the point.
+fn window_at_point(windows: &[(String, WindowRect)], x: f64, y: f64) -> Option<String> {
+    windows
+        .iter()
+        .find(|(_, r)| x >= r.x && x < r.x + r.width && y >= r.y && y < r.y + r.height)
+        .map(|(label, _)| label.clone())
+}
+
+/// Move a tab into another open document window. Returns the transfer id.
+#[tauri::command]
+pub fn transfer_tab(
+    app: AppHandle,
+    source_label: String,
+    target_label: String,
+    tab: TabState,
+) -> Result<String, String> {
+    if source_label == target_label {
+        return Err("Source and target window are the same".to_string());
+    }
+    if !quit::is_document_window_label(&target_label) {
+        return Err(format!("'{}' is not a document window", target_label));
+    }
+    let target = app
+        .get_webview_window(&target_label)
+        .ok_or_else(|| format!("Window '{}' not found", target_label))?;
+
+    let id = MOVE_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let transfer_id = format!("move-{:06}", id);
+    moves().get_or_insert_with(HashMap::new).insert(
+        transfer_id.clone(),
+        TabMove {
+            transfer_id: transfer_id.clone(),
+            source_label,
+            target_label: target_label.clone(),
+            tab,
+            claimed: false,
+        },
+    );
+
+    let _ = target.set_focus();
+    let _ = app.emit_to(target_label.as_str(), "tab:transfer-available", &transfer_id);
+    Ok(transfer_id)
+}
+
+/// Pull tabs moved into a window that it hasn't claimed yet.
+#[tauri::command]
+pub fn claim_tab_moves(window_label: String) -> Vec<TabMove> {
+    let mut guard = moves();
+    match guard.as_mut() {
+        Some(map) => claim_moves_for(map, &window_label),
+        None => Vec::new(),
+    }
+}
+
+/// Confirm that the target restored a moved tab; tells the source to drop it.
+#[tauri::command]
+pub fn complete_tab_transfer(app: AppHandle, transfer_id: String) -> Result<(), String> {
+    let done = moves()
+        .as_mut()
+        .and_then(|map| map.remove(&transfer_id))
+        .ok_or_else(|| format!("Unknown tab transfer: {}", transfer_id))?;
+
+    let _ = app.emit_to(
+        done.source_label.as_str(),
+        "tab:transfer-complete",
+        TransferComplete {
+            transfer_id,
+            tab_id: done.tab.id,
+            target_label: done.target_label,
+        },
+    );
+    Ok(())
+}
+
+/// Drop a move the target could not take (e.g. it already has the file open
+/// with its own unsaved edits). The source keeps its tab.
+#[tauri::command]
+pub fn cancel_tab_transfer(transfer_id: String) {
+    if let Some(map) = moves().as_mut() {
+        map.remove(&transfer_id);
+    }
+}
+
+/// Document window under a screen point (logical pixels), ignoring `exclude`.
+/// Used to decide whether a dragged-out tab was dropped onto another window.
+#[tauri::command]
+pub fn find_window_at_position(
+    app: AppHandle,
+    x: f64,
+    y: f64,
+    exclude: Option<String>,
+) -> Option<String> {
+    let mut windows: Vec<(String, WindowRect)> = app
+        .webview_windows()
+        .into_iter()
+        .filter(|(label, _)| {
+            quit::is_document_window_label(label) && Some(label) != exclude.as_ref()
+        })
+        .filter(|(_, w)| w.is_visible().unwrap_or(false) && !w.is_minimized().unwrap_or(false))
+        .filter_map(|(label, w)| {
+            let scale = w.scale_factor().ok()?;
+            let position = w.outer_position().ok()?.to_logical::<f64>(scale);
+            let size = w.outer_size().ok()?.to_logical::<f64>(scale);
+            Some((
+                label,
+                WindowRect {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                },
+            ))
+        })
+        .collect();
+    // Stacking order isn't exposed; prefer the focused window, then label order
+    windows.sort_by(|a, b| a.0.cmp(&b.0));
+    if let Some(pos) = windows.iter().position(|(label, _)| {
+        app.get_webview_window(label)
+            .and_then(|w| w.is_focused().ok())
+            .unwrap_or(false)
+    }) {
+        let focused = windows.remove(pos);
+        windows.insert(0, focused);
+    }
+    window_at_point(&windows, x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hot_exit::session::DocumentState;
+
+    fn tab(id: &str) -> TabState {
+        TabState {
+            id: id.to_string(),
+            file_path: Some(format!("/notes/{id}.md")),
+            title: id.to_string(),
+            is_pinned: false,
+            document: DocumentState {
+                content: "# Draft".to_string(),
+                saved_content: String::new(),
+                is_dirty: true,
+                is_missing: false,
+                is_divergent: false,
+                line_ending: "\n".to_string(),
+                cursor_info: None,
+                last_modified_timestamp: None,
+                is_untitled: false,
+                untitled_number: None,
+                undo_history: Vec::new(),
+                redo_history: Vec::new(),
+            },
+        }
+    }
+
+    fn pending(id: &str, source: &str, target: &str) -> TabMove {
+        TabMove {
+            transfer_id: id.to_string(),
+            source_label: source.to_string(),
+            target_label: target.to_string(),
+            tab: tab(id),
+            claimed: false,
+        }
+    }
+
+    #[test]
+    fn claims_only_unclaimed_moves_for_target() {
+        let mut map = HashMap::new();
+        map.insert("move-2".into(), pending("move-2", "main", "doc-1"));
+        map.insert("move-1".into(), pending("move-1", "main", "doc-1"));
+        map.insert("move-3".into(), pending("move-3", "doc-1", "main"));
+
+        let claimed = claim_moves_for(&mut map, "doc-1");
+        let ids: Vec<_> = claimed.iter().map(|m| m.transfer_id.as_str()).collect();
+        assert_eq!(ids, vec!["move-1", "move-2"]);
+        assert!(claim_moves_for(&mut map, "doc-1").is_empty());
+        assert_eq!(claim_moves_for(&mut map, "main").len(), 1);
+    }
+
+    #[test]
+    fn tab_move_serializes_hot_exit_tab_state() {
+        let json = serde_json::to_value(pending("move-9", "main", "doc-2")).unwrap();
+        assert_eq!(json["transferId"], "move-9");
+        assert_eq!(json["targetLabel"], "doc-2");
+        // Tab itself keeps the hot-exit (snake_case) schema
+        assert_eq!(json["tab"]["document"]["is_dirty"], true);
+    }
+
+    #[test]
+    fn finds_window_under_point() {
+        let rect = |x, y| WindowRect {
+            x,
+            y,
+            width: 800.0,
+            height: 600.0,
+        };
+        let windows = vec![
+            ("doc-1".to_string(), rect(100.0, 100.0)),
+            ("main".to_string(), rect(0.0, 0.0)),
+        ];
+        // Overlap resolves to the first (focused) window
+        assert_eq!(window_at_point(&windows, 150.0, 150.0), Some("doc-1".into()));
+        assert_eq!(window_at_point(&windows, 50.0, 50.0), Some("main".into()));
+        assert_eq!(window_at_point(&windows, 950.0, 50.0), None);
+    }
+
+    #[test]
+    fn destroyed_window_drops_its_moves() {
+        moves()
+            .get_or_insert_with(HashMap::new)
+            .insert("move-x".into(), pending("move-x", "doc-gone", "main"));
+        clear_unclaimed_transfer("doc-gone");
+        assert!(!moves().as_ref().unwrap().contains_key("move-x"));
+    }
 }
@@ -0,0 +1,449 @@
+//! Table Data
+//!
+//! Converts between delimited text (CSV, TSV, semicolon CSV from European
+//! Excel, ...) and markdown pipe tables, for pasting spreadsheets into a
+//! document and exporting a table's data back out.
+//!
+//! Imports accept a file path or the pasted text itself. The delimiter is
+//! sniffed from the first lines unless given; files are decoded with the same
+//! encoding detection as the editor, so Excel's windows-1252 exports work.
+//! Very large inputs are cut at `maxRows` and reported as truncated rather
+//! than producing a table the editor can't handle.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Default row limit for imports
+const DEFAULT_MAX_ROWS: usize = 5_000;
+
+/// Largest file `csv_to_markdown_table` will read
+const MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Delimiters considered when sniffing, in order of preference on ties
+const CANDIDATE_DELIMITERS: &[u8] = b"\t,;|";
+
+/// Lines looked at when sniffing the delimiter
+const SNIFF_LINES: usize = 20;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CsvImportOptions {
+    /// Single-character delimiter; sniffed when absent
+    pub delimiter: Option<String>,
+    /// Use the first row as the table header (otherwise columns are named
+    /// "Column 1", "Column 2", ...)
+    pub has_header: bool,
+    /// Rows (excluding the header) to keep
+    pub max_rows: usize,
+    /// Right-align columns whose cells are all numbers
+    pub align_numbers: bool,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            has_header: true,
+            max_rows: DEFAULT_MAX_ROWS,
+            align_numbers: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableImport {
+    pub markdown: String,
+    /// Body rows in the table (excluding the header)
+    pub rows: usize,
+    pub columns: usize,
+    /// The delimiter used, e.g. "," or "\t"
+    pub delimiter: String,
+    /// Rows were dropped because of `maxRows`
+    pub truncated: bool,
+}
+
+// ============================================================================
+// Delimited text → markdown
+// ============================================================================
+
+/// Count of `delimiter` per line outside quotes, for the first few lines.
+fn delimiter_counts(text: &str, delimiter: u8) -> Vec<usize> {
+    let mut counts = Vec::new();
+    let mut count = 0;
+    let mut in_quotes = false;
+    for &b in text.as_bytes() {
+        match b {
+            b'"' => in_quotes = !in_quotes,
+            b'\n' if !in_quotes => {
+                counts.push(count);
+                count = 0;
+                if counts.len() == SNIFF_LINES {
+                    return counts;
+                }
+            }
+            _ if b == delimiter && !in_quotes => count += 1,
+            _ => {}
+        }
+    }
+    if count > 0 || counts.is_empty() {
+        counts.push(count);
+    }
+    counts
+}
+
+/// Pick the delimiter that splits the first lines most consistently: every
+/// line must have the same (non-zero) count, and more columns win. Falls back
+/// to the candidate present on the most lines, then to a comma.
+fn sniff_delimiter(text: &str) -> u8 {
+    let mut best: Option<(u8, usize)> = None;
+    let mut fallback: Option<(u8, usize)> = None;
+    for &delimiter in CANDIDATE_DELIMITERS {
+        let counts = delimiter_counts(text, delimiter);
+        let lines_with = counts.iter().filter(|&&c| c > 0).count();
+        if counts[0] > 0 && counts.iter().all(|&c| c == counts[0]) {
+            if best.is_none_or(|(_, n)| counts[0] > n) {
+                best = Some((delimiter, counts[0]));
+            }
+        } else if lines_with > 0 && fallback.is_none_or(|(_, n)| lines_with > n) {
+            fallback = Some((delimiter, lines_with));
+        }
+    }
+    best.or(fallback).map_or(b',', |(d, _)| d)
+}
+
+fn parse_delimiter(delimiter: &str) -> Result<u8, String> {
+    match delimiter {
+        "\\t" | "tab" => Ok(b'\t'),
+        d if d.len() == 1 => Ok(d.as_bytes()[0]),
+        d => Err(format!("Delimiter must be a single character, got {d:?}")),
+    }
+}
+
+/// Escape a cell for a pipe table: `|` is escaped and line breaks become
+/// `<br>`, since a table row must stay on one line.
+fn escape_cell(cell: &str) -> String {
+    cell.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+fn is_number(cell: &str) -> bool {
+    let cell = cell.trim().trim_start_matches(['$', '€', '£', '¥']).trim_end_matches('%');
+    !cell.is_empty() && cell.replace(',', "").parse::<f64>().is_ok()
+}
+
+fn render_row(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::from("|");
+    for (cell, &width) in cells.iter().zip(widths) {
+        let pad = width.saturating_sub(cell.chars().count());
+        line.push(' ');
+        line.push_str(cell);
+        line.push_str(&" ".repeat(pad));
+        line.push_str(" |");
+    }
+    line
+}
+
+fn delimited_to_markdown(text: &str, options: &CsvImportOptions) -> Result<TableImport, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let delimiter = match options.delimiter.as_deref() {
+        Some(d) => parse_delimiter(d)?,
+        None => sniff_delimiter(text),
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let limit = options.max_rows.saturating_add(usize::from(options.has_header));
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut truncated = false;
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {e}"))?;
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        if rows.len() == limit {
+            truncated = true;
+            break;
+        }
+        rows.push(record.iter().map(escape_cell).collect());
+    }
+    if rows.is_empty() {
+        return Err("No table data found".to_string());
+    }
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(columns, String::new());
+    }
+    let header = if options.has_header {
+        rows.remove(0)
+    } else {
+        (1..=columns).map(|i| format!("Column {i}")).collect()
+    };
+
+    let right_aligned: Vec<bool> = (0..columns)
+        .map(|col| {
+            options.align_numbers
+                && rows.iter().any(|row| !row[col].is_empty())
+                && rows.iter().all(|row| row[col].is_empty() || is_number(&row[col]))
+        })
+        .collect();
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+    let separator: Vec<String> = widths
+        .iter()
+        .zip(&right_aligned)
+        .map(|(&width, &right)| {
+            if right {
+                format!("{}:", "-".repeat(width - 1))
+            } else {
+                "-".repeat(width)
+            }
+        })
+        .collect();
+
+    let mut markdown = render_row(&header, &widths);
+    markdown.push('\n');
+    markdown.push_str(&render_row(&separator, &widths));
+    for row in &rows {
+        markdown.push('\n');
+        markdown.push_str(&render_row(row, &widths));
+    }
+
+    Ok(TableImport {
+        markdown,
+        rows: rows.len(),
+        columns,
+        delimiter: (delimiter as char).to_string(),
+        truncated,
+    })
+}
+
+/// A single-line argument naming an existing file is read as a file;
+/// anything else is the table text itself.
+fn read_input(path_or_text: &str) -> Result<String, String> {
+    let candidate = path_or_text.trim();
+    if candidate.is_empty() || candidate.contains('\n') {
+        return Ok(path_or_text.to_string());
+    }
+    let path = Path::new(candidate);
+    if !path.is_file() {
+        return Ok(path_or_text.to_string());
+    }
+    let size = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {candidate}: {e}"))?
+        .len();
+    if size > MAX_FILE_BYTES {
+        return Err(format!(
+            "{candidate} is too large to import as a table ({} MB)",
+            size / (1024 * 1024)
+        ));
+    }
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {candidate}: {e}"))?;
+    Ok(crate::text_encoding::decode_bytes(&bytes).content)
+}
+
+// ============================================================================
+// Markdown → delimited text
+// ============================================================================
+
+/// Split a table row on unescaped pipes (as in GFM, this includes pipes
+/// inside code spans).
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    // A trailing pipe closes the last cell rather than starting a new one
+    if !cell.trim().is_empty() || !line.trim_end().ends_with('|') {
+        cells.push(cell);
+    }
+    cells
+        .into_iter()
+        .map(|cell| {
+            cell.trim()
+                .replace("<br>", "\n")
+                .replace("<br/>", "\n")
+                .replace("<br />", "\n")
+        })
+        .collect()
+}
+
+fn is_separator_row(cells: &[String]) -> bool {
+    !cells.is_empty()
+        && cells.iter().all(|cell| {
+            let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+fn markdown_to_delimited(table_text: &str, delimiter: u8) -> Result<String, String> {
+    let rows: Vec<Vec<String>> = table_text
+        .lines()
+        .filter(|line| line.contains('|'))
+        .map(split_row)
+        .filter(|cells| !is_separator_row(cells))
+        .collect();
+    if rows.is_empty() {
+        return Err("No markdown table found".to_string());
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(Vec::new());
+    for row in &rows {
+        writer
+            .write_record(row)
+            .map_err(|e| format!("Failed to write CSV: {e}"))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {e}"))?;
+    String::from_utf8(bytes).map_err(|e| format!("Failed to write CSV: {e}"))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Convert CSV/TSV (a file path or pasted text) to a markdown table.
+#[tauri::command]
+pub async fn csv_to_markdown_table(
+    path_or_text: String,
+    options: Option<CsvImportOptions>,
+) -> Result<TableImport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let text = read_input(&path_or_text)?;
+        delimited_to_markdown(&text, &options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Failed to import table: {e}"))?
+}
+
+/// Convert a markdown pipe table to CSV (or TSV with `delimiter: "\t"`).
+#[tauri::command]
+pub fn markdown_table_to_csv(table_text: String, delimiter: Option<String>) -> Result<String, String> {
+    let delimiter = delimiter.as_deref().map_or(Ok(b','), parse_delimiter)?;
+    markdown_to_delimited(&table_text, delimiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(text: &str) -> TableImport {
+        delimited_to_markdown(text, &CsvImportOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_sniff_delimiter() {
+        assert_eq!(sniff_delimiter("a,b,c\n1,2,3\n"), b',');
+        assert_eq!(sniff_delimiter("a\tb\n1,5\t2\n"), b'\t');
+        assert_eq!(sniff_delimiter("name;price\n\"a;b\";1,5\n"), b';');
+        assert_eq!(sniff_delimiter("single column\nvalue\n"), b',');
+    }
+
+    #[test]
+    fn test_csv_to_markdown() {
+        let result = import("Item,Price\n\"Pen, blue\",1.50\n\"Say \"\"hi\"\"\",12\n");
+        assert_eq!(result.rows, 2);
+        assert_eq!(result.columns, 2);
+        assert_eq!(result.delimiter, ",");
+        assert_eq!(
+            result.markdown,
+            "| Item      | Price |\n\
+             | --------- | ----: |\n\
+             | Pen, blue | 1.50  |\n\
+             | Say \"hi\"  | 12    |"
+        );
+    }
+
+    #[test]
+    fn test_cells_are_escaped_and_rows_padded() {
+        let result = import("a\tb\tc\nx|y\t\"line1\nline2\"\n");
+        assert_eq!(result.columns, 3);
+        assert!(result.markdown.contains("| x\\|y | line1<br>line2 |     |"));
+    }
+
+    #[test]
+    fn test_without_header_and_truncation() {
+        let options = CsvImportOptions {
+            has_header: false,
+            max_rows: 2,
+            ..Default::default()
+        };
+        let result = delimited_to_markdown("1;2\n3;4\n5;6\n", &options).unwrap();
+        assert!(result.truncated);
+        assert_eq!(result.rows, 2);
+        assert!(result.markdown.starts_with("| Column 1 | Column 2 |"));
+        assert!(delimited_to_markdown("\n\n", &options).is_err());
+        assert!(delimited_to_markdown(
+            "a",
+            &CsvImportOptions {
+                delimiter: Some("ab".into()),
+                ..Default::default()
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_input_file_or_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        // windows-1252 "Café"
+        fs::write(&path, b"Name\nCaf\xe9\n").unwrap();
+        let text = read_input(&path.to_string_lossy()).unwrap();
+        assert_eq!(text, "Name\nCafé\n");
+        assert_eq!(read_input("a,b\n1,2").unwrap(), "a,b\n1,2");
+        assert_eq!(read_input("not/a/file.csv").unwrap(), "not/a/file.csv");
+    }
+
+    #[test]
+    fn test_markdown_to_csv() {
+        let table = "| Item | Note |\n|:--|--:|\n| Pen, blue | a \\| b |\n| `x\\|y` | one<br>two |\n";
+        assert_eq!(
+            markdown_to_delimited(table, b',').unwrap(),
+            "Item,Note\n\"Pen, blue\",a | b\n`x|y`,\"one\ntwo\"\n"
+        );
+        assert_eq!(
+            markdown_to_delimited("a | b\n--- | ---\n1 | 2", b'\t').unwrap(),
+            "a\tb\n1\t2\n"
+        );
+        assert!(markdown_to_delimited("no table here", b',').is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let csv = "Name,Quote\nAda,\"She said \"\"hi\"\", then left\"\n";
+        let table = import(csv).markdown;
+        assert_eq!(markdown_to_delimited(&table, b',').unwrap(), csv);
+    }
+}
@@ -0,0 +1,347 @@
+//! Task Calendar (ICS Export)
+//!
+//! Collects tasks with due dates from the workspace's notes — the Tasks
+//! plugin syntax, `- [ ] Send invoice 📅 2026-05-01` — and writes them as
+//! all-day events to an `.ics` file. Calendar apps can import the file or
+//! subscribe to it; each export rewrites it in place, and event UIDs are
+//! stable (note + task text), so a changed due date moves the existing event
+//! instead of adding a new one.
+//!
+//! The default location is `<workspace>/.vmark/tasks.ics`.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::file_finder::{collect_notes, relative_path};
+
+const DUE_MARKER: char = '📅';
+
+/// Other Tasks plugin fields, stripped from event titles
+const TASK_FIELD_MARKERS: &[char] = &['⏳', '🛫', '✅', '➕', '🔁', '⏫', '🔼', '🔽', '🔺', '⏬'];
+
+const DEFAULT_ICS_FILE: &str = "tasks.ics";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DatedTask {
+    /// Workspace-relative note path
+    pub note: String,
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+    /// `YYYY-MM-DD`
+    pub due: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IcsExport {
+    pub path: String,
+    pub tasks: Vec<DatedTask>,
+    /// False when the file already had this content
+    pub changed: bool,
+}
+
+/// `- [ ] text`, `* [x] text`, `1. [ ] text` → `(done, text)`.
+pub(crate) fn parse_task_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    let rest = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+        .or_else(|| {
+            let (n, rest) = trimmed.split_once(". ")?;
+            n.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+        })?;
+    let (done, text) = if let Some(text) = rest.strip_prefix("[ ] ") {
+        (false, text)
+    } else if let Some(text) = rest.strip_prefix("[x] ").or_else(|| rest.strip_prefix("[X] ")) {
+        (true, text)
+    } else {
+        return None;
+    };
+    Some((done, text))
+}
+
+/// Due date after the 📅 marker.
+pub(crate) fn parse_due(text: &str) -> Option<NaiveDate> {
+    let (_, after) = text.split_once(DUE_MARKER)?;
+    let date = after.trim_start().get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Task text without Tasks plugin fields (`📅 2026-05-01`, `⏫`, ...).
+pub(crate) fn task_title(text: &str) -> String {
+    let end = text
+        .char_indices()
+        .find(|(_, c)| *c == DUE_MARKER || TASK_FIELD_MARKERS.contains(c))
+        .map_or(text.len(), |(i, _)| i);
+    text[..end].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Dated tasks in one note, skipping fenced code.
+fn extract_tasks(note: &str, content: &str) -> Vec<DatedTask> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((done, text)) = parse_task_item(line) else { continue };
+        let Some(due) = parse_due(text) else { continue };
+        let title = task_title(text);
+        if title.is_empty() {
+            continue;
+        }
+        tasks.push(DatedTask {
+            note: note.to_string(),
+            line: index + 1,
+            text: title,
+            due: due.format("%Y-%m-%d").to_string(),
+            done,
+        });
+    }
+    tasks
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Fold a content line at 75 octets (RFC 5545 §3.1), without splitting characters.
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Stable event UID: the same task text in the same note keeps its UID when
+/// its due date or line changes. Repeats of a text get an occurrence suffix.
+fn task_uids(tasks: &[DatedTask]) -> Vec<String> {
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    tasks
+        .iter()
+        .map(|task| {
+            let count = seen.entry((task.note.clone(), task.text.clone())).or_default();
+            *count += 1;
+            let key = format!("{}\n{}\n{}", task.note, task.text, count);
+            format!("{}@vmark", &crate::watcher::content_hash(key.as_bytes())[..24])
+        })
+        .collect()
+}
+
+/// Calendar for `tasks`. `stamp` is the DTSTAMP for every event; using the
+/// notes' modification time keeps unchanged exports byte-identical.
+fn build_ics(calendar_name: &str, tasks: &[DatedTask], stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let mut line = |text: String| fold_line(&text, &mut out);
+    line("BEGIN:VCALENDAR".into());
+    line("VERSION:2.0".into());
+    line("PRODID:-//VMark//Tasks//EN".into());
+    line("CALSCALE:GREGORIAN".into());
+    line(format!("X-WR-CALNAME:{}", escape_text(calendar_name)));
+    let stamp = stamp.format("%Y%m%dT%H%M%SZ").to_string();
+    for (task, uid) in tasks.iter().zip(task_uids(tasks)) {
+        let Ok(due) = NaiveDate::parse_from_str(&task.due, "%Y-%m-%d") else { continue };
+        let next = due.succ_opt().unwrap_or(due);
+        line("BEGIN:VEVENT".into());
+        line(format!("UID:{uid}"));
+        line(format!("DTSTAMP:{stamp}"));
+        line(format!("DTSTART;VALUE=DATE:{}", due.format("%Y%m%d")));
+        line(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+        let summary = if task.done { format!("✓ {}", task.text) } else { task.text.clone() };
+        line(format!("SUMMARY:{}", escape_text(&summary)));
+        line(format!("DESCRIPTION:{}", escape_text(&format!("{} (line {})", task.note, task.line))));
+        line("TRANSP:TRANSPARENT".into());
+        if task.done {
+            line("STATUS:CANCELLED".into());
+        }
+        line("END:VEVENT".into());
+    }
+    line("END:VCALENDAR".into());
+    out
+}
+
+fn collect_tasks(root: &Path, notes: &[PathBuf]) -> (Vec<DatedTask>, Option<DateTime<Utc>>) {
+    let mut tasks = Vec::new();
+    let mut newest: Option<DateTime<Utc>> = None;
+    for path in notes {
+        if crate::encryption::is_encrypted_path(path) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        let found = extract_tasks(&relative_path(root, path), &content);
+        if found.is_empty() {
+            continue;
+        }
+        if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+            let modified = DateTime::<Utc>::from(modified);
+            newest = Some(newest.map_or(modified, |n| n.max(modified)));
+        }
+        tasks.extend(found);
+    }
+    tasks.sort_by(|a, b| a.due.cmp(&b.due).then(a.note.cmp(&b.note)).then(a.line.cmp(&b.line)));
+    (tasks, newest)
+}
+
+fn export_impl(root: &Path, output: &Path, include_done: bool, excludes: &[String]) -> Result<IcsExport, String> {
+    let (mut tasks, newest) = collect_tasks(root, &collect_notes(root, excludes));
+    if !include_done {
+        tasks.retain(|t| !t.done);
+    }
+    let name = root
+        .file_name()
+        .map(|n| format!("{} tasks", n.to_string_lossy()))
+        .unwrap_or_else(|| "VMark tasks".to_string());
+    let ics = build_ics(&name, &tasks, newest.unwrap_or(DateTime::<Utc>::UNIX_EPOCH));
+
+    let changed = fs::read(output).map_or(true, |existing| existing != ics.as_bytes());
+    if changed {
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        crate::app_paths::atomic_write_file(output, ics.as_bytes())?;
+    }
+    Ok(IcsExport {
+        path: output.to_string_lossy().into_owned(),
+        tasks,
+        changed,
+    })
+}
+
+/// Write (or update) the workspace's task calendar. `output_path` defaults
+/// to `.vmark/tasks.ics`; completed tasks are left out unless `include_done`.
+#[tauri::command]
+pub async fn export_tasks_ics(
+    root_path: String,
+    output_path: Option<String>,
+    include_done: Option<bool>,
+) -> Result<IcsExport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = PathBuf::from(&root_path);
+        if !root.is_dir() {
+            return Err(format!("Not a folder: {root_path}"));
+        }
+        let output = output_path
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| root.join(".vmark").join(DEFAULT_ICS_FILE));
+        let excludes = crate::workspace::workspace_excludes(&root);
+        export_impl(&root, &output, include_done.unwrap_or(false), &excludes)
+    })
+    .await
+    .map_err(|e| format!("Failed to export tasks: {e}"))?
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_extract_tasks() {
+        let content = "# Plan\n\
+            - [ ] Send invoice 📅 2026-05-01\n\
+            - [x] Book venue ⏫ 📅 2026-04-20 ✅ 2026-04-18\n\
+            - [ ] No date\n\
+            - Not a task 📅 2026-05-01\n\
+            ```\n- [ ] In code 📅 2026-05-01\n```\n\
+            1. [ ] Numbered 📅2026-06-01\n";
+        let tasks = extract_tasks("Plan.md", content);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].text, "Send invoice");
+        assert_eq!(tasks[0].due, "2026-05-01");
+        assert_eq!(tasks[0].line, 2);
+        assert_eq!(tasks[1].text, "Book venue");
+        assert!(tasks[1].done);
+        assert_eq!(tasks[2].due, "2026-06-01");
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape_text("a, b; c\\d\ne"), "a\\, b\\; c\\\\d\\ne");
+
+        let mut out = String::new();
+        fold_line(&format!("SUMMARY:{}", "é".repeat(50)), &mut out);
+        for line in out.split("\r\n") {
+            assert!(line.len() <= 75);
+        }
+        assert_eq!(out.replace("\r\n ", "").trim_end(), format!("SUMMARY:{}", "é".repeat(50)));
+    }
+
+    #[test]
+    fn test_uids_stable_across_date_changes() {
+        let task = |due: &str, line: usize| DatedTask {
+            note: "Plan.md".into(),
+            line,
+            text: "Send invoice".into(),
+            due: due.into(),
+            done: false,
+        };
+        let before = task_uids(&[task("2026-05-01", 2)]);
+        let after = task_uids(&[task("2026-05-09", 7)]);
+        assert_eq!(before, after);
+        let twice = task_uids(&[task("2026-05-01", 2), task("2026-05-02", 3)]);
+        assert_ne!(twice[0], twice[1]);
+    }
+
+    #[test]
+    fn test_build_ics() {
+        let tasks = extract_tasks("Plan.md", "- [ ] Pay rent, utilities 📅 2026-05-31");
+        let stamp = Utc.with_ymd_and_hms(2026, 5, 1, 8, 0, 0).unwrap();
+        let ics = build_ics("Notes tasks", &tasks, stamp);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260531\r\nDTEND;VALUE=DATE:20260601\r\n"));
+        assert!(ics.contains("SUMMARY:Pay rent\\, utilities\r\n"));
+        assert!(ics.contains("DTSTAMP:20260501T080000Z\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_export_updates_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("notes")).unwrap();
+        fs::write(root.join("notes/a.md"), "- [ ] One 📅 2026-05-01\n- [x] Two 📅 2026-05-02\n").unwrap();
+        let output = root.join(".vmark").join(DEFAULT_ICS_FILE);
+
+        let first = export_impl(root, &output, false, &[]).unwrap();
+        assert!(first.changed);
+        assert_eq!(first.tasks.len(), 1);
+        assert_eq!(first.tasks[0].note, "notes/a.md");
+        assert!(!export_impl(root, &output, false, &[]).unwrap().changed);
+
+        let with_done = export_impl(root, &output, true, &[]).unwrap();
+        assert!(with_done.changed);
+        assert!(fs::read_to_string(&output).unwrap().contains("SUMMARY:✓ Two"));
+    }
+}
@@ -0,0 +1,562 @@
+//! Task Index
+//!
+//! Every checkbox item (`- [ ] task`, `- [x] done`) in a workspace's notes,
+//! with its file, line, status, due date (`📅 2026-05-01`) and `#tags`, for
+//! the tasks panel. The index is built on the first query and then kept
+//! current by the file watcher: `watcher` hands each change to
+//! `apply_fs_change`, which re-reads only the affected notes.
+//!
+//! `toggle_task` checks or unchecks an item in place on disk.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::task_calendar::{parse_due, parse_task_item, task_title};
+use crate::watcher::FsChangeEvent;
+use crate::file_finder::{collect_notes, relative_path};
+
+/// Task indexes keyed by workspace root
+static INDEXES: Mutex<Option<HashMap<String, TaskIndex>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    /// Workspace-relative note path
+    pub path: String,
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+    pub done: bool,
+    /// `YYYY-MM-DD`
+    pub due: Option<String>,
+    /// Lowercase, without `#`
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatusFilter {
+    #[default]
+    Open,
+    Done,
+    All,
+}
+
+/// Filters for `query_tasks`; all optional
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TaskQuery {
+    pub status: TaskStatusFilter,
+    /// Tasks with this tag (case-insensitive, `#` optional)
+    pub tag: Option<String>,
+    /// Tasks due on or before this date (`YYYY-MM-DD`)
+    pub due_before: Option<String>,
+    /// Tasks due on or after this date (`YYYY-MM-DD`)
+    pub due_after: Option<String>,
+    /// Only tasks with (true) or without (false) a due date
+    pub has_due: Option<bool>,
+    /// Tasks in this note or folder (workspace-relative)
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagCount {
+    pub tag: String,
+    pub open: usize,
+    pub total: usize,
+}
+
+struct TaskIndex {
+    root: PathBuf,
+    excludes: Vec<String>,
+    /// Tasks by workspace-relative note path
+    files: HashMap<String, Vec<Task>>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// `#tags` in task text: `#` at a word start followed by a letter, so
+/// `#1` issue numbers and `a#b` are left alone.
+fn task_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+            .collect();
+        if tag.chars().next().is_some_and(char::is_alphabetic) {
+            let tag = tag.to_lowercase();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
+/// All checkbox items in a note, skipping fenced code.
+fn parse_tasks(rel_path: &str, content: &str) -> Vec<Task> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((done, text)) = parse_task_item(line) else {
+            continue;
+        };
+        let title = task_title(text);
+        if title.is_empty() {
+            continue;
+        }
+        tasks.push(Task {
+            path: rel_path.to_string(),
+            line: index + 1,
+            tags: task_tags(text),
+            text: title,
+            done,
+            due: parse_due(text).map(|d| d.format("%Y-%m-%d").to_string()),
+        });
+    }
+    tasks
+}
+
+fn read_tasks(root: &Path, path: &Path) -> Vec<Task> {
+    if crate::encryption::is_encrypted_path(path) {
+        return Vec::new();
+    }
+    fs::read(path)
+        .map(|bytes| parse_tasks(&relative_path(root, path), &String::from_utf8_lossy(&bytes)))
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+impl TaskIndex {
+    fn build(root: &Path) -> Self {
+        let excludes = crate::workspace::workspace_excludes(root);
+        let files = collect_notes(root, &excludes)
+            .into_iter()
+            .map(|path| (relative_path(root, &path), read_tasks(root, &path)))
+            .filter(|(_, tasks)| !tasks.is_empty())
+            .collect();
+        Self {
+            root: root.to_path_buf(),
+            excludes,
+            files,
+        }
+    }
+
+    /// Whether changes to `path` belong in this index (the same rules as
+    /// `collect_notes`: markdown, not hidden, not in an excluded folder).
+    fn covers(&self, path: &Path) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let names: Vec<String> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let Some(file_name) = names.last() else {
+            return false;
+        };
+        crate::file_tree::is_markdown(file_name)
+            && names
+                .iter()
+                .all(|n| !n.starts_with('.') && !self.excludes.contains(n))
+    }
+
+    /// Re-read `path`, or drop it when it no longer exists.
+    fn update_file(&mut self, path: &Path) {
+        if !self.covers(path) {
+            return;
+        }
+        let rel = relative_path(&self.root, path);
+        let tasks = if path.is_file() {
+            read_tasks(&self.root, path)
+        } else {
+            Vec::new()
+        };
+        if tasks.is_empty() {
+            self.files.remove(&rel);
+        } else {
+            self.files.insert(rel, tasks);
+        }
+    }
+
+    /// Drop every note under a removed or renamed folder.
+    fn remove_prefix(&mut self, path: &Path) {
+        if let Ok(rel) = path.strip_prefix(&self.root) {
+            let prefix = format!("{}/", rel.to_string_lossy().replace('\\', "/"));
+            self.files.retain(|note, _| !note.starts_with(&prefix));
+        }
+    }
+
+    fn tasks(&self) -> impl Iterator<Item = &Task> {
+        self.files.values().flatten()
+    }
+}
+
+fn with_index<T>(root_path: &str, f: impl FnOnce(&mut TaskIndex) -> T) -> Result<T, String> {
+    let root = Path::new(root_path);
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {root_path}"));
+    }
+    let mut guard = INDEXES.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let index = guard
+        .get_or_insert_with(HashMap::new)
+        .entry(root_path.to_string())
+        .or_insert_with(|| TaskIndex::build(root));
+    Ok(f(index))
+}
+
+/// Keep built indexes current with a watcher event. Called by `watcher` for
+/// every change it reports; a no-op for workspaces nobody has queried.
+pub(crate) fn apply_fs_change(event: &FsChangeEvent) {
+    let Ok(mut guard) = INDEXES.lock() else {
+        return;
+    };
+    let Some(indexes) = guard.as_mut() else {
+        return;
+    };
+    let paths = event
+        .paths
+        .iter()
+        .chain(event.from.iter())
+        .chain(event.to.iter());
+    for path in paths {
+        let path = Path::new(path);
+        for index in indexes.values_mut().filter(|i| path.starts_with(&i.root)) {
+            if path.is_dir() {
+                // A folder appeared (moved in): index what's inside
+                for note in collect_notes(path, &index.excludes) {
+                    index.update_file(&note);
+                }
+            } else {
+                index.update_file(path);
+                if !path.exists() {
+                    index.remove_prefix(path);
+                }
+            }
+        }
+    }
+}
+
+fn matches(task: &Task, query: &TaskQuery) -> bool {
+    let status_ok = match query.status {
+        TaskStatusFilter::Open => !task.done,
+        TaskStatusFilter::Done => task.done,
+        TaskStatusFilter::All => true,
+    };
+    let tag_ok = query.tag.as_deref().is_none_or(|tag| {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        task.tags
+            .iter()
+            .any(|t| *t == tag || t.starts_with(&format!("{tag}/")))
+    });
+    let due = task.due.as_deref();
+    let before_ok = query
+        .due_before
+        .as_deref()
+        .is_none_or(|d| due.is_some_and(|due| due <= d));
+    let after_ok = query
+        .due_after
+        .as_deref()
+        .is_none_or(|d| due.is_some_and(|due| due >= d));
+    let has_due_ok = query.has_due.is_none_or(|has| due.is_some() == has);
+    let path_ok = query.path.as_deref().is_none_or(|p| {
+        let p = p.trim_end_matches('/');
+        task.path == p || task.path.starts_with(&format!("{p}/"))
+    });
+    status_ok && tag_ok && before_ok && after_ok && has_due_ok && path_ok
+}
+
+/// Matching tasks: dated ones first by due date, then by note and line.
+fn run_query<'a>(tasks: impl Iterator<Item = &'a Task>, query: &TaskQuery) -> Vec<Task> {
+    let mut results: Vec<Task> = tasks.filter(|t| matches(t, query)).cloned().collect();
+    results.sort_by(|a, b| {
+        let due = |t: &Task| (t.due.is_none(), t.due.clone());
+        due(a)
+            .cmp(&due(b))
+            .then_with(|| a.path.cmp(&b.path))
+            .then(a.line.cmp(&b.line))
+    });
+    results
+}
+
+fn tag_counts<'a>(tasks: impl Iterator<Item = &'a Task>) -> Vec<TagCount> {
+    let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for task in tasks {
+        for tag in &task.tags {
+            let entry = counts.entry(tag.clone()).or_default();
+            entry.1 += 1;
+            if !task.done {
+                entry.0 += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(tag, (open, total))| TagCount { tag, open, total })
+        .collect()
+}
+
+/// Flip the checkbox on `line` (1-based) of `content`. `expected_text` guards
+/// against toggling a line that changed since the task list was loaded.
+fn toggle_line(
+    content: &str,
+    line: usize,
+    expected_text: Option<&str>,
+) -> Result<(String, bool), String> {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    let target = line
+        .checked_sub(1)
+        .and_then(|i| lines.get(i).copied())
+        .ok_or_else(|| format!("Line {line} is past the end of the note"))?;
+    let (done, text) = parse_task_item(target.trim_end_matches('\r'))
+        .ok_or_else(|| format!("Line {line} is not a task"))?;
+    if expected_text.is_some_and(|expected| task_title(text) != expected) {
+        return Err("The task changed on disk; reload the task list and try again".to_string());
+    }
+    let (from, to) = if done { ("x]", " ]") } else { (" ]", "x]") };
+    let checkbox = target
+        .find(if done { "[x]" } else { "[ ]" })
+        .or_else(|| target.find("[X]"))
+        .ok_or_else(|| format!("Line {line} is not a task"))?;
+    let toggled = format!(
+        "{}[{}{}",
+        &target[..checkbox],
+        &to,
+        &target[checkbox + 1 + from.len()..]
+    );
+    lines[line - 1] = &toggled;
+    Ok((lines.join("\n"), !done))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Tasks in the workspace matching `query` (default: all open tasks).
+#[tauri::command]
+pub async fn query_tasks(root_path: String, query: Option<TaskQuery>) -> Result<Vec<Task>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let query = query.unwrap_or_default();
+        with_index(&root_path, |index| run_query(index.tasks(), &query))
+    })
+    .await
+    .map_err(|e| format!("Failed to query tasks: {e}"))?
+}
+
+/// Tags used on tasks, with open and total counts.
+#[tauri::command]
+pub async fn list_task_tags(root_path: String) -> Result<Vec<TagCount>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_index(&root_path, |index| tag_counts(index.tasks()))
+    })
+    .await
+    .map_err(|e| format!("Failed to list task tags: {e}"))?
+}
+
+/// Rebuild a workspace's task index from scratch (e.g. after settings
+/// changes to excluded folders).
+#[tauri::command]
+pub fn rebuild_task_index(root_path: String) -> Result<(), String> {
+    if let Some(indexes) = INDEXES
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .as_mut()
+    {
+        indexes.remove(&root_path);
+    }
+    Ok(())
+}
+
+/// Check or uncheck the task on `line` (1-based) of `path`. Returns the task
+/// as it is now.
+#[tauri::command]
+pub fn toggle_task(
+    root_path: String,
+    path: String,
+    line: usize,
+    expected_text: Option<String>,
+) -> Result<Task, String> {
+    let root = Path::new(&root_path);
+    let file = root.join(&path);
+    let content = fs::read_to_string(&file).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let (updated, _) = toggle_line(&content, line, expected_text.as_deref())?;
+    crate::app_paths::write_document_file(&file, updated.as_bytes())?;
+
+    let tasks = parse_tasks(&relative_path(root, &file), &updated);
+    if let Ok(mut guard) = INDEXES.lock() {
+        if let Some(index) = guard.as_mut().and_then(|m| m.get_mut(&root_path)) {
+            index.update_file(&file);
+        }
+    }
+    tasks
+        .into_iter()
+        .find(|t| t.line == line)
+        .ok_or_else(|| format!("Line {line} is not a task"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tasks() {
+        let content = "- [ ] Write intro #draft #Book/ch1 📅 2026-05-01\n\
+            - [x] Outline #draft\n\
+            - [ ] Fix #12\n\
+            ```\n- [ ] not a task\n```\n";
+        let tasks = parse_tasks("book.md", content);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].text, "Write intro #draft #Book/ch1");
+        assert_eq!(tasks[0].tags, vec!["draft", "book/ch1"]);
+        assert_eq!(tasks[0].due.as_deref(), Some("2026-05-01"));
+        assert!(tasks[1].done);
+        assert!(tasks[2].tags.is_empty());
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let tasks = parse_tasks(
+            "work/plan.md",
+            "- [ ] A #work 📅 2026-05-03\n- [ ] B #work/admin 📅 2026-05-01\n- [ ] C\n- [x] D #work",
+        );
+        let query = |q: TaskQuery| {
+            run_query(tasks.iter(), &q)
+                .into_iter()
+                .map(|t| t.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            query(TaskQuery::default()),
+            ["B #work/admin", "A #work", "C"]
+        );
+        assert_eq!(
+            query(TaskQuery {
+                tag: Some("#work".into()),
+                status: TaskStatusFilter::All,
+                ..Default::default()
+            }),
+            ["B #work/admin", "A #work", "D #work"]
+        );
+        assert_eq!(
+            query(TaskQuery {
+                due_before: Some("2026-05-02".into()),
+                ..Default::default()
+            }),
+            ["B #work/admin"]
+        );
+        assert_eq!(
+            query(TaskQuery {
+                has_due: Some(false),
+                path: Some("work".into()),
+                ..Default::default()
+            }),
+            ["C"]
+        );
+        assert!(query(TaskQuery {
+            path: Some("wor".into()),
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_tag_counts() {
+        let tasks = parse_tasks("a.md", "- [ ] A #x\n- [x] B #x #y");
+        assert_eq!(
+            tag_counts(tasks.iter()),
+            vec![
+                TagCount {
+                    tag: "x".into(),
+                    open: 1,
+                    total: 2
+                },
+                TagCount {
+                    tag: "y".into(),
+                    open: 0,
+                    total: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toggle_line() {
+        let content = "# Plan\r\n- [ ] Call [Sam](x.md)\r\n  * [X] Done\r\n";
+        let (updated, done) = toggle_line(content, 2, Some("Call [Sam](x.md)")).unwrap();
+        assert!(done);
+        assert_eq!(
+            updated,
+            "# Plan\r\n- [x] Call [Sam](x.md)\r\n  * [X] Done\r\n"
+        );
+        let (updated, done) = toggle_line(&updated, 3, None).unwrap();
+        assert!(!done);
+        assert_eq!(
+            updated,
+            "# Plan\r\n- [x] Call [Sam](x.md)\r\n  * [ ] Done\r\n"
+        );
+
+        assert!(toggle_line(content, 1, None).is_err());
+        assert!(toggle_line(content, 9, None).is_err());
+        assert!(toggle_line(content, 2, Some("Call Sam"))
+            .unwrap_err()
+            .contains("changed"));
+    }
+
+    #[test]
+    fn test_index_follows_fs_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().into_owned();
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        fs::write(dir.path().join("notes/a.md"), "- [ ] One\n").unwrap();
+
+        let open = |root: &str| {
+            with_index(root, |i| run_query(i.tasks(), &TaskQuery::default()).len()).unwrap()
+        };
+        assert_eq!(open(&root), 1);
+
+        let event = |kind: &str, path: PathBuf| FsChangeEvent {
+            watch_id: "main".into(),
+            root_path: root.clone(),
+            paths: vec![path.to_string_lossy().into_owned()],
+            kind: kind.into(),
+            from: None,
+            to: None,
+            digests: HashMap::new(),
+        };
+        let b = dir.path().join("notes/b.md");
+        fs::write(&b, "- [ ] Two\n- [ ] Three\n").unwrap();
+        apply_fs_change(&event("create", b.clone()));
+        assert_eq!(open(&root), 3);
+
+        let task = toggle_task(root.clone(), "notes/b.md".into(), 1, Some("Two".into())).unwrap();
+        assert!(task.done);
+        assert_eq!(open(&root), 2);
+
+        fs::remove_dir_all(dir.path().join("notes")).unwrap();
+        apply_fs_change(&event("remove", dir.path().join("notes")));
+        assert_eq!(open(&root), 0);
+        rebuild_task_index(root).unwrap();
+    }
+}
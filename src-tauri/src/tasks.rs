@@ -0,0 +1,394 @@
+//! Workspace Tasks
+//!
+//! Runs named one-shot commands from the workspace config (`vmark.tasks`,
+//! e.g. `"preview": "hugo server"`) in a managed PTY owned by the backend.
+//!
+//! Output is streamed as `task:output` and mirrored into the terminal
+//! scrollback store under the task id, so it can be fetched later with
+//! `pty_get_scrollback`. Completion is emitted as `task:exit`.
+//! Tasks are execution: the workspace must be trusted.
+
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::terminal::{self, ShellInfo, ShellKind};
+
+/// Grace period before a stopped task is killed
+const STOP_GRACE: Duration = Duration::from_millis(3000);
+
+/// Lifecycle state of a task
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Running,
+    Exited,
+}
+
+/// A started task (running or finished)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    /// Unique id; also the scrollback key for its output
+    pub id: String,
+    pub workspace_root: String,
+    /// Task name from the workspace config
+    pub name: String,
+    pub command: String,
+    pub pid: Option<u32>,
+    /// Unix timestamp ms
+    pub started_at: i64,
+    pub status: TaskStatus,
+    pub exit_code: Option<u32>,
+}
+
+/// Payload for `task:output`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOutput {
+    pub task_id: String,
+    pub data: String,
+}
+
+struct TaskEntry {
+    info: TaskInfo,
+    killer: Option<Box<dyn ChildKiller + Send + Sync>>,
+    /// Kept alive so the PTY stays open while the task runs
+    _master: Option<Box<dyn MasterPty + Send>>,
+}
+
+/// Tasks keyed by task id
+static TASKS: Mutex<Option<HashMap<String, TaskEntry>>> = Mutex::new(None);
+
+/// Arguments that make `shell` run `command` once and exit.
+fn one_shot_args(shell: &ShellInfo, command: &str) -> Vec<String> {
+    let flag = match shell.kind {
+        ShellKind::Cmd => "/C",
+        ShellKind::Pwsh | ShellKind::PowerShell => "-Command",
+        ShellKind::GitBash | ShellKind::Posix => "-c",
+    };
+    let mut args = match shell.kind {
+        ShellKind::Pwsh | ShellKind::PowerShell => vec!["-NoLogo".to_string()],
+        _ => Vec::new(),
+    };
+    args.push(flag.to_string());
+    args.push(command.to_string());
+    args
+}
+
+/// Look up a task command in the workspace config.
+fn configured_command(workspace_root: &str, name: &str) -> Result<String, String> {
+    let config = crate::workspace::read_workspace_config(workspace_root)?
+        .ok_or_else(|| format!("No workspace config in {workspace_root}"))?;
+    config
+        .tasks
+        .get(name)
+        .filter(|c| !c.trim().is_empty())
+        .cloned()
+        .ok_or_else(|| format!("Task not defined: {name}"))
+}
+
+fn update_task(task_id: &str, f: impl FnOnce(&mut TaskEntry)) {
+    if let Ok(mut guard) = TASKS.lock() {
+        if let Some(entry) = guard.as_mut().and_then(|m| m.get_mut(task_id)) {
+            f(entry);
+        }
+    }
+}
+
+/// Claim the running slot for a task. The already-running check and the
+/// insert happen under one lock, so concurrent starts can't both pass.
+fn reserve_task(info: &TaskInfo) -> Result<(), String> {
+    let mut guard = TASKS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let map = guard.get_or_insert_with(HashMap::new);
+    let running = map.values().any(|t| {
+        t.info.status == TaskStatus::Running
+            && t.info.workspace_root == info.workspace_root
+            && t.info.name == info.name
+    });
+    if running {
+        return Err(format!("Task already running: {}", info.name));
+    }
+    map.insert(
+        info.id.clone(),
+        TaskEntry {
+            info: info.clone(),
+            killer: None,
+            _master: None,
+        },
+    );
+    Ok(())
+}
+
+/// Start a configured task in a new PTY. Only one instance of a task name
+/// may run per workspace.
+#[tauri::command]
+pub async fn start_task(
+    app: AppHandle,
+    workspace_root: String,
+    name: String,
+) -> Result<TaskInfo, String> {
+    // Reads the config and resolves the login shell PATH: keep it off the main thread
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::workspace_trust::require_trusted(Path::new(&workspace_root))?;
+        let command = configured_command(&workspace_root, &name)?;
+        let mut info = TaskInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            workspace_root,
+            name,
+            command,
+            pid: None,
+            started_at: chrono::Utc::now().timestamp_millis(),
+            status: TaskStatus::Running,
+            exit_code: None,
+        };
+        reserve_task(&info)?;
+        if let Err(e) = spawn_task(app, &mut info) {
+            if let Ok(mut guard) = TASKS.lock() {
+                if let Some(map) = guard.as_mut() {
+                    map.remove(&info.id);
+                }
+            }
+            return Err(e);
+        }
+        Ok(info)
+    })
+    .await
+    .map_err(|e| format!("Failed to start task: {e}"))?
+}
+
+/// Run a reserved task's command and attach its PTY to the task entry.
+fn spawn_task(app: AppHandle, info: &mut TaskInfo) -> Result<(), String> {
+    let pair = native_pty_system()
+        .openpty(PtySize {
+            rows: 24,
+            cols: 120,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Failed to open PTY: {e}"))?;
+
+    let shell = terminal::default_shell();
+    let mut cmd = CommandBuilder::new(&shell.path);
+    cmd.args(one_shot_args(&shell, &info.command));
+    cmd.cwd(&info.workspace_root);
+    let env = terminal::build_terminal_env(
+        &crate::ai_provider::login_shell_path(),
+        Some(&info.workspace_root),
+        &HashMap::new(),
+    );
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+
+    let mut child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| format!("Failed to start task: {e}"))?;
+    // The child holds its own copy; ours would keep the PTY from reporting EOF
+    drop(pair.slave);
+
+    let mut reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to read task output: {e}"))?;
+
+    info.pid = child.process_id();
+    let (pid, killer) = (info.pid, child.clone_killer());
+    update_task(&info.id, |entry| {
+        entry.info.pid = pid;
+        entry.killer = Some(killer);
+        entry._master = Some(pair.master);
+    });
+
+    // Output: stream to the frontend and keep it in scrollback
+    let output_app = app.clone();
+    let output_id = info.id.clone();
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        let mut carry = Vec::new();
+        while let Ok(n) = reader.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            carry.extend_from_slice(&buf[..n]);
+            let data = terminal::take_utf8(&mut carry);
+            if data.is_empty() {
+                continue;
+            }
+            let _ = terminal::append_scrollback(&output_id, &data);
+            let _ = output_app.emit(
+                "task:output",
+                TaskOutput {
+                    task_id: output_id.clone(),
+                    data,
+                },
+            );
+        }
+    });
+
+    // Completion: record exit code, release the PTY, notify
+    let exit_id = info.id.clone();
+    std::thread::spawn(move || {
+        let exit_code = child.wait().ok().map(|status| status.exit_code());
+        let mut finished = None;
+        update_task(&exit_id, |entry| {
+            entry.info.status = TaskStatus::Exited;
+            entry.info.exit_code = exit_code;
+            entry.killer = None;
+            entry._master = None;
+            finished = Some(entry.info.clone());
+        });
+        if let Some(info) = finished {
+            let _ = app.emit("task:exit", info);
+        }
+    });
+
+    Ok(())
+}
+
+/// Whether a task has exited (or was forgotten).
+fn task_exited(task_id: &str) -> bool {
+    TASKS.lock().map_or(true, |guard| {
+        guard
+            .as_ref()
+            .and_then(|m| m.get(task_id))
+            .is_none_or(|t| t.info.status == TaskStatus::Exited)
+    })
+}
+
+/// Stop a running task: terminate its process group, escalating to a kill
+/// after a grace period (immediately when `force`).
+#[tauri::command]
+pub async fn stop_task(task_id: String, force: Option<bool>) -> Result<(), String> {
+    let (pid, killer) = {
+        let mut guard = TASKS.lock().map_err(|e| format!("Lock error: {e}"))?;
+        let entry = guard
+            .as_mut()
+            .and_then(|m| m.get_mut(&task_id))
+            .ok_or_else(|| format!("Unknown task: {task_id}"))?;
+        if entry.info.status != TaskStatus::Running {
+            return Ok(());
+        }
+        (entry.info.pid, entry.killer.as_ref().map(|k| k.clone_killer()))
+    };
+
+    let force = force.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        // The exit thread marks the task exited once its child handle's wait returns
+        let exited = |timeout| terminal::poll_until(timeout, || task_exited(&task_id));
+        let stopped = pid.is_some_and(|pid| {
+            terminal::terminate_process_tree(pid, force, STOP_GRACE, exited).exited
+        });
+        // Fallback for platforms/processes the group signal didn't reach
+        if !stopped {
+            if let Some(mut killer) = killer {
+                let _ = killer.kill();
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Stop failed: {e}"))
+}
+
+/// List tasks, optionally only those of one workspace. Newest first.
+#[tauri::command]
+pub fn list_tasks(workspace_root: Option<String>) -> Result<Vec<TaskInfo>, String> {
+    let guard = TASKS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let mut tasks: Vec<TaskInfo> = guard
+        .as_ref()
+        .map(|m| {
+            m.values()
+                .filter(|t| workspace_root.as_ref().is_none_or(|r| &t.info.workspace_root == r))
+                .map(|t| t.info.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    tasks.sort_by_key(|t| std::cmp::Reverse(t.started_at));
+    Ok(tasks)
+}
+
+/// Forget finished tasks and their output.
+#[tauri::command]
+pub fn clear_finished_tasks() -> Result<(), String> {
+    let mut guard = TASKS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    if let Some(map) = guard.as_mut() {
+        let finished: Vec<String> = map
+            .values()
+            .filter(|t| t.info.status == TaskStatus::Exited)
+            .map(|t| t.info.id.clone())
+            .collect();
+        for id in finished {
+            map.remove(&id);
+            let _ = terminal::pty_clear_scrollback(id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell(kind: ShellKind) -> ShellInfo {
+        ShellInfo {
+            name: String::new(),
+            path: String::new(),
+            kind,
+            args: vec![],
+        }
+    }
+
+    #[test]
+    fn test_one_shot_args() {
+        assert_eq!(one_shot_args(&shell(ShellKind::Posix), "hugo server"), vec!["-c", "hugo server"]);
+        assert_eq!(one_shot_args(&shell(ShellKind::Cmd), "dir"), vec!["/C", "dir"]);
+        assert_eq!(
+            one_shot_args(&shell(ShellKind::Pwsh), "ls"),
+            vec!["-NoLogo", "-Command", "ls"]
+        );
+    }
+
+    #[test]
+    fn test_reserve_task_allows_one_running_instance() {
+        let info = |id: &str| TaskInfo {
+            id: id.into(),
+            workspace_root: "/reserve-test".into(),
+            name: "preview".into(),
+            command: "hugo server".into(),
+            pid: None,
+            started_at: 0,
+            status: TaskStatus::Running,
+            exit_code: None,
+        };
+        reserve_task(&info("r-1")).unwrap();
+        assert!(reserve_task(&info("r-2")).is_err());
+
+        update_task("r-1", |entry| entry.info.status = TaskStatus::Exited);
+        reserve_task(&info("r-3")).unwrap();
+
+        let mut guard = TASKS.lock().unwrap();
+        let map = guard.as_mut().unwrap();
+        map.remove("r-1");
+        map.remove("r-3");
+    }
+
+    #[test]
+    fn test_configured_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_string_lossy().to_string();
+        let mut config = crate::workspace::WorkspaceConfig::default();
+        config.tasks.insert("preview".into(), "hugo server".into());
+        config.tasks.insert("empty".into(), "  ".into());
+        crate::workspace::write_workspace_config(&root, config).unwrap();
+
+        assert_eq!(configured_command(&root, "preview").unwrap(), "hugo server");
+        assert!(configured_command(&root, "empty").is_err());
+        assert!(configured_command(&root, "missing").is_err());
+    }
+}
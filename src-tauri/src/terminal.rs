@@ -0,0 +1,1377 @@
+//! Integrated terminal support
+//!
+//! PTYs are opened here through `portable-pty` (ConPTY on Windows, openpty
+//! elsewhere). This module also decides *what* to run in them: which shell,
+//! with which arguments.
+//!
+//! Windows preference order: PowerShell 7 (`pwsh`), Windows PowerShell,
+//! `cmd.exe` (`%COMSPEC%`). Git Bash is offered when Git for Windows is
+//! installed (located via the registry, then `%ProgramFiles%`).
+//! macOS/Linux: `$SHELL`, then the first of zsh/bash/sh that exists.
+//!
+//! It also builds the session environment. Apps launched from Finder get a
+//! minimal PATH, so the login shell's PATH is injected for every session.
+//!
+//! The PTY reader thread streams output to the window as `pty:data` and
+//! keeps it in a capped per-session scrollback buffer, so the terminal can be
+//! re-rendered after a webview reload or hot-exit restore.
+//!
+//! Each session's pid and exit are recorded here, so the backend can emit a
+//! detailed `pty:exit`, answer `pty_wait` and terminate the whole process
+//! group on close instead of orphaning children.
+
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Maximum lines kept per session scrollback
+const MAX_SCROLLBACK_LINES: usize = 5000;
+
+/// Maximum bytes kept per session scrollback (long lines without newlines)
+const MAX_SCROLLBACK_BYTES: usize = 2 * 1024 * 1024;
+
+/// Scrollback lines saved per terminal in hot-exit sessions
+const PERSISTED_SCROLLBACK_LINES: usize = 1000;
+
+/// Default time a session gets to exit after SIGHUP/SIGTERM before SIGKILL
+const DEFAULT_KILL_GRACE: Duration = Duration::from_millis(2000);
+
+/// Shell family, used by the frontend for quoting and startup commands
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShellKind {
+    Pwsh,
+    PowerShell,
+    Cmd,
+    GitBash,
+    Posix,
+}
+
+/// A shell the terminal can launch
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ShellInfo {
+    /// Display name (e.g. "PowerShell 7")
+    pub name: String,
+    /// Absolute path to the executable
+    pub path: String,
+    pub kind: ShellKind,
+    /// Arguments to start an interactive session
+    pub args: Vec<String>,
+}
+
+impl ShellInfo {
+    fn new(name: &str, path: &Path, kind: ShellKind, args: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            kind,
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+// ============================================================================
+// Windows
+// ============================================================================
+
+/// Candidate Windows shells in preference order (not yet checked for existence).
+///
+/// `env` looks up environment variables; `git_install` is the Git for Windows
+/// install directory from the registry, if any.
+fn windows_shell_candidates(
+    env: &dyn Fn(&str) -> Option<String>,
+    git_install: Option<PathBuf>,
+) -> Vec<ShellInfo> {
+    let mut candidates = Vec::new();
+    let program_files = env("ProgramFiles").map(PathBuf::from);
+    let system_root = env("SystemRoot")
+        .or_else(|| env("windir"))
+        .map(PathBuf::from);
+
+    if let Some(pf) = &program_files {
+        let pwsh = pf.join("PowerShell").join("7").join("pwsh.exe");
+        candidates.push(ShellInfo::new("PowerShell 7", &pwsh, ShellKind::Pwsh, &["-NoLogo"]));
+    }
+    if let Some(root) = &system_root {
+        let powershell = root
+            .join("System32")
+            .join("WindowsPowerShell")
+            .join("v1.0")
+            .join("powershell.exe");
+        candidates.push(ShellInfo::new(
+            "Windows PowerShell",
+            &powershell,
+            ShellKind::PowerShell,
+            &["-NoLogo"],
+        ));
+    }
+
+    let cmd = env("COMSPEC")
+        .map(PathBuf::from)
+        .or_else(|| system_root.as_ref().map(|r| r.join("System32").join("cmd.exe")));
+    if let Some(cmd) = cmd {
+        candidates.push(ShellInfo::new("Command Prompt", &cmd, ShellKind::Cmd, &[]));
+    }
+
+    let git_roots = git_install
+        .into_iter()
+        .chain(program_files.iter().map(|pf| pf.join("Git")));
+    for root in git_roots {
+        let bash = root.join("bin").join("bash.exe");
+        candidates.push(ShellInfo::new("Git Bash", &bash, ShellKind::GitBash, &["--login", "-i"]));
+    }
+
+    candidates
+}
+
+/// Extract a REG_SZ value from `reg query` output.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_reg_value(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != name {
+            return None;
+        }
+        if !parts.next()?.starts_with("REG_") {
+            return None;
+        }
+        // Value may contain spaces ("C:\Program Files\Git")
+        let value = parts.collect::<Vec<_>>().join(" ");
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+/// Git for Windows install directory from the registry.
+#[cfg(target_os = "windows")]
+fn git_install_dir() -> Option<PathBuf> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    ["HKLM\\SOFTWARE\\GitForWindows", "HKCU\\SOFTWARE\\GitForWindows"]
+        .iter()
+        .find_map(|key| {
+            let output = std::process::Command::new("reg")
+                .args(["query", key, "/v", "InstallPath"])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .ok()
+                .filter(|o| o.status.success())?;
+            parse_reg_value(&String::from_utf8_lossy(&output.stdout), "InstallPath")
+                .map(PathBuf::from)
+        })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn git_install_dir() -> Option<PathBuf> {
+    None
+}
+
+// ============================================================================
+// macOS / Linux
+// ============================================================================
+
+/// Candidate POSIX shells: `$SHELL` first, then common fallbacks and `/etc/shells`.
+fn posix_shell_candidates(shell_env: Option<String>, etc_shells: &str) -> Vec<ShellInfo> {
+    let listed = etc_shells
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from);
+    let fallbacks = ["/bin/zsh", "/bin/bash", "/bin/sh"].map(String::from);
+
+    let mut seen = Vec::new();
+    shell_env
+        .into_iter()
+        .chain(fallbacks)
+        .chain(listed)
+        .filter(|path| {
+            if seen.contains(path) {
+                return false;
+            }
+            seen.push(path.clone());
+            true
+        })
+        .map(|path| {
+            let path = PathBuf::from(path);
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            ShellInfo::new(&name, &path, ShellKind::Posix, &[])
+        })
+        .collect()
+}
+
+// ============================================================================
+// Resolution
+// ============================================================================
+
+/// All shells installed on this machine, in preference order.
+fn available_shells() -> Vec<ShellInfo> {
+    let candidates = if cfg!(target_os = "windows") {
+        windows_shell_candidates(&|name| std::env::var(name).ok(), git_install_dir())
+    } else {
+        posix_shell_candidates(
+            std::env::var("SHELL").ok(),
+            &std::fs::read_to_string("/etc/shells").unwrap_or_default(),
+        )
+    };
+
+    candidates
+        .into_iter()
+        .filter(|shell| Path::new(&shell.path).is_file())
+        .collect()
+}
+
+/// The shell new terminals should use.
+pub(crate) fn default_shell() -> ShellInfo {
+    available_shells().into_iter().next().unwrap_or_else(|| {
+        if cfg!(target_os = "windows") {
+            ShellInfo::new("Command Prompt", Path::new("cmd.exe"), ShellKind::Cmd, &[])
+        } else {
+            ShellInfo::new("sh", Path::new("/bin/sh"), ShellKind::Posix, &[])
+        }
+    })
+}
+
+/// Return the path of the user's default shell.
+#[tauri::command]
+pub fn get_default_shell() -> String {
+    default_shell().path
+}
+
+/// Return the default shell with its kind and startup arguments.
+#[tauri::command]
+pub fn get_default_shell_info() -> ShellInfo {
+    default_shell()
+}
+
+/// List installed shells the terminal can launch, default first.
+#[tauri::command]
+pub fn list_available_shells() -> Vec<ShellInfo> {
+    available_shells()
+}
+
+// ============================================================================
+// Environment
+// ============================================================================
+
+/// Build the environment for a new terminal session.
+///
+/// Base variables come first, then user `extra` variables override them.
+/// `$PATH` / `${PATH}` inside extra values expand to the login shell PATH,
+/// so `PATH=/opt/venv/bin:$PATH` prepends as expected.
+pub(crate) fn build_terminal_env(
+    login_path: &str,
+    workspace_root: Option<&str>,
+    extra: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    env.insert("TERM".to_string(), "xterm-256color".to_string());
+    env.insert("COLORTERM".to_string(), "truecolor".to_string());
+    env.insert("TERM_PROGRAM".to_string(), "vmark".to_string());
+    env.insert("EDITOR".to_string(), "vmark".to_string());
+    if !login_path.is_empty() {
+        env.insert("PATH".to_string(), login_path.to_string());
+    }
+    if let Some(root) = workspace_root.filter(|r| !r.is_empty()) {
+        env.insert("VMARK_WORKSPACE".to_string(), root.to_string());
+    }
+
+    for (key, value) in extra {
+        let key = key.trim();
+        if key.is_empty() || key.contains('=') {
+            continue;
+        }
+        let value = value
+            .replace("${PATH}", login_path)
+            .replace("$PATH", login_path);
+        env.insert(key.to_string(), value);
+    }
+    env
+}
+
+/// Environment variables for a new terminal session: terminal capabilities,
+/// the login shell PATH, the workspace root and user-defined extras.
+/// The first call runs a login shell to read PATH, so it's kept off the main thread.
+#[tauri::command]
+pub async fn get_terminal_env(
+    workspace_root: Option<String>,
+    extra: Option<HashMap<String, String>>,
+) -> Result<HashMap<String, String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        build_terminal_env(
+            &crate::ai_provider::login_shell_path(),
+            workspace_root.as_deref(),
+            &extra.unwrap_or_default(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to build terminal environment: {e}"))
+}
+
+// ============================================================================
+// Scrollback
+// ============================================================================
+
+/// Capped ring buffer of raw terminal output (escape sequences included).
+#[derive(Default)]
+struct Scrollback {
+    /// Complete lines, each ending with its '\n'
+    lines: VecDeque<String>,
+    /// Output after the last newline
+    partial: String,
+    bytes: usize,
+}
+
+impl Scrollback {
+    fn push(&mut self, data: &str) {
+        let mut rest = data;
+        while let Some(pos) = rest.find('\n') {
+            self.partial.push_str(&rest[..=pos]);
+            let line = std::mem::take(&mut self.partial);
+            self.bytes += line.len();
+            self.lines.push_back(line);
+            rest = &rest[pos + 1..];
+        }
+        self.partial.push_str(rest);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.lines.len() > MAX_SCROLLBACK_LINES
+            || (self.bytes + self.partial.len() > MAX_SCROLLBACK_BYTES && !self.lines.is_empty())
+        {
+            if let Some(line) = self.lines.pop_front() {
+                self.bytes -= line.len();
+            }
+        }
+        // A single runaway line: keep its tail
+        if self.partial.len() > MAX_SCROLLBACK_BYTES {
+            let mut cut = self.partial.len() - MAX_SCROLLBACK_BYTES;
+            while !self.partial.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.partial.drain(..cut);
+        }
+    }
+
+    /// The last `lines` lines (all when None), plus any partial line.
+    fn tail(&self, lines: Option<usize>) -> String {
+        let skip = lines.map_or(0, |n| self.lines.len().saturating_sub(n));
+        let mut out = String::with_capacity(self.bytes + self.partial.len());
+        for line in self.lines.iter().skip(skip) {
+            out.push_str(line);
+        }
+        out.push_str(&self.partial);
+        out
+    }
+}
+
+/// Scrollback keyed by terminal session id
+static SCROLLBACK: Mutex<Option<HashMap<String, Scrollback>>> = Mutex::new(None);
+
+/// Read a session's scrollback (None if the session has none).
+pub(crate) fn scrollback_snapshot(session_id: &str, lines: Option<usize>) -> Option<String> {
+    let guard = SCROLLBACK.lock().ok()?;
+    guard.as_ref()?.get(session_id).map(|s| s.tail(lines))
+}
+
+/// Append output to a scrollback buffer (terminal sessions and tasks).
+pub(crate) fn append_scrollback(session_id: &str, data: &str) -> Result<(), String> {
+    let mut guard = SCROLLBACK.lock().map_err(|e| format!("Lock error: {e}"))?;
+    guard
+        .get_or_insert_with(HashMap::new)
+        .entry(session_id.to_string())
+        .or_default()
+        .push(data);
+    Ok(())
+}
+
+/// Return the last `lines` lines of a session's output (all retained output
+/// when omitted). Empty if the session has no scrollback.
+#[tauri::command]
+pub fn pty_get_scrollback(session_id: String, lines: Option<usize>) -> String {
+    scrollback_snapshot(&session_id, lines).unwrap_or_default()
+}
+
+/// Drop a session's scrollback (session closed).
+#[tauri::command]
+pub fn pty_clear_scrollback(session_id: String) -> Result<(), String> {
+    let mut guard = SCROLLBACK.lock().map_err(|e| format!("Lock error: {e}"))?;
+    if let Some(map) = guard.as_mut() {
+        map.remove(&session_id);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Session lifecycle
+// ============================================================================
+
+/// How a terminal session's process ended (payload of `pty:exit`)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyExit {
+    pub session_id: String,
+    /// The PTY that exited (see `pty_spawn`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pty_id: Option<String>,
+    pub exit_code: i32,
+    /// Terminating signal name (e.g. "SIGINT"), if the process was signalled
+    pub signal: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    pid: Option<u32>,
+    /// Directory the shell was started in
+    cwd: Option<String>,
+    shell_path: Option<String>,
+    shell_kind: Option<String>,
+    /// The session's current PTY; exits of replaced PTYs are ignored
+    pty_id: Option<String>,
+    exit: Option<PtyExit>,
+}
+
+/// Session state keyed by terminal session id
+static SESSIONS: Mutex<Option<HashMap<String, SessionState>>> = Mutex::new(None);
+
+/// Signalled whenever a session exit is recorded
+static SESSION_EXITED: Condvar = Condvar::new();
+
+/// Map a POSIX signal number to its name.
+fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        4 => "SIGILL",
+        6 => "SIGABRT",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        14 => "SIGALRM",
+        15 => "SIGTERM",
+        _ => return None,
+    })
+}
+
+/// Resolve the terminating signal for an exit.
+///
+/// An explicit signal number wins. Otherwise POSIX shells report a
+/// signalled process as 128 + N, which is decoded here; Windows exit codes
+/// carry no signal information.
+fn resolve_exit_signal(exit_code: i32, signal: Option<i32>) -> Option<String> {
+    if let Some(sig) = signal.filter(|s| *s > 0) {
+        return Some(signal_name(sig).map_or_else(|| format!("SIG{sig}"), String::from));
+    }
+    if cfg!(target_os = "windows") || !(129..=192).contains(&exit_code) {
+        return None;
+    }
+    signal_name(exit_code - 128).map(String::from)
+}
+
+/// Signal number for the description portable-pty reports: `strsignal`
+/// text, or "Signal N" when the platform has none.
+#[cfg(unix)]
+fn signal_number(description: &str) -> Option<i32> {
+    if let Some(n) = description.strip_prefix("Signal ").and_then(|n| n.parse().ok()) {
+        return Some(n);
+    }
+    (1..65).find(|&n| {
+        // SAFETY: strsignal returns a static (or thread-local) C string or null
+        let name = unsafe { libc::strsignal(n) };
+        !name.is_null() && unsafe { std::ffi::CStr::from_ptr(name) }.to_string_lossy() == description
+    })
+}
+
+#[cfg(not(unix))]
+fn signal_number(_description: &str) -> Option<i32> {
+    None
+}
+
+/// Exit code and signal number of a finished shell. A signalled shell gets
+/// the conventional 128 + N code.
+fn exit_details(status: &portable_pty::ExitStatus) -> (i32, Option<i32>) {
+    match status.signal().and_then(signal_number) {
+        Some(signal) => (128 + signal, Some(signal)),
+        None => (status.exit_code() as i32, None),
+    }
+}
+
+/// A session's shell pid, current PTY and whether its exit is recorded.
+fn session_process(session_id: &str) -> Option<(u32, Option<String>, bool)> {
+    let guard = SESSIONS.lock().ok()?;
+    let state = guard.as_ref()?.get(session_id)?;
+    Some((state.pid?, state.pty_id.clone(), state.exit.is_some()))
+}
+
+/// Block until a session exits or the timeout elapses.
+fn wait_for_exit(session_id: &str, timeout: Option<Duration>) -> Result<PtyExit, String> {
+    let deadline = timeout.map(|t| std::time::Instant::now() + t);
+    let mut guard = SESSIONS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    loop {
+        match guard.as_ref().and_then(|m| m.get(session_id)) {
+            None => return Err(format!("Unknown terminal session: {session_id}")),
+            Some(SessionState { exit: Some(exit), .. }) => return Ok(exit.clone()),
+            Some(_) => {}
+        }
+        guard = match deadline {
+            Some(deadline) => {
+                let now = std::time::Instant::now();
+                if now >= deadline {
+                    return Err(format!("Timed out waiting for terminal session: {session_id}"));
+                }
+                SESSION_EXITED
+                    .wait_timeout(guard, deadline - now)
+                    .map_err(|e| format!("Lock error: {e}"))?
+                    .0
+            }
+            None => SESSION_EXITED
+                .wait(guard)
+                .map_err(|e| format!("Lock error: {e}"))?,
+        };
+    }
+}
+
+/// Current working directory of a running process (best effort).
+#[cfg(target_os = "linux")]
+fn process_cwd(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{pid}/cwd"))
+        .ok()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn process_cwd(pid: u32) -> Option<String> {
+    // `-Fn` prints one field per line; the cwd is the line prefixed with 'n'
+    let output = std::process::Command::new("lsof")
+        .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix('n').map(str::to_string))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn process_cwd(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Recent output to save with hot exit, with API keys and tokens masked.
+fn persisted_scrollback(session_id: &str) -> Option<String> {
+    let output = scrollback_snapshot(session_id, Some(PERSISTED_SCROLLBACK_LINES))?;
+    let secrets = crate::settings::PromptRedactionSettings {
+        enabled: true,
+        entities: vec!["apiKey".into()],
+        ..Default::default()
+    };
+    // Without the scrubber, nothing is written
+    let redactor = crate::ai_redaction::Redactor::from_settings(&secrets).ok()??;
+    Some(redactor.redact(&output).text)
+}
+
+/// Fill a hot-exit terminal record with the session's cwd and shell, plus its
+/// recent output when `keep_scrollback` (Settings → Terminal).
+/// A still-running shell reports its live cwd; otherwise the spawn cwd is used.
+pub(crate) fn fill_session_state(
+    terminal: &mut crate::hot_exit::session::TerminalSessionState,
+    keep_scrollback: bool,
+) {
+    let state = SESSIONS.lock().ok().and_then(|guard| {
+        guard.as_ref()?.get(&terminal.session_id).map(|s| {
+            let live_pid = if s.exit.is_none() { s.pid } else { None };
+            (live_pid, s.cwd.clone(), s.shell_path.clone(), s.shell_kind.clone())
+        })
+    });
+    if let Some((live_pid, spawn_cwd, shell_path, shell_kind)) = state {
+        terminal.cwd = live_pid.and_then(process_cwd).or(spawn_cwd);
+        terminal.shell_path = shell_path;
+        terminal.shell_kind = shell_kind;
+    }
+    if !keep_scrollback {
+        return;
+    }
+    if let Some(scrollback) = persisted_scrollback(&terminal.session_id) {
+        terminal.scrollback = scrollback;
+    }
+}
+
+/// Replace a session's scrollback with restored output (hot-exit restore).
+pub(crate) fn seed_scrollback(session_id: &str, data: &str) {
+    let _ = pty_clear_scrollback(session_id.to_string());
+    if !data.is_empty() {
+        let _ = append_scrollback(session_id, data);
+    }
+}
+
+/// Record that a session's shell started (clears any previous exit).
+/// Cwd and shell are kept so the session can be recreated after hot exit.
+fn record_spawn(session_id: &str, state: SessionState) -> Result<(), String> {
+    let mut guard = SESSIONS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    guard
+        .get_or_insert_with(HashMap::new)
+        .insert(session_id.to_string(), state);
+    Ok(())
+}
+
+/// Record a session exit and wake waiters. Returns None when `pty_id` is no
+/// longer the session's PTY (it was restarted or released).
+fn record_exit(
+    session_id: &str,
+    pty_id: &str,
+    exit_code: i32,
+    signal: Option<i32>,
+) -> Option<PtyExit> {
+    let exit = PtyExit {
+        session_id: session_id.to_string(),
+        pty_id: Some(pty_id.to_string()),
+        exit_code,
+        signal: resolve_exit_signal(exit_code, signal),
+    };
+    {
+        let mut guard = SESSIONS.lock().ok()?;
+        let state = guard.as_mut()?.get_mut(session_id)?;
+        if state.pty_id.as_deref() != Some(pty_id) {
+            return None;
+        }
+        state.exit = Some(exit.clone());
+    }
+    SESSION_EXITED.notify_all();
+    Some(exit)
+}
+
+/// Wait for a session's shell to exit. Returns immediately if it already has.
+/// Errors on unknown sessions or when `timeout_ms` elapses first.
+#[tauri::command]
+pub async fn pty_wait(session_id: String, timeout_ms: Option<u64>) -> Result<PtyExit, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        wait_for_exit(&session_id, timeout_ms.map(Duration::from_millis))
+    })
+    .await
+    .map_err(|e| format!("Wait failed: {e}"))?
+}
+
+/// Forget a closed session: exit state and scrollback.
+#[tauri::command]
+pub fn pty_release_session(session_id: String) -> Result<(), String> {
+    {
+        let mut guard = SESSIONS.lock().map_err(|e| format!("Lock error: {e}"))?;
+        if let Some(map) = guard.as_mut() {
+            map.remove(&session_id);
+        }
+    }
+    // Waiters on a released session get "unknown session"
+    SESSION_EXITED.notify_all();
+    pty_clear_scrollback(session_id)
+}
+
+// ============================================================================
+// PTY sessions
+// ============================================================================
+
+/// How long the exit thread waits for the reader to drain remaining output
+const OUTPUT_DRAIN: Duration = Duration::from_millis(200);
+
+/// What to run in a new PTY (resolved by the frontend from the shell list,
+/// `get_terminal_env` and the session's cwd)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySpawnOptions {
+    pub shell_path: String,
+    #[serde(default)]
+    pub shell_kind: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// A started PTY
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySpawned {
+    pub pty_id: String,
+    pub pid: Option<u32>,
+}
+
+/// PTY output chunk (payload of `pty:data`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyData {
+    pub pty_id: String,
+    pub data: String,
+}
+
+struct PtyHandle {
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    /// Feeds the writer thread, so input is written in order without blocking
+    input: mpsc::Sender<Vec<u8>>,
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+}
+
+/// Open PTYs keyed by pty id
+static PTYS: Mutex<Option<HashMap<String, Arc<PtyHandle>>>> = Mutex::new(None);
+
+fn pty_handle(pty_id: &str) -> Result<Arc<PtyHandle>, String> {
+    let guard = PTYS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    guard
+        .as_ref()
+        .and_then(|m| m.get(pty_id))
+        .cloned()
+        .ok_or_else(|| format!("Unknown PTY: {pty_id}"))
+}
+
+/// Split a byte stream into valid UTF-8, carrying incomplete sequences over.
+pub(crate) fn take_utf8(carry: &mut Vec<u8>) -> String {
+    match std::str::from_utf8(carry) {
+        Ok(text) => {
+            let text = text.to_string();
+            carry.clear();
+            text
+        }
+        Err(e) if e.error_len().is_none() => {
+            // Incomplete sequence at the end: keep it for the next read
+            let valid = e.valid_up_to();
+            let text = String::from_utf8_lossy(&carry[..valid]).to_string();
+            carry.drain(..valid);
+            text
+        }
+        Err(_) => {
+            let text = String::from_utf8_lossy(carry).to_string();
+            carry.clear();
+            text
+        }
+    }
+}
+
+fn write_input(mut writer: Box<dyn Write + Send>, input: mpsc::Receiver<Vec<u8>>) {
+    for data in input {
+        if writer.write_all(&data).and_then(|_| writer.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+fn spawn_session(
+    app: AppHandle,
+    window_label: String,
+    session_id: String,
+    pty_id: String,
+    options: PtySpawnOptions,
+) -> Result<PtySpawned, String> {
+    let pair = native_pty_system()
+        .openpty(PtySize {
+            rows: options.rows.max(1),
+            cols: options.cols.max(1),
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Failed to open PTY: {e}"))?;
+
+    let mut cmd = CommandBuilder::new(&options.shell_path);
+    cmd.args(&options.args);
+    if let Some(cwd) = options.cwd.as_deref().filter(|c| Path::new(c).is_dir()) {
+        cmd.cwd(cwd);
+    }
+    for (key, value) in &options.env {
+        cmd.env(key, value);
+    }
+
+    let mut child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| format!("Failed to start shell: {e}"))?;
+    // The child holds its own copy; ours would keep the PTY from reporting EOF
+    drop(pair.slave);
+
+    let mut reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to read terminal output: {e}"))?;
+    let writer = pair
+        .master
+        .take_writer()
+        .map_err(|e| format!("Failed to open terminal input: {e}"))?;
+    let (input, input_rx) = mpsc::channel();
+    std::thread::spawn(move || write_input(writer, input_rx));
+
+    let pid = child.process_id();
+    let handle = Arc::new(PtyHandle {
+        master: Mutex::new(pair.master),
+        input,
+        killer: Mutex::new(child.clone_killer()),
+    });
+    PTYS.lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .get_or_insert_with(HashMap::new)
+        .insert(pty_id.clone(), handle);
+    record_spawn(
+        &session_id,
+        SessionState {
+            pid,
+            cwd: options.cwd,
+            shell_path: Some(options.shell_path),
+            shell_kind: options.shell_kind,
+            pty_id: Some(pty_id.clone()),
+            exit: None,
+        },
+    )?;
+
+    // Output: keep it in scrollback and stream it to the window
+    let (drained, output_done) = mpsc::channel::<()>();
+    let output_app = app.clone();
+    let output_label = window_label.clone();
+    let output_session = session_id.clone();
+    let output_pty = pty_id.clone();
+    std::thread::spawn(move || {
+        let _drained = drained;
+        let mut buf = [0u8; 8192];
+        let mut carry = Vec::new();
+        while let Ok(n) = reader.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            carry.extend_from_slice(&buf[..n]);
+            let data = take_utf8(&mut carry);
+            if data.is_empty() {
+                continue;
+            }
+            let _ = append_scrollback(&output_session, &data);
+            let _ = output_app.emit_to(
+                output_label.as_str(),
+                "pty:data",
+                PtyData {
+                    pty_id: output_pty.clone(),
+                    data,
+                },
+            );
+        }
+    });
+
+    // Exit: let the reader finish, release the PTY, record and notify
+    let exit_pty = pty_id.clone();
+    std::thread::spawn(move || {
+        let (exit_code, signal) = child.wait().map_or((1, None), |status| exit_details(&status));
+        let _ = output_done.recv_timeout(OUTPUT_DRAIN);
+        if let Ok(mut guard) = PTYS.lock() {
+            if let Some(map) = guard.as_mut() {
+                map.remove(&exit_pty);
+            }
+        }
+        if let Some(exit) = record_exit(&session_id, &exit_pty, exit_code, signal) {
+            let _ = app.emit_to(window_label.as_str(), "pty:exit", &exit);
+        }
+    });
+
+    Ok(PtySpawned { pty_id, pid })
+}
+
+/// Start a shell for a terminal session in a new PTY. `pty_id` is chosen by
+/// the caller so it can listen for `pty:data` before output starts.
+#[tauri::command]
+pub async fn pty_spawn(
+    app: AppHandle,
+    window: tauri::WebviewWindow,
+    session_id: String,
+    pty_id: String,
+    options: PtySpawnOptions,
+) -> Result<PtySpawned, String> {
+    let window_label = window.label().to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        spawn_session(app, window_label, session_id, pty_id, options)
+    })
+    .await
+    .map_err(|e| format!("Failed to start shell: {e}"))?
+}
+
+/// Send input to a PTY.
+#[tauri::command]
+pub fn pty_write(pty_id: String, data: String) -> Result<(), String> {
+    pty_handle(&pty_id)?
+        .input
+        .send(data.into_bytes())
+        .map_err(|_| format!("Terminal input closed: {pty_id}"))
+}
+
+/// Resize a PTY.
+#[tauri::command]
+pub fn pty_resize(pty_id: String, cols: u16, rows: u16) -> Result<(), String> {
+    let handle = pty_handle(&pty_id)?;
+    let master = handle.master.lock().map_err(|e| format!("Lock error: {e}"))?;
+    master
+        .resize(PtySize {
+            rows: rows.max(1),
+            cols: cols.max(1),
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Failed to resize terminal: {e}"))
+}
+
+/// Kill a PTY's shell (not its process group; see `pty_terminate`).
+#[tauri::command]
+pub fn pty_kill(pty_id: String) -> Result<(), String> {
+    let handle = pty_handle(&pty_id)?;
+    let mut killer = handle.killer.lock().map_err(|e| format!("Lock error: {e}"))?;
+    killer
+        .kill()
+        .map_err(|e| format!("Failed to kill terminal: {e}"))
+}
+
+// ============================================================================
+// Termination
+// ============================================================================
+
+/// Result of terminating a session's process tree
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyTerminateResult {
+    pub pid: u32,
+    /// SIGKILL (or forced taskkill) was needed
+    pub escalated: bool,
+    /// The shell is gone
+    pub exited: bool,
+}
+
+#[cfg(unix)]
+pub(crate) fn process_alive(pid: u32) -> bool {
+    // Signal 0 only checks existence/permission
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+/// Send a signal to the shell's process group (falls back to the pid).
+#[cfg(unix)]
+fn signal_group(pid: u32, signal: libc::c_int) {
+    let pid = pid as libc::pid_t;
+    unsafe {
+        let pgid = libc::getpgid(pid);
+        // The PTY shell leads its own group; never signal our own group
+        if pgid > 0 && pgid != libc::getpgrp() {
+            libc::kill(-pgid, signal);
+        } else {
+            libc::kill(pid, signal);
+        }
+    }
+}
+
+/// Poll `done` until it holds or `timeout` ends.
+pub(crate) fn poll_until(timeout: Duration, done: impl Fn() -> bool) -> bool {
+    let deadline = std::time::Instant::now() + timeout;
+    while std::time::Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(25));
+    }
+    done()
+}
+
+/// SIGHUP + SIGTERM to the process group, SIGKILL after `grace` (or at once
+/// when `force`).
+///
+/// `exited(timeout)` waits for the owner of the child handle to see the exit;
+/// a pid check can't tell a zombie or a reused pid from a running shell.
+#[cfg(unix)]
+pub(crate) fn terminate_process_tree(
+    pid: u32,
+    force: bool,
+    grace: Duration,
+    exited: impl Fn(Duration) -> bool,
+) -> PtyTerminateResult {
+    if !force {
+        signal_group(pid, libc::SIGHUP);
+        signal_group(pid, libc::SIGTERM);
+        if exited(grace) {
+            return PtyTerminateResult { pid, escalated: false, exited: true };
+        }
+    }
+    signal_group(pid, libc::SIGKILL);
+    let exited = exited(Duration::from_millis(500));
+    PtyTerminateResult { pid, escalated: true, exited }
+}
+
+/// `taskkill /T` ends the whole tree; `/F` is used when forced or after
+/// `grace`. A successful taskkill only means the request was delivered, so
+/// the exit is confirmed through `exited` (see the unix version).
+#[cfg(windows)]
+pub(crate) fn terminate_process_tree(
+    pid: u32,
+    force: bool,
+    grace: Duration,
+    exited: impl Fn(Duration) -> bool,
+) -> PtyTerminateResult {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let taskkill = |forced: bool| {
+        let mut cmd = std::process::Command::new("taskkill");
+        cmd.args(["/T", "/PID", &pid.to_string()])
+            .creation_flags(CREATE_NO_WINDOW);
+        if forced {
+            cmd.arg("/F");
+        }
+        cmd.output().map(|o| o.status.success()).unwrap_or(false)
+    };
+
+    if !force && taskkill(false) && exited(grace) {
+        return PtyTerminateResult { pid, escalated: false, exited: true };
+    }
+    taskkill(true);
+    let exited = exited(Duration::from_millis(500));
+    PtyTerminateResult { pid, escalated: true, exited }
+}
+
+/// Terminate a session's shell and everything it started.
+///
+/// Sends SIGHUP/SIGTERM to the process group and escalates to SIGKILL after
+/// `timeout_ms` (default 2s); `force` skips straight to SIGKILL. The shell
+/// counts as exited once its PTY's wait on the child handle returns.
+#[tauri::command]
+pub async fn pty_terminate(
+    session_id: String,
+    force: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<PtyTerminateResult, String> {
+    let (pid, pty_id, has_exited) = session_process(&session_id)
+        .ok_or_else(|| format!("No process recorded for terminal session: {session_id}"))?;
+    if has_exited {
+        return Ok(PtyTerminateResult { pid, escalated: false, exited: true });
+    }
+    let grace = timeout_ms.map_or(DEFAULT_KILL_GRACE, Duration::from_millis);
+    let force = force.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        let exited = |timeout| wait_for_exit(&session_id, Some(timeout)).is_ok();
+        let mut result = terminate_process_tree(pid, force, grace, exited);
+        if !result.exited {
+            // The tree kill missed the shell: kill it through its child handle
+            if let Some(handle) = pty_id.as_deref().and_then(|id| pty_handle(id).ok()) {
+                if let Ok(mut killer) = handle.killer.lock() {
+                    let _ = killer.kill();
+                }
+            }
+            result.exited = exited(Duration::from_millis(500));
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Terminate failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_windows_candidates_order() {
+        let env = env_from(&[
+            ("ProgramFiles", "C:\\Program Files"),
+            ("SystemRoot", "C:\\Windows"),
+            ("COMSPEC", "C:\\Windows\\System32\\cmd.exe"),
+        ]);
+        let kinds: Vec<ShellKind> = windows_shell_candidates(&env, None)
+            .iter()
+            .map(|s| s.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![ShellKind::Pwsh, ShellKind::PowerShell, ShellKind::Cmd, ShellKind::GitBash]
+        );
+    }
+
+    #[test]
+    fn test_windows_registry_git_comes_first() {
+        let env = env_from(&[("ProgramFiles", "C:\\Program Files")]);
+        let shells = windows_shell_candidates(&env, Some(PathBuf::from("D:\\Tools\\Git")));
+        let git: Vec<&ShellInfo> = shells.iter().filter(|s| s.kind == ShellKind::GitBash).collect();
+        assert_eq!(git.len(), 2);
+        assert!(git[0].path.starts_with("D:\\Tools\\Git"));
+        assert_eq!(git[0].args, vec!["--login", "-i"]);
+    }
+
+    #[test]
+    fn test_windows_cmd_falls_back_to_system_root() {
+        let env = env_from(&[("windir", "C:\\Windows")]);
+        let shells = windows_shell_candidates(&env, None);
+        let cmd = shells.iter().find(|s| s.kind == ShellKind::Cmd).unwrap();
+        assert!(cmd.path.ends_with("cmd.exe"));
+        assert!(cmd.path.starts_with("C:\\Windows"));
+    }
+
+    #[test]
+    fn test_parse_reg_value() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\GitForWindows\r\n    InstallPath    REG_SZ    C:\\Program Files\\Git\r\n\r\n";
+        assert_eq!(
+            parse_reg_value(output, "InstallPath").as_deref(),
+            Some("C:\\Program Files\\Git")
+        );
+        assert_eq!(parse_reg_value(output, "Missing"), None);
+    }
+
+    #[test]
+    fn test_posix_candidates_prefer_shell_env_and_dedupe() {
+        let etc = "# /etc/shells\n/bin/sh\n/bin/bash\n/usr/bin/fish\n";
+        let shells = posix_shell_candidates(Some("/usr/bin/fish".to_string()), etc);
+        let paths: Vec<&str> = shells.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, vec!["/usr/bin/fish", "/bin/zsh", "/bin/bash", "/bin/sh"]);
+        assert_eq!(shells[0].name, "fish");
+    }
+
+    #[test]
+    fn test_terminal_env_injects_path_and_workspace() {
+        let env = build_terminal_env("/opt/homebrew/bin:/usr/bin", Some("/w"), &HashMap::new());
+        assert_eq!(env["PATH"], "/opt/homebrew/bin:/usr/bin");
+        assert_eq!(env["VMARK_WORKSPACE"], "/w");
+        assert_eq!(env["TERM"], "xterm-256color");
+    }
+
+    #[test]
+    fn test_terminal_env_extra_overrides_and_expands_path() {
+        let extra = HashMap::from([
+            ("PATH".to_string(), "/venv/bin:$PATH".to_string()),
+            ("TERM".to_string(), "xterm".to_string()),
+            ("BAD=KEY".to_string(), "x".to_string()),
+            ("  ".to_string(), "x".to_string()),
+        ]);
+        let env = build_terminal_env("/usr/bin", None, &extra);
+        assert_eq!(env["PATH"], "/venv/bin:/usr/bin");
+        assert_eq!(env["TERM"], "xterm");
+        assert!(!env.contains_key("VMARK_WORKSPACE"));
+        assert!(!env.keys().any(|k| k.contains('=') || k.trim().is_empty()));
+    }
+
+    #[test]
+    fn test_scrollback_tail_lines() {
+        let mut sb = Scrollback::default();
+        sb.push("one\r\ntwo\r\nthr");
+        sb.push("ee\r\n$ ");
+        assert_eq!(sb.tail(None), "one\r\ntwo\r\nthree\r\n$ ");
+        assert_eq!(sb.tail(Some(1)), "three\r\n$ ");
+        assert_eq!(sb.tail(Some(10)), sb.tail(None));
+    }
+
+    #[test]
+    fn test_scrollback_caps_lines() {
+        let mut sb = Scrollback::default();
+        for i in 0..MAX_SCROLLBACK_LINES + 10 {
+            sb.push(&format!("{i}\n"));
+        }
+        assert_eq!(sb.lines.len(), MAX_SCROLLBACK_LINES);
+        assert!(sb.tail(None).starts_with("10\n"));
+        assert_eq!(sb.bytes, sb.lines.iter().map(String::len).sum::<usize>());
+    }
+
+    #[test]
+    fn test_scrollback_caps_runaway_line() {
+        let mut sb = Scrollback::default();
+        sb.push("head\n");
+        sb.push(&"é".repeat(MAX_SCROLLBACK_BYTES));
+        assert!(sb.lines.is_empty());
+        assert!(sb.partial.len() <= MAX_SCROLLBACK_BYTES);
+    }
+
+    #[test]
+    fn test_scrollback_commands() {
+        append_scrollback("t-test", "hello\n").unwrap();
+        assert_eq!(pty_get_scrollback("t-test".into(), None), "hello\n");
+        pty_clear_scrollback("t-test".into()).unwrap();
+        assert_eq!(pty_get_scrollback("t-test".into(), None), "");
+    }
+
+    #[test]
+    fn test_resolve_exit_signal() {
+        assert_eq!(resolve_exit_signal(0, None), None);
+        assert_eq!(resolve_exit_signal(1, Some(9)).as_deref(), Some("SIGKILL"));
+        assert_eq!(resolve_exit_signal(1, Some(64)).as_deref(), Some("SIG64"));
+        if !cfg!(target_os = "windows") {
+            assert_eq!(resolve_exit_signal(130, None).as_deref(), Some("SIGINT"));
+            assert_eq!(resolve_exit_signal(143, None).as_deref(), Some("SIGTERM"));
+        }
+        assert_eq!(resolve_exit_signal(127, None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_details_reads_signal() {
+        let status = std::process::Command::new("sh")
+            .args(["-c", "kill -TERM $$"])
+            .status()
+            .unwrap();
+        let status = portable_pty::ExitStatus::from(status);
+        assert_eq!(exit_details(&status), (143, Some(15)));
+        assert_eq!(
+            exit_details(&portable_pty::ExitStatus::with_exit_code(2)),
+            (2, None)
+        );
+        assert_eq!(signal_number("Signal 9"), Some(9));
+    }
+
+    #[test]
+    fn test_wait_for_exit() {
+        assert!(wait_for_exit("t-wait-unknown", Some(Duration::from_millis(1))).is_err());
+
+        record_spawn(
+            "t-wait",
+            SessionState {
+                pid: Some(42),
+                pty_id: Some("p-1".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(session_process("t-wait"), Some((42, Some("p-1".into()), false)));
+        assert!(wait_for_exit("t-wait", Some(Duration::from_millis(10))).is_err());
+
+        let waiter = std::thread::spawn(|| wait_for_exit("t-wait", Some(Duration::from_secs(5))));
+        // A replaced PTY's exit is ignored
+        assert!(record_exit("t-wait", "p-0", 1, None).is_none());
+        assert!(record_exit("t-wait", "p-1", 3, None).is_some());
+        assert_eq!(waiter.join().unwrap().unwrap().exit_code, 3);
+
+        pty_release_session("t-wait".into()).unwrap();
+        assert!(wait_for_exit("t-wait", Some(Duration::from_millis(1))).is_err());
+    }
+
+    #[cfg(unix)]
+    fn spawn_in_own_group(args: &[&str]) -> std::process::Child {
+        use std::os::unix::process::CommandExt;
+        std::process::Command::new("sh")
+            .args(args)
+            .process_group(0)
+            .spawn()
+            .unwrap()
+    }
+
+    /// Wait on the child in a thread, like a PTY's exit thread; the closure
+    /// reports whether that wait has returned.
+    #[cfg(unix)]
+    #[allow(clippy::type_complexity)]
+    fn reap(
+        mut child: std::process::Child,
+    ) -> (
+        std::thread::JoinHandle<std::io::Result<std::process::ExitStatus>>,
+        impl Fn(Duration) -> bool,
+    ) {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        let done = Arc::new(AtomicBool::new(false));
+        let flag = done.clone();
+        let reaper = std::thread::spawn(move || {
+            let status = child.wait();
+            flag.store(true, Ordering::SeqCst);
+            status
+        });
+        (reaper, move |timeout| poll_until(timeout, || done.load(Ordering::SeqCst)))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_graceful() {
+        let child = spawn_in_own_group(&["-c", "sleep 30"]);
+        let pid = child.id();
+        let (reaper, exited) = reap(child);
+
+        let result = terminate_process_tree(pid, false, Duration::from_secs(2), exited);
+        reaper.join().unwrap().unwrap();
+        assert!(!result.escalated);
+        assert!(result.exited);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_escalates_when_signals_ignored() {
+        let child = spawn_in_own_group(&["-c", "trap '' HUP TERM; while :; do sleep 0.05; done"]);
+        let pid = child.id();
+        // Give the shell time to install its traps
+        std::thread::sleep(Duration::from_millis(200));
+        let (reaper, exited) = reap(child);
+
+        let result = terminate_process_tree(pid, false, Duration::from_millis(300), exited);
+        let status = reaper.join().unwrap().unwrap();
+        assert!(result.escalated);
+        assert!(result.exited);
+        assert!(!status.success());
+    }
+
+    #[test]
+    fn test_hot_exit_fill_and_seed() {
+        use crate::hot_exit::session::TerminalSessionState;
+
+        record_spawn(
+            "t-hot",
+            SessionState {
+                cwd: Some("/w".into()),
+                shell_path: Some("/bin/zsh".into()),
+                shell_kind: Some("posix".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        append_scrollback("t-hot", "$ ls\r\nnotes.md\r\n").unwrap();
+        append_scrollback("t-hot", "$ echo sk-abcdefghijklmnopqrstuvwx\r\n").unwrap();
+
+        let mut record = TerminalSessionState {
+            session_id: "t-hot".into(),
+            label: "Terminal 1".into(),
+            is_active: true,
+            cwd: None,
+            shell_path: None,
+            shell_kind: None,
+            scrollback: String::new(),
+        };
+        fill_session_state(&mut record, false);
+        assert_eq!(record.cwd.as_deref(), Some("/w"));
+        assert_eq!(record.shell_path.as_deref(), Some("/bin/zsh"));
+        assert_eq!(record.scrollback, "");
+
+        fill_session_state(&mut record, true);
+        assert_eq!(record.scrollback, "$ ls\r\nnotes.md\r\n$ echo [API_KEY_1]\r\n");
+
+        pty_release_session("t-hot".into()).unwrap();
+        seed_scrollback("t-hot", &record.scrollback);
+        assert_eq!(pty_get_scrollback("t-hot".into(), None), record.scrollback);
+        pty_clear_scrollback("t-hot".into()).unwrap();
+    }
+
+    #[test]
+    fn test_take_utf8_carries_split_sequences() {
+        let bytes = "héllo".as_bytes();
+        // Split inside the two-byte 'é'
+        let mut carry = bytes[..2].to_vec();
+        assert_eq!(take_utf8(&mut carry), "h");
+        assert_eq!(carry.len(), 1);
+        carry.extend_from_slice(&bytes[2..]);
+        assert_eq!(take_utf8(&mut carry), "éllo");
+        assert!(carry.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_cwd_of_self() {
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(
+            process_cwd(std::process::id()).map(PathBuf::from),
+            Some(cwd)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Text Encoding
+//!
+//! Opens files that aren't UTF-8 (GBK, Shift_JIS, windows-1252, UTF-16, ...)
+//! by detecting their encoding, decoding to UTF-8 for the editor, and writing
+//! them back in the same encoding on save.
+//!
+//! Detection order: byte order mark, then valid UTF-8, then chardetng's guess.
+//! Encodings use WHATWG names as returned by `encoding_rs` ("UTF-8", "GBK",
+//! "Shift_JIS", "windows-1252", ...).
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::app_paths;
+
+/// A decoded file and what's needed to write it back unchanged
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedFile {
+    pub content: String,
+    /// WHATWG encoding name
+    pub encoding: String,
+    /// The file started with a byte order mark
+    pub bom: bool,
+    /// "lf", "crlf", "cr", "mixed" or "unknown" (no line breaks)
+    pub line_ending: &'static str,
+    /// Some bytes were invalid in the detected encoding and were replaced
+    pub had_errors: bool,
+    /// SHA-256 of the file's bytes, as in watcher events and safe saves
+    pub digest: String,
+}
+
+fn detect_encoding(bytes: &[u8]) -> (&'static Encoding, bool) {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return (encoding, true);
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        return (UTF_8, false);
+    }
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    (detector.guess(None, true), false)
+}
+
+fn detect_line_ending(text: &str) -> &'static str {
+    let (mut crlf, mut lf, mut cr) = (0usize, 0usize, 0usize);
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                crlf += 1;
+                i += 1;
+            }
+            b'\r' => cr += 1,
+            b'\n' => lf += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    match (crlf > 0, lf > 0, cr > 0) {
+        (false, false, false) => "unknown",
+        (true, false, false) => "crlf",
+        (false, true, false) => "lf",
+        (false, false, true) => "cr",
+        _ => "mixed",
+    }
+}
+
+pub(crate) fn decode_bytes(bytes: &[u8]) -> DecodedFile {
+    let (encoding, bom) = detect_encoding(bytes);
+    // `decode` strips a BOM matching the encoding
+    let (content, actual, had_errors) = encoding.decode(bytes);
+    DecodedFile {
+        line_ending: detect_line_ending(&content),
+        content: content.into_owned(),
+        encoding: actual.name().to_string(),
+        bom,
+        had_errors,
+        digest: crate::watcher::content_hash(bytes),
+    }
+}
+
+/// Encode `content` in the named encoding, failing if any character can't be
+/// represented (rather than writing HTML character references).
+fn encode_text(content: &str, encoding_name: &str, bom: bool) -> Result<Vec<u8>, String> {
+    let encoding = Encoding::for_label(encoding_name.as_bytes())
+        .ok_or_else(|| format!("Unknown encoding: {encoding_name}"))?;
+
+    // encoding_rs only encodes UTF-16 as UTF-8 (per WHATWG), so do it by hand
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let mut out = Vec::with_capacity(content.len() * 2 + 2);
+        let units = (if bom { Some('\u{feff}') } else { None })
+            .into_iter()
+            .chain(content.chars())
+            .collect::<String>();
+        for unit in units.encode_utf16() {
+            let pair = if encoding == UTF_16LE {
+                unit.to_le_bytes()
+            } else {
+                unit.to_be_bytes()
+            };
+            out.extend_from_slice(&pair);
+        }
+        return Ok(out);
+    }
+
+    let (bytes, _, unmappable) = encoding.encode(content);
+    if unmappable {
+        let bad = content
+            .chars()
+            .find(|c| encoding.encode(c.encode_utf8(&mut [0; 4])).2)
+            .map(|c| format!(" (e.g. '{c}')"))
+            .unwrap_or_default();
+        return Err(format!(
+            "Some characters can't be saved as {}{bad}. Save as UTF-8 instead.",
+            encoding.name()
+        ));
+    }
+    let mut out = Vec::with_capacity(bytes.len() + 3);
+    if bom && encoding == UTF_8 {
+        out.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    out.extend_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Read a text file in whatever encoding it uses, converted to UTF-8.
+#[tauri::command]
+pub fn read_file_with_encoding(path: String) -> Result<DecodedFile, String> {
+    let bytes = fs::read(&path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    Ok(decode_bytes(&bytes))
+}
+
+/// Write `content` in `encoding` (as reported by `read_file_with_encoding`).
+/// Fails without writing if the text has characters the encoding can't hold.
+#[tauri::command]
+pub fn save_file_with_encoding(
+    path: String,
+    content: String,
+    encoding: String,
+    bom: bool,
+) -> Result<(), String> {
+    let bytes = encode_text(&content, &encoding, bom)?;
+    app_paths::atomic_write_file(Path::new(&path), &bytes)
+}
+
+/// Encodings offered in the "Reopen/Save with Encoding" pickers
+#[tauri::command]
+pub fn list_text_encodings() -> Vec<&'static str> {
+    vec![
+        "UTF-8",
+        "UTF-16LE",
+        "UTF-16BE",
+        "GBK",
+        "gb18030",
+        "Big5",
+        "Shift_JIS",
+        "EUC-JP",
+        "ISO-2022-JP",
+        "EUC-KR",
+        "windows-1250",
+        "windows-1251",
+        "windows-1252",
+        "ISO-8859-2",
+        "ISO-8859-15",
+        "KOI8-R",
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_passthrough() {
+        let decoded = decode_bytes("# 标题\nbody\n".as_bytes());
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert_eq!(decoded.content, "# 标题\nbody\n");
+        assert_eq!(decoded.line_ending, "lf");
+        assert!(!decoded.bom);
+        assert!(!decoded.had_errors);
+        assert_eq!(decoded.digest, crate::watcher::content_hash("# 标题\nbody\n".as_bytes()));
+    }
+
+    #[test]
+    fn test_detects_legacy_cjk_encodings() {
+        let text = "中文文档的标题\r\n这是一段用于检测编码的中文内容，包含足够多的汉字。\r\n";
+        let (gbk, _, _) = encoding_rs::GBK.encode(text);
+        let decoded = decode_bytes(&gbk);
+        assert_eq!(decoded.encoding, "GBK");
+        assert_eq!(decoded.content, text);
+        assert_eq!(decoded.line_ending, "crlf");
+
+        let text = "日本語の文書です。文字コードの判定に十分な長さの文章を用意します。\n";
+        let (sjis, _, _) = encoding_rs::SHIFT_JIS.encode(text);
+        let decoded = decode_bytes(&sjis);
+        assert_eq!(decoded.encoding, "Shift_JIS");
+        assert_eq!(decoded.content, text);
+    }
+
+    #[test]
+    fn test_detects_latin1() {
+        let decoded = decode_bytes(b"Caf\xe9 cr\xe8me br\xfbl\xe9e\n");
+        assert_eq!(decoded.encoding, "windows-1252");
+        assert_eq!(decoded.content, "Café crème brûlée\n");
+    }
+
+    #[test]
+    fn test_bom_round_trips() {
+        let utf16 = encode_text("hi\n", "UTF-16LE", true).unwrap();
+        assert_eq!(utf16, b"\xFF\xFEh\0i\0\n\0");
+        let decoded = decode_bytes(&utf16);
+        assert_eq!(decoded.encoding, "UTF-16LE");
+        assert!(decoded.bom);
+        assert_eq!(decoded.content, "hi\n");
+
+        let utf8 = encode_text("hi", "UTF-8", true).unwrap();
+        assert_eq!(utf8, b"\xEF\xBB\xBFhi");
+        let decoded = decode_bytes(&utf8);
+        assert!(decoded.bom);
+        assert_eq!(decoded.content, "hi");
+    }
+
+    #[test]
+    fn test_encode_rejects_unmappable() {
+        assert_eq!(encode_text("Café", "windows-1252", false).unwrap(), b"Caf\xe9");
+        let err = encode_text("Café 中", "windows-1252", false).unwrap_err();
+        assert!(err.contains("'中'"));
+        assert!(encode_text("x", "not-an-encoding", false).is_err());
+    }
+
+    #[test]
+    fn test_line_endings() {
+        assert_eq!(detect_line_ending("a"), "unknown");
+        assert_eq!(detect_line_ending("a\rb\r"), "cr");
+        assert_eq!(detect_line_ending("a\r\nb\n"), "mixed");
+    }
+
+    #[test]
+    fn test_save_writes_original_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gbk.md");
+        save_file_with_encoding(
+            path.to_string_lossy().into_owned(),
+            "中文".to_string(),
+            "GBK".to_string(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), encoding_rs::GBK.encode("中文").0.as_ref());
+    }
+}
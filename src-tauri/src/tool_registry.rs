@@ -0,0 +1,447 @@
+//! External Tool Registry
+//!
+//! One place to find the command-line tools VMark can use but doesn't ship:
+//! pandoc, WeasyPrint, mermaid-cli, tesseract and ffmpeg. Each tool is
+//! looked up on the login-shell PATH, its version is read and compared with
+//! the oldest supported one, and the result is cached so the Settings
+//! dependency dashboard and the features that need a tool share one check.
+//!
+//! Re-detecting a tool whose status changed (installed, removed, upgraded)
+//! emits `tools:status-changed` with the changed entries.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use crate::ai_provider::{build_command, check_command, login_shell_path};
+
+pub const TOOLS_STATUS_CHANGED_EVENT: &str = "tools:status-changed";
+
+/// How to install a tool on each platform.
+struct InstallCommands {
+    macos: &'static str,
+    windows: &'static str,
+    linux: &'static str,
+}
+
+struct ToolSpec {
+    id: &'static str,
+    name: &'static str,
+    /// What VMark uses it for
+    purpose: &'static str,
+    /// Executable names to try, in order
+    binaries: &'static [&'static str],
+    version_args: &'static [&'static str],
+    parse_version: fn(&str) -> Option<String>,
+    min_version: Option<&'static str>,
+    homepage: &'static str,
+    install: InstallCommands,
+}
+
+const TOOLS: &[ToolSpec] = &[
+    ToolSpec {
+        id: "pandoc",
+        name: "Pandoc",
+        purpose: "Import and export of ODT, LaTeX, reStructuredText and MediaWiki",
+        binaries: &["pandoc"],
+        version_args: &["--version"],
+        parse_version: crate::pandoc::parse_version,
+        // Built-in citeproc
+        min_version: Some("2.11"),
+        homepage: "https://pandoc.org/installing.html",
+        install: InstallCommands {
+            macos: "brew install pandoc",
+            windows: "winget install --id JohnMacFarlane.Pandoc",
+            linux: "sudo apt install pandoc",
+        },
+    },
+    ToolSpec {
+        id: "weasyprint",
+        name: "WeasyPrint",
+        purpose: "Print-quality PDF export",
+        binaries: &["weasyprint"],
+        version_args: &["--version"],
+        parse_version: first_version_number,
+        // No longer needs cairo
+        min_version: Some("53.0"),
+        homepage: "https://doc.courtbouillon.org/weasyprint/stable/first_steps.html",
+        install: InstallCommands {
+            macos: "brew install weasyprint",
+            windows: "pip install weasyprint",
+            linux: "pip install weasyprint",
+        },
+    },
+    ToolSpec {
+        id: "mermaid",
+        name: "Mermaid CLI",
+        purpose: "Rendering Mermaid diagrams to images in exports",
+        binaries: &["mmdc"],
+        version_args: &["--version"],
+        parse_version: first_version_number,
+        min_version: Some("10.0"),
+        homepage: "https://github.com/mermaid-js/mermaid-cli",
+        install: InstallCommands {
+            macos: "npm install -g @mermaid-js/mermaid-cli",
+            windows: "npm install -g @mermaid-js/mermaid-cli",
+            linux: "npm install -g @mermaid-js/mermaid-cli",
+        },
+    },
+    ToolSpec {
+        id: "tesseract",
+        name: "Tesseract",
+        purpose: "Text recognition (OCR) in images and scanned PDFs",
+        binaries: &["tesseract"],
+        version_args: &["--version"],
+        parse_version: crate::ocr::parse_version,
+        min_version: Some("4.0"),
+        homepage: "https://tesseract-ocr.github.io/tessdoc/Installation.html",
+        install: InstallCommands {
+            macos: "brew install tesseract",
+            windows: "winget install --id UB-Mannheim.TesseractOCR",
+            linux: "sudo apt install tesseract-ocr",
+        },
+    },
+    ToolSpec {
+        id: "ffmpeg",
+        name: "FFmpeg",
+        purpose: "Converting audio and video for transcription",
+        binaries: &["ffmpeg"],
+        version_args: &["-version"],
+        parse_version: first_version_number,
+        min_version: None,
+        homepage: "https://ffmpeg.org/download.html",
+        install: InstallCommands {
+            macos: "brew install ffmpeg",
+            windows: "winget install --id Gyan.FFmpeg",
+            linux: "sudo apt install ffmpeg",
+        },
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallHint {
+    /// Install command for this platform
+    pub command: String,
+    pub url: &'static str,
+    /// Sentence to show when the tool is missing or too old
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolStatus {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub purpose: &'static str,
+    pub available: bool,
+    pub path: Option<String>,
+    pub version: Option<String>,
+    pub min_version: Option<&'static str>,
+    /// Installed, but older than `min_version`
+    pub outdated: bool,
+    pub install_hint: InstallHint,
+    /// Unix seconds
+    pub checked_at: u64,
+}
+
+impl ToolStatus {
+    /// Whether anything a user would notice differs.
+    fn differs_from(&self, other: &ToolStatus) -> bool {
+        self.available != other.available
+            || self.path != other.path
+            || self.version != other.version
+    }
+}
+
+fn cache() -> &'static Mutex<HashMap<&'static str, ToolStatus>> {
+    static CACHE: OnceLock<Mutex<HashMap<&'static str, ToolStatus>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn spec(id: &str) -> Result<&'static ToolSpec, String> {
+    TOOLS
+        .iter()
+        .find(|tool| tool.id == id)
+        .ok_or_else(|| format!("Unknown tool: {id}"))
+}
+
+/// First version-looking word of the first non-empty line:
+/// `WeasyPrint version 60.1` → `60.1`, `ffmpeg version n6.0` → `6.0`.
+fn first_version_number(output: &str) -> Option<String> {
+    let first = output.lines().find(|l| !l.trim().is_empty())?;
+    first.split_whitespace().find_map(|word| {
+        let word = word
+            .trim_start_matches(['v', 'n'])
+            .trim_end_matches([',', ';', ')']);
+        (word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+            .then(|| word.to_string())
+    })
+}
+
+/// Numeric parts of a version (`3.1.9-rc1` → [3, 1, 9]).
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map_while(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// Whether `version` is older than `min`. Unparseable versions aren't.
+fn is_older(version: &str, min: &str) -> bool {
+    let (mut have, mut want) = (version_parts(version), version_parts(min));
+    if have.is_empty() {
+        return false;
+    }
+    let len = have.len().max(want.len());
+    have.resize(len, 0);
+    want.resize(len, 0);
+    have < want
+}
+
+fn install_hint(spec: &ToolSpec, version: Option<&str>, outdated: bool) -> InstallHint {
+    let command = if cfg!(target_os = "macos") {
+        spec.install.macos
+    } else if cfg!(target_os = "windows") {
+        spec.install.windows
+    } else {
+        spec.install.linux
+    };
+    let message = match (outdated, version, spec.min_version) {
+        (true, Some(version), Some(min)) => format!(
+            "{} {version} is older than the supported {min}. Update it with `{command}` or see {}.",
+            spec.name, spec.homepage
+        ),
+        _ => format!(
+            "{} is not installed. Install it with `{command}` or see {}.",
+            spec.name, spec.homepage
+        ),
+    };
+    InstallHint {
+        command: command.to_string(),
+        url: spec.homepage,
+        message,
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn read_version(spec: &ToolSpec, exe: &str) -> Option<String> {
+    let output = build_command(exe, spec.version_args)
+        .env("PATH", login_shell_path())
+        .output()
+        .ok()?;
+    // Some tools print their version to stderr
+    (spec.parse_version)(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| (spec.parse_version)(&String::from_utf8_lossy(&output.stderr)))
+}
+
+fn status_for(spec: &ToolSpec, path: Option<String>, version: Option<String>) -> ToolStatus {
+    let outdated = match (&version, spec.min_version) {
+        (Some(version), Some(min)) => is_older(version, min),
+        _ => false,
+    };
+    ToolStatus {
+        id: spec.id,
+        name: spec.name,
+        purpose: spec.purpose,
+        available: path.is_some(),
+        install_hint: install_hint(spec, version.as_deref(), outdated),
+        path,
+        version,
+        min_version: spec.min_version,
+        outdated,
+        checked_at: unix_now(),
+    }
+}
+
+fn probe_spec(spec: &ToolSpec) -> ToolStatus {
+    let path = spec
+        .binaries
+        .iter()
+        .find_map(|name| match check_command(name) {
+            (true, Some(path)) => Some(path),
+            _ => None,
+        });
+    let version = path.as_deref().and_then(|exe| read_version(spec, exe));
+    status_for(spec, path, version)
+}
+
+/// Re-detect tools, update the cache and emit `tools:status-changed` for
+/// any whose status changed since the last check. Blocking.
+pub(crate) fn refresh(app: &AppHandle, ids: &[&str]) -> Result<Vec<ToolStatus>, String> {
+    let specs = ids
+        .iter()
+        .map(|id| spec(id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let statuses: Vec<ToolStatus> = specs.into_iter().map(probe_spec).collect();
+
+    let changed: Vec<ToolStatus> = {
+        let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+        statuses
+            .iter()
+            .filter(|status| {
+                let previous = cache.insert(status.id, (*status).clone());
+                previous.is_some_and(|previous| previous.differs_from(status))
+            })
+            .cloned()
+            .collect()
+    };
+    if !changed.is_empty() {
+        let _ = app.emit(TOOLS_STATUS_CHANGED_EVENT, &changed);
+    }
+    Ok(statuses)
+}
+
+/// Cached status of a tool, detecting it on first use. Blocking.
+pub(crate) fn status(app: &AppHandle, id: &str) -> Result<ToolStatus, String> {
+    let cached = cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(id)
+        .cloned();
+    match cached {
+        Some(status) => Ok(status),
+        None => Ok(refresh(app, &[id])?.remove(0)),
+    }
+}
+
+/// Path of an installed tool. A cached "missing" is re-checked, so a
+/// tool installed while VMark is running is picked up. Blocking.
+pub(crate) fn find(app: &AppHandle, id: &str) -> Result<Option<String>, String> {
+    let cached = status(app, id)?;
+    let current = if cached.available {
+        cached
+    } else {
+        refresh(app, &[id])?.remove(0)
+    };
+    Ok(current.path)
+}
+
+/// Status of every known tool. Cached results are used unless `refresh`.
+#[tauri::command]
+pub async fn list_tools(app: AppHandle, refresh: Option<bool>) -> Result<Vec<ToolStatus>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if refresh.unwrap_or(false) {
+            let ids: Vec<&str> = TOOLS.iter().map(|tool| tool.id).collect();
+            return self::refresh(&app, &ids);
+        }
+        TOOLS.iter().map(|tool| status(&app, tool.id)).collect()
+    })
+    .await
+    .map_err(|e| format!("Tool detection failed: {e}"))?
+}
+
+/// Status of one tool (e.g. "pandoc"). Cached unless `refresh`.
+#[tauri::command]
+pub async fn detect_tool(
+    app: AppHandle,
+    id: String,
+    refresh: Option<bool>,
+) -> Result<ToolStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if refresh.unwrap_or(false) {
+            Ok(self::refresh(&app, &[id.as_str()])?.remove(0))
+        } else {
+            status(&app, &id)
+        }
+    })
+    .await
+    .map_err(|e| format!("Tool detection failed: {e}"))?
+}
+
+/// How to install a tool on this platform.
+#[tauri::command]
+pub fn get_tool_install_hint(id: String) -> Result<InstallHint, String> {
+    Ok(install_hint(spec(&id)?, None, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_ids_are_unique() {
+        for (i, tool) in TOOLS.iter().enumerate() {
+            assert!(
+                TOOLS[i + 1..].iter().all(|other| other.id != tool.id),
+                "{}",
+                tool.id
+            );
+            assert!(!tool.binaries.is_empty());
+        }
+        assert!(spec("pandoc").is_ok());
+        assert!(spec("nope").is_err());
+    }
+
+    #[test]
+    fn parses_common_version_output() {
+        assert_eq!(
+            first_version_number("WeasyPrint version 60.1\n").as_deref(),
+            Some("60.1")
+        );
+        assert_eq!(first_version_number("10.6.1\n").as_deref(), Some("10.6.1"));
+        assert_eq!(
+            first_version_number("ffmpeg version n6.0 Copyright (c) 2000-2023\n").as_deref(),
+            Some("6.0")
+        );
+        assert_eq!(
+            first_version_number("ffmpeg version 6.1.1-3ubuntu5 Copyright").as_deref(),
+            Some("6.1.1-3ubuntu5")
+        );
+        assert_eq!(first_version_number("no version here"), None);
+    }
+
+    #[test]
+    fn compares_versions() {
+        assert!(is_older("2.9.2", "2.11"));
+        assert!(!is_older("2.11", "2.11.0"));
+        assert!(!is_older("3.1.9", "2.11"));
+        assert!(!is_older("6.1.1-3ubuntu5", "6.1"));
+        assert!(is_older("52", "53.0"));
+        assert!(!is_older("unknown", "1.0"));
+    }
+
+    #[test]
+    fn outdated_tools_get_an_update_hint() {
+        let pandoc = spec("pandoc").unwrap();
+        let status = status_for(pandoc, Some("/usr/bin/pandoc".into()), Some("2.5".into()));
+        assert!(status.available);
+        assert!(status.outdated);
+        assert!(status
+            .install_hint
+            .message
+            .starts_with("Pandoc 2.5 is older than the supported 2.11"));
+
+        let missing = status_for(pandoc, None, None);
+        assert!(!missing.available && !missing.outdated);
+        assert!(missing
+            .install_hint
+            .message
+            .starts_with("Pandoc is not installed"));
+        assert_eq!(
+            missing.install_hint.url,
+            "https://pandoc.org/installing.html"
+        );
+    }
+
+    #[test]
+    fn only_visible_changes_count() {
+        let tool = spec("ffmpeg").unwrap();
+        let a = status_for(tool, Some("/usr/bin/ffmpeg".into()), Some("6.0".into()));
+        let mut b = a.clone();
+        b.checked_at += 60;
+        assert!(!a.differs_from(&b));
+        b.version = Some("7.0".into());
+        assert!(a.differs_from(&b));
+    }
+}
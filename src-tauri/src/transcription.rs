@@ -0,0 +1,587 @@
+//! Audio Transcription
+//!
+//! Turns voice memos into text with whisper.cpp. The binary is found next to
+//! the app executable when bundled (`whisper-cli`, like the MCP sidecar), or
+//! on the user's login-shell PATH (`whisper-cli`, or `whisper-cpp` from
+//! Homebrew).
+//!
+//! whisper.cpp needs a ggml model file; models are downloaded into
+//! `<app_data>/whisper-models` (`transcription:download-progress`). Audio it
+//! can't read directly (m4a, aac, webm, ...) is converted to 16 kHz WAV with
+//! ffmpeg first.
+//!
+//! A transcription reports `transcription:progress` and one
+//! `transcription:segment` per recognized segment, keyed by the caller's
+//! request id, and can be cancelled with `cancel_transcription`.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_provider::{build_command, check_command, login_shell_path};
+
+/// Shown when whisper.cpp can't be found
+const INSTALL_HINT: &str = "whisper.cpp is not installed. Install it (e.g. `brew install whisper-cpp`) \
+     from https://github.com/ggml-org/whisper.cpp and try again.";
+
+/// Executable names, newest first (`main` was renamed `whisper-cli` in 1.7)
+const BINARY_NAMES: &[&str] = &["whisper-cli", "whisper-cpp"];
+
+const MODEL_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Models offered for download, smallest first
+const MODELS: &[&str] = &[
+    "tiny", "tiny.en", "base", "base.en", "small", "small.en", "medium", "medium.en", "large-v3-turbo", "large-v3",
+];
+
+/// Formats whisper.cpp decodes itself; anything else goes through ffmpeg
+const NATIVE_FORMATS: &[&str] = &["wav", "mp3", "flac", "ogg"];
+
+const DOWNLOAD_TIMEOUT_SECS: u64 = 1800;
+
+/// Running transcriptions by request id, so they can be cancelled
+static RUNNING: Mutex<Option<HashMap<String, Child>>> = Mutex::new(None);
+
+/// Detected whisper.cpp installation
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperInfo {
+    pub available: bool,
+    pub path: Option<String>,
+    /// Shipped with VMark rather than installed by the user
+    pub bundled: bool,
+    /// Downloaded models, by name (`base.en`)
+    pub models: Vec<String>,
+    /// Whether ffmpeg is available to convert other audio formats
+    pub ffmpeg: bool,
+}
+
+/// A recognized stretch of speech
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Transcript {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// Payload for `transcription:progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionProgress {
+    pub request_id: String,
+    pub percent: u8,
+}
+
+/// Payload for `transcription:segment`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSegmentEvent {
+    pub request_id: String,
+    pub segment: TranscriptSegment,
+}
+
+/// Payload for `transcription:download-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelDownloadProgress {
+    pub model: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+fn models_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data.join("whisper-models"))
+}
+
+fn model_path(dir: &Path, model: &str) -> Result<PathBuf, String> {
+    if !MODELS.contains(&model) {
+        return Err(format!("Unknown whisper model: {:?}", model));
+    }
+    Ok(dir.join(format!("ggml-{}.bin", model)))
+}
+
+fn downloaded_models(dir: &Path) -> Vec<String> {
+    MODELS
+        .iter()
+        .filter(|m| model_path(dir, m).is_ok_and(|p| p.is_file()))
+        .map(|m| m.to_string())
+        .collect()
+}
+
+/// A whisper.cpp binary shipped next to the app executable.
+fn bundled_binary() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let name = if cfg!(target_os = "windows") { "whisper-cli.exe" } else { "whisper-cli" };
+    let path = exe.parent()?.join(name);
+    path.is_file().then_some(path)
+}
+
+/// `(path, bundled)` of the whisper.cpp binary to use.
+fn find_binary() -> Option<(String, bool)> {
+    if let Some(path) = bundled_binary() {
+        return Some((path.to_string_lossy().into_owned(), true));
+    }
+    BINARY_NAMES.iter().find_map(|name| match check_command(name) {
+        (true, Some(path)) => Some((path, false)),
+        _ => None,
+    })
+}
+
+fn detect(dir: &Path) -> WhisperInfo {
+    let found = find_binary();
+    WhisperInfo {
+        available: found.is_some(),
+        bundled: found.as_ref().is_some_and(|(_, bundled)| *bundled),
+        path: found.map(|(path, _)| path),
+        models: downloaded_models(dir),
+        ffmpeg: check_command("ffmpeg").0,
+    }
+}
+
+/// `00:01:02.500` → 62500 ms.
+fn parse_timestamp(ts: &str) -> Option<u64> {
+    let mut parts = ts.trim().split(':').rev();
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+    let hours: u64 = parts.next().map_or(Some(0), |h| h.parse().ok())?;
+    Some((hours * 3600 + minutes * 60) * 1000 + (seconds * 1000.0).round() as u64)
+}
+
+/// A stdout line like `[00:00:00.000 --> 00:00:04.200]   Hello there.`
+fn parse_segment(line: &str) -> Option<TranscriptSegment> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let (range, text) = rest.split_once(']')?;
+    let (start, end) = range.split_once("-->")?;
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(TranscriptSegment {
+        start_ms: parse_timestamp(start)?,
+        end_ms: parse_timestamp(end)?,
+        text: text.to_string(),
+    })
+}
+
+/// A stderr line like `whisper_print_progress_callback: progress =  40%`
+fn parse_progress(line: &str) -> Option<u8> {
+    let (_, value) = line.split_once("progress =")?;
+    value.trim().trim_end_matches('%').trim().parse::<u8>().ok().map(|p| p.min(100))
+}
+
+/// Plain text from segments: one paragraph, the way people read a memo.
+fn join_segments(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `--language` value: a whisper.cpp language code (`en`, `haw`) or `auto`.
+fn validate_language(lang: &str) -> Result<(), String> {
+    let valid = lang == "auto"
+        || (!lang.is_empty() && lang.len() <= 3 && lang.chars().all(|c| c.is_ascii_lowercase()));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid transcription language: {:?}", lang))
+    }
+}
+
+fn build_args(model: &Path, audio: &Path, lang: &str) -> Vec<String> {
+    vec![
+        "--model".to_string(),
+        model.to_string_lossy().into_owned(),
+        "--file".to_string(),
+        audio.to_string_lossy().into_owned(),
+        "--language".to_string(),
+        lang.to_string(),
+        "--print-progress".to_string(),
+    ]
+}
+
+fn needs_conversion(audio: &Path) -> bool {
+    let ext = audio
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    !NATIVE_FORMATS.contains(&ext.as_str())
+}
+
+/// Convert `audio` to 16 kHz mono WAV in `dir` with ffmpeg.
+fn convert_to_wav(audio: &Path, dir: &Path) -> Result<PathBuf, String> {
+    let (available, ffmpeg) = check_command("ffmpeg");
+    let Some(ffmpeg) = ffmpeg.filter(|_| available) else {
+        return Err("This audio format needs ffmpeg to be transcribed. Install ffmpeg, or convert the file to WAV or MP3.".to_string());
+    };
+    let wav = dir.join("audio.wav");
+    let audio = audio.to_string_lossy();
+    let wav_str = wav.to_string_lossy();
+    let output = build_command(
+        &ffmpeg,
+        &["-nostdin", "-y", "-i", &audio, "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le", &wav_str],
+    )
+    .env("PATH", login_shell_path())
+    .output()
+    .map_err(|e| format!("Failed to start ffmpeg: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(format!("Failed to convert audio: {}", last.trim()));
+    }
+    Ok(wav)
+}
+
+fn register(request_id: &str, child: Child) {
+    if let Ok(mut guard) = RUNNING.lock() {
+        guard.get_or_insert_with(HashMap::new).insert(request_id.to_string(), child);
+    }
+}
+
+fn unregister(request_id: &str) -> Option<Child> {
+    RUNNING.lock().ok()?.as_mut()?.remove(request_id)
+}
+
+/// Run whisper.cpp, reporting progress and each segment as it is recognized.
+fn run_whisper(
+    exe: &str,
+    args: &[String],
+    request_id: &str,
+    mut on_progress: impl FnMut(u8),
+    mut on_segment: impl FnMut(&TranscriptSegment),
+) -> Result<Vec<TranscriptSegment>, String> {
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut child = build_command(exe, &arg_refs)
+        .env("PATH", login_shell_path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start whisper.cpp: {}", e))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    register(request_id, child);
+
+    // Progress arrives on stderr while segments arrive on stdout
+    let (progress_tx, progress_rx) = std::sync::mpsc::channel::<u8>();
+    let stderr_reader = stderr.map(|pipe| {
+        std::thread::spawn(move || {
+            let mut errors = Vec::new();
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                if let Some(percent) = parse_progress(&line) {
+                    let _ = progress_tx.send(percent);
+                } else if line.contains("error") || line.contains("failed") {
+                    errors.push(line.trim().to_string());
+                }
+            }
+            errors
+        })
+    });
+
+    let mut segments = Vec::new();
+    if let Some(stdout) = stdout {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            for percent in progress_rx.try_iter() {
+                on_progress(percent);
+            }
+            if let Some(segment) = parse_segment(&line) {
+                on_segment(&segment);
+                segments.push(segment);
+            }
+        }
+    }
+    let errors = stderr_reader.and_then(|r| r.join().ok()).unwrap_or_default();
+    for percent in progress_rx.try_iter() {
+        on_progress(percent);
+    }
+
+    // Gone from the map means cancel_transcription killed it
+    let Some(mut child) = unregister(request_id) else {
+        return Err("Transcription cancelled".to_string());
+    };
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for whisper.cpp: {}", e))?;
+    if !status.success() {
+        let summary = match status.code() {
+            Some(code) => format!("whisper.cpp exited with status {}", code),
+            None => "whisper.cpp was terminated".to_string(),
+        };
+        return Err(if errors.is_empty() {
+            summary
+        } else {
+            format!("{}: {}", summary, errors.join("\n"))
+        });
+    }
+    Ok(segments)
+}
+
+/// Detect whisper.cpp, downloaded models and ffmpeg.
+#[tauri::command]
+pub async fn detect_whisper(app: AppHandle) -> Result<WhisperInfo, String> {
+    let dir = models_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || detect(&dir))
+        .await
+        .map_err(|e| format!("whisper.cpp detection failed: {}", e))
+}
+
+/// Models that can be downloaded, smallest first.
+#[tauri::command]
+pub fn list_whisper_models() -> Vec<String> {
+    MODELS.iter().map(|m| m.to_string()).collect()
+}
+
+/// Transcribe an audio file. `language` is a two-letter code or `auto`
+/// (the default). Progress and segments are emitted as
+/// `transcription:progress` / `transcription:segment` with `request_id`.
+#[tauri::command]
+pub async fn transcribe_audio(
+    app: AppHandle,
+    request_id: String,
+    path: String,
+    model: String,
+    language: Option<String>,
+) -> Result<Transcript, String> {
+    let audio = PathBuf::from(&path);
+    if !audio.is_absolute() {
+        return Err(format!("Audio path must be absolute: {}", path));
+    }
+    if !audio.is_file() {
+        return Err(format!("Audio file not found: {}", path));
+    }
+    let model_file = model_path(&models_dir(&app)?, &model)?;
+    if !model_file.is_file() {
+        return Err(format!("The {} model hasn't been downloaded yet", model));
+    }
+    let language = language.filter(|l| !l.is_empty()).unwrap_or_else(|| "auto".to_string());
+    validate_language(&language)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some((exe, _)) = find_binary() else {
+            return Err(INSTALL_HINT.to_string());
+        };
+        let temp = tempfile::tempdir().map_err(|e| format!("Failed to create temp folder: {}", e))?;
+        let input = if needs_conversion(&audio) {
+            convert_to_wav(&audio, temp.path())?
+        } else {
+            audio
+        };
+
+        tracing::info!("[Transcription] Transcribing {} with {}", path, model);
+        let args = build_args(&model_file, &input, &language);
+        let segments = run_whisper(
+            &exe,
+            &args,
+            &request_id,
+            |percent| {
+                let _ = app.emit(
+                    "transcription:progress",
+                    TranscriptionProgress {
+                        request_id: request_id.clone(),
+                        percent,
+                    },
+                );
+            },
+            |segment| {
+                let _ = app.emit(
+                    "transcription:segment",
+                    TranscriptionSegmentEvent {
+                        request_id: request_id.clone(),
+                        segment: segment.clone(),
+                    },
+                );
+            },
+        )
+        .inspect_err(|e| tracing::warn!("[Transcription] {}", e))?;
+
+        Ok(Transcript {
+            text: join_segments(&segments),
+            segments,
+        })
+    })
+    .await
+    .map_err(|e| format!("Transcription failed: {}", e))?
+}
+
+/// Stop a running transcription; `transcribe_audio` then fails with
+/// "Transcription cancelled".
+#[tauri::command]
+pub fn cancel_transcription(request_id: String) -> Result<(), String> {
+    if let Some(mut child) = unregister(&request_id) {
+        child.kill().map_err(|e| format!("Failed to stop whisper.cpp: {}", e))?;
+        let _ = child.wait();
+    }
+    Ok(())
+}
+
+/// Download a whisper model into the app data folder.
+#[tauri::command]
+pub async fn download_whisper_model(app: AppHandle, model: String) -> Result<(), String> {
+    let dir = models_dir(&app)?;
+    let dest = model_path(&dir, &model)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create models folder: {}", e))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .user_agent(concat!("VMark/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let url = format!("{}/ggml-{}.bin", MODEL_URL, model);
+    tracing::info!("[Transcription] Downloading model {}", model);
+    let mut resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", model, e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Failed to download {} (HTTP {})", model, resp.status().as_u16()));
+    }
+
+    let total = resp.content_length();
+    let mut data = Vec::with_capacity(total.unwrap_or(0) as usize);
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", model, e))?
+    {
+        data.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "transcription:download-progress",
+            ModelDownloadProgress {
+                model: model.clone(),
+                downloaded: data.len() as u64,
+                total,
+            },
+        );
+    }
+    if total.is_some_and(|t| t != data.len() as u64) {
+        return Err(format!("Download of {} was incomplete", model));
+    }
+
+    crate::app_paths::atomic_write_file(&dest, &data)
+}
+
+/// Delete a downloaded whisper model.
+#[tauri::command]
+pub fn remove_whisper_model(app: AppHandle, model: String) -> Result<(), String> {
+    match fs::remove_file(model_path(&models_dir(&app)?, &model)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove {}: {}", model, e)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("00:00:04.200"), Some(4200));
+        assert_eq!(parse_timestamp("01:02:03.005"), Some(3_723_005));
+        assert_eq!(parse_timestamp("xx"), None);
+    }
+
+    #[test]
+    fn test_parse_segment() {
+        assert_eq!(
+            parse_segment("[00:00:00.000 --> 00:00:04.200]   Hello there."),
+            Some(TranscriptSegment {
+                start_ms: 0,
+                end_ms: 4200,
+                text: "Hello there.".to_string()
+            })
+        );
+        assert_eq!(parse_segment("[00:00:04.200 --> 00:00:05.000]   "), None);
+        assert_eq!(parse_segment("whisper_init_from_file: loading model"), None);
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(parse_progress("whisper_print_progress_callback: progress =  40%"), Some(40));
+        assert_eq!(parse_progress("main: processing 'a.wav'"), None);
+    }
+
+    #[test]
+    fn test_model_path_rejects_unknown_models() {
+        let dir = Path::new("/models");
+        assert_eq!(model_path(dir, "base.en").unwrap(), dir.join("ggml-base.en.bin"));
+        assert!(model_path(dir, "../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_validate_language() {
+        assert!(validate_language("auto").is_ok());
+        assert!(validate_language("de").is_ok());
+        assert!(validate_language("--help").is_err());
+        assert!(validate_language("").is_err());
+    }
+
+    #[test]
+    fn test_needs_conversion() {
+        assert!(!needs_conversion(Path::new("/memo.WAV")));
+        assert!(!needs_conversion(Path::new("/memo.mp3")));
+        assert!(needs_conversion(Path::new("/memo.m4a")));
+        assert!(needs_conversion(Path::new("/memo")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_whisper_streams_segments_and_progress() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("whisper-cli");
+        fs::write(
+            &exe,
+            "#!/bin/sh\n\
+             echo 'whisper_print_progress_callback: progress = 50%' >&2\n\
+             echo '[00:00:00.000 --> 00:00:02.000]   Buy milk.'\n\
+             echo '[00:00:02.000 --> 00:00:03.500]   And eggs.'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut progress = Vec::new();
+        let mut streamed = 0;
+        let segments = run_whisper(
+            exe.to_str().unwrap(),
+            &[],
+            "test-stream",
+            |p| progress.push(p),
+            |_| streamed += 1,
+        )
+        .unwrap();
+        assert_eq!(streamed, 2);
+        assert_eq!(join_segments(&segments), "Buy milk. And eggs.");
+        assert_eq!(segments[1].end_ms, 3500);
+        assert_eq!(progress, vec![50]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_whisper_reports_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("whisper-cli");
+        fs::write(&exe, "#!/bin/sh\necho 'error: failed to open model' >&2\nexit 3\n").unwrap();
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = run_whisper(exe.to_str().unwrap(), &[], "test-fail", |_| {}, |_| {}).unwrap_err();
+        assert_eq!(err, "whisper.cpp exited with status 3: error: failed to open model");
+    }
+}
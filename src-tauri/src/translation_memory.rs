@@ -0,0 +1,295 @@
+//! Translation Memory
+//!
+//! Translation genies translate the same boilerplate (disclaimers, headers,
+//! signatures) over and over. Their results are cached here, keyed by a
+//! hash of the source text, the target language and the provider (with its
+//! model), so repeating a translation is instant and costs nothing.
+//!
+//! The frontend looks up a translation before calling the provider and
+//! stores the result afterwards. Entries expire after `TTL_MS`; beyond
+//! `MAX_ENTRIES` or `MAX_BYTES` the least recently used are dropped. The
+//! cache lives in `translation-memory.json` in the app data directory.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+
+use crate::app_paths;
+
+/// Cache file name in the app data directory
+pub const TRANSLATION_MEMORY_FILE: &str = "translation-memory.json";
+
+/// Entries older than this are treated as missing (30 days)
+const TTL_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+const MAX_ENTRIES: usize = 2000;
+
+/// Total size of the cached translations (8 MB)
+const MAX_BYTES: usize = 8 * 1024 * 1024;
+
+/// Longer texts are rarely repeated word for word, so they aren't cached
+const MAX_SOURCE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct MemoryEntry {
+    translation: String,
+    /// Milliseconds since the Unix epoch
+    created_at: i64,
+    last_used: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Memory {
+    #[serde(default)]
+    entries: BTreeMap<String, MemoryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationMemoryStats {
+    pub entries: usize,
+    /// Size of the cached translations, in bytes
+    pub bytes: usize,
+}
+
+/// Cache key: source hash, target language and provider. Surrounding
+/// whitespace doesn't change the translation, so it isn't hashed.
+fn memory_key(text: &str, target_language: &str, provider: &str) -> String {
+    let hash = format!("{:x}", Sha256::digest(text.trim().as_bytes()));
+    format!(
+        "{}|{}|{}",
+        hash,
+        target_language.trim().to_lowercase(),
+        provider.trim()
+    )
+}
+
+impl Memory {
+    fn bytes(&self) -> usize {
+        self.entries.values().map(|e| e.translation.len()).sum()
+    }
+
+    fn lookup(&mut self, key: &str, now: i64) -> Option<String> {
+        let entry = self.entries.get_mut(key)?;
+        if now - entry.created_at > TTL_MS {
+            self.entries.remove(key);
+            return None;
+        }
+        entry.last_used = now;
+        Some(entry.translation.clone())
+    }
+
+    fn insert(&mut self, key: String, translation: String, now: i64) {
+        self.entries.insert(
+            key,
+            MemoryEntry {
+                translation,
+                created_at: now,
+                last_used: now,
+            },
+        );
+        self.evict(now);
+    }
+
+    /// Drop expired entries, then the least recently used until the cache
+    /// fits its limits.
+    fn evict(&mut self, now: i64) {
+        self.entries.retain(|_, e| now - e.created_at <= TTL_MS);
+
+        let mut bytes = self.bytes();
+        if self.entries.len() <= MAX_ENTRIES && bytes <= MAX_BYTES {
+            return;
+        }
+        let mut by_use: Vec<(i64, String)> = self
+            .entries
+            .iter()
+            .map(|(key, e)| (e.last_used, key.clone()))
+            .collect();
+        by_use.sort();
+        for (_, key) in by_use {
+            if self.entries.len() <= MAX_ENTRIES && bytes <= MAX_BYTES {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&key) {
+                bytes -= entry.translation.len();
+            }
+        }
+    }
+}
+
+/// Loaded cache plus the file it persists to
+struct MemoryState {
+    file: PathBuf,
+    memory: Memory,
+}
+
+static MEMORY_STATE: OnceLock<Mutex<MemoryState>> = OnceLock::new();
+
+/// Load the translation memory from app data. Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(TRANSLATION_MEMORY_FILE);
+    let mut memory = load_memory(&file);
+    memory.evict(chrono::Utc::now().timestamp_millis());
+    let _ = MEMORY_STATE.set(Mutex::new(MemoryState { file, memory }));
+    Ok(())
+}
+
+fn load_memory(file: &Path) -> Memory {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_memory(file: &Path, memory: &Memory) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string(memory)
+        .map_err(|e| format!("Failed to serialize translation memory: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+fn with_state<T>(f: impl FnOnce(&mut MemoryState) -> Result<T, String>) -> Result<T, String> {
+    let state = MEMORY_STATE
+        .get()
+        .ok_or("Translation memory not initialized")?;
+    let mut guard = state.lock().map_err(|e| e.to_string())?;
+    f(&mut guard)
+}
+
+/// Cached translation of `text` into `target_language` by `provider`, if
+/// any. `provider` identifies the provider and model, e.g.
+/// `openai/gpt-4o-mini`.
+#[tauri::command]
+pub fn lookup_translation(
+    text: String,
+    target_language: String,
+    provider: String,
+) -> Result<Option<String>, String> {
+    if text.trim().is_empty() || text.len() > MAX_SOURCE_BYTES {
+        return Ok(None);
+    }
+    let key = memory_key(&text, &target_language, &provider);
+    let now = chrono::Utc::now().timestamp_millis();
+    // Last-used times are saved with the next stored translation
+    with_state(|state| Ok(state.memory.lookup(&key, now)))
+}
+
+/// Remember a translation returned by `provider`.
+#[tauri::command]
+pub fn store_translation(
+    text: String,
+    target_language: String,
+    provider: String,
+    translation: String,
+) -> Result<(), String> {
+    if text.trim().is_empty() || text.len() > MAX_SOURCE_BYTES || translation.trim().is_empty() {
+        return Ok(());
+    }
+    let key = memory_key(&text, &target_language, &provider);
+    let now = chrono::Utc::now().timestamp_millis();
+    with_state(|state| {
+        state.memory.insert(key, translation, now);
+        save_memory(&state.file, &state.memory)
+    })
+}
+
+#[tauri::command]
+pub fn translation_memory_stats() -> Result<TranslationMemoryStats, String> {
+    with_state(|state| {
+        Ok(TranslationMemoryStats {
+            entries: state.memory.entries.len(),
+            bytes: state.memory.bytes(),
+        })
+    })
+}
+
+#[tauri::command]
+pub fn clear_translation_memory() -> Result<(), String> {
+    with_state(|state| {
+        state.memory.entries.clear();
+        save_memory(&state.file, &state.memory)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_ignores_surrounding_whitespace_and_case_of_language() {
+        assert_eq!(
+            memory_key("  Hallo Welt\n", "EN", "openai/gpt-4o"),
+            memory_key("Hallo Welt", "en", "openai/gpt-4o")
+        );
+        assert_ne!(
+            memory_key("Hallo Welt", "en", "openai/gpt-4o"),
+            memory_key("Hallo Welt", "fr", "openai/gpt-4o")
+        );
+        assert_ne!(
+            memory_key("Hallo Welt", "en", "openai/gpt-4o"),
+            memory_key("Hallo Welt", "en", "ollama-api/llama3.2")
+        );
+    }
+
+    #[test]
+    fn test_lookup_updates_last_used_and_expires() {
+        let mut memory = Memory::default();
+        memory.insert("k".into(), "Hello".into(), 1_000);
+        assert_eq!(memory.lookup("k", 5_000).as_deref(), Some("Hello"));
+        assert_eq!(memory.entries["k"].last_used, 5_000);
+
+        assert_eq!(memory.lookup("k", 1_000 + TTL_MS + 1), None);
+        assert!(memory.entries.is_empty());
+        assert_eq!(memory.lookup("missing", 0), None);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_over_limits() {
+        let mut memory = Memory::default();
+        for i in 0..MAX_ENTRIES {
+            memory.insert(format!("k{i}"), "x".into(), i as i64);
+        }
+        // Using the oldest entry keeps it; the next oldest goes instead
+        memory.lookup("k0", MAX_ENTRIES as i64);
+        memory.insert("new".into(), "y".into(), MAX_ENTRIES as i64 + 1);
+        assert_eq!(memory.entries.len(), MAX_ENTRIES);
+        assert!(memory.entries.contains_key("k0"));
+        assert!(!memory.entries.contains_key("k1"));
+        assert!(memory.entries.contains_key("new"));
+
+        let mut memory = Memory::default();
+        let big = "z".repeat(MAX_BYTES / 2 + 1);
+        memory.insert("a".into(), big.clone(), 1);
+        memory.insert("b".into(), big, 2);
+        assert_eq!(memory.entries.keys().collect::<Vec<_>>(), vec!["b"]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join(TRANSLATION_MEMORY_FILE);
+        assert!(load_memory(&file).entries.is_empty());
+
+        let mut memory = Memory::default();
+        memory.insert(memory_key("Danke", "en", "anthropic"), "Thanks".into(), 1);
+        save_memory(&file, &memory).unwrap();
+        let mut loaded = load_memory(&file);
+        assert_eq!(
+            loaded
+                .lookup(&memory_key("Danke", "en", "anthropic"), 2)
+                .as_deref(),
+            Some("Thanks")
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! System tray / menu bar icon
+//!
+//! Offers Quick Note, the recent files list and a toggle for the main
+//! window. Tray menu events arrive through the app-wide menu handler
+//! (`menu_events`), which forwards `tray-*` ids here.
+
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::{menu, quick_capture, quit, window_manager};
+
+const TRAY_ID: &str = "vmark-tray";
+const TRAY_ID_PREFIX: &str = "tray-";
+const RECENT_ID_PREFIX: &str = "tray-recent-";
+
+/// Most recent files listed in the tray menu
+const MAX_TRAY_RECENTS: usize = 10;
+
+fn build_tray_menu(app: &AppHandle, recent_files: &[String]) -> tauri::Result<Menu<Wry>> {
+    let recent = Submenu::new(app, "Recent Files", !recent_files.is_empty())?;
+    for (index, path) in recent_files.iter().take(MAX_TRAY_RECENTS).enumerate() {
+        let filename = std::path::Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+        let id = format!("{}{}", RECENT_ID_PREFIX, index);
+        recent.append(&MenuItem::with_id(app, id, filename, true, None::<&str>)?)?;
+    }
+
+    Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, "tray-quick-note", "Quick Note", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &recent,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "tray-toggle-main", "Show/Hide VMark", true, None::<&str>)?,
+            &MenuItem::with_id(app, "tray-quit", "Quit VMark", true, None::<&str>)?,
+        ],
+    )
+}
+
+/// Create the tray icon. Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let menu = build_tray_menu(app, &[]).map_err(|e| e.to_string())?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("VMark")
+        .menu(&menu)
+        .show_menu_on_left_click(true);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Rebuild the tray menu after the recent files list changed.
+pub fn refresh_recent_files(app: &AppHandle, recent_files: &[String]) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app, recent_files) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => tracing::warn!("Failed to rebuild menu: {}", e),
+    }
+}
+
+/// Show the main document window, or hide it when it's already in front.
+fn toggle_main_window(app: &AppHandle) {
+    let window = app.get_webview_window("main").or_else(|| {
+        let mut labels: Vec<String> = app
+            .webview_windows()
+            .into_keys()
+            .filter(|label| quit::is_document_window_label(label))
+            .collect();
+        labels.sort();
+        labels.first().and_then(|label| app.get_webview_window(label))
+    });
+
+    let Some(window) = window else {
+        let _ = window_manager::create_main_window(app);
+        return;
+    };
+
+    let visible = window.is_visible().unwrap_or(false);
+    let focused = window.is_focused().unwrap_or(false);
+    if visible && focused {
+        let _ = window.hide();
+    } else {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Handle a tray menu item. Returns false if `id` isn't a tray item.
+pub fn handle_tray_menu_event(app: &AppHandle, id: &str) -> bool {
+    if !id.starts_with(TRAY_ID_PREFIX) {
+        return false;
+    }
+
+    if let Some(index) = id.strip_prefix(RECENT_ID_PREFIX) {
+        let path = index
+            .parse::<usize>()
+            .ok()
+            .and_then(menu::get_recent_file_path);
+        if let Some(path) = path {
+            let _ = window_manager::open_file_in_new_window(app.clone(), path);
+        }
+        return true;
+    }
+
+    match id {
+        "tray-quick-note" => {
+            if let Err(e) = quick_capture::show_capture_window(app) {
+                tracing::warn!("Failed to open quick capture: {}", e);
+            }
+        }
+        "tray-toggle-main" => toggle_main_window(app),
+        "tray-quit" => quit::start_quit(app),
+        _ => {}
+    }
+    true
+}
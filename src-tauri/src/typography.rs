@@ -0,0 +1,436 @@
+//! Smart Typography
+//!
+//! `smarten_typography` turns typewriter punctuation in Markdown into
+//! typographic punctuation, for exports and publishing:
+//!
+//! - straight quotes become the locale's quotes (“…” in English, „…“ in
+//!   German, « … » in French, 「…」 in Japanese), and apostrophes become ’
+//! - `---` becomes an em dash, `--` an en dash, `...` an ellipsis
+//! - French gets narrow no-break spaces inside guillemets and before
+//!   `;`, `:`, `!` and `?`
+//!
+//! The "straighten" mode does the reverse. Code (fenced, indented and
+//! inline), frontmatter, HTML tags, link targets and URLs are never touched,
+//! and neither are lines that are Markdown syntax made of dashes (thematic
+//! breaks, setext underlines, table separators).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use crate::language_detect;
+
+const NARROW_NBSP: char = '\u{202f}';
+const NBSP: char = '\u{a0}';
+
+/// Spans left alone inside a line: inline code, link targets, autolinks
+/// and HTML tags, bare URLs
+static PROTECTED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(`+)[^`]*?`+|\]\([^)]*\)|<[^>\s][^>]*>|https?://[^\s)>\]]+")
+        .expect("valid protected span regex")
+});
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TypographyMode {
+    #[default]
+    Smarten,
+    Straighten,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypographyResult {
+    pub content: String,
+    /// Characters or sequences replaced
+    pub replacements: usize,
+    /// Locale the quotes were chosen for
+    pub locale: String,
+}
+
+/// Quote characters of a locale
+struct Quotes {
+    double: (&'static str, &'static str),
+    single: (&'static str, &'static str),
+    /// French spacing: narrow no-break spaces inside guillemets and before
+    /// high punctuation
+    french_spacing: bool,
+}
+
+fn quotes_for(locale: &str) -> (&'static str, Quotes) {
+    let quotes = |code, double, single, french_spacing| {
+        (
+            code,
+            Quotes {
+                double,
+                single,
+                french_spacing,
+            },
+        )
+    };
+    match locale.split(['-', '_']).next().unwrap_or("") {
+        "de" => quotes("de", ("„", "“"), ("‚", "‘"), false),
+        "fr" => quotes("fr", ("«", "»"), ("“", "”"), true),
+        "es" => quotes("es", ("«", "»"), ("“", "”"), false),
+        "it" => quotes("it", ("«", "»"), ("“", "”"), false),
+        "ru" => quotes("ru", ("«", "»"), ("„", "“"), false),
+        "pl" => quotes("pl", ("„", "”"), ("«", "»"), false),
+        "ja" => quotes("ja", ("「", "」"), ("『", "』"), false),
+        "zh" => quotes("zh", ("“", "”"), ("‘", "’"), false),
+        _ => quotes("en", ("“", "”"), ("‘", "’"), false),
+    }
+}
+
+/// Locale to use: the one given, else the detected language of `content`,
+/// else English.
+fn resolve_locale(locale: Option<String>, content: &str) -> String {
+    locale
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .or_else(|| {
+            language_detect::detect(content)
+                .filter(|d| d.reliable)
+                .map(|d| d.code)
+        })
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// A quote here opens (rather than closes) when it starts the text or
+/// follows whitespace, opening punctuation, emphasis markers or a tag.
+fn opens(previous: Option<char>) -> bool {
+    match previous {
+        None => true,
+        Some(c) => {
+            c.is_whitespace()
+                || matches!(c, '(' | '[' | '{' | '—' | '–' | '-' | '/' | '*' | '_' | '>')
+        }
+    }
+}
+
+fn smarten_text(text: &str, previous: Option<char>, quotes: &Quotes, count: &mut usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let at = |i: usize| chars.get(i).copied();
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut previous = previous;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = at(i + 1);
+        // Replacement and how many characters it replaces
+        let (replacement, width): (String, usize) = match c {
+            // Exactly three dots or dashes; longer runs are left alone
+            '.' if previous != Some('.')
+                && next == Some('.')
+                && at(i + 2) == Some('.')
+                && at(i + 3) != Some('.') =>
+            {
+                ("…".into(), 3)
+            }
+            '-' if previous != Some('-')
+                && next == Some('-')
+                && at(i + 2) == Some('-')
+                && at(i + 3) != Some('-') =>
+            {
+                ("—".into(), 3)
+            }
+            '-' if next == Some('-') && at(i + 2) != Some('-') && previous != Some('-') => {
+                ("–".into(), 2)
+            }
+            '"' if opens(previous) => {
+                let mut quote = quotes.double.0.to_string();
+                if quotes.french_spacing && next.is_some_and(|n| !n.is_whitespace()) {
+                    quote.push(NARROW_NBSP);
+                }
+                (quote, 1)
+            }
+            '"' => {
+                let mut quote = String::new();
+                if quotes.french_spacing && previous != Some(NARROW_NBSP) {
+                    quote.push(NARROW_NBSP);
+                }
+                quote.push_str(quotes.double.1);
+                (quote, 1)
+            }
+            '\'' => {
+                let between_letters = previous.is_some_and(char::is_alphanumeric)
+                    && next.is_some_and(char::is_alphanumeric);
+                // Elided years: '90s
+                let elision = opens(previous) && next.is_some_and(|n| n.is_ascii_digit());
+                let quote = if between_letters || elision {
+                    "’"
+                } else if opens(previous) {
+                    quotes.single.0
+                } else if previous.is_some_and(char::is_alphanumeric) {
+                    // Possessive plural ("the dogs' bowls")
+                    "’"
+                } else {
+                    quotes.single.1
+                };
+                (quote.into(), 1)
+            }
+            // Only before punctuation that ends a word, so "a :b" stays
+            ' ' if quotes.french_spacing
+                && matches!(next, Some(';' | ':' | '!' | '?'))
+                && at(i + 2).is_none_or(|a| a.is_whitespace() || matches!(a, '!' | '?')) =>
+            {
+                (NARROW_NBSP.to_string(), 1)
+            }
+            _ => {
+                out.push(c);
+                previous = Some(c);
+                i += 1;
+                continue;
+            }
+        };
+        out.push_str(&replacement);
+        *count += 1;
+        previous = out.chars().last();
+        i += width;
+    }
+    out
+}
+
+fn straighten_text(text: &str, count: &mut usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let replacement = match c {
+            '“' | '”' | '„' | '‟' | '«' | '»' => Some("\""),
+            '‘' | '’' | '‚' | '‛' | '‹' | '›' => Some("'"),
+            '—' => Some("---"),
+            '–' => Some("--"),
+            '…' => Some("..."),
+            // French spacing goes with the guillemets and punctuation
+            NARROW_NBSP | NBSP => {
+                let next = chars.get(i + 1).copied();
+                let previous = i.checked_sub(1).map(|p| chars[p]);
+                if previous == Some('«') || next == Some('»') {
+                    Some("")
+                } else if matches!(next, Some(';' | ':' | '!' | '?')) {
+                    Some(" ")
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        match replacement {
+            Some(r) => {
+                out.push_str(r);
+                *count += 1;
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Lines that are Markdown syntax made of dashes: thematic breaks, setext
+/// underlines and table separator rows.
+fn is_dash_syntax(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.contains('-')
+        && trimmed
+            .chars()
+            .all(|c| matches!(c, '-' | ':' | '|' | ' ' | '*' | '_' | '='))
+}
+
+fn convert_line(line: &str, mode: TypographyMode, quotes: &Quotes, count: &mut usize) -> String {
+    if is_dash_syntax(line) {
+        return line.to_string();
+    }
+    let mut out = String::with_capacity(line.len());
+    let mut previous: Option<char> = None;
+    let mut last = 0;
+    let spans = PROTECTED_RE
+        .find_iter(line)
+        .map(|m| (m.start(), m.end()))
+        .chain(std::iter::once((line.len(), line.len())));
+    for (start, end) in spans {
+        let text = &line[last..start];
+        out.push_str(&match mode {
+            TypographyMode::Smarten => smarten_text(text, previous, quotes, count),
+            TypographyMode::Straighten => straighten_text(text, count),
+        });
+        out.push_str(&line[start..end]);
+        previous = out.chars().last();
+        last = end;
+    }
+    out
+}
+
+fn convert(content: &str, mode: TypographyMode, quotes: &Quotes) -> (String, usize) {
+    let mut count = 0;
+    let mut out = String::with_capacity(content.len() + 16);
+    let mut lines = content.split_inclusive('\n').peekable();
+
+    // Frontmatter
+    if lines.peek().is_some_and(|l| l.trim_end() == "---") {
+        out.push_str(lines.next().unwrap_or_default());
+        for line in lines.by_ref() {
+            out.push_str(line);
+            if line.trim_end() == "---" {
+                break;
+            }
+        }
+    }
+
+    let mut fence: Option<String> = None;
+    let mut in_math = false;
+    let mut previous_blank = true;
+    let mut in_indented = false;
+    for raw in lines {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let ending = &raw[line.len()..];
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = &fence {
+            if trimmed.starts_with(marker.as_str()) {
+                fence = None;
+            }
+            out.push_str(raw);
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let marker: String = trimmed
+                .chars()
+                .take_while(|&c| c == trimmed.as_bytes()[0] as char)
+                .collect();
+            fence = Some(marker);
+            out.push_str(raw);
+            continue;
+        }
+        if trimmed.starts_with("$$") {
+            in_math = !(in_math || trimmed.len() > 2 && trimmed.ends_with("$$"));
+            out.push_str(raw);
+            continue;
+        }
+        // An indented code block runs until a non-blank line that isn't indented
+        let blank = line.trim().is_empty();
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        in_indented = (in_indented || previous_blank) && (indented || (in_indented && blank));
+        previous_blank = blank;
+        if in_math || in_indented {
+            out.push_str(raw);
+            continue;
+        }
+        out.push_str(&convert_line(line, mode, quotes, &mut count));
+        out.push_str(ending);
+    }
+    (out, count)
+}
+
+/// Smarten (or, with `mode: "straighten"`, straighten) the punctuation of a
+/// Markdown document. `locale` picks the quote style (e.g. "de", "fr-CA");
+/// without it the document's detected language is used.
+#[tauri::command]
+pub async fn smarten_typography(
+    content: String,
+    locale: Option<String>,
+    mode: Option<TypographyMode>,
+) -> Result<TypographyResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (locale, quotes) = quotes_for(&resolve_locale(locale, &content));
+        let (content, replacements) = convert(&content, mode.unwrap_or_default(), &quotes);
+        TypographyResult {
+            content,
+            replacements,
+            locale: locale.to_string(),
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to convert typography: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smarten(content: &str, locale: &str) -> String {
+        convert(content, TypographyMode::Smarten, &quotes_for(locale).1).0
+    }
+
+    fn straighten(content: &str) -> String {
+        convert(content, TypographyMode::Straighten, &quotes_for("en").1).0
+    }
+
+    #[test]
+    fn test_english_quotes_and_apostrophes() {
+        assert_eq!(
+            smarten(
+                "\"Don't,\" she said, 'it's the '90s.' The dogs' bowls.",
+                "en"
+            ),
+            "“Don’t,” she said, ‘it’s the ’90s.’ The dogs’ bowls."
+        );
+        assert_eq!(smarten("(\"quoted\")", "en"), "(“quoted”)");
+    }
+
+    #[test]
+    fn test_dashes_and_ellipses() {
+        assert_eq!(
+            smarten("Wait... pages 10--20 --- or more....", "en"),
+            "Wait… pages 10–20 — or more...."
+        );
+    }
+
+    #[test]
+    fn test_locale_quotes() {
+        assert_eq!(smarten("Er sagte \"Hallo\".", "de-AT"), "Er sagte „Hallo“.");
+        assert_eq!(smarten("\"テスト\"", "ja"), "「テスト」");
+        assert_eq!(
+            smarten("Il a dit \"bonjour\" ! Vraiment ?", "fr"),
+            "Il a dit «\u{202f}bonjour\u{202f}»\u{202f}! Vraiment\u{202f}?"
+        );
+        assert_eq!(smarten("\"x\"", "tlh"), "“x”");
+    }
+
+    #[test]
+    fn test_code_and_markdown_syntax_untouched() {
+        let doc = "---\ntitle: \"Quoted\"\n---\nSay \"hi\" with `\"code\"` and [a link](http://x.com/a--b).\n\n```\nlet s = \"raw\";\n```\n\n    indented \"code\"\n\nText\n---\n| a | b |\n|---|:--:|\n<div class=\"x\">\"inside\"</div>\n";
+        let out = smarten(doc, "en");
+        assert!(out.starts_with("---\ntitle: \"Quoted\"\n---\nSay “hi” with `\"code\"` and [a link](http://x.com/a--b).\n"));
+        assert!(out.contains("let s = \"raw\";"));
+        assert!(out.contains("    indented \"code\""));
+        assert!(out.contains("Text\n---\n"));
+        assert!(out.contains("|---|:--:|"));
+        assert!(out.contains("<div class=\"x\">“inside”</div>"));
+    }
+
+    #[test]
+    fn test_indented_code_block_spans_lines() {
+        let doc = "text\n\n    a = \"x\"\n\n    b = \"y\"\n    c = \"z\"\nafter \"quoted\"\n";
+        assert_eq!(
+            smarten(doc, "en"),
+            "text\n\n    a = \"x\"\n\n    b = \"y\"\n    c = \"z\"\nafter “quoted”\n"
+        );
+    }
+
+    #[test]
+    fn test_straighten_reverses_smarten() {
+        let original = "\"Don't\" -- she said --- 'wait...'\n";
+        let smart = smarten(original, "en");
+        assert_ne!(smart, original);
+        assert_eq!(straighten(&smart), original);
+
+        let french = smarten("Il a dit \"oui\" ! Et toi ?", "fr");
+        assert_eq!(straighten(&french), "Il a dit \"oui\" ! Et toi ?");
+        assert_eq!(straighten("`“code”` „Hallo“"), "`“code”` \"Hallo\"");
+    }
+
+    #[test]
+    fn test_counts_and_locale_detection() {
+        let (_, quotes) = quotes_for("en");
+        let (_, count) = convert("\"a\" -- b...", TypographyMode::Smarten, &quotes);
+        assert_eq!(count, 4);
+        assert_eq!(resolve_locale(Some(" DE ".into()), ""), "de");
+        assert_eq!(
+            resolve_locale(
+                None,
+                "Das Wetter ist heute schön und wir gehen mit den Kindern in den Park."
+            ),
+            "de"
+        );
+        assert_eq!(resolve_locale(None, "12345"), "en");
+    }
+}
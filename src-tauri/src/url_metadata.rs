@@ -0,0 +1,549 @@
+//! URL Metadata
+//!
+//! Fetches a web page's title so pasting a bare URL can produce a
+//! `[Title](url)` link. Only the start of the page is downloaded: the title
+//! and `og:` tags live in `<head>`, and some pages are many MB.
+//!
+//! `fetch_link_preview` builds on the same fetch for link hover cards: it
+//! adds the description, site name, favicon and `og:image`. The images are
+//! downloaded next to a JSON record in `<app data>/link-previews/`, keyed by
+//! the URL's hash, so the webview never loads remote resources itself.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::rich_text::{attr, decode_entities};
+
+const REQUEST_TIMEOUT_SECS: u64 = 8;
+const MAX_REDIRECTS: usize = 5;
+
+/// Stop reading after this much of the page, even without `</head>`.
+const MAX_HEAD_BYTES: usize = 256 * 1024;
+
+/// Longer titles are cut at a character boundary.
+const MAX_TITLE_CHARS: usize = 200;
+
+pub(crate) fn parse_http_url(url: &str) -> Result<reqwest::Url, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {e}"))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        other => Err(format!("Unsupported URL scheme: {other}")),
+    }
+}
+
+pub(crate) fn make_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+        .user_agent(concat!("VMark/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))
+}
+
+/// The charset named in a `Content-Type` value or a `<meta charset>` tag.
+fn charset_label(text: &str) -> Option<&str> {
+    let lower = text.to_ascii_lowercase();
+    let start = lower.find("charset=")? + "charset=".len();
+    let rest = text[start..].trim_start_matches(['"', '\'']);
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|label| !label.is_empty())
+}
+
+/// Decode page bytes using the declared charset (UTF-8 when unknown).
+fn decode_page(bytes: &[u8], content_type: Option<&str>) -> String {
+    let sniff = String::from_utf8_lossy(&bytes[..bytes.len().min(2048)]);
+    let encoding = content_type
+        .and_then(charset_label)
+        .or_else(|| charset_label(&sniff))
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(bytes).0.into_owned()
+}
+
+/// Raw attribute strings of every `<tag …>` in `html`.
+fn tag_attrs<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    let open = format!("<{tag}");
+    let mut found = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find(&open) {
+        let start = pos + start + open.len();
+        let Some(end) = lower[start..].find('>') else {
+            break;
+        };
+        // `<link` must not match `<linkage>`
+        if lower[start..].starts_with(|c: char| c.is_ascii_whitespace() || c == '/') {
+            found.push(&html[start..start + end]);
+        }
+        pos = start + end;
+    }
+    found
+}
+
+/// `(name or property, content)` for each `<meta>` tag.
+pub(crate) fn meta_tags(html: &str) -> Vec<(String, String)> {
+    tag_attrs(html, "meta")
+        .into_iter()
+        .filter_map(|attrs| {
+            let key = attr(attrs, "property").or_else(|| attr(attrs, "name"))?;
+            Some((key.to_ascii_lowercase(), attr(attrs, "content")?))
+        })
+        .collect()
+}
+
+/// Content of the first meta tag among `keys`, in order of preference.
+pub(crate) fn meta_content(tags: &[(String, String)], keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| tags.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()))
+        .and_then(|value| clean_text(&value))
+}
+
+/// Collapse whitespace and cap the length; None if nothing is left.
+pub(crate) fn clean_text(text: &str) -> Option<String> {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    Some(match collapsed.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((cut, _)) => format!("{}…", collapsed[..cut].trim_end()),
+        None => collapsed,
+    })
+}
+
+fn title_element(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    clean_text(&decode_entities(&html[start..end]))
+}
+
+/// The page title, preferring `og:title` (which rarely carries a site suffix).
+pub(crate) fn extract_title(html: &str) -> Option<String> {
+    meta_content(&meta_tags(html), &["og:title", "twitter:title"]).or_else(|| title_element(html))
+}
+
+/// An HTML page (or its start) and the URL it was served from.
+pub(crate) struct HtmlPage {
+    /// Final URL after redirects, for resolving relative links
+    pub url: reqwest::Url,
+    pub html: String,
+}
+
+/// Download the start of an HTML page. None if the URL isn't HTML.
+pub(crate) async fn fetch_page_head(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+) -> Result<Option<HtmlPage>, String> {
+    fetch_html(client, url, MAX_HEAD_BYTES, true).await
+}
+
+/// Download up to `max_bytes` of an HTML page, stopping after `</head>`
+/// when `head_only`. None if the URL isn't HTML.
+pub(crate) async fn fetch_html(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    max_bytes: usize,
+    head_only: bool,
+) -> Result<Option<HtmlPage>, String> {
+    let mut resp = client
+        .get(url.clone())
+        .header(
+            reqwest::header::ACCEPT,
+            "text/html,application/xhtml+xml;q=0.9,*/*;q=0.1",
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {url}: {e}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("{url} returned {}", resp.status()));
+    }
+    let content_type = header_str(&resp, reqwest::header::CONTENT_TYPE);
+    if content_type
+        .as_deref()
+        .is_some_and(|ct| !ct.contains("html"))
+    {
+        return Ok(None);
+    }
+    let final_url = resp.url().clone();
+
+    let mut body = Vec::new();
+    while body.len() < max_bytes {
+        match resp
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read {url}: {e}"))?
+        {
+            Some(chunk) => body.extend_from_slice(&chunk),
+            None => break,
+        }
+        // Everything needed is in <head>; don't wait for the rest
+        if head_only && body.windows(7).any(|w| w.eq_ignore_ascii_case(b"</head>")) {
+            break;
+        }
+    }
+    Ok(Some(HtmlPage {
+        url: final_url,
+        html: decode_page(&body, content_type.as_deref()),
+    }))
+}
+
+fn header_str(resp: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    resp.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Title of the page at `url`, or None if it has none or isn't HTML.
+#[tauri::command]
+pub async fn fetch_url_title(url: String) -> Result<Option<String>, String> {
+    let url = parse_http_url(&url)?;
+    let client = make_client()?;
+    Ok(fetch_page_head(&client, &url)
+        .await?
+        .and_then(|page| extract_title(&page.html)))
+}
+
+// --- Link previews ---
+
+const PREVIEW_DIR: &str = "link-previews";
+
+/// Cached previews are refetched after a week.
+const PREVIEW_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+const MAX_FAVICON_BYTES: usize = 512 * 1024;
+const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Metadata for a link's hover card. Images are local files under app
+/// data, for use with `convertFileSrc`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+    pub favicon: Option<String>,
+    pub image: Option<String>,
+    /// Unix seconds
+    pub fetched_at: u64,
+}
+
+/// Page metadata before the images are downloaded.
+#[derive(Debug, Default, PartialEq)]
+struct PageMeta {
+    title: Option<String>,
+    description: Option<String>,
+    site_name: Option<String>,
+    favicon_url: Option<reqwest::Url>,
+    image_url: Option<reqwest::Url>,
+}
+
+fn extract_meta(html: &str, base: &reqwest::Url) -> PageMeta {
+    let tags = meta_tags(html);
+    let resolve = |href: String| {
+        base.join(href.trim())
+            .ok()
+            .filter(|u| matches!(u.scheme(), "http" | "https"))
+    };
+    PageMeta {
+        title: extract_title(html),
+        description: meta_content(
+            &tags,
+            &["og:description", "twitter:description", "description"],
+        ),
+        site_name: meta_content(&tags, &["og:site_name", "application-name"]),
+        favicon_url: favicon_href(html)
+            .and_then(resolve)
+            .or_else(|| base.join("/favicon.ico").ok()),
+        image_url: tags
+            .iter()
+            .find(|(k, _)| matches!(k.as_str(), "og:image" | "og:image:url" | "twitter:image"))
+            .and_then(|(_, v)| resolve(v.clone())),
+    }
+}
+
+/// `href` of the page's icon link, preferring `icon` over `apple-touch-icon`.
+fn favicon_href(html: &str) -> Option<String> {
+    let links: Vec<(String, String)> = tag_attrs(html, "link")
+        .into_iter()
+        .filter_map(|attrs| {
+            Some((
+                attr(attrs, "rel")?.to_ascii_lowercase(),
+                attr(attrs, "href")?,
+            ))
+        })
+        .collect();
+    let find = |wanted: &str| {
+        links
+            .iter()
+            .find(|(rel, _)| rel.split_whitespace().any(|r| r == wanted))
+            .map(|(_, href)| href.clone())
+    };
+    find("icon").or_else(|| find("apple-touch-icon"))
+}
+
+/// File extension for an image content type; None for anything else.
+fn image_extension(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+    Some(match mime.as_str() {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/avif" => "avif",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        _ => return None,
+    })
+}
+
+/// Download an image to `<stem>.<ext>`. None if it isn't an image, is
+/// larger than `max_bytes`, or can't be fetched.
+async fn download_image(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    stem: &Path,
+    max_bytes: usize,
+) -> Option<PathBuf> {
+    let mut resp = client.get(url.clone()).send().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let ext = image_extension(&header_str(&resp, reqwest::header::CONTENT_TYPE)?)?;
+    if resp
+        .content_length()
+        .is_some_and(|len| len > max_bytes as u64)
+    {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = resp.chunk().await.ok()? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > max_bytes {
+            return None;
+        }
+    }
+    let path = stem.with_extension(ext);
+    crate::app_paths::atomic_write_file(&path, &bytes).ok()?;
+    Some(path)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A cached preview that is fresh and whose images are still on disk.
+fn read_cached_preview(file: &Path) -> Option<LinkPreview> {
+    let preview: LinkPreview = serde_json::from_str(&fs::read_to_string(file).ok()?).ok()?;
+    let fresh = unix_now().saturating_sub(preview.fetched_at) < PREVIEW_TTL_SECS;
+    let files_exist = [&preview.favicon, &preview.image]
+        .into_iter()
+        .flatten()
+        .all(|path| Path::new(path).exists());
+    (fresh && files_exist).then_some(preview)
+}
+
+async fn build_preview(
+    url: &reqwest::Url,
+    cache_dir: &Path,
+    key: &str,
+) -> Result<LinkPreview, String> {
+    let client = make_client()?;
+    let mut preview = LinkPreview {
+        url: url.to_string(),
+        fetched_at: unix_now(),
+        ..Default::default()
+    };
+    // Not HTML (a PDF, an image…): nothing to show beyond the URL
+    let Some(page) = fetch_page_head(&client, url).await? else {
+        return Ok(preview);
+    };
+    let meta = extract_meta(&page.html, &page.url);
+    preview.url = page.url.to_string();
+    preview.title = meta.title;
+    preview.description = meta.description;
+    preview.site_name = meta.site_name;
+
+    let favicon = async {
+        let url = meta.favicon_url.as_ref()?;
+        download_image(
+            &client,
+            url,
+            &cache_dir.join(format!("{key}-favicon")),
+            MAX_FAVICON_BYTES,
+        )
+        .await
+    };
+    let image = async {
+        let url = meta.image_url.as_ref()?;
+        download_image(
+            &client,
+            url,
+            &cache_dir.join(format!("{key}-image")),
+            MAX_IMAGE_BYTES,
+        )
+        .await
+    };
+    let (favicon, image) = tokio::join!(favicon, image);
+    preview.favicon = favicon.map(|p| p.to_string_lossy().into_owned());
+    preview.image = image.map(|p| p.to_string_lossy().into_owned());
+    Ok(preview)
+}
+
+/// Title, description, favicon and preview image for a link. Results are
+/// cached under app data for a week; `refresh` refetches regardless.
+#[tauri::command]
+pub async fn fetch_link_preview(
+    app: AppHandle,
+    url: String,
+    refresh: Option<bool>,
+) -> Result<LinkPreview, String> {
+    let url = parse_http_url(&url)?;
+    let cache_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(PREVIEW_DIR);
+    let key = crate::watcher::content_hash(url.as_str().as_bytes());
+    let cache_file = cache_dir.join(format!("{key}.json"));
+
+    if !refresh.unwrap_or(false) {
+        if let Some(preview) = read_cached_preview(&cache_file) {
+            return Ok(preview);
+        }
+    }
+
+    fs::create_dir_all(&cache_dir)
+        .map_err(|e| format!("Failed to create {}: {}", cache_dir.display(), e))?;
+    let preview = build_preview(&url, &cache_dir, &key).await?;
+    let json = serde_json::to_string_pretty(&preview)
+        .map_err(|e| format!("Failed to serialize link preview: {e}"))?;
+    crate::app_paths::atomic_write_file(&cache_file, json.as_bytes())?;
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_http_urls_are_fetched() {
+        assert!(parse_http_url("https://example.com/a").is_ok());
+        assert!(parse_http_url(" http://example.com ").is_ok());
+        assert!(parse_http_url("file:///etc/passwd").is_err());
+        assert!(parse_http_url("not a url").is_err());
+    }
+
+    #[test]
+    fn prefers_og_title() {
+        let html = r#"<head><title>Post | Site</title><meta property="og:title" content="Post &amp; more"></head>"#;
+        assert_eq!(extract_title(html).as_deref(), Some("Post & more"));
+    }
+
+    #[test]
+    fn falls_back_to_title_element() {
+        let html = "<HTML><HEAD><TITLE lang=en>\n  A   long\n title </TITLE></HEAD>";
+        assert_eq!(extract_title(html).as_deref(), Some("A long title"));
+        assert_eq!(extract_title("<title>  </title>"), None);
+        assert_eq!(extract_title("<p>no head</p>"), None);
+    }
+
+    #[test]
+    fn reads_name_and_property_meta() {
+        let html = r#"<meta name="twitter:title" content="T"><meta charset="utf-8"><meta content="D" name="Description">"#;
+        let tags = meta_tags(html);
+        assert_eq!(
+            meta_content(&tags, &["og:title", "twitter:title"]).as_deref(),
+            Some("T")
+        );
+        assert_eq!(meta_content(&tags, &["description"]).as_deref(), Some("D"));
+    }
+
+    #[test]
+    fn tag_names_must_match_exactly() {
+        let html = r#"<metadata name="x" content="no"><meta/ name="y" content="yes">"#;
+        assert_eq!(meta_tags(html), vec![("y".to_string(), "yes".to_string())]);
+    }
+
+    #[test]
+    fn preview_metadata_resolves_relative_urls() {
+        let base = reqwest::Url::parse("https://example.com/blog/post").unwrap();
+        let html = r#"<head>
+            <meta property="og:site_name" content="Example">
+            <meta name="description" content="Plain">
+            <meta property="og:description" content="  Rich
+              description ">
+            <meta property="og:image" content="/img/card.png">
+            <link rel="apple-touch-icon" href="/touch.png">
+            <link rel="shortcut icon" href="icons/fav.ico">
+            <title>Post</title>
+        </head>"#;
+        let meta = extract_meta(html, &base);
+        assert_eq!(meta.title.as_deref(), Some("Post"));
+        assert_eq!(meta.description.as_deref(), Some("Rich description"));
+        assert_eq!(meta.site_name.as_deref(), Some("Example"));
+        assert_eq!(
+            meta.favicon_url.unwrap().as_str(),
+            "https://example.com/blog/icons/fav.ico"
+        );
+        assert_eq!(
+            meta.image_url.unwrap().as_str(),
+            "https://example.com/img/card.png"
+        );
+    }
+
+    #[test]
+    fn favicon_defaults_to_site_root() {
+        let base = reqwest::Url::parse("https://example.com/a/b").unwrap();
+        let meta = extract_meta("<title>x</title>", &base);
+        assert_eq!(
+            meta.favicon_url.unwrap().as_str(),
+            "https://example.com/favicon.ico"
+        );
+        assert_eq!(meta.image_url, None);
+        // Only http(s) image URLs are downloaded
+        let meta = extract_meta(
+            r#"<meta property="og:image" content="data:image/png;base64,AA">"#,
+            &base,
+        );
+        assert_eq!(meta.image_url, None);
+    }
+
+    #[test]
+    fn only_images_are_saved() {
+        assert_eq!(image_extension("image/png"), Some("png"));
+        assert_eq!(image_extension("Image/JPEG; charset=binary"), Some("jpg"));
+        assert_eq!(image_extension("image/vnd.microsoft.icon"), Some("ico"));
+        assert_eq!(image_extension("text/html"), None);
+    }
+
+    #[test]
+    fn long_titles_are_truncated() {
+        let title = clean_text(&"é".repeat(500)).unwrap();
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS + 1);
+        assert!(title.ends_with('…'));
+    }
+
+    #[test]
+    fn decodes_declared_charset() {
+        // "café" in Windows-1252
+        let bytes = b"<meta charset=\"windows-1252\"><title>caf\xe9</title>";
+        assert_eq!(
+            extract_title(&decode_page(bytes, None)).as_deref(),
+            Some("café")
+        );
+        let latin1 = b"<title>caf\xe9</title>";
+        let decoded = decode_page(latin1, Some("text/html; charset=ISO-8859-1"));
+        assert_eq!(extract_title(&decoded).as_deref(), Some("café"));
+        assert_eq!(charset_label("text/html"), None);
+    }
+}
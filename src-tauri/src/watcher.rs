@@ -1,14 +1,28 @@
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use serde::Serialize;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 /// Minimum interval between emitting events for the same path (debounce).
 const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
 
+/// Files larger than this are reported without a content digest.
+const MAX_DIGEST_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Watched files larger than this are compared by size and mtime only.
+const MAX_SNAPSHOT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// How long to hold one half of a rename waiting for its partner.
+const RENAME_CORRELATION_WINDOW: Duration = Duration::from_millis(150);
+
 /// Watchers keyed by watch_id (typically window label or unique identifier)
 static WATCHERS: Mutex<Option<HashMap<String, WatcherEntry>>> = Mutex::new(None);
 
@@ -19,7 +33,7 @@ struct WatcherEntry {
 
 /// File system change event with watch context.
 /// Includes watchId to scope events to their originating watcher.
-#[derive(Clone, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FsChangeEvent {
     /// Unique identifier for this watcher (window label)
     #[serde(rename = "watchId")]
@@ -31,6 +45,15 @@ pub struct FsChangeEvent {
     pub paths: Vec<String>,
     /// Event kind: "create", "modify", "remove", "rename"
     pub kind: String,
+    /// Old path for "rename" events (None if the source was outside the watch or unknown)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// New path for "rename" events (None if the target left the watch or is unknown)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// SHA-256 per regular file that exists after the change (path -> hex hash)
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub digests: HashMap<String, String>,
 }
 
 /// Map notify event kinds to simple string identifiers.
@@ -62,6 +85,16 @@ const IGNORED_DIRS: &[&str] = &[
     "__pycache__",
 ];
 
+/// Check whether a single path component should be ignored.
+fn is_ignored_name(name: &str, include_hidden: bool) -> bool {
+    // Skip known noisy directories
+    if IGNORED_DIRS.contains(&name) {
+        return true;
+    }
+    // Skip hidden directories/files (start with '.') unless opted in
+    !include_hidden && name.starts_with('.')
+}
+
 /// Check whether a filesystem path should be ignored by the watcher.
 ///
 /// Returns true if any path component is in the ignore list or starts with
@@ -69,20 +102,525 @@ const IGNORED_DIRS: &[&str] = &[
 /// events from tool metadata directories (e.g. Obsidian vaults) from
 /// flooding the frontend.
 fn should_ignore_path(path: &Path) -> bool {
-    for component in path.components() {
-        if let std::path::Component::Normal(name) = component {
-            let name_str = name.to_string_lossy();
-            // Skip known noisy directories
-            if IGNORED_DIRS.contains(&name_str.as_ref()) {
-                return true;
+    path.components().any(|component| match component {
+        std::path::Component::Normal(name) => is_ignored_name(&name.to_string_lossy(), false),
+        _ => false,
+    })
+}
+
+/// Per-watch ignore options passed from the frontend.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatchOptions {
+    /// Extra exclude patterns (workspace `excludeFolders`). Plain names match
+    /// any path component; patterns with `/` or wildcards match relative to the root.
+    pub exclude: Vec<String>,
+    /// Deliver events for dotfiles and dot-directories (noisy tool dirs stay ignored)
+    pub include_hidden: bool,
+    /// Also skip paths matched by the root's `.gitignore`
+    pub use_gitignore: bool,
+    /// Coalesce events over this window and emit them as one "fs:batch" event.
+    /// None or 0 emits each event immediately as "fs:changed".
+    pub batch_window_ms: Option<u64>,
+}
+
+/// Compiled ignore rules for one watch.
+struct WatchFilter {
+    root: PathBuf,
+    include_hidden: bool,
+    exclude: GlobSet,
+    gitignore: Option<Gitignore>,
+}
+
+impl WatchFilter {
+    fn new(root: &Path, options: &WatchOptions) -> Result<Self, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in options.exclude.iter().map(|p| p.trim().trim_end_matches('/')) {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut variants = vec![pattern.to_string(), format!("{pattern}/**")];
+            if !pattern.contains('/') {
+                variants.push(format!("**/{pattern}"));
+                variants.push(format!("**/{pattern}/**"));
+            }
+            for variant in variants {
+                let glob = Glob::new(&variant)
+                    .map_err(|e| format!("Invalid exclude pattern '{pattern}': {e}"))?;
+                builder.add(glob);
+            }
+        }
+        let exclude = builder
+            .build()
+            .map_err(|e| format!("Failed to build exclude set: {e}"))?;
+
+        let gitignore = if options.use_gitignore {
+            let mut gi = GitignoreBuilder::new(root);
+            // A missing .gitignore just yields an empty matcher
+            let _ = gi.add(root.join(".gitignore"));
+            gi.build().ok()
+        } else {
+            None
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            include_hidden: options.include_hidden,
+            exclude,
+            gitignore,
+        })
+    }
+
+    /// Whether events for this path should be dropped.
+    /// Only components below the watch root are checked, so watching a folder
+    /// that itself lives under a hidden directory still works. Paths outside
+    /// the root fall back to the built-in rules.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return should_ignore_path(path);
+        };
+        let hidden_or_noisy = rel.components().any(|component| match component {
+            std::path::Component::Normal(name) => {
+                is_ignored_name(&name.to_string_lossy(), self.include_hidden)
+            }
+            _ => false,
+        });
+        if hidden_or_noisy || self.exclude.is_match(rel) {
+            return true;
+        }
+        self.gitignore
+            .as_ref()
+            .is_some_and(|gi| gi.matched_path_or_any_parents(rel, path.is_dir()).is_ignore())
+    }
+}
+
+// ============================================================================
+// Timers
+// ============================================================================
+
+/// Deferred work: rename flushes and batch flushes.
+type TimerJob = Box<dyn FnOnce() + Send>;
+
+/// Queue feeding the single timer thread that runs every deferred flush
+static TIMERS: OnceLock<Sender<(Instant, TimerJob)>> = OnceLock::new();
+
+/// Run `job` once `delay` has passed, on the shared timer thread.
+fn schedule(delay: Duration, job: impl FnOnce() + Send + 'static) {
+    let timers = TIMERS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || run_timers(rx));
+        tx
+    });
+    let _ = timers.send((Instant::now() + delay, Box::new(job)));
+}
+
+fn run_timers(rx: Receiver<(Instant, TimerJob)>) {
+    let mut queue: Vec<(Instant, TimerJob)> = Vec::new();
+    loop {
+        let now = Instant::now();
+        let (mut due, waiting): (Vec<_>, Vec<_>) =
+            queue.drain(..).partition(|(at, _)| *at <= now);
+        queue = waiting;
+        due.sort_by_key(|(at, _)| *at);
+        for (_, job) in due {
+            job();
+        }
+
+        let received = match queue.iter().map(|(at, _)| *at).min() {
+            Some(next) => rx.recv_timeout(next.saturating_duration_since(Instant::now())),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(timer) => queue.push(timer),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+// ============================================================================
+// Rename correlation
+// ============================================================================
+
+/// One side of a rename (or a remove that may turn out to be a move).
+#[derive(Debug, Clone)]
+struct RenameHalf {
+    path: PathBuf,
+    /// OS cookie linking From/To halves (inotify, Windows); None on FSEvents
+    tracker: Option<usize>,
+    /// True for a plain remove event (only pairs with a create of the same file name)
+    is_remove: bool,
+    at: Instant,
+}
+
+/// What a notify event means for rename pairing.
+#[derive(Debug, PartialEq)]
+enum RenameSignal {
+    /// Both paths known in one event: (from, to)
+    Both(PathBuf, PathBuf),
+    /// Old path; hold it until the new path shows up
+    From(PathBuf),
+    /// New path; look for a held old path
+    To(PathBuf),
+    /// Removal; hold it in case a create of the same name follows (split move)
+    Remove(PathBuf),
+    /// Creation; may complete a held removal
+    Create(PathBuf),
+    /// Not rename-related
+    None,
+}
+
+fn classify_rename(event: &Event) -> RenameSignal {
+    let first = || event.paths.first().cloned();
+    match &event.kind {
+        EventKind::Modify(ModifyKind::Name(mode)) => match mode {
+            RenameMode::Both if event.paths.len() >= 2 => {
+                RenameSignal::Both(event.paths[0].clone(), event.paths[1].clone())
+            }
+            RenameMode::From => first().map_or(RenameSignal::None, RenameSignal::From),
+            RenameMode::To => first().map_or(RenameSignal::None, RenameSignal::To),
+            // FSEvents reports both sides as Any: the side that still exists is the target
+            _ => match first() {
+                Some(path) if path.exists() => RenameSignal::To(path),
+                Some(path) => RenameSignal::From(path),
+                None => RenameSignal::None,
+            },
+        },
+        EventKind::Remove(_) => first().map_or(RenameSignal::None, RenameSignal::Remove),
+        EventKind::Create(_) => first().map_or(RenameSignal::None, RenameSignal::Create),
+        _ => RenameSignal::None,
+    }
+}
+
+/// Held rename halves per watch.
+#[derive(Default)]
+struct RenameCorrelator {
+    pending: Vec<RenameHalf>,
+}
+
+impl RenameCorrelator {
+    fn hold(&mut self, half: RenameHalf) {
+        self.pending.push(half);
+    }
+
+    /// Find and remove the held half that pairs with a new path.
+    /// Tracker matches win; otherwise the most recent unexpired rename half,
+    /// or for creates, a removal of the same file name.
+    fn take_partner(
+        &mut self,
+        to: &Path,
+        tracker: Option<usize>,
+        from_create: bool,
+        now: Instant,
+    ) -> Option<RenameHalf> {
+        let fresh = |h: &RenameHalf| now.duration_since(h.at) < RENAME_CORRELATION_WINDOW;
+
+        if let Some(t) = tracker {
+            if let Some(i) = self.pending.iter().position(|h| h.tracker == Some(t)) {
+                return Some(self.pending.remove(i));
+            }
+        }
+
+        let index = self.pending.iter().rposition(|h| {
+            if !fresh(h) || h.path == to {
+                return false;
+            }
+            if from_create || h.is_remove {
+                // Split move: only pair a remove + create of the same file name
+                from_create && h.is_remove && h.path.file_name() == to.file_name()
+            } else {
+                true
+            }
+        })?;
+        Some(self.pending.remove(index))
+    }
+
+    /// Remove and return halves older than the correlation window.
+    fn drain_expired(&mut self, now: Instant) -> Vec<RenameHalf> {
+        let (expired, keep) = self
+            .pending
+            .drain(..)
+            .partition(|h| now.duration_since(h.at) >= RENAME_CORRELATION_WINDOW);
+        self.pending = keep;
+        expired
+    }
+}
+
+/// Held rename halves keyed by watch_id
+static PENDING_RENAMES: Mutex<Option<HashMap<String, RenameCorrelator>>> = Mutex::new(None);
+
+fn emit_fs_event(
+    app: &AppHandle,
+    watch_id: &str,
+    root_path: &str,
+    kind: &str,
+    from: Option<&Path>,
+    to: Option<&Path>,
+) {
+    let from = from.map(|p| p.to_string_lossy().to_string());
+    let to = to.map(|p| p.to_string_lossy().to_string());
+    let paths = from.iter().chain(to.iter()).cloned().collect();
+    let digests = collect_digests(to.iter());
+    let payload = FsChangeEvent {
+        watch_id: watch_id.to_string(),
+        root_path: root_path.to_string(),
+        paths,
+        kind: kind.to_string(),
+        from,
+        to,
+        digests,
+    };
+    emit_change(app, payload);
+}
+
+/// Emit halves whose partner never arrived: lone renames as "rename" with one
+/// side, held removals as plain "remove".
+fn flush_expired_renames(app: &AppHandle, watch_id: &str, root_path: &str) {
+    let expired = {
+        let Ok(mut guard) = PENDING_RENAMES.lock() else {
+            return;
+        };
+        match guard.as_mut().and_then(|m| m.get_mut(watch_id)) {
+            Some(correlator) => correlator.drain_expired(Instant::now()),
+            None => return,
+        }
+    };
+    for half in expired {
+        if half.is_remove {
+            emit_fs_event(app, watch_id, root_path, "remove", Some(&half.path), None);
+        } else {
+            emit_fs_event(app, watch_id, root_path, "rename", Some(&half.path), None);
+        }
+    }
+}
+
+/// Hold a rename half and schedule a flush in case no partner arrives.
+fn hold_rename_half(app: &AppHandle, watch_id: &str, root_path: &str, half: RenameHalf) {
+    if let Ok(mut guard) = PENDING_RENAMES.lock() {
+        guard
+            .get_or_insert_with(HashMap::new)
+            .entry(watch_id.to_string())
+            .or_default()
+            .hold(half);
+    }
+
+    let app = app.clone();
+    let watch_id = watch_id.to_string();
+    let root_path = root_path.to_string();
+    schedule(RENAME_CORRELATION_WINDOW, move || {
+        flush_expired_renames(&app, &watch_id, &root_path);
+    });
+}
+
+/// Try to complete a held half with a new path. Returns true if a rename was emitted.
+fn complete_rename(
+    app: &AppHandle,
+    watch_id: &str,
+    root_path: &str,
+    to: &Path,
+    tracker: Option<usize>,
+    from_create: bool,
+) -> bool {
+    let partner = PENDING_RENAMES.lock().ok().and_then(|mut guard| {
+        guard
+            .as_mut()
+            .and_then(|m| m.get_mut(watch_id))
+            .and_then(|c| c.take_partner(to, tracker, from_create, Instant::now()))
+    });
+    match partner {
+        Some(from) => {
+            emit_fs_event(app, watch_id, root_path, "rename", Some(&from.path), Some(to));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Handle the rename-related part of an event. Returns true if the event was consumed.
+fn handle_rename_event(
+    app: &AppHandle,
+    watch_id: &str,
+    root_path: &str,
+    filter: &WatchFilter,
+    event: &Event,
+) -> bool {
+    let tracker = event.tracker();
+    let now = Instant::now();
+    let visible = |p: &Path| !filter.is_ignored(p);
+
+    match classify_rename(event) {
+        RenameSignal::Both(from, to) => {
+            let from = visible(&from).then_some(from);
+            let to = visible(&to).then_some(to);
+            if from.is_some() || to.is_some() {
+                emit_fs_event(app, watch_id, root_path, "rename", from.as_deref(), to.as_deref());
+            }
+            true
+        }
+        RenameSignal::From(path) => {
+            if visible(&path) {
+                hold_rename_half(
+                    app,
+                    watch_id,
+                    root_path,
+                    RenameHalf { path, tracker, is_remove: false, at: now },
+                );
+            }
+            true
+        }
+        RenameSignal::To(path) => {
+            if visible(&path) && !complete_rename(app, watch_id, root_path, &path, tracker, false) {
+                emit_fs_event(app, watch_id, root_path, "rename", None, Some(&path));
             }
-            // Skip hidden directories/files (start with '.')
-            if name_str.starts_with('.') {
-                return true;
+            true
+        }
+        RenameSignal::Remove(path) => {
+            if visible(&path) {
+                hold_rename_half(
+                    app,
+                    watch_id,
+                    root_path,
+                    RenameHalf { path, tracker, is_remove: true, at: now },
+                );
             }
+            true
+        }
+        RenameSignal::Create(path) => {
+            visible(&path) && complete_rename(app, watch_id, root_path, &path, tracker, true)
+        }
+        RenameSignal::None => false,
+    }
+}
+
+// ============================================================================
+// Batching
+// ============================================================================
+
+/// Number of raw events per kind folded into a batch.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FsChangeCounts {
+    pub create: usize,
+    pub modify: usize,
+    pub remove: usize,
+    pub rename: usize,
+}
+
+/// Aggregated changes for one watch over a coalescing window.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FsBatchEvent {
+    pub watch_id: String,
+    pub root_path: String,
+    /// One merged change per kind (deduplicated paths); renames stay individual
+    /// so their from/to pairing is preserved
+    pub changes: Vec<FsChangeEvent>,
+    pub counts: FsChangeCounts,
+    /// Total raw events folded into this batch
+    pub total: usize,
+}
+
+/// Changes collected for one watch during the current window.
+#[derive(Default)]
+struct BatchCoalescer {
+    changes: Vec<FsChangeEvent>,
+    counts: FsChangeCounts,
+}
+
+impl BatchCoalescer {
+    /// Add an event. Returns true if this is the first event of a new batch.
+    fn push(&mut self, event: FsChangeEvent) -> bool {
+        let first = self.changes.is_empty();
+        match event.kind.as_str() {
+            "create" => self.counts.create += 1,
+            "modify" => self.counts.modify += 1,
+            "remove" => self.counts.remove += 1,
+            _ => self.counts.rename += 1,
+        }
+
+        if event.kind == "rename" {
+            self.changes.push(event);
+            return first;
+        }
+
+        match self.changes.iter_mut().find(|c| c.kind == event.kind) {
+            Some(existing) => {
+                for path in event.paths {
+                    if !existing.paths.contains(&path) {
+                        existing.paths.push(path);
+                    }
+                }
+                // Later digests describe the newest content
+                existing.digests.extend(event.digests);
+            }
+            None => self.changes.push(event),
+        }
+        first
+    }
+
+    fn into_event(self, watch_id: &str, root_path: &str) -> FsBatchEvent {
+        let c = &self.counts;
+        let total = c.create + c.modify + c.remove + c.rename;
+        FsBatchEvent {
+            watch_id: watch_id.to_string(),
+            root_path: root_path.to_string(),
+            changes: self.changes,
+            counts: self.counts,
+            total,
         }
     }
-    false
+}
+
+/// Coalescing window per watch_id (only watches with batching enabled)
+static BATCH_WINDOWS: Mutex<Option<HashMap<String, Duration>>> = Mutex::new(None);
+
+/// Open batches keyed by watch_id
+static PENDING_BATCHES: Mutex<Option<HashMap<String, BatchCoalescer>>> = Mutex::new(None);
+
+fn batch_window(watch_id: &str) -> Option<Duration> {
+    BATCH_WINDOWS
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().and_then(|m| m.get(watch_id).copied()))
+}
+
+/// Emit a change now, or add it to the watch's open batch when batching is on.
+fn emit_change(app: &AppHandle, event: FsChangeEvent) {
+    crate::task_index::apply_fs_change(&event);
+    crate::file_finder::apply_fs_change(&event);
+    crate::embeddings::apply_fs_change(&event);
+    crate::link_index::apply_fs_change(&event);
+    crate::preview_server::apply_fs_change(app, &event);
+
+    let Some(window) = batch_window(&event.watch_id) else {
+        let _ = app.emit("fs:changed", event);
+        return;
+    };
+
+    let watch_id = event.watch_id.clone();
+    let root_path = event.root_path.clone();
+    let started = match PENDING_BATCHES.lock() {
+        Ok(mut guard) => guard
+            .get_or_insert_with(HashMap::new)
+            .entry(watch_id.clone())
+            .or_default()
+            .push(event),
+        Err(_) => return,
+    };
+
+    if started {
+        let app = app.clone();
+        schedule(window, move || flush_batch(&app, &watch_id, &root_path));
+    }
+}
+
+fn flush_batch(app: &AppHandle, watch_id: &str, root_path: &str) {
+    let batch = PENDING_BATCHES
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.as_mut().and_then(|m| m.remove(watch_id)));
+    if let Some(batch) = batch {
+        let _ = app.emit("fs:batch", batch.into_event(watch_id, root_path));
+    }
 }
 
 /// Per-path debounce state to suppress duplicate events from macOS FSEvents.
@@ -91,11 +629,22 @@ static LAST_EMITTED: Mutex<Option<HashMap<(String, String), Instant>>> = Mutex::
 
 /// Handle a notify event and emit it to the frontend.
 /// Deduplicates events for the same path within DEBOUNCE_INTERVAL.
-fn handle_event(app: &AppHandle, watch_id: &str, root_path: &str, event: Event) {
+fn handle_event(
+    app: &AppHandle,
+    watch_id: &str,
+    root_path: &str,
+    filter: &WatchFilter,
+    event: Event,
+) {
     let Some(kind_str) = event_kind_to_string(&event.kind) else {
         return;
     };
 
+    // Renames (and removes that may be split moves) are paired before emitting
+    if handle_rename_event(app, watch_id, root_path, filter, &event) {
+        return;
+    }
+
     let now = Instant::now();
 
     // Collect paths, filtering ignored dirs and those within the debounce window
@@ -105,7 +654,7 @@ fn handle_event(app: &AppHandle, watch_id: &str, root_path: &str, event: Event)
     let paths: Vec<String> = event
         .paths
         .iter()
-        .filter(|p| !should_ignore_path(p))
+        .filter(|p| !filter.is_ignored(p))
         .filter_map(|p| {
             let path_str = p.to_string_lossy().to_string();
             let key = (watch_id.to_string(), path_str.clone());
@@ -126,14 +675,22 @@ fn handle_event(app: &AppHandle, watch_id: &str, root_path: &str, event: Event)
         return;
     }
 
+    let digests = if kind_str == "remove" {
+        HashMap::new()
+    } else {
+        collect_digests(paths.iter())
+    };
     let payload = FsChangeEvent {
         watch_id: watch_id.to_string(),
         root_path: root_path.to_string(),
         paths,
         kind: kind_str.to_string(),
+        from: None,
+        to: None,
+        digests,
     };
 
-    let _ = app.emit("fs:changed", payload);
+    emit_change(app, payload);
 }
 
 /// Start watching a directory.
@@ -142,8 +699,14 @@ fn handle_event(app: &AppHandle, watch_id: &str, root_path: &str, event: Event)
 /// * `app` - Tauri app handle for emitting events
 /// * `watch_id` - Unique identifier for this watcher (typically window label)
 /// * `path` - Directory path to watch recursively
+/// * `options` - Exclude patterns, hidden-file and .gitignore handling (defaults if omitted)
 #[tauri::command]
-pub fn start_watching(app: AppHandle, watch_id: String, path: String) -> Result<(), String> {
+pub fn start_watching(
+    app: AppHandle,
+    watch_id: String,
+    path: String,
+    options: Option<WatchOptions>,
+) -> Result<(), String> {
     let watch_path = Path::new(&path);
     if !watch_path.exists() {
         return Err(format!("Path does not exist: {path}"));
@@ -152,6 +715,8 @@ pub fn start_watching(app: AppHandle, watch_id: String, path: String) -> Result<
     // Stop any existing watcher for this watch_id first
     stop_watching(watch_id.clone())?;
 
+    let options = options.unwrap_or_default();
+    let filter = WatchFilter::new(watch_path, &options)?;
     let app_handle = app.clone();
     let watch_id_clone = watch_id.clone();
     let root_path_clone = path.clone();
@@ -159,7 +724,7 @@ pub fn start_watching(app: AppHandle, watch_id: String, path: String) -> Result<
     let mut watcher = RecommendedWatcher::new(
         move |res: Result<Event, notify::Error>| {
             if let Ok(event) = res {
-                handle_event(&app_handle, &watch_id_clone, &root_path_clone, event);
+                handle_event(&app_handle, &watch_id_clone, &root_path_clone, &filter, event);
             }
         },
         Config::default(),
@@ -172,6 +737,13 @@ pub fn start_watching(app: AppHandle, watch_id: String, path: String) -> Result<
 
     let mut guard = WATCHERS.lock().map_err(|e| format!("Lock error: {e}"))?;
     let watchers = guard.get_or_insert_with(HashMap::new);
+    if let Some(ms) = options.batch_window_ms.filter(|ms| *ms > 0) {
+        if let Ok(mut windows) = BATCH_WINDOWS.lock() {
+            windows
+                .get_or_insert_with(HashMap::new)
+                .insert(watch_id.clone(), Duration::from_millis(ms));
+        }
+    }
     watchers.insert(watch_id, WatcherEntry { _watcher: watcher });
 
     Ok(())
@@ -190,6 +762,21 @@ pub fn stop_watching(watch_id: String) -> Result<(), String> {
             map.retain(|(wid, _), _| wid != &watch_id);
         }
     }
+    if let Ok(mut pending_guard) = PENDING_RENAMES.lock() {
+        if let Some(map) = pending_guard.as_mut() {
+            map.remove(&watch_id);
+        }
+    }
+    if let Ok(mut windows) = BATCH_WINDOWS.lock() {
+        if let Some(map) = windows.as_mut() {
+            map.remove(&watch_id);
+        }
+    }
+    if let Ok(mut batches) = PENDING_BATCHES.lock() {
+        if let Some(map) = batches.as_mut() {
+            map.remove(&watch_id);
+        }
+    }
     Ok(())
 }
 
@@ -198,6 +785,12 @@ pub fn stop_watching(watch_id: String) -> Result<(), String> {
 pub fn stop_all_watchers() -> Result<(), String> {
     let mut guard = WATCHERS.lock().map_err(|e| format!("Lock error: {e}"))?;
     *guard = None;
+    if let Ok(mut windows) = BATCH_WINDOWS.lock() {
+        *windows = None;
+    }
+    if let Ok(mut batches) = PENDING_BATCHES.lock() {
+        *batches = None;
+    }
     Ok(())
 }
 
@@ -211,6 +804,204 @@ pub fn list_watchers() -> Result<Vec<String>, String> {
         .unwrap_or_default())
 }
 
+// ============================================================================
+// Per-file subscriptions
+// ============================================================================
+
+/// Single-file watchers keyed by file path
+static FILE_WATCHERS: Mutex<Option<HashMap<String, FileWatchEntry>>> = Mutex::new(None);
+
+struct FileWatchEntry {
+    /// Stored to keep the watcher alive; dropping stops watching
+    _watcher: RecommendedWatcher,
+    /// Number of watch_file calls for this path (tabs in several windows)
+    subscribers: usize,
+}
+
+/// Payload for "file:changed"
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FileChangedEvent {
+    pub path: String,
+    /// Modification time (unix timestamp ms)
+    pub mtime: Option<i64>,
+    /// Size in bytes
+    pub size: u64,
+    /// SHA-256 of the file content (hex); None for files over `MAX_SNAPSHOT_BYTES`
+    pub hash: Option<String>,
+}
+
+/// Payload for "file:removed"
+#[derive(Debug, Clone, Serialize)]
+pub struct FileRemovedEvent {
+    pub path: String,
+}
+
+/// Hex SHA-256 of a byte slice.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Read a file's current mtime, size and content hash. None if it can't be read.
+fn file_snapshot(path: &Path) -> Option<FileChangedEvent> {
+    file_snapshot_with_limit(path, MAX_SNAPSHOT_BYTES)
+}
+
+/// Like `file_snapshot`, hashing only files of at most `max_bytes`.
+fn file_snapshot_with_limit(path: &Path, max_bytes: u64) -> Option<FileChangedEvent> {
+    let meta = std::fs::metadata(path).ok().filter(|m| m.is_file())?;
+    let hash = if meta.len() <= max_bytes {
+        Some(content_hash(&std::fs::read(path).ok()?))
+    } else {
+        None
+    };
+    let mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64);
+    Some(FileChangedEvent {
+        path: path.to_string_lossy().to_string(),
+        mtime,
+        size: meta.len(),
+        hash,
+    })
+}
+
+/// What a single-file watcher should report.
+#[derive(Debug, PartialEq)]
+enum FileWatchUpdate {
+    Changed(FileChangedEvent),
+    Removed,
+}
+
+/// Digest of a regular file, or None for directories, missing or oversized files.
+fn file_digest(path: &Path) -> Option<String> {
+    let meta = std::fs::metadata(path).ok()?;
+    if !meta.is_file() || meta.len() > MAX_DIGEST_BYTES {
+        return None;
+    }
+    std::fs::read(path).ok().map(|bytes| content_hash(&bytes))
+}
+
+/// Digests for the given paths that are readable regular files.
+fn collect_digests<'a>(paths: impl Iterator<Item = &'a String>) -> HashMap<String, String> {
+    paths
+        .filter_map(|p| file_digest(Path::new(p)).map(|hash| (p.clone(), hash)))
+        .collect()
+}
+
+/// Decide what to emit for a watched file given the last state sent.
+/// Updates `last` and returns None when nothing observable changed.
+fn next_file_state(
+    last: &mut Option<FileChangedEvent>,
+    removed: &mut bool,
+    current: Option<FileChangedEvent>,
+) -> Option<FileWatchUpdate> {
+    match current {
+        Some(snapshot) => {
+            *removed = false;
+            if last.as_ref() == Some(&snapshot) {
+                return None;
+            }
+            *last = Some(snapshot.clone());
+            Some(FileWatchUpdate::Changed(snapshot))
+        }
+        None if !*removed => {
+            *removed = true;
+            *last = None;
+            Some(FileWatchUpdate::Removed)
+        }
+        None => None,
+    }
+}
+
+/// Watch a single file and emit "file:changed" (with mtime and hash) or
+/// "file:removed". The parent directory is watched non-recursively so
+/// atomic saves that replace the file are still seen.
+#[tauri::command]
+pub fn watch_file(app: AppHandle, path: String) -> Result<(), String> {
+    let mut guard = FILE_WATCHERS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    let watchers = guard.get_or_insert_with(HashMap::new);
+    if let Some(entry) = watchers.get_mut(&path) {
+        entry.subscribers += 1;
+        return Ok(());
+    }
+
+    let file_path = PathBuf::from(&path);
+    let parent = file_path
+        .parent()
+        .filter(|p| p.is_dir())
+        .ok_or_else(|| format!("Parent directory does not exist: {path}"))?
+        .to_path_buf();
+
+    let target = file_path.clone();
+    let mut last = file_snapshot(&file_path);
+    let mut removed = last.is_none();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<Event, notify::Error>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if !event.paths.iter().any(|p| p == &target) {
+                return;
+            }
+            let current = file_snapshot(&target);
+            match next_file_state(&mut last, &mut removed, current) {
+                Some(FileWatchUpdate::Changed(changed)) => {
+                    let _ = app.emit("file:changed", changed);
+                }
+                Some(FileWatchUpdate::Removed) => {
+                    let _ = app.emit(
+                        "file:removed",
+                        FileRemovedEvent {
+                            path: target.to_string_lossy().to_string(),
+                        },
+                    );
+                }
+                None => {}
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create watcher: {e}"))?;
+
+    watcher
+        .watch(&parent, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch path: {e}"))?;
+
+    watchers.insert(
+        path,
+        FileWatchEntry {
+            _watcher: watcher,
+            subscribers: 1,
+        },
+    );
+    Ok(())
+}
+
+/// Drop one subscription for a file; the watcher stops when none remain.
+#[tauri::command]
+pub fn unwatch_file(path: String) -> Result<(), String> {
+    let mut guard = FILE_WATCHERS.lock().map_err(|e| format!("Lock error: {e}"))?;
+    if let Some(watchers) = guard.as_mut() {
+        if let Some(entry) = watchers.get_mut(&path) {
+            entry.subscribers = entry.subscribers.saturating_sub(1);
+            if entry.subscribers == 0 {
+                watchers.remove(&path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compute the SHA-256 (hex) of a file's content, matching the digests in
+/// watcher events, so the frontend can tell real edits from mtime-only touches.
+#[tauri::command]
+pub fn compute_file_digest(path: String) -> Result<String, String> {
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    Ok(content_hash(&bytes))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +1086,9 @@ mod tests {
             root_path: "/Users/test".to_string(),
             paths: vec!["/Users/test/file.md".to_string()],
             kind: "modify".to_string(),
+            from: None,
+            to: None,
+            digests: HashMap::new(),
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -302,4 +1096,335 @@ mod tests {
         assert!(json.contains("\"rootPath\":\"/Users/test\""));
         assert!(json.contains("\"kind\":\"modify\""));
     }
+
+    fn half(path: &str, tracker: Option<usize>, is_remove: bool, at: Instant) -> RenameHalf {
+        RenameHalf {
+            path: PathBuf::from(path),
+            tracker,
+            is_remove,
+            at,
+        }
+    }
+
+    #[test]
+    fn test_classify_rename_both() {
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/w/a.md"))
+            .add_path(PathBuf::from("/w/b.md"));
+        assert_eq!(
+            classify_rename(&event),
+            RenameSignal::Both(PathBuf::from("/w/a.md"), PathBuf::from("/w/b.md"))
+        );
+    }
+
+    #[test]
+    fn test_classify_rename_any_uses_existence() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("new.md");
+        std::fs::write(&existing, "").unwrap();
+        let gone = dir.path().join("old.md");
+
+        let to = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Any))).add_path(existing.clone());
+        let from = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Any))).add_path(gone.clone());
+        assert_eq!(classify_rename(&to), RenameSignal::To(existing));
+        assert_eq!(classify_rename(&from), RenameSignal::From(gone));
+    }
+
+    #[test]
+    fn test_correlator_pairs_by_tracker() {
+        let now = Instant::now();
+        let mut c = RenameCorrelator::default();
+        c.hold(half("/w/a.md", Some(1), false, now));
+        c.hold(half("/w/x.md", Some(2), false, now));
+
+        let partner = c.take_partner(Path::new("/w/b.md"), Some(1), false, now).unwrap();
+        assert_eq!(partner.path, PathBuf::from("/w/a.md"));
+        assert_eq!(c.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_correlator_pairs_most_recent_without_tracker() {
+        let now = Instant::now();
+        let mut c = RenameCorrelator::default();
+        c.hold(half("/w/a.md", None, false, now));
+        c.hold(half("/w/c.md", None, false, now));
+
+        let partner = c.take_partner(Path::new("/w/d.md"), None, false, now).unwrap();
+        assert_eq!(partner.path, PathBuf::from("/w/c.md"));
+    }
+
+    #[test]
+    fn test_correlator_split_move_needs_same_name() {
+        let now = Instant::now();
+        let mut c = RenameCorrelator::default();
+        c.hold(half("/w/a.md", None, true, now));
+
+        assert!(c.take_partner(Path::new("/w/b.md"), None, true, now).is_none());
+        let partner = c.take_partner(Path::new("/w/sub/a.md"), None, true, now).unwrap();
+        assert_eq!(partner.path, PathBuf::from("/w/a.md"));
+    }
+
+    #[test]
+    fn test_correlator_ignores_expired_and_drains() {
+        let now = Instant::now();
+        let old = now - RENAME_CORRELATION_WINDOW - Duration::from_millis(1);
+        let mut c = RenameCorrelator::default();
+        c.hold(half("/w/a.md", None, false, old));
+        c.hold(half("/w/b.md", None, true, now));
+
+        assert!(c.take_partner(Path::new("/w/z.md"), None, false, now).is_none());
+        let expired = c.drain_expired(now);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].path, PathBuf::from("/w/a.md"));
+        assert_eq!(c.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_rename_event_serialization() {
+        let event = FsChangeEvent {
+            watch_id: "main".to_string(),
+            root_path: "/w".to_string(),
+            paths: vec!["/w/a.md".to_string(), "/w/b.md".to_string()],
+            kind: "rename".to_string(),
+            from: Some("/w/a.md".to_string()),
+            to: Some("/w/b.md".to_string()),
+            digests: HashMap::new(),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"from\":\"/w/a.md\""));
+        assert!(json.contains("\"to\":\"/w/b.md\""));
+    }
+
+    fn filter(root: &Path, options: WatchOptions) -> WatchFilter {
+        WatchFilter::new(root, &options).unwrap()
+    }
+
+    #[test]
+    fn test_filter_defaults_match_builtin_rules() {
+        let f = filter(Path::new("/project"), WatchOptions::default());
+        assert!(f.is_ignored(Path::new("/project/.git/HEAD")));
+        assert!(f.is_ignored(Path::new("/project/.hidden/file.md")));
+        assert!(f.is_ignored(Path::new("/project/node_modules/pkg/a.md")));
+        assert!(!f.is_ignored(Path::new("/project/notes/a.md")));
+    }
+
+    #[test]
+    fn test_filter_only_checks_below_root() {
+        let f = filter(Path::new("/home/me/.notes/vault"), WatchOptions::default());
+        assert!(!f.is_ignored(Path::new("/home/me/.notes/vault/a.md")));
+    }
+
+    #[test]
+    fn test_filter_include_hidden() {
+        let options = WatchOptions {
+            include_hidden: true,
+            ..Default::default()
+        };
+        let f = filter(Path::new("/project"), options);
+        assert!(!f.is_ignored(Path::new("/project/.drafts/a.md")));
+        // Noisy tool directories stay ignored
+        assert!(f.is_ignored(Path::new("/project/.git/HEAD")));
+    }
+
+    #[test]
+    fn test_filter_exclude_patterns() {
+        let options = WatchOptions {
+            exclude: vec!["build".into(), "docs/generated/".into(), "*.tmp".into()],
+            ..Default::default()
+        };
+        let f = filter(Path::new("/project"), options);
+        assert!(f.is_ignored(Path::new("/project/build/out.md")));
+        assert!(f.is_ignored(Path::new("/project/sub/build/out.md")));
+        assert!(f.is_ignored(Path::new("/project/docs/generated/api.md")));
+        assert!(f.is_ignored(Path::new("/project/notes/x.tmp")));
+        assert!(!f.is_ignored(Path::new("/project/docs/guide.md")));
+        assert!(!f.is_ignored(Path::new("/project/builder.md")));
+    }
+
+    #[test]
+    fn test_filter_invalid_pattern_errors() {
+        let options = WatchOptions {
+            exclude: vec!["[".into()],
+            ..Default::default()
+        };
+        assert!(WatchFilter::new(Path::new("/project"), &options).is_err());
+    }
+
+    #[test]
+    fn test_filter_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "dist/\n*.log\n").unwrap();
+        std::fs::create_dir(dir.path().join("dist")).unwrap();
+
+        let without = filter(dir.path(), WatchOptions::default());
+        assert!(!without.is_ignored(&dir.path().join("dist/a.md")));
+
+        let with = filter(
+            dir.path(),
+            WatchOptions {
+                use_gitignore: true,
+                ..Default::default()
+            },
+        );
+        assert!(with.is_ignored(&dir.path().join("dist/a.md")));
+        assert!(with.is_ignored(&dir.path().join("notes/run.log")));
+        assert!(!with.is_ignored(&dir.path().join("notes/a.md")));
+    }
+
+    fn change(kind: &str, paths: &[&str]) -> FsChangeEvent {
+        FsChangeEvent {
+            watch_id: "main".to_string(),
+            root_path: "/w".to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            kind: kind.to_string(),
+            from: None,
+            to: None,
+            digests: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_timers_run_in_deadline_order() {
+        let (tx, rx) = mpsc::channel();
+        for (delay, label) in [(60, "late"), (0, "now"), (20, "soon")] {
+            let tx = tx.clone();
+            schedule(Duration::from_millis(delay), move || tx.send(label).unwrap());
+        }
+        let order: Vec<_> = (0..3)
+            .map(|_| rx.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect();
+        assert_eq!(order, ["now", "soon", "late"]);
+    }
+
+    #[test]
+    fn test_batch_merges_duplicate_paths_per_kind() {
+        let mut batch = BatchCoalescer::default();
+        assert!(batch.push(change("modify", &["/w/a.md"])));
+        assert!(!batch.push(change("modify", &["/w/a.md", "/w/b.md"])));
+        assert!(!batch.push(change("create", &["/w/c.md"])));
+
+        let event = batch.into_event("main", "/w");
+        assert_eq!(event.changes.len(), 2);
+        assert_eq!(event.changes[0].paths, vec!["/w/a.md", "/w/b.md"]);
+        assert_eq!(event.counts.modify, 2);
+        assert_eq!(event.counts.create, 1);
+        assert_eq!(event.total, 3);
+    }
+
+    #[test]
+    fn test_batch_keeps_renames_individual() {
+        let mut batch = BatchCoalescer::default();
+        let mut rename = change("rename", &["/w/a.md", "/w/b.md"]);
+        rename.from = Some("/w/a.md".to_string());
+        rename.to = Some("/w/b.md".to_string());
+        batch.push(rename.clone());
+        batch.push(rename);
+
+        let event = batch.into_event("main", "/w");
+        assert_eq!(event.changes.len(), 2);
+        assert_eq!(event.counts.rename, 2);
+        assert_eq!(event.changes[0].from.as_deref(), Some("/w/a.md"));
+    }
+
+    #[test]
+    fn test_batch_event_serialization() {
+        let mut batch = BatchCoalescer::default();
+        batch.push(change("remove", &["/w/a.md"]));
+        let json = serde_json::to_string(&batch.into_event("main", "/w")).unwrap();
+        assert!(json.contains("\"watchId\":\"main\""));
+        assert!(json.contains("\"counts\":{\"create\":0,\"modify\":0,\"remove\":1,\"rename\":0}"));
+        assert!(json.contains("\"total\":1"));
+    }
+
+    #[test]
+    fn test_file_snapshot_hash_and_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.md");
+        std::fs::write(&path, "hello").unwrap();
+
+        let snapshot = file_snapshot(&path).unwrap();
+        assert_eq!(
+            snapshot.hash.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert_eq!(snapshot.size, 5);
+        assert!(snapshot.mtime.is_some());
+        assert!(file_snapshot(&dir.path().join("missing.md")).is_none());
+        assert!(file_snapshot(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_file_snapshot_skips_hash_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.md");
+        std::fs::write(&path, "hello").unwrap();
+
+        let snapshot = file_snapshot_with_limit(&path, 4).unwrap();
+        assert_eq!(snapshot.hash, None);
+        assert_eq!(snapshot.size, 5);
+    }
+
+    #[test]
+    fn test_next_file_state_skips_unchanged() {
+        let snap = |hash: &str| FileChangedEvent {
+            path: "/w/a.md".to_string(),
+            mtime: Some(1),
+            size: 1,
+            hash: Some(hash.to_string()),
+        };
+        let mut last = Some(snap("a"));
+        let mut removed = false;
+
+        assert_eq!(next_file_state(&mut last, &mut removed, Some(snap("a"))), None);
+        assert_eq!(
+            next_file_state(&mut last, &mut removed, Some(snap("b"))),
+            Some(FileWatchUpdate::Changed(snap("b")))
+        );
+        // Removal is reported once
+        assert_eq!(next_file_state(&mut last, &mut removed, None), Some(FileWatchUpdate::Removed));
+        assert_eq!(next_file_state(&mut last, &mut removed, None), None);
+        // Recreated with the same content after removal is a change
+        assert_eq!(
+            next_file_state(&mut last, &mut removed, Some(snap("b"))),
+            Some(FileWatchUpdate::Changed(snap("b")))
+        );
+    }
+
+    #[test]
+    fn test_digests_skip_dirs_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.md");
+        std::fs::write(&file, "hello").unwrap();
+        let paths = [
+            file.to_string_lossy().to_string(),
+            dir.path().to_string_lossy().to_string(),
+            dir.path().join("gone.md").to_string_lossy().to_string(),
+        ];
+
+        let digests = collect_digests(paths.iter());
+        assert_eq!(digests.len(), 1);
+        assert_eq!(
+            digests.get(&paths[0]).map(String::as_str),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert_eq!(
+            compute_file_digest(paths[0].clone()).unwrap(),
+            digests[&paths[0]]
+        );
+        assert!(compute_file_digest(paths[2].clone()).is_err());
+    }
+
+    #[test]
+    fn test_batch_merges_digests() {
+        let mut batch = BatchCoalescer::default();
+        let mut first = change("modify", &["/w/a.md"]);
+        first.digests.insert("/w/a.md".into(), "old".into());
+        let mut second = change("modify", &["/w/a.md"]);
+        second.digests.insert("/w/a.md".into(), "new".into());
+        batch.push(first);
+        batch.push(second);
+
+        let event = batch.into_event("main", "/w");
+        assert_eq!(event.changes[0].digests["/w/a.md"], "new");
+    }
 }
@@ -0,0 +1,1261 @@
+//! WebDAV Sync
+//!
+//! Mirrors a workspace to a WebDAV folder (Nextcloud, ownCloud or any
+//! RFC 4918 server). Each sync compares both sides against a manifest of
+//! what the previous sync saw:
+//!
+//! - local changes are detected by mtime/size, confirmed by SHA-256
+//! - remote changes are detected by ETag (or Last-Modified)
+//! - a file changed on both sides with different content is a conflict: the
+//!   local file wins, and the remote version is written alongside it as
+//!   `name (conflict <timestamp>).ext` and synced like any other file
+//! - deletions propagate only when the other side is unchanged; locally
+//!   they go to the trash
+//!
+//! Per-workspace files: `.vmark/sync.json` (endpoint, username, interval)
+//! and `.vmark/sync-state.json` (the manifest). The password is kept in the
+//! OS keychain. Status is emitted as `sync:status`.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::app_paths;
+
+const SYNC_CONFIG_FILE: &str = "sync.json";
+const SYNC_STATE_FILE: &str = "sync-state.json";
+
+/// Keychain service for WebDAV passwords (account: `user@endpoint`)
+const KEYCHAIN_SERVICE: &str = "app.vmark.sync";
+
+/// Never synced, whatever the workspace excludes
+const ALWAYS_EXCLUDED: &[&str] = &[".vmark", ".git", ".DS_Store", "Thumbs.db"];
+
+const REQUEST_TIMEOUT_SECS: u64 = 60;
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/><d:getetag/><d:getlastmodified/><d:getcontentlength/></d:prop></d:propfind>"#;
+
+/// Per-workspace sync settings (`.vmark/sync.json`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    /// WebDAV folder URL, e.g. `https://cloud.example.com/remote.php/dav/files/me/Notes/`
+    pub endpoint: String,
+    pub username: String,
+    /// Background sync interval; 0 syncs only on demand
+    #[serde(default)]
+    pub interval_minutes: u32,
+}
+
+/// What the last sync saw for one file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+struct FileState {
+    hash: String,
+    local_mtime: i64,
+    size: u64,
+    remote_tag: String,
+}
+
+/// Manifest from the last sync (`.vmark/sync-state.json`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SyncState {
+    #[serde(default)]
+    files: BTreeMap<String, FileState>,
+    #[serde(default)]
+    last_sync: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LocalFile {
+    mtime: i64,
+    size: u64,
+    hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RemoteFile {
+    /// ETag, or Last-Modified when the server has no ETags
+    tag: String,
+    size: u64,
+}
+
+/// What to do with one path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncAction {
+    /// Unchanged on both sides
+    Keep,
+    Upload,
+    Download,
+    DeleteLocal,
+    DeleteRemote,
+    /// Changed on both sides: compare content, write a conflict copy if it differs
+    Resolve,
+    /// Deleted on both sides
+    Forget,
+}
+
+/// Result of a sync run
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSummary {
+    pub uploaded: usize,
+    pub downloaded: usize,
+    pub deleted_local: usize,
+    pub deleted_remote: usize,
+    /// Conflict copies written, relative to the workspace root
+    pub conflicts: Vec<String>,
+    /// Unix timestamp ms
+    pub finished_at: i64,
+}
+
+/// Payload for `sync:status`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub root_path: String,
+    /// "syncing", "done" or "failed"
+    pub state: &'static str,
+    pub summary: Option<SyncSummary>,
+    pub error: Option<String>,
+}
+
+/// Workspaces with a sync in progress
+static RUNNING: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Background sync loop of a workspace, shared by the windows showing it
+struct Schedule {
+    handle: tauri::async_runtime::JoinHandle<()>,
+    windows: HashSet<String>,
+}
+
+/// Background sync loops keyed by workspace root
+static SCHEDULES: Mutex<Option<HashMap<String, Schedule>>> = Mutex::new(None);
+
+// ============================================================================
+// Config and manifest
+// ============================================================================
+
+fn vmark_dir(root: &Path) -> PathBuf {
+    root.join(".vmark")
+}
+
+fn load_config(root: &Path) -> Result<Option<SyncConfig>, String> {
+    let path = vmark_dir(root).join(SYNC_CONFIG_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read sync config: {e}"))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse sync config: {e}"))
+}
+
+fn save_json<T: Serialize>(root: &Path, name: &str, value: &T) -> Result<(), String> {
+    let dir = vmark_dir(root);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create .vmark directory: {e}"))?;
+    let content =
+        serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize {name}: {e}"))?;
+    app_paths::atomic_write_file(&dir.join(name), content.as_bytes())
+}
+
+fn load_state(root: &Path) -> SyncState {
+    fs::read_to_string(vmark_dir(root).join(SYNC_STATE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SyncState>(&content).ok())
+        .map(|mut state| {
+            state.files.retain(|rel, _| is_safe_rel_path(rel));
+            state
+        })
+        .unwrap_or_default()
+}
+
+fn validate_config(config: &SyncConfig) -> Result<(), String> {
+    let url = reqwest::Url::parse(config.endpoint.trim())
+        .map_err(|e| format!("Invalid WebDAV URL: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported WebDAV URL scheme: {}", url.scheme()));
+    }
+    if config.username.trim().is_empty() {
+        return Err("WebDAV username is required".to_string());
+    }
+    Ok(())
+}
+
+fn keychain_entry(config: &SyncConfig) -> Result<keyring::Entry, String> {
+    let account = format!("{}@{}", config.username.trim(), config.endpoint.trim());
+    keyring::Entry::new(KEYCHAIN_SERVICE, &account).map_err(|e| format!("Keychain unavailable: {e}"))
+}
+
+fn load_password(config: &SyncConfig) -> Result<String, String> {
+    match keychain_entry(config)?.get_password() {
+        Ok(password) => Ok(password),
+        Err(keyring::Error::NoEntry) => Err("No WebDAV password saved for this workspace".to_string()),
+        Err(e) => Err(format!("Failed to read WebDAV password from keychain: {e}")),
+    }
+}
+
+// ============================================================================
+// Local side
+// ============================================================================
+
+fn is_excluded(rel_path: &str, excludes: &[String]) -> bool {
+    rel_path.split('/').any(|part| {
+        ALWAYS_EXCLUDED.contains(&part) || excludes.iter().any(|e| e == part)
+    })
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Files under `root`, keyed by `/`-separated relative path. Hashes are
+/// reused from the manifest when mtime and size are unchanged.
+fn scan_local(
+    root: &Path,
+    state: &SyncState,
+    excludes: &[String],
+) -> Result<BTreeMap<String, LocalFile>, String> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![(root.to_path_buf(), String::new())];
+
+    while let Some((dir, prefix)) = dirs.pop() {
+        let entries = fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let rel = format!("{prefix}{name}");
+            if is_excluded(&rel, excludes) {
+                continue;
+            }
+            // Symlinks are skipped: following them could leave the workspace
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                dirs.push((entry.path(), format!("{rel}/")));
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let metadata = entry
+                .metadata()
+                .map_err(|e| format!("Failed to stat {rel}: {e}"))?;
+            let (mtime, size) = (modified_ms(&metadata), metadata.len());
+            let hash = match state.files.get(&rel) {
+                Some(known) if known.local_mtime == mtime && known.size == size => known.hash.clone(),
+                _ => hash_bytes(&fs::read(entry.path()).map_err(|e| format!("Failed to read {rel}: {e}"))?),
+            };
+            files.insert(rel, LocalFile { mtime, size, hash });
+        }
+    }
+    Ok(files)
+}
+
+/// Whether `rel` is a plain relative path: no empty, `.` or `..` parts, no
+/// leading `/` and no drive or backslash tricks.
+fn is_safe_rel_path(rel: &str) -> bool {
+    !rel.is_empty()
+        && rel.split('/').all(|part| {
+            !part.is_empty() && part != "." && part != ".." && !part.contains(['\\', ':', '\0'])
+        })
+}
+
+/// Absolute path of `rel` inside `root`. Fails when `rel` isn't a plain
+/// relative path or resolves (through symlinks) outside the root.
+fn local_path(root: &Path, rel: &str) -> Result<PathBuf, String> {
+    if !is_safe_rel_path(rel) {
+        return Err(format!("Refusing unsafe sync path: {rel}"));
+    }
+    let path = rel.split('/').fold(root.to_path_buf(), |path, part| path.join(part));
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", root.display()))?;
+    // The file itself may not exist yet; check the nearest existing ancestor
+    let existing = path.ancestors().find(|p| p.exists()).unwrap_or(root);
+    let resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {e}", existing.display()))?;
+    if !resolved.starts_with(&canonical_root) {
+        return Err(format!("Refusing sync path outside the workspace: {rel}"));
+    }
+    Ok(path)
+}
+
+/// `notes/idea.md` → `notes/idea (conflict 2026-01-31 091200).md`
+fn conflict_path(rel: &str, stamp: &str) -> String {
+    let (dir, name) = rel.rsplit_once('/').map_or(("", rel), |(d, n)| (d, n));
+    let (stem, ext) = match name.rfind('.') {
+        Some(i) if i > 0 => (&name[..i], &name[i..]),
+        _ => (name, ""),
+    };
+    let file = format!("{stem} (conflict {stamp}){ext}");
+    if dir.is_empty() {
+        file
+    } else {
+        format!("{dir}/{file}")
+    }
+}
+
+// ============================================================================
+// Planning
+// ============================================================================
+
+/// Decide what to do with every path seen locally, remotely or in the manifest.
+fn plan_sync(
+    local: &BTreeMap<String, LocalFile>,
+    remote: &BTreeMap<String, RemoteFile>,
+    state: &SyncState,
+) -> BTreeMap<String, SyncAction> {
+    let paths: BTreeSet<&String> = local.keys().chain(remote.keys()).chain(state.files.keys()).collect();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let base = state.files.get(path);
+            let l = local.get(path);
+            let r = remote.get(path);
+            let local_changed = match (l, base) {
+                (Some(l), Some(b)) => l.hash != b.hash,
+                (None, None) => false,
+                _ => true,
+            };
+            let remote_changed = match (r, base) {
+                (Some(r), Some(b)) => r.tag != b.remote_tag,
+                (None, None) => false,
+                _ => true,
+            };
+            let action = match (l.is_some(), r.is_some()) {
+                (true, true) => match (local_changed, remote_changed) {
+                    (false, false) => SyncAction::Keep,
+                    (true, false) => SyncAction::Upload,
+                    (false, true) => SyncAction::Download,
+                    (true, true) => SyncAction::Resolve,
+                },
+                // Deleted remotely: follow unless the local copy changed since
+                (true, false) if base.is_some() && !local_changed => SyncAction::DeleteLocal,
+                (true, false) => SyncAction::Upload,
+                (false, true) if base.is_some() && !remote_changed => SyncAction::DeleteRemote,
+                (false, true) => SyncAction::Download,
+                (false, false) => SyncAction::Forget,
+            };
+            (path.clone(), action)
+        })
+        .collect()
+}
+
+// ============================================================================
+// WebDAV client
+// ============================================================================
+
+/// One entry of a PROPFIND response
+#[derive(Debug, Clone, PartialEq)]
+struct DavEntry {
+    /// Path relative to the sync root, without a trailing slash
+    rel_path: String,
+    is_dir: bool,
+    tag: String,
+    size: u64,
+}
+
+/// Parse a PROPFIND multistatus body. `base_path` is the (decoded) URL path
+/// of the sync root; hrefs outside it are dropped.
+fn parse_multistatus(xml: &str, base_path: &str) -> Result<Vec<DavEntry>, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| format!("Invalid WebDAV response: {e}"))?;
+    let base = base_path.trim_end_matches('/');
+    let child_text = |node: roxmltree::Node, name: &str| -> Option<String> {
+        node.descendants()
+            .find(|n| n.tag_name().name() == name)
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+    };
+
+    let mut entries = Vec::new();
+    for response in doc.descendants().filter(|n| n.tag_name().name() == "response") {
+        let Some(href) = child_text(response, "href") else { continue };
+        // Servers send either a path or a full URL
+        let path = reqwest::Url::parse(&href).map_or(href.clone(), |u| u.path().to_string());
+        let path = urlencoding::decode(&path).map_err(|e| format!("Invalid href {href}: {e}"))?;
+        // Only entries below the root (`/dav/notes` must not match `/dav/notes-old`)
+        let Some(rel) = path.trim_end_matches('/').strip_prefix(base) else { continue };
+        let Some(rel) = rel.strip_prefix('/') else { continue };
+        if rel.is_empty() {
+            continue;
+        }
+        if !is_safe_rel_path(rel) {
+            tracing::warn!("[Sync] Ignoring unsafe remote path: {}", rel);
+            continue;
+        }
+
+        let is_dir = response
+            .descendants()
+            .any(|n| n.tag_name().name() == "collection");
+        let tag = child_text(response, "getetag")
+            .filter(|t| !t.is_empty())
+            .or_else(|| child_text(response, "getlastmodified"))
+            .unwrap_or_default();
+        let size = child_text(response, "getcontentlength")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        entries.push(DavEntry {
+            rel_path: rel.to_string(),
+            is_dir,
+            tag,
+            size,
+        });
+    }
+    Ok(entries)
+}
+
+struct DavClient {
+    client: reqwest::Client,
+    base: reqwest::Url,
+    username: String,
+    password: String,
+}
+
+impl DavClient {
+    fn new(config: &SyncConfig, password: String) -> Result<Self, String> {
+        let mut endpoint = config.endpoint.trim().to_string();
+        if !endpoint.ends_with('/') {
+            endpoint.push('/');
+        }
+        let base = reqwest::Url::parse(&endpoint).map_err(|e| format!("Invalid WebDAV URL: {e}"))?;
+        let client = crate::http_client::builder_for(Some(base.as_str()))?
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .user_agent(concat!("VMark/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+        Ok(Self {
+            client,
+            base,
+            username: config.username.trim().to_string(),
+            password,
+        })
+    }
+
+    fn url_for(&self, rel: &str) -> Result<reqwest::Url, String> {
+        if !is_safe_rel_path(rel) {
+            return Err(format!("Refusing unsafe sync path: {rel}"));
+        }
+        let encoded: Vec<String> = rel
+            .split('/')
+            .map(|part| urlencoding::encode(part).into_owned())
+            .collect();
+        self.base
+            .join(&encoded.join("/"))
+            .map_err(|e| format!("Invalid path {rel}: {e}"))
+    }
+
+    fn base_path(&self) -> String {
+        urlencoding::decode(self.base.path())
+            .map(|p| p.into_owned())
+            .unwrap_or_else(|_| self.base.path().to_string())
+    }
+
+    fn request(&self, method: &str, url: reqwest::Url) -> reqwest::RequestBuilder {
+        let method = reqwest::Method::from_bytes(method.as_bytes()).expect("valid HTTP method");
+        self.client
+            .request(method, url)
+            .basic_auth(&self.username, Some(&self.password))
+    }
+
+    async fn send(&self, builder: reqwest::RequestBuilder, what: &str) -> Result<reqwest::Response, String> {
+        let resp = builder
+            .send()
+            .await
+            .map_err(|e| format!("{what} failed: {e}"))?;
+        match resp.status().as_u16() {
+            401 | 403 => Err("WebDAV server rejected the username or password".to_string()),
+            _ => Ok(resp),
+        }
+    }
+
+    async fn propfind(&self, url: reqwest::Url, depth: &str) -> Result<Vec<DavEntry>, String> {
+        let builder = self
+            .request("PROPFIND", url)
+            .header("Depth", depth)
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(PROPFIND_BODY);
+        let resp = self.send(builder, "Listing remote folder").await?;
+        if resp.status().as_u16() != 207 {
+            return Err(format!("Listing remote folder failed (HTTP {})", resp.status().as_u16()));
+        }
+        let body = resp.text().await.map_err(|e| format!("Failed to read listing: {e}"))?;
+        parse_multistatus(&body, &self.base_path())
+    }
+
+    /// Every remote file under the sync root.
+    async fn list_all(&self, excludes: &[String]) -> Result<BTreeMap<String, RemoteFile>, String> {
+        let mut files = BTreeMap::new();
+        // Depth: infinity is often disabled (Nextcloud), so walk one level at a time
+        let mut pending = vec![String::new()];
+        while let Some(dir) = pending.pop() {
+            let url = if dir.is_empty() {
+                self.base.clone()
+            } else {
+                self.url_for(&format!("{dir}/"))?
+            };
+            for entry in self.propfind(url, "1").await? {
+                if entry.rel_path == dir || is_excluded(&entry.rel_path, excludes) {
+                    continue;
+                }
+                if entry.is_dir {
+                    pending.push(entry.rel_path);
+                } else {
+                    files.insert(
+                        entry.rel_path,
+                        RemoteFile {
+                            tag: entry.tag,
+                            size: entry.size,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Current state of one remote file.
+    async fn stat(&self, rel: &str) -> Result<RemoteFile, String> {
+        self.propfind(self.url_for(rel)?, "0")
+            .await?
+            .into_iter()
+            .find(|e| e.rel_path == rel)
+            .map(|e| RemoteFile { tag: e.tag, size: e.size })
+            .ok_or_else(|| format!("{rel} is missing on the server after upload"))
+    }
+
+    /// Download a file, with the ETag of the version downloaded when the
+    /// server sends one.
+    async fn get(&self, rel: &str) -> Result<(Vec<u8>, Option<String>), String> {
+        let resp = self.send(self.request("GET", self.url_for(rel)?), "Downloading").await?;
+        if !resp.status().is_success() {
+            return Err(format!("Downloading {rel} failed (HTTP {})", resp.status().as_u16()));
+        }
+        let etag = resp
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let data = resp
+            .bytes()
+            .await
+            .map_err(|e| format!("Downloading {rel} failed: {e}"))?;
+        Ok((data.to_vec(), etag))
+    }
+
+    /// Create the parent collections of `rel` that don't exist yet.
+    async fn ensure_parents(&self, rel: &str, created: &mut HashSet<String>) -> Result<(), String> {
+        let parts: Vec<&str> = rel.split('/').collect();
+        for depth in 1..parts.len() {
+            let dir = parts[..depth].join("/");
+            if created.contains(&dir) {
+                continue;
+            }
+            let resp = self
+                .send(self.request("MKCOL", self.url_for(&format!("{dir}/"))?), "Creating folder")
+                .await?;
+            // 405: already exists
+            if !resp.status().is_success() && resp.status().as_u16() != 405 {
+                return Err(format!("Creating folder {dir} failed (HTTP {})", resp.status().as_u16()));
+            }
+            created.insert(dir);
+        }
+        Ok(())
+    }
+
+    /// Upload `data` as long as the server still has what the sync saw there.
+    /// Returns `None` when it doesn't (HTTP 412): the file changed meanwhile.
+    async fn put(
+        &self,
+        rel: &str,
+        data: Vec<u8>,
+        expected: Expected<'_>,
+        created: &mut HashSet<String>,
+    ) -> Result<Option<RemoteFile>, String> {
+        self.ensure_parents(rel, created).await?;
+        let mut builder = self.request("PUT", self.url_for(rel)?).body(data);
+        builder = match expected {
+            Expected::Absent => builder.header("If-None-Match", "*"),
+            Expected::Tag(tag) if is_strong_etag(tag) => builder.header("If-Match", tag),
+            Expected::Tag(_) => builder,
+        };
+        let resp = self.send(builder, "Uploading").await?;
+        if resp.status().as_u16() == 412 {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(format!("Uploading {rel} failed (HTTP {})", resp.status().as_u16()));
+        }
+        // Re-read the tag the way listings report it, so the next sync compares like with like
+        self.stat(rel).await.map(Some)
+    }
+
+    async fn delete(&self, rel: &str) -> Result<(), String> {
+        let resp = self.send(self.request("DELETE", self.url_for(rel)?), "Deleting").await?;
+        if resp.status().is_success() || resp.status().as_u16() == 404 {
+            Ok(())
+        } else {
+            Err(format!("Deleting {rel} failed (HTTP {})", resp.status().as_u16()))
+        }
+    }
+}
+
+/// What a PUT expects to find on the server.
+#[derive(Clone, Copy)]
+enum Expected<'a> {
+    /// No file yet
+    Absent,
+    /// The file with this tag
+    Tag(&'a str),
+}
+
+/// Whether `tag` is a strong ETag. Only those can go in `If-Match`: weak
+/// ones never match, and Last-Modified fallbacks aren't ETags at all.
+fn is_strong_etag(tag: &str) -> bool {
+    tag.len() >= 2 && tag.starts_with('"') && tag.ends_with('"')
+}
+
+// ============================================================================
+// Sync run
+// ============================================================================
+
+fn write_local(root: &Path, rel: &str, data: &[u8]) -> Result<LocalFile, String> {
+    let path = local_path(root, rel)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    app_paths::atomic_write_file(&path, data)?;
+    let metadata = fs::metadata(&path).map_err(|e| format!("Failed to stat {rel}: {e}"))?;
+    Ok(LocalFile {
+        mtime: modified_ms(&metadata),
+        size: metadata.len(),
+        hash: hash_bytes(data),
+    })
+}
+
+/// Whether the local file is no longer what the scan saw (edited, created or
+/// deleted since), so a download mustn't overwrite it.
+fn changed_since_scan(root: &Path, rel: &str, scanned: Option<&LocalFile>) -> Result<bool, String> {
+    let current = match fs::read(local_path(root, rel)?) {
+        Ok(bytes) => Some(hash_bytes(&bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {rel}: {e}")),
+    };
+    Ok(current.as_deref() != scanned.map(|l| l.hash.as_str()))
+}
+
+fn file_state(local: &LocalFile, remote: &RemoteFile) -> FileState {
+    FileState {
+        hash: local.hash.clone(),
+        local_mtime: local.mtime,
+        size: local.size,
+        remote_tag: remote.tag.clone(),
+    }
+}
+
+/// Carry out the plan, recording progress in `files` as it goes so a failed
+/// run still remembers what it finished.
+async fn execute(
+    root: &Path,
+    client: &DavClient,
+    local: &BTreeMap<String, LocalFile>,
+    remote: &BTreeMap<String, RemoteFile>,
+    plan: &BTreeMap<String, SyncAction>,
+    files: &mut BTreeMap<String, FileState>,
+) -> Result<SyncSummary, String> {
+    let mut summary = SyncSummary::default();
+    let mut created_dirs = HashSet::new();
+
+    for (path, action) in plan {
+        match action {
+            SyncAction::Keep => {
+                if let (Some(l), Some(r)) = (local.get(path), remote.get(path)) {
+                    files.insert(path.clone(), file_state(l, r));
+                }
+            }
+            SyncAction::Upload => {
+                let l = &local[path];
+                let data = fs::read(local_path(root, path)?).map_err(|e| format!("Failed to read {path}: {e}"))?;
+                let expected = remote.get(path).map_or(Expected::Absent, |r| Expected::Tag(&r.tag));
+                match client.put(path, data, expected, &mut created_dirs).await? {
+                    Some(r) => {
+                        files.insert(path.clone(), file_state(l, &r));
+                        summary.uploaded += 1;
+                    }
+                    // Changed on the server since it was listed
+                    None => resolve(root, client, path, l, &mut created_dirs, files, &mut summary).await?,
+                }
+            }
+            SyncAction::Download => {
+                if changed_since_scan(root, path, local.get(path))? {
+                    // Left for the next sync, which will see both sides changed
+                    tracing::warn!("[Sync] {} changed locally during sync, not downloading it", path);
+                    continue;
+                }
+                let (data, _) = client.get(path).await?;
+                let l = write_local(root, path, &data)?;
+                files.insert(path.clone(), file_state(&l, &remote[path]));
+                summary.downloaded += 1;
+            }
+            SyncAction::DeleteLocal => {
+                trash::delete(local_path(root, path)?)
+                    .map_err(|e| format!("Failed to move {path} to trash: {e}"))?;
+                files.remove(path);
+                summary.deleted_local += 1;
+            }
+            SyncAction::DeleteRemote => {
+                client.delete(path).await?;
+                files.remove(path);
+                summary.deleted_remote += 1;
+            }
+            SyncAction::Resolve => {
+                resolve(root, client, path, &local[path], &mut created_dirs, files, &mut summary).await?;
+            }
+            SyncAction::Forget => {
+                files.remove(path);
+            }
+        }
+    }
+
+    summary.finished_at = chrono::Utc::now().timestamp_millis();
+    Ok(summary)
+}
+
+/// A file changed on both sides. Unless both now hold the same content, the
+/// remote version is kept as a conflict copy and the local one uploaded.
+async fn resolve(
+    root: &Path,
+    client: &DavClient,
+    path: &str,
+    l: &LocalFile,
+    created_dirs: &mut HashSet<String>,
+    files: &mut BTreeMap<String, FileState>,
+    summary: &mut SyncSummary,
+) -> Result<(), String> {
+    let (data, etag) = client.get(path).await?;
+    if hash_bytes(&data) == l.hash {
+        let r = client.stat(path).await?;
+        files.insert(path.to_string(), file_state(l, &r));
+        return Ok(());
+    }
+
+    let stamp = chrono::Local::now().format("%Y-%m-%d %H%M%S").to_string();
+    let conflict = conflict_path(path, &stamp);
+    tracing::warn!("[Sync] Conflict on {}, remote copy saved as {}", path, conflict);
+    let copy = write_local(root, &conflict, &data)?;
+    let copy_remote = client
+        .put(&conflict, data, Expected::Absent, created_dirs)
+        .await?
+        .ok_or_else(|| format!("{conflict} already exists on the server"))?;
+    files.insert(conflict.clone(), file_state(&copy, &copy_remote));
+    summary.conflicts.push(conflict);
+
+    let local_data = fs::read(local_path(root, path)?).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    // Only over the version just saved as the copy
+    let expected = Expected::Tag(etag.as_deref().unwrap_or_default());
+    match client.put(path, local_data, expected, created_dirs).await? {
+        Some(r) => {
+            files.insert(path.to_string(), file_state(l, &r));
+            summary.uploaded += 1;
+        }
+        // Changed yet again; the next sync resolves it against the newer version
+        None => tracing::warn!("[Sync] {} changed on the server during sync, not uploading it", path),
+    }
+    Ok(())
+}
+
+
+/// Marks a workspace as syncing until dropped
+struct RunGuard(String);
+
+impl RunGuard {
+    fn acquire(root: &str) -> Result<Self, String> {
+        let mut guard = RUNNING.lock().map_err(|e| format!("Lock error: {e}"))?;
+        if !guard.get_or_insert_with(HashSet::new).insert(root.to_string()) {
+            return Err("A sync is already running for this workspace".to_string());
+        }
+        Ok(Self(root.to_string()))
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut guard) = RUNNING.lock() {
+            if let Some(set) = guard.as_mut() {
+                set.remove(&self.0);
+            }
+        }
+    }
+}
+
+async fn run_sync(root: &Path, config: &SyncConfig) -> Result<SyncSummary, String> {
+    let client = DavClient::new(config, load_password(config)?)?;
+    let excludes = crate::workspace::workspace_excludes(root);
+
+    let remote = client.list_all(&excludes).await?;
+    let mut state = load_state(root);
+    let local = scan_local(root, &state, &excludes)?;
+    let plan = plan_sync(&local, &remote, &state);
+
+    let result = execute(root, &client, &local, &remote, &plan, &mut state.files).await;
+    if result.is_ok() {
+        state.last_sync = Some(chrono::Utc::now().timestamp_millis());
+    }
+    save_json(root, SYNC_STATE_FILE, &state)?;
+    result
+}
+
+/// Sync `root_path`, emitting `sync:status` before and after.
+async fn sync_and_emit(app: &AppHandle, root_path: &str) -> Result<SyncSummary, String> {
+    let _running = RunGuard::acquire(root_path)?;
+    let root = Path::new(root_path);
+    let config = load_config(root)?.ok_or("Sync is not set up for this workspace")?;
+
+    let emit = |state: &'static str, summary: Option<SyncSummary>, error: Option<String>| {
+        let _ = app.emit(
+            "sync:status",
+            SyncStatus {
+                root_path: root_path.to_string(),
+                state,
+                summary,
+                error,
+            },
+        );
+    };
+
+    emit("syncing", None, None);
+    match run_sync(root, &config).await {
+        Ok(summary) => {
+            tracing::info!(
+                "[Sync] {}: {} up, {} down, {} conflicts",
+                root_path,
+                summary.uploaded,
+                summary.downloaded,
+                summary.conflicts.len()
+            );
+            emit("done", Some(summary.clone()), None);
+            Ok(summary)
+        }
+        Err(e) => {
+            tracing::warn!("[Sync] {} failed: {}", root_path, e);
+            emit("failed", None, Some(e.clone()));
+            Err(e)
+        }
+    }
+}
+
+fn stop_schedule(root_path: &str) {
+    if let Ok(mut guard) = SCHEDULES.lock() {
+        if let Some(schedule) = guard.as_mut().and_then(|m| m.remove(root_path)) {
+            schedule.handle.abort();
+        }
+    }
+}
+
+/// Drop `label` from the windows using a schedule; the last one stops it.
+fn release_schedule(schedules: &mut HashMap<String, Schedule>, root_path: &str, label: &str) {
+    let Some(schedule) = schedules.get_mut(root_path) else { return };
+    schedule.windows.remove(label);
+    if schedule.windows.is_empty() {
+        if let Some(schedule) = schedules.remove(root_path) {
+            schedule.handle.abort();
+        }
+    }
+}
+
+/// Release every schedule a closed window was using.
+pub fn clear_window_schedules(label: &str) {
+    if let Ok(mut guard) = SCHEDULES.lock() {
+        if let Some(schedules) = guard.as_mut() {
+            let roots: Vec<String> = schedules.keys().cloned().collect();
+            for root in roots {
+                release_schedule(schedules, &root, label);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_sync_config(root_path: String) -> Result<Option<SyncConfig>, String> {
+    load_config(Path::new(&root_path))
+}
+
+/// Save the sync settings (and the password, when given) for a workspace.
+#[tauri::command]
+pub fn set_sync_config(
+    root_path: String,
+    config: SyncConfig,
+    password: Option<String>,
+) -> Result<(), String> {
+    validate_config(&config)?;
+    let root = Path::new(&root_path);
+    // A different server makes the manifest meaningless
+    if load_config(root)?.is_some_and(|old| old.endpoint != config.endpoint) {
+        let _ = fs::remove_file(vmark_dir(root).join(SYNC_STATE_FILE));
+    }
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        keychain_entry(&config)?
+            .set_password(&password)
+            .map_err(|e| format!("Failed to save WebDAV password to keychain: {e}"))?;
+    }
+    save_json(root, SYNC_CONFIG_FILE, &config)
+}
+
+/// Stop syncing a workspace: forgets settings, manifest and password.
+#[tauri::command]
+pub fn remove_sync_config(root_path: String) -> Result<(), String> {
+    stop_schedule(&root_path);
+    let root = Path::new(&root_path);
+    if let Some(config) = load_config(root)? {
+        if let Ok(entry) = keychain_entry(&config) {
+            let _ = entry.delete_credential();
+        }
+    }
+    for name in [SYNC_CONFIG_FILE, SYNC_STATE_FILE] {
+        let path = vmark_dir(root).join(name);
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove {name}: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Set up sync into an empty folder and download everything from the server.
+#[tauri::command]
+pub async fn sync_clone(
+    app: AppHandle,
+    root_path: String,
+    config: SyncConfig,
+    password: String,
+) -> Result<SyncSummary, String> {
+    let root = Path::new(&root_path);
+    fs::create_dir_all(root).map_err(|e| format!("Failed to create {root_path}: {e}"))?;
+    if !scan_local(root, &SyncState::default(), &[])?.is_empty() {
+        return Err("Choose an empty folder to clone into".to_string());
+    }
+    set_sync_config(root_path.clone(), config, Some(password))?;
+    sync_and_emit(&app, &root_path).await
+}
+
+/// Run a two-way sync now.
+#[tauri::command]
+pub async fn sync_now(app: AppHandle, root_path: String) -> Result<SyncSummary, String> {
+    sync_and_emit(&app, &root_path).await
+}
+
+/// Start background sync at the configured interval for the calling window
+/// (restarting the workspace's loop so a new interval applies). The loop runs
+/// until every window that started it has stopped it or closed. Returns
+/// false when the workspace has no interval set.
+#[tauri::command]
+pub fn start_scheduled_sync(app: AppHandle, window: tauri::Window, root_path: String) -> Result<bool, String> {
+    let mut windows = SCHEDULES
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .as_mut()
+        .and_then(|m| m.remove(&root_path))
+        .map(|schedule| {
+            schedule.handle.abort();
+            schedule.windows
+        })
+        .unwrap_or_default();
+    let interval = match load_config(Path::new(&root_path))? {
+        Some(config) if config.interval_minutes > 0 => config.interval_minutes,
+        _ => return Ok(false),
+    };
+    windows.insert(window.label().to_string());
+
+    let root = root_path.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(u64::from(interval) * 60)).await;
+            // Failures are reported through `sync:status`; keep the schedule running
+            let _ = sync_and_emit(&app, &root).await;
+        }
+    });
+    SCHEDULES
+        .lock()
+        .map_err(|e| format!("Lock error: {e}"))?
+        .get_or_insert_with(HashMap::new)
+        .insert(root_path, Schedule { handle, windows });
+    Ok(true)
+}
+
+/// Stop the calling window's use of the workspace's background sync.
+#[tauri::command]
+pub fn stop_scheduled_sync(window: tauri::Window, root_path: String) {
+    if let Ok(mut guard) = SCHEDULES.lock() {
+        if let Some(schedules) = guard.as_mut() {
+            release_schedule(schedules, &root_path, window.label());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn local(hash: &str) -> LocalFile {
+        LocalFile {
+            mtime: 1,
+            size: 1,
+            hash: hash.to_string(),
+        }
+    }
+
+    fn remote(tag: &str) -> RemoteFile {
+        RemoteFile {
+            tag: tag.to_string(),
+            size: 1,
+        }
+    }
+
+    fn known(hash: &str, tag: &str) -> FileState {
+        FileState {
+            hash: hash.to_string(),
+            local_mtime: 1,
+            size: 1,
+            remote_tag: tag.to_string(),
+        }
+    }
+
+    fn plan_one(l: Option<&str>, r: Option<&str>, base: Option<(&str, &str)>) -> SyncAction {
+        let local: BTreeMap<_, _> = l.map(|h| ("a.md".to_string(), local(h))).into_iter().collect();
+        let remote: BTreeMap<_, _> = r.map(|t| ("a.md".to_string(), remote(t))).into_iter().collect();
+        let mut state = SyncState::default();
+        if let Some((h, t)) = base {
+            state.files.insert("a.md".to_string(), known(h, t));
+        }
+        plan_sync(&local, &remote, &state).get("a.md").copied().unwrap()
+    }
+
+    #[test]
+    fn test_plan_new_and_changed_files() {
+        assert_eq!(plan_one(Some("h1"), None, None), SyncAction::Upload);
+        assert_eq!(plan_one(None, Some("e1"), None), SyncAction::Download);
+        assert_eq!(plan_one(Some("h1"), Some("e1"), Some(("h1", "e1"))), SyncAction::Keep);
+        assert_eq!(plan_one(Some("h2"), Some("e1"), Some(("h1", "e1"))), SyncAction::Upload);
+        assert_eq!(plan_one(Some("h1"), Some("e2"), Some(("h1", "e1"))), SyncAction::Download);
+        assert_eq!(plan_one(Some("h2"), Some("e2"), Some(("h1", "e1"))), SyncAction::Resolve);
+        // First sync with the file on both sides: compare content
+        assert_eq!(plan_one(Some("h1"), Some("e1"), None), SyncAction::Resolve);
+    }
+
+    #[test]
+    fn test_plan_deletions() {
+        assert_eq!(plan_one(Some("h1"), None, Some(("h1", "e1"))), SyncAction::DeleteLocal);
+        assert_eq!(plan_one(None, Some("e1"), Some(("h1", "e1"))), SyncAction::DeleteRemote);
+        // An edit beats a deletion on the other side
+        assert_eq!(plan_one(Some("h2"), None, Some(("h1", "e1"))), SyncAction::Upload);
+        assert_eq!(plan_one(None, Some("e2"), Some(("h1", "e1"))), SyncAction::Download);
+        assert_eq!(plan_one(None, None, Some(("h1", "e1"))), SyncAction::Forget);
+    }
+
+    #[test]
+    fn test_conflict_path() {
+        assert_eq!(conflict_path("notes/idea.md", "2026-01-31 091200"), "notes/idea (conflict 2026-01-31 091200).md");
+        assert_eq!(conflict_path("README", "x"), "README (conflict x)");
+        assert_eq!(conflict_path(".env", "x"), ".env (conflict x)");
+    }
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/remote.php/dav/files/me/Notes/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/files/me/Notes/My%20Ideas.md</d:href>
+    <d:propstat><d:prop>
+      <d:resourcetype/>
+      <d:getetag>"abc123"</d:getetag>
+      <d:getcontentlength>42</d:getcontentlength>
+    </d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>https://cloud.example.com/remote.php/dav/files/me/Notes/sub/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/files/me/Notes/old.md</d:href>
+    <d:propstat><d:prop>
+      <d:getlastmodified>Tue, 15 Nov 1994 08:12:31 GMT</d:getlastmodified>
+    </d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        let entries = parse_multistatus(xml, "/remote.php/dav/files/me/Notes/").unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                DavEntry { rel_path: "My Ideas.md".into(), is_dir: false, tag: "\"abc123\"".into(), size: 42 },
+                DavEntry { rel_path: "sub".into(), is_dir: true, tag: String::new(), size: 0 },
+                DavEntry {
+                    rel_path: "old.md".into(),
+                    is_dir: false,
+                    tag: "Tue, 15 Nov 1994 08:12:31 GMT".into(),
+                    size: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_url_for_encodes_segments() {
+        let config = SyncConfig {
+            endpoint: "https://cloud.example.com/dav/Notes".into(),
+            username: "me".into(),
+            interval_minutes: 0,
+        };
+        let client = DavClient::new(&config, String::new()).unwrap();
+        assert_eq!(
+            client.url_for("sub dir/a#1.md").unwrap().as_str(),
+            "https://cloud.example.com/dav/Notes/sub%20dir/a%231.md"
+        );
+        assert_eq!(client.base_path(), "/dav/Notes/");
+    }
+
+    #[test]
+    fn test_scan_local_excludes_and_reuses_hashes() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("docs")).unwrap();
+        fs::create_dir_all(temp.path().join(".vmark")).unwrap();
+        fs::create_dir_all(temp.path().join("node_modules")).unwrap();
+        fs::write(temp.path().join("docs/a.md"), "a").unwrap();
+        fs::write(temp.path().join(".vmark/sync.json"), "{}").unwrap();
+        fs::write(temp.path().join("node_modules/x.js"), "x").unwrap();
+
+        let excludes = vec!["node_modules".to_string()];
+        let files = scan_local(temp.path(), &SyncState::default(), &excludes).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["docs/a.md"]);
+        assert_eq!(files["docs/a.md"].hash, hash_bytes(b"a"));
+
+        // Unchanged mtime and size: the manifest hash is trusted
+        let mut state = SyncState::default();
+        let scanned = &files["docs/a.md"];
+        state.files.insert(
+            "docs/a.md".into(),
+            FileState {
+                hash: "cached".into(),
+                local_mtime: scanned.mtime,
+                size: scanned.size,
+                remote_tag: String::new(),
+            },
+        );
+        assert_eq!(scan_local(temp.path(), &state, &excludes).unwrap()["docs/a.md"].hash, "cached");
+    }
+
+    #[test]
+    fn test_config_round_trip_and_validation() {
+        let temp = TempDir::new().unwrap();
+        let config = SyncConfig {
+            endpoint: "https://cloud.example.com/dav/".into(),
+            username: "me".into(),
+            interval_minutes: 15,
+        };
+        save_json(temp.path(), SYNC_CONFIG_FILE, &config).unwrap();
+        assert_eq!(load_config(temp.path()).unwrap(), Some(config.clone()));
+
+        assert!(validate_config(&SyncConfig { endpoint: "ftp://x/".into(), ..config.clone() }).is_err());
+        assert!(validate_config(&SyncConfig { username: " ".into(), ..config }).is_err());
+    }
+
+    #[test]
+    fn test_write_local_creates_parents() {
+        let temp = TempDir::new().unwrap();
+        let file = write_local(temp.path(), "a/b/c.md", b"hello").unwrap();
+        assert_eq!(file.hash, hash_bytes(b"hello"));
+        assert_eq!(fs::read_to_string(temp.path().join("a/b/c.md")).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_changed_since_scan() {
+        let temp = TempDir::new().unwrap();
+        let scanned = write_local(temp.path(), "a.md", b"hello").unwrap();
+        assert!(!changed_since_scan(temp.path(), "a.md", Some(&scanned)).unwrap());
+        fs::write(temp.path().join("a.md"), "edited").unwrap();
+        assert!(changed_since_scan(temp.path(), "a.md", Some(&scanned)).unwrap());
+        assert!(!changed_since_scan(temp.path(), "new.md", None).unwrap());
+        fs::write(temp.path().join("new.md"), "created").unwrap();
+        assert!(changed_since_scan(temp.path(), "new.md", None).unwrap());
+    }
+
+    #[test]
+    fn test_is_strong_etag() {
+        assert!(is_strong_etag("\"abc123\""));
+        assert!(!is_strong_etag("W/\"abc123\""));
+        assert!(!is_strong_etag("Tue, 15 Nov 1994 12:45:26 GMT"));
+        assert!(!is_strong_etag("\""));
+        assert!(!is_strong_etag(""));
+    }
+
+    #[test]
+    fn test_is_safe_rel_path() {
+        assert!(is_safe_rel_path("a/b.md"));
+        assert!(is_safe_rel_path(".vmarkrc"));
+        for rel in ["", "/etc/passwd", "a//b", "a/./b", "../x", "a/../../x", "a\\..\\x", "C:/x"] {
+            assert!(!is_safe_rel_path(rel), "{rel}");
+        }
+    }
+
+    #[test]
+    fn test_parse_multistatus_requires_prefix_boundary_and_safe_paths() {
+        let xml = r#"<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/dav/Notes-old/a.md</d:href></d:response>
+  <d:response><d:href>/dav/Notes/%2E%2E/escape.md</d:href></d:response>
+  <d:response><d:href>/dav/Notes/b.md</d:href></d:response>
+</d:multistatus>"#;
+        let entries = parse_multistatus(xml, "/dav/Notes/").unwrap();
+        assert_eq!(entries.iter().map(|e| e.rel_path.as_str()).collect::<Vec<_>>(), vec!["b.md"]);
+    }
+
+    #[test]
+    fn test_local_path_stays_in_root() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(local_path(temp.path(), "a/b.md").unwrap(), temp.path().join("a").join("b.md"));
+        assert!(local_path(temp.path(), "../x.md").is_err());
+        assert!(write_local(temp.path(), "a/../../x.md", b"x").is_err());
+
+        #[cfg(unix)]
+        {
+            let outside = TempDir::new().unwrap();
+            std::os::unix::fs::symlink(outside.path(), temp.path().join("link")).unwrap();
+            assert!(local_path(temp.path(), "link/x.md").is_err());
+        }
+    }
+
+    #[test]
+    fn test_schedule_released_by_last_window() {
+        let handle = tauri::async_runtime::spawn(async {});
+        let windows = ["main".to_string(), "doc-1".to_string()].into_iter().collect();
+        let mut schedules = HashMap::from([("/ws".to_string(), Schedule { handle, windows })]);
+
+        release_schedule(&mut schedules, "/ws", "main");
+        assert!(schedules.contains_key("/ws"));
+        release_schedule(&mut schedules, "/ws", "doc-1");
+        assert!(schedules.is_empty());
+    }
+}
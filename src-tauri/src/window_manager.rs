@@ -1,8 +1,13 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::app_paths;
+use crate::window_registry;
 use crate::PendingFileOpen;
 
 static WINDOW_COUNTER: AtomicU32 = AtomicU32::new(0);
@@ -55,12 +60,17 @@ pub fn group_paths_by_workspace(paths: &[String]) -> HashMap<String, Vec<String>
 }
 
 /// Append files to the pending queue with a shared workspace root.
+/// Paths already queued are skipped (the same file can arrive both as a
+/// CLI argument and as an OS open request).
 pub fn queue_pending_file_opens(
     pending: &mut Vec<PendingFileOpen>,
     file_paths: Vec<String>,
     workspace_root: Option<&str>,
 ) {
     for path in file_paths {
+        if pending.iter().any(|p| p.path == path) {
+            continue;
+        }
         pending.push(PendingFileOpen {
             path,
             workspace_root: workspace_root.map(String::from),
@@ -89,6 +99,270 @@ fn get_cascaded_position(count: u32) -> (f64, f64) {
     )
 }
 
+// ============================================================================
+// Window geometry memory
+// ============================================================================
+
+/// File in app data holding the last-used geometry per window type
+const WINDOW_GEOMETRY_FILE: &str = "window-geometry.json";
+
+/// Window geometry in logical pixels (outer position, inner size)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Usable monitor area in logical pixels (excludes menu bar / taskbar)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+struct GeometryState {
+    file: PathBuf,
+    by_kind: HashMap<String, SavedGeometry>,
+    dirty: bool,
+}
+
+static GEOMETRY_STATE: OnceLock<Mutex<GeometryState>> = OnceLock::new();
+
+/// Label prefix of read-only viewer windows
+pub const VIEWER_LABEL_PREFIX: &str = "viewer-";
+
+/// Window type used to key remembered geometry.
+/// `main` and `doc-*` windows share the "document" geometry.
+pub fn window_kind(label: &str) -> Option<&'static str> {
+    if label == "main" || label.starts_with("doc-") {
+        Some("document")
+    } else if label.starts_with(VIEWER_LABEL_PREFIX) {
+        Some("viewer")
+    } else if label == "settings" {
+        Some("settings")
+    } else {
+        None
+    }
+}
+
+/// Load remembered window geometry from app data. Called once during app setup.
+pub fn init_window_geometry(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(WINDOW_GEOMETRY_FILE);
+    let by_kind = load_geometry(&file);
+    let _ = GEOMETRY_STATE.set(Mutex::new(GeometryState {
+        file,
+        by_kind,
+        dirty: false,
+    }));
+    Ok(())
+}
+
+fn load_geometry(file: &Path) -> HashMap<String, SavedGeometry> {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_geometry(file: &Path, by_kind: &HashMap<String, SavedGeometry>) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(by_kind)
+        .map_err(|e| format!("Failed to serialize window geometry: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Remembered geometry for a window type, if any.
+fn saved_geometry(kind: &str) -> Option<SavedGeometry> {
+    let state = GEOMETRY_STATE.get()?.lock().ok()?;
+    state.by_kind.get(kind).copied()
+}
+
+/// Record a window's current geometry in memory (on move/resize).
+/// Minimized, maximized and fullscreen states are not remembered.
+pub fn record_window_geometry<R: tauri::Runtime>(window: &tauri::Window<R>) {
+    let Some(kind) = window_kind(window.label()) else {
+        return;
+    };
+    if window.is_minimized().unwrap_or(false)
+        || window.is_maximized().unwrap_or(false)
+        || window.is_fullscreen().unwrap_or(false)
+    {
+        return;
+    }
+    let (Ok(scale), Ok(position), Ok(size)) =
+        (window.scale_factor(), window.outer_position(), window.inner_size())
+    else {
+        return;
+    };
+    let position = position.to_logical::<f64>(scale);
+    let size = size.to_logical::<f64>(scale);
+    if size.width <= 0.0 || size.height <= 0.0 {
+        return;
+    }
+
+    let Some(lock) = GEOMETRY_STATE.get() else {
+        return;
+    };
+    if let Ok(mut state) = lock.lock() {
+        let geometry = SavedGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        };
+        if state.by_kind.get(kind) != Some(&geometry) {
+            state.by_kind.insert(kind.to_string(), geometry);
+            state.dirty = true;
+        }
+    }
+}
+
+/// Write remembered geometry to disk if it changed (on window close).
+pub fn persist_window_geometry() {
+    let Some(lock) = GEOMETRY_STATE.get() else {
+        return;
+    };
+    if let Ok(mut state) = lock.lock() {
+        if !state.dirty {
+            return;
+        }
+        match save_geometry(&state.file, &state.by_kind) {
+            Ok(()) => state.dirty = false,
+            Err(_e) => {
+                tracing::debug!("Failed to save window geometry: {}", _e);
+            }
+        }
+    }
+}
+
+/// Compute geometry for a new document window.
+///
+/// Size comes from the remembered geometry (never below the minimum).
+/// Position cascades from the focused window when there is one, otherwise
+/// reuses the remembered position, otherwise falls back to the fixed cascade.
+/// The result is clamped to the monitor's usable area.
+pub fn compute_window_placement(
+    saved: Option<SavedGeometry>,
+    focused_position: Option<(f64, f64)>,
+    count: u32,
+    monitor: Option<MonitorArea>,
+) -> SavedGeometry {
+    let (width, height) = saved
+        .map(|g| (g.width.max(MIN_WIDTH), g.height.max(MIN_HEIGHT)))
+        .unwrap_or((MIN_WIDTH, MIN_HEIGHT));
+    let (x, y) = match (focused_position, saved) {
+        (Some((fx, fy)), _) => (fx + CASCADE_OFFSET, fy + CASCADE_OFFSET),
+        (None, Some(g)) => (g.x, g.y),
+        (None, None) => get_cascaded_position(count),
+    };
+    let geometry = SavedGeometry { x, y, width, height };
+    match monitor {
+        Some(area) => clamp_to_monitor(geometry, area),
+        None => geometry,
+    }
+}
+
+/// Keep a window inside a monitor area. A cascade that runs off the
+/// right/bottom edge wraps back to the area's left/top edge.
+fn clamp_to_monitor(geometry: SavedGeometry, area: MonitorArea) -> SavedGeometry {
+    let width = geometry.width.min(area.width);
+    let height = geometry.height.min(area.height);
+    let mut x = geometry.x;
+    let mut y = geometry.y;
+    if x + width > area.x + area.width {
+        x = area.x;
+    }
+    if y + height > area.y + area.height {
+        y = area.y;
+    }
+    SavedGeometry {
+        x: x.max(area.x),
+        y: y.max(area.y),
+        width,
+        height,
+    }
+}
+
+fn monitor_area(monitor: &tauri::Monitor) -> MonitorArea {
+    let scale = monitor.scale_factor();
+    let work_area = monitor.work_area();
+    let position = work_area.position.to_logical::<f64>(scale);
+    let size = work_area.size.to_logical::<f64>(scale);
+    MonitorArea {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    }
+}
+
+/// Geometry for the next document window: remembered size, cascaded from
+/// the focused document window, clamped to that window's monitor.
+fn next_document_geometry(app: &AppHandle, count: u32) -> SavedGeometry {
+    let focused = app.webview_windows().into_values().find(|w| {
+        window_kind(w.label()) == Some("document") && w.is_focused().unwrap_or(false)
+    });
+
+    let focused_position = focused.as_ref().and_then(|w| {
+        let scale = w.scale_factor().ok()?;
+        let position = w.outer_position().ok()?.to_logical::<f64>(scale);
+        Some((position.x, position.y))
+    });
+
+    let monitor = focused
+        .as_ref()
+        .and_then(|w| w.current_monitor().ok().flatten())
+        .or_else(|| app.primary_monitor().ok().flatten())
+        .map(|m| monitor_area(&m));
+
+    compute_window_placement(saved_geometry("document"), focused_position, count, monitor)
+}
+
+/// Place a split window beside its source: same size, on the right when it
+/// fits on screen, else on the left, else on whichever side has more room.
+/// `outer_width` includes window decorations so the two don't overlap.
+pub fn compute_split_placement(
+    source: SavedGeometry,
+    outer_width: f64,
+    monitor: Option<MonitorArea>,
+) -> SavedGeometry {
+    let right_x = source.x + outer_width;
+    let left_x = source.x - outer_width;
+    let x = match monitor {
+        None => right_x,
+        Some(area) => {
+            let area_right = area.x + area.width;
+            if right_x + outer_width <= area_right {
+                right_x
+            } else if left_x >= area.x {
+                left_x
+            } else if area_right - right_x >= source.x - area.x {
+                right_x
+            } else {
+                left_x
+            }
+        }
+    };
+    let geometry = SavedGeometry {
+        x,
+        y: source.y,
+        width: source.width,
+        height: source.height,
+    };
+    match monitor {
+        Some(area) => clamp_to_monitor(geometry, area),
+        None => geometry,
+    }
+}
+
 /// Build window URL with optional query params
 fn build_window_url(file_path: Option<&str>, workspace_root: Option<&str>) -> String {
     let mut params = Vec::new();
@@ -129,21 +403,23 @@ fn build_window_url_with_files(file_paths: &[String], workspace_root: Option<&st
 }
 
 /// Create a new document window from a pre-built URL.
+/// Without explicit geometry the window gets the usual remembered/cascaded placement.
 fn create_document_window_with_url(
     app: &AppHandle,
     url: String,
+    geometry: Option<SavedGeometry>,
 ) -> Result<String, tauri::Error> {
     let count = WINDOW_COUNTER.fetch_add(1, Ordering::SeqCst);
     let label = format!("doc-{}", count);
 
     let title = String::new();
-    let (x, y) = get_cascaded_position(count);
+    let geometry = geometry.unwrap_or_else(|| next_document_geometry(app, count));
 
     let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(url.into()))
         .title(&title)
-        .inner_size(MIN_WIDTH, MIN_HEIGHT)
+        .inner_size(geometry.width, geometry.height)
         .min_inner_size(800.0, 600.0)
-        .position(x, y)
+        .position(geometry.x, geometry.y)
         .resizable(true)
         .fullscreen(false)
         .focused(true);
@@ -157,6 +433,7 @@ fn create_document_window_with_url(
     }
 
     builder.build()?;
+    window_registry::register_window(&label, Vec::new(), None);
 
     Ok(label)
 }
@@ -166,7 +443,7 @@ fn create_document_window_with_url(
 pub fn create_document_window_for_transfer(
     app: &AppHandle,
 ) -> Result<String, tauri::Error> {
-    create_document_window_with_url(app, "/?transfer=true".to_string())
+    create_document_window_with_url(app, "/?transfer=true".to_string(), None)
 }
 
 /// Create a new document window with optional file path and workspace root.
@@ -190,15 +467,15 @@ pub fn create_document_window(
     // Empty initial title - React will update based on settings
     let title = String::new();
 
-    // Get cascaded position (always use minimum size for new windows)
-    let (x, y) = get_cascaded_position(count);
+    // Remembered size, cascaded from the focused window, clamped to its screen
+    let geometry = next_document_geometry(app, count);
 
     // CRITICAL: Full window configuration for proper behavior
     let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(url.into()))
         .title(&title)
-        .inner_size(MIN_WIDTH, MIN_HEIGHT)
+        .inner_size(geometry.width, geometry.height)
         .min_inner_size(800.0, 600.0)
-        .position(x, y)
+        .position(geometry.x, geometry.y)
         .resizable(true)
         .fullscreen(false)
         .focused(true);
@@ -213,6 +490,11 @@ pub fn create_document_window(
     }
 
     builder.build()?;
+    window_registry::register_window(
+        &label,
+        file_path.map(String::from).into_iter().collect(),
+        workspace_root.map(String::from),
+    );
 
     Ok(label)
 }
@@ -240,6 +522,7 @@ pub fn create_main_window(app: &AppHandle) -> Result<String, tauri::Error> {
     }
 
     builder.build()?;
+    window_registry::register_window(label, Vec::new(), None);
 
     Ok(label.to_string())
 }
@@ -250,12 +533,75 @@ pub fn new_window(app: AppHandle) -> Result<String, String> {
     create_document_window(&app, None, None).map_err(|e| e.to_string())
 }
 
-/// Open a file in a new window (Tauri command)
+/// Open a file in a new window (Tauri command).
+/// If another window already has the file open, that window is focused instead.
 #[tauri::command]
 pub fn open_file_in_new_window(app: AppHandle, path: String) -> Result<String, String> {
+    if let Some(label) = window_registry::focus_existing_window_for_file(&app, &path) {
+        return Ok(label);
+    }
     create_document_window(&app, Some(&path), None).map_err(|e| e.to_string())
 }
 
+/// Create a read-only viewer window for a file, or focus the viewer that
+/// already shows it. Viewers have minimal chrome and are never offered as
+/// the window to edit a file in.
+pub fn create_viewer_window(app: &AppHandle, file_path: &str) -> Result<String, tauri::Error> {
+    const VIEWER_WIDTH: f64 = 640.0;
+    const VIEWER_HEIGHT: f64 = 760.0;
+
+    if let Some(label) = window_registry::viewer_for_file(app, file_path) {
+        if let Some(window) = app.get_webview_window(&label) {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        return Ok(label);
+    }
+
+    let count = WINDOW_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let label = format!("{VIEWER_LABEL_PREFIX}{count}");
+    let url = format!("/viewer?file={}", urlencoding::encode(file_path));
+    let title = Path::new(file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (width, height) = saved_geometry("viewer")
+        .map(|g| (g.width, g.height))
+        .unwrap_or((VIEWER_WIDTH, VIEWER_HEIGHT));
+    let (x, y) = get_cascaded_position(count);
+
+    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(url.into()))
+        .title(&title)
+        .inner_size(width, height)
+        .min_inner_size(320.0, 240.0)
+        .position(x, y)
+        .resizable(true)
+        .focused(true);
+
+    #[cfg(target_os = "macos")]
+    {
+        builder = builder
+            .title_bar_style(tauri::TitleBarStyle::Overlay)
+            .hidden_title(true)
+            .accept_first_mouse(true);
+    }
+
+    builder.build()?;
+    window_registry::register_window(&label, vec![file_path.to_string()], None);
+
+    Ok(label)
+}
+
+/// Open a read-only view of a file in its own window (Tauri command)
+#[tauri::command]
+pub fn create_preview_window(app: AppHandle, file_path: String) -> Result<String, String> {
+    if !Path::new(&file_path).is_file() {
+        return Err(format!("File not found: {file_path}"));
+    }
+    create_viewer_window(&app, &file_path).map_err(|e| e.to_string())
+}
+
 /// Open a workspace in a new window with optional file to open (Tauri command)
 ///
 /// Creates a new window with the workspace root set. If a file path is provided,
@@ -274,6 +620,22 @@ pub fn open_workspace_in_new_window(
     .map_err(|e| e.to_string())
 }
 
+/// Create a new document window with several files open as tabs.
+pub fn create_document_window_with_files(
+    app: &AppHandle,
+    file_paths: &[String],
+    workspace_root: Option<&str>,
+) -> Result<String, tauri::Error> {
+    let url = build_window_url_with_files(file_paths, workspace_root);
+    let label = create_document_window_with_url(app, url, None)?;
+    window_registry::register_window(
+        &label,
+        file_paths.to_vec(),
+        workspace_root.map(String::from),
+    );
+    Ok(label)
+}
+
 /// Open a workspace in a new window with multiple files.
 #[tauri::command]
 pub fn open_workspace_with_files_in_new_window(
@@ -281,22 +643,70 @@ pub fn open_workspace_with_files_in_new_window(
     workspace_root: String,
     file_paths: Vec<String>,
 ) -> Result<String, String> {
-    let url = build_window_url_with_files(&file_paths, Some(&workspace_root));
-    create_document_window_with_url(&app, url).map_err(|e| e.to_string())
+    create_document_window_with_files(&app, &file_paths, Some(&workspace_root))
+        .map_err(|e| e.to_string())
+}
+
+/// Open a file in a new window snapped beside `source_label` (same size,
+/// right or left depending on screen space), sharing the source's workspace.
+/// Emulates side-by-side editing until in-window splits exist.
+#[tauri::command]
+pub fn create_split_window(
+    app: AppHandle,
+    source_label: String,
+    file_path: Option<String>,
+) -> Result<String, String> {
+    let source = app
+        .get_webview_window(&source_label)
+        .ok_or_else(|| format!("Window '{}' not found", source_label))?;
+
+    let scale = source.scale_factor().map_err(|e| e.to_string())?;
+    let position = source
+        .outer_position()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let inner = source
+        .inner_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let outer = source
+        .outer_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let monitor = source
+        .current_monitor()
+        .ok()
+        .flatten()
+        .map(|m| monitor_area(&m));
+
+    let geometry = compute_split_placement(
+        SavedGeometry {
+            x: position.x,
+            y: position.y,
+            width: inner.width,
+            height: inner.height,
+        },
+        outer.width,
+        monitor,
+    );
+
+    let workspace_root = window_registry::workspace_root_for(&source_label);
+    let url = build_window_url(file_path.as_deref(), workspace_root.as_deref());
+    let label =
+        create_document_window_with_url(&app, url, Some(geometry)).map_err(|e| e.to_string())?;
+    window_registry::register_window(&label, file_path.into_iter().collect(), workspace_root);
+    Ok(label)
 }
 
 /// Close a specific window by label
 #[tauri::command]
 pub fn close_window(app: AppHandle, label: String) -> Result<(), String> {
-    #[cfg(debug_assertions)]
-    eprintln!("[Tauri] close_window called for '{}'", label);
+    tracing::debug!("close_window called for '{}'", label);
 
     if let Some(window) = app.get_webview_window(&label) {
-        #[cfg(debug_assertions)]
-        eprintln!("[Tauri] destroying window '{}'", label);
+        tracing::debug!("destroying window '{}'", label);
         let result = window.destroy().map_err(|e| e.to_string());
-        #[cfg(debug_assertions)]
-        eprintln!("[Tauri] window '{}' destroy result: {:?}", label, result);
+        tracing::debug!("window '{}' destroy result: {:?}", label, result);
         result
     } else {
         Err(format!("Window '{}' not found", label))
@@ -324,12 +734,10 @@ pub fn show_settings_window_section(app: &AppHandle, section: Option<&str>) -> R
 
     // If settings window exists, bring it to front, focus, and navigate to section
     if let Some(window) = app.get_webview_window(SETTINGS_LABEL) {
-        #[cfg(debug_assertions)]
-        eprintln!("[window_manager] Settings window exists, focusing it");
+        tracing::debug!("Settings window exists, focusing it");
         // Unminimize if minimized
         if window.is_minimized().unwrap_or(false) {
-            #[cfg(debug_assertions)]
-            eprintln!("[window_manager] Settings was minimized, unminimizing");
+            tracing::debug!("Settings was minimized, unminimizing");
             let _ = window.unminimize();
         }
         // Show and focus
@@ -342,8 +750,7 @@ pub fn show_settings_window_section(app: &AppHandle, section: Option<&str>) -> R
         return Ok(SETTINGS_LABEL.to_string());
     }
 
-    #[cfg(debug_assertions)]
-    eprintln!("[window_manager] Creating new settings window");
+    tracing::debug!("Creating new settings window");
 
     // Build URL with optional section query param
     let url = match section {
@@ -376,16 +783,82 @@ pub fn show_settings_window_section(app: &AppHandle, section: Option<&str>) -> R
     let window = builder.build()?;
 
     // Override any restored state by explicitly setting size and centering
-    let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize {
-        width: SETTINGS_WIDTH,
-        height: SETTINGS_HEIGHT,
-    }));
+    // (reuse the last size the user gave the settings window)
+    let (width, height) = saved_geometry("settings")
+        .map(|g| (g.width.max(SETTINGS_MIN_WIDTH), g.height.max(SETTINGS_MIN_HEIGHT)))
+        .unwrap_or((SETTINGS_WIDTH, SETTINGS_HEIGHT));
+    let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }));
     let _ = window.center();
     let _ = window.show();
 
     Ok(SETTINGS_LABEL.to_string())
 }
 
+/// Lowest opacity a window can be faded to; below this text is unreadable.
+const MIN_WINDOW_OPACITY: f64 = 0.2;
+
+/// Clamp a requested opacity to the supported range (NaN means opaque).
+fn clamp_opacity(opacity: f64) -> f64 {
+    if opacity.is_nan() {
+        1.0
+    } else {
+        opacity.clamp(MIN_WINDOW_OPACITY, 1.0)
+    }
+}
+
+/// Keep a window above other apps' windows, or release it.
+/// Also updates the "Keep on Top" check mark for that window.
+pub fn pin_window(app: &AppHandle, label: &str, on_top: bool) -> Result<(), String> {
+    let window = app
+        .get_webview_window(label)
+        .ok_or_else(|| format!("Window not found: {label}"))?;
+    window.set_always_on_top(on_top).map_err(|e| e.to_string())?;
+    crate::menu_state::set_window_item_checked(app, label, "keep-on-top", on_top);
+    Ok(())
+}
+
+/// Set a window's opacity, returning the value actually applied.
+pub fn fade_window(app: &AppHandle, label: &str, opacity: f64) -> Result<f64, String> {
+    let window = app
+        .get_webview_window(label)
+        .ok_or_else(|| format!("Window not found: {label}"))?;
+    let opacity = clamp_opacity(opacity);
+    apply_window_opacity(&window, opacity)?;
+    Ok(opacity)
+}
+
+#[cfg(target_os = "macos")]
+fn apply_window_opacity(window: &tauri::WebviewWindow, opacity: f64) -> Result<(), String> {
+    // Raw pointers aren't Send; the address is only dereferenced on the main thread
+    let ns_window = window.ns_window().map_err(|e| e.to_string())? as usize;
+    window
+        .run_on_main_thread(move || {
+            // SAFETY: Tauri owns a live NSWindow at this address while the window exists
+            let ns_window = unsafe { &*(ns_window as *const objc2_app_kit::NSWindow) };
+            ns_window.setAlphaValue(opacity);
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri has no cross-platform opacity API; only the macOS path is wired up.
+#[cfg(not(target_os = "macos"))]
+fn apply_window_opacity(_window: &tauri::WebviewWindow, _opacity: f64) -> Result<(), String> {
+    Err("Window opacity is only supported on macOS".to_string())
+}
+
+/// Pin a window above other apps (Tauri command).
+#[tauri::command]
+pub fn set_window_always_on_top(app: AppHandle, label: String, on_top: bool) -> Result<(), String> {
+    pin_window(&app, &label, on_top)
+}
+
+/// Fade a window, e.g. a pinned note over a browser (Tauri command).
+/// The opacity is clamped to 0.2–1.0; the applied value is returned.
+#[tauri::command]
+pub fn set_window_opacity(app: AppHandle, label: String, opacity: f64) -> Result<f64, String> {
+    fade_window(&app, &label, opacity)
+}
+
 /// Force quit the entire application
 #[tauri::command]
 pub fn force_quit(app: AppHandle) {
@@ -431,6 +904,16 @@ mod tests {
         assert_eq!(get_workspace_root_for_file(""), None);
     }
 
+    // -- clamp_opacity ---------------------------------------------------------
+
+    #[test]
+    fn opacity_is_clamped_to_readable_range() {
+        assert_eq!(clamp_opacity(0.5), 0.5);
+        assert_eq!(clamp_opacity(0.0), MIN_WINDOW_OPACITY);
+        assert_eq!(clamp_opacity(3.0), 1.0);
+        assert_eq!(clamp_opacity(f64::NAN), 1.0);
+    }
+
     // -- determine_file_open_action --------------------------------------------
 
     #[test]
@@ -559,10 +1042,130 @@ mod tests {
         assert_eq!(pending[1].path, "/new.md");
     }
 
+    #[test]
+    fn queue_skips_already_queued_paths() {
+        let mut pending = Vec::new();
+        queue_pending_file_opens(&mut pending, vec!["/a/x.md".to_string()], Some("/a"));
+        queue_pending_file_opens(
+            &mut pending,
+            vec!["/a/x.md".to_string(), "/a/y.md".to_string()],
+            Some("/a"),
+        );
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[1].path, "/a/y.md");
+    }
+
     #[test]
     fn queue_empty_file_paths_is_noop() {
         let mut pending = Vec::new();
         queue_pending_file_opens(&mut pending, vec![], Some("/a"));
         assert!(pending.is_empty());
     }
+
+    // -- window geometry -------------------------------------------------------
+
+    const SCREEN: MonitorArea = MonitorArea {
+        x: 0.0,
+        y: 25.0,
+        width: 1440.0,
+        height: 875.0,
+    };
+
+    fn geom(x: f64, y: f64, width: f64, height: f64) -> SavedGeometry {
+        SavedGeometry { x, y, width, height }
+    }
+
+    #[test]
+    fn window_kind_groups_document_windows() {
+        assert_eq!(window_kind("main"), Some("document"));
+        assert_eq!(window_kind("doc-3"), Some("document"));
+        assert_eq!(window_kind("settings"), Some("settings"));
+        assert_eq!(window_kind("viewer-4"), Some("viewer"));
+        assert_eq!(window_kind("other"), None);
+    }
+
+    #[test]
+    fn placement_defaults_without_memory() {
+        let g = compute_window_placement(None, None, 0, None);
+        assert_eq!(g, geom(BASE_X, BASE_Y, MIN_WIDTH, MIN_HEIGHT));
+    }
+
+    #[test]
+    fn placement_reuses_saved_geometry() {
+        let saved = geom(200.0, 150.0, 1000.0, 700.0);
+        let g = compute_window_placement(Some(saved), None, 4, Some(SCREEN));
+        assert_eq!(g, saved);
+    }
+
+    #[test]
+    fn placement_cascades_from_focused_window() {
+        let saved = geom(200.0, 150.0, 1000.0, 700.0);
+        let g = compute_window_placement(Some(saved), Some((50.0, 60.0)), 0, Some(SCREEN));
+        assert_eq!(g, geom(75.0, 85.0, 1000.0, 700.0));
+    }
+
+    #[test]
+    fn placement_never_smaller_than_minimum() {
+        let saved = geom(0.0, 25.0, 300.0, 200.0);
+        let g = compute_window_placement(Some(saved), None, 0, None);
+        assert_eq!((g.width, g.height), (MIN_WIDTH, MIN_HEIGHT));
+    }
+
+    #[test]
+    fn placement_wraps_cascade_at_screen_edge() {
+        let saved = geom(0.0, 0.0, 1000.0, 700.0);
+        let g = compute_window_placement(Some(saved), Some((430.0, 180.0)), 0, Some(SCREEN));
+        // 455 + 1000 > 1440 and 205 + 700 > 900: wrap to the work area origin
+        assert_eq!(g, geom(0.0, 25.0, 1000.0, 700.0));
+    }
+
+    #[test]
+    fn placement_clamps_to_screen() {
+        let saved = geom(-3000.0, -50.0, 2000.0, 1200.0);
+        let g = compute_window_placement(Some(saved), None, 0, Some(SCREEN));
+        assert_eq!(g, geom(0.0, 25.0, 1440.0, 875.0));
+    }
+
+    #[test]
+    fn split_goes_right_when_it_fits() {
+        let source = geom(0.0, 25.0, 700.0, 800.0);
+        let g = compute_split_placement(source, 700.0, Some(SCREEN));
+        assert_eq!(g, geom(700.0, 25.0, 700.0, 800.0));
+    }
+
+    #[test]
+    fn split_goes_left_when_right_is_full() {
+        let source = geom(720.0, 40.0, 700.0, 800.0);
+        let g = compute_split_placement(source, 700.0, Some(SCREEN));
+        assert_eq!(g, geom(20.0, 40.0, 700.0, 800.0));
+    }
+
+    #[test]
+    fn split_prefers_roomier_side_when_neither_fits() {
+        // 1000px wide source near the left edge: more room on the right
+        let source = geom(100.0, 25.0, 1000.0, 700.0);
+        let g = compute_split_placement(source, 1000.0, Some(SCREEN));
+        // Right side (x=1100) overflows, so it is clamped back on screen
+        assert_eq!(g.width, 1000.0);
+        assert!(g.x + g.width <= SCREEN.x + SCREEN.width);
+    }
+
+    #[test]
+    fn split_without_monitor_goes_right() {
+        let source = geom(50.0, 60.0, 800.0, 600.0);
+        let g = compute_split_placement(source, 810.0, None);
+        assert_eq!(g, geom(860.0, 60.0, 800.0, 600.0));
+    }
+
+    #[test]
+    fn geometry_file_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("nested").join(WINDOW_GEOMETRY_FILE);
+        assert!(load_geometry(&file).is_empty());
+
+        let mut by_kind = HashMap::new();
+        by_kind.insert("document".to_string(), geom(10.0, 20.0, 900.0, 650.0));
+        save_geometry(&file, &by_kind).unwrap();
+        assert_eq!(load_geometry(&file), by_kind);
+    }
 }
@@ -0,0 +1,308 @@
+//! Window registry - which window shows which files.
+//!
+//! Tracks every window's type, open files and workspace root so that opening
+//! a file can focus the window that already has it instead of creating a
+//! duplicate. Windows are registered by window_manager when created; document
+//! windows then keep their entry current via `update_window_state`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::window_manager;
+
+/// Files and workspace reported for a window
+#[derive(Debug, Clone, Default, PartialEq)]
+struct WindowRecord {
+    files: Vec<String>,
+    /// `files` normalized, resolved before the registry is locked
+    canonical: Vec<PathBuf>,
+    workspace_root: Option<String>,
+}
+
+impl WindowRecord {
+    fn new(files: Vec<String>, workspace_root: Option<String>) -> Self {
+        let canonical = files.iter().map(|file| normalize(file)).collect();
+        Self {
+            files,
+            canonical,
+            workspace_root,
+        }
+    }
+}
+
+/// Window state returned to the frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowInfo {
+    pub label: String,
+    /// "document", "viewer", "settings" or "other"
+    pub kind: String,
+    pub files: Vec<String>,
+    pub workspace_root: Option<String>,
+    pub focused: bool,
+}
+
+/// Payload of the window-local "open-file" event
+#[derive(Debug, Clone, Serialize)]
+struct OpenFilePayload {
+    path: String,
+}
+
+/// Registry of window state, keyed by window label.
+static REGISTRY: Mutex<Option<HashMap<String, WindowRecord>>> = Mutex::new(None);
+
+fn registry() -> std::sync::MutexGuard<'static, Option<HashMap<String, WindowRecord>>> {
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Register a newly created window with its initial file(s) and workspace.
+pub fn register_window(label: &str, files: Vec<String>, workspace_root: Option<String>) {
+    // Canonicalizing touches the disk: do it before taking the lock
+    let record = WindowRecord::new(files, workspace_root);
+    let mut guard = registry();
+    guard
+        .get_or_insert_with(HashMap::new)
+        .insert(label.to_string(), record);
+}
+
+/// Forget a window. Called from the `WindowEvent::Destroyed` handler.
+pub fn unregister_window(label: &str) {
+    let mut guard = registry();
+    if let Some(map) = guard.as_mut() {
+        map.remove(label);
+    }
+}
+
+/// Workspace root reported by a window, if any.
+pub fn workspace_root_for(label: &str) -> Option<String> {
+    registry().as_ref()?.get(label)?.workspace_root.clone()
+}
+
+/// Workspace roots of all registered windows.
+pub fn workspace_roots() -> Vec<String> {
+    registry()
+        .as_ref()
+        .map(|map| map.values().filter_map(|r| r.workspace_root.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// Every file open in any registered window.
+pub fn all_open_files() -> Vec<String> {
+    registry()
+        .as_ref()
+        .map(|map| map.values().flat_map(|r| r.files.iter().cloned()).collect())
+        .unwrap_or_default()
+}
+
+/// Normalize a path for comparison (canonical when it exists).
+fn normalize(path: &str) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| Path::new(path).to_path_buf())
+}
+
+/// Find the window that has `path` (normalized: `target`) open. Labels are
+/// checked in sorted order so the result is stable when several windows have
+/// the same file. Read-only viewers are skipped: they can't edit the file.
+fn find_window_for_file(
+    records: &HashMap<String, WindowRecord>,
+    path: &str,
+    target: &Path,
+) -> Option<String> {
+    find_window(records, path, target, false)
+}
+
+fn find_window(
+    records: &HashMap<String, WindowRecord>,
+    path: &str,
+    target: &Path,
+    viewer: bool,
+) -> Option<String> {
+    let mut labels: Vec<&String> = records
+        .keys()
+        .filter(|label| (window_manager::window_kind(label) == Some("viewer")) == viewer)
+        .collect();
+    labels.sort();
+    labels
+        .into_iter()
+        .find(|label| {
+            let record = &records[*label];
+            record.files.iter().any(|file| file == path)
+                || record.canonical.iter().any(|file| file == target)
+        })
+        .cloned()
+}
+
+/// Registered window (still alive) that has `path` open.
+pub fn window_for_file(app: &AppHandle, path: &str) -> Option<String> {
+    let target = normalize(path);
+    let guard = registry();
+    let records = guard.as_ref()?;
+    find_window_for_file(records, path, &target).filter(|label| app.get_webview_window(label).is_some())
+}
+
+/// Viewer window (still alive) showing `path`.
+pub fn viewer_for_file(app: &AppHandle, path: &str) -> Option<String> {
+    let target = normalize(path);
+    let guard = registry();
+    let records = guard.as_ref()?;
+    find_window(records, path, &target, true).filter(|label| app.get_webview_window(label).is_some())
+}
+
+/// Bring the window that has `path` open to the front and switch it to that
+/// file's tab. Returns the window label, or None if no window has the file.
+pub fn focus_existing_window_for_file(app: &AppHandle, path: &str) -> Option<String> {
+    let label = window_for_file(app, path)?;
+    let window = app.get_webview_window(&label)?;
+    if window.is_minimized().unwrap_or(false) {
+        let _ = window.unminimize();
+    }
+    let _ = window.show();
+    let _ = window.set_focus();
+    // Window-local "open-file" activates the existing tab for the path
+    let _ = app.emit_to(
+        label.as_str(),
+        "open-file",
+        OpenFilePayload {
+            path: path.to_string(),
+        },
+    );
+    Some(label)
+}
+
+/// Replace a window's reported files and workspace root.
+#[tauri::command]
+pub fn update_window_state(
+    app: AppHandle,
+    window_label: String,
+    files: Vec<String>,
+    workspace_root: Option<String>,
+) -> Result<(), String> {
+    register_window(&window_label, files, workspace_root);
+    // A `vmark --wait` invocation ends when its files are closed
+    crate::cli::check_wait(&app);
+    Ok(())
+}
+
+/// Label of the window that has `path` open, if any.
+#[tauri::command]
+pub fn get_window_for_file(app: AppHandle, path: String) -> Option<String> {
+    window_for_file(&app, &path)
+}
+
+/// All open windows with their type, files and workspace root.
+#[tauri::command]
+pub fn list_windows_with_state(app: AppHandle) -> Vec<WindowInfo> {
+    let records = registry().clone().unwrap_or_default();
+    let mut windows: Vec<WindowInfo> = app
+        .webview_windows()
+        .into_iter()
+        .map(|(label, window)| {
+            let record = records.get(&label).cloned().unwrap_or_default();
+            WindowInfo {
+                kind: window_manager::window_kind(&label)
+                    .unwrap_or("other")
+                    .to_string(),
+                files: record.files,
+                workspace_root: record.workspace_root,
+                focused: window.is_focused().unwrap_or(false),
+                label,
+            }
+        })
+        .collect();
+    windows.sort_by(|a, b| a.label.cmp(&b.label));
+    windows
+}
+
+/// Focus the window that has `path` open. Returns its label, or None when
+/// the file isn't open anywhere (the caller should open it normally).
+#[tauri::command]
+pub fn focus_window_for_file(app: AppHandle, path: String) -> Option<String> {
+    focus_existing_window_for_file(&app, &path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(files: &[&str], root: Option<&str>) -> WindowRecord {
+        WindowRecord::new(
+            files.iter().map(|f| f.to_string()).collect(),
+            root.map(String::from),
+        )
+    }
+
+    fn find(records: &HashMap<String, WindowRecord>, path: &str) -> Option<String> {
+        find_window_for_file(records, path, &normalize(path))
+    }
+
+    #[test]
+    fn finds_window_with_file() {
+        let mut records = HashMap::new();
+        records.insert("main".to_string(), record(&["/a/one.md"], Some("/a")));
+        records.insert("doc-1".to_string(), record(&["/b/two.md"], None));
+
+        assert_eq!(find(&records, "/b/two.md"), Some("doc-1".into()));
+        assert_eq!(find(&records, "/a/one.md"), Some("main".into()));
+        assert_eq!(find(&records, "/c/three.md"), None);
+    }
+
+    #[test]
+    fn viewers_are_tracked_separately() {
+        let mut records = HashMap::new();
+        records.insert("viewer-2".to_string(), record(&["/a/one.md"], None));
+
+        assert_eq!(find(&records, "/a/one.md"), None);
+        assert_eq!(find_window(&records, "/a/one.md", Path::new("/a/one.md"), true), Some("viewer-2".into()));
+
+        records.insert("doc-1".to_string(), record(&["/a/one.md"], None));
+        assert_eq!(find(&records, "/a/one.md"), Some("doc-1".into()));
+    }
+
+    #[test]
+    fn duplicate_file_resolves_to_first_label() {
+        let mut records = HashMap::new();
+        records.insert("main".to_string(), record(&["/a/one.md"], None));
+        records.insert("doc-0".to_string(), record(&["/a/one.md"], None));
+
+        assert_eq!(find(&records, "/a/one.md"), Some("doc-0".into()));
+    }
+
+    #[test]
+    fn matches_equivalent_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("note.md");
+        std::fs::write(&real, "# Note").unwrap();
+
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let mut records = HashMap::new();
+        records.insert(
+            "doc-2".to_string(),
+            record(&[real.to_str().unwrap()], None),
+        );
+
+        // Same file reached via a `..` detour
+        let detour = dir.path().join("sub").join("..").join("note.md");
+        assert_eq!(
+            find(&records, detour.to_str().unwrap()),
+            Some("doc-2".into())
+        );
+    }
+
+    #[test]
+    fn register_update_and_unregister() {
+        register_window("doc-reg", vec!["/x/a.md".into()], Some("/x".into()));
+        register_window("doc-reg", vec!["/x/b.md".into()], None);
+        assert!(all_open_files().contains(&"/x/b.md".to_string()));
+        {
+            let guard = registry();
+            let entry = &guard.as_ref().unwrap()["doc-reg"];
+            assert_eq!(entry, &record(&["/x/b.md"], None));
+        }
+        unregister_window("doc-reg");
+        assert!(!registry().as_ref().unwrap().contains_key("doc-reg"));
+    }
+}
@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use tauri_plugin_dialog::{DialogExt, FilePath};
 
+/// Current workspace settings schema version.
+/// - v1: excludeFolders, showHiddenFiles, lastOpenTabs, ai, identity
+/// - v2: adds per-workspace editor settings (`vmark.editor`) and named task
+///   commands (`vmark.tasks`, optional)
+pub const WORKSPACE_SCHEMA_VERSION: u32 = 2;
+
 /// VS Code-compatible workspace file with VMark namespace extensions.
 /// Stored in `.vmark/vmark.code-workspace`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,9 +43,70 @@ pub struct WorkspaceIdentity {
     pub trusted_at: Option<i64>,
 }
 
+/// Per-workspace editor settings (schema v2).
+/// Unset fields fall back to the user's global settings.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct WorkspaceEditorSettings {
+    /// Line endings for saved files: "lf" or "crlf"
+    #[serde(rename = "lineEnding", default, skip_serializing_if = "Option::is_none")]
+    pub line_ending: Option<String>,
+    /// Folder for pasted/dropped assets, relative to the workspace root
+    #[serde(rename = "assetsFolder", default, skip_serializing_if = "Option::is_none")]
+    pub assets_folder: Option<String>,
+    /// Theme used for HTML/PDF export
+    #[serde(rename = "exportTheme", default, skip_serializing_if = "Option::is_none")]
+    pub export_theme: Option<String>,
+    /// Spellcheck language (BCP 47 tag, e.g. "en-US")
+    #[serde(rename = "spellcheckLanguage", default, skip_serializing_if = "Option::is_none")]
+    pub spellcheck_language: Option<String>,
+    /// Document encryption policy: "off", "ask" or "always" (see `encryption`)
+    #[serde(rename = "encryption", default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<String>,
+}
+
+impl WorkspaceEditorSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Reject values that would be unsafe or meaningless to apply.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref ending) = self.line_ending {
+            if ending != "lf" && ending != "crlf" {
+                return Err(format!("Invalid line ending: {ending} (expected \"lf\" or \"crlf\")"));
+            }
+        }
+        if let Some(ref policy) = self.encryption {
+            if !crate::encryption::ENCRYPTION_POLICIES.contains(&policy.as_str()) {
+                return Err(format!(
+                    "Invalid encryption policy: {policy} (expected \"off\", \"ask\" or \"always\")"
+                ));
+            }
+        }
+        if let Some(ref folder) = self.assets_folder {
+            let path = Path::new(folder);
+            let escapes = path.components().any(|c| {
+                matches!(
+                    c,
+                    std::path::Component::ParentDir
+                        | std::path::Component::RootDir
+                        | std::path::Component::Prefix(_)
+                )
+            });
+            if escapes {
+                return Err(format!("Assets folder must be inside the workspace: {folder}"));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Settings block with VMark-namespaced fields
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WorkspaceSettings {
+    /// Settings schema version (missing in v1 files)
+    #[serde(rename = "vmark.schemaVersion", default)]
+    pub schema_version: u32,
     /// Folders to exclude from file tree (VMark extension)
     #[serde(rename = "vmark.excludeFolders", default)]
     pub exclude_folders: Vec<String>,
@@ -54,6 +122,12 @@ pub struct WorkspaceSettings {
     /// Workspace identity and trust info (VMark extension)
     #[serde(rename = "vmark.identity", default, skip_serializing_if = "Option::is_none")]
     pub identity: Option<WorkspaceIdentity>,
+    /// Per-workspace editor settings (VMark extension, schema v2)
+    #[serde(rename = "vmark.editor", default, skip_serializing_if = "WorkspaceEditorSettings::is_empty")]
+    pub editor: WorkspaceEditorSettings,
+    /// Named one-shot commands, e.g. "preview": "hugo server" (VMark extension)
+    #[serde(rename = "vmark.tasks", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tasks: BTreeMap<String, String>,
 }
 
 impl Default for WorkspaceFile {
@@ -63,6 +137,7 @@ impl Default for WorkspaceFile {
                 path: ".".to_string(),
             }],
             settings: WorkspaceSettings {
+                schema_version: WORKSPACE_SCHEMA_VERSION,
                 exclude_folders: vec![
                     ".git".to_string(),
                     "node_modules".to_string(),
@@ -72,6 +147,8 @@ impl Default for WorkspaceFile {
                 last_open_tabs: vec![],
                 ai: None,
                 identity: None,
+                editor: WorkspaceEditorSettings::default(),
+                tasks: BTreeMap::new(),
             },
         }
     }
@@ -106,12 +183,16 @@ pub struct WorkspaceConfig {
     pub ai: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub identity: Option<WorkspaceIdentity>,
+    #[serde(default)]
+    pub editor: WorkspaceEditorSettings,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tasks: BTreeMap<String, String>,
 }
 
 impl Default for WorkspaceConfig {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: WORKSPACE_SCHEMA_VERSION,
             exclude_folders: vec![
                 ".git".to_string(),
                 "node_modules".to_string(),
@@ -121,19 +202,24 @@ impl Default for WorkspaceConfig {
             last_open_tabs: vec![],
             ai: None,
             identity: None,
+            editor: WorkspaceEditorSettings::default(),
+            tasks: BTreeMap::new(),
         }
     }
 }
 
 impl From<WorkspaceFile> for WorkspaceConfig {
     fn from(file: WorkspaceFile) -> Self {
+        let file = migrate_workspace_file(file);
         Self {
-            version: 1,
+            version: file.settings.schema_version,
             exclude_folders: file.settings.exclude_folders,
             show_hidden_files: file.settings.show_hidden_files,
             last_open_tabs: file.settings.last_open_tabs,
             ai: file.settings.ai,
             identity: file.settings.identity,
+            editor: file.settings.editor,
+            tasks: file.settings.tasks,
         }
     }
 }
@@ -145,11 +231,14 @@ impl From<WorkspaceConfig> for WorkspaceFile {
                 path: ".".to_string(),
             }],
             settings: WorkspaceSettings {
+                schema_version: WORKSPACE_SCHEMA_VERSION,
                 exclude_folders: config.exclude_folders,
                 show_hidden_files: config.show_hidden_files,
                 last_open_tabs: config.last_open_tabs,
                 ai: config.ai,
                 identity: config.identity,
+                editor: config.editor,
+                tasks: config.tasks,
             },
         }
     }
@@ -164,10 +253,21 @@ impl From<LegacyWorkspaceConfig> for WorkspaceConfig {
             last_open_tabs: legacy.last_open_tabs,
             ai: legacy.ai,
             identity: None, // Legacy configs don't have identity
+            editor: WorkspaceEditorSettings::default(),
+            tasks: BTreeMap::new(),
         }
     }
 }
 
+/// Upgrade a workspace file to the current schema version.
+/// v1 -> v2: editor settings start empty (serde default), so only the version changes.
+fn migrate_workspace_file(mut file: WorkspaceFile) -> WorkspaceFile {
+    if file.settings.schema_version < WORKSPACE_SCHEMA_VERSION {
+        file.settings.schema_version = WORKSPACE_SCHEMA_VERSION;
+    }
+    file
+}
+
 /// Get the path to the new workspace file (.vmark/vmark.code-workspace)
 fn get_workspace_file_path(root_path: &Path) -> std::path::PathBuf {
     root_path.join(".vmark").join("vmark.code-workspace")
@@ -266,6 +366,7 @@ pub async fn open_folder_dialog(app: tauri::AppHandle) -> Result<Option<String>,
 }
 
 /// Read workspace config, with automatic migration from legacy format.
+/// AI settings are omitted unless the workspace is trusted.
 #[tauri::command]
 pub fn read_workspace_config(root_path: &str) -> Result<Option<WorkspaceConfig>, String> {
     let root = Path::new(root_path);
@@ -279,7 +380,7 @@ pub fn read_workspace_config(root_path: &str) -> Result<Option<WorkspaceConfig>,
     if !workspace_path.exists() {
         // Fall back to legacy location for backwards compatibility
         if let Some(legacy) = read_legacy_config(root)? {
-            return Ok(Some(legacy.into()));
+            return Ok(Some(apply_trust(root, legacy.into())));
         }
         return Ok(None);
     }
@@ -290,12 +391,32 @@ pub fn read_workspace_config(root_path: &str) -> Result<Option<WorkspaceConfig>,
     let workspace_file: WorkspaceFile = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse workspace file: {e}"))?;
 
-    Ok(Some(workspace_file.into()))
+    Ok(Some(apply_trust(root, workspace_file.into())))
+}
+
+/// Folder names the workspace config excludes; none when it has no config or
+/// can't be read.
+pub(crate) fn workspace_excludes(root: &Path) -> Vec<String> {
+    read_workspace_config(&root.to_string_lossy())
+        .ok()
+        .flatten()
+        .map(|c| c.exclude_folders)
+        .unwrap_or_default()
+}
+
+/// Drop execution-adjacent settings (AI configuration) from untrusted workspaces.
+fn apply_trust(root: &Path, mut config: WorkspaceConfig) -> WorkspaceConfig {
+    if !crate::workspace_trust::is_trusted(root) {
+        config.ai = None;
+    }
+    config
 }
 
 /// Write workspace config to .vmark/vmark.code-workspace
 #[tauri::command]
 pub fn write_workspace_config(root_path: &str, config: WorkspaceConfig) -> Result<(), String> {
+    config.editor.validate()?;
+
     let root = Path::new(root_path);
     let vmark_dir = root.join(".vmark");
 
@@ -305,8 +426,16 @@ pub fn write_workspace_config(root_path: &str, config: WorkspaceConfig) -> Resul
             .map_err(|e| format!("Failed to create .vmark directory: {e}"))?;
     }
 
-    let workspace_file: WorkspaceFile = config.into();
     let workspace_path = get_workspace_file_path(root);
+    let mut workspace_file: WorkspaceFile = config.into();
+
+    // Untrusted reads omit AI settings; keep the on-disk value instead of erasing it
+    if workspace_file.settings.ai.is_none() && !crate::workspace_trust::is_trusted(root) {
+        workspace_file.settings.ai = fs::read_to_string(&workspace_path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<WorkspaceFile>(&c).ok())
+            .and_then(|existing| existing.settings.ai);
+    }
 
     let content = serde_json::to_string_pretty(&workspace_file)
         .map_err(|e| format!("Failed to serialize workspace: {e}"))?;
@@ -348,6 +477,8 @@ mod tests {
             last_open_tabs: vec!["file.md".to_string()],
             ai: None,
             identity: None,
+            editor: WorkspaceEditorSettings::default(),
+            tasks: BTreeMap::new(),
         };
 
         let file: WorkspaceFile = config.clone().into();
@@ -378,6 +509,8 @@ mod tests {
             last_open_tabs: vec!["doc.md".to_string()],
             ai: None,
             identity: None,
+            editor: WorkspaceEditorSettings::default(),
+            tasks: BTreeMap::new(),
         };
 
         write_workspace_config(root, config.clone()).unwrap();
@@ -455,4 +588,97 @@ mod tests {
         let result = read_workspace_config(root.to_str().unwrap());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_untrusted_workspace_hides_but_keeps_ai_settings() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let config = WorkspaceConfig {
+            ai: Some(serde_json::json!({ "provider": "ollama" })),
+            ..WorkspaceConfig::default()
+        };
+        write_workspace_config(root, config).unwrap();
+
+        // Not trusted: AI settings are hidden from the reader
+        let read = read_workspace_config(root).unwrap().unwrap();
+        assert!(read.ai.is_none());
+
+        // Writing back the stripped config must not erase them on disk
+        write_workspace_config(root, read).unwrap();
+        let content = fs::read_to_string(get_workspace_file_path(dir.path())).unwrap();
+        let file: WorkspaceFile = serde_json::from_str(&content).unwrap();
+        assert_eq!(file.settings.ai, Some(serde_json::json!({ "provider": "ollama" })));
+    }
+
+    #[test]
+    fn test_v1_file_migrates_to_v2() {
+        let v1 = r#"{
+            "folders": [{ "path": "." }],
+            "settings": { "vmark.excludeFolders": [".git"], "vmark.lastOpenTabs": [] }
+        }"#;
+        let file: WorkspaceFile = serde_json::from_str(v1).unwrap();
+        assert_eq!(file.settings.schema_version, 0);
+
+        let config: WorkspaceConfig = file.into();
+        assert_eq!(config.version, WORKSPACE_SCHEMA_VERSION);
+        assert!(config.editor.is_empty());
+    }
+
+    #[test]
+    fn test_editor_settings_roundtrip() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let config = WorkspaceConfig {
+            editor: WorkspaceEditorSettings {
+                line_ending: Some("crlf".to_string()),
+                assets_folder: Some("assets".to_string()),
+                ..Default::default()
+            },
+            ..WorkspaceConfig::default()
+        };
+        write_workspace_config(root, config).unwrap();
+
+        let content = fs::read_to_string(get_workspace_file_path(dir.path())).unwrap();
+        assert!(content.contains("\"vmark.schemaVersion\": 2"));
+        assert!(content.contains("\"lineEnding\": \"crlf\""));
+
+        let read = read_workspace_config(root).unwrap().unwrap();
+        assert_eq!(read.editor.line_ending.as_deref(), Some("crlf"));
+        assert_eq!(read.editor.assets_folder.as_deref(), Some("assets"));
+    }
+
+    #[test]
+    fn test_editor_settings_validation() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let bad_ending = WorkspaceConfig {
+            editor: WorkspaceEditorSettings {
+                line_ending: Some("cr".to_string()),
+                ..Default::default()
+            },
+            ..WorkspaceConfig::default()
+        };
+        assert!(write_workspace_config(root, bad_ending).is_err());
+
+        let escaping = WorkspaceConfig {
+            editor: WorkspaceEditorSettings {
+                assets_folder: Some("../outside".to_string()),
+                ..Default::default()
+            },
+            ..WorkspaceConfig::default()
+        };
+        assert!(write_workspace_config(root, escaping).is_err());
+
+        let bad_policy = WorkspaceConfig {
+            editor: WorkspaceEditorSettings {
+                encryption: Some("sometimes".to_string()),
+                ..Default::default()
+            },
+            ..WorkspaceConfig::default()
+        };
+        assert!(write_workspace_config(root, bad_policy).is_err());
+    }
 }
@@ -0,0 +1,457 @@
+//! Workspace Statistics
+//!
+//! Backs the insights panel: note and word counts, link health (internal,
+//! broken, orphaned notes), file size distribution, recently modified notes
+//! and edits per day. Activity comes from file mtimes, so each note counts
+//! once, on the day it was last modified.
+//!
+//! Files are read and analyzed on worker threads; link resolution runs once
+//! all notes are known.
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::file_finder::{collect_notes, relative_path};
+
+/// Notes listed under "recently modified"
+const RECENT_LIMIT: usize = 10;
+
+/// Days of activity reported when the caller doesn't say
+const DEFAULT_ACTIVITY_DAYS: u32 = 90;
+
+/// Upper bounds (bytes, exclusive) and labels for the size histogram
+const SIZE_BUCKETS: &[(u64, &str)] = &[
+    (1024, "< 1 KB"),
+    (10 * 1024, "1–10 KB"),
+    (100 * 1024, "10–100 KB"),
+    (1024 * 1024, "100 KB–1 MB"),
+    (u64::MAX, "> 1 MB"),
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeBucket {
+    pub label: &'static str,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentNote {
+    /// Relative to the workspace root, `/`-separated
+    pub path: String,
+    /// Unix timestamp ms
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayActivity {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStats {
+    pub total_notes: usize,
+    pub total_words: usize,
+    pub total_bytes: u64,
+    /// Markdown links and wiki links, internal and external
+    pub total_links: usize,
+    /// Links that point at a note in the workspace
+    pub internal_links: usize,
+    /// Relative links and wiki links whose target doesn't exist
+    pub broken_links: usize,
+    /// Notes no other note links to
+    pub orphaned_notes: Vec<String>,
+    pub size_distribution: Vec<SizeBucket>,
+    pub recent_notes: Vec<RecentNote>,
+    /// One entry per day in the requested window, oldest first
+    pub activity: Vec<DayActivity>,
+}
+
+/// A link found in a note
+#[derive(Debug, Clone, PartialEq)]
+enum LinkTarget {
+    /// `[[Name]]` / `[[folder/Name#Heading|alias]]` (without heading and alias)
+    Wiki(String),
+    /// Relative path from `[text](path)`, without anchor or query
+    Relative(String),
+    External,
+}
+
+/// Per-note results from the parallel pass
+struct NoteInfo {
+    rel_path: String,
+    size: u64,
+    modified: Option<i64>,
+    words: usize,
+    links: Vec<LinkTarget>,
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Body without frontmatter, with fenced code blocks removed.
+pub(crate) fn prose(content: &str) -> String {
+    let body = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+        .and_then(|rest| {
+            rest.find("\n---")
+                .map(|end| rest[end + 4..].trim_start_matches(['\r', '\n']))
+        })
+        .unwrap_or(content);
+
+    let mut out = String::with_capacity(body.len());
+    let mut fence: Option<&str> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+pub(crate) fn count_words(text: &str) -> usize {
+    // CJK text has no spaces between words; count each ideograph/kana/hangul as one
+    text.split_whitespace()
+        .map(|token| {
+            let cjk = token.chars().filter(|c| is_cjk(*c)).count();
+            let rest = token.chars().any(|c| c.is_alphanumeric() && !is_cjk(c));
+            cjk + usize::from(rest)
+        })
+        .sum()
+}
+
+/// Hiragana, katakana, CJK ideographs or hangul: scripts written without
+/// spaces between words.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+fn classify_href(href: &str) -> LinkTarget {
+    let href = href.trim().trim_start_matches('<').trim_end_matches('>');
+    // Drop an optional title: [x](path "title")
+    let href = href.split_whitespace().next().unwrap_or("");
+    if href.is_empty() || href.starts_with('#') || href.contains("://") || href.starts_with("mailto:") {
+        return LinkTarget::External;
+    }
+    let path = href.split(['#', '?']).next().unwrap_or("");
+    let path = urlencoding::decode(path).map(|p| p.into_owned()).unwrap_or_else(|_| path.to_string());
+    LinkTarget::Relative(path)
+}
+
+fn extract_links(text: &str) -> Vec<LinkTarget> {
+    let mut links = Vec::new();
+
+    // [[target#heading|alias]]
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else { break };
+        let inner = &after[..end];
+        let target = inner.split(['|', '#']).next().unwrap_or("").trim();
+        if !target.is_empty() && !inner.contains('\n') {
+            links.push(LinkTarget::Wiki(target.to_string()));
+        }
+        rest = &after[end + 2..];
+    }
+
+    // [text](href) and ![alt](src) — images count as links too
+    let mut rest = text;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else { break };
+        let href = &after[..end];
+        if !href.contains('\n') {
+            links.push(classify_href(href));
+        }
+        rest = &after[end + 1..];
+    }
+    links
+}
+
+// ============================================================================
+// Collection
+// ============================================================================
+
+fn analyze_note(root: &Path, path: &Path) -> NoteInfo {
+    let meta = fs::metadata(path).ok();
+    let modified = meta
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64);
+    // Encrypted notes can't be read here; they still count as notes
+    let text = if crate::encryption::is_encrypted_path(path) {
+        String::new()
+    } else {
+        fs::read(path)
+            .map(|bytes| prose(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default()
+    };
+    NoteInfo {
+        rel_path: relative_path(root, path),
+        size: meta.map(|m| m.len()).unwrap_or(0),
+        modified,
+        words: count_words(&text),
+        links: extract_links(&text),
+    }
+}
+
+/// Analyze notes on up to `available_parallelism` threads.
+fn analyze_parallel(root: &Path, paths: &[PathBuf]) -> Vec<NoteInfo> {
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+    let chunk = paths.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|p| analyze_note(root, p)).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+// ============================================================================
+// Aggregation
+// ============================================================================
+
+/// Lexically resolve `rel` against the note's folder. None if it escapes the root.
+pub(crate) fn resolve_relative(from_note: &str, rel: &str) -> Option<String> {
+    let mut parts: Vec<&str> = from_note.split('/').collect();
+    parts.pop();
+    let rel = rel.strip_prefix('/').map_or(rel, |r| {
+        parts.clear(); // workspace-absolute
+        r
+    });
+    for component in Path::new(rel).components() {
+        match component {
+            Component::Normal(p) => parts.push(p.to_str()?),
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            _ => {}
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// `path` without a markdown extension (other extensions are kept).
+pub(crate) fn strip_markdown_ext(path: &str) -> &str {
+    match path.rsplit_once('.') {
+        Some((stem, ext)) if crate::file_tree::is_markdown(&format!("x.{ext}")) => stem,
+        _ => path,
+    }
+}
+
+fn aggregate(notes: &[NoteInfo], today: NaiveDate, activity_days: u32) -> WorkspaceStats {
+    let paths: HashSet<&str> = notes.iter().map(|n| n.rel_path.as_str()).collect();
+    // Wiki links match a note by name or by path, without extension, ignoring case
+    let mut by_name: HashMap<String, &str> = HashMap::new();
+    for note in notes {
+        let no_ext = strip_markdown_ext(&note.rel_path);
+        by_name.insert(no_ext.to_lowercase(), &note.rel_path);
+        let name = no_ext.rsplit('/').next().unwrap_or(no_ext).to_lowercase();
+        by_name.entry(name).or_insert(&note.rel_path);
+    }
+
+    let mut linked: HashSet<&str> = HashSet::new();
+    let (mut total_links, mut internal, mut broken) = (0, 0, 0);
+    for note in notes {
+        for link in &note.links {
+            total_links += 1;
+            let target = match link {
+                LinkTarget::External => continue,
+                LinkTarget::Wiki(name) => by_name.get(&strip_markdown_ext(name).to_lowercase()).copied(),
+                LinkTarget::Relative(rel) => {
+                    let resolved = resolve_relative(&note.rel_path, rel);
+                    match resolved {
+                        Some(p) if paths.contains(p.as_str()) => paths.get(p.as_str()).copied(),
+                        // Links to images and other files aren't note links
+                        Some(p) if !crate::file_tree::is_markdown(&p) => continue,
+                        _ => None,
+                    }
+                }
+            };
+            match target {
+                Some(t) => {
+                    internal += 1;
+                    if t != note.rel_path {
+                        linked.insert(t);
+                    }
+                }
+                None => broken += 1,
+            }
+        }
+    }
+
+    let mut orphaned_notes: Vec<String> = notes
+        .iter()
+        .filter(|n| !linked.contains(n.rel_path.as_str()))
+        .map(|n| n.rel_path.clone())
+        .collect();
+    orphaned_notes.sort();
+
+    let size_distribution = SIZE_BUCKETS
+        .iter()
+        .enumerate()
+        .map(|(i, &(upper, label))| {
+            let lower = if i == 0 { 0 } else { SIZE_BUCKETS[i - 1].0 };
+            SizeBucket {
+                label,
+                count: notes.iter().filter(|n| n.size >= lower && n.size < upper).count(),
+            }
+        })
+        .collect();
+
+    let mut recent: Vec<RecentNote> = notes
+        .iter()
+        .filter_map(|n| {
+            n.modified.map(|modified| RecentNote {
+                path: n.rel_path.clone(),
+                modified,
+            })
+        })
+        .collect();
+    recent.sort_by_key(|r| std::cmp::Reverse(r.modified));
+    recent.truncate(RECENT_LIMIT);
+
+    let mut per_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for modified in notes.iter().filter_map(|n| n.modified) {
+        if let Some(time) = DateTime::from_timestamp_millis(modified) {
+            *per_day.entry(time.with_timezone(&Local).date_naive()).or_default() += 1;
+        }
+    }
+    let activity = (0..activity_days)
+        .rev()
+        .filter_map(|back| today.checked_sub_days(chrono::Days::new(u64::from(back))))
+        .map(|date| DayActivity {
+            date: date.format("%Y-%m-%d").to_string(),
+            count: per_day.get(&date).copied().unwrap_or(0),
+        })
+        .collect();
+
+    WorkspaceStats {
+        total_notes: notes.len(),
+        total_words: notes.iter().map(|n| n.words).sum(),
+        total_bytes: notes.iter().map(|n| n.size).sum(),
+        total_links,
+        internal_links: internal,
+        broken_links: broken,
+        orphaned_notes,
+        size_distribution,
+        recent_notes: recent,
+        activity,
+    }
+}
+
+/// Compute statistics for the markdown notes under `root_path`, skipping
+/// hidden folders and the workspace's excluded folders. `activity_days`
+/// (default 90) sets how many days of activity to return.
+#[tauri::command]
+pub async fn get_workspace_stats(root_path: String, activity_days: Option<u32>) -> Result<WorkspaceStats, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = PathBuf::from(&root_path);
+        if !root.is_dir() {
+            return Err(format!("Not a folder: {root_path}"));
+        }
+        let excludes = crate::workspace::workspace_excludes(&root);
+        let notes = analyze_parallel(&root, &collect_notes(&root, &excludes));
+        Ok(aggregate(
+            &notes,
+            Local::now().date_naive(),
+            activity_days.unwrap_or(DEFAULT_ACTIVITY_DAYS),
+        ))
+    })
+    .await
+    .map_err(|e| format!("Failed to compute workspace stats: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prose_strips_frontmatter_and_code() {
+        let text = "---\ntitle: x\n---\n# Hi\n```\n[[not a link]]\n```\nafter\n";
+        assert_eq!(prose(text), "# Hi\nafter\n");
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("Hello, world — again"), 3);
+        assert_eq!(count_words("中文内容 and more"), 6);
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let links = extract_links("See [[Other Note#Intro|alias]], [a](./b.md#x), [w](https://x.y) and ![i](img.png \"t\").");
+        assert_eq!(
+            links,
+            vec![
+                LinkTarget::Wiki("Other Note".to_string()),
+                LinkTarget::Relative("./b.md".to_string()),
+                LinkTarget::External,
+                LinkTarget::Relative("img.png".to_string()),
+            ]
+        );
+        assert_eq!(extract_links("[sp](My%20Note.md)"), vec![LinkTarget::Relative("My Note.md".to_string())]);
+    }
+
+    #[test]
+    fn test_resolve_relative() {
+        assert_eq!(resolve_relative("a/b.md", "../c.md").as_deref(), Some("c.md"));
+        assert_eq!(resolve_relative("a/b.md", "./d/e.md").as_deref(), Some("a/d/e.md"));
+        assert_eq!(resolve_relative("a/b.md", "/top.md").as_deref(), Some("top.md"));
+        assert_eq!(resolve_relative("b.md", "../../x.md"), None);
+    }
+
+    #[test]
+    fn test_workspace_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+        fs::write(root.join("index.md"), "Links to [[Alpha]] and [beta](notes/beta.md) and [[Missing]].").unwrap();
+        fs::write(root.join("notes/alpha.md"), "alpha words here ![img](pic.png)").unwrap();
+        fs::write(root.join("notes/beta.md"), "beta [back](../index.md) [gone](nope.md)").unwrap();
+        fs::write(root.join("lonely.md"), "nobody links here").unwrap();
+        fs::write(root.join(".hidden/skip.md"), "[[lonely]]").unwrap();
+        fs::write(root.join("readme.txt"), "not a note").unwrap();
+
+        let notes = analyze_parallel(root, &collect_notes(root, &[]));
+        let today = Local::now().date_naive();
+        let stats = aggregate(&notes, today, 7);
+
+        assert_eq!(stats.total_notes, 4);
+        assert_eq!(stats.total_links, 6);
+        assert_eq!(stats.internal_links, 3);
+        assert_eq!(stats.broken_links, 2);
+        assert_eq!(stats.orphaned_notes, vec!["lonely.md"]);
+        assert_eq!(stats.size_distribution[0].count, 4);
+        assert_eq!(stats.recent_notes.len(), 4);
+        assert_eq!(stats.activity.len(), 7);
+        assert_eq!(stats.activity.last().unwrap().count, 4);
+        assert_eq!(stats.activity.last().unwrap().date, today.format("%Y-%m-%d").to_string());
+    }
+}
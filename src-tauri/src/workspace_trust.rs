@@ -0,0 +1,249 @@
+//! Workspace Trust
+//!
+//! Opening a folder does not make it trusted. Execution-adjacent features
+//! (workspace tasks, terminal cwd, workspace AI settings from `.vmark/`) stay
+//! inactive until the user explicitly trusts the workspace root.
+//!
+//! Trusted roots are stored in the app data directory (`workspace-trust.json`),
+//! never inside the workspace itself, so a cloned folder cannot grant itself trust.
+//! A path is trusted if it equals or is nested under a trusted root. The
+//! frontend mirrors the answer in the workspace identity's `trustLevel` and
+//! asks the user when an untrusted workspace is opened.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_paths;
+
+/// Trust store file name in the app data directory
+pub const WORKSPACE_TRUST_FILE: &str = "workspace-trust.json";
+
+/// A workspace root the user has trusted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustedRoot {
+    pub path: String,
+    /// When trust was granted (unix timestamp ms)
+    #[serde(rename = "trustedAt")]
+    pub trusted_at: i64,
+}
+
+/// On-disk trust store
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct TrustStore {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    roots: Vec<TrustedRoot>,
+}
+
+/// Loaded trust store plus the file it persists to
+struct TrustState {
+    file: PathBuf,
+    store: TrustStore,
+}
+
+static TRUST_STATE: OnceLock<Mutex<TrustState>> = OnceLock::new();
+
+/// Load the trust store from app data. Called once during app setup.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let file = app_data.join(WORKSPACE_TRUST_FILE);
+    let store = load_store(&file);
+    let _ = TRUST_STATE.set(Mutex::new(TrustState { file, store }));
+    Ok(())
+}
+
+fn load_store(file: &Path) -> TrustStore {
+    fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_store(file: &Path, store: &TrustStore) -> Result<(), String> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize trust store: {}", e))?;
+    app_paths::atomic_write_file(file, content.as_bytes())
+}
+
+/// Normalize a path for comparison (canonical when it exists).
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Check a path against a store (equal to or under a trusted root).
+fn store_trusts(store: &TrustStore, path: &Path) -> bool {
+    let path = normalize(path);
+    store
+        .roots
+        .iter()
+        .any(|root| path.starts_with(Path::new(&root.path)))
+}
+
+/// Add a root to the store. Returns false if it was already trusted.
+fn grant_in_store(store: &mut TrustStore, root: &Path, now_ms: i64) -> bool {
+    let path = normalize(root).to_string_lossy().to_string();
+    if store.roots.iter().any(|r| r.path == path) {
+        return false;
+    }
+    store.version = 1;
+    store.roots.push(TrustedRoot {
+        path,
+        trusted_at: now_ms,
+    });
+    store.roots.sort_by(|a, b| a.path.cmp(&b.path));
+    true
+}
+
+/// Remove a root from the store. Returns false if it was not trusted.
+fn revoke_in_store(store: &mut TrustStore, root: &Path) -> bool {
+    let path = normalize(root).to_string_lossy().to_string();
+    let before = store.roots.len();
+    store.roots.retain(|r| r.path != path);
+    store.roots.len() != before
+}
+
+/// Whether a path is inside a trusted workspace root.
+/// Returns false if the trust store was never initialized.
+pub fn is_trusted(path: &Path) -> bool {
+    TRUST_STATE
+        .get()
+        .and_then(|state| state.lock().ok())
+        .is_some_and(|guard| store_trusts(&guard.store, path))
+}
+
+/// Return an error unless the path is inside a trusted workspace root.
+pub fn require_trusted(path: &Path) -> Result<(), String> {
+    if is_trusted(path) {
+        Ok(())
+    } else {
+        Err(format!("Workspace is not trusted: {}", path.display()))
+    }
+}
+
+fn with_state<T>(f: impl FnOnce(&mut TrustState) -> Result<T, String>) -> Result<T, String> {
+    let state = TRUST_STATE.get().ok_or("Workspace trust not initialized")?;
+    let mut guard = state.lock().map_err(|e| format!("Lock error: {e}"))?;
+    f(&mut guard)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Trust a workspace root. Returns the updated list of trusted roots.
+#[tauri::command]
+pub fn workspace_trust_grant(app: AppHandle, root_path: String) -> Result<Vec<TrustedRoot>, String> {
+    let root = Path::new(&root_path);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {root_path}"));
+    }
+    let roots = with_state(|state| {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        if grant_in_store(&mut state.store, root, now_ms) {
+            save_store(&state.file, &state.store)?;
+        }
+        Ok(state.store.roots.clone())
+    })?;
+    let _ = app.emit("workspace:trust-changed", &roots);
+    Ok(roots)
+}
+
+/// Revoke trust for a workspace root. Returns the updated list of trusted roots.
+#[tauri::command]
+pub fn workspace_trust_revoke(app: AppHandle, root_path: String) -> Result<Vec<TrustedRoot>, String> {
+    let roots = with_state(|state| {
+        if revoke_in_store(&mut state.store, Path::new(&root_path)) {
+            save_store(&state.file, &state.store)?;
+        }
+        Ok(state.store.roots.clone())
+    })?;
+    let _ = app.emit("workspace:trust-changed", &roots);
+    Ok(roots)
+}
+
+/// List all trusted workspace roots.
+#[tauri::command]
+pub fn workspace_trust_list() -> Result<Vec<TrustedRoot>, String> {
+    with_state(|state| Ok(state.store.roots.clone()))
+}
+
+/// Check whether a path is inside a trusted workspace root.
+#[tauri::command]
+pub fn workspace_trust_check(path: String) -> bool {
+    is_trusted(Path::new(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_nested_path_is_trusted() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("notes");
+        fs::create_dir(&nested).unwrap();
+
+        let mut store = TrustStore::default();
+        assert!(!store_trusts(&store, &nested));
+
+        assert!(grant_in_store(&mut store, dir.path(), 1));
+        assert!(store_trusts(&store, dir.path()));
+        assert!(store_trusts(&store, &nested));
+    }
+
+    #[test]
+    fn test_sibling_prefix_is_not_trusted() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("work");
+        let sibling = dir.path().join("work-other");
+        fs::create_dir(&root).unwrap();
+        fs::create_dir(&sibling).unwrap();
+
+        let mut store = TrustStore::default();
+        grant_in_store(&mut store, &root, 1);
+        assert!(!store_trusts(&store, &sibling));
+    }
+
+    #[test]
+    fn test_grant_twice_and_revoke() {
+        let dir = tempdir().unwrap();
+        let mut store = TrustStore::default();
+
+        assert!(grant_in_store(&mut store, dir.path(), 1));
+        assert!(!grant_in_store(&mut store, dir.path(), 2));
+        assert_eq!(store.roots.len(), 1);
+        assert_eq!(store.roots[0].trusted_at, 1);
+
+        assert!(revoke_in_store(&mut store, dir.path()));
+        assert!(!revoke_in_store(&mut store, dir.path()));
+        assert!(!store_trusts(&store, dir.path()));
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("app").join(WORKSPACE_TRUST_FILE);
+        let mut store = TrustStore::default();
+        grant_in_store(&mut store, dir.path(), 42);
+
+        save_store(&file, &store).unwrap();
+        let loaded = load_store(&file);
+        assert_eq!(loaded.roots, store.roots);
+    }
+
+    #[test]
+    fn test_missing_store_is_empty() {
+        let dir = tempdir().unwrap();
+        let loaded = load_store(&dir.path().join(WORKSPACE_TRUST_FILE));
+        assert!(loaded.roots.is_empty());
+    }
+}
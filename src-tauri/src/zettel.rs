@@ -0,0 +1,187 @@
+//! Zettelkasten Helpers
+//!
+//! Unique note IDs and new-note creation from a filename pattern. IDs are
+//! either a local timestamp (`202601311012`, the classic Zettelkasten form)
+//! or a ULID (`01JJ6Z3Q8W9X4K2M5N7P0R1S3T`, sortable and collision-free).
+//!
+//! Filename patterns use `{id}`, `{title}`, `{slug}` and `{date}`
+//! (`YYYY-MM-DD`); the default is `{id} {title}`. Renames keep links intact
+//! through `link_rewrite::update_links_on_rename`.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_PATTERN: &str = "{id} {title}";
+
+/// Crockford base32, as used by ULIDs
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteIdFormat {
+    #[default]
+    Timestamp,
+    Ulid,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedZettel {
+    pub id: String,
+    pub path: String,
+}
+
+fn timestamp_id(now: DateTime<Local>) -> String {
+    now.format("%Y%m%d%H%M%S").to_string()
+}
+
+/// 48-bit millisecond timestamp + 80 random bits, Crockford base32 (26 chars).
+fn ulid(now_ms: u64, random: [u8; 10]) -> String {
+    let mut value = u128::from(now_ms & 0xFFFF_FFFF_FFFF) << 80;
+    for (i, byte) in random.iter().enumerate() {
+        value |= u128::from(*byte) << (72 - 8 * i);
+    }
+    (0..26)
+        .map(|i| CROCKFORD[((value >> (125 - 5 * i)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+fn new_id(format: NoteIdFormat) -> String {
+    let now = Local::now();
+    match format {
+        NoteIdFormat::Timestamp => timestamp_id(now),
+        NoteIdFormat::Ulid => {
+            let mut random = [0u8; 10];
+            random.copy_from_slice(&uuid::Uuid::new_v4().as_bytes()[..10]);
+            ulid(now.timestamp_millis() as u64, random)
+        }
+    }
+}
+
+pub(crate) fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// File name (without extension) for a pattern, with characters that are
+/// invalid in file names on any platform removed.
+fn render_file_name(pattern: &str, id: &str, title: &str, date: &str) -> String {
+    let name = pattern
+        .replace("{id}", id)
+        .replace("{title}", title.trim())
+        .replace("{slug}", &slugify(title))
+        .replace("{date}", date);
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') && !c.is_control())
+        .collect();
+    let name = name.trim().trim_matches('.').to_string();
+    if name.is_empty() {
+        id.to_string()
+    } else {
+        name
+    }
+}
+
+fn zettel_template(id: &str, title: &str) -> String {
+    let quoted = title.replace('"', "\\\"");
+    if title.trim().is_empty() {
+        format!("---\nid: {id}\n---\n\n")
+    } else {
+        format!("---\nid: {id}\ntitle: \"{quoted}\"\n---\n\n# {}\n\n", title.trim())
+    }
+}
+
+/// Create `name.md` in `folder`, adding " (n)" if the name is taken.
+fn create_unique(folder: &Path, name: &str, content: &str) -> Result<PathBuf, String> {
+    for n in 1..1000 {
+        let file_name = if n == 1 { format!("{name}.md") } else { format!("{name} ({n}).md") };
+        let path = folder.join(file_name);
+        match fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {e}", path.display())),
+        }
+    }
+    Err(format!("Too many notes named {name}"))
+}
+
+/// Create a new note in `folder` named by `pattern`, with its ID (and title)
+/// in the frontmatter.
+#[tauri::command]
+pub fn create_zettel(
+    folder: String,
+    title: String,
+    pattern: Option<String>,
+    id_format: Option<NoteIdFormat>,
+) -> Result<CreatedZettel, String> {
+    let folder = Path::new(&folder);
+    if !folder.is_dir() {
+        return Err(format!("Not a folder: {}", folder.display()));
+    }
+    let id = new_id(id_format.unwrap_or_default());
+    let pattern = pattern.filter(|p| !p.trim().is_empty());
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let name = render_file_name(pattern.as_deref().unwrap_or(DEFAULT_PATTERN), &id, &title, &date);
+    let path = create_unique(folder, &name, &zettel_template(&id, &title))?;
+    Ok(CreatedZettel {
+        id,
+        path: path.to_string_lossy().into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_timestamp_id() {
+        let now = Local.with_ymd_and_hms(2026, 1, 31, 10, 12, 5).unwrap();
+        assert_eq!(timestamp_id(now), "20260131101205");
+    }
+
+    #[test]
+    fn test_ulid_encoding() {
+        assert_eq!(ulid(0, [0; 10]), "00000000000000000000000000");
+        assert_eq!(ulid(u64::MAX, [0xFF; 10]), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        // Timestamp prefix from the ULID spec example
+        assert!(ulid(1_469_918_176_385, [0; 10]).starts_with("01ARYZ6S41"));
+        let id = new_id(NoteIdFormat::Ulid);
+        assert_eq!(id.len(), 26);
+    }
+
+    #[test]
+    fn test_render_file_name() {
+        assert_eq!(render_file_name("{id} {title}", "2026", "Ideas: big/small?", "d"), "2026 Ideas bigsmall");
+        assert_eq!(render_file_name("{date}-{slug}", "x", "Hello, World!", "2026-01-31"), "2026-01-31-hello-world");
+        assert_eq!(render_file_name("{title}", "2026", "  ", "d"), "2026");
+    }
+
+    #[test]
+    fn test_create_zettel() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().to_string_lossy().into_owned();
+        let first = create_zettel(folder.clone(), "Note".to_string(), Some("{title}".to_string()), None).unwrap();
+        let second = create_zettel(folder, "Note".to_string(), Some("{title}".to_string()), None).unwrap();
+
+        assert!(first.path.ends_with("Note.md"));
+        assert!(second.path.ends_with("Note (2).md"));
+        let content = fs::read_to_string(&first.path).unwrap();
+        assert!(content.starts_with(&format!("---\nid: {}\ntitle: \"Note\"\n---\n\n# Note\n", first.id)));
+    }
+}
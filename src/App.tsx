@@ -9,7 +9,19 @@ import { FindBar } from "@/components/FindBar";
 import { TitleBar } from "@/components/TitleBar";
 import { UniversalToolbar } from "@/components/Editor/UniversalToolbar";
 import { TerminalPanel } from "@/components/Terminal";
+import { CompareView } from "@/components/CompareView";
+import { QuickOpen } from "@/components/QuickOpen";
+import { ReadabilityDialog } from "@/components/ReadabilityDialog";
+import { PassphraseDialog } from "@/components/PassphraseDialog";
+import { BackupDialog } from "@/components/BackupDialog";
+import { InsightsDialog } from "@/components/InsightsDialog";
+import { FlavorDialog } from "@/components/FlavorDialog";
+import { SymbolDialog } from "@/components/SymbolDialog";
+import { TranscriptionDialog } from "@/components/TranscriptionDialog";
+import { WorkspaceQaDialog } from "@/components/WorkspaceQaDialog";
 import { SettingsPage } from "@/pages/Settings";
+import { QuickCapturePage } from "@/pages/QuickCapture";
+import { DocumentViewerPage } from "@/pages/DocumentViewer";
 import { WindowProvider, useIsDocumentWindow, useWindowLabel } from "@/contexts/WindowContext";
 
 // Error Boundary to catch and display React errors
@@ -59,6 +71,7 @@ import { useMenuEvents } from "@/hooks/useMenuEvents";
 import { useViewMenuEvents } from "@/hooks/useViewMenuEvents";
 import { useRecentFilesMenuEvents } from "@/hooks/useRecentFilesMenuEvents";
 import { useExportMenuEvents } from "@/hooks/useExportMenuEvents";
+import { usePublishMenuEvents } from "@/hooks/usePublishMenuEvents";
 import { useWorkspaceMenuEvents } from "@/hooks/useWorkspaceMenuEvents";
 import { useWorkspaceBootstrap } from "@/hooks/useWorkspaceBootstrap";
 import { useFileOperations } from "@/hooks/useFileOperations";
@@ -66,6 +79,7 @@ import { useSearchCommands } from "@/hooks/useSearchCommands";
 import { useAutoSave } from "@/hooks/useAutoSave";
 import { useTheme } from "@/hooks/useTheme";
 import { useSettingsSync } from "@/hooks/useSettingsSync";
+import { useKeymapSync } from "@/hooks/useKeymapSync";
 import { useRecentFilesSync } from "@/hooks/useRecentFilesSync";
 import { useRecentWorkspacesSync } from "@/hooks/useRecentWorkspacesSync";
 import { useRecentWorkspacesMenuEvents } from "@/hooks/useRecentWorkspacesMenuEvents";
@@ -78,18 +92,32 @@ import { useReloadGuard } from "@/hooks/useReloadGuard";
 import { useDragDropOpen } from "@/hooks/useDragDropOpen";
 import { useExternalFileChanges } from "@/hooks/useExternalFileChanges";
 import { useWindowFileWatcher } from "@/hooks/useWindowFileWatcher";
+import { useWindowRegistrySync } from "@/hooks/useWindowRegistrySync";
+import { useMenuStateSync } from "@/hooks/useMenuStateSync";
+import { useTabMoveReceiver } from "@/hooks/useTabMoveReceiver";
 import { useSidebarResize } from "@/hooks/useSidebarResize";
 import { useUniversalToolbar } from "@/hooks/useUniversalToolbar";
 import { useMcpAutoStart } from "@/hooks/useMcpAutoStart";
 import { useMcpBridge } from "@/hooks/useMcpBridge";
 import { useFileExplorerShortcuts } from "@/hooks/useFileExplorerShortcuts";
 import { useImagePasteToast } from "@/hooks/useImagePasteToast";
+import { useClipboardHistory } from "@/hooks/useClipboardHistory";
+import { useWorkspaceSync } from "@/hooks/useWorkspaceSync";
+import { useWorkspaceBackup } from "@/hooks/useWorkspaceBackup";
+import { useFileLocks } from "@/hooks/useFileLocks";
 import { useUpdateChecker } from "@/hooks/useUpdateChecker";
 import { useUpdateBroadcast } from "@/hooks/useUpdateSync";
 import { useFinderFileOpen } from "@/hooks/useFinderFileOpen";
 import { useHotExitCapture } from "@/utils/hotExit/useHotExitCapture";
 import { useHotExitRestore } from "@/utils/hotExit/useHotExitRestore";
 import { useHotExitStartup } from "@/utils/hotExit/useHotExitStartup";
+import { usePendingReveal } from "@/hooks/usePendingReveal";
+import { useWorkspaceTrust } from "@/hooks/useWorkspaceTrust";
+import { useDirtyBackupResponder } from "@/utils/dirtyBackup";
+import { useLivePreviewSync } from "@/hooks/useLivePreviewSync";
+import { useSaveHooksToast } from "@/hooks/useSaveHooksToast";
+import { useNotificationEvents } from "@/hooks/useNotificationEvents";
+import { useOllamaWarmup } from "@/hooks/useOllamaWarmup";
 import { useGenieShortcuts } from "@/hooks/useGenieShortcuts";
 import { GeniePicker } from "@/components/GeniePicker/GeniePicker";
 
@@ -140,9 +168,18 @@ function DocumentWindowHooks() {
   useWindowTitle();
   useDragDropOpen(); // Open dropped markdown files
   useWindowFileWatcher(); // Start file watcher for this window
+  useWorkspaceTrust(); // Mirror the workspace trust store in this window
+  useWindowRegistrySync(); // Report open files to the window registry
+  useMenuStateSync(); // Keep native menu checkmarks and enabled items in sync
+  useTabMoveReceiver(); // Accept tabs dragged in from other windows
   useExternalFileChanges(); // Handle external file changes (auto-reload or prompt)
   useHotExitCapture(); // Respond to hot exit capture requests
   useHotExitRestore(); // Handle hot exit restore on restart
+  usePendingReveal(); // Jump to the line a vmark:// link asked for
+  useDirtyBackupResponder(); // Send unsaved documents for periodic backup
+  useLivePreviewSync(); // Re-render the browser preview when its file changes
+  useSaveHooksToast(); // Report frontmatter filled in by save hooks
+  useOllamaWarmup(); // Preload the local Ollama model for genies
   return null;
 }
 
@@ -160,6 +197,7 @@ function MainWindowHooks() {
   useUpdateBroadcast(); // Broadcast update state to other windows
   useHotExitStartup(); // Check for saved session and restore if present (MUST run before Finder)
   useFinderFileOpen(); // Handle files opened from Finder (waits for hot exit to complete)
+  useNotificationEvents(); // Handle presses on notifications and reminders
   return null;
 }
 
@@ -182,10 +220,12 @@ function MainLayout() {
   useViewMenuEvents();
   useRecentFilesMenuEvents();
   useExportMenuEvents();
+  usePublishMenuEvents();
   useWorkspaceMenuEvents();
   useFileOperations();
   useSearchCommands();
   useSettingsSync(); // Sync settings across windows
+  useKeymapSync(); // Load menu shortcuts saved in keymap.json
   useTheme();
   useAutoSave(); // Auto-save when dirty
   useRecentFilesSync(); // Sync recent files to native menu
@@ -198,6 +238,10 @@ function MainLayout() {
   useUniversalToolbar(); // Universal toolbar toggle (shortcut configurable)
   useFileExplorerShortcuts(); // Toggle hidden files
   useImagePasteToast(); // Image paste confirmation toast
+  useClipboardHistory(); // Record copies for Paste from History
+  useWorkspaceSync(); // Background WebDAV sync for the open workspace
+  useWorkspaceBackup(); // Scheduled backups for the open workspace
+  useFileLocks(); // Advisory locks for open documents (opt-in)
 
   const classNames = [
     "app-layout",
@@ -230,6 +274,18 @@ function MainLayout() {
       {/* Drop zone indicator for drag-and-drop */}
       <DropOverlay />
 
+      {/* Side-by-side diff for `vmark --diff a b` */}
+      {isDocumentWindow && <CompareView />}
+      {isDocumentWindow && <QuickOpen />}
+      {isDocumentWindow && <PassphraseDialog />}
+      {isDocumentWindow && <BackupDialog />}
+      {isDocumentWindow && <InsightsDialog />}
+      {isDocumentWindow && <TranscriptionDialog />}
+      {isDocumentWindow && <SymbolDialog />}
+      {isDocumentWindow && <WorkspaceQaDialog />}
+      {isDocumentWindow && <ReadabilityDialog />}
+      {isDocumentWindow && <FlavorDialog />}
+
       {/* Title bar with drag region and filename display */}
       <TitleBar />
 
@@ -286,6 +342,8 @@ function App() {
         <Routes>
           <Route path="/" element={<MainLayout />} />
           <Route path="/settings" element={<SettingsPage />} />
+          <Route path="/capture" element={<QuickCapturePage />} />
+          <Route path="/viewer" element={<DocumentViewerPage />} />
         </Routes>
         <GeniePicker />
         <Toaster
@@ -0,0 +1,400 @@
+/**
+ * Backup Dialog
+ *
+ * Workspace backup settings (target, schedule, retention) and the stored
+ * snapshots, which can be verified, restored into a new folder or deleted.
+ * Opened from File → Workspace Backups…; the work is done by backup.rs.
+ */
+
+import { useCallback, useEffect, useState } from "react";
+import { createPortal } from "react-dom";
+import { listen } from "@tauri-apps/api/event";
+import { ask, open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { useBackupDialogStore } from "@/stores/backupDialogStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import {
+  createBackup,
+  deleteBackup,
+  describeBackupProgress,
+  formatBackupSize,
+  getBackupConfig,
+  listBackups,
+  restoreBackup,
+  setBackupConfig,
+  startScheduledBackup,
+  verifyBackup,
+  type BackupConfig,
+  type BackupProgress,
+  type BackupSnapshot,
+} from "@/utils/backup";
+import "./backup-dialog.css";
+
+interface BackupForm {
+  type: "local" | "s3";
+  path: string;
+  endpoint: string;
+  region: string;
+  bucket: string;
+  prefix: string;
+  accessKeyId: string;
+  secretKey: string;
+  pathStyle: boolean;
+  intervalHours: string;
+  keep: string;
+  excludes: string;
+}
+
+const EMPTY_FORM: BackupForm = {
+  type: "local",
+  path: "",
+  endpoint: "",
+  region: "us-east-1",
+  bucket: "",
+  prefix: "",
+  accessKeyId: "",
+  secretKey: "",
+  pathStyle: false,
+  intervalHours: "0",
+  keep: "10",
+  excludes: "",
+};
+
+function formFromConfig(config: BackupConfig): BackupForm {
+  const { target } = config;
+  return {
+    ...EMPTY_FORM,
+    ...(target.type === "local"
+      ? { type: "local" as const, path: target.path }
+      : {
+          type: "s3" as const,
+          endpoint: target.endpoint,
+          region: target.region,
+          bucket: target.bucket,
+          prefix: target.prefix ?? "",
+          accessKeyId: target.accessKeyId,
+          pathStyle: target.pathStyle ?? false,
+        }),
+    intervalHours: String(config.intervalHours),
+    keep: String(config.keep),
+    excludes: config.excludes.join(", "),
+  };
+}
+
+function configFromForm(form: BackupForm): BackupConfig {
+  return {
+    target:
+      form.type === "local"
+        ? { type: "local", path: form.path.trim() }
+        : {
+            type: "s3",
+            endpoint: form.endpoint.trim(),
+            region: form.region.trim(),
+            bucket: form.bucket.trim(),
+            prefix: form.prefix.trim() || undefined,
+            accessKeyId: form.accessKeyId.trim(),
+            pathStyle: form.pathStyle,
+          },
+    intervalHours: Math.max(0, Math.floor(Number(form.intervalHours) || 0)),
+    keep: Math.max(0, Math.floor(Number(form.keep) || 0)),
+    excludes: form.excludes
+      .split(",")
+      .map((name) => name.trim())
+      .filter(Boolean),
+  };
+}
+
+export function BackupDialog() {
+  const isOpen = useBackupDialogStore((s) => s.isOpen);
+  const close = useBackupDialogStore((s) => s.close);
+  const rootPath = useWorkspaceStore((s) => s.rootPath);
+
+  const [form, setForm] = useState<BackupForm>(EMPTY_FORM);
+  const [configured, setConfigured] = useState(false);
+  const [snapshots, setSnapshots] = useState<BackupSnapshot[]>([]);
+  const [status, setStatus] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshSnapshots = useCallback(async () => {
+    if (!rootPath) return;
+    try {
+      setSnapshots(await listBackups(rootPath));
+    } catch (err) {
+      setSnapshots([]);
+      setError(String(err));
+    }
+  }, [rootPath]);
+
+  // Load the settings and snapshots each time the dialog opens
+  useEffect(() => {
+    if (!isOpen || !rootPath) return;
+    let cancelled = false;
+    setError(null);
+    setStatus(null);
+    getBackupConfig(rootPath)
+      .then((config) => {
+        if (cancelled) return;
+        setConfigured(!!config);
+        setForm(config ? formFromConfig(config) : EMPTY_FORM);
+        if (config) void refreshSnapshots();
+        else setSnapshots([]);
+      })
+      .catch((err) => !cancelled && setError(String(err)));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, rootPath, refreshSnapshots]);
+
+  useEffect(() => {
+    if (!isOpen || !rootPath) return;
+    const unlistenPromise = listen<BackupProgress>("backup:progress", (event) => {
+      if (event.payload.rootPath === rootPath) setStatus(describeBackupProgress(event.payload));
+    });
+    return () => {
+      void unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [isOpen, rootPath]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  if (!isOpen) return null;
+
+  const update = (changes: Partial<BackupForm>) => setForm((current) => ({ ...current, ...changes }));
+
+  const run = async (job: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await job();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const save = () =>
+    run(async () => {
+      if (!rootPath) return;
+      await setBackupConfig(rootPath, configFromForm(form), form.secretKey || undefined);
+      update({ secretKey: "" });
+      setConfigured(true);
+      // Restart the schedule with the new interval
+      await startScheduledBackup(rootPath);
+      await refreshSnapshots();
+    });
+
+  const chooseFolder = async () => {
+    const folder = await open({ directory: true, title: "Backup Folder" });
+    if (typeof folder === "string") update({ path: folder });
+  };
+
+  const backUpNow = () =>
+    run(async () => {
+      if (!rootPath) return;
+      const snapshot = await createBackup(rootPath);
+      toast.success(`Backed up workspace (${formatBackupSize(snapshot.size)})`);
+      await refreshSnapshots();
+    });
+
+  const verify = (snapshot: BackupSnapshot) =>
+    run(async () => {
+      if (!rootPath) return;
+      await verifyBackup(rootPath, snapshot.name);
+      toast.success(`${snapshot.name} is intact`);
+    });
+
+  const restore = (snapshot: BackupSnapshot) =>
+    run(async () => {
+      if (!rootPath) return;
+      const destination = await open({ directory: true, title: "Restore Into (empty folder)" });
+      if (typeof destination !== "string") return;
+      await restoreBackup(rootPath, snapshot.name, destination);
+      toast.success(`Restored ${snapshot.name} into ${destination}`);
+    });
+
+  const remove = (snapshot: BackupSnapshot) =>
+    run(async () => {
+      if (!rootPath) return;
+      const confirmed = await ask(`Delete the backup ${snapshot.name}?`, {
+        title: "Delete Backup",
+        kind: "warning",
+        okLabel: "Delete",
+      });
+      if (!confirmed) return;
+      await deleteBackup(rootPath, snapshot.name);
+      await refreshSnapshots();
+    });
+
+  return createPortal(
+    <div className="backup-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="backup-dialog" role="dialog" aria-modal="true" aria-label="Workspace Backups">
+        <div className="backup-dialog-header">
+          <span className="backup-dialog-title">Workspace Backups</span>
+          <button className="backup-dialog-button" onClick={close}>
+            Close
+          </button>
+        </div>
+
+        {!rootPath ? (
+          <div className="backup-dialog-message">Open a folder to back it up.</div>
+        ) : (
+          <>
+            <div className="backup-dialog-section">
+              <label className="backup-dialog-field">
+                <span>Store in</span>
+                <select
+                  value={form.type}
+                  onChange={(e) => update({ type: e.target.value as BackupForm["type"] })}
+                >
+                  <option value="local">Local folder</option>
+                  <option value="s3">S3-compatible bucket</option>
+                </select>
+              </label>
+
+              {form.type === "local" ? (
+                <label className="backup-dialog-field">
+                  <span>Folder</span>
+                  <input value={form.path} onChange={(e) => update({ path: e.target.value })} />
+                  <button className="backup-dialog-button" onClick={() => void chooseFolder()}>
+                    Choose…
+                  </button>
+                </label>
+              ) : (
+                <>
+                  <label className="backup-dialog-field">
+                    <span>Endpoint</span>
+                    <input
+                      placeholder="https://s3.amazonaws.com"
+                      value={form.endpoint}
+                      onChange={(e) => update({ endpoint: e.target.value })}
+                    />
+                  </label>
+                  <label className="backup-dialog-field">
+                    <span>Region</span>
+                    <input value={form.region} onChange={(e) => update({ region: e.target.value })} />
+                  </label>
+                  <label className="backup-dialog-field">
+                    <span>Bucket</span>
+                    <input value={form.bucket} onChange={(e) => update({ bucket: e.target.value })} />
+                  </label>
+                  <label className="backup-dialog-field">
+                    <span>Prefix</span>
+                    <input
+                      placeholder="Optional"
+                      value={form.prefix}
+                      onChange={(e) => update({ prefix: e.target.value })}
+                    />
+                  </label>
+                  <label className="backup-dialog-field">
+                    <span>Access key</span>
+                    <input
+                      value={form.accessKeyId}
+                      onChange={(e) => update({ accessKeyId: e.target.value })}
+                    />
+                  </label>
+                  <label className="backup-dialog-field">
+                    <span>Secret key</span>
+                    <input
+                      type="password"
+                      autoComplete="off"
+                      placeholder={configured ? "Unchanged" : ""}
+                      value={form.secretKey}
+                      onChange={(e) => update({ secretKey: e.target.value })}
+                    />
+                  </label>
+                  <label className="backup-dialog-check">
+                    <input
+                      type="checkbox"
+                      checked={form.pathStyle}
+                      onChange={(e) => update({ pathStyle: e.target.checked })}
+                    />
+                    Path-style URLs (MinIO and most self-hosted servers)
+                  </label>
+                </>
+              )}
+
+              <label className="backup-dialog-field">
+                <span>Every (hours)</span>
+                <input
+                  type="number"
+                  min={0}
+                  value={form.intervalHours}
+                  onChange={(e) => update({ intervalHours: e.target.value })}
+                />
+                <span className="backup-dialog-hint">0 backs up only on demand</span>
+              </label>
+              <label className="backup-dialog-field">
+                <span>Keep</span>
+                <input
+                  type="number"
+                  min={0}
+                  value={form.keep}
+                  onChange={(e) => update({ keep: e.target.value })}
+                />
+                <span className="backup-dialog-hint">0 keeps all</span>
+              </label>
+              <label className="backup-dialog-field">
+                <span>Exclude</span>
+                <input
+                  placeholder="node_modules, dist"
+                  value={form.excludes}
+                  onChange={(e) => update({ excludes: e.target.value })}
+                />
+              </label>
+
+              <div className="backup-dialog-actions">
+                <button className="backup-dialog-button" disabled={busy} onClick={() => void save()}>
+                  Save Settings
+                </button>
+                <button
+                  className="backup-dialog-button primary"
+                  disabled={busy || !configured}
+                  onClick={() => void backUpNow()}
+                >
+                  Back Up Now
+                </button>
+              </div>
+            </div>
+
+            {(status || error) && (
+              <div className={error ? "backup-dialog-error" : "backup-dialog-status"}>{error ?? status}</div>
+            )}
+
+            <div className="backup-dialog-list">
+              {configured && snapshots.length === 0 && (
+                <div className="backup-dialog-message">No backups yet.</div>
+              )}
+              {snapshots.map((snapshot) => (
+                <div key={snapshot.name} className="backup-dialog-item">
+                  <span className="backup-dialog-name" title={snapshot.name}>
+                    {new Date(snapshot.createdAt).toLocaleString()}
+                  </span>
+                  <span className="backup-dialog-size">{formatBackupSize(snapshot.size)}</span>
+                  <button className="backup-dialog-button" disabled={busy} onClick={() => void verify(snapshot)}>
+                    Verify
+                  </button>
+                  <button className="backup-dialog-button" disabled={busy} onClick={() => void restore(snapshot)}>
+                    Restore…
+                  </button>
+                  <button className="backup-dialog-button" disabled={busy} onClick={() => void remove(snapshot)}>
+                    Delete
+                  </button>
+                </div>
+              ))}
+            </div>
+          </>
+        )}
+      </div>
+    </div>,
+    document.body
+  );
+}
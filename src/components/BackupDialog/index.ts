@@ -0,0 +1 @@
+export { BackupDialog } from "./BackupDialog";
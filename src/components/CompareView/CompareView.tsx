@@ -0,0 +1,138 @@
+/**
+ * Compare View
+ *
+ * Side-by-side line diff of two texts from compareViewStore: the two
+ * files given to `vmark --diff a b`, or a history snapshot against the
+ * current document. For `--diff` the backend opens a new window and holds
+ * the pair until that window's frontend takes it (see cli.rs).
+ */
+
+import { useEffect, useMemo, useState } from "react";
+import { createPortal } from "react-dom";
+import { invoke } from "@tauri-apps/api/core";
+import { readTextFile } from "@tauri-apps/plugin-fs";
+import { toast } from "sonner";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useCompareViewStore } from "@/stores/compareViewStore";
+import {
+  diffDocuments,
+  formatDiffStats,
+  sideBySideRows,
+  type DiffSegment,
+  type DocumentDiff,
+} from "@/utils/documentDiff";
+import { getFileName } from "@/utils/paths";
+import "./compare-view.css";
+
+interface DiffRequest {
+  left: string;
+  right: string;
+}
+
+function Side({ segment, side }: { segment?: DiffSegment; side: "left" | "right" }) {
+  const number = side === "left" ? segment?.oldLine : segment?.newLine;
+  const changed = segment && segment.kind !== "equal";
+  const className = changed ? `compare-view-${segment.kind}` : undefined;
+  return (
+    <>
+      <td className={`compare-view-line-number ${className ?? ""}`}>{number ?? ""}</td>
+      <td className={className}>{segment?.text ?? ""}</td>
+    </>
+  );
+}
+
+export function CompareView() {
+  const windowLabel = useWindowLabel();
+  const request = useCompareViewStore((state) => state.request);
+  const close = useCompareViewStore((state) => state.close);
+  const [diff, setDiff] = useState<DocumentDiff | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  // Files passed on the command line, if this window was opened for them
+  useEffect(() => {
+    let cancelled = false;
+    (async () => {
+      const pending = await invoke<DiffRequest | null>("take_pending_diff", { windowLabel });
+      if (!pending || cancelled) return;
+      try {
+        const [left, right] = await Promise.all([
+          readTextFile(pending.left),
+          readTextFile(pending.right),
+        ]);
+        if (cancelled) return;
+        useCompareViewStore.getState().open({
+          leftLabel: getFileName(pending.left),
+          rightLabel: getFileName(pending.right),
+          title: `${pending.left} ↔ ${pending.right}`,
+          left,
+          right,
+        });
+      } catch (e) {
+        toast.error(`Could not compare files: ${e}`);
+      }
+    })().catch((e) => console.error("[Compare] Failed to load diff:", e));
+    return () => {
+      cancelled = true;
+    };
+  }, [windowLabel]);
+
+  useEffect(() => {
+    setDiff(null);
+    setError(null);
+    if (!request) return;
+    let cancelled = false;
+    diffDocuments(request.left, request.right, "line")
+      .then((result) => !cancelled && setDiff(result))
+      .catch((e) => !cancelled && setError(String(e)));
+    return () => {
+      cancelled = true;
+    };
+  }, [request]);
+
+  useEffect(() => {
+    if (!request) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [request, close]);
+
+  const rows = useMemo(() => (diff ? sideBySideRows(diff.segments) : []), [diff]);
+
+  if (!request) return null;
+
+  return createPortal(
+    <div className="compare-view-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="compare-view" role="dialog" aria-label="Compare versions">
+        <div className="compare-view-header">
+          <span className="compare-view-title" title={request.title}>
+            {request.leftLabel} ↔ {request.rightLabel}
+          </span>
+          {diff && <span className="compare-view-stats">{formatDiffStats(diff, "line")}</span>}
+          <button className="compare-view-close" onClick={close}>
+            Close
+          </button>
+        </div>
+        <div className="compare-view-body">
+          {error && <div className="compare-view-message">Could not compare: {error}</div>}
+          {!error && !diff && <div className="compare-view-message">Comparing…</div>}
+          {diff?.identical && <div className="compare-view-message">No differences.</div>}
+          {diff && !diff.identical && (
+            <table className="compare-view-table">
+              <tbody>
+                {rows.map((row, i) => (
+                  <tr key={i}>
+                    <Side segment={row.left} side="left" />
+                    <Side segment={row.right} side="right" />
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </div>
+      </div>
+    </div>,
+    document.body
+  );
+}
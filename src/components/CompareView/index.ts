@@ -0,0 +1 @@
+export { CompareView } from "./CompareView";
@@ -2,11 +2,12 @@
  * Image Context Menu
  *
  * Context menu shown when right-clicking on an image.
- * Provides actions: Change Image, Delete, Copy Path, Reveal in file manager.
+ * Provides actions: Change Image, Delete, Copy Path, Copy Text from Image,
+ * Reveal in file manager.
  */
 
 import { useEffect, useRef, useCallback, useMemo } from "react";
-import { ImagePlus, Trash2, Copy, FolderOpen } from "lucide-react";
+import { ImagePlus, Trash2, Copy, FolderOpen, ScanText } from "lucide-react";
 import { useImageContextMenuStore } from "@/stores/imageContextMenuStore";
 import "@/components/Sidebar/FileExplorer/ContextMenu.css";
 import { isImeKeyEvent } from "@/utils/imeGuard";
@@ -29,6 +30,7 @@ function buildMenuItems(revealLabel: string): MenuItem[] {
       separator: true,
     },
     { id: "copyPath", label: "Copy Image Path", icon: <Copy size={14} /> },
+    { id: "copyText", label: "Copy Text from Image", icon: <ScanText size={14} /> },
     {
       id: "revealInFinder",
       label: revealLabel,
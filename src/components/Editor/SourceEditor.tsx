@@ -6,12 +6,13 @@ import { useSettingsStore } from "@/stores/settingsStore";
 import { useShortcutsStore } from "@/stores/shortcutsStore";
 import { useSearchStore } from "@/stores/searchStore";
 import {
+  useActiveTabId,
   useDocumentContent,
   useDocumentCursorInfo,
   useDocumentActions,
 } from "@/hooks/useDocumentState";
 import { useSourceMenuCommands } from "@/hooks/useSourceMenuCommands";
-import { useSourceEditorSearch } from "@/hooks/useSourceEditorSearch";
+import { recomputeMatches, useSourceEditorSearch } from "@/hooks/useSourceEditorSearch";
 import { useSourceEditorSync } from "@/hooks/useSourceEditorSync";
 import {
   getCursorInfoFromCodeMirror,
@@ -27,6 +28,7 @@ import { runOrQueueCodeMirrorAction } from "@/utils/imeGuard";
 import { computeSourceCursorContext } from "@/plugins/sourceContextDetection/cursorContext";
 import { useImageDragDrop } from "@/hooks/useImageDragDrop";
 import { countMatches } from "@/utils/sourceEditorSearch";
+import { shouldUseNativeFind, trackFindEdits } from "@/utils/nativeFind";
 import {
   createSourceEditorExtensions,
   shortcutKeymapCompartment,
@@ -49,6 +51,9 @@ export function SourceEditor({ hidden = false }: SourceEditorProps) {
   const content = useDocumentContent();
   const cursorInfo = useDocumentCursorInfo();
   const { setContent, setCursorInfo } = useDocumentActions();
+  const tabId = useActiveTabId();
+  const tabIdRef = useRef(tabId);
+  tabIdRef.current = tabId;
 
   // Refs to capture callbacks for use in CodeMirror listener
   const setContentRef = useRef(setContent);
@@ -86,24 +91,31 @@ export function SourceEditor({ hidden = false }: SourceEditorProps) {
         requestAnimationFrame(() => {
           isInternalChange.current = false;
         });
+        // Keep the backend search copy of a large document current
+        const tabId = tabIdRef.current;
+        if (tabId) trackFindEdits(tabId, update);
         // Update match count when document changes and search is open
         const searchState = useSearchStore.getState();
         if (searchState.isOpen && searchState.query) {
-          const matchCount = countMatches(
-            newContent,
-            searchState.query,
-            searchState.caseSensitive,
-            searchState.wholeWord,
-            searchState.useRegex
-          );
-          // Keep currentIndex valid: reset to 0 if out of bounds or -1
-          let newIndex = searchState.currentIndex;
-          if (matchCount === 0) {
-            newIndex = -1;
-          } else if (newIndex < 0 || newIndex >= matchCount) {
-            newIndex = 0;
+          if (tabId && shouldUseNativeFind(update.state.doc.length)) {
+            recomputeMatches(update.view, searchState, false, tabId);
+          } else {
+            const matchCount = countMatches(
+              newContent,
+              searchState.query,
+              searchState.caseSensitive,
+              searchState.wholeWord,
+              searchState.useRegex
+            );
+            // Keep currentIndex valid: reset to 0 if out of bounds or -1
+            let newIndex = searchState.currentIndex;
+            if (matchCount === 0) {
+              newIndex = -1;
+            } else if (newIndex < 0 || newIndex >= matchCount) {
+              newIndex = 0;
+            }
+            useSearchStore.getState().setMatches(matchCount, newIndex);
           }
-          useSearchStore.getState().setMatches(matchCount, newIndex);
         }
       }
       // Track cursor position for mode sync
@@ -232,7 +244,7 @@ export function SourceEditor({ hidden = false }: SourceEditorProps) {
     hiddenRef,
   });
 
-  useSourceEditorSearch(viewRef);
+  useSourceEditorSearch(viewRef, tabId);
 
   return (
     <div
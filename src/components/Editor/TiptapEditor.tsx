@@ -29,6 +29,7 @@ import { useTiptapSelectionCommands } from "@/hooks/useTiptapSelectionCommands";
 import { useTiptapTableCommands } from "@/hooks/useTiptapTableCommands";
 import { useImageDragDrop } from "@/hooks/useImageDragDrop";
 import { ImageContextMenu } from "./ImageContextMenu";
+import { getSpellcheckLanguage } from "@/utils/workspaceEditorSettings";
 import "@/plugins/codeBlockLineNumbers/code-block-line-numbers.css";
 import "@/plugins/sourcePeekInline/source-peek-inline.css";
 
@@ -184,10 +185,15 @@ export function TiptapEditorInner({ hidden = false }: TiptapEditorInnerProps) {
   const editor = useEditor({
     extensions,
     editorProps: {
-      attributes: {
-        class: "ProseMirror",
-        // Enable native browser spellcheck for system-level spell checking
-        spellcheck: "true",
+      // Re-read on each update so a workspace language change applies
+      attributes: () => {
+        const lang = getSpellcheckLanguage();
+        return {
+          class: "ProseMirror",
+          // Enable native browser spellcheck for system-level spell checking
+          spellcheck: "true",
+          ...(lang ? { lang } : {}),
+        };
       },
     },
     onCreate: ({ editor }) => {
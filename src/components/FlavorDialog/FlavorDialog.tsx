@@ -0,0 +1,116 @@
+/**
+ * Flavor Dialog
+ *
+ * Rewrites the current document from one Markdown flavor to another
+ * (CommonMark, GitHub, Obsidian), reporting what changed and anything the
+ * target can't express. Opened from Format → Text Cleanup → Convert
+ * Markdown Flavor…; the conversion is done by flavor.rs.
+ */
+
+import { useEffect, useState } from "react";
+import { createPortal } from "react-dom";
+import { toast } from "sonner";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useDocumentStore } from "@/stores/documentStore";
+import { useFlavorDialogStore } from "@/stores/flavorDialogStore";
+import { getActiveDocument, getActiveTabId } from "@/utils/activeDocument";
+import { MARKDOWN_FLAVORS, convertFlavor, type MarkdownFlavor } from "@/utils/markdownFlavor";
+import "./flavor-dialog.css";
+
+export function FlavorDialog() {
+  const windowLabel = useWindowLabel();
+  const isOpen = useFlavorDialogStore((s) => s.isOpen);
+  const close = useFlavorDialogStore((s) => s.close);
+
+  const [from, setFrom] = useState<MarkdownFlavor>("obsidian");
+  const [to, setTo] = useState<MarkdownFlavor>("github");
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (isOpen) setError(null);
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  if (!isOpen) return null;
+
+  const handleConvert = async () => {
+    const tabId = getActiveTabId(windowLabel);
+    const content = getActiveDocument(windowLabel)?.content;
+    if (!tabId || content === undefined) return;
+
+    setBusy(true);
+    setError(null);
+    try {
+      const result = await convertFlavor(content, from, to);
+      close();
+      if (result.changes === 0) {
+        toast.info("Nothing needed converting");
+        return;
+      }
+      useDocumentStore.getState().setContent(tabId, result.content);
+      const summary = `Converted ${result.changes} ${result.changes === 1 ? "construct" : "constructs"}`;
+      if (result.warnings.length > 0) {
+        toast.warning(summary, { description: result.warnings.join("\n") });
+      } else {
+        toast.success(summary);
+      }
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const flavorSelect = (value: MarkdownFlavor, onChange: (flavor: MarkdownFlavor) => void) => (
+    <select value={value} onChange={(e) => onChange(e.target.value as MarkdownFlavor)} disabled={busy}>
+      {MARKDOWN_FLAVORS.map((flavor) => (
+        <option key={flavor.value} value={flavor.value}>
+          {flavor.label}
+        </option>
+      ))}
+    </select>
+  );
+
+  return createPortal(
+    <div className="flavor-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="flavor-dialog" role="dialog" aria-modal="true" aria-label="Convert Markdown Flavor">
+        <div className="flavor-dialog-title">Convert Markdown Flavor</div>
+        <label className="flavor-dialog-field">
+          <span>From</span>
+          {flavorSelect(from, setFrom)}
+        </label>
+        <label className="flavor-dialog-field">
+          <span>To</span>
+          {flavorSelect(to, setTo)}
+        </label>
+        <div className="flavor-dialog-hint">
+          Rewrites wiki links, highlights, callouts, footnotes, strikethrough and comments. Code and frontmatter are
+          left as they are.
+        </div>
+        {error && <div className="flavor-dialog-error">{error}</div>}
+        <div className="flavor-dialog-actions">
+          <button className="flavor-dialog-button" onClick={close}>
+            Cancel
+          </button>
+          <button
+            className="flavor-dialog-button primary"
+            onClick={() => void handleConvert()}
+            disabled={busy || from === to}
+          >
+            Convert
+          </button>
+        </div>
+      </div>
+    </div>,
+    document.body
+  );
+}
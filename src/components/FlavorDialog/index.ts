@@ -0,0 +1 @@
+export { FlavorDialog } from "./FlavorDialog";
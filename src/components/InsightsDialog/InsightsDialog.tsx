@@ -0,0 +1,157 @@
+/**
+ * Insights Dialog
+ *
+ * Workspace statistics: note and word counts, link health, note sizes,
+ * recent activity, and notes nothing links to. Opened from File →
+ * Workspace Insights…; the numbers come from workspace_stats.rs.
+ */
+
+import { useEffect, useState } from "react";
+import { createPortal } from "react-dom";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useInsightsDialogStore } from "@/stores/insightsDialogStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { formatBackupSize } from "@/utils/backup";
+import { joinPath } from "@/utils/pathUtils";
+import { getWorkspaceStats, linkHealthPercent, type WorkspaceStats } from "@/utils/workspaceStats";
+import "./insights-dialog.css";
+
+/** Days of activity shown */
+const ACTIVITY_DAYS = 30;
+/** Orphaned notes listed before "and N more" */
+const MAX_ORPHANS = 20;
+
+function Bars({ items }: { items: { label: string; count: number }[] }) {
+  const max = Math.max(1, ...items.map((item) => item.count));
+  return (
+    <div className="insights-dialog-bars">
+      {items.map((item) => (
+        <div
+          key={item.label}
+          className="insights-dialog-bar"
+          style={{ height: `${(item.count / max) * 100}%` }}
+          title={`${item.label}: ${item.count}`}
+        />
+      ))}
+    </div>
+  );
+}
+
+export function InsightsDialog() {
+  const isOpen = useInsightsDialogStore((s) => s.isOpen);
+  const close = useInsightsDialogStore((s) => s.close);
+  const rootPath = useWorkspaceStore((s) => s.rootPath);
+
+  const [stats, setStats] = useState<WorkspaceStats | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  // Recount each time the dialog opens
+  useEffect(() => {
+    if (!isOpen || !rootPath) return;
+    let cancelled = false;
+    setStats(null);
+    setError(null);
+    getWorkspaceStats(rootPath, ACTIVITY_DAYS)
+      .then((result) => !cancelled && setStats(result))
+      .catch((err) => !cancelled && setError(String(err)));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, rootPath]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  if (!isOpen) return null;
+
+  const openNote = (path: string) => {
+    if (!rootPath) return;
+    close();
+    void getCurrentWebviewWindow().emit("open-file", { path: joinPath(rootPath, path) });
+  };
+
+  const noteLink = (path: string, detail?: string) => (
+    <button key={path} className="insights-dialog-note" title={path} onClick={() => openNote(path)}>
+      <span className="insights-dialog-note-path">{path}</span>
+      {detail && <span className="insights-dialog-note-detail">{detail}</span>}
+    </button>
+  );
+
+  const renderStats = (s: WorkspaceStats) => (
+    <>
+      <div className="insights-dialog-totals">
+        <div>
+          <strong>{s.totalNotes.toLocaleString()}</strong> notes
+        </div>
+        <div>
+          <strong>{s.totalWords.toLocaleString()}</strong> words
+        </div>
+        <div>
+          <strong>{formatBackupSize(s.totalBytes)}</strong>
+        </div>
+        <div title={`${s.internalLinks} note links resolve, ${s.brokenLinks} are broken`}>
+          <strong>{linkHealthPercent(s)}%</strong> links healthy
+        </div>
+      </div>
+
+      <div className="insights-dialog-charts">
+        <div className="insights-dialog-chart">
+          <span className="insights-dialog-label">Note sizes</span>
+          <Bars items={s.sizeDistribution} />
+          <div className="insights-dialog-axis">
+            {s.sizeDistribution.map((bucket) => (
+              <span key={bucket.label}>{bucket.label}</span>
+            ))}
+          </div>
+        </div>
+        <div className="insights-dialog-chart">
+          <span className="insights-dialog-label">Notes changed, last {ACTIVITY_DAYS} days</span>
+          <Bars items={s.activity.map((day) => ({ label: day.date, count: day.count }))} />
+        </div>
+      </div>
+
+      <div className="insights-dialog-section">
+        <span className="insights-dialog-label">Recently modified</span>
+        {s.recentNotes.map((note) => noteLink(note.path, new Date(note.modified).toLocaleString()))}
+      </div>
+
+      <div className="insights-dialog-section">
+        <span className="insights-dialog-label">Not linked from any note ({s.orphanedNotes.length})</span>
+        {s.orphanedNotes.slice(0, MAX_ORPHANS).map((path) => noteLink(path))}
+        {s.orphanedNotes.length > MAX_ORPHANS && (
+          <span className="insights-dialog-more">and {s.orphanedNotes.length - MAX_ORPHANS} more</span>
+        )}
+      </div>
+    </>
+  );
+
+  return createPortal(
+    <div className="insights-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="insights-dialog" role="dialog" aria-modal="true" aria-label="Workspace Insights">
+        <div className="insights-dialog-header">
+          <span className="insights-dialog-title">Workspace Insights</span>
+          <button className="insights-dialog-button" onClick={close}>
+            Close
+          </button>
+        </div>
+
+        {!rootPath ? (
+          <div className="insights-dialog-message">Open a folder to see its insights.</div>
+        ) : error ? (
+          <div className="insights-dialog-error">{error}</div>
+        ) : !stats ? (
+          <div className="insights-dialog-message">Counting notes…</div>
+        ) : (
+          <div className="insights-dialog-body">{renderStats(stats)}</div>
+        )}
+      </div>
+    </div>,
+    document.body
+  );
+}
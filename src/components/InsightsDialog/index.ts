@@ -0,0 +1 @@
+export { InsightsDialog } from "./InsightsDialog";
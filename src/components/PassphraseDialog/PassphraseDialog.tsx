@@ -0,0 +1,114 @@
+/**
+ * Passphrase Dialog
+ *
+ * Masked passphrase prompt for encrypted documents. Registers itself as the
+ * requester in encryptedDocuments.ts; new passphrases are typed twice.
+ */
+
+import { useEffect, useRef, useState } from "react";
+import { createPortal } from "react-dom";
+import { usePassphraseDialogStore } from "@/stores/passphraseDialogStore";
+import { setPassphraseRequester } from "@/utils/encryptedDocuments";
+import { isImeKeyEvent } from "@/utils/imeGuard";
+import { getFileName } from "@/utils/paths";
+import "./passphrase-dialog.css";
+
+export function PassphraseDialog() {
+  const request = usePassphraseDialogStore((s) => s.request);
+  const finish = usePassphraseDialogStore((s) => s.finish);
+
+  const [passphrase, setPassphrase] = useState("");
+  const [confirmation, setConfirmation] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    setPassphraseRequester((path, { confirm }) =>
+      usePassphraseDialogStore.getState().requestPassphrase(path, confirm)
+    );
+  }, []);
+
+  // Fresh fields for each request
+  useEffect(() => {
+    setPassphrase("");
+    setConfirmation("");
+    setError(null);
+    if (request) requestAnimationFrame(() => inputRef.current?.focus());
+  }, [request]);
+
+  if (!request) return null;
+
+  const submit = () => {
+    if (!passphrase) {
+      setError("Enter a passphrase.");
+      return;
+    }
+    if (request.confirm && passphrase !== confirmation) {
+      setError("The passphrases don't match.");
+      return;
+    }
+    finish(passphrase);
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (isImeKeyEvent(e.nativeEvent)) return;
+    if (e.key === "Escape") {
+      e.preventDefault();
+      finish(null);
+    } else if (e.key === "Enter") {
+      e.preventDefault();
+      submit();
+    }
+  };
+
+  const name = getFileName(request.path);
+
+  return createPortal(
+    <div className="passphrase-dialog-backdrop">
+      <div
+        className="passphrase-dialog"
+        role="dialog"
+        aria-label="Passphrase"
+        onKeyDown={handleKeyDown}
+      >
+        <div className="passphrase-dialog-title">
+          {request.confirm ? `Choose a passphrase for ${name}` : `Enter the passphrase for ${name}`}
+        </div>
+        <input
+          ref={inputRef}
+          type="password"
+          className="passphrase-dialog-input"
+          placeholder="Passphrase"
+          autoComplete="off"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+        />
+        {request.confirm && (
+          <>
+            <input
+              type="password"
+              className="passphrase-dialog-input"
+              placeholder="Confirm passphrase"
+              autoComplete="off"
+              value={confirmation}
+              onChange={(e) => setConfirmation(e.target.value)}
+            />
+            <div className="passphrase-dialog-hint">
+              The document can't be recovered without this passphrase.
+            </div>
+          </>
+        )}
+        {error && <div className="passphrase-dialog-error">{error}</div>}
+        <div className="passphrase-dialog-buttons">
+          <button className="passphrase-dialog-button" onClick={() => finish(null)}>
+            Cancel
+          </button>
+          <button className="passphrase-dialog-button primary" onClick={submit}>
+            {request.confirm ? "Encrypt" : "Unlock"}
+          </button>
+        </div>
+      </div>
+    </div>,
+    document.body
+  );
+}
@@ -0,0 +1 @@
+export { PassphraseDialog } from "./PassphraseDialog";
@@ -0,0 +1,186 @@
+/**
+ * Quick Open
+ *
+ * Spotlight-style file switcher for the open workspace (Cmd+P). Ranking is
+ * done by the backend's watcher-maintained file list (see fileFinder.ts);
+ * Enter opens the selected file like a sidebar click.
+ */
+
+import { useCallback, useEffect, useRef, useState } from "react";
+import { createPortal } from "react-dom";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useQuickOpenStore } from "@/stores/quickOpenStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { fuzzyFindFiles, highlightSegments, type FileMatch } from "@/utils/fileFinder";
+import { isImeKeyEvent } from "@/utils/imeGuard";
+import { joinPath } from "@/utils/pathUtils";
+import "./quick-open.css";
+
+/** Results shown at once */
+const RESULT_LIMIT = 50;
+
+function Highlighted({ text, positions }: { text: string; positions: number[] }) {
+  return (
+    <>
+      {highlightSegments(text, positions).map((segment, i) =>
+        segment.matched ? (
+          <span key={i} className="quick-open-match">
+            {segment.text}
+          </span>
+        ) : (
+          segment.text
+        )
+      )}
+    </>
+  );
+}
+
+function MatchItem({
+  match,
+  selected,
+  onOpen,
+  onHover,
+}: {
+  match: FileMatch;
+  selected: boolean;
+  onOpen: () => void;
+  onHover: () => void;
+}) {
+  const nameStart = Math.max(match.path.lastIndexOf("/"), match.path.lastIndexOf("\\")) + 1;
+  const name = match.path.slice(nameStart);
+  const namePositions = match.positions.filter((p) => p >= nameStart).map((p) => p - nameStart);
+  const ref = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    if (selected) ref.current?.scrollIntoView({ block: "nearest" });
+  }, [selected]);
+
+  return (
+    <div
+      ref={ref}
+      className={`quick-open-item${selected ? " selected" : ""}`}
+      onMouseDown={(e) => {
+        e.preventDefault();
+        onOpen();
+      }}
+      onMouseMove={onHover}
+    >
+      <span className="quick-open-name">
+        <Highlighted text={name} positions={namePositions} />
+      </span>
+      {nameStart > 0 && (
+        <span className="quick-open-dir">{match.path.slice(0, nameStart - 1)}</span>
+      )}
+    </div>
+  );
+}
+
+export function QuickOpen() {
+  const isOpen = useQuickOpenStore((s) => s.isOpen);
+  const close = useQuickOpenStore((s) => s.close);
+  const rootPath = useWorkspaceStore((s) => s.rootPath);
+
+  const [query, setQuery] = useState("");
+  const [matches, setMatches] = useState<FileMatch[]>([]);
+  const [selectedIndex, setSelectedIndex] = useState(0);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  // Fresh search each time the switcher opens
+  useEffect(() => {
+    if (!isOpen) return;
+    setQuery("");
+    setSelectedIndex(0);
+    requestAnimationFrame(() => inputRef.current?.focus());
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!isOpen || !rootPath) {
+      setMatches([]);
+      return;
+    }
+    let cancelled = false;
+    fuzzyFindFiles(rootPath, query, RESULT_LIMIT)
+      .then((result) => {
+        if (cancelled) return;
+        setMatches(result.matches);
+        setSelectedIndex(0);
+      })
+      .catch((error) => console.error("[QuickOpen] Search failed:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, rootPath, query]);
+
+  const openMatch = useCallback(
+    async (match: FileMatch | undefined) => {
+      if (!match || !rootPath) return;
+      close();
+      try {
+        // Same window-local event as a sidebar click
+        await getCurrentWebviewWindow().emit("open-file", { path: joinPath(rootPath, match.path) });
+      } catch (error) {
+        console.error("[QuickOpen] Failed to open file:", error);
+      }
+    },
+    [rootPath, close]
+  );
+
+  const handleKeyDown = useCallback(
+    (e: React.KeyboardEvent) => {
+      if (isImeKeyEvent(e.nativeEvent)) return;
+      switch (e.key) {
+        case "Escape":
+          e.preventDefault();
+          close();
+          break;
+        case "ArrowDown":
+          e.preventDefault();
+          setSelectedIndex((i) => Math.min(i + 1, matches.length - 1));
+          break;
+        case "ArrowUp":
+          e.preventDefault();
+          setSelectedIndex((i) => Math.max(i - 1, 0));
+          break;
+        case "Enter":
+          e.preventDefault();
+          void openMatch(matches[selectedIndex]);
+          break;
+      }
+    },
+    [close, matches, selectedIndex, openMatch]
+  );
+
+  if (!isOpen) return null;
+
+  return createPortal(
+    <div className="quick-open-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="quick-open" role="dialog" aria-label="Quick Open">
+        <input
+          ref={inputRef}
+          className="quick-open-search"
+          placeholder={rootPath ? "Search files by name" : "Open a folder to search its files"}
+          value={query}
+          disabled={!rootPath}
+          onChange={(e) => setQuery(e.target.value)}
+          onKeyDown={handleKeyDown}
+          onBlur={close}
+        />
+        <div className="quick-open-list">
+          {rootPath && matches.length === 0 && (
+            <div className="quick-open-empty">No matching files</div>
+          )}
+          {matches.map((match, i) => (
+            <MatchItem
+              key={match.path}
+              match={match}
+              selected={i === selectedIndex}
+              onOpen={() => void openMatch(match)}
+              onHover={() => setSelectedIndex(i)}
+            />
+          ))}
+        </div>
+      </div>
+    </div>,
+    document.body
+  );
+}
@@ -0,0 +1 @@
+export { QuickOpen } from "./QuickOpen";
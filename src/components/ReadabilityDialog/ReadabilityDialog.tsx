@@ -0,0 +1,153 @@
+/**
+ * Readability Dialog
+ *
+ * Readability of the current document: reading ease and grade, sentence
+ * lengths, passive voice and long words, plus a heatmap of its paragraphs
+ * from easy to hard. Clicking a paragraph moves the cursor to it. Opened
+ * from View → Readability Report…; the scores come from readability.rs.
+ */
+
+import { useEffect, useState } from "react";
+import { createPortal } from "react-dom";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useReadabilityDialogStore } from "@/stores/readabilityDialogStore";
+import { getActiveDocument, getActiveTabId } from "@/utils/activeDocument";
+import {
+  analyzeReadability,
+  heatmapColor,
+  readingEaseLabel,
+  type ReadabilityReport,
+} from "@/utils/readability";
+import { revealLine } from "@/utils/revealLine";
+import "./readability-dialog.css";
+
+export function ReadabilityDialog() {
+  const windowLabel = useWindowLabel();
+  const isOpen = useReadabilityDialogStore((s) => s.isOpen);
+  const close = useReadabilityDialogStore((s) => s.close);
+
+  const [report, setReport] = useState<ReadabilityReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  // Rescore each time the dialog opens
+  useEffect(() => {
+    if (!isOpen) return;
+    let cancelled = false;
+    setReport(null);
+    setError(null);
+    analyzeReadability(getActiveDocument(windowLabel)?.content ?? "")
+      .then((result) => !cancelled && setReport(result))
+      .catch((err) => !cancelled && setError(String(err)));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, windowLabel]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  if (!isOpen) return null;
+
+  const goToLine = (line: number) => {
+    const tabId = getActiveTabId(windowLabel);
+    if (!tabId) return;
+    close();
+    revealLine(tabId, line);
+  };
+
+  const renderReport = (r: ReadabilityReport) => {
+    const maxLength = Math.max(1, ...r.sentenceLengths.map((bucket) => bucket.count));
+    return (
+      <>
+        <div className="readability-dialog-totals">
+          <div title="Flesch reading ease, 0–100">
+            <strong>{Math.round(r.fleschReadingEase)}</strong> {readingEaseLabel(r.fleschReadingEase)}
+          </div>
+          <div title="Flesch–Kincaid grade level">
+            <strong>{r.fleschKincaidGrade.toFixed(1)}</strong> grade
+          </div>
+          <div title={`Longest sentence: ${r.longestSentence} words`}>
+            <strong>{r.averageSentenceLength.toFixed(1)}</strong> words per sentence
+          </div>
+          <div>
+            <strong>{r.passiveSentences}</strong> passive of {r.sentences} sentences
+          </div>
+          <div>
+            <strong>{r.longWords}</strong> long words of {r.words}
+          </div>
+        </div>
+
+        <div className="readability-dialog-section">
+          <span className="readability-dialog-label">Sentence lengths (words)</span>
+          <div className="readability-dialog-bars">
+            {r.sentenceLengths.map((bucket) => (
+              <div
+                key={bucket.label}
+                className="readability-dialog-bar"
+                style={{ height: `${(bucket.count / maxLength) * 100}%` }}
+                title={`${bucket.label} words: ${bucket.count}`}
+              />
+            ))}
+          </div>
+          <div className="readability-dialog-axis">
+            {r.sentenceLengths.map((bucket) => (
+              <span key={bucket.label}>{bucket.label}</span>
+            ))}
+          </div>
+        </div>
+
+        <div className="readability-dialog-section">
+          <span className="readability-dialog-label">Paragraphs, easy to hard</span>
+          <div className="readability-dialog-heatmap">
+            {r.paragraphs.map((p) => (
+              <button
+                key={p.startLine}
+                className="readability-dialog-paragraph"
+                style={{ background: heatmapColor(p.difficulty) }}
+                onClick={() => goToLine(p.startLine)}
+                title={`Grade ${p.fleschKincaidGrade.toFixed(1)} · ${p.averageSentenceLength.toFixed(1)} words per sentence · ${p.passiveSentences} passive · ${p.longWords} long words`}
+              >
+                <span>
+                  {p.startLine === p.endLine ? `Line ${p.startLine}` : `Lines ${p.startLine}–${p.endLine}`}
+                </span>
+                <span className="readability-dialog-paragraph-detail">
+                  {readingEaseLabel(p.fleschReadingEase)} · {p.words} words
+                </span>
+              </button>
+            ))}
+          </div>
+        </div>
+      </>
+    );
+  };
+
+  return createPortal(
+    <div className="readability-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="readability-dialog" role="dialog" aria-modal="true" aria-label="Readability">
+        <div className="readability-dialog-header">
+          <span className="readability-dialog-title">Readability</span>
+          <button className="readability-dialog-button" onClick={close}>
+            Close
+          </button>
+        </div>
+
+        {error ? (
+          <div className="readability-dialog-error">{error}</div>
+        ) : !report ? (
+          <div className="readability-dialog-message">Scoring…</div>
+        ) : report.words === 0 ? (
+          <div className="readability-dialog-message">There is no prose to score yet.</div>
+        ) : (
+          <div className="readability-dialog-body">{renderReport(report)}</div>
+        )}
+      </div>
+    </div>,
+    document.body
+  );
+}
@@ -0,0 +1 @@
+export { ReadabilityDialog } from "./ReadabilityDialog";
@@ -3,11 +3,13 @@ import {
   FileText,
   FolderPlus,
   FilePlus,
+  Hash,
   Pencil,
   Trash2,
   Copy,
   FolderOpen,
   FolderInput,
+  Eye,
 } from "lucide-react";
 import { isImeKeyEvent } from "@/utils/imeGuard";
 import { getRevealInFileManagerLabel } from "@/utils/pathUtils";
@@ -32,6 +34,7 @@ interface MenuItem {
 function buildFileMenuItems(revealLabel: string): MenuItem[] {
   return [
     { id: "open", label: "Open", icon: <FileText size={14} /> },
+    { id: "openInViewer", label: "Open in Viewer", icon: <Eye size={14} /> },
     { id: "rename", label: "Rename", icon: <Pencil size={14} />, separator: true },
     { id: "duplicate", label: "Duplicate", icon: <Copy size={14} /> },
     { id: "moveTo", label: "Move to...", icon: <FolderInput size={14} /> },
@@ -44,6 +47,7 @@ function buildFileMenuItems(revealLabel: string): MenuItem[] {
 function buildFolderMenuItems(revealLabel: string): MenuItem[] {
   return [
     { id: "newFile", label: "New File", icon: <FilePlus size={14} /> },
+    { id: "newZettel", label: "New Zettel", icon: <Hash size={14} /> },
     { id: "newFolder", label: "New Folder", icon: <FolderPlus size={14} />, separator: true },
     { id: "rename", label: "Rename", icon: <Pencil size={14} /> },
     { id: "delete", label: "Delete", icon: <Trash2 size={14} />, separator: true },
@@ -54,6 +58,7 @@ function buildFolderMenuItems(revealLabel: string): MenuItem[] {
 
 const EMPTY_MENU_ITEMS: MenuItem[] = [
   { id: "newFile", label: "New File", icon: <FilePlus size={14} /> },
+  { id: "newZettel", label: "New Zettel", icon: <Hash size={14} /> },
   { id: "newFolder", label: "New Folder", icon: <FolderPlus size={14} /> },
 ];
 
@@ -9,6 +9,7 @@ import { ContextMenu, type ContextMenuType, type ContextMenuPosition } from "./C
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useWindowLabel } from "@/contexts/WindowContext";
 import { getFileName, getParentDir } from "@/utils/paths";
+import { openInViewer } from "@/utils/viewerWindow";
 import type { FileNode as FileNodeType } from "./types";
 import "./FileExplorer.css";
 
@@ -65,6 +66,7 @@ export const FileExplorer = forwardRef<FileExplorerHandle, FileExplorerProps>(
   });
   const {
     createFile,
+    createZettelNote,
     createFolder,
     renameItem,
     deleteItem,
@@ -131,6 +133,14 @@ export const FileExplorer = forwardRef<FileExplorerHandle, FileExplorerProps>(
           }
           break;
 
+        case "openInViewer":
+          if (targetPath && !targetIsFolder) {
+            await openInViewer(targetPath).catch((error) => {
+              console.error("[FileExplorer] Failed to open viewer:", error);
+            });
+          }
+          break;
+
         case "rename":
           if (targetPath) {
             const node = treeRef.current?.get(targetPath);
@@ -180,12 +190,16 @@ export const FileExplorer = forwardRef<FileExplorerHandle, FileExplorerProps>(
           await handleNewFile(targetPath);
           break;
 
+        case "newZettel":
+          await handleNewZettel(targetPath);
+          break;
+
         case "newFolder":
           await handleNewFolder(targetPath);
           break;
       }
     },
-    // eslint-disable-next-line react-hooks/exhaustive-deps -- handleNewFile/handleNewFolder use getState() pattern
+    // eslint-disable-next-line react-hooks/exhaustive-deps -- handleNewFile/handleNewZettel/handleNewFolder use getState() pattern
     [contextMenu, openFile, duplicateFile, moveItem, deleteItem, copyPath, revealInFinder]
   );
 
@@ -263,6 +277,26 @@ export const FileExplorer = forwardRef<FileExplorerHandle, FileExplorerProps>(
     [rootPath, createFile, refresh]
   );
 
+  // Create a note named by a new ID and open it
+  const handleNewZettel = useCallback(
+    async (parentPath?: string | null) => {
+      if (!rootPath) return;
+
+      let targetPath = parentPath;
+      if (!targetPath) {
+        const selected = treeRef.current?.selectedNodes[0];
+        targetPath = selected?.data.isFolder ? selected.data.id : rootPath;
+      }
+
+      const path = await createZettelNote(targetPath);
+      if (path) {
+        await refresh();
+        await openFile(path);
+      }
+    },
+    [rootPath, createZettelNote, refresh, openFile]
+  );
+
   // Create new folder
   const handleNewFolder = useCallback(
     async (parentPath?: string | null) => {
@@ -10,6 +10,8 @@ export interface DirectoryEntry {
   path: string;
   isDirectory: boolean;
   isHidden: boolean;
+  size?: number; // Bytes (files only)
+  modified?: number; // Unix timestamp ms
 }
 
 /**
@@ -25,4 +27,23 @@ export interface FsChangeEvent {
   paths: string[];
   /** Event kind */
   kind: "create" | "modify" | "remove" | "rename";
+  /** Old path for rename events (absent if the source is unknown) */
+  from?: string;
+  /** New path for rename events (absent if the target left the watch) */
+  to?: string;
+  /** SHA-256 (hex) per existing regular file after the change */
+  digests?: Record<string, string>;
+}
+
+/**
+ * Coalesced watcher changes, emitted as "fs:batch" when a watch is started
+ * with batchWindowMs. Non-rename kinds are merged with deduplicated paths.
+ */
+export interface FsBatchEvent {
+  watchId: string;
+  rootPath: string;
+  changes: FsChangeEvent[];
+  /** Raw event counts per kind folded into this batch */
+  counts: { create: number; modify: number; remove: number; rename: number };
+  total: number;
 }
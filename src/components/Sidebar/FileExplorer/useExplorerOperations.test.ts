@@ -0,0 +1,100 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { renderHook } from "@testing-library/react";
+
+vi.mock("@tauri-apps/plugin-opener", () => ({
+  revealItemInDir: vi.fn(),
+}));
+
+vi.mock("@/stores/tabStore", () => ({
+  useTabStore: {
+    getState: vi.fn(() => ({ getAllOpenFilePaths: () => ["/notes/a.md"] })),
+  },
+}));
+
+vi.mock("@/hooks/commands", () => ({
+  applyPathReconciliation: vi.fn(),
+}));
+
+vi.mock("@/utils/errorDialog", () => ({
+  showError: vi.fn(),
+  FileErrors: {
+    createFailed: (name: string) => `create ${name}`,
+    renameFailed: (name: string) => `rename ${name}`,
+    moveFailed: (name: string) => `move ${name}`,
+  },
+}));
+
+vi.mock("@/utils/zettel", () => ({
+  createZettel: vi.fn(),
+  reportLinkUpdates: vi.fn(),
+}));
+
+vi.mock("@/stores/workspaceStore", () => ({
+  useWorkspaceStore: {
+    getState: vi.fn(() => ({ rootPath: "/notes" })),
+  },
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { applyPathReconciliation } from "@/hooks/commands";
+import { showError } from "@/utils/errorDialog";
+import { reportLinkUpdates } from "@/utils/zettel";
+import { useExplorerOperations } from "./useExplorerOperations";
+
+describe("useExplorerOperations", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.clearAllMocks();
+  });
+
+  const operations = () => renderHook(() => useExplorerOperations()).result.current;
+
+  it("creates folders through the backend, which picks a free name", async () => {
+    vi.mocked(invoke).mockResolvedValue("/notes/Ideas (2)");
+
+    expect(await operations().createFolder("/notes", "Ideas")).toBe("/notes/Ideas (2)");
+    expect(invoke).toHaveBeenCalledWith("create_folder", { parent: "/notes", name: "Ideas" });
+  });
+
+  it("renames through the backend and follows the final path", async () => {
+    const outcome = { path: "/notes/b (2).md", updatedLinks: [] };
+    vi.mocked(invoke).mockResolvedValue(outcome);
+
+    expect(await operations().renameItem("/notes/a.md", "b")).toBe("/notes/b (2).md");
+    expect(invoke).toHaveBeenCalledWith("rename_path", {
+      path: "/notes/a.md",
+      newName: "b.md",
+      updateLinks: true,
+      rootPath: "/notes",
+    });
+    expect(applyPathReconciliation).toHaveBeenCalled();
+    expect(reportLinkUpdates).toHaveBeenCalledWith(outcome);
+  });
+
+  it("leaves an unchanged name alone", async () => {
+    expect(await operations().renameItem("/notes/a.md", "a")).toBe("/notes/a.md");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("moves through the backend", async () => {
+    const outcome = { path: "/notes/archive/a.md", updatedLinks: [] };
+    vi.mocked(invoke).mockResolvedValue(outcome);
+
+    expect(await operations().moveItem("/notes/a.md", "/notes/archive")).toBe("/notes/archive/a.md");
+    expect(invoke).toHaveBeenCalledWith("move_path", {
+      path: "/notes/a.md",
+      destDir: "/notes/archive",
+      updateLinks: true,
+      rootPath: "/notes",
+    });
+    expect(reportLinkUpdates).toHaveBeenCalledWith(outcome);
+  });
+
+  it("reports a failed move", async () => {
+    vi.mocked(invoke).mockRejectedValue("Permission denied");
+    vi.spyOn(console, "error").mockImplementation(() => {});
+
+    expect(await operations().moveItem("/notes/a.md", "/locked")).toBeNull();
+    expect(showError).toHaveBeenCalledWith("move a.md");
+  });
+});
@@ -1,12 +1,6 @@
 import { useCallback, useRef } from "react";
-import {
-  writeTextFile,
-  readTextFile,
-  mkdir,
-  rename,
-  remove,
-  exists,
-} from "@tauri-apps/plugin-fs";
+import { writeTextFile, exists } from "@tauri-apps/plugin-fs";
+import { invoke } from "@tauri-apps/api/core";
 import { ask } from "@tauri-apps/plugin-dialog";
 import { join, basename } from "@tauri-apps/api/path";
 import { emit } from "@tauri-apps/api/event";
@@ -17,6 +11,9 @@ import { useTabStore } from "@/stores/tabStore";
 import { reconcilePathChange } from "@/utils/pathReconciliation";
 import { applyPathReconciliation } from "@/hooks/commands";
 import { showError, FileErrors } from "@/utils/errorDialog";
+import { createZettel, reportLinkUpdates, type MoveOutcome } from "@/utils/zettel";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { isMacPlatform } from "@/utils/shortcutMatch";
 
 // Re-entry guards
 const isCreatingRef = { current: false };
@@ -52,21 +49,34 @@ export function useExplorerOperations() {
     []
   );
 
-  const createFolder = useCallback(
-    async (parentPath: string, name: string): Promise<string | null> => {
+  const createZettelNote = useCallback(
+    async (parentPath: string): Promise<string | null> => {
       if (isCreatingRef.current) return null;
       isCreatingRef.current = true;
 
       try {
-        const folderPath = await join(parentPath, name);
+        // Named by its ID; the title goes in the note
+        const { path } = await createZettel(parentPath, "");
+        return path;
+      } catch (error) {
+        console.error("[Explorer] Failed to create zettel:", error);
+        toast.error("Failed to create note", { description: String(error) });
+        return null;
+      } finally {
+        isCreatingRef.current = false;
+      }
+    },
+    []
+  );
 
-        if (await exists(folderPath)) {
-          await showError(FileErrors.folderExists(name));
-          return null;
-        }
+  const createFolder = useCallback(
+    async (parentPath: string, name: string): Promise<string | null> => {
+      if (isCreatingRef.current) return null;
+      isCreatingRef.current = true;
 
-        await mkdir(folderPath);
-        return folderPath;
+      try {
+        // The backend picks a free name if `name` is taken
+        return await invoke<string>("create_folder", { parent: parentPath, name });
       } catch (error) {
         console.error("[Explorer] Failed to create folder:", error);
         await showError(FileErrors.createFailed(name));
@@ -93,24 +103,21 @@ export function useExplorerOperations() {
           ? `${newName}.md`
           : newName;
 
-        const newPath = await join(parentPath, finalName);
-
-        if (oldPath === newPath) return oldPath;
-
-        if (await exists(newPath)) {
-          const isTargetFile = finalName.includes(".");
-          await showError(
-            isTargetFile
-              ? FileErrors.fileExists(finalName)
-              : FileErrors.folderExists(finalName)
-          );
-          return null;
-        }
+        if (oldPath === (await join(parentPath, finalName))) return oldPath;
 
         // Get open file paths before rename
         const openFilePaths = useTabStore.getState().getAllOpenFilePaths();
 
-        await rename(oldPath, newPath);
+        // The backend suffixes the name if it is taken, and points links
+        // across the workspace at the new path
+        const rootPath = useWorkspaceStore.getState().rootPath;
+        const outcome = await invoke<MoveOutcome>("rename_path", {
+          path: oldPath,
+          newName: finalName,
+          updateLinks: Boolean(rootPath),
+          rootPath,
+        });
+        const newPath = outcome.path;
 
         // Reconcile: update any open tabs/documents pointing to old path
         const results = reconcilePathChange({
@@ -120,6 +127,7 @@ export function useExplorerOperations() {
           openFilePaths,
         });
         applyPathReconciliation(results);
+        reportLinkUpdates(outcome);
 
         return newPath;
       } catch (error) {
@@ -146,12 +154,13 @@ export function useExplorerOperations() {
         const parentName = await basename(parentPath);
         const locationHint = parentName ? `\n\nLocation: ${parentName}/` : "";
         const message = isFolder
-          ? `Delete folder "${name}" and all its contents?${locationHint}`
-          : `Delete "${name}"?${locationHint}`;
+          ? `Move folder "${name}" and all its contents to the Trash?${locationHint}`
+          : `Move "${name}" to the Trash?${locationHint}`;
 
         const confirmed = await ask(message, {
           title: `Delete ${itemType}`,
           kind: "warning",
+          okLabel: "Move to Trash",
         });
 
         if (!confirmed) return false;
@@ -159,7 +168,24 @@ export function useExplorerOperations() {
         // Get open file paths before delete
         const openFilePaths = useTabStore.getState().getAllOpenFilePaths();
 
-        await remove(path, { recursive: isFolder });
+        await invoke("delete_to_trash", { path });
+        // macOS doesn't let apps read the Trash; Finder's "Put Back" restores there
+        toast.success(
+          `Moved "${name}" to the Trash`,
+          isMacPlatform()
+            ? undefined
+            : {
+                action: {
+                  label: "Undo",
+                  onClick: () => {
+                    invoke("restore_from_trash", { path }).catch((error) => {
+                      console.error("[Explorer] Failed to restore from trash:", error);
+                      toast.error(`Failed to restore "${name}"`);
+                    });
+                  },
+                },
+              }
+        );
 
         // Reconcile: mark any open tabs/documents as missing
         const results = reconcilePathChange({
@@ -186,26 +212,21 @@ export function useExplorerOperations() {
     async (srcPath: string, destFolder: string): Promise<string | null> => {
       const name = await basename(srcPath);
       try {
-        const destPath = await join(destFolder, name);
-
-        if (srcPath === destPath) return srcPath;
-
-        if (await exists(destPath)) {
-          const isFile = name.includes(".");
-          await showError(
-            isFile
-              ? FileErrors.fileExists(name)
-              : FileErrors.folderExists(name)
-          );
-          return null;
-        }
+        if (srcPath === (await join(destFolder, name))) return srcPath;
 
         // Get open file paths before move
         const openFilePaths = useTabStore.getState().getAllOpenFilePaths();
 
-        // For files, use rename (more efficient)
-        // For folders, rename also works on most systems
-        await rename(srcPath, destPath);
+        // The backend suffixes the name if it is taken, copies across
+        // volumes and points links across the workspace at the new path
+        const rootPath = useWorkspaceStore.getState().rootPath;
+        const outcome = await invoke<MoveOutcome>("move_path", {
+          path: srcPath,
+          destDir: destFolder,
+          updateLinks: Boolean(rootPath),
+          rootPath,
+        });
+        const destPath = outcome.path;
 
         // Reconcile: update any open tabs/documents pointing to old path
         const results = reconcilePathChange({
@@ -215,6 +236,7 @@ export function useExplorerOperations() {
           openFilePaths,
         });
         applyPathReconciliation(results);
+        reportLinkUpdates(outcome);
 
         return destPath;
       } catch (error) {
@@ -234,31 +256,8 @@ export function useExplorerOperations() {
     async (path: string): Promise<string | null> => {
       const name = await basename(path);
       try {
-        const parentPath = path.slice(0, -name.length - 1);
-        const nameWithoutExt = name.replace(/\.md$/, "");
-
-        // Find a unique name (with reasonable upper bound to prevent infinite loops)
-        const MAX_COPIES = 1000;
-        let counter = 1;
-        let newName = `${nameWithoutExt} copy.md`;
-        let newPath = await join(parentPath, newName);
-
-        while (await exists(newPath)) {
-          counter++;
-          if (counter > MAX_COPIES) {
-            console.error("[Explorer] Too many copies exist, cannot duplicate:", path);
-            await showError(FileErrors.tooManyCopies(name));
-            return null;
-          }
-          newName = `${nameWithoutExt} copy ${counter}.md`;
-          newPath = await join(parentPath, newName);
-        }
-
-        // Copy content
-        const content = await readTextFile(path);
-        await writeTextFile(newPath, content);
-
-        return newPath;
+        // The backend picks a free "name (2).md" and copies the file as-is
+        return await invoke<string>("duplicate_file", { path });
       } catch (error) {
         console.error("[Explorer] Failed to duplicate:", error);
         await showError(FileErrors.duplicateFailed(name));
@@ -288,6 +287,7 @@ export function useExplorerOperations() {
 
   return {
     createFile,
+    createZettelNote,
     createFolder,
     renameItem,
     deleteItem,
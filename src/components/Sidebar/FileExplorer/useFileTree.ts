@@ -1,9 +1,10 @@
 import { useState, useEffect, useCallback, useRef } from "react";
 import { basename } from "@tauri-apps/api/path";
-import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { UnlistenFn } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 import type { FileNode, FsChangeEvent, DirectoryEntry } from "./types";
 import { shouldRefreshTree } from "@/utils/fsEventFilter";
+import { listenFsChanges } from "@/utils/fsChanges";
 import { isMarkdownFileName, stripMarkdownExtension } from "@/utils/dropPaths";
 import { shouldIncludeEntry, type FileTreeFilterOptions } from "./fileTreeFilters";
 
@@ -130,10 +131,10 @@ export function useFileTree(
 
     // Listen for fs changes
     let cancelled = false;
-    listen<FsChangeEvent>("fs:changed", (event) => {
+    listenFsChanges<FsChangeEvent>((changes) => {
       if (cancelled) return;
-      // Use pure helper to determine if we should refresh
-      if (shouldRefreshTree(event.payload, watchId, rootPath)) {
+      // Use pure helper to determine if we should refresh; once per batch
+      if (changes.some((change) => shouldRefreshTree(change, watchId, rootPath))) {
         loadTree();
       }
     }).then((unlisten) => {
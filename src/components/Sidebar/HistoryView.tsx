@@ -1,13 +1,13 @@
 /**
  * History View Component
  *
- * Displays document version history with revert functionality.
+ * Displays document version history with compare and revert functionality.
  */
 
 import { useState, useEffect, useRef } from "react";
-import { RotateCcw } from "lucide-react";
+import { GitCompare, RotateCcw } from "lucide-react";
 import { ask } from "@tauri-apps/plugin-dialog";
-import { writeTextFile } from "@tauri-apps/plugin-fs";
+import { useCompareViewStore } from "@/stores/compareViewStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import {
   useDocumentFilePath,
@@ -15,10 +15,13 @@ import {
 } from "@/hooks/useDocumentState";
 import {
   getSnapshots,
+  loadSnapshot,
   revertToSnapshot,
   type Snapshot,
 } from "@/hooks/useHistoryOperations";
 import { formatSnapshotTime, groupByDay } from "@/utils/dateUtils";
+import { getFileName } from "@/utils/paths";
+import { writeDocumentFile } from "@/utils/encryptedDocuments";
 
 export function HistoryView() {
   const filePath = useDocumentFilePath();
@@ -62,6 +65,20 @@ export function HistoryView() {
     fetchSnapshots();
   }, [filePath, historyEnabled]);
 
+  const handleCompare = async (snapshot: Snapshot) => {
+    if (!filePath) return;
+    const content = await loadSnapshot(filePath, snapshot.id);
+    if (content === null) return;
+    const time = formatSnapshotTime(snapshot.timestamp);
+    useCompareViewStore.getState().open({
+      leftLabel: time,
+      rightLabel: "Current",
+      title: `${getFileName(filePath)}: ${time} ↔ current`,
+      left: content,
+      right: getContent(),
+    });
+  };
+
   const handleRevert = async (snapshot: Snapshot) => {
     if (!filePath) return;
     // Prevent re-entry (duplicate dialogs from rapid clicks)
@@ -94,7 +111,7 @@ export function HistoryView() {
 
       if (restoredContent !== null) {
         // Write to file
-        await writeTextFile(filePath, restoredContent);
+        await writeDocumentFile(filePath, restoredContent);
         // Update editor
         loadContent(restoredContent, filePath);
         // Refresh snapshots
@@ -158,13 +175,22 @@ export function HistoryView() {
                 </span>
                 <span className="history-type">({snapshot.type})</span>
               </div>
-              <button
-                className="history-revert-btn"
-                onClick={() => handleRevert(snapshot)}
-                title="Revert to this version"
-              >
-                <RotateCcw size={12} />
-              </button>
+              <div className="history-item-actions">
+                <button
+                  className="history-revert-btn"
+                  onClick={() => void handleCompare(snapshot)}
+                  title="Compare with current"
+                >
+                  <GitCompare size={12} />
+                </button>
+                <button
+                  className="history-revert-btn"
+                  onClick={() => handleRevert(snapshot)}
+                  title="Revert to this version"
+                >
+                  <RotateCcw size={12} />
+                </button>
+              </div>
             </div>
           ))}
         </div>
@@ -1,14 +1,16 @@
 /**
  * Outline View Component
  *
- * Displays document heading structure as a tree.
+ * Displays document heading structure as a tree. Headings of documents too
+ * large to scan here come from the backend block parser.
  */
 
 import { useState, useMemo, useRef } from "react";
 import { ChevronRight, ChevronDown } from "lucide-react";
 import { emit } from "@tauri-apps/api/event";
 import { useUIStore } from "@/stores/uiStore";
-import { useDocumentContent } from "@/hooks/useDocumentState";
+import { useActiveTabId, useDocumentContent } from "@/hooks/useDocumentState";
+import { useParsedOutline } from "@/hooks/useParsedOutline";
 import { perfStart, perfEnd } from "@/utils/perfLog";
 import {
   extractHeadings,
@@ -92,11 +94,13 @@ const MAX_CONTENT_FOR_OUTLINE = 100000; // 100KB threshold
 const MAX_OUTLINE_ITEMS = 100; // Limit total visible items
 
 export function OutlineView() {
+  const tabId = useActiveTabId();
   const content = useDocumentContent();
   const activeHeadingIndex = useUIStore((state) => state.activeHeadingLine);
 
-  // Check if document is too large (used after hooks)
+  // Documents over the threshold are parsed in the backend instead
   const isTooLarge = content.length > MAX_CONTENT_FOR_OUTLINE;
+  const parsedHeadings = useParsedOutline(tabId, content, isTooLarge);
 
   // Create a stable key based only on heading lines.
   // This prevents re-extraction when typing in non-heading content.
@@ -111,7 +115,7 @@ export function OutlineView() {
 
   // Only re-extract headings when heading lines actually change
   const headings = useMemo(() => {
-    if (isTooLarge) return [];
+    if (isTooLarge) return parsedHeadings;
     if (headingLinesKey === prevKeyRef.current) {
       return prevHeadingsRef.current;
     }
@@ -121,15 +125,14 @@ export function OutlineView() {
     prevHeadingsRef.current = newHeadings;
     prevKeyRef.current = headingLinesKey;
     return newHeadings;
-  }, [headingLinesKey, content, isTooLarge]);
+  }, [headingLinesKey, content, isTooLarge, parsedHeadings]);
 
   const tree = useMemo(() => {
-    if (isTooLarge) return [];
     perfStart("OutlineView:buildHeadingTree");
     const result = buildHeadingTree(headings);
     perfEnd("OutlineView:buildHeadingTree", { rootNodes: result.length });
     return result;
-  }, [headings, isTooLarge]);
+  }, [headings]);
 
   // Count total nodes and build limited tree if needed
   const totalNodes = useMemo(() => countTreeNodes(tree), [tree]);
@@ -179,15 +182,6 @@ export function OutlineView() {
     useUIStore.getState().setActiveHeadingLine(headingIndex);
   };
 
-  // Skip outline for very large documents to prevent performance issues
-  if (isTooLarge) {
-    return (
-      <div className="sidebar-view outline-view">
-        <div className="sidebar-empty">Document too large for outline</div>
-      </div>
-    );
-  }
-
   return (
     <div className="sidebar-view outline-view">
       {headings.length > 0 ? (
@@ -1,16 +1,17 @@
 /**
  * Sidebar Component
  *
- * Navigation sidebar with Files, Outline, and History views.
+ * Navigation sidebar with Files, Outline, History, and Tasks views.
  */
 
 import { useRef } from "react";
-import { ListTree, TableOfContents, History, FilePlus, FolderPlus } from "lucide-react";
+import { ListTree, TableOfContents, History, ListChecks, FilePlus, FolderPlus } from "lucide-react";
 import { useUIStore, type SidebarViewMode } from "@/stores/uiStore";
 import { useDocumentFilePath } from "@/hooks/useDocumentState";
 import { FileExplorer, type FileExplorerHandle } from "./FileExplorer";
 import { OutlineView } from "./OutlineView";
 import { HistoryView } from "./HistoryView";
+import { TasksView } from "./TasksView";
 import "./Sidebar.css";
 
 // Constants
@@ -24,7 +25,8 @@ const VIEW_CONFIG: Record<SidebarViewMode, {
 }> = {
   files: { icon: ListTree, title: "FILES", next: "outline" },
   outline: { icon: TableOfContents, title: "OUTLINE", next: "history" },
-  history: { icon: History, title: "HISTORY", next: "files" },
+  history: { icon: History, title: "HISTORY", next: "tasks" },
+  tasks: { icon: ListChecks, title: "TASKS", next: "files" },
 };
 
 export function Sidebar() {
@@ -78,6 +80,7 @@ export function Sidebar() {
         {viewMode === "files" && <FileExplorer ref={fileExplorerRef} currentFilePath={filePath} />}
         {viewMode === "outline" && <OutlineView />}
         {viewMode === "history" && <HistoryView />}
+        {viewMode === "tasks" && <TasksView />}
       </div>
     </div>
   );
@@ -0,0 +1,148 @@
+/**
+ * Tasks View Component
+ *
+ * Checkbox items from every note in the workspace, grouped by note and
+ * filtered by status and tag. Checking a task edits its note on disk; the
+ * index itself is kept current by task_index.rs from watcher events.
+ */
+
+import { useCallback, useEffect, useRef, useState } from "react";
+import { RefreshCw } from "lucide-react";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { toast } from "sonner";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { joinPath } from "@/utils/pathUtils";
+import { localDate } from "@/utils/taskCalendar";
+import {
+  groupTasksByNote,
+  listTaskTags,
+  queryTasks,
+  rebuildTaskIndex,
+  toggleTask,
+  type IndexedTask,
+  type TaskQuery,
+  type TaskTagCount,
+} from "@/utils/taskIndex";
+
+type Status = NonNullable<TaskQuery["status"]>;
+
+export function TasksView() {
+  const rootPath = useWorkspaceStore((state) => state.rootPath);
+  const [status, setStatus] = useState<Status>("open");
+  const [tag, setTag] = useState("");
+  const [tasks, setTasks] = useState<IndexedTask[]>([]);
+  const [tags, setTags] = useState<TaskTagCount[]>([]);
+  const [loading, setLoading] = useState(false);
+  const requestIdRef = useRef(0);
+
+  const load = useCallback(async () => {
+    if (!rootPath) return;
+    const currentRequestId = ++requestIdRef.current;
+    setLoading(true);
+    try {
+      const [found, tagCounts] = await Promise.all([
+        queryTasks(rootPath, { status, tag: tag || undefined }),
+        listTaskTags(rootPath),
+      ]);
+      if (currentRequestId !== requestIdRef.current) return;
+      setTasks(found);
+      setTags(tagCounts);
+    } catch (error) {
+      if (currentRequestId === requestIdRef.current) {
+        console.error("[Tasks] Failed to query tasks:", error);
+        setTasks([]);
+      }
+    } finally {
+      if (currentRequestId === requestIdRef.current) setLoading(false);
+    }
+  }, [rootPath, status, tag]);
+
+  useEffect(() => {
+    void load();
+  }, [load]);
+
+  const handleRebuild = async () => {
+    if (!rootPath) return;
+    try {
+      await rebuildTaskIndex(rootPath);
+    } catch (error) {
+      console.error("[Tasks] Failed to rebuild index:", error);
+    }
+    void load();
+  };
+
+  const handleToggle = async (task: IndexedTask) => {
+    if (!rootPath) return;
+    try {
+      const updated = await toggleTask(rootPath, task);
+      setTasks((current) =>
+        current.map((t) => (t.path === task.path && t.line === task.line ? updated : t))
+      );
+    } catch (error) {
+      toast.error(String(error));
+      void load();
+    }
+  };
+
+  const openNote = (path: string) => {
+    if (!rootPath) return;
+    void getCurrentWebviewWindow().emit("open-file", { path: joinPath(rootPath, path) });
+  };
+
+  if (!rootPath) {
+    return (
+      <div className="sidebar-view">
+        <div className="sidebar-empty">Open a folder to see its tasks</div>
+      </div>
+    );
+  }
+
+  const today = localDate();
+  const groups = groupTasksByNote(tasks);
+
+  return (
+    <div className="sidebar-view tasks-view">
+      <div className="tasks-filters">
+        <select value={status} onChange={(e) => setStatus(e.target.value as Status)}>
+          <option value="open">Open</option>
+          <option value="done">Done</option>
+          <option value="all">All</option>
+        </select>
+        <select value={tag} onChange={(e) => setTag(e.target.value)}>
+          <option value="">All tags</option>
+          {tags.map((t) => (
+            <option key={t.tag} value={t.tag}>
+              #{t.tag} ({t.open})
+            </option>
+          ))}
+        </select>
+        <button className="tasks-refresh-btn" onClick={() => void handleRebuild()} title="Rescan workspace">
+          <RefreshCw size={12} />
+        </button>
+      </div>
+
+      {groups.size === 0 ? (
+        <div className="sidebar-empty">{loading ? "Loading..." : "No tasks"}</div>
+      ) : (
+        Array.from(groups.entries()).map(([path, noteTasks]) => (
+          <div key={path} className="tasks-group">
+            <button className="tasks-note" onClick={() => openNote(path)} title={path}>
+              {path}
+            </button>
+            {noteTasks.map((task) => (
+              <label key={task.line} className={`tasks-item ${task.done ? "done" : ""}`}>
+                <input type="checkbox" checked={task.done} onChange={() => void handleToggle(task)} />
+                <span className="tasks-text">{task.text}</span>
+                {task.due && (
+                  <span className={`tasks-due ${!task.done && task.due < today ? "overdue" : ""}`}>
+                    {task.due}
+                  </span>
+                )}
+              </label>
+            ))}
+          </div>
+        ))
+      )}
+    </div>
+  );
+}
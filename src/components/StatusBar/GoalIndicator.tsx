@@ -0,0 +1,154 @@
+/**
+ * Goal Indicator Component
+ *
+ * Writing goals in the StatusBar: today's words against the daily goal
+ * (icon only when there is none), with the streak in the tooltip. Clicking
+ * opens a popover to set the daily goal and a goal for the current
+ * document, and shows the last 7 days.
+ *
+ * Progress is recorded by saveToPath and tracked in goals.rs.
+ */
+
+import { useEffect, useRef, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Target } from "lucide-react";
+import { toast } from "sonner";
+import { useDocumentFilePath } from "@/hooks/useDocumentState";
+import { useWritingGoalsStore } from "@/stores/writingGoalsStore";
+import { getFileName } from "@/utils/paths";
+import { getProgress, goalPercent, setGoal } from "@/utils/writingGoals";
+
+function refresh() {
+  getProgress()
+    .then((progress) => useWritingGoalsStore.getState().setProgress(progress))
+    .catch((error) => console.warn("[Goals] Failed to load progress:", error));
+}
+
+function GoalInput({ value, onCommit }: { value: number | null; onCommit: (target: number) => void }) {
+  const [draft, setDraft] = useState(value ? String(value) : "");
+  useEffect(() => setDraft(value ? String(value) : ""), [value]);
+  const commit = () => {
+    const target = Number(draft) || 0;
+    if (target !== (value ?? 0)) onCommit(target);
+  };
+  return (
+    <input
+      type="number"
+      min={0}
+      step={100}
+      value={draft}
+      placeholder="None"
+      onChange={(e) => setDraft(e.target.value)}
+      onBlur={commit}
+      onKeyDown={(e) => {
+        if (e.key === "Enter") commit();
+      }}
+    />
+  );
+}
+
+export function GoalIndicator() {
+  const progress = useWritingGoalsStore((s) => s.progress);
+  const filePath = useDocumentFilePath();
+  const [open, setOpen] = useState(false);
+  const ref = useRef<HTMLDivElement>(null);
+
+  // Load once, and again when any window reaches a goal
+  useEffect(() => {
+    refresh();
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    listen("goals:met", refresh).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!open) return;
+    const onMouseDown = (e: MouseEvent) => {
+      if (ref.current && !ref.current.contains(e.target as Node)) setOpen(false);
+    };
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") setOpen(false);
+    };
+    document.addEventListener("mousedown", onMouseDown, true);
+    document.addEventListener("keydown", onKeyDown);
+    return () => {
+      document.removeEventListener("mousedown", onMouseDown, true);
+      document.removeEventListener("keydown", onKeyDown);
+    };
+  }, [open]);
+
+  const update = (kind: "daily" | "project", target: number, path?: string) => {
+    setGoal(kind, target, path)
+      .then((next) => useWritingGoalsStore.getState().setProgress(next))
+      .catch((error) => toast.error(String(error)));
+  };
+
+  const todayWords = progress?.todayWords ?? 0;
+  const dailyGoal = progress?.dailyGoal ?? null;
+  const documentGoal = filePath ? progress?.projects.find((p) => p.path === filePath) : undefined;
+  const maxDay = Math.max(1, ...(progress?.recentDays.map((d) => d.words) ?? []));
+  const streak = progress?.streak ?? 0;
+
+  const title = [
+    dailyGoal ? `${todayWords} of ${dailyGoal} words today` : `${todayWords} words today`,
+    streak > 0 ? `${streak}-day streak` : null,
+  ]
+    .filter(Boolean)
+    .join(" · ");
+
+  return (
+    <div className="status-goal-wrapper" ref={ref}>
+      <button
+        className={`status-goal ${progress?.dailyMet ? "met" : ""}`}
+        onClick={() => setOpen(!open)}
+        title={`${title} · Click to set goals`}
+      >
+        <Target size={12} />
+        {dailyGoal ? `${goalPercent(todayWords, dailyGoal)}%` : null}
+      </button>
+
+      {open && (
+        <div className="status-goal-popover" role="dialog" aria-label="Writing goals">
+          <div className="status-goal-summary">{title}</div>
+          <label className="status-goal-field">
+            <span>Daily goal</span>
+            <GoalInput value={dailyGoal} onCommit={(target) => update("daily", target)} />
+          </label>
+          {filePath && (
+            <label className="status-goal-field" title={filePath}>
+              <span>{getFileName(filePath)}</span>
+              <GoalInput
+                value={documentGoal?.target ?? null}
+                onCommit={(target) => update("project", target, filePath)}
+              />
+            </label>
+          )}
+          {documentGoal && (
+            <div className="status-goal-summary">
+              {documentGoal.current} of {documentGoal.target} words ({goalPercent(documentGoal.current, documentGoal.target)}%)
+            </div>
+          )}
+          {progress && (
+            <div className="status-goal-days">
+              {progress.recentDays.map((day) => (
+                <div
+                  key={day.date}
+                  className="status-goal-day"
+                  style={{ height: `${(day.words / maxDay) * 100}%` }}
+                  title={`${day.date}: ${day.words} words`}
+                />
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
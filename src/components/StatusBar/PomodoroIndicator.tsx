@@ -0,0 +1,108 @@
+/**
+ * Pomodoro Indicator Component
+ *
+ * Focus timer in the StatusBar:
+ * - Timer icon when idle (click to start)
+ * - Countdown while running (click to pause)
+ * - Dimmed countdown when paused (click to resume)
+ * - Right-click stops the cycle
+ *
+ * The timer itself runs in pomodoro.rs; this only mirrors its state.
+ */
+
+import { useCallback, useEffect, useState, type MouseEvent } from "react";
+import { Coffee, Timer } from "lucide-react";
+import {
+  formatFocusTime,
+  formatRemaining,
+  getPomodoroState,
+  getPomodoroStats,
+  onPomodoroState,
+  pausePomodoro,
+  startPomodoro,
+  stopPomodoro,
+  type PomodoroState,
+} from "@/utils/pomodoro";
+
+const PHASE_LABELS: Record<PomodoroState["phase"], string> = {
+  work: "Focus",
+  shortBreak: "Short break",
+  longBreak: "Long break",
+};
+
+export function PomodoroIndicator() {
+  const [state, setState] = useState<PomodoroState | null>(null);
+  const [todayFocus, setTodayFocus] = useState(0);
+
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    getPomodoroState()
+      .then((initial) => !cancelled && setState(initial))
+      .catch((error) => console.warn("[Pomodoro] Failed to load state:", error));
+    onPomodoroState(setState).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  // Today's total changes when a session completes
+  const completed = state?.completedSessions;
+  useEffect(() => {
+    getPomodoroStats(1)
+      .then((days) => setTodayFocus(days[days.length - 1]?.focusSeconds ?? 0))
+      .catch((error) => console.warn("[Pomodoro] Failed to load stats:", error));
+  }, [completed]);
+
+  const handleClick = useCallback(() => {
+    const action = state?.status === "running" ? pausePomodoro : startPomodoro;
+    action()
+      .then(setState)
+      .catch((error) => console.warn("[Pomodoro] Action failed:", error));
+  }, [state?.status]);
+
+  const handleContextMenu = useCallback(
+    (e: MouseEvent) => {
+      e.preventDefault();
+      if (!state || state.status === "idle") return;
+      stopPomodoro()
+        .then(setState)
+        .catch((error) => console.warn("[Pomodoro] Stop failed:", error));
+    },
+    [state]
+  );
+
+  const status = state?.status ?? "idle";
+  const isBreak = state?.phase !== "work";
+  const today = `Focused today: ${formatFocusTime(todayFocus)}`;
+
+  if (!state || status === "idle") {
+    return (
+      <button className="status-pomodoro" onClick={handleClick} title={`Start focus timer · ${today}`}>
+        <Timer size={12} />
+      </button>
+    );
+  }
+
+  const phase = PHASE_LABELS[state.phase];
+  const title =
+    status === "running"
+      ? `${phase} · click to pause, right-click to stop · ${today}`
+      : `${phase} paused · click to resume, right-click to stop · ${today}`;
+
+  return (
+    <button
+      className={`status-pomodoro ${status} ${isBreak ? "break" : "running"}`}
+      onClick={handleClick}
+      onContextMenu={handleContextMenu}
+      title={title}
+    >
+      {isBreak ? <Coffee size={12} /> : <Timer size={12} />}
+      {formatRemaining(state.remainingMs)}
+    </button>
+  );
+}
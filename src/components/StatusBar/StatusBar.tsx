@@ -27,6 +27,8 @@ import { useShortcutsStore, formatKeyForDisplay } from "@/stores/shortcutsStore"
 import { useMcpServer } from "@/hooks/useMcpServer";
 import { openSettingsWindow } from "@/utils/settingsWindow";
 import { UpdateIndicator } from "./UpdateIndicator";
+import { PomodoroIndicator } from "./PomodoroIndicator";
+import { GoalIndicator } from "./GoalIndicator";
 import "./StatusBar.css";
 
 /**
@@ -255,6 +257,12 @@ export function StatusBar() {
               <Satellite size={12} />
             </button>
 
+            {/* Focus timer */}
+            {isDocumentWindow && <PomodoroIndicator />}
+
+            {/* Writing goals */}
+            {isDocumentWindow && <GoalIndicator />}
+
             {/* Update status indicator */}
             <UpdateIndicator />
 
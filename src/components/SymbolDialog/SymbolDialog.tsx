@@ -0,0 +1,154 @@
+/**
+ * Symbol Dialog
+ *
+ * Searches emoji by shortcode or name and Unicode symbols by name or code
+ * point, and inserts the chosen character at the cursor. Opened from
+ * Insert → Emoji & Symbols…; the tables live in symbols/ in the backend.
+ */
+
+import { useCallback, useEffect, useState } from "react";
+import { createPortal } from "react-dom";
+import { useSymbolDialogStore } from "@/stores/symbolDialogStore";
+import { useActiveEditorStore } from "@/stores/activeEditorStore";
+import { useEditorStore } from "@/stores/editorStore";
+import { searchEmoji, searchUnicode } from "@/utils/symbolSearch";
+import "./symbol-dialog.css";
+
+type Kind = "emoji" | "symbol";
+
+interface Result {
+  char: string;
+  label: string;
+  detail: string;
+}
+
+const RESULT_LIMIT = 60;
+const SEARCH_DELAY_MS = 120;
+
+async function search(kind: Kind, query: string): Promise<Result[]> {
+  if (kind === "emoji") {
+    const matches = await searchEmoji(query, RESULT_LIMIT);
+    return matches.map((m) => ({ char: m.emoji, label: `:${m.shortcode}:`, detail: m.name }));
+  }
+  const matches = await searchUnicode(query, RESULT_LIMIT);
+  return matches.map((m) => ({ char: m.symbol, label: m.codepoint, detail: m.name }));
+}
+
+function insertIntoEditor(text: string) {
+  const { activeSourceView, activeWysiwygEditor } = useActiveEditorStore.getState();
+  if (useEditorStore.getState().sourceMode) {
+    if (!activeSourceView) return;
+    const { from, to } = activeSourceView.state.selection.main;
+    activeSourceView.dispatch({
+      changes: { from, to, insert: text },
+      selection: { anchor: from + text.length },
+    });
+    activeSourceView.focus();
+  } else if (activeWysiwygEditor) {
+    const { view } = activeWysiwygEditor;
+    view.dispatch(view.state.tr.insertText(text).scrollIntoView());
+    view.focus();
+  }
+}
+
+export function SymbolDialog() {
+  const isOpen = useSymbolDialogStore((s) => s.isOpen);
+  const close = useSymbolDialogStore((s) => s.close);
+  const [kind, setKind] = useState<Kind>("emoji");
+  const [query, setQuery] = useState("");
+  const [results, setResults] = useState<Result[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    setQuery("");
+    setResults([]);
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!isOpen || !query.trim()) {
+      setResults([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = window.setTimeout(() => {
+      search(kind, query.trim())
+        .then((found) => {
+          if (cancelled) return;
+          setResults(found);
+          setError(null);
+        })
+        .catch((err) => !cancelled && setError(String(err)));
+    }, SEARCH_DELAY_MS);
+    return () => {
+      cancelled = true;
+      window.clearTimeout(timer);
+    };
+  }, [isOpen, kind, query]);
+
+  const choose = useCallback(
+    (result: Result) => {
+      close();
+      insertIntoEditor(result.char);
+    },
+    [close]
+  );
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  if (!isOpen) return null;
+
+  return createPortal(
+    <div className="symbol-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="symbol-dialog" role="dialog" aria-modal="true" aria-label="Emoji & Symbols">
+        <div className="symbol-dialog-header">
+          <input
+            className="symbol-dialog-search"
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter" && results.length > 0) choose(results[0]);
+            }}
+            placeholder={kind === "emoji" ? "Search emoji (smile, :+1:)" : "Search symbols (arrow, U+00B0)"}
+            spellCheck={false}
+            autoFocus
+          />
+          <div className="symbol-dialog-tabs">
+            {(["emoji", "symbol"] as const).map((k) => (
+              <button key={k} className={kind === k ? "active" : ""} onClick={() => setKind(k)}>
+                {k === "emoji" ? "Emoji" : "Symbols"}
+              </button>
+            ))}
+          </div>
+        </div>
+
+        {error ? (
+          <div className="symbol-dialog-message">{error}</div>
+        ) : results.length === 0 ? (
+          <div className="symbol-dialog-message">{query.trim() ? "No matches" : "Type to search"}</div>
+        ) : (
+          <div className="symbol-dialog-grid">
+            {results.map((result) => (
+              <button
+                key={`${result.char}-${result.label}`}
+                className="symbol-dialog-item"
+                onClick={() => choose(result)}
+                title={`${result.detail} · ${result.label}`}
+              >
+                {result.char}
+              </button>
+            ))}
+          </div>
+        )}
+      </div>
+    </div>,
+    document.body
+  );
+}
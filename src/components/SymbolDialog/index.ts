@@ -0,0 +1 @@
+export { SymbolDialog } from "./SymbolDialog";
@@ -8,6 +8,7 @@ import { useTabStore, type Tab as TabType } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { closeTabWithDirtyCheck } from "@/hooks/useTabOperations";
 import { useTabDragOut } from "@/hooks/useTabDragOut";
+import { captureTabState } from "@/utils/hotExit/useHotExitCapture";
 import { Tab } from "./Tab";
 import { TabContextMenu, type ContextMenuPosition } from "./TabContextMenu";
 
@@ -25,7 +26,7 @@ export function TabBar() {
   const tabBarRef = useRef<HTMLDivElement>(null);
 
   const handleDragOut = useCallback(
-    async (tabId: string) => {
+    async (tabId: string, screenX: number, screenY: number) => {
       const tabState = useTabStore.getState();
       const windowTabs = tabState.getTabsByWindow(windowLabel);
       const tab = windowTabs.find((t) => t.id === tabId);
@@ -38,6 +39,23 @@ export function TabBar() {
       if (!doc) return;
 
       try {
+        // Dropped onto another window: move the tab there. The source keeps
+        // the tab until the target confirms (see useTabMoveReceiver).
+        const target = await invoke<string | null>("find_window_at_position", {
+          x: screenX,
+          y: screenY,
+          exclude: windowLabel,
+        });
+        const tabSnapshot = target ? captureTabState(windowLabel, tabId) : null;
+        if (target && tabSnapshot) {
+          await invoke<string>("transfer_tab", {
+            sourceLabel: windowLabel,
+            targetLabel: target,
+            tab: tabSnapshot,
+          });
+          return;
+        }
+
         await invoke<string>("detach_tab_to_new_window", {
           data: {
             tabId: tab.id,
@@ -1,10 +1,12 @@
-import { useCallback, useEffect, useRef } from "react";
+import { useCallback, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
 import { useTabStore, type Tab } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { closeTabWithDirtyCheck, closeTabsWithDirtyCheck } from "@/hooks/useTabOperations";
 import { saveToPath } from "@/utils/saveToPath";
-import { isImeKeyEvent } from "@/utils/imeGuard";
-import "./TabContextMenu.css";
+import { showNativeContextMenu } from "@/utils/nativeContextMenu";
+import { decryptDocument, encryptDocument, isEncryptedPath } from "@/utils/encryptedDocuments";
 
 export interface ContextMenuPosition {
   x: number;
@@ -19,76 +21,26 @@ interface TabContextMenuProps {
 }
 
 interface MenuItem {
+  id: string;
   label: string;
   action: () => void;
   disabled?: boolean;
   separator?: boolean;
 }
 
+/**
+ * Right-click menu for a tab, shown as a native menu at `position`.
+ * Renders nothing; `onClose` runs once the menu is picked from or dismissed.
+ */
 export function TabContextMenu({
   tab,
   position,
   windowLabel,
   onClose,
 }: TabContextMenuProps) {
-  const menuRef = useRef<HTMLDivElement>(null);
   const tabs = useTabStore((state) => state.tabs[windowLabel] ?? []);
   const doc = useDocumentStore((state) => state.documents[tab.id]);
 
-  // Close on click outside
-  useEffect(() => {
-    const handleClickOutside = (e: MouseEvent) => {
-      if (menuRef.current && !menuRef.current.contains(e.target as Node)) {
-        onClose();
-      }
-    };
-
-    const handleEscape = (e: KeyboardEvent) => {
-      if (isImeKeyEvent(e)) return;
-      if (e.key === "Escape") {
-        onClose();
-      }
-    };
-
-    document.addEventListener("mousedown", handleClickOutside, true);
-    document.addEventListener("keydown", handleEscape);
-
-    return () => {
-      document.removeEventListener("mousedown", handleClickOutside, true);
-      document.removeEventListener("keydown", handleEscape);
-    };
-  }, [onClose]);
-
-  // Position adjustment to keep menu in viewport
-  useEffect(() => {
-    if (!menuRef.current) return;
-
-    const menu = menuRef.current;
-    const rect = menu.getBoundingClientRect();
-    const viewportWidth = window.innerWidth;
-    const viewportHeight = window.innerHeight;
-
-    let adjustedX = position.x;
-    let adjustedY = position.y;
-
-    // Adjust horizontal position
-    if (position.x + rect.width > viewportWidth - 10) {
-      adjustedX = viewportWidth - rect.width - 10;
-    }
-
-    // Adjust vertical position - show above if would overflow bottom
-    if (position.y + rect.height > viewportHeight - 10) {
-      adjustedY = position.y - rect.height - 5;
-      // If still overflows top, pin to top with margin
-      if (adjustedY < 10) {
-        adjustedY = 10;
-      }
-    }
-
-    menu.style.left = `${adjustedX}px`;
-    menu.style.top = `${adjustedY}px`;
-  }, [position]);
-
   const handleClose = useCallback(async () => {
     await closeTabWithDirtyCheck(windowLabel, tab.id);
     onClose();
@@ -131,6 +83,37 @@ export function TabContextMenu({
     onClose();
   }, [tab.id, doc?.filePath, doc?.content, onClose]);
 
+  // Open the file in a new window beside this one
+  const handleOpenToSide = useCallback(async () => {
+    if (!doc?.filePath) return;
+    onClose();
+    try {
+      await invoke("create_split_window", {
+        sourceLabel: windowLabel,
+        filePath: doc.filePath,
+      });
+    } catch (err) {
+      console.error("[TabContextMenu] Open to the side failed:", err);
+    }
+  }, [windowLabel, doc?.filePath, onClose]);
+
+  // Replace the file with an encrypted copy, or back; the tab follows it
+  const handleToggleEncryption = useCallback(async () => {
+    onClose();
+    if (!doc?.filePath) return;
+    const oldPath = doc.filePath;
+    try {
+      const newPath = isEncryptedPath(oldPath)
+        ? await decryptDocument(oldPath)
+        : await encryptDocument(oldPath);
+      if (!newPath) return;
+      useDocumentStore.getState().setFilePath(tab.id, newPath);
+      useTabStore.getState().updateTabPath(tab.id, newPath);
+    } catch (err) {
+      toast.error(`Failed to change encryption: ${String(err)}`);
+    }
+  }, [tab.id, doc?.filePath, onClose]);
+
   // Copy file path to clipboard
   const handleCopyPath = useCallback(async () => {
     if (!doc?.filePath) return;
@@ -145,69 +128,90 @@ export function TabContextMenu({
   // Build menu items dynamically based on state
   const menuItems: MenuItem[] = [
     {
+      id: "pin",
       label: tab.isPinned ? "Unpin" : "Pin",
       action: handlePin,
     },
     {
+      id: "copy-path",
       label: "Copy Path",
       action: handleCopyPath,
       disabled: !doc?.filePath,
     },
+    {
+      id: "open-to-side",
+      label: "Open to the Side",
+      action: handleOpenToSide,
+      disabled: !doc?.filePath,
+    },
+    {
+      id: "toggle-encryption",
+      label: doc?.filePath && isEncryptedPath(doc.filePath) ? "Remove Encryption…" : "Encrypt…",
+      action: handleToggleEncryption,
+      // Works on the file on disk, so unsaved edits must be saved first
+      disabled: !doc?.filePath || doc.isDirty || doc.isMissing,
+    },
     // Show "Restore to Disk" when file is missing
     ...(doc?.isMissing && doc.filePath
       ? [
           {
+            id: "restore",
             label: "Restore to Disk",
             action: handleRestoreToDisk,
           },
         ]
       : []),
-    { label: "", action: () => {}, separator: true },
+    { id: "separator", label: "", action: () => {}, separator: true },
     {
+      id: "close",
       label: "Close",
       action: handleClose,
       disabled: tab.isPinned,
     },
     {
+      id: "close-others",
       label: "Close Others",
       action: handleCloseOthers,
       disabled: !hasOtherTabs,
     },
     {
+      id: "close-right",
       label: "Close to the Right",
       action: handleCloseToRight,
       disabled: !hasTabsToRight,
     },
     {
+      id: "close-all",
       label: "Close All",
       action: handleCloseAll,
       disabled: tabs.every((t) => t.isPinned),
     },
   ];
 
-  return (
-    <div
-      ref={menuRef}
-      className="tab-context-menu"
-      style={{ left: position.x, top: position.y }}
-    >
-      {menuItems.map((item, index) =>
-        item.separator ? (
-          <div key={`separator-${index}`} className="tab-context-menu-separator" />
-        ) : (
-          <button
-            key={item.label}
-            type="button"
-            className="tab-context-menu-item"
-            onClick={item.action}
-            disabled={item.disabled}
-          >
-            {item.label}
-          </button>
-        )
-      )}
-    </div>
-  );
+  useEffect(() => {
+    let cancelled = false;
+    showNativeContextMenu(
+      menuItems.map((item) =>
+        item.separator
+          ? { separator: true }
+          : { id: item.id, label: item.label, enabled: !item.disabled }
+      ),
+      position
+    ).then((choice) => {
+      // A newer menu took over; it will close the component itself
+      if (cancelled) return;
+      const item = menuItems.find((i) => i.id === choice);
+      if (item) item.action();
+      else onClose();
+    });
+    return () => {
+      cancelled = true;
+    };
+    // Show once per right-click; menuItems reflects state at that moment
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [tab.id, position]);
+
+  return null;
 }
 
 export default TabContextMenu;
@@ -2,9 +2,8 @@ import { describe, it, expect, vi, beforeEach } from "vitest";
 import { render, screen, fireEvent } from "@testing-library/react";
 import { TerminalContextMenu } from "./TerminalContextMenu";
 import type { Terminal } from "@xterm/xterm";
-import type { IPty } from "tauri-pty";
+import type { TerminalPty } from "./backendPty";
 
-vi.mock("tauri-pty", () => ({ spawn: vi.fn() }));
 vi.mock("@/utils/imeGuard", () => ({
   isImeKeyEvent: vi.fn(() => false),
 }));
@@ -23,12 +22,12 @@ function makeTerm(overrides: Partial<Terminal> = {}): Terminal {
 
 describe("TerminalContextMenu", () => {
   let onClose: () => void;
-  let ptyRef: React.RefObject<IPty | null>;
+  let ptyRef: React.RefObject<TerminalPty | null>;
 
   beforeEach(() => {
     vi.clearAllMocks();
     onClose = vi.fn<() => void>();
-    ptyRef = { current: { write: vi.fn() } as unknown as IPty };
+    ptyRef = { current: { write: vi.fn() } as unknown as TerminalPty };
   });
 
   it("renders all menu items", () => {
@@ -2,7 +2,7 @@ import { useEffect, useRef, useCallback } from "react";
 import { Copy, ClipboardPaste, Square, Trash2 } from "lucide-react";
 import { readText, writeText } from "@tauri-apps/plugin-clipboard-manager";
 import type { Terminal } from "@xterm/xterm";
-import type { IPty } from "tauri-pty";
+import type { TerminalPty } from "./backendPty";
 import { isImeKeyEvent } from "@/utils/imeGuard";
 import "../Sidebar/FileExplorer/ContextMenu.css";
 
@@ -16,7 +16,7 @@ interface MenuItem {
 interface TerminalContextMenuProps {
   position: { x: number; y: number };
   term: Terminal;
-  ptyRef: React.RefObject<IPty | null>;
+  ptyRef: React.RefObject<TerminalPty | null>;
   onClose: () => void;
 }
 
@@ -0,0 +1,101 @@
+/**
+ * Backend PTY
+ *
+ * Purpose: Handle to a shell running in a PTY owned by the Rust side
+ * (terminal.rs). The backend reader keeps the session's scrollback and
+ * streams output here as `pty:data`; the exit arrives as `pty:exit`.
+ *
+ * @module components/Terminal/backendPty
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/** A running terminal shell */
+export interface TerminalPty {
+  readonly ptyId: string;
+  readonly pid: number | null;
+  write(data: string): void;
+  resize(cols: number, rows: number): void;
+  /** Kill the shell. No exit is reported for a killed PTY. */
+  kill(): void;
+}
+
+/** What to run (mirrors PtySpawnOptions in terminal.rs) */
+export interface PtySpawnOptions {
+  shellPath: string;
+  shellKind: string;
+  args: string[];
+  cwd?: string;
+  env: Record<string, string>;
+  cols: number;
+  rows: number;
+}
+
+/** Payload of `pty:exit` */
+export interface PtyExitEvent {
+  sessionId: string;
+  ptyId?: string;
+  exitCode: number;
+  /** Terminating signal name (e.g. "SIGINT"), if signalled */
+  signal: string | null;
+}
+
+export interface PtyHandlers {
+  onData: (data: string) => void;
+  onExit: (exit: PtyExitEvent) => void;
+}
+
+/**
+ * Start a shell for `sessionId`. Listeners are attached before the shell
+ * starts, so no early output (the first prompt) is missed.
+ */
+export async function spawnBackendPty(
+  sessionId: string,
+  options: PtySpawnOptions,
+  handlers: PtyHandlers
+): Promise<TerminalPty> {
+  const ptyId = crypto.randomUUID();
+  const unlisteners: UnlistenFn[] = [];
+  const stop = () => unlisteners.splice(0).forEach((unlisten) => unlisten());
+
+  unlisteners.push(
+    await listen<{ ptyId: string; data: string }>("pty:data", (event) => {
+      if (event.payload.ptyId === ptyId) handlers.onData(event.payload.data);
+    }),
+    await listen<PtyExitEvent>("pty:exit", (event) => {
+      if (event.payload.ptyId !== ptyId) return;
+      stop();
+      handlers.onExit(event.payload);
+    })
+  );
+
+  let pid: number | null;
+  try {
+    ({ pid } = await invoke<{ ptyId: string; pid: number | null }>("pty_spawn", {
+      sessionId,
+      ptyId,
+      options,
+    }));
+  } catch (error) {
+    stop();
+    throw error;
+  }
+
+  return {
+    ptyId,
+    pid,
+    write(data) {
+      invoke("pty_write", { ptyId, data }).catch(() => {
+        /* the shell has exited */
+      });
+    },
+    resize(cols, rows) {
+      invoke("pty_resize", { ptyId, cols, rows }).catch(() => {});
+    },
+    kill() {
+      stop();
+      invoke("pty_kill", { ptyId }).catch(() => {});
+    },
+  };
+}
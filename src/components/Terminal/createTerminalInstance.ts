@@ -52,7 +52,7 @@ export interface TerminalInstanceSettings {
 interface CreateOptions {
   parentEl: HTMLElement;
   settings: TerminalInstanceSettings;
-  ptyRef: React.RefObject<import("tauri-pty").IPty | null>;
+  ptyRef: React.RefObject<import("./backendPty").TerminalPty | null>;
   onSearch: () => void;
 }
 
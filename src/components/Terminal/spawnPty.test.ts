@@ -18,16 +18,6 @@ vi.mock("@/utils/workspaceStorage", () => ({
   getCurrentWindowLabel: vi.fn(() => "main"),
 }));
 
-vi.mock("tauri-pty", () => ({
-  spawn: vi.fn(() => ({
-    onData: vi.fn(),
-    onExit: vi.fn(),
-    write: vi.fn(),
-    resize: vi.fn(),
-    kill: vi.fn(),
-  })),
-}));
-
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
@@ -40,11 +30,27 @@ describe("resolveTerminalCwd", () => {
   it("returns workspace root when available", () => {
     vi.mocked(useWorkspaceStore.getState).mockReturnValue({
       rootPath: "/workspace/root",
-    } as ReturnType<typeof useWorkspaceStore.getState>);
+      isWorkspaceTrusted: () => true,
+    } as unknown as ReturnType<typeof useWorkspaceStore.getState>);
 
     expect(resolveTerminalCwd()).toBe("/workspace/root");
   });
 
+  it("falls back to the shell default for an untrusted workspace", () => {
+    vi.mocked(useWorkspaceStore.getState).mockReturnValue({
+      rootPath: "/workspace/root",
+      isWorkspaceTrusted: () => false,
+    } as unknown as ReturnType<typeof useWorkspaceStore.getState>);
+    vi.mocked(useTabStore.getState).mockReturnValue({
+      activeTabId: { main: "tab1" },
+    } as unknown as ReturnType<typeof useTabStore.getState>);
+    vi.mocked(useDocumentStore.getState).mockReturnValue({
+      getDocument: () => ({ filePath: "/workspace/root/notes/file.md" }),
+    } as unknown as ReturnType<typeof useDocumentStore.getState>);
+
+    expect(resolveTerminalCwd()).toBeUndefined();
+  });
+
   it("returns active file parent dir when no workspace", () => {
     vi.mocked(useWorkspaceStore.getState).mockReturnValue({
       rootPath: null,
@@ -59,6 +65,20 @@ describe("resolveTerminalCwd", () => {
     expect(resolveTerminalCwd()).toBe("/Users/test/docs");
   });
 
+  it("handles Windows backslash paths", () => {
+    vi.mocked(useWorkspaceStore.getState).mockReturnValue({
+      rootPath: null,
+    } as ReturnType<typeof useWorkspaceStore.getState>);
+    vi.mocked(useTabStore.getState).mockReturnValue({
+      activeTabId: { main: "tab1" },
+    } as unknown as ReturnType<typeof useTabStore.getState>);
+    vi.mocked(useDocumentStore.getState).mockReturnValue({
+      getDocument: () => ({ filePath: "C:\\Users\\test\\docs\\file.md" }),
+    } as unknown as ReturnType<typeof useDocumentStore.getState>);
+
+    expect(resolveTerminalCwd()).toBe("C:\\Users\\test\\docs");
+  });
+
   it("returns undefined when no workspace and no active file", () => {
     vi.mocked(useWorkspaceStore.getState).mockReturnValue({
       rootPath: null,
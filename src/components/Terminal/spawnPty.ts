@@ -1,27 +1,32 @@
-import { spawn, type IPty } from "tauri-pty";
 import { invoke } from "@tauri-apps/api/core";
 import type { Terminal } from "@xterm/xterm";
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
+import { useSettingsStore } from "@/stores/settingsStore";
 import { getCurrentWindowLabel } from "@/utils/workspaceStorage";
+import { spawnBackendPty, type TerminalPty } from "./backendPty";
 
 /**
  * Resolve terminal working directory:
- * 1. Workspace root (if open)
+ * 1. Workspace root (if open and trusted; an untrusted workspace gets $HOME)
  * 2. Active file's parent directory (if saved)
  * 3. undefined — lets the shell start in its default ($HOME)
  */
 export function resolveTerminalCwd(): string | undefined {
-  const workspaceRoot = useWorkspaceStore.getState().rootPath;
-  if (workspaceRoot) return workspaceRoot;
+  const { rootPath: workspaceRoot, isWorkspaceTrusted } = useWorkspaceStore.getState();
+  if (workspaceRoot) return isWorkspaceTrusted() ? workspaceRoot : undefined;
 
   const windowLabel = getCurrentWindowLabel();
   const activeTabId = useTabStore.getState().activeTabId[windowLabel];
   if (activeTabId) {
     const doc = useDocumentStore.getState().getDocument(activeTabId);
     if (doc?.filePath) {
-      const lastSlash = doc.filePath.lastIndexOf("/");
+      // Windows paths use backslashes
+      const lastSlash = Math.max(
+        doc.filePath.lastIndexOf("/"),
+        doc.filePath.lastIndexOf("\\")
+      );
       if (lastSlash > 0) return doc.filePath.substring(0, lastSlash);
     }
   }
@@ -29,49 +34,109 @@ export function resolveTerminalCwd(): string | undefined {
   return undefined;
 }
 
+/** Shell resolved by the backend (see terminal.rs) */
+export interface ShellInfo {
+  name: string;
+  path: string;
+  kind: "pwsh" | "power-shell" | "cmd" | "git-bash" | "posix";
+  args: string[];
+}
+
+/**
+ * Replay a session's backend scrollback into a fresh xterm instance
+ * (after a webview reload or hot-exit restore). Returns true if anything
+ * was written.
+ */
+export async function restoreScrollback(
+  sessionId: string,
+  term: Terminal
+): Promise<boolean> {
+  try {
+    const data = await invoke<string>("pty_get_scrollback", { sessionId });
+    if (!data) return false;
+    term.write(data);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Resolve the shell to spawn: the requested one if it is still installed,
+ * otherwise the backend default.
+ */
+async function resolveShell(shellPath: string | undefined): Promise<ShellInfo> {
+  if (shellPath) {
+    try {
+      const shells = await invoke<ShellInfo[]>("list_available_shells");
+      const match = shells.find((s) => s.path === shellPath);
+      if (match) return match;
+    } catch {
+      /* fall back to the default shell */
+    }
+  }
+  return invoke<ShellInfo>("get_default_shell_info");
+}
+
 export interface SpawnOptions {
   term: Terminal;
-  onExit: (exitCode: number) => void;
+  /** Session whose scrollback and exit the backend records */
+  sessionId: string;
+  /** Working directory override (hot-exit restore); defaults to resolveTerminalCwd() */
+  cwd?: string;
+  /** Shell path to reuse (hot-exit restore); ignored if no longer available */
+  shellPath?: string;
+  /** Called with the exit code and, if signalled, the signal name (e.g. "SIGINT") */
+  onExit: (exitCode: number, signal: string | null) => void;
   disposed: () => boolean;
 }
 
 /**
  * Spawn a PTY process connected to the terminal.
- * Reads shell from Tauri backend, resolves cwd, wires data streams.
+ * Reads shell and environment from Tauri backend, resolves cwd, wires data
+ * streams and types the configured startup command.
  */
-export async function spawnPty(options: SpawnOptions): Promise<IPty> {
-  const { term, onExit, disposed } = options;
+export async function spawnPty(options: SpawnOptions): Promise<TerminalPty> {
+  const { term, sessionId, onExit, disposed } = options;
 
-  const shell = await invoke<string>("get_default_shell");
+  const shell = await resolveShell(options.shellPath);
   if (disposed()) throw new Error("disposed before spawn");
 
-  const cwd = resolveTerminalCwd();
+  const cwd = options.cwd ?? resolveTerminalCwd();
   const workspaceRoot = useWorkspaceStore.getState().rootPath;
+  const { env: extraEnv, startupCommand } = useSettingsStore.getState().terminal;
 
-  const env: Record<string, string> = {
-    TERM_PROGRAM: "vmark",
-    EDITOR: "vmark",
-  };
-  if (workspaceRoot) {
-    env.VMARK_WORKSPACE = workspaceRoot;
-  }
-
-  const pty = spawn(shell, [], {
-    cols: term.cols || 80,
-    rows: term.rows || 24,
-    cwd,
-    env,
+  // Backend adds TERM, the login shell PATH and VMARK_WORKSPACE
+  const env = await invoke<Record<string, string>>("get_terminal_env", {
+    workspaceRoot,
+    extra: extraEnv ?? {},
   });
+  if (disposed()) throw new Error("disposed before spawn");
 
-  // PTY → xterm
-  pty.onData((data) => {
-    if (!disposed()) term.write(data);
-  });
+  // Backend keeps the scrollback and decodes the exit status
+  const pty = await spawnBackendPty(
+    sessionId,
+    {
+      shellPath: shell.path,
+      shellKind: shell.kind,
+      args: shell.args,
+      cwd,
+      env,
+      cols: term.cols || 80,
+      rows: term.rows || 24,
+    },
+    {
+      onData: (data) => {
+        if (!disposed()) term.write(data);
+      },
+      onExit: (exit) => onExit(exit.exitCode, exit.signal),
+    }
+  );
 
-  // PTY exit
-  pty.onExit(({ exitCode }) => {
-    onExit(exitCode);
-  });
+  // Input is buffered until the shell reads it, so no need to wait for a prompt
+  if (startupCommand?.trim()) {
+    pty.write(`${startupCommand.trim()}\r`);
+  }
 
   return pty;
 }
@@ -2,9 +2,8 @@ import { describe, it, expect, vi, beforeEach } from "vitest";
 import { createTerminalKeyHandler, type KeyHandlerCallbacks } from "./terminalKeyHandler";
 import { readText, writeText } from "@tauri-apps/plugin-clipboard-manager";
 import type { Terminal } from "@xterm/xterm";
-import type { IPty } from "tauri-pty";
+import type { TerminalPty } from "./backendPty";
 
-vi.mock("tauri-pty", () => ({ spawn: vi.fn() }));
 
 function makeTerm(overrides: Partial<Terminal> = {}): Terminal {
   return {
@@ -22,14 +21,14 @@ function makeEvent(key: string, meta = true): KeyboardEvent {
 
 describe("createTerminalKeyHandler", () => {
   let callbacks: KeyHandlerCallbacks;
-  let ptyRef: React.RefObject<IPty | null>;
+  let ptyRef: React.RefObject<TerminalPty | null>;
   let mockPty: { write: ReturnType<typeof vi.fn> };
 
   beforeEach(() => {
     vi.clearAllMocks();
     callbacks = { onSearch: vi.fn() };
     mockPty = { write: vi.fn() };
-    ptyRef = { current: mockPty as unknown as IPty };
+    ptyRef = { current: mockPty as unknown as TerminalPty };
   });
 
   it("copies selection on Cmd+C when selection exists", () => {
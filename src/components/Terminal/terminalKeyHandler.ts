@@ -1,4 +1,4 @@
-import type { IPty } from "tauri-pty";
+import type { TerminalPty } from "./backendPty";
 import { readText, writeText } from "@tauri-apps/plugin-clipboard-manager";
 import type { Terminal } from "@xterm/xterm";
 import { useTerminalSessionStore } from "@/stores/terminalSessionStore";
@@ -15,7 +15,7 @@ export interface KeyHandlerCallbacks {
  */
 export function createTerminalKeyHandler(
   term: Terminal,
-  ptyRef: React.RefObject<IPty | null>,
+  ptyRef: React.RefObject<TerminalPty | null>,
   callbacks: KeyHandlerCallbacks,
 ): (event: KeyboardEvent) => boolean {
   return (event: KeyboardEvent): boolean => {
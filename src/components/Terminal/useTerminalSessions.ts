@@ -1,19 +1,20 @@
 import { useRef, useEffect, useCallback } from "react";
-import type { IPty } from "tauri-pty";
+import { invoke } from "@tauri-apps/api/core";
+import type { TerminalPty } from "./backendPty";
 import { useSettingsStore, themes } from "@/stores/settingsStore";
 import { useTerminalSessionStore } from "@/stores/terminalSessionStore";
 import {
   createTerminalInstance,
   type TerminalInstance,
 } from "./createTerminalInstance";
-import { spawnPty, resolveTerminalCwd } from "./spawnPty";
+import { spawnPty, resolveTerminalCwd, restoreScrollback } from "./spawnPty";
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import type { SearchAddon } from "@xterm/addon-search";
 
 interface SessionEntry {
   instance: TerminalInstance;
-  pty: IPty | null;
-  ptyRefForKeys: React.RefObject<IPty | null>;
+  pty: TerminalPty | null;
+  ptyRefForKeys: React.RefObject<TerminalPty | null>;
   spawnedCwd: string | undefined;
   shellStarted: boolean;
   shellExited: boolean;
@@ -83,17 +84,25 @@ export function useTerminalSessions(
     if (!entry || entry.disposed) return;
 
     entry.shellExited = false;
-    const cwd = resolveTerminalCwd();
+    // Sessions recreated after hot exit reopen in their saved cwd/shell once
+    const store = useTerminalSessionStore.getState();
+    const restore = store.sessions.find((s) => s.id === sessionId)?.restore;
+    if (restore) store.clearRestore(sessionId);
+    const cwd = restore?.cwd ?? resolveTerminalCwd();
 
     try {
       const pty = await spawnPty({
         term: entry.instance.term,
-        onExit: (exitCode) => {
+        sessionId,
+        cwd,
+        shellPath: restore?.shellPath,
+        onExit: (exitCode, signal) => {
           const e = sessionsRef.current.get(sessionId);
           if (e && !e.disposed) {
-            e.instance.term.write(
-              `\r\n[Process exited with code ${exitCode}]\r\n`,
-            );
+            const reason = signal
+              ? `terminated by ${signal} (code ${exitCode})`
+              : `exited with code ${exitCode}`;
+            e.instance.term.write(`\r\n[Process ${reason}]\r\n`);
             e.instance.term.write("Press any key to restart...\r\n");
             e.pty = null;
             e.shellExited = true;
@@ -115,8 +124,10 @@ export function useTerminalSessions(
       currentEntry.spawnedCwd = cwd;
 
       // If workspace changed while spawning, cd to the current root
-      const currentRoot = useWorkspaceStore.getState().rootPath;
-      if (currentRoot && currentRoot !== cwd) {
+      // (restored sessions keep their saved directory)
+      const workspace = useWorkspaceStore.getState();
+      const currentRoot = workspace.rootPath;
+      if (!restore && currentRoot && currentRoot !== cwd && workspace.isWorkspaceTrusted()) {
         const escaped = currentRoot.replace(/'/g, "'\\''");
         pty.write(`\x15cd '${escaped}'\n`);
         currentEntry.spawnedCwd = currentRoot;
@@ -169,7 +180,7 @@ export function useTerminalSessions(
       const lineHeight = termSettings?.lineHeight ?? 1.2;
 
       // Create a shared ptyRef that we'll update as the pty changes
-      const ptyRefForKeys: React.RefObject<IPty | null> = { current: null };
+      const ptyRefForKeys: React.RefObject<TerminalPty | null> = { current: null };
 
       const instance = createTerminalInstance({
         parentEl: parent,
@@ -189,6 +200,9 @@ export function useTerminalSessions(
       };
       sessionsRef.current.set(sessionId, entry);
 
+      // Re-render earlier output if this session survived a webview reload
+      void restoreScrollback(sessionId, instance.term);
+
       // xterm → PTY (or restart on key press after exit)
       instance.term.onData((data) => {
         const e = sessionsRef.current.get(sessionId);
@@ -221,11 +235,19 @@ export function useTerminalSessions(
     const entry = sessionsRef.current.get(sessionId);
     if (!entry) return;
     entry.disposed = true;
-    if (entry.pty) {
-      try { entry.pty.kill(); } catch { /* ignore */ }
-    }
+    const pty = entry.pty;
     entry.instance.dispose();
     sessionsRef.current.delete(sessionId);
+    // Terminate the shell's whole process group (SIGHUP/SIGTERM, then SIGKILL)
+    // so background jobs aren't orphaned, then close the PTY handle.
+    invoke("pty_terminate", { sessionId })
+      .catch(() => {})
+      .finally(() => {
+        if (pty) {
+          try { pty.kill(); } catch { /* ignore */ }
+        }
+        invoke("pty_release_session", { sessionId }).catch(() => {});
+      });
   }, []);
 
   /** Show active session container, hide others. */
@@ -351,16 +373,18 @@ export function useTerminalSessions(
     });
   }, []);
 
-  // cd running sessions when workspace root changes
+  // cd running sessions when workspace root changes or becomes trusted
   useEffect(() => {
     let prevRoot = useWorkspaceStore.getState().rootPath;
+    let prevTrusted = useWorkspaceStore.getState().isWorkspaceTrusted();
     return useWorkspaceStore.subscribe((state) => {
       const newRoot = state.rootPath;
-      if (!newRoot || newRoot === prevRoot) {
-        prevRoot = newRoot;
-        return;
-      }
+      const trusted = state.isWorkspaceTrusted();
+      const changed = newRoot !== prevRoot || trusted !== prevTrusted;
       prevRoot = newRoot;
+      prevTrusted = trusted;
+      // Untrusted workspaces don't pull the terminal into their folder
+      if (!newRoot || !changed || !trusted) return;
 
       const escaped = newRoot.replace(/'/g, "'\\''");
       for (const [, entry] of sessionsRef.current) {
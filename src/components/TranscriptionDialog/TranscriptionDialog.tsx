@@ -0,0 +1,284 @@
+/**
+ * Transcription Dialog
+ *
+ * Transcribes a voice memo with whisper.cpp and inserts the text at the
+ * cursor. Models are downloaded (or removed) from here. Opened from
+ * Insert → Transcribe Audio…; the work is done by transcription.rs.
+ */
+
+import { useCallback, useEffect, useRef, useState } from "react";
+import { createPortal } from "react-dom";
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { useTranscriptionDialogStore } from "@/stores/transcriptionDialogStore";
+import { useActiveEditorStore } from "@/stores/activeEditorStore";
+import { useEditorStore } from "@/stores/editorStore";
+import { getFileName } from "@/utils/paths";
+import {
+  WHISPER_MODELS,
+  cancelTranscription,
+  detectWhisper,
+  downloadWhisperModel,
+  formatTranscript,
+  insertTranscript,
+  listWhisperModels,
+  onTranscriptionProgress,
+  onWhisperDownloadProgress,
+  removeWhisperModel,
+  transcribeAudio,
+  type Transcript,
+  type WhisperInfo,
+} from "@/utils/transcription";
+import "./transcription-dialog.css";
+
+const AUDIO_EXTENSIONS = ["wav", "mp3", "m4a", "flac", "ogg", "aac", "webm"];
+const DEFAULT_MODEL = "base";
+
+/** Insert into whichever editor is showing. Resolves to whether anything was inserted. */
+function insertIntoEditor(transcript: Transcript, timestamps: boolean): boolean {
+  const { activeSourceView, activeWysiwygEditor } = useActiveEditorStore.getState();
+  if (useEditorStore.getState().sourceMode) {
+    const markdown = formatTranscript(transcript, timestamps);
+    if (!activeSourceView || !markdown) return false;
+    const { from, to } = activeSourceView.state.selection.main;
+    activeSourceView.dispatch({
+      changes: { from, to, insert: markdown },
+      selection: { anchor: from + markdown.length },
+    });
+    activeSourceView.focus();
+    return true;
+  }
+  return activeWysiwygEditor ? insertTranscript(activeWysiwygEditor, transcript, timestamps) : false;
+}
+
+export function TranscriptionDialog() {
+  const isOpen = useTranscriptionDialogStore((s) => s.isOpen);
+  const close = useTranscriptionDialogStore((s) => s.close);
+
+  const [info, setInfo] = useState<WhisperInfo | null>(null);
+  const [models, setModels] = useState<string[]>([]);
+  const [audioPath, setAudioPath] = useState("");
+  const [model, setModel] = useState(DEFAULT_MODEL);
+  const [language, setLanguage] = useState("");
+  const [timestamps, setTimestamps] = useState(false);
+  const [busy, setBusy] = useState<"download" | "transcribe" | null>(null);
+  const [status, setStatus] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const requestId = useRef<string | null>(null);
+
+  const refreshModels = useCallback(async () => {
+    try {
+      setModels(await listWhisperModels());
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  // Look for whisper.cpp each time the dialog opens
+  useEffect(() => {
+    if (!isOpen) return;
+    let cancelled = false;
+    setInfo(null);
+    setError(null);
+    setStatus("");
+    detectWhisper()
+      .then((detected) => {
+        if (cancelled) return;
+        setInfo(detected);
+        setModels(detected.models);
+        if (detected.models.length > 0 && !detected.models.includes(DEFAULT_MODEL)) {
+          setModel(detected.models[0]);
+        }
+      })
+      .catch((err) => !cancelled && setError(String(err)));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen]);
+
+  useEffect(() => {
+    if (!busy) return;
+    let cancelled = false;
+    const unlisteners: (() => void)[] = [];
+    const track = (promise: Promise<() => void>) =>
+      promise.then((fn) => {
+        if (cancelled) fn();
+        else unlisteners.push(fn);
+      });
+    void track(
+      onTranscriptionProgress((progress) => {
+        if (progress.requestId === requestId.current) setStatus(`Transcribing — ${progress.percent}%`);
+      })
+    );
+    void track(
+      onWhisperDownloadProgress((progress) => {
+        if (progress.model !== model || !progress.total) return;
+        setStatus(`Downloading ${model} — ${Math.round((progress.downloaded / progress.total) * 100)}%`);
+      })
+    );
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, [busy, model]);
+
+  const handleClose = useCallback(() => {
+    if (requestId.current) void cancelTranscription(requestId.current);
+    close();
+  }, [close]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") handleClose();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, handleClose]);
+
+  if (!isOpen) return null;
+
+  const hasModel = models.includes(model);
+
+  const chooseAudio = async () => {
+    const selected = await open({ filters: [{ name: "Audio", extensions: AUDIO_EXTENSIONS }] });
+    if (typeof selected === "string") setAudioPath(selected);
+  };
+
+  const handleDownload = async () => {
+    setBusy("download");
+    setError(null);
+    setStatus(`Downloading ${model}…`);
+    try {
+      await downloadWhisperModel(model);
+      await refreshModels();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(null);
+      setStatus("");
+    }
+  };
+
+  const handleRemove = async () => {
+    if (!window.confirm(`Remove the ${model} model?`)) return;
+    try {
+      await removeWhisperModel(model);
+      await refreshModels();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleTranscribe = async () => {
+    const id = crypto.randomUUID();
+    requestId.current = id;
+    setBusy("transcribe");
+    setError(null);
+    setStatus("Transcribing…");
+    try {
+      const transcript = await transcribeAudio(id, audioPath, model, language.trim() || "auto");
+      if (requestId.current !== id) return;
+      if (insertIntoEditor(transcript, timestamps)) {
+        toast.success(`Transcribed ${getFileName(audioPath)}`);
+        close();
+      } else {
+        setError("Nothing was recognized in the recording.");
+      }
+    } catch (err) {
+      if (requestId.current === id) setError(String(err));
+    } finally {
+      if (requestId.current === id) requestId.current = null;
+      setBusy(null);
+      setStatus("");
+    }
+  };
+
+  const handleCancel = () => {
+    if (requestId.current) {
+      void cancelTranscription(requestId.current);
+      requestId.current = null;
+      return;
+    }
+    close();
+  };
+
+  return createPortal(
+    <div className="transcription-dialog-backdrop" onMouseDown={(e) => e.target === e.currentTarget && handleClose()}>
+      <div className="transcription-dialog" role="dialog" aria-modal="true" aria-label="Transcribe Audio">
+        <div className="transcription-dialog-title">Transcribe Audio</div>
+
+        {info === null && !error ? (
+          <div className="transcription-dialog-status">Looking for whisper.cpp…</div>
+        ) : info && !info.available ? (
+          <div className="transcription-dialog-error">
+            whisper.cpp is not installed. Install it from https://github.com/ggml-org/whisper.cpp and try again.
+          </div>
+        ) : (
+          <>
+            <label className="transcription-dialog-field">
+              <span>Recording</span>
+              <input value={audioPath ? getFileName(audioPath) : ""} placeholder="No file chosen" readOnly title={audioPath} />
+              <button className="transcription-dialog-button" onClick={() => void chooseAudio()} disabled={busy !== null}>
+                Choose…
+              </button>
+            </label>
+            <label className="transcription-dialog-field">
+              <span>Model</span>
+              <select value={model} onChange={(e) => setModel(e.target.value)} disabled={busy !== null}>
+                {WHISPER_MODELS.map((name) => (
+                  <option key={name} value={name}>
+                    {models.includes(name) ? name : `${name} (not downloaded)`}
+                  </option>
+                ))}
+              </select>
+              {hasModel ? (
+                <button className="transcription-dialog-button" onClick={() => void handleRemove()} disabled={busy !== null}>
+                  Remove
+                </button>
+              ) : (
+                <button className="transcription-dialog-button" onClick={() => void handleDownload()} disabled={busy !== null}>
+                  Download
+                </button>
+              )}
+            </label>
+            <label className="transcription-dialog-field">
+              <span>Language</span>
+              <input
+                value={language}
+                onChange={(e) => setLanguage(e.target.value)}
+                placeholder="auto (or en, de, fr…)"
+                disabled={busy !== null}
+                spellCheck={false}
+              />
+            </label>
+            <label className="transcription-dialog-check">
+              <input type="checkbox" checked={timestamps} onChange={(e) => setTimestamps(e.target.checked)} />
+              Timestamp each segment
+            </label>
+            {info && !info.ffmpeg && (
+              <div className="transcription-dialog-hint">Install ffmpeg to transcribe m4a, aac and webm recordings.</div>
+            )}
+          </>
+        )}
+
+        {status && <div className="transcription-dialog-status">{status}</div>}
+        {error && <div className="transcription-dialog-error">{error}</div>}
+
+        <div className="transcription-dialog-actions">
+          <button className="transcription-dialog-button" onClick={handleCancel}>
+            Cancel
+          </button>
+          <button
+            className="transcription-dialog-button primary"
+            onClick={() => void handleTranscribe()}
+            disabled={!info?.available || !audioPath || !hasModel || busy !== null}
+          >
+            Transcribe
+          </button>
+        </div>
+      </div>
+    </div>,
+    document.body
+  );
+}
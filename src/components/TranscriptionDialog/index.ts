@@ -0,0 +1 @@
+export { TranscriptionDialog } from "./TranscriptionDialog";
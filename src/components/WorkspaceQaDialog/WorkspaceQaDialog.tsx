@@ -0,0 +1,213 @@
+/**
+ * Workspace Q&A Dialog
+ *
+ * Ask the active AI provider questions about the notes in the open
+ * workspace. Answers stream in with the notes they cite, which open on
+ * click; follow-up questions continue the conversation until it is
+ * cleared. Opened from File → Ask Workspace…; the retrieval and the
+ * conversation live in ai_workspace.rs.
+ */
+
+import { useCallback, useEffect, useRef, useState } from "react";
+import { createPortal } from "react-dom";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useAiProviderStore } from "@/stores/aiProviderStore";
+import { useWorkspaceQaDialogStore } from "@/stores/workspaceQaDialogStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import type { AiResponseChunk } from "@/types/aiGenies";
+import type { ContextSource } from "@/utils/notesContext";
+import { joinPath } from "@/utils/pathUtils";
+import {
+  askWorkspace,
+  citedSources,
+  clearWorkspaceSession,
+  getWorkspaceSession,
+  onCitations,
+  type WorkspaceTurn,
+} from "@/utils/workspaceQa";
+import "./workspace-qa-dialog.css";
+
+export function WorkspaceQaDialog() {
+  const isOpen = useWorkspaceQaDialogStore((s) => s.isOpen);
+  const close = useWorkspaceQaDialogStore((s) => s.close);
+  const rootPath = useWorkspaceStore((s) => s.rootPath);
+  const hasProvider = useAiProviderStore((s) => s.activeProvider !== null);
+
+  const sessionId = useRef(crypto.randomUUID());
+  const [turns, setTurns] = useState<WorkspaceTurn[]>([]);
+  const [pending, setPending] = useState<WorkspaceTurn | null>(null);
+  const [question, setQuestion] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  // A new workspace starts a new conversation
+  useEffect(() => {
+    const previous = sessionId.current;
+    sessionId.current = crypto.randomUUID();
+    setTurns([]);
+    void clearWorkspaceSession(previous);
+  }, [rootPath]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    let cancelled = false;
+    getWorkspaceSession(sessionId.current)
+      .then((restored) => !cancelled && setTurns(restored))
+      .catch((err) => console.warn("[WorkspaceQa] Failed to load session:", err));
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, rootPath]);
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ block: "end" });
+  }, [turns, pending]);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") close();
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [isOpen, close]);
+
+  const handleAsk = useCallback(async () => {
+    const text = question.trim();
+    if (!text || !rootPath || pending) return;
+    const requestId = crypto.randomUUID();
+    setQuestion("");
+    setError(null);
+    setPending({ question: text, answer: "", sources: [], cited: [] });
+
+    const unlistenAnswer = await listen<AiResponseChunk>("ai:response", (event) => {
+      const chunk = event.payload;
+      if (chunk.requestId !== requestId) return;
+      if (chunk.error) setError(chunk.error);
+      else if (chunk.chunk) setPending((p) => p && { ...p, answer: p.answer + chunk.chunk });
+    });
+    const unlistenCitations = await onCitations(requestId, (citations) => {
+      setPending((p) => p && { ...p, sources: citations.sources, cited: citations.cited });
+    });
+
+    try {
+      const turn = await askWorkspace(sessionId.current, requestId, text, rootPath);
+      if (turn) setTurns((current) => [...current, turn]);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      unlistenAnswer();
+      unlistenCitations();
+      setPending(null);
+    }
+  }, [question, rootPath, pending]);
+
+  const handleClear = () => {
+    const previous = sessionId.current;
+    sessionId.current = crypto.randomUUID();
+    setTurns([]);
+    setError(null);
+    void clearWorkspaceSession(previous);
+  };
+
+  if (!isOpen) return null;
+
+  const openNote = (source: ContextSource) => {
+    if (!rootPath) return;
+    close();
+    void getCurrentWebviewWindow().emit("open-file", { path: joinPath(rootPath, source.path) });
+  };
+
+  const renderTurn = (turn: WorkspaceTurn, key: string | number) => {
+    const sources = citedSources(turn);
+    return (
+      <div key={key} className="workspace-qa-turn">
+        <div className="workspace-qa-question">{turn.question}</div>
+        <div className="workspace-qa-answer">{turn.answer || "…"}</div>
+        {sources.length > 0 && (
+          <div className="workspace-qa-sources">
+            {sources.map((source) => (
+              <button
+                key={source.index}
+                className="workspace-qa-source"
+                onClick={() => openNote(source)}
+                title={`${source.path}:${source.line}`}
+              >
+                [{source.index}] {source.title}
+                {source.heading ? ` › ${source.heading}` : ""}
+              </button>
+            ))}
+          </div>
+        )}
+      </div>
+    );
+  };
+
+  const unavailable = !rootPath
+    ? "Open a folder to ask about its notes."
+    : !hasProvider
+      ? "Choose an AI provider in Settings → Integrations to ask about your notes."
+      : null;
+
+  return createPortal(
+    <div className="workspace-qa-backdrop" onMouseDown={(e) => e.target === e.currentTarget && close()}>
+      <div className="workspace-qa" role="dialog" aria-modal="true" aria-label="Ask Workspace">
+        <div className="workspace-qa-header">
+          <span className="workspace-qa-title">Ask Workspace</span>
+          <div className="workspace-qa-header-actions">
+            <button
+              className="workspace-qa-button"
+              onClick={handleClear}
+              disabled={turns.length === 0 || pending !== null}
+            >
+              New Conversation
+            </button>
+            <button className="workspace-qa-button" onClick={close}>
+              Close
+            </button>
+          </div>
+        </div>
+
+        {unavailable ? (
+          <div className="workspace-qa-message">{unavailable}</div>
+        ) : (
+          <>
+            <div className="workspace-qa-body">
+              {turns.length === 0 && !pending && (
+                <div className="workspace-qa-message">
+                  Answers come from your notes, with the notes they cite listed below each answer.
+                </div>
+              )}
+              {turns.map((turn, i) => renderTurn(turn, i))}
+              {pending && renderTurn(pending, "pending")}
+              {error && <div className="workspace-qa-error">{error}</div>}
+              <div ref={bottomRef} />
+            </div>
+            <div className="workspace-qa-input-row">
+              <input
+                className="workspace-qa-input"
+                value={question}
+                onChange={(e) => setQuestion(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === "Enter" && !e.nativeEvent.isComposing) void handleAsk();
+                }}
+                placeholder={turns.length > 0 ? "Ask a follow-up…" : "Ask a question about your notes…"}
+                disabled={pending !== null}
+                autoFocus
+              />
+              <button
+                className="workspace-qa-button primary"
+                onClick={() => void handleAsk()}
+                disabled={pending !== null || !question.trim()}
+              >
+                Ask
+              </button>
+            </div>
+          </>
+        )}
+      </div>
+    </div>,
+    document.body
+  );
+}
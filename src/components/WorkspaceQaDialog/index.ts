@@ -0,0 +1 @@
+export { WorkspaceQaDialog } from "./WorkspaceQaDialog";
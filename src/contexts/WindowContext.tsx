@@ -1,7 +1,6 @@
 import { createContext, useContext, useEffect, useState, useRef, type ReactNode } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { toast } from "sonner";
 import { useDocumentStore } from "../stores/documentStore";
 import { useTabStore } from "../stores/tabStore";
@@ -16,6 +15,7 @@ import {
 } from "../utils/workspaceStorage";
 import { resolveWorkspaceRootForExternalFile } from "../utils/openPolicy";
 import { isWithinRoot } from "../utils/paths";
+import { readDocumentFile } from "../utils/encryptedDocuments";
 
 /** Transfer data shape returned by claim_tab_transfer. */
 interface TabTransferData {
@@ -195,7 +195,7 @@ export function WindowProvider({ children }: WindowProviderProps) {
               for (const path of filePaths) {
                 const tabId = useTabStore.getState().createTab(label, path);
                 try {
-                  const content = await readTextFile(path);
+                  const content = await readDocumentFile(path);
                   useDocumentStore.getState().initDocument(tabId, content, path);
                   useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
                   useRecentFilesStore.getState().addFile(path);
@@ -213,7 +213,7 @@ export function WindowProvider({ children }: WindowProviderProps) {
               if (filePath) {
                 // Load file content from disk
                 try {
-                  const content = await readTextFile(filePath);
+                  const content = await readDocumentFile(filePath);
                   useDocumentStore.getState().initDocument(tabId, content, filePath);
                   useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
                   useRecentFilesStore.getState().addFile(filePath);
@@ -0,0 +1,39 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { applyCitations } from "./citations";
+
+vi.mock("sonner", () => ({ toast: { warning: vi.fn() } }));
+
+describe("applyCitations", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    useWorkspaceStore.setState({ rootPath: "/project" });
+  });
+
+  it("leaves documents without citations alone", async () => {
+    expect(await applyCitations("# Title\n\nmail@example.com")).toBe("# Title\n\nmail@example.com");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("skips citations outside a workspace", async () => {
+    useWorkspaceStore.setState({ rootPath: null });
+    expect(await applyCitations("See [@smith2020].")).toBe("See [@smith2020].");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("returns the rewritten markdown", async () => {
+    vi.mocked(invoke).mockResolvedValue({ content: "See (Smith, 2020).", missing: [] });
+    expect(await applyCitations("See [@smith2020].")).toBe("See (Smith, 2020).");
+    expect(invoke).toHaveBeenCalledWith("apply_citations", {
+      workspaceRoot: "/project",
+      content: "See [@smith2020].",
+      cslStyle: "apa",
+    });
+  });
+
+  it("falls back to the original markdown on error", async () => {
+    vi.mocked(invoke).mockRejectedValue("No .bib file found in workspace");
+    expect(await applyCitations("See [@smith2020].")).toBe("See [@smith2020].");
+  });
+});
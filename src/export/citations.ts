@@ -0,0 +1,47 @@
+/**
+ * Citations for export.
+ *
+ * Rewrites pandoc-style `[@key]` citations using the workspace `.bib` file
+ * and appends the bibliography before the document is rendered.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+
+export const DEFAULT_CSL_STYLE = "apa";
+
+interface CitationResult {
+  content: string;
+  missing: string[];
+}
+
+const CITATION_PATTERN = /\[[^\]]*@[^\]]*\]/;
+
+/**
+ * Return markdown with citations resolved. Falls back to the original
+ * markdown when there is no workspace, no citations or no bibliography.
+ */
+export async function applyCitations(
+  markdown: string,
+  cslStyle: string = DEFAULT_CSL_STYLE
+): Promise<string> {
+  const workspaceRoot = useWorkspaceStore.getState().rootPath;
+  if (!workspaceRoot || !CITATION_PATTERN.test(markdown)) {
+    return markdown;
+  }
+
+  try {
+    const result = await invoke<CitationResult>("apply_citations", {
+      workspaceRoot,
+      content: markdown,
+      cslStyle,
+    });
+    if (result.missing.length > 0) {
+      toast.warning(`Unknown citation keys: ${result.missing.join(", ")}`);
+    }
+    return result.content;
+  } catch (error) {
+    console.warn("[Export] Citations not applied:", error);
+    return markdown;
+  }
+}
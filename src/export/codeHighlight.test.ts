@@ -0,0 +1,16 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { exportCodeTheme } from "./codeHighlight";
+
+describe("exportCodeTheme", () => {
+  it("keeps the editor theme", () => {
+    expect(exportCodeTheme("sepia", true)).toBe("sepia");
+    expect(exportCodeTheme("night", false)).toBe("night");
+  });
+
+  it("swaps the dark theme for light exports", () => {
+    expect(exportCodeTheme("night", true)).toBe("paper");
+  });
+});
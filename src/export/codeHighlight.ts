@@ -0,0 +1,33 @@
+/**
+ * Code Highlighting for Export
+ *
+ * Purpose: Replace the editor's class-based code colors with inline-styled
+ * highlighting from the backend, so code stays colored in browsers, mail
+ * clients and printed PDFs.
+ *
+ * @module export/codeHighlight
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { useSettingsStore, type ThemeId } from "@/stores/settingsStore";
+import { resolveExportTheme } from "@/utils/workspaceEditorSettings";
+
+/** The editor theme to highlight with; light exports never use the dark theme. */
+export function exportCodeTheme(theme: ThemeId, lightTheme: boolean): ThemeId {
+  return lightTheme && theme === "night" ? "paper" : theme;
+}
+
+/** Highlight the code blocks in rendered HTML; returns the input on failure. */
+export async function highlightCodeBlocks(html: string, lightTheme = true): Promise<string> {
+  if (!html.includes('<code class="language-')) return html;
+  const theme = exportCodeTheme(
+    resolveExportTheme(useSettingsStore.getState().appearance.theme),
+    lightTheme
+  );
+  try {
+    return await invoke<string>("highlight_code_blocks", { html, theme });
+  } catch (error) {
+    console.warn("[Export] Code highlighting failed:", error);
+    return html;
+  }
+}
@@ -0,0 +1,40 @@
+import { describe, it, expect } from "vitest";
+import {
+  metadataHeadTags,
+  metadataStamp,
+  pandocMetadata,
+  type ExportMetadata,
+} from "./exportMetadata";
+
+const meta: ExportMetadata = {
+  title: "Field Notes",
+  author: "Ada",
+  keywords: ["engines", "notes"],
+  description: null,
+  date: "2026-03-14",
+  dateSource: "frontmatter",
+  gitCommit: "abc1234",
+  gitDirty: true,
+  appVersion: "1.2.3",
+};
+
+describe("exportMetadata", () => {
+  it("builds head meta tags", () => {
+    const tags = metadataHeadTags({ ...meta, author: 'A "B"' });
+    expect(tags).toContain('<meta name="author" content="A &quot;B&quot;">');
+    expect(tags).toContain('<meta name="keywords" content="engines, notes">');
+    expect(tags).toContain('<meta name="vmark:commit" content="abc1234+">');
+    expect(tags).not.toContain("description");
+  });
+
+  it("formats the footer stamp", () => {
+    expect(metadataStamp(meta)).toBe("Field Notes · Ada · 2026-03-14 · abc1234+ · VMark 1.2.3");
+    expect(metadataStamp({ ...meta, title: null, author: null, gitCommit: null })).toBe(
+      "2026-03-14 · VMark 1.2.3"
+    );
+  });
+
+  it("maps pandoc metadata", () => {
+    expect(pandocMetadata(meta)).toEqual({ date: "2026-03-14", author: "Ada", keywords: "engines, notes" });
+  });
+});
@@ -0,0 +1,97 @@
+/**
+ * Export metadata stamping.
+ *
+ * The backend collects title, author, keywords and a reproducible date from
+ * the frontmatter (falling back to git and file dates), plus the workspace's
+ * git commit and the VMark version. These helpers turn it into `<head>`
+ * meta tags (which browsers carry into saved PDFs' document properties), a
+ * document footer, and pandoc metadata fields.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ExportMetadata {
+  title: string | null;
+  author: string | null;
+  keywords: string[];
+  description: string | null;
+  /** `YYYY-MM-DD` */
+  date: string;
+  dateSource: "frontmatter" | "sourceDateEpoch" | "gitCommit" | "modified" | "today";
+  gitCommit: string | null;
+  gitDirty: boolean;
+  appVersion: string;
+}
+
+export function getExportMetadata(
+  content: string,
+  path?: string | null,
+  workspaceRoot?: string | null
+): Promise<ExportMetadata> {
+  return invoke<ExportMetadata>("get_export_metadata", {
+    content,
+    path: path ?? null,
+    workspaceRoot: workspaceRoot ?? null,
+  });
+}
+
+function escapeAttr(text: string): string {
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+/** `abc1234`, or `abc1234+` when the document has uncommitted changes. */
+export function formatCommit(meta: ExportMetadata): string | null {
+  if (!meta.gitCommit) return null;
+  return meta.gitDirty ? `${meta.gitCommit}+` : meta.gitCommit;
+}
+
+/** `<meta>` tags for the exported page's `<head>`. */
+export function metadataHeadTags(meta: ExportMetadata): string {
+  const tags: Array<[string, string | null]> = [
+    ["author", meta.author],
+    ["keywords", meta.keywords.length > 0 ? meta.keywords.join(", ") : null],
+    ["description", meta.description],
+    ["date", meta.date],
+    ["generator", `VMark ${meta.appVersion}`],
+    ["vmark:commit", formatCommit(meta)],
+  ];
+  return tags
+    .filter((tag): tag is [string, string] => Boolean(tag[1]))
+    .map(([name, content]) => `<meta name="${name}" content="${escapeAttr(content)}">`)
+    .join("\n  ");
+}
+
+/** One-line stamp, e.g. "Field Notes · Ada · 2026-03-14 · abc1234 · VMark 1.2.3". */
+export function metadataStamp(meta: ExportMetadata): string {
+  return [meta.title, meta.author, meta.date, formatCommit(meta), `VMark ${meta.appVersion}`]
+    .filter(Boolean)
+    .join(" · ");
+}
+
+/** Footer appended after the document content. */
+export function metadataFooterHtml(meta: ExportMetadata): string {
+  return `<footer class="export-metadata">${escapeAttr(metadataStamp(meta))}</footer>`;
+}
+
+/** Styles for the metadata footer. */
+export const EXPORT_METADATA_CSS = `
+.export-metadata {
+  margin-top: 3em;
+  padding-top: 0.75em;
+  border-top: 1px solid var(--border-color, #ddd);
+  font-size: 0.75em;
+  color: var(--text-secondary, #888);
+}`;
+
+/** Fields for pandoc's `--metadata` (document properties in ODT/DOCX). */
+export function pandocMetadata(meta: ExportMetadata): Record<string, string> {
+  const fields: Record<string, string> = { date: meta.date };
+  if (meta.author) fields.author = meta.author;
+  if (meta.keywords.length > 0) fields.keywords = meta.keywords.join(", ");
+  if (meta.description) fields.description = meta.description;
+  return fields;
+}
@@ -41,6 +41,12 @@ import {
   type EmbeddedFont,
 } from "./fontEmbedder";
 import { getReaderCSS, getReaderJS } from "./reader";
+import {
+  EXPORT_METADATA_CSS,
+  metadataFooterHtml,
+  metadataHeadTags,
+  type ExportMetadata,
+} from "./exportMetadata";
 
 /**
  * Sanitize HTML by removing editor-specific artifacts.
@@ -120,6 +126,8 @@ export interface HtmlExportOptions {
   forceLightTheme?: boolean;
   /** Include interactive reader controls (default: true) */
   includeReader?: boolean;
+  /** Stamp document metadata into the head and a footer */
+  metadata?: ExportMetadata | null;
 }
 
 export interface HtmlExportResult {
@@ -998,9 +1006,11 @@ function generateIndexHtml(
     contentCSS: string;
     isDark?: boolean;
     includeKaTeX?: boolean;
+    headTags?: string;
+    footer?: string;
   }
 ): string {
-  const { title, themeCSS, fontCSS, contentCSS, isDark, includeKaTeX = true } = options;
+  const { title, themeCSS, fontCSS, contentCSS, isDark, includeKaTeX = true, headTags = "", footer = "" } = options;
 
   // Inline only theme, font, and content CSS (small)
   // Reader CSS/JS are external
@@ -1022,6 +1032,7 @@ function generateIndexHtml(
   <meta charset="UTF-8">
   <meta name="viewport" content="width=device-width, initial-scale=1.0">
   <title>${escapeHtml(title)}</title>
+  ${headTags}
   ${katexLink}
   <link rel="stylesheet" href="assets/vmark-reader.css">
   <style>
@@ -1033,6 +1044,7 @@ ${inlineStyles}
     <div class="export-surface-editor">
 ${content}
     </div>
+    ${footer}
   </div>
   <script src="assets/vmark-reader.js"></script>
 </body>
@@ -1053,9 +1065,22 @@ function generateStandaloneHtml(
     readerJS: string;
     isDark?: boolean;
     includeKaTeX?: boolean;
+    headTags?: string;
+    footer?: string;
   }
 ): string {
-  const { title, themeCSS, fontCSS, contentCSS, readerCSS, readerJS, isDark, includeKaTeX = true } = options;
+  const {
+    title,
+    themeCSS,
+    fontCSS,
+    contentCSS,
+    readerCSS,
+    readerJS,
+    isDark,
+    includeKaTeX = true,
+    headTags = "",
+    footer = "",
+  } = options;
 
   const allStyles = [
     `/* Theme Variables */\n${themeCSS}`,
@@ -1076,6 +1101,7 @@ function generateStandaloneHtml(
   <meta charset="UTF-8">
   <meta name="viewport" content="width=device-width, initial-scale=1.0">
   <title>${escapeHtml(title)}</title>
+  ${headTags}
   ${katexLink}
   <style>
 ${allStyles}
@@ -1086,6 +1112,7 @@ ${allStyles}
     <div class="export-surface-editor">
 ${content}
     </div>
+    ${footer}
   </div>
   <script>
 ${readerJS}
@@ -1094,6 +1121,30 @@ ${readerJS}
 </html>`;
 }
 
+/**
+ * Build a self-contained page for the live preview server: images embedded
+ * as data URIs, light theme, no reader controls.
+ */
+export async function buildPreviewPage(
+  html: string,
+  options: { title?: string; sourceFilePath?: string | null }
+): Promise<string> {
+  const baseDir = await getDocumentBaseDir(options.sourceFilePath ?? null);
+  const { html: content } = await resolveResources(sanitizeExportHtml(html), {
+    baseDir,
+    mode: "single",
+  });
+  return generateStandaloneHtml(content, {
+    title: options.title ?? "Preview",
+    themeCSS: captureThemeCSS(),
+    fontCSS: "",
+    contentCSS: getEditorContentCSS(),
+    readerCSS: "",
+    readerJS: "",
+    includeKaTeX: contentHasMath(content),
+  });
+}
+
 /**
  * Escape HTML special characters.
  */
@@ -1139,6 +1190,7 @@ export async function exportHtml(
     fontSettings,
     forceLightTheme = true,
     includeReader = true,
+    metadata,
   } = options;
 
   const warnings: string[] = [];
@@ -1231,7 +1283,11 @@ export async function exportHtml(
 
     // Generate CSS
     const themeCSS = captureThemeCSS();
-    const contentCSS = getEditorContentCSS();
+    const contentCSS = metadata
+      ? `${getEditorContentCSS()}\n${EXPORT_METADATA_CSS}`
+      : getEditorContentCSS();
+    const headTags = metadata ? metadataHeadTags(metadata) : "";
+    const footer = metadata ? metadataFooterHtml(metadata) : "";
     const readerCSS = includeReader ? getReaderCSS() : "";
     const readerJS = includeReader ? getReaderJS() : "";
 
@@ -1257,6 +1313,8 @@ export async function exportHtml(
       fontCSS,
       contentCSS,
       isDark: useDarkTheme,
+      headTags,
+      footer,
     });
     await writeTextFile(indexPath, indexHtml);
     totalSize += new TextEncoder().encode(indexHtml).length;
@@ -1270,6 +1328,8 @@ export async function exportHtml(
       readerCSS,
       readerJS,
       isDark: useDarkTheme,
+      headTags,
+      footer,
     });
     await writeTextFile(standalonePath, standaloneHtml);
     totalSize += new TextEncoder().encode(standaloneHtml).length;
@@ -60,7 +60,7 @@ export {
 export type { FontConfig, FontEmbedResult, FontFile, DownloadedFont, EmbeddedFont } from "./fontEmbedder";
 
 // HTML Export
-export { exportHtml, copyHtmlToClipboard, getEditorContentCSS } from "./htmlExport";
+export { exportHtml, copyHtmlToClipboard, getEditorContentCSS, buildPreviewPage } from "./htmlExport";
 export type { HtmlExportOptions, HtmlExportResult } from "./htmlExport";
 
 // Export Operations
@@ -68,6 +68,34 @@ export {
   exportToHtml,
   exportToPdf,
   copyAsHtml,
+  copyAsRichText,
   getRenderedHtml,
 } from "./useExportOperations";
 export type { ExportToHtmlOptions } from "./useExportOperations";
+
+// Metadata stamping
+export {
+  getExportMetadata,
+  metadataHeadTags,
+  metadataFooterHtml,
+  metadataStamp,
+  pandocMetadata,
+} from "./exportMetadata";
+export type { ExportMetadata } from "./exportMetadata";
+
+// Live preview server
+export { startLivePreview, refreshLivePreview, stopLivePreview } from "./livePreview";
+export type { PreviewStatus } from "./livePreview";
+
+// Citations
+export { applyCitations, DEFAULT_CSL_STYLE } from "./citations";
+
+// Pandoc formats
+export {
+  exportWithPandoc,
+  importWithPandoc,
+  convertWithPandoc,
+  formatForExtension,
+  PANDOC_FORMATS,
+} from "./pandoc";
+export type { PandocFormatId, PandocConvertOptions, ExportWithPandocOptions } from "./pandoc";
@@ -0,0 +1,50 @@
+/**
+ * Live Preview
+ *
+ * Purpose: Render a document and hand it to the backend preview server,
+ * which serves it on localhost (or the LAN) and reloads connected browsers
+ * whenever the page changes.
+ *
+ * @module export/livePreview
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { readTextFile } from "@tauri-apps/plugin-fs";
+import { getRenderedHtml } from "./useExportOperations";
+import { buildPreviewPage } from "./htmlExport";
+import { applyCitations } from "./citations";
+import { getFileName } from "@/utils/pathUtils";
+
+export interface PreviewStatus {
+  running: boolean;
+  path: string | null;
+  url: string | null;
+  /** Set when the preview is shared on the network */
+  lanUrl: string | null;
+}
+
+async function renderPage(markdown: string, filePath: string | null): Promise<string> {
+  const html = await getRenderedHtml(await applyCitations(markdown), true);
+  const title = filePath ? getFileName(filePath) : "Untitled";
+  return buildPreviewPage(html, { title, sourceFilePath: filePath });
+}
+
+/** Start (or retarget) the preview server with this document. */
+export async function startLivePreview(
+  markdown: string,
+  filePath: string | null,
+  lan = false
+): Promise<PreviewStatus> {
+  const html = await renderPage(markdown, filePath);
+  return invoke<PreviewStatus>("start_preview_server", { path: filePath, html, lan });
+}
+
+/** Re-render the previewed file from disk, e.g. after it was saved. */
+export async function refreshLivePreview(filePath: string): Promise<PreviewStatus> {
+  const html = await renderPage(await readTextFile(filePath), filePath);
+  return invoke<PreviewStatus>("update_preview", { path: filePath, html });
+}
+
+export function stopLivePreview(): Promise<void> {
+  return invoke("stop_preview_server");
+}
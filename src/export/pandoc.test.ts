@@ -0,0 +1,46 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { exportWithPandoc, formatForExtension } from "./pandoc";
+
+vi.mock("sonner", () => ({
+  toast: { loading: vi.fn(() => "toast"), dismiss: vi.fn(), success: vi.fn(), warning: vi.fn(), error: vi.fn() },
+}));
+
+describe("pandoc", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(save).mockReset();
+  });
+
+  it("maps file extensions to pandoc formats", () => {
+    expect(formatForExtension("/docs/Report.ODT")).toBe("odt");
+    expect(formatForExtension("paper.tex")).toBe("latex");
+    expect(formatForExtension("page.wiki")).toBe("mediawiki");
+    expect(formatForExtension("notes.md")).toBeNull();
+  });
+
+  it("exports to the chosen file", async () => {
+    vi.mocked(save).mockResolvedValueOnce("/out/Notes.rst");
+    vi.mocked(invoke).mockResolvedValueOnce({ content: null, outputPath: "/out/Notes.rst", warnings: [] });
+
+    const ok = await exportWithPandoc({ markdown: "# Notes", format: "rst", defaultName: "Notes" });
+
+    expect(ok).toBe(true);
+    expect(invoke).toHaveBeenCalledWith(
+      "convert_with_pandoc",
+      expect.objectContaining({
+        input: "# Notes",
+        from: "gfm",
+        to: "rst",
+        options: expect.objectContaining({ outputPath: "/out/Notes.rst", standalone: true }),
+      })
+    );
+  });
+
+  it("does nothing when the save dialog is cancelled", async () => {
+    vi.mocked(save).mockResolvedValueOnce(null);
+    expect(await exportWithPandoc({ markdown: "# Notes", format: "odt" })).toBe(false);
+    expect(invoke).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,209 @@
+/**
+ * Pandoc Import/Export
+ *
+ * Formats VMark doesn't render itself (ODT, reStructuredText, LaTeX,
+ * MediaWiki) are converted by a system-installed pandoc in the backend.
+ * Conversion progress arrives as `pandoc:progress` events.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+
+import { applyCitations } from "./citations";
+import { getExportMetadata, pandocMetadata } from "./exportMetadata";
+import { useTabStore } from "@/stores/tabStore";
+import { useDocumentStore } from "@/stores/documentStore";
+import { getDirectory, joinPath } from "@/utils/pathUtils";
+
+/** Formats offered through pandoc, keyed by pandoc reader/writer name */
+export const PANDOC_FORMATS = {
+  odt: { name: "OpenDocument Text", extension: "odt" },
+  rst: { name: "reStructuredText", extension: "rst" },
+  latex: { name: "LaTeX", extension: "tex" },
+  mediawiki: { name: "MediaWiki", extension: "wiki" },
+} as const;
+
+export type PandocFormatId = keyof typeof PANDOC_FORMATS;
+
+/** Markdown flavour used on the VMark side of every conversion */
+const MARKDOWN_FORMAT = "gfm";
+
+interface PandocResult {
+  content: string | null;
+  outputPath: string | null;
+  warnings: string[];
+}
+
+interface PandocProgress {
+  requestId: string;
+  stage: "started" | "log" | "finished" | "failed";
+  message: string | null;
+}
+
+export interface PandocConvertOptions {
+  inputPath?: string;
+  outputPath?: string;
+  standalone?: boolean;
+  resourcePath?: string;
+  title?: string;
+  /** Extra `--metadata` fields (author, date, keywords, ...) */
+  metadata?: Record<string, string>;
+}
+
+let requestCounter = 0;
+
+/** Pandoc format for a file extension, if it is one we import. */
+export function formatForExtension(path: string): PandocFormatId | null {
+  const ext = path.split(".").pop()?.toLowerCase();
+  const entry = Object.entries(PANDOC_FORMATS).find(([, f]) => f.extension === ext);
+  return entry ? (entry[0] as PandocFormatId) : null;
+}
+
+/**
+ * Run a pandoc conversion, showing its log output in a loading toast.
+ */
+export async function convertWithPandoc(
+  input: string,
+  from: string,
+  to: string,
+  options: PandocConvertOptions,
+  label: string
+): Promise<PandocResult> {
+  const requestId = `pandoc-${Date.now()}-${++requestCounter}`;
+  const toastId = toast.loading(label);
+
+  const unlisten = await listen<PandocProgress>("pandoc:progress", (event) => {
+    const { requestId: id, stage, message } = event.payload;
+    if (id !== requestId || stage !== "log" || !message) return;
+    toast.loading(label, { id: toastId, description: message });
+  });
+
+  try {
+    const result = await invoke<PandocResult>("convert_with_pandoc", {
+      requestId,
+      input,
+      from,
+      to,
+      options,
+    });
+    if (result.warnings.length > 0) {
+      console.warn("[Pandoc] Warnings:", result.warnings);
+    }
+    toast.dismiss(toastId);
+    return result;
+  } catch (error) {
+    toast.dismiss(toastId);
+    throw error;
+  } finally {
+    unlisten();
+  }
+}
+
+export interface ExportWithPandocOptions {
+  markdown: string;
+  format: PandocFormatId;
+  defaultName?: string;
+  defaultDirectory?: string;
+  sourceFilePath?: string | null;
+  /** Write title, author, date and keywords into the document properties */
+  embedMetadata?: boolean;
+  workspaceRoot?: string | null;
+}
+
+/**
+ * Export markdown to a pandoc format, asking where to save it.
+ */
+export async function exportWithPandoc(options: ExportWithPandocOptions): Promise<boolean> {
+  const {
+    markdown,
+    format,
+    defaultName = "document",
+    defaultDirectory,
+    sourceFilePath,
+    embedMetadata,
+    workspaceRoot,
+  } = options;
+  const { name, extension } = PANDOC_FORMATS[format];
+
+  if (!markdown.trim()) {
+    toast.error("No content to export!");
+    return false;
+  }
+
+  const fileName = `${defaultName}.${extension}`;
+  const outputPath = await save({
+    defaultPath: defaultDirectory ? joinPath(defaultDirectory, fileName) : fileName,
+    title: `Export ${name}`,
+    filters: [{ name, extensions: [extension] }],
+  });
+  if (!outputPath) return false;
+
+  try {
+    const metadata = embedMetadata
+      ? await getExportMetadata(markdown, sourceFilePath, workspaceRoot).catch(() => null)
+      : null;
+    const result = await convertWithPandoc(
+      await applyCitations(markdown),
+      MARKDOWN_FORMAT,
+      format,
+      {
+        outputPath,
+        standalone: true,
+        resourcePath: sourceFilePath ? getDirectory(sourceFilePath) : undefined,
+        title: metadata?.title ?? defaultName,
+        metadata: metadata ? pandocMetadata(metadata) : undefined,
+      },
+      `Exporting ${name}…`
+    );
+    if (result.warnings.length > 0) {
+      toast.warning(`Exported with ${result.warnings.length} warning(s)`);
+    } else {
+      toast.success(`Exported ${name}`);
+    }
+    return true;
+  } catch (error) {
+    console.error(`[Pandoc] Failed to export ${format}:`, error);
+    toast.error(String(error));
+    return false;
+  }
+}
+
+/**
+ * Pick a document in a pandoc format and open it as a new untitled tab.
+ */
+export async function importWithPandoc(windowLabel: string): Promise<boolean> {
+  const formats = Object.values(PANDOC_FORMATS);
+  const inputPath = await open({
+    title: "Import",
+    filters: [
+      { name: "Documents", extensions: formats.map((f) => f.extension) },
+      ...formats.map((f) => ({ name: f.name, extensions: [f.extension] })),
+    ],
+  });
+  if (!inputPath) return false;
+
+  const format = formatForExtension(inputPath);
+  if (!format) {
+    toast.error("Unsupported file type");
+    return false;
+  }
+
+  try {
+    const result = await convertWithPandoc(
+      "",
+      format,
+      MARKDOWN_FORMAT,
+      { inputPath },
+      `Importing ${PANDOC_FORMATS[format].name}…`
+    );
+    const tabId = useTabStore.getState().createTab(windowLabel, null);
+    useDocumentStore.getState().initDocument(tabId, result.content ?? "", null);
+    return true;
+  } catch (error) {
+    console.error(`[Pandoc] Failed to import ${inputPath}:`, error);
+    toast.error(String(error));
+    return false;
+  }
+}
@@ -16,6 +16,15 @@ import { ExportSurface, type ExportSurfaceRef } from "./ExportSurface";
 import { exportHtml } from "./htmlExport";
 import { waitForAssets } from "./waitForAssets";
 import { captureThemeCSS } from "./themeSnapshot";
+import { applyCitations } from "./citations";
+import { highlightCodeBlocks } from "./codeHighlight";
+import {
+  EXPORT_METADATA_CSS,
+  getExportMetadata,
+  metadataFooterHtml,
+  metadataHeadTags,
+  type ExportMetadata,
+} from "./exportMetadata";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { joinPath } from "@/utils/pathUtils";
 import { showError, FileErrors } from "@/utils/errorDialog";
@@ -125,6 +134,29 @@ export interface ExportToHtmlOptions {
   defaultDirectory?: string;
   /** Source file path for resource resolution */
   sourceFilePath?: string | null;
+  /** Stamp title, author, date, git commit and VMark version into the export */
+  embedMetadata?: boolean;
+  /** Workspace root, for the git commit in embedded metadata */
+  workspaceRoot?: string | null;
+}
+
+/**
+ * Metadata to stamp into an export, or null when disabled. Failures only
+ * drop the stamp; they never fail the export.
+ */
+async function loadMetadata(
+  markdown: string,
+  embed: boolean | undefined,
+  sourceFilePath?: string | null,
+  workspaceRoot?: string | null
+): Promise<ExportMetadata | null> {
+  if (!embed) return null;
+  try {
+    return await getExportMetadata(markdown, sourceFilePath, workspaceRoot);
+  } catch (error) {
+    console.warn("[Export] Failed to collect metadata:", error);
+    return null;
+  }
 }
 
 /**
@@ -143,6 +175,8 @@ export async function exportToHtml(
     defaultName = "document",
     defaultDirectory,
     sourceFilePath,
+    embedMetadata,
+    workspaceRoot,
   } = options;
 
   // Check for empty content
@@ -172,8 +206,10 @@ export async function exportToHtml(
     // Strip the .html extension if present (user might have edited the name)
     const folderPath = selectedPath.replace(/\.html$/i, "");
 
-    // Render markdown to HTML
-    const html = await renderMarkdownToHtml(markdown, true);
+    // Render markdown to HTML (citations resolved against the workspace .bib)
+    const html = await highlightCodeBlocks(
+      await renderMarkdownToHtml(await applyCitations(markdown), true)
+    );
 
     // Get font settings
     const settings = useSettingsStore.getState();
@@ -182,13 +218,16 @@ export async function exportToHtml(
       monoFontFamily: settings.appearance.monoFont,
     };
 
+    const metadata = await loadMetadata(markdown, embedMetadata, sourceFilePath, workspaceRoot);
+
     // Export with options
     const result = await exportHtml(html, {
-      title: defaultName.replace(/\.[^.]+$/, ""),
+      title: metadata?.title ?? defaultName.replace(/\.[^.]+$/, ""),
       sourceFilePath,
       outputPath: folderPath,
       fontSettings,
       forceLightTheme: true,
+      metadata,
     });
 
     if (!result.success) {
@@ -227,6 +266,10 @@ function rewriteAssetUrls(html: string): string {
     .replace(/https:\/\/asset\.localhost/g, "file://");
 }
 
+function escapeTitle(text: string): string {
+  return text.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
 /**
  * Print document by opening a self-contained HTML file in the system browser.
  *
@@ -238,9 +281,18 @@ function rewriteAssetUrls(html: string): string {
  *  3. Write it to a temp file
  *  4. Open it in the default browser, which auto-triggers `window.print()`
  *
+ * With `embedMetadata`, the title, author and keywords go into the page's
+ * `<title>`/`<meta>` tags, which browsers use as the saved PDF's document
+ * properties, and a stamp with date, git commit and VMark version is printed
+ * after the content.
+ *
  * @param markdown - The markdown content
+ * @param options - Metadata stamping options
  */
-export async function exportToPdf(markdown: string): Promise<void> {
+export async function exportToPdf(
+  markdown: string,
+  options: Pick<ExportToHtmlOptions, "sourceFilePath" | "embedMetadata" | "workspaceRoot"> = {}
+): Promise<void> {
   // Check for empty content
   const trimmedContent = markdown.trim();
   if (!trimmedContent) {
@@ -250,7 +302,9 @@ export async function exportToPdf(markdown: string): Promise<void> {
 
   try {
     // 1. Render markdown to HTML via ExportSurface (always light theme for print)
-    const html = await renderMarkdownToHtml(markdown, true);
+    const html = await highlightCodeBlocks(
+      await renderMarkdownToHtml(await applyCitations(markdown), true)
+    );
 
     // 2. Capture CSS
     const themeCSS = captureThemeCSS();
@@ -260,13 +314,22 @@ export async function exportToPdf(markdown: string): Promise<void> {
     // 3. Rewrite asset:// URLs to file:// for browser access
     const resolvedHtml = rewriteAssetUrls(html);
 
+    const metadata = await loadMetadata(
+      markdown,
+      options.embedMetadata,
+      options.sourceFilePath,
+      options.workspaceRoot
+    );
+    const title = escapeTitle(metadata?.title ?? "Print");
+
     // 4. Build self-contained HTML with auto-print
     const fullHtml = `<!DOCTYPE html>
 <html lang="en">
 <head>
   <meta charset="UTF-8">
   <meta name="viewport" content="width=device-width, initial-scale=1.0">
-  <title>Print</title>
+  <title>${title}</title>
+  ${metadata ? metadataHeadTags(metadata) : ""}
   <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/katex@0.16.9/dist/katex.min.css" crossorigin="anonymous">
   <style>
 /* Theme Variables */
@@ -274,6 +337,7 @@ ${themeCSS}
 
 /* Content Styles */
 ${contentCSS}
+${metadata ? EXPORT_METADATA_CSS : ""}
 
 /* Print-specific overrides */
 @media print {
@@ -288,6 +352,7 @@ ${contentCSS}
     <div class="export-surface-editor">
 ${resolvedHtml}
     </div>
+    ${metadata ? metadataFooterHtml(metadata) : ""}
   </div>
   <script>
     window.addEventListener('load', function() {
@@ -320,7 +385,7 @@ export async function copyAsHtml(
 ): Promise<boolean> {
   try {
     // Render markdown to HTML
-    const html = await renderMarkdownToHtml(markdown, true);
+    const html = await highlightCodeBlocks(await renderMarkdownToHtml(markdown, true));
 
     if (includeStyles) {
       const themeCSS = captureThemeCSS();
@@ -339,6 +404,24 @@ export async function copyAsHtml(
   }
 }
 
+/**
+ * Copy rendered document as rich text (RTF + HTML, with markdown as plain text),
+ * so pasting into word processors and mail clients keeps formatting.
+ */
+export async function copyAsRichText(markdown: string): Promise<boolean> {
+  try {
+    const html = await renderMarkdownToHtml(markdown, true);
+    await invoke("copy_as_rich_text", { html, plainText: markdown });
+
+    toast.success("Rich text copied to clipboard");
+    return true;
+  } catch (error) {
+    console.error("[Export] Failed to copy rich text:", error);
+    await showError(FileErrors.copyFailed);
+    return false;
+  }
+}
+
 /**
  * Get rendered HTML from markdown (for programmatic use).
  */
@@ -7,6 +7,7 @@
 import { message, save, open } from "@tauri-apps/plugin-dialog";
 import { getDefaultSaveFolderWithFallback } from "@/hooks/useDefaultSaveFolder";
 import { saveToPath } from "@/utils/saveToPath";
+import { applyEncryptionPolicy } from "@/utils/encryptedDocuments";
 import { joinPath, getDirectory } from "@/utils/pathUtils";
 
 export interface CloseSaveContext {
@@ -119,7 +120,7 @@ export async function promptSaveForDirtyDocument(
     if (!newPath) {
       return { action: "cancelled" };
     }
-    path = newPath;
+    path = await applyEncryptionPolicy(newPath);
   }
 
   const saved = await saveToPath(tabId, path, content, "manual");
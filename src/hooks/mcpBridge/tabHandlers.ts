@@ -2,10 +2,10 @@
  * MCP Bridge - Tab Management Handlers
  */
 
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { respond, resolveWindowId } from "./utils";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Tab information for MCP responses.
@@ -267,7 +267,7 @@ export async function handleTabsReopenClosed(
     if (reopenedTab.filePath) {
       // Load content from file
       try {
-        const content = await readTextFile(reopenedTab.filePath);
+        const content = await readDocumentFile(reopenedTab.filePath);
         docStore.initDocument(reopenedTab.id, content, reopenedTab.filePath);
       } catch {
         // File may have been deleted, init with empty content
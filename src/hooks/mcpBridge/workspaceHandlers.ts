@@ -2,7 +2,6 @@
  * MCP Bridge - Workspace and Window Operation Handlers
  */
 
-import { readTextFile, writeTextFile } from "@tauri-apps/plugin-fs";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { useDocumentStore } from "@/stores/documentStore";
 import { useTabStore } from "@/stores/tabStore";
@@ -12,6 +11,7 @@ import { serializeMarkdown } from "@/utils/markdownPipeline";
 import { getFileName } from "@/utils/paths";
 import { reloadTabFromDisk } from "@/utils/reloadFromDisk";
 import { respond, getEditor, resolveWindowId } from "./utils";
+import { readDocumentFile, writeDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Handle windows.list request.
@@ -127,7 +127,7 @@ export async function handleWorkspaceOpenDocument(
     }
 
     // Read file content
-    const content = await readTextFile(path);
+    const content = await readDocumentFile(path);
 
     // Create new tab and initialize document with content
     const tabStore = useTabStore.getState();
@@ -168,7 +168,7 @@ export async function handleWorkspaceSaveDocument(id: string): Promise<void> {
     if (!editor) throw new Error("No active editor");
 
     const content = serializeMarkdown(editor.state.schema, editor.state.doc);
-    await writeTextFile(doc.filePath, content);
+    await writeDocumentFile(doc.filePath, content);
     docStore.markSaved(activeTabId, content);
 
     await respond({ id, success: true, data: null });
@@ -233,7 +233,7 @@ export async function handleWorkspaceSaveDocumentAs(
     if (!editor) throw new Error("No active editor");
 
     const content = serializeMarkdown(editor.state.schema, editor.state.doc);
-    await writeTextFile(path, content);
+    await writeDocumentFile(path, content);
 
     // Update tab and document with new path
     tabStore.updateTabPath(activeTabId, path);
@@ -1,8 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
 import { useWorkspaceStore, type WorkspaceConfig } from "@/stores/workspaceStore";
+import { promptWorkspaceTrust } from "@/utils/workspaceTrust";
 
 /**
- * Open a workspace and load config from disk (if available).
+ * Open a workspace and load config from disk (if available), then ask
+ * whether to trust it if it isn't yet (without waiting for the answer).
  * Returns the loaded config for optional follow-up use.
  */
 export async function openWorkspaceWithConfig(
@@ -13,6 +15,7 @@ export async function openWorkspaceWithConfig(
       rootPath,
     });
     useWorkspaceStore.getState().openWorkspace(rootPath, config);
+    void promptWorkspaceTrust(rootPath);
     return config;
   } catch (error) {
     console.error("[Workspace] Failed to load config:", error);
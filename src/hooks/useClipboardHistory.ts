@@ -0,0 +1,20 @@
+/**
+ * Records copies and cuts made in this window into the clipboard history.
+ */
+import { useEffect } from "react";
+import { copiedText, recordCopy } from "@/utils/clipboardHistory";
+
+export function useClipboardHistory() {
+  useEffect(() => {
+    const handleCopy = (event: ClipboardEvent) => {
+      void recordCopy(copiedText(event));
+    };
+
+    document.addEventListener("copy", handleCopy);
+    document.addEventListener("cut", handleCopy);
+    return () => {
+      document.removeEventListener("copy", handleCopy);
+      document.removeEventListener("cut", handleCopy);
+    };
+  }, []);
+}
@@ -9,7 +9,6 @@
  */
 import { useEffect, useRef } from "react";
 import { getCurrentWebview } from "@tauri-apps/api/webview";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { toast } from "sonner";
 import { invoke } from "@tauri-apps/api/core";
 import { useWindowLabel } from "@/contexts/WindowContext";
@@ -24,6 +23,7 @@ import { getReplaceableTab, findExistingTabForPath } from "@/hooks/useReplaceabl
 import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { openWorkspaceWithConfig } from "@/hooks/openWorkspaceWithConfig";
 import { safeUnlisten } from "@/utils/safeUnlisten";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Opens a file in a new tab (or activates existing tab if already open).
@@ -40,7 +40,7 @@ async function openFileInNewTab(windowLabel: string, path: string): Promise<void
   }
 
   try {
-    const content = await readTextFile(path);
+    const content = await readDocumentFile(path);
     const tabId = useTabStore.getState().createTab(windowLabel, path);
     useDocumentStore.getState().initDocument(tabId, content, path);
     useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
@@ -183,7 +183,7 @@ export function useDragDropOpen(): void {
             for (const path of markdownPaths) {
               if (!replaceableTabUsed && initialReplaceableTab) {
                 try {
-                  const content = await readTextFile(path);
+                  const content = await readDocumentFile(path);
                   useTabStore.getState().updateTabPath(initialReplaceableTab.tabId, path);
                   useDocumentStore.getState().loadContent(
                     initialReplaceableTab.tabId,
@@ -229,7 +229,7 @@ export function useDragDropOpen(): void {
             case "replace_tab":
               // Replace the clean untitled tab with the file content (only once)
               try {
-                const content = await readTextFile(path);
+                const content = await readDocumentFile(path);
                 useTabStore.getState().updateTabPath(decision.tabId, decision.filePath);
                 useDocumentStore.getState().loadContent(
                   decision.tabId,
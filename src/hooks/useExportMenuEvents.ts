@@ -1,7 +1,10 @@
 /**
  * Hook for export menu event handling.
  *
- * Handles menu:export-html, menu:export-pdf, and menu:copy-html events.
+ * Handles menu:export-html, menu:export-pdf, menu:copy-html and menu:copy-rich-text events,
+ * plus the pandoc formats (menu:export-odt, -rst, -latex, -mediawiki), menu:import-file
+ * the live preview server (menu:live-preview, -lan, menu:stop-live-preview) and the
+ * workspace task calendar (menu:export-tasks-ics).
  * Extracted from useMenuEvents to keep file sizes under 300 lines.
  *
  * Uses ExportSurface for visual parity.
@@ -11,6 +14,7 @@
 import { useEffect, useRef } from "react";
 import { type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { toast } from "sonner";
 import { safeUnlistenAll } from "@/utils/safeUnlisten";
 // Export module is dynamically imported to avoid loading exportStyles.css at startup.
 // This prevents CSS cascade conflicts between dev and prod builds.
@@ -19,6 +23,25 @@ import { getExportFolderName } from "@/utils/exportNaming";
 import { flushActiveWysiwygNow } from "@/utils/wysiwygFlush";
 import { withReentryGuard } from "@/utils/reentryGuard";
 import { getActiveDocument } from "@/utils/activeDocument";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { smartenForExport } from "@/utils/typography";
+import { calendarFeedUrl, exportTasksIcs, localDate, overdueTasks } from "@/utils/taskCalendar";
+
+/** Metadata stamping options from settings, for every export format. */
+function metadataOptions() {
+  return {
+    embedMetadata: useSettingsStore.getState().markdown.embedExportMetadata,
+    workspaceRoot: useWorkspaceStore.getState().rootPath,
+  };
+}
+
+/** Markdown to export: smartened when smart typography is on. */
+async function exportMarkdown(content: string): Promise<string> {
+  return useSettingsStore.getState().markdown.smartTypographyOnExport
+    ? smartenForExport(content)
+    : content;
+}
 
 export function useExportMenuEvents(): void {
   const unlistenRefs = useRef<UnlistenFn[]>([]);
@@ -50,10 +73,11 @@ export function useExportMenuEvents(): void {
           try {
             const { exportToHtml } = await import("@/export");
             await exportToHtml({
-              markdown: doc.content,
+              markdown: await exportMarkdown(doc.content),
               defaultName,
               defaultDirectory: defaultDir,
               sourceFilePath: doc.filePath,
+              ...metadataOptions(),
             });
           } catch (error) {
             console.error("[Menu] Failed to export HTML:", error);
@@ -73,7 +97,7 @@ export function useExportMenuEvents(): void {
           if (!doc) return;
           try {
             const { exportToPdf } = await import("@/export");
-            await exportToPdf(doc.content);
+            await exportToPdf(await exportMarkdown(doc.content), { sourceFilePath: doc.filePath, ...metadataOptions() });
           } catch (error) {
             console.error("[Menu] Failed to export PDF:", error);
           }
@@ -99,6 +123,144 @@ export function useExportMenuEvents(): void {
       });
       if (cancelled) { unlistenCopyHtml(); return; }
       unlistenRefs.current.push(unlistenCopyHtml);
+
+      const unlistenCopyRichText = await currentWindow.listen<string>("menu:copy-rich-text", async (event) => {
+        if (event.payload !== windowLabel) return;
+        flushActiveWysiwygNow();
+
+        await withReentryGuard(windowLabel, "export", async () => {
+          const doc = getActiveDocument(windowLabel);
+          if (!doc) return;
+          try {
+            const { copyAsRichText } = await import("@/export");
+            await copyAsRichText(doc.content);
+          } catch (error) {
+            console.error("[Menu] Failed to copy rich text:", error);
+          }
+        });
+      });
+      if (cancelled) { unlistenCopyRichText(); return; }
+      unlistenRefs.current.push(unlistenCopyRichText);
+
+      // Formats converted by a system pandoc
+      const pandocFormats = ["odt", "rst", "latex", "mediawiki"] as const;
+      for (const format of pandocFormats) {
+        const unlistenPandoc = await currentWindow.listen<string>(`menu:export-${format}`, async (event) => {
+          if (event.payload !== windowLabel) return;
+          flushActiveWysiwygNow();
+
+          await withReentryGuard(windowLabel, "export", async () => {
+            const doc = getActiveDocument(windowLabel);
+            if (!doc) return;
+            try {
+              const { exportWithPandoc } = await import("@/export");
+              await exportWithPandoc({
+                markdown: await exportMarkdown(doc.content),
+                format,
+                defaultName: getExportFolderName(doc.content, doc.filePath),
+                defaultDirectory: doc.filePath ? getDirectory(doc.filePath) : undefined,
+                sourceFilePath: doc.filePath,
+                ...metadataOptions(),
+              });
+            } catch (error) {
+              console.error(`[Menu] Failed to export ${format}:`, error);
+            }
+          });
+        });
+        if (cancelled) { unlistenPandoc(); return; }
+        unlistenRefs.current.push(unlistenPandoc);
+      }
+
+      const unlistenImport = await currentWindow.listen<string>("menu:import-file", async (event) => {
+        if (event.payload !== windowLabel) return;
+
+        await withReentryGuard(windowLabel, "import", async () => {
+          try {
+            const { importWithPandoc } = await import("@/export");
+            await importWithPandoc(windowLabel);
+          } catch (error) {
+            console.error("[Menu] Failed to import file:", error);
+          }
+        });
+      });
+      if (cancelled) { unlistenImport(); return; }
+      unlistenRefs.current.push(unlistenImport);
+
+      // Live preview in an external browser, optionally shared on the LAN
+      for (const lan of [false, true]) {
+        const menuEvent = lan ? "menu:live-preview-lan" : "menu:live-preview";
+        const unlistenPreview = await currentWindow.listen<string>(menuEvent, async (event) => {
+          if (event.payload !== windowLabel) return;
+          flushActiveWysiwygNow();
+
+          await withReentryGuard(windowLabel, "export", async () => {
+            const doc = getActiveDocument(windowLabel);
+            if (!doc) return;
+            try {
+              const { startLivePreview } = await import("@/export");
+              const status = await startLivePreview(doc.content, doc.filePath, lan);
+              if (status.url) {
+                const { openUrl } = await import("@tauri-apps/plugin-opener");
+                await openUrl(status.url);
+              }
+              if (lan) {
+                if (status.lanUrl) {
+                  await navigator.clipboard.writeText(status.lanUrl);
+                  toast.success("Network preview link copied");
+                } else {
+                  toast.error("No network connection to share the preview on");
+                }
+              }
+              if (!doc.filePath) {
+                toast.info("Save the document to keep the preview updated");
+              }
+            } catch (error) {
+              console.error("[Menu] Failed to start live preview:", error);
+              toast.error("Failed to start live preview");
+            }
+          });
+        });
+        if (cancelled) { unlistenPreview(); return; }
+        unlistenRefs.current.push(unlistenPreview);
+      }
+
+      const unlistenStopPreview = await currentWindow.listen<string>("menu:stop-live-preview", async (event) => {
+        if (event.payload !== windowLabel) return;
+        try {
+          const { stopLivePreview } = await import("@/export");
+          await stopLivePreview();
+        } catch (error) {
+          console.error("[Menu] Failed to stop live preview:", error);
+        }
+      });
+      if (cancelled) { unlistenStopPreview(); return; }
+      unlistenRefs.current.push(unlistenStopPreview);
+
+      // Dated tasks as an .ics calendar that calendar apps can subscribe to
+      const unlistenTasksIcs = await currentWindow.listen<string>("menu:export-tasks-ics", async (event) => {
+        if (event.payload !== windowLabel) return;
+        const rootPath = useWorkspaceStore.getState().rootPath;
+        if (!rootPath) return;
+
+        await withReentryGuard(windowLabel, "export", async () => {
+          try {
+            const result = await exportTasksIcs(rootPath);
+            const overdue = overdueTasks(result.tasks, localDate()).length;
+            toast.success(`Exported ${result.tasks.length} dated tasks to the calendar`, {
+              description: overdue > 0 ? `${overdue} overdue` : undefined,
+              action: {
+                label: "Copy Feed URL",
+                onClick: () => void navigator.clipboard.writeText(calendarFeedUrl(result.path)),
+              },
+            });
+          } catch (error) {
+            console.error("[Menu] Failed to export tasks:", error);
+            toast.error(`Failed to export tasks: ${error}`);
+          }
+        });
+      });
+      if (cancelled) { unlistenTasksIcs(); return; }
+      unlistenRefs.current.push(unlistenTasksIcs);
     };
 
     setupListeners();
@@ -0,0 +1,85 @@
+/**
+ * Tests for useExternalFileChanges hook
+ *
+ * @module hooks/useExternalFileChanges.test
+ */
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { renderHook, waitFor } from "@testing-library/react";
+
+vi.mock("@/contexts/WindowContext", () => ({
+  useWindowLabel: vi.fn(() => "main"),
+}));
+
+vi.mock("@/stores/tabStore", () => ({
+  useTabStore: {
+    getState: vi.fn(() => ({ getTabsByWindow: () => [{ id: "tab-1" }] })),
+  },
+}));
+
+vi.mock("@/utils/fsChanges", () => ({
+  listenFsChanges: vi.fn(),
+}));
+
+vi.mock("@/utils/encryptedDocuments", () => ({
+  readDocumentFile: vi.fn(),
+}));
+
+vi.mock("@/utils/documentFiles", () => ({
+  getDiskDigest: vi.fn(),
+}));
+
+vi.mock("@/utils/saveToPath", () => ({
+  saveToPath: vi.fn(),
+}));
+
+vi.mock("@/utils/reloadFromDisk", () => ({
+  reloadTabFromDisk: vi.fn(),
+}));
+
+import { useExternalFileChanges } from "./useExternalFileChanges";
+import { useDocumentStore } from "@/stores/documentStore";
+import { listenFsChanges } from "@/utils/fsChanges";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
+import { getDiskDigest } from "@/utils/documentFiles";
+
+type ChangeHandler = (changes: unknown[]) => Promise<void>;
+
+describe("useExternalFileChanges", () => {
+  let handleChanges: ChangeHandler | null = null;
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    handleChanges = null;
+    vi.mocked(listenFsChanges).mockImplementation(async (handler) => {
+      handleChanges = handler as ChangeHandler;
+      return vi.fn();
+    });
+    useDocumentStore.setState({ documents: {} });
+    useDocumentStore.getState().initDocument("tab-1", "# Note", "/notes/a.md");
+  });
+
+  async function emitModify(digests?: Record<string, string>) {
+    renderHook(() => useExternalFileChanges());
+    await waitFor(() => expect(handleChanges).not.toBeNull());
+    await handleChanges!([{ watchId: "main", rootPath: "/notes", paths: ["/notes/a.md"], kind: "modify", digests }]);
+  }
+
+  it("ignores changes whose digest matches the bytes last read or written", async () => {
+    vi.mocked(getDiskDigest).mockReturnValue("abc");
+
+    await emitModify({ "/notes/a.md": "abc" });
+
+    expect(readDocumentFile).not.toHaveBeenCalled();
+    expect(useDocumentStore.getState().getDocument("tab-1")?.content).toBe("# Note");
+  });
+
+  it("reloads clean documents when the digest differs", async () => {
+    vi.mocked(getDiskDigest).mockReturnValue("abc");
+    vi.mocked(readDocumentFile).mockResolvedValue("# Edited elsewhere");
+
+    await emitModify({ "/notes/a.md": "def" });
+
+    expect(readDocumentFile).toHaveBeenCalledWith("/notes/a.md");
+    expect(useDocumentStore.getState().getDocument("tab-1")?.content).toBe("# Edited elsewhere");
+  });
+});
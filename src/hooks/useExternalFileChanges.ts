@@ -9,8 +9,7 @@
  * @module hooks/useExternalFileChanges
  */
 import { useEffect, useRef, useCallback } from "react";
-import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import { readTextFile } from "@tauri-apps/plugin-fs";
+import type { UnlistenFn } from "@tauri-apps/api/event";
 import { message, save } from "@tauri-apps/plugin-dialog";
 import { toast } from "sonner";
 import { useWindowLabel } from "@/contexts/WindowContext";
@@ -23,6 +22,9 @@ import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { reloadTabFromDisk } from "@/utils/reloadFromDisk";
 import { matchesPendingSave } from "@/utils/pendingSaves";
 import { getFileName } from "@/utils/paths";
+import { listenFsChanges } from "@/utils/fsChanges";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
+import { getDiskDigest } from "@/utils/documentFiles";
 
 /** Pending dirty file change awaiting user decision */
 interface PendingDirtyChange {
@@ -38,6 +40,10 @@ interface FsChangeEvent {
   rootPath: string;
   paths: string[];
   kind: "create" | "modify" | "remove" | "rename";
+  from?: string;
+  to?: string;
+  /** SHA-256 of changed files that could be hashed, by path */
+  digests?: Record<string, string>;
 }
 
 /**
@@ -239,10 +245,8 @@ export function useExternalFileChanges(): void {
     const setupListener = async () => {
       if (cancelled) return;
 
-      const unlisten = await listen<FsChangeEvent>("fs:changed", async (event) => {
-        if (cancelled) return;
-
-        const { kind, paths, watchId } = event.payload;
+      const handleChange = async (change: FsChangeEvent) => {
+        const { kind, paths, watchId } = change;
 
         // Only process events from this window's watcher (scoped by windowLabel)
         if (watchId !== windowLabel) return;
@@ -291,11 +295,17 @@ export function useExternalFileChanges(): void {
 
           // Handle file modification (create could be a recreation after delete)
           if (kind === "modify" || kind === "create") {
+            // Same bytes as we last read or wrote: a touch, nothing to reload
+            const digest = change.digests?.[changedPath];
+            if (digest && digest === getDiskDigest(changedPath)) {
+              continue;
+            }
+
             // Content-based verification: read file and compare
             // This eliminates false positives from file touches, sync services, etc.
             let diskContent: string;
             try {
-              diskContent = await readTextFile(changedPath);
+              diskContent = await readDocumentFile(changedPath);
             } catch {
               // File unreadable (might be deleted or locked) - skip
               continue;
@@ -336,6 +346,13 @@ export function useExternalFileChanges(): void {
             }
           }
         }
+      };
+
+      const unlisten = await listenFsChanges<FsChangeEvent>(async (changes) => {
+        for (const change of changes) {
+          if (cancelled) return;
+          await handleChange(change);
+        }
       });
 
       if (cancelled) {
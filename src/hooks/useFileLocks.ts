@@ -0,0 +1,89 @@
+/**
+ * Advisory locks for the documents open in this window, when "Lock open
+ * files" is enabled. A file someone else has locked prompts to take the
+ * lock over or keep editing without one; closing the tab releases the lock.
+ */
+import { useEffect, useRef } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { useTabStore } from "@/stores/tabStore";
+import { acquireFileLock, describeLock, forceUnlockFile, releaseFileLock } from "@/utils/fileLock";
+import { getFileName } from "@/utils/paths";
+
+/** Lock a document, asking what to do if someone else holds it. Resolves to whether we hold it. */
+async function lockDocument(path: string): Promise<boolean> {
+  const status = await acquireFileLock(path);
+  if (status.status !== "lockedByOther") return true;
+
+  const takeOver = await ask(
+    `${getFileName(path)} is in use. ${describeLock(status.info)}.\n\nEditing it at the same time may overwrite their changes.`,
+    { title: "File Locked", kind: "warning", okLabel: "Take Over Lock", cancelLabel: "Edit Without Lock" }
+  );
+  if (!takeOver) return false;
+  await forceUnlockFile(path, status.info);
+  return (await acquireFileLock(path)).status !== "lockedByOther";
+}
+
+function release(path: string) {
+  releaseFileLock(path).catch((error) => console.warn("[FileLock] Failed to release lock:", path, error));
+}
+
+export function useFileLocks(): void {
+  const windowLabel = useWindowLabel();
+  const enabled = useSettingsStore((s) => s.general.lockFilesOnOpen);
+  const tabs = useTabStore((s) => s.tabs[windowLabel]);
+
+  const held = useRef(new Set<string>());
+  /** Locked by someone else and opened anyway: don't ask again while open */
+  const skipped = useRef(new Set<string>());
+  const pending = useRef(new Set<string>());
+
+  useEffect(() => {
+    const openPaths = new Set<string>();
+    if (enabled) {
+      for (const tab of tabs ?? []) {
+        if (tab.filePath) openPaths.add(tab.filePath);
+      }
+    }
+    const isStillOpen = (path: string) =>
+      useSettingsStore.getState().general.lockFilesOnOpen &&
+      (useTabStore.getState().tabs[windowLabel] ?? []).some((tab) => tab.filePath === path);
+
+    for (const path of [...held.current]) {
+      if (openPaths.has(path)) continue;
+      held.current.delete(path);
+      release(path);
+    }
+    for (const path of [...skipped.current]) {
+      if (!openPaths.has(path)) skipped.current.delete(path);
+    }
+
+    for (const path of openPaths) {
+      if (held.current.has(path) || skipped.current.has(path) || pending.current.has(path)) continue;
+      pending.current.add(path);
+      lockDocument(path)
+        .then((locked) => {
+          if (!locked) {
+            skipped.current.add(path);
+          } else if (isStillOpen(path)) {
+            held.current.add(path);
+          } else {
+            // Closed while we were locking it
+            release(path);
+          }
+        })
+        .catch((error) => console.warn("[FileLock] Failed to lock:", path, error))
+        .finally(() => pending.current.delete(path));
+    }
+  }, [enabled, tabs, windowLabel]);
+
+  // Release everything when the window goes away
+  useEffect(() => {
+    const heldPaths = held.current;
+    return () => {
+      heldPaths.forEach(release);
+      heldPaths.clear();
+    };
+  }, []);
+}
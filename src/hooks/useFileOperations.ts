@@ -4,7 +4,7 @@ import { type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { invoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
-import { readTextFile, remove } from "@tauri-apps/plugin-fs";
+import { remove } from "@tauri-apps/plugin-fs";
 import { perfReset, perfStart, perfEnd, perfMark } from "@/utils/perfLog";
 import { useWindowLabel } from "@/contexts/WindowContext";
 import { useDocumentStore } from "@/stores/documentStore";
@@ -29,9 +29,11 @@ import { createUntitledTab } from "@/utils/newFile";
 import { joinPath } from "@/utils/pathUtils";
 import { getSaveFileName } from "@/utils/exportNaming";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
+import { applyEncryptionPolicy, readDocumentFile } from "@/utils/encryptedDocuments";
 import { isWithinRoot, getParentDir } from "@/utils/paths";
 import { saveAllDocuments, type CloseSaveContext } from "@/hooks/closeSave";
 import { safeUnlistenAll } from "@/utils/safeUnlisten";
+import { updateWorkspaceLinks } from "@/utils/zettel";
 
 /**
  * Move a tab to a new workspace window if the file is outside current workspace.
@@ -92,7 +94,7 @@ export function useFileOperations() {
 
       try {
         perfStart("readTextFile");
-        const content = await readTextFile(path);
+        const content = await readDocumentFile(path);
         perfEnd("readTextFile", { size: content.length });
 
         perfStart("initDocument");
@@ -157,7 +159,7 @@ export function useFileOperations() {
           // Replace the clean untitled tab with the file content
           try {
             perfStart("replace_tab:readTextFile");
-            const content = await readTextFile(path);
+            const content = await readDocumentFile(path);
             perfEnd("replace_tab:readTextFile", { size: content.length });
 
             perfStart("replace_tab:updateTabPath");
@@ -233,10 +235,11 @@ export function useFileOperations() {
         const folder = await getDefaultSaveFolderWithFallback(windowLabel);
         const defaultPath = joinPath(folder, filename);
 
-        const path = await save({
+        const chosenPath = await save({
           defaultPath,
           filters: [{ name: "Markdown", extensions: ["md"] }],
         });
+        const path = chosenPath && (doc.filePath ? chosenPath : await applyEncryptionPolicy(chosenPath));
         if (path) {
           const success = await saveToPath(tabId, path, doc.content, "manual");
           if (success) {
@@ -298,10 +301,12 @@ export function useFileOperations() {
         defaultPath = joinPath(folder, filename);
       }
 
-      const path = await save({
+      const chosenPath = await save({
         defaultPath,
         filters: [{ name: "Markdown", extensions: ["md"] }],
       });
+      // New documents follow the workspace encryption policy
+      const path = chosenPath && (doc.filePath ? chosenPath : await applyEncryptionPolicy(chosenPath));
       if (path) {
         const success = await saveToPath(tabId, path, doc.content, "manual");
         if (!success) return;
@@ -357,6 +362,7 @@ export function useFileOperations() {
           // File was saved to new location, but old file couldn't be deleted
           toast.warning("File saved to new location, but couldn't delete original file");
         }
+        await updateWorkspaceLinks(oldPath, newPath);
       }
 
       // If moved outside workspace, open in new window
@@ -34,8 +34,8 @@ vi.mock("@tauri-apps/api/core", () => ({
   invoke: (...args: unknown[]) => invokeMock(...args),
 }));
 
-vi.mock("@tauri-apps/plugin-fs", () => ({
-  readTextFile: vi.fn(),
+vi.mock("@/utils/encryptedDocuments", () => ({
+  readDocumentFile: vi.fn(),
 }));
 
 vi.mock("@/contexts/WindowContext", () => ({
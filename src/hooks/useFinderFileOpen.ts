@@ -17,7 +17,6 @@
  */
 import { useEffect, useRef } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
 import { useWindowLabel } from "@/contexts/WindowContext";
 import { useTabStore } from "@/stores/tabStore";
@@ -29,6 +28,7 @@ import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { openWorkspaceWithConfig } from "@/hooks/openWorkspaceWithConfig";
 import { isWithinRoot } from "@/utils/paths";
 import { waitForRestoreComplete } from "@/utils/hotExit/hotExitCoordination";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 interface OpenFilePayload {
   path: string;
@@ -54,7 +54,7 @@ async function loadFileIntoTab(
   path: string,
   isNewTab: boolean,
 ): Promise<void> {
-  const content = await readTextFile(path);
+  const content = await readDocumentFile(path);
   const meta = detectLinebreaks(content);
   if (isNewTab) {
     useDocumentStore.getState().initDocument(tabId, content, path);
@@ -3,6 +3,9 @@
  *
  * Orchestrates the full AI genie pipeline:
  * extract content → fill template → invoke provider → stream → create suggestion
+ *
+ * Translation genies (`translate:` in their frontmatter) check translation
+ * memory first and skip the provider on a hit.
  */
 
 import { useCallback, useEffect, useRef } from "react";
@@ -18,9 +21,14 @@ import { useEditorStore } from "@/stores/editorStore";
 import { useTiptapEditorStore } from "@/stores/tiptapEditorStore";
 import { useGeniesStore } from "@/stores/geniesStore";
 import { useTabStore } from "@/stores/tabStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { getExpandedSourcePeekRange, serializeSourcePeekRange } from "@/utils/sourcePeek";
 import { extractSurroundingContext } from "@/utils/extractContext";
 import { serializeMarkdown } from "@/utils/markdownPipeline";
+import { formatAiPromptError, isBudgetError } from "@/utils/aiUsage";
+import { buildContext } from "@/utils/notesContext";
+import { languageName } from "@/utils/languageDetection";
+import { lookupTranslation, storeTranslation, translationProviderKey } from "@/utils/translationMemory";
 
 // ============================================================================
 // Content Extraction
@@ -113,16 +121,61 @@ function formatContext(before: string, after: string): string {
   return parts.join("\n\n");
 }
 
-function fillTemplate(template: string, content: string, context?: string): string {
+const NOTES_VAR = /\{\{\s*notes\s*\}\}/;
+
+/** Excerpts from workspace notes relevant to `query`, or "" outside a workspace. */
+async function notesContext(query: string): Promise<string> {
+  const rootPath = useWorkspaceStore.getState().rootPath;
+  if (!rootPath) return "";
+  try {
+    return (await buildContext(query, rootPath)).context;
+  } catch (e) {
+    console.warn("[Genie] Failed to build notes context:", e);
+    return "";
+  }
+}
+
+const LANGUAGE_VAR = /\{\{\s*language\s*\}\}/;
+
+function fillTemplate(
+  template: string,
+  content: string,
+  context?: string,
+  notes?: string,
+  language?: string
+): string {
   let result = template.replace(/\{\{\s*content\s*\}\}/g, content);
   if (context !== undefined) {
     result = result.replace(/\{\{\s*context\s*\}\}/g, context);
   }
-  // Safety net: strip any {{context}} missed above (e.g., context undefined)
-  result = result.replace(/\{\{\s*context\s*\}\}/g, "");
+  if (notes !== undefined) {
+    result = result.replace(/\{\{\s*notes\s*\}\}/g, notes);
+  }
+  if (language !== undefined) {
+    result = result.replace(/\{\{\s*language\s*\}\}/g, language);
+  }
+  // Safety net: strip any {{context}}/{{notes}}/{{language}} missed above (e.g., context undefined)
+  result = result.replace(/\{\{\s*(context|notes|language)\s*\}\}/g, "");
   return result;
 }
 
+function addGenieSuggestion(
+  tabId: string,
+  extraction: ExtractionResult,
+  content: string,
+  action: GenieAction
+): void {
+  const isInsert = action === "insert";
+  useAiSuggestionStore.getState().addSuggestion({
+    tabId,
+    type: isInsert ? "insert" : "replace",
+    from: isInsert ? extraction.to : extraction.from,
+    to: extraction.to,
+    newContent: content,
+    originalContent: isInsert ? "" : extraction.text,
+  });
+}
+
 // ============================================================================
 // Hook
 // ============================================================================
@@ -147,7 +200,13 @@ export function useGenieInvocation() {
   }, [cancel]);
 
   const runGenie = useCallback(
-    async (filledPrompt: string, extraction: ExtractionResult, model?: string, action: GenieAction = "replace") => {
+    async (
+      filledPrompt: string,
+      extraction: ExtractionResult,
+      model?: string,
+      action: GenieAction = "replace",
+      translateTo?: string
+    ) => {
       const providerState = useAiProviderStore.getState();
       const provider = providerState.activeProvider;
       if (!provider) return; // Callers ensure provider exists
@@ -181,6 +240,16 @@ export function useGenieInvocation() {
       // Capture current tab ID for suggestion scoping
       const tabId = useTabStore.getState().activeTabId["main"] ?? "unknown";
 
+      // Repeated translations come from translation memory
+      const memoryProvider = translationProviderKey(provider, model ?? restConfig?.model);
+      if (translateTo) {
+        const cached = await lookupTranslation(extraction.text, translateTo, memoryProvider);
+        if (cached) {
+          addGenieSuggestion(tabId, extraction, cached, action);
+          return;
+        }
+      }
+
       // Try to acquire the invocation lock
       if (!useAiInvocationStore.getState().tryStart(requestId)) {
         return; // Already running
@@ -204,15 +273,10 @@ export function useGenieInvocation() {
         if (chunk.done) {
           // Create suggestion from accumulated result
           if (accumulated.trim()) {
-            const isInsert = action === "insert";
-            useAiSuggestionStore.getState().addSuggestion({
-              tabId,
-              type: isInsert ? "insert" : "replace",
-              from: isInsert ? extraction.to : extraction.from,
-              to: extraction.to,
-              newContent: accumulated.trim(),
-              originalContent: isInsert ? "" : extraction.text,
-            });
+            addGenieSuggestion(tabId, extraction, accumulated.trim(), action);
+            if (translateTo) {
+              void storeTranslation(extraction.text, translateTo, memoryProvider, accumulated.trim());
+            }
           }
           cancel();
         }
@@ -234,7 +298,11 @@ export function useGenieInvocation() {
           cliPath: cliInfo?.path ?? null,
         });
       } catch (e) {
-        toast.error(`Failed to invoke AI genie: ${e}`);
+        if (isBudgetError(e)) {
+          toast.error(e.message);
+        } else {
+          toast.error(`Failed to invoke AI genie: ${formatAiPromptError(e)}`);
+        }
         cancel();
       }
     },
@@ -270,12 +338,28 @@ export function useGenieInvocation() {
         ? formatContext(extracted.contextBefore ?? "", extracted.contextAfter ?? "")
         : undefined;
 
-      const filled = fillTemplate(genie.template, extracted.text, contextStr);
+      // Look up relevant notes only if template uses {{notes}}
+      const notesStr = NOTES_VAR.test(genie.template)
+        ? await notesContext(extracted.text)
+        : undefined;
+
+      // Detect the source language only if template uses {{language}}
+      const languageStr = LANGUAGE_VAR.test(genie.template)
+        ? await languageName(extracted.text, "the original language")
+        : undefined;
+
+      const filled = fillTemplate(genie.template, extracted.text, contextStr, notesStr, languageStr);
 
       // Track genie as recent
       useGeniesStore.getState().addRecent(genie.metadata.name);
 
-      await runGenie(filled, extracted, genie.metadata.model, genie.metadata.action ?? "replace");
+      await runGenie(
+        filled,
+        extracted,
+        genie.metadata.model,
+        genie.metadata.action ?? "replace",
+        genie.metadata.translate
+      );
     },
     [runGenie]
   );
@@ -108,6 +108,18 @@ export function useGenieShortcuts() {
     };
   }, []);
 
+  // Genie files changed on disk — the backend already rebuilt the menu
+  useEffect(() => {
+    const unlisten = listen("genies:changed", () => {
+      useGeniesStore.getState().loadGenies().catch((e) =>
+        console.error("[useGenieShortcuts] Failed to reload genies:", e)
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => {});
+    };
+  }, []);
+
   // "Reload Genies" menu item re-scans the genies folder
   useEffect(() => {
     const unlisten = listen("menu:reload-genies", () => {
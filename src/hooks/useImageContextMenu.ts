@@ -5,6 +5,7 @@
  * - Change Image: Opens file picker to replace the image
  * - Delete Image: Removes the image node
  * - Copy Image Path: Copies absolute path to clipboard
+ * - Copy Text from Image: Recognizes the image's text and copies it
  * - Reveal in Finder: Opens Finder at the image location
  */
 
@@ -17,6 +18,8 @@ import type { EditorView } from "@tiptap/pm/view";
 import { useImageContextMenuStore } from "@/stores/imageContextMenuStore";
 import { copyImageToAssets } from "@/hooks/useImageOperations";
 import { useDocumentFilePath } from "@/hooks/useDocumentState";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { extractTextFromImage } from "@/utils/ocr";
 
 type GetEditorView = () => EditorView | null;
 
@@ -149,6 +152,39 @@ export function useImageContextMenu(getEditorView: GetEditorView) {
           break;
         }
 
+        case "copyText": {
+          if (!filePath && !imageSrc.startsWith("/")) {
+            await message("Document must be saved to read text from its images.", {
+              kind: "warning",
+            });
+            return;
+          }
+
+          const absolutePath = filePath ? await resolveImagePath(imageSrc, filePath) : imageSrc;
+          if (!absolutePath || absolutePath.startsWith("http")) {
+            await message("Text can only be read from local images.", { kind: "warning" });
+            return;
+          }
+
+          const toastId = toast.loading("Reading text from image…");
+          try {
+            const text = await extractTextFromImage(
+              absolutePath,
+              useSettingsStore.getState().image.ocrLanguages
+            );
+            if (!text) {
+              toast.info("No text found in image", { id: toastId });
+              return;
+            }
+            await navigator.clipboard.writeText(text);
+            toast.success("Image text copied to clipboard", { id: toastId });
+          } catch (error) {
+            console.error("Failed to read image text:", error);
+            toast.error(String(error), { id: toastId });
+          }
+          break;
+        }
+
         case "revealInFinder": {
           if (!filePath) {
             await message("Document must be saved to reveal image.", {
@@ -14,7 +14,6 @@ import { mkdir, exists, copyFile, writeFile, readFile } from "@tauri-apps/plugin
 import { dirname, join } from "@tauri-apps/api/path";
 import type { EditorView } from "@tiptap/pm/view";
 import {
-  ASSETS_FOLDER,
   generateUniqueFilename,
   getFilename,
   buildAssetRelativePath,
@@ -22,13 +21,15 @@ import {
 import { computeDataHash } from "@/utils/imageHash";
 import { findExistingImage, registerImageHash } from "@/utils/imageHashRegistry";
 import { resizeImageIfNeeded } from "@/utils/imageResize";
+import { resolveAssetsDir } from "@/utils/workspaceEditorSettings";
 
 /**
- * Get the assets folder path relative to the document.
+ * Get the assets folder path for a document: next to it, or the
+ * workspace's assets folder if one is configured.
  */
 export async function getAssetsFolder(documentPath: string): Promise<string> {
   const docDir = await dirname(documentPath);
-  return join(docDir, ASSETS_FOLDER);
+  return join(docDir, resolveAssetsDir(documentPath));
 }
 
 /**
@@ -78,7 +79,7 @@ export async function saveImageToAssets(
   // Register hash for future deduplication
   await registerImageHash(documentPath, hash, filename);
 
-  return buildAssetRelativePath(filename);
+  return buildAssetRelativePath(filename, resolveAssetsDir(documentPath));
 }
 
 /**
@@ -123,7 +124,7 @@ export async function copyImageToAssets(
   // Register hash for future deduplication
   await registerImageHash(documentPath, hash, filename);
 
-  return buildAssetRelativePath(filename);
+  return buildAssetRelativePath(filename, resolveAssetsDir(documentPath));
 }
 
 /**
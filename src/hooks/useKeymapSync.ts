@@ -0,0 +1,22 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { loadMenuShortcutsFromKeymap } from "@/stores/shortcutsStore";
+
+/**
+ * Keep menu shortcut bindings in line with the backend keymap, on start
+ * and whenever it changes outside Settings (set_shortcut / reset_keymap).
+ */
+export function useKeymapSync(): void {
+  useEffect(() => {
+    const load = () =>
+      loadMenuShortcutsFromKeymap().catch((error) =>
+        console.warn("[Keymap] Failed to load menu shortcuts:", error)
+      );
+    void load();
+
+    const unlisten = listen("keymap:changed", () => void load());
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, []);
+}
@@ -0,0 +1,55 @@
+/**
+ * Live Preview Sync
+ *
+ * Re-renders the live preview when the backend reports that the previewed
+ * file changed on disk (saved here or edited elsewhere). Only the window
+ * that started the preview receives `preview:source-changed`.
+ *
+ * @module hooks/useLivePreviewSync
+ */
+import { useEffect } from "react";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+
+export function useLivePreviewSync(): void {
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    // Renders run one at a time; a change during a render triggers one more
+    let rendering = false;
+    let pending: string | null = null;
+
+    const refresh = async (path: string) => {
+      if (rendering) {
+        pending = path;
+        return;
+      }
+      rendering = true;
+      try {
+        // Dynamic import keeps export styles out of startup
+        const { refreshLivePreview } = await import("@/export");
+        await refreshLivePreview(path);
+      } catch (error) {
+        console.warn("[LivePreview] Failed to refresh:", error);
+      } finally {
+        rendering = false;
+        const next = pending;
+        pending = null;
+        if (next && !cancelled) void refresh(next);
+      }
+    };
+
+    getCurrentWebviewWindow()
+      .listen<string>("preview:source-changed", (event) => {
+        void refresh(event.payload);
+      })
+      .then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+}
@@ -9,6 +9,11 @@ import { useDocumentStore } from "@/stores/documentStore";
 import { useUIStore } from "@/stores/uiStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useSettingsStore } from "@/stores/settingsStore";
+import { useFlavorDialogStore } from "@/stores/flavorDialogStore";
+import { useReadabilityDialogStore } from "@/stores/readabilityDialogStore";
+import { useSymbolDialogStore } from "@/stores/symbolDialogStore";
+import { useTranscriptionDialogStore } from "@/stores/transcriptionDialogStore";
+import { copyTableAsCsv, importCsvTable } from "@/utils/tableDataCommands";
 import { clearAllHistory } from "@/hooks/useHistoryRecovery";
 import { historyLog } from "@/utils/debug";
 import { withReentryGuard } from "@/utils/reentryGuard";
@@ -21,7 +26,9 @@ const SHORTCUTS_URL = "https://vmark.app/guide/shortcuts";
 const REPORT_ISSUE_URL = "https://github.com/xiaolai/vmark/issues/new";
 
 /**
- * Handles miscellaneous menu events: preferences, history, and cleanup.
+ * Handles miscellaneous menu events: preferences, history, cleanup, audio
+ * transcription, the symbol picker, the readability report, Markdown
+ * flavor conversion, and CSV table import/copy.
  * View menu and recent files events are handled by separate hooks.
  */
 export function useMenuEvents(): void {
@@ -98,6 +105,53 @@ export function useMenuEvents(): void {
       if (cancelled) { unlistenCleanupImages(); return; }
       unlistenRefs.current.push(unlistenCleanupImages);
 
+      // Transcribe Audio - voice memo to text at the cursor
+      const unlistenTranscribe = await currentWindow.listen<string>("menu:transcribe-audio", (event) => {
+        if (event.payload !== windowLabel) return;
+        useTranscriptionDialogStore.getState().open();
+      });
+      if (cancelled) { unlistenTranscribe(); return; }
+      unlistenRefs.current.push(unlistenTranscribe);
+
+      // Emoji & Symbols - picker that inserts at the cursor
+      const unlistenSymbol = await currentWindow.listen<string>("menu:insert-symbol", (event) => {
+        if (event.payload !== windowLabel) return;
+        useSymbolDialogStore.getState().open();
+      });
+      if (cancelled) { unlistenSymbol(); return; }
+      unlistenRefs.current.push(unlistenSymbol);
+
+      // Readability Report - scores and paragraph heatmap for the document
+      const unlistenReadability = await currentWindow.listen<string>("menu:readability-report", (event) => {
+        if (event.payload !== windowLabel) return;
+        useReadabilityDialogStore.getState().open();
+      });
+      if (cancelled) { unlistenReadability(); return; }
+      unlistenRefs.current.push(unlistenReadability);
+
+      // Convert Markdown Flavor - CommonMark / GitHub / Obsidian syntax
+      const unlistenFlavor = await currentWindow.listen<string>("menu:convert-flavor", (event) => {
+        if (event.payload !== windowLabel) return;
+        useFlavorDialogStore.getState().open();
+      });
+      if (cancelled) { unlistenFlavor(); return; }
+      unlistenRefs.current.push(unlistenFlavor);
+
+      // Table ⇄ CSV
+      const unlistenImportCsv = await currentWindow.listen<string>("menu:import-csv-table", async (event) => {
+        if (event.payload !== windowLabel) return;
+        await withReentryGuard(windowLabel, "import-csv-table", importCsvTable);
+      });
+      if (cancelled) { unlistenImportCsv(); return; }
+      unlistenRefs.current.push(unlistenImportCsv);
+
+      const unlistenCopyCsv = await currentWindow.listen<string>("menu:copy-table-csv", async (event) => {
+        if (event.payload !== windowLabel) return;
+        await copyTableAsCsv();
+      });
+      if (cancelled) { unlistenCopyCsv(); return; }
+      unlistenRefs.current.push(unlistenCopyCsv);
+
       // Help menu items
       const unlistenVMarkHelp = await currentWindow.listen<string>("menu:vmark-help", async (event) => {
         if (event.payload !== windowLabel) return;
@@ -0,0 +1,78 @@
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { useEditorStore } from "@/stores/editorStore";
+import { useUIStore } from "@/stores/uiStore";
+import { useTabStore } from "@/stores/tabStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { useWindowLabel } from "@/contexts/WindowContext";
+
+/**
+ * Report this window's view toggles and available actions to the native
+ * menu so checkmarks and disabled items follow the focused window.
+ */
+export function useMenuStateSync(): void {
+  const windowLabel = useWindowLabel();
+  const hasTab = useTabStore((state) => Boolean(state.activeTabId[windowLabel]));
+  const hasFilePath = useTabStore((state) => {
+    const activeId = state.activeTabId[windowLabel];
+    const tab = state.tabs[windowLabel]?.find((t) => t.id === activeId);
+    return Boolean(tab?.filePath);
+  });
+  const hasWorkspace = useWorkspaceStore((state) => Boolean(state.rootPath));
+  const sourceMode = useEditorStore((state) => state.sourceMode);
+  const focusMode = useEditorStore((state) => state.focusModeEnabled);
+  const typewriterMode = useEditorStore((state) => state.typewriterModeEnabled);
+  const wordWrap = useEditorStore((state) => state.wordWrap);
+  const lineNumbers = useEditorStore((state) => state.showLineNumbers);
+  const diagramPreview = useEditorStore((state) => state.diagramPreviewEnabled);
+  const sidebar = useUIStore((state) => state.sidebarVisible);
+  const outline = useUIStore((state) => state.outlineVisible);
+  const terminal = useUIStore((state) => state.terminalVisible);
+
+  useEffect(() => {
+    const checked: Record<string, boolean> = {
+      "source-mode": sourceMode,
+      "focus-mode": focusMode,
+      "typewriter-mode": typewriterMode,
+      "word-wrap": wordWrap,
+      "line-numbers": lineNumbers,
+      "diagram-preview": diagramPreview,
+      sidebar,
+      outline,
+      "toggle-terminal": terminal,
+    };
+    for (const [id, value] of Object.entries(checked)) {
+      invoke("set_menu_item_checked", { id, checked: value }).catch((err) => {
+        console.warn("[MenuState] Failed to update menu item:", id, err);
+      });
+    }
+  }, [
+    sourceMode,
+    focusMode,
+    typewriterMode,
+    wordWrap,
+    lineNumbers,
+    diagramPreview,
+    sidebar,
+    outline,
+    terminal,
+  ]);
+
+  useEffect(() => {
+    const enabled: Record<string, boolean> = {
+      "save-as": hasTab,
+      "move-to": hasFilePath,
+      "quick-open": hasWorkspace,
+      "close-workspace": hasWorkspace,
+      "workspace-backups": hasWorkspace,
+      "workspace-insights": hasWorkspace,
+      "ask-workspace": hasWorkspace,
+      "export-tasks-ics": hasWorkspace,
+    };
+    for (const [id, value] of Object.entries(enabled)) {
+      invoke("set_menu_item_enabled", { id, enabled: value }).catch((err) => {
+        console.warn("[MenuState] Failed to update menu item:", id, err);
+      });
+    }
+  }, [hasTab, hasFilePath, hasWorkspace]);
+}
@@ -0,0 +1,51 @@
+/**
+ * Tests for notification action handling
+ *
+ * @module hooks/useNotificationEvents.test
+ */
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+const { emitMock, setFocusMock } = vi.hoisted(() => ({
+  emitMock: vi.fn(),
+  setFocusMock: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/api/webviewWindow", () => ({
+  getCurrentWebviewWindow: () => ({ emit: emitMock, setFocus: setFocusMock }),
+}));
+
+vi.mock("@/utils/reminders", () => ({
+  snoozeReminder: vi.fn(),
+}));
+
+import { snoozeReminder } from "@/utils/reminders";
+import { handleNotificationAction } from "./useNotificationEvents";
+
+describe("handleNotificationAction", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("opens the note for Open note and a click on the notification", async () => {
+    await handleNotificationAction("open", "r1", "/notes/a.md");
+    await handleNotificationAction("default", "r1", "/notes/a.md");
+
+    expect(emitMock).toHaveBeenCalledTimes(2);
+    expect(emitMock).toHaveBeenCalledWith("open-file", { path: "/notes/a.md" });
+    expect(setFocusMock).toHaveBeenCalled();
+  });
+
+  it("snoozes the reminder", async () => {
+    await handleNotificationAction("snooze", "r1", "/notes/a.md");
+
+    expect(snoozeReminder).toHaveBeenCalledWith("r1");
+    expect(emitMock).not.toHaveBeenCalled();
+  });
+
+  it("does nothing for dismiss", async () => {
+    await handleNotificationAction("dismiss", "r1", "/notes/a.md");
+
+    expect(snoozeReminder).not.toHaveBeenCalled();
+    expect(emitMock).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,98 @@
+/**
+ * Notification Events
+ *
+ * Handles presses on system notifications (`notification:action`): "Open
+ * note" or a click on the notification opens the note in this window. Where
+ * notifications can't carry buttons (`notification:shown`), the actions are
+ * offered on an in-app toast instead.
+ *
+ * Mounted in the main window only, so each press is handled once.
+ *
+ * @module hooks/useNotificationEvents
+ */
+import { useEffect } from "react";
+import { toast } from "sonner";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import {
+  snoozeReminder,
+  type NotificationActionEvent,
+  type NotificationRequest,
+} from "@/utils/reminders";
+
+const ACTION_OPEN = "open";
+const ACTION_SNOOZE = "snooze";
+const ACTION_DISMISS = "dismiss";
+
+async function openNote(path: string) {
+  const window = getCurrentWebviewWindow();
+  await window.setFocus();
+  // Same window-local event as a sidebar click
+  await window.emit("open-file", { path });
+}
+
+/** Carry out a notification action. Dismiss needs nothing: the reminder has already fired. */
+export async function handleNotificationAction(
+  actionId: string,
+  notificationId: string,
+  notePath: string | null | undefined
+): Promise<void> {
+  if ((actionId === ACTION_OPEN || actionId === "default") && notePath) {
+    await openNote(notePath);
+  } else if (actionId === ACTION_SNOOZE && notificationId) {
+    await snoozeReminder(notificationId);
+  }
+}
+
+function runAction(actionId: string, notificationId: string, notePath: string | null | undefined) {
+  handleNotificationAction(actionId, notificationId, notePath).catch((error) =>
+    console.warn("[Notifications] Action failed:", actionId, error)
+  );
+}
+
+/** In-app copy of a notification shown without buttons */
+function showActionToast(request: NotificationRequest) {
+  const notificationId = request.id ?? "";
+  // Closing the toast dismisses; the first two other actions become its buttons
+  const [primary, secondary] = (request.actions ?? []).filter((action) => action.id !== ACTION_DISMISS);
+  toast(request.title, {
+    description: request.body,
+    duration: Infinity,
+    action: primary && {
+      label: primary.label,
+      onClick: () => runAction(primary.id, notificationId, request.notePath),
+    },
+    cancel: secondary && {
+      label: secondary.label,
+      onClick: () => runAction(secondary.id, notificationId, request.notePath),
+    },
+  });
+}
+
+export function useNotificationEvents(): void {
+  useEffect(() => {
+    let cancelled = false;
+    const unlisteners: (() => void)[] = [];
+    const track = (promise: Promise<() => void>) =>
+      promise.then((fn) => {
+        if (cancelled) fn();
+        else unlisteners.push(fn);
+      });
+
+    void track(
+      listen<NotificationActionEvent>("notification:action", ({ payload }) =>
+        runAction(payload.actionId, payload.notificationId, payload.notePath)
+      )
+    );
+    void track(
+      listen<NotificationRequest>("notification:shown", ({ payload }) => {
+        if (payload.actions?.length) showActionToast(payload);
+      })
+    );
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, []);
+}
@@ -0,0 +1,37 @@
+/**
+ * Ollama Warmup
+ *
+ * When Ollama (API) is the active provider, loads its model in the
+ * background at startup and whenever the model or endpoint changes, so the
+ * first genie run doesn't wait for the model to load. With "Keep model
+ * loaded" on, the backend also keeps pinging it. Failures are ignored —
+ * the genie run reports them if Ollama really is unreachable.
+ *
+ * @module hooks/useOllamaWarmup
+ */
+import { useEffect } from "react";
+import { useAiProviderStore } from "@/stores/aiProviderStore";
+import { ollamaWarmup, setOllamaKeepAlive } from "@/utils/ollama";
+
+export function useOllamaWarmup(): void {
+  const activeProvider = useAiProviderStore((s) => s.activeProvider);
+  const keepWarm = useAiProviderStore((s) => s.ollamaKeepWarm);
+  const ollama = useAiProviderStore((s) => s.restProviders.find((p) => p.type === "ollama-api"));
+  const model = ollama?.model ?? "";
+  const endpoint = ollama?.endpoint ?? "";
+
+  useEffect(() => {
+    if (activeProvider !== "ollama-api" || !model) {
+      void setOllamaKeepAlive(null).catch(() => {});
+      return;
+    }
+    // Debounce so typing a model name doesn't load every prefix
+    const timer = setTimeout(() => {
+      void ollamaWarmup(model, endpoint).catch((error) => {
+        console.debug("[Ollama] Warmup failed:", error);
+      });
+      void setOllamaKeepAlive(keepWarm ? model : null, endpoint).catch(() => {});
+    }, 1000);
+    return () => clearTimeout(timer);
+  }, [activeProvider, model, endpoint, keepWarm]);
+}
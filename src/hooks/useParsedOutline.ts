@@ -0,0 +1,63 @@
+/**
+ * Parsed Outline
+ *
+ * Headings of documents too large to scan in the webview, from the backend
+ * block parser (see markdownAst.ts). The document is re-parsed once typing
+ * pauses; the parse runs off the main thread.
+ *
+ * @module hooks/useParsedOutline
+ */
+import { useEffect, useState } from "react";
+import type { HeadingItem } from "@/components/Sidebar/outlineUtils";
+import { getDocumentOutline, parseDocument, releaseParsedDocument } from "@/utils/markdownAst";
+
+/** Wait this long after the last change before parsing again */
+const PARSE_DELAY_MS = 500;
+
+const NO_HEADINGS: HeadingItem[] = [];
+
+/** Headings of `content`, parsed in the backend while `enabled`. */
+export function useParsedOutline(
+  tabId: string | null,
+  content: string,
+  enabled: boolean
+): HeadingItem[] {
+  const [headings, setHeadings] = useState<HeadingItem[]>(NO_HEADINGS);
+  const docId = tabId ? `outline:${tabId}` : null;
+
+  useEffect(() => {
+    if (!enabled || !docId) return;
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      try {
+        await parseDocument(docId, content);
+        const outline = await getDocumentOutline(docId);
+        if (cancelled) return;
+        setHeadings(
+          outline.map((block) => ({
+            level: block.level ?? 1,
+            text: block.text ?? "",
+            line: block.startLine,
+          }))
+        );
+      } catch (error) {
+        console.warn("[Outline] Failed to parse document:", error);
+      }
+    }, PARSE_DELAY_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [docId, content, enabled]);
+
+  // Forget the parsed copy when the tab changes or the outline closes
+  useEffect(() => {
+    if (!enabled || !docId) return;
+    return () => {
+      setHeadings(NO_HEADINGS);
+      releaseParsedDocument(docId).catch(() => {});
+    };
+  }, [docId, enabled]);
+
+  return enabled ? headings : NO_HEADINGS;
+}
@@ -0,0 +1,49 @@
+/**
+ * Pending Reveal
+ *
+ * Jumps to the line a `vmark://open?file=...&line=...` link asked for. The
+ * backend holds the line until the file is this window's active tab: it's
+ * checked whenever the active tab changes, and again when the backend
+ * reports a new link (the file may already be showing).
+ *
+ * @module hooks/usePendingReveal
+ */
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useDocumentStore } from "@/stores/documentStore";
+import { useTabStore } from "@/stores/tabStore";
+import { REVEAL_PENDING_EVENT, revealLine, takePendingReveal } from "@/utils/revealLine";
+
+export function usePendingReveal(): void {
+  const windowLabel = useWindowLabel();
+  const tabId = useTabStore((s) => s.activeTabId[windowLabel] ?? null);
+  const filePath = useDocumentStore((s) => (tabId ? s.documents[tabId]?.filePath : null) ?? null);
+
+  useEffect(() => {
+    if (!tabId || !filePath) return;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+
+    const check = () => {
+      takePendingReveal(filePath)
+        .then((line) => {
+          if (line && !cancelled) revealLine(tabId, line);
+        })
+        .catch((error) => {
+          console.debug("[Reveal] Failed to check pending line:", error);
+        });
+    };
+
+    check();
+    listen(REVEAL_PENDING_EVENT, check).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [tabId, filePath]);
+}
@@ -0,0 +1,104 @@
+/**
+ * Hook for publish menu event handling.
+ *
+ * Handles menu:publish-gist, menu:publish-webhook, menu:unpublish-gist and
+ * menu:unpublish-webhook for the active document. The backend returns the
+ * document with its publish metadata updated, which replaces the editor
+ * content (saving is left to the user).
+ *
+ * @module hooks/usePublishMenuEvents
+ */
+import { useEffect, useRef } from "react";
+import { type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { toast } from "sonner";
+import { useDocumentStore } from "@/stores/documentStore";
+import { getActiveTabId } from "@/utils/activeDocument";
+import { flushActiveWysiwygNow } from "@/utils/wysiwygFlush";
+import { withReentryGuard } from "@/utils/reentryGuard";
+import { safeUnlistenAll } from "@/utils/safeUnlisten";
+import {
+  publishDocument,
+  unpublishDocument,
+  type PublishRequest,
+  type PublishTarget,
+} from "@/utils/publish";
+
+const TARGET_NAMES: Record<PublishTarget, string> = {
+  gist: "GitHub Gist",
+  webhook: "webhook",
+};
+
+async function runPublish(
+  windowLabel: string,
+  target: PublishTarget,
+  unpublish: boolean
+): Promise<void> {
+  flushActiveWysiwygNow();
+  await withReentryGuard(windowLabel, "publish", async () => {
+    const tabId = getActiveTabId(windowLabel);
+    const doc = tabId ? useDocumentStore.getState().getDocument(tabId) : undefined;
+    if (!tabId || !doc) return;
+    try {
+      const request: PublishRequest = {
+        target,
+        content: doc.content,
+        fileName: doc.filePath ?? undefined,
+      };
+      const outcome = unpublish ? await unpublishDocument(request) : await publishDocument(request);
+      // Don't clobber edits made while the request was in flight
+      const current = useDocumentStore.getState().getDocument(tabId);
+      if (current?.content === doc.content && outcome.content !== doc.content) {
+        useDocumentStore.getState().setContent(tabId, outcome.content);
+      }
+      if (unpublish) {
+        toast.success(`Unpublished from ${TARGET_NAMES[target]}`);
+      } else {
+        toast.success(
+          outcome.url ? `Published: ${outcome.url}` : `Published to ${TARGET_NAMES[target]}`
+        );
+      }
+    } catch (error) {
+      console.error("[Publish] Failed:", error);
+      toast.error(String(error));
+    }
+  });
+}
+
+export function usePublishMenuEvents(): void {
+  const unlistenRefs = useRef<UnlistenFn[]>([]);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    const setupListeners = async () => {
+      unlistenRefs.current = safeUnlistenAll(unlistenRefs.current);
+      if (cancelled) return;
+
+      const currentWindow = getCurrentWebviewWindow();
+      const windowLabel = currentWindow.label;
+
+      const handlers: [string, PublishTarget, boolean][] = [
+        ["menu:publish-gist", "gist", false],
+        ["menu:publish-webhook", "webhook", false],
+        ["menu:unpublish-gist", "gist", true],
+        ["menu:unpublish-webhook", "webhook", true],
+      ];
+      for (const [eventName, target, unpublish] of handlers) {
+        const unlisten = await currentWindow.listen<string>(eventName, (event) => {
+          if (event.payload !== windowLabel) return;
+          void runPublish(windowLabel, target, unpublish);
+        });
+        if (cancelled) { unlisten(); return; }
+        unlistenRefs.current.push(unlisten);
+      }
+    };
+
+    setupListeners();
+
+    return () => {
+      cancelled = true;
+      unlistenRefs.current = safeUnlistenAll(unlistenRefs.current);
+    };
+  }, []);
+}
@@ -2,7 +2,6 @@ import { useEffect, useRef } from "react";
 import { type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { ask } from "@tauri-apps/plugin-dialog";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import { useDocumentStore } from "@/stores/documentStore";
@@ -15,6 +14,7 @@ import { getReplaceableTab } from "@/hooks/useReplaceableTab";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { openWorkspaceWithConfig } from "@/hooks/openWorkspaceWithConfig";
 import { safeUnlistenAll } from "@/utils/safeUnlisten";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Handles recent files menu events: open-recent-file, clear-recent.
@@ -85,7 +85,7 @@ export function useRecentFilesMenuEvents(): void {
 
             case "create_tab":
               try {
-                const content = await readTextFile(file.path);
+                const content = await readDocumentFile(file.path);
                 const tabId = useTabStore.getState().createTab(windowLabel, file.path);
                 useDocumentStore.getState().initDocument(tabId, content, file.path);
                 useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
@@ -104,7 +104,7 @@ export function useRecentFilesMenuEvents(): void {
 
             case "replace_tab":
               try {
-                const content = await readTextFile(file.path);
+                const content = await readDocumentFile(file.path);
                 useTabStore.getState().updateTabPath(result.tabId, result.filePath);
                 useDocumentStore.getState().loadContent(
                   result.tabId,
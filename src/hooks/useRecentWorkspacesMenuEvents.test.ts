@@ -21,7 +21,10 @@ vi.mock("@tauri-apps/plugin-dialog", () => ({
 
 vi.mock("@tauri-apps/plugin-fs", () => ({
   exists: vi.fn(() => Promise.resolve(true)),
-  readTextFile: vi.fn(() => Promise.resolve("# Test content")),
+}));
+
+vi.mock("@/utils/encryptedDocuments", () => ({
+  readDocumentFile: vi.fn(() => Promise.resolve("# Test content")),
 }));
 
 vi.mock("@tauri-apps/api/core", () => ({
@@ -2,7 +2,7 @@ import { useEffect, useRef } from "react";
 import { type UnlistenFn } from "@tauri-apps/api/event";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { ask } from "@tauri-apps/plugin-dialog";
-import { exists, readTextFile } from "@tauri-apps/plugin-fs";
+import { exists } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
 import { useRecentWorkspacesStore } from "@/stores/recentWorkspacesStore";
 import { useTabStore } from "@/stores/tabStore";
@@ -12,6 +12,7 @@ import { withReentryGuard } from "@/utils/reentryGuard";
 import { openWorkspaceWithConfig } from "@/hooks/openWorkspaceWithConfig";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { safeUnlistenAll } from "@/utils/safeUnlisten";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Handles recent workspaces menu events: open-recent-workspace, clear-recent-workspaces.
@@ -112,7 +113,7 @@ export function useRecentWorkspacesMenuEvents(): void {
             if (existing?.lastOpenTabs && existing.lastOpenTabs.length > 0) {
               for (const filePath of existing.lastOpenTabs) {
                 try {
-                  const content = await readTextFile(filePath);
+                  const content = await readDocumentFile(filePath);
                   const tabId = useTabStore.getState().createTab(windowLabel, filePath);
                   useDocumentStore.getState().initDocument(tabId, content, filePath);
                   useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
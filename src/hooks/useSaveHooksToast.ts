@@ -0,0 +1,35 @@
+/**
+ * Save Hooks Toast
+ *
+ * Tells the user when save hooks have filled in frontmatter for a note
+ * saved from this window (`save-hooks:applied`). The open document picks
+ * the change up through the usual external-change handling.
+ *
+ * @module hooks/useSaveHooksToast
+ */
+import { useEffect } from "react";
+import { toast } from "sonner";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import type { SaveHooksApplied } from "@/utils/saveHooks";
+
+export function useSaveHooksToast(): void {
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+
+    getCurrentWebviewWindow()
+      .listen<SaveHooksApplied>("save-hooks:applied", ({ payload }) => {
+        const name = payload.path.split(/[/\\]/).pop() ?? payload.path;
+        toast.info(`Added ${payload.fields.join(", ")} to ${name}`);
+      })
+      .then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+}
@@ -1,6 +1,6 @@
 import { beforeEach, describe, expect, it } from "vitest";
 import { useSettingsStore } from "@/stores/settingsStore";
-import { handleSettingsStorageEvent } from "./useSettingsSync";
+import { applyBackendSettings, handleSettingsStorageEvent } from "./useSettingsSync";
 
 // Helper to create a storage event with settings
 function createStorageEvent(newSettings: Record<string, unknown>): StorageEvent {
@@ -194,3 +194,23 @@ describe("useSettingsSync cross-window sync", () => {
     });
   });
 });
+
+describe("applyBackendSettings", () => {
+  it("applies changed groups and reports them", () => {
+    const changed = applyBackendSettings({
+      version: 1,
+      appearance: { ...useSettingsStore.getState().appearance, theme: "mint" },
+      general: useSettingsStore.getState().general,
+    });
+
+    expect(changed).toEqual(["appearance"]);
+    expect(useSettingsStore.getState().appearance.theme).toBe("mint");
+  });
+
+  it("keeps local keys the backend does not know", () => {
+    applyBackendSettings({ terminal: { fontSize: 15 } });
+
+    expect(useSettingsStore.getState().terminal.fontSize).toBe(15);
+    expect(useSettingsStore.getState().terminal.startupCommand).toBe("");
+  });
+});
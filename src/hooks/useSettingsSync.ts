@@ -1,4 +1,7 @@
 import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { useSettingsStore } from "@/stores/settingsStore";
 
 const STORAGE_KEY = "vmark-settings";
@@ -15,6 +18,7 @@ const SYNC_GROUPS = [
   "cjkFormatting",
   "advanced",
   "update",
+  "terminal",
 ] as const;
 
 type SyncGroup = (typeof SYNC_GROUPS)[number];
@@ -55,13 +59,103 @@ export function handleSettingsStorageEvent(event: StorageEvent): void {
   }
 }
 
+/** Payload of the backend "settings:changed" event */
+export interface SettingsChangedPayload {
+  path: string | null;
+  settings: Record<string, unknown>;
+  source: string | null;
+}
+
+/**
+ * Apply backend settings to the store, returning the groups that changed.
+ * Exported for testing.
+ */
+export function applyBackendSettings(settings: Record<string, unknown>): SyncGroup[] {
+  const currentState = useSettingsStore.getState();
+  const updates: Record<string, unknown> = {};
+  for (const group of SYNC_GROUPS) {
+    const newValue = settings[group];
+    if (newValue && JSON.stringify(currentState[group]) !== JSON.stringify(newValue)) {
+      updates[group] = { ...(currentState[group] as object), ...(newValue as object) };
+    }
+  }
+  if (Object.keys(updates).length > 0) {
+    useSettingsStore.setState(updates);
+  }
+  return Object.keys(updates) as SyncGroup[];
+}
+
 /**
- * Syncs settings across windows using storage events.
- * When one window updates localStorage, other windows receive the event.
+ * Syncs settings across windows.
+ *
+ * The backend settings store (settings.json) is the source of truth: local
+ * changes are sent with set_setting, and "settings:changed" from other
+ * windows is applied here. Storage events remain as a fallback for windows
+ * sharing localStorage.
  */
 export function useSettingsSync() {
   useEffect(() => {
     window.addEventListener("storage", handleSettingsStorageEvent);
     return () => window.removeEventListener("storage", handleSettingsStorageEvent);
   }, []);
+
+  useEffect(() => {
+    const label = getCurrentWebviewWindow().label;
+    // Set while applying backend values so they aren't echoed back
+    let applying = false;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+
+    const apply = (settings: Record<string, unknown>) => {
+      applying = true;
+      try {
+        applyBackendSettings(settings);
+      } finally {
+        applying = false;
+      }
+    };
+
+    const unsubscribeStore = useSettingsStore.subscribe((state, prev) => {
+      if (applying) return;
+      for (const group of SYNC_GROUPS) {
+        if (state[group] !== prev[group]) {
+          invoke("set_setting", { path: group, value: state[group] }).catch((err) => {
+            console.warn("[Settings] Failed to save setting group:", group, err);
+          });
+        }
+      }
+    });
+
+    (async () => {
+      unlisten = await listen<SettingsChangedPayload>("settings:changed", (event) => {
+        if (event.payload.source !== label) {
+          apply(event.payload.settings);
+        }
+      });
+      if (cancelled) {
+        unlisten();
+        return;
+      }
+
+      const snapshot = await invoke<{ settings: Record<string, unknown>; persisted: boolean }>(
+        "subscribe_settings"
+      );
+      if (snapshot.persisted) {
+        apply(snapshot.settings);
+      } else {
+        // First run with the backend store: seed it from this window's settings
+        const state = useSettingsStore.getState();
+        const seed = Object.fromEntries(SYNC_GROUPS.map((group) => [group, state[group]]));
+        await invoke("import_settings", { settings: seed });
+      }
+    })().catch((err) => {
+      console.warn("[Settings] Failed to connect to settings store:", err);
+    });
+
+    return () => {
+      cancelled = true;
+      unsubscribeStore();
+      unlisten?.();
+    };
+  }, []);
 }
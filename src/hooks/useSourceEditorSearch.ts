@@ -15,6 +15,11 @@ import {
 import { useSearchStore } from "@/stores/searchStore";
 import { runOrQueueCodeMirrorAction } from "@/utils/imeGuard";
 import { countMatches } from "@/utils/sourceEditorSearch";
+import {
+  countMatchesNative,
+  releaseFindDocument,
+  shouldUseNativeFind,
+} from "@/utils/nativeFind";
 
 interface SearchState {
   query: string;
@@ -39,20 +44,34 @@ function buildSearchQuery(state: SearchState): SearchQuery {
   });
 }
 
+// Bumped per native count so a slow, stale result doesn't overwrite a newer one
+let nativeCountGeneration = 0;
+
 /**
  * Recompute match count from document and update search store.
  * Centralizes the match counting logic to avoid duplication.
+ * Very large documents are counted in the backend when `tabId` is given.
  */
-function recomputeMatches(
+export function recomputeMatches(
   view: EditorView,
   state: SearchState,
-  preserveIndex = false
+  preserveIndex = false,
+  tabId: string | null = null
 ): void {
   if (!state.query) {
     useSearchStore.getState().setMatches(0, -1);
     return;
   }
 
+  if (tabId && shouldUseNativeFind(view.state.doc.length)) {
+    const generation = ++nativeCountGeneration;
+    void countMatchesNative(tabId, view.state.doc, state).then((matchCount) => {
+      if (generation !== nativeCountGeneration) return;
+      applyMatchCount(matchCount, useSearchStore.getState(), preserveIndex);
+    });
+    return;
+  }
+
   const text = view.state.doc.toString();
   const matchCount = countMatches(
     text,
@@ -61,7 +80,14 @@ function recomputeMatches(
     state.wholeWord,
     state.useRegex
   );
+  applyMatchCount(matchCount, state, preserveIndex);
+}
 
+function applyMatchCount(
+  matchCount: number,
+  state: SearchState,
+  preserveIndex: boolean
+): void {
   // Determine new index
   let newIndex: number;
   if (matchCount === 0) {
@@ -81,8 +107,17 @@ function recomputeMatches(
  * Subscribe to searchStore and manage CodeMirror search operations.
  */
 export function useSourceEditorSearch(
-  viewRef: MutableRefObject<EditorView | null>
+  viewRef: MutableRefObject<EditorView | null>,
+  tabId: string | null = null
 ): void {
+  // Drop the backend copy of a large document when the tab changes
+  useEffect(() => {
+    if (!tabId) return;
+    return () => {
+      void releaseFindDocument(tabId);
+    };
+  }, [tabId]);
+
   useEffect(() => {
     let isInitialized = false;
 
@@ -93,7 +128,7 @@ export function useSourceEditorSearch(
 
       const state = useSearchStore.getState();
       if (state.isOpen && state.query) {
-        recomputeMatches(view, state);
+        recomputeMatches(view, state, false, tabId);
         const query = buildSearchQuery(state);
         runOrQueueCodeMirrorAction(view, () => {
           view.dispatch({ effects: setSearchQuery.of(query) });
@@ -137,7 +172,7 @@ export function useSourceEditorSearch(
           runOrQueueCodeMirrorAction(view, () => {
             view.dispatch({ effects: setSearchQuery.of(query) });
           });
-          recomputeMatches(view, state);
+          recomputeMatches(view, state, false, tabId);
         } else {
           // Clear search
           runOrQueueCodeMirrorAction(view, () => {
@@ -177,7 +212,7 @@ export function useSourceEditorSearch(
         requestAnimationFrame(() => {
           const state = useSearchStore.getState();
           if (viewRef.current) {
-            recomputeMatches(viewRef.current, state, true);
+            recomputeMatches(viewRef.current, state, true, tabId);
           }
         });
       });
@@ -193,7 +228,7 @@ export function useSourceEditorSearch(
         requestAnimationFrame(() => {
           const state = useSearchStore.getState();
           if (viewRef.current) {
-            recomputeMatches(viewRef.current, state);
+            recomputeMatches(viewRef.current, state, false, tabId);
           }
         });
       });
@@ -207,5 +242,5 @@ export function useSourceEditorSearch(
       window.removeEventListener("search:replace-current", handleReplaceCurrent);
       window.removeEventListener("search:replace-all", handleReplaceAll);
     };
-  }, [viewRef]);
+  }, [viewRef, tabId]);
 }
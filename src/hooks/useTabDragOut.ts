@@ -5,7 +5,8 @@ const DRAG_OUT_THRESHOLD = 40;
 
 interface UseTabDragOutOptions {
   tabBarRef: RefObject<HTMLElement | null>;
-  onDragOut: (tabId: string) => void;
+  /** Called on drop with the pointer's screen position (logical px) */
+  onDragOut: (tabId: string, screenX: number, screenY: number) => void;
 }
 
 interface TabDragHandlers {
@@ -67,10 +68,10 @@ export function useTabDragOut({ tabBarRef, onDragOut }: UseTabDragOutOptions): U
           }
         };
 
-        const handleUp = () => {
+        const handleUp = (ev: PointerEvent) => {
           const s = stateRef.current;
           if (s.tabId && s.triggered) {
-            onDragOutRef.current(s.tabId);
+            onDragOutRef.current(s.tabId, ev.screenX, ev.screenY);
           }
           cleanup();
         };
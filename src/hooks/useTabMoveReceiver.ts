@@ -0,0 +1,91 @@
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useTabStore } from "@/stores/tabStore";
+import { useDocumentStore } from "@/stores/documentStore";
+import { useUnifiedHistoryStore } from "@/stores/unifiedHistoryStore";
+import { restoreTransferredTab, TransferConflictError } from "@/utils/hotExit/useHotExitRestore";
+import type { TabState } from "@/utils/hotExit/types";
+
+/** Pending move returned by claim_tab_moves (see tab_transfer.rs) */
+interface TabMove {
+  transferId: string;
+  sourceLabel: string;
+  targetLabel: string;
+  tab: TabState;
+}
+
+/** Payload of "tab:transfer-complete" sent to the source window */
+interface TransferComplete {
+  transferId: string;
+  tabId: string;
+  targetLabel: string;
+}
+
+/**
+ * Both ends of moving a tab between windows:
+ * - as target, pull moved tabs (on mount and on "tab:transfer-available"),
+ *   restore them and confirm with complete_tab_transfer
+ * - as source, drop the tab once the target confirms
+ */
+export function useTabMoveReceiver(): void {
+  const windowLabel = useWindowLabel();
+
+  useEffect(() => {
+    const currentWindow = getCurrentWebviewWindow();
+    let cancelled = false;
+
+    const receive = async () => {
+      const moves = await invoke<TabMove[]>("claim_tab_moves", { windowLabel });
+      for (const move of moves) {
+        if (cancelled) return;
+        try {
+          await restoreTransferredTab(windowLabel, move.tab);
+        } catch (err) {
+          // Leave the tab in its source window
+          await invoke("cancel_tab_transfer", { transferId: move.transferId }).catch(() => {});
+          if (err instanceof TransferConflictError) {
+            toast.warning(`${move.tab.title} has unsaved changes in both windows — save one first`);
+          } else {
+            console.error("[TabMove] Failed to receive tab:", err);
+          }
+          continue;
+        }
+        await invoke("complete_tab_transfer", { transferId: move.transferId }).catch((err) =>
+          console.error("[TabMove] Failed to confirm transfer:", err)
+        );
+      }
+    };
+
+    const release = (payload: TransferComplete) => {
+      const tabStore = useTabStore.getState();
+      tabStore.detachTab(windowLabel, payload.tabId);
+      useDocumentStore.getState().removeDocument(payload.tabId);
+      useUnifiedHistoryStore.getState().clearDocument(payload.tabId);
+
+      // A doc window left without tabs closes (main stays open)
+      if (windowLabel !== "main" && tabStore.getTabsByWindow(windowLabel).length === 0) {
+        invoke("close_window", { label: windowLabel }).catch(() => {});
+      }
+    };
+
+    const unlistenAvailable = currentWindow.listen<string>("tab:transfer-available", () => {
+      void receive();
+    });
+    const unlistenComplete = currentWindow.listen<TransferComplete>(
+      "tab:transfer-complete",
+      (event) => release(event.payload)
+    );
+
+    // Catch moves that arrived before the listeners were attached
+    void receive().catch(() => {});
+
+    return () => {
+      cancelled = true;
+      void unlistenAvailable.then((fn) => fn());
+      void unlistenComplete.then((fn) => fn());
+    };
+  }, [windowLabel]);
+}
@@ -16,6 +16,7 @@ import { useDocumentStore } from "@/stores/documentStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { findOrphanedImages, deleteOrphanedImages } from "@/utils/orphanAssetCleanup";
 import { clearDocumentHistory } from "@/hooks/useUnifiedHistory";
+import { lockIfUnused } from "@/utils/encryptedDocuments";
 
 /**
  * Clean up orphaned images for a document if setting is enabled.
@@ -63,6 +64,7 @@ export async function closeTabWithDirtyCheck(
     useTabStore.getState().closeTab(windowLabel, tabId);
     useDocumentStore.getState().removeDocument(tabId);
     clearDocumentHistory(tabId);
+    lockIfUnused(windowLabel, doc.filePath);
     return true;
   }
 
@@ -90,9 +92,11 @@ export async function closeTabWithDirtyCheck(
   }
 
   // Proceed to close
+  const closedPath = useDocumentStore.getState().getDocument(tabId)?.filePath ?? null;
   useTabStore.getState().closeTab(windowLabel, tabId);
   useDocumentStore.getState().removeDocument(tabId);
   clearDocumentHistory(tabId);
+  lockIfUnused(windowLabel, closedPath);
   return true;
 }
 
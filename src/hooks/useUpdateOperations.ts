@@ -14,6 +14,7 @@ import { emit } from "@tauri-apps/api/event";
 import { useUpdateStore } from "@/stores/updateStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { getVersion } from "@tauri-apps/api/app";
+import { isInRollout, newRolloutBucket } from "@/utils/updateRollout";
 
 // Event names for cross-window communication
 const EVENTS = {
@@ -104,7 +105,14 @@ export function useUpdateOperationHandler() {
     setStatus("checking");
 
     try {
-      const update = await check();
+      let bucket = useSettingsStore.getState().update.rolloutBucket;
+      if (bucket === null) {
+        bucket = newRolloutBucket();
+        updateUpdateSetting("rolloutBucket", bucket);
+      }
+      const found = await check();
+      // Staged releases skip installs outside the current rollout
+      const update = found && isInRollout(found.rawJson, bucket) ? found : null;
 
       if (update) {
         setPendingUpdate(update);
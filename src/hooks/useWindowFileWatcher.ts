@@ -5,6 +5,7 @@ import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { getDirectory } from "@/utils/pathUtils";
+import { WATCH_BATCH_WINDOW_MS } from "@/utils/fsChanges";
 
 /**
  * Start/stop a filesystem watcher for the current window.
@@ -15,6 +16,12 @@ export function useWindowFileWatcher(): void {
   const windowLabel = useWindowLabel();
   const isWorkspaceMode = useWorkspaceStore((state) => state.isWorkspaceMode);
   const rootPath = useWorkspaceStore((state) => state.rootPath);
+  const excludeFolders = useWorkspaceStore(
+    (state) => state.config?.excludeFolders
+  );
+  const showHiddenFiles = useWorkspaceStore(
+    (state) => state.config?.showHiddenFiles ?? false
+  );
   const activeTabId = useTabStore(
     (state) => state.activeTabId[windowLabel] ?? null
   );
@@ -41,16 +48,32 @@ export function useWindowFileWatcher(): void {
       return;
     }
 
-    invoke("start_watching", { watchId: windowLabel, path: watchPath }).catch(
-      (err) => {
-        console.warn("[Watcher] Failed to start watcher:", err);
-      }
-    );
+    const options = {
+      exclude: isWorkspaceMode ? excludeFolders ?? [] : [],
+      includeHidden: showHiddenFiles,
+      useGitignore: isWorkspaceMode,
+      // Bursts (checkouts, sync clients) arrive as one "fs:batch"
+      batchWindowMs: WATCH_BATCH_WINDOW_MS,
+    };
+
+    invoke("start_watching", {
+      watchId: windowLabel,
+      path: watchPath,
+      options,
+    }).catch((err) => {
+      console.warn("[Watcher] Failed to start watcher:", err);
+    });
 
     return () => {
       invoke("stop_watching", { watchId: windowLabel }).catch((err) => {
         console.warn("[Watcher] Failed to stop watcher on cleanup:", err);
       });
     };
-  }, [windowLabel, watchPath]);
+  }, [
+    windowLabel,
+    watchPath,
+    isWorkspaceMode,
+    excludeFolders,
+    showHiddenFiles,
+  ]);
 }
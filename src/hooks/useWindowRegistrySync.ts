@@ -0,0 +1,39 @@
+import { useEffect, useMemo } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { useWindowLabel } from "@/contexts/WindowContext";
+import { useTabStore } from "@/stores/tabStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+
+/**
+ * Report this window's open files and workspace root to the Rust window
+ * registry, so opening a file that is already open focuses this window
+ * instead of creating a duplicate.
+ */
+export function useWindowRegistrySync(): void {
+  const windowLabel = useWindowLabel();
+  const tabs = useTabStore((state) => state.tabs[windowLabel]);
+  const rootPath = useWorkspaceStore((state) =>
+    state.isWorkspaceMode ? state.rootPath : null
+  );
+
+  // Joined key so the effect only re-runs when the file set changes
+  const filesKey = useMemo(
+    () =>
+      (tabs ?? [])
+        .map((tab) => tab.filePath)
+        .filter((path): path is string => !!path)
+        .join("\n"),
+    [tabs]
+  );
+
+  useEffect(() => {
+    const files = filesKey ? filesKey.split("\n") : [];
+    invoke("update_window_state", {
+      windowLabel,
+      files,
+      workspaceRoot: rootPath,
+    }).catch((err) => {
+      console.warn("[WindowRegistry] Failed to update window state:", err);
+    });
+  }, [windowLabel, filesKey, rootPath]);
+}
@@ -0,0 +1,38 @@
+/**
+ * Runs scheduled backups for the open workspace (when configured) and
+ * reports failures while the backups dialog isn't showing them.
+ */
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { useBackupDialogStore } from "@/stores/backupDialogStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import {
+  describeBackupProgress,
+  startScheduledBackup,
+  stopScheduledBackup,
+  type BackupProgress,
+} from "@/utils/backup";
+
+export function useWorkspaceBackup() {
+  const rootPath = useWorkspaceStore((state) => state.rootPath);
+
+  useEffect(() => {
+    if (!rootPath) return;
+
+    startScheduledBackup(rootPath).catch((error) => {
+      console.error("[Backup] Failed to start scheduled backups:", error);
+    });
+
+    const unlistenPromise = listen<BackupProgress>("backup:progress", (event) => {
+      if (event.payload.rootPath !== rootPath || event.payload.stage !== "failed") return;
+      if (useBackupDialogStore.getState().isOpen) return;
+      toast.error(describeBackupProgress(event.payload));
+    });
+
+    return () => {
+      void stopScheduledBackup(rootPath);
+      void unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [rootPath]);
+}
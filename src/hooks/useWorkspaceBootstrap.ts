@@ -9,12 +9,12 @@
 import { useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { useWorkspaceStore, type WorkspaceConfig } from "@/stores/workspaceStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { needsBootstrap } from "@/utils/workspaceBootstrap";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Hook that bootstraps workspace config on startup.
@@ -52,7 +52,7 @@ export function useWorkspaceBootstrap() {
 
           for (const filePath of config.lastOpenTabs) {
             try {
-              const content = await readTextFile(filePath);
+              const content = await readDocumentFile(filePath);
               const tabId = useTabStore.getState().createTab(windowLabel, filePath);
               useDocumentStore.getState().initDocument(tabId, content, filePath);
               useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
@@ -2,17 +2,21 @@ import { useEffect, useRef } from "react";
 import { type UnlistenFn } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { ask, open } from "@tauri-apps/plugin-dialog";
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useUIStore } from "@/stores/uiStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useDocumentStore } from "@/stores/documentStore";
 import { useRecentWorkspacesStore } from "@/stores/recentWorkspacesStore";
+import { useQuickOpenStore } from "@/stores/quickOpenStore";
+import { useBackupDialogStore } from "@/stores/backupDialogStore";
+import { useInsightsDialogStore } from "@/stores/insightsDialogStore";
+import { useWorkspaceQaDialogStore } from "@/stores/workspaceQaDialogStore";
 import { persistWorkspaceSession } from "@/hooks/workspaceSession";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
 import { openWorkspaceWithConfig } from "@/hooks/openWorkspaceWithConfig";
 import { safeUnlistenAll } from "@/utils/safeUnlisten";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Hook to handle workspace-related menu events
@@ -84,7 +88,7 @@ export function useWorkspaceMenuEvents() {
           if (existing?.lastOpenTabs && existing.lastOpenTabs.length > 0) {
             for (const filePath of existing.lastOpenTabs) {
               try {
-                const content = await readTextFile(filePath);
+                const content = await readDocumentFile(filePath);
                 const tabId = useTabStore.getState().createTab(windowLabel, filePath);
                 useDocumentStore.getState().initDocument(tabId, content, filePath);
                 useDocumentStore.getState().setLineMetadata(tabId, detectLinebreaks(content));
@@ -104,6 +108,50 @@ export function useWorkspaceMenuEvents() {
       }
       unlistenRefs.current.push(unlistenOpenFolder);
 
+      // Quick Open - fuzzy file switcher for the workspace
+      const unlistenQuickOpen = await currentWindow.listen<string>("menu:quick-open", (event) => {
+        if (event.payload !== windowLabel) return;
+        useQuickOpenStore.getState().open();
+      });
+      if (cancelled) {
+        unlistenQuickOpen();
+        return;
+      }
+      unlistenRefs.current.push(unlistenQuickOpen);
+
+      // Workspace Backups - settings and snapshots
+      const unlistenBackups = await currentWindow.listen<string>("menu:workspace-backups", (event) => {
+        if (event.payload !== windowLabel) return;
+        useBackupDialogStore.getState().open();
+      });
+      if (cancelled) {
+        unlistenBackups();
+        return;
+      }
+      unlistenRefs.current.push(unlistenBackups);
+
+      // Workspace Insights - note, link and activity statistics
+      const unlistenInsights = await currentWindow.listen<string>("menu:workspace-insights", (event) => {
+        if (event.payload !== windowLabel) return;
+        useInsightsDialogStore.getState().open();
+      });
+      if (cancelled) {
+        unlistenInsights();
+        return;
+      }
+      unlistenRefs.current.push(unlistenInsights);
+
+      // Ask Workspace - questions answered from the workspace's notes
+      const unlistenAsk = await currentWindow.listen<string>("menu:ask-workspace", (event) => {
+        if (event.payload !== windowLabel) return;
+        useWorkspaceQaDialogStore.getState().open();
+      });
+      if (cancelled) {
+        unlistenAsk();
+        return;
+      }
+      unlistenRefs.current.push(unlistenAsk);
+
       // Close Workspace - save open tabs before closing
       const unlistenCloseWorkspace = await currentWindow.listen<string>(
         "menu:close-workspace",
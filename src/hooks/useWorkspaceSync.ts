@@ -0,0 +1,36 @@
+/**
+ * Runs background WebDAV sync for the open workspace (when configured)
+ * and reports conflicts and failures.
+ */
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { startScheduledSync, stopScheduledSync, type SyncStatus } from "@/utils/webdavSync";
+
+export function useWorkspaceSync() {
+  const rootPath = useWorkspaceStore((state) => state.rootPath);
+
+  useEffect(() => {
+    if (!rootPath) return;
+
+    startScheduledSync(rootPath).catch((error) => {
+      console.error("[Sync] Failed to start scheduled sync:", error);
+    });
+
+    const unlistenPromise = listen<SyncStatus>("sync:status", (event) => {
+      const { rootPath: root, state, summary, error } = event.payload;
+      if (root !== rootPath) return;
+      if (state === "failed" && error) {
+        toast.error(`Sync failed: ${error}`);
+      } else if (state === "done" && summary && summary.conflicts.length > 0) {
+        toast.warning(`Sync conflicts saved as: ${summary.conflicts.join(", ")}`);
+      }
+    });
+
+    return () => {
+      void stopScheduledSync(rootPath);
+      void unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [rootPath]);
+}
@@ -0,0 +1,40 @@
+/**
+ * Workspace Trust Sync
+ *
+ * Keeps this window's workspace trustLevel in step with the backend trust
+ * store: checked whenever the workspace root changes (including restored
+ * sessions), and again when trust is granted or revoked in any window.
+ *
+ * @module hooks/useWorkspaceTrust
+ */
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { syncWorkspaceTrust, WORKSPACE_TRUST_CHANGED_EVENT } from "@/utils/workspaceTrust";
+
+export function useWorkspaceTrust(): void {
+  const rootPath = useWorkspaceStore((s) => s.rootPath);
+
+  useEffect(() => {
+    if (!rootPath) return;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+
+    const sync = () => {
+      syncWorkspaceTrust(rootPath).catch((error) => {
+        console.debug("[Workspace] Failed to check trust:", error);
+      });
+    };
+
+    sync();
+    listen(WORKSPACE_TRUST_CHANGED_EVENT, sync).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [rootPath]);
+}
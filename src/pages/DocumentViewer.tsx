@@ -0,0 +1,85 @@
+/**
+ * Document Viewer Page
+ *
+ * Read-only rendered view of one file, opened with `create_preview_window`.
+ * Minimal chrome: a title strip and the rendered document, re-read whenever
+ * the file changes on disk.
+ */
+
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useTheme } from "@/hooks/useTheme";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { ExportSurface } from "@/export/ExportSurface";
+import { getFileName } from "@/utils/pathUtils";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
+
+const TITLEBAR_HEIGHT = 28;
+
+function viewerFilePath(): string | null {
+  return new URLSearchParams(window.location.search).get("file");
+}
+
+export function DocumentViewerPage() {
+  const [filePath] = useState(viewerFilePath);
+  const [markdown, setMarkdown] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const theme = useSettingsStore((state) => state.appearance.theme);
+
+  // Apply theme to this window
+  useTheme();
+
+  useEffect(() => {
+    if (!filePath) return;
+    let cancelled = false;
+
+    const load = () => {
+      readDocumentFile(filePath)
+        .then((content) => {
+          if (cancelled) return;
+          setMarkdown(content);
+          setError(null);
+        })
+        .catch((err) => !cancelled && setError(String(err)));
+    };
+    load();
+
+    invoke("watch_file", { path: filePath }).catch(() => {});
+    const unlisteners = [
+      listen<{ path: string }>("file:changed", (event) => {
+        if (event.payload.path === filePath) load();
+      }),
+      listen<{ path: string }>("file:removed", (event) => {
+        if (event.payload.path === filePath) setError("The file was deleted or moved");
+      }),
+    ];
+
+    return () => {
+      cancelled = true;
+      invoke("unwatch_file", { path: filePath }).catch(() => {});
+      unlisteners.forEach((p) => p.then((fn) => fn()));
+    };
+  }, [filePath]);
+
+  return (
+    <div className="flex h-screen flex-col bg-[var(--bg-primary)]">
+      <div
+        data-tauri-drag-region
+        className="flex shrink-0 items-center justify-center text-xs text-[var(--text-secondary)]"
+        style={{ height: TITLEBAR_HEIGHT }}
+      >
+        {filePath ? getFileName(filePath) : "Viewer"}
+        <span className="ml-2 text-[var(--text-tertiary)]">Read only</span>
+      </div>
+      <div className="min-h-0 flex-1 overflow-auto px-8 pb-8">
+        {error && (
+          <div className="py-2 text-xs text-[var(--error-color)]">{error}</div>
+        )}
+        {markdown !== null && (
+          <ExportSurface markdown={markdown} lightTheme={theme !== "night"} />
+        )}
+      </div>
+    </div>
+  );
+}
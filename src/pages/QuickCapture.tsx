@@ -0,0 +1,80 @@
+/**
+ * Quick Capture Page
+ *
+ * Small always-on-top window opened from the tray (or a global hotkey).
+ * Mod+Enter appends the note to the inbox file; Escape closes the window.
+ */
+
+import { useState, useRef, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useTheme } from "@/hooks/useTheme";
+import { isImeKeyEvent } from "@/utils/imeGuard";
+
+export function QuickCapturePage() {
+  const [text, setText] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+  const textareaRef = useRef<HTMLTextAreaElement>(null);
+
+  // Apply theme to this window
+  useTheme();
+
+  useEffect(() => {
+    textareaRef.current?.focus();
+  }, []);
+
+  const close = () => {
+    getCurrentWebviewWindow().close().catch(() => {});
+  };
+
+  const save = async () => {
+    if (!text.trim() || saving) return;
+    setSaving(true);
+    try {
+      await invoke<string>("append_quick_capture", { text });
+      setText("");
+      close();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent<HTMLTextAreaElement>) => {
+    if (isImeKeyEvent(e.nativeEvent)) return;
+    if (e.key === "Enter" && (e.metaKey || e.ctrlKey)) {
+      e.preventDefault();
+      void save();
+    } else if (e.key === "Escape") {
+      e.preventDefault();
+      close();
+    }
+  };
+
+  return (
+    <div className="flex h-screen flex-col gap-2 bg-[var(--bg-primary)] p-3">
+      <textarea
+        ref={textareaRef}
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        onKeyDown={handleKeyDown}
+        placeholder="Quick note…"
+        className="flex-1 resize-none rounded-md border border-gray-200 dark:border-gray-700
+                   bg-[var(--bg-secondary)] p-2 text-sm text-[var(--text-primary)] outline-none"
+      />
+      <div className="flex items-center justify-between text-xs text-[var(--text-secondary)]">
+        <span>{error ?? "⌘/Ctrl+Enter to save · Esc to close"}</span>
+        <button
+          type="button"
+          onClick={() => void save()}
+          disabled={!text.trim() || saving}
+          className="rounded-md bg-[var(--primary-color)] px-3 py-1 text-white disabled:opacity-50"
+        >
+          Save
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -23,6 +23,7 @@ import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { useTheme } from "@/hooks/useTheme";
 import { useUpdateListener } from "@/hooks/useUpdateSync";
+import { useKeymapSync } from "@/hooks/useKeymapSync";
 import { isImeKeyEvent } from "@/utils/imeGuard";
 import { safeUnlistenAsync } from "@/utils/safeUnlisten";
 
@@ -155,6 +156,8 @@ export function SettingsPage() {
   useDevSectionShortcut();
   // Listen for update state changes from main window
   useUpdateListener();
+  // Show menu shortcuts saved in keymap.json
+  useKeymapSync();
 
   // Listen for navigation events (e.g., from "Check for Updates" menu)
   useEffect(() => {
@@ -6,12 +6,30 @@
 
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
 import { toast } from "sonner";
-import { SettingRow, SettingsGroup, Toggle, TagInput } from "./components";
-import { useSettingsStore } from "@/stores/settingsStore";
+import { Button, Select, SettingRow, SettingsGroup, Toggle, TagInput } from "./components";
+import { useSettingsStore, type RedactionEntity, type SaveHook } from "@/stores/settingsStore";
 import { restartWithHotExit } from "@/utils/hotExit/restartWithHotExit";
 import type { SessionData } from "@/utils/hotExit/types";
 
+const REDACTION_ENTITIES: { entity: RedactionEntity; label: string }[] = [
+  { entity: "email", label: "Email addresses" },
+  { entity: "apiKey", label: "API keys and tokens" },
+  { entity: "phone", label: "Phone numbers" },
+  { entity: "ipAddress", label: "IP addresses" },
+];
+
+const COOLDOWN_OPTIONS = [
+  { value: "300", label: "5 minutes" },
+  { value: "600", label: "10 minutes" },
+  { value: "1800", label: "30 minutes" },
+  { value: "3600", label: "1 hour" },
+  { value: "86400", label: "1 day" },
+];
+
+const RUNS_PER_HOUR_OPTIONS = ["5", "10", "20", "50"].map((v) => ({ value: v, label: v }));
+
 /**
  * Helper to wrap async operations with error handling
  */
@@ -34,8 +52,49 @@ export function AdvancedSettings() {
   const [isBusy, setIsBusy] = useState(false);
   const customLinkProtocols = useSettingsStore((state) => state.advanced.customLinkProtocols);
   const keepBothEditorsAlive = useSettingsStore((state) => state.advanced.keepBothEditorsAlive);
+  const tls = useSettingsStore((state) => state.advanced.tls);
+  const redaction = useSettingsStore((state) => state.advanced.promptRedaction);
+  const saveHooks = useSettingsStore((state) => state.advanced.saveHooks);
+  const semanticSearch = useSettingsStore((state) => state.advanced.semanticSearch);
+  const [embeddingEndpoint, setEmbeddingEndpoint] = useState(semanticSearch.endpoint);
+  const [newHook, setNewHook] = useState<SaveHook>({ genie: "", field: "", list: false });
   const updateAdvancedSetting = useSettingsStore((state) => state.updateAdvancedSetting);
 
+  const toggleRedactionEntity = (entity: RedactionEntity, on: boolean) => {
+    const entities = redaction.entities.filter((e) => e !== entity);
+    updateAdvancedSetting("promptRedaction", {
+      ...redaction,
+      entities: on ? [...entities, entity] : entities,
+    });
+  };
+
+  const setHooks = (hooks: SaveHook[]) =>
+    updateAdvancedSetting("saveHooks", { ...saveHooks, hooks });
+
+  const addHook = () => {
+    const hook = { ...newHook, genie: newHook.genie.trim(), field: newHook.field.trim() };
+    if (!hook.genie || !hook.field) return;
+    setHooks([...saveHooks.hooks.filter((h) => h.field !== hook.field), hook]);
+    setNewHook({ genie: "", field: "", list: false });
+  };
+
+  const saveEmbeddingEndpoint = () => {
+    const endpoint = embeddingEndpoint.trim();
+    if (endpoint === semanticSearch.endpoint) return;
+    updateAdvancedSetting("semanticSearch", { ...semanticSearch, endpoint });
+  };
+
+  const chooseCaBundle = async () => {
+    const path = await open({
+      multiple: false,
+      title: "Choose CA certificate bundle",
+      filters: [{ name: "Certificates", extensions: ["pem", "crt", "cer"] }],
+    });
+    if (typeof path === "string") {
+      updateAdvancedSetting("tls", { ...tls, caBundlePath: path });
+    }
+  };
+
   return (
     <div>
       <SettingsGroup title="Developer">
@@ -60,6 +119,166 @@ export function AdvancedSettings() {
         </div>
       </SettingsGroup>
 
+      <SettingsGroup title="Network">
+        <SettingRow
+          label="Custom CA certificates"
+          description={tls.caBundlePath ?? "Trust extra root certificates (PEM) for self-hosted endpoints"}
+        >
+          <div className="flex gap-2">
+            {tls.caBundlePath && (
+              <Button onClick={() => updateAdvancedSetting("tls", { ...tls, caBundlePath: null })}>
+                Clear
+              </Button>
+            )}
+            <Button onClick={chooseCaBundle}>Choose...</Button>
+          </div>
+        </SettingRow>
+        <div className="py-2.5">
+          <div className="text-sm font-medium text-[var(--text-primary)] mb-1">
+            Skip certificate verification
+          </div>
+          <div className="text-xs text-[var(--text-tertiary)] mb-2">
+            Hosts to connect to without verifying TLS certificates (e.g., ollama.local, 10.0.0.5:11434).
+            Only use this for servers you control.
+          </div>
+          <TagInput
+            value={tls.insecureHosts ?? []}
+            onChange={(v) => updateAdvancedSetting("tls", { ...tls, insecureHosts: v })}
+            placeholder="Add host..."
+          />
+        </div>
+      </SettingsGroup>
+
+      <SettingsGroup title="Prompt Privacy">
+        <SettingRow
+          label="Redact prompts to remote AI providers"
+          description="Replace sensitive text with placeholders before sending, and restore it in the reply"
+        >
+          <Toggle
+            checked={redaction.enabled}
+            onChange={(v) => updateAdvancedSetting("promptRedaction", { ...redaction, enabled: v })}
+          />
+        </SettingRow>
+        {REDACTION_ENTITIES.map(({ entity, label }) => (
+          <SettingRow key={entity} label={label} disabled={!redaction.enabled}>
+            <Toggle
+              checked={redaction.entities.includes(entity)}
+              onChange={(v) => toggleRedactionEntity(entity, v)}
+              disabled={!redaction.enabled}
+            />
+          </SettingRow>
+        ))}
+        <div className="py-2.5">
+          <div className="text-sm font-medium text-[var(--text-primary)] mb-1">Names and terms</div>
+          <div className="text-xs text-[var(--text-tertiary)] mb-2">
+            People, clients or projects to hide, matched as whole words
+          </div>
+          <TagInput
+            value={redaction.names}
+            onChange={(v) => updateAdvancedSetting("promptRedaction", { ...redaction, names: v })}
+            placeholder="Add name..."
+          />
+        </div>
+        <div className="py-2.5">
+          <div className="text-sm font-medium text-[var(--text-primary)] mb-1">Custom patterns</div>
+          <div className="text-xs text-[var(--text-tertiary)] mb-2">
+            Regular expressions for anything else (e.g., INV-\d+)
+          </div>
+          <TagInput
+            value={redaction.patterns}
+            onChange={(v) => updateAdvancedSetting("promptRedaction", { ...redaction, patterns: v })}
+            placeholder="Add pattern..."
+          />
+        </div>
+      </SettingsGroup>
+
+      <SettingsGroup title="Save Hooks">
+        <SettingRow
+          label="Fill in frontmatter on save"
+          description="Run genies in the background to add missing fields (e.g., summary, keywords) to saved notes"
+        >
+          <Toggle
+            checked={saveHooks.enabled}
+            onChange={(v) => updateAdvancedSetting("saveHooks", { ...saveHooks, enabled: v })}
+          />
+        </SettingRow>
+        {saveHooks.hooks.map((hook) => (
+          <SettingRow
+            key={hook.field}
+            label={hook.field}
+            description={`${hook.genie}${hook.list ? " (list)" : ""}`}
+            disabled={!saveHooks.enabled}
+          >
+            <Button onClick={() => setHooks(saveHooks.hooks.filter((h) => h !== hook))}>Remove</Button>
+          </SettingRow>
+        ))}
+        <div className="py-2.5 flex items-center gap-2">
+          <input
+            value={newHook.field}
+            onChange={(e) => setNewHook({ ...newHook, field: e.target.value })}
+            placeholder="Field"
+            className="w-28 px-2 py-1 rounded border border-[var(--border-color)] bg-[var(--bg-primary)] text-sm text-[var(--text-primary)]"
+          />
+          <input
+            value={newHook.genie}
+            onChange={(e) => setNewHook({ ...newHook, genie: e.target.value })}
+            placeholder="Genie (e.g., metadata/summary.md)"
+            className="flex-1 px-2 py-1 rounded border border-[var(--border-color)] bg-[var(--bg-primary)] text-sm text-[var(--text-primary)]"
+          />
+          <label className="flex items-center gap-1 text-xs text-[var(--text-secondary)]">
+            <input
+              type="checkbox"
+              checked={newHook.list}
+              onChange={(e) => setNewHook({ ...newHook, list: e.target.checked })}
+            />
+            List
+          </label>
+          <Button onClick={addHook} disabled={!newHook.genie.trim() || !newHook.field.trim()}>
+            Add
+          </Button>
+        </div>
+        <SettingRow
+          label="Wait between runs"
+          description="Minimum time before the same note is processed again"
+          disabled={!saveHooks.enabled}
+        >
+          <Select
+            value={String(saveHooks.cooldownSecs)}
+            options={COOLDOWN_OPTIONS}
+            onChange={(v) => updateAdvancedSetting("saveHooks", { ...saveHooks, cooldownSecs: Number(v) })}
+            disabled={!saveHooks.enabled}
+          />
+        </SettingRow>
+        <SettingRow
+          label="Runs per hour"
+          description="Genie runs allowed per hour across all notes"
+          disabled={!saveHooks.enabled}
+        >
+          <Select
+            value={String(saveHooks.maxRunsPerHour)}
+            options={RUNS_PER_HOUR_OPTIONS}
+            onChange={(v) => updateAdvancedSetting("saveHooks", { ...saveHooks, maxRunsPerHour: Number(v) })}
+            disabled={!saveHooks.enabled}
+          />
+        </SettingRow>
+      </SettingsGroup>
+
+      <SettingsGroup title="Semantic Search">
+        <SettingRow
+          label="Embedding endpoint"
+          description="Ollama server that embeds notes for semantic search and notes context. Text sent off this machine is redacted as above."
+        >
+          <input
+            value={embeddingEndpoint}
+            onChange={(e) => setEmbeddingEndpoint(e.target.value)}
+            onBlur={saveEmbeddingEndpoint}
+            onKeyDown={(e) => e.key === "Enter" && saveEmbeddingEndpoint()}
+            placeholder="http://localhost:11434"
+            className="w-56 px-2 py-1 rounded border border-[var(--border-color)] bg-[var(--bg-primary)] text-sm text-[var(--text-primary)]"
+          />
+        </SettingRow>
+      </SettingsGroup>
+
       <SettingsGroup title="Performance">
         <SettingRow
           label="Keep both editors alive"
@@ -1,7 +1,7 @@
 /**
  * Editor Settings Section
  *
- * Typography, display, behavior, and whitespace configuration.
+ * Typography, display, behavior, whitespace, and focus timer configuration.
  */
 
 import {
@@ -11,6 +11,7 @@ import {
   type CopyFormat,
 } from "@/stores/settingsStore";
 import { SettingRow, SettingsGroup, Select, Toggle } from "./components";
+import { FocusTimerSettings } from "./FocusTimerSettings";
 
 /** Shared option for system default */
 const SYSTEM_DEFAULT = { value: "system", label: "System Default" };
@@ -236,6 +237,42 @@ export function EditorSettings() {
             onChange={(v) => updateMarkdownSetting("copyOnSelect", v)}
           />
         </SettingRow>
+        <SettingRow
+          label="Fetch titles for pasted links"
+          description="Pasting a URL fetches the page title and inserts [Title](url)"
+        >
+          <Toggle
+            checked={markdown.fetchLinkTitles ?? false}
+            onChange={(v) => updateMarkdownSetting("fetchLinkTitles", v)}
+          />
+        </SettingRow>
+        <SettingRow
+          label="Preview web links"
+          description="The link popup shows the page's title, description and image"
+        >
+          <Toggle
+            checked={markdown.linkPreviews ?? false}
+            onChange={(v) => updateMarkdownSetting("linkPreviews", v)}
+          />
+        </SettingRow>
+        <SettingRow
+          label="Stamp export metadata"
+          description="Add title, author, date, git commit and VMark version to HTML, PDF and pandoc exports"
+        >
+          <Toggle
+            checked={markdown.embedExportMetadata ?? false}
+            onChange={(v) => updateMarkdownSetting("embedExportMetadata", v)}
+          />
+        </SettingRow>
+        <SettingRow
+          label="Smart typography in exports"
+          description="Use curly quotes, dashes and ellipses in HTML, PDF and pandoc exports, in the style of the document's language"
+        >
+          <Toggle
+            checked={markdown.smartTypographyOnExport ?? false}
+            onChange={(v) => updateMarkdownSetting("smartTypographyOnExport", v)}
+          />
+        </SettingRow>
       </SettingsGroup>
 
       {/* Whitespace */}
@@ -287,6 +324,11 @@ export function EditorSettings() {
           />
         </SettingRow>
       </SettingsGroup>
+
+      {/* Focus Timer */}
+      <SettingsGroup title="Focus Timer">
+        <FocusTimerSettings />
+      </SettingsGroup>
     </div>
   );
 }
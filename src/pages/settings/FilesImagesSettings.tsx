@@ -1,13 +1,15 @@
 /**
  * Files & Images Settings Section
  *
- * File browser, auto-save, document history, and image configuration.
+ * File browser, auto-save, document history, image configuration, and
+ * text recognition.
  */
 
 import { SettingRow, SettingsGroup, Toggle, Select } from "./components";
 import { useWorkspaceStore } from "@/stores/workspaceStore";
 import { useSettingsStore, type ImageAutoResizeOption } from "@/stores/settingsStore";
 import { updateWorkspaceConfig } from "@/hooks/workspaceConfig";
+import { OcrLanguageSettings } from "./OcrLanguageSettings";
 
 const autoResizeOptions: { value: string; label: string }[] = [
   { value: "0", label: "Off" },
@@ -122,6 +124,15 @@ export function FilesImagesSettings() {
             disabled={!general.historyEnabled}
           />
         </SettingRow>
+        <SettingRow
+          label="Lock open files"
+          description="Warn others on a shared drive that a file is being edited"
+        >
+          <Toggle
+            checked={general.lockFilesOnOpen}
+            onChange={(v) => updateGeneralSetting("lockFilesOnOpen", v)}
+          />
+        </SettingRow>
       </SettingsGroup>
 
       {/* Images */}
@@ -160,6 +171,11 @@ export function FilesImagesSettings() {
           />
         </SettingRow>
       </SettingsGroup>
+
+      {/* Text Recognition */}
+      <SettingsGroup title="Text Recognition">
+        <OcrLanguageSettings />
+      </SettingsGroup>
     </div>
   );
 }
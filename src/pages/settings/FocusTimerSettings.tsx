@@ -0,0 +1,90 @@
+/**
+ * Focus timer (pomodoro) durations and behaviour, plus focus time over the
+ * last week. The config lives in the backend, next to the timer itself.
+ */
+
+import { useEffect, useState } from "react";
+import { toast } from "sonner";
+import {
+  formatFocusTime,
+  getPomodoroConfig,
+  getPomodoroStats,
+  setPomodoroConfig,
+  type DailyStats,
+  type PomodoroConfig,
+} from "@/utils/pomodoro";
+import { Select, SettingRow, Toggle } from "./components";
+
+/** Preset minutes, plus the current value if it was set to something else */
+const minuteOptions = (values: number[], current: number) =>
+  [...new Set([...values, current])]
+    .sort((a, b) => a - b)
+    .map((v) => ({ value: String(v), label: `${v} minutes` }));
+
+export function FocusTimerSettings() {
+  const [config, setConfig] = useState<PomodoroConfig | null>(null);
+  const [week, setWeek] = useState<DailyStats[]>([]);
+
+  useEffect(() => {
+    getPomodoroConfig()
+      .then(setConfig)
+      .catch((error) => console.warn("[Pomodoro] Failed to load config:", error));
+    getPomodoroStats(7)
+      .then(setWeek)
+      .catch((error) => console.warn("[Pomodoro] Failed to load stats:", error));
+  }, []);
+
+  if (!config) return null;
+
+  const update = <K extends keyof PomodoroConfig>(key: K, value: PomodoroConfig[K]) => {
+    const next = { ...config, [key]: value };
+    setConfig(next);
+    setPomodoroConfig(next).catch((error) => toast.error(String(error)));
+  };
+
+  const weekSeconds = week.reduce((sum, day) => sum + day.focusSeconds, 0);
+  const weekSessions = week.reduce((sum, day) => sum + day.sessions, 0);
+
+  return (
+    <>
+      <SettingRow label="Focus length">
+        <Select
+          value={String(config.workMinutes)}
+          options={minuteOptions([15, 20, 25, 30, 45, 50, 60], config.workMinutes)}
+          onChange={(v) => update("workMinutes", Number(v))}
+        />
+      </SettingRow>
+      <SettingRow label="Short break">
+        <Select
+          value={String(config.shortBreakMinutes)}
+          options={minuteOptions([3, 5, 10], config.shortBreakMinutes)}
+          onChange={(v) => update("shortBreakMinutes", Number(v))}
+        />
+      </SettingRow>
+      <SettingRow label="Long break" description="Taken after the number of sessions below">
+        <Select
+          value={String(config.longBreakMinutes)}
+          options={minuteOptions([10, 15, 20, 30], config.longBreakMinutes)}
+          onChange={(v) => update("longBreakMinutes", Number(v))}
+        />
+      </SettingRow>
+      <SettingRow label="Sessions before a long break">
+        <Select
+          value={String(config.sessionsBeforeLongBreak)}
+          options={[2, 3, 4, 5, 6].map((v) => ({ value: String(v), label: String(v) }))}
+          onChange={(v) => update("sessionsBeforeLongBreak", Number(v))}
+        />
+      </SettingRow>
+      <SettingRow label="Continue automatically" description="Start the next phase without waiting">
+        <Toggle checked={config.autoContinue} onChange={(v) => update("autoContinue", v)} />
+      </SettingRow>
+      <SettingRow label="Notify when a phase ends">
+        <Toggle checked={config.notifications} onChange={(v) => update("notifications", v)} />
+      </SettingRow>
+      <div className="py-2.5 text-xs text-[var(--text-tertiary)]">
+        Last 7 days: {formatFocusTime(weekSeconds)} focused in {weekSessions}{" "}
+        {weekSessions === 1 ? "session" : "sessions"}
+      </div>
+    </>
+  );
+}
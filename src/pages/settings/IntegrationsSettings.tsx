@@ -1,12 +1,12 @@
 /**
  * Integrations Settings Section
  *
- * MCP server and AI assistant integration settings.
+ * MCP server, AI assistant and external tool integration settings.
  */
 
-import { useState, useEffect } from "react";
+import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { SettingRow, Toggle, SettingsGroup, CopyButton, Select } from "./components";
+import { SettingRow, Toggle, SettingsGroup, CopyButton, Select, Button } from "./components";
 import type { McpToolMode } from "@/stores/settingsStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { useMcpServer } from "@/hooks/useMcpServer";
@@ -17,6 +17,34 @@ import { McpConfigInstaller } from "./McpConfigInstaller";
 import { RefreshCw, Users, ExternalLink } from "lucide-react";
 import type { ProviderType } from "@/types/aiGenies";
 import { RestProviderConfigFields } from "./RestProviderConfigFields";
+import { OllamaModelSettings } from "./OllamaModelSettings";
+import {
+  captureCurlExample,
+  getCaptureServerStatus,
+  regenerateCaptureServerToken,
+  setCaptureServerEnabled,
+  type CaptureServerStatus,
+} from "@/utils/captureServer";
+import {
+  listTools,
+  mergeToolStatus,
+  onToolStatusChanged,
+  toolStatusLabel,
+  type ToolStatus,
+} from "@/utils/toolRegistry";
+import {
+  getUsageSummary,
+  setUsageLimits,
+  usageFor,
+  type ProviderLimits,
+  type UsageSummary,
+} from "@/utils/aiUsage";
+import {
+  deletePublishToken,
+  hasPublishToken,
+  setPublishToken,
+  setPublishWebhook,
+} from "@/utils/publish";
 
 function StatusBadge({ running, loading }: { running: boolean; loading: boolean }) {
   if (loading) {
@@ -281,10 +309,237 @@ export function IntegrationsSettings() {
       <div className="mt-6">
         <AiProviderSettings />
       </div>
+
+      <div className="mt-6">
+        <AiUsageSettings />
+      </div>
+
+      <div className="mt-6">
+        <CaptureServerSettings />
+      </div>
+
+      <div className="mt-6">
+        <PublishingSettings />
+      </div>
+
+      <div className="mt-6">
+        <ExternalToolsSettings />
+      </div>
     </div>
   );
 }
 
+// ============================================================================
+// Capture Server Settings
+// ============================================================================
+
+function CaptureServerSettings() {
+  const [status, setStatus] = useState<CaptureServerStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getCaptureServerStatus()
+      .then(setStatus)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  const update = (request: Promise<CaptureServerStatus>) => {
+    request
+      .then((next) => {
+        setStatus(next);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  };
+
+  const example = status?.running ? captureCurlExample(status) : null;
+
+  return (
+    <SettingsGroup title="Capture Server">
+      <SettingRow
+        label="Accept captures from browser extensions"
+        description="Local endpoint that appends POSTed markdown and links to your inbox"
+      >
+        <div className="flex items-center gap-3">
+          <StatusBadge running={!!status?.running} loading={false} />
+          <Toggle
+            checked={!!status?.enabled}
+            onChange={(enabled) => update(setCaptureServerEnabled(enabled))}
+            disabled={!status}
+          />
+        </div>
+      </SettingRow>
+
+      <SettingRow label="Captures per minute" description="Further requests are refused">
+        <Select<string>
+          value={String(status?.maxPerMinute ?? 30)}
+          options={["10", "30", "60", "120"].map((value) => ({ value, label: value }))}
+          onChange={(value) =>
+            status && update(setCaptureServerEnabled(status.enabled, Number(value)))
+          }
+          disabled={!status}
+        />
+      </SettingRow>
+
+      {error && (
+        <div className="mt-2 text-xs text-[var(--error-color)]">
+          {error}
+        </div>
+      )}
+
+      {status?.running && status.port && status.token && (
+        <div className="mt-4 pt-3 border-t border-[var(--border-color)] text-xs">
+          <div className="flex items-center justify-between">
+            <span className="text-[var(--text-tertiary)]">Endpoint</span>
+            <span className="flex items-center gap-1.5">
+              <code className="font-mono text-[var(--text-secondary)]">
+                http://127.0.0.1:{status.port}/capture
+              </code>
+              <CopyButton text={`http://127.0.0.1:${status.port}/capture`} />
+            </span>
+          </div>
+          <div className="flex items-center justify-between mt-1.5">
+            <span className="text-[var(--text-tertiary)]">Token</span>
+            <span className="flex items-center gap-1.5">
+              <code className="font-mono text-[var(--text-secondary)]">
+                {status.token.slice(0, 8)}…
+              </code>
+              <CopyButton text={status.token} />
+              <button
+                onClick={() => update(regenerateCaptureServerToken())}
+                title="Generate a new token"
+                className="text-[var(--text-tertiary)] hover:text-[var(--text-color)]"
+              >
+                <RefreshCw size={12} />
+              </button>
+            </span>
+          </div>
+          {example && (
+            <div className="flex items-center justify-between mt-1.5">
+              <span className="text-[var(--text-tertiary)]">Try it</span>
+              <CopyButton text={example} />
+            </div>
+          )}
+        </div>
+      )}
+    </SettingsGroup>
+  );
+}
+
+// ============================================================================
+// Publishing Settings
+// ============================================================================
+
+const publishInputClass = `w-56 px-2 py-1 text-xs rounded
+  bg-[var(--bg-tertiary)] text-[var(--text-color)]
+  border border-[var(--border-color)]
+  focus:border-[var(--primary-color)] outline-none font-mono`;
+
+function PublishingSettings() {
+  const [hasGist, setHasGist] = useState(false);
+  const [hasWebhook, setHasWebhook] = useState(false);
+  const [gistToken, setGistToken] = useState("");
+  const [webhookUrl, setWebhookUrl] = useState("");
+  const [webhookToken, setWebhookToken] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(() => {
+    Promise.all([hasPublishToken("gist"), hasPublishToken("webhook")])
+      .then(([gist, webhook]) => {
+        setHasGist(gist);
+        setHasWebhook(webhook);
+      })
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  useEffect(() => refresh(), [refresh]);
+
+  const run = (request: Promise<void>, clear: () => void) => {
+    request
+      .then(() => {
+        clear();
+        setError(null);
+        refresh();
+      })
+      .catch((err) => setError(String(err)));
+  };
+
+  return (
+    <SettingsGroup title="Publishing">
+      <SettingRow
+        label="GitHub token"
+        description={hasGist ? "Stored in the keychain" : "Token with the gist scope, for File → Publish"}
+      >
+        <div className="flex items-center gap-1.5">
+          <input
+            className={publishInputClass}
+            type="password"
+            placeholder={hasGist ? "••••••••" : "ghp_…"}
+            value={gistToken}
+            onChange={(e) => setGistToken(e.target.value)}
+          />
+          <Button
+            disabled={!gistToken.trim()}
+            onClick={() => run(setPublishToken("gist", gistToken), () => setGistToken(""))}
+          >
+            Save
+          </Button>
+          {hasGist && (
+            <Button variant="danger" onClick={() => run(deletePublishToken("gist"), () => {})}>
+              Remove
+            </Button>
+          )}
+        </div>
+      </SettingRow>
+
+      <SettingRow
+        label="Webhook"
+        description={hasWebhook ? "Stored in the keychain" : "https endpoint that receives published documents"}
+      >
+        <div className="flex flex-col items-end gap-1.5">
+          <input
+            className={publishInputClass}
+            placeholder="https://…"
+            value={webhookUrl}
+            onChange={(e) => setWebhookUrl(e.target.value)}
+          />
+          <div className="flex items-center gap-1.5">
+            <input
+              className={publishInputClass}
+              type="password"
+              placeholder="Bearer token (optional)"
+              value={webhookToken}
+              onChange={(e) => setWebhookToken(e.target.value)}
+            />
+            <Button
+              disabled={!webhookUrl.trim()}
+              onClick={() =>
+                run(setPublishWebhook(webhookUrl, webhookToken || null), () => {
+                  setWebhookUrl("");
+                  setWebhookToken("");
+                })
+              }
+            >
+              Save
+            </Button>
+            {hasWebhook && (
+              <Button variant="danger" onClick={() => run(deletePublishToken("webhook"), () => {})}>
+                Remove
+              </Button>
+            )}
+          </div>
+        </div>
+      </SettingRow>
+
+      {error && (
+        <div className="mt-2 text-xs text-[var(--error-color)]">
+          {error}
+        </div>
+      )}
+    </SettingsGroup>
+  );
+}
+
 // ============================================================================
 // AI Provider Settings
 // ============================================================================
@@ -431,6 +686,9 @@ function AiProviderSettings() {
                   model={p.model}
                 />
               )}
+              {isActive && p.type === "ollama-api" && (
+                <OllamaModelSettings endpoint={p.endpoint} />
+              )}
             </div>
           );
         })}
@@ -438,3 +696,168 @@ function AiProviderSettings() {
     </SettingsGroup>
   );
 }
+
+// ============================================================================
+// AI Usage Limits
+// ============================================================================
+
+const LIMIT_FIELDS: { key: keyof ProviderLimits; label: string; description: string }[] = [
+  { key: "requestsPerMinute", label: "Requests per minute", description: "Refuse prompts above this rate" },
+  { key: "monthlyTokens", label: "Monthly token budget", description: "Input plus output tokens" },
+  { key: "monthlyCost", label: "Monthly cost budget", description: "Estimated spend in USD" },
+  { key: "inputPrice", label: "Input price", description: "USD per million input tokens" },
+  { key: "outputPrice", label: "Output price", description: "USD per million output tokens" },
+];
+
+const limitInputClass = `w-24 px-2 py-1 text-xs text-right rounded
+  bg-[var(--bg-tertiary)] text-[var(--text-color)]
+  border border-[var(--border-color)]
+  focus:border-[var(--primary-color)] outline-none`;
+
+function AiUsageSettings() {
+  const activeProvider = useAiProviderStore((s) => s.activeProvider);
+  const [summary, setSummary] = useState<UsageSummary | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getUsageSummary()
+      .then(setSummary)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  if (!activeProvider) return null;
+
+  const usage = usageFor(summary, activeProvider);
+
+  const saveLimit = (key: keyof ProviderLimits, raw: string) => {
+    const value = raw.trim() === "" ? null : Number(raw);
+    if (value !== null && (!Number.isFinite(value) || value < 0)) return;
+    if (value === usage.limits[key]) return;
+    setUsageLimits(activeProvider, { ...usage.limits, [key]: value })
+      .then((next) => {
+        setSummary(next);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  };
+
+  return (
+    <SettingsGroup title="AI Usage Limits">
+      <div className="text-xs text-[var(--text-tertiary)] mb-2">
+        This month: {usage.requests} requests,{" "}
+        {(usage.inputTokens + usage.outputTokens).toLocaleString()} tokens, ${usage.cost.toFixed(2)}
+      </div>
+
+      {summary && LIMIT_FIELDS.map(({ key, label, description }) => (
+        <SettingRow key={`${activeProvider}-${key}`} label={label} description={description}>
+          <input
+            type="number"
+            min={0}
+            className={limitInputClass}
+            placeholder="No limit"
+            defaultValue={usage.limits[key] ?? ""}
+            onBlur={(e) => saveLimit(key, e.target.value)}
+          />
+        </SettingRow>
+      ))}
+
+      {error && (
+        <div className="mt-2 text-xs text-[var(--error-color)]">
+          {error}
+        </div>
+      )}
+    </SettingsGroup>
+  );
+}
+
+// ============================================================================
+// External Tools
+// ============================================================================
+
+function ExternalToolsSettings() {
+  const [tools, setTools] = useState<ToolStatus[]>([]);
+  const [checking, setChecking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listTools()
+      .then(setTools)
+      .catch((err) => setError(String(err)));
+
+    const unlisten = onToolStatusChanged((changed) => {
+      setTools((current) => mergeToolStatus(current, changed));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const recheck = () => {
+    setChecking(true);
+    listTools(true)
+      .then((next) => {
+        setTools(next);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)))
+      .finally(() => setChecking(false));
+  };
+
+  return (
+    <SettingsGroup title="External Tools">
+      <div className="flex items-center justify-between mb-2">
+        <span className="text-xs text-[var(--text-tertiary)]">
+          Optional programs used for some imports, exports and OCR
+        </span>
+        <button
+          onClick={recheck}
+          disabled={checking}
+          className="flex items-center gap-1 text-xs text-[var(--primary-color)] hover:underline disabled:opacity-50"
+        >
+          <RefreshCw size={12} className={checking ? "animate-spin" : ""} />
+          Check again
+        </button>
+      </div>
+
+      {tools.map((tool) => (
+        <SettingRow key={tool.id} label={tool.name} description={tool.purpose}>
+          <div className="flex flex-col items-end gap-0.5 text-xs">
+            <span
+              className={
+                tool.available && !tool.outdated
+                  ? "text-[var(--success-color)]"
+                  : "text-[var(--text-tertiary)]"
+              }
+              title={tool.path ?? undefined}
+            >
+              {toolStatusLabel(tool)}
+            </span>
+            {(!tool.available || tool.outdated) && (
+              <span className="flex items-center gap-1.5">
+                <code className="font-mono text-[var(--text-secondary)]">
+                  {tool.installHint.command}
+                </code>
+                <CopyButton text={tool.installHint.command} />
+                <a
+                  href={tool.installHint.url}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                  title={tool.installHint.message}
+                  className="text-[var(--primary-color)]"
+                >
+                  <ExternalLink size={10} />
+                </a>
+              </span>
+            )}
+          </div>
+        </SettingRow>
+      ))}
+
+      {error && (
+        <div className="mt-2 text-xs text-[var(--error-color)]">
+          {error}
+        </div>
+      )}
+    </SettingsGroup>
+  );
+}
@@ -11,7 +11,7 @@ import { SettingsGroup, Button, CopyButton } from "./components";
 import { McpConfigPreviewDialog } from "./McpConfigPreviewDialog";
 import { getFileName, normalizePath } from "@/utils/paths";
 
-type DiagnosticStatus = "Valid" | "PathMismatch" | "BinaryMissing" | "NotConfigured";
+type DiagnosticStatus = "Valid" | "PathMismatch" | "PortMismatch" | "BinaryMissing" | "NotConfigured";
 
 interface ProviderDiagnostic {
   provider: string;
@@ -22,6 +22,8 @@ interface ProviderDiagnostic {
   expectedBinaryPath: string | null;
   configuredBinaryPath: string | null;
   binaryExists: boolean;
+  configuredPort: number | null;
+  bridgePort: number | null;
   status: DiagnosticStatus;
   message: string;
 }
@@ -58,6 +60,7 @@ function DiagnosticIcon({ status }: { status: DiagnosticStatus }) {
         </span>
       );
     case "PathMismatch":
+    case "PortMismatch":
       return (
         <span className="w-4 h-4 text-[var(--warning-color)]">
           <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
@@ -114,8 +117,9 @@ interface ProviderRowProps {
 }
 
 function ProviderRow({ diagnostic, onPreview, onRepair, onUninstall, loading }: ProviderRowProps) {
-  const showRepairButton = diagnostic.status === "PathMismatch";
-  const showUpdateRemove = diagnostic.hasVmark && diagnostic.status !== "PathMismatch";
+  const needsRepair = diagnostic.status === "PathMismatch" || diagnostic.status === "PortMismatch";
+  const showRepairButton = needsRepair;
+  const showUpdateRemove = diagnostic.hasVmark && !needsRepair;
   const showInstall = !diagnostic.hasVmark;
 
   return (
@@ -0,0 +1,195 @@
+/**
+ * Text recognition options, shown under Images: which tesseract languages
+ * Copy Text from Image uses, and downloading or removing language packs.
+ */
+
+import { useCallback, useEffect, useState } from "react";
+import { Download, Loader2, RefreshCw, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { useSettingsStore } from "@/stores/settingsStore";
+import {
+  detectTesseract,
+  downloadOcrLanguage,
+  downloadPercent,
+  listOcrLanguages,
+  onOcrDownloadProgress,
+  removeOcrLanguage,
+  type OcrInfo,
+  type OcrLanguage,
+} from "@/utils/ocr";
+
+const inputClass = `flex-1 px-2 py-1 text-xs rounded
+  bg-[var(--bg-tertiary)] text-[var(--text-color)]
+  border border-[var(--border-color)]
+  focus:border-[var(--primary-color)] outline-none
+  font-mono`;
+
+const iconBtnClass = `shrink-0 p-1 rounded
+  text-[var(--text-secondary)] hover:text-[var(--text-color)]
+  hover:bg-[var(--hover-bg)] cursor-pointer
+  disabled:opacity-50 disabled:cursor-default`;
+
+const buttonClass = `shrink-0 flex items-center gap-1 px-2 py-1 text-xs rounded
+  text-[var(--text-secondary)] hover:text-[var(--text-color)]
+  hover:bg-[var(--hover-bg)] cursor-pointer
+  disabled:opacity-50 disabled:cursor-default`;
+
+export function OcrLanguageSettings() {
+  const selected = useSettingsStore((s) => s.image.ocrLanguages);
+  const updateImageSetting = useSettingsStore((s) => s.updateImageSetting);
+  const [info, setInfo] = useState<OcrInfo | null>(null);
+  const [languages, setLanguages] = useState<OcrLanguage[] | null>(null);
+  const [listError, setListError] = useState<string | null>(null);
+  const [downloadCode, setDownloadCode] = useState("");
+  const [downloading, setDownloading] = useState<string | null>(null);
+  const [status, setStatus] = useState("");
+  const [removing, setRemoving] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const detected = await detectTesseract();
+      setInfo(detected);
+      setLanguages(detected.available ? await listOcrLanguages() : []);
+      setListError(null);
+    } catch (error) {
+      setLanguages(null);
+      setListError(String(error));
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    if (!downloading) return;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    onOcrDownloadProgress((progress) => {
+      if (progress.lang !== downloading) return;
+      const percent = downloadPercent(progress);
+      setStatus(percent === null ? "Downloading…" : `Downloading — ${percent}%`);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [downloading]);
+
+  const toggleLanguage = (code: string, use: boolean) => {
+    const next = use ? [...selected, code] : selected.filter((c) => c !== code);
+    updateImageSetting("ocrLanguages", next);
+  };
+
+  const handleDownload = async () => {
+    const code = downloadCode.trim();
+    if (!code || downloading) return;
+    setDownloading(code);
+    setStatus("Starting…");
+    try {
+      await downloadOcrLanguage(code);
+      toast.success(`Downloaded ${code}`);
+      setDownloadCode("");
+      void refresh();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setDownloading(null);
+      setStatus("");
+    }
+  };
+
+  const handleRemove = async (code: string) => {
+    if (removing) return;
+    if (!window.confirm(`Remove the ${code} language pack?`)) return;
+    setRemoving(code);
+    try {
+      await removeOcrLanguage(code);
+      if (selected.includes(code)) toggleLanguage(code, false);
+      void refresh();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setRemoving(null);
+    }
+  };
+
+  return (
+    <div className="py-2.5 space-y-2 text-xs">
+      <div className="flex items-center justify-between text-[var(--text-tertiary)]">
+        <span>
+          {info === null
+            ? "Looking for tesseract…"
+            : info.available
+              ? `Tesseract ${info.version ?? ""} — checked languages are used together; none means English`
+              : "Tesseract is not installed. Install it to copy text from images."}
+        </span>
+        <button
+          type="button"
+          className={iconBtnClass}
+          onClick={() => void refresh()}
+          title="Refresh"
+          aria-label="Refresh language packs"
+        >
+          <RefreshCw size={12} />
+        </button>
+      </div>
+      {listError && <div className="text-[var(--error-color)] truncate">{listError}</div>}
+      {languages?.map((lang) => (
+        <label key={lang.code} className="flex items-center gap-2 py-0.5">
+          <input
+            type="checkbox"
+            checked={selected.includes(lang.code)}
+            onChange={(e) => toggleLanguage(lang.code, e.target.checked)}
+          />
+          <span className="flex-1 font-mono text-[var(--text-color)]">{lang.code}</span>
+          {lang.source === "managed" && (
+            <button
+              type="button"
+              className={iconBtnClass}
+              onClick={() => void handleRemove(lang.code)}
+              disabled={removing !== null}
+              title="Remove"
+              aria-label={`Remove ${lang.code}`}
+            >
+              {removing === lang.code ? (
+                <Loader2 size={12} className="animate-spin" />
+              ) : (
+                <Trash2 size={12} />
+              )}
+            </button>
+          )}
+        </label>
+      ))}
+      {info?.available && (
+        <div className="flex items-center gap-1.5">
+          <input
+            type="text"
+            className={inputClass}
+            value={downloadCode}
+            onChange={(e) => setDownloadCode(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") void handleDownload();
+            }}
+            placeholder="Language to download, e.g. deu"
+            disabled={downloading !== null}
+            spellCheck={false}
+          />
+          <button
+            type="button"
+            className={buttonClass}
+            onClick={() => void handleDownload()}
+            disabled={!downloadCode.trim() || downloading !== null}
+          >
+            {downloading ? <Loader2 size={12} className="animate-spin" /> : <Download size={12} />}
+            Download
+          </button>
+        </div>
+      )}
+      {downloading && <div className="text-[var(--text-tertiary)] truncate">{status}</div>}
+    </div>
+  );
+}
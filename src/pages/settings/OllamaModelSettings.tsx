@@ -0,0 +1,200 @@
+/**
+ * Ollama model options, shown under the Ollama (API) provider fields:
+ * keeping the model loaded between genie runs, and listing, pulling and
+ * deleting installed models.
+ */
+
+import { useCallback, useEffect, useState } from "react";
+import { Download, Loader2, RefreshCw, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { useAiProviderStore } from "@/stores/aiProviderStore";
+import {
+  deleteOllamaModel,
+  formatModelSize,
+  listLocalOllamaModels,
+  onOllamaPullProgress,
+  pullOllamaModel,
+  pullPercent,
+  type LocalOllamaModel,
+} from "@/utils/ollama";
+import { Toggle } from "./components";
+
+const inputClass = `flex-1 px-2 py-1 text-xs rounded
+  bg-[var(--bg-tertiary)] text-[var(--text-color)]
+  border border-[var(--border-color)]
+  focus:border-[var(--primary-color)] outline-none
+  font-mono`;
+
+const iconBtnClass = `shrink-0 p-1 rounded
+  text-[var(--text-secondary)] hover:text-[var(--text-color)]
+  hover:bg-[var(--hover-bg)] cursor-pointer
+  disabled:opacity-50 disabled:cursor-default`;
+
+const buttonClass = `shrink-0 flex items-center gap-1 px-2 py-1 text-xs rounded
+  text-[var(--text-secondary)] hover:text-[var(--text-color)]
+  hover:bg-[var(--hover-bg)] cursor-pointer
+  disabled:opacity-50 disabled:cursor-default`;
+
+interface OllamaModelSettingsProps {
+  endpoint: string;
+}
+
+export function OllamaModelSettings({ endpoint }: OllamaModelSettingsProps) {
+  const keepWarm = useAiProviderStore((s) => s.ollamaKeepWarm);
+  const [pullName, setPullName] = useState("");
+  const [pulling, setPulling] = useState<string | null>(null);
+  const [status, setStatus] = useState("");
+  const [models, setModels] = useState<LocalOllamaModel[] | null>(null);
+  const [listError, setListError] = useState<string | null>(null);
+  const [deleting, setDeleting] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setModels(await listLocalOllamaModels(endpoint));
+      setListError(null);
+    } catch (error) {
+      setModels(null);
+      setListError(String(error));
+    }
+  }, [endpoint]);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  useEffect(() => {
+    if (!pulling) return;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    onOllamaPullProgress((progress) => {
+      if (progress.model !== pulling) return;
+      const percent = pullPercent(progress);
+      setStatus(percent === null ? progress.status : `${progress.status} — ${percent}%`);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [pulling]);
+
+  const handlePull = async () => {
+    const model = pullName.trim();
+    if (!model || pulling) return;
+    setPulling(model);
+    setStatus("Starting…");
+    try {
+      await pullOllamaModel(model, endpoint);
+      toast.success(`Pulled ${model}`);
+      setPullName("");
+      void refresh();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setPulling(null);
+      setStatus("");
+    }
+  };
+
+  const handleDelete = async (model: string) => {
+    if (deleting) return;
+    if (!window.confirm(`Delete ${model} from Ollama?`)) return;
+    setDeleting(model);
+    try {
+      await deleteOllamaModel(model, endpoint);
+      toast.success(`Deleted ${model}`);
+      void refresh();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setDeleting(null);
+    }
+  };
+
+  return (
+    <div className="ml-5 mt-2 space-y-2">
+      <div className="flex items-center justify-between gap-2 text-xs">
+        <div>
+          <div className="text-[var(--text-color)]">Keep model loaded</div>
+          <div className="text-[var(--text-tertiary)]">
+            Ping Ollama in the background so genies don't wait for the model to load
+          </div>
+        </div>
+        <Toggle
+          checked={keepWarm}
+          onChange={(v) => useAiProviderStore.getState().setOllamaKeepWarm(v)}
+        />
+      </div>
+      <div className="text-xs">
+        <div className="flex items-center justify-between text-[var(--text-tertiary)]">
+          <span>Installed models</span>
+          <button
+            type="button"
+            className={iconBtnClass}
+            onClick={() => void refresh()}
+            title="Refresh"
+            aria-label="Refresh installed models"
+          >
+            <RefreshCw size={12} />
+          </button>
+        </div>
+        {listError && <div className="text-[var(--error-color)] truncate">{listError}</div>}
+        {models?.length === 0 && (
+          <div className="text-[var(--text-tertiary)]">No models installed</div>
+        )}
+        {models?.map((m) => (
+          <div key={m.name} className="flex items-center gap-2 py-0.5">
+            <span className="flex-1 font-mono text-[var(--text-color)] truncate">{m.name}</span>
+            <span className="text-[var(--text-tertiary)]">
+              {[m.parameterSize, m.quantizationLevel, formatModelSize(m.size)]
+                .filter(Boolean)
+                .join(" · ")}
+            </span>
+            <button
+              type="button"
+              className={iconBtnClass}
+              onClick={() => void handleDelete(m.name)}
+              disabled={deleting !== null}
+              title="Delete"
+              aria-label={`Delete ${m.name}`}
+            >
+              {deleting === m.name ? (
+                <Loader2 size={12} className="animate-spin" />
+              ) : (
+                <Trash2 size={12} />
+              )}
+            </button>
+          </div>
+        ))}
+      </div>
+      <div className="flex items-center gap-1.5">
+        <input
+          type="text"
+          className={inputClass}
+          value={pullName}
+          onChange={(e) => setPullName(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === "Enter") void handlePull();
+          }}
+          placeholder="Model to pull, e.g. llama3.2"
+          disabled={pulling !== null}
+          spellCheck={false}
+        />
+        <button
+          type="button"
+          className={buttonClass}
+          onClick={() => void handlePull()}
+          disabled={!pullName.trim() || pulling !== null}
+        >
+          {pulling ? <Loader2 size={12} className="animate-spin" /> : <Download size={12} />}
+          Pull
+        </button>
+      </div>
+      {pulling && (
+        <div className="text-xs text-[var(--text-tertiary)] truncate">{status}</div>
+      )}
+    </div>
+  );
+}
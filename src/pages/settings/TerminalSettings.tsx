@@ -1,7 +1,7 @@
 /**
  * Terminal Settings Section
  *
- * Font size and line height for the integrated terminal.
+ * Font size, line height and output restore for the integrated terminal.
  */
 
 import { useSettingsStore } from "@/stores/settingsStore";
@@ -57,6 +57,16 @@ export function TerminalSettings() {
             onChange={(v) => updateTerminalSetting("copyOnSelect", v)}
           />
         </SettingRow>
+
+        <SettingRow
+          label="Restore Output After Restart"
+          description="Save recent terminal output to disk when restarting for an update. API keys are masked, but other secrets may be kept."
+        >
+          <Toggle
+            checked={terminal.restoreScrollback}
+            onChange={(v) => updateTerminalSetting("restoreScrollback", v)}
+          />
+        </SettingRow>
       </SettingsGroup>
     </div>
   );
@@ -118,6 +118,7 @@ export const MENU_TO_ACTION: Record<MenuEventId, MenuActionMapping> = {
   // === Text Cleanup ===
   "menu:remove-trailing-spaces": { actionId: "removeTrailingSpaces" },
   "menu:collapse-blank-lines": { actionId: "collapseBlankLines" },
+  "menu:normalize-footnotes": { actionId: "normalizeFootnotes" },
   "menu:line-endings-lf": { actionId: "lineEndingsLF" },
   "menu:line-endings-crlf": { actionId: "lineEndingsCRLF" },
 
@@ -558,6 +559,12 @@ export const ACTION_DEFINITIONS: Record<ActionId, ActionDefinition> = {
     category: "cleanup",
     supports: { wysiwyg: true, source: true },
   },
+  normalizeFootnotes: {
+    id: "normalizeFootnotes",
+    label: "Normalize Footnotes",
+    category: "cleanup",
+    supports: { wysiwyg: true, source: true },
+  },
   lineEndingsLF: {
     id: "lineEndingsLF",
     label: "Convert to LF",
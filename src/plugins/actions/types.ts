@@ -103,6 +103,7 @@ export const ACTION_IDS = [
   // === Text Cleanup ===
   "removeTrailingSpaces",
   "collapseBlankLines",
+  "normalizeFootnotes",
   "lineEndingsLF",
   "lineEndingsCRLF",
 
@@ -0,0 +1,46 @@
+import { describe, it, expect, vi } from "vitest";
+import { EditorState } from "@codemirror/state";
+import { EditorView } from "@codemirror/view";
+
+let resolveTitle: (title: string | null) => void = () => {};
+
+vi.mock("@/utils/urlTitle", () => ({
+  shouldFetchLinkTitle: () => true,
+  fetchUrlTitle: () => new Promise<string | null>((resolve) => (resolveTitle = resolve)),
+  markdownLink: (title: string, url: string) => `[${title}](${url})`,
+}));
+
+import { createSmartPastePlugin } from "../smartPaste";
+
+function pasteUrl(view: EditorView, url: string) {
+  const event = new Event("paste", { bubbles: true, cancelable: true });
+  Object.defineProperty(event, "clipboardData", {
+    value: { getData: (type: string) => (type === "text/plain" ? url : "") },
+  });
+  view.contentDOM.dispatchEvent(event);
+}
+
+describe("smart paste link titles", () => {
+  it("replaces the URL where it moved to while the title loaded", async () => {
+    const url = "https://example.com/page";
+    const parent = document.createElement("div");
+    document.body.appendChild(parent);
+    const view = new EditorView({
+      state: EditorState.create({ doc: "see ", extensions: [createSmartPastePlugin()] }),
+      parent,
+    });
+    view.dispatch({ selection: { anchor: 4 } });
+
+    pasteUrl(view, url);
+    expect(view.state.doc.toString()).toBe(`see ${url}`);
+
+    // Typed before the URL while the title was loading
+    view.dispatch({ changes: { from: 0, insert: "Please " } });
+    resolveTitle("Example");
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(view.state.doc.toString()).toBe(`Please see [Example](${url})`);
+    view.destroy();
+    parent.remove();
+  });
+});
@@ -0,0 +1,20 @@
+import { describe, it, expect } from "vitest";
+import { findSnippetPrefix } from "../snippetExpansion";
+
+describe("findSnippetPrefix", () => {
+  const prefixes = ["note", "!note", "tbl"];
+
+  it("matches a prefix that starts a word", () => {
+    expect(findSnippetPrefix("note", prefixes)).toBe("note");
+    expect(findSnippetPrefix("see tbl", prefixes)).toBe("tbl");
+  });
+
+  it("prefers the longest prefix", () => {
+    expect(findSnippetPrefix("- !note", prefixes)).toBe("!note");
+  });
+
+  it("ignores prefixes inside a word", () => {
+    expect(findSnippetPrefix("footnote", prefixes)).toBeNull();
+    expect(findSnippetPrefix("note ", prefixes)).toBeNull();
+  });
+});
@@ -0,0 +1,86 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { CompletionContext } from "@codemirror/autocomplete";
+import { EditorState } from "@codemirror/state";
+import { EditorView } from "@codemirror/view";
+
+const suggestLinkTargets = vi.fn();
+const searchEmoji = vi.fn();
+
+vi.mock("@/utils/linkSuggestions", async (importOriginal) => ({
+  ...(await importOriginal<typeof import("@/utils/linkSuggestions")>()),
+  suggestLinkTargets: (...args: unknown[]) => suggestLinkTargets(...args),
+}));
+vi.mock("@/utils/symbolSearch", async (importOriginal) => ({
+  ...(await importOriginal<typeof import("@/utils/symbolSearch")>()),
+  searchEmoji: (...args: unknown[]) => searchEmoji(...args),
+}));
+vi.mock("@/stores/workspaceStore", () => ({
+  useWorkspaceStore: { getState: () => ({ rootPath: "/ws" }) },
+}));
+
+import { emojiCompletionSource, wikiLinkCompletionSource } from "../sourceCompletion";
+
+function contextAt(doc: string, pos = doc.length) {
+  return new CompletionContext(EditorState.create({ doc }), pos, false);
+}
+
+describe("wiki link completion", () => {
+  beforeEach(() => {
+    suggestLinkTargets.mockReset();
+    suggestLinkTargets.mockResolvedValue([
+      { path: "projects/roadmap.md", title: "Roadmap", heading: null, score: 10 },
+    ]);
+  });
+
+  it("queries the link index with the text after [[", async () => {
+    const result = await wikiLinkCompletionSource(contextAt("See [[road"));
+    expect(suggestLinkTargets).toHaveBeenCalledWith("/ws", "road", 20);
+    expect(result?.from).toBe(6);
+    expect(result?.options.map((o) => o.label)).toEqual(["projects/roadmap"]);
+  });
+
+  it("ignores text outside a wiki link", async () => {
+    expect(await wikiLinkCompletionSource(contextAt("See [road"))).toBeNull();
+    expect(await wikiLinkCompletionSource(contextAt("See [[a]] road"))).toBeNull();
+    expect(suggestLinkTargets).not.toHaveBeenCalled();
+  });
+
+  it("closes the link when accepting", async () => {
+    const view = new EditorView({ state: EditorState.create({ doc: "[[road" }) });
+    const result = await wikiLinkCompletionSource(contextAt("[[road"));
+    const option = result!.options[0];
+    (option.apply as (v: EditorView, c: typeof option, from: number, to: number) => void)(view, option, 2, 6);
+    expect(view.state.doc.toString()).toBe("[[projects/roadmap]]");
+    expect(view.state.selection.main.head).toBe(20);
+  });
+});
+
+describe("emoji completion", () => {
+  beforeEach(() => {
+    searchEmoji.mockReset();
+    searchEmoji.mockResolvedValue([
+      { emoji: "👍", shortcode: "thumbsup", aliases: ["+1"], name: "thumbs up" },
+    ]);
+  });
+
+  it("searches the shortcode before the cursor", async () => {
+    const result = await emojiCompletionSource(contextAt("Nice :thu"));
+    expect(searchEmoji).toHaveBeenCalledWith("thu", 20);
+    expect(result?.from).toBe(5);
+    expect(result?.options.map((o) => o.label)).toEqual([":thumbsup:"]);
+  });
+
+  it("ignores times and single characters", async () => {
+    expect(await emojiCompletionSource(contextAt("at 10:30"))).toBeNull();
+    expect(await emojiCompletionSource(contextAt("Nice :t"))).toBeNull();
+    expect(searchEmoji).not.toHaveBeenCalled();
+  });
+
+  it("replaces the shortcode and a closing colon with the emoji", async () => {
+    const view = new EditorView({ state: EditorState.create({ doc: ":thu: ok" }) });
+    const result = await emojiCompletionSource(contextAt(":thu: ok", 4));
+    const option = result!.options[0];
+    (option.apply as (v: EditorView, c: typeof option, from: number, to: number) => void)(view, option, 0, 4);
+    expect(view.state.doc.toString()).toBe("👍 ok");
+  });
+});
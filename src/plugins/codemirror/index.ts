@@ -9,6 +9,8 @@ export { createBrHidingPlugin } from "./brHidingPlugin";
 export { createListBlankLinePlugin } from "./listBlankLinePlugin";
 export { createMarkdownAutoPairPlugin, markdownPairBackspace } from "./markdownAutoPair";
 export { tabEscapeKeymap } from "./tabEscape";
+export { snippetExpansionExtensions } from "./snippetExpansion";
+export { sourceCompletionExtensions } from "./sourceCompletion";
 export { tabIndentFallbackKeymap, shiftTabIndentFallbackKeymap } from "./tabIndent";
 export { listContinuationKeymap } from "./listContinuation";
 export { tableTabKeymap, tableShiftTabKeymap, tableArrowUpKeymap, tableArrowDownKeymap, tableModEnterKeymap, tableModShiftEnterKeymap } from "./tableTabNav";
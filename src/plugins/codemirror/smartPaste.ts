@@ -3,9 +3,11 @@
  *
  * Features:
  * - When text is selected and user pastes a URL, creates a markdown link
+ * - With no selection, optionally replaces a pasted URL with [Title](url)
  * - When pasting an image URL/path, prompts user to insert as image
  */
 
+import { StateEffect, StateField, type Extension } from "@codemirror/state";
 import { EditorView } from "@codemirror/view";
 import { exists } from "@tauri-apps/plugin-fs";
 import { homeDir, join } from "@tauri-apps/api/path";
@@ -19,6 +21,7 @@ import { detectMultipleImagePaths, type ImagePathResult } from "@/utils/imagePat
 import { encodeMarkdownUrl } from "@/utils/markdownUrl";
 import { parseMultiplePaths } from "@/utils/multiImageParsing";
 import { findWordAtCursorSource } from "@/plugins/toolbarActions/sourceAdapterLinks";
+import { fetchUrlTitle, markdownLink, shouldFetchLinkTitle } from "@/utils/urlTitle";
 
 /**
  * Check if a CodeMirror view is still connected and valid.
@@ -536,12 +539,80 @@ async function validateAndShowToast(
   showImagePasteToast(view, detection, originalText, capturedFrom, capturedTo, altText);
 }
 
+/** A pasted URL waiting for its title */
+interface PendingTitle {
+  id: number;
+  from: number;
+  to: number;
+}
+
+let nextPendingId = 0;
+
+const addPendingTitle = StateEffect.define<PendingTitle>();
+const removePendingTitle = StateEffect.define<number>();
+
+/** Where each pasted URL is now, following the edits made while its title loads */
+const pendingTitles = StateField.define<PendingTitle[]>({
+  create: () => [],
+  update(pending, tr) {
+    let next = tr.docChanged
+      ? pending.map((p) => ({
+          ...p,
+          // Typing right before or after the URL isn't part of it
+          from: tr.changes.mapPos(p.from, 1),
+          to: tr.changes.mapPos(p.to, -1),
+        }))
+      : pending;
+    for (const effect of tr.effects) {
+      if (effect.is(addPendingTitle)) next = [...next, effect.value];
+      else if (effect.is(removePendingTitle)) next = next.filter((p) => p.id !== effect.value);
+    }
+    return next;
+  },
+});
+
+/**
+ * Paste a bare URL, then swap it for [Title](url) once the title arrives.
+ * Leaves the URL alone if the user has edited it in the meantime.
+ */
+function pasteUrlWithTitle(view: EditorView, url: string, from: number): void {
+  const id = nextPendingId++;
+  view.dispatch({
+    changes: { from, insert: url },
+    selection: { anchor: from + url.length },
+    effects: addPendingTitle.of({ id, from, to: from + url.length }),
+  });
+
+  void fetchUrlTitle(url).then((title) => {
+    if (!isViewConnected(view)) return;
+    const pending = view.state.field(pendingTitles, false)?.find((p) => p.id === id);
+    if (!pending) return;
+    const { from: start, to: end } = pending;
+    if (!title || view.state.doc.sliceString(start, end) !== url) {
+      view.dispatch({ effects: removePendingTitle.of(id) });
+      return;
+    }
+
+    const link = markdownLink(title, url);
+    const { head } = view.state.selection.main;
+    view.dispatch({
+      changes: { from: start, to: end, insert: link },
+      selection: head === end ? { anchor: start + link.length } : undefined,
+      effects: removePendingTitle.of(id),
+    });
+  });
+}
+
 /**
  * Creates an extension that intercepts paste events
  * and converts URL paste on selection to markdown links,
  * and prompts for image URL/path pasting.
  */
-export function createSmartPastePlugin() {
+export function createSmartPastePlugin(): Extension {
+  return [pendingTitles, smartPasteHandlers()];
+}
+
+function smartPasteHandlers() {
   return EditorView.domEventHandlers({
     paste: (event, view) => {
       const pastedText = event.clipboardData?.getData("text/plain");
@@ -557,8 +628,17 @@ export function createSmartPastePlugin() {
         return true;
       }
 
-      // No selection - let default paste handle it
-      if (from === to) return false;
+      if (from === to) {
+        // A bare URL outside existing link syntax: optionally resolve its title
+        const before = view.state.doc.sliceString(Math.max(0, from - 1), from);
+        if (before !== "(" && before !== "<" && shouldFetchLinkTitle(pastedText)) {
+          event.preventDefault();
+          pasteUrlWithTitle(view, trimmedText, from);
+          return true;
+        }
+        // Otherwise let default paste handle it
+        return false;
+      }
 
       // Not a URL - let default paste handle it
       if (!isValidUrl(trimmedText)) return false;
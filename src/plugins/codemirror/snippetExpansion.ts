@@ -0,0 +1,190 @@
+/**
+ * Snippet Expansion for CodeMirror
+ *
+ * Tab after a snippet prefix replaces the prefix with the snippet, expanded
+ * by the backend (see snippets.rs). Tab / Shift-Tab then walk the tab stops;
+ * leaving the current stop, Escape or passing the last stop ends the session
+ * at the snippet's final cursor.
+ */
+
+import { EditorSelection, StateEffect, StateField, type ChangeDesc, type Extension } from "@codemirror/state";
+import { EditorView, ViewPlugin, keymap, type KeyBinding } from "@codemirror/view";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { guardCodeMirrorKeyBinding } from "@/utils/imeGuard";
+import { listSnippets, resolveSnippet, type ResolvedSnippet } from "@/utils/snippets";
+
+/** Snippet scope of the source editor */
+const SCOPE = "source";
+/** How long the known prefixes are trusted before reloading */
+const PREFIX_CACHE_MS = 10_000;
+
+interface PrefixCache {
+  root: string | null;
+  loadedAt: number;
+  prefixes: string[];
+}
+
+let prefixCache: PrefixCache | null = null;
+
+/** Known prefixes for the open workspace, refreshed in the background. */
+function knownPrefixes(): string[] {
+  const root = useWorkspaceStore.getState().rootPath;
+  const now = Date.now();
+  if (!prefixCache || prefixCache.root !== root || now - prefixCache.loadedAt > PREFIX_CACHE_MS) {
+    const cache: PrefixCache = {
+      root,
+      loadedAt: now,
+      prefixes: prefixCache?.root === root ? prefixCache.prefixes : [],
+    };
+    prefixCache = cache;
+    listSnippets(root, SCOPE)
+      .then((snippets) => {
+        cache.prefixes = snippets.flatMap((snippet) => snippet.prefix);
+      })
+      .catch((error) => console.warn("[Snippets] Failed to load snippets:", error));
+  }
+  return prefixCache.prefixes;
+}
+
+/**
+ * The longest prefix that ends `textBefore` and starts a word (at the line
+ * start or after whitespace).
+ */
+export function findSnippetPrefix(textBefore: string, prefixes: string[]): string | null {
+  let best: string | null = null;
+  for (const prefix of prefixes) {
+    if (!prefix || !textBefore.endsWith(prefix)) continue;
+    const before = textBefore.slice(0, textBefore.length - prefix.length);
+    if (before && !/\s$/.test(before)) continue;
+    if (!best || prefix.length > best.length) best = prefix;
+  }
+  return best;
+}
+
+interface SnippetSession {
+  /** Ranges of each tab stop, in document positions */
+  stops: { from: number; to: number }[][];
+  final: number;
+  active: number;
+}
+
+const setSession = StateEffect.define<SnippetSession | null>();
+
+function mapSession(session: SnippetSession, changes: ChangeDesc): SnippetSession {
+  return {
+    // Typing at either edge of a stop grows it
+    stops: session.stops.map((ranges) =>
+      ranges.map(({ from, to }) => ({ from: changes.mapPos(from, -1), to: changes.mapPos(to, 1) }))
+    ),
+    final: changes.mapPos(session.final, 1),
+    active: session.active,
+  };
+}
+
+const snippetSession = StateField.define<SnippetSession | null>({
+  create: () => null,
+  update(session, tr) {
+    for (const effect of tr.effects) {
+      if (effect.is(setSession)) return effect.value;
+    }
+    if (!session) return null;
+    const mapped = tr.docChanged ? mapSession(session, tr.changes) : session;
+    if (tr.selection) {
+      const head = tr.selection.main.head;
+      const inStop = mapped.stops[mapped.active].some(({ from, to }) => head >= from && head <= to);
+      if (!inStop) return null;
+    }
+    return mapped;
+  },
+});
+
+/** Select tab stop `index`, or finish at the final cursor past the last one. */
+function selectStop(view: EditorView, session: SnippetSession, index: number): boolean {
+  if (index < 0) return true;
+  if (index >= session.stops.length) {
+    view.dispatch({
+      selection: { anchor: session.final },
+      effects: setSession.of(null),
+      scrollIntoView: true,
+    });
+    return true;
+  }
+  const ranges = session.stops[index].map(({ from, to }) => EditorSelection.range(from, to));
+  view.dispatch({
+    selection: EditorSelection.create(ranges),
+    effects: setSession.of({ ...session, active: index }),
+    scrollIntoView: true,
+  });
+  return true;
+}
+
+function insertSnippet(view: EditorView, from: number, to: number, snippet: ResolvedSnippet) {
+  view.dispatch({ changes: { from, to, insert: snippet.text }, scrollIntoView: true });
+  const session: SnippetSession = {
+    stops: snippet.tabStops.map((stop) =>
+      stop.ranges.map((range) => ({ from: from + range.from, to: from + range.to }))
+    ),
+    final: from + snippet.finalCursor,
+    active: 0,
+  };
+  selectStop(view, session, 0);
+}
+
+/** Tab: next tab stop, or expand the snippet prefix before the cursor. */
+const snippetTabKeymap: KeyBinding = guardCodeMirrorKeyBinding({
+  key: "Tab",
+  run: (view) => {
+    const session = view.state.field(snippetSession, false);
+    if (session) return selectStop(view, session, session.active + 1);
+
+    const { selection } = view.state;
+    if (selection.ranges.length > 1 || !selection.main.empty) return false;
+    const head = selection.main.head;
+    const line = view.state.doc.lineAt(head);
+    const prefix = findSnippetPrefix(line.text.slice(0, head - line.from), knownPrefixes());
+    if (!prefix) return false;
+
+    const from = head - prefix.length;
+    const root = useWorkspaceStore.getState().rootPath;
+    resolveSnippet(prefix, root, { scope: SCOPE })
+      .then((snippet) => {
+        // Skip if the prefix was edited while the snippet was resolving
+        if (!snippet || !view.dom.isConnected || view.state.sliceDoc(from, head) !== prefix) return;
+        insertSnippet(view, from, head, snippet);
+      })
+      .catch((error) => console.warn("[Snippets] Failed to expand snippet:", error));
+    return true;
+  },
+});
+
+/** Shift-Tab: previous tab stop. */
+const snippetShiftTabKeymap: KeyBinding = guardCodeMirrorKeyBinding({
+  key: "Shift-Tab",
+  run: (view) => {
+    const session = view.state.field(snippetSession, false);
+    return session ? selectStop(view, session, session.active - 1) : false;
+  },
+});
+
+/** Escape ends the session; other Escape handlers still run. */
+const snippetEscapeKeymap: KeyBinding = {
+  key: "Escape",
+  run: (view) => {
+    if (view.state.field(snippetSession, false)) {
+      view.dispatch({ effects: setSession.of(null) });
+    }
+    return false;
+  },
+};
+
+/** Load the prefixes as soon as a source editor opens */
+const preloadPrefixes = ViewPlugin.define(() => {
+  knownPrefixes();
+  return {};
+});
+
+export const snippetExpansionExtensions: Extension[] = [
+  snippetSession,
+  preloadPrefixes,
+  keymap.of([snippetTabKeymap, snippetShiftTabKeymap, snippetEscapeKeymap]),
+];
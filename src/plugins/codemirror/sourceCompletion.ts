@@ -0,0 +1,108 @@
+/**
+ * Source Completion
+ *
+ * Autocomplete popup for the Source mode editor. After `[[` it suggests
+ * notes (and `note#heading` headings) from the backend link index, so the
+ * webview never loads the workspace's file list. After `:sh` it suggests
+ * emoji by shortcode from the backend's emoji table.
+ */
+import {
+  autocompletion,
+  type Completion,
+  type CompletionContext,
+  type CompletionResult,
+} from "@codemirror/autocomplete";
+import type { EditorView } from "@codemirror/view";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { formatWikiLinkTarget, suggestLinkTargets } from "@/utils/linkSuggestions";
+import { searchEmoji, shortcodeBeforeCursor } from "@/utils/symbolSearch";
+
+const WIKI_LINK_LIMIT = 20;
+const EMOJI_LIMIT = 20;
+
+/** Insert `target`, closing the link unless `]]` already follows. */
+function applyWikiLink(target: string) {
+  return (view: EditorView, _completion: Completion, from: number, to: number) => {
+    const closed = view.state.sliceDoc(to, to + 2) === "]]";
+    view.dispatch({
+      changes: { from, to, insert: closed ? target : `${target}]]` },
+      selection: { anchor: from + target.length + 2 },
+      userEvent: "input.complete",
+    });
+  };
+}
+
+export async function wikiLinkCompletionSource(context: CompletionContext): Promise<CompletionResult | null> {
+  const match = context.matchBefore(/\[\[[^[\]|\n]*$/);
+  if (!match) return null;
+  const rootPath = useWorkspaceStore.getState().rootPath;
+  if (!rootPath) return null;
+
+  let suggestions;
+  try {
+    suggestions = await suggestLinkTargets(rootPath, match.text.slice(2), WIKI_LINK_LIMIT);
+  } catch (error) {
+    console.warn("[Completion] Link suggestions failed:", error);
+    return null;
+  }
+  if (context.aborted || suggestions.length === 0) return null;
+
+  return {
+    from: match.from + 2,
+    // Already ranked by the backend, which is asked again as the query changes
+    filter: false,
+    options: suggestions.map((suggestion, index) => {
+      const target = formatWikiLinkTarget(suggestion);
+      return {
+        label: target,
+        detail: suggestion.heading ? undefined : suggestion.title,
+        type: suggestion.heading ? "property" : "text",
+        boost: -index,
+        apply: applyWikiLink(target),
+      };
+    }),
+  };
+}
+
+/** Replaces `:partial` (and a closing `:` if one follows) with the emoji itself. */
+export async function emojiCompletionSource(context: CompletionContext): Promise<CompletionResult | null> {
+  const line = context.state.doc.lineAt(context.pos);
+  const shortcode = shortcodeBeforeCursor(line.text.slice(0, context.pos - line.from));
+  if (!shortcode) return null;
+
+  let matches;
+  try {
+    matches = await searchEmoji(shortcode, EMOJI_LIMIT);
+  } catch (error) {
+    console.warn("[Completion] Emoji search failed:", error);
+    return null;
+  }
+  if (context.aborted || matches.length === 0) return null;
+
+  const from = context.pos - shortcode.length - 1;
+  return {
+    from,
+    filter: false,
+    options: matches.map((match, index) => ({
+      label: `:${match.shortcode}:`,
+      displayLabel: `${match.emoji}  :${match.shortcode}:`,
+      detail: match.name,
+      boost: -index,
+      apply: (view: EditorView, _completion: Completion, applyFrom: number, to: number) => {
+        const end = view.state.sliceDoc(to, to + 1) === ":" ? to + 1 : to;
+        view.dispatch({
+          changes: { from: applyFrom, to: end, insert: match.emoji },
+          selection: { anchor: applyFrom + match.emoji.length },
+          userEvent: "input.complete",
+        });
+      },
+    })),
+  };
+}
+
+export const sourceCompletionExtensions = [
+  autocompletion({
+    override: [wikiLinkCompletionSource, emojiCompletionSource],
+    icons: false,
+  }),
+];
@@ -114,4 +114,28 @@ export const sourceEditorTheme = EditorView.theme({
     borderLeftColor: "var(--primary-color)",
     borderLeftWidth: "2px",
   },
+  // Autocomplete popup
+  ".cm-tooltip.cm-tooltip-autocomplete": {
+    border: "0.5px solid var(--border-color)",
+    borderRadius: "var(--radius-md)",
+    backgroundColor: "var(--bg-color)",
+    boxShadow: "var(--popup-shadow)",
+    overflow: "hidden",
+  },
+  ".cm-tooltip-autocomplete > ul": {
+    fontFamily: "var(--font-sans)",
+    maxHeight: "16em",
+  },
+  ".cm-tooltip-autocomplete > ul > li": {
+    padding: "2px 8px",
+  },
+  ".cm-tooltip-autocomplete > ul > li[aria-selected]": {
+    backgroundColor: "var(--primary-color)",
+    color: "var(--contrast-text)",
+  },
+  ".cm-completionDetail": {
+    marginLeft: "8px",
+    fontStyle: "normal",
+    color: "var(--text-tertiary)",
+  },
 });
@@ -2,13 +2,22 @@
  * Link Popup View
  *
  * DOM management for the link editing popup.
- * Shows when clicking on a link, allows editing/opening/copying/removing.
+ * Shows when clicking on a link, allows editing/opening/copying/removing,
+ * and archiving a local copy of web pages when a workspace is open. With
+ * link previews enabled, web links also get a card with the page's title,
+ * description and image.
  *
  * Extends WysiwygPopupView for common popup lifecycle management.
  */
 
 import { TextSelection } from "@tiptap/pm/state";
 import { useLinkPopupStore } from "@/stores/linkPopupStore";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { getActiveDocument } from "@/utils/activeDocument";
+import { getWindowLabel } from "@/hooks/useWindowFocus";
+import { ARCHIVE_LABEL, archiveLink } from "@/utils/linkArchive";
+import { canPreviewLink, fetchLinkPreview, type LinkPreview } from "@/utils/linkPreview";
 import { findHeadingById } from "@/utils/headingSlug";
 import { isImeKeyEvent } from "@/utils/imeGuard";
 import { popupIcons } from "@/utils/popupComponents";
@@ -26,6 +35,9 @@ interface LinkPopupState extends PopupStoreBase {
  * Link popup view - manages the floating popup UI.
  */
 export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
+  /** Bumped on every show/hide so a late preview for an old link is dropped */
+  private previewRequest = 0;
+
   constructor(view: EditorViewLike) {
     super(view, useLinkPopupStore);
     // Attach event listeners after super() (arrow functions are now initialized)
@@ -37,12 +49,14 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     this.input.addEventListener("keydown", this.handleInputKeydown);
     this.openBtn.addEventListener("click", this.handleOpen);
     this.copyBtn.addEventListener("click", this.handleCopy);
+    this.archiveBtn.addEventListener("click", this.handleArchive);
     this.saveBtn.addEventListener("click", this.handleSave);
     this.deleteBtn.addEventListener("click", this.handleRemove);
+    this.preview.addEventListener("click", this.handleOpen);
   }
 
   protected getPopupDimensions() {
-    return { width: 320, height: 36, gap: 6, preferAbove: true };
+    return { width: 350, height: 36, gap: 6, preferAbove: true };
   }
 
   // Lazy getters for DOM elements (avoids constructor timing issues)
@@ -58,6 +72,10 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     return this.container.querySelector(".link-popup-btn-copy") as HTMLElement;
   }
 
+  private get archiveBtn(): HTMLButtonElement {
+    return this.container.querySelector(".link-popup-btn-archive") as HTMLButtonElement;
+  }
+
   private get saveBtn(): HTMLElement {
     return this.container.querySelector(".link-popup-btn-save") as HTMLElement;
   }
@@ -66,6 +84,10 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     return this.container.querySelector(".link-popup-btn-delete") as HTMLElement;
   }
 
+  private get preview(): HTMLElement {
+    return this.container.querySelector(".link-popup-preview") as HTMLElement;
+  }
+
   protected buildContainer(): HTMLElement {
     const container = document.createElement("div");
     container.className = "link-popup";
@@ -83,15 +105,23 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     // Icon buttons (event listeners attached in attachEventListeners)
     const openBtn = this.buildButton(popupIcons.open, "Open link", "link-popup-btn-open");
     const copyBtn = this.buildButton(popupIcons.copy, "Copy URL", "link-popup-btn-copy");
+    const archiveBtn = this.buildButton(popupIcons.archive, "Archive page", "link-popup-btn-archive");
     const saveBtn = this.buildButton(popupIcons.save, "Save", "link-popup-btn-save");
     const deleteBtn = this.buildButton(popupIcons.delete, "Remove link", "link-popup-btn-delete");
 
     container.appendChild(input);
     container.appendChild(openBtn);
     container.appendChild(copyBtn);
+    container.appendChild(archiveBtn);
     container.appendChild(saveBtn);
     container.appendChild(deleteBtn);
 
+    // Preview card, hung off the side away from the link (filled in onShow)
+    const preview = document.createElement("div");
+    preview.className = "link-popup-preview";
+    preview.style.display = "none";
+    container.appendChild(preview);
+
     return container;
   }
 
@@ -113,6 +143,11 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     this.input.classList.remove("disabled");
     this.saveBtn.style.display = "";
     this.openBtn.title = isBookmark ? "Go to heading" : "Open link";
+    // Archiving needs a web page and a workspace to store it in
+    const canArchive = /^https?:\/\//i.test(state.href) && !!useWorkspaceStore.getState().rootPath;
+    this.archiveBtn.style.display = canArchive ? "" : "none";
+    this.archiveBtn.disabled = false;
+    this.loadPreview(state);
 
     // Focus and select input
     requestAnimationFrame(() => {
@@ -122,7 +157,57 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
   }
 
   protected onHide(): void {
-    // No special cleanup needed
+    this.previewRequest++;
+    this.preview.style.display = "none";
+  }
+
+  private loadPreview(state: LinkPopupState): void {
+    const request = ++this.previewRequest;
+    this.preview.style.display = "none";
+    if (!useSettingsStore.getState().markdown.linkPreviews || !canPreviewLink(state.href)) return;
+
+    void fetchLinkPreview(state.href).then((preview) => {
+      if (request !== this.previewRequest || !preview || !(preview.title || preview.description)) return;
+      this.renderPreview(preview);
+      // Below the popup when it sits above the link, and vice versa
+      const above = !!state.anchorRect && this.container.getBoundingClientRect().top < state.anchorRect.top;
+      this.preview.classList.toggle("above", above);
+      this.preview.style.display = "";
+    });
+  }
+
+  /** Page text goes in via textContent only; it comes from the web. */
+  private renderPreview(preview: LinkPreview): void {
+    this.preview.replaceChildren();
+    if (preview.image) {
+      const image = document.createElement("img");
+      image.className = "link-popup-preview-image";
+      image.src = preview.image;
+      image.alt = "";
+      this.preview.appendChild(image);
+    }
+    const site = document.createElement("div");
+    site.className = "link-popup-preview-site";
+    if (preview.favicon) {
+      const icon = document.createElement("img");
+      icon.src = preview.favicon;
+      icon.alt = "";
+      site.appendChild(icon);
+    }
+    site.append(preview.siteName ?? new URL(preview.url).hostname);
+    this.preview.appendChild(site);
+    if (preview.title) {
+      const title = document.createElement("div");
+      title.className = "link-popup-preview-title";
+      title.textContent = preview.title;
+      this.preview.appendChild(title);
+    }
+    if (preview.description) {
+      const description = document.createElement("div");
+      description.className = "link-popup-preview-description";
+      description.textContent = preview.description;
+      this.preview.appendChild(description);
+    }
   }
 
   private handleInputChange = () => {
@@ -212,6 +297,42 @@ export class LinkPopupView extends WysiwygPopupView<LinkPopupState> {
     }
   };
 
+  /**
+   * Save a copy of the page under .vmark/archive and add an
+   * "(archived)" link to it right after the link.
+   */
+  private handleArchive = async () => {
+    const { href, linkTo } = this.store.getState();
+    const rootPath = useWorkspaceStore.getState().rootPath;
+    if (!href || !rootPath) return;
+
+    this.archiveBtn.disabled = true;
+    try {
+      const documentPath = getActiveDocument(getWindowLabel())?.filePath ?? null;
+      const archived = await archiveLink(href, rootPath, documentPath);
+
+      const { state: editorState, dispatch } = this.editorView;
+      const linkMark = editorState?.schema.marks.link;
+      if (!editorState || !linkMark || linkTo > editorState.doc.content.size) return;
+
+      const { schema } = editorState;
+      const annotation = [
+        schema.text(" ("),
+        schema.text(ARCHIVE_LABEL, [linkMark.create({ href: archived.href })]),
+        schema.text(")"),
+      ];
+      dispatch(editorState.tr.insert(linkTo, annotation));
+      this.closePopup();
+      this.focusEditor();
+    } catch (error) {
+      console.error("[LinkPopup] Archive failed:", error);
+      const { message } = await import("@tauri-apps/plugin-dialog");
+      await message(`Could not archive this page.\n\n${error}`, { kind: "error" });
+    } finally {
+      this.archiveBtn.disabled = false;
+    }
+  };
+
   private handleRemove = () => {
     const state = this.store.getState();
     const { linkFrom, linkTo } = state;
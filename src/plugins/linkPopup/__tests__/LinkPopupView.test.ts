@@ -16,6 +16,10 @@ import type { AnchorRect } from "@/utils/popupPosition";
 const mockClosePopup = vi.fn();
 const mockSetHref = vi.fn();
 const mockOpenUrl = vi.fn(() => Promise.resolve());
+const mockArchiveLink = vi.fn();
+const mockFetchLinkPreview = vi.fn(() => Promise.resolve(null as unknown));
+let workspaceRoot: string | null = "/ws";
+let linkPreviews = false;
 
 let storeState = {
   isOpen: false,
@@ -41,6 +45,32 @@ vi.mock("@/stores/linkPopupStore", () => ({
   },
 }));
 
+vi.mock("@/stores/workspaceStore", () => ({
+  useWorkspaceStore: { getState: () => ({ rootPath: workspaceRoot }) },
+}));
+
+vi.mock("@/stores/settingsStore", () => ({
+  useSettingsStore: { getState: () => ({ markdown: { linkPreviews } }) },
+}));
+
+vi.mock("@/utils/linkPreview", () => ({
+  canPreviewLink: (href: string) => href.startsWith("http"),
+  fetchLinkPreview: (...args: unknown[]) => mockFetchLinkPreview(...(args as [])),
+}));
+
+vi.mock("@/utils/activeDocument", () => ({
+  getActiveDocument: () => ({ filePath: "/ws/note.md" }),
+}));
+
+vi.mock("@/hooks/useWindowFocus", () => ({
+  getWindowLabel: () => "main",
+}));
+
+vi.mock("@/utils/linkArchive", () => ({
+  ARCHIVE_LABEL: "archived",
+  archiveLink: (...args: unknown[]) => mockArchiveLink(...args),
+}));
+
 vi.mock("@/utils/imeGuard", () => ({
   isImeKeyEvent: () => false,
 }));
@@ -107,13 +137,15 @@ function createMockView(editorDom: HTMLElement) {
     addMark: vi.fn().mockReturnThis(),
     setSelection: vi.fn().mockReturnThis(),
     scrollIntoView: vi.fn().mockReturnThis(),
+    insert: vi.fn().mockReturnThis(),
   };
 
   return {
     dom: editorDom,
     state: {
-      doc: { resolve: vi.fn() },
+      doc: { resolve: vi.fn(), content: { size: 100 } },
       schema: {
+        text: (text: string, marks?: unknown[]) => ({ text, marks }),
         marks: {
           link: { create: (attrs: Record<string, unknown>) => ({ type: "link", attrs }) },
         },
@@ -324,6 +356,78 @@ describe("LinkPopupView", () => {
 
       expect(mockClosePopup).toHaveBeenCalled();
     });
+
+    it("archive button saves a copy and annotates the link", async () => {
+      mockArchiveLink.mockResolvedValue({ href: ".vmark/archive/x.md" });
+      const archiveBtn = dom.container.querySelector(".link-popup-btn-archive") as HTMLElement;
+      expect(archiveBtn.style.display).toBe("");
+      archiveBtn.click();
+
+      await new Promise((r) => setTimeout(r, 10));
+
+      expect(mockArchiveLink).toHaveBeenCalledWith("https://test.com", "/ws", "/ws/note.md");
+      expect(view.state.tr.insert).toHaveBeenCalledWith(15, [
+        { text: " (", marks: undefined },
+        { text: "archived", marks: [{ type: "link", attrs: { href: ".vmark/archive/x.md" } }] },
+        { text: ")", marks: undefined },
+      ]);
+      expect(view.dispatch).toHaveBeenCalled();
+      expect(mockClosePopup).toHaveBeenCalled();
+    });
+
+    it("hides the archive button without a workspace", async () => {
+      workspaceRoot = null;
+      emitStateChange({ isOpen: false, anchorRect: null });
+      emitStateChange({ isOpen: true, href: "https://test.com", anchorRect });
+      await new Promise((r) => requestAnimationFrame(r));
+
+      const archiveBtn = dom.container.querySelector(".link-popup-btn-archive") as HTMLElement;
+      expect(archiveBtn.style.display).toBe("none");
+      workspaceRoot = "/ws";
+    });
+  });
+
+  describe("Link preview", () => {
+    afterEach(() => {
+      linkPreviews = false;
+    });
+
+    const open = async (href: string) => {
+      emitStateChange({ isOpen: true, href, linkFrom: 5, linkTo: 15, anchorRect });
+      await new Promise((r) => setTimeout(r, 10));
+      return dom.container.querySelector(".link-popup-preview") as HTMLElement;
+    };
+
+    it("shows a card for web links when enabled", async () => {
+      linkPreviews = true;
+      mockFetchLinkPreview.mockResolvedValue({
+        url: "https://test.com/",
+        title: "<b>Test</b> page",
+        description: "About testing",
+        siteName: null,
+        favicon: null,
+        image: null,
+        fetchedAt: 0,
+      });
+      const preview = await open("https://test.com");
+
+      expect(mockFetchLinkPreview).toHaveBeenCalledWith("https://test.com");
+      expect(preview.style.display).toBe("");
+      expect(preview.querySelector(".link-popup-preview-title")?.textContent).toBe("<b>Test</b> page");
+      expect(preview.querySelector(".link-popup-preview-site")?.textContent).toBe("test.com");
+    });
+
+    it("does not fetch when previews are off", async () => {
+      const preview = await open("https://test.com");
+      expect(mockFetchLinkPreview).not.toHaveBeenCalled();
+      expect(preview.style.display).toBe("none");
+    });
+
+    it("does not fetch for bookmarks", async () => {
+      linkPreviews = true;
+      await open("#intro");
+      expect(mockFetchLinkPreview).not.toHaveBeenCalled();
+    });
   });
 
   describe("Click outside handling", () => {
@@ -10,6 +10,7 @@ import type { MarkdownPasteMode } from "@/stores/settingsStore";
 import {
   createMarkdownPasteTransaction,
   shouldHandleMarkdownPaste,
+  shouldHandleSpreadsheetPaste,
   triggerPastePlainText,
 } from "./tiptap";
 
@@ -122,6 +123,12 @@ describe("markdownPasteExtension", () => {
     expect(result).toBe(false);
   });
 
+  it("takes tab-separated rows as a spreadsheet paste", () => {
+    const state = createState(createParagraphDoc(""));
+    expect(shouldHandleSpreadsheetPaste(state, "Name\tQty\nApples\t3\n")).toBe(true);
+    expect(shouldHandleSpreadsheetPaste(state, "Name\tQty\nApples")).toBe(false);
+  });
+
   it("does not treat plain text as markdown", () => {
     const state = createState(createParagraphDoc(""));
     const result = shouldHandleMarkdownPaste(state, "Just a sentence.", {
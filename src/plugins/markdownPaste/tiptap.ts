@@ -9,6 +9,7 @@ import { isMarkdownPasteCandidate } from "@/utils/markdownPasteDetection";
 import { isSubstantialHtml } from "@/utils/htmlToMarkdown";
 import { useSettingsStore, type MarkdownPasteMode } from "@/stores/settingsStore";
 import { isMultiSelection, isSelectionInCode } from "@/utils/pasteUtils";
+import { csvToMarkdownTable, looksLikeSpreadsheetPaste } from "@/utils/tableData";
 
 const markdownPastePluginKey = new PluginKey("markdownPaste");
 
@@ -85,6 +86,27 @@ export function shouldHandleMarkdownPaste(
   return isMarkdownPasteCandidate(trimmed);
 }
 
+/** Rows copied from a spreadsheet (tab-separated) become a markdown table. */
+export function shouldHandleSpreadsheetPaste(state: EditorState, text: string): boolean {
+  if (text.length > MAX_MARKDOWN_PASTE_CHARS) return false;
+  if (isMultiSelection(state) || isSelectionInCode(state)) return false;
+  return looksLikeSpreadsheetPaste(text);
+}
+
+async function pasteSpreadsheetTable(view: EditorView, text: string): Promise<void> {
+  try {
+    const table = await csvToMarkdownTable(text, { delimiter: "\t" });
+    const tr = createMarkdownPasteTransaction(view.state, table.markdown);
+    if (tr) {
+      view.dispatch(tr.scrollIntoView());
+      return;
+    }
+  } catch (error) {
+    console.error("[MarkdownPaste] Failed to convert spreadsheet paste:", error);
+  }
+  // Fall back to the text as copied
+  view.dispatch(view.state.tr.insertText(text).scrollIntoView());
+}
 
 async function readClipboardPlainText(): Promise<string> {
   try {
@@ -113,6 +135,13 @@ function handlePaste(view: EditorView, event: ClipboardEvent): boolean {
   const pasteMode = settings.markdown.pasteMarkdownInWysiwyg ?? "auto";
   const html = event.clipboardData?.getData("text/html") ?? "";
 
+  // Before the HTML check: spreadsheets also put a styled HTML table on the clipboard
+  if (pasteMode !== "off" && shouldHandleSpreadsheetPaste(view.state, text)) {
+    event.preventDefault();
+    void pasteSpreadsheetTable(view, text);
+    return true;
+  }
+
   if (!shouldHandleMarkdownPaste(view.state, text, { pasteMode, html })) {
     return false;
   }
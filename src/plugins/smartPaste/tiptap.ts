@@ -1,18 +1,86 @@
 import { Extension } from "@tiptap/core";
-import { Plugin, PluginKey } from "@tiptap/pm/state";
+import { Plugin, PluginKey, type Transaction } from "@tiptap/pm/state";
 import type { EditorView } from "@tiptap/pm/view";
 import { isSelectionInCode } from "@/utils/pasteUtils";
+import { fetchUrlTitle, shouldFetchLinkTitle } from "@/utils/urlTitle";
 
-const smartPastePluginKey = new PluginKey("smartPaste");
+/** A pasted URL waiting for its title */
+interface PendingTitle {
+  id: number;
+  from: number;
+  to: number;
+}
+
+type PendingTitleMeta = { add: PendingTitle } | { remove: number };
+
+const smartPastePluginKey = new PluginKey<PendingTitle[]>("smartPaste");
+
+let nextPendingId = 0;
+
+/** Where each pasted URL is now, following the edits made while its title loads */
+function applyPendingTitles(pending: PendingTitle[], tr: Transaction): PendingTitle[] {
+  let next = tr.docChanged
+    ? pending.map((p) => ({
+        ...p,
+        // Typing right before or after the URL isn't part of it
+        from: tr.mapping.map(p.from, 1),
+        to: tr.mapping.map(p.to, -1),
+      }))
+    : pending;
+  const meta = tr.getMeta(smartPastePluginKey) as PendingTitleMeta | undefined;
+  if (meta && "add" in meta) next = [...next, meta.add];
+  if (meta && "remove" in meta) next = next.filter((p) => p.id !== meta.remove);
+  return next;
+}
 
 function isValidUrl(str: string): boolean {
   return /^https?:\/\//i.test(str.trim());
 }
 
+/**
+ * Insert a bare URL as a link, then swap its text for the page title
+ * once it arrives, unless the user has edited the link in the meantime.
+ */
+function pasteUrlWithTitle(view: EditorView, url: string): boolean {
+  const linkMark = view.state.schema.marks.link;
+  if (!linkMark) return false;
+
+  const from = view.state.selection.from;
+  const mark = linkMark.create({ href: url });
+  const id = nextPendingId++;
+  const add: PendingTitleMeta = { add: { id, from, to: from + url.length } };
+  view.dispatch(
+    view.state.tr.insert(from, view.state.schema.text(url, [mark])).setMeta(smartPastePluginKey, add)
+  );
+
+  void fetchUrlTitle(url).then((title) => {
+    if (view.isDestroyed) return;
+    const pending = smartPastePluginKey.getState(view.state)?.find((p) => p.id === id);
+    if (!pending) return;
+    const remove: PendingTitleMeta = { remove: id };
+    const { from: start, to: end } = pending;
+    const { doc } = view.state;
+    const unchanged =
+      end <= doc.content.size &&
+      doc.textBetween(start, end) === url &&
+      doc.rangeHasMark(start, end, linkMark);
+    const tr = view.state.tr.setMeta(smartPastePluginKey, remove);
+    if (title && unchanged) tr.replaceWith(start, end, view.state.schema.text(title, [mark]));
+    view.dispatch(tr);
+  });
+  return true;
+}
+
 function handlePaste(view: EditorView, event: ClipboardEvent): boolean {
   const { from, to, empty } = view.state.selection;
 
-  if (empty) return false;
+  if (empty) {
+    const text = event.clipboardData?.getData("text/plain") ?? "";
+    if (isSelectionInCode(view.state) || !shouldFetchLinkTitle(text)) return false;
+    if (!pasteUrlWithTitle(view, text.trim())) return false;
+    event.preventDefault();
+    return true;
+  }
 
   // Don't apply link in code contexts
   if (isSelectionInCode(view.state)) return false;
@@ -34,8 +102,12 @@ export const smartPasteExtension = Extension.create({
   name: "smartPaste",
   addProseMirrorPlugins() {
     return [
-      new Plugin({
+      new Plugin<PendingTitle[]>({
         key: smartPastePluginKey,
+        state: {
+          init: () => [],
+          apply: applyPendingTitles,
+        },
         props: {
           handlePaste,
         },
@@ -32,6 +32,7 @@ import { useSettingsStore, type CJKFormattingSettings } from "@/stores/settingsS
 import { useTabStore } from "@/stores/tabStore";
 import { getWindowLabel } from "@/hooks/useWindowFocus";
 import { collapseNewlines, formatMarkdown, formatSelection, removeTrailingSpaces } from "@/lib/cjkFormatter";
+import { normalizeFootnotes } from "@/utils/footnotes";
 import { normalizeLineEndings, resolveHardBreakStyle } from "@/utils/linebreaks";
 import { getSourceBlockRange } from "@/utils/sourceSelection";
 import {
@@ -551,6 +552,8 @@ export function performSourceToolbarAction(action: string, context: SourceToolba
       return handleRemoveTrailingSpaces(view);
     case "collapseBlankLines":
       return handleCollapseBlankLines(view);
+    case "normalizeFootnotes":
+      return handleNormalizeFootnotes(view);
     case "lineEndingsLF":
       return handleLineEndings(view, "lf");
     case "lineEndingsCRLF":
@@ -878,6 +881,16 @@ function handleCollapseBlankLines(view: EditorView): boolean {
   return applyFullDocumentTransform(view, collapseNewlines);
 }
 
+function handleNormalizeFootnotes(view: EditorView): boolean {
+  const content = view.state.doc.toString();
+  void normalizeFootnotes(content).then((normalized) => {
+    if (normalized === null) return;
+    // Leave the document alone if it was edited while the backend worked
+    applyFullDocumentTransform(view, (current) => (current === content ? normalized : current));
+  });
+  return true;
+}
+
 function handleLineEndings(view: EditorView, target: "lf" | "crlf"): boolean {
   const windowLabel = getWindowLabel();
   const tabId = useTabStore.getState().activeTabId[windowLabel] ?? null;
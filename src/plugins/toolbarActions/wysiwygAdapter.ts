@@ -18,6 +18,7 @@ import { useSettingsStore } from "@/stores/settingsStore";
 import { useWikiLinkPopupStore } from "@/stores/wikiLinkPopupStore";
 import { useTabStore } from "@/stores/tabStore";
 import { collapseNewlines, formatMarkdown, formatSelection, removeTrailingSpaces } from "@/lib/cjkFormatter";
+import { normalizeFootnotes } from "@/utils/footnotes";
 import { normalizeLineEndings, resolveHardBreakStyle } from "@/utils/linebreaks";
 import { parseMarkdown, serializeMarkdown } from "@/utils/markdownPipeline";
 import { toUpperCase, toLowerCase, toTitleCase, toggleCase } from "@/utils/textTransformations";
@@ -834,6 +835,8 @@ export function performWysiwygToolbarAction(action: string, context: WysiwygTool
       return handleRemoveTrailingSpaces(context);
     case "collapseBlankLines":
       return handleCollapseBlankLines(context);
+    case "normalizeFootnotes":
+      return handleNormalizeFootnotes(context);
     case "lineEndingsLF":
       return handleLineEndings(context, "lf");
     case "lineEndingsCRLF":
@@ -1027,6 +1030,19 @@ function handleCollapseBlankLines(context: WysiwygToolbarContext): boolean {
   return applyFullDocumentTransform(context, collapseNewlines);
 }
 
+function handleNormalizeFootnotes(context: WysiwygToolbarContext): boolean {
+  const { editor } = context;
+  if (!editor) return false;
+
+  const content = serializeMarkdown(editor.schema, editor.state.doc, getSerializeOptions());
+  void normalizeFootnotes(content).then((normalized) => {
+    if (normalized === null) return;
+    // Leave the document alone if it was edited while the backend worked
+    applyFullDocumentTransform(context, (current) => (current === content ? normalized : current));
+  });
+  return true;
+}
+
 function handleLineEndings(context: WysiwygToolbarContext, target: "lf" | "crlf"): boolean {
   const windowLabel = getWindowLabel();
   const tabId = useTabStore.getState().activeTabId[windowLabel] ?? null;
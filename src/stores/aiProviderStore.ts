@@ -25,6 +25,8 @@ interface AiProviderState {
   cliProviders: CliProviderInfo[];
   restProviders: RestProviderConfig[];
   detecting: boolean;
+  /** Keep the Ollama model loaded between genie runs (background pings) */
+  ollamaKeepWarm: boolean;
 }
 
 interface AiProviderActions {
@@ -38,6 +40,7 @@ interface AiProviderActions {
     type: RestProviderType,
     updates: Partial<RestProviderConfig>
   ): void;
+  setOllamaKeepWarm(keepWarm: boolean): void;
   /** Load API keys from environment variables into empty REST provider fields. */
   loadEnvApiKeys(): Promise<void>;
   getActiveProviderName(): string;
@@ -97,6 +100,7 @@ export const useAiProviderStore = create<AiProviderState & AiProviderActions>()(
       activeProvider: null,
       cliProviders: [],
       restProviders: DEFAULT_REST_PROVIDERS,
+      ollamaKeepWarm: false,
       detecting: false,
 
       detectProviders: async () => {
@@ -182,6 +186,10 @@ export const useAiProviderStore = create<AiProviderState & AiProviderActions>()(
         }));
       },
 
+      setOllamaKeepWarm: (keepWarm) => {
+        set({ ollamaKeepWarm: keepWarm });
+      },
+
       loadEnvApiKeys: async () => {
         try {
           const envKeys: Record<string, string> =
@@ -219,6 +227,7 @@ export const useAiProviderStore = create<AiProviderState & AiProviderActions>()(
       partialize: (state) => ({
         activeProvider: state.activeProvider,
         restProviders: state.restProviders,
+        ollamaKeepWarm: state.ollamaKeepWarm,
       }),
       onRehydrateStorage: () => {
         // After hydration:
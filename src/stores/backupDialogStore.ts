@@ -0,0 +1,22 @@
+/**
+ * Backup Dialog Store
+ *
+ * Minimal open/close state for the workspace backups dialog.
+ */
+
+import { create } from "zustand";
+
+interface BackupDialogState {
+  isOpen: boolean;
+}
+
+interface BackupDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useBackupDialogStore = create<BackupDialogState & BackupDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
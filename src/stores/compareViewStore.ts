@@ -0,0 +1,32 @@
+/**
+ * Compare View Store
+ *
+ * The two texts shown side by side by CompareView, with a label for each.
+ * Opened by `vmark --diff a b` and by History → Compare with current.
+ */
+
+import { create } from "zustand";
+
+export interface CompareRequest {
+  leftLabel: string;
+  rightLabel: string;
+  /** Full path or description shown on hover */
+  title: string;
+  left: string;
+  right: string;
+}
+
+interface CompareViewState {
+  request: CompareRequest | null;
+}
+
+interface CompareViewActions {
+  open(request: CompareRequest): void;
+  close(): void;
+}
+
+export const useCompareViewStore = create<CompareViewState & CompareViewActions>((set) => ({
+  request: null,
+  open: (request) => set({ request }),
+  close: () => set({ request: null }),
+}));
@@ -0,0 +1,22 @@
+/**
+ * Flavor Dialog Store
+ *
+ * Minimal open/close state for the Markdown flavor conversion dialog.
+ */
+
+import { create } from "zustand";
+
+interface FlavorDialogState {
+  isOpen: boolean;
+}
+
+interface FlavorDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useFlavorDialogStore = create<FlavorDialogState & FlavorDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
@@ -0,0 +1,22 @@
+/**
+ * Insights Dialog Store
+ *
+ * Minimal open/close state for the workspace insights dialog.
+ */
+
+import { create } from "zustand";
+
+interface InsightsDialogState {
+  isOpen: boolean;
+}
+
+interface InsightsDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useInsightsDialogStore = create<InsightsDialogState & InsightsDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
@@ -0,0 +1,41 @@
+/**
+ * Passphrase Dialog Store
+ *
+ * The pending passphrase request, if any, and the callback that answers it.
+ */
+
+import { create } from "zustand";
+
+interface PassphraseRequestState {
+  path: string;
+  /** A new passphrase, to be typed twice */
+  confirm: boolean;
+  resolve: (passphrase: string | null) => void;
+}
+
+interface PassphraseDialogState {
+  request: PassphraseRequestState | null;
+}
+
+interface PassphraseDialogActions {
+  /** Ask for a passphrase; a request still open is cancelled. */
+  requestPassphrase(path: string, confirm: boolean): Promise<string | null>;
+  /** Answer the open request (null cancels it). */
+  finish(passphrase: string | null): void;
+}
+
+export const usePassphraseDialogStore = create<PassphraseDialogState & PassphraseDialogActions>(
+  (set, get) => ({
+    request: null,
+    requestPassphrase: (path, confirm) =>
+      new Promise((resolve) => {
+        get().request?.resolve(null);
+        set({ request: { path, confirm, resolve } });
+      }),
+    finish: (passphrase) => {
+      const request = get().request;
+      set({ request: null });
+      request?.resolve(passphrase);
+    },
+  })
+);
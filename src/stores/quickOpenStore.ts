@@ -0,0 +1,22 @@
+/**
+ * Quick Open Store
+ *
+ * Minimal open/close state for the quick open (Cmd+P) file switcher.
+ */
+
+import { create } from "zustand";
+
+interface QuickOpenState {
+  isOpen: boolean;
+}
+
+interface QuickOpenActions {
+  open(): void;
+  close(): void;
+}
+
+export const useQuickOpenStore = create<QuickOpenState & QuickOpenActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
@@ -0,0 +1,22 @@
+/**
+ * Readability Dialog Store
+ *
+ * Minimal open/close state for the readability report.
+ */
+
+import { create } from "zustand";
+
+interface ReadabilityDialogState {
+  isOpen: boolean;
+}
+
+interface ReadabilityDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useReadabilityDialogStore = create<ReadabilityDialogState & ReadabilityDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
@@ -194,12 +194,50 @@ export interface TerminalSettings {
   fontSize: number;    // Default: 13 (range: 10–24)
   lineHeight: number;  // Default: 1.4 (range: 1.0–2.0)
   copyOnSelect: boolean; // Default: false — auto-copy selected text to clipboard
+  env: Record<string, string>; // Extra environment variables ($PATH expands to the login PATH)
+  startupCommand: string; // Command typed into new sessions after spawn (e.g. venv activation)
+  restoreScrollback: boolean; // Default: false — save recent output to disk for replay after update restarts
+}
+
+export interface TlsSettings {
+  caBundlePath: string | null; // PEM file with extra root certificates (trusted alongside system roots)
+  insecureHosts: string[]; // Hosts ("host" or "host:port") whose certificates are not verified
+}
+
+export type RedactionEntity = "email" | "apiKey" | "phone" | "ipAddress";
+
+export interface PromptRedactionSettings {
+  enabled: boolean; // Redact prompts sent to remote AI providers (default: false)
+  entities: RedactionEntity[]; // Built-in detectors to apply
+  names: string[]; // Names and other literal terms, matched as whole words
+  patterns: string[]; // Extra regular expressions
+}
+
+export interface SaveHook {
+  genie: string; // Genie file relative to the genies folder (e.g. "metadata/summary.md")
+  field: string; // Frontmatter field filled when missing
+  list: boolean; // Write the output as a list
+}
+
+export interface SaveHookSettings {
+  enabled: boolean; // Run hooks after saving (default: false)
+  hooks: SaveHook[];
+  cooldownSecs: number; // Minimum seconds between runs for the same note
+  maxRunsPerHour: number; // Genie runs allowed per hour across all notes
+}
+
+export interface SemanticSearchSettings {
+  endpoint: string; // Ollama endpoint that embeds notes for semantic search
 }
 
 export interface AdvancedSettingsState {
   mcpServer: McpServerSettings;
   customLinkProtocols: string[]; // Custom URL protocols to recognize (e.g., "obsidian", "vscode")
   keepBothEditorsAlive: boolean; // Keep both editors mounted for faster mode switching (default: false)
+  tls: TlsSettings; // TLS options for self-hosted AI, WebDAV and S3 endpoints
+  promptRedaction: PromptRedactionSettings; // Placeholder substitution before prompts leave the machine
+  saveHooks: SaveHookSettings; // Genies that fill in missing frontmatter after saving
+  semanticSearch: SemanticSearchSettings; // Embedding server for semantic search and notes context
 }
 
 export interface MarkdownSettings {
@@ -221,6 +259,10 @@ export interface MarkdownSettings {
   autoPairRightDoubleQuote: boolean; // Typing " also inserts "" pair (IME compat)
   copyFormat: CopyFormat; // What to put in text/plain on copy (default = plain text, markdown = markdown syntax)
   copyOnSelect: boolean; // Auto-copy selected text to clipboard
+  embedExportMetadata: boolean; // Stamp title, author, date, git commit and version into exports
+  smartTypographyOnExport: boolean; // Curly quotes, dashes and ellipses in exported documents
+  fetchLinkTitles: boolean; // Pasting a bare URL fetches the page title for the link text
+  linkPreviews: boolean; // Link popup shows a preview card for web links
 }
 
 // Image auto-resize options (0 = off, positive = max dimension in pixels)
@@ -238,6 +280,8 @@ export interface ImageSettings {
   copyToAssets: boolean;
   // Auto-cleanup orphaned images when closing a document
   cleanupOrphansOnClose: boolean;
+  // Tesseract language codes for Copy Text from Image (empty = English)
+  ocrLanguages: string[];
 }
 
 export interface GeneralSettings {
@@ -251,6 +295,8 @@ export interface GeneralSettings {
   // Editor
   tabSize: number; // Number of spaces for Tab key (2 or 4)
   lineEndingsOnSave: LineEndingOnSave; // Preserve or normalize line endings
+  // Advisory `.~lock` files for notes on shared drives
+  lockFilesOnOpen: boolean;
 }
 
 export type UpdateCheckFrequency = "startup" | "daily" | "weekly" | "manual";
@@ -261,6 +307,7 @@ export interface UpdateSettings {
   autoDownload: boolean; // Download updates automatically
   lastCheckTimestamp: number | null; // Unix timestamp of last check
   skipVersion: string | null; // Version to skip (user clicked "Skip")
+  rolloutBucket: number | null; // Stable per-install 0-99 bucket for staged rollouts (set on first check)
 }
 
 interface SettingsState {
@@ -322,6 +369,7 @@ const initialState: SettingsState = {
     historyMaxAgeDays: 7,
     tabSize: 2,
     lineEndingsOnSave: "preserve",
+    lockFilesOnOpen: false,
   },
   appearance: {
     theme: "paper",
@@ -383,6 +431,10 @@ const initialState: SettingsState = {
     autoPairRightDoubleQuote: false,
     copyFormat: "default",
     copyOnSelect: false,
+    embedExportMetadata: false,
+    smartTypographyOnExport: false,
+    fetchLinkTitles: false, // Off by default - pasting would make network requests
+    linkPreviews: false, // Off by default - opening a link would make network requests
   },
   image: {
     autoResizeMax: 0, // Off by default
@@ -390,11 +442,15 @@ const initialState: SettingsState = {
     inlineThreshold: 1.0, // 1.0× line height
     copyToAssets: true,
     cleanupOrphansOnClose: false, // Off by default - user must opt in
+    ocrLanguages: [],
   },
   terminal: {
     fontSize: 13,
     lineHeight: 1.2,
     copyOnSelect: false,
+    env: {},
+    startupCommand: "",
+    restoreScrollback: false,
   },
   advanced: {
     mcpServer: {
@@ -405,6 +461,28 @@ const initialState: SettingsState = {
     },
     customLinkProtocols: ["obsidian", "vscode", "dict", "x-dictionary"],
     keepBothEditorsAlive: false,
+    tls: {
+      caBundlePath: null,
+      insecureHosts: [],
+    },
+    promptRedaction: {
+      enabled: false,
+      entities: ["email", "apiKey"],
+      names: [],
+      patterns: [],
+    },
+    saveHooks: {
+      enabled: false,
+      hooks: [
+        { genie: "metadata/summary.md", field: "summary", list: false },
+        { genie: "metadata/keywords.md", field: "keywords", list: true },
+      ],
+      cooldownSecs: 600,
+      maxRunsPerHour: 20,
+    },
+    semanticSearch: {
+      endpoint: "http://localhost:11434",
+    },
   },
   update: {
     autoCheckEnabled: true,
@@ -412,6 +490,7 @@ const initialState: SettingsState = {
     autoDownload: false,
     lastCheckTimestamp: null,
     skipVersion: null,
+    rolloutBucket: null,
   },
   showDevSection: false,
 };
@@ -12,7 +12,9 @@ import {
   CATEGORY_LABELS,
   getShortcutsByCategory,
   formatKeyForDisplay,
+  loadMenuShortcutsFromKeymap,
 } from "./shortcutsStore";
+import { invoke } from "@tauri-apps/api/core";
 
 // Mock Tauri invoke
 vi.mock("@tauri-apps/api/core", () => ({
@@ -317,4 +319,36 @@ describe("shortcutsStore", () => {
       expect(formatKeyForDisplay("Mod-Down")).toBe("⌘↓");
     });
   });
+
+  describe("loadMenuShortcutsFromKeymap", () => {
+    it("takes menu bindings from the keymap", async () => {
+      useShortcutsStore.setState({ customBindings: { italic: "Alt-i", newTab: "Mod-Alt-t" } });
+      vi.mocked(invoke).mockResolvedValueOnce([
+        { id: "bold", defaultAccelerator: "CmdOrCtrl+B", accelerator: "CmdOrCtrl+Shift+B", overridden: true },
+        { id: "italic", defaultAccelerator: "CmdOrCtrl+I", accelerator: "CmdOrCtrl+I", overridden: false },
+      ]);
+
+      await loadMenuShortcutsFromKeymap();
+
+      const { customBindings } = useShortcutsStore.getState();
+      expect(customBindings.bold).toBe("Mod-Shift-b");
+      expect(customBindings.italic).toBeUndefined();
+      // Not a menu item: untouched
+      expect(customBindings.newTab).toBe("Mod-Alt-t");
+    });
+
+    it("pushes older menu customizations to an empty keymap", async () => {
+      useShortcutsStore.setState({ customBindings: { bold: "Mod-Shift-b" } });
+      vi.mocked(invoke).mockResolvedValueOnce([
+        { id: "bold", defaultAccelerator: "CmdOrCtrl+B", accelerator: "CmdOrCtrl+B", overridden: false },
+      ]);
+
+      await loadMenuShortcutsFromKeymap();
+
+      expect(invoke).toHaveBeenCalledWith("rebuild_menu", {
+        shortcuts: expect.objectContaining({ bold: "CmdOrCtrl+Shift+B" }),
+      });
+      expect(useShortcutsStore.getState().customBindings.bold).toBe("Mod-Shift-b");
+    });
+  });
 });
@@ -101,6 +101,7 @@ export const DEFAULT_SHORTCUTS: ShortcutDefinition[] = [
   { id: "formatCJKSelection", label: "Format CJK Selection", category: "editing", defaultKey: "Mod-Shift-f", menuId: "format-cjk" },
   { id: "formatCJKFile", label: "Format CJK File", category: "editing", defaultKey: "Alt-Mod-Shift-f", menuId: "format-cjk-file" },
   { id: "copyAsHTML", label: "Copy as HTML", category: "editing", defaultKey: "Mod-Shift-c", menuId: "copy-html" },
+  { id: "copyAsRichText", label: "Copy as Rich Text", category: "editing", defaultKey: "", menuId: "copy-rich-text", description: "Copy formatted text for Word, Mail or Google Docs" },
   { id: "pastePlainText", label: "Paste as Plain Text", category: "editing", defaultKey: "Mod-Shift-v", description: "Paste without formatting in WYSIWYG" },
   { id: "toggleComment", label: "Toggle Comment", category: "editing", defaultKey: "Mod-/", description: "Insert HTML comment <!-- -->" },
   { id: "aiPrompts", label: "AI Genies", category: "editing", defaultKey: "Mod-y", menuId: "search-genies", scope: "global", description: "Open AI genie picker" },
@@ -142,12 +143,13 @@ export const DEFAULT_SHORTCUTS: ShortcutDefinition[] = [
   { id: "newFile", label: "New File", category: "file", defaultKey: "Mod-n", menuId: "new", scope: "global" },
   { id: "openFile", label: "Open File", category: "file", defaultKey: "Mod-o", menuId: "open", scope: "global" },
   { id: "openFolder", label: "Open Folder", category: "file", defaultKey: "Mod-Shift-o", menuId: "open-folder", scope: "global" },
+  { id: "quickOpen", label: "Quick Open", category: "file", defaultKey: "Mod-p", menuId: "quick-open", description: "Find and open a workspace file", scope: "global" },
   { id: "save", label: "Save", category: "file", defaultKey: "Mod-s", menuId: "save", scope: "global" },
   { id: "saveAs", label: "Save As", category: "file", defaultKey: "Mod-Shift-s", menuId: "save-as", scope: "global" },
   { id: "moveTo", label: "Move to", category: "file", defaultKey: "", menuId: "move-to", scope: "global" },
   { id: "closeFile", label: "Close", category: "file", defaultKey: "Mod-w", menuId: "close", scope: "global" },
   { id: "exportHTML", label: "Export HTML", category: "file", defaultKey: "Alt-Mod-e", menuId: "export-html", scope: "global" },
-  { id: "print", label: "Print", category: "file", defaultKey: "Mod-p", menuId: "export-pdf", scope: "global" },
+  { id: "print", label: "Print", category: "file", defaultKey: "", menuId: "export-pdf", scope: "global" },
   { id: "preferences", label: "Settings", category: "file", defaultKey: "Mod-,", menuId: "preferences", scope: "global" },
   { id: "saveAllQuit", label: "Save All and Quit", category: "file", defaultKey: "Alt-Mod-Shift-q", menuId: "save-all-quit", scope: "global" },
 
@@ -361,16 +363,55 @@ async function syncMenuShortcuts(shortcuts: Record<string, string>) {
         menuShortcuts[def.menuId] = prosemirrorToTauri(key);
       }
     }
+    // rebuild_menu re-populates the Genies submenu when it was shown
     await invoke("rebuild_menu", { shortcuts: menuShortcuts });
-
-    // rebuild_menu resets the Genies submenu to a placeholder — re-populate it
-    await invoke("refresh_genies_menu");
   } catch (e) {
     // Menu rebuild may fail if command not yet implemented
     console.warn("Failed to sync menu shortcuts:", e);
   }
 }
 
+/** Menu shortcut as reported by get_keymap (keymap.rs) */
+interface KeymapEntry {
+  id: string;
+  defaultAccelerator: string;
+  accelerator: string;
+  overridden: boolean;
+}
+
+/**
+ * Take menu bindings from the backend keymap (keymap.json), where
+ * syncMenuShortcuts saves them. Bindings of non-menu shortcuts stay as they
+ * are. Menu customizations from before the keymap existed are pushed to it
+ * once instead.
+ */
+export async function loadMenuShortcutsFromKeymap(): Promise<void> {
+  const entries = await invoke<KeymapEntry[]>("get_keymap");
+  const store = useShortcutsStore.getState();
+  const menuDefs = DEFAULT_SHORTCUTS.filter((def) => def.menuId);
+
+  if (!entries.some((e) => e.overridden)) {
+    if (menuDefs.some((def) => store.isCustomized(def.id))) {
+      await syncMenuShortcuts(store.getAllShortcuts());
+    }
+    return;
+  }
+
+  const byMenuId = new Map(entries.map((e) => [e.id, e]));
+  const customBindings = { ...store.customBindings };
+  for (const def of menuDefs) {
+    const entry = byMenuId.get(def.menuId!);
+    if (!entry) continue;
+    const key = tauriToProsemirror(entry.accelerator);
+    if (entry.overridden && normalizeKey(key) !== normalizeKey(resolveDefaultKey(def))) {
+      customBindings[def.id] = key;
+    } else {
+      delete customBindings[def.id];
+    }
+  }
+  useShortcutsStore.setState({ customBindings });
+}
+
 /**
  * Convert ProseMirror key format to Tauri accelerator format.
  * Mod-b -> CmdOrCtrl+B
@@ -386,6 +427,20 @@ function prosemirrorToTauri(key: string): string {
     .replace(/\+(\w)$/, (_, char) => `+${char.toUpperCase()}`);
 }
 
+/**
+ * Convert Tauri accelerator format to ProseMirror key format.
+ * CmdOrCtrl+Shift+B -> Mod-Shift-b
+ */
+function tauriToProsemirror(accelerator: string): string {
+  if (!accelerator) return "";
+  const parts = accelerator.split("+");
+  const key = parts.pop() ?? "";
+  const modifiers = parts.map((part) =>
+    /^(CmdOrCtrl|CommandOrControl)$/i.test(part) ? "Mod" : part
+  );
+  return [...modifiers, key.length === 1 ? key.toLowerCase() : key].join("-");
+}
+
 /**
  * Format key for display (user-friendly).
  * Mod-b -> ⌘B (on macOS)
@@ -0,0 +1,22 @@
+/**
+ * Symbol Dialog Store
+ *
+ * Minimal open/close state for the emoji and symbol picker.
+ */
+
+import { create } from "zustand";
+
+interface SymbolDialogState {
+  isOpen: boolean;
+}
+
+interface SymbolDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useSymbolDialogStore = create<SymbolDialogState & SymbolDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
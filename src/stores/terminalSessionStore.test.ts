@@ -88,4 +88,25 @@ describe("terminalSessionStore", () => {
     const s3 = useTerminalSessionStore.getState().createSession()!;
     expect(s3.label).toBe("Terminal 1");
   });
+
+  it("restores sessions and keeps new ids unique", () => {
+    useTerminalSessionStore.getState().restoreSessions(
+      [
+        { id: "term-3", label: "Terminal 1", cwd: "/w", shellPath: "/bin/zsh" },
+        { id: "term-7", label: "Build" },
+      ],
+      "term-7",
+    );
+
+    const state = useTerminalSessionStore.getState();
+    expect(state.sessions.map((s) => s.id)).toEqual(["term-3", "term-7"]);
+    expect(state.activeSessionId).toBe("term-7");
+    expect(state.sessions[0].restore).toEqual({ cwd: "/w", shellPath: "/bin/zsh" });
+
+    const next = useTerminalSessionStore.getState().createSession()!;
+    expect(next.id).toBe("term-8");
+
+    useTerminalSessionStore.getState().clearRestore("term-3");
+    expect(useTerminalSessionStore.getState().sessions[0].restore).toBeUndefined();
+  });
 });
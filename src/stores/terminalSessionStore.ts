@@ -4,6 +4,16 @@ export interface TerminalSession {
   id: string;
   label: string;
   isAlive: boolean;
+  /** Cwd/shell to use for the first spawn after a hot-exit restore */
+  restore?: { cwd?: string; shellPath?: string };
+}
+
+/** Session recreated from a hot-exit snapshot */
+export interface RestoredTerminalSession {
+  id: string;
+  label: string;
+  cwd?: string;
+  shellPath?: string;
 }
 
 const MAX_SESSIONS = 5;
@@ -19,6 +29,8 @@ interface TerminalSessionActions {
   setActiveSession: (id: string) => void;
   markSessionDead: (id: string) => void;
   renameSession: (id: string, label: string) => void;
+  restoreSessions: (sessions: RestoredTerminalSession[], activeId: string | null) => void;
+  clearRestore: (id: string) => void;
 }
 
 let nextId = 1;
@@ -27,6 +39,12 @@ function generateId(): string {
   return `term-${nextId++}`;
 }
 
+/** Keep generated ids ahead of restored `term-N` ids. */
+function reserveId(id: string): void {
+  const m = id.match(/^term-(\d+)$/);
+  if (m) nextId = Math.max(nextId, parseInt(m[1], 10) + 1);
+}
+
 function generateLabel(sessions: TerminalSession[]): string {
   // Find lowest unused number
   const used = new Set(
@@ -101,6 +119,34 @@ export const useTerminalSessionStore = create<
       ),
     }));
   },
+
+  restoreSessions: (restored, activeId) => {
+    const sessions: TerminalSession[] = restored
+      .slice(0, MAX_SESSIONS)
+      .map((r) => {
+        reserveId(r.id);
+        return {
+          id: r.id,
+          label: r.label,
+          isAlive: true,
+          restore: { cwd: r.cwd, shellPath: r.shellPath },
+        };
+      });
+    if (sessions.length === 0) return;
+
+    const active = sessions.some((s) => s.id === activeId)
+      ? activeId
+      : sessions[0].id;
+    set({ sessions, activeSessionId: active });
+  },
+
+  clearRestore: (id) => {
+    set((state) => ({
+      sessions: state.sessions.map((s) =>
+        s.id === id && s.restore ? { ...s, restore: undefined } : s,
+      ),
+    }));
+  },
 }));
 
 /** Reset store and ID counter — for tests only. */
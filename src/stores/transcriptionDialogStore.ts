@@ -0,0 +1,22 @@
+/**
+ * Transcription Dialog Store
+ *
+ * Minimal open/close state for the audio transcription dialog.
+ */
+
+import { create } from "zustand";
+
+interface TranscriptionDialogState {
+  isOpen: boolean;
+}
+
+interface TranscriptionDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useTranscriptionDialogStore = create<TranscriptionDialogState & TranscriptionDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
@@ -1,6 +1,6 @@
 import { create } from "zustand";
 
-export type SidebarViewMode = "files" | "outline" | "history";
+export type SidebarViewMode = "files" | "outline" | "history" | "tasks";
 
 // Sidebar width constraints
 const SIDEBAR_MIN_WIDTH = 180;
@@ -0,0 +1,22 @@
+/**
+ * Workspace Q&A Dialog Store
+ *
+ * Minimal open/close state for the Ask Workspace dialog.
+ */
+
+import { create } from "zustand";
+
+interface WorkspaceQaDialogState {
+  isOpen: boolean;
+}
+
+interface WorkspaceQaDialogActions {
+  open(): void;
+  close(): void;
+}
+
+export const useWorkspaceQaDialogStore = create<WorkspaceQaDialogState & WorkspaceQaDialogActions>((set) => ({
+  isOpen: false,
+  open: () => set({ isOpen: true }),
+  close: () => set({ isOpen: false }),
+}));
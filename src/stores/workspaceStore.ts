@@ -11,13 +11,26 @@ import {
 import { windowScopedStorage } from "@/utils/workspaceStorage";
 
 // Workspace configuration stored in .vmark file
+// Per-workspace editor settings (schema v2); unset fields inherit global settings
+export interface WorkspaceEditorSettings {
+  lineEnding?: "lf" | "crlf";
+  assetsFolder?: string; // Relative to workspace root
+  exportTheme?: string;
+  spellcheckLanguage?: string; // BCP 47 tag, e.g. "en-US"
+  encryption?: EncryptionPolicy; // For new documents saved in the workspace
+}
+
+export type EncryptionPolicy = "off" | "ask" | "always";
+
 export interface WorkspaceConfig {
-  version: 1;
+  version: 1 | 2;
   excludeFolders: string[];
   lastOpenTabs: string[]; // File paths for session restore
   showHiddenFiles: boolean;
   ai?: Record<string, unknown>; // Future AI settings
   identity?: WorkspaceIdentity; // Workspace identity and trust info
+  editor?: WorkspaceEditorSettings;
+  tasks?: Record<string, string>; // Named one-shot commands, e.g. { preview: "hugo server" }
 }
 
 // Runtime workspace state
@@ -0,0 +1,22 @@
+/**
+ * Writing Goals Store
+ *
+ * The latest goal progress reported by the backend, so the status bar
+ * updates after each save without asking again.
+ */
+
+import { create } from "zustand";
+import type { GoalProgress } from "@/utils/writingGoals";
+
+interface WritingGoalsState {
+  progress: GoalProgress | null;
+}
+
+interface WritingGoalsActions {
+  setProgress(progress: GoalProgress): void;
+}
+
+export const useWritingGoalsStore = create<WritingGoalsState & WritingGoalsActions>((set) => ({
+  progress: null,
+  setProgress: (progress) => set({ progress }),
+}));
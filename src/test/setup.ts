@@ -62,16 +62,6 @@ vi.mock("@tauri-apps/api/webviewWindow", () => {
   };
 });
 
-vi.mock("tauri-pty", () => ({
-  spawn: vi.fn(() => ({
-    onData: vi.fn(),
-    onExit: vi.fn(),
-    write: vi.fn(),
-    resize: vi.fn(),
-    kill: vi.fn(),
-  })),
-}));
-
 vi.mock("@xterm/xterm", () => {
   const Terminal = vi.fn(() => ({
     loadAddon: vi.fn(),
@@ -23,6 +23,8 @@ export interface GenieMetadata {
   action?: GenieAction;
   /** Number of surrounding blocks to include as context (0–2). */
   context?: number;
+  /** Target language of a translation genie (e.g. "en"); results are kept in translation memory. */
+  translate?: string;
 }
 
 export interface GenieDefinition {
@@ -0,0 +1,34 @@
+/**
+ * Active Provider Config
+ *
+ * Purpose: The active AI provider's settings in the shape backend
+ * commands take them (`ProviderConfig`), for features that prompt the
+ * provider from Rust — workspace Q&A and save hooks.
+ *
+ * @module utils/activeProvider
+ */
+
+import { useAiProviderStore } from "@/stores/aiProviderStore";
+
+export interface ProviderConfig {
+  provider: string;
+  model: string | null;
+  apiKey: string | null;
+  endpoint: string | null;
+  /** Resolved CLI path from detection */
+  cliPath: string | null;
+}
+
+/** Settings of the active provider; `null` when none is selected. */
+export function activeProviderConfig(): ProviderConfig | null {
+  const { activeProvider, restProviders, cliProviders } = useAiProviderStore.getState();
+  if (!activeProvider) return null;
+  const rest = restProviders.find((p) => p.type === activeProvider);
+  return {
+    provider: activeProvider,
+    model: rest?.model ?? null,
+    apiKey: rest?.apiKey ?? null,
+    endpoint: rest?.endpoint ?? null,
+    cliPath: cliProviders.find((p) => p.type === activeProvider)?.path ?? null,
+  };
+}
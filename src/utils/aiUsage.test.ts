@@ -0,0 +1,53 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import {
+  EMPTY_LIMITS,
+  formatAiPromptError,
+  isBudgetError,
+  usageFor,
+  type BudgetExceededError,
+  type UsageSummary,
+} from "./aiUsage";
+
+const budgetError: BudgetExceededError = {
+  kind: "budget",
+  provider: "openai",
+  limit: "cost",
+  used: 10.5,
+  cap: 10,
+  retryAt: 0,
+  message: "openai has used its monthly budget of $10.00.",
+};
+
+describe("isBudgetError", () => {
+  it("recognizes budget errors only", () => {
+    expect(isBudgetError(budgetError)).toBe(true);
+    expect(isBudgetError({ kind: "failed", message: "boom" })).toBe(false);
+    expect(isBudgetError("Unknown provider: foo")).toBe(false);
+    expect(isBudgetError(null)).toBe(false);
+  });
+});
+
+describe("formatAiPromptError", () => {
+  it("uses the message of structured errors", () => {
+    expect(formatAiPromptError(budgetError)).toBe(budgetError.message);
+    expect(formatAiPromptError({ kind: "failed", message: "Unknown provider: foo" })).toBe(
+      "Unknown provider: foo"
+    );
+    expect(formatAiPromptError("plain")).toBe("plain");
+  });
+});
+
+describe("usageFor", () => {
+  it("returns zeros for providers without usage", () => {
+    const summary: UsageSummary = { month: "2026-03", resetsAt: 0, providers: [] };
+    expect(usageFor(summary, "anthropic")).toMatchObject({
+      provider: "anthropic",
+      requests: 0,
+      limits: EMPTY_LIMITS,
+    });
+    expect(usageFor(null, "openai").cost).toBe(0);
+  });
+});
@@ -0,0 +1,110 @@
+/**
+ * AI Usage Limits
+ *
+ * Purpose: Per-provider rate limits and monthly token/cost budgets enforced
+ * by the backend AI router, and this month's usage counters. When a cap is
+ * reached, `run_ai_prompt` rejects with a budget error instead of sending
+ * the prompt; `formatAiPromptError` turns that into a readable message.
+ *
+ * @module utils/aiUsage
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ProviderLimits {
+  requestsPerMinute: number | null;
+  /** Input plus output tokens per calendar month */
+  monthlyTokens: number | null;
+  /** Estimated spend per calendar month, in USD */
+  monthlyCost: number | null;
+  /** USD per million input tokens */
+  inputPrice: number | null;
+  /** USD per million output tokens */
+  outputPrice: number | null;
+}
+
+export interface ProviderUsage {
+  provider: string;
+  requests: number;
+  inputTokens: number;
+  outputTokens: number;
+  cost: number;
+  requestsLastMinute: number;
+  limits: ProviderLimits;
+}
+
+export interface UsageSummary {
+  /** YYYY-MM */
+  month: string;
+  /** When monthly counters reset (ms since epoch) */
+  resetsAt: number;
+  providers: ProviderUsage[];
+}
+
+export type BudgetLimit = "rate" | "tokens" | "cost";
+
+export interface BudgetExceededError {
+  kind: "budget";
+  provider: string;
+  limit: BudgetLimit;
+  used: number;
+  cap: number;
+  /** When the limit frees up (ms since epoch) */
+  retryAt: number;
+  message: string;
+}
+
+export interface AiPromptFailedError {
+  kind: "failed";
+  message: string;
+}
+
+export type AiPromptError = BudgetExceededError | AiPromptFailedError;
+
+export const EMPTY_LIMITS: ProviderLimits = {
+  requestsPerMinute: null,
+  monthlyTokens: null,
+  monthlyCost: null,
+  inputPrice: null,
+  outputPrice: null,
+};
+
+export function getUsageSummary(): Promise<UsageSummary> {
+  return invoke<UsageSummary>("get_usage_summary");
+}
+
+/** Set a provider's limits and prices; all-null limits remove them. */
+export function setUsageLimits(provider: string, limits: ProviderLimits): Promise<UsageSummary> {
+  return invoke<UsageSummary>("set_usage_limits", { provider, limits });
+}
+
+export function isBudgetError(error: unknown): error is BudgetExceededError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    (error as { kind?: unknown }).kind === "budget"
+  );
+}
+
+/** Message for an error thrown by `run_ai_prompt`. */
+export function formatAiPromptError(error: unknown): string {
+  if (typeof error === "object" && error !== null && "message" in error) {
+    return String((error as { message: unknown }).message);
+  }
+  return String(error);
+}
+
+/** Usage of `provider` this month, or zeros when it has none yet. */
+export function usageFor(summary: UsageSummary | null, provider: string): ProviderUsage {
+  return (
+    summary?.providers.find((p) => p.provider === provider) ?? {
+      provider,
+      requests: 0,
+      inputTokens: 0,
+      outputTokens: 0,
+      cost: 0,
+      requestsLastMinute: 0,
+      limits: EMPTY_LIMITS,
+    }
+  );
+}
@@ -0,0 +1,17 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { automationUrl } from "./automation";
+
+describe("automationUrl", () => {
+  it("encodes parameters and skips empty ones", () => {
+    expect(
+      automationUrl("append", { text: "Buy milk & eggs", path: "/notes/a.md", open: true, title: "" })
+    ).toBe("vmark://append?text=Buy%20milk%20%26%20eggs&path=%2Fnotes%2Fa.md&open=true");
+  });
+
+  it("omits the query when there are no parameters", () => {
+    expect(automationUrl("new", { open: false })).toBe("vmark://new");
+  });
+});
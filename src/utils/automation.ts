@@ -0,0 +1,49 @@
+/**
+ * Automation
+ *
+ * Purpose: The `vmark://` URL scheme used by Shortcuts, the share sheet and
+ * AppleScript, plus command wrappers for the same actions.
+ *
+ * @module utils/automation
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type AutomationAction = "open" | "new" | "append";
+
+/**
+ * Build a `vmark://` URL, e.g. for a Shortcuts "Open URLs" action.
+ * Empty parameters are left out.
+ */
+export function automationUrl(
+  action: AutomationAction,
+  params: Record<string, string | boolean | undefined> = {}
+): string {
+  const query = new URLSearchParams();
+  for (const [key, value] of Object.entries(params)) {
+    if (value === undefined || value === "" || value === false) continue;
+    query.set(key, String(value));
+  }
+  const search = query.toString().replace(/\+/g, "%20");
+  return search ? `vmark://${action}?${search}` : `vmark://${action}`;
+}
+
+/**
+ * Create a note (in the inbox's folder by default). Returns its path.
+ * Folders outside the inbox's folder and the open workspaces are refused.
+ */
+export function createNote(text: string, title?: string, folder?: string): Promise<string> {
+  return invoke<string>("automation_create_note", {
+    text,
+    title: title ?? null,
+    folder: folder ?? null,
+  });
+}
+
+/**
+ * Append to a note, or the quick capture inbox when `path` is omitted.
+ * Notes outside the inbox's folder and the open workspaces are refused.
+ */
+export function appendToNote(text: string, path?: string): Promise<string> {
+  return invoke<string>("automation_append_to_note", { text, path: path ?? null });
+}
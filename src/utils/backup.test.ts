@@ -0,0 +1,32 @@
+import { describe, it, expect } from "vitest";
+import { describeBackupProgress, formatBackupSize, type BackupProgress } from "./backup";
+
+const progress = (overrides: Partial<BackupProgress>): BackupProgress => ({
+  rootPath: "/ws",
+  stage: "archiving",
+  current: 0,
+  total: 0,
+  message: null,
+  ...overrides,
+});
+
+describe("describeBackupProgress", () => {
+  it("shows archiving counts", () => {
+    expect(describeBackupProgress(progress({ current: 50, total: 120 }))).toBe("Archiving 50/120");
+    expect(describeBackupProgress(progress({}))).toBe("Archiving");
+  });
+
+  it("includes the failure reason", () => {
+    expect(describeBackupProgress(progress({ stage: "failed", message: "S3 error (HTTP 403)" }))).toBe(
+      "Backup failed: S3 error (HTTP 403)"
+    );
+  });
+});
+
+describe("formatBackupSize", () => {
+  it("picks a readable unit", () => {
+    expect(formatBackupSize(512)).toBe("512 B");
+    expect(formatBackupSize(1536)).toBe("1.5 KB");
+    expect(formatBackupSize(3 * 1024 * 1024)).toBe("3.0 MB");
+  });
+});
@@ -0,0 +1,118 @@
+/**
+ * Workspace backups, run by the backend.
+ *
+ * Snapshots are `.tar.zst` archives stored in a local folder or an
+ * S3-compatible bucket. Settings live in `.vmark/backup.json`; the S3 secret
+ * key is stored in the OS keychain. Progress is emitted as `backup:progress`.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export type BackupTarget =
+  | { type: "local"; path: string }
+  | {
+      type: "s3";
+      endpoint: string;
+      region: string;
+      bucket: string;
+      prefix?: string;
+      accessKeyId: string;
+      /** `endpoint/bucket/key` URLs (MinIO and most self-hosted servers) */
+      pathStyle?: boolean;
+    };
+
+export interface BackupConfig {
+  target: BackupTarget;
+  /** Scheduled backup interval; 0 backs up only on demand */
+  intervalHours: number;
+  /** Snapshots to keep (0 keeps all) */
+  keep: number;
+  /** Extra folder/file names to leave out */
+  excludes: string[];
+}
+
+export interface BackupSnapshot {
+  name: string;
+  size: number;
+  createdAt: number;
+}
+
+export interface BackupProgress {
+  rootPath: string;
+  stage: "archiving" | "uploading" | "downloading" | "verifying" | "extracting" | "done" | "failed";
+  current: number;
+  total: number;
+  message: string | null;
+}
+
+export function getBackupConfig(rootPath: string): Promise<BackupConfig | null> {
+  return invoke<BackupConfig | null>("get_backup_config", { rootPath });
+}
+
+/** Save settings; the S3 secret key is only updated when given. */
+export function setBackupConfig(rootPath: string, config: BackupConfig, secretKey?: string): Promise<void> {
+  return invoke("set_backup_config", { rootPath, config, secretKey: secretKey ?? null });
+}
+
+export function createBackup(rootPath: string): Promise<BackupSnapshot> {
+  return invoke<BackupSnapshot>("create_backup", { rootPath });
+}
+
+/** Stored snapshots, newest first. */
+export function listBackups(rootPath: string): Promise<BackupSnapshot[]> {
+  return invoke<BackupSnapshot[]>("list_backups", { rootPath });
+}
+
+export function verifyBackup(rootPath: string, name: string): Promise<void> {
+  return invoke("verify_backup", { rootPath, name });
+}
+
+/** Restore a snapshot into an empty or new folder. */
+export function restoreBackup(rootPath: string, name: string, destination: string): Promise<void> {
+  return invoke("restore_backup", { rootPath, name, destination });
+}
+
+export function deleteBackup(rootPath: string, name: string): Promise<void> {
+  return invoke("delete_backup", { rootPath, name });
+}
+
+/** Returns false when the workspace has no backup interval configured. */
+export function startScheduledBackup(rootPath: string): Promise<boolean> {
+  return invoke<boolean>("start_scheduled_backup", { rootPath });
+}
+
+export function stopScheduledBackup(rootPath: string): Promise<void> {
+  return invoke("stop_scheduled_backup", { rootPath });
+}
+
+/** Snapshot size for display, e.g. "1.5 MB". */
+export function formatBackupSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  const units = ["KB", "MB", "GB"];
+  let value = bytes / 1024;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${value.toFixed(1)} ${units[unit]}`;
+}
+
+/** Status line for a progress event, e.g. "Archiving 50/120". */
+export function describeBackupProgress(progress: BackupProgress): string {
+  switch (progress.stage) {
+    case "archiving":
+      return progress.total > 0 ? `Archiving ${progress.current}/${progress.total}` : "Archiving";
+    case "uploading":
+      return "Uploading snapshot";
+    case "downloading":
+      return "Downloading snapshot";
+    case "verifying":
+      return "Verifying snapshot";
+    case "extracting":
+      return "Restoring files";
+    case "done":
+      return "Done";
+    case "failed":
+      return progress.message ? `Backup failed: ${progress.message}` : "Backup failed";
+  }
+}
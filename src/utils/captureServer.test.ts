@@ -0,0 +1,20 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { captureCurlExample } from "./captureServer";
+
+const status = { enabled: true, running: true, port: 51234, token: "abc", maxPerMinute: 30 };
+
+describe("captureCurlExample", () => {
+  it("includes the port and token", () => {
+    const example = captureCurlExample(status);
+    expect(example).toContain("http://127.0.0.1:51234/capture");
+    expect(example).toContain("Bearer abc");
+  });
+
+  it("is null before a port and token exist", () => {
+    expect(captureCurlExample({ ...status, port: null })).toBeNull();
+    expect(captureCurlExample({ ...status, token: null })).toBeNull();
+  });
+});
@@ -0,0 +1,48 @@
+/**
+ * Capture Server
+ *
+ * Purpose: Wrappers for the localhost endpoint browser extensions POST
+ * clippings to, plus a snippet users can paste into their own tools.
+ *
+ * @module utils/captureServer
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface CaptureServerStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number | null;
+  token: string | null;
+  maxPerMinute: number;
+}
+
+export function getCaptureServerStatus(): Promise<CaptureServerStatus> {
+  return invoke<CaptureServerStatus>("get_capture_server_status");
+}
+
+export function setCaptureServerEnabled(
+  enabled: boolean,
+  maxPerMinute?: number
+): Promise<CaptureServerStatus> {
+  return invoke<CaptureServerStatus>("set_capture_server_enabled", {
+    enabled,
+    maxPerMinute: maxPerMinute ?? null,
+  });
+}
+
+/** New token; extensions set up with the old one stop working. */
+export function regenerateCaptureServerToken(): Promise<CaptureServerStatus> {
+  return invoke<CaptureServerStatus>("regenerate_capture_server_token");
+}
+
+/** A `curl` command that captures "Hello" to the inbox. */
+export function captureCurlExample(status: CaptureServerStatus): string | null {
+  if (!status.port || !status.token) return null;
+  return [
+    `curl -X POST http://127.0.0.1:${status.port}/capture`,
+    `-H "Authorization: Bearer ${status.token}"`,
+    `-H "Content-Type: application/json"`,
+    `-d '{"text": "Hello"}'`,
+  ].join(" ");
+}
@@ -0,0 +1,30 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { copiedText, recordCopy } from "./clipboardHistory";
+
+describe("clipboardHistory", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("prefers the text the editor put on the clipboard", () => {
+    const event = {
+      clipboardData: { getData: (type: string) => (type === "text/plain" ? "**bold**" : "") },
+    } as unknown as ClipboardEvent;
+    expect(copiedText(event)).toBe("**bold**");
+  });
+
+  it("falls back to the DOM selection", () => {
+    vi.spyOn(window, "getSelection").mockReturnValue({ toString: () => "selected" } as Selection);
+    expect(copiedText({ clipboardData: null } as unknown as ClipboardEvent)).toBe("selected");
+  });
+
+  it("records non-blank copies only", async () => {
+    vi.mocked(invoke).mockResolvedValue(undefined);
+    await recordCopy("   ");
+    expect(invoke).not.toHaveBeenCalled();
+
+    await recordCopy("hello");
+    expect(invoke).toHaveBeenCalledWith("record_clipboard_copy", { text: "hello" });
+  });
+});
@@ -0,0 +1,39 @@
+/**
+ * Clipboard history (backend ring of recent copies made in VMark).
+ *
+ * Copies are recorded from document-level copy/cut events; the editor
+ * reads the ring back for "Paste from History".
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ClipboardEntry {
+  text: string;
+  /** Milliseconds since the Unix epoch */
+  copiedAt: number;
+}
+
+/** Text placed on the clipboard by a copy/cut event (editors set it before it bubbles). */
+export function copiedText(event: ClipboardEvent): string {
+  const data = event.clipboardData?.getData("text/plain");
+  if (data) return data;
+  return window.getSelection()?.toString() ?? "";
+}
+
+export async function recordCopy(text: string): Promise<void> {
+  if (!text.trim()) return;
+  try {
+    await invoke("record_clipboard_copy", { text });
+  } catch (error) {
+    console.warn("[ClipboardHistory] Failed to record copy:", error);
+  }
+}
+
+/** Recent copies, newest first. */
+export function listClipboardHistory(): Promise<ClipboardEntry[]> {
+  return invoke<ClipboardEntry[]>("list_clipboard_history");
+}
+
+/** Text of the nth copy (0 = most recent), which becomes the most recent entry. */
+export function pasteFromHistory(index: number): Promise<string> {
+  return invoke<string>("paste_clipboard_history", { index });
+}
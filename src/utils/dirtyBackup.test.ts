@@ -0,0 +1,35 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }));
+vi.mock("@tauri-apps/api/webviewWindow", () => ({
+  getCurrentWebviewWindow: () => ({ label: "main" }),
+}));
+
+import { pendingDocuments, sha256Hex } from "./dirtyBackup";
+
+describe("pendingDocuments", () => {
+  it("keeps only dirty, unencrypted tabs that have a document", () => {
+    const tabs = [
+      { id: "a", filePath: "/a.md", title: "a", isPinned: false },
+      { id: "b", filePath: null, title: "Untitled", isPinned: false },
+      { id: "c", filePath: "/c.md", title: "c", isPinned: false },
+      { id: "d", filePath: "/d.md.enc", title: "d", isPinned: false },
+    ];
+    const docs: Record<string, { content: string; isDirty: boolean }> = {
+      a: { content: "saved", isDirty: false },
+      b: { content: "draft", isDirty: true },
+      d: { content: "secret", isDirty: true },
+    };
+    const pending = pendingDocuments(tabs, (id) => docs[id]);
+    expect(pending.map((p) => [p.tab.id, p.content])).toEqual([["b", "draft"]]);
+  });
+});
+
+describe("sha256Hex", () => {
+  it("returns lowercase hex", async () => {
+    expect(await sha256Hex("abc")).toBe(
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+  });
+});
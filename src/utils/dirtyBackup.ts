@@ -0,0 +1,145 @@
+/**
+ * Dirty Document Backups
+ *
+ * Purpose: Periodic copies of unsaved documents. The Rust timer emits
+ * `dirty-backup:request`; each window reports digests of its dirty tabs
+ * and sends only the contents that have no copy yet.
+ *
+ * @module utils/dirtyBackup
+ */
+
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useTabStore, type Tab } from "@/stores/tabStore";
+import { useDocumentStore } from "@/stores/documentStore";
+import { isEncryptedPath } from "@/utils/encryptedDocuments";
+
+export const DIRTY_BACKUP_REQUEST_EVENT = "dirty-backup:request";
+
+export interface DirtyBackupConfig {
+  enabled: boolean;
+  intervalMinutes: number;
+  keepPerDocument: number;
+  maxAgeDays: number;
+}
+
+export interface DirtyBackup {
+  /** `<document key>/<file>` */
+  id: string;
+  path: string | null;
+  title: string;
+  /** Milliseconds since epoch */
+  createdAt: number;
+  size: number;
+}
+
+interface DirtyDocument {
+  tabId: string;
+  path: string | null;
+  title: string;
+  digest: string;
+}
+
+interface PendingDocument {
+  tab: Tab;
+  content: string;
+}
+
+/** Dirty tabs with their current content; encrypted documents are never copied. */
+export function pendingDocuments(
+  tabs: Tab[],
+  getDocument: (tabId: string) => { content: string; isDirty: boolean } | undefined
+): PendingDocument[] {
+  return tabs.flatMap((tab) => {
+    if (tab.filePath && isEncryptedPath(tab.filePath)) return [];
+    const doc = getDocument(tab.id);
+    return doc?.isDirty ? [{ tab, content: doc.content }] : [];
+  });
+}
+
+/** Lowercase hex SHA-256, matching the backend's content hash. */
+export async function sha256Hex(content: string): Promise<string> {
+  const digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(content));
+  return Array.from(new Uint8Array(digest))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+async function backupWindow(windowLabel: string): Promise<void> {
+  const tabs = useTabStore.getState().getTabsByWindow(windowLabel);
+  const pending = pendingDocuments(tabs, (id) => useDocumentStore.getState().getDocument(id));
+  if (pending.length === 0) return;
+
+  const documents = new Map<string, { document: DirtyDocument; content: string }>();
+  for (const { tab, content } of pending) {
+    documents.set(tab.id, {
+      document: {
+        tabId: tab.id,
+        path: tab.filePath,
+        title: tab.title,
+        digest: await sha256Hex(content),
+      },
+      content,
+    });
+  }
+
+  const needed = await invoke<string[]>("report_dirty_digests", {
+    documents: [...documents.values()].map((d) => d.document),
+  });
+  for (const tabId of needed) {
+    const entry = documents.get(tabId);
+    if (!entry) continue;
+    await invoke("write_dirty_backup", entry).catch((error) => {
+      console.warn("[DirtyBackup] Failed to back up", entry.document.title, error);
+    });
+  }
+}
+
+/**
+ * Answer the backend's periodic backup requests for this window's tabs.
+ */
+export function useDirtyBackupResponder(): void {
+  useEffect(() => {
+    const windowLabel = getCurrentWebviewWindow().label;
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+
+    listen(DIRTY_BACKUP_REQUEST_EVENT, () => {
+      backupWindow(windowLabel).catch((error) => {
+        console.warn("[DirtyBackup] Backup request failed:", error);
+      });
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+}
+
+export function getDirtyBackupConfig(): Promise<DirtyBackupConfig> {
+  return invoke<DirtyBackupConfig>("get_dirty_backup_config");
+}
+
+export function setDirtyBackupConfig(config: DirtyBackupConfig): Promise<void> {
+  return invoke("set_dirty_backup_config", { config });
+}
+
+/** Stored copies, newest first. */
+export function listDirtyBackups(): Promise<DirtyBackup[]> {
+  return invoke<DirtyBackup[]>("list_dirty_backups");
+}
+
+/** Read a copy's content, optionally writing it to `targetPath` too. */
+export function restoreDirtyBackup(id: string, targetPath?: string): Promise<string> {
+  return invoke<string>("restore_dirty_backup", { id, targetPath: targetPath ?? null });
+}
+
+export function deleteDirtyBackup(id: string): Promise<void> {
+  return invoke("delete_dirty_backup", { id });
+}
@@ -0,0 +1,46 @@
+import { describe, it, expect } from "vitest";
+import {
+  formatDiffStats,
+  sideBySideRows,
+  type DiffSegment,
+  type DocumentDiff,
+} from "./documentDiff";
+
+const diff = (insertions: number, deletions: number): DocumentDiff => ({
+  identical: insertions + deletions === 0,
+  segments: [],
+  stats: { insertions, deletions, unchanged: 0 },
+});
+
+describe("formatDiffStats", () => {
+  it("summarizes changes", () => {
+    expect(formatDiffStats(diff(3, 1), "line")).toBe("+3 −1 lines");
+    expect(formatDiffStats(diff(1, 0), "word")).toBe("+1 −0 word");
+  });
+
+  it("reports identical documents", () => {
+    expect(formatDiffStats(diff(0, 0), "line")).toBe("No changes");
+  });
+});
+
+describe("sideBySideRows", () => {
+  const seg = (kind: DiffSegment["kind"], text: string): DiffSegment => ({ kind, text });
+
+  it("pairs deletions with the insertions that replace them", () => {
+    const rows = sideBySideRows([
+      seg("equal", "a"),
+      seg("delete", "b"),
+      seg("delete", "c"),
+      seg("insert", "B"),
+      seg("equal", "d"),
+      seg("insert", "e"),
+    ]);
+    expect(rows.map((r) => [r.left?.text, r.right?.text])).toEqual([
+      ["a", "a"],
+      ["b", "B"],
+      ["c", undefined],
+      ["d", "d"],
+      [undefined, "e"],
+    ]);
+  });
+});
@@ -0,0 +1,84 @@
+/**
+ * Document comparison.
+ *
+ * The backend diffs two versions of a document line by line or word by
+ * word (used by local history, external-change conflicts and "compare two
+ * files"), optionally rendering the result as HTML.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export type DiffMode = "line" | "word";
+export type DiffKind = "equal" | "insert" | "delete";
+
+export interface DiffSegment {
+  kind: DiffKind;
+  text: string;
+  /** 1-based, line mode only */
+  oldLine?: number;
+  newLine?: number;
+}
+
+export interface DocumentDiff {
+  identical: boolean;
+  segments: DiffSegment[];
+  stats: { insertions: number; deletions: number; unchanged: number };
+  html?: string;
+}
+
+export interface DiffOptions {
+  html?: boolean;
+  ignoreWhitespace?: boolean;
+}
+
+export function diffDocuments(
+  oldText: string,
+  newText: string,
+  mode: DiffMode = "line",
+  options?: DiffOptions
+): Promise<DocumentDiff> {
+  return invoke<DocumentDiff>("diff_documents", { old: oldText, new: newText, mode, options });
+}
+
+/** Short summary for a status line, e.g. "+3 −1 lines". */
+export function formatDiffStats(diff: DocumentDiff, mode: DiffMode): string {
+  if (diff.identical) return "No changes";
+  const unit = mode === "line" ? "line" : "word";
+  const total = diff.stats.insertions + diff.stats.deletions;
+  return `+${diff.stats.insertions} −${diff.stats.deletions} ${unit}${total === 1 ? "" : "s"}`;
+}
+
+/** One row of a side-by-side view; a missing side is blank. */
+export interface DiffRow {
+  left?: DiffSegment;
+  right?: DiffSegment;
+}
+
+/**
+ * Lay out line-mode segments side by side: unchanged lines on both sides,
+ * and each run of deletions paired row by row with the insertions after it.
+ */
+export function sideBySideRows(segments: DiffSegment[]): DiffRow[] {
+  const rows: DiffRow[] = [];
+  let deleted: DiffSegment[] = [];
+  let inserted: DiffSegment[] = [];
+  const flush = () => {
+    for (let i = 0; i < Math.max(deleted.length, inserted.length); i++) {
+      rows.push({ left: deleted[i], right: inserted[i] });
+    }
+    deleted = [];
+    inserted = [];
+  };
+  for (const segment of segments) {
+    if (segment.kind === "delete") {
+      if (inserted.length) flush();
+      deleted.push(segment);
+    } else if (segment.kind === "insert") {
+      inserted.push(segment);
+    } else {
+      flush();
+      rows.push({ left: segment, right: segment });
+    }
+  }
+  flush();
+  return rows;
+}
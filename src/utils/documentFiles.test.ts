@@ -0,0 +1,93 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  getDiskDigest,
+  getOriginalEncoding,
+  readTextDocument,
+  saveTextDocument,
+  writeTextDocument,
+} from "./documentFiles";
+
+const metadata = (isLarge: boolean) => ({ size: 10, modified: 1, isLarge, isReadonly: false });
+
+describe("documentFiles", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("saves files opened in another encoding back in that encoding", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(metadata(false))
+      .mockResolvedValueOnce({ content: "你好", encoding: "GBK", bom: false, lineEnding: "lf", hadErrors: false, digest: "d1" });
+
+    expect(await readTextDocument("/notes/gbk.md")).toBe("你好");
+    expect(getOriginalEncoding("/notes/gbk.md")).toEqual({ encoding: "GBK", bom: false });
+
+    vi.mocked(invoke).mockResolvedValueOnce(undefined);
+    await writeTextDocument("/notes/gbk.md", "再见");
+    expect(invoke).toHaveBeenLastCalledWith("save_file_with_encoding", {
+      path: "/notes/gbk.md",
+      content: "再见",
+      encoding: "GBK",
+      bom: false,
+    });
+  });
+
+  it("writes plain UTF-8 files atomically, as is", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(metadata(false))
+      .mockResolvedValueOnce({ content: "# Hi", encoding: "UTF-8", bom: false, lineEnding: "lf", hadErrors: false, digest: "d1" });
+
+    expect(await readTextDocument("/notes/a.md")).toBe("# Hi");
+    expect(getDiskDigest("/notes/a.md")).toBe("d1");
+
+    vi.mocked(invoke).mockResolvedValueOnce({ content: "# Bye", digest: "d2" });
+    await writeTextDocument("/notes/a.md", "# Bye");
+    expect(invoke).toHaveBeenLastCalledWith("write_document", {
+      path: "/notes/a.md",
+      content: "# Bye",
+      lineEnding: "preserve",
+      finalNewline: false,
+      trimTrailingWhitespace: false,
+    });
+    expect(getDiskDigest("/notes/a.md")).toBe("d2");
+  });
+
+  it("saves only over the bytes it last read", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(metadata(false))
+      .mockResolvedValueOnce({ content: "# Hi", encoding: "UTF-8", bom: false, lineEnding: "lf", hadErrors: false, digest: "d1" });
+    await readTextDocument("/notes/safe.md");
+
+    vi.mocked(invoke).mockResolvedValueOnce({ status: "saved", digest: "d2" });
+    expect(await saveTextDocument("/notes/safe.md", "# Bye")).toEqual({ status: "saved", digest: "d2" });
+    expect(invoke).toHaveBeenLastCalledWith("save_file_safe", {
+      path: "/notes/safe.md",
+      content: "# Bye",
+      expectedDigest: "d1",
+    });
+    expect(getDiskDigest("/notes/safe.md")).toBe("d2");
+
+    vi.mocked(invoke).mockResolvedValueOnce({ status: "conflict", diskContent: "# Theirs", diskDigest: "d3" });
+    await saveTextDocument("/notes/safe.md", "# Again");
+    expect(getDiskDigest("/notes/safe.md")).toBe("d2");
+
+    vi.mocked(invoke).mockResolvedValueOnce({ status: "saved", digest: "d4" });
+    await saveTextDocument("/notes/safe.md", "# Again", null);
+    expect(invoke).toHaveBeenLastCalledWith("save_file_safe", {
+      path: "/notes/safe.md",
+      content: "# Again",
+      expectedDigest: null,
+    });
+  });
+
+  it("loads large files in chunks", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(metadata(true))
+      .mockResolvedValueOnce({ content: "big", offset: 0, nextOffset: 3, eof: true, totalSize: 3, modified: 1 });
+
+    expect(await readTextDocument("/notes/big.md")).toBe("big");
+    expect(getDiskDigest("/notes/big.md")).toBeUndefined();
+    expect(invoke).toHaveBeenLastCalledWith("read_file_chunked", expect.objectContaining({ path: "/notes/big.md" }));
+  });
+});
@@ -0,0 +1,103 @@
+/**
+ * Plain (unencrypted) document files.
+ *
+ * Files over the backend's large-file threshold are loaded in chunks so the
+ * renderer stays responsive (see largeFile.ts); others are decoded from
+ * whatever encoding they use (see textEncoding.ts). A file that wasn't plain
+ * UTF-8 is saved back in its original encoding.
+ *
+ * The digest of each file as last read or saved is kept too. Saves only
+ * overwrite a file that still has it, and watcher events that report the
+ * same bytes can be ignored without reading the file again.
+ */
+import { toast } from "sonner";
+import { getFileMetadata, readFileIncrementally } from "@/utils/largeFile";
+import { computeFileDigest, saveFileSafe, writeDocument, type SafeSaveResult } from "@/utils/safeSave";
+import { needsEncodingConversion, readFileWithEncoding, saveFileWithEncoding } from "@/utils/textEncoding";
+import { getFileName, normalizePath } from "@/utils/paths";
+
+interface OriginalEncoding {
+  encoding: string;
+  bom: boolean;
+}
+
+/** Encodings of opened files that aren't plain UTF-8, by path */
+const originalEncodings = new Map<string, OriginalEncoding>();
+
+/** SHA-256 of each file as last read or written, by normalized path */
+const diskDigests = new Map<string, string>();
+
+/** The digest of a file as the editor last read or wrote it, if known. */
+export function getDiskDigest(path: string): string | undefined {
+  return diskDigests.get(normalizePath(path));
+}
+
+/** The encoding a file was opened in, when it isn't plain UTF-8. */
+export function getOriginalEncoding(path: string): OriginalEncoding | undefined {
+  return originalEncodings.get(path);
+}
+
+/** Read a text document as UTF-8, whatever its size and encoding. */
+export async function readTextDocument(path: string): Promise<string> {
+  const metadata = await getFileMetadata(path);
+  if (metadata.isLarge) {
+    originalEncodings.delete(path);
+    diskDigests.delete(normalizePath(path));
+    return readFileIncrementally(path);
+  }
+
+  const decoded = await readFileWithEncoding(path);
+  if (needsEncodingConversion(decoded)) {
+    originalEncodings.set(path, { encoding: decoded.encoding, bom: decoded.bom });
+  } else {
+    originalEncodings.delete(path);
+  }
+  diskDigests.set(normalizePath(path), decoded.digest);
+  if (decoded.hadErrors) {
+    toast.warning(`Some characters in ${getFileName(path)} couldn't be read as ${decoded.encoding}`);
+  }
+  return decoded.content;
+}
+
+/**
+ * Write a text document atomically, in its original encoding if it was opened
+ * from a non-UTF-8 file. Fails without writing when the text has characters
+ * that encoding can't hold.
+ */
+export async function writeTextDocument(path: string, content: string): Promise<void> {
+  const original = originalEncodings.get(path);
+  if (original) {
+    // The encoded bytes aren't hashed here; the next read records them
+    diskDigests.delete(normalizePath(path));
+    return saveFileWithEncoding(path, content, original.encoding, original.bom);
+  }
+  const written = await writeDocument(path, content, "preserve", false);
+  diskDigests.set(normalizePath(path), written.digest);
+}
+
+/**
+ * Save a document unless the file changed on disk since the editor last read
+ * or saved it; then nothing is written and the disk content comes back as a
+ * conflict. `expectedDigest` checks against another digest instead (null
+ * skips the check). Files kept in another encoding, and files whose digest
+ * isn't known (new, or loaded in chunks), are written without it.
+ */
+export async function saveTextDocument(
+  path: string,
+  content: string,
+  expectedDigest?: string | null
+): Promise<SafeSaveResult> {
+  if (originalEncodings.has(path)) {
+    await writeTextDocument(path, content);
+    const digest = await computeFileDigest(path);
+    diskDigests.set(normalizePath(path), digest);
+    return { status: "saved", digest };
+  }
+
+  const expected = expectedDigest === undefined ? (getDiskDigest(path) ?? null) : expectedDigest;
+  const result = await saveFileSafe(path, content, expected);
+  if (result.status === "saved") {
+    diskDigests.set(normalizePath(path), result.digest);
+  }
+  return result;
+}
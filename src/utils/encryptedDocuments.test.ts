@@ -0,0 +1,104 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { readTextDocument, writeTextDocument } from "@/utils/documentFiles";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import {
+  applyEncryptionPolicy,
+  isEncryptedPath,
+  readDocumentFile,
+  setPassphraseRequester,
+  writeDocumentFile,
+} from "./encryptedDocuments";
+
+vi.mock("@/utils/documentFiles", () => ({
+  readTextDocument: vi.fn(),
+  writeTextDocument: vi.fn(),
+}));
+
+describe("encryptedDocuments", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(readTextDocument).mockReset();
+    vi.mocked(writeTextDocument).mockReset();
+  });
+
+  it("detects encrypted paths", () => {
+    expect(isEncryptedPath("/notes/journal.md.enc")).toBe(true);
+    expect(isEncryptedPath("/notes/journal.md")).toBe(false);
+  });
+
+  it("reads and writes plain documents as text documents", async () => {
+    vi.mocked(readTextDocument).mockResolvedValue("# Hi");
+    expect(await readDocumentFile("/notes/a.md")).toBe("# Hi");
+
+    await writeDocumentFile("/notes/a.md", "# Bye");
+    expect(writeTextDocument).toHaveBeenCalledWith("/notes/a.md", "# Bye");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("asks for a passphrase when the document is locked", async () => {
+    const requester = vi.fn().mockResolvedValue("secret");
+    setPassphraseRequester(requester);
+    vi.mocked(invoke).mockRejectedValueOnce("locked").mockResolvedValueOnce("# Diary");
+
+    expect(await readDocumentFile("/notes/diary.md.enc")).toBe("# Diary");
+    expect(requester).toHaveBeenCalledWith("/notes/diary.md.enc", { confirm: false });
+    expect(invoke).toHaveBeenLastCalledWith("open_encrypted_document", {
+      path: "/notes/diary.md.enc",
+      passphrase: "secret",
+    });
+  });
+
+  it("saves with the remembered key without asking", async () => {
+    const requester = vi.fn();
+    setPassphraseRequester(requester);
+    vi.mocked(invoke).mockResolvedValue(undefined);
+
+    await writeDocumentFile("/notes/diary.md.enc", "# Diary");
+    expect(requester).not.toHaveBeenCalled();
+    expect(writeTextDocument).not.toHaveBeenCalled();
+  });
+
+  it("fails when the passphrase prompt is cancelled", async () => {
+    setPassphraseRequester(vi.fn().mockResolvedValue(null));
+    vi.mocked(invoke).mockRejectedValueOnce("locked");
+
+    await expect(readDocumentFile("/notes/diary.md.enc")).rejects.toThrow("Passphrase required");
+  });
+
+  it("asks for a confirmed passphrase when saving a new encrypted document", async () => {
+    const requester = vi.fn().mockResolvedValue("secret");
+    setPassphraseRequester(requester);
+    vi.mocked(invoke).mockRejectedValueOnce("locked").mockResolvedValueOnce(undefined);
+
+    await writeDocumentFile("/notes/new.md.enc", "# New");
+    expect(requester).toHaveBeenCalledWith("/notes/new.md.enc", { confirm: true });
+  });
+
+  it("applies the workspace encryption policy to new documents", async () => {
+    const setPolicy = (encryption: "off" | "ask" | "always") =>
+      useWorkspaceStore.setState({
+        rootPath: "/notes",
+        config: {
+          version: 2,
+          excludeFolders: [],
+          lastOpenTabs: [],
+          showHiddenFiles: false,
+          editor: { encryption },
+        },
+      });
+
+    setPolicy("always");
+    expect(await applyEncryptionPolicy("/notes/a.md")).toBe("/notes/a.md.enc");
+    expect(await applyEncryptionPolicy("/elsewhere/a.md")).toBe("/elsewhere/a.md");
+
+    setPolicy("ask");
+    vi.mocked(ask).mockResolvedValueOnce(false);
+    expect(await applyEncryptionPolicy("/notes/a.md")).toBe("/notes/a.md");
+
+    setPolicy("off");
+    expect(await applyEncryptionPolicy("/notes/a.md")).toBe("/notes/a.md");
+    useWorkspaceStore.setState({ rootPath: null, config: null });
+  });
+});
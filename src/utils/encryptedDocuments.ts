@@ -0,0 +1,124 @@
+/**
+ * Encrypted documents (`*.md.enc`).
+ *
+ * Reading and writing go through the backend, which keeps the derived key
+ * of an opened document in memory, so the passphrase is asked for once per
+ * document (on open) and saves reuse it. Closing the last tab of a document
+ * forgets its key. Other documents are read and written by documentFiles.ts.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { useTabStore } from "@/stores/tabStore";
+import { readTextDocument, writeTextDocument } from "@/utils/documentFiles";
+import { getEncryptionPolicy } from "@/utils/workspaceEditorSettings";
+
+export const ENCRYPTED_EXTENSION = ".enc";
+
+export interface PassphraseRequest {
+  /** A new passphrase, to be typed twice */
+  confirm: boolean;
+}
+
+/** Asks the user for a passphrase; resolves to null when cancelled. */
+export type PassphraseRequester = (path: string, request: PassphraseRequest) => Promise<string | null>;
+
+// Until the passphrase dialog is mounted there is nobody to ask
+let requestPassphrase: PassphraseRequester = async () => null;
+
+/** Set the passphrase prompt (the in-app dialog registers itself). */
+export function setPassphraseRequester(requester: PassphraseRequester): void {
+  requestPassphrase = requester;
+}
+
+export function isEncryptedPath(path: string): boolean {
+  return path.toLowerCase().endsWith(ENCRYPTED_EXTENSION);
+}
+
+function isLocked(error: unknown): boolean {
+  return String(error) === "locked";
+}
+
+/** Read a document, decrypting `.enc` files (asks for the passphrase if needed). */
+export async function readDocumentFile(path: string): Promise<string> {
+  if (!isEncryptedPath(path)) {
+    return readTextDocument(path);
+  }
+  try {
+    return await invoke<string>("open_encrypted_document", { path, passphrase: null });
+  } catch (error) {
+    if (!isLocked(error)) throw error;
+  }
+  const passphrase = await requestPassphrase(path, { confirm: false });
+  if (!passphrase) {
+    throw new Error("Passphrase required to open encrypted document");
+  }
+  return invoke<string>("open_encrypted_document", { path, passphrase });
+}
+
+/** Write a document, encrypting `.enc` files with the key from open (or a new passphrase). */
+export async function writeDocumentFile(path: string, content: string): Promise<void> {
+  if (!isEncryptedPath(path)) {
+    return writeTextDocument(path, content);
+  }
+  try {
+    await invoke("save_encrypted_document", { path, content, passphrase: null });
+    return;
+  } catch (error) {
+    if (!isLocked(error)) throw error;
+  }
+  const passphrase = await requestPassphrase(path, { confirm: true });
+  if (!passphrase) {
+    throw new Error("Passphrase required to save encrypted document");
+  }
+  await invoke("save_encrypted_document", { path, content, passphrase });
+}
+
+/**
+ * Apply the workspace encryption policy to the path chosen for a new
+ * document: "always" saves it encrypted, "ask" offers to.
+ */
+export async function applyEncryptionPolicy(path: string): Promise<string> {
+  if (isEncryptedPath(path)) return path;
+  const policy = getEncryptionPolicy(path);
+  if (policy === "always") return path + ENCRYPTED_EXTENSION;
+  if (policy === "ask") {
+    const encrypt = await ask("Encrypt this document with a passphrase?", {
+      title: "Encrypt Document",
+      kind: "info",
+      okLabel: "Encrypt",
+      cancelLabel: "Save Unencrypted",
+    });
+    if (encrypt) return path + ENCRYPTED_EXTENSION;
+  }
+  return path;
+}
+
+/**
+ * Encrypt a saved document in place of the original. Resolves to the new
+ * path, or null when the passphrase prompt was cancelled.
+ */
+export async function encryptDocument(path: string): Promise<string | null> {
+  const passphrase = await requestPassphrase(path + ENCRYPTED_EXTENSION, { confirm: true });
+  if (!passphrase) return null;
+  return invoke<string>("encrypt_file", { path, passphrase, deleteOriginal: true });
+}
+
+/**
+ * Replace an encrypted document with its plain text. Resolves to the new
+ * path, or null when the passphrase prompt was cancelled.
+ */
+export async function decryptDocument(path: string): Promise<string | null> {
+  const passphrase = await requestPassphrase(path, { confirm: false });
+  if (!passphrase) return null;
+  return invoke<string>("decrypt_file", { path, passphrase, deleteOriginal: true });
+}
+
+/** Forget the key of an encrypted document once none of this window's tabs show it. */
+export function lockIfUnused(windowLabel: string, path: string | null): void {
+  if (!path || !isEncryptedPath(path)) return;
+  const tabs = useTabStore.getState().tabs[windowLabel] ?? [];
+  if (tabs.some((tab) => tab.filePath === path)) return;
+  invoke("lock_encrypted_document", { path }).catch((error) =>
+    console.warn("[Encryption] Failed to lock document:", error)
+  );
+}
@@ -34,5 +34,4 @@ export const FileErrors = {
   duplicateFailed: (name: string) => `Failed to duplicate "${name}".`,
   copyFailed: "Failed to copy to clipboard.",
   exportFailed: (format: string) => `Failed to export to ${format}.`,
-  tooManyCopies: (name: string) => `Too many copies of "${name}" exist. Please delete some first.`,
 };
@@ -0,0 +1,31 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { fuzzyFindFiles, highlightSegments } from "./fileFinder";
+
+describe("fileFinder", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("passes root, query and limit", async () => {
+    vi.mocked(invoke).mockResolvedValue({ matches: [], total: 0 });
+    await fuzzyFindFiles("/vault", "plan", 20);
+    expect(invoke).toHaveBeenCalledWith("fuzzy_find_files", {
+      rootPath: "/vault",
+      query: "plan",
+      limit: 20,
+    });
+  });
+
+  it("splits a path into matched and plain runs", () => {
+    expect(highlightSegments("notes/plan.md", [6, 7, 8, 9])).toEqual([
+      { text: "notes/", matched: false },
+      { text: "plan", matched: true },
+      { text: ".md", matched: false },
+    ]);
+    expect(highlightSegments("a.md", [])).toEqual([{ text: "a.md", matched: false }]);
+  });
+});
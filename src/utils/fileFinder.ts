@@ -0,0 +1,54 @@
+/**
+ * Fuzzy file finder.
+ *
+ * The backend keeps each workspace's note list in memory (current from
+ * file watcher events) and ranks it fzf-style, so the quick switcher stays
+ * instant on large workspaces. Space-separated terms must all match; a term
+ * with an uppercase letter matches case-sensitively.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface FileMatch {
+  /** Workspace-relative path */
+  path: string;
+  score: number;
+  /** Offsets of the matched characters in `path`, for highlighting */
+  positions: number[];
+}
+
+export interface FuzzyFindResult {
+  matches: FileMatch[];
+  /** Files that matched, before `limit` */
+  total: number;
+}
+
+export function fuzzyFindFiles(
+  rootPath: string,
+  query: string,
+  limit?: number
+): Promise<FuzzyFindResult> {
+  return invoke<FuzzyFindResult>("fuzzy_find_files", { rootPath, query, limit });
+}
+
+export function rebuildFileFinderIndex(rootPath: string): Promise<void> {
+  return invoke("rebuild_file_finder_index", { rootPath });
+}
+
+/** Split `path` into plain and matched runs for rendering highlights. */
+export function highlightSegments(
+  path: string,
+  positions: number[]
+): { text: string; matched: boolean }[] {
+  const matched = new Set(positions);
+  const segments: { text: string; matched: boolean }[] = [];
+  for (let i = 0; i < path.length; i++) {
+    const isMatch = matched.has(i);
+    const last = segments[segments.length - 1];
+    if (last && last.matched === isMatch) {
+      last.text += path[i];
+    } else {
+      segments.push({ text: path[i], matched: isMatch });
+    }
+  }
+  return segments;
+}
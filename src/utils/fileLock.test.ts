@@ -0,0 +1,15 @@
+import { describe, it, expect } from "vitest";
+import { describeLock } from "./fileLock";
+
+describe("describeLock", () => {
+  it("names the user and host", () => {
+    expect(describeLock({ user: "jane", host: "studio-mac", pid: 1, acquiredAt: 0 })).toBe(
+      "Locked by jane on studio-mac"
+    );
+  });
+
+  it("omits an unknown host and includes the time when known", () => {
+    const text = describeLock({ user: "Jane Doe", host: "", pid: 0, acquiredAt: Date.UTC(2026, 0, 1, 10, 0) });
+    expect(text.startsWith("Locked by Jane Doe since ")).toBe(true);
+  });
+});
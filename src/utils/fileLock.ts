@@ -0,0 +1,48 @@
+/**
+ * Advisory locks for documents on shared drives.
+ *
+ * The backend writes a `.~lock.<name>#` sidecar recording who has the file
+ * open. Locks are advisory: they warn, they don't prevent writes.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface LockInfo {
+  user: string;
+  host: string;
+  pid: number;
+  acquiredAt: number;
+}
+
+export type LockStatus =
+  | { status: "unlocked" }
+  | { status: "acquired" }
+  /** `stale`: left by a process on this machine that is no longer running */
+  | { status: "lockedByOther"; info: LockInfo; stale: boolean };
+
+export function acquireFileLock(path: string, force = false): Promise<LockStatus> {
+  return invoke<LockStatus>("acquire_file_lock", { path, force });
+}
+
+export function checkFileLock(path: string): Promise<LockStatus> {
+  return invoke<LockStatus>("check_file_lock", { path });
+}
+
+export function releaseFileLock(path: string): Promise<void> {
+  return invoke("release_file_lock", { path });
+}
+
+/** Remove someone else's lock; fails if it's no longer the `expected` lock. */
+export function forceUnlockFile(path: string, expected: LockInfo): Promise<void> {
+  return invoke("force_unlock_file", { path, expected });
+}
+
+/** "Locked by jane on studio-mac since 10:42", for the open-file prompt. */
+export function describeLock(info: LockInfo): string {
+  const who = info.host ? `${info.user} on ${info.host}` : info.user;
+  if (!info.acquiredAt) return `Locked by ${who}`;
+  const since = new Date(info.acquiredAt).toLocaleString(undefined, {
+    dateStyle: "short",
+    timeStyle: "short",
+  });
+  return `Locked by ${who} since ${since}`;
+}
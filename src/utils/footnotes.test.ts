@@ -0,0 +1,44 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { describeFootnoteIssues, normalizeFootnotes, type FootnoteReport } from "./footnotes";
+
+vi.mock("sonner", () => ({ toast: { warning: vi.fn(), error: vi.fn() } }));
+
+const report = (overrides: Partial<FootnoteReport> = {}): FootnoteReport => ({
+  content: "Text[^1].\n\n[^1]: Note.\n",
+  changed: true,
+  missingDefinitions: [],
+  orphanedDefinitions: [],
+  duplicateDefinitions: [],
+  ...overrides,
+});
+
+describe("footnotes", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(toast.warning).mockReset();
+  });
+
+  it("describes each kind of issue", () => {
+    expect(describeFootnoteIssues(report())).toBeNull();
+    expect(
+      describeFootnoteIssues(report({ missingDefinitions: ["a"], duplicateDefinitions: ["b", "c"] }))
+    ).toBe("Missing definitions: [^a]\nDefined more than once: [^b], [^c]");
+  });
+
+  it("returns the normalized content only when it changed", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce(report());
+    expect(await normalizeFootnotes("Text[^x].\n\n[^x]: Note.\n")).toBe("Text[^1].\n\n[^1]: Note.\n");
+
+    vi.mocked(invoke).mockResolvedValueOnce(report({ changed: false }));
+    expect(await normalizeFootnotes("Text[^1].\n\n[^1]: Note.\n")).toBeNull();
+    expect(toast.warning).not.toHaveBeenCalled();
+  });
+
+  it("warns about orphaned definitions", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce(report({ orphanedDefinitions: ["old"] }));
+    await normalizeFootnotes("...");
+    expect(toast.warning).toHaveBeenCalledWith("Unused definitions: [^old]");
+  });
+});
@@ -0,0 +1,47 @@
+/**
+ * Footnote normalization (renumbering and moving definitions to the end)
+ * runs in the backend; this reports problems it finds to the user.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+
+export interface FootnoteReport {
+  content: string;
+  changed: boolean;
+  missingDefinitions: string[];
+  orphanedDefinitions: string[];
+  duplicateDefinitions: string[];
+}
+
+const formatLabels = (labels: string[]) => labels.map((label) => `[^${label}]`).join(", ");
+
+/** Human-readable summary of footnote problems, or null when there are none. */
+export function describeFootnoteIssues(report: FootnoteReport): string | null {
+  const issues: string[] = [];
+  if (report.missingDefinitions.length > 0) {
+    issues.push(`Missing definitions: ${formatLabels(report.missingDefinitions)}`);
+  }
+  if (report.orphanedDefinitions.length > 0) {
+    issues.push(`Unused definitions: ${formatLabels(report.orphanedDefinitions)}`);
+  }
+  if (report.duplicateDefinitions.length > 0) {
+    issues.push(`Defined more than once: ${formatLabels(report.duplicateDefinitions)}`);
+  }
+  return issues.length > 0 ? issues.join("\n") : null;
+}
+
+/** Normalized markdown, or null when nothing changed (or on failure). */
+export async function normalizeFootnotes(content: string): Promise<string | null> {
+  try {
+    const report = await invoke<FootnoteReport>("normalize_footnotes", { content });
+    const issues = describeFootnoteIssues(report);
+    if (issues) {
+      toast.warning(issues);
+    }
+    return report.changed ? report.content : null;
+  } catch (error) {
+    console.error("[Footnotes] Failed to normalize footnotes:", error);
+    toast.error("Failed to normalize footnotes");
+    return null;
+  }
+}
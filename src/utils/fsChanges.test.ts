@@ -0,0 +1,38 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }));
+
+import { listen } from "@tauri-apps/api/event";
+import { listenFsChanges } from "./fsChanges";
+
+type Listener = (event: { payload: unknown }) => void;
+
+describe("listenFsChanges", () => {
+  const listeners = new Map<string, Listener>();
+  const unlisten = vi.fn();
+
+  beforeEach(() => {
+    listeners.clear();
+    unlisten.mockReset();
+    vi.mocked(listen).mockImplementation(async (name, handler) => {
+      listeners.set(name, handler as Listener);
+      return unlisten;
+    });
+  });
+
+  it("passes single changes and whole batches", async () => {
+    const handler = vi.fn();
+    const stop = await listenFsChanges(handler);
+
+    listeners.get("fs:changed")!({ payload: { kind: "modify" } });
+    expect(handler).toHaveBeenLastCalledWith([{ kind: "modify" }]);
+
+    listeners.get("fs:batch")!({
+      payload: { changes: [{ kind: "create" }, { kind: "remove" }] },
+    });
+    expect(handler).toHaveBeenLastCalledWith([{ kind: "create" }, { kind: "remove" }]);
+
+    stop();
+    expect(unlisten).toHaveBeenCalledTimes(2);
+  });
+});
@@ -0,0 +1,31 @@
+/**
+ * File System Changes
+ *
+ * Purpose: Deliver watcher changes the same way whether the backend emits
+ * them one at a time ("fs:changed") or coalesced ("fs:batch", for watches
+ * started with batchWindowMs).
+ *
+ * @module utils/fsChanges
+ */
+
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/** Coalescing window the window watcher asks the backend for */
+export const WATCH_BATCH_WINDOW_MS = 100;
+
+/** Listen for watcher changes; a batch arrives as one call with all its changes. */
+export async function listenFsChanges<T>(handler: (changes: T[]) => void): Promise<UnlistenFn> {
+  const unlistenBatch = await listen<{ changes: T[] }>("fs:batch", (event) => {
+    handler(event.payload.changes);
+  });
+  try {
+    const unlistenChange = await listen<T>("fs:changed", (event) => handler([event.payload]));
+    return () => {
+      unlistenBatch();
+      unlistenChange();
+    };
+  } catch (error) {
+    unlistenBatch();
+    throw error;
+  }
+}
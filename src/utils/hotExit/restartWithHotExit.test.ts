@@ -86,8 +86,12 @@ describe('checkAndRestoreSession', () => {
     await new Promise(resolve => setTimeout(resolve, 50));
 
     // Verify restore was called (single-window uses legacy command)
-    // Session is migrated from v1 to v2 before restore
-    const migratedSession = { ...mockSession, version: 2 };
+    // Session is migrated from v1 to the current version before restore
+    const migratedSession = {
+      ...mockSession,
+      version: 3,
+      windows: mockSession.windows.map(w => ({ ...w, terminal_sessions: [] })),
+    };
     expect(mockInvoke).toHaveBeenCalledWith('hot_exit_restore', { session: migratedSession });
 
     // Verify clear_session has NOT been called yet (waiting for event)
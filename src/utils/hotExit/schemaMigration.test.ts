@@ -182,6 +182,40 @@ describe('Schema Migration', () => {
     });
   });
 
+  describe('v2 -> v3 migration', () => {
+    it('should add empty terminal_sessions to each window', () => {
+      const v2Session = {
+        version: 2,
+        timestamp: Date.now() / 1000,
+        vmark_version: '0.3.30',
+        windows: [
+          {
+            window_label: 'main',
+            is_main_window: true,
+            active_tab_id: null,
+            tabs: [],
+            ui_state: {
+              sidebar_visible: true,
+              sidebar_width: 260,
+              outline_visible: false,
+              sidebar_view_mode: 'files',
+              status_bar_visible: true,
+              source_mode_enabled: false,
+              focus_mode_enabled: false,
+              typewriter_mode_enabled: false,
+            },
+            geometry: null,
+          },
+        ],
+        workspace: null,
+      } as SessionData;
+
+      const migrated = migrateSession(v2Session);
+      expect(migrated.version).toBe(3);
+      expect(migrated.windows[0].terminal_sessions).toEqual([]);
+    });
+  });
+
   describe('Edge cases', () => {
     it('should handle empty windows array', () => {
       const emptySession: SessionData = {
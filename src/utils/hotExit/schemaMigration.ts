@@ -33,6 +33,7 @@ type MigrationFn = (session: SessionData) => SessionData;
  */
 const migrations: Record<number, MigrationFn> = {
   1: migrateV1toV2,
+  2: migrateV2toV3,
 };
 
 /**
@@ -140,6 +141,23 @@ function migrateV1toV2(session: SessionData): SessionData {
   };
 }
 
+/**
+ * Migrate v2 -> v3: Add terminal sessions to windows
+ *
+ * v3 adds terminal_sessions to WindowState so integrated terminals
+ * are recreated after an update restart.
+ */
+function migrateV2toV3(session: SessionData): SessionData {
+  return {
+    ...session,
+    version: 3,
+    windows: session.windows.map(window => ({
+      ...window,
+      terminal_sessions: window.terminal_sessions ?? [],
+    })),
+  };
+}
+
 /**
  * V1 DocumentState - all fields except undo/redo history (added in v2)
  */
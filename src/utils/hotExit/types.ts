@@ -5,7 +5,7 @@
  * These types define the complete application session state for save/restore.
  */
 
-export const SCHEMA_VERSION = 2;
+export const SCHEMA_VERSION = 3;
 
 /**
  * Line ending types
@@ -30,6 +30,22 @@ export interface WindowState {
   tabs: TabState[];
   ui_state: UiState;
   geometry: WindowGeometry | null;
+  /** Integrated terminal sessions (added in v3) */
+  terminal_sessions?: TerminalSessionState[];
+}
+
+/**
+ * Integrated terminal session. The frontend sends id/label/active;
+ * Rust fills in cwd, shell and scrollback when capturing.
+ */
+export interface TerminalSessionState {
+  session_id: string;
+  label: string;
+  is_active: boolean;
+  cwd?: string | null;
+  shell_path?: string | null;
+  shell_kind?: string | null;
+  scrollback?: string;
 }
 
 export interface TabState {
@@ -3,6 +3,10 @@
  *
  * Listens for capture requests from Rust coordinator and responds with
  * current window state (tabs, documents, UI state).
+ *
+ * Encrypted documents are never written to the session file: unsaved edits
+ * are saved to the document itself (with its remembered key) and the tab is
+ * left out of the session.
  */
 
 import { useEffect } from 'react';
@@ -13,7 +17,10 @@ import { useDocumentStore } from '@/stores/documentStore';
 import { useUIStore } from '@/stores/uiStore';
 import { useEditorStore } from '@/stores/editorStore';
 import { useUnifiedHistoryStore } from '@/stores/unifiedHistoryStore';
-import type { WindowState, TabState, CaptureRequest, CaptureResponse, CursorInfo } from './types';
+import { useTerminalSessionStore } from '@/stores/terminalSessionStore';
+import { isEncryptedPath } from '@/utils/encryptedDocuments';
+import { saveToPath } from '@/utils/saveToPath';
+import type { TerminalSessionState, WindowState, TabState, CaptureRequest, CaptureResponse, CursorInfo } from './types';
 import { HOT_EXIT_EVENTS, MAIN_WINDOW_LABEL } from './types';
 import type { LineEnding as StoreLineEnding } from '@/utils/linebreakDetection';
 import type { HistoryCheckpoint as StoreHistoryCheckpoint } from '@/stores/unifiedHistoryStore';
@@ -65,6 +72,18 @@ function toHotExitCheckpoint(checkpoint: StoreHistoryCheckpoint) {
   };
 }
 
+/**
+ * Gather terminal sessions (Rust adds cwd, shell and scrollback)
+ */
+function captureTerminalSessions(): TerminalSessionState[] {
+  const { sessions, activeSessionId } = useTerminalSessionStore.getState();
+  return sessions.map((s) => ({
+    session_id: s.id,
+    label: s.label,
+    is_active: s.id === activeSessionId,
+  }));
+}
+
 /**
  * Gather UI state from stores (safe - catches errors)
  */
@@ -176,6 +195,28 @@ function captureDocumentState(
   };
 }
 
+/**
+ * Capture one tab (document, cursor and undo history) for moving it to
+ * another window. Returns null if the tab doesn't exist.
+ */
+export function captureTabState(windowLabel: string, tabId: string): TabState | null {
+  const tab = useTabStore.getState().getTabsByWindow(windowLabel).find((t) => t.id === tabId);
+  if (!tab) return null;
+  return {
+    id: tab.id,
+    file_path: tab.filePath,
+    title: tab.title,
+    is_pinned: tab.isPinned,
+    document: captureDocumentState(
+      tab.id,
+      tab.filePath,
+      tab.title,
+      useDocumentStore.getState(),
+      useUnifiedHistoryStore.getState()
+    ),
+  };
+}
+
 /**
  * Capture complete window state
  */
@@ -184,8 +225,10 @@ function captureWindowState(windowLabel: string, isMainWindow: boolean): WindowS
   const documentStore = useDocumentStore.getState();
   const historyStore = useUnifiedHistoryStore.getState();
 
-  // Get tabs for this window
-  const windowTabs = tabStore.getTabsByWindow(windowLabel);
+  // Get tabs for this window (encrypted documents stay out of the session)
+  const windowTabs = tabStore
+    .getTabsByWindow(windowLabel)
+    .filter((tab) => !tab.filePath || !isEncryptedPath(tab.filePath));
 
   const tabs: TabState[] = windowTabs.map((tab) => ({
     id: tab.id,
@@ -204,9 +247,24 @@ function captureWindowState(windowLabel: string, isMainWindow: boolean): WindowS
     tabs,
     ui_state: getUiStateSafe(),
     geometry: null, // Window geometry capture not yet implemented
+    terminal_sessions: captureTerminalSessions(),
   };
 }
 
+/**
+ * Save unsaved edits of encrypted documents in place, since the session
+ * file can't hold them.
+ */
+async function saveEncryptedTabs(windowLabel: string): Promise<void> {
+  const documentStore = useDocumentStore.getState();
+  for (const tab of useTabStore.getState().getTabsByWindow(windowLabel)) {
+    const doc = documentStore.getDocument(tab.id);
+    if (!tab.filePath || !isEncryptedPath(tab.filePath) || !doc?.isDirty) continue;
+    const saved = await saveToPath(tab.id, tab.filePath, doc.content, 'auto');
+    if (!saved) console.warn('[HotExit] Unsaved edits of encrypted document lost:', tab.filePath);
+  }
+}
+
 export function useHotExitCapture() {
   useEffect(() => {
     const unlistenPromise = listen<CaptureRequest>(HOT_EXIT_EVENTS.CAPTURE_REQUEST, async (event) => {
@@ -223,6 +281,7 @@ export function useHotExitCapture() {
       let response: CaptureResponse;
 
       try {
+        await saveEncryptedTabs(windowLabel);
         const windowState = captureWindowState(windowLabel, isMainWindow);
         response = buildCaptureResponse(captureId, windowLabel, windowState);
       } catch (error) {
@@ -160,3 +160,42 @@ describe('Hot Exit Restore Multi-Window', () => {
     });
   });
 });
+
+describe('restoreTransferredTab', () => {
+  const movedTab = (isDirty: boolean) => {
+    const tab = createMockSession([{ label: 'doc-0', isMain: false, tabCount: 1 }]).windows[0].tabs[0];
+    return { ...tab, document: { ...tab.document, is_dirty: isDirty } };
+  };
+
+  function setup(existingDirty: boolean) {
+    const tabStore = {
+      findTabByPath: vi.fn(() => ({ id: 'existing' })),
+      createTab: vi.fn(() => 'new-tab'),
+      setActiveTab: vi.fn(),
+    };
+    mockTabStore.getState.mockReturnValue(tabStore);
+    mockDocumentStore.getState.mockReturnValue({
+      getDocument: vi.fn(() => ({ isDirty: existingDirty })),
+    });
+    return tabStore;
+  }
+
+  it('activates the open tab instead of duplicating a clean moved file', async () => {
+    const tabStore = setup(true);
+    const { restoreTransferredTab } = await import('./useHotExitRestore');
+
+    await expect(restoreTransferredTab('main', movedTab(false))).resolves.toBe('existing');
+    expect(tabStore.createTab).not.toHaveBeenCalled();
+    expect(tabStore.setActiveTab).toHaveBeenCalledWith('main', 'existing');
+  });
+
+  it('refuses a dirty move onto a tab with its own unsaved edits', async () => {
+    const tabStore = setup(true);
+    const { restoreTransferredTab, TransferConflictError } = await import('./useHotExitRestore');
+
+    await expect(restoreTransferredTab('main', movedTab(true))).rejects.toBeInstanceOf(
+      TransferConflictError
+    );
+    expect(tabStore.createTab).not.toHaveBeenCalled();
+  });
+});
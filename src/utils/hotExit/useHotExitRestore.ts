@@ -22,6 +22,7 @@ import { useDocumentStore } from '@/stores/documentStore';
 import { useUIStore } from '@/stores/uiStore';
 import { useEditorStore } from '@/stores/editorStore';
 import { useUnifiedHistoryStore } from '@/stores/unifiedHistoryStore';
+import { useTerminalSessionStore } from '@/stores/terminalSessionStore';
 import type { WindowState, HistoryCheckpoint, CursorInfo } from './types';
 import { HOT_EXIT_EVENTS } from './types';
 import type { LineEnding } from '@/utils/linebreakDetection';
@@ -302,6 +303,29 @@ async function restoreWindowState(windowLabel: string, windowState: WindowState)
 
   // Restore tabs
   await restoreTabs(windowLabel, windowState);
+
+  restoreTerminalSessions(windowState);
+}
+
+/**
+ * Recreate terminal sessions. Rust has already seeded their scrollback,
+ * which the terminal replays when each session's xterm is created; the
+ * shell is respawned in its saved cwd on first show.
+ */
+function restoreTerminalSessions(windowState: WindowState): void {
+  const terminals = windowState.terminal_sessions ?? [];
+  if (terminals.length === 0) return;
+
+  const active = terminals.find((t) => t.is_active);
+  useTerminalSessionStore.getState().restoreSessions(
+    terminals.map((t) => ({
+      id: t.session_id,
+      label: t.label,
+      cwd: t.cwd ?? undefined,
+      shellPath: t.shell_path ?? undefined,
+    })),
+    active?.session_id ?? null,
+  );
 }
 
 /**
@@ -414,6 +438,52 @@ async function restoreTabs(windowLabel: string, windowState: WindowState): Promi
   }
 }
 
+/** The target window already has the moved file open with unsaved edits */
+export class TransferConflictError extends Error {
+  constructor(filePath: string) {
+    super(`${filePath} is already open with unsaved changes in this window`);
+    this.name = 'TransferConflictError';
+  }
+}
+
+/**
+ * Add a tab moved in from another window (see tab_transfer.rs) and make it
+ * active. Returns the tab id.
+ *
+ * If the window already has the file open (createTab would just return that
+ * tab), the moved state only replaces it when the existing tab is clean; a
+ * clean moved tab simply activates the existing one. When both have unsaved
+ * edits a TransferConflictError is thrown so the source keeps its copy.
+ */
+export async function restoreTransferredTab(
+  windowLabel: string,
+  tabState: import('./types').TabState
+): Promise<string> {
+  const tabStore = useTabStore.getState();
+  const existing = tabState.file_path
+    ? tabStore.findTabByPath(windowLabel, tabState.file_path)
+    : null;
+  if (existing && tabState.file_path) {
+    const existingDirty = useDocumentStore.getState().getDocument(existing.id)?.isDirty ?? false;
+    if (tabState.document.is_dirty) {
+      if (existingDirty) throw new TransferConflictError(tabState.file_path);
+      useUnifiedHistoryStore.getState().clearDocument(existing.id);
+      await restoreDocumentState(existing.id, tabState, useDocumentStore.getState());
+    }
+    tabStore.setActiveTab(windowLabel, existing.id);
+    return existing.id;
+  }
+
+  const tabId = tabStore.createTab(windowLabel, tabState.file_path);
+  tabStore.updateTabTitle(tabId, tabState.title);
+  if (tabState.is_pinned) {
+    tabStore.togglePin(windowLabel, tabId);
+  }
+  await restoreDocumentState(tabId, tabState, useDocumentStore.getState());
+  useTabStore.getState().setActiveTab(windowLabel, tabId);
+  return tabId;
+}
+
 /**
  * Restore document state for a tab
  */
@@ -7,7 +7,8 @@
 
 import { exists, readTextFile, writeTextFile } from "@tauri-apps/plugin-fs";
 import { dirname, join } from "@tauri-apps/api/path";
-import { ASSETS_FOLDER } from "@/utils/imageUtils";
+import { buildAssetRelativePath } from "@/utils/imageUtils";
+import { resolveAssetsDir } from "@/utils/workspaceEditorSettings";
 
 const REGISTRY_FILENAME = "image-hashes.json";
 const REGISTRY_VERSION = 1;
@@ -22,7 +23,7 @@ interface HashRegistry {
  */
 async function getRegistryPath(documentPath: string): Promise<string> {
   const docDir = await dirname(documentPath);
-  const assetsPath = await join(docDir, ASSETS_FOLDER);
+  const assetsPath = await join(docDir, resolveAssetsDir(documentPath));
   return join(assetsPath, REGISTRY_FILENAME);
 }
 
@@ -95,7 +96,7 @@ export async function findExistingImage(
 
   // Verify the file still exists
   const docDir = await dirname(documentPath);
-  const assetsPath = await join(docDir, ASSETS_FOLDER);
+  const assetsPath = await join(docDir, resolveAssetsDir(documentPath));
   const filePath = await join(assetsPath, filename);
 
   const fileExists = await exists(filePath);
@@ -107,7 +108,7 @@ export async function findExistingImage(
   }
 
   // Return relative path in markdown format
-  return `./${ASSETS_FOLDER}/${filename}`;
+  return buildAssetRelativePath(filename, resolveAssetsDir(documentPath));
 }
 
 /**
@@ -142,5 +142,12 @@ describe("imageUtils", () => {
       const filename = "photo-1234567890-abcd.jpg";
       expect(buildAssetRelativePath(filename)).toBe("./assets/images/photo-1234567890-abcd.jpg");
     });
+
+    it("uses a given assets folder", () => {
+      expect(buildAssetRelativePath("a.png", "media")).toBe("./media/a.png");
+      expect(buildAssetRelativePath("a.png", "../media")).toBe("../media/a.png");
+      expect(buildAssetRelativePath("a.png", "..")).toBe("../a.png");
+      expect(buildAssetRelativePath("a.png", ".")).toBe("./a.png");
+    });
   });
 });
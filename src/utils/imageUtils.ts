@@ -53,8 +53,10 @@ export function generateUniqueFilename(originalName: string): string {
 
 /**
  * Build the relative path for an image in the assets folder.
+ * `assetsDir` is relative to the document's folder.
  * Pure function - doesn't touch file system.
  */
-export function buildAssetRelativePath(filename: string): string {
-  return `./${ASSETS_FOLDER}/${filename}`;
+export function buildAssetRelativePath(filename: string, assetsDir = ASSETS_FOLDER): string {
+  if (assetsDir === ".") return `./${filename}`;
+  return /^\.\.(\/|$)/.test(assetsDir) ? `${assetsDir}/${filename}` : `./${assetsDir}/${filename}`;
 }
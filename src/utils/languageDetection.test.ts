@@ -0,0 +1,33 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { detectLanguage, languageName } from "./languageDetection";
+
+const german = { code: "de", name: "German", script: "Latin", confidence: 0.9, reliable: true };
+
+describe("languageDetection", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("skips blank text", async () => {
+    await expect(detectLanguage("  ")).resolves.toBeNull();
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("names reliably detected languages", async () => {
+    vi.mocked(invoke).mockResolvedValue(german);
+    await expect(languageName("Das ist gut", "the original language")).resolves.toBe("German");
+  });
+
+  it("falls back when unsure", async () => {
+    vi.mocked(invoke).mockResolvedValue({ ...german, confidence: 0.2, reliable: false });
+    await expect(languageName("ok", "the original language")).resolves.toBe(
+      "the original language"
+    );
+    vi.mocked(invoke).mockRejectedValue("boom");
+    await expect(languageName("ok", "?")).resolves.toBe("?");
+  });
+});
@@ -0,0 +1,40 @@
+/**
+ * Language Detection
+ *
+ * Purpose: Guess the language of a piece of text locally (no network), so
+ * translation genies can name the source language and spellcheck can pick
+ * a dictionary.
+ *
+ * @module utils/languageDetection
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface DetectedLanguage {
+  /** ISO 639-1 code, e.g. "en", "zh" */
+  code: string;
+  /** English name, e.g. "English" */
+  name: string;
+  script: string;
+  /** 0–1 */
+  confidence: number;
+  /** Confident enough to act on */
+  reliable: boolean;
+}
+
+/** Language of `text`, or `null` if it can't be told (or detection fails). */
+export async function detectLanguage(text: string): Promise<DetectedLanguage | null> {
+  if (!text.trim()) return null;
+  try {
+    return await invoke<DetectedLanguage | null>("detect_language", { text });
+  } catch (error) {
+    console.debug("[LanguageDetection] Failed:", error);
+    return null;
+  }
+}
+
+/** Name of the language of `text` when detection is reliable, else `fallback`. */
+export async function languageName(text: string, fallback: string): Promise<string> {
+  const detected = await detectLanguage(text);
+  return detected?.reliable ? detected.name : fallback;
+}
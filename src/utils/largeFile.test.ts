@@ -0,0 +1,47 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { readFileIncrementally, type FileChunk } from "./largeFile";
+
+const chunk = (content: string, offset: number, totalSize: number, modified = 1): FileChunk => ({
+  content,
+  offset,
+  nextOffset: offset + content.length,
+  eof: offset + content.length >= totalSize,
+  totalSize,
+  modified,
+});
+
+describe("readFileIncrementally", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("concatenates chunks until eof and reports progress", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(chunk("line 1\n", 0, 14))
+      .mockResolvedValueOnce(chunk("line 2\n", 7, 14));
+    const progress = vi.fn();
+
+    const text = await readFileIncrementally("/big.md", { chunkSize: 8, onProgress: progress });
+
+    expect(text).toBe("line 1\nline 2\n");
+    expect(invoke).toHaveBeenLastCalledWith("read_file_chunked", { path: "/big.md", offset: 7, len: 8 });
+    expect(progress).toHaveBeenLastCalledWith(14, 14);
+  });
+
+  it("restarts when the file changes mid-read", async () => {
+    vi.mocked(invoke)
+      .mockResolvedValueOnce(chunk("old\n", 0, 8, 1))
+      .mockResolvedValueOnce(chunk("new\n", 4, 8, 2))
+      .mockResolvedValueOnce(chunk("new\nnew\n", 0, 8, 2));
+
+    expect(await readFileIncrementally("/big.md")).toBe("new\nnew\n");
+  });
+
+  it("stops when aborted", async () => {
+    const controller = new AbortController();
+    controller.abort();
+    await expect(readFileIncrementally("/big.md", { signal: controller.signal })).rejects.toThrow("Read aborted");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,84 @@
+/**
+ * Incremental loading for very large files.
+ *
+ * Reading a multi-hundred-MB file in one IPC call freezes the renderer.
+ * Large files are pulled from the backend in chunks instead, yielding to the
+ * event loop between chunks. Chunks end on character (and usually line)
+ * boundaries, so they can be concatenated as-is. If the file changes on disk
+ * mid-load the read restarts.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface FileMetadata {
+  size: number;
+  modified: number | null;
+  isLarge: boolean;
+  isReadonly: boolean;
+}
+
+export interface FileChunk {
+  content: string;
+  offset: number;
+  nextOffset: number;
+  eof: boolean;
+  totalSize: number;
+  modified: number | null;
+}
+
+export interface IncrementalReadOptions {
+  /** Bytes per chunk (default 4 MB) */
+  chunkSize?: number;
+  /** Called after each chunk with bytes read so far and the file size */
+  onProgress?: (loaded: number, total: number) => void;
+  signal?: AbortSignal;
+}
+
+const DEFAULT_CHUNK_SIZE = 4 * 1024 * 1024;
+
+/** Restarts allowed when the file keeps changing during a read */
+const MAX_RESTARTS = 3;
+
+export function getFileMetadata(path: string): Promise<FileMetadata> {
+  return invoke<FileMetadata>("get_file_metadata", { path });
+}
+
+export function readFileChunk(path: string, offset: number, len: number): Promise<FileChunk> {
+  return invoke<FileChunk>("read_file_chunked", { path, offset, len });
+}
+
+const yieldToEventLoop = () => new Promise<void>((resolve) => setTimeout(resolve, 0));
+
+/**
+ * Read a whole text file chunk by chunk. Rejects with an AbortError when
+ * `signal` fires.
+ */
+export async function readFileIncrementally(path: string, options: IncrementalReadOptions = {}): Promise<string> {
+  const { chunkSize = DEFAULT_CHUNK_SIZE, onProgress, signal } = options;
+
+  for (let attempt = 0; attempt <= MAX_RESTARTS; attempt++) {
+    const parts: string[] = [];
+    let offset = 0;
+    let first: FileChunk | null = null;
+    let changed = false;
+
+    for (;;) {
+      if (signal?.aborted) throw new DOMException("Read aborted", "AbortError");
+      const chunk = await readFileChunk(path, offset, chunkSize);
+      first ??= chunk;
+      if (chunk.totalSize !== first.totalSize || chunk.modified !== first.modified) {
+        changed = true;
+        break;
+      }
+      parts.push(chunk.content);
+      offset = chunk.nextOffset;
+      onProgress?.(offset, chunk.totalSize);
+      if (chunk.eof) return parts.join("");
+      await yieldToEventLoop();
+    }
+
+    if (changed) {
+      console.warn(`[LargeFile] ${path} changed while loading, restarting`);
+    }
+  }
+  throw new Error(`File keeps changing while loading: ${path}`);
+}
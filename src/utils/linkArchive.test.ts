@@ -0,0 +1,30 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { archiveAnnotation, archiveLink } from "./linkArchive";
+
+describe("linkArchive", () => {
+  it("passes the document path for relative hrefs", async () => {
+    vi.mocked(invoke).mockResolvedValue({ href: "../.vmark/archive/a.md" });
+    await archiveLink("https://example.com", "/ws", "/ws/notes/n.md");
+    expect(invoke).toHaveBeenCalledWith("archive_link", {
+      url: "https://example.com",
+      workspaceRoot: "/ws",
+      documentPath: "/ws/notes/n.md",
+      format: "markdown",
+    });
+  });
+
+  it("builds the annotation", () => {
+    expect(
+      archiveAnnotation({
+        path: "/ws/.vmark/archive/2026-01-02-x.md",
+        href: ".vmark/archive/2026-01-02-x.md",
+        title: "X",
+        archivedAt: "2026-01-02T00:00:00+00:00",
+      })
+    ).toBe(" ([archived](.vmark/archive/2026-01-02-x.md))");
+  });
+});
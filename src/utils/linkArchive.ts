@@ -0,0 +1,39 @@
+/**
+ * Link Archive
+ *
+ * Purpose: Save a readable copy of a linked web page into the workspace's
+ * `.vmark/archive/` folder, and build the "(archived)" annotation that is
+ * placed after the link so the note keeps its source if the page goes away.
+ *
+ * @module utils/linkArchive
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type ArchiveFormat = "markdown" | "html";
+
+export interface ArchivedLink {
+  /** Absolute path of the archived copy */
+  path: string;
+  /** Href for the copy, relative to the document */
+  href: string;
+  title: string | null;
+  archivedAt: string;
+}
+
+/** Text of the annotation link placed after an archived link. */
+export const ARCHIVE_LABEL = "archived";
+
+export function archiveLink(
+  url: string,
+  workspaceRoot: string,
+  documentPath: string | null,
+  format: ArchiveFormat = "markdown"
+): Promise<ArchivedLink> {
+  return invoke<ArchivedLink>("archive_link", { url, workspaceRoot, documentPath, format });
+}
+
+/** Markdown annotation for source mode, e.g. ` ([archived](.vmark/archive/x.md))`. */
+export function archiveAnnotation(archived: ArchivedLink): string {
+  return ` ([${ARCHIVE_LABEL}](${archived.href}))`;
+}
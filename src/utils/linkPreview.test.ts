@@ -0,0 +1,48 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+  convertFileSrc: vi.fn((path: string) => `asset://${path}`),
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { canPreviewLink, fetchLinkPreview } from "./linkPreview";
+
+describe("fetchLinkPreview", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("only previews web links", async () => {
+    expect(canPreviewLink("https://example.com")).toBe(true);
+    expect(canPreviewLink("#heading")).toBe(false);
+    expect(await fetchLinkPreview("file:///tmp/a.md")).toBeNull();
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("converts cached image paths to asset URLs", async () => {
+    vi.mocked(invoke).mockResolvedValue({
+      url: "https://example.com/",
+      title: "Example",
+      description: null,
+      siteName: null,
+      favicon: "/data/link-previews/abc-favicon.png",
+      image: null,
+      fetchedAt: 1,
+    });
+    const preview = await fetchLinkPreview("https://example.com/");
+    expect(preview?.favicon).toBe("asset:///data/link-previews/abc-favicon.png");
+    expect(preview?.image).toBeNull();
+  });
+
+  it("shares one request per link", async () => {
+    vi.mocked(invoke).mockResolvedValue({ url: "https://a.io/", fetchedAt: 1 });
+    await Promise.all([fetchLinkPreview("https://a.io/"), fetchLinkPreview("https://a.io/")]);
+    expect(invoke).toHaveBeenCalledTimes(1);
+  });
+
+  it("resolves to null on failure", async () => {
+    vi.mocked(invoke).mockRejectedValue("offline");
+    expect(await fetchLinkPreview("https://down.example/")).toBeNull();
+  });
+});
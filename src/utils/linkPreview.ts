@@ -0,0 +1,55 @@
+/**
+ * Link Previews
+ *
+ * Purpose: Fetch hover-card metadata for external links. The backend
+ * downloads the favicon and preview image into app data; this wrapper turns
+ * those paths into asset URLs the webview can display.
+ *
+ * @module utils/linkPreview
+ */
+
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+
+export interface LinkPreview {
+  /** Final URL after redirects */
+  url: string;
+  title: string | null;
+  description: string | null;
+  siteName: string | null;
+  /** Asset URL of the downloaded favicon */
+  favicon: string | null;
+  /** Asset URL of the downloaded og:image */
+  image: string | null;
+  fetchedAt: number;
+}
+
+const pending = new Map<string, Promise<LinkPreview | null>>();
+
+/** Only web links get previews. */
+export function canPreviewLink(href: string): boolean {
+  return /^https?:\/\//i.test(href);
+}
+
+/**
+ * Preview for `href`, or null if it can't be fetched. Concurrent calls
+ * for the same link share one request.
+ */
+export function fetchLinkPreview(href: string, refresh = false): Promise<LinkPreview | null> {
+  if (!canPreviewLink(href)) return Promise.resolve(null);
+  const existing = pending.get(href);
+  if (existing && !refresh) return existing;
+
+  const request = invoke<LinkPreview>("fetch_link_preview", { url: href, refresh })
+    .then((preview) => ({
+      ...preview,
+      favicon: preview.favicon ? convertFileSrc(preview.favicon) : null,
+      image: preview.image ? convertFileSrc(preview.image) : null,
+    }))
+    .catch((error) => {
+      console.warn("[linkPreview] Failed to fetch preview:", error);
+      pending.delete(href);
+      return null;
+    });
+  pending.set(href, request);
+  return request;
+}
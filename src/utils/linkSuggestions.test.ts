@@ -0,0 +1,12 @@
+import { describe, it, expect } from "vitest";
+import { formatWikiLinkTarget } from "./linkSuggestions";
+
+describe("formatWikiLinkTarget", () => {
+  it("drops the markdown extension", () => {
+    expect(formatWikiLinkTarget({ path: "projects/roadmap.md", heading: null })).toBe("projects/roadmap");
+  });
+
+  it("appends the heading", () => {
+    expect(formatWikiLinkTarget({ path: "roadmap.markdown", heading: "Q2 Goals" })).toBe("roadmap#Q2 Goals");
+  });
+});
@@ -0,0 +1,25 @@
+/**
+ * `[[` autocomplete backed by the backend link index.
+ *
+ * The backend ranks note titles, paths and headings so the webview never
+ * loads the whole file list. `note#heading` queries suggest headings.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface LinkSuggestion {
+  /** Relative to the workspace root */
+  path: string;
+  title: string;
+  heading: string | null;
+  score: number;
+}
+
+export function suggestLinkTargets(workspaceRoot: string, query: string, limit?: number): Promise<LinkSuggestion[]> {
+  return invoke<LinkSuggestion[]>("suggest_link_targets", { workspaceRoot, query, limit: limit ?? null });
+}
+
+/** Wiki link text for a suggestion: path without the markdown extension, plus `#heading`. */
+export function formatWikiLinkTarget(suggestion: Pick<LinkSuggestion, "path" | "heading">): string {
+  const target = suggestion.path.replace(/\.(md|markdown|mdown|mkd|mdx)$/i, "");
+  return suggestion.heading ? `${target}#${suggestion.heading}` : target;
+}
@@ -0,0 +1,129 @@
+/**
+ * Markdown Block Structure
+ *
+ * Purpose: Wrappers for the backend block parser. A document is parsed once,
+ * then kept current by forwarding CodeMirror change sets, so outline,
+ * folding and genie scope lookups don't re-parse in the webview.
+ *
+ * Offsets are UTF-16 code units, the same as editor positions.
+ *
+ * @module utils/markdownAst
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ViewUpdate } from "@codemirror/view";
+import { changesToEdits } from "./nativeFind";
+
+export type BlockKind =
+  | "frontMatter"
+  | "heading"
+  | "paragraph"
+  | "codeBlock"
+  | "mathBlock"
+  | "blockquote"
+  | "list"
+  | "listItem"
+  | "table"
+  | "thematicBreak"
+  | "html";
+
+export interface MarkdownBlock {
+  kind: BlockKind;
+  from: number;
+  to: number;
+  /** 0-based */
+  startLine: number;
+  endLine: number;
+  level?: number;
+  text?: string;
+  lang?: string;
+  /** Content of a blockquote or list item; the items of a list */
+  children?: MarkdownBlock[];
+}
+
+export interface NodeAt {
+  /** Top-level block */
+  block: MarkdownBlock | null;
+  /** Deepest block at the offset */
+  innermost: MarkdownBlock | null;
+  /** Headings whose sections contain the offset, outermost first */
+  headings: MarkdownBlock[];
+}
+
+export interface FoldRange {
+  kind: BlockKind;
+  from: number;
+  to: number;
+  startLine: number;
+  endLine: number;
+}
+
+/**
+ * Parses and edits of each document, run one after another: the backend
+ * handles them off the main thread, so they could otherwise finish out of
+ * order and apply an edit to the wrong text.
+ */
+const documentQueues = new Map<string, Promise<void>>();
+
+function enqueue<T>(docId: string, task: () => Promise<T>): Promise<T> {
+  const run = (documentQueues.get(docId) ?? Promise.resolve()).then(task);
+  documentQueues.set(
+    docId,
+    run.then(
+      () => undefined,
+      () => undefined
+    )
+  );
+  return run;
+}
+
+/** Resolves to the number of blocks. */
+export function parseDocument(docId: string, content: string): Promise<number> {
+  return enqueue(docId, () => invoke<number>("parse_document", { docId, content }));
+}
+
+/** Forward an editor update; on failure the document is parsed again. */
+export function trackParsedEdits(docId: string, update: ViewUpdate): void {
+  if (!update.docChanged) return;
+  const edits = changesToEdits(update.changes);
+  enqueue(docId, () => invoke("edit_parsed_document", { docId, edits })).catch(() => {
+    void parseDocument(docId, update.state.doc.toString());
+  });
+}
+
+export function getDocumentBlocks(
+  docId: string,
+  from?: number,
+  to?: number
+): Promise<MarkdownBlock[]> {
+  return invoke<MarkdownBlock[]>("get_document_blocks", {
+    docId,
+    from: from ?? null,
+    to: to ?? null,
+  });
+}
+
+export function getDocumentOutline(docId: string): Promise<MarkdownBlock[]> {
+  return invoke<MarkdownBlock[]>("get_document_outline", { docId });
+}
+
+export function getNodeAtOffset(docId: string, offset: number): Promise<NodeAt> {
+  return invoke<NodeAt>("get_node_at_offset", { docId, offset });
+}
+
+export function getFoldRanges(docId: string): Promise<FoldRange[]> {
+  return invoke<FoldRange[]>("get_fold_ranges", { docId });
+}
+
+export function getCodeFenceLanguages(docId: string): Promise<string[]> {
+  return invoke<string[]>("get_code_fence_languages", { docId });
+}
+
+export function releaseParsedDocument(docId: string): Promise<void> {
+  const run = enqueue(docId, () => invoke<void>("release_parsed_document", { docId }));
+  const last = documentQueues.get(docId);
+  void last?.then(() => {
+    if (documentQueues.get(docId) === last) documentQueues.delete(docId);
+  });
+  return run;
+}
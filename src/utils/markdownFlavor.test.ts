@@ -0,0 +1,29 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { convertFlavor, MARKDOWN_FLAVORS } from "./markdownFlavor";
+
+describe("markdownFlavor", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("passes content and flavors", async () => {
+    const result = { content: "[Note](Note.md)", changes: 1, warnings: [] };
+    vi.mocked(invoke).mockResolvedValue(result);
+    await expect(convertFlavor("[[Note]]", "obsidian", "github")).resolves.toEqual(result);
+    expect(invoke).toHaveBeenCalledWith("convert_flavor", {
+      content: "[[Note]]",
+      from: "obsidian",
+      to: "github",
+    });
+  });
+
+  it("lists every flavor once", () => {
+    const values = MARKDOWN_FLAVORS.map((f) => f.value);
+    expect(new Set(values).size).toBe(values.length);
+    expect(values).toEqual(["commonmark", "github", "obsidian"]);
+  });
+});
@@ -0,0 +1,36 @@
+/**
+ * Markdown Flavor Conversion
+ *
+ * Purpose: Rewrite a document between CommonMark, GitHub and Obsidian
+ * Markdown — wiki links, highlights, callouts, footnotes, strikethrough and
+ * comments — for migrating vaults or publishing notes to GitHub. Code and
+ * frontmatter are left untouched by the backend.
+ *
+ * @module utils/markdownFlavor
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type MarkdownFlavor = "commonmark" | "github" | "obsidian";
+
+export const MARKDOWN_FLAVORS: { value: MarkdownFlavor; label: string }[] = [
+  { value: "commonmark", label: "CommonMark" },
+  { value: "github", label: "GitHub" },
+  { value: "obsidian", label: "Obsidian" },
+];
+
+export interface FlavorConversion {
+  content: string;
+  /** Constructs rewritten */
+  changes: number;
+  /** Lossy conversions, e.g. callout types GitHub doesn't have */
+  warnings: string[];
+}
+
+export function convertFlavor(
+  content: string,
+  from: MarkdownFlavor,
+  to: MarkdownFlavor
+): Promise<FlavorConversion> {
+  return invoke<FlavorConversion>("convert_flavor", { content, from, to });
+}
@@ -0,0 +1,60 @@
+/**
+ * Native context menus
+ *
+ * Describes a right-click menu and shows it with the Rust
+ * `show_context_menu` command, resolving to the chosen item id.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface NativeContextMenuItem {
+  id?: string;
+  label?: string;
+  enabled?: boolean;
+  /** Present for check items */
+  checked?: boolean;
+  accelerator?: string;
+  separator?: boolean;
+  /** Present for submenus */
+  items?: NativeContextMenuItem[];
+}
+
+/** Page interactions that mean a popped-up menu has closed */
+const DISMISS_EVENTS = ["pointerdown", "keydown", "wheel"] as const;
+
+/**
+ * Show a native context menu at a position in window (CSS) coordinates,
+ * or at the cursor. Resolves to the chosen item id, or null when the menu
+ * is dismissed (or replaced by another one).
+ *
+ * Native menus don't report being closed without a pick, so the next
+ * pointer, key or wheel event in the page dismisses the request.
+ */
+export async function showNativeContextMenu(
+  items: NativeContextMenuItem[],
+  position?: { x: number; y: number }
+): Promise<string | null> {
+  const requestId = crypto.randomUUID();
+  const dismiss = () => {
+    invoke("dismiss_context_menu", { requestId }).catch(() => {});
+  };
+  // Attach after the opening right-click has finished dispatching
+  const timer = window.setTimeout(() => {
+    DISMISS_EVENTS.forEach((type) => window.addEventListener(type, dismiss, true));
+  }, 0);
+
+  try {
+    return await invoke<string | null>("show_context_menu", {
+      requestId,
+      items,
+      x: position?.x ?? null,
+      y: position?.y ?? null,
+    });
+  } catch (err) {
+    console.warn("[ContextMenu] Failed to show native menu:", err);
+    return null;
+  } finally {
+    window.clearTimeout(timer);
+    DISMISS_EVENTS.forEach((type) => window.removeEventListener(type, dismiss, true));
+  }
+}
@@ -0,0 +1,80 @@
+import { describe, it, expect, vi } from "vitest";
+import { ChangeSet, Text } from "@codemirror/state";
+import type { ViewUpdate } from "@codemirror/view";
+import { invoke } from "@tauri-apps/api/core";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import {
+  changesToEdits,
+  findInDocument,
+  shouldUseNativeFind,
+  trackFindEdits,
+  NATIVE_FIND_THRESHOLD,
+} from "./nativeFind";
+
+describe("changesToEdits", () => {
+  it("uses offsets of the original document", () => {
+    const changes = ChangeSet.of(
+      [
+        { from: 0, to: 3, insert: "two" },
+        { from: 7, insert: "!" },
+      ],
+      7
+    );
+    expect(changesToEdits(changes)).toEqual([
+      { from: 0, to: 3, insert: "two" },
+      { from: 7, to: 7, insert: "!" },
+    ]);
+  });
+});
+
+describe("shouldUseNativeFind", () => {
+  it("only applies to very large documents", () => {
+    expect(shouldUseNativeFind(NATIVE_FIND_THRESHOLD)).toBe(false);
+    expect(shouldUseNativeFind(NATIVE_FIND_THRESHOLD + 1)).toBe(true);
+  });
+});
+
+describe("command order", () => {
+  it("sends a tab's edits only after its document has loaded", async () => {
+    const calls: string[] = [];
+    let finishLoad: () => void = () => {};
+    vi.mocked(invoke).mockImplementation(async (command) => {
+      calls.push(`${command}:start`);
+      if (command === "load_find_document") {
+        await new Promise<void>((resolve) => (finishLoad = resolve));
+      }
+      calls.push(`${command}:end`);
+      return { matches: [], total: 0, truncated: false, page: 0, pageSize: 0 };
+    });
+
+    const doc = Text.of(["hello"]);
+    const search = findInDocument("tab-order", doc, {
+      query: "h",
+      caseSensitive: false,
+      wholeWord: false,
+      useRegex: false,
+    });
+    const changes = ChangeSet.of([{ from: 5, insert: "!" }], 5);
+    trackFindEdits("tab-order", {
+      docChanged: true,
+      startState: { doc },
+      state: { doc: changes.apply(doc) },
+      changes,
+    } as unknown as ViewUpdate);
+
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    expect(calls).toEqual(["load_find_document:start"]);
+    finishLoad();
+    await search;
+    expect(calls).toEqual([
+      "load_find_document:start",
+      "load_find_document:end",
+      "edit_find_document:start",
+      "edit_find_document:end",
+      "find_in_document:start",
+      "find_in_document:end",
+    ]);
+  });
+});
@@ -0,0 +1,142 @@
+/**
+ * Native Find
+ *
+ * Purpose: Search very large source-mode documents in the backend, where a
+ * regex over several MB doesn't block typing. The text is sent once per
+ * tab, then kept current by forwarding CodeMirror change sets.
+ *
+ * Offsets are UTF-16 code units, the same as CodeMirror positions.
+ *
+ * @module utils/nativeFind
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { ChangeSet, Text } from "@codemirror/state";
+import type { ViewUpdate } from "@codemirror/view";
+
+/** Documents longer than this (in characters) are searched natively. */
+export const NATIVE_FIND_THRESHOLD = 1_000_000;
+
+export interface FindOptions {
+  query: string;
+  caseSensitive: boolean;
+  wholeWord: boolean;
+  useRegex: boolean;
+}
+
+export interface FindEdit {
+  from: number;
+  to: number;
+  insert: string;
+}
+
+export interface FindPage {
+  matches: { from: number; to: number }[];
+  total: number;
+  /** True when the backend stopped at its match limit */
+  truncated: boolean;
+  page: number;
+  pageSize: number;
+}
+
+// The document text last sent to the backend, per tab
+const synced = new Map<string, Text>();
+
+// The backend runs the commands concurrently, so each tab's are chained to
+// keep edits in order
+const queues = new Map<string, Promise<unknown>>();
+
+function enqueue<T>(tabId: string, job: () => Promise<T>): Promise<T> {
+  const next = (queues.get(tabId) ?? Promise.resolve()).catch(() => {}).then(job);
+  queues.set(tabId, next);
+  const forget = () => {
+    if (queues.get(tabId) === next) queues.delete(tabId);
+  };
+  next.then(forget, forget);
+  return next;
+}
+
+export function shouldUseNativeFind(length: number): boolean {
+  return length > NATIVE_FIND_THRESHOLD;
+}
+
+/** A CodeMirror change set as edits in offsets of the old document. */
+export function changesToEdits(changes: ChangeSet): FindEdit[] {
+  const edits: FindEdit[] = [];
+  changes.iterChanges((fromA, toA, _fromB, _toB, inserted) => {
+    edits.push({ from: fromA, to: toA, insert: inserted.toString() });
+  });
+  return edits;
+}
+
+function toFindQuery(options: FindOptions) {
+  return {
+    pattern: options.query,
+    caseSensitive: options.caseSensitive,
+    wholeWord: options.wholeWord,
+    regex: options.useRegex,
+  };
+}
+
+/** Send the document unless the backend already has this exact text. */
+async function syncFindDocument(tabId: string, doc: Text): Promise<void> {
+  if (synced.get(tabId) === doc) return;
+  synced.set(tabId, doc);
+  try {
+    await enqueue(tabId, () => invoke("load_find_document", { tabId, content: doc.toString() }));
+  } catch (error) {
+    if (synced.get(tabId) === doc) synced.delete(tabId);
+    throw error;
+  }
+}
+
+/**
+ * Forward an editor update to the backend copy, if one is loaded.
+ * Anything unexpected just drops the copy; the next search reloads it.
+ */
+export function trackFindEdits(tabId: string, update: ViewUpdate): void {
+  if (!update.docChanged || synced.get(tabId) !== update.startState.doc) return;
+  synced.set(tabId, update.state.doc);
+  const doc = update.state.doc;
+  const edits = changesToEdits(update.changes);
+  enqueue(tabId, () => invoke("edit_find_document", { tabId, edits })).catch(() => {
+    if (synced.get(tabId) === doc) synced.delete(tabId);
+  });
+}
+
+/** One page of matches, loading the document first if needed. */
+export async function findInDocument(
+  tabId: string,
+  doc: Text,
+  options: FindOptions,
+  page = 0,
+  pageSize = 100
+): Promise<FindPage> {
+  await syncFindDocument(tabId, doc);
+  return enqueue(tabId, () =>
+    invoke<FindPage>("find_in_document", {
+      tabId,
+      query: toFindQuery(options),
+      page,
+      pageSize,
+    })
+  );
+}
+
+/** Number of matches; 0 for an invalid pattern, like `countMatches`. */
+export async function countMatchesNative(
+  tabId: string,
+  doc: Text,
+  options: FindOptions
+): Promise<number> {
+  try {
+    return (await findInDocument(tabId, doc, options, 0, 0)).total;
+  } catch {
+    return 0;
+  }
+}
+
+export function releaseFindDocument(tabId: string): Promise<void> {
+  if (!synced.delete(tabId)) return Promise.resolve();
+  return enqueue(tabId, () => invoke("release_find_document", { tabId }));
+}
@@ -0,0 +1,33 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { buildContext } from "./notesContext";
+
+describe("buildContext", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(invoke).mockResolvedValue({ context: "", sources: [], tokens: 0, method: "keyword" });
+  });
+
+  it("uses the default budget and model", async () => {
+    await buildContext("when to water tomatoes", "/ws");
+    expect(invoke).toHaveBeenCalledWith("build_context", {
+      query: "when to water tomatoes",
+      workspaceRoot: "/ws",
+      budgetTokens: null,
+      model: null,
+    });
+  });
+
+  it("passes an explicit budget and model", async () => {
+    await buildContext("q", "/ws", 500, { model: "mxbai-embed-large" });
+    expect(invoke).toHaveBeenCalledWith("build_context", {
+      query: "q",
+      workspaceRoot: "/ws",
+      budgetTokens: 500,
+      model: "mxbai-embed-large",
+    });
+  });
+});
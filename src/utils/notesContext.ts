@@ -0,0 +1,49 @@
+/**
+ * Notes Context
+ *
+ * Purpose: Gather excerpts from workspace notes relevant to a question, for
+ * genies that answer from the user's notes (`{{notes}}`). The backend ranks
+ * note passages with the semantic index, falling back to keyword matching
+ * when no embedding model is reachable, and returns them numbered with
+ * their source note so the AI can cite them.
+ *
+ * @module utils/notesContext
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { embeddingArgs, type EmbeddingOptions } from "@/utils/semanticSearch";
+
+export interface ContextSource {
+  /** Number of the excerpt in the context block ([1], [2], ...) */
+  index: number;
+  /** Workspace-relative note path */
+  path: string;
+  title: string;
+  heading: string | null;
+  /** 1-based line where the excerpt starts */
+  line: number;
+  score: number;
+}
+
+export interface ContextBlock {
+  /** Excerpts ready to paste into a prompt; empty when nothing matched */
+  context: string;
+  sources: ContextSource[];
+  /** Estimated token count of `context` */
+  tokens: number;
+  method: "semantic" | "keyword";
+}
+
+export function buildContext(
+  query: string,
+  workspaceRoot: string,
+  budgetTokens?: number,
+  options: EmbeddingOptions = {}
+): Promise<ContextBlock> {
+  return invoke<ContextBlock>("build_context", {
+    query,
+    workspaceRoot,
+    budgetTokens: budgetTokens ?? null,
+    ...embeddingArgs(options),
+  });
+}
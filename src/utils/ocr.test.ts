@@ -0,0 +1,23 @@
+import { describe, it, expect } from "vitest";
+import { downloadPercent, ocrLanguageArg } from "./ocr";
+
+describe("ocrLanguageArg", () => {
+  it("joins languages with +", () => {
+    expect(ocrLanguageArg(["eng", "deu", "eng"])).toBe("eng+deu");
+  });
+
+  it("returns null for no languages", () => {
+    expect(ocrLanguageArg([" ", ""])).toBeNull();
+  });
+});
+
+describe("downloadPercent", () => {
+  it("rounds and caps the percentage", () => {
+    expect(downloadPercent({ lang: "eng", downloaded: 1, total: 3 })).toBe(33);
+    expect(downloadPercent({ lang: "eng", downloaded: 5, total: 3 })).toBe(100);
+  });
+
+  it("returns null without a total", () => {
+    expect(downloadPercent({ lang: "eng", downloaded: 10, total: null })).toBeNull();
+  });
+});
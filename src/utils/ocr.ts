@@ -0,0 +1,68 @@
+/**
+ * Text recognition for images dropped into documents.
+ *
+ * The backend runs a system-installed `tesseract`. Extra language packs can
+ * be downloaded into the app data folder; download progress is emitted as
+ * `ocr:download-progress`.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface OcrInfo {
+  available: boolean;
+  path: string | null;
+  version: string | null;
+  /** Language codes usable with `extractTextFromImage` */
+  languages: string[];
+}
+
+export interface OcrLanguage {
+  code: string;
+  /** `managed` packs were downloaded by VMark and can be removed */
+  source: "system" | "managed";
+}
+
+export interface OcrDownloadProgress {
+  lang: string;
+  downloaded: number;
+  total: number | null;
+}
+
+export function detectTesseract(): Promise<OcrInfo> {
+  return invoke<OcrInfo>("detect_tesseract");
+}
+
+/** Recognize the text in an image; `langs` defaults to English. */
+export function extractTextFromImage(path: string, langs: string[] = []): Promise<string> {
+  return invoke<string>("extract_text_from_image", { path, lang: ocrLanguageArg(langs) });
+}
+
+export function listOcrLanguages(): Promise<OcrLanguage[]> {
+  return invoke<OcrLanguage[]>("list_ocr_languages");
+}
+
+export function downloadOcrLanguage(lang: string): Promise<void> {
+  return invoke("download_ocr_language", { lang });
+}
+
+export function removeOcrLanguage(lang: string): Promise<void> {
+  return invoke("remove_ocr_language", { lang });
+}
+
+export function onOcrDownloadProgress(
+  handler: (progress: OcrDownloadProgress) => void
+): Promise<UnlistenFn> {
+  return listen<OcrDownloadProgress>("ocr:download-progress", (event) => handler(event.payload));
+}
+
+/** Tesseract's multi-language form: `["eng", "deu"]` → `"eng+deu"`. */
+export function ocrLanguageArg(langs: string[]): string | null {
+  const unique = [...new Set(langs.map((l) => l.trim()).filter(Boolean))];
+  return unique.length > 0 ? unique.join("+") : null;
+}
+
+/** Percentage for a download progress bar, or null when the size is unknown. */
+export function downloadPercent(progress: OcrDownloadProgress): number | null {
+  if (!progress.total) return null;
+  return Math.min(100, Math.round((progress.downloaded / progress.total) * 100));
+}
@@ -0,0 +1,51 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import {
+  deleteOllamaModel,
+  formatModelSize,
+  ollamaWarmup,
+  pullPercent,
+  setOllamaKeepAlive,
+} from "./ollama";
+
+describe("ollama", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(invoke).mockResolvedValue(undefined);
+  });
+
+  it("passes an empty endpoint as null", async () => {
+    await ollamaWarmup("llama3.2", "");
+    expect(invoke).toHaveBeenCalledWith("ollama_warmup", { model: "llama3.2", endpoint: null });
+    await setOllamaKeepAlive(null, "http://gpu-box:11434");
+    expect(invoke).toHaveBeenCalledWith("ollama_keep_alive", {
+      model: null,
+      endpoint: "http://gpu-box:11434",
+    });
+  });
+
+  it("deletes a model", async () => {
+    await deleteOllamaModel("llama3.2:latest");
+    expect(invoke).toHaveBeenCalledWith("delete_ollama_model", {
+      model: "llama3.2:latest",
+      endpoint: null,
+    });
+  });
+
+  it("formats model sizes", () => {
+    expect(formatModelSize(512)).toBe("512 B");
+    expect(formatModelSize(274302450)).toBe("274.3 MB");
+    expect(formatModelSize(2019393189)).toBe("2.0 GB");
+  });
+
+  it("computes pull progress", () => {
+    const base = { model: "m", status: "pulling" };
+    expect(pullPercent({ ...base, completed: 50, total: 200 })).toBe(25);
+    expect(pullPercent({ ...base, completed: null, total: null })).toBeNull();
+    expect(pullPercent({ ...base, completed: 0, total: 0 })).toBeNull();
+  });
+});
@@ -0,0 +1,82 @@
+/**
+ * Ollama
+ *
+ * Purpose: Keep local Ollama models ready for genies. Ollama loads a model
+ * on its first request (often several seconds) and unloads it after a few
+ * idle minutes; `ollamaWarmup` loads it ahead of time and
+ * `setOllamaKeepAlive` keeps it loaded with background pings. Installed
+ * models can be listed, pulled (with progress events) and deleted.
+ *
+ * @module utils/ollama
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface LocalOllamaModel {
+  name: string;
+  /** Size on disk, in bytes */
+  size: number;
+  modifiedAt: string | null;
+  /** e.g. "3.2B" */
+  parameterSize: string | null;
+  /** e.g. "Q4_K_M" */
+  quantizationLevel: string | null;
+}
+
+export interface OllamaPullProgress {
+  model: string;
+  /** Ollama's status line, e.g. "pulling manifest", "success" */
+  status: string;
+  /** Bytes of the current layer downloaded so far */
+  completed: number | null;
+  /** Size of the current layer */
+  total: number | null;
+}
+
+/** Load `model` into memory; resolves to the load time in milliseconds. */
+export function ollamaWarmup(model: string, endpoint?: string | null): Promise<number> {
+  return invoke<number>("ollama_warmup", { model, endpoint: endpoint || null });
+}
+
+/** Keep `model` loaded until called again with another model or `null`. */
+export function setOllamaKeepAlive(model: string | null, endpoint?: string | null): Promise<void> {
+  return invoke("ollama_keep_alive", { model, endpoint: endpoint || null });
+}
+
+export function listLocalOllamaModels(endpoint?: string | null): Promise<LocalOllamaModel[]> {
+  return invoke<LocalOllamaModel[]>("list_local_ollama_models", { endpoint: endpoint || null });
+}
+
+/** Download `model`; progress arrives through `onOllamaPullProgress`. */
+export function pullOllamaModel(model: string, endpoint?: string | null): Promise<void> {
+  return invoke("pull_ollama_model", { model, endpoint: endpoint || null });
+}
+
+export function deleteOllamaModel(model: string, endpoint?: string | null): Promise<void> {
+  return invoke("delete_ollama_model", { model, endpoint: endpoint || null });
+}
+
+export function onOllamaPullProgress(
+  handler: (progress: OllamaPullProgress) => void
+): Promise<UnlistenFn> {
+  return listen<OllamaPullProgress>("ollama:pull-progress", (event) => handler(event.payload));
+}
+
+/** Percentage of the current layer, or `null` while there's no size yet. */
+export function pullPercent(progress: OllamaPullProgress): number | null {
+  if (!progress.total || progress.completed == null) return null;
+  return Math.min(100, Math.round((progress.completed / progress.total) * 100));
+}
+
+/** Human-readable model size, e.g. "2.0 GB". */
+export function formatModelSize(bytes: number): string {
+  const units = ["B", "KB", "MB", "GB", "TB"];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1000 && unit < units.length - 1) {
+    size /= 1000;
+    unit++;
+  }
+  return unit === 0 ? `${size} B` : `${size.toFixed(1)} ${units[unit]}`;
+}
@@ -11,6 +11,7 @@ import { readDir, remove, exists } from "@tauri-apps/plugin-fs";
 import { dirname, join } from "@tauri-apps/api/path";
 import { confirm, message } from "@tauri-apps/plugin-dialog";
 import { ASSETS_FOLDER, IMAGE_EXTENSIONS } from "./imageUtils";
+import { workspaceAssetsDir } from "./workspaceEditorSettings";
 
 export interface OrphanedImage {
   filename: string;
@@ -77,11 +78,16 @@ export function extractImageReferences(content: string): Set<string> {
 /**
  * Find orphaned images in the assets folder.
  * Returns images that exist in the folder but aren't referenced in the document.
+ * A workspace assets folder is shared by many documents, so it's never scanned.
  */
 export async function findOrphanedImages(
   documentPath: string,
   documentContent: string
 ): Promise<OrphanCleanupResult> {
+  if (workspaceAssetsDir(documentPath) !== null) {
+    return { orphanedImages: [], referencedCount: 0, totalInFolder: 0 };
+  }
+
   const docDir = await dirname(documentPath);
   const assetsPath = await join(docDir, ASSETS_FOLDER);
 
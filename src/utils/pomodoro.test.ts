@@ -0,0 +1,17 @@
+import { describe, it, expect } from "vitest";
+import { formatFocusTime, formatRemaining } from "./pomodoro";
+
+describe("formatRemaining", () => {
+  it("rounds up to whole seconds", () => {
+    expect(formatRemaining(1_445_200)).toBe("24:06");
+    expect(formatRemaining(0)).toBe("0:00");
+  });
+});
+
+describe("formatFocusTime", () => {
+  it("formats minutes and hours", () => {
+    expect(formatFocusTime(2400)).toBe("40m");
+    expect(formatFocusTime(5100)).toBe("1h 25m");
+    expect(formatFocusTime(7200)).toBe("2h");
+  });
+});
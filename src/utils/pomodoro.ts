@@ -0,0 +1,90 @@
+/**
+ * Focus timer (pomodoro), run by the backend so it survives webview
+ * reloads. The backend emits `pomodoro:tick` every second while running and
+ * `pomodoro:phase-changed` when a phase ends.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export interface PomodoroConfig {
+  workMinutes: number;
+  shortBreakMinutes: number;
+  longBreakMinutes: number;
+  /** Work sessions before a long break */
+  sessionsBeforeLongBreak: number;
+  /** Start the next phase automatically instead of waiting paused */
+  autoContinue: boolean;
+  notifications: boolean;
+}
+
+export type PomodoroPhase = "work" | "shortBreak" | "longBreak";
+
+export interface PomodoroState {
+  status: "idle" | "running" | "paused";
+  phase: PomodoroPhase;
+  remainingMs: number;
+  durationMs: number;
+  completedSessions: number;
+}
+
+export interface DailyStats {
+  /** `YYYY-MM-DD` */
+  date: string;
+  focusSeconds: number;
+  sessions: number;
+}
+
+export function getPomodoroConfig(): Promise<PomodoroConfig> {
+  return invoke<PomodoroConfig>("get_pomodoro_config");
+}
+
+export function setPomodoroConfig(config: PomodoroConfig): Promise<void> {
+  return invoke("set_pomodoro_config", { config });
+}
+
+/** Start a new cycle, or resume a paused one. */
+export function startPomodoro(): Promise<PomodoroState> {
+  return invoke<PomodoroState>("pomodoro_start");
+}
+
+export function pausePomodoro(): Promise<PomodoroState> {
+  return invoke<PomodoroState>("pomodoro_pause");
+}
+
+export function stopPomodoro(): Promise<PomodoroState> {
+  return invoke<PomodoroState>("pomodoro_stop");
+}
+
+export function getPomodoroState(): Promise<PomodoroState> {
+  return invoke<PomodoroState>("get_pomodoro_state");
+}
+
+/** Every state change: ticks while running, phase changes, start/pause/stop. */
+export async function onPomodoroState(handler: (state: PomodoroState) => void): Promise<UnlistenFn> {
+  const unlistenTick = await listen<PomodoroState>("pomodoro:tick", (event) => handler(event.payload));
+  const unlistenPhase = await listen<PomodoroState>("pomodoro:phase-changed", (event) => handler(event.payload));
+  return () => {
+    unlistenTick();
+    unlistenPhase();
+  };
+}
+
+/** Focus time for the last `days` days, oldest first. */
+export function getPomodoroStats(days = 7): Promise<DailyStats[]> {
+  return invoke<DailyStats[]>("get_pomodoro_stats", { days });
+}
+
+/** `"24:05"` countdown for the status bar. */
+export function formatRemaining(ms: number): string {
+  const total = Math.ceil(ms / 1000);
+  const minutes = Math.floor(total / 60);
+  return `${minutes}:${String(total % 60).padStart(2, "0")}`;
+}
+
+/** `"1h 25m"` / `"40m"` for focus time totals. */
+export function formatFocusTime(seconds: number): string {
+  const minutes = Math.round(seconds / 60);
+  const hours = Math.floor(minutes / 60);
+  if (hours === 0) return `${minutes}m`;
+  return minutes % 60 === 0 ? `${hours}h` : `${hours}h ${minutes % 60}m`;
+}
@@ -0,0 +1,14 @@
+import { describe, it, expect } from "vitest";
+import { getPublishedUrl } from "./publish";
+
+describe("getPublishedUrl", () => {
+  it("reads the url recorded for a target", () => {
+    const doc = '---\ntitle: Post\npublish_gist_url: "https://gist.github.com/u/1"\n---\n# Post\n';
+    expect(getPublishedUrl(doc, "gist")).toBe("https://gist.github.com/u/1");
+    expect(getPublishedUrl(doc, "webhook")).toBeNull();
+  });
+
+  it("ignores documents without frontmatter", () => {
+    expect(getPublishedUrl("publish_gist_url: x\n", "gist")).toBeNull();
+  });
+});
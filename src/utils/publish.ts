@@ -0,0 +1,68 @@
+/**
+ * Publishing (GitHub Gist or a webhook), done by the backend.
+ *
+ * Publish metadata (`publish_<target>_id/_url/_updated`) lives in the
+ * document frontmatter; every call returns the document content with that
+ * metadata updated, which the caller writes back into the editor.
+ * Tokens (and the webhook URL, kept with its token) are stored in the OS
+ * keychain and are write-only from here.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export type PublishTarget = "gist" | "webhook";
+
+export interface PublishRequest {
+  target: PublishTarget;
+  /** Full document markdown, frontmatter included */
+  content: string;
+  html?: string;
+  fileName?: string;
+  title?: string;
+  /** Public rather than secret gist */
+  public?: boolean;
+}
+
+export interface PublishOutcome {
+  content: string;
+  id: string | null;
+  url: string | null;
+}
+
+export function setPublishToken(target: PublishTarget, token: string): Promise<void> {
+  return invoke("set_publish_token", { target, token });
+}
+
+/** Store the webhook endpoint (https only) with its optional bearer token. */
+export function setPublishWebhook(url: string, token: string | null): Promise<void> {
+  return invoke("set_publish_webhook", { url, token });
+}
+
+export function deletePublishToken(target: PublishTarget): Promise<void> {
+  return invoke("delete_publish_token", { target });
+}
+
+export function hasPublishToken(target: PublishTarget): Promise<boolean> {
+  return invoke<boolean>("has_publish_token", { target });
+}
+
+/** Publish, or update the existing post when the document was published before. */
+export function publishDocument(request: PublishRequest): Promise<PublishOutcome> {
+  return invoke<PublishOutcome>("publish_document", { request });
+}
+
+export function updatePublishedDocument(request: PublishRequest): Promise<PublishOutcome> {
+  return invoke<PublishOutcome>("update_published_document", { request });
+}
+
+export function unpublishDocument(request: PublishRequest): Promise<PublishOutcome> {
+  return invoke<PublishOutcome>("unpublish_document", { request });
+}
+
+/** Published URL recorded in the frontmatter for `target`, if any. */
+export function getPublishedUrl(content: string, target: PublishTarget): string | null {
+  const match = /^---\r?\n([\s\S]*?)\r?\n(?:---|\.\.\.)\r?\n/.exec(content);
+  if (!match) return null;
+  const line = new RegExp(`^publish_${target}_url:\\s*(.+)$`, "m").exec(match[1]);
+  if (!line) return null;
+  return line[1].trim().replace(/^"(.*)"$/, "$1").replace(/^'(.*)'$/, "$1") || null;
+}
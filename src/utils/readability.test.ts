@@ -0,0 +1,26 @@
+import { describe, it, expect } from "vitest";
+import { heatmapColor, readingEaseLabel } from "./readability";
+
+describe("readingEaseLabel", () => {
+  it("bands scores", () => {
+    expect(readingEaseLabel(95)).toBe("Easy");
+    expect(readingEaseLabel(65)).toBe("Plain");
+    expect(readingEaseLabel(45)).toBe("Fairly difficult");
+    expect(readingEaseLabel(25)).toBe("Difficult");
+    expect(readingEaseLabel(-10)).toBe("Very difficult");
+  });
+});
+
+describe("heatmapColor", () => {
+  it("leaves easy paragraphs unshaded", () => {
+    expect(heatmapColor(0)).toBe("transparent");
+    expect(heatmapColor(0.1)).toBe("transparent");
+  });
+
+  it("shades harder paragraphs more", () => {
+    expect(heatmapColor(0.5)).toBe(
+      "color-mix(in srgb, var(--warning-color, #f59e0b) 18%, transparent)"
+    );
+    expect(heatmapColor(2)).toBe("color-mix(in srgb, var(--warning-color, #f59e0b) 35%, transparent)");
+  });
+});
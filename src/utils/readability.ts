@@ -0,0 +1,64 @@
+/**
+ * Readability
+ *
+ * Purpose: Score a Markdown document's readability in the backend
+ * (Flesch reading ease, Flesch–Kincaid grade, sentence lengths, passive
+ * voice, long words), overall and per paragraph, for the editor's
+ * readability heatmap.
+ *
+ * @module utils/readability
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ParagraphReadability {
+  /** 1-based first and last line in the document */
+  startLine: number;
+  endLine: number;
+  words: number;
+  sentences: number;
+  averageSentenceLength: number;
+  fleschReadingEase: number;
+  fleschKincaidGrade: number;
+  passiveSentences: number;
+  longWords: number;
+  /** 0 (easy) – 1 (hard) */
+  difficulty: number;
+}
+
+export interface ReadabilityReport {
+  words: number;
+  sentences: number;
+  averageSentenceLength: number;
+  longestSentence: number;
+  fleschReadingEase: number;
+  fleschKincaidGrade: number;
+  passiveSentences: number;
+  longWords: number;
+  sentenceLengths: { label: string; count: number }[];
+  paragraphs: ParagraphReadability[];
+}
+
+export function analyzeReadability(text: string): Promise<ReadabilityReport> {
+  return invoke<ReadabilityReport>("analyze_readability", { text });
+}
+
+/** Plain-language band for a Flesch reading ease score. */
+export function readingEaseLabel(score: number): string {
+  if (score >= 80) return "Easy";
+  if (score >= 60) return "Plain";
+  if (score >= 40) return "Fairly difficult";
+  if (score >= 20) return "Difficult";
+  return "Very difficult";
+}
+
+/**
+ * Heatmap background for a paragraph: transparent when easy, increasingly
+ * opaque warning color as it gets harder.
+ */
+export function heatmapColor(difficulty: number): string {
+  const percent = Math.round(Math.max(0, Math.min(1, difficulty)) * 35);
+  return percent < 5
+    ? "transparent"
+    : `color-mix(in srgb, var(--warning-color, #f59e0b) ${percent}%, transparent)`;
+}
@@ -6,9 +6,9 @@
  * - MCP bridge workspaceHandlers (workspace.reloadDocument)
  */
 
-import { readTextFile } from "@tauri-apps/plugin-fs";
 import { useDocumentStore } from "@/stores/documentStore";
 import { detectLinebreaks } from "@/utils/linebreakDetection";
+import { readDocumentFile } from "@/utils/encryptedDocuments";
 
 /**
  * Reload a tab's document content from disk.
@@ -16,10 +16,10 @@ import { detectLinebreaks } from "@/utils/linebreakDetection";
  * Reads the file, detects linebreak style, updates the document store,
  * and clears any "missing" flag.
  *
- * @throws If readTextFile fails (e.g. file deleted)
+ * @throws If reading fails (e.g. file deleted)
  */
 export async function reloadTabFromDisk(tabId: string, filePath: string): Promise<void> {
-  const content = await readTextFile(filePath);
+  const content = await readDocumentFile(filePath);
   const docStore = useDocumentStore.getState();
   docStore.loadContent(tabId, content, filePath, detectLinebreaks(content));
   docStore.clearMissing(tabId);
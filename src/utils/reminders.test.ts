@@ -0,0 +1,8 @@
+import { describe, it, expect } from "vitest";
+import { formatRemindMarker } from "./reminders";
+
+describe("formatRemindMarker", () => {
+  it("formats local date and time with padding", () => {
+    expect(formatRemindMarker(new Date(2026, 4, 1, 9, 5))).toBe("@remind(2026-05-01 09:05)");
+  });
+});
@@ -0,0 +1,74 @@
+/**
+ * System notifications and `@remind(...)` reminders, run by the backend.
+ *
+ * Action presses arrive as `notification:action`. On platforms whose
+ * notifications can't carry buttons (macOS, Windows) the backend also emits
+ * `notification:shown` so the actions can be offered in-app. Due reminders
+ * are emitted as `reminders:fired`.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface NotificationAction {
+  id: string;
+  label: string;
+}
+
+export interface NotificationRequest {
+  id?: string;
+  title: string;
+  body?: string;
+  actions?: NotificationAction[];
+  /** Note the notification is about (for "Open note") */
+  notePath?: string;
+}
+
+export interface NotificationActionEvent {
+  notificationId: string;
+  /** Action id, or `default` when the notification itself was clicked */
+  actionId: string;
+  notePath: string | null;
+}
+
+export interface Reminder {
+  id: string;
+  notePath: string;
+  text: string;
+  /** Unix timestamp ms */
+  dueAt: number;
+  /** 1-based line of the `@remind` marker */
+  line: number;
+  snoozed: boolean;
+}
+
+/** Show a notification now; resolves to its id. */
+export function showNotification(request: NotificationRequest): Promise<string> {
+  return invoke<string>("show_notification", { request });
+}
+
+/** Update a note's reminders from its content (after saving). */
+export function scanNoteReminders(path: string, content: string): Promise<Reminder[]> {
+  return invoke<Reminder[]>("scan_note_reminders", { path, content });
+}
+
+export function clearNoteReminders(path: string): Promise<void> {
+  return invoke("clear_note_reminders", { path });
+}
+
+export function listReminders(): Promise<Reminder[]> {
+  return invoke<Reminder[]>("list_reminders");
+}
+
+export function snoozeReminder(id: string, minutes?: number): Promise<void> {
+  return invoke("snooze_reminder", { id, minutes });
+}
+
+export function dismissReminder(id: string): Promise<void> {
+  return invoke("dismiss_reminder", { id });
+}
+
+/** `@remind(2026-05-01 09:30)` marker for a local date and time. */
+export function formatRemindMarker(date: Date): string {
+  const pad = (n: number) => String(n).padStart(2, "0");
+  const day = `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+  return `@remind(${day} ${pad(date.getHours())}:${pad(date.getMinutes())})`;
+}
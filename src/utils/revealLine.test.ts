@@ -0,0 +1,31 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { useDocumentStore } from "@/stores/documentStore";
+import { lineCursorInfo, revealLine, takePendingReveal } from "./revealLine";
+
+describe("revealLine", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("builds a cursor at the start of the line", () => {
+    expect(lineCursorInfo(42)).toMatchObject({ sourceLine: 42, percentInLine: 0 });
+    expect(lineCursorInfo(0).sourceLine).toBe(1);
+  });
+
+  it("takes the pending line for a path", async () => {
+    vi.mocked(invoke).mockResolvedValue(7);
+    await expect(takePendingReveal("/notes/a.md")).resolves.toBe(7);
+    expect(invoke).toHaveBeenCalledWith("take_pending_reveal", { path: "/notes/a.md" });
+  });
+
+  it("saves the line as the tab's cursor", () => {
+    const { initDocument, getDocument } = useDocumentStore.getState();
+    initDocument("reveal-tab", "# A\n\nText", "/notes/a.md");
+    revealLine("reveal-tab", 3);
+    expect(getDocument("reveal-tab")?.cursorInfo?.sourceLine).toBe(3);
+  });
+});
@@ -0,0 +1,53 @@
+/**
+ * Reveal Line
+ *
+ * Purpose: Move a document's cursor to a source line and scroll it into
+ * view, for `vmark://open?file=...&line=...` links. The line also becomes
+ * the tab's saved cursor, so editors that mount later start there.
+ *
+ * @module utils/revealLine
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type { CursorInfo } from "@/types/cursorSync";
+import { useActiveEditorStore } from "@/stores/activeEditorStore";
+import { useDocumentStore } from "@/stores/documentStore";
+import { useEditorStore } from "@/stores/editorStore";
+import { restoreCursorInCodeMirror } from "@/utils/cursorSync/codemirror";
+import { restoreCursorInTiptap } from "@/utils/cursorSync/tiptap";
+import { getTiptapEditorView } from "@/utils/tiptapView";
+
+/** Emitted by the backend when a link is waiting to reveal a line */
+export const REVEAL_PENDING_EVENT = "deep-link:reveal-pending";
+
+/** Cursor at the start of a 1-based source line */
+export function lineCursorInfo(line: number): CursorInfo {
+  return {
+    sourceLine: Math.max(1, Math.floor(line)),
+    wordAtCursor: "",
+    offsetInWord: 0,
+    nodeType: "paragraph",
+    percentInLine: 0,
+    contextBefore: "",
+    contextAfter: "",
+  };
+}
+
+/** The line a link asked for in `path`, taken so it's revealed only once. */
+export function takePendingReveal(path: string): Promise<number | null> {
+  return invoke<number | null>("take_pending_reveal", { path });
+}
+
+/** Move the cursor of `tabId` (the window's active tab) to `line`. */
+export function revealLine(tabId: string, line: number): void {
+  const info = lineCursorInfo(line);
+  useDocumentStore.getState().setCursorInfo(tabId, info);
+
+  const { activeSourceView, activeWysiwygEditor } = useActiveEditorStore.getState();
+  if (useEditorStore.getState().sourceMode) {
+    if (activeSourceView) restoreCursorInCodeMirror(activeSourceView, info);
+    return;
+  }
+  const view = getTiptapEditorView(activeWysiwygEditor);
+  if (view) restoreCursorInTiptap(view, info);
+}
@@ -0,0 +1,49 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { saveFileSafe, isDeletedConflict, mergeWithDisk } from "./safeSave";
+
+describe("saveFileSafe", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("passes the expected digest to the backend", async () => {
+    vi.mocked(invoke).mockResolvedValue({ status: "saved", digest: "new" });
+    const result = await saveFileSafe("/a.md", "text", "old");
+    expect(invoke).toHaveBeenCalledWith("save_file_safe", {
+      path: "/a.md",
+      content: "text",
+      expectedDigest: "old",
+    });
+    expect(result).toEqual({ status: "saved", digest: "new" });
+  });
+
+  it("reports deleted-file conflicts", () => {
+    expect(isDeletedConflict({ status: "conflict", diskContent: null, diskDigest: null })).toBe(true);
+    expect(isDeletedConflict({ status: "conflict", diskContent: "x", diskDigest: "d" })).toBe(false);
+    expect(isDeletedConflict({ status: "saved", digest: "d" })).toBe(false);
+  });
+});
+
+describe("mergeWithDisk", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("merges against the disk content", async () => {
+    vi.mocked(invoke).mockResolvedValue({ content: "m", clean: true, conflicts: [] });
+    await mergeWithDisk("base", "mine", { status: "conflict", diskContent: "disk", diskDigest: "d" });
+    expect(invoke).toHaveBeenCalledWith("merge_documents", {
+      base: "base",
+      mine: "mine",
+      theirs: "disk",
+      options: { mineLabel: "Editor", theirsLabel: "On disk" },
+    });
+  });
+
+  it("skips deleted files", async () => {
+    const result = await mergeWithDisk("b", "m", { status: "conflict", diskContent: null, diskDigest: null });
+    expect(result).toBeNull();
+    expect(invoke).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,101 @@
+/**
+ * Backend document writes.
+ *
+ * `write_document` applies the save semantics (line endings, trailing
+ * newline) and writes atomically. `save_file_safe` only writes when the file
+ * on disk still matches the digest the editor last read; otherwise it returns
+ * the current disk content so the caller can offer a merge instead of
+ * silently overwriting external edits. `mergeWithDisk` three-way merges the
+ * editor's text with that disk content against the last saved text.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export type SafeSaveResult =
+  | { status: "saved"; digest: string }
+  | { status: "conflict"; diskContent: string | null; diskDigest: string | null };
+
+/**
+ * Write `content` if the file still has `expectedDigest`. Pass null for new
+ * files or an overwrite the user already confirmed.
+ */
+export function saveFileSafe(
+  path: string,
+  content: string,
+  expectedDigest: string | null
+): Promise<SafeSaveResult> {
+  return invoke<SafeSaveResult>("save_file_safe", { path, content, expectedDigest });
+}
+
+/** SHA-256 (hex) of a file on disk, matching watcher event digests. */
+export function computeFileDigest(path: string): Promise<string> {
+  return invoke<string>("compute_file_digest", { path });
+}
+
+/** True when the file was deleted externally (nothing to merge with). */
+export function isDeletedConflict(result: SafeSaveResult): boolean {
+  return result.status === "conflict" && result.diskContent === null;
+}
+
+export interface MergeConflict {
+  /** 1-based lines of the `<<<<<<<` and `>>>>>>>` markers */
+  startLine: number;
+  endLine: number;
+  base: string;
+  mine: string;
+  theirs: string;
+}
+
+export interface MergeResult {
+  content: string;
+  clean: boolean;
+  conflicts: MergeConflict[];
+}
+
+export interface MergeOptions {
+  mineLabel?: string;
+  theirsLabel?: string;
+  /** Include the base text in conflicts (diff3 style) */
+  showBase?: boolean;
+}
+
+/**
+ * Merge the editor's text (`mine`) with the disk content from a safe-save
+ * conflict, using the last saved text as the common base. Returns null for
+ * deleted files, where there is nothing to merge with.
+ */
+export async function mergeWithDisk(
+  base: string,
+  mine: string,
+  conflict: SafeSaveResult,
+  options: MergeOptions = { mineLabel: "Editor", theirsLabel: "On disk" }
+): Promise<MergeResult | null> {
+  if (conflict.status !== "conflict" || conflict.diskContent === null) return null;
+  return invoke<MergeResult>("merge_documents", { base, mine, theirs: conflict.diskContent, options });
+}
+
+export interface WrittenDocument {
+  /** The text as written (line endings and trailing newline applied) */
+  content: string;
+  /** Digest of the written file, for the next `saveFileSafe` */
+  digest: string;
+}
+
+/**
+ * Write a document with line-ending normalization, an optional trailing
+ * whitespace trim and a guaranteed final newline, atomically.
+ */
+export function writeDocument(
+  path: string,
+  content: string,
+  lineEnding: "lf" | "crlf" | "preserve",
+  finalNewline: boolean,
+  trimTrailingWhitespace = false
+): Promise<WrittenDocument> {
+  return invoke<WrittenDocument>("write_document", {
+    path,
+    content,
+    lineEnding,
+    finalNewline,
+    trimTrailingWhitespace,
+  });
+}
@@ -0,0 +1,56 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@/stores/settingsStore", () => ({
+  useSettingsStore: { getState: vi.fn() },
+}));
+vi.mock("@/utils/activeProvider", () => ({ activeProviderConfig: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { activeProviderConfig } from "@/utils/activeProvider";
+import { runSaveHooks } from "./saveHooks";
+
+const provider = { provider: "openai", model: "gpt-4o", apiKey: "sk", endpoint: null, cliPath: null };
+
+function withSaveHooks(enabled: boolean) {
+  vi.mocked(useSettingsStore.getState).mockReturnValue({
+    advanced: {
+      saveHooks: {
+        enabled,
+        hooks: [{ genie: "metadata/summary.md", field: "summary", list: false }],
+        cooldownSecs: 600,
+        maxRunsPerHour: 20,
+      },
+    },
+  } as unknown as ReturnType<typeof useSettingsStore.getState>);
+}
+
+describe("runSaveHooks", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(invoke).mockResolvedValue(1);
+    vi.mocked(activeProviderConfig).mockReturnValue(provider);
+  });
+
+  it("queues hooks with the active provider", async () => {
+    withSaveHooks(true);
+    expect(await runSaveHooks("/ws/note.md")).toBe(1);
+    expect(invoke).toHaveBeenCalledWith("run_save_hooks", { path: "/ws/note.md", provider });
+  });
+
+  it("does nothing when disabled or without a provider", async () => {
+    withSaveHooks(false);
+    expect(await runSaveHooks("/ws/note.md")).toBe(0);
+    withSaveHooks(true);
+    vi.mocked(activeProviderConfig).mockReturnValue(null);
+    expect(await runSaveHooks("/ws/note.md")).toBe(0);
+    expect(invoke).not.toHaveBeenCalled();
+  });
+
+  it("swallows backend errors", async () => {
+    withSaveHooks(true);
+    vi.mocked(invoke).mockRejectedValue("boom");
+    expect(await runSaveHooks("/ws/note.md")).toBe(0);
+  });
+});
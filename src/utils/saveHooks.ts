@@ -0,0 +1,36 @@
+/**
+ * Save Hooks
+ *
+ * Purpose: After a note is saved, ask the backend to run the configured
+ * save-hook genies (Settings → Advanced), which fill in missing frontmatter
+ * such as `summary` and `keywords` in the background. The backend applies
+ * the per-note cooldown and hourly cap; this only skips the call when
+ * hooks are off or no AI provider is selected. The window that saved gets
+ * `save-hooks:applied` once fields are written.
+ *
+ * @module utils/saveHooks
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { activeProviderConfig } from "@/utils/activeProvider";
+
+export interface SaveHooksApplied {
+  path: string;
+  /** Frontmatter fields that were filled in */
+  fields: string[];
+}
+
+/** Queue save hooks for `path`; resolves to the number of genies queued. */
+export async function runSaveHooks(path: string): Promise<number> {
+  const saveHooks = useSettingsStore.getState().advanced?.saveHooks;
+  if (!saveHooks?.enabled || saveHooks.hooks.length === 0) return 0;
+  const provider = activeProviderConfig();
+  if (!provider) return 0;
+  try {
+    return await invoke<number>("run_save_hooks", { path, provider });
+  } catch (error) {
+    console.warn("[SaveHooks] Failed to queue hooks:", error);
+    return 0;
+  }
+}
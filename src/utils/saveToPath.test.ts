@@ -6,8 +6,8 @@
 import { describe, it, expect, vi, beforeEach, afterEach } from "vitest";
 import { saveToPath } from "./saveToPath";
 
-vi.mock("@tauri-apps/plugin-fs", () => ({
-  writeTextFile: vi.fn(),
+vi.mock("@/utils/documentFiles", () => ({
+  saveTextDocument: vi.fn(),
 }));
 
 vi.mock("@/hooks/useHistoryOperations", () => ({
@@ -43,18 +43,40 @@ vi.mock("@/utils/pendingSaves", () => ({
   clearPendingSave: vi.fn(),
 }));
 
-import { writeTextFile } from "@tauri-apps/plugin-fs";
+vi.mock("@/utils/saveHooks", () => ({
+  runSaveHooks: vi.fn(),
+}));
+
+vi.mock("@/utils/reminders", () => ({
+  scanNoteReminders: vi.fn().mockResolvedValue([]),
+}));
+
+vi.mock("@/utils/writingGoals", () => ({
+  recordDocumentSave: vi.fn().mockResolvedValue({ todayWords: 0, projects: [], recentDays: [] }),
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { message } from "@tauri-apps/plugin-dialog";
+import { saveTextDocument } from "@/utils/documentFiles";
 import { createSnapshot } from "@/hooks/useHistoryOperations";
 import { useDocumentStore } from "@/stores/documentStore";
 import { useTabStore } from "@/stores/tabStore";
 import { useRecentFilesStore } from "@/stores/recentFilesStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { registerPendingSave, clearPendingSave } from "@/utils/pendingSaves";
+import { runSaveHooks } from "@/utils/saveHooks";
+import { scanNoteReminders } from "@/utils/reminders";
+import { recordDocumentSave } from "@/utils/writingGoals";
+
+const saved = { status: "saved", digest: "d1" } as const;
 
 describe("saveToPath", () => {
   const mockSetFilePath = vi.fn();
   const mockMarkSaved = vi.fn();
   const mockMarkAutoSaved = vi.fn();
+  const mockMarkDivergent = vi.fn();
+  const mockLoadContent = vi.fn();
+  const mockSetContent = vi.fn();
   const mockSetLineMetadata = vi.fn();
   const mockUpdateTabPath = vi.fn();
   const mockAddFile = vi.fn();
@@ -67,6 +89,9 @@ describe("saveToPath", () => {
       setFilePath: mockSetFilePath,
       markSaved: mockMarkSaved,
       markAutoSaved: mockMarkAutoSaved,
+      markDivergent: mockMarkDivergent,
+      loadContent: mockLoadContent,
+      setContent: mockSetContent,
       setLineMetadata: mockSetLineMetadata,
       getDocument: mockGetDocument,
     } as unknown as ReturnType<typeof useDocumentStore.getState>);
@@ -95,12 +120,12 @@ describe("saveToPath", () => {
   });
 
   it("writes content and updates stores on success", async () => {
-    vi.mocked(writeTextFile).mockResolvedValue(undefined);
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
     const result = await saveToPath("tab-1", "/tmp/doc.md", "Hello", "manual");
 
     expect(result).toBe(true);
-    expect(writeTextFile).toHaveBeenCalledWith("/tmp/doc.md", "Hello");
+    expect(saveTextDocument).toHaveBeenCalledWith("/tmp/doc.md", "Hello");
     expect(mockSetFilePath).toHaveBeenCalledWith("tab-1", "/tmp/doc.md");
     expect(mockMarkSaved).toHaveBeenCalledWith("tab-1", "Hello");
     expect(mockUpdateTabPath).toHaveBeenCalledWith("tab-1", "/tmp/doc.md");
@@ -112,13 +137,13 @@ describe("saveToPath", () => {
   });
 
   it("normalizes line endings based on settings", async () => {
-    vi.mocked(writeTextFile).mockResolvedValue(undefined);
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
     mockGetDocument.mockReturnValue({ lineEnding: "crlf" });
 
     const result = await saveToPath("tab-1", "/tmp/doc.md", "a\nb\n", "manual");
 
     expect(result).toBe(true);
-    expect(writeTextFile).toHaveBeenCalledWith("/tmp/doc.md", "a\r\nb\r\n");
+    expect(saveTextDocument).toHaveBeenCalledWith("/tmp/doc.md", "a\r\nb\r\n");
     expect(mockSetLineMetadata).toHaveBeenCalledWith("tab-1", {
       lineEnding: "crlf",
       hardBreakStyle: "twoSpaces", // Default for unknown docs (wider compatibility)
@@ -126,7 +151,7 @@ describe("saveToPath", () => {
   });
 
   it("normalizes hard breaks based on settings", async () => {
-    vi.mocked(writeTextFile).mockResolvedValue(undefined);
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
     mockGetDocument.mockReturnValue({ lineEnding: "lf", hardBreakStyle: "backslash" });
     vi.mocked(useSettingsStore.getState).mockReturnValue({
       general: {
@@ -143,7 +168,7 @@ describe("saveToPath", () => {
     const result = await saveToPath("tab-1", "/tmp/doc.md", "a\\\nb\n", "manual");
 
     expect(result).toBe(true);
-    expect(writeTextFile).toHaveBeenCalledWith("/tmp/doc.md", "a  \nb\n");
+    expect(saveTextDocument).toHaveBeenCalledWith("/tmp/doc.md", "a  \nb\n");
     expect(mockSetLineMetadata).toHaveBeenCalledWith("tab-1", {
       lineEnding: "lf",
       hardBreakStyle: "twoSpaces",
@@ -151,7 +176,7 @@ describe("saveToPath", () => {
   });
 
   it("skips history snapshot when disabled", async () => {
-    vi.mocked(writeTextFile).mockResolvedValue(undefined);
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
     vi.mocked(useSettingsStore.getState).mockReturnValue({
       general: {
         historyEnabled: false,
@@ -170,8 +195,35 @@ describe("saveToPath", () => {
     expect(createSnapshot).not.toHaveBeenCalled();
   });
 
+  it("runs save hooks after a successful save", async () => {
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
+
+    await saveToPath("tab-1", "/tmp/doc.md", "Hello", "auto");
+
+    expect(runSaveHooks).toHaveBeenCalledWith("/tmp/doc.md");
+  });
+
+  it("scans reminders in the saved content", async () => {
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
+
+    await saveToPath("tab-1", "/tmp/doc.md", "Call @remind(2026-05-01 09:30)", "manual");
+
+    expect(scanNoteReminders).toHaveBeenCalledWith("/tmp/doc.md", "Call @remind(2026-05-01 09:30)");
+  });
+
+  it("records the save toward writing goals", async () => {
+    vi.mocked(saveTextDocument).mockResolvedValue(saved);
+
+    await saveToPath("tab-1", "/tmp/doc.md", "Hello", "auto");
+    expect(recordDocumentSave).toHaveBeenCalledWith("/tmp/doc.md", "Hello", true);
+
+    mockGetDocument.mockReturnValue({ lineEnding: "unknown", filePath: "/tmp/doc.md" });
+    await saveToPath("tab-1", "/tmp/doc.md", "Hello again", "auto");
+    expect(recordDocumentSave).toHaveBeenLastCalledWith("/tmp/doc.md", "Hello again", false);
+  });
+
   it("returns false and skips updates when write fails", async () => {
-    vi.mocked(writeTextFile).mockRejectedValue(new Error("disk error"));
+    vi.mocked(saveTextDocument).mockRejectedValue(new Error("disk error"));
     const consoleError = vi.spyOn(console, "error").mockImplementation(() => {});
 
     const result = await saveToPath("tab-3", "/tmp/fail.md", "fail", "manual");
@@ -182,12 +234,111 @@ describe("saveToPath", () => {
     expect(mockUpdateTabPath).not.toHaveBeenCalled();
     expect(mockAddFile).not.toHaveBeenCalled();
     expect(createSnapshot).not.toHaveBeenCalled();
+    expect(runSaveHooks).not.toHaveBeenCalled();
+    expect(scanNoteReminders).not.toHaveBeenCalled();
+    expect(recordDocumentSave).not.toHaveBeenCalled();
     consoleError.mockRestore();
   });
 
+  describe("files changed on disk", () => {
+    const conflict = { status: "conflict", diskContent: "Theirs\nB\n", diskDigest: "d2" } as const;
+    const merge = (content: string, conflicts: unknown[] = []) =>
+      vi.mocked(invoke).mockResolvedValue({ content, clean: conflicts.length === 0, conflicts });
+
+    beforeEach(() => {
+      mockGetDocument.mockReturnValue({ lineEnding: "lf", lastDiskContent: "A\nB\n" });
+    });
+
+    it("leaves the file alone on auto-save and marks the document divergent", async () => {
+      vi.mocked(saveTextDocument).mockResolvedValue(conflict);
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "A\nMine\n", "auto");
+
+      expect(result).toBe(false);
+      expect(saveTextDocument).toHaveBeenCalledTimes(1);
+      expect(invoke).not.toHaveBeenCalled();
+      expect(mockMarkDivergent).toHaveBeenCalledWith("tab-1");
+      expect(mockMarkAutoSaved).not.toHaveBeenCalled();
+      expect(clearPendingSave).toHaveBeenCalledWith("/tmp/doc.md");
+    });
+
+    it("saves a clean merge against the disk digest and shows it", async () => {
+      vi.mocked(saveTextDocument).mockResolvedValueOnce(conflict).mockResolvedValueOnce(saved);
+      merge("Theirs\nMine\n");
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "A\nMine\n", "manual");
+
+      expect(result).toBe(true);
+      expect(invoke).toHaveBeenCalledWith("merge_documents", {
+        base: "A\nB\n",
+        mine: "A\nMine\n",
+        theirs: "Theirs\nB\n",
+        options: { mineLabel: "Editor", theirsLabel: "On disk" },
+      });
+      expect(saveTextDocument).toHaveBeenLastCalledWith("/tmp/doc.md", "Theirs\nMine\n", "d2");
+      expect(mockLoadContent).toHaveBeenCalledWith("tab-1", "Theirs\nMine\n", "/tmp/doc.md", expect.anything());
+      expect(mockMarkSaved).toHaveBeenCalledWith("tab-1", "Theirs\nMine\n");
+      expect(message).not.toHaveBeenCalled();
+    });
+
+    it("shows overlapping changes with conflict markers, unsaved", async () => {
+      vi.mocked(saveTextDocument).mockResolvedValue(conflict);
+      merge("<<<<<<< Editor\nMine\n=======\nTheirs\n>>>>>>> On disk\n", [{ startLine: 1, endLine: 5 }]);
+      vi.mocked(message).mockResolvedValue("Review Conflicts");
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "Mine\nB\n", "manual");
+
+      expect(result).toBe(false);
+      expect(saveTextDocument).toHaveBeenCalledTimes(1);
+      expect(mockLoadContent).toHaveBeenCalledWith("tab-1", "Theirs\nB\n", "/tmp/doc.md", expect.anything());
+      expect(mockSetContent).toHaveBeenCalledWith(
+        "tab-1",
+        "<<<<<<< Editor\nMine\n=======\nTheirs\n>>>>>>> On disk\n"
+      );
+      expect(mockMarkSaved).not.toHaveBeenCalled();
+    });
+
+    it("overwrites overlapping changes when the user chooses to", async () => {
+      vi.mocked(saveTextDocument).mockResolvedValueOnce(conflict).mockResolvedValueOnce(saved);
+      merge("conflicted", [{ startLine: 1, endLine: 5 }]);
+      vi.mocked(message).mockResolvedValue("Overwrite");
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "Mine\nB\n", "manual");
+
+      expect(result).toBe(true);
+      expect(saveTextDocument).toHaveBeenLastCalledWith("/tmp/doc.md", "Mine\nB\n", null);
+      expect(mockMarkSaved).toHaveBeenCalledWith("tab-1", "Mine\nB\n");
+    });
+
+    it("keeps the disk version when the user cancels", async () => {
+      vi.mocked(saveTextDocument).mockResolvedValue(conflict);
+      merge("conflicted", [{ startLine: 1, endLine: 5 }]);
+      vi.mocked(message).mockResolvedValue("Cancel");
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "Mine\nB\n", "manual");
+
+      expect(result).toBe(false);
+      expect(saveTextDocument).toHaveBeenCalledTimes(1);
+      expect(mockSetContent).not.toHaveBeenCalled();
+      expect(mockMarkSaved).not.toHaveBeenCalled();
+    });
+
+    it("recreates files deleted since they were read", async () => {
+      vi.mocked(saveTextDocument)
+        .mockResolvedValueOnce({ status: "conflict", diskContent: null, diskDigest: null })
+        .mockResolvedValueOnce(saved);
+
+      const result = await saveToPath("tab-1", "/tmp/doc.md", "Mine", "auto");
+
+      expect(result).toBe(true);
+      expect(invoke).not.toHaveBeenCalled();
+      expect(saveTextDocument).toHaveBeenLastCalledWith("/tmp/doc.md", "Mine", null);
+    });
+  });
+
   describe("saveType handling", () => {
     it("uses markSaved for manual saves", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "manual");
 
@@ -196,7 +347,7 @@ describe("saveToPath", () => {
     });
 
     it("uses markAutoSaved for auto saves", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "auto");
 
@@ -205,7 +356,7 @@ describe("saveToPath", () => {
     });
 
     it("adds to recent files for manual saves", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "manual");
 
@@ -213,7 +364,7 @@ describe("saveToPath", () => {
     });
 
     it("skips recent files for auto saves", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "auto");
 
@@ -223,19 +374,19 @@ describe("saveToPath", () => {
 
   describe("pending save handling", () => {
     it("registers pending save before write", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "manual");
 
       expect(registerPendingSave).toHaveBeenCalledWith("/tmp/doc.md", "content");
-      // registerPendingSave should be called before writeTextFile
+      // registerPendingSave should be called before the write
       const registerCall = vi.mocked(registerPendingSave).mock.invocationCallOrder[0];
-      const writeCall = vi.mocked(writeTextFile).mock.invocationCallOrder[0];
+      const writeCall = vi.mocked(saveTextDocument).mock.invocationCallOrder[0];
       expect(registerCall).toBeLessThan(writeCall);
     });
 
     it("clears pending save after successful write (delayed)", async () => {
-      vi.mocked(writeTextFile).mockResolvedValue(undefined);
+      vi.mocked(saveTextDocument).mockResolvedValue(saved);
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "manual");
 
@@ -246,7 +397,7 @@ describe("saveToPath", () => {
     });
 
     it("clears pending save on write failure", async () => {
-      vi.mocked(writeTextFile).mockRejectedValue(new Error("disk error"));
+      vi.mocked(saveTextDocument).mockRejectedValue(new Error("disk error"));
       const consoleError = vi.spyOn(console, "error").mockImplementation(() => {});
 
       await saveToPath("tab-1", "/tmp/doc.md", "content", "manual");
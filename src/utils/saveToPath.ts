@@ -3,7 +3,7 @@
  *
  * Shared helper for manual/auto saves across file flows.
  */
-import { writeTextFile } from "@tauri-apps/plugin-fs";
+import { message } from "@tauri-apps/plugin-dialog";
 import { toast } from "sonner";
 import { useDocumentStore } from "@/stores/documentStore";
 import { useTabStore } from "@/stores/tabStore";
@@ -17,6 +17,107 @@ import {
   normalizeLineEndings,
 } from "@/utils/linebreaks";
 import { registerPendingSave, clearPendingSave } from "@/utils/pendingSaves";
+import { isEncryptedPath, writeDocumentFile } from "@/utils/encryptedDocuments";
+import { saveTextDocument } from "@/utils/documentFiles";
+import { isDeletedConflict, mergeWithDisk, type SafeSaveResult } from "@/utils/safeSave";
+import { detectLinebreaks } from "@/utils/linebreakDetection";
+import { getFileName } from "@/utils/paths";
+import { scanNoteReminders } from "@/utils/reminders";
+import { runSaveHooks } from "@/utils/saveHooks";
+import { recordDocumentSave } from "@/utils/writingGoals";
+import { useWritingGoalsStore } from "@/stores/writingGoalsStore";
+import { resolveLineEndingPreference } from "@/utils/workspaceEditorSettings";
+
+/**
+ * Write the document, refusing to overwrite edits made to the file on disk
+ * since it was last read or saved. On such a conflict a manual save merges
+ * those edits with the document's, asking what to do if they overlap; an
+ * auto-save leaves the file alone and marks the document divergent.
+ * Returns the text written, or null when nothing was.
+ */
+async function writeUnlessChanged(
+  tabId: string,
+  path: string,
+  output: string,
+  saveType: "manual" | "auto"
+): Promise<string | null> {
+  if (isEncryptedPath(path)) {
+    await writeDocumentFile(path, output);
+    return output;
+  }
+
+  const result = await saveTextDocument(path, output);
+  if (result.status === "saved") return output;
+
+  // Deleted since it was read: saving recreates it
+  if (isDeletedConflict(result)) {
+    await saveTextDocument(path, output, null);
+    return output;
+  }
+
+  useDocumentStore.getState().markDivergent(tabId);
+  if (saveType === "auto") return null;
+  return resolveSaveConflict(tabId, path, output, result);
+}
+
+/**
+ * Merge the document with the edits found on disk by a refused save. A clean
+ * merge is saved and shown in the editor. Otherwise the user picks between
+ * reviewing the conflicts (the merge, with conflict markers, replaces the
+ * editor text unsaved), overwriting the disk edits, and cancelling.
+ */
+async function resolveSaveConflict(
+  tabId: string,
+  path: string,
+  output: string,
+  conflict: SafeSaveResult
+): Promise<string | null> {
+  if (conflict.status !== "conflict" || conflict.diskContent === null) return null;
+  const base = useDocumentStore.getState().getDocument(tabId)?.lastDiskContent ?? "";
+  const merged = await mergeWithDisk(base, output, conflict);
+  if (!merged) return null;
+
+  if (merged.clean) {
+    registerPendingSave(path, merged.content);
+    const result = await saveTextDocument(path, merged.content, conflict.diskDigest);
+    if (result.status !== "saved") {
+      toast.error(`"${getFileName(path)}" is still changing on disk; try saving again`);
+      return null;
+    }
+    if (merged.content !== output) {
+      useDocumentStore.getState().loadContent(tabId, merged.content, path, detectLinebreaks(merged.content));
+      toast.info(`Merged changes made on disk to ${getFileName(path)}`);
+    }
+    return merged.content;
+  }
+
+  const buttons = { review: "Review Conflicts", overwrite: "Overwrite", cancel: "Cancel" } as const;
+  const count = merged.conflicts.length;
+  const overlaps = count === 1 ? "1 change overlaps" : `${count} changes overlap`;
+  const choice = await message(
+    `"${getFileName(path)}" was changed on disk since you opened it, and ${overlaps} with yours.\n\n` +
+      "What would you like to do?",
+    {
+      title: "File Changed",
+      kind: "warning",
+      buttons: { yes: buttons.review, no: buttons.overwrite, cancel: buttons.cancel },
+    }
+  );
+
+  if (choice === "Yes" || choice === buttons.review) {
+    // The disk version becomes the saved state; the merge is the unsaved edit
+    const store = useDocumentStore.getState();
+    store.loadContent(tabId, conflict.diskContent, path, detectLinebreaks(conflict.diskContent));
+    store.setContent(tabId, merged.content);
+    toast.warning(`Resolve the conflicts marked in ${getFileName(path)}, then save`);
+    return null;
+  }
+  if (choice === "No" || choice === buttons.overwrite) {
+    await saveTextDocument(path, output, null);
+    return output;
+  }
+  return null;
+}
 
 export async function saveToPath(
   tabId: string,
@@ -25,8 +126,10 @@ export async function saveToPath(
   saveType: "manual" | "auto" = "manual"
 ): Promise<boolean> {
   const doc = useDocumentStore.getState().getDocument(tabId);
+  // First save of a new document: all of its words count toward goals
+  const isNew = !doc?.filePath;
   const settings = useSettingsStore.getState();
-  const lineEndingPref = settings.general.lineEndingsOnSave;
+  const lineEndingPref = resolveLineEndingPreference(path, settings.general.lineEndingsOnSave);
   const hardBreakPref = settings.markdown.hardBreakStyleOnSave;
   const targetLineEnding = resolveLineEndingOnSave(doc?.lineEnding ?? "unknown", lineEndingPref);
   const targetHardBreakStyle = resolveHardBreakStyle(
@@ -39,8 +142,9 @@ export async function saveToPath(
   // Register pending save with content for content-based verification
   registerPendingSave(path, output);
 
+  let written: string | null;
   try {
-    await writeTextFile(path, output);
+    written = await writeUnlessChanged(tabId, path, output, saveType);
   } catch (error) {
     // CRITICAL: Always clear pending save on failure to prevent stale entries
     clearPendingSave(path);
@@ -49,6 +153,10 @@ export async function saveToPath(
     toast.error(`Failed to save: ${message}`);
     return false;
   }
+  if (written === null) {
+    clearPendingSave(path);
+    return false;
+  }
 
   // Write succeeded - update state
   useDocumentStore.getState().setFilePath(tabId, path);
@@ -56,9 +164,9 @@ export async function saveToPath(
     .getState()
     .setLineMetadata(tabId, { lineEnding: targetLineEnding, hardBreakStyle: targetHardBreakStyle });
   if (saveType === "auto") {
-    useDocumentStore.getState().markAutoSaved(tabId, output);
+    useDocumentStore.getState().markAutoSaved(tabId, written);
   } else {
-    useDocumentStore.getState().markSaved(tabId, output);
+    useDocumentStore.getState().markSaved(tabId, written);
   }
 
   // Delay clearing pending save to allow late-arriving watcher events
@@ -73,11 +181,12 @@ export async function saveToPath(
     useRecentFilesStore.getState().addFile(path);
   }
 
-  // Create history snapshot if enabled
+  // Create history snapshot if enabled (never for encrypted documents,
+  // whose history would be stored in plain text)
   const { general } = useSettingsStore.getState();
-  if (general.historyEnabled) {
+  if (general.historyEnabled && !isEncryptedPath(path)) {
     try {
-      await createSnapshot(path, output, saveType, {
+      await createSnapshot(path, written, saveType, {
         maxSnapshots: general.historyMaxSnapshots,
         maxAgeDays: general.historyMaxAgeDays,
       });
@@ -87,5 +196,21 @@ export async function saveToPath(
     }
   }
 
+  // Fill in missing frontmatter in the background (no-op unless enabled)
+  void runSaveHooks(path);
+
+  // Pick up @remind(...) markers (not from encrypted documents, whose
+  // reminder text would be stored in plain text)
+  if (!isEncryptedPath(path)) {
+    scanNoteReminders(path, written).catch((error) =>
+      console.warn("[Reminders] Failed to scan reminders:", error)
+    );
+  }
+
+  // Count the words added toward writing goals
+  recordDocumentSave(path, written, isNew)
+    .then((progress) => useWritingGoalsStore.getState().setProgress(progress))
+    .catch((error) => console.warn("[Goals] Failed to record save:", error));
+
   return true;
 }
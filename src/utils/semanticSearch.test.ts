@@ -0,0 +1,33 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { relatedNotes, semanticSearch } from "./semanticSearch";
+
+describe("semanticSearch", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(invoke).mockResolvedValue([]);
+  });
+
+  it("uses the index's model by default", async () => {
+    await semanticSearch("/ws", "garden planning");
+    expect(invoke).toHaveBeenCalledWith("semantic_search", {
+      workspaceRoot: "/ws",
+      query: "garden planning",
+      k: 10,
+      model: null,
+    });
+  });
+
+  it("passes an explicit model", async () => {
+    await relatedNotes("/ws", "/ws/a.md", 5, { model: "mxbai-embed-large" });
+    expect(invoke).toHaveBeenCalledWith("related_notes", {
+      workspaceRoot: "/ws",
+      path: "/ws/a.md",
+      k: 5,
+      model: "mxbai-embed-large",
+    });
+  });
+});
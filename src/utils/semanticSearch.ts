@@ -0,0 +1,80 @@
+/**
+ * Semantic Search
+ *
+ * Purpose: Find notes by meaning rather than exact words, and list notes
+ * related to the current one. The backend embeds workspace notes with an
+ * Ollama embedding model and keeps the vectors under `.vmark/embeddings/`,
+ * updating them as notes are saved. Requests go to the embedding endpoint
+ * in Settings → Advanced.
+ *
+ * @module utils/semanticSearch
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface SemanticMatch {
+  /** Workspace-relative note path */
+  path: string;
+  title: string;
+  /** Heading above the best-matching passage */
+  heading: string | null;
+  /** 1-based line of the best-matching passage */
+  line: number;
+  snippet: string;
+  /** Cosine similarity, higher is closer */
+  score: number;
+}
+
+export interface EmbeddingIndexStatus {
+  model: string;
+  notes: number;
+  chunks: number;
+}
+
+export interface EmbeddingOptions {
+  /** Ollama embedding model (switching models re-embeds the workspace) */
+  model?: string;
+}
+
+export function embeddingArgs(options: EmbeddingOptions) {
+  return { model: options.model ?? null };
+}
+
+export function semanticSearch(
+  workspaceRoot: string,
+  query: string,
+  k = 10,
+  options: EmbeddingOptions = {}
+): Promise<SemanticMatch[]> {
+  return invoke<SemanticMatch[]>("semantic_search", {
+    workspaceRoot,
+    query,
+    k,
+    ...embeddingArgs(options),
+  });
+}
+
+/** Notes similar to `path` (absolute or workspace-relative). */
+export function relatedNotes(
+  workspaceRoot: string,
+  path: string,
+  k = 10,
+  options: EmbeddingOptions = {}
+): Promise<SemanticMatch[]> {
+  return invoke<SemanticMatch[]>("related_notes", {
+    workspaceRoot,
+    path,
+    k,
+    ...embeddingArgs(options),
+  });
+}
+
+export function rebuildEmbeddingIndex(
+  workspaceRoot: string,
+  options: EmbeddingOptions = {}
+): Promise<EmbeddingIndexStatus> {
+  return invoke<EmbeddingIndexStatus>("rebuild_embedding_index", {
+    workspaceRoot,
+    ...embeddingArgs(options),
+  });
+}
@@ -0,0 +1,46 @@
+/**
+ * Snippets (backend-loaded from app data and `.vmark/snippets`).
+ *
+ * The backend expands snippet bodies; offsets in the result are JS string
+ * offsets relative to the start of the inserted text.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface Snippet {
+  name: string;
+  prefix: string[];
+  body: string;
+  description?: string;
+  /** Editor scopes the snippet applies to; empty means everywhere */
+  scope: string[];
+  source: "global" | "workspace";
+}
+
+export interface SnippetTabStop {
+  index: number;
+  ranges: { from: number; to: number }[];
+}
+
+export interface ResolvedSnippet {
+  name: string;
+  text: string;
+  tabStops: SnippetTabStop[];
+  finalCursor: number;
+}
+
+export function listSnippets(workspaceRoot: string | null, scope?: string): Promise<Snippet[]> {
+  return invoke<Snippet[]>("list_snippets", { workspaceRoot, scope: scope ?? null });
+}
+
+export function resolveSnippet(
+  prefix: string,
+  workspaceRoot: string | null,
+  options: { scope?: string; selection?: string } = {}
+): Promise<ResolvedSnippet | null> {
+  return invoke<ResolvedSnippet | null>("resolve_snippet", {
+    prefix,
+    workspaceRoot,
+    scope: options.scope ?? null,
+    selection: options.selection ?? null,
+  });
+}
@@ -20,6 +20,8 @@ import {
   createMarkdownAutoPairPlugin,
   markdownPairBackspace,
   tabEscapeKeymap,
+  snippetExpansionExtensions,
+  sourceCompletionExtensions,
   tabIndentFallbackKeymap,
   shiftTabIndentFallbackKeymap,
   listContinuationKeymap,
@@ -125,6 +127,10 @@ export function createSourceEditorExtensions(config: ExtensionConfig): Extension
     history(),
     // Shortcuts from settings (dynamic via compartment)
     shortcutKeymapCompartment.of(keymap.of(buildSourceShortcutKeymap())),
+    // Snippet expansion and tab stops (Tab before table navigation and tab escape)
+    ...snippetExpansionExtensions,
+    // Autocomplete: [[ wiki link targets
+    ...sourceCompletionExtensions,
     // Keymaps (no searchKeymap - we use our unified FindBar)
     keymap.of([
       // Visual line navigation (must be before default keymap to override)
@@ -0,0 +1,19 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { shortcodeBeforeCursor } from "./symbolSearch";
+
+describe("shortcodeBeforeCursor", () => {
+  it("finds a shortcode being typed", () => {
+    expect(shortcodeBeforeCursor("Nice :thu")).toBe("thu");
+    expect(shortcodeBeforeCursor(":+1")).toBe("+1");
+    expect(shortcodeBeforeCursor("(:heart_ey")).toBe("heart_ey");
+  });
+
+  it("ignores colons in text", () => {
+    expect(shortcodeBeforeCursor("at 10:30")).toBeNull();
+    expect(shortcodeBeforeCursor("Note:x")).toBeNull();
+    expect(shortcodeBeforeCursor("Nice :t")).toBeNull();
+  });
+});
@@ -0,0 +1,43 @@
+/**
+ * Emoji and Symbol Search
+ *
+ * Purpose: Look up emoji by `:shortcode:` and Unicode symbols by name or
+ * code point. The tables live in the backend so they aren't bundled here.
+ *
+ * @module utils/symbolSearch
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface EmojiMatch {
+  emoji: string;
+  /** Preferred shortcode, without colons */
+  shortcode: string;
+  aliases: string[];
+  name: string;
+}
+
+export interface SymbolMatch {
+  symbol: string;
+  /** e.g. "U+2192" */
+  codepoint: string;
+  name: string;
+}
+
+export function searchEmoji(query: string, limit?: number): Promise<EmojiMatch[]> {
+  return invoke<EmojiMatch[]>("search_emoji", { query, limit: limit ?? null });
+}
+
+/** Accepts a name ("right arrow"), a code point ("U+2192") or the character itself. */
+export function searchUnicode(query: string, limit?: number): Promise<SymbolMatch[]> {
+  return invoke<SymbolMatch[]>("search_unicode", { query, limit: limit ?? null });
+}
+
+/**
+ * The `:partial` shortcode being typed just before the cursor, if any.
+ * Needs at least two characters so times like "10:30" don't trigger it.
+ */
+export function shortcodeBeforeCursor(textBefore: string): string | null {
+  const match = /(?:^|[\s(])(:[a-z0-9_+-]{2,})$/i.exec(textBefore);
+  return match ? match[1].slice(1) : null;
+}
@@ -0,0 +1,14 @@
+import { describe, it, expect } from "vitest";
+import { looksLikeSpreadsheetPaste } from "./tableData";
+
+describe("looksLikeSpreadsheetPaste", () => {
+  it("detects tab-separated rows", () => {
+    expect(looksLikeSpreadsheetPaste("a\tb\r\n1\t2\r\n")).toBe(true);
+  });
+
+  it("ignores prose and ragged text", () => {
+    expect(looksLikeSpreadsheetPaste("just a line")).toBe(false);
+    expect(looksLikeSpreadsheetPaste("a\tb\nplain")).toBe(false);
+    expect(looksLikeSpreadsheetPaste("one\ntwo")).toBe(false);
+  });
+});
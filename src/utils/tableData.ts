@@ -0,0 +1,48 @@
+/**
+ * CSV/TSV ⇄ markdown table conversion.
+ *
+ * The backend parses delimited text (a file path or pasted text), sniffing
+ * the delimiter and handling quoted cells, and renders a pipe table; and
+ * turns a pipe table back into CSV or TSV for copying to a spreadsheet.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface CsvImportOptions {
+  /** Single character; sniffed when omitted */
+  delimiter?: string;
+  /** First row is the header (default true) */
+  hasHeader?: boolean;
+  /** Body rows to keep (default 5000) */
+  maxRows?: number;
+  /** Right-align numeric columns (default true) */
+  alignNumbers?: boolean;
+}
+
+export interface TableImport {
+  markdown: string;
+  rows: number;
+  columns: number;
+  delimiter: string;
+  /** Rows beyond `maxRows` were dropped */
+  truncated: boolean;
+}
+
+export function csvToMarkdownTable(pathOrText: string, options?: CsvImportOptions): Promise<TableImport> {
+  return invoke<TableImport>("csv_to_markdown_table", { pathOrText, options });
+}
+
+export function markdownTableToCsv(tableText: string, delimiter?: string): Promise<string> {
+  return invoke<string>("markdown_table_to_csv", { tableText, delimiter });
+}
+
+/**
+ * Whether pasted plain text looks like spreadsheet data: at least two lines
+ * with the same number of tabs (what Excel, Numbers and Sheets put on the
+ * clipboard).
+ */
+export function looksLikeSpreadsheetPaste(text: string): boolean {
+  const lines = text.replace(/\r\n/g, "\n").replace(/\n+$/, "").split("\n");
+  if (lines.length < 2) return false;
+  const tabs = lines.map((line) => line.split("\t").length - 1);
+  return tabs[0] > 0 && tabs.every((count) => count === tabs[0]);
+}
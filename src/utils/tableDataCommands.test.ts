@@ -0,0 +1,15 @@
+import { describe, it, expect } from "vitest";
+import { pipeTableAt } from "./tableDataCommands";
+
+const text = "Intro\n\n| a | b |\n| - | - |\n| 1 | 2 |\n\nOutro";
+
+describe("pipeTableAt", () => {
+  it("returns the whole table around the cursor", () => {
+    expect(pipeTableAt(text, text.indexOf("1 |"))).toBe("| a | b |\n| - | - |\n| 1 | 2 |");
+  });
+
+  it("returns null outside a table", () => {
+    expect(pipeTableAt(text, 2)).toBeNull();
+    expect(pipeTableAt(text, text.length)).toBeNull();
+  });
+});
@@ -0,0 +1,102 @@
+/**
+ * Table import/export commands for Insert → Table: Import CSV… inserts a
+ * spreadsheet file as a markdown table at the cursor, and Copy Table as CSV
+ * puts the table around the cursor on the clipboard for a spreadsheet.
+ * Both work in WYSIWYG and Source mode.
+ */
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import type { EditorView as CodeMirrorView } from "@codemirror/view";
+import type { Editor } from "@tiptap/core";
+import { useActiveEditorStore } from "@/stores/activeEditorStore";
+import { useEditorStore } from "@/stores/editorStore";
+import { createMarkdownPasteTransaction } from "@/plugins/markdownPaste/tiptap";
+import { serializeMarkdown } from "@/utils/markdownPipeline";
+import { csvToMarkdownTable, markdownTableToCsv } from "@/utils/tableData";
+
+/** Contiguous `|` lines around `pos` in source text, or null outside a table. */
+export function pipeTableAt(text: string, pos: number): string | null {
+  const lines = text.split("\n");
+  let offset = 0;
+  let index = 0;
+  while (index < lines.length - 1 && offset + lines[index].length < pos) {
+    offset += lines[index].length + 1;
+    index++;
+  }
+  const isRow = (line: string | undefined) => line !== undefined && line.trim().startsWith("|");
+  if (!isRow(lines[index])) return null;
+  let start = index;
+  let end = index;
+  while (isRow(lines[start - 1])) start--;
+  while (isRow(lines[end + 1])) end++;
+  return end > start ? lines.slice(start, end + 1).join("\n") : null;
+}
+
+function wysiwygTableMarkdown(editor: Editor): string | null {
+  const { $from } = editor.state.selection;
+  for (let depth = $from.depth; depth > 0; depth--) {
+    const node = $from.node(depth);
+    if (node.type.name !== "table") continue;
+    const schema = editor.schema;
+    return serializeMarkdown(schema, schema.topNodeType.create(null, node));
+  }
+  return null;
+}
+
+function insertIntoSource(view: CodeMirrorView, markdown: string) {
+  const { from, to } = view.state.selection.main;
+  // Tables need blank lines around them to parse
+  const insert = `\n${markdown}\n`;
+  view.dispatch({
+    changes: { from, to, insert },
+    selection: { anchor: from + insert.length },
+  });
+  view.focus();
+}
+
+export async function importCsvTable(): Promise<void> {
+  const { activeSourceView, activeWysiwygEditor } = useActiveEditorStore.getState();
+  const sourceMode = useEditorStore.getState().sourceMode;
+  if (sourceMode ? !activeSourceView : !activeWysiwygEditor) return;
+
+  const path = await open({ filters: [{ name: "Spreadsheet data", extensions: ["csv", "tsv", "txt"] }] });
+  if (typeof path !== "string") return;
+
+  try {
+    const table = await csvToMarkdownTable(path);
+    if (sourceMode && activeSourceView) {
+      insertIntoSource(activeSourceView, table.markdown);
+    } else if (activeWysiwygEditor) {
+      const tr = createMarkdownPasteTransaction(activeWysiwygEditor.state, table.markdown);
+      if (tr) activeWysiwygEditor.view.dispatch(tr.scrollIntoView());
+    }
+    if (table.truncated) {
+      toast.warning(`Imported the first ${table.rows} rows; the rest were left out`);
+    }
+  } catch (error) {
+    toast.error(`Failed to import table: ${error}`);
+  }
+}
+
+export async function copyTableAsCsv(): Promise<void> {
+  const { activeSourceView, activeWysiwygEditor } = useActiveEditorStore.getState();
+  let table: string | null = null;
+  if (useEditorStore.getState().sourceMode) {
+    if (activeSourceView) {
+      table = pipeTableAt(activeSourceView.state.doc.toString(), activeSourceView.state.selection.main.head);
+    }
+  } else if (activeWysiwygEditor) {
+    table = wysiwygTableMarkdown(activeWysiwygEditor);
+  }
+  if (!table) {
+    toast.info("Place the cursor in a table to copy it");
+    return;
+  }
+
+  try {
+    await navigator.clipboard.writeText(await markdownTableToCsv(table));
+    toast.success("Table copied as CSV");
+  } catch (error) {
+    toast.error(`Failed to copy table: ${error}`);
+  }
+}
@@ -0,0 +1,25 @@
+import { describe, it, expect } from "vitest";
+import { calendarFeedUrl, localDate, overdueTasks } from "./taskCalendar";
+
+describe("overdueTasks", () => {
+  it("keeps open tasks due before today", () => {
+    const task = (due: string, done = false) => ({ note: "a.md", line: 1, text: due, due, done });
+    const tasks = [task("2026-04-30"), task("2026-04-29", true), task("2026-05-01")];
+    expect(overdueTasks(tasks, "2026-05-01").map((t) => t.due)).toEqual(["2026-04-30"]);
+  });
+});
+
+describe("localDate", () => {
+  it("pads month and day", () => {
+    expect(localDate(new Date(2026, 0, 5))).toBe("2026-01-05");
+  });
+});
+
+describe("calendarFeedUrl", () => {
+  it("builds file URLs on every platform", () => {
+    expect(calendarFeedUrl("/Users/me/My Notes/.vmark/tasks.ics")).toBe(
+      "file:///Users/me/My%20Notes/.vmark/tasks.ics"
+    );
+    expect(calendarFeedUrl("C:\\Notes\\.vmark\\tasks.ics")).toBe("file:///C:/Notes/.vmark/tasks.ics");
+  });
+});
@@ -0,0 +1,47 @@
+/**
+ * Task calendar export.
+ *
+ * The backend collects `- [ ] task 📅 2026-05-01` items across the workspace
+ * and writes them as all-day events to an `.ics` file (by default
+ * `.vmark/tasks.ics`) that calendar apps can import or subscribe to.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface DatedTask {
+  /** Workspace-relative note path */
+  note: string;
+  line: number;
+  text: string;
+  /** `YYYY-MM-DD` */
+  due: string;
+  done: boolean;
+}
+
+export interface IcsExport {
+  path: string;
+  tasks: DatedTask[];
+  /** False when the file already had this content */
+  changed: boolean;
+}
+
+export function exportTasksIcs(rootPath: string, outputPath?: string, includeDone = false): Promise<IcsExport> {
+  return invoke<IcsExport>("export_tasks_ics", { rootPath, outputPath, includeDone });
+}
+
+/** `YYYY-MM-DD` in local time, as due dates are written. */
+export function localDate(date = new Date()): string {
+  const pad = (n: number) => String(n).padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+}
+
+/** Open tasks due before `today` (`YYYY-MM-DD`). */
+export function overdueTasks(tasks: DatedTask[], today: string): DatedTask[] {
+  return tasks.filter((task) => !task.done && task.due < today);
+}
+
+/** `file://` URL for subscribing to the exported calendar. */
+export function calendarFeedUrl(path: string): string {
+  const normalized = path.replace(/\\/g, "/");
+  const withRoot = normalized.startsWith("/") ? normalized : `/${normalized}`;
+  return `file://${encodeURI(withRoot)}`;
+}
@@ -0,0 +1,18 @@
+import { describe, it, expect } from "vitest";
+import { groupTasksByNote, type IndexedTask } from "./taskIndex";
+
+describe("groupTasksByNote", () => {
+  it("groups tasks by note in query order", () => {
+    const task = (path: string, line: number): IndexedTask => ({
+      path,
+      line,
+      text: `${path}:${line}`,
+      done: false,
+      due: null,
+      tags: [],
+    });
+    const groups = groupTasksByNote([task("b.md", 3), task("a.md", 1), task("b.md", 1)]);
+    expect([...groups.keys()]).toEqual(["b.md", "a.md"]);
+    expect(groups.get("b.md")?.map((t) => t.line)).toEqual([3, 1]);
+  });
+});
@@ -0,0 +1,70 @@
+/**
+ * Workspace task index.
+ *
+ * The backend indexes every checkbox item (`- [ ] task`) across the
+ * workspace, with due dates (`📅 2026-05-01`) and `#tags`, and keeps the
+ * index current from file watcher events.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface IndexedTask {
+  /** Workspace-relative note path */
+  path: string;
+  /** 1-based line number */
+  line: number;
+  text: string;
+  done: boolean;
+  /** `YYYY-MM-DD` */
+  due: string | null;
+  /** Lowercase, without `#` */
+  tags: string[];
+}
+
+export interface TaskQuery {
+  status?: "open" | "done" | "all";
+  tag?: string;
+  dueBefore?: string;
+  dueAfter?: string;
+  hasDue?: boolean;
+  /** Note or folder, workspace-relative */
+  path?: string;
+}
+
+export interface TaskTagCount {
+  tag: string;
+  open: number;
+  total: number;
+}
+
+export function queryTasks(rootPath: string, query?: TaskQuery): Promise<IndexedTask[]> {
+  return invoke<IndexedTask[]>("query_tasks", { rootPath, query });
+}
+
+export function listTaskTags(rootPath: string): Promise<TaskTagCount[]> {
+  return invoke<TaskTagCount[]>("list_task_tags", { rootPath });
+}
+
+export function rebuildTaskIndex(rootPath: string): Promise<void> {
+  return invoke("rebuild_task_index", { rootPath });
+}
+
+/** Check or uncheck a task; fails if the line no longer holds `task.text`. */
+export function toggleTask(rootPath: string, task: IndexedTask): Promise<IndexedTask> {
+  return invoke<IndexedTask>("toggle_task", {
+    rootPath,
+    path: task.path,
+    line: task.line,
+    expectedText: task.text,
+  });
+}
+
+/** Group tasks by note, keeping the query's order. */
+export function groupTasksByNote(tasks: IndexedTask[]): Map<string, IndexedTask[]> {
+  const groups = new Map<string, IndexedTask[]>();
+  for (const task of tasks) {
+    const group = groups.get(task.path);
+    if (group) group.push(task);
+    else groups.set(task.path, [task]);
+  }
+  return groups;
+}
@@ -0,0 +1,10 @@
+import { describe, it, expect } from "vitest";
+import { needsEncodingConversion } from "./textEncoding";
+
+describe("needsEncodingConversion", () => {
+  it("is false only for plain UTF-8", () => {
+    expect(needsEncodingConversion({ encoding: "UTF-8", bom: false })).toBe(false);
+    expect(needsEncodingConversion({ encoding: "UTF-8", bom: true })).toBe(true);
+    expect(needsEncodingConversion({ encoding: "GBK", bom: false })).toBe(true);
+  });
+});
@@ -0,0 +1,37 @@
+/**
+ * Non-UTF-8 text files.
+ *
+ * The backend detects a file's encoding (BOM, UTF-8 validity, then a
+ * statistical guess), hands the editor UTF-8, and can write the text back
+ * in the original encoding. Encodings use WHATWG names ("GBK", "Shift_JIS").
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface DecodedFile {
+  content: string;
+  encoding: string;
+  bom: boolean;
+  lineEnding: "lf" | "crlf" | "cr" | "mixed" | "unknown";
+  /** Some bytes were invalid in the detected encoding and were replaced */
+  hadErrors: boolean;
+  /** SHA-256 of the file's bytes, as in watcher events and safe saves */
+  digest: string;
+}
+
+export function readFileWithEncoding(path: string): Promise<DecodedFile> {
+  return invoke<DecodedFile>("read_file_with_encoding", { path });
+}
+
+/** Fails without writing if the text has characters the encoding can't hold. */
+export function saveFileWithEncoding(path: string, content: string, encoding: string, bom = false): Promise<void> {
+  return invoke("save_file_with_encoding", { path, content, encoding, bom });
+}
+
+export function listTextEncodings(): Promise<string[]> {
+  return invoke<string[]>("list_text_encodings");
+}
+
+/** True when a file needs the encoding-aware save path (anything but plain UTF-8). */
+export function needsEncodingConversion(file: Pick<DecodedFile, "encoding" | "bom">): boolean {
+  return file.encoding !== "UTF-8" || file.bom;
+}
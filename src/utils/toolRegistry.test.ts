@@ -0,0 +1,40 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }));
+
+import { mergeToolStatus, toolStatusLabel, type ToolStatus } from "./toolRegistry";
+
+function tool(overrides: Partial<ToolStatus> = {}): ToolStatus {
+  return {
+    id: "pandoc",
+    name: "Pandoc",
+    purpose: "Import and export",
+    available: true,
+    path: "/usr/bin/pandoc",
+    version: "3.1.9",
+    minVersion: "2.11",
+    outdated: false,
+    installHint: { command: "brew install pandoc", url: "https://pandoc.org", message: "" },
+    checkedAt: 0,
+    ...overrides,
+  };
+}
+
+describe("toolStatusLabel", () => {
+  it("describes each state", () => {
+    expect(toolStatusLabel(tool())).toBe("3.1.9");
+    expect(toolStatusLabel(tool({ available: false, path: null, version: null }))).toBe(
+      "Not installed"
+    );
+    expect(toolStatusLabel(tool({ version: "2.5", outdated: true }))).toBe("2.5 (needs 2.11+)");
+  });
+});
+
+describe("mergeToolStatus", () => {
+  it("replaces changed tools only", () => {
+    const ffmpeg = tool({ id: "ffmpeg", name: "FFmpeg" });
+    const updated = tool({ version: "3.2" });
+    expect(mergeToolStatus([tool(), ffmpeg], [updated])).toEqual([updated, ffmpeg]);
+  });
+});
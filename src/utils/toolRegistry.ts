@@ -0,0 +1,67 @@
+/**
+ * External Tools
+ *
+ * Purpose: Status of the command-line tools VMark can use (pandoc,
+ * WeasyPrint, mermaid-cli, tesseract, ffmpeg) for the Settings dependency
+ * dashboard. Detection results are cached in the backend; pass
+ * `refresh` to look again after installing something.
+ *
+ * @module utils/toolRegistry
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export const TOOLS_STATUS_CHANGED_EVENT = "tools:status-changed";
+
+export interface InstallHint {
+  /** Install command for this platform */
+  command: string;
+  url: string;
+  message: string;
+}
+
+export interface ToolStatus {
+  id: string;
+  name: string;
+  purpose: string;
+  available: boolean;
+  path: string | null;
+  version: string | null;
+  minVersion: string | null;
+  /** Installed, but older than minVersion */
+  outdated: boolean;
+  installHint: InstallHint;
+  checkedAt: number;
+}
+
+export function listTools(refresh = false): Promise<ToolStatus[]> {
+  return invoke<ToolStatus[]>("list_tools", { refresh });
+}
+
+export function detectTool(id: string, refresh = false): Promise<ToolStatus> {
+  return invoke<ToolStatus>("detect_tool", { id, refresh });
+}
+
+export function getToolInstallHint(id: string): Promise<InstallHint> {
+  return invoke<InstallHint>("get_tool_install_hint", { id });
+}
+
+/** Called with the tools whose status changed on a re-check. */
+export function onToolStatusChanged(
+  handler: (changed: ToolStatus[]) => void
+): Promise<UnlistenFn> {
+  return listen<ToolStatus[]>(TOOLS_STATUS_CHANGED_EVENT, (event) => handler(event.payload));
+}
+
+/** Replace entries in `tools` with the changed ones. */
+export function mergeToolStatus(tools: ToolStatus[], changed: ToolStatus[]): ToolStatus[] {
+  return tools.map((tool) => changed.find((c) => c.id === tool.id) ?? tool);
+}
+
+/** Short status label for the dashboard. */
+export function toolStatusLabel(tool: ToolStatus): string {
+  if (!tool.available) return "Not installed";
+  const version = tool.version ?? "unknown version";
+  return tool.outdated ? `${version} (needs ${tool.minVersion}+)` : version;
+}
@@ -0,0 +1,27 @@
+import { describe, it, expect } from "vitest";
+import { formatTimestamp, formatTranscript } from "./transcription";
+
+const transcript = {
+  text: "Buy milk. And eggs.",
+  segments: [
+    { startMs: 0, endMs: 2000, text: "Buy milk." },
+    { startMs: 62500, endMs: 64000, text: " And eggs." },
+  ],
+};
+
+describe("formatTimestamp", () => {
+  it("formats minutes and hours", () => {
+    expect(formatTimestamp(62500)).toBe("1:02");
+    expect(formatTimestamp(3723000)).toBe("1:02:03");
+  });
+});
+
+describe("formatTranscript", () => {
+  it("returns the plain text by default", () => {
+    expect(formatTranscript(transcript)).toBe("Buy milk. And eggs.");
+  });
+
+  it("puts each segment on its own timestamped line", () => {
+    expect(formatTranscript(transcript, true)).toBe("**[0:00]** Buy milk.\n\n**[1:02]** And eggs.");
+  });
+});
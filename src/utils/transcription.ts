@@ -0,0 +1,129 @@
+/**
+ * Voice memo transcription with whisper.cpp, run by the backend.
+ *
+ * Models are downloaded on demand (`transcription:download-progress`).
+ * A running transcription emits `transcription:progress` and one
+ * `transcription:segment` per recognized segment, keyed by request id.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { Editor } from "@tiptap/core";
+import { parseBlockMarkdown } from "@/hooks/mcpBridge/markdownHelpers";
+
+/** Models offered for download, smallest first (matches transcription.rs) */
+export const WHISPER_MODELS = [
+  "tiny",
+  "tiny.en",
+  "base",
+  "base.en",
+  "small",
+  "small.en",
+  "medium",
+  "medium.en",
+  "large-v3-turbo",
+  "large-v3",
+] as const;
+
+export interface WhisperInfo {
+  available: boolean;
+  path: string | null;
+  /** Shipped with VMark rather than installed by the user */
+  bundled: boolean;
+  /** Downloaded models (`base.en`) */
+  models: string[];
+  /** ffmpeg converts formats whisper.cpp can't read (m4a, aac, webm) */
+  ffmpeg: boolean;
+}
+
+export interface TranscriptSegment {
+  startMs: number;
+  endMs: number;
+  text: string;
+}
+
+export interface Transcript {
+  text: string;
+  segments: TranscriptSegment[];
+}
+
+export interface TranscriptionProgress {
+  requestId: string;
+  percent: number;
+}
+
+export interface WhisperDownloadProgress {
+  model: string;
+  downloaded: number;
+  total: number | null;
+}
+
+export function detectWhisper(): Promise<WhisperInfo> {
+  return invoke<WhisperInfo>("detect_whisper");
+}
+
+export function listWhisperModels(): Promise<string[]> {
+  return invoke<string[]>("list_whisper_models");
+}
+
+/** `language` is a whisper.cpp code (`en`, `de`) or `auto`. */
+export function transcribeAudio(
+  requestId: string,
+  path: string,
+  model: string,
+  language = "auto"
+): Promise<Transcript> {
+  return invoke<Transcript>("transcribe_audio", { requestId, path, model, language });
+}
+
+export function cancelTranscription(requestId: string): Promise<void> {
+  return invoke("cancel_transcription", { requestId });
+}
+
+export function downloadWhisperModel(model: string): Promise<void> {
+  return invoke("download_whisper_model", { model });
+}
+
+export function removeWhisperModel(model: string): Promise<void> {
+  return invoke("remove_whisper_model", { model });
+}
+
+export function onTranscriptionProgress(
+  handler: (progress: TranscriptionProgress) => void
+): Promise<UnlistenFn> {
+  return listen<TranscriptionProgress>("transcription:progress", (event) => handler(event.payload));
+}
+
+export function onWhisperDownloadProgress(
+  handler: (progress: WhisperDownloadProgress) => void
+): Promise<UnlistenFn> {
+  return listen<WhisperDownloadProgress>("transcription:download-progress", (event) =>
+    handler(event.payload)
+  );
+}
+
+/** `62500` → `"1:02"`, `3723000` → `"1:02:03"`. */
+export function formatTimestamp(ms: number): string {
+  const total = Math.floor(ms / 1000);
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  const seconds = String(total % 60).padStart(2, "0");
+  return hours > 0 ? `${hours}:${String(minutes).padStart(2, "0")}:${seconds}` : `${minutes}:${seconds}`;
+}
+
+/**
+ * Markdown for a transcript: one paragraph, or one timestamped line per
+ * segment.
+ */
+export function formatTranscript(transcript: Transcript, timestamps = false): string {
+  if (!timestamps) return transcript.text.trim();
+  return transcript.segments
+    .map((s) => `**[${formatTimestamp(s.startMs)}]** ${s.text.trim()}`)
+    .join("\n\n");
+}
+
+/** Insert a transcript at the cursor. */
+export function insertTranscript(editor: Editor, transcript: Transcript, timestamps = false): boolean {
+  const markdown = formatTranscript(transcript, timestamps);
+  if (!markdown) return false;
+  return editor.chain().focus().insertContent(parseBlockMarkdown(editor.schema, markdown)).run();
+}
@@ -0,0 +1,32 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { lookupTranslation, translationProviderKey } from "./translationMemory";
+
+describe("translationMemory", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("builds provider keys with the model", () => {
+    expect(translationProviderKey("openai", "gpt-4o-mini")).toBe("openai/gpt-4o-mini");
+    expect(translationProviderKey("claude", null)).toBe("claude");
+  });
+
+  it("looks up translations", async () => {
+    vi.mocked(invoke).mockResolvedValue("Thanks");
+    await expect(lookupTranslation("Danke", "en", "anthropic")).resolves.toBe("Thanks");
+    expect(invoke).toHaveBeenCalledWith("lookup_translation", {
+      text: "Danke",
+      targetLanguage: "en",
+      provider: "anthropic",
+    });
+  });
+
+  it("treats lookup errors as a miss", async () => {
+    vi.mocked(invoke).mockRejectedValue("Translation memory not initialized");
+    await expect(lookupTranslation("Danke", "en", "anthropic")).resolves.toBeNull();
+  });
+});
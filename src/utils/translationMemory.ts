@@ -0,0 +1,58 @@
+/**
+ * Translation Memory
+ *
+ * Purpose: Cache translation genie results so repeated translations of the
+ * same text (boilerplate, signatures, headers) skip the provider. Entries
+ * are keyed by the source text, target language and provider + model, and
+ * expire or get evicted in the backend.
+ *
+ * @module utils/translationMemory
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface TranslationMemoryStats {
+  entries: number;
+  /** Size of the cached translations, in bytes */
+  bytes: number;
+}
+
+/** Provider identity for the cache, e.g. "openai/gpt-4o-mini". */
+export function translationProviderKey(provider: string, model?: string | null): string {
+  return model ? `${provider}/${model}` : provider;
+}
+
+/** Cached translation, or `null` on a miss (or if the cache is unavailable). */
+export async function lookupTranslation(
+  text: string,
+  targetLanguage: string,
+  provider: string
+): Promise<string | null> {
+  try {
+    return await invoke<string | null>("lookup_translation", { text, targetLanguage, provider });
+  } catch (error) {
+    console.debug("[TranslationMemory] Lookup failed:", error);
+    return null;
+  }
+}
+
+export async function storeTranslation(
+  text: string,
+  targetLanguage: string,
+  provider: string,
+  translation: string
+): Promise<void> {
+  try {
+    await invoke("store_translation", { text, targetLanguage, provider, translation });
+  } catch (error) {
+    console.debug("[TranslationMemory] Store failed:", error);
+  }
+}
+
+export function translationMemoryStats(): Promise<TranslationMemoryStats> {
+  return invoke<TranslationMemoryStats>("translation_memory_stats");
+}
+
+export function clearTranslationMemory(): Promise<void> {
+  return invoke("clear_translation_memory");
+}
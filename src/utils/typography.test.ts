@@ -0,0 +1,32 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { invoke } from "@tauri-apps/api/core";
+import { smartenForExport, smartenTypography } from "./typography";
+
+describe("typography", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+  });
+
+  it("passes locale and mode", async () => {
+    vi.mocked(invoke).mockResolvedValue({ content: "\"x\"", replacements: 2, locale: "en" });
+    await smartenTypography("“x”", "", "straighten");
+    expect(invoke).toHaveBeenCalledWith("smarten_typography", {
+      content: "“x”",
+      locale: null,
+      mode: "straighten",
+    });
+  });
+
+  it("exports the smartened content", async () => {
+    vi.mocked(invoke).mockResolvedValue({ content: "“x”", replacements: 2, locale: "en" });
+    await expect(smartenForExport("\"x\"")).resolves.toBe("“x”");
+  });
+
+  it("exports the original content if conversion fails", async () => {
+    vi.mocked(invoke).mockRejectedValue("boom");
+    await expect(smartenForExport("\"x\"")).resolves.toBe("\"x\"");
+  });
+});
@@ -0,0 +1,44 @@
+/**
+ * Smart Typography
+ *
+ * Purpose: Convert typewriter punctuation in Markdown to typographic
+ * punctuation (locale-aware quotes, dashes, ellipses, French spacing) or
+ * back ("straighten"). Code, frontmatter, HTML tags and URLs are left
+ * untouched by the backend.
+ *
+ * @module utils/typography
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export type TypographyMode = "smarten" | "straighten";
+
+export interface TypographyResult {
+  content: string;
+  /** Characters or sequences replaced */
+  replacements: number;
+  /** Locale the quotes were chosen for */
+  locale: string;
+}
+
+/**
+ * Convert `content`'s punctuation. Without `locale` (e.g. "de", "fr-CA")
+ * the document's detected language picks the quote style.
+ */
+export function smartenTypography(
+  content: string,
+  locale?: string | null,
+  mode: TypographyMode = "smarten"
+): Promise<TypographyResult> {
+  return invoke<TypographyResult>("smarten_typography", { content, locale: locale || null, mode });
+}
+
+/** `content` smartened for export, or unchanged if conversion fails. */
+export async function smartenForExport(content: string): Promise<string> {
+  try {
+    return (await smartenTypography(content)).content;
+  } catch (error) {
+    console.warn("[Typography] Failed to smarten export:", error);
+    return content;
+  }
+}
@@ -0,0 +1,29 @@
+import { describe, it, expect } from "vitest";
+import { isInRollout, newRolloutBucket } from "./updateRollout";
+
+describe("isInRollout", () => {
+  it("gates a staged release by bucket", () => {
+    const manifest = { version: "1.2.0", rollout: 25 };
+    expect(isInRollout(manifest, 0)).toBe(true);
+    expect(isInRollout(manifest, 24)).toBe(true);
+    expect(isInRollout(manifest, 25)).toBe(false);
+    expect(isInRollout(manifest, 99)).toBe(false);
+  });
+
+  it("sends releases without a rollout to everyone", () => {
+    expect(isInRollout({ version: "1.2.0" }, 99)).toBe(true);
+    expect(isInRollout({ rollout: "50" }, 99)).toBe(true);
+    expect(isInRollout({ rollout: 100 }, 99)).toBe(true);
+    expect(isInRollout({ rollout: 0 }, 0)).toBe(false);
+  });
+});
+
+describe("newRolloutBucket", () => {
+  it("stays within 0-99", () => {
+    for (let i = 0; i < 50; i++) {
+      const bucket = newRolloutBucket();
+      expect(bucket).toBeGreaterThanOrEqual(0);
+      expect(bucket).toBeLessThan(100);
+    }
+  });
+});
@@ -0,0 +1,19 @@
+/**
+ * Staged update rollout
+ *
+ * A release manifest (latest.json) may carry `"rollout": <percent>`. Each
+ * install has a stable bucket (0-99); installs whose bucket is at or above
+ * the percentage don't see the release yet, so it reaches users in stages.
+ */
+
+/** Random bucket for an install that doesn't have one yet */
+export function newRolloutBucket(): number {
+  return Math.floor(Math.random() * 100);
+}
+
+/** Whether an install in `bucket` gets the release described by `manifest`. */
+export function isInRollout(manifest: Record<string, unknown>, bucket: number): boolean {
+  const percent = manifest.rollout;
+  if (typeof percent !== "number" || !Number.isFinite(percent)) return true;
+  return bucket < percent;
+}
@@ -0,0 +1,26 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+import { isBareUrl, markdownLink } from "./urlTitle";
+
+describe("isBareUrl", () => {
+  it("accepts a single http(s) URL", () => {
+    expect(isBareUrl("https://example.com/a?b=1")).toBe(true);
+    expect(isBareUrl("  http://example.com\n")).toBe(true);
+  });
+
+  it("rejects other text", () => {
+    expect(isBareUrl("see https://example.com")).toBe(false);
+    expect(isBareUrl("ftp://example.com")).toBe(false);
+    expect(isBareUrl("https://a.com https://b.com")).toBe(false);
+  });
+});
+
+describe("markdownLink", () => {
+  it("escapes brackets in the title", () => {
+    expect(markdownLink("[RFC] Links \\ more", "https://x.io")).toBe(
+      "[\\[RFC\\] Links \\\\ more](https://x.io)"
+    );
+  });
+});
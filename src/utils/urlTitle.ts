@@ -0,0 +1,47 @@
+/**
+ * Pasted Link Titles
+ *
+ * Purpose: When "Fetch titles for pasted links" is on, pasting a bare URL
+ * first inserts the URL, then the backend fetches the page title and the
+ * editors replace it with `[Title](url)`, provided the pasted text is still
+ * there untouched.
+ *
+ * @module utils/urlTitle
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { useSettingsStore } from "@/stores/settingsStore";
+
+const titleCache = new Map<string, Promise<string | null>>();
+
+/** A single http(s) URL with no surrounding text. */
+export function isBareUrl(text: string): boolean {
+  return /^https?:\/\/\S+$/i.test(text.trim());
+}
+
+export function shouldFetchLinkTitle(text: string): boolean {
+  return (useSettingsStore.getState().markdown.fetchLinkTitles ?? false) && isBareUrl(text);
+}
+
+/** Page title for `url`, or null if it has none or can't be fetched. */
+export function fetchUrlTitle(url: string): Promise<string | null> {
+  let pending = titleCache.get(url);
+  if (!pending) {
+    pending = invoke<string | null>("fetch_url_title", { url }).catch((error) => {
+      console.warn("[urlTitle] Failed to fetch title:", error);
+      titleCache.delete(url);
+      return null;
+    });
+    titleCache.set(url, pending);
+  }
+  return pending;
+}
+
+/** Escape a title for use as markdown link text. */
+export function escapeLinkText(title: string): string {
+  return title.replace(/[\\[\]]/g, "\\$&");
+}
+
+export function markdownLink(title: string, url: string): string {
+  return `[${escapeLinkText(title)}](${url})`;
+}
@@ -0,0 +1,14 @@
+/**
+ * Viewer Window Utility
+ *
+ * Opens a read-only rendered view of a file in its own small window, for
+ * referencing one note while editing another. The backend focuses the
+ * existing viewer when the file already has one.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+/** Open (or focus) the viewer for `filePath`. Returns the window label. */
+export function openInViewer(filePath: string): Promise<string> {
+  return invoke<string>("create_preview_window", { filePath });
+}
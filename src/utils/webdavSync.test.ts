@@ -0,0 +1,24 @@
+import { describe, it, expect } from "vitest";
+import { describeSyncSummary, type SyncSummary } from "./webdavSync";
+
+const summary = (overrides: Partial<SyncSummary> = {}): SyncSummary => ({
+  uploaded: 0,
+  downloaded: 0,
+  deletedLocal: 0,
+  deletedRemote: 0,
+  conflicts: [],
+  finishedAt: 0,
+  ...overrides,
+});
+
+describe("describeSyncSummary", () => {
+  it("returns null when nothing changed", () => {
+    expect(describeSyncSummary(summary())).toBeNull();
+  });
+
+  it("lists what changed", () => {
+    expect(
+      describeSyncSummary(summary({ uploaded: 2, deletedLocal: 1, deletedRemote: 1, conflicts: ["a (conflict x).md"] }))
+    ).toBe("2 uploaded, 2 deleted, 1 conflict");
+  });
+});
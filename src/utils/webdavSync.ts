@@ -0,0 +1,76 @@
+/**
+ * WebDAV workspace sync, run by the backend.
+ *
+ * Settings live in `.vmark/sync.json`; the password is stored in the OS
+ * keychain. Progress is emitted as `sync:status`.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface SyncConfig {
+  /** WebDAV folder URL */
+  endpoint: string;
+  username: string;
+  /** Background sync interval; 0 syncs only on demand */
+  intervalMinutes: number;
+}
+
+export interface SyncSummary {
+  uploaded: number;
+  downloaded: number;
+  deletedLocal: number;
+  deletedRemote: number;
+  /** Conflict copies written, relative to the workspace root */
+  conflicts: string[];
+  finishedAt: number;
+}
+
+export interface SyncStatus {
+  rootPath: string;
+  state: "syncing" | "done" | "failed";
+  summary: SyncSummary | null;
+  error: string | null;
+}
+
+export function getSyncConfig(rootPath: string): Promise<SyncConfig | null> {
+  return invoke<SyncConfig | null>("get_sync_config", { rootPath });
+}
+
+/** Save settings; the password is only updated when given. */
+export function setSyncConfig(rootPath: string, config: SyncConfig, password?: string): Promise<void> {
+  return invoke("set_sync_config", { rootPath, config, password: password ?? null });
+}
+
+export function removeSyncConfig(rootPath: string): Promise<void> {
+  return invoke("remove_sync_config", { rootPath });
+}
+
+/** Set up sync into an empty folder and download the remote contents. */
+export function syncClone(rootPath: string, config: SyncConfig, password: string): Promise<SyncSummary> {
+  return invoke<SyncSummary>("sync_clone", { rootPath, config, password });
+}
+
+export function syncNow(rootPath: string): Promise<SyncSummary> {
+  return invoke<SyncSummary>("sync_now", { rootPath });
+}
+
+/** Returns false when the workspace has no sync interval configured. */
+export function startScheduledSync(rootPath: string): Promise<boolean> {
+  return invoke<boolean>("start_scheduled_sync", { rootPath });
+}
+
+export function stopScheduledSync(rootPath: string): Promise<void> {
+  return invoke("stop_scheduled_sync", { rootPath });
+}
+
+/** One-line description of a finished sync, or null when nothing happened. */
+export function describeSyncSummary(summary: SyncSummary): string | null {
+  const parts: string[] = [];
+  if (summary.uploaded > 0) parts.push(`${summary.uploaded} uploaded`);
+  if (summary.downloaded > 0) parts.push(`${summary.downloaded} downloaded`);
+  const deleted = summary.deletedLocal + summary.deletedRemote;
+  if (deleted > 0) parts.push(`${deleted} deleted`);
+  if (summary.conflicts.length > 0) {
+    parts.push(`${summary.conflicts.length} conflict${summary.conflicts.length === 1 ? "" : "s"}`);
+  }
+  return parts.length > 0 ? parts.join(", ") : null;
+}
@@ -0,0 +1,22 @@
+/**
+ * Window Pinning
+ *
+ * Purpose: Keep a window above other apps and fade it, so a small note can
+ * sit over a browser while researching. The Window menu's "Keep on Top"
+ * and "Opacity" items call the same backend functions.
+ *
+ * Opacity is currently applied on macOS only; elsewhere the call rejects.
+ *
+ * @module utils/windowPin
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+
+export function setWindowAlwaysOnTop(label: string, onTop: boolean): Promise<void> {
+  return invoke("set_window_always_on_top", { label, onTop });
+}
+
+/** Resolves to the opacity actually applied (clamped to 0.2–1.0). */
+export function setWindowOpacity(label: string, opacity: number): Promise<number> {
+  return invoke<number>("set_window_opacity", { label, opacity });
+}
@@ -0,0 +1,66 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@/utils/workspaceStorage", () => ({
+  windowScopedStorage: {
+    getItem: vi.fn(() => null),
+    setItem: vi.fn(),
+    removeItem: vi.fn(),
+  },
+}));
+
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import {
+  relativeAssetsDir,
+  resolveAssetsDir,
+  resolveExportTheme,
+  resolveLineEndingPreference,
+  getSpellcheckLanguage,
+} from "./workspaceEditorSettings";
+
+describe("workspaceEditorSettings", () => {
+  beforeEach(() => {
+    useWorkspaceStore.getState().openWorkspace("/work");
+    useWorkspaceStore.getState().updateConfig({
+      editor: {
+        lineEnding: "crlf",
+        assetsFolder: "media",
+        exportTheme: "sepia",
+        spellcheckLanguage: "de-DE",
+      },
+    });
+  });
+
+  it("overrides global settings inside the workspace", () => {
+    expect(resolveLineEndingPreference("/work/notes/a.md", "preserve")).toBe("crlf");
+    expect(resolveAssetsDir("/work/notes/a.md")).toBe("../media");
+    expect(resolveExportTheme("white")).toBe("sepia");
+    expect(getSpellcheckLanguage()).toBe("de-DE");
+  });
+
+  it("leaves files outside the workspace alone", () => {
+    expect(resolveLineEndingPreference("/elsewhere/a.md", "lf")).toBe("lf");
+    expect(resolveAssetsDir("/elsewhere/a.md")).toBe("assets/images");
+  });
+
+  it("falls back when unset or invalid", () => {
+    useWorkspaceStore.getState().updateConfig({ editor: { exportTheme: "neon" } });
+    expect(resolveLineEndingPreference("/work/a.md", "lf")).toBe("lf");
+    expect(resolveExportTheme("paper")).toBe("paper");
+    expect(getSpellcheckLanguage()).toBeUndefined();
+  });
+
+  it("finds the assets folder from the document's folder", () => {
+    expect(relativeAssetsDir("/work/a.md", "/work", "media/img")).toBe("media/img");
+    expect(relativeAssetsDir("/work/media/a.md", "/work", "media/img")).toBe("img");
+    expect(relativeAssetsDir("/work/media/img/a.md", "/work", "media/img")).toBe(".");
+    expect(relativeAssetsDir("/work/a/b/c.md", "/work", "media")).toBe("../../media");
+    expect(relativeAssetsDir("C:\\work\\a\\b.md", "C:\\work", "media")).toBe("../media");
+  });
+
+  it("rejects assets folders outside the workspace", () => {
+    expect(relativeAssetsDir("/work/a.md", "/work", "../media")).toBeNull();
+    expect(relativeAssetsDir("/work/a.md", "/work", "/media")).toBeNull();
+    expect(relativeAssetsDir("/work/a.md", "/work", "C:\\media")).toBeNull();
+    expect(relativeAssetsDir("/work/a.md", "/work", undefined)).toBeNull();
+  });
+});
@@ -0,0 +1,98 @@
+/**
+ * Workspace Editor Settings
+ *
+ * Purpose: Apply the `editor` block of the open workspace's config on top of
+ * the user's global settings. Unset fields fall back to the global value;
+ * line endings, the assets folder and the encryption policy only apply to
+ * files inside the workspace.
+ *
+ * @module utils/workspaceEditorSettings
+ */
+
+import {
+  useWorkspaceStore,
+  type EncryptionPolicy,
+  type WorkspaceEditorSettings,
+} from "@/stores/workspaceStore";
+import type { ThemeId } from "@/stores/settingsStore";
+import type { LineEndingOnSave } from "@/utils/linebreakDetection";
+import { getParentDir, isWithinRoot, normalizePath, pathSegments } from "@/utils/paths";
+import { ASSETS_FOLDER } from "@/utils/imageUtils";
+
+const THEME_IDS: readonly ThemeId[] = ["white", "paper", "mint", "sepia", "night"];
+
+/** The workspace's editor settings, if a workspace is open. */
+export function getWorkspaceEditorSettings(): WorkspaceEditorSettings {
+  return useWorkspaceStore.getState().config?.editor ?? {};
+}
+
+/** Editor settings for a file: empty when it lies outside the workspace. */
+function settingsForFile(filePath: string): WorkspaceEditorSettings {
+  const rootPath = useWorkspaceStore.getState().rootPath;
+  if (!rootPath || !isWithinRoot(rootPath, filePath)) return {};
+  return getWorkspaceEditorSettings();
+}
+
+/** Line ending preference for saving `filePath`. */
+export function resolveLineEndingPreference(
+  filePath: string,
+  globalPref: LineEndingOnSave
+): LineEndingOnSave {
+  return settingsForFile(filePath).lineEnding ?? globalPref;
+}
+
+/** Encryption policy for a new document saved at `filePath`. */
+export function getEncryptionPolicy(filePath: string): EncryptionPolicy {
+  const policy = settingsForFile(filePath).encryption;
+  return policy === "ask" || policy === "always" ? policy : "off";
+}
+
+/** Spellcheck language for the editor, if the workspace sets one. */
+export function getSpellcheckLanguage(): string | undefined {
+  return getWorkspaceEditorSettings().spellcheckLanguage?.trim() || undefined;
+}
+
+/** Theme for exports; unknown workspace values are ignored. */
+export function resolveExportTheme(globalTheme: ThemeId): ThemeId {
+  const theme = getWorkspaceEditorSettings().exportTheme as ThemeId | undefined;
+  return theme && THEME_IDS.includes(theme) ? theme : globalTheme;
+}
+
+/**
+ * Path from a document's folder to a workspace assets folder, using `/`.
+ * Returns null if the folder is unset, escapes the workspace, or the
+ * document is outside it.
+ */
+export function relativeAssetsDir(
+  documentPath: string,
+  rootPath: string,
+  assetsFolder: string | undefined
+): string | null {
+  if (!assetsFolder || !isWithinRoot(rootPath, documentPath)) return null;
+  const folder = pathSegments(assetsFolder);
+  const absolute = /^([a-zA-Z]:|[\\/])/.test(assetsFolder);
+  if (absolute || !folder.length || folder.some((s) => s === ".." || s === ".")) return null;
+
+  const root = normalizePath(rootPath);
+  const docDir = pathSegments(getParentDir(documentPath).slice(root.length));
+  // Climb to the root, then descend, skipping the part both paths share
+  let shared = 0;
+  while (shared < docDir.length && shared < folder.length && docDir[shared] === folder[shared]) {
+    shared++;
+  }
+  const up = docDir.slice(shared).map(() => "..");
+  const relative = [...up, ...folder.slice(shared)];
+  return relative.length ? relative.join("/") : ".";
+}
+
+/** The shared workspace assets folder for a document, relative to its folder. */
+export function workspaceAssetsDir(documentPath: string): string | null {
+  const rootPath = useWorkspaceStore.getState().rootPath;
+  if (!rootPath) return null;
+  return relativeAssetsDir(documentPath, rootPath, getWorkspaceEditorSettings().assetsFolder);
+}
+
+/** Where a document's images go, relative to its folder. */
+export function resolveAssetsDir(documentPath: string): string {
+  return workspaceAssetsDir(documentPath) ?? ASSETS_FOLDER;
+}
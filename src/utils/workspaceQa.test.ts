@@ -0,0 +1,69 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@tauri-apps/api/event", () => ({ listen: vi.fn() }));
+vi.mock("@/stores/aiProviderStore", () => ({
+  useAiProviderStore: {
+    getState: () => ({
+      activeProvider: "anthropic",
+      restProviders: [
+        { type: "anthropic", model: "claude-x", apiKey: "sk-test", endpoint: "https://api.anthropic.com" },
+      ],
+      cliProviders: [],
+    }),
+  },
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { askWorkspace, citedSources, onCitations } from "./workspaceQa";
+
+const source = (index: number) => ({
+  index,
+  path: `n${index}.md`,
+  title: `N${index}`,
+  heading: null,
+  line: 1,
+  score: 1,
+});
+
+describe("workspaceQa", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(invoke).mockResolvedValue(null);
+  });
+
+  it("sends the active provider and embedding settings", async () => {
+    await askWorkspace("s1", "r1", "What did I plant?", "/ws", { budgetTokens: 800 });
+    expect(invoke).toHaveBeenCalledWith("ask_workspace", {
+      request: {
+        sessionId: "s1",
+        requestId: "r1",
+        question: "What did I plant?",
+        workspaceRoot: "/ws",
+        budgetTokens: 800,
+        embeddingModel: null,
+        provider: "anthropic",
+        model: "claude-x",
+        apiKey: "sk-test",
+        endpoint: "https://api.anthropic.com",
+        cliPath: null,
+      },
+    });
+  });
+
+  it("filters citation events by request", async () => {
+    const handler = vi.fn();
+    await onCitations("r1", handler);
+    const callback = vi.mocked(listen).mock.calls[0][1] as (e: { payload: unknown }) => void;
+    callback({ payload: { requestId: "r2", sources: [], cited: [], done: true } });
+    callback({ payload: { requestId: "r1", sources: [], cited: [], done: true } });
+    expect(handler).toHaveBeenCalledTimes(1);
+  });
+
+  it("lists cited sources, or all when none are cited", () => {
+    const sources = [source(1), source(2), source(3)];
+    expect(citedSources({ sources, cited: [3, 1] }).map((s) => s.index)).toEqual([1, 3]);
+    expect(citedSources({ sources, cited: [] })).toHaveLength(3);
+  });
+});
@@ -0,0 +1,95 @@
+/**
+ * Workspace Q&A
+ *
+ * Purpose: Ask questions about the notes in the open workspace. The backend
+ * picks relevant note excerpts, sends them with the question and the
+ * conversation so far to the active AI provider, and streams the answer as
+ * `ai:response` events. The excerpts used arrive on `ai:citations` — once
+ * before the answer starts, and again with the excerpts the answer cites —
+ * so the UI can link back to the source notes. Conversations live in the
+ * backend per session id and are not saved.
+ *
+ * @module utils/workspaceQa
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { activeProviderConfig } from "@/utils/activeProvider";
+import type { ContextSource } from "@/utils/notesContext";
+import { embeddingArgs, type EmbeddingOptions } from "@/utils/semanticSearch";
+
+export interface WorkspaceTurn {
+  question: string;
+  answer: string;
+  sources: ContextSource[];
+  /** Numbers of the sources the answer cites ([1] → 1) */
+  cited: number[];
+}
+
+export interface WorkspaceCitations {
+  requestId: string;
+  sessionId: string;
+  sources: ContextSource[];
+  cited: number[];
+  /** False while the answer is still streaming */
+  done: boolean;
+}
+
+export interface AskWorkspaceOptions extends EmbeddingOptions {
+  /** Size of the notes excerpts in tokens (backend default 2000) */
+  budgetTokens?: number;
+}
+
+/**
+ * Ask `question` in the conversation `sessionId`. The answer streams on
+ * `ai:response` for `requestId`; resolves to the finished turn, or `null`
+ * when the provider failed (the error is on the stream).
+ */
+export function askWorkspace(
+  sessionId: string,
+  requestId: string,
+  question: string,
+  workspaceRoot: string,
+  options: AskWorkspaceOptions = {}
+): Promise<WorkspaceTurn | null> {
+  return invoke<WorkspaceTurn | null>("ask_workspace", {
+    request: {
+      sessionId,
+      requestId,
+      question,
+      workspaceRoot,
+      budgetTokens: options.budgetTokens ?? null,
+      embeddingModel: embeddingArgs(options).model,
+      provider: "",
+      model: null,
+      apiKey: null,
+      endpoint: null,
+      cliPath: null,
+      ...activeProviderConfig(),
+    },
+  });
+}
+
+export function getWorkspaceSession(sessionId: string): Promise<WorkspaceTurn[]> {
+  return invoke<WorkspaceTurn[]>("get_workspace_session", { sessionId });
+}
+
+export function clearWorkspaceSession(sessionId: string): Promise<void> {
+  return invoke("clear_workspace_session", { sessionId });
+}
+
+/** Listen for the sources of answers to `requestId`. */
+export function onCitations(
+  requestId: string,
+  handler: (citations: WorkspaceCitations) => void
+): Promise<UnlistenFn> {
+  return listen<WorkspaceCitations>("ai:citations", (event) => {
+    if (event.payload.requestId === requestId) handler(event.payload);
+  });
+}
+
+/** The sources an answer cites, falling back to all of them if it cites none. */
+export function citedSources(turn: Pick<WorkspaceTurn, "sources" | "cited">): ContextSource[] {
+  if (turn.cited.length === 0) return turn.sources;
+  return turn.sources.filter((s) => turn.cited.includes(s.index));
+}
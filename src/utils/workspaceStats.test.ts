@@ -0,0 +1,12 @@
+import { describe, it, expect } from "vitest";
+import { linkHealthPercent } from "./workspaceStats";
+
+describe("linkHealthPercent", () => {
+  it("is 100 with no note links", () => {
+    expect(linkHealthPercent({ internalLinks: 0, brokenLinks: 0 })).toBe(100);
+  });
+
+  it("rounds the resolved share", () => {
+    expect(linkHealthPercent({ internalLinks: 2, brokenLinks: 1 })).toBe(67);
+  });
+});
@@ -0,0 +1,29 @@
+/**
+ * Workspace statistics for the Workspace Insights dialog, computed by the backend.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface WorkspaceStats {
+  totalNotes: number;
+  totalWords: number;
+  totalBytes: number;
+  totalLinks: number;
+  internalLinks: number;
+  brokenLinks: number;
+  /** Notes no other note links to (relative paths) */
+  orphanedNotes: string[];
+  sizeDistribution: { label: string; count: number }[];
+  recentNotes: { path: string; modified: number }[];
+  /** Notes last modified per day, oldest first */
+  activity: { date: string; count: number }[];
+}
+
+export function getWorkspaceStats(rootPath: string, activityDays?: number): Promise<WorkspaceStats> {
+  return invoke<WorkspaceStats>("get_workspace_stats", { rootPath, activityDays: activityDays ?? null });
+}
+
+/** Share of note links that resolve, as a whole percentage (100 when there are none). */
+export function linkHealthPercent(stats: Pick<WorkspaceStats, "internalLinks" | "brokenLinks">): number {
+  const total = stats.internalLinks + stats.brokenLinks;
+  return total === 0 ? 100 : Math.round((stats.internalLinks / total) * 100);
+}
@@ -0,0 +1,57 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("@tauri-apps/plugin-dialog", () => ({ ask: vi.fn() }));
+vi.mock("@/utils/workspaceStorage", () => ({
+  windowScopedStorage: {
+    getItem: vi.fn(() => null),
+    setItem: vi.fn(),
+    removeItem: vi.fn(),
+  },
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { promptWorkspaceTrust, setWorkspaceTrust, syncWorkspaceTrust } from "./workspaceTrust";
+
+describe("workspaceTrust", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(ask).mockReset();
+    useWorkspaceStore.getState().openWorkspace("/work");
+  });
+
+  it("takes trust from the backend, not the config file", async () => {
+    useWorkspaceStore.getState().trustWorkspace();
+    vi.mocked(invoke).mockResolvedValue(false);
+
+    await expect(syncWorkspaceTrust("/work")).resolves.toBe(false);
+    expect(invoke).toHaveBeenCalledWith("workspace_trust_check", { path: "/work" });
+    expect(useWorkspaceStore.getState().isWorkspaceTrusted()).toBe(false);
+  });
+
+  it("grants and revokes trust", async () => {
+    vi.mocked(invoke).mockResolvedValue([]);
+
+    await setWorkspaceTrust("/work", true);
+    expect(invoke).toHaveBeenCalledWith("workspace_trust_grant", { rootPath: "/work" });
+    expect(useWorkspaceStore.getState().isWorkspaceTrusted()).toBe(true);
+
+    await setWorkspaceTrust("/work", false);
+    expect(invoke).toHaveBeenCalledWith("workspace_trust_revoke", { rootPath: "/work" });
+    expect(useWorkspaceStore.getState().isWorkspaceTrusted()).toBe(false);
+  });
+
+  it("asks once per workspace and grants on confirm", async () => {
+    vi.mocked(invoke).mockImplementation(async (cmd) => (cmd === "workspace_trust_check" ? false : []));
+    vi.mocked(ask).mockResolvedValue(true);
+
+    await promptWorkspaceTrust("/work");
+    await promptWorkspaceTrust("/work");
+
+    expect(ask).toHaveBeenCalledTimes(1);
+    expect(invoke).toHaveBeenCalledWith("workspace_trust_grant", { rootPath: "/work" });
+    expect(useWorkspaceStore.getState().isWorkspaceTrusted()).toBe(true);
+  });
+});
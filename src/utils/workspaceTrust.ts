@@ -0,0 +1,76 @@
+/**
+ * Workspace Trust
+ *
+ * Purpose: Keep the workspace identity's trustLevel in step with the
+ * backend trust store (app data, see workspace_trust.rs). The store is the
+ * source of truth, so a `.vmark` file can't mark its own folder trusted.
+ * Trust lets the terminal start in the workspace and enables workspace
+ * tasks and AI settings.
+ *
+ * @module utils/workspaceTrust
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import { ask } from "@tauri-apps/plugin-dialog";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { getFileName } from "@/utils/paths";
+
+/** Emitted by the backend after trust is granted or revoked */
+export const WORKSPACE_TRUST_CHANGED_EVENT = "workspace:trust-changed";
+
+/** Roots already asked about this session */
+const askedRoots = new Set<string>();
+
+/** Whether the backend trusts `rootPath`. */
+export function checkWorkspaceTrust(rootPath: string): Promise<boolean> {
+  return invoke<boolean>("workspace_trust_check", { path: rootPath });
+}
+
+/** Reflect a trust answer in the store, if `rootPath` is still the open workspace. */
+export function applyWorkspaceTrust(rootPath: string, trusted: boolean): void {
+  const store = useWorkspaceStore.getState();
+  if (store.rootPath !== rootPath || store.isWorkspaceTrusted() === trusted) return;
+  if (trusted) store.trustWorkspace();
+  else store.untrustWorkspace();
+}
+
+/** Re-read trust for `rootPath` from the backend. Returns the answer. */
+export async function syncWorkspaceTrust(rootPath: string): Promise<boolean> {
+  const trusted = await checkWorkspaceTrust(rootPath);
+  applyWorkspaceTrust(rootPath, trusted);
+  return trusted;
+}
+
+/** Grant or revoke trust for `rootPath`. */
+export async function setWorkspaceTrust(rootPath: string, trusted: boolean): Promise<void> {
+  await invoke(trusted ? "workspace_trust_grant" : "workspace_trust_revoke", { rootPath });
+  applyWorkspaceTrust(rootPath, trusted);
+}
+
+/**
+ * Ask whether to trust a workspace the user just opened, unless it's
+ * already trusted or was asked about earlier this session.
+ */
+export async function promptWorkspaceTrust(rootPath: string): Promise<void> {
+  if (askedRoots.has(rootPath)) return;
+  askedRoots.add(rootPath);
+
+  try {
+    if (await syncWorkspaceTrust(rootPath)) return;
+
+    const confirmed = await ask(
+      `Do you trust the files in "${getFileName(rootPath) || rootPath}"?\n\n` +
+        "Trusting this folder lets the terminal start in it and enables its " +
+        "workspace tasks and AI settings. Only trust folders from sources you know.",
+      {
+        title: "Trust Workspace",
+        kind: "warning",
+        okLabel: "Trust",
+        cancelLabel: "Not Now",
+      }
+    );
+    if (confirmed) await setWorkspaceTrust(rootPath, true);
+  } catch (error) {
+    console.error("[Workspace] Failed to update trust:", error);
+  }
+}
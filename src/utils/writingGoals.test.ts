@@ -0,0 +1,14 @@
+import { describe, it, expect } from "vitest";
+import { goalPercent } from "./writingGoals";
+
+describe("goalPercent", () => {
+  it("caps at 100", () => {
+    expect(goalPercent(250, 500)).toBe(50);
+    expect(goalPercent(900, 500)).toBe(100);
+  });
+
+  it("is 0 without a goal", () => {
+    expect(goalPercent(120, null)).toBe(0);
+    expect(goalPercent(120, 0)).toBe(0);
+  });
+});
@@ -0,0 +1,50 @@
+/**
+ * Writing goals and streaks, tracked by the backend.
+ *
+ * Each save reports the document's content; growth in its word count is
+ * added to today's total. The backend emits `goals:met` (and shows a system
+ * notification) when a daily or project goal is reached.
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ProjectProgress {
+  /** Document or folder the goal covers */
+  path: string;
+  target: number;
+  current: number;
+  met: boolean;
+}
+
+export interface GoalProgress {
+  todayWords: number;
+  dailyGoal: number | null;
+  dailyMet: boolean;
+  streak: number;
+  projects: ProjectProgress[];
+  /** The last 7 days, oldest first */
+  recentDays: { date: string; words: number }[];
+}
+
+export type GoalMet =
+  | { kind: "daily"; target: number }
+  | { kind: "project"; path: string; target: number };
+
+/** `isNew`: the document was created this session, so its first save counts in full. */
+export function recordDocumentSave(path: string, content: string, isNew = false): Promise<GoalProgress> {
+  return invoke<GoalProgress>("record_document_save", { path, content, isNew });
+}
+
+/** Set a goal; a target of 0 clears it. Project goals need a document or folder path. */
+export function setGoal(kind: "daily" | "project", target: number, path?: string): Promise<GoalProgress> {
+  return invoke<GoalProgress>("set_goal", { kind, target, path });
+}
+
+export function getProgress(): Promise<GoalProgress> {
+  return invoke<GoalProgress>("get_progress");
+}
+
+/** Progress toward a target as a 0–100 percentage. */
+export function goalPercent(current: number, target: number | null): number {
+  if (!target) return 0;
+  return Math.min(100, Math.floor((current / target) * 100));
+}
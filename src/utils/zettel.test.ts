@@ -0,0 +1,79 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+vi.mock("sonner", () => ({ toast: { success: vi.fn(), error: vi.fn(), warning: vi.fn() } }));
+vi.mock("@/stores/workspaceStore", () => ({
+  useWorkspaceStore: { getState: () => ({ rootPath: "/ws" }) },
+}));
+
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { describeLinkUpdates, reportLinkUpdates, updateWorkspaceLinks } from "./zettel";
+
+describe("describeLinkUpdates", () => {
+  it("returns null when nothing changed", () => {
+    expect(describeLinkUpdates([])).toBeNull();
+  });
+
+  it("sums replacements across files", () => {
+    expect(
+      describeLinkUpdates([
+        { path: "/a.md", replacements: 2 },
+        { path: "/b.md", replacements: 1 },
+      ])
+    ).toBe("Updated 3 links in 2 files");
+    expect(describeLinkUpdates([{ path: "/a.md", replacements: 1 }])).toBe("Updated 1 link in 1 file");
+  });
+});
+
+describe("updateWorkspaceLinks", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(toast.success).mockReset();
+  });
+
+  it("rewrites links for moves inside the workspace", async () => {
+    vi.mocked(invoke).mockResolvedValue([{ path: "/ws/b.md", replacements: 2 }]);
+    await updateWorkspaceLinks("/ws/a.md", "/ws/notes/a.md");
+    expect(invoke).toHaveBeenCalledWith("update_links_on_rename", {
+      rootPath: "/ws",
+      oldPath: "/ws/a.md",
+      newPath: "/ws/notes/a.md",
+    });
+    expect(toast.success).toHaveBeenCalledWith("Updated 2 links in 1 file");
+  });
+
+  it("leaves moves out of the workspace alone", async () => {
+    await updateWorkspaceLinks("/ws/a.md", "/elsewhere/a.md");
+    expect(invoke).not.toHaveBeenCalled();
+  });
+});
+
+describe("reportLinkUpdates", () => {
+  beforeEach(() => {
+    vi.mocked(invoke).mockReset();
+    vi.mocked(toast.success).mockReset();
+    vi.mocked(toast.warning).mockReset();
+  });
+
+  it("offers to restore the rewritten notes", () => {
+    const updatedLinks = [{ path: "/ws/b.md", replacements: 2, previousContent: "[a](a.md)" }];
+    vi.mocked(invoke).mockResolvedValue(undefined);
+
+    reportLinkUpdates({ path: "/ws/notes/a.md", updatedLinks });
+
+    const [message, options] = vi.mocked(toast.success).mock.calls[0];
+    expect(message).toBe("Updated 2 links in 1 file");
+    (options as { action: { onClick: () => void } }).action.onClick();
+    expect(invoke).toHaveBeenCalledWith("restore_link_updates", { updates: updatedLinks });
+  });
+
+  it("warns when the links couldn't be rewritten", () => {
+    reportLinkUpdates({ path: "/ws/notes/a.md", updatedLinks: [], linkWarning: "Failed to update links: denied" });
+
+    expect(toast.warning).toHaveBeenCalledWith("Couldn't update links to the moved note", {
+      description: "Failed to update links: denied",
+    });
+    expect(toast.success).not.toHaveBeenCalled();
+  });
+});
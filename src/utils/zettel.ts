@@ -0,0 +1,98 @@
+/**
+ * Zettelkasten helpers: new notes named by ID (File Explorer → New Zettel),
+ * and link updates after a rename or move so the knowledge graph stays
+ * connected.
+ */
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { useWorkspaceStore } from "@/stores/workspaceStore";
+import { isWithinRoot } from "@/utils/paths";
+
+export type NoteIdFormat = "timestamp" | "ulid";
+
+export interface CreatedZettel {
+  id: string;
+  path: string;
+}
+
+export interface UpdatedLinkFile {
+  path: string;
+  replacements: number;
+}
+
+/** A note rewritten by `rename_path` / `move_path`, with its content before */
+export interface LinkUpdate extends UpdatedLinkFile {
+  previousContent: string;
+}
+
+/** Result of the `rename_path` / `move_path` commands */
+export interface MoveOutcome {
+  /** Final path (the backend adds " (2)" on a name collision) */
+  path: string;
+  updatedLinks: LinkUpdate[];
+  /** Set when links couldn't be rewritten; the move itself happened */
+  linkWarning?: string;
+}
+
+/**
+ * Create a note in `folder`. `pattern` may use `{id}`, `{title}`, `{slug}`
+ * and `{date}`; it defaults to `{id} {title}`.
+ */
+export function createZettel(
+  folder: string,
+  title: string,
+  pattern?: string,
+  idFormat: NoteIdFormat = "timestamp"
+): Promise<CreatedZettel> {
+  return invoke<CreatedZettel>("create_zettel", { folder, title, pattern: pattern ?? null, idFormat });
+}
+
+/** Rewrite links across the workspace after `oldPath` was renamed or moved to `newPath`. */
+export function updateLinksOnRename(rootPath: string, oldPath: string, newPath: string): Promise<UpdatedLinkFile[]> {
+  return invoke<UpdatedLinkFile[]>("update_links_on_rename", { rootPath, oldPath, newPath });
+}
+
+/** "Updated 3 links in 2 files", or null when nothing changed. */
+export function describeLinkUpdates(updated: UpdatedLinkFile[]): string | null {
+  if (updated.length === 0) return null;
+  const links = updated.reduce((sum, f) => sum + f.replacements, 0);
+  const plural = (n: number, word: string) => `${n} ${word}${n === 1 ? "" : "s"}`;
+  return `Updated ${plural(links, "link")} in ${plural(updated.length, "file")}`;
+}
+
+/** After a rename or move inside the open workspace, point links at the new path. */
+export async function updateWorkspaceLinks(oldPath: string, newPath: string): Promise<void> {
+  const rootPath = useWorkspaceStore.getState().rootPath;
+  if (!rootPath || !isWithinRoot(rootPath, oldPath) || !isWithinRoot(rootPath, newPath)) return;
+  try {
+    const message = describeLinkUpdates(await updateLinksOnRename(rootPath, oldPath, newPath));
+    if (message) toast.success(message);
+  } catch (error) {
+    console.warn("[Zettel] Failed to update links:", error);
+    toast.error("Couldn't update links to the moved note");
+  }
+}
+
+/**
+ * Report the links a rename or move rewrote, with an Undo that puts the
+ * rewritten notes back as they were (the move itself stays).
+ */
+export function reportLinkUpdates(outcome: MoveOutcome): void {
+  if (outcome.linkWarning) {
+    toast.warning("Couldn't update links to the moved note", { description: outcome.linkWarning });
+    return;
+  }
+  const message = describeLinkUpdates(outcome.updatedLinks);
+  if (!message) return;
+  toast.success(message, {
+    action: {
+      label: "Undo",
+      onClick: () => {
+        invoke("restore_link_updates", { updates: outcome.updatedLinks }).catch((error) => {
+          console.warn("[Zettel] Failed to restore links:", error);
+          toast.error("Couldn't restore the previous links");
+        });
+      },
+    },
+  });
+}